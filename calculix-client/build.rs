@@ -0,0 +1,149 @@
+//! Generate the client from the service's OpenAPI spec
+//!
+//! The component schemas become `types.rs` through typify, and each operation
+//! becomes a method of `Client` in `client.rs`. Operations that stream
+//! server-sent events are left out, since there is no typed body to return.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use heck::ToUpperCamelCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use schemars::schema::Schema;
+use serde_json::Value;
+use typify::{TypeSpace, TypeSpaceSettings};
+
+const SPEC: &str = "openapi.json";
+const METHODS: [&str; 4] = ["get", "post", "put", "delete"];
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SPEC);
+    let spec: Value = serde_json::from_str(&std::fs::read_to_string(SPEC).expect("Failed to read the spec"))
+        .expect("Invalid spec");
+
+    let mut type_space = TypeSpace::new(TypeSpaceSettings::default().with_type_mod("types").with_struct_builder(false));
+    let schemas: BTreeMap<String, Schema> = serde_json::from_value(spec["components"]["schemas"].clone())
+        .expect("Invalid component schemas");
+    type_space.add_ref_types(schemas).expect("Failed to generate types");
+
+    let mut methods = Vec::new();
+    let mut queries = Vec::new();
+    for (path, item) in spec["paths"].as_object().expect("Spec has no paths") {
+        for method in METHODS {
+            if let Some(operation) = item.get(method) {
+                if let Some((tokens, query)) = operation_method(&mut type_space, path, method, operation) {
+                    methods.push(tokens);
+                    queries.extend(query);
+                }
+            }
+        }
+    }
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let client = quote! {
+        #(#queries)*
+
+        impl Client {
+            #(#methods)*
+        }
+    };
+    write(Path::new(&out_dir).join("types.rs"), type_space.to_stream());
+    write(Path::new(&out_dir).join("client.rs"), client);
+}
+
+fn write(path: impl AsRef<Path>, tokens: TokenStream) {
+    std::fs::write(path, tokens.to_string()).expect("Failed to write generated code");
+}
+
+/// Method of `Client` calling one operation, and the struct of its query
+/// parameters if it has any
+fn operation_method(
+    type_space: &mut TypeSpace,
+    path: &str,
+    method: &str,
+    operation: &Value,
+) -> Option<(TokenStream, Option<TokenStream>)> {
+    let operation_id = operation["operationId"].as_str().expect("Operation has no operationId");
+    let name = format_ident!("{}", operation_id);
+    let doc = operation["summary"].as_str().unwrap_or(operation_id);
+
+    let (_, response) = operation["responses"].as_object()?
+        .iter()
+        .find(|(status, _)| status.starts_with('2'))?;
+    let content = response["content"].as_object()?;
+    let (output, read) = if let Some(json) = content.get("application/json") {
+        (schema_type(type_space, &json["schema"]), quote! { json })
+    } else if content.contains_key("text/event-stream") {
+        return None;
+    } else {
+        // Files and metrics are returned as they are
+        (quote! { String }, quote! { text })
+    };
+
+    let parameters = operation["parameters"].as_array().cloned().unwrap_or_default();
+    let path_params: Vec<_> = parameters.iter()
+        .filter(|p| p["in"] == "path")
+        .map(|p| format_ident!("{}", p["name"].as_str().unwrap()))
+        .collect();
+    let url = path.split('/')
+        .map(|segment| if segment.starts_with('{') { "{}" } else { segment })
+        .fold("{}".to_string(), |url, segment| if segment.is_empty() { url } else { url + "/" + segment });
+    let verb = format_ident!("{}", method.to_uppercase());
+
+    let mut args: Vec<TokenStream> = path_params.iter().map(|p| quote! { #p: &str }).collect();
+    let mut send = quote! { self.request(reqwest::Method::#verb, url) };
+    let mut query_struct = None;
+
+    let query_params: Vec<_> = parameters.iter().filter(|p| p["in"] == "query").collect();
+    if !query_params.is_empty() {
+        let query_name = format_ident!("{}Query", operation_id.to_upper_camel_case());
+        let fields = query_params.iter().map(|p| {
+            let field = format_ident!("{}", p["name"].as_str().unwrap());
+            let ty = schema_type(type_space, &p["schema"]);
+            let doc = p["description"].as_str().map(|d| quote! { #[doc = #d] });
+            if p["required"] == true {
+                quote! { #doc pub #field: #ty }
+            } else {
+                quote! {
+                    #doc
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    pub #field: Option<#ty>
+                }
+            }
+        });
+        let query_doc = format!("Query parameters of [`Client::{}`]", operation_id);
+        query_struct = Some(quote! {
+            #[doc = #query_doc]
+            #[derive(Debug, Clone, Default, serde::Serialize)]
+            pub struct #query_name {
+                #(#fields),*
+            }
+        });
+        args.push(quote! { query: &#query_name });
+        send = quote! { #send.query(query) };
+    }
+
+    if let Some(body) = operation["requestBody"]["content"].get("application/json") {
+        let ty = schema_type(type_space, &body["schema"]);
+        args.push(quote! { body: &#ty });
+        send = quote! { #send.json(body) };
+    }
+
+    let tokens = quote! {
+        #[doc = #doc]
+        pub async fn #name(&self, #(#args),*) -> Result<#output, Error> {
+            let url = format!(#url, self.base_url #(, #path_params)*);
+            let response = #send.send().await?;
+            Self::#read(response).await
+        }
+    };
+    Some((tokens, query_struct))
+}
+
+/// Rust type of a response, body or parameter schema
+fn schema_type(type_space: &mut TypeSpace, schema: &Value) -> TokenStream {
+    let schema: Schema = serde_json::from_value(schema.clone()).expect("Invalid schema");
+    let id = type_space.add_type(&schema).expect("Unsupported schema");
+    type_space.get_type(&id).unwrap().ident()
+}
@@ -0,0 +1,98 @@
+//! Typed client of the calculix-service HTTP API
+//!
+//! Types and methods are generated at build time from `openapi.json`, the
+//! spec served by the service at `/api/v1/openapi.json`. The service's tests
+//! check that the copy here is current.
+
+/// Request and response types of the API
+#[allow(clippy::all)]
+pub mod types {
+    include!(concat!(env!("OUT_DIR"), "/types.rs"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/client.rs"));
+
+/// Client of a CalculiX service
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    client: reqwest::Client,
+    api_key: Option<String>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_client(base_url, reqwest::Client::new())
+    }
+
+    /// A client sending requests through an existing `reqwest` client
+    pub fn with_client(base_url: impl Into<String>, client: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+            api_key: None,
+        }
+    }
+
+    /// Send every request with an API key, for services that require one
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.api_key {
+            Some(key) => request.header("x-api-key", key),
+            None => request,
+        }
+    }
+
+    async fn json<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+        Ok(Self::check(response).await?.json().await?)
+    }
+
+    async fn text(response: reqwest::Response) -> Result<String, Error> {
+        Ok(Self::check(response).await?.text().await?)
+    }
+
+    /// Turn an error status into an [`Error::Api`] with the service's message
+    /// and, for rejected models, the issues it found
+    async fn check(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await?;
+        let (message, issues) = match serde_json::from_str::<types::ErrorBody>(&body) {
+            Ok(error) => (error.error, error.issues),
+            Err(_) => (body, Vec::new()),
+        };
+        Err(Error::Api { status: status.as_u16(), message, issues })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("HTTP request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Service returned {status}: {message}")]
+    Api { status: u16, message: String, issues: Vec<types::ModelIssue> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_types_read_service_requests() {
+        let request: types::AnalysisRequest =
+            serde_json::from_str(include_str!("../../calculix-service/portal_frame_request.json")).unwrap();
+        assert!(!request.model.beams.is_empty());
+        assert_eq!(Client::new("http://localhost:8084/").base_url(), "http://localhost:8084");
+    }
+}
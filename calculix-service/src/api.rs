@@ -1,54 +1,211 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    extract::{rejection::JsonRejection, DefaultBodyLimit, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
+use futures_util::{stream, Stream};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 use tower_http::cors::{CorsLayer, Any};
-use uuid::Uuid;
+use utoipa::openapi::security::{ApiKey as ApiKeyScheme, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
 
-use crate::executor::{CalculiXExecutor, ExecutorError};
-use crate::generator::CalculiXGenerator;
-use crate::models::{AnalysisRequest, AnalysisResponse, AnalysisStatus, StructuralModel};
-
-pub type SharedExecutor = Arc<Mutex<CalculiXExecutor>>;
+use crate::auth::{ApiKey, ApiKeys, AuthError, API_KEY_HEADER};
+use crate::engine::{EngineError, Engines};
+use crate::jobs::{Artifact, JobError, JobQueue};
+use crate::metrics::Metrics;
+use crate::models::{AnalysisRequest, AnalysisResponse, BatchRequest, BatchResponse, Engine};
+use crate::progress::ProgressEvent;
+use crate::store::{JobQuery, JobStore, JobSummary, StoreError};
+use crate::validation::{ModelIssue, ModelLimits};
 
 /// Application state
 pub struct AppState {
-    executor: SharedExecutor,
-    generator: CalculiXGenerator,
+    engines: Arc<Engines>,
+    jobs: JobQueue,
+    keys: ApiKeys,
+    limits: ModelLimits,
+    /// Largest request body accepted, in bytes
+    max_request_bytes: usize,
+    /// Most analyses a batch may queue
+    max_batch: usize,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        Self {
-            executor: Arc::new(Mutex::new(CalculiXExecutor::new())),
-            generator: CalculiXGenerator::new(),
+        // Worker count and queue length can be tuned per deployment
+        let env = |name: &str, default: usize| {
+            std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+        };
+        let workers = env("ANALYSIS_WORKERS", std::thread::available_parallelism().map_or(2, |n| n.get()));
+        let capacity = env("ANALYSIS_QUEUE_CAPACITY", 64);
+        let retention = env("ANALYSIS_RETENTION_SECS", 60 * 60);
+        // 0 lifts the time limit
+        let timeout = env("ANALYSIS_TIMEOUT_SECS", 30 * 60);
+        let processes = env("CALCULIX_MAX_PROCESSES", 1);
+        let defaults = ModelLimits::default();
+        let limits = ModelLimits {
+            max_nodes: env("ANALYSIS_MAX_NODES", defaults.max_nodes),
+            max_elements: env("ANALYSIS_MAX_ELEMENTS", defaults.max_elements),
+        };
+        let max_request_bytes = env("ANALYSIS_MAX_REQUEST_BYTES", 32 * 1024 * 1024);
+        let max_batch = env("ANALYSIS_MAX_BATCH", 32);
+        tracing::info!("Starting {} analysis workers with a queue of {}", workers, capacity);
+        tracing::info!("Running up to {} ccx processes, with a time limit of {} s per job", processes, timeout);
+        tracing::info!("Accepting models of up to {} nodes and {} elements in requests of up to {} bytes, and batches of up to {} analyses",
+            limits.max_nodes, limits.max_elements, max_request_bytes, max_batch);
+
+        let metrics = Arc::new(Metrics::new());
+        let engines = Arc::new(Engines::new().with_max_processes(processes).with_metrics(Arc::clone(&metrics)));
+        let mut jobs = JobQueue::new(Arc::clone(&engines), workers, capacity)
+            .with_retention(Duration::from_secs(retention as u64))
+            .with_metrics(metrics);
+        if timeout > 0 {
+            jobs = jobs.with_timeout(Duration::from_secs(timeout as u64));
+        }
+        if let Ok(dir) = std::env::var("ANALYSIS_ARTIFACT_DIR") {
+            jobs = jobs.with_artifact_dir(dir);
+        }
+        let db_path = std::env::var("ANALYSIS_DB_PATH").unwrap_or_else(|_| "calculix-jobs.sqlite".to_string());
+        match JobStore::open(&db_path) {
+            Ok(store) => {
+                tracing::info!("Keeping job history in {}", db_path);
+                jobs = jobs.with_store(store);
+            }
+            Err(e) => tracing::warn!("Keeping job history in memory only, as {} cannot be opened: {}", db_path, e),
+        }
+        jobs.clear_artifacts();
+
+        // A key file that is set but unusable must not leave the API open
+        let keys = match std::env::var("CALCULIX_API_KEYS_FILE") {
+            Ok(path) => ApiKeys::load(&path).unwrap_or_else(|e| {
+                tracing::error!("{}", e);
+                std::process::exit(1);
+            }),
+            Err(_) => ApiKeys::default(),
+        };
+        if keys.is_empty() {
+            tracing::warn!("No API keys configured (CALCULIX_API_KEYS_FILE), so the API is open to every client");
+        } else {
+            tracing::info!("Requiring one of {} API keys", keys.len());
         }
+        Self { jobs, engines, keys, limits, max_request_bytes, max_batch }
+    }
+}
+
+/// OpenAPI description of the API, served at `/api/v1/openapi.json`
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "CalculiX FEA Service",
+        description = "Structural analysis of beam and shell models with CalculiX (ccx) or the native fea-solver engine. \
+            Analyses run as jobs: submit a request, then poll the job or follow its progress events. \
+            When the service has API keys, `/api/v1` requests need one in an `X-API-Key` header or as a bearer token; \
+            they answer 401 without a valid key, 429 over the key's rate limit and 413 for models over its size quota. \
+            Malformed models are rejected with 422 and an issue per entity at fault. \
+            A batch queues several models, or variations of one, as a job each, and counts as a request per job \
+            against the key's rate limit. \
+            Jobs belong to the key that submitted them; other keys get 404 for them and do not see them listed.",
+    ),
+    modifiers(&SecurityAddon),
+    paths(
+        health_check,
+        metrics_handler,
+        version_handler,
+        validate_handler,
+        analyze_handler,
+        batch_handler,
+        get_batch_handler,
+        list_jobs_handler,
+        job_handler,
+        cancel_job_handler,
+        job_events_handler,
+        job_request_handler,
+        job_file_handler,
+    ),
+    tags(
+        (name = "service", description = "Service status"),
+        (name = "jobs", description = "Analysis jobs"),
+    ),
+)]
+pub struct ApiDoc;
+
+/// Declares the API key scheme the `/api/v1` paths refer to
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKeyScheme::Header(ApiKeyValue::new(API_KEY_HEADER))),
+        );
     }
 }
 
 /// Build the API router
 pub fn create_router() -> Router {
-    let state = AppState::new();
+    let state = Arc::new(AppState::new());
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    Router::new()
-        .route("/", get(root_handler))
-        .route("/health", get(health_check))
+    let api = Router::new()
         .route("/api/v1/analyze", post(analyze_handler))
+        .route("/api/v1/analyze/batch", post(batch_handler))
+        .route("/api/v1/analyze/batch/:id", get(get_batch_handler))
+        .route("/api/v1/jobs", post(analyze_handler).get(list_jobs_handler))
+        .route("/api/v1/jobs/:id", get(job_handler).delete(cancel_job_handler))
+        .route("/api/v1/jobs/:id/events", get(job_events_handler))
+        .route("/api/v1/jobs/:id/request", get(job_request_handler))
+        .route("/api/v1/jobs/:id/files/:kind", get(job_file_handler))
         .route("/api/v1/version", get(version_handler))
         .route("/api/v1/validate", post(validate_handler))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), require_api_key))
+        .layer(DefaultBodyLimit::max(state.max_request_bytes));
+
+    Router::new()
+        .route("/", get(root_handler))
+        .route("/health", get(health_check))
+        .route("/metrics", get(metrics_handler))
+        .merge(api)
+        .merge(SwaggerUi::new("/swagger-ui").url("/api/v1/openapi.json", ApiDoc::openapi()))
         .layer(cors)
-        .with_state(Arc::new(state))
+        .with_state(state)
+}
+
+/// Reject `/api/v1` requests without a valid key, once keys are configured,
+/// and hand the key on to the handlers
+async fn require_api_key(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !state.keys.is_empty() {
+        let key = state.keys.authenticate(request.headers()).inspect_err(|e| {
+            tracing::warn!("Rejected {} {}: {}", request.method(), request.uri().path(), e);
+        })?;
+        request.extensions_mut().insert(key);
+    }
+    Ok(next.run(request).await)
+}
+
+/// Name of the key a request was made with, which owns the jobs it
+/// submits; `None` when the service has no keys
+fn owner(key: &Option<Extension<Arc<ApiKey>>>) -> Option<&str> {
+    key.as_ref().map(|Extension(key)| key.name.as_str())
 }
 
 /// Root endpoint
@@ -57,14 +214,54 @@ async fn root_handler() -> Json<serde_json::Value> {
         "service": "CalculiX FEA Service",
         "version": "0.1.0",
         "status": "running",
-        "description": "Structural analysis for beams and shells using CalculiX (ccx) solver",
+        "description": "Structural analysis for beams and shells using CalculiX (ccx) or the native fea-solver engine",
         "supported_elements": ["beams", "shells"],
+        "engines": ["CalculiX", "Native"],
         "supported_materials": ["steel", "custom"]
     }))
 }
 
+/// Service health and whether the engines are available
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthStatus {
+    pub status: String,
+    pub timestamp: String,
+    pub calculix_available: bool,
+    /// Command the service runs ccx with
+    pub calculix_command: String,
+    pub native_available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VersionInfo {
+    pub service: String,
+    pub version: String,
+    pub api_version: String,
+    pub solver: String,
+    pub engines: Vec<Engine>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidationResult {
+    pub valid: bool,
+    pub message: String,
+}
+
+/// Body of every error response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+    /// Problems with a rejected model, one per entity at fault
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<ModelIssue>,
+}
+
 /// Health check endpoint
-async fn health_check() -> Json<serde_json::Value> {
+#[utoipa::path(
+    get, path = "/health", operation_id = "health", tag = "service",
+    responses((status = 200, description = "The service is up", body = HealthStatus)),
+)]
+async fn health_check() -> Json<HealthStatus> {
     // Check if CalculiX is available
     let ccx_path = std::env::var("CALCULIX_PATH")
         .unwrap_or_else(|_| "ccx".to_string());
@@ -74,46 +271,95 @@ async fn health_check() -> Json<serde_json::Value> {
         .output()
         .is_ok();
 
-    Json(json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "calculix_available": ccx_available,
-        "calculix_command": ccx_path
-    }))
+    Json(HealthStatus {
+        status: "healthy".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        calculix_available: ccx_available,
+        calculix_command: ccx_path,
+        native_available: true,
+    })
+}
+
+/// Prometheus metrics: job counts, queue depth, analysis times, result
+/// parse failures and ccx exit codes
+#[utoipa::path(
+    get, path = "/metrics", operation_id = "metrics", tag = "service",
+    responses((status = 200, description = "Metrics in the OpenMetrics text format", body = String, content_type = "application/openmetrics-text")),
+)]
+async fn metrics_handler(State(state): State<Arc<AppState>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/openmetrics-text; version=1.0.0; charset=utf-8")],
+        state.jobs.metrics().await,
+    ).into_response()
 }
 
 /// Version endpoint
-async fn version_handler() -> Json<serde_json::Value> {
-    Json(json!({
-        "service": "CalculiX FEA Service",
-        "version": "0.1.0",
-        "api_version": "v1",
-        "solver": "CalculiX (ccx)"
-    }))
+#[utoipa::path(
+    get, path = "/api/v1/version", operation_id = "version", tag = "service", security(("api_key" = [])),
+    responses((status = 200, description = "Service and API version", body = VersionInfo)),
+)]
+async fn version_handler() -> Json<VersionInfo> {
+    Json(VersionInfo {
+        service: "CalculiX FEA Service".to_string(),
+        version: "0.1.0".to_string(),
+        api_version: "v1".to_string(),
+        solver: "CalculiX (ccx)".to_string(),
+        engines: vec![Engine::CalculiX, Engine::Native],
+    })
 }
 
 /// Validate model without running analysis
+#[utoipa::path(
+    post, path = "/api/v1/validate", operation_id = "validate", tag = "jobs", security(("api_key" = [])),
+    request_body = AnalysisRequest,
+    responses(
+        (status = 200, description = "The model can be analysed", body = ValidationResult),
+        (status = 400, description = "The analysis is invalid for the model or engine", body = ErrorBody),
+        (status = 422, description = "The model is malformed", body = ErrorBody),
+    ),
+)]
 async fn validate_handler(
-    State(_state): State<Arc<AppState>>,
-    Json(request): Json<AnalysisRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    request: Result<Json<AnalysisRequest>, JsonRejection>,
+) -> Result<Json<ValidationResult>, ApiError> {
     tracing::info!("Validating model");
-    
+    let Json(request) = request?;
+
     // Validate the model
-    validate_model(&request.model)?;
+    state.limits.check(&request.model).map_err(ApiError::InvalidModel)?;
+    if let Some(Extension(key)) = key {
+        key.check_model(&request.model)?;
+    }
+    state.engines.check(&request)?;
     
-    Ok(Json(json!({
-        "valid": true,
-        "message": "Model validation passed"
-    })))
+    Ok(Json(ValidationResult {
+        valid: true,
+        message: "Model validation passed".to_string(),
+    }))
 }
 
-/// Run analysis
+/// Queue an analysis, returning its job id to poll
+///
+/// Also served at `/api/v1/analyze` for older clients.
+#[utoipa::path(
+    post, path = "/api/v1/jobs", operation_id = "submit_job", tag = "jobs", security(("api_key" = [])),
+    request_body = AnalysisRequest,
+    responses(
+        (status = 202, description = "The job is queued", body = AnalysisResponse),
+        (status = 400, description = "The analysis is invalid for the model or engine", body = ErrorBody),
+        (status = 422, description = "The model is malformed", body = ErrorBody),
+        (status = 503, description = "The queue is full", body = ErrorBody),
+    ),
+)]
 async fn analyze_handler(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<AnalysisRequest>,
-) -> Result<Json<AnalysisResponse>, ApiError> {
-    tracing::info!("Received analysis request");
+    key: Option<Extension<Arc<ApiKey>>>,
+    request: Result<Json<AnalysisRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<AnalysisResponse>), ApiError> {
+    let Json(request) = request?;
+    tracing::info!("Received analysis request ({:?} engine, {:?}){}", request.engine, request.analysis,
+        key.as_ref().map(|k| format!(" from {}", k.name)).unwrap_or_default());
     tracing::info!("  Nodes: {}, Beams: {}, Shells: {}", 
         request.model.nodes.len(),
         request.model.beams.len(), 
@@ -141,40 +387,241 @@ async fn analyze_handler(
             beam.section.section_type, beam.section.width, beam.section.height);
     }
 
-    // 1. Validate model
-    validate_model(&request.model)?;
+    // 1. Validate model, so bad requests fail here rather than in the job
+    state.limits.check(&request.model).map_err(|issues| {
+        tracing::warn!("Rejected a model with {} issues", issues.len());
+        ApiError::InvalidModel(issues)
+    })?;
+    if let Some(Extension(key)) = &key {
+        key.check_model(&request.model)?;
+    }
+    state.engines.check(&request)?;
     tracing::info!("Model validation passed");
 
-    // 2. Generate Input File
-    let inp_content = state.generator.generate_inp_file(&request.model)
-        .map_err(|e| ApiError::InternalError(format!("Failed to generate input file: {}", e)))?;
-    tracing::info!("Input file generated");
-
-    // 3. Execute Analysis
-    let mut executor = state.executor.lock().await;
-    let results = executor.execute(&request.model, &inp_content).await
-        .map_err(|e| ApiError::InternalError(format!("Analysis execution failed: {}", e)))?;
-
-    Ok(Json(AnalysisResponse {
-        job_id: Uuid::new_v4().to_string(),
-        status: AnalysisStatus::Success,
-        results: Some(results),
-        error_message: None,
-        timestamp: chrono::Utc::now().to_rfc3339(),
-    }))
+    // 2. Queue the analysis on the selected engine
+    let response = state.jobs.submit(request, owner(&key)).await?;
+    tracing::info!("Queued job {}", response.job_id);
+
+    Ok((StatusCode::ACCEPTED, Json(response)))
 }
 
-fn validate_model(model: &StructuralModel) -> Result<(), ApiError> {
-    if model.nodes.is_empty() {
-        return Err(ApiError::ValidationError("Model must have at least one node".to_string()));
+/// Queue a batch: a list of requests, or a base request and named
+/// variations of its sections, thicknesses or material, each run as its
+/// own job across the workers
+///
+/// Every variant is checked before any is queued, and the batch is queued
+/// whole or not at all. Each analysis is charged to the key's rate limit.
+#[utoipa::path(
+    post, path = "/api/v1/analyze/batch", operation_id = "submit_batch", tag = "jobs", security(("api_key" = [])),
+    request_body = BatchRequest,
+    responses(
+        (status = 202, description = "The jobs are queued", body = BatchResponse),
+        (status = 400, description = "The batch, or an analysis in it, is invalid", body = ErrorBody),
+        (status = 413, description = "The batch has more analyses than the service or the key's rate limit allows", body = ErrorBody),
+        (status = 422, description = "A model is malformed; issues are prefixed with the variant's name", body = ErrorBody),
+        (status = 503, description = "The queue cannot take the whole batch", body = ErrorBody),
+    ),
+)]
+async fn batch_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    request: Result<Json<BatchRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<BatchResponse>), ApiError> {
+    let Json(batch) = request?;
+    let requests = batch.expand().map_err(ApiError::ValidationError)?;
+    tracing::info!("Received a batch of {} analyses{}", requests.len(),
+        key.as_ref().map(|k| format!(" from {}", k.name)).unwrap_or_default());
+    if requests.len() > state.max_batch {
+        return Err(ApiError::QuotaExceeded(format!("A batch of {} analyses is over the limit of {}", requests.len(), state.max_batch)));
     }
-    if model.beams.is_empty() && model.shells.is_empty() {
-        return Err(ApiError::ValidationError("Model must include at least one beam or shell".to_string()));
+    let mut issues = Vec::new();
+    for (name, request) in &requests {
+        if let Err(found) = state.limits.check(&request.model) {
+            issues.extend(found.into_iter().map(|issue| ModelIssue { message: format!("{}: {}", name, issue.message), ..issue }));
+            continue;
+        }
+        if let Some(Extension(key)) = &key {
+            key.check_model(&request.model)?;
+        }
+        state.engines.check(request).map_err(|e| ApiError::ValidationError(format!("{}: {}", name, e)))?;
+    }
+    if !issues.is_empty() {
+        tracing::warn!("Rejected a batch with {} issues", issues.len());
+        return Err(ApiError::InvalidModel(issues));
     }
-    if model.supports.is_empty() {
-        return Err(ApiError::ValidationError("Model must have at least one support".to_string()));
+    // Only a batch that will run is charged; the batch request itself paid
+    // for its first analysis
+    if let Some(Extension(key)) = &key {
+        state.keys.charge(key, requests.len() - 1)?;
     }
-    Ok(())
+
+    let response = state.jobs.submit_batch(requests, owner(&key)).await?;
+    tracing::info!("Queued batch {} of {} jobs", response.batch_id, response.jobs.len());
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+/// Status of each job of a batch, with results once they have finished
+#[utoipa::path(
+    get, path = "/api/v1/analyze/batch/{id}", operation_id = "get_batch", tag = "jobs", security(("api_key" = [])),
+    params(("id" = String, Path, description = "Batch id")),
+    responses(
+        (status = 200, description = "The batch", body = BatchResponse),
+        (status = 404, description = "No such batch", body = ErrorBody),
+    ),
+)]
+async fn get_batch_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path(id): Path<String>,
+) -> Result<Json<BatchResponse>, ApiError> {
+    state.jobs.batch(&id, owner(&key)).await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No batch with id {}", id)))
+}
+
+/// Status of a job, with its results once it has finished
+#[utoipa::path(
+    get, path = "/api/v1/jobs/{id}", operation_id = "get_job", tag = "jobs", security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "The job", body = AnalysisResponse),
+        (status = 404, description = "No such job", body = ErrorBody),
+    ),
+)]
+async fn job_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path(id): Path<String>,
+) -> Result<Json<AnalysisResponse>, ApiError> {
+    state.jobs.get(&id, owner(&key)).await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No job with id {}", id)))
+}
+
+/// Stored jobs of the caller's key, newest first, filtered by `status` (or
+/// `done`), `since` and `limit`
+#[utoipa::path(
+    get, path = "/api/v1/jobs", operation_id = "list_jobs", tag = "jobs", security(("api_key" = [])),
+    params(JobQuery),
+    responses(
+        (status = 200, description = "Matching jobs", body = Vec<JobSummary>),
+        (status = 400, description = "Invalid filter", body = ErrorBody),
+    ),
+)]
+async fn list_jobs_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Query(query): Query<JobQuery>,
+) -> Result<Json<Vec<JobSummary>>, ApiError> {
+    Ok(Json(state.jobs.list(&query, owner(&key))?))
+}
+
+/// The request a job was submitted with, model included
+#[utoipa::path(
+    get, path = "/api/v1/jobs/{id}/request", operation_id = "get_job_request", tag = "jobs", security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "The submitted request", body = AnalysisRequest),
+        (status = 404, description = "No such job", body = ErrorBody),
+    ),
+)]
+async fn job_request_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path(id): Path<String>,
+) -> Result<Json<AnalysisRequest>, ApiError> {
+    state.jobs.request(&id, owner(&key))?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No job with id {}", id)))
+}
+
+/// Cancel a queued or running job
+#[utoipa::path(
+    delete, path = "/api/v1/jobs/{id}", operation_id = "cancel_job", tag = "jobs", security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "The cancelled job", body = AnalysisResponse),
+        (status = 404, description = "No such job", body = ErrorBody),
+        (status = 409, description = "The job has already finished", body = ErrorBody),
+    ),
+)]
+async fn cancel_job_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path(id): Path<String>,
+) -> Result<Json<AnalysisResponse>, ApiError> {
+    let response = state.jobs.cancel(&id, owner(&key)).await
+        .ok_or_else(|| ApiError::NotFound(format!("No job with id {}", id)))??;
+    tracing::info!("Cancelled job {}", id);
+    Ok(Json(response))
+}
+
+/// Input, output or log file of a finished job: `inp`, `dat`, `frd` or `log`
+#[utoipa::path(
+    get, path = "/api/v1/jobs/{id}/files/{kind}", operation_id = "get_job_file", tag = "jobs", security(("api_key" = [])),
+    params(
+        ("id" = String, Path, description = "Job id"),
+        ("kind" = String, Path, description = "`inp`, `dat`, `frd` or `log`"),
+    ),
+    responses(
+        (status = 200, description = "The file", body = String, content_type = "text/plain"),
+        (status = 404, description = "No such job or file", body = ErrorBody),
+    ),
+)]
+async fn job_file_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path((id, kind)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    let artifact: Artifact = kind.parse().map_err(ApiError::NotFound)?;
+    let path = state.jobs.artifact(&id, owner(&key), artifact).await
+        .ok_or_else(|| ApiError::NotFound(format!("No {} file for job {}; files are kept for finished CalculiX jobs", kind, id)))?;
+    let content = tokio::fs::read(&path).await
+        .map_err(|e| ApiError::InternalError(format!("Failed to read {:?}: {}", path, e)))?;
+    let disposition = format!("attachment; filename=\"{}-{}\"", id, artifact.file_name());
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        content,
+    ).into_response())
+}
+
+/// Progress of a job as Server-Sent Events: the events so far, then new
+/// ones as they happen, ending with its `finished` event
+#[utoipa::path(
+    get, path = "/api/v1/jobs/{id}/events", operation_id = "job_events", tag = "jobs", security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "A stream of progress events, one JSON object per event", body = ProgressEvent, content_type = "text/event-stream"),
+        (status = 404, description = "No such job", body = ErrorBody),
+    ),
+)]
+async fn job_events_handler(
+    State(state): State<Arc<AppState>>,
+    key: Option<Extension<Arc<ApiKey>>>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let (history, receiver) = state.jobs.events(&id, owner(&key)).await
+        .ok_or_else(|| ApiError::NotFound(format!("No job with id {}", id)))?;
+
+    let events = stream::unfold((history.into_iter(), receiver, false), |(mut history, mut receiver, done)| async move {
+        if done {
+            return None;
+        }
+        let event = match history.next() {
+            Some(event) => event,
+            None => loop {
+                match receiver.recv().await {
+                    Ok(event) => break event,
+                    // A slow client misses some solver output rather than holding up the job
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            },
+        };
+        let done = event.is_finished();
+        Some((Event::default().json_data(&event), (history, receiver, done)))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
 }
 
 /// API Errors
@@ -184,19 +631,153 @@ pub enum ApiError {
     ValidationError(String),
     #[error("Internal error: {0}")]
     InternalError(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Too many requests: {message}")]
+    TooManyRequests { message: String, retry_after: Duration },
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+    #[error("Invalid model: {} issues", .0.len())]
+    InvalidModel(Vec<ModelIssue>),
+    /// A body that is not a request, with the status axum gives it
+    #[error("Invalid request body: {message}")]
+    InvalidBody { status: StatusCode, message: String },
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::InvalidBody { status: rejection.status(), message: rejection.body_text() }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(error: AuthError) -> Self {
+        match error {
+            AuthError::Missing | AuthError::Invalid => ApiError::Unauthorized(error.to_string()),
+            AuthError::RateLimited(retry_after) => ApiError::TooManyRequests { message: error.to_string(), retry_after },
+            AuthError::Quota(msg) => ApiError::QuotaExceeded(msg),
+            AuthError::Config(msg) => ApiError::InternalError(msg),
+        }
+    }
+}
+
+impl From<EngineError> for ApiError {
+    fn from(error: EngineError) -> Self {
+        match error {
+            EngineError::Unsupported(msg) => ApiError::ValidationError(msg),
+            EngineError::Failed(msg) => ApiError::InternalError(msg),
+        }
+    }
+}
+
+impl From<StoreError> for ApiError {
+    fn from(error: StoreError) -> Self {
+        match error {
+            StoreError::Query(msg) => ApiError::ValidationError(msg),
+            _ => ApiError::InternalError(error.to_string()),
+        }
+    }
+}
+
+impl From<JobError> for ApiError {
+    fn from(error: JobError) -> Self {
+        match error {
+            JobError::QueueFull => ApiError::Unavailable(error.to_string()),
+            JobError::Finished => ApiError::Conflict(error.to_string()),
+            JobError::TooMany { .. } => ApiError::ValidationError(error.to_string()),
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let mut headers = Vec::new();
+        let mut issues = Vec::new();
         let (status, message) = match self {
             ApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
             ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            ApiError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            ApiError::Unauthorized(msg) => {
+                headers.push((header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer")));
+                (StatusCode::UNAUTHORIZED, msg)
+            }
+            ApiError::TooManyRequests { message, retry_after } => {
+                headers.push((header::RETRY_AFTER, HeaderValue::from(retry_after.as_secs_f64().ceil() as u64)));
+                (StatusCode::TOO_MANY_REQUESTS, message)
+            }
+            ApiError::QuotaExceeded(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
+            ApiError::InvalidModel(found) => {
+                let message = match found.as_slice() {
+                    [issue] => format!("Model is invalid: {}", issue.message),
+                    [first, rest @ ..] => format!(
+                        "Model is invalid: {} (and {} more issue{})",
+                        first.message,
+                        rest.len(),
+                        if rest.len() == 1 { "" } else { "s" }
+                    ),
+                    [] => "Model is invalid".to_string(),
+                };
+                issues = found;
+                (StatusCode::UNPROCESSABLE_ENTITY, message)
+            }
+            ApiError::InvalidBody { status, message } => (status, message),
         };
 
-        let body = Json(json!({
-            "error": message
-        }));
+        let body = Json(ErrorBody { error: message, issues });
+
+        (status, headers.into_iter().collect::<HeaderMap>(), body).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The calculix-client crate is generated from a checked-in copy of the
+    /// spec; run with `UPDATE_OPENAPI=1` after changing the API to refresh it
+    #[test]
+    fn test_openapi_spec_is_current() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../calculix-client/openapi.json");
+        let spec = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(path, &spec).unwrap();
+        }
+        let current = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(current == spec, "{} is out of date, rerun the tests with UPDATE_OPENAPI=1", path);
+    }
 
-        (status, body).into_response()
+    #[tokio::test]
+    async fn test_rejected_batch_is_not_charged() {
+        let key = Arc::new(ApiKey {
+            name: "test".to_string(),
+            key: "secret".to_string(),
+            requests_per_minute: Some(3),
+            max_nodes: Some(3),
+            max_elements: None,
+        });
+        let engines = Arc::new(Engines::new());
+        let state = Arc::new(AppState {
+            jobs: JobQueue::new(Arc::clone(&engines), 1, 4),
+            engines,
+            keys: ApiKeys::new(vec![(*key).clone()]),
+            limits: ModelLimits::default(),
+            max_request_bytes: 1024 * 1024,
+            max_batch: 32,
+        });
+        // Every model of the batch is over the key's node quota
+        let request: AnalysisRequest = serde_json::from_str(include_str!("../portal_frame_request.json")).unwrap();
+        let batch = BatchRequest { requests: vec![request; 3], base: None, variations: Vec::new() };
+        let rejected = batch_handler(State(Arc::clone(&state)), Some(Extension(Arc::clone(&key))), Ok(Json(batch))).await;
+        assert!(matches!(rejected, Err(ApiError::QuotaExceeded(_))));
+        // The key still has the whole minute's worth of requests
+        assert!(state.keys.charge(&key, 3).is_ok());
     }
 }
@@ -0,0 +1,279 @@
+//! API keys, with a request rate and model size quota per key
+//!
+//! Keys are read from a JSON file of [`ApiKey`] entries, e.g.
+//!
+//! ```json
+//! [{"name": "frontend", "key": "…", "requests_per_minute": 60, "max_nodes": 5000, "max_elements": 5000}]
+//! ```
+//!
+//! Clients send their key in an `X-API-Key` header, or as
+//! `Authorization: Bearer <key>`. Without a key file the service is open,
+//! which is only meant for running it on localhost.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::http::{header, HeaderMap};
+use serde::Deserialize;
+
+use crate::models::StructuralModel;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// A client's key and quotas; a missing quota is unlimited
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKey {
+    /// Who the key belongs to, for logs; jobs submitted with the key belong
+    /// to this name
+    pub name: String,
+    pub key: String,
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    #[serde(default)]
+    pub max_nodes: Option<usize>,
+    /// Beams and shells together
+    #[serde(default)]
+    pub max_elements: Option<usize>,
+}
+
+impl ApiKey {
+    /// Check a submitted model against the key's size quota
+    pub fn check_model(&self, model: &StructuralModel) -> Result<(), AuthError> {
+        let elements = model.beams.len() + model.shells.len();
+        if let Some(max) = self.max_nodes.filter(|max| model.nodes.len() > *max) {
+            return Err(AuthError::Quota(format!("Model has {} nodes, over the quota of {}", model.nodes.len(), max)));
+        }
+        if let Some(max) = self.max_elements.filter(|max| elements > *max) {
+            return Err(AuthError::Quota(format!("Model has {} elements, over the quota of {}", elements, max)));
+        }
+        Ok(())
+    }
+}
+
+/// Requests a key may still make, refilled continuously up to one minute's worth
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Spend `count` requests, or the wait until they are available
+    fn take(&mut self, per_minute: u32, count: usize, now: Instant) -> Result<(), Duration> {
+        let capacity = per_minute as f64;
+        let count = count as f64;
+        let refilled = now.duration_since(self.updated).as_secs_f64() * capacity / 60.0;
+        self.tokens = (self.tokens + refilled).min(capacity);
+        self.updated = now;
+        if self.tokens >= count {
+            self.tokens -= count;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((count - self.tokens) * 60.0 / capacity))
+        }
+    }
+}
+
+struct Entry {
+    key: Arc<ApiKey>,
+    bucket: Mutex<Bucket>,
+}
+
+/// The keys allowed to use the API
+#[derive(Default)]
+pub struct ApiKeys {
+    entries: HashMap<String, Entry>,
+}
+
+impl ApiKeys {
+    pub fn new(keys: Vec<ApiKey>) -> Self {
+        let now = Instant::now();
+        let entries = keys.into_iter()
+            .map(|key| {
+                let bucket = Bucket { tokens: key.requests_per_minute.unwrap_or(0) as f64, updated: now };
+                (key.key.clone(), Entry { key: Arc::new(key), bucket: Mutex::new(bucket) })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Keys from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AuthError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AuthError::Config(format!("Failed to read {:?}: {}", path, e)))?;
+        let keys: Vec<ApiKey> = serde_json::from_str(&text)
+            .map_err(|e| AuthError::Config(format!("Invalid key file {:?}: {}", path, e)))?;
+        if let Some(key) = keys.iter().find(|k| k.key.is_empty()) {
+            return Err(AuthError::Config(format!("Key '{}' is empty", key.name)));
+        }
+        // A limit of nothing a minute would never refill
+        if let Some(key) = keys.iter().find(|k| k.requests_per_minute == Some(0)) {
+            return Err(AuthError::Config(format!("Key '{}' allows no requests a minute", key.name)));
+        }
+        // Jobs are owned by key name, so two keys of one name would share them
+        let mut names = HashSet::new();
+        if let Some(key) = keys.iter().find(|k| !names.insert(k.name.as_str())) {
+            return Err(AuthError::Config(format!("More than one key is named '{}'", key.name)));
+        }
+        Ok(Self::new(keys))
+    }
+
+    /// Without keys, requests need no key at all
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The key a request was sent with, charged against its rate limit
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<Arc<ApiKey>, AuthError> {
+        self.authenticate_at(headers, Instant::now())
+    }
+
+    fn authenticate_at(&self, headers: &HeaderMap, now: Instant) -> Result<Arc<ApiKey>, AuthError> {
+        let key = request_key(headers).ok_or(AuthError::Missing)?;
+        let entry = self.entries.get(key).ok_or(AuthError::Invalid)?;
+        if let Some(per_minute) = entry.key.requests_per_minute {
+            entry.bucket.lock().unwrap().take(per_minute, 1, now).map_err(AuthError::RateLimited)?;
+        }
+        Ok(Arc::clone(&entry.key))
+    }
+
+    /// Charge an authenticated key for `count` more requests, for a request
+    /// that does the work of several, such as a batch of analyses
+    pub fn charge(&self, key: &ApiKey, count: usize) -> Result<(), AuthError> {
+        self.charge_at(key, count, Instant::now())
+    }
+
+    fn charge_at(&self, key: &ApiKey, count: usize, now: Instant) -> Result<(), AuthError> {
+        let Some((entry, per_minute)) = self.entries.get(&key.key)
+            .and_then(|entry| Some((entry, entry.key.requests_per_minute?))) else {
+            return Ok(());
+        };
+        // More than a minute's worth would never be available
+        if count > per_minute as usize {
+            return Err(AuthError::Quota(format!("{} requests at once are over the rate limit of {} a minute", count, per_minute)));
+        }
+        entry.bucket.lock().unwrap().take(per_minute, count, now).map_err(AuthError::RateLimited)
+    }
+}
+
+/// Key from the `X-API-Key` header, or a bearer token
+fn request_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(key) = headers.get(API_KEY_HEADER) {
+        return key.to_str().ok();
+    }
+    headers.get(header::AUTHORIZATION)?
+        .to_str().ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("An API key is required, in an X-API-Key header or as a bearer token")]
+    Missing,
+    #[error("Unknown API key")]
+    Invalid,
+    #[error("Rate limit exceeded, retry in {:.0} s", .0.as_secs_f64().ceil())]
+    RateLimited(Duration),
+    #[error("{0}")]
+    Quota(String),
+    #[error("{0}")]
+    Config(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn key(requests_per_minute: Option<u32>) -> ApiKey {
+        ApiKey {
+            name: "test".to_string(),
+            key: "secret".to_string(),
+            requests_per_minute,
+            max_nodes: Some(3),
+            max_elements: None,
+        }
+    }
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_authenticate_and_rate_limit() {
+        let keys = ApiKeys::new(vec![key(Some(2))]);
+        let bearer = headers(header::AUTHORIZATION, "Bearer secret");
+        let now = Instant::now();
+
+        assert!(matches!(keys.authenticate_at(&HeaderMap::new(), now), Err(AuthError::Missing)));
+        assert!(matches!(keys.authenticate_at(&headers(header::HeaderName::from_static(API_KEY_HEADER), "wrong"), now), Err(AuthError::Invalid)));
+        assert_eq!(keys.authenticate_at(&bearer, now).unwrap().name, "test");
+        assert!(keys.authenticate_at(&bearer, now).is_ok());
+        match keys.authenticate_at(&bearer, now) {
+            Err(AuthError::RateLimited(wait)) => assert!((wait.as_secs_f64() - 30.0).abs() < 1e-6),
+            other => panic!("expected a rate limit, got {:?}", other.map(|k| k.name.clone())),
+        }
+        // Two requests a minute refill one every 30 s
+        assert!(keys.authenticate_at(&bearer, now + Duration::from_secs(30)).is_ok());
+        assert!(ApiKeys::default().is_empty());
+    }
+
+    #[test]
+    fn test_charge_several_requests() {
+        let keys = ApiKeys::new(vec![key(Some(4))]);
+        let bearer = headers(header::AUTHORIZATION, "Bearer secret");
+        let now = Instant::now();
+        let key = keys.authenticate_at(&bearer, now).unwrap();
+
+        assert!(matches!(keys.charge_at(&key, 5, now), Err(AuthError::Quota(_))));
+        assert!(keys.charge_at(&key, 2, now).is_ok());
+        // One left, so two more wait 15 s for the second
+        match keys.charge_at(&key, 2, now) {
+            Err(AuthError::RateLimited(wait)) => assert!((wait.as_secs_f64() - 15.0).abs() < 1e-6),
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+        assert!(keys.charge_at(&key, 2, now + Duration::from_secs(15)).is_ok());
+        let unlimited = ApiKeys::new(vec![self::key(None)]);
+        assert!(unlimited.charge_at(&self::key(None), 1000, now).is_ok());
+    }
+
+    #[test]
+    fn test_key_names_are_unique() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("keys.json");
+        std::fs::write(&path, r#"[{"name": "a", "key": "one"}, {"name": "a", "key": "two"}]"#).unwrap();
+        assert!(matches!(ApiKeys::load(&path), Err(AuthError::Config(_))));
+        std::fs::write(&path, r#"[{"name": "a", "key": "one"}, {"name": "b", "key": "two"}]"#).unwrap();
+        assert_eq!(ApiKeys::load(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_zero_rate_limit_is_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("keys.json");
+        std::fs::write(&path, r#"[{"name": "a", "key": "one", "requests_per_minute": 0}]"#).unwrap();
+        assert!(matches!(ApiKeys::load(&path), Err(AuthError::Config(_))));
+        std::fs::write(&path, r#"[{"name": "a", "key": "one", "requests_per_minute": 1}]"#).unwrap();
+        assert_eq!(ApiKeys::load(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_model_quota() {
+        let mut model: StructuralModel = serde_json::from_str::<crate::models::AnalysisRequest>(
+            include_str!("../portal_frame_request.json"),
+        ).unwrap().model;
+        model.nodes.truncate(3);
+        assert!(key(None).check_model(&model).is_ok());
+        model.nodes.push(model.nodes[0].clone());
+        assert!(matches!(key(None).check_model(&model), Err(AuthError::Quota(_))));
+    }
+}
@@ -1,12 +1,19 @@
+#![allow(dead_code)]
+
 #[path = "../generator.rs"]
 mod generator;
 #[path = "../executor.rs"]
 mod executor;
 #[path = "../models.rs"]
 mod models;
+#[path = "../frd.rs"]
+mod frd;
+#[path = "../progress.rs"]
+mod progress;
 
 use generator::CalculiXGenerator;
 use executor::CalculiXExecutor;
+use progress::ProgressLog;
 use models::AnalysisRequest;
 
 #[tokio::main]
@@ -22,10 +29,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let request: AnalysisRequest = serde_json::from_str(&json)?;
 
     let generator = CalculiXGenerator::new();
-    let inp = generator.generate_inp_file(&request.model)?;
+    let inp = generator.generate_inp_file(&request.model, request.analysis)?;
 
+    let work_dir = tempfile::TempDir::new()?;
     let mut executor = CalculiXExecutor::new();
-    let results = executor.execute(&request.model, &inp).await?;
+    let results = executor.execute(&request.model, &inp, request.analysis, work_dir.path(), &ProgressLog::default()).await?;
 
     println!("{}", serde_json::to_string_pretty(&results)?);
     Ok(())
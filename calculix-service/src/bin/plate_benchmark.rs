@@ -6,10 +6,15 @@ mod executor;
 mod generator;
 #[path = "../models.rs"]
 mod models;
+#[path = "../frd.rs"]
+mod frd;
+#[path = "../progress.rs"]
+mod progress;
 
 use executor::CalculiXExecutor;
+use progress::ProgressLog;
 use generator::CalculiXGenerator;
-use models::{Material, Node, PressureLoad, Shell, StructuralModel, Support, SupportType};
+use models::{AnalysisKind, Material, Node, PressureLoad, Shell, StructuralModel, Support, SupportType};
 
 fn build_plate_model() -> StructuralModel {
     let span_x = 8.0;
@@ -63,6 +68,8 @@ fn build_plate_model() -> StructuralModel {
     let pressure_loads = vec![PressureLoad {
         element_ids: (0..shells.len()).collect(),
         magnitude: 5.0,
+        direction: None,
+        load_case: models::DEFAULT_LOAD_CASE.to_string(),
     }];
 
     StructuralModel {
@@ -74,11 +81,15 @@ fn build_plate_model() -> StructuralModel {
             elastic_modulus: 210e6,
             poisson_ratio: 0.3,
             density: 78.5,
+            thermal_expansion: None,
         },
         supports,
         point_loads: Vec::new(),
         distributed_loads: Vec::new(),
         pressure_loads,
+        temperature_loads: Vec::new(),
+        contact_pairs: Vec::new(),
+        load_combinations: Vec::new(),
     }
 }
 
@@ -87,7 +98,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let model = build_plate_model();
     let generator = CalculiXGenerator::new();
-    let inp = generator.generate_inp_file(&model)?;
+    let inp = generator.generate_inp_file(&model, AnalysisKind::Static)?;
 
     std::fs::write("plate_benchmark.inp", &inp)?;
     println!("Input written to plate_benchmark.inp");
@@ -95,7 +106,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut executor = CalculiXExecutor::new();
     std::env::set_var("CALCULIX_PATH", "ccx" );
     let rt = tokio::runtime::Runtime::new()?;
-    let results = rt.block_on(executor.execute(&model, &inp))?;
+    let work_dir = tempfile::TempDir::new()?;
+    let results = rt.block_on(executor.execute(&model, &inp, AnalysisKind::Static, work_dir.path(), &ProgressLog::default()))?;
 
     println!("Max displacement: {:.6} m", results.max_displacement);
     println!("Max stress: {:.6} Pa", results.max_stress);
@@ -0,0 +1,160 @@
+//! Solver engines an analysis request can select
+//!
+//! CalculiX runs as an external `ccx` process on a generated input file; the
+//! native engine runs fea-solver in-process, so linear static and modal jobs
+//! do not need the binary installed.
+
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::executor::{CalculiXExecutor, ExecutorError};
+use crate::generator::CalculiXGenerator;
+use crate::metrics::Metrics;
+use crate::models::{AnalysisKind, AnalysisRequest, AnalysisResults, Engine};
+use crate::native::NativeEngine;
+use crate::progress::{Phase, ProgressLog};
+
+/// The available engines
+pub struct Engines {
+    /// One permit per ccx process allowed to run at a time
+    ccx_slots: Semaphore,
+    /// ccx to run instead of the one `CALCULIX_PATH` names
+    ccx_path: Option<String>,
+    generator: CalculiXGenerator,
+    native: Arc<NativeEngine>,
+    metrics: Arc<Metrics>,
+}
+
+impl Engines {
+    pub fn new() -> Self {
+        Self {
+            ccx_slots: Semaphore::new(1),
+            ccx_path: None,
+            generator: CalculiXGenerator::new(),
+            native: Arc::new(NativeEngine::new()),
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Record ccx exit codes and unreadable results in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Allow up to `processes` ccx processes at once; jobs beyond that wait
+    /// for one to finish
+    pub fn with_max_processes(mut self, processes: usize) -> Self {
+        self.ccx_slots = Semaphore::new(processes.max(1));
+        self
+    }
+
+    /// Run `path` as ccx, such as a stand-in that never finishes
+    #[cfg(test)]
+    pub fn with_ccx_path(mut self, path: impl Into<String>) -> Self {
+        self.ccx_path = Some(path.into());
+        self
+    }
+
+    /// ccx processes that could start now
+    #[cfg(test)]
+    pub fn free_ccx_slots(&self) -> usize {
+        self.ccx_slots.available_permits()
+    }
+
+    /// Check that the selected engine can run the requested analysis
+    pub fn check(&self, request: &AnalysisRequest) -> Result<(), EngineError> {
+        match (request.engine, request.analysis) {
+            (Engine::Native, AnalysisKind::Buckling { .. }) => Err(EngineError::Unsupported(
+                "Buckling analysis is only available with the CalculiX engine".to_string(),
+            )),
+            (_, AnalysisKind::Modal { num_modes: 0 } | AnalysisKind::Buckling { num_modes: 0 }) => {
+                Err(EngineError::Unsupported("Modal and buckling analyses need at least one mode".to_string()))
+            }
+            (_, AnalysisKind::Nonlinear { increments: 0, .. }) => {
+                Err(EngineError::Unsupported("Nonlinear analysis needs at least one increment".to_string()))
+            }
+            (_, AnalysisKind::Buckling { .. }) if request.model.combinations().len() > 1 => Err(EngineError::Unsupported(
+                "Buckling analysis takes a single load combination".to_string(),
+            )),
+            (_, AnalysisKind::Modal { .. } | AnalysisKind::Buckling { .. }) if !request.model.contact_pairs.is_empty() => {
+                Err(EngineError::Unsupported("Contact is only analysed in static analyses".to_string()))
+            }
+            // Each CalculiX step starts from the deformed state of the last
+            (Engine::CalculiX, AnalysisKind::Nonlinear { .. }) if request.model.combinations().len() > 1 => {
+                Err(EngineError::Unsupported(
+                    "Nonlinear analysis with CalculiX takes a single load combination".to_string(),
+                ))
+            }
+            (Engine::Native, AnalysisKind::Nonlinear { .. }) if !request.model.shells.is_empty() => {
+                Err(EngineError::Unsupported(
+                    "Nonlinear analysis of shells is only available with the CalculiX engine".to_string(),
+                ))
+            }
+            (Engine::CalculiX, _) => Ok(()),
+            (Engine::Native, _) => self.native.check(&request.model).map_err(EngineError::Unsupported),
+        }
+    }
+
+    /// Run the request on its engine, reporting its progress to `progress`;
+    /// CalculiX leaves its files in `work_dir`
+    pub async fn analyze(
+        &self,
+        request: &AnalysisRequest,
+        work_dir: &Path,
+        progress: &ProgressLog,
+    ) -> Result<AnalysisResults, EngineError> {
+        self.check(request)?;
+        match request.engine {
+            Engine::CalculiX => {
+                progress.phase(Phase::Generating);
+                let inp_content = self.generator.generate_inp_file(&request.model, request.analysis)
+                    .map_err(|e| EngineError::Failed(format!("Failed to generate input file: {}", e)))?;
+                tracing::info!("Input file generated");
+
+                // Dropping the permit, also when the job is cancelled, frees the slot
+                let _slot = self.ccx_slots.acquire().await
+                    .map_err(|e| EngineError::Failed(format!("CalculiX is unavailable: {}", e)))?;
+                let mut executor = match &self.ccx_path {
+                    Some(path) => CalculiXExecutor::with_ccx_path(path.as_str()),
+                    None => CalculiXExecutor::new(),
+                };
+                let result = executor.execute(&request.model, &inp_content, request.analysis, work_dir, progress).await;
+                match &result {
+                    Ok(_) => self.metrics.ccx_exit(Some(0)),
+                    Err(ExecutorError::Exited { code, .. } | ExecutorError::NotConverged { code, .. }) => {
+                        self.metrics.ccx_exit(*code)
+                    }
+                    Err(ExecutorError::ParseError(_)) => {
+                        self.metrics.ccx_exit(Some(0));
+                        self.metrics.parse_failure();
+                    }
+                    Err(_) => {}
+                }
+                result.map_err(|e| EngineError::Failed(format!("Analysis execution failed: {}", e)))
+            }
+            Engine::Native => {
+                // The solve is CPU-bound, so keep it off the async workers
+                let native = Arc::clone(&self.native);
+                let model = request.model.clone();
+                let analysis = request.analysis;
+                progress.phase(Phase::Solving);
+                tokio::task::spawn_blocking(move || native.execute(&model, analysis))
+                    .await
+                    .map_err(|e| EngineError::Failed(format!("Native analysis panicked: {}", e)))?
+                    .map_err(|e| EngineError::Failed(format!("Native analysis failed: {}", e)))
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EngineError {
+    /// The engine cannot run this request
+    #[error("{0}")]
+    Unsupported(String),
+    /// The engine ran but the analysis failed
+    #[error("{0}")]
+    Failed(String),
+}
@@ -1,113 +1,240 @@
 use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
-use tempfile::TempDir;
-use uuid::Uuid;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
 
-use crate::models::{AnalysisResults, StructuralModel, NodeDisplacement, NodeReaction, ElementStress, BeamForces};
+use crate::frd::{FrdElement, FrdFile};
+use crate::progress::{Phase, ProgressLog};
+use crate::models::{AnalysisKind, AnalysisResults, BucklingMode, CombinationResults, Increment, ModeResult, StructuralModel, NodeDisplacement, NodeReaction, NodeStress, ElementStress, BeamForces};
+
+/// Job name ccx is run with, so its files are `analysis.inp`, `analysis.dat`, ...
+pub const JOB_NAME: &str = "analysis";
+
+/// File the console output of ccx is saved to
+pub const LOG_FILE: &str = "analysis.log";
 
 pub struct CalculiXExecutor {
-    work_dir: PathBuf,
+    ccx_path: String,
 }
 
 impl CalculiXExecutor {
+    /// Runs the ccx named by `CALCULIX_PATH`, or `ccx` on the path
     pub fn new() -> Self {
-        Self {
-            work_dir: std::env::temp_dir().join("calculix_work"),
-        }
+        Self::with_ccx_path(std::env::var("CALCULIX_PATH").unwrap_or_else(|_| "ccx".to_string()))
     }
 
+    pub fn with_ccx_path(ccx_path: impl Into<String>) -> Self {
+        Self { ccx_path: ccx_path.into() }
+    }
+
+    /// Run ccx on `inp_content` in `work_path`, which keeps the input file,
+    /// the files ccx writes and its console output in [`LOG_FILE`]
     pub async fn execute(
         &mut self,
         model: &StructuralModel,
         inp_content: &str,
+        analysis: AnalysisKind,
+        work_path: &Path,
+        progress: &ProgressLog,
     ) -> Result<AnalysisResults, ExecutorError> {
-        // Create a unique temporary directory for this analysis
-        let analysis_id = Uuid::new_v4();
-        let temp_dir = TempDir::new().map_err(|e| ExecutorError::IoError(e.to_string()))?;
-        let work_path = temp_dir.path();
-
-        tracing::info!("Starting analysis {} in {:?}", analysis_id, work_path);
+        tracing::info!("Starting analysis in {:?}", work_path);
+        fs::create_dir_all(work_path)
+            .map_err(|e| ExecutorError::IoError(format!("Failed to create {:?}: {}", work_path, e)))?;
 
         // Write the .inp file
-        let inp_path = work_path.join("analysis.inp");
+        let inp_path = work_path.join(format!("{}.inp", JOB_NAME));
         fs::write(&inp_path, inp_content)
             .map_err(|e| ExecutorError::IoError(format!("Failed to write .inp file: {}", e)))?;
 
-        Self::maybe_export_debug_file(&inp_path, &analysis_id, "inp");
-
         // Run CalculiX (ccx)
         // Note: ccx expects the job name WITHOUT extension
-        let job_name = "analysis";
-        let ccx_path = std::env::var("CALCULIX_PATH").unwrap_or_else(|_| "ccx".to_string());
+        let job_name = JOB_NAME;
+        let ccx_path = &self.ccx_path;
 
         tracing::info!("Running command: {} {}", ccx_path, job_name);
 
-        let output = Command::new(&ccx_path)
+        let mut child = Command::new(ccx_path)
             .arg(job_name)
             .current_dir(work_path)
-            .output()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
             .map_err(|e| ExecutorError::ExecutionError(format!("Failed to execute ccx: {}", e)))?;
+        progress.phase(Phase::Solving);
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            tracing::error!("CalculiX failed. Stderr: {}\nStdout: {}", stderr, stdout);
-            return Err(ExecutorError::AnalysisFailed(format!(
-                "CalculiX exited with status {}. Check logs.",
-                output.status
-            )));
+        // Forward stdout as ccx prints it; stderr is only needed if it fails
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let mut stdout_text = String::new();
+        let mut stderr_text = String::new();
+        let read_stdout = async {
+            while let Ok(Some(line)) = stdout.next_line().await {
+                progress.ccx_line(&line);
+                stdout_text.push_str(&line);
+                stdout_text.push('\n');
+            }
+        };
+        let (_, _) = tokio::join!(read_stdout, stderr.read_to_string(&mut stderr_text));
+        let status = child.wait().await
+            .map_err(|e| ExecutorError::ExecutionError(format!("Failed to wait for ccx: {}", e)))?;
+        if let Err(e) = fs::write(work_path.join(LOG_FILE), format!("{}{}", stdout_text, stderr_text)) {
+            tracing::warn!("Failed to save the ccx output: {}", e);
         }
 
-        // Parse results from the .dat file
-        let results = self.parse_dat_results(work_path, model)?;
+        if !status.success() {
+            tracing::error!("CalculiX failed. Stderr: {}\nStdout: {}", stderr_text, stdout_text);
+            if let Some(error) = stdout_text.lines().find(|line| Self::is_convergence_error(line)) {
+                let increments = Self::read_increments(work_path);
+                return Err(ExecutorError::NotConverged {
+                    message: Self::convergence_message(error, increments.last()),
+                    code: status.code(),
+                });
+            }
+            return Err(ExecutorError::Exited { status: status.to_string(), code: status.code() });
+        }
 
-        // Export the resulting .dat for debugging if requested
+        // Parse results from the .dat file, then prefer the .frd nodal fields
+        progress.phase(Phase::Parsing);
+        if !analysis.is_static() {
+            let content = fs::read_to_string(work_path.join("analysis.dat"))
+                .map_err(|e| ExecutorError::IoError(format!("Failed to read .dat file: {}", e)))?;
+            return Ok(Self::parse_eigen_results(&content, analysis));
+        }
         let dat_path = work_path.join("analysis.dat");
-        if dat_path.exists() {
-            Self::maybe_export_debug_file(&dat_path, &analysis_id, "dat");
+        if !dat_path.exists() {
+            return Err(ExecutorError::ParseError("No .dat file generated".to_string()));
+        }
+        let content = fs::read_to_string(&dat_path)
+            .map_err(|e| ExecutorError::IoError(format!("Failed to read .dat file: {}", e)))?;
+
+        // DEBUG: Print first 200 lines of .dat file to logs
+        tracing::info!("--- .dat file content (first 200 lines) ---");
+        for (i, line) in content.lines().take(200).enumerate() {
+            tracing::info!("{:03}: {}", i + 1, line);
         }
+        tracing::info!("--- end of .dat preview ---");
+
+        let frd_path = work_path.join("analysis.frd");
+        let frd = if frd_path.exists() {
+            let frd = fs::read_to_string(&frd_path)
+                .map_err(|e| ExecutorError::IoError(format!("Failed to read .frd file: {}", e)))
+                .and_then(|content| FrdFile::parse(&content).map_err(|e| ExecutorError::AnalysisFailed(e.to_string())));
+            frd.inspect_err(|e| tracing::warn!("Using .dat results only: {}", e)).ok()
+        } else {
+            None
+        };
 
+        // One static step per load combination
+        let combinations = model.combinations();
+        let steps = Self::split_steps(&content);
+        if steps.len() < combinations.len() {
+            return Err(ExecutorError::ParseError(format!(
+                "Expected results of {} load combinations, found {} steps",
+                combinations.len(),
+                steps.len()
+            )));
+        }
+        let mut results = Vec::new();
+        for (step, (combination, dat)) in combinations.iter().zip(&steps).enumerate() {
+            let mut step_results = self.parse_dat_results(dat, model)?;
+            if let Some(frd) = &frd {
+                Self::apply_frd_results(frd, step, model, &mut step_results);
+            }
+            results.push(CombinationResults::new(&combination.name, step_results));
+        }
+
+        let mut results = AnalysisResults::from_combinations(model, results);
+        if matches!(analysis, AnalysisKind::Nonlinear { .. }) {
+            results.increments = Self::read_increments(work_path);
+        }
         Ok(results)
     }
 
-    fn maybe_export_debug_file(path: &Path, analysis_id: &Uuid, extension: &str) {
-        if let Ok(dest_dir) = std::env::var("CALCULIX_DEBUG_EXPORT") {
-            let dest_path = PathBuf::from(dest_dir);
-            if let Err(err) = fs::create_dir_all(&dest_path) {
-                tracing::warn!("Failed to create debug export directory {:?}: {}", dest_path, err);
-                return;
+    /// The .dat output at the end of each step; ccx heads every table with
+    /// the total time of its increment, e.g. "displacements (vx,vy,vz) for set
+    /// NALL and time  0.2000000E+01", and each static step takes a unit of time
+    fn split_steps(content: &str) -> Vec<String> {
+        let step_of = |time: &str| time.parse::<f64>().map_or(0, |t| (t - 1e-6).ceil().max(1.0) as usize - 1);
+        // Tables of each increment, by step
+        let mut increments = vec![(0, String::new())];
+        let mut time: Option<&str> = None;
+        for line in content.lines() {
+            if let Some((_, t)) = line.split_once(" and time") {
+                let t = t.trim();
+                match time {
+                    Some(current) if current != t => increments.push((step_of(t), String::new())),
+                    Some(_) => {}
+                    None => increments[0].0 = step_of(t),
+                }
+                time = Some(t);
             }
+            let text = &mut increments.last_mut().expect("there is always an increment").1;
+            text.push_str(line);
+            text.push('\n');
+        }
 
-            let file_name = format!("analysis_{}.{}", analysis_id, extension);
-            let dest_file = dest_path.join(file_name);
-            if let Err(err) = fs::copy(path, &dest_file) {
-                tracing::warn!("Failed to export debug file {:?}: {}", dest_file, err);
-            } else {
-                tracing::info!("Exported debug file to {:?}", dest_file);
+        // The last increment of a step holds its results
+        let mut steps = Vec::new();
+        for (step, text) in increments {
+            if steps.len() <= step {
+                steps.resize(step + 1, String::new());
             }
+            steps[step] = text;
+        }
+        steps
+    }
+
+    /// Converged increments listed in the `.sta` file, if ccx wrote one
+    fn read_increments(work_path: &Path) -> Vec<Increment> {
+        fs::read_to_string(work_path.join(format!("{}.sta", JOB_NAME)))
+            .map(|content| Self::parse_sta(&content))
+            .unwrap_or_default()
+    }
+
+    /// Rows of the `.sta` summary, `STEP INC ATT ITRS TOT TIME STEP TIME INC TIME`
+    fn parse_sta(content: &str) -> Vec<Increment> {
+        content.lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let [step, increment, attempts, iterations, _, step_time, _] = fields[..] else {
+                    return None;
+                };
+                Some(Increment {
+                    step: step.parse().ok()?,
+                    increment: increment.parse().ok()?,
+                    attempts: attempts.parse().ok()?,
+                    iterations: iterations.parse().ok()?,
+                    load_factor: step_time.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+
+    /// ccx errors that end a nonlinear step short of its full load
+    fn is_convergence_error(line: &str) -> bool {
+        line.contains("*ERROR") && (line.contains("cutback") || line.contains("increment size"))
+    }
+
+    fn convergence_message(error: &str, last: Option<&Increment>) -> String {
+        let error = error.trim().trim_start_matches("*ERROR").trim_start_matches([':', ' ']);
+        match last {
+            Some(last) => format!(
+                "{} after {:.0}% of the load of step {}",
+                error,
+                last.load_factor * 100.0,
+                last.step
+            ),
+            None => format!("{} in the first increment", error),
         }
     }
 
     fn parse_dat_results(
         &self,
-        work_path: &std::path::Path,
+        content: &str,
         model: &StructuralModel,
     ) -> Result<AnalysisResults, ExecutorError> {
-        let dat_path = work_path.join("analysis.dat");
-        if !dat_path.exists() {
-            return Err(ExecutorError::AnalysisFailed("No .dat file generated".to_string()));
-        }
-
-        let content = fs::read_to_string(&dat_path)
-            .map_err(|e| ExecutorError::IoError(format!("Failed to read .dat file: {}", e)))?;
-
-        // DEBUG: Print first 200 lines of .dat file to logs
-        tracing::info!("--- .dat file content (first 200 lines) ---");
-        for (i, line) in content.lines().take(200).enumerate() {
-            tracing::info!("{:03}: {}", i + 1, line);
-        }
-        tracing::info!("--- end of .dat preview ---");
 
         let mut results = AnalysisResults {
             displacements: Vec::new(),
@@ -117,6 +244,10 @@ impl CalculiXExecutor {
             max_displacement: 0.0,
             max_stress: 0.0,
             max_beam_stress: 0.0,
+            modes: Vec::new(),
+            buckling_modes: Vec::new(),
+            combinations: Vec::new(),
+            increments: Vec::new(),
         };
 
         // Calculate max original node ID to distinguish top/bottom nodes
@@ -133,17 +264,19 @@ impl CalculiXExecutor {
         for line in content.lines() {
             let line_lower = line.to_lowercase();
             
-            // Detect sections
-            if line_lower.contains("displacements") && line_lower.contains("vx") {
+            // Detect sections by their headers, e.g.
+            // " displacements (vx,vy,vz) for set NALL and time  0.1000000E+01"
+            let header = line_lower.trim_start();
+            if header.starts_with("displacements") {
                 current_section = "displacements";
                 tracing::info!("Found displacements section: {}", line);
                 seen_node_ids.clear(); // Reset for new displacement section
                 continue;
-            } else if line_lower.contains("total forces") && line_lower.contains("fx") {
+            } else if header.starts_with("forces") {
                 current_section = "forces";
                 tracing::info!("Found forces section: {}", line);
                 continue;
-            } else if line_lower.contains("stresses") {
+            } else if header.starts_with("stresses") {
                 current_section = "stresses";
                 tracing::info!("Found stresses section: {}", line);
                 continue;
@@ -155,56 +288,52 @@ impl CalculiXExecutor {
             }
             
             // Skip header lines that contain text
-            if line.trim().chars().next().map_or(false, |c| !c.is_numeric() && c != '-') {
+            if line.trim().chars().next().is_some_and(|c| !c.is_numeric() && c != '-') {
                 continue;
             }
 
             let parts: Vec<&str> = line.split_whitespace().collect();
             
             match current_section {
-                "displacements" => {
+                "displacements" if parts.len() >= 4 => {
                     // Format: node_id dx dy dz
-                    if parts.len() >= 4 {
-                        if let (Ok(id), Ok(dx), Ok(dy), Ok(dz)) = (
-                            parts[0].parse::<usize>(),
-                            parts[1].parse::<f64>(),
-                            parts[2].parse::<f64>(),
-                            parts[3].parse::<f64>(),
-                        ) {
-                            // Skip duplicate node IDs (CalculiX may output displacements multiple times)
-                            if seen_node_ids.contains(&id) {
-                                continue;
-                            }
-                            seen_node_ids.insert(id);
-
-                            let disp_mag = (dx*dx + dy*dy + dz*dz).sqrt();
-                            if disp_mag > results.max_displacement {
-                                results.max_displacement = disp_mag;
-                            }
+                    if let (Ok(id), Ok(dx), Ok(dy), Ok(dz)) = (
+                        parts[0].parse::<usize>(),
+                        parts[1].parse::<f64>(),
+                        parts[2].parse::<f64>(),
+                        parts[3].parse::<f64>(),
+                    ) {
+                        // Skip duplicate node IDs (CalculiX may output displacements multiple times)
+                        if seen_node_ids.contains(&id) {
+                            continue;
+                        }
+                        seen_node_ids.insert(id);
 
-                            results.displacements.push(NodeDisplacement {
-                                node_id: id - 1, // Convert from 1-based to 0-based
-                                dx, dy, dz,
-                                rx: 0.0, ry: 0.0, rz: 0.0,
-                            });
+                        let disp_mag = (dx*dx + dy*dy + dz*dz).sqrt();
+                        if disp_mag > results.max_displacement {
+                            results.max_displacement = disp_mag;
                         }
+
+                        results.displacements.push(NodeDisplacement {
+                            node_id: id - 1, // Convert from 1-based to 0-based
+                            dx, dy, dz,
+                            rx: 0.0, ry: 0.0, rz: 0.0,
+                        });
                     }
                 },
-                "forces" => {
+                "forces" if parts.len() >= 4 => {
                     // Format: node_id fx fy fz
-                    if parts.len() >= 4 {
-                        if let (Ok(id), Ok(fx), Ok(fy), Ok(fz)) = (
-                            parts[0].parse::<usize>(),
-                            parts[1].parse::<f64>(),
-                            parts[2].parse::<f64>(),
-                            parts[3].parse::<f64>(),
-                        ) {
-                            results.reactions.push(NodeReaction {
-                                node_id: id - 1, // Convert from 1-based to 0-based
-                                fx, fy, fz,
-                                mx: 0.0, my: 0.0, mz: 0.0,
-                            });
-                        }
+                    if let (Ok(id), Ok(fx), Ok(fy), Ok(fz)) = (
+                        parts[0].parse::<usize>(),
+                        parts[1].parse::<f64>(),
+                        parts[2].parse::<f64>(),
+                        parts[3].parse::<f64>(),
+                    ) {
+                        results.reactions.push(NodeReaction {
+                            node_id: id - 1, // Convert from 1-based to 0-based
+                            fx, fy, fz,
+                            mx: 0.0, my: 0.0, mz: 0.0,
+                        });
                     }
                 },
                 "stresses" => {
@@ -276,9 +405,8 @@ impl CalculiXExecutor {
                             } else {
                                 // Shell element - store for later processing
                                 element_stresses.entry(elem_id)
-                                    .or_insert_with(Vec::new)
+                                    .or_default()
                                     .push(ElementStress {
-                                        element_id: elem_id,
                                         integration_point: int_pt,
                                         von_mises: vm,
                                         sxx, syy, szz, sxy, syz, szx,
@@ -480,6 +608,208 @@ impl CalculiXExecutor {
 
         Ok(results)
     }
+
+    /// Frequencies or buckling factors and mode shapes of an eigenvalue step
+    fn parse_eigen_results(content: &str, analysis: AnalysisKind) -> AnalysisResults {
+        let mut output = EigenOutput::parse(content);
+        let mut results = AnalysisResults {
+            displacements: Vec::new(),
+            reactions: Vec::new(),
+            stresses: Vec::new(),
+            beam_forces: Vec::new(),
+            max_displacement: 0.0,
+            max_stress: 0.0,
+            max_beam_stress: 0.0,
+            modes: Vec::new(),
+            buckling_modes: Vec::new(),
+            combinations: Vec::new(),
+            increments: Vec::new(),
+        };
+        let mut shapes = output.shapes.drain(..);
+        for (i, &value) in output.values.iter().enumerate() {
+            let shape = shapes.next().unwrap_or_default();
+            if matches!(analysis, AnalysisKind::Buckling { .. }) {
+                results.buckling_modes.push(BucklingMode { factor: value, shape });
+                continue;
+            }
+            let ratio = |k: usize| match (output.effective_mass.get(i), output.total_mass) {
+                (Some(mass), Some(total)) if total[k] > 0.0 => mass[k] / total[k],
+                _ => 0.0,
+            };
+            results.modes.push(ModeResult {
+                frequency: value,
+                // Rigid-body modes have no period
+                period: if value > 0.0 { 1.0 / value } else { 0.0 },
+                mass_ratio: [ratio(0), ratio(1), ratio(2)],
+                shape,
+            });
+        }
+        tracing::info!("Parsed {} modes from .dat file", output.values.len());
+        results
+    }
+
+    /// Replace the displacements and shell stresses parsed from the .dat
+    /// file with the nodal fields of the .frd file
+    ///
+    /// Model node n is ccx node n + 1. Shell nodes that ccx only wrote
+    /// expanded take the mean of their bottom and top face nodes, which is
+    /// the mid-surface value; shell stresses are averaged over the elements
+    /// sharing a node on each face.
+    fn apply_frd_results(frd: &FrdFile, step: usize, model: &StructuralModel, results: &mut AnalysisResults) {
+        let elements: std::collections::HashMap<usize, &FrdElement> =
+            frd.elements.iter().map(|e| (e.id, e)).collect();
+
+        // Model node id -> (bottom face nodes, top face nodes)
+        let mut faces: std::collections::BTreeMap<usize, (Vec<usize>, Vec<usize>)> = std::collections::BTreeMap::new();
+        for (index, shell) in model.shells.iter().enumerate() {
+            let Some((bottom, top)) = elements.get(&(index + 1000001)).and_then(|e| e.layers()) else {
+                continue;
+            };
+            for ((&node_id, b), t) in shell.node_ids.iter().zip(bottom).zip(top) {
+                let face = faces.entry(node_id).or_default();
+                face.0.push(b);
+                face.1.push(t);
+            }
+        }
+
+        if let Some(disp) = frd.step_block("DISP", step) {
+            for node in &model.nodes {
+                let value = mean::<3>(&disp.values, &[node.id + 1]).or_else(|| {
+                    let (bottom, top) = faces.get(&node.id)?;
+                    mean::<3>(&disp.values, &[bottom.as_slice(), top.as_slice()].concat())
+                });
+                let Some([dx, dy, dz]) = value else {
+                    continue;
+                };
+                match results.displacements.iter_mut().find(|d| d.node_id == node.id) {
+                    Some(d) => (d.dx, d.dy, d.dz) = (dx, dy, dz),
+                    None => results.displacements.push(NodeDisplacement {
+                        node_id: node.id,
+                        dx, dy, dz,
+                        rx: 0.0, ry: 0.0, rz: 0.0,
+                    }),
+                }
+            }
+            results.max_displacement = results.displacements.iter()
+                .map(|d| (d.dx * d.dx + d.dy * d.dy + d.dz * d.dz).sqrt())
+                .fold(0.0, f64::max);
+        }
+
+        let Some(stress) = frd.step_block("STRESS", step) else {
+            return;
+        };
+        let von_mises = |[sxx, syy, szz, sxy, syz, szx]: [f64; 6]| {
+            (0.5 * ((sxx - syy).powi(2) + (syy - szz).powi(2) + (szz - sxx).powi(2)
+                + 6.0 * (sxy.powi(2) + syz.powi(2) + szx.powi(2)))).sqrt()
+        };
+        let mut stresses = Vec::new();
+        for (&node_id, (bottom, top)) in &faces {
+            let (Some(b), Some(t)) = (mean::<6>(&stress.values, bottom), mean::<6>(&stress.values, top)) else {
+                continue;
+            };
+            let mid: [f64; 6] = std::array::from_fn(|i| 0.5 * (b[i] + t[i]));
+            stresses.push(NodeStress {
+                node_id,
+                von_mises: von_mises(mid),
+                von_mises_top: Some(von_mises(t)),
+                von_mises_bottom: Some(von_mises(b)),
+                sxx: Some(mid[0]), syy: Some(mid[1]), szz: Some(mid[2]), sxy: Some(mid[3]),
+            });
+        }
+        if !stresses.is_empty() {
+            tracing::info!("Using .frd stresses for {} shell nodes", stresses.len());
+            results.max_stress = stresses.iter()
+                .flat_map(|s| [Some(s.von_mises), s.von_mises_top, s.von_mises_bottom])
+                .flatten()
+                .fold(0.0, f64::max);
+            results.stresses = stresses;
+        }
+    }
+}
+
+/// Eigenvalue output of a `*FREQUENCY` or `*BUCKLE` step in the .dat file
+#[derive(Debug, Default)]
+struct EigenOutput {
+    /// Frequencies (Hz) or buckling factors, by mode
+    values: Vec<f64>,
+    /// Effective modal mass in X, Y and Z, by mode
+    effective_mass: Vec<[f64; 3]>,
+    /// Total effective mass in X, Y and Z
+    total_mass: Option<[f64; 3]>,
+    /// One `*NODE PRINT` displacement table per mode
+    shapes: Vec<Vec<NodeDisplacement>>,
+}
+
+impl EigenOutput {
+    fn parse(content: &str) -> Self {
+        #[derive(PartialEq)]
+        enum Table { None, Frequencies, Factors, EffectiveMass, TotalMass, Displacements }
+
+        let mut output = EigenOutput::default();
+        let mut table = Table::None;
+        for line in content.lines() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some(first) = words.first() else {
+                continue;
+            };
+
+            // Headers are letter-spaced, e.g. "E I G E N V A L U E   O U T P U T"
+            if first.parse::<f64>().is_err() {
+                let header = words.concat().to_lowercase();
+                if header.starts_with("eigenvalueoutput") {
+                    table = Table::Frequencies;
+                } else if header.starts_with("bucklingfactoroutput") {
+                    table = Table::Factors;
+                } else if header.starts_with("effectivemodalmass") {
+                    table = Table::EffectiveMass;
+                } else if header.starts_with("totaleffectivemass") {
+                    table = Table::TotalMass;
+                } else if header.starts_with("participationfactors") {
+                    table = Table::None;
+                } else if header.starts_with("displacements") {
+                    output.shapes.push(Vec::new());
+                    table = Table::Displacements;
+                }
+                // Anything else is a column heading or a TOTAL row
+                continue;
+            }
+
+            let numbers: Vec<f64> = words.iter().map_while(|w| w.parse().ok()).collect();
+            match table {
+                // MODE NO, EIGENVALUE, RAD/TIME, CYCLES/TIME, IMAGINARY
+                Table::Frequencies if numbers.len() >= 4 => output.values.push(numbers[3]),
+                // MODE NO, BUCKLING FACTOR
+                Table::Factors if numbers.len() >= 2 => output.values.push(numbers[1]),
+                Table::EffectiveMass if numbers.len() >= 4 => {
+                    output.effective_mass.push([numbers[1], numbers[2], numbers[3]]);
+                }
+                Table::TotalMass if numbers.len() >= 3 => {
+                    output.total_mass = Some([numbers[0], numbers[1], numbers[2]]);
+                    table = Table::None;
+                }
+                Table::Displacements if numbers.len() >= 4 => {
+                    if let Some(shape) = output.shapes.last_mut() {
+                        shape.push(NodeDisplacement {
+                            node_id: (numbers[0] as usize).saturating_sub(1), // 1-based to 0-based
+                            dx: numbers[1], dy: numbers[2], dz: numbers[3],
+                            rx: 0.0, ry: 0.0, rz: 0.0,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        output
+    }
+}
+
+/// Mean of the first N values of the given nodes, skipping nodes without them
+fn mean<const N: usize>(values: &std::collections::BTreeMap<usize, Vec<f64>>, nodes: &[usize]) -> Option<[f64; N]> {
+    let rows: Vec<&Vec<f64>> = nodes.iter().filter_map(|n| values.get(n)).filter(|v| v.len() >= N).collect();
+    if rows.is_empty() {
+        return None;
+    }
+    Some(std::array::from_fn(|i| rows.iter().map(|row| row[i]).sum::<f64>() / rows.len() as f64))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -490,6 +820,216 @@ pub enum ExecutorError {
     ExecutionError(String),
     #[error("Analysis failed: {0}")]
     AnalysisFailed(String),
-    #[error("Parsing error: {0}")]
-    ParsingError(String),
+    /// ccx ran but failed, with its exit code unless it was killed
+    #[error("CalculiX exited with {status}. Check logs.")]
+    Exited { status: String, code: Option<i32> },
+    /// ccx succeeded but left no results that could be read
+    #[error("Failed to read results: {0}")]
+    ParseError(String),
+    /// A nonlinear analysis failed to converge and ccx gave up
+    #[error("CalculiX did not converge: {message}")]
+    NotConverged { message: String, code: Option<i32> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frd::FrdBlock;
+    use crate::models::{Material, Node, Shell};
+
+    /// One S4 plate whose nodes ccx wrote only expanded, as nodes 11-18
+    fn plate() -> (StructuralModel, FrdFile) {
+        let model = StructuralModel {
+            nodes: (0..4).map(|id| Node { id, x: (id % 2) as f64, y: (id / 2) as f64, z: 0.0 }).collect(),
+            beams: Vec::new(),
+            shells: vec![Shell { id: 0, node_ids: vec![0, 1, 3, 2], thickness: 0.01, is_quadratic: false }],
+            material: Material {
+                name: "Steel".to_string(),
+                elastic_modulus: 2.1e8,
+                poisson_ratio: 0.3,
+                density: 7850.0,
+                thermal_expansion: None,
+            },
+            supports: Vec::new(),
+            point_loads: Vec::new(),
+            distributed_loads: Vec::new(),
+            pressure_loads: Vec::new(),
+            temperature_loads: Vec::new(),
+            contact_pairs: Vec::new(),
+            load_combinations: Vec::new(),
+        };
+
+        let block = |name: &str, value: &dyn Fn(usize) -> Vec<f64>| FrdBlock {
+            name: name.to_string(),
+            step: 1,
+            components: Vec::new(),
+            values: (11..=18).map(|n| (n, value(n))).collect(),
+        };
+        // Bottom face 11-14 bends in compression, top face 15-18 in tension
+        let frd = FrdFile {
+            nodes: Default::default(),
+            elements: vec![FrdElement { id: 1000001, kind: 1, nodes: (11..=18).collect() }],
+            blocks: vec![
+                block("DISP", &|n| vec![0.0, 0.0, if n % 2 == 0 { -2e-3 } else { -4e-3 }]),
+                block("STRESS", &|n| {
+                    let sxx = if n <= 14 { -6e7 } else { 6e7 };
+                    vec![sxx, 1e7, 0.0, 0.0, 0.0, 0.0]
+                }),
+            ],
+        };
+        (model, frd)
+    }
+
+    #[test]
+    fn test_frd_results_replace_dat_fields() {
+        let (model, frd) = plate();
+        let mut results = AnalysisResults {
+            displacements: vec![NodeDisplacement { node_id: 0, dx: 9.0, dy: 9.0, dz: 9.0, rx: 0.0, ry: 0.0, rz: 0.0 }],
+            reactions: Vec::new(),
+            stresses: Vec::new(),
+            beam_forces: Vec::new(),
+            max_displacement: 0.0,
+            max_stress: 0.0,
+            max_beam_stress: 0.0,
+            modes: Vec::new(),
+            buckling_modes: Vec::new(),
+            combinations: Vec::new(),
+            increments: Vec::new(),
+        };
+        CalculiXExecutor::apply_frd_results(&frd, 0, &model, &mut results);
+
+        // Mid-surface displacement is the mean of the two faces
+        assert_eq!(results.displacements.len(), 4);
+        let node0 = results.displacements.iter().find(|d| d.node_id == 0).unwrap();
+        assert!((node0.dz + 4e-3).abs() < 1e-12);
+        assert!((results.max_displacement - 4e-3).abs() < 1e-12);
+
+        // Bending cancels at the mid-surface, leaving the membrane stress
+        let stress = &results.stresses[0];
+        assert_eq!(results.stresses.len(), 4);
+        assert!((stress.sxx.unwrap()).abs() < 1e-6);
+        assert!((stress.von_mises - 1e7).abs() < 1e-3);
+        assert!(stress.von_mises_top.unwrap() > 5e7 && stress.von_mises_bottom.unwrap() > 5e7);
+        assert_eq!(results.max_stress, stress.von_mises_bottom.unwrap());
+    }
+
+    #[test]
+    fn test_split_static_steps() {
+        let dat = "
+ displacements (vx,vy,vz) for set NALL and time  0.1000000E+01
+
+         1  0.000000E+00 -1.000000E-03  0.000000E+00
+
+ forces (fx,fy,fz) for set NALL and time  0.1000000E+01
+
+         1  0.000000E+00  1.000000E+04  0.000000E+00
+
+ displacements (vx,vy,vz) for set NALL and time  0.2000000E+01
+
+         1  2.000000E-03  0.000000E+00  0.000000E+00
+";
+        let steps = CalculiXExecutor::split_steps(dat);
+        assert_eq!(steps.len(), 2);
+        assert!(steps[0].contains("forces") && steps[0].contains("-1.000000E-03"));
+        assert!(!steps[1].contains("forces") && steps[1].contains("2.000000E-03"));
+        assert_eq!(CalculiXExecutor::split_steps("").len(), 1);
+
+        // Nonlinear steps print every increment; the last holds the results
+        let increments = "
+ displacements (vx,vy,vz) for set NALL and time  0.5000000E+00
+
+         1  0.000000E+00 -4.000000E-04  0.000000E+00
+
+ displacements (vx,vy,vz) for set NALL and time  0.1000000E+01
+
+         1  0.000000E+00 -9.000000E-04  0.000000E+00
+";
+        let steps = CalculiXExecutor::split_steps(increments);
+        assert_eq!(steps.len(), 1);
+        assert!(steps[0].contains("-9.000000E-04") && !steps[0].contains("-4.000000E-04"));
+    }
+
+    #[test]
+    fn test_parse_convergence() {
+        let sta = "
+SUMMARY OF JOB INFORMATION
+  STEP      INC     ATT    ITRS     TOT TIME     STEP TIME         INC TIME
+     1        1       1       3  0.250000E+00  0.250000E+00  0.2500000E+00
+     1        2       3       5  0.312500E+00  0.312500E+00  0.6250000E-01
+";
+        let increments = CalculiXExecutor::parse_sta(sta);
+        assert_eq!(increments.len(), 2);
+        assert_eq!(increments[1], Increment { step: 1, increment: 2, attempts: 3, iterations: 5, load_factor: 0.3125 });
+
+        let error = " *ERROR: too many cutbacks";
+        assert!(CalculiXExecutor::is_convergence_error(error));
+        assert!(!CalculiXExecutor::is_convergence_error(" *ERROR reading *STATIC"));
+        assert_eq!(
+            CalculiXExecutor::convergence_message(error, increments.last()),
+            "too many cutbacks after 31% of the load of step 1"
+        );
+    }
+
+    #[test]
+    fn test_parse_eigen_output() {
+        let modal = "
+     E I G E N V A L U E   O U T P U T
+
+ MODE NO    EIGENVALUE                       FREQUENCY
+                                     REAL PART            IMAGINARY PART
+                           (RAD/TIME)      (CYCLES/TIME     (RAD/TIME)
+
+      1   0.3947842E+04   0.6283185E+02   0.1000000E+02   0.0000000E+00
+      2   0.3553058E+05   0.1884956E+03   0.3000000E+02   0.0000000E+00
+
+     E F F E C T I V E   M O D A L   M A S S
+
+ MODE NO.   X-COMPONENT     Y-COMPONENT     Z-COMPONENT     X-ROTATION      Y-ROTATION      Z-ROTATION
+
+      1   0.0000000E+00   0.8000000E+02   0.0000000E+00   0.0000000E+00   0.0000000E+00   0.0000000E+00
+      2   0.5000000E+02   0.0000000E+00   0.0000000E+00   0.0000000E+00   0.0000000E+00   0.0000000E+00
+
+ TOTAL    0.5000000E+02   0.8000000E+02   0.0000000E+00   0.0000000E+00   0.0000000E+00   0.0000000E+00
+
+     T O T A L   E F F E C T I V E   M A S S
+
+ X-COMPONENT     Y-COMPONENT     Z-COMPONENT     X-ROTATION      Y-ROTATION      Z-ROTATION
+
+   0.1000000E+03   0.1000000E+03   0.0000000E+00   0.0000000E+00   0.0000000E+00   0.0000000E+00
+
+ displacements (vx,vy,vz) for set NALL and time  0.3947842E+04
+
+         1  0.000000E+00  0.000000E+00  0.000000E+00
+         2  0.000000E+00  1.000000E-01  0.000000E+00
+
+ displacements (vx,vy,vz) for set NALL and time  0.3553058E+05
+
+         1  0.000000E+00  0.000000E+00  0.000000E+00
+         2  2.000000E-01  0.000000E+00  0.000000E+00
+";
+        let results = CalculiXExecutor::parse_eigen_results(modal, AnalysisKind::Modal { num_modes: 2 });
+        assert_eq!(results.modes.len(), 2);
+        assert_eq!(results.modes[0].frequency, 10.0);
+        assert!((results.modes[1].period - 1.0 / 30.0).abs() < 1e-12);
+        assert_eq!(results.modes[0].mass_ratio, [0.0, 0.8, 0.0]);
+        assert_eq!(results.modes[1].shape[1].node_id, 1);
+        assert_eq!(results.modes[1].shape[1].dx, 0.2);
+
+        let buckling = "
+     B U C K L I N G   F A C T O R   O U T P U T
+
+ MODE NO       BUCKLING
+                FACTOR
+
+      1   0.4250000E+01
+
+ displacements (vx,vy,vz) for set NALL and time  0.4250000E+01
+
+         2  0.000000E+00  0.000000E+00  1.000000E+00
+";
+        let results = CalculiXExecutor::parse_eigen_results(buckling, AnalysisKind::Buckling { num_modes: 1 });
+        assert!(results.modes.is_empty());
+        assert_eq!(results.buckling_modes[0].factor, 4.25);
+        assert_eq!(results.buckling_modes[0].shape[0].dz, 1.0);
+    }
 }
@@ -0,0 +1,308 @@
+//! Reader for CalculiX `.frd` result files
+//!
+//! The `.frd` file holds the field results requested with `*NODE FILE` and
+//! `*EL FILE` in fixed-width ASCII blocks: the node coordinates (`2C`), the
+//! element definitions (`3C`) and one `100C` block per result and increment,
+//! each with a `-4` name record, `-5` component records and one `-1` record
+//! per node. ccx writes 1D and 2D elements expanded into 3D elements, so
+//! shell stresses come out at the nodes of the bottom and top faces; see
+//! [`FrdElement::layers`].
+
+use std::collections::BTreeMap;
+
+/// Width of a value field
+const VALUE_WIDTH: usize = 12;
+
+/// Contents of an `.frd` file
+#[derive(Debug, Default)]
+pub struct FrdFile {
+    /// Node coordinates by node number
+    pub nodes: BTreeMap<usize, [f64; 3]>,
+    pub elements: Vec<FrdElement>,
+    /// Result blocks in file order, one per result and increment
+    pub blocks: Vec<FrdBlock>,
+}
+
+/// An element of the (expanded) mesh
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrdElement {
+    pub id: usize,
+    /// frd element type, e.g. 1 = 8-node brick, 4 = 20-node brick
+    pub kind: usize,
+    pub nodes: Vec<usize>,
+}
+
+/// Nodal values of one result, e.g. `DISP` or `STRESS`
+#[derive(Debug, Clone, Default)]
+pub struct FrdBlock {
+    pub name: String,
+    /// Step of the analysis the values belong to, from 1
+    pub step: usize,
+    /// Component names, e.g. `D1`, `D2`, `D3`
+    pub components: Vec<String>,
+    pub values: BTreeMap<usize, Vec<f64>>,
+}
+
+impl FrdFile {
+    pub fn parse(content: &str) -> Result<Self, FrdError> {
+        let mut frd = FrdFile::default();
+        let mut id_width = 10;
+        let mut section = Section::None;
+        let mut step = 1;
+
+        for (index, line) in content.lines().enumerate() {
+            let line_no = index + 1;
+            let record = line.get(..3).unwrap_or(line).trim();
+            match record {
+                "-1" | "-2" => {
+                    let rest = line.get(3..).unwrap_or("");
+                    match &mut section {
+                        Section::Nodes => {
+                            let (id, values) = split_id(rest, id_width, line_no)?;
+                            let xyz = values_of(values, line_no)?;
+                            if xyz.len() < 3 {
+                                return Err(FrdError::new(line_no, "node record needs three coordinates"));
+                            }
+                            frd.nodes.insert(id, [xyz[0], xyz[1], xyz[2]]);
+                        }
+                        Section::Elements if record == "-1" => {
+                            let (id, rest) = split_id(rest, id_width, line_no)?;
+                            let kind = integers(rest, 5, line_no)?.first().copied()
+                                .ok_or_else(|| FrdError::new(line_no, "element record without a type"))?;
+                            frd.elements.push(FrdElement { id, kind, nodes: Vec::new() });
+                        }
+                        Section::Elements => {
+                            let element = frd.elements.last_mut()
+                                .ok_or_else(|| FrdError::new(line_no, "element nodes before an element"))?;
+                            element.nodes.extend(integers(rest, id_width, line_no)?);
+                        }
+                        Section::Results { last } if record == "-1" => {
+                            let block = frd.blocks.last_mut().expect("results section has a block");
+                            let (id, values) = split_id(rest, id_width, line_no)?;
+                            block.values.insert(id, values_of(values, line_no)?);
+                            *last = Some(id);
+                        }
+                        Section::Results { last } => {
+                            // Continuation of the previous node's values
+                            let block = frd.blocks.last_mut().expect("results section has a block");
+                            let values = values_of(rest.get(id_width..).unwrap_or(""), line_no)?;
+                            if let Some(row) = last.and_then(|id| block.values.get_mut(&id)) {
+                                row.extend(values);
+                            }
+                        }
+                        Section::None => {}
+                    }
+                }
+                "-3" => section = Section::None,
+                "-4" => {
+                    let name = line.get(3..).unwrap_or("").split_whitespace().next().unwrap_or("");
+                    frd.blocks.push(FrdBlock { name: name.to_string(), step, ..FrdBlock::default() });
+                    section = Section::Results { last: None };
+                }
+                "-5" => {
+                    if let (Some(block), Some(name)) = (frd.blocks.last_mut(), line.get(3..).and_then(|r| r.split_whitespace().next())) {
+                        // ccx lists an `ALL` pseudo component after the vector ones
+                        if name != "ALL" {
+                            block.components.push(name.to_string());
+                        }
+                    }
+                }
+                _ => {
+                    // Headers: `2C`/`3C` open the node and element blocks and
+                    // end with the format flag, 0 = short (I5) ids, 1 = long (I10)
+                    let header = line.trim_start();
+                    // `1PSTEP` records precede each increment's results and
+                    // end with the step number
+                    if header.starts_with("1PSTEP") {
+                        step = header.split_whitespace().last().and_then(|s| s.parse().ok()).unwrap_or(step);
+                    }
+                    if header.starts_with("2C") || header.starts_with("3C") {
+                        id_width = match header.split_whitespace().last() {
+                            Some("0") => 5,
+                            _ => 10,
+                        };
+                        section = if header.starts_with("2C") { Section::Nodes } else { Section::Elements };
+                    }
+                }
+            }
+        }
+        Ok(frd)
+    }
+
+    /// The last block of a result in a step, counting steps from 0, i.e.
+    /// its values at the end of that step
+    pub fn step_block(&self, name: &str, step: usize) -> Option<&FrdBlock> {
+        self.blocks.iter().rev().find(|b| b.name == name && b.step == step + 1)
+    }
+}
+
+impl FrdElement {
+    /// Nodes of the bottom and top faces of an expanded shell, in the order
+    /// of the shell's own nodes (corners, then midside nodes)
+    ///
+    /// ccx expands a shell along its normal into a wedge or brick whose
+    /// first face lies at -t/2 and second at +t/2; quadratic bricks list the
+    /// corners of both faces, then the midside nodes of both faces, then the
+    /// through-thickness midside nodes.
+    pub fn layers(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        // (corner nodes per face, midside nodes per face)
+        let (corners, midside) = match self.kind {
+            1 => (4, 0),  // 8-node brick, from S4
+            2 => (3, 0),  // 6-node wedge, from S3
+            4 => (4, 4),  // 20-node brick, from S8
+            5 => (3, 3),  // 15-node wedge, from S6
+            _ => return None,
+        };
+        if self.nodes.len() < 2 * (corners + midside) {
+            return None;
+        }
+        let n = &self.nodes;
+        let mid = 2 * corners;
+        let bottom = n[..corners].iter().chain(&n[mid..mid + midside]).copied().collect();
+        let top = n[corners..mid].iter().chain(&n[mid + midside..mid + 2 * midside]).copied().collect();
+        Some((bottom, top))
+    }
+}
+
+enum Section {
+    None,
+    Nodes,
+    Elements,
+    /// Result values; `last` is the node whose values a `-2` record continues
+    Results { last: Option<usize> },
+}
+
+/// Split a record into its leading id and the rest
+fn split_id(rest: &str, width: usize, line_no: usize) -> Result<(usize, &str), FrdError> {
+    let id = rest.get(..width).unwrap_or(rest).trim();
+    let id = id.parse().map_err(|_| FrdError::new(line_no, &format!("invalid id '{}'", id)))?;
+    Ok((id, rest.get(width..).unwrap_or("")))
+}
+
+/// Fixed-width values; adjacent fields can run together, e.g. `1.0E+00-2.0E+00`
+fn values_of(text: &str, line_no: usize) -> Result<Vec<f64>, FrdError> {
+    fields(text, VALUE_WIDTH)
+        .map(|field| field.parse().map_err(|_| FrdError::new(line_no, &format!("invalid value '{}'", field))))
+        .collect()
+}
+
+fn integers(text: &str, width: usize, line_no: usize) -> Result<Vec<usize>, FrdError> {
+    fields(text, width)
+        .map(|field| field.parse().map_err(|_| FrdError::new(line_no, &format!("invalid integer '{}'", field))))
+        .collect()
+}
+
+fn fields(text: &str, width: usize) -> impl Iterator<Item = &str> {
+    let text = text.trim_end();
+    (0..text.len()).step_by(width)
+        .map(move |start| text.get(start..(start + width).min(text.len())).unwrap_or("").trim())
+        .filter(|field| !field.is_empty())
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid .frd file at line {line}: {message}")]
+pub struct FrdError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl FrdError {
+    fn new(line: usize, message: &str) -> Self {
+        Self { line, message: message.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One S4 shell expanded to an 8-node brick, with two increments of results
+    const SAMPLE: &str = "\
+    1C
+    2C                             8                                     1
+ -1         1 0.00000E+00 0.00000E+00-5.00000E-03
+ -1         2 1.00000E+00 0.00000E+00-5.00000E-03
+ -1         3 1.00000E+00 1.00000E+00-5.00000E-03
+ -1         4 0.00000E+00 1.00000E+00-5.00000E-03
+ -1         5 0.00000E+00 0.00000E+00 5.00000E-03
+ -1         6 1.00000E+00 0.00000E+00 5.00000E-03
+ -1         7 1.00000E+00 1.00000E+00 5.00000E-03
+ -1         8 0.00000E+00 1.00000E+00 5.00000E-03
+ -3
+    3C                             1                                     1
+ -1   1000001    1    0    1
+ -2         1         2         3         4         5         6         7         8
+ -3
+  100CL  101 5.000000000         8                     0    1           1
+ -4  DISP        4    1
+ -5  D1          1    2    1    0
+ -5  D2          1    2    2    0
+ -5  D3          1    2    3    0
+ -5  ALL         1    2    0    0    1ALL
+ -1         1 0.00000E+00 0.00000E+00-1.00000E-04
+ -3
+  100CL  101 1.000000000         8                     0    1           1
+ -4  DISP        4    1
+ -5  D1          1    2    1    0
+ -5  D2          1    2    2    0
+ -5  D3          1    2    3    0
+ -5  ALL         1    2    0    0    1ALL
+ -1         1 0.00000E+00 0.00000E+00-2.00000E-04
+ -1         5 1.00000E-06 0.00000E+00-2.00000E-04
+ -3
+  100CL  101 1.000000000         8                     0    1           1
+ -4  STRESS      6    1
+ -5  SXX         1    4    1    1
+ -5  SYY         1    4    2    2
+ -5  SZZ         1    4    3    3
+ -5  SXY         1    4    1    2
+ -5  SYZ         1    4    2    3
+ -5  SZX         1    4    3    1
+ -1         1-1.20000E+07 3.00000E+06 0.00000E+00 1.00000E+05 0.00000E+00 0.00000E+00
+ -3
+9999
+";
+
+    #[test]
+    fn test_parse_frd() {
+        let frd = FrdFile::parse(SAMPLE).unwrap();
+        assert_eq!(frd.nodes.len(), 8);
+        assert_eq!(frd.nodes[&5], [0.0, 0.0, 5e-3]);
+        assert_eq!(frd.elements[0].id, 1000001);
+        assert_eq!(frd.elements[0].nodes, (1..=8).collect::<Vec<_>>());
+
+        // The last increment wins
+        let disp = frd.step_block("DISP", 0).unwrap();
+        assert_eq!(disp.components, ["D1", "D2", "D3"]);
+        assert_eq!(disp.values[&1], [0.0, 0.0, -2e-4]);
+
+        // Values that run together are split by width
+        let stress = frd.step_block("STRESS", 0).unwrap();
+        assert_eq!(stress.values[&1], [-1.2e7, 3e6, 0.0, 1e5, 0.0, 0.0]);
+        assert!(frd.step_block("FORC", 0).is_none());
+
+        // Later steps follow a `1PSTEP` record
+        let second = SAMPLE.replace("9999\n", "    1PSTEP                         3           1           2\n \
+            -4  DISP        4    1\n -5  D1          1    2    1    0\n -1         1 0.00000E+00-3.00000E-04 0.00000E+00\n -3\n9999\n");
+        let frd = FrdFile::parse(&second).unwrap();
+        assert_eq!(frd.step_block("DISP", 0).unwrap().values[&1], [0.0, 0.0, -2e-4]);
+        assert_eq!(frd.step_block("DISP", 1).unwrap().values[&1], [0.0, -3e-4, 0.0]);
+        assert!(frd.step_block("STRESS", 1).is_none());
+
+        assert_eq!(FrdFile::parse(" -1         x").unwrap().nodes.len(), 0);
+        assert!(FrdFile::parse("    2C  1\n -1         1 abc").is_err());
+    }
+
+    #[test]
+    fn test_shell_layers() {
+        let brick = FrdElement { id: 1, kind: 1, nodes: (1..=8).collect() };
+        assert_eq!(brick.layers(), Some((vec![1, 2, 3, 4], vec![5, 6, 7, 8])));
+
+        let quadratic = FrdElement { id: 1, kind: 4, nodes: (1..=20).collect() };
+        let (bottom, top) = quadratic.layers().unwrap();
+        assert_eq!(bottom, [1, 2, 3, 4, 9, 10, 11, 12]);
+        assert_eq!(top, [5, 6, 7, 8, 13, 14, 15, 16]);
+
+        assert_eq!(FrdElement { id: 1, kind: 11, nodes: vec![1, 2] }.layers(), None);
+    }
+}
@@ -1,19 +1,28 @@
-use crate::models::{StructuralModel, SupportType};
+use crate::models::{
+    AnalysisKind, Beam, BeamSection, ContactPair, LoadCombination, SectionType, ShellFace, StructuralModel, SupportType,
+};
 
 pub struct CalculiXGenerator;
 
-/// Beam orientation category - used to group beams with same orientation vector
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-enum BeamOrientationCategory {
-    Vertical,       // Mostly along Y axis (columns)
-    HorizontalX,    // Mostly along X axis
-    HorizontalZ,    // Mostly along Z axis
-    Other,          // Diagonal or other
+/// Beams written as one element set under one beam section
+struct BeamGroup<'a> {
+    section: &'a BeamSection,
+    /// Section 1-direction, the native model's local y
+    orientation: [f64; 3],
+    element_type: &'static str,
+    beams: Vec<&'a Beam>,
+}
+
+/// Beam tangent, section 1- and 2-directions, and length
+struct BeamAxes {
+    tangent: [f64; 3],
+    dir1: [f64; 3],
+    dir2: [f64; 3],
+    length: f64,
 }
 
 impl CalculiXGenerator {
     const KILO_TO_BASE: f64 = 1000.0;
-    const GRAVITY: f64 = 9.81;
 
     pub fn new() -> Self {
         Self
@@ -81,82 +90,9 @@ impl CalculiXGenerator {
         }
     }
 
-    /// Get orientation category for a beam based on its direction
-    fn get_beam_orientation_category(model: &StructuralModel, beam: &crate::models::Beam) -> BeamOrientationCategory {
-        if beam.node_ids.len() < 2 {
-            return BeamOrientationCategory::HorizontalX;
-        }
-        
-        let n0 = &model.nodes[beam.node_ids[0]];
-        let n1 = &model.nodes[beam.node_ids[1]];
-
-        let dx = n1.x - n0.x;
-        let dy = n1.y - n0.y;
-        let dz = n1.z - n0.z;
-
-        let len = (dx*dx + dy*dy + dz*dz).sqrt();
-        if len < 1e-10 {
-            return BeamOrientationCategory::HorizontalX;
-        }
-
-        let abs_x = dx.abs() / len;
-        let abs_y = dy.abs() / len;
-        let abs_z = dz.abs() / len;
-
-        // Threshold for "mostly" aligned (cos(30°) ≈ 0.866)
-        let threshold = 0.7;
-
-        if abs_y > threshold {
-            BeamOrientationCategory::Vertical
-        } else if abs_x > abs_z && abs_x > threshold {
-            BeamOrientationCategory::HorizontalX
-        } else if abs_z > threshold {
-            BeamOrientationCategory::HorizontalZ
-        } else {
-            BeamOrientationCategory::Other
-        }
-    }
-
-    /// Get the orientation vector (local y-axis) for a beam category
-    fn get_orientation_for_category(category: BeamOrientationCategory) -> (f64, f64, f64) {
-        match category {
-            // Vertical beams: use global X as local y (cross product gives Z as local z)
-            BeamOrientationCategory::Vertical => (1.0, 0.0, 0.0),
-            // Horizontal X beams: use global Y (up) as local y
-            BeamOrientationCategory::HorizontalX => (0.0, 1.0, 0.0),
-            // Horizontal Z beams: use global Y (up) as local y
-            BeamOrientationCategory::HorizontalZ => (0.0, 1.0, 0.0),
-            // Other diagonal beams: default to Y up
-            BeamOrientationCategory::Other => (0.0, 1.0, 0.0),
-        }
-    }
-
-    /// Get element set name suffix for a beam category
-    fn get_elset_suffix(category: BeamOrientationCategory) -> &'static str {
-        match category {
-            BeamOrientationCategory::Vertical => "_VERT",
-            BeamOrientationCategory::HorizontalX => "_HORX",
-            BeamOrientationCategory::HorizontalZ => "_HORZ",
-            BeamOrientationCategory::Other => "_DIAG",
-        }
-    }
-
-    /// Compute beam orientation vector (legacy - for single orientation)
-    /// For CalculiX, the direction vector must NOT be parallel to the beam axis
-    fn compute_beam_orientation(model: &StructuralModel) -> (f64, f64, f64) {
-        if model.beams.is_empty() {
-            return (0.0, 1.0, 0.0);
-        }
-
-        if let Some(beam) = model.beams.first() {
-            let cat = Self::get_beam_orientation_category(model, beam);
-            Self::get_orientation_for_category(cat)
-        } else {
-            (0.0, 1.0, 0.0)
-        }
-    }
-
-    pub fn generate_inp_file(&self, model: &StructuralModel) -> Result<String, GeneratorError> {
+    /// Input file for the given analysis: a `*STATIC` step per load
+    /// combination, or a `*FREQUENCY` or `*BUCKLE` step, on the same model data
+    pub fn generate_inp_file(&self, model: &StructuralModel, analysis: AnalysisKind) -> Result<String, GeneratorError> {
         let mut inp = String::new();
 
         if model.nodes.is_empty() {
@@ -175,58 +111,31 @@ impl CalculiXGenerator {
         }
 
         // 3. Elements (Beams)
-        // Using B32 (3-node quadratic beam) for better accuracy
-        // B32 requires 3 nodes: start, end, and midpoint
-        // 3. Elements (Beams)
-        // Group beams by orientation to provide correct local y-axis for each group
-        // This is essential for 3D frames with both horizontal beams and vertical columns
-        if !model.beams.is_empty() {
-            use std::collections::HashMap;
-            
-            // Categorize beams by orientation
-            let mut beam_categories: HashMap<&'static str, Vec<&crate::models::Beam>> = HashMap::new();
-            
-            for beam in &model.beams {
-                let cat = Self::get_beam_orientation_category(model, beam);
-                let suffix = Self::get_elset_suffix(cat);
-                beam_categories.entry(suffix).or_insert_with(Vec::new).push(beam);
-            }
-            
-            // Check if beams have midpoint nodes (quadratic)
-            let has_midpoint = model.beams.first().map_or(false, |b| b.node_ids.len() >= 3);
-            let element_type = if has_midpoint { "B32" } else { "B31" };
-            
-            // Generate element definitions for each category
-            for (suffix, beams) in &beam_categories {
-                let elset_name = format!("EBEAMS{}", suffix);
-                inp.push_str(&format!("*ELEMENT, TYPE={}, ELSET={}\n", element_type, elset_name));
-                
-                for beam in beams {
-                    if has_midpoint && beam.node_ids.len() >= 3 {
-                        inp.push_str(&format!("{}, {}, {}, {}\n", 
-                            beam.id + 1, 
-                            beam.node_ids[0] + 1, 
-                            beam.node_ids[1] + 1, 
-                            beam.node_ids[2] + 1));
-                    } else if beam.node_ids.len() >= 2 {
-                        inp.push_str(&format!("{}, {}, {}\n", 
-                            beam.id + 1, 
-                            beam.node_ids[0] + 1, 
-                            beam.node_ids[1] + 1));
-                    }
-                }
+        // One element set per section and orientation, so every beam gets
+        // its own section and local axes
+        let beam_groups = Self::beam_groups(model);
+        if beam_groups.iter().any(|g| g.element_type == "U1") {
+            // Linear beam for general sections, with three translations and
+            // three rotations per node
+            inp.push_str("*USER ELEMENT, TYPE=U1, INTEGRATION POINTS=2, MAXDOF=6, NODES=2\n");
+            inp.push_str("1, 2, 3, 4, 5, 6\n");
+        }
+        for (index, group) in beam_groups.iter().enumerate() {
+            inp.push_str(&format!("*ELEMENT, TYPE={}, ELSET=EBEAMS_{}\n", group.element_type, index + 1));
+            for beam in &group.beams {
+                let nodes = if group.element_type.starts_with("B32") { 3 } else { 2 };
+                let ids: Vec<String> = std::iter::once(beam.id + 1)
+                    .chain(beam.node_ids[..nodes].iter().map(|id| id + 1))
+                    .map(|id| id.to_string())
+                    .collect();
+                inp.push_str(&format!("{}\n", ids.join(", ")));
             }
-            
-            // Create combined EBEAMS set for stress output
+        }
+        if !beam_groups.is_empty() {
+            // Combined EBEAMS set for stress output
             inp.push_str("*ELSET, ELSET=EBEAMS\n");
-            let suffixes: Vec<_> = beam_categories.keys().collect();
-            for (i, suffix) in suffixes.iter().enumerate() {
-                if i < suffixes.len() - 1 {
-                    inp.push_str(&format!("EBEAMS{},\n", suffix));
-                } else {
-                    inp.push_str(&format!("EBEAMS{}\n", suffix));
-                }
-            }
+            let names: Vec<String> = (1..=beam_groups.len()).map(|i| format!("EBEAMS_{}", i)).collect();
+            inp.push_str(&format!("{}\n", names.join(",\n")));
         }
 
         // 4. Elements (Shells/Plates)
@@ -313,52 +222,17 @@ impl CalculiXGenerator {
         inp.push_str(&format!("{:.4}, {:.4}\n", elastic_modulus, model.material.poisson_ratio));
         inp.push_str("*DENSITY\n");
         inp.push_str(&format!("{:.4}\n", density));
+        if let Some(alpha) = model.material.thermal_expansion {
+            // Strain-free at the initial temperature of 0 set below
+            inp.push_str("*EXPANSION, ZERO=0.\n");
+            inp.push_str(&format!("{:e}\n", alpha));
+        }
 
         // 6. Sections
-        // Beam Sections - one per orientation category with correct local y-axis
-        if !model.beams.is_empty() {
-            use std::collections::HashMap;
-            
-            // Get beam categories that exist in the model
-            let mut categories: HashMap<&'static str, BeamOrientationCategory> = HashMap::new();
-            for beam in &model.beams {
-                let cat = Self::get_beam_orientation_category(model, beam);
-                let suffix = Self::get_elset_suffix(cat);
-                categories.insert(suffix, cat);
-            }
-            
-            // Get section properties from first beam
-            let first_beam = model.beams.first().unwrap();
-            
-            // Generate a beam section for each category with its specific orientation
-            for (suffix, cat) in &categories {
-                let elset_name = format!("EBEAMS{}", suffix);
-                let orientation = Self::get_orientation_for_category(*cat);
-                
-                match first_beam.section.section_type {
-                    crate::models::SectionType::IBeam => {
-                        inp.push_str(&format!("*BEAM SECTION, ELSET={}, MATERIAL=MATERIAL1, SECTION=BOX\n", elset_name));
-                        let height = first_beam.section.height;
-                        let width = first_beam.section.width;
-                        let tf = first_beam.section.flange_thickness.unwrap_or(0.0108);
-                        let tw = first_beam.section.web_thickness.unwrap_or(0.0059);
-                        inp.push_str(&format!("{:.6}, {:.6}, {:.6}, {:.6}, {:.6}, {:.6}\n", 
-                            height, width, tf, tw, tf, tw));
-                    },
-                    crate::models::SectionType::Circular => {
-                        inp.push_str(&format!("*BEAM SECTION, ELSET={}, MATERIAL=MATERIAL1, SECTION=CIRC\n", elset_name));
-                        let radius = first_beam.section.width / 2.0;
-                        inp.push_str(&format!("{:.6}\n", radius));
-                    },
-                    crate::models::SectionType::Rectangular => {
-                        inp.push_str(&format!("*BEAM SECTION, ELSET={}, MATERIAL=MATERIAL1, SECTION=RECT\n", elset_name));
-                        inp.push_str(&format!("{:.6}, {:.6}\n", first_beam.section.height, first_beam.section.width));
-                    },
-                }
-                // Orientation vector for this beam category
-                inp.push_str(&format!("{:.1}, {:.1}, {:.1}\n", 
-                    orientation.0, orientation.1, orientation.2));
-            }
+        // Beam sections, each with its 1-direction so ccx sets up the same
+        // local axes as the native model
+        for (index, group) in beam_groups.iter().enumerate() {
+            Self::write_beam_section(&mut inp, &format!("EBEAMS_{}", index + 1), group);
         }
 
         // Shell Section
@@ -404,8 +278,9 @@ impl CalculiXGenerator {
                             
                             // Prevent rigid body motion with minimal additional constraints
                             if idx == 0 {
-                                // First support: add one in-plane constraint (prevent translation in X)
-                                let in_plane_dof1 = if normal_dof == 2 { 1 } else { 1 };
+                                // First support: add one in-plane constraint (prevent translation in X,
+                                // which is in-plane for every plate except an X-normal one)
+                                let in_plane_dof1 = 1;
                                 inp.push_str(&format!("{}, {}, {}, 0.0\n", support.node_id + 1, in_plane_dof1, in_plane_dof1));
                             } else if idx == 1 {
                                 // Second support: add perpendicular in-plane constraint (prevent rotation about normal)
@@ -434,91 +309,319 @@ impl CalculiXGenerator {
             }
         }
 
+        // Contact pairs: slave nodes bearing on a face of master shells
+        for (index, pair) in model.contact_pairs.iter().enumerate() {
+            Self::write_contact_pair(&mut inp, index + 1, pair);
+        }
+
+        // Temperature loads are changes from a stress-free state at 0
+        if !model.temperature_loads.is_empty() {
+            inp.push_str("*INITIAL CONDITIONS, TYPE=TEMPERATURE\n");
+            inp.push_str("NALL, 0.0\n");
+        }
+
         // 8. Steps and Loads
-        inp.push_str("*STEP\n");
-        inp.push_str("*STATIC\n");
-        
-        // Point Loads
-        if !model.point_loads.is_empty() {
-            inp.push_str("*CLOAD\n");
-            for load in &model.point_loads {
-                let fx = Self::to_newtons(load.fx);
-                let fy = Self::to_newtons(load.fy);
-                let fz = Self::to_newtons(load.fz);
-
-                if fx.abs() > 1e-6 { inp.push_str(&format!("{}, 1, {:.4}\n", load.node_id + 1, fx)); }
-                if fy.abs() > 1e-6 { inp.push_str(&format!("{}, 2, {:.4}\n", load.node_id + 1, fy)); }
-                if fz.abs() > 1e-6 { inp.push_str(&format!("{}, 3, {:.4}\n", load.node_id + 1, fz)); }
+        match analysis {
+            AnalysisKind::Static | AnalysisKind::Nonlinear { .. } => {
+                // One step per load combination; later steps replace the loads
+                // of the one before, and being linear they do not depend on it.
+                // Nonlinear analyses take a single combination.
+                for (step, combination) in model.combinations().iter().enumerate() {
+                    inp.push_str(&format!("** Load combination: {}\n", combination.name));
+                    if let AnalysisKind::Nonlinear { increments, max_increments } = analysis {
+                        // Initial, total, minimum and maximum increment; the
+                        // increment never grows past the initial one
+                        let initial = 1.0 / increments.max(1) as f64;
+                        inp.push_str(&format!("*STEP, NLGEOM, INC={}\n", max_increments));
+                        inp.push_str("*STATIC\n");
+                        inp.push_str(&format!("{:e}, 1.0, {:e}, {:e}\n", initial, initial * 1e-3, initial));
+                    } else {
+                        inp.push_str("*STEP\n");
+                        inp.push_str("*STATIC\n");
+                    }
+                    Self::write_loads(model, combination, step > 0, &mut inp);
+                    Self::write_static_outputs(model, &mut inp);
+                    inp.push_str("*END STEP\n");
+                }
+            }
+            AnalysisKind::Modal { num_modes } => {
+                // Mass comes from *DENSITY; loads play no part in the eigenproblem
+                inp.push_str("*STEP\n");
+                inp.push_str("*FREQUENCY\n");
+                inp.push_str(&format!("{}\n", num_modes));
+                Self::write_mode_shape_outputs(&mut inp);
+                inp.push_str("*END STEP\n");
+            }
+            AnalysisKind::Buckling { num_modes } => {
+                // Buckling factors multiply the loads of this step, those of
+                // the first (and only) combination
+                inp.push_str("*STEP\n");
+                inp.push_str("*BUCKLE\n");
+                inp.push_str(&format!("{}\n", num_modes));
+                if let Some(combination) = model.combinations().first() {
+                    Self::write_loads(model, combination, false, &mut inp);
+                }
+                Self::write_mode_shape_outputs(&mut inp);
+                inp.push_str("*END STEP\n");
+            }
+        }
+
+        Ok(inp)
+    }
+
+    /// Local y-axis of a beam in the native model, which becomes its
+    /// section's 1-direction
+    fn beam_orientation(model: &StructuralModel, beam: &Beam) -> Option<[f64; 3]> {
+        let n0 = model.nodes.get(*beam.node_ids.first()?)?;
+        let n1 = model.nodes.get(*beam.node_ids.get(1)?)?;
+        let (i, j) = ([n0.x, n0.y, n0.z], [n1.x, n1.y, n1.z]);
+        let length = ((j[0] - i[0]).powi(2) + (j[1] - i[1]).powi(2) + (j[2] - i[2]).powi(2)).sqrt();
+        if length < 1e-10 {
+            return None;
+        }
+        let t = fea_solver::math::member_transformation_matrix(&i, &j, beam.rotation.to_radians());
+        let r = fea_solver::math::extract_rotation_matrix(&t);
+        Some([r[(1, 0)], r[(1, 1)], r[(1, 2)]])
+    }
+
+    /// Beams grouped by section, orientation and element type, in order of
+    /// first appearance
+    fn beam_groups(model: &StructuralModel) -> Vec<BeamGroup<'_>> {
+        let mut groups: Vec<BeamGroup> = Vec::new();
+        for beam in &model.beams {
+            let Some(orientation) = Self::beam_orientation(model, beam) else {
+                tracing::warn!("Beam {} has no length or too few nodes, skipping", beam.id);
+                continue;
+            };
+            let quadratic = beam.node_ids.len() >= 3;
+            let element_type = match beam.section.section_type {
+                SectionType::Rectangular | SectionType::Circular => if quadratic { "B32" } else { "B31" },
+                // Hollow sections are integrated over their walls, which ccx
+                // does for reduced-integration beams only
+                SectionType::Box | SectionType::Pipe => if quadratic { "B32R" } else { "B31R" },
+                // Profiles ccx cannot expand into solids take the linear user
+                // beam, which works from the section properties directly
+                SectionType::IBeam | SectionType::General => "U1",
+            };
+            let same = |g: &BeamGroup| {
+                g.section == &beam.section
+                    && g.element_type == element_type
+                    && g.orientation.iter().zip(orientation).all(|(a, b)| (a - b).abs() < 1e-9)
+            };
+            match groups.iter_mut().find(|g| same(g)) {
+                Some(group) => group.beams.push(beam),
+                None => groups.push(BeamGroup { section: &beam.section, orientation, element_type, beams: vec![beam] }),
+            }
+        }
+        groups
+    }
+
+    /// Area, I11, I22 and torsion constant of sections given by properties
+    ///
+    /// The 1-direction is local y, so I11 is the native iy.
+    fn general_properties(section: &BeamSection) -> [f64; 4] {
+        match section.section_type {
+            SectionType::IBeam => {
+                let tf = section.flange_thickness.unwrap_or(0.0108);
+                let tw = section.web_thickness.unwrap_or(0.0059);
+                // Height runs along local y, so the strong axis is local z
+                let s = fea_solver::prelude::Section::wide_flange(section.height, section.width, tf, tw);
+                [s.a, s.iz, s.iy, s.j]
+            }
+            _ => [section.area, section.iy, section.iz, section.j].map(Option::unwrap_or_default),
+        }
+    }
+
+    fn write_beam_section(inp: &mut String, elset: &str, group: &BeamGroup) {
+        let section = group.section;
+        let t = section.wall_thickness.unwrap_or_default();
+        let (keyword, kind, data) = match section.section_type {
+            // Dimensions along the 1- then the 2-direction
+            SectionType::Rectangular => ("BEAM SECTION", "RECT", vec![section.height, section.width]),
+            SectionType::Circular => ("BEAM SECTION", "CIRC", vec![section.width / 2.0]),
+            SectionType::Pipe => ("BEAM SECTION", "PIPE", vec![section.width / 2.0, t]),
+            SectionType::Box => ("BEAM SECTION", "BOX", vec![section.height, section.width, t, t, t, t]),
+            SectionType::IBeam | SectionType::General => {
+                let [a, i11, i22, it] = Self::general_properties(section);
+                ("BEAM GENERAL SECTION", "GENERAL", vec![a, i11, 0.0, i22, it])
+            }
+        };
+        inp.push_str(&format!("*{}, ELSET={}, MATERIAL=MATERIAL1, SECTION={}\n", keyword, elset, kind));
+        let data: Vec<String> = data.iter().map(|v| format!("{:.6e}", v)).collect();
+        inp.push_str(&format!("{}\n", data.join(", ")));
+        let [x, y, z] = group.orientation;
+        inp.push_str(&format!("{:.6}, {:.6}, {:.6}\n", x, y, z));
+    }
+
+    /// Local axes of a beam as ccx sets them up
+    ///
+    /// ccx takes the 2-direction as t × n1 for the orientation vector n1 of
+    /// the beam's section, which is square to the beam already.
+    fn beam_axes(model: &StructuralModel, beam: &Beam) -> Option<BeamAxes> {
+        let n0 = model.nodes.get(*beam.node_ids.first()?)?;
+        let n1 = model.nodes.get(*beam.node_ids.get(1)?)?;
+        let d = [n1.x - n0.x, n1.y - n0.y, n1.z - n0.z];
+        let length = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        let dir1 = Self::beam_orientation(model, beam)?;
+        let t = d.map(|c| c / length);
+        let dir2 = [t[1] * dir1[2] - t[2] * dir1[1], t[2] * dir1[0] - t[0] * dir1[2], t[0] * dir1[1] - t[1] * dir1[0]];
+        Some(BeamAxes { tangent: t, dir1, dir2, length })
+    }
+
+    /// Loads of a combination, each scaled by the factor of its load case;
+    /// `replace` clears the loads of earlier steps
+    fn write_loads(model: &StructuralModel, combination: &LoadCombination, replace: bool, inp: &mut String) {
+        use std::collections::{BTreeMap, HashMap};
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+
+        // Nodal forces (N) by 0-based node id: point loads plus the axial
+        // part of member loads, which *DLOAD cannot apply to beams
+        let mut nodal: BTreeMap<usize, [f64; 3]> = BTreeMap::new();
+        for load in &model.point_loads {
+            let factor = combination.factor(&load.load_case);
+            let force = nodal.entry(load.node_id).or_default();
+            for (f, value) in force.iter_mut().zip([load.fx, load.fy, load.fz]) {
+                *f += Self::to_newtons(value) * factor;
             }
         }
 
         // Distributed Loads on Beams (UDL)
-        if !model.distributed_loads.is_empty() {
-            inp.push_str("*DLOAD\n");
-            for load in &model.distributed_loads {
-                for elem_id in &load.element_ids {
-                    // Beam element ID (1-based)
-                    let beam_id = elem_id + 1;
-                    
-                    match &load.load_type {
-                        crate::models::LoadType::Uniform { value, direction } => {
-                            // Convert from kN/m to N/m
-                            let load_value = Self::to_newtons(*value);
-                            
-                            // For beams in CalculiX with local y-axis pointing up (0,1,0):
-                            // P1 = load in local y direction (vertical for horizontal beams)
-                            // P2 = load in local z direction (horizontal perpendicular to beam)
-                            // Negative value = downward (in negative local y direction)
-                            match direction {
-                                crate::models::LoadDirection::X => {
-                                    // Axial load along beam - not directly supported as DLOAD
-                                    // Would need *CLOAD at nodes or body force
-                                    tracing::warn!("Axial distributed loads not yet supported, skipping");
-                                },
-                                crate::models::LoadDirection::Y => {
-                                    // Global Y direction (typically gravity = negative Y)
-                                    // With local y = (0,1,0), P1 acts in global Y
-                                    // Load value should be negative for downward loads
-                                    inp.push_str(&format!("{}, P1, {:.6}\n", beam_id, load_value));
-                                },
-                                crate::models::LoadDirection::Z => {
-                                    // Global Z direction - use P2 (local z)
-                                    inp.push_str(&format!("{}, P2, {:.6}\n", beam_id, load_value));
-                                },
+        let mut dload = Vec::new();
+        let beams: HashMap<usize, &Beam> = model.beams.iter().map(|b| (b.id, b)).collect();
+        for load in &model.distributed_loads {
+            let factor = combination.factor(&load.load_case);
+            if factor == 0.0 {
+                continue;
+            }
+            for elem_id in &load.element_ids {
+                // Beam element ID (1-based)
+                let beam_id = elem_id + 1;
+
+                match &load.load_type {
+                    crate::models::LoadType::Uniform { value, direction } => {
+                        let Some(BeamAxes { tangent: t, dir1, dir2, length }) = beams.get(elem_id).and_then(|b| Self::beam_axes(model, b)) else {
+                            tracing::warn!("Distributed load on unknown or zero-length beam {}, skipping", elem_id);
+                            continue;
+                        };
+                        // Resolve the global load (kN/m to N/m) into the section's
+                        // 1- and 2-directions (P1, P2) and the beam axis
+                        let mut w = [0.0; 3];
+                        w[direction.index()] = Self::to_newtons(*value) * factor;
+                        for (label, axis) in [("P1", dir1), ("P2", dir2)] {
+                            let p = dot(w, axis);
+                            if p.abs() > 1e-6 {
+                                dload.push(format!("{}, {}, {:.6}\n", beam_id, label, p));
                             }
-                        },
-                        crate::models::LoadType::Gravity { g } => {
-                            // Apply gravity load using GRAV
-                            // GRAV requires magnitude and direction components
-                            // Gravity is in -Y direction
-                            inp.push_str(&format!("{}, GRAV, {:.6}, 0.0, -1.0, 0.0\n", beam_id, g));
-                        },
-                    }
+                        }
+                        let axial = dot(w, t) * length;
+                        if axial.abs() > 1e-6 {
+                            // Consistent nodal loads: L/2 at each end, or L/6 at the
+                            // ends and 2L/3 at the midpoint of a B32
+                            let beam = beams[elem_id];
+                            let shares: &[(usize, f64)] = match beam.node_ids.get(2) {
+                                Some(&mid) => &[(beam.node_ids[0], 1.0 / 6.0), (beam.node_ids[1], 1.0 / 6.0), (mid, 2.0 / 3.0)],
+                                None => &[(beam.node_ids[0], 0.5), (beam.node_ids[1], 0.5)],
+                            };
+                            for &(node, share) in shares {
+                                let force = nodal.entry(node).or_default();
+                                for (f, tc) in force.iter_mut().zip(t) {
+                                    *f += axial * share * tc;
+                                }
+                            }
+                        }
+                    },
+                    crate::models::LoadType::Gravity { g } => {
+                        // Apply gravity load using GRAV
+                        // GRAV requires magnitude and direction components
+                        // Gravity is in -Y direction
+                        dload.push(format!("{}, GRAV, {:.6}, 0.0, -1.0, 0.0\n", beam_id, g * factor));
+                    },
                 }
             }
         }
 
         // Pressure Loads (on Shells)
-        if !model.pressure_loads.is_empty() {
-            // Continue with *DLOAD if not already started, or add to existing
-            if model.distributed_loads.is_empty() {
-                inp.push_str("*DLOAD\n");
+        for load in &model.pressure_loads {
+            let factor = combination.factor(&load.load_case);
+            for &elem_id in &load.element_ids {
+                // For shells the P load acts along the element normal; the
+                // sign follows the requested side when a direction is given
+                let shell_id = elem_id + 1000001;
+                let Some(pressure) = model.shells.get(elem_id).and_then(|shell| model.shell_pressure(load, shell)) else {
+                    tracing::warn!("Pressure cannot act on shell {} in the requested direction, skipping", elem_id);
+                    continue;
+                };
+                let pressure = Self::to_pascal(pressure) * factor;
+                if pressure.abs() > 1e-6 {
+                    dload.push(format!("{}, P, {:.4}\n", shell_id, pressure));
+                }
             }
-            for load in &model.pressure_loads {
-                for elem_id in &load.element_ids {
-                    // Pn is pressure normal to face n. For shells, face 1 is top, face 2 is bottom?
-                    // Or just P for pressure? CalculiX manual says P for shells.
-                    // Positive pressure acts in the direction of the normal.
-                    // Apply offset for shell elements
-                    let shell_id = elem_id + 1000001;
-                    let pressure = Self::to_pascal(load.magnitude);
-                    if pressure.abs() > 1e-6 {
-                        inp.push_str(&format!("{}, P, {:.4}\n", shell_id, pressure));
+        }
+
+        let op = if replace { ", OP=NEW" } else { "" };
+        if replace || nodal.values().any(|f| f.iter().any(|c| c.abs() > 1e-6)) {
+            inp.push_str(&format!("*CLOAD{}\n", op));
+            for (node_id, force) in &nodal {
+                for (dof, f) in force.iter().enumerate() {
+                    if f.abs() > 1e-6 {
+                        inp.push_str(&format!("{}, {}, {:.4}\n", node_id + 1, dof + 1, f));
                     }
                 }
             }
         }
+        if replace || !dload.is_empty() {
+            inp.push_str(&format!("*DLOAD{}\n", op));
+            inp.push_str(&dload.concat());
+        }
+
+        // Temperatures by 0-based node id; OP=NEW returns the nodes of
+        // earlier steps to the initial temperature
+        let mut temperatures: BTreeMap<usize, f64> = BTreeMap::new();
+        for load in &model.temperature_loads {
+            let factor = combination.factor(&load.load_case);
+            for &node_id in &load.node_ids {
+                *temperatures.entry(node_id).or_default() += load.temperature * factor;
+            }
+        }
+        if !model.temperature_loads.is_empty() && (replace || temperatures.values().any(|t| t.abs() > 1e-9)) {
+            inp.push_str(&format!("*TEMPERATURE{}\n", op));
+            for (node_id, t) in temperatures.iter().filter(|(_, t)| t.abs() > 1e-9) {
+                inp.push_str(&format!("{}, {:.4}\n", node_id + 1, t));
+            }
+        }
+    }
+
+    /// Node-to-surface contact of a pair, with a linear pressure-overclosure
+    /// law and, with friction, a sticking stiffness a tenth of the normal one
+    fn write_contact_pair(inp: &mut String, number: usize, pair: &ContactPair) {
+        let name = format!("CONTACT{}", number);
+        inp.push_str(&format!("** Contact pair: {}\n", pair.name));
+        inp.push_str(&format!("*SURFACE, NAME={}_SLAVE, TYPE=NODE\n", name));
+        for node_id in &pair.slave_node_ids {
+            inp.push_str(&format!("{}\n", node_id + 1));
+        }
+        let face = match pair.master_face {
+            ShellFace::Positive => "SPOS",
+            ShellFace::Negative => "SNEG",
+        };
+        inp.push_str(&format!("*SURFACE, NAME={}_MASTER\n", name));
+        for index in &pair.master_element_ids {
+            inp.push_str(&format!("{}, {}\n", 1000001 + index, face));
+        }
+
+        let stiffness = Self::to_pascal(pair.stiffness);
+        inp.push_str(&format!("*SURFACE INTERACTION, NAME={}_INTERACTION\n", name));
+        inp.push_str("*SURFACE BEHAVIOR, PRESSURE-OVERCLOSURE=LINEAR\n");
+        inp.push_str(&format!("{:e}\n", stiffness));
+        if let Some(mu) = pair.friction {
+            inp.push_str("*FRICTION\n");
+            inp.push_str(&format!("{}, {:e}\n", mu, stiffness / 10.0));
+        }
+        inp.push_str(&format!("*CONTACT PAIR, INTERACTION={}_INTERACTION, TYPE=NODE TO SURFACE\n", name));
+        inp.push_str(&format!("{}_SLAVE, {}_MASTER\n", name, name));
+    }
 
+    fn write_static_outputs(model: &StructuralModel, inp: &mut String) {
         // Output requests
         inp.push_str("*NODE PRINT, NSET=NALL\n");
         inp.push_str("U, RF\n"); 
@@ -537,9 +640,22 @@ impl CalculiXGenerator {
             inp.push_str("S\n");
         }
 
-        inp.push_str("*END STEP\n");
+        // Nodal fields for the .frd file; shells come out expanded, with
+        // stresses on their bottom and top faces
+        inp.push_str("*NODE FILE\n");
+        inp.push_str("U\n");
+        if !model.beams.is_empty() || !model.shells.is_empty() {
+            inp.push_str("*EL FILE\n");
+            inp.push_str("S\n");
+        }
+    }
 
-        Ok(inp)
+    /// One displacement table per mode in the .dat file, and the shapes in the .frd file
+    fn write_mode_shape_outputs(inp: &mut String) {
+        inp.push_str("*NODE PRINT, NSET=NALL\n");
+        inp.push_str("U\n");
+        inp.push_str("*NODE FILE\n");
+        inp.push_str("U\n");
     }
 }
 #[derive(Debug, thiserror::Error)]
@@ -547,3 +663,182 @@ pub enum GeneratorError {
     #[error("Generation error: {0}")]
     GenerationError(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DEFAULT_LOAD_CASE;
+
+    fn portal() -> StructuralModel {
+        let json = include_str!("../portal_frame_request.json");
+        serde_json::from_str::<crate::models::AnalysisRequest>(json).unwrap().model
+    }
+
+    #[test]
+    fn test_eigenvalue_steps() {
+        let generator = CalculiXGenerator::new();
+        let model = portal();
+
+        let modal = generator.generate_inp_file(&model, AnalysisKind::Modal { num_modes: 5 }).unwrap();
+        assert!(modal.contains("*DENSITY\n"));
+        assert!(modal.contains("*STEP\n*FREQUENCY\n5\n"));
+        assert!(!modal.contains("*CLOAD") && !modal.contains("*STATIC"));
+
+        let buckling = generator.generate_inp_file(&model, AnalysisKind::Buckling { num_modes: 3 }).unwrap();
+        assert!(buckling.contains("*STEP\n*BUCKLE\n3\n"));
+        assert!(buckling.contains("*CLOAD") || buckling.contains("*DLOAD"));
+        assert!(buckling.trim_end().ends_with("*END STEP"));
+    }
+
+    #[test]
+    fn test_nonlinear_step() {
+        let inp = CalculiXGenerator::new()
+            .generate_inp_file(&portal(), AnalysisKind::Nonlinear { increments: 4, max_increments: 50 })
+            .unwrap();
+        assert!(inp.contains("*STEP, NLGEOM, INC=50\n*STATIC\n2.5e-1, 1.0, 2.5e-4, 2.5e-1\n"));
+        assert_eq!(inp.matches("*STEP").count(), 1);
+    }
+
+    #[test]
+    fn test_temperature_loads() {
+        use crate::models::TemperatureLoad;
+        let mut model = portal();
+        model.material.thermal_expansion = Some(1.2e-5);
+        model.temperature_loads = vec![TemperatureLoad { node_ids: vec![1, 2], temperature: 30.0, load_case: "Heat".to_string() }];
+        model.load_combinations = vec![
+            LoadCombination { name: "Hot".to_string(), factors: [("Heat".to_string(), 1.5)].into() },
+            LoadCombination { name: "Cold".to_string(), factors: [(DEFAULT_LOAD_CASE.to_string(), 1.0)].into() },
+        ];
+
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        assert!(inp.contains("*DENSITY\n7850.0000\n*EXPANSION, ZERO=0.\n1.2e-5\n"));
+        assert!(inp.contains("*INITIAL CONDITIONS, TYPE=TEMPERATURE\nNALL, 0.0\n"));
+        assert!(inp.contains("*TEMPERATURE\n2, 45.0000\n3, 45.0000\n"));
+        // The second combination returns the nodes to the initial temperature
+        assert!(inp.contains("*TEMPERATURE, OP=NEW\n*NODE PRINT"));
+    }
+
+    #[test]
+    fn test_contact_pair() {
+        use crate::models::{Node, Shell};
+        let mut model = portal();
+        let base = model.nodes.len();
+        for (i, (x, z)) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].into_iter().enumerate() {
+            model.nodes.push(Node { id: base + i, x, y: -0.1, z });
+        }
+        model.shells = vec![Shell { id: 0, node_ids: (base..base + 4).collect(), thickness: 0.3, is_quadratic: false }];
+        model.contact_pairs = vec![ContactPair {
+            name: "Base on footing".to_string(),
+            slave_node_ids: vec![0],
+            master_element_ids: vec![0],
+            master_face: ShellFace::Negative,
+            stiffness: 1e6,
+            friction: Some(0.4),
+        }];
+
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        assert!(inp.contains("** Contact pair: Base on footing\n*SURFACE, NAME=CONTACT1_SLAVE, TYPE=NODE\n1\n"));
+        assert!(inp.contains("*SURFACE, NAME=CONTACT1_MASTER\n1000001, SNEG\n"));
+        assert!(inp.contains("PRESSURE-OVERCLOSURE=LINEAR\n1e9\n*FRICTION\n0.4, 1e8\n"));
+        assert!(inp.contains("*CONTACT PAIR, INTERACTION=CONTACT1_INTERACTION, TYPE=NODE TO SURFACE\nCONTACT1_SLAVE, CONTACT1_MASTER\n"));
+        // Contact is model data, ahead of the steps
+        assert!(inp.find("*CONTACT PAIR").unwrap() < inp.find("*STEP").unwrap());
+    }
+
+    #[test]
+    fn test_member_and_pressure_loads() {
+        use crate::models::{DistributedLoad, LoadDirection, LoadType, Node, PressureLoad, Shell};
+        let mut model = portal();
+        model.point_loads.clear();
+        model.pressure_loads.clear();
+        // A column (beam 0) and a girder (beam 1) under the same global -Y load
+        let column = model.beams.iter().find(|b| {
+            let (a, b) = (&model.nodes[b.node_ids[0]], &model.nodes[b.node_ids[1]]);
+            (a.x - b.x).abs() < 1e-9 && (a.z - b.z).abs() < 1e-9
+        }).unwrap().clone();
+        let girder = model.beams.iter().find(|b| {
+            (model.nodes[b.node_ids[0]].y - model.nodes[b.node_ids[1]].y).abs() < 1e-9
+        }).unwrap().clone();
+        let uniform = LoadType::Uniform { value: -2.0, direction: LoadDirection::Y };
+        model.distributed_loads = vec![DistributedLoad { element_ids: vec![column.id, girder.id], load_type: uniform, load_case: DEFAULT_LOAD_CASE.to_string() }];
+
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        // The girder takes it as a P1 line load; the column's share is axial
+        // and goes to its end nodes
+        assert!(inp.contains(&format!("\n{}, P1, -2000.000000\n", girder.id + 1)));
+        assert!(!inp.contains(&format!("\n{}, P1,", column.id + 1)) && !inp.contains(&format!("\n{}, P2,", column.id + 1)));
+        let n0 = &model.nodes[column.node_ids[0]];
+        let n1 = &model.nodes[column.node_ids[1]];
+        let half = -2000.0 * (n1.y - n0.y).abs() / 2.0;
+        assert!(inp.contains(&format!("*CLOAD\n{}, 2, {:.4}\n", column.node_ids[0].min(column.node_ids[1]) + 1, half)));
+
+        // A pressure towards -Y acts on a plate whichever way its nodes run
+        let base = model.nodes.len();
+        for (i, (x, z)) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].into_iter().enumerate() {
+            model.nodes.push(Node { id: base + i, x, y: 5.0, z });
+        }
+        let up = vec![base + 3, base + 2, base + 1, base];
+        let down = vec![base, base + 1, base + 2, base + 3];
+        model.shells = vec![
+            Shell { id: 0, node_ids: up, thickness: 0.2, is_quadratic: false },
+            Shell { id: 1, node_ids: down, thickness: 0.2, is_quadratic: false },
+        ];
+        model.distributed_loads.clear();
+        model.pressure_loads = vec![PressureLoad { element_ids: vec![0, 1], magnitude: 5.0, direction: Some(LoadDirection::Y), load_case: DEFAULT_LOAD_CASE.to_string() }];
+        assert_eq!(model.shell_normal(&model.shells[0]).unwrap()[1], 1.0);
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        assert!(inp.contains("*DLOAD\n1000001, P, 5000.0000\n1000002, P, -5000.0000\n"));
+    }
+
+    #[test]
+    fn test_load_combination_steps() {
+        use crate::models::PointLoad;
+        let mut model = portal();
+        let node_id = 2;
+        model.point_loads = vec![
+            PointLoad { node_id, fx: 0.0, fy: -10.0, fz: 0.0, load_case: "Dead".to_string() },
+            PointLoad { node_id, fx: 4.0, fy: 0.0, fz: 0.0, load_case: "Wind".to_string() },
+        ];
+        model.distributed_loads.clear();
+        model.pressure_loads.clear();
+        model.load_combinations = vec![
+            LoadCombination { name: "ULS".to_string(), factors: [("Dead".to_string(), 1.35), ("Wind".to_string(), 1.5)].into() },
+            LoadCombination { name: "Dead only".to_string(), factors: [("Dead".to_string(), 1.0)].into() },
+        ];
+
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        // Each combination is its own step, replacing the loads of the last
+        assert_eq!(inp.matches("*STEP\n*STATIC\n").count(), 2);
+        assert!(inp.contains(&format!("*CLOAD\n{}, 1, 6000.0000\n{}, 2, -13500.0000\n", node_id + 1, node_id + 1)));
+        assert!(inp.contains(&format!("*CLOAD, OP=NEW\n{}, 2, -10000.0000\n", node_id + 1)));
+        assert!(inp.find("** Load combination: ULS").unwrap() < inp.find("** Load combination: Dead only").unwrap());
+    }
+
+    #[test]
+    fn test_beam_sections_and_orientation() {
+        use crate::models::{Node, SectionType};
+        let mut model = portal();
+        let base = model.nodes.len();
+        model.nodes.push(Node { id: base, x: 0.0, y: 0.0, z: 0.0 });
+        model.nodes.push(Node { id: base + 1, x: 0.0, y: 3.0, z: 0.0 });
+        model.nodes.push(Node { id: base + 2, x: 4.0, y: 3.0, z: 0.0 });
+        let template = model.beams[0].clone();
+        let mut column = Beam { id: 100, node_ids: vec![base, base + 1], ..template.clone() };
+        column.section.section_type = SectionType::Pipe;
+        column.section.wall_thickness = Some(0.01);
+        let mut girder = Beam { id: 101, node_ids: vec![base + 1, base + 2], rotation: 90.0, ..template.clone() };
+        girder.section = BeamSection { section_type: SectionType::General, area: Some(0.01), iy: Some(2e-5), iz: Some(3e-4), j: Some(1e-6), ..template.section };
+        model.beams = vec![column, girder];
+
+        let inp = CalculiXGenerator::new().generate_inp_file(&model, AnalysisKind::Static).unwrap();
+        // An upright column's local y is -X; a 90° roll turns an X girder's
+        // local y from +Y to +Z
+        assert!(inp.contains("*ELEMENT, TYPE=B31R, ELSET=EBEAMS_1\n101, "));
+        assert!(inp.contains(&format!("SECTION=PIPE\n{:.6e}, 1.000000e-2\n-1.000000, 0.000000, 0.000000\n", model.beams[0].section.width / 2.0)));
+        assert!(inp.contains("*USER ELEMENT, TYPE=U1"));
+        assert!(inp.contains("*ELEMENT, TYPE=U1, ELSET=EBEAMS_2\n102, "));
+        assert!(inp.contains("*BEAM GENERAL SECTION, ELSET=EBEAMS_2, MATERIAL=MATERIAL1, SECTION=GENERAL\n\
+            1.000000e-2, 2.000000e-5, 0.000000e0, 3.000000e-4, 1.000000e-6\n0.000000, 0.000000, 1.000000\n"));
+        assert!(inp.contains("*ELSET, ELSET=EBEAMS\nEBEAMS_1,\nEBEAMS_2\n"));
+    }
+}
@@ -0,0 +1,622 @@
+//! Analysis job queue
+//!
+//! Submitted requests wait in a bounded queue for one of a fixed pool of
+//! workers, so HTTP handlers return a job id immediately and clients poll
+//! `GET /api/v1/jobs/{id}` for the status and results, or follow its progress
+//! events. Jobs are written through to a [`JobStore`], which keeps their
+//! history once the queue forgets them. Running jobs are stopped when they exceed the time limit or are
+//! cancelled. Each job runs in its own directory, whose input, output and log
+//! files can be downloaded once it finishes. A batch queues several jobs at
+//! once, all or none of them, and is looked up by its own id for as long as
+//! any of its jobs is held. Finished jobs and their files
+//! are kept for the retention period and then dropped. Jobs belong to the
+//! API key that submitted them, and every lookup is made on behalf of an
+//! owner, so one client never sees, downloads or cancels another's jobs.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{broadcast, mpsc, Mutex, Notify, RwLock};
+use uuid::Uuid;
+
+use crate::engine::{EngineError, Engines};
+use crate::executor::{JOB_NAME, LOG_FILE};
+use crate::metrics::Metrics;
+use crate::models::{AnalysisRequest, AnalysisResponse, AnalysisStatus, BatchJob, BatchResponse, Engine};
+use crate::progress::{Phase, ProgressEvent, ProgressLog};
+use crate::store::{JobQuery, JobStore, JobSummary, StoreError};
+
+/// How long finished jobs stay available for polling by default
+const JOB_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// A submitted analysis and its outcome so far
+struct Job {
+    response: AnalysisResponse,
+    engine: Engine,
+    /// Name of the key that submitted the job, if the service has keys
+    owner: Option<String>,
+    /// When the job finished, for retention
+    finished: Option<Instant>,
+    progress: Arc<ProgressLog>,
+    cancel: Arc<Notify>,
+}
+
+type JobMap = Arc<RwLock<HashMap<String, Job>>>;
+
+pub struct JobQueue {
+    jobs: JobMap,
+    /// Names and job ids of each batch, by batch id
+    batches: RwLock<HashMap<String, Vec<(String, String)>>>,
+    sender: mpsc::Sender<QueuedJob>,
+    /// Parent of the job directories
+    artifact_dir: PathBuf,
+    retention: Duration,
+    /// Wall-clock limit of a running job
+    timeout: Option<Duration>,
+    store: Arc<JobStore>,
+    metrics: Arc<Metrics>,
+}
+
+/// A waiting job, with where to report its progress and keep its files,
+/// and how it is stopped
+struct QueuedJob {
+    id: String,
+    request: AnalysisRequest,
+    progress: Arc<ProgressLog>,
+    dir: PathBuf,
+    timeout: Option<Duration>,
+    cancel: Arc<Notify>,
+    store: Arc<JobStore>,
+    metrics: Arc<Metrics>,
+}
+
+/// A file of a job that can be downloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    /// Generated CalculiX input
+    Inp,
+    /// ccx printed output
+    Dat,
+    /// ccx field results
+    Frd,
+    /// ccx console output
+    Log,
+}
+
+impl Artifact {
+    pub fn file_name(self) -> String {
+        match self {
+            Artifact::Inp => format!("{}.inp", JOB_NAME),
+            Artifact::Dat => format!("{}.dat", JOB_NAME),
+            Artifact::Frd => format!("{}.frd", JOB_NAME),
+            Artifact::Log => LOG_FILE.to_string(),
+        }
+    }
+}
+
+impl FromStr for Artifact {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "inp" => Ok(Artifact::Inp),
+            "dat" => Ok(Artifact::Dat),
+            "frd" => Ok(Artifact::Frd),
+            "log" => Ok(Artifact::Log),
+            _ => Err(format!("Unknown file '{}', expected inp, dat, frd or log", kind)),
+        }
+    }
+}
+
+impl JobQueue {
+    /// Start `workers` workers taking jobs from a queue of `capacity` waiting jobs
+    pub fn new(engines: Arc<Engines>, workers: usize, capacity: usize) -> Self {
+        let jobs: JobMap = Arc::default();
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let receiver = Arc::new(Mutex::new(receiver));
+        for worker in 0..workers.max(1) {
+            tokio::spawn(Self::work(worker, Arc::clone(&receiver), Arc::clone(&jobs), Arc::clone(&engines)));
+        }
+        Self {
+            jobs,
+            batches: RwLock::default(),
+            sender,
+            artifact_dir: std::env::temp_dir().join("calculix-service-jobs"),
+            retention: JOB_RETENTION,
+            timeout: None,
+            store: Arc::new(JobStore::in_memory().expect("an in-memory database opens")),
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Record job counts and analysis times in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Keep job history in `store` rather than in memory
+    pub fn with_store(mut self, store: JobStore) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Stop jobs that run for longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Keep job directories under `dir`
+    pub fn with_artifact_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.artifact_dir = dir.into();
+        self
+    }
+
+    /// Keep finished jobs and their files for `retention`
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Remove job directories left behind by an earlier run of the service
+    pub fn clear_artifacts(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.artifact_dir) else {
+            return;
+        };
+        // Only job directories, in case the directory is shared
+        for entry in entries.flatten() {
+            let is_job = entry.file_name().to_str().is_some_and(|name| Uuid::parse_str(name).is_ok());
+            if is_job && entry.path().is_dir() {
+                if let Err(e) = std::fs::remove_dir_all(entry.path()) {
+                    tracing::warn!("Failed to remove old job files {:?}: {}", entry.path(), e);
+                }
+            }
+        }
+    }
+
+    /// Queue a request of `owner`, returning its queued job
+    pub async fn submit(&self, request: AnalysisRequest, owner: Option<&str>) -> Result<AnalysisResponse, JobError> {
+        let mut queued = self.submit_all(vec![request], owner).await?;
+        Ok(queued.remove(0))
+    }
+
+    /// Queue named requests of `owner` as one batch, returning its queued jobs in order
+    pub async fn submit_batch(&self, requests: Vec<(String, AnalysisRequest)>, owner: Option<&str>) -> Result<BatchResponse, JobError> {
+        let (names, requests): (Vec<String>, Vec<AnalysisRequest>) = requests.into_iter().unzip();
+        let queued = self.submit_all(requests, owner).await?;
+        let batch_id = Uuid::new_v4().to_string();
+        let mut batches = self.batches.write().await;
+        // Batches go once the queue has forgotten all their jobs
+        let jobs = self.jobs.read().await;
+        batches.retain(|_, batch| batch.iter().any(|(_, id)| jobs.contains_key(id)));
+        batches.insert(batch_id.clone(), names.iter().cloned().zip(queued.iter().map(|job| job.job_id.clone())).collect());
+        let jobs = names.into_iter().zip(queued).map(|(name, job)| BatchJob { name, job }).collect();
+        Ok(BatchResponse { batch_id, jobs })
+    }
+
+    /// The jobs of a batch of `owner` as they stand
+    pub async fn batch(&self, id: &str, owner: Option<&str>) -> Option<BatchResponse> {
+        let names = self.batches.read().await.get(id)?.clone();
+        let mut jobs = Vec::with_capacity(names.len());
+        for (name, job_id) in names {
+            jobs.push(BatchJob { name, job: self.get(&job_id, owner).await? });
+        }
+        Some(BatchResponse { batch_id: id.to_string(), jobs })
+    }
+
+    /// Queue every request or, if the queue cannot take them all, none
+    async fn submit_all(&self, requests: Vec<AnalysisRequest>, owner: Option<&str>) -> Result<Vec<AnalysisResponse>, JobError> {
+        if requests.len() > self.sender.max_capacity() {
+            return Err(JobError::TooMany { jobs: requests.len(), capacity: self.sender.max_capacity() });
+        }
+
+        // The jobs are listed before a worker can update them, as workers need this lock
+        let mut jobs = self.jobs.write().await;
+        jobs.retain(|id, job| {
+            let keep = job.finished.is_none_or(|t| t.elapsed() < self.retention);
+            if !keep {
+                let dir = self.artifact_dir.join(id);
+                tokio::spawn(async move {
+                    // Native jobs leave no files
+                    let _ = tokio::fs::remove_dir_all(dir).await;
+                });
+            }
+            keep
+        });
+        let permits = self.sender.try_reserve_many(requests.len()).map_err(|_| JobError::QueueFull)?;
+        let mut submitted = Vec::with_capacity(requests.len());
+        for (request, permit) in requests.into_iter().zip(permits) {
+            let id = Uuid::new_v4().to_string();
+            let response = AnalysisResponse {
+                job_id: id.clone(),
+                status: AnalysisStatus::Queued,
+                results: None,
+                error_message: None,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            };
+            Self::persist(self.store.insert(&response, &request, owner));
+            let progress = Arc::new(ProgressLog::default());
+            let cancel = Arc::new(Notify::new());
+            let engine = request.engine;
+            permit.send(QueuedJob {
+                id: id.clone(),
+                request,
+                progress: Arc::clone(&progress),
+                dir: self.artifact_dir.join(&id),
+                timeout: self.timeout,
+                cancel: Arc::clone(&cancel),
+                store: Arc::clone(&self.store),
+                metrics: Arc::clone(&self.metrics),
+            });
+            progress.phase(Phase::Queued);
+            self.metrics.job_submitted(engine);
+            let owner = owner.map(str::to_string);
+            jobs.insert(id, Job { response: response.clone(), engine, owner, finished: None, progress, cancel });
+            submitted.push(response);
+        }
+        Ok(submitted)
+    }
+
+    /// A held job, if it belongs to `owner`
+    fn owned<'a>(jobs: &'a HashMap<String, Job>, id: &str, owner: Option<&str>) -> Option<&'a Job> {
+        jobs.get(id).filter(|job| job.owner.as_deref() == owner)
+    }
+
+    /// Current status of a job of `owner`, with its results once finished;
+    /// jobs the queue no longer holds come from the store
+    pub async fn get(&self, id: &str, owner: Option<&str>) -> Option<AnalysisResponse> {
+        if let Some(job) = self.jobs.read().await.get(id) {
+            return (job.owner.as_deref() == owner).then(|| job.response.clone());
+        }
+        self.store.get(id, owner).unwrap_or_else(|e| {
+            tracing::error!("Failed to read job {}: {}", id, e);
+            None
+        })
+    }
+
+    /// The request a job of `owner` was submitted with
+    pub fn request(&self, id: &str, owner: Option<&str>) -> Result<Option<AnalysisRequest>, StoreError> {
+        self.store.request(id, owner)
+    }
+
+    /// Stored jobs of `owner` matching `query`, most recent first
+    pub fn list(&self, query: &JobQuery, owner: Option<&str>) -> Result<Vec<JobSummary>, StoreError> {
+        self.store.list(query, owner)
+    }
+
+    /// Jobs waiting for a worker and jobs running
+    pub async fn load(&self) -> (usize, usize) {
+        let jobs = self.jobs.read().await;
+        let count = |status| jobs.values().filter(|job| job.response.status == status).count();
+        (count(AnalysisStatus::Queued), count(AnalysisStatus::Running))
+    }
+
+    /// Metrics in the Prometheus text format
+    pub async fn metrics(&self) -> String {
+        let (queued, running) = self.load().await;
+        self.metrics.encode(queued, running)
+    }
+
+    /// Progress events of a job of `owner` so far, and a receiver for the rest
+    pub async fn events(&self, id: &str, owner: Option<&str>) -> Option<(Vec<ProgressEvent>, broadcast::Receiver<ProgressEvent>)> {
+        Self::owned(&*self.jobs.read().await, id, owner).map(|job| job.progress.subscribe())
+    }
+
+    /// Cancel a queued or running job of `owner`, killing its ccx process;
+    /// `None` if there is no such job
+    pub async fn cancel(&self, id: &str, owner: Option<&str>) -> Option<Result<AnalysisResponse, JobError>> {
+        let mut jobs = self.jobs.write().await;
+        let job = jobs.get_mut(id).filter(|job| job.owner.as_deref() == owner)?;
+        if job.finished.is_some() {
+            return Some(Err(JobError::Finished));
+        }
+        Self::finish(job, &self.store, &self.metrics, AnalysisStatus::Cancelled, |_| ());
+        // Stored until a worker waits for it, if the job is still queued
+        job.cancel.notify_one();
+        Some(Ok(job.response.clone()))
+    }
+
+    /// Path of a file of a finished job of `owner`, if the job produced it
+    pub async fn artifact(&self, id: &str, owner: Option<&str>, artifact: Artifact) -> Option<PathBuf> {
+        Self::owned(&*self.jobs.read().await, id, owner)?.finished?;
+        let path = self.artifact_dir.join(id).join(artifact.file_name());
+        Self::is_file(&path).await.then_some(path)
+    }
+
+    async fn is_file(path: &Path) -> bool {
+        tokio::fs::metadata(path).await.is_ok_and(|m| m.is_file())
+    }
+
+    async fn work(
+        worker: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<QueuedJob>>>,
+        jobs: JobMap,
+        engines: Arc<Engines>,
+    ) {
+        loop {
+            // Hold the receiver only while waiting, so idle workers queue up for the next job
+            let Some(QueuedJob { id, request, progress, dir, timeout, cancel, store, metrics }) = receiver.lock().await.recv().await else {
+                return;
+            };
+            if Self::update(&jobs, &store, &metrics, &id, AnalysisStatus::Running, |_| ()).await.is_none() {
+                // Cancelled while it was queued
+                continue;
+            }
+            tracing::info!("Worker {} running job {}", worker, id);
+
+            // Dropping the analysis when it is stopped kills its ccx process;
+            // a native solve cannot be interrupted and finishes unseen
+            let started = Instant::now();
+            let analysis = async {
+                let run = engines.analyze(&request, &dir, &progress);
+                match timeout {
+                    Some(limit) => tokio::time::timeout(limit, run).await.unwrap_or_else(|_| {
+                        Err(EngineError::Failed(format!("Analysis exceeded the time limit of {} s", limit.as_secs())))
+                    }),
+                    None => run.await,
+                }
+            };
+            let result = tokio::select! {
+                result = analysis => result,
+                _ = cancel.notified() => {
+                    tracing::info!("Job {} cancelled", id);
+                    continue;
+                }
+            };
+            metrics.analysis_time(request.engine, request.analysis, started.elapsed().as_secs_f64());
+            match result {
+                Ok(results) => {
+                    Self::update(&jobs, &store, &metrics, &id, AnalysisStatus::Success, |r| r.results = Some(results)).await;
+                }
+                Err(e) => {
+                    tracing::error!("Job {} failed: {}", id, e);
+                    Self::update(&jobs, &store, &metrics, &id, AnalysisStatus::Failed, |r| r.error_message = Some(e.to_string())).await;
+                }
+            }
+        }
+    }
+
+    /// Apply a new status to a job that has not finished yet, returning
+    /// `None` if it has (or is gone)
+    async fn update(
+        jobs: &JobMap,
+        store: &JobStore,
+        metrics: &Metrics,
+        id: &str,
+        status: AnalysisStatus,
+        apply: impl FnOnce(&mut AnalysisResponse),
+    ) -> Option<()> {
+        let mut jobs = jobs.write().await;
+        let job = jobs.get_mut(id).filter(|job| job.finished.is_none())?;
+        Self::finish(job, store, metrics, status, apply);
+        Some(())
+    }
+
+    /// Set a job's status, marking it finished when the status is final
+    fn finish(job: &mut Job, store: &JobStore, metrics: &Metrics, status: AnalysisStatus, apply: impl FnOnce(&mut AnalysisResponse)) {
+        job.response.status = status;
+        job.response.timestamp = chrono::Utc::now().to_rfc3339();
+        apply(&mut job.response);
+        Self::persist(store.update(&job.response));
+        if matches!(status, AnalysisStatus::Success | AnalysisStatus::Failed | AnalysisStatus::Cancelled) {
+            job.finished = Some(Instant::now());
+            metrics.job_finished(job.engine, status);
+            job.progress.emit(ProgressEvent::Finished {
+                status,
+                error_message: job.response.error_message.clone(),
+            });
+        }
+    }
+
+    /// Log a failed write to the store; the job itself carries on
+    fn persist(result: Result<(), StoreError>) {
+        if let Err(e) = result {
+            tracing::error!("Failed to save a job: {}", e);
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    #[error("The analysis queue is full, try again later")]
+    QueueFull,
+    #[error("The job has already finished")]
+    Finished,
+    #[error("A batch of {jobs} analyses is more than the queue holds ({capacity})")]
+    TooMany { jobs: usize, capacity: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Engine, SupportType};
+
+    fn request() -> AnalysisRequest {
+        let json = include_str!("../portal_frame_request.json");
+        let mut request: AnalysisRequest = serde_json::from_str(json).unwrap();
+        request.engine = Engine::Native;
+        for support in &mut request.model.supports {
+            support.constraint_type = SupportType::Fixed;
+        }
+        request
+    }
+
+    async fn wait(queue: &JobQueue, id: &str) -> AnalysisResponse {
+        loop {
+            let response = queue.get(id, None).await.unwrap();
+            if matches!(response.status, AnalysisStatus::Success | AnalysisStatus::Failed | AnalysisStatus::Cancelled) {
+                return response;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_in_the_background() {
+        let queue = JobQueue::new(Arc::new(Engines::new()), 2, 8);
+        let submitted = queue.submit(request(), None).await.unwrap();
+        assert!(matches!(submitted.status, AnalysisStatus::Queued));
+        assert!(queue.get("unknown", None).await.is_none());
+
+        let done = wait(&queue, &submitted.job_id).await;
+        assert!(matches!(done.status, AnalysisStatus::Success));
+        assert_eq!(done.results.unwrap().displacements.len(), 4);
+
+        // The job's events are replayed once it has finished
+        let (events, _) = queue.events(&submitted.job_id, None).await.unwrap();
+        assert_eq!(events[0], ProgressEvent::Phase { phase: Phase::Queued });
+        assert!(events.contains(&ProgressEvent::Phase { phase: Phase::Solving }));
+        assert!(events.last().unwrap().is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_rejects_jobs() {
+        // A queue without workers, so nothing is taken off it
+        let (sender, _receiver) = mpsc::channel(1);
+        let queue = JobQueue { jobs: Arc::default(), batches: RwLock::default(), sender, artifact_dir: PathBuf::new(), retention: JOB_RETENTION, timeout: None, store: Arc::new(JobStore::in_memory().unwrap()), metrics: Arc::new(Metrics::new()) };
+        let first = queue.submit(request(), None).await.unwrap();
+        assert!(matches!(queue.submit(request(), None).await, Err(JobError::QueueFull)));
+        assert!(matches!(queue.get(&first.job_id, None).await.unwrap().status, AnalysisStatus::Queued));
+    }
+
+    #[tokio::test]
+    async fn test_batches_queue_all_or_nothing() {
+        // A queue without workers, so nothing is taken off it
+        let (sender, _receiver) = mpsc::channel(3);
+        let queue = JobQueue { jobs: Arc::default(), batches: RwLock::default(), sender, artifact_dir: PathBuf::new(), retention: JOB_RETENTION, timeout: None, store: Arc::new(JobStore::in_memory().unwrap()), metrics: Arc::new(Metrics::new()) };
+        let named = |names: &[&str]| names.iter().map(|name| (name.to_string(), request())).collect::<Vec<_>>();
+        assert!(matches!(queue.submit_batch(named(&["a", "b", "c", "d"]), None).await, Err(JobError::TooMany { jobs: 4, capacity: 3 })));
+
+        let batch = queue.submit_batch(named(&["a", "b"]), None).await.unwrap();
+        let names: Vec<&str> = batch.jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        // One place left, so a second batch of two is refused whole
+        assert!(matches!(queue.submit_batch(named(&["c", "d"]), None).await, Err(JobError::QueueFull)));
+        assert_eq!(queue.load().await, (2, 0));
+
+        let found = queue.batch(&batch.batch_id, None).await.unwrap();
+        assert_eq!(found.jobs[1].job.job_id, batch.jobs[1].job.job_id);
+        assert!(queue.batch("unknown", None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job() {
+        // Workers start once the job is cancelled, so it is still queued
+        let (sender, receiver) = mpsc::channel(4);
+        let queue = JobQueue { jobs: Arc::default(), batches: RwLock::default(), sender, artifact_dir: PathBuf::new(), retention: JOB_RETENTION, timeout: None, store: Arc::new(JobStore::in_memory().unwrap()), metrics: Arc::new(Metrics::new()) };
+        let job = queue.submit(request(), None).await.unwrap();
+        let cancelled = queue.cancel(&job.job_id, None).await.unwrap().unwrap();
+        assert_eq!(cancelled.status, AnalysisStatus::Cancelled);
+        assert!(matches!(queue.cancel(&job.job_id, None).await, Some(Err(JobError::Finished))));
+        assert!(queue.cancel("unknown", None).await.is_none());
+
+        // The worker skips it
+        let receiver = Arc::new(Mutex::new(receiver));
+        tokio::spawn(JobQueue::work(0, receiver, Arc::clone(&queue.jobs), Arc::new(Engines::new())));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(queue.get(&job.job_id, None).await.unwrap().status, AnalysisStatus::Cancelled);
+        let (events, _) = queue.events(&job.job_id, None).await.unwrap();
+        assert_eq!(events.iter().filter(|e| e.is_finished()).count(), 1);
+    }
+
+    /// A CalculiX job whose ccx never finishes, and the engines running it
+    #[cfg(unix)]
+    fn stuck_calculix_job(dir: &Path) -> (Arc<Engines>, AnalysisRequest) {
+        use std::os::unix::fs::PermissionsExt;
+        let ccx = dir.join("ccx");
+        std::fs::write(&ccx, "#!/bin/sh\nexec sleep 60\n").unwrap();
+        std::fs::set_permissions(&ccx, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut request = request();
+        request.engine = Engine::CalculiX;
+        (Arc::new(Engines::new().with_ccx_path(ccx.to_str().unwrap())), request)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_time_limit_stops_a_running_job() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (engines, request) = stuck_calculix_job(dir.path());
+        let queue = JobQueue::new(Arc::clone(&engines), 1, 8)
+            .with_artifact_dir(dir.path().join("jobs"))
+            .with_timeout(Duration::from_millis(300));
+        let job = queue.submit(request, None).await.unwrap();
+        let done = tokio::time::timeout(Duration::from_secs(10), wait(&queue, &job.job_id)).await.unwrap();
+        assert_eq!(done.status, AnalysisStatus::Failed);
+        assert!(done.error_message.unwrap().contains("exceeded the time limit"));
+        assert_eq!(engines.free_ccx_slots(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_running_job() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (engines, request) = stuck_calculix_job(dir.path());
+        let queue = JobQueue::new(Arc::clone(&engines), 1, 8).with_artifact_dir(dir.path().join("jobs"));
+        let job = queue.submit(request, None).await.unwrap();
+        // Running once ccx holds the only slot
+        while engines.free_ccx_slots() > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(queue.get(&job.job_id, None).await.unwrap().status, AnalysisStatus::Running);
+
+        let cancelled = queue.cancel(&job.job_id, None).await.unwrap().unwrap();
+        assert_eq!(cancelled.status, AnalysisStatus::Cancelled);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while engines.free_ccx_slots() == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }).await.unwrap();
+        assert_eq!(queue.get(&job.job_id, None).await.unwrap().status, AnalysisStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_hidden_from_other_owners() {
+        // A queue without workers, so nothing is taken off it
+        let (sender, _receiver) = mpsc::channel(4);
+        let queue = JobQueue { jobs: Arc::default(), batches: RwLock::default(), sender, artifact_dir: PathBuf::new(), retention: JOB_RETENTION, timeout: None, store: Arc::new(JobStore::in_memory().unwrap()), metrics: Arc::new(Metrics::new()) };
+        let job = queue.submit(request(), Some("alice")).await.unwrap();
+        let batch = queue.submit_batch(vec![("a".to_string(), request())], Some("alice")).await.unwrap();
+
+        for owner in [Some("bob"), None] {
+            assert!(queue.get(&job.job_id, owner).await.is_none());
+            assert!(queue.request(&job.job_id, owner).unwrap().is_none());
+            assert!(queue.events(&job.job_id, owner).await.is_none());
+            assert!(queue.cancel(&job.job_id, owner).await.is_none());
+            assert!(queue.batch(&batch.batch_id, owner).await.is_none());
+            assert!(queue.list(&JobQuery::default(), owner).unwrap().is_empty());
+        }
+        assert_eq!(queue.get(&job.job_id, Some("alice")).await.unwrap().status, AnalysisStatus::Queued);
+        assert_eq!(queue.list(&JobQuery::default(), Some("alice")).unwrap().len(), 2);
+        assert!(queue.cancel(&job.job_id, Some("alice")).await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_job_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let queue = JobQueue::new(Arc::new(Engines::new()), 1, 8).with_artifact_dir(dir.path());
+
+        // CalculiX keeps its input file whether or not ccx is installed
+        let mut calculix = request();
+        calculix.engine = Engine::CalculiX;
+        let job = queue.submit(calculix, None).await.unwrap();
+        assert!(queue.artifact(&job.job_id, None, Artifact::Inp).await.is_none());
+        wait(&queue, &job.job_id).await;
+        let inp = queue.artifact(&job.job_id, None, Artifact::Inp).await.unwrap();
+        assert!(std::fs::read_to_string(inp).unwrap().contains("*STEP"));
+
+        // Native jobs write nothing
+        let native = queue.submit(request(), None).await.unwrap();
+        wait(&queue, &native.job_id).await;
+        assert!(queue.artifact(&native.job_id, None, Artifact::Inp).await.is_none());
+        assert_eq!("frd".parse::<Artifact>(), Ok(Artifact::Frd));
+        assert!("exe".parse::<Artifact>().is_err());
+
+        // Files of earlier runs are cleared
+        queue.clear_artifacts();
+        assert!(queue.artifact(&job.job_id, None, Artifact::Inp).await.is_none());
+    }
+}
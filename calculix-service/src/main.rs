@@ -1,7 +1,16 @@
 mod api;
+mod auth;
+mod engine;
 mod executor;
+mod frd;
 mod generator;
+mod jobs;
+mod metrics;
 mod models;
+mod native;
+mod progress;
+mod store;
+mod validation;
 
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -32,7 +41,8 @@ async fn main() {
         Err(e) => {
             tracing::warn!("CalculiX not found or not accessible: {}", e);
             tracing::warn!("Set CALCULIX_PATH environment variable to the correct path");
-            tracing::warn!("Service will start but analyses will fail until CalculiX is available");
+            tracing::warn!("Service will start but CalculiX analyses will fail until it is available");
+            tracing::warn!("Requests with \"engine\": \"Native\" run in-process and are unaffected");
         }
     }
 
@@ -47,8 +57,16 @@ async fn main() {
     tracing::info!("Listening on {}", addr);
     tracing::info!("API endpoints:");
     tracing::info!("  GET  /health");
+    tracing::info!("  GET  /metrics (Prometheus)");
     tracing::info!("  GET  /api/v1/version");
-    tracing::info!("  POST /api/v1/analyze");
+    tracing::info!("  POST /api/v1/analyze (queues a job)");
+    tracing::info!("  POST /api/v1/jobs");
+    tracing::info!("  GET  /api/v1/jobs?status=&since=&limit= (job history)");
+    tracing::info!("  GET  /api/v1/jobs/{{id}}");
+    tracing::info!("  DELETE /api/v1/jobs/{{id}} (cancels the job)");
+    tracing::info!("  GET  /api/v1/jobs/{{id}}/events (Server-Sent Events)");
+    tracing::info!("  GET  /api/v1/jobs/{{id}}/request");
+    tracing::info!("  GET  /api/v1/jobs/{{id}}/files/{{inp|dat|frd|log}}");
     tracing::info!("  POST /api/v1/validate");
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
@@ -0,0 +1,141 @@
+//! Prometheus metrics of the service, served at `/metrics`
+//!
+//! Job counts and analysis times are recorded as jobs run; the queue depth
+//! and running jobs are read from the queue when the metrics are scraped.
+
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+
+use crate::models::{AnalysisKind, AnalysisStatus, Engine};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct EngineLabels {
+    engine: String,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct JobLabels {
+    engine: String,
+    status: String,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct AnalysisLabels {
+    engine: String,
+    analysis: String,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ExitLabels {
+    /// Exit code, or `signal` when ccx was killed
+    code: String,
+}
+
+/// Analysis times from 0.1 s up to about 40 minutes, past the default time limit
+fn analysis_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.1, 2.5, 12))
+}
+
+pub struct Metrics {
+    registry: Registry,
+    jobs_submitted: Family<EngineLabels, Counter>,
+    jobs_finished: Family<JobLabels, Counter>,
+    jobs_queued: Gauge,
+    jobs_running: Gauge,
+    analysis_seconds: Family<AnalysisLabels, Histogram, fn() -> Histogram>,
+    parse_failures: Counter,
+    ccx_exits: Family<ExitLabels, Counter>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let mut registry = Registry::with_prefix("calculix");
+        let jobs_submitted = Family::default();
+        let jobs_finished = Family::default();
+        let jobs_queued = Gauge::default();
+        let jobs_running = Gauge::default();
+        let analysis_seconds = Family::new_with_constructor(analysis_histogram as fn() -> Histogram);
+        let parse_failures = Counter::default();
+        let ccx_exits = Family::default();
+        registry.register("jobs_submitted", "Jobs accepted into the queue", jobs_submitted.clone());
+        registry.register("jobs_finished", "Jobs that succeeded, failed or were cancelled", jobs_finished.clone());
+        registry.register("jobs_queued", "Jobs waiting for a worker", jobs_queued.clone());
+        registry.register("jobs_running", "Jobs being analysed", jobs_running.clone());
+        registry.register("analysis_seconds", "Wall time of analyses that ran to success or failure", analysis_seconds.clone());
+        registry.register("result_parse_failures", "CalculiX runs whose results could not be read", parse_failures.clone());
+        registry.register("ccx_exits", "ccx processes that exited, by exit code", ccx_exits.clone());
+        Self { registry, jobs_submitted, jobs_finished, jobs_queued, jobs_running, analysis_seconds, parse_failures, ccx_exits }
+    }
+
+    pub fn job_submitted(&self, engine: Engine) {
+        self.jobs_submitted.get_or_create(&EngineLabels { engine: format!("{:?}", engine) }).inc();
+    }
+
+    pub fn job_finished(&self, engine: Engine, status: AnalysisStatus) {
+        let labels = JobLabels { engine: format!("{:?}", engine), status: format!("{:?}", status) };
+        self.jobs_finished.get_or_create(&labels).inc();
+    }
+
+    pub fn analysis_time(&self, engine: Engine, analysis: AnalysisKind, seconds: f64) {
+        let analysis = match analysis {
+            AnalysisKind::Static => "Static",
+            AnalysisKind::Modal { .. } => "Modal",
+            AnalysisKind::Buckling { .. } => "Buckling",
+            AnalysisKind::Nonlinear { .. } => "Nonlinear",
+        };
+        let labels = AnalysisLabels { engine: format!("{:?}", engine), analysis: analysis.to_string() };
+        self.analysis_seconds.get_or_create(&labels).observe(seconds);
+    }
+
+    pub fn parse_failure(&self) {
+        self.parse_failures.inc();
+    }
+
+    pub fn ccx_exit(&self, code: Option<i32>) {
+        let code = code.map_or_else(|| "signal".to_string(), |c| c.to_string());
+        self.ccx_exits.get_or_create(&ExitLabels { code }).inc();
+    }
+
+    /// Metrics in the Prometheus text format, with the queue as it is now
+    pub fn encode(&self, queued: usize, running: usize) -> String {
+        self.jobs_queued.set(queued as i64);
+        self.jobs_running.set(running as i64);
+        let mut text = String::new();
+        prometheus_client::encoding::text::encode(&mut text, &self.registry).expect("writing to a String cannot fail");
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_metrics() {
+        let metrics = Metrics::new();
+        metrics.job_submitted(Engine::CalculiX);
+        metrics.job_finished(Engine::CalculiX, AnalysisStatus::Failed);
+        metrics.analysis_time(Engine::CalculiX, AnalysisKind::Static, 1.5);
+        metrics.ccx_exit(Some(201));
+        metrics.ccx_exit(None);
+        metrics.parse_failure();
+
+        let text = metrics.encode(3, 1);
+        for line in [
+            "calculix_jobs_submitted_total{engine=\"CalculiX\"} 1",
+            "calculix_jobs_finished_total{engine=\"CalculiX\",status=\"Failed\"} 1",
+            "calculix_jobs_queued 3",
+            "calculix_jobs_running 1",
+            "calculix_analysis_seconds_count{engine=\"CalculiX\",analysis=\"Static\"} 1",
+            "calculix_result_parse_failures_total 1",
+            "calculix_ccx_exits_total{code=\"201\"} 1",
+            "calculix_ccx_exits_total{code=\"signal\"} 1",
+        ] {
+            assert!(text.contains(line), "{} missing from\n{}", line, text);
+        }
+    }
+}
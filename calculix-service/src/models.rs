@@ -1,14 +1,181 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Load case of loads that do not name one
+pub const DEFAULT_LOAD_CASE: &str = "Case 1";
+
+fn default_load_case() -> String {
+    DEFAULT_LOAD_CASE.to_string()
+}
+
+fn default_max_increments() -> usize {
+    100
+}
 
 /// Main analysis request structure from the frameworkz app
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AnalysisRequest {
     pub model: StructuralModel,
     #[serde(default)]
     pub use_mock: bool,
+    /// Solver that runs the analysis (CalculiX when omitted)
+    #[serde(default)]
+    pub engine: Engine,
+    /// Static (when omitted), nonlinear, modal or buckling analysis
+    #[serde(default)]
+    pub analysis: AnalysisKind,
+}
+
+/// Analyses submitted together, each queued as its own job: a list of
+/// requests, or a base request and named variations of it, such as the
+/// candidate sections of a sizing sweep
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchRequest {
+    #[serde(default)]
+    pub requests: Vec<AnalysisRequest>,
+    #[serde(default)]
+    pub base: Option<AnalysisRequest>,
+    #[serde(default)]
+    pub variations: Vec<Variation>,
+}
+
+/// Changes to the base request of a batch
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Variation {
+    pub name: String,
+    /// New sections of beams, by beam id
+    #[serde(default)]
+    pub sections: Vec<SectionVariation>,
+    /// New thicknesses of shells, by shell id
+    #[serde(default)]
+    pub thicknesses: Vec<ThicknessVariation>,
+    #[serde(default)]
+    pub material: Option<Material>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SectionVariation {
+    pub beam_ids: Vec<usize>,
+    pub section: BeamSection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ThicknessVariation {
+    pub shell_ids: Vec<usize>,
+    pub thickness: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl BatchRequest {
+    /// The named requests of the batch, in order: `Request 1`, `Request 2`…
+    /// for a list, or each variation of the base applied to a copy of it
+    pub fn expand(&self) -> Result<Vec<(String, AnalysisRequest)>, String> {
+        let Some(base) = &self.base else {
+            if self.requests.is_empty() {
+                return Err("A batch needs requests, or a base request and variations of it".to_string());
+            }
+            if !self.variations.is_empty() {
+                return Err("Variations need a base request to apply to".to_string());
+            }
+            return Ok(self.requests.iter().enumerate()
+                .map(|(index, request)| (format!("Request {}", index + 1), request.clone()))
+                .collect());
+        };
+        if !self.requests.is_empty() {
+            return Err("A batch takes either requests or a base request, not both".to_string());
+        }
+        if self.variations.is_empty() {
+            return Err("A base request needs variations".to_string());
+        }
+        let mut batch = Vec::new();
+        for (index, variation) in self.variations.iter().enumerate() {
+            if self.variations[..index].iter().any(|v| v.name == variation.name) {
+                return Err(format!("Variation '{}' is defined twice", variation.name));
+            }
+            let request = variation.apply(base).map_err(|e| format!("Variation '{}': {}", variation.name, e))?;
+            batch.push((variation.name.clone(), request));
+        }
+        Ok(batch)
+    }
+}
+
+impl Variation {
+    fn apply(&self, base: &AnalysisRequest) -> Result<AnalysisRequest, String> {
+        let mut request = base.clone();
+        let model = &mut request.model;
+        for change in &self.sections {
+            for id in &change.beam_ids {
+                let beam = model.beams.iter_mut().find(|b| b.id == *id).ok_or(format!("Beam {} is not defined", id))?;
+                beam.section = change.section.clone();
+            }
+        }
+        for change in &self.thicknesses {
+            for id in &change.shell_ids {
+                let shell = model.shells.iter_mut().find(|s| s.id == *id).ok_or(format!("Shell {} is not defined", id))?;
+                shell.thickness = change.thickness;
+            }
+        }
+        if let Some(material) = &self.material {
+            model.material = material.clone();
+        }
+        Ok(request)
+    }
+}
+
+/// The jobs of a batch, in the order of its requests or variations
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchResponse {
+    pub batch_id: String,
+    pub jobs: Vec<BatchJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchJob {
+    /// Name of the variation, or `Request n`
+    pub name: String,
+    pub job: AnalysisResponse,
+}
+
+/// Solver engine selected per request
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum Engine {
+    /// External CalculiX (ccx) binary
+    #[default]
+    CalculiX,
+    /// In-process fea-solver
+    Native,
+}
+
+/// Kind of analysis to run
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum AnalysisKind {
+    /// Linear static analysis
+    #[default]
+    Static,
+    /// Natural frequencies and mode shapes
+    Modal { num_modes: usize },
+    /// Linear buckling factors of the applied loads and buckled shapes
+    Buckling { num_modes: usize },
+    /// Geometrically nonlinear static analysis (NLGEOM), for second-order
+    /// effects; the load goes on in `increments` equal increments, which
+    /// CalculiX cuts back where they do not converge, up to `max_increments`
+    /// in all
+    Nonlinear {
+        increments: usize,
+        #[serde(default = "default_max_increments")]
+        max_increments: usize,
+    },
+}
+
+impl AnalysisKind {
+    /// Whether the analysis finds the response to the applied loads, linear or not
+    pub fn is_static(&self) -> bool {
+        matches!(self, Self::Static | Self::Nonlinear { .. })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StructuralModel {
     pub nodes: Vec<Node>,
     pub beams: Vec<Beam>,
@@ -18,9 +185,122 @@ pub struct StructuralModel {
     pub point_loads: Vec<PointLoad>,
     pub distributed_loads: Vec<DistributedLoad>,
     pub pressure_loads: Vec<PressureLoad>,
+    /// Temperature changes, run with CalculiX only
+    #[serde(default)]
+    pub temperature_loads: Vec<TemperatureLoad>,
+    /// Surfaces that may come into contact, run with CalculiX only
+    #[serde(default)]
+    pub contact_pairs: Vec<ContactPair>,
+    /// Combinations of load cases to analyse, each as its own step; without
+    /// them each load case is analysed on its own
+    #[serde(default)]
+    pub load_combinations: Vec<LoadCombination>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Factored load cases analysed together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LoadCombination {
+    pub name: String,
+    /// Factor of each load case; cases left out do not act
+    pub factors: BTreeMap<String, f64>,
+}
+
+impl LoadCombination {
+    pub fn factor(&self, case: &str) -> f64 {
+        self.factors.get(case).copied().unwrap_or(0.0)
+    }
+}
+
+impl StructuralModel {
+    /// Load cases of the loads, in order of first use
+    pub fn load_cases(&self) -> Vec<&str> {
+        let mut cases: Vec<&str> = Vec::new();
+        let used = self.point_loads.iter().map(|l| l.load_case.as_str())
+            .chain(self.distributed_loads.iter().map(|l| l.load_case.as_str()))
+            .chain(self.pressure_loads.iter().map(|l| l.load_case.as_str()))
+            .chain(self.temperature_loads.iter().map(|l| l.load_case.as_str()));
+        for case in used {
+            if !cases.contains(&case) {
+                cases.push(case);
+            }
+        }
+        if cases.is_empty() {
+            cases.push(DEFAULT_LOAD_CASE);
+        }
+        cases
+    }
+
+    /// Combinations a static analysis runs, in order: the requested ones,
+    /// or each load case at a factor of one
+    pub fn combinations(&self) -> Vec<LoadCombination> {
+        if !self.load_combinations.is_empty() {
+            return self.load_combinations.clone();
+        }
+        self.load_cases().into_iter()
+            .map(|case| LoadCombination { name: case.to_string(), factors: BTreeMap::from([(case.to_string(), 1.0)]) })
+            .collect()
+    }
+
+    /// Whether results are reported per combination, rather than for a
+    /// single set of loads
+    pub fn has_combinations(&self) -> bool {
+        !self.load_combinations.is_empty() || self.load_cases().len() > 1
+    }
+
+    /// Check that combinations are named uniquely and only use load cases
+    /// of the loads
+    pub fn check_combinations(&self) -> Result<(), String> {
+        let cases = self.load_cases();
+        for (index, combination) in self.load_combinations.iter().enumerate() {
+            if combination.name.trim().is_empty() {
+                return Err(format!("Load combination {} has no name", index + 1));
+            }
+            if self.load_combinations[..index].iter().any(|c| c.name == combination.name) {
+                return Err(format!("Load combination '{}' is defined twice", combination.name));
+            }
+            if combination.factors.is_empty() {
+                return Err(format!("Load combination '{}' has no load cases", combination.name));
+            }
+            if let Some(case) = combination.factors.keys().find(|case| !cases.contains(&case.as_str())) {
+                return Err(format!("Load combination '{}' uses load case '{}', which has no loads", combination.name, case));
+            }
+        }
+        Ok(())
+    }
+
+    fn node(&self, id: usize) -> Option<&Node> {
+        // Nodes are normally stored in id order
+        self.nodes.get(id).filter(|n| n.id == id).or_else(|| self.nodes.iter().find(|n| n.id == id))
+    }
+
+    /// Unit normal of a shell by the right-hand rule on its node order,
+    /// taken across the corner diagonals of quads
+    pub fn shell_normal(&self, shell: &Shell) -> Option<[f64; 3]> {
+        let corner = |i: usize| self.node(*shell.node_ids.get(i)?).map(|n| [n.x, n.y, n.z]);
+        let sub = |a: [f64; 3], b: [f64; 3]| [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+        let (u, v) = if shell.node_ids.len() >= 4 {
+            (sub(corner(2)?, corner(0)?), sub(corner(3)?, corner(1)?))
+        } else {
+            (sub(corner(1)?, corner(0)?), sub(corner(2)?, corner(0)?))
+        };
+        let n = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+        let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        (length > 1e-12).then(|| n.map(|c| c / length))
+    }
+
+    /// Pressure on a shell along its normal, or `None` when a pressure
+    /// given along a global axis cannot act on it (the shell is parallel
+    /// to the axis)
+    pub fn shell_pressure(&self, load: &PressureLoad, shell: &Shell) -> Option<f64> {
+        let Some(direction) = load.direction else {
+            return Some(load.magnitude);
+        };
+        let component = self.shell_normal(shell)?[direction.index()];
+        (component.abs() > 1e-6).then(|| load.magnitude * component.signum())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Node {
     pub id: usize,
     pub x: f64,
@@ -28,14 +308,18 @@ pub struct Node {
     pub z: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Beam {
     pub id: usize,
     pub node_ids: Vec<usize>,  // Start and end node
     pub section: BeamSection,
+    /// Rotation of the section about the member axis (degrees), turning
+    /// local y towards local z
+    #[serde(default)]
+    pub rotation: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct BeamSection {
     pub width: f64,       // For RECT: width, For I-beam: flange width (bf)
     pub height: f64,      // For RECT: height, For I-beam: total depth (d)
@@ -44,9 +328,41 @@ pub struct BeamSection {
     pub flange_thickness: Option<f64>,  // tf for I-beam
     #[serde(default)]
     pub web_thickness: Option<f64>,     // tw for I-beam
+    #[serde(default)]
+    pub wall_thickness: Option<f64>,    // t for Box and Pipe
+    /// Properties of a General section: area (m²), second moments of area
+    /// about local y and z (m⁴) and torsion constant (m⁴)
+    #[serde(default)]
+    pub area: Option<f64>,
+    #[serde(default)]
+    pub iy: Option<f64>,
+    #[serde(default)]
+    pub iz: Option<f64>,
+    #[serde(default)]
+    pub j: Option<f64>,
+}
+
+impl BeamSection {
+    /// Reason the section cannot be built from its dimensions, if any
+    pub fn check(&self) -> Result<(), String> {
+        let positive = |value: Option<f64>| value.is_some_and(|v| v > 0.0);
+        match self.section_type {
+            SectionType::General if ![self.area, self.iy, self.iz, self.j].into_iter().all(positive) => {
+                Err("General sections need a positive area, iy, iz and j".to_string())
+            }
+            SectionType::Box | SectionType::Pipe => {
+                let smallest = if self.section_type == SectionType::Pipe { self.width } else { self.width.min(self.height) };
+                match self.wall_thickness {
+                    Some(t) if t > 0.0 && 2.0 * t < smallest => Ok(()),
+                    _ => Err(format!("{:?} sections need a wall thickness below half their width", self.section_type)),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Shell {
     pub id: usize,
     pub node_ids: Vec<usize>,
@@ -55,28 +371,37 @@ pub struct Shell {
     pub is_quadratic: bool,  // True for S8 (8-node), false for S4 (4-node)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum SectionType {
     Rectangular,
     Circular,
     IBeam,
+    /// Rectangular hollow section; height along local y, width along local z
+    Box,
+    /// Circular hollow section; width is the outer diameter
+    Pipe,
+    /// Any other profile, given by its properties
+    General,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Material {
     pub name: String,
     pub elastic_modulus: f64,  // Pa or kN/m²
     pub poisson_ratio: f64,
     pub density: f64,          // kg/m³ or kN/m³
+    /// Coefficient of thermal expansion (1/K), needed by temperature loads
+    #[serde(default)]
+    pub thermal_expansion: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Support {
     pub node_id: usize,
     pub constraint_type: SupportType,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 pub enum SupportType {
     Fixed,      // All DOFs constrained (DX, DY, DZ, DRX, DRY, DRZ)
     Pinned,     // Translations constrained, rotations free (DX, DY, DZ)
@@ -85,41 +410,101 @@ pub enum SupportType {
     RollerZ,    // X, Y constrained, Z free (DX, DY)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PointLoad {
     pub node_id: usize,
     pub fx: f64,
     pub fy: f64,
     pub fz: f64,
+    #[serde(default = "default_load_case")]
+    pub load_case: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PressureLoad {
     pub element_ids: Vec<usize>,
     pub magnitude: f64, // Pressure value (Pa)
+    /// Global axis a positive pressure pushes the shells towards, whichever
+    /// way their nodes are ordered; without it the pressure acts along each
+    /// shell's own normal
+    #[serde(default)]
+    pub direction: Option<LoadDirection>,
+    #[serde(default = "default_load_case")]
+    pub load_case: String,
+}
+
+/// Slave nodes that may press on a face of some shells, such as a baseplate
+/// bearing on its foundation; the surfaces separate freely and resist
+/// overclosure with a linear penalty stiffness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContactPair {
+    pub name: String,
+    pub slave_node_ids: Vec<usize>,
+    /// Shells of the master surface, by their position in the model as for
+    /// pressure loads
+    pub master_element_ids: Vec<usize>,
+    /// Face of the master shells the slave nodes bear on
+    #[serde(default)]
+    pub master_face: ShellFace,
+    /// Contact pressure per unit overclosure (kN/m³)
+    pub stiffness: f64,
+    /// Coulomb friction coefficient; frictionless without one
+    #[serde(default)]
+    pub friction: Option<f64>,
+}
+
+/// Side of a shell by its normal, the right-hand rule on its node order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ShellFace {
+    #[default]
+    Positive,
+    Negative,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Uniform change in temperature (K) of nodes from the stress-free state;
+/// beams and shells take it through their whole section
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TemperatureLoad {
+    pub node_ids: Vec<usize>,
+    pub temperature: f64,
+    #[serde(default = "default_load_case")]
+    pub load_case: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DistributedLoad {
     pub element_ids: Vec<usize>,
     pub load_type: LoadType,
+    #[serde(default = "default_load_case")]
+    pub load_case: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum LoadType {
     Gravity { g: f64 },
     Uniform { value: f64, direction: LoadDirection },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum LoadDirection {
     X,
     Y,
     Z,
 }
 
+impl LoadDirection {
+    /// Index of the axis: 0 = X, 1 = Y, 2 = Z
+    pub fn index(self) -> usize {
+        match self {
+            LoadDirection::X => 0,
+            LoadDirection::Y => 1,
+            LoadDirection::Z => 2,
+        }
+    }
+}
+
 /// Analysis response structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AnalysisResponse {
     pub job_id: String,
     pub status: AnalysisStatus,
@@ -128,14 +513,16 @@ pub struct AnalysisResponse {
     pub timestamp: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum AnalysisStatus {
+    Queued,
     Success,
     Failed,
     Running,
+    Cancelled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AnalysisResults {
     pub displacements: Vec<NodeDisplacement>,
     pub reactions: Vec<NodeReaction>,
@@ -145,10 +532,112 @@ pub struct AnalysisResults {
     pub max_stress: f64,
     /// Maximum beam stress (Pa) for beam elements
     pub max_beam_stress: f64,
+    /// Modes of a modal analysis, in order of increasing frequency
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<ModeResult>,
+    /// Buckling modes of a buckling analysis, lowest factor first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckling_modes: Vec<BucklingMode>,
+    /// Results of each load combination of a static analysis with several
+    /// load cases or combinations, in order; the fields above hold the first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub combinations: Vec<CombinationResults>,
+    /// Converged increments of a nonlinear analysis run with CalculiX
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub increments: Vec<Increment>,
+}
+
+impl AnalysisResults {
+    /// Results of a static analysis of `model`, one set per load
+    /// combination, listed when the model has several cases or combinations
+    pub fn from_combinations(model: &StructuralModel, mut combinations: Vec<CombinationResults>) -> Self {
+        let first = combinations.first().cloned().unwrap_or_default();
+        if !model.has_combinations() {
+            combinations.clear();
+        }
+        Self {
+            displacements: first.displacements,
+            reactions: first.reactions,
+            stresses: first.stresses,
+            beam_forces: first.beam_forces,
+            max_displacement: first.max_displacement,
+            max_stress: first.max_stress,
+            max_beam_stress: first.max_beam_stress,
+            modes: Vec::new(),
+            buckling_modes: Vec::new(),
+            combinations,
+            increments: Vec::new(),
+        }
+    }
+}
+
+/// Static results under one load combination
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CombinationResults {
+    /// Name of the combination, or of the load case analysed on its own
+    pub name: String,
+    pub displacements: Vec<NodeDisplacement>,
+    pub reactions: Vec<NodeReaction>,
+    pub stresses: Vec<NodeStress>,
+    pub beam_forces: Vec<BeamForces>,
+    pub max_displacement: f64,
+    pub max_stress: f64,
+    pub max_beam_stress: f64,
+}
+
+impl CombinationResults {
+    pub fn new(name: impl Into<String>, results: AnalysisResults) -> Self {
+        Self {
+            name: name.into(),
+            displacements: results.displacements,
+            reactions: results.reactions,
+            stresses: results.stresses,
+            beam_forces: results.beam_forces,
+            max_displacement: results.max_displacement,
+            max_stress: results.max_stress,
+            max_beam_stress: results.max_beam_stress,
+        }
+    }
+}
+
+/// One natural mode of a modal analysis
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModeResult {
+    /// Natural frequency (Hz)
+    pub frequency: f64,
+    /// Natural period (s)
+    pub period: f64,
+    /// Effective modal mass in X, Y and Z as a fraction of the free mass
+    pub mass_ratio: [f64; 3],
+    /// Mass-normalised mode shape
+    pub shape: Vec<NodeDisplacement>,
+}
+
+/// A converged load increment of a nonlinear analysis, from the ccx `.sta` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Increment {
+    /// Step, i.e. load combination, from 1
+    pub step: usize,
+    pub increment: usize,
+    /// Attempts at the increment; more than one means it was cut back
+    pub attempts: usize,
+    /// Equilibrium iterations of the converged attempt
+    pub iterations: usize,
+    /// Fraction of the step's loads applied at the end of the increment
+    pub load_factor: f64,
+}
+
+/// One buckling mode of a linear buckling analysis
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BucklingMode {
+    /// Factor on the applied loads at which the structure buckles
+    pub factor: f64,
+    /// Buckled shape, normalised by CalculiX
+    pub shape: Vec<NodeDisplacement>,
 }
 
 /// Beam section forces at stations along a beam element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct BeamForces {
     pub element_id: usize,
     /// Axial force (N) - tension positive
@@ -174,7 +663,7 @@ pub struct BeamForces {
     pub bending_stress: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct NodeDisplacement {
     pub node_id: usize,
     pub dx: f64,
@@ -185,7 +674,7 @@ pub struct NodeDisplacement {
     pub rz: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct NodeReaction {
     pub node_id: usize,
     pub fx: f64,
@@ -196,7 +685,7 @@ pub struct NodeReaction {
     pub mz: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct NodeStress {
     pub node_id: usize,
     pub von_mises: f64,
@@ -217,7 +706,6 @@ pub struct NodeStress {
 // Internal struct for parsing element stresses before averaging
 #[derive(Debug, Clone)]
 pub struct ElementStress {
-    pub element_id: usize,
     pub integration_point: usize, // 1=bottom, 2=middle, 3=top for shells
     pub von_mises: f64,
     pub sxx: f64,
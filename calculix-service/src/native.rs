@@ -0,0 +1,440 @@
+//! In-process analysis with fea-solver
+//!
+//! Builds an `FEModel` from the request model with the same unit conventions
+//! as the CalculiX input generator (forces in kN, stiffness and pressure in
+//! kN/m², density in kg/m³) and maps its results back to the response types,
+//! so either engine can serve the same requests.
+
+use std::collections::{BTreeSet, HashMap};
+
+use fea_solver::loads::LoadDirection as FeaDirection;
+use fea_solver::prelude::*;
+
+use crate::models::{
+    AnalysisKind, AnalysisResults, Beam, BeamForces, BeamSection, CombinationResults, LoadDirection, LoadType,
+    ModeResult, NodeDisplacement, NodeReaction, NodeStress, SectionType, StructuralModel, SupportType,
+};
+
+const KILO_TO_BASE: f64 = 1000.0;
+const MATERIAL: &str = "MATERIAL1";
+
+/// Runs analyses in-process with fea-solver
+pub struct NativeEngine;
+
+impl NativeEngine {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reason the model cannot be analysed natively, if any
+    pub fn check(&self, model: &StructuralModel) -> Result<(), String> {
+        if !model.temperature_loads.is_empty() {
+            return Err("Temperature loads are only available with the CalculiX engine".to_string());
+        }
+        if !model.contact_pairs.is_empty() {
+            return Err("Contact is only available with the CalculiX engine".to_string());
+        }
+        if let Some(shell) = model.shells.iter().find(|s| s.is_quadratic || s.node_ids.len() != 4) {
+            return Err(format!(
+                "Shell {} has {} nodes; the native engine supports 4-node shells only",
+                shell.id,
+                shell.node_ids.len()
+            ));
+        }
+        if let Some(beam) = model.beams.iter().find(|b| b.node_ids.len() < 2) {
+            return Err(format!("Beam {} needs a start and an end node", beam.id));
+        }
+        let ids: BTreeSet<usize> = model.nodes.iter().map(|n| n.id).collect();
+        let referenced = model.beams.iter().flat_map(|b| &b.node_ids[..2])
+            .chain(model.shells.iter().flat_map(|s| &s.node_ids))
+            .chain(model.supports.iter().map(|s| &s.node_id))
+            .chain(model.point_loads.iter().map(|l| &l.node_id));
+        for id in referenced {
+            if !ids.contains(id) {
+                return Err(format!("Node {id} is referenced but not defined"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn execute(&self, model: &StructuralModel, analysis: AnalysisKind) -> Result<AnalysisResults, String> {
+        self.check(model)?;
+        let mut fe = build_model(model).map_err(|e| e.to_string())?;
+        let mut results = AnalysisResults {
+            displacements: Vec::new(),
+            reactions: Vec::new(),
+            stresses: Vec::new(),
+            beam_forces: Vec::new(),
+            max_displacement: 0.0,
+            max_stress: 0.0,
+            max_beam_stress: 0.0,
+            modes: Vec::new(),
+            buckling_modes: Vec::new(),
+            combinations: Vec::new(),
+            increments: Vec::new(),
+        };
+
+        match analysis {
+            AnalysisKind::Static | AnalysisKind::Nonlinear { .. } => {
+                // Nonlinear analyses follow the members' large displacements
+                // (co-rotational) through the same number of equal increments
+                let options = match analysis {
+                    AnalysisKind::Nonlinear { increments, .. } => AnalysisOptions::large_displacement(increments),
+                    _ => AnalysisOptions::linear(),
+                };
+                fe.analyze(options).map_err(|e| e.to_string())?;
+                let mut combinations = Vec::new();
+                for combination in model.combinations() {
+                    let mut combination_results = results.clone();
+                    static_results(model, &fe, &combination.name, &mut combination_results).map_err(|e| e.to_string())?;
+                    combinations.push(CombinationResults::new(&combination.name, combination_results));
+                }
+                results = AnalysisResults::from_combinations(model, combinations);
+            }
+            AnalysisKind::Modal { num_modes } => {
+                let modal = fe.analyze_modal(&AnalysisOptions::modal(num_modes)).map_err(|e| e.to_string())?;
+                results.modes = modal.modes.iter()
+                    .map(|mode| ModeResult {
+                        frequency: mode.frequency,
+                        period: mode.period,
+                        mass_ratio: mode.mass_ratio,
+                        shape: mode.shape.iter()
+                            .map(|(name, d)| displacement(node_id(name), d))
+                            .collect(),
+                    })
+                    .collect();
+            }
+            AnalysisKind::Buckling { .. } => {
+                return Err("Buckling analysis is not available in the native engine".to_string());
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Node, element and section names of the request's numeric ids
+fn node_name(id: usize) -> String {
+    id.to_string()
+}
+
+fn node_id(name: &str) -> usize {
+    name.parse().expect("native model nodes are named by id")
+}
+
+fn beam_name(id: usize) -> String {
+    format!("B{id}")
+}
+
+fn shell_name(index: usize) -> String {
+    format!("S{index}")
+}
+
+/// Section properties, with the height along the member's local y axis
+/// (vertical for horizontal beams) as in the generated CalculiX sections
+fn section(section: &BeamSection) -> Section {
+    match section.section_type {
+        // fea-solver rectangles take the local y extent first
+        SectionType::Rectangular => Section::rectangular(section.height, section.width),
+        SectionType::Circular => Section::circular(section.width),
+        SectionType::IBeam => {
+            let tf = section.flange_thickness.unwrap_or(0.0108);
+            let tw = section.web_thickness.unwrap_or(0.0059);
+            // Wide flanges bend strongly about local y; the height here runs along it
+            let s = Section::wide_flange(section.height, section.width, tf, tw);
+            Section::new(s.a, s.iz, s.iy, s.j)
+        }
+        SectionType::Box => Section::box_section(section.height, section.width, section.wall_thickness.unwrap_or_default()),
+        SectionType::Pipe => Section::pipe(section.width, section.wall_thickness.unwrap_or_default()),
+        SectionType::General => Section::new(
+            section.area.unwrap_or_default(),
+            section.iy.unwrap_or_default(),
+            section.iz.unwrap_or_default(),
+            section.j.unwrap_or_default(),
+        ),
+    }
+}
+
+/// Distances from the centroid to the extreme fibres along local y and z
+fn fibre_distances(section: &BeamSection) -> (f64, f64) {
+    match section.section_type {
+        SectionType::Circular | SectionType::Pipe => (section.width / 2.0, section.width / 2.0),
+        // General sections give their extents in the same fields, when known
+        SectionType::Rectangular | SectionType::IBeam | SectionType::Box | SectionType::General => {
+            (section.height / 2.0, section.width / 2.0)
+        }
+    }
+}
+
+fn build_model(model: &StructuralModel) -> FEAResult<FEModel> {
+    let mut fe = FEModel::new();
+
+    // Only nodes attached to an element get stiffness; others (such as beam
+    // midpoint nodes) are left out rather than made singular
+    let connected: BTreeSet<usize> = model.beams.iter().flat_map(|b| b.node_ids[..2].iter().copied())
+        .chain(model.shells.iter().flat_map(|s| s.node_ids.iter().copied()))
+        .collect();
+    for node in model.nodes.iter().filter(|n| connected.contains(&n.id)) {
+        fe.add_node(&node_name(node.id), Node::new(node.x, node.y, node.z))?;
+    }
+
+    let m = &model.material;
+    fe.add_material(MATERIAL, Material::isotropic(m.elastic_modulus * KILO_TO_BASE, m.poisson_ratio, m.density))?;
+
+    for beam in &model.beams {
+        let name = beam_name(beam.id);
+        fe.add_section(&name, section(&beam.section))?;
+        let (i, j) = (node_name(beam.node_ids[0]), node_name(beam.node_ids[1]));
+        fe.add_member(&name, Member::new(&i, &j, MATERIAL, &name).with_rotation(beam.rotation.to_radians()))?;
+    }
+    for (index, shell) in model.shells.iter().enumerate() {
+        let n: Vec<String> = shell.node_ids.iter().map(|&id| node_name(id)).collect();
+        fe.add_quad(&shell_name(index), Quad::new(&n[0], &n[1], &n[2], &n[3], shell.thickness, MATERIAL))?;
+    }
+
+    for support in model.supports.iter().filter(|s| connected.contains(&s.node_id)) {
+        // Restrained DOFs match the *BOUNDARY lines of the generator
+        let support_type = match support.constraint_type {
+            SupportType::Fixed => Support::fixed(),
+            SupportType::Pinned => Support::pinned(),
+            SupportType::RollerX => Support::with_restraints(true, false, false, false, false, false),
+            SupportType::RollerY => Support::with_restraints(false, true, false, false, false, false),
+            SupportType::RollerZ => Support::with_restraints(false, false, true, false, false, false),
+        };
+        fe.add_support(&node_name(support.node_id), support_type)?;
+    }
+
+    for load in &model.point_loads {
+        let [fx, fy, fz] = [load.fx, load.fy, load.fz].map(|f| f * KILO_TO_BASE);
+        fe.add_node_load(&node_name(load.node_id), NodeLoad::new(fx, fy, fz, 0.0, 0.0, 0.0, &load.load_case))?;
+    }
+    let beams: HashMap<usize, &Beam> = model.beams.iter().map(|b| (b.id, b)).collect();
+    for load in &model.distributed_loads {
+        for id in &load.element_ids {
+            let Some(beam) = beams.get(id) else {
+                return Err(FEAError::MemberNotFound(beam_name(*id)));
+            };
+            let (w, direction) = match &load.load_type {
+                LoadType::Uniform { value, direction } => {
+                    let direction = match direction {
+                        LoadDirection::X => FeaDirection::FX,
+                        LoadDirection::Y => FeaDirection::FY,
+                        LoadDirection::Z => FeaDirection::FZ,
+                    };
+                    (value * KILO_TO_BASE, direction)
+                }
+                // Self-weight per unit length, acting in -Y
+                LoadType::Gravity { g } => (-model.material.density * section(&beam.section).a * g, FeaDirection::FY),
+            };
+            let member_load = DistributedLoad::new(w, w, 0.0, f64::INFINITY, direction, &load.load_case);
+            fe.add_member_dist_load(&beam_name(*id), member_load)?;
+        }
+    }
+    for load in &model.pressure_loads {
+        for &index in &load.element_ids {
+            let Some(shell) = model.shells.get(index) else {
+                return Err(FEAError::PlateNotFound(shell_name(index)));
+            };
+            // Plate loads act along the plate's local z, its right-hand normal
+            if let Some(pressure) = model.shell_pressure(load, shell) {
+                fe.add_plate_load(&shell_name(index), PlateLoad::new(pressure * KILO_TO_BASE, &load.load_case))?;
+            }
+        }
+    }
+
+    for combination in model.combinations() {
+        let combo = combination.factors.iter()
+            .fold(LoadCombination::new(&combination.name), |combo, (case, factor)| combo.with_case(case, *factor));
+        fe.add_load_combo(combo)?;
+    }
+    Ok(fe)
+}
+
+fn displacement(node_id: usize, d: &[f64; 6]) -> NodeDisplacement {
+    NodeDisplacement { node_id, dx: d[0], dy: d[1], dz: d[2], rx: d[3], ry: d[4], rz: d[5] }
+}
+
+/// Results of one load combination
+fn static_results(model: &StructuralModel, fe: &FEModel, combo: &str, results: &mut AnalysisResults) -> FEAResult<()> {
+    let store = fe.result_store();
+    for (_, name) in store.displacements.rows().iter() {
+        let Some(d) = store.displacements.get(name, combo) else { continue };
+        results.max_displacement = results.max_displacement.max((d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt());
+        results.displacements.push(displacement(node_id(name), &d));
+    }
+    for (_, name) in store.reactions.rows().iter() {
+        let Some(r) = store.reactions.get(name, combo) else { continue };
+        results.reactions.push(NodeReaction {
+            node_id: node_id(name),
+            fx: r[0], fy: r[1], fz: r[2], mx: r[3], my: r[4], mz: r[5],
+        });
+    }
+
+    // Forces at whichever end of each beam is more highly stressed
+    for beam in &model.beams {
+        let name = beam_name(beam.id);
+        let properties = section(&beam.section);
+        let (cy, cz) = fibre_distances(&beam.section);
+        let stresses = |f: &MemberForces| {
+            let axial = f.axial / properties.a;
+            let bending = f.moment_z.abs() * cy / properties.iz + f.moment_y.abs() * cz / properties.iy;
+            (axial, bending, axial.abs() + bending)
+        };
+        let ends = [fe.member_forces_i(&name, combo)?, fe.member_forces_j(&name, combo)?];
+        let (forces, (axial_stress, bending_stress, combined_stress)) = ends.iter()
+            .map(|f| (f, stresses(f)))
+            .max_by(|a, b| a.1.2.total_cmp(&b.1.2))
+            .unwrap();
+        results.max_beam_stress = results.max_beam_stress.max(combined_stress);
+        results.beam_forces.push(BeamForces {
+            element_id: beam.id,
+            axial_force: forces.axial,
+            shear_y: forces.shear_y,
+            shear_z: forces.shear_z,
+            moment_y: forces.moment_y,
+            moment_z: forces.moment_z,
+            torsion: forces.torsion,
+            combined_stress,
+            axial_stress,
+            bending_stress,
+        });
+    }
+
+    if !model.shells.is_empty() {
+        for (name, stress) in fe.shell_nodal_stresses(combo)? {
+            let ShellSurfaceStress { top, bottom } = stress.averaged;
+            let middle = PlateStress::from_components(
+                (top.sx + bottom.sx) / 2.0,
+                (top.sy + bottom.sy) / 2.0,
+                (top.txy + bottom.txy) / 2.0,
+            );
+            results.max_stress = results.max_stress.max(stress.averaged.max_von_mises()).max(middle.von_mises);
+            results.stresses.push(NodeStress {
+                node_id: node_id(&name),
+                von_mises: middle.von_mises,
+                von_mises_top: Some(top.von_mises),
+                von_mises_bottom: Some(bottom.von_mises),
+                sxx: Some(middle.sx),
+                syy: Some(middle.sy),
+                szz: None,
+                sxy: Some(middle.txy),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DistributedLoad, LoadCombination, Material, Node, PointLoad, Support, DEFAULT_LOAD_CASE};
+
+    fn cantilever() -> StructuralModel {
+        let section = BeamSection {
+            width: 0.1,
+            height: 0.2,
+            section_type: SectionType::Rectangular,
+            flange_thickness: None,
+            web_thickness: None,
+            wall_thickness: None,
+            area: None,
+            iy: None,
+            iz: None,
+            j: None,
+        };
+        StructuralModel {
+            nodes: vec![
+                Node { id: 0, x: 0.0, y: 0.0, z: 0.0 },
+                Node { id: 1, x: 4.0, y: 0.0, z: 0.0 },
+            ],
+            beams: vec![Beam { id: 0, node_ids: vec![0, 1], section, rotation: 0.0 }],
+            shells: Vec::new(),
+            material: Material {
+                name: "Steel".to_string(),
+                elastic_modulus: 2.1e8,
+                poisson_ratio: 0.3,
+                density: 7850.0,
+                thermal_expansion: None,
+            },
+            supports: vec![Support { node_id: 0, constraint_type: SupportType::Fixed }],
+            point_loads: vec![PointLoad { node_id: 1, fx: 0.0, fy: -10.0, fz: 0.0, load_case: DEFAULT_LOAD_CASE.to_string() }],
+            distributed_loads: Vec::new(),
+            pressure_loads: Vec::new(),
+            temperature_loads: Vec::new(),
+            contact_pairs: Vec::new(),
+            load_combinations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_static_cantilever() {
+        let results = NativeEngine::new().execute(&cantilever(), AnalysisKind::Static).unwrap();
+
+        // Tip deflection P L^3 / 3 E I about the strong axis, with kN and kN/m² inputs
+        let i = 0.1 * 0.2_f64.powi(3) / 12.0;
+        let expected = -10e3 * 64.0 / (3.0 * 2.1e11 * i);
+        let tip = results.displacements.iter().find(|d| d.node_id == 1).unwrap();
+        assert!((tip.dy - expected).abs() < 1e-9 * expected.abs());
+        let base = &results.reactions[0];
+        assert!((base.fy - 10e3).abs() < 1e-6 && (base.mz - 40e3).abs() < 1e-6);
+
+        // Fixed-end bending stress M c / I
+        let beam = &results.beam_forces[0];
+        assert!((beam.bending_stress - 40e3 * 0.1 / i).abs() < 1e-3);
+        assert_eq!(results.max_beam_stress, beam.combined_stress);
+    }
+
+    #[test]
+    fn test_load_combinations() {
+        let mut model = cantilever();
+        model.point_loads.push(PointLoad { node_id: 1, fx: 0.0, fy: 0.0, fz: 5.0, load_case: "Wind".to_string() });
+        model.load_combinations = vec![
+            LoadCombination { name: "ULS".to_string(), factors: [(DEFAULT_LOAD_CASE.to_string(), 1.5)].into() },
+            LoadCombination { name: "Wind".to_string(), factors: [("Wind".to_string(), 1.0)].into() },
+        ];
+        let single = NativeEngine::new().execute(&cantilever(), AnalysisKind::Static).unwrap();
+        let results = NativeEngine::new().execute(&model, AnalysisKind::Static).unwrap();
+
+        let names: Vec<&str> = results.combinations.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["ULS", "Wind"]);
+        let tip = |displacements: &[NodeDisplacement]| displacements.iter().find(|d| d.node_id == 1).unwrap().clone();
+        let (uls, wind) = (tip(&results.combinations[0].displacements), tip(&results.combinations[1].displacements));
+        assert!((uls.dy - 1.5 * tip(&single.displacements).dy).abs() < 1e-12 && uls.dz == 0.0);
+        assert!(wind.dy == 0.0 && wind.dz > 0.0);
+        // The top-level results are those of the first combination
+        assert_eq!(tip(&results.displacements).dy, uls.dy);
+        assert!(single.combinations.is_empty());
+    }
+
+    #[test]
+    fn test_nonlinear_amplifies_sway() {
+        // The cantilever in four members under axial compression P, whose tip
+        // deflection grows by 3 (tan u - u) / u^3 with u = L sqrt(P / EI)
+        let mut model = cantilever();
+        let template = model.beams[0].clone();
+        model.nodes = (0..=4).map(|id| Node { id, x: id as f64, y: 0.0, z: 0.0 }).collect();
+        model.beams = (0..4).map(|id| Beam { id, node_ids: vec![id, id + 1], ..template.clone() }).collect();
+        model.point_loads[0] = PointLoad { node_id: 4, fx: -500.0, ..model.point_loads[0].clone() };
+        let linear = NativeEngine::new().execute(&model, AnalysisKind::Static).unwrap();
+        let nonlinear = NativeEngine::new()
+            .execute(&model, AnalysisKind::Nonlinear { increments: 10, max_increments: 100 })
+            .unwrap();
+        let tip = |results: &AnalysisResults| results.displacements.iter().find(|d| d.node_id == 4).unwrap().dy;
+        let u = 4.0 * (500e3 / (2.1e11 * 0.1 * 0.2_f64.powi(3) / 12.0)).sqrt();
+        let expected = 3.0 * (u.tan() - u) / u.powi(3);
+        let amplification = tip(&nonlinear) / tip(&linear);
+        assert!((amplification - expected).abs() < 0.02 * expected, "{} vs {}", amplification, expected);
+        assert!(nonlinear.increments.is_empty());
+    }
+
+    #[test]
+    fn test_modal_and_unsupported_models() {
+        let mut model = cantilever();
+        model.distributed_loads.push(DistributedLoad { element_ids: vec![0], load_type: LoadType::Gravity { g: 9.81 }, load_case: DEFAULT_LOAD_CASE.to_string() });
+        let results = NativeEngine::new().execute(&model, AnalysisKind::Modal { num_modes: 2 }).unwrap();
+        assert_eq!(results.modes.len(), 2);
+        assert!(results.modes[0].frequency > 0.0 && results.displacements.is_empty());
+
+        model.beams[0].node_ids = vec![0, 5];
+        assert_eq!(NativeEngine::new().check(&model).unwrap_err(), "Node 5 is referenced but not defined");
+    }
+}
@@ -0,0 +1,155 @@
+//! Live progress of a running analysis
+//!
+//! Each job records the phases it goes through and, for CalculiX, every line
+//! ccx prints, parsed into step/increment/iteration events where possible.
+//! Clients follow a job through `GET /api/v1/jobs/{id}/events`, which
+//! replays the events so far and then streams new ones as Server-Sent Events.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use tokio::sync::broadcast;
+
+use crate::models::AnalysisStatus;
+
+/// Events kept for clients that connect after the job started; older
+/// solver output is dropped first
+const MAX_HISTORY: usize = 5000;
+
+/// Live events buffered per subscriber before slow clients miss some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Stage of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Queued,
+    Generating,
+    Solving,
+    Parsing,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// The job moved to a new stage
+    Phase { phase: Phase },
+    /// A line of solver output
+    Log { line: String },
+    /// CalculiX started a step
+    Step { step: usize },
+    /// CalculiX started a load increment of the current step
+    Increment { increment: usize, attempt: usize },
+    /// CalculiX started an equilibrium iteration of the current increment
+    Iteration { iteration: usize },
+    /// The job finished; always the last event
+    Finished { status: AnalysisStatus, error_message: Option<String> },
+}
+
+impl ProgressEvent {
+    /// The step/increment/iteration event a line of ccx output announces
+    ///
+    /// ccx prints `STEP 1`, `increment 2 attempt 1` and `iteration 3` on
+    /// lines of their own as a nonlinear analysis advances.
+    pub fn from_ccx_line(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        let keyword = words.next()?;
+        let mut number = || words.next().and_then(|w| w.parse::<usize>().ok());
+        match keyword {
+            "STEP" => Some(Self::Step { step: number()? }),
+            "increment" => {
+                let increment = number()?;
+                let attempt = match words.next() {
+                    Some("attempt") => words.next().and_then(|w| w.parse().ok()).unwrap_or(1),
+                    _ => 1,
+                };
+                Some(Self::Increment { increment, attempt })
+            }
+            "iteration" => Some(Self::Iteration { iteration: number()? }),
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Finished { .. })
+    }
+}
+
+/// The events of one job, with a channel for clients following it live
+pub struct ProgressLog {
+    history: Mutex<VecDeque<ProgressEvent>>,
+    sender: broadcast::Sender<ProgressEvent>,
+}
+
+impl Default for ProgressLog {
+    fn default() -> Self {
+        Self {
+            history: Mutex::new(VecDeque::new()),
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl ProgressLog {
+    pub fn emit(&self, event: ProgressEvent) {
+        // Sent while holding the history, so a subscriber sees each event once
+        let mut history = self.history.lock().unwrap();
+        if history.len() >= MAX_HISTORY {
+            let oldest_log = history.iter().position(|e| matches!(e, ProgressEvent::Log { .. }));
+            history.remove(oldest_log.unwrap_or(0));
+        }
+        history.push_back(event.clone());
+        let _ = self.sender.send(event);
+    }
+
+    pub fn phase(&self, phase: Phase) {
+        self.emit(ProgressEvent::Phase { phase });
+    }
+
+    /// Record a line of ccx output, followed by the progress it announces
+    pub fn ccx_line(&self, line: &str) {
+        self.emit(ProgressEvent::Log { line: line.to_string() });
+        if let Some(event) = ProgressEvent::from_ccx_line(line) {
+            self.emit(event);
+        }
+    }
+
+    /// The events so far, and a receiver for those still to come
+    pub fn subscribe(&self) -> (Vec<ProgressEvent>, broadcast::Receiver<ProgressEvent>) {
+        let history = self.history.lock().unwrap();
+        (history.iter().cloned().collect(), self.sender.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ccx_progress() {
+        assert_eq!(ProgressEvent::from_ccx_line(" STEP            1"), Some(ProgressEvent::Step { step: 1 }));
+        assert_eq!(
+            ProgressEvent::from_ccx_line(" increment 3 attempt 2 "),
+            Some(ProgressEvent::Increment { increment: 3, attempt: 2 })
+        );
+        assert_eq!(ProgressEvent::from_ccx_line(" iteration 4"), Some(ProgressEvent::Iteration { iteration: 4 }));
+        assert_eq!(ProgressEvent::from_ccx_line(" increment size= 1.000000e+00"), None);
+        assert_eq!(ProgressEvent::from_ccx_line(" Job finished"), None);
+    }
+
+    #[test]
+    fn test_late_subscribers_get_history() {
+        let log = ProgressLog::default();
+        log.phase(Phase::Solving);
+        log.ccx_line(" STEP 1");
+
+        let (history, mut live) = log.subscribe();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2], ProgressEvent::Step { step: 1 });
+
+        log.emit(ProgressEvent::Finished { status: AnalysisStatus::Success, error_message: None });
+        assert!(live.try_recv().unwrap().is_finished());
+    }
+}
@@ -0,0 +1,324 @@
+//! SQLite store of submitted jobs
+//!
+//! Every job's request, status and results are written through to the
+//! store, so job history survives restarts of the service and can be listed
+//! and filtered. Jobs that were queued or running when the service stopped
+//! are marked failed when the store is opened again. Each job records the
+//! name of the API key it was submitted with, and lookups only find the
+//! jobs of the given owner.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::{AnalysisKind, AnalysisRequest, AnalysisResponse, AnalysisStatus, Engine};
+
+/// Jobs listed when a query gives no limit, and the most it may ask for
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS jobs (
+    id TEXT PRIMARY KEY,
+    status TEXT NOT NULL,
+    engine TEXT NOT NULL,
+    analysis TEXT NOT NULL,
+    request TEXT NOT NULL,
+    results TEXT,
+    error_message TEXT,
+    submitted_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    owner TEXT
+);
+CREATE INDEX IF NOT EXISTS jobs_submitted_at ON jobs (submitted_at);
+";
+
+pub struct JobStore {
+    conn: Mutex<Connection>,
+}
+
+/// Filter of `GET /api/v1/jobs`
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobQuery {
+    /// A status, e.g. `running`, or `done` for any finished job
+    pub status: Option<String>,
+    /// Only jobs submitted at or after this RFC 3339 time
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// A stored job without its model and results
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub status: AnalysisStatus,
+    pub engine: Engine,
+    pub analysis: AnalysisKind,
+    pub error_message: Option<String>,
+    pub submitted_at: String,
+    pub updated_at: String,
+}
+
+impl JobStore {
+    /// Open (or create) the store in a file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// A store that lasts as long as the service
+    pub fn in_memory() -> Result<Self, StoreError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch(SCHEMA)?;
+        // Stores written before jobs had owners lack the column; their jobs
+        // are left without one
+        let has_owner = conn.prepare("SELECT 1 FROM pragma_table_info('jobs') WHERE name = 'owner'")?.exists([])?;
+        if !has_owner {
+            conn.execute_batch("ALTER TABLE jobs ADD COLUMN owner TEXT")?;
+        }
+        let interrupted = conn.execute(
+            "UPDATE jobs SET status = ?1, error_message = ?2, updated_at = ?3 WHERE status IN (?4, ?5)",
+            params![
+                status_name(AnalysisStatus::Failed),
+                "Interrupted by a restart of the service",
+                chrono::Utc::now().to_rfc3339(),
+                status_name(AnalysisStatus::Queued),
+                status_name(AnalysisStatus::Running),
+            ],
+        )?;
+        if interrupted > 0 {
+            tracing::warn!("Marked {} interrupted jobs as failed", interrupted);
+        }
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Record a newly submitted job of `owner`, the name of the key it was
+    /// submitted with, if any
+    pub fn insert(&self, response: &AnalysisResponse, request: &AnalysisRequest, owner: Option<&str>) -> Result<(), StoreError> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO jobs (id, status, engine, analysis, request, submitted_at, updated_at, owner)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7)",
+            params![
+                response.job_id,
+                status_name(response.status),
+                serde_json::to_string(&request.engine)?,
+                serde_json::to_string(&request.analysis)?,
+                serde_json::to_string(request)?,
+                response.timestamp,
+                owner,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Save a job's new status, with its results or error
+    pub fn update(&self, response: &AnalysisResponse) -> Result<(), StoreError> {
+        let results = response.results.as_ref().map(serde_json::to_string).transpose()?;
+        self.conn.lock().unwrap().execute(
+            "UPDATE jobs SET status = ?2, results = ?3, error_message = ?4, updated_at = ?5 WHERE id = ?1",
+            params![response.job_id, status_name(response.status), results, response.error_message, response.timestamp],
+        )?;
+        Ok(())
+    }
+
+    /// A job of `owner`
+    pub fn get(&self, id: &str, owner: Option<&str>) -> Result<Option<AnalysisResponse>, StoreError> {
+        let row = self.conn.lock().unwrap().query_row(
+            "SELECT status, results, error_message, updated_at FROM jobs WHERE id = ?1 AND owner IS ?2",
+            params![id, owner],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get(2)?, row.get(3)?)),
+        ).optional()?;
+        let Some((status, results, error_message, timestamp)) = row else {
+            return Ok(None);
+        };
+        Ok(Some(AnalysisResponse {
+            job_id: id.to_string(),
+            status: parse_status(&status)?,
+            results: results.map(|r| serde_json::from_str(&r)).transpose()?,
+            error_message,
+            timestamp,
+        }))
+    }
+
+    /// The request a job of `owner` was submitted with
+    pub fn request(&self, id: &str, owner: Option<&str>) -> Result<Option<AnalysisRequest>, StoreError> {
+        let request: Option<String> = self.conn.lock().unwrap()
+            .query_row("SELECT request FROM jobs WHERE id = ?1 AND owner IS ?2", params![id, owner], |row| row.get(0))
+            .optional()?;
+        Ok(request.map(|r| serde_json::from_str(&r)).transpose()?)
+    }
+
+    /// Jobs of `owner` matching `query`, most recently submitted first
+    pub fn list(&self, query: &JobQuery, owner: Option<&str>) -> Result<Vec<JobSummary>, StoreError> {
+        let statuses: Vec<AnalysisStatus> = match query.status.as_deref() {
+            None => Vec::new(),
+            Some("done") => vec![AnalysisStatus::Success, AnalysisStatus::Failed, AnalysisStatus::Cancelled],
+            Some(status) => vec![parse_status(status)?],
+        };
+        let since = query.since.as_deref()
+            .map(|since| {
+                chrono::DateTime::parse_from_rfc3339(since)
+                    .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339())
+                    .map_err(|_| StoreError::Query(format!("'{}' is not an RFC 3339 time", since)))
+            })
+            .transpose()?;
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+        // Statuses are a fixed set of names, so they are safe to inline
+        let mut sql = "SELECT id, status, engine, analysis, error_message, submitted_at, updated_at FROM jobs WHERE submitted_at >= ?1 AND owner IS ?3".to_string();
+        if !statuses.is_empty() {
+            let names: Vec<String> = statuses.iter().map(|s| format!("'{}'", status_name(*s))).collect();
+            sql.push_str(&format!(" AND status IN ({})", names.join(", ")));
+        }
+        sql.push_str(" ORDER BY submitted_at DESC LIMIT ?2");
+
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(&sql)?;
+        let rows = statement.query_map(params![since.unwrap_or_default(), limit as i64, owner], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+        rows.map(|row| {
+            let (job_id, status, engine, analysis, error_message, submitted_at, updated_at) = row?;
+            Ok(JobSummary {
+                job_id,
+                status: parse_status(&status)?,
+                engine: serde_json::from_str(&engine)?,
+                analysis: serde_json::from_str(&analysis)?,
+                error_message,
+                submitted_at,
+                updated_at,
+            })
+        })
+        .collect()
+    }
+}
+
+fn status_name(status: AnalysisStatus) -> String {
+    format!("{:?}", status)
+}
+
+/// Status by its name, in any case
+fn parse_status(name: &str) -> Result<AnalysisStatus, StoreError> {
+    [
+        AnalysisStatus::Queued,
+        AnalysisStatus::Running,
+        AnalysisStatus::Success,
+        AnalysisStatus::Failed,
+        AnalysisStatus::Cancelled,
+    ]
+    .into_iter()
+    .find(|s| status_name(*s).eq_ignore_ascii_case(name))
+    .ok_or_else(|| StoreError::Query(format!("Unknown status '{}'", name)))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error("Job store error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Invalid stored job: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("{0}")]
+    Query(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> AnalysisRequest {
+        serde_json::from_str(include_str!("../portal_frame_request.json")).unwrap()
+    }
+
+    fn response(id: &str, status: AnalysisStatus, timestamp: &str) -> AnalysisResponse {
+        AnalysisResponse {
+            job_id: id.to_string(),
+            status,
+            results: None,
+            error_message: None,
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_store_and_query_jobs() {
+        let store = JobStore::in_memory().unwrap();
+        store.insert(&response("a", AnalysisStatus::Queued, "2026-01-01T00:00:00+00:00"), &request(), None).unwrap();
+        store.insert(&response("b", AnalysisStatus::Queued, "2026-02-01T00:00:00+00:00"), &request(), None).unwrap();
+        let mut failed = response("b", AnalysisStatus::Failed, "2026-02-01T00:01:00+00:00");
+        failed.error_message = Some("singular".to_string());
+        store.update(&failed).unwrap();
+
+        let b = store.get("b", None).unwrap().unwrap();
+        assert_eq!(b.status, AnalysisStatus::Failed);
+        assert_eq!(b.error_message.as_deref(), Some("singular"));
+        assert!(store.get("c", None).unwrap().is_none());
+        assert_eq!(store.request("a", None).unwrap().unwrap().model.nodes.len(), request().model.nodes.len());
+
+        let ids = |query: JobQuery| -> Vec<String> { store.list(&query, None).unwrap().into_iter().map(|j| j.job_id).collect() };
+        assert_eq!(ids(JobQuery::default()), ["b", "a"]);
+        assert_eq!(ids(JobQuery { status: Some("done".into()), ..JobQuery::default() }), ["b"]);
+        assert_eq!(ids(JobQuery { status: Some("queued".into()), ..JobQuery::default() }), ["a"]);
+        assert_eq!(ids(JobQuery { since: Some("2026-01-15T00:00:00Z".into()), ..JobQuery::default() }), ["b"]);
+        assert_eq!(ids(JobQuery { limit: Some(1), ..JobQuery::default() }), ["b"]);
+        assert!(store.list(&JobQuery { status: Some("bogus".into()), ..JobQuery::default() }, None).is_err());
+        assert!(store.list(&JobQuery { since: Some("yesterday".into()), ..JobQuery::default() }, None).is_err());
+    }
+
+    #[test]
+    fn test_jobs_are_only_found_by_their_owner() {
+        let store = JobStore::in_memory().unwrap();
+        store.insert(&response("a", AnalysisStatus::Queued, "2026-01-01T00:00:00+00:00"), &request(), Some("alice")).unwrap();
+        store.insert(&response("b", AnalysisStatus::Queued, "2026-02-01T00:00:00+00:00"), &request(), Some("bob")).unwrap();
+
+        assert!(store.get("a", Some("alice")).unwrap().is_some());
+        assert!(store.get("a", Some("bob")).unwrap().is_none());
+        assert!(store.get("a", None).unwrap().is_none());
+        assert!(store.request("a", Some("bob")).unwrap().is_none());
+        let ids: Vec<String> = store.list(&JobQuery::default(), Some("bob")).unwrap().into_iter().map(|j| j.job_id).collect();
+        assert_eq!(ids, ["b"]);
+    }
+
+    #[test]
+    fn test_stores_without_owners_are_migrated() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("jobs.sqlite");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(&SCHEMA.replace(",\n    owner TEXT", "")).unwrap();
+        conn.execute(
+            "INSERT INTO jobs (id, status, engine, analysis, request, submitted_at, updated_at) VALUES ('a', 'Success', '\"Native\"', '\"Static\"', ?1, 't', 't')",
+            [serde_json::to_string(&request()).unwrap()],
+        ).unwrap();
+        drop(conn);
+
+        let store = JobStore::open(&path).unwrap();
+        assert!(store.get("a", None).unwrap().is_some());
+        store.insert(&response("b", AnalysisStatus::Queued, "2026-02-01T00:00:00+00:00"), &request(), Some("alice")).unwrap();
+        assert!(store.get("b", Some("alice")).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_unfinished_jobs_fail_on_reopen() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("jobs.sqlite");
+        let store = JobStore::open(&path).unwrap();
+        store.insert(&response("a", AnalysisStatus::Running, "2026-01-01T00:00:00+00:00"), &request(), None).unwrap();
+        drop(store);
+
+        let store = JobStore::open(&path).unwrap();
+        assert_eq!(store.get("a", None).unwrap().unwrap().status, AnalysisStatus::Failed);
+    }
+}
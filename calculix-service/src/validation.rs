@@ -0,0 +1,295 @@
+//! Checks of submitted models before they reach an engine
+//!
+//! A malformed model (a beam on a missing node, a NaN coordinate, a shell
+//! with five nodes) would otherwise fail deep inside ccx or the native
+//! solver, if it failed at all. Every problem found is reported as a
+//! [`ModelIssue`] naming the entity at fault, and the API answers 422 with
+//! the full list. Service-wide size caps, set per deployment, keep models
+//! that are too large to analyse off the queue whatever the client's quota.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::{LoadType, StructuralModel};
+
+/// Issues reported at most, so a broken mesh does not flood the response
+const MAX_ISSUES: usize = 100;
+
+/// A problem with one entity of a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ModelIssue {
+    /// Kind of entity: `model`, `node`, `beam`, `shell`, `material`,
+    /// `support`, `point_load`, `distributed_load`, `pressure_load`,
+    /// `temperature_load`, `contact_pair` or `load_combination`
+    pub entity: String,
+    /// Id of the node or element, or index of the support, load, contact
+    /// pair or combination
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub message: String,
+}
+
+impl ModelIssue {
+    fn new(entity: &str, id: Option<usize>, message: impl Into<String>) -> Self {
+        Self { entity: entity.to_string(), id, message: message.into() }
+    }
+}
+
+/// Service-wide caps on the size of a model
+#[derive(Debug, Clone, Copy)]
+pub struct ModelLimits {
+    pub max_nodes: usize,
+    /// Beams and shells together
+    pub max_elements: usize,
+}
+
+impl Default for ModelLimits {
+    fn default() -> Self {
+        Self { max_nodes: 200_000, max_elements: 200_000 }
+    }
+}
+
+impl ModelLimits {
+    /// Every problem with `model`, or none if it can be analysed
+    pub fn check(&self, model: &StructuralModel) -> Result<(), Vec<ModelIssue>> {
+        let mut issues = Issues::default();
+        self.check_size(model, &mut issues);
+        // Entity checks of an oversized model would only waste time
+        if issues.list.is_empty() {
+            check_nodes(model, &mut issues);
+            check_elements(model, &mut issues);
+            check_material(model, &mut issues);
+            check_supports_and_loads(model, &mut issues);
+            if let Err(message) = model.check_combinations() {
+                issues.push("load_combination", None, message);
+            }
+        }
+        if issues.list.is_empty() {
+            Ok(())
+        } else {
+            Err(issues.list)
+        }
+    }
+
+    fn check_size(&self, model: &StructuralModel, issues: &mut Issues) {
+        let elements = model.beams.len() + model.shells.len();
+        if model.nodes.len() > self.max_nodes {
+            issues.push("model", None, format!("Model has {} nodes; the service takes at most {}", model.nodes.len(), self.max_nodes));
+        }
+        if elements > self.max_elements {
+            issues.push("model", None, format!("Model has {} elements; the service takes at most {}", elements, self.max_elements));
+        }
+        if model.nodes.is_empty() {
+            issues.push("model", None, "Model must have at least one node");
+        }
+        if elements == 0 {
+            issues.push("model", None, "Model must include at least one beam or shell");
+        }
+        if model.supports.is_empty() {
+            issues.push("model", None, "Model must have at least one support");
+        }
+    }
+}
+
+#[derive(Default)]
+struct Issues {
+    list: Vec<ModelIssue>,
+}
+
+impl Issues {
+    fn push(&mut self, entity: &str, id: Option<usize>, message: impl Into<String>) {
+        if self.list.len() < MAX_ISSUES {
+            self.list.push(ModelIssue::new(entity, id, message));
+        }
+    }
+}
+
+fn check_nodes(model: &StructuralModel, issues: &mut Issues) {
+    let mut seen = HashSet::new();
+    for node in &model.nodes {
+        if !seen.insert(node.id) {
+            issues.push("node", Some(node.id), format!("Node id {} is used more than once", node.id));
+        }
+        if ![node.x, node.y, node.z].iter().all(|c| c.is_finite()) {
+            issues.push("node", Some(node.id), "Coordinates must be finite numbers");
+        }
+    }
+}
+
+fn check_elements(model: &StructuralModel, issues: &mut Issues) {
+    let nodes: HashMap<usize, [f64; 3]> = model.nodes.iter().map(|n| (n.id, [n.x, n.y, n.z])).collect();
+    let missing = |ids: &[usize]| ids.iter().find(|id| !nodes.contains_key(id)).copied();
+
+    let mut seen = HashSet::new();
+    for beam in &model.beams {
+        let id = Some(beam.id);
+        if !seen.insert(beam.id) {
+            issues.push("beam", id, format!("Beam id {} is used more than once", beam.id));
+        }
+        if !(2..=3).contains(&beam.node_ids.len()) {
+            issues.push("beam", id, format!("Beams need 2 or 3 nodes, not {}", beam.node_ids.len()));
+        } else if let Some(node) = missing(&beam.node_ids) {
+            issues.push("beam", id, format!("Node {} is not defined", node));
+        } else if nodes[&beam.node_ids[0]] == nodes[&beam.node_ids[1]] {
+            issues.push("beam", id, "Start and end nodes are at the same point");
+        }
+        if let Err(e) = beam.section.check() {
+            issues.push("beam", id, e);
+        }
+        let dimensions = [beam.section.width, beam.section.height, beam.rotation];
+        if !dimensions.iter().all(|d| d.is_finite()) || beam.section.width <= 0.0 || beam.section.height <= 0.0 {
+            issues.push("beam", id, "Section width and height must be positive and the rotation finite");
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for shell in &model.shells {
+        let id = Some(shell.id);
+        if !seen.insert(shell.id) {
+            issues.push("shell", id, format!("Shell id {} is used more than once", shell.id));
+        }
+        if ![3, 4, 8].contains(&shell.node_ids.len()) || (shell.is_quadratic && shell.node_ids.len() != 8) {
+            issues.push("shell", id, format!("Shells need 3, 4 or (quadratic) 8 nodes, not {}", shell.node_ids.len()));
+        } else if let Some(node) = missing(&shell.node_ids) {
+            issues.push("shell", id, format!("Node {} is not defined", node));
+        } else if shell.node_ids.iter().collect::<HashSet<_>>().len() < shell.node_ids.len() {
+            issues.push("shell", id, "A node is used more than once");
+        }
+        if !(shell.thickness.is_finite() && shell.thickness > 0.0) {
+            issues.push("shell", id, "Thickness must be positive");
+        }
+    }
+}
+
+/// Every element takes the model's one material, so it must be usable
+fn check_material(model: &StructuralModel, issues: &mut Issues) {
+    let m = &model.material;
+    if m.name.trim().is_empty() {
+        issues.push("material", None, "Material needs a name");
+    }
+    if !(m.elastic_modulus.is_finite() && m.elastic_modulus > 0.0) {
+        issues.push("material", None, "Elastic modulus must be positive");
+    }
+    if !(m.poisson_ratio > -1.0 && m.poisson_ratio < 0.5) {
+        issues.push("material", None, "Poisson's ratio must lie between -1 and 0.5");
+    }
+    if !(m.density.is_finite() && m.density >= 0.0) {
+        issues.push("material", None, "Density must not be negative");
+    }
+    match m.thermal_expansion {
+        Some(alpha) if !alpha.is_finite() => issues.push("material", None, "Thermal expansion must be a finite number"),
+        None if !model.temperature_loads.is_empty() => {
+            issues.push("material", None, "Temperature loads need the material's thermal expansion")
+        }
+        _ => {}
+    }
+}
+
+fn check_supports_and_loads(model: &StructuralModel, issues: &mut Issues) {
+    let nodes: HashSet<usize> = model.nodes.iter().map(|n| n.id).collect();
+    let beams: HashSet<usize> = model.beams.iter().map(|b| b.id).collect();
+
+    for (index, support) in model.supports.iter().enumerate() {
+        if !nodes.contains(&support.node_id) {
+            issues.push("support", Some(index), format!("Node {} is not defined", support.node_id));
+        }
+    }
+    for (index, load) in model.point_loads.iter().enumerate() {
+        if !nodes.contains(&load.node_id) {
+            issues.push("point_load", Some(index), format!("Node {} is not defined", load.node_id));
+        }
+        if ![load.fx, load.fy, load.fz].iter().all(|f| f.is_finite()) {
+            issues.push("point_load", Some(index), "Forces must be finite numbers");
+        }
+    }
+    for (index, load) in model.distributed_loads.iter().enumerate() {
+        if let Some(id) = load.element_ids.iter().find(|id| !beams.contains(id)) {
+            issues.push("distributed_load", Some(index), format!("Beam {} is not defined", id));
+        }
+        let value = match load.load_type {
+            LoadType::Gravity { g } => g,
+            LoadType::Uniform { value, .. } => value,
+        };
+        if !value.is_finite() {
+            issues.push("distributed_load", Some(index), "Load must be a finite number");
+        }
+    }
+    for (index, load) in model.pressure_loads.iter().enumerate() {
+        // Pressure loads refer to shells by their position in the model
+        if let Some(i) = load.element_ids.iter().find(|&&i| i >= model.shells.len()) {
+            issues.push("pressure_load", Some(index), format!("Shell {} is not defined", i));
+        }
+        if !load.magnitude.is_finite() {
+            issues.push("pressure_load", Some(index), "Pressure must be a finite number");
+        }
+    }
+    for (index, pair) in model.contact_pairs.iter().enumerate() {
+        let id = Some(index);
+        if pair.slave_node_ids.is_empty() || pair.master_element_ids.is_empty() {
+            issues.push("contact_pair", id, "Contact pairs need slave nodes and master shells");
+        }
+        if let Some(node) = pair.slave_node_ids.iter().find(|node| !nodes.contains(node)) {
+            issues.push("contact_pair", id, format!("Node {} is not defined", node));
+        }
+        if let Some(i) = pair.master_element_ids.iter().find(|&&i| i >= model.shells.len()) {
+            issues.push("contact_pair", id, format!("Shell {} is not defined", i));
+        }
+        if !(pair.stiffness.is_finite() && pair.stiffness > 0.0) {
+            issues.push("contact_pair", id, "Contact stiffness must be positive");
+        }
+        if pair.friction.is_some_and(|mu| !(mu.is_finite() && mu >= 0.0)) {
+            issues.push("contact_pair", id, "Friction coefficient must not be negative");
+        }
+    }
+    for (index, load) in model.temperature_loads.iter().enumerate() {
+        if let Some(id) = load.node_ids.iter().find(|id| !nodes.contains(id)) {
+            issues.push("temperature_load", Some(index), format!("Node {} is not defined", id));
+        }
+        if !load.temperature.is_finite() {
+            issues.push("temperature_load", Some(index), "Temperature must be a finite number");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AnalysisRequest, Node, PointLoad};
+
+    fn portal() -> StructuralModel {
+        serde_json::from_str::<AnalysisRequest>(include_str!("../portal_frame_request.json")).unwrap().model
+    }
+
+    #[test]
+    fn test_reports_every_issue() {
+        let limits = ModelLimits::default();
+        assert_eq!(limits.check(&portal()), Ok(()));
+
+        let mut model = portal();
+        model.nodes[1].y = f64::NAN;
+        model.nodes.push(Node { id: 0, x: 9.0, y: 0.0, z: 0.0 });
+        model.beams[0].node_ids = vec![0, 99];
+        model.material.poisson_ratio = 0.5;
+        model.point_loads.push(PointLoad { node_id: 42, fx: 1.0, fy: 0.0, fz: 0.0, load_case: "Case 1".to_string() });
+        let issues = limits.check(&model).unwrap_err();
+        let found: Vec<(&str, Option<usize>)> = issues.iter().map(|i| (i.entity.as_str(), i.id)).collect();
+        assert_eq!(found, [
+            ("node", Some(1)),
+            ("node", Some(0)),
+            ("beam", Some(model.beams[0].id)),
+            ("material", None),
+            ("point_load", Some(0)),
+        ]);
+        assert_eq!(issues[2].message, "Node 99 is not defined");
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let limits = ModelLimits { max_nodes: 3, max_elements: 10 };
+        let issues = limits.check(&portal()).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "Model has 4 nodes; the service takes at most 3");
+    }
+}
@@ -0,0 +1,266 @@
+//! Command-line runner for fea-solver models
+//!
+//! Reads a model saved as JSON or written in the TOML format, runs the
+//! requested analysis and writes the results as tables, CSV, JSON or a
+//! Markdown/HTML report. With `--check` the results are compared against a
+//! reference written earlier by `--format json`, and the exit status tells a
+//! CI job whether the model still gives the same answers:
+//!
+//! - 0: analysis done (and, with `--check`, every value matched)
+//! - 1: `--check` found differences
+//! - 2: the model could not be read or analysed
+
+mod results;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use fea_solver::prelude::*;
+use fea_solver::report;
+
+use results::ResultSet;
+
+/// Analyse a fea-solver model and write its results
+#[derive(Debug, Parser)]
+#[command(name = "fea-cli", version, about)]
+struct Cli {
+    /// Model file: `.json` as saved by the solver, or `.toml`
+    model: PathBuf,
+
+    /// Analysis to run
+    #[arg(short, long, value_enum, default_value_t = Analysis::Linear)]
+    analysis: Analysis,
+
+    /// Number of modes to find; runs a modal analysis as well as the
+    /// static one (10 when `--analysis modal` is given without it)
+    #[arg(long)]
+    modes: Option<usize>,
+
+    /// Load increments of a large displacement analysis
+    #[arg(long, default_value_t = 10)]
+    steps: usize,
+
+    /// Only analyse and report this combination (repeatable)
+    #[arg(short, long = "combo", value_name = "NAME")]
+    combos: Vec<String>,
+
+    /// Only analyse combinations with this tag (repeatable)
+    #[arg(short, long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Linear solver backend
+    #[arg(long, value_enum, default_value_t = Solver::Auto)]
+    solver: Solver,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Write the output to a file instead of standard output
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Report title for the markdown and html formats (defaults to the
+    /// model file name)
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Draw deformed shapes in markdown and html reports
+    #[arg(long)]
+    diagrams: bool,
+
+    /// Compare the results against a reference written by `--format json`
+    #[arg(long, value_name = "REFERENCE")]
+    check: Option<PathBuf>,
+
+    /// Relative tolerance of `--check`
+    #[arg(long, default_value_t = 1e-6)]
+    rtol: f64,
+
+    /// Absolute tolerance of `--check`
+    #[arg(long, default_value_t = 1e-9)]
+    atol: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Analysis {
+    Linear,
+    PDelta,
+    Nonlinear,
+    LargeDisplacement,
+    /// Modal analysis only, without static results
+    Modal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Solver {
+    Auto,
+    DenseLu,
+    SparseCholesky,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Aligned plain-text tables
+    Table,
+    /// One value per line: combo,kind,name,component,value
+    Csv,
+    /// Results for `--check`
+    Json,
+    /// Calculation report
+    Markdown,
+    /// Calculation report as a standalone page
+    Html,
+}
+
+impl Cli {
+    fn options(&self) -> AnalysisOptions {
+        let options = match self.analysis {
+            Analysis::Linear => AnalysisOptions::linear(),
+            Analysis::PDelta => AnalysisOptions::p_delta(),
+            Analysis::Nonlinear => AnalysisOptions::nonlinear(),
+            Analysis::LargeDisplacement => AnalysisOptions::large_displacement(self.steps),
+            Analysis::Modal => AnalysisOptions::modal(self.modes.unwrap_or(10)),
+        };
+        let solver = match self.solver {
+            Solver::Auto => SolverBackend::Auto,
+            Solver::DenseLu => SolverBackend::DenseLu,
+            Solver::SparseCholesky => SolverBackend::SparseCholesky,
+        };
+        let options = options.with_solver(solver);
+        if self.tags.is_empty() { options } else { options.with_tags(self.tags.clone()) }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("fea-cli: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<ExitCode, String> {
+    let mut model = load(&cli.model)?;
+    let (results, combos) = analyze(&mut model, cli).map_err(|e| format!("analysis failed: {e}"))?;
+    for warning in model.warnings() {
+        eprintln!("warning: {warning}");
+    }
+
+    let output = match cli.format {
+        Format::Table => results.to_table(),
+        Format::Csv => results.to_csv(),
+        Format::Json => serde_json::to_string_pretty(&results).map_err(|e| e.to_string())? + "\n",
+        Format::Markdown | Format::Html => {
+            let title = cli.title.clone().unwrap_or_else(|| {
+                cli.model.file_name().map_or_else(String::new, |n| n.to_string_lossy().into_owned())
+            });
+            let combos: Vec<&str> = combos.iter().map(String::as_str).collect();
+            let options = ReportOptions::new(&title).with_combos(&combos).with_diagrams(cli.diagrams);
+            let report = report::generate(&model, &options).map_err(|e| format!("report failed: {e}"))?;
+            if cli.format == Format::Html { report.to_html() } else { report.to_markdown() }
+        }
+    };
+    match &cli.output {
+        Some(path) => fs::write(path, output).map_err(|e| format!("cannot write {}: {e}", path.display()))?,
+        None => print!("{output}"),
+    }
+
+    let Some(path) = &cli.check else {
+        return Ok(ExitCode::SUCCESS);
+    };
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let reference: ResultSet = serde_json::from_str(&text)
+        .map_err(|e| format!("invalid reference {}: {e}", path.display()))?;
+    let differences = results.compare(&reference, cli.rtol, cli.atol);
+    if differences.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    for difference in &differences {
+        eprintln!("{difference}");
+    }
+    eprintln!("fea-cli: {} value(s) differ from the reference", differences.len());
+    Ok(ExitCode::from(1))
+}
+
+/// Read a model, choosing the format by file extension
+fn load(path: &Path) -> Result<FEModel, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let model = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => FEModel::from_toml(&text),
+        Some("json") => FEModel::from_json(&text),
+        _ => return Err(format!("{}: expected a .json or .toml model", path.display())),
+    };
+    model.map_err(|e| format!("invalid model {}: {e}", path.display()))
+}
+
+/// Run the requested analyses and collect the results of the reported
+/// combinations, which are returned with them
+fn analyze(model: &mut FEModel, cli: &Cli) -> FEAResult<(ResultSet, Vec<String>)> {
+    let options = cli.options();
+    let modal = match (cli.analysis, cli.modes) {
+        (Analysis::Modal, _) => Some(model.analyze_modal(&options)?),
+        (_, Some(modes)) => Some(model.analyze_modal(&AnalysisOptions { num_modes: modes, ..options.clone() })?),
+        _ => None,
+    };
+    if cli.analysis == Analysis::Modal {
+        return Ok((ResultSet::collect(model, &[], modal.as_ref()), Vec::new()));
+    }
+
+    if cli.combos.is_empty() {
+        model.analyze(options)?;
+    } else {
+        for combo in &cli.combos {
+            model.analyze_combo(combo, options.clone())?;
+        }
+    }
+    let combos = if cli.combos.is_empty() {
+        model.result_store().displacements.combos().map(str::to_string).collect()
+    } else {
+        cli.combos.clone()
+    };
+    Ok((ResultSet::collect(model, &combos, modal.as_ref()), combos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cli(args: &[&str]) -> Cli {
+        let model = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/portal.toml");
+        Cli::parse_from(["fea-cli", model].iter().chain(args))
+    }
+
+    #[test]
+    fn test_analyze_selected_combos() {
+        let cli = cli(&["--combo", "1.4D", "--solver", "dense-lu", "--modes", "2"]);
+        let mut model = load(&cli.model).unwrap();
+        let (results, combos) = analyze(&mut model, &cli).unwrap();
+        assert_eq!(combos, ["1.4D"]);
+        assert_eq!(results.combos.keys().collect::<Vec<_>>(), ["1.4D"]);
+        assert_eq!(results.modes.len(), 2);
+
+        // The fixed bases carry the whole factored beam load
+        let reactions = &results.combos["1.4D"].reactions;
+        let total: f64 = reactions.values().map(|r| r[1]).sum();
+        assert!((total - 1.4 * 5e3 * 6.0).abs() < 1e-6);
+
+        // The same run matches its own JSON output
+        let json = serde_json::to_string(&results).unwrap();
+        let reference: ResultSet = serde_json::from_str(&json).unwrap();
+        assert!(results.compare(&reference, 1e-12, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_combo_fails() {
+        let cli = cli(&["--combo", "Missing"]);
+        let mut model = load(&cli.model).unwrap();
+        assert!(matches!(analyze(&mut model, &cli), Err(FEAError::LoadCombinationNotFound(_))));
+        assert!(load(Path::new("model.txt")).unwrap_err().contains("cannot read"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Result tables of an analysed model, their output formats and the
+//! comparison against a reference run
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use fea_solver::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const DISPLACEMENTS: [&str; 6] = ["DX", "DY", "DZ", "RX", "RY", "RZ"];
+const REACTIONS: [&str; 6] = ["FX", "FY", "FZ", "MX", "MY", "MZ"];
+const MEMBER_FORCES: [&str; 12] = [
+    "Fx_i", "Fy_i", "Fz_i", "Mx_i", "My_i", "Mz_i",
+    "Fx_j", "Fy_j", "Fz_j", "Mx_j", "My_j", "Mz_j",
+];
+
+/// Results of one run, as written by `--format json` and read by `--check`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultSet {
+    /// Static results by combination name
+    #[serde(default)]
+    pub combos: BTreeMap<String, ComboResults>,
+    /// Modes of a modal analysis, in order of increasing frequency
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<ModeResult>,
+}
+
+/// Static results of one load combination
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComboResults {
+    /// Global node displacements [DX, DY, DZ, RX, RY, RZ]
+    #[serde(default)]
+    pub displacements: BTreeMap<String, Vec<f64>>,
+    /// Support reactions [FX, FY, FZ, MX, MY, MZ]
+    #[serde(default)]
+    pub reactions: BTreeMap<String, Vec<f64>>,
+    /// Local member end forces, i-end then j-end
+    #[serde(default)]
+    pub member_forces: BTreeMap<String, Vec<f64>>,
+}
+
+/// Name, column headers and rows of one kind of result
+type Kind<'a> = (&'static str, &'static [&'static str], &'a BTreeMap<String, Vec<f64>>);
+
+impl ComboResults {
+    /// Each kind of result, in output order
+    fn kinds(&self) -> [Kind<'_>; 3] {
+        [
+            ("displacements", &DISPLACEMENTS, &self.displacements),
+            ("reactions", &REACTIONS, &self.reactions),
+            ("member_forces", &MEMBER_FORCES, &self.member_forces),
+        ]
+    }
+}
+
+/// Frequency and period of one mode
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModeResult {
+    /// Natural frequency (Hz)
+    pub frequency: f64,
+    /// Natural period (s)
+    pub period: f64,
+}
+
+/// A difference between a run and its reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    /// Where the value is, e.g. `1.4D/displacements/N2/DY`
+    pub path: String,
+    /// Value in the reference
+    pub expected: f64,
+    /// Value in this run (None = missing from this run)
+    pub actual: Option<f64>,
+}
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.actual {
+            Some(actual) => write!(f, "{}: expected {:e}, got {actual:e}", self.path, self.expected),
+            None => write!(f, "{}: expected {:e}, got nothing", self.path, self.expected),
+        }
+    }
+}
+
+impl ResultSet {
+    /// Collect the results of the given analysed combinations and modes
+    pub fn collect(model: &FEModel, combos: &[String], modal: Option<&ModalResults>) -> Self {
+        let store = model.result_store();
+        let combos = combos.iter()
+            .map(|combo| {
+                let results = ComboResults {
+                    displacements: rows(&store.displacements, combo),
+                    reactions: rows(&store.reactions, combo),
+                    member_forces: rows(&store.member_forces, combo),
+                };
+                (combo.clone(), results)
+            })
+            .collect();
+        let modes = modal.map_or_else(Vec::new, |modal| {
+            modal.modes.iter().map(|m| ModeResult { frequency: m.frequency, period: m.period }).collect()
+        });
+        Self { combos, modes }
+    }
+
+    /// Aligned plain-text tables, one per combination and result kind
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+        for (combo, results) in &self.combos {
+            for (kind, headers, rows) in results.kinds() {
+                if rows.is_empty() {
+                    continue;
+                }
+                let width = rows.keys().map(|n| n.len()).max().unwrap_or(0).max(4);
+                let caption = match kind {
+                    "displacements" => "Displacements",
+                    "reactions" => "Reactions",
+                    _ => "Member end forces",
+                };
+                let _ = write!(out, "{caption}, {combo}\n{:<width$}", "Name");
+                for header in headers {
+                    let _ = write!(out, " {header:>12}");
+                }
+                out.push('\n');
+                for (name, values) in rows {
+                    let _ = write!(out, "{name:<width$}");
+                    for v in values {
+                        let _ = write!(out, " {v:>12.4e}");
+                    }
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+        }
+        if !self.modes.is_empty() {
+            let _ = writeln!(out, "Modes\n{:<4} {:>12} {:>12}", "Mode", "f (Hz)", "T (s)");
+            for (i, mode) in self.modes.iter().enumerate() {
+                let _ = writeln!(out, "{:<4} {:>12.4e} {:>12.4e}", i + 1, mode.frequency, mode.period);
+            }
+        }
+        out
+    }
+
+    /// One `combo,kind,name,component,value` line per value, for spreadsheets
+    /// and data frames
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("combo,kind,name,component,value\n");
+        for (combo, results) in &self.combos {
+            for (kind, headers, rows) in results.kinds() {
+                for (name, row) in rows {
+                    for (component, v) in headers.iter().zip(row) {
+                        let _ = writeln!(out, "{},{kind},{},{component},{v:e}", csv_field(combo), csv_field(name));
+                    }
+                }
+            }
+        }
+        for (i, mode) in self.modes.iter().enumerate() {
+            let _ = writeln!(out, ",modes,{},frequency,{:e}", i + 1, mode.frequency);
+            let _ = writeln!(out, ",modes,{},period,{:e}", i + 1, mode.period);
+        }
+        out
+    }
+
+    /// Values of the reference that this run does not match within
+    /// `|actual - expected| <= atol + rtol * |expected|`
+    ///
+    /// Only the combinations, entities and modes in the reference are
+    /// compared, so a reference can pin down just the results that matter.
+    pub fn compare(&self, reference: &ResultSet, rtol: f64, atol: f64) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let mut check = |path: String, expected: f64, actual: Option<f64>| {
+            if !actual.is_some_and(|a| (a - expected).abs() <= atol + rtol * expected.abs()) {
+                differences.push(Difference { path, expected, actual });
+            }
+        };
+
+        for (combo, expected) in &reference.combos {
+            let actual = self.combos.get(combo).map(ComboResults::kinds);
+            for (i, (kind, headers, expected)) in expected.kinds().into_iter().enumerate() {
+                for (name, row) in expected {
+                    let actual = actual.as_ref().and_then(|a| a[i].2.get(name));
+                    for (k, (component, &value)) in headers.iter().zip(row).enumerate() {
+                        let path = format!("{combo}/{kind}/{name}/{component}");
+                        check(path, value, actual.and_then(|a| a.get(k).copied()));
+                    }
+                }
+            }
+        }
+        for (i, expected) in reference.modes.iter().enumerate() {
+            let actual = self.modes.get(i);
+            check(format!("modes/{}/frequency", i + 1), expected.frequency, actual.map(|m| m.frequency));
+        }
+        differences
+    }
+}
+
+/// Stored rows of a result table for one combination, by entity name
+fn rows<I: Handle, const N: usize>(
+    table: &ResultTable<I, N>,
+    combo: &str,
+) -> BTreeMap<String, Vec<f64>> {
+    table.rows().iter()
+        .filter_map(|(_, name)| Some((name.to_string(), table.get(name, combo)?.to_vec())))
+        .collect()
+}
+
+/// A CSV field, quoted when it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ResultSet {
+        let mut combo = ComboResults::default();
+        combo.displacements.insert("N2".to_string(), vec![0.0, -1e-3, 0.0, 0.0, 0.0, 2e-4]);
+        combo.reactions.insert("N1".to_string(), vec![0.0, 1400.0, 0.0, 0.0, 0.0, 4200.0]);
+        ResultSet {
+            combos: BTreeMap::from([("1.4D".to_string(), combo)]),
+            modes: vec![ModeResult { frequency: 2.5, period: 0.4 }],
+        }
+    }
+
+    #[test]
+    fn test_compare_within_tolerance() {
+        let reference = sample();
+        let mut run = sample();
+        assert!(run.compare(&reference, 1e-6, 1e-12).is_empty());
+
+        // A drifted value, a missing entity and a missing mode are all reported
+        let combo = run.combos.get_mut("1.4D").unwrap();
+        combo.displacements.get_mut("N2").unwrap()[1] = -1.01e-3;
+        combo.reactions.clear();
+        run.modes.clear();
+        let differences = run.compare(&reference, 1e-6, 1e-12);
+        let paths: Vec<&str> = differences.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths[0], "1.4D/displacements/N2/DY");
+        assert_eq!(paths.iter().filter(|p| p.starts_with("1.4D/reactions/N1/")).count(), 6);
+        assert_eq!(*paths.last().unwrap(), "modes/1/frequency");
+        assert!(run.compare(&reference, 0.02, 0.0).len() == 7);
+
+        // Results absent from the reference are not checked
+        assert!(reference.compare(&run, 0.02, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let results = sample();
+        let json = serde_json::to_string(&results).unwrap();
+        assert_eq!(serde_json::from_str::<ResultSet>(&json).unwrap(), results);
+
+        let csv = results.to_csv();
+        assert!(csv.contains("1.4D,displacements,N2,DY,-1e-3\n"));
+        assert!(csv.ends_with(",modes,1,period,4e-1\n"));
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+
+        let table = results.to_table();
+        assert!(table.starts_with("Displacements, 1.4D\nName"));
+        assert!(table.contains("Modes\n"));
+    }
+}
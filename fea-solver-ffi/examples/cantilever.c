@@ -0,0 +1,55 @@
+/*
+ * Cantilever with a tip load, analysed through the C API.
+ *
+ *   cargo build --release
+ *   cc examples/cantilever.c -Iinclude -Ltarget/release -lfea_solver_ffi -o cantilever
+ *   LD_LIBRARY_PATH=target/release ./cantilever
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "fea_solver.h"
+
+#define CHECK(call)                                                  \
+    do {                                                             \
+        if ((call) != FEA_STATUS_OK) {                               \
+            fprintf(stderr, "%s: %s\n", #call, fea_last_error());   \
+            return 1;                                                \
+        }                                                            \
+    } while (0)
+
+int main(void) {
+    FeaModel *model = fea_model_new();
+    CHECK(fea_add_material(model, "Steel", 200e9, 77e9, 0.3, 7850));
+    CHECK(fea_add_section(model, "W", 0.01, 1e-4, 2e-4, 1e-5));
+    CHECK(fea_add_node(model, "A", 0, 0, 0));
+    CHECK(fea_add_node(model, "B", 3, 0, 0));
+    CHECK(fea_add_member(model, "M1", "A", "B", "Steel", "W", 0));
+    CHECK(fea_add_support(model, "A", true, true, true, true, true, true));
+    CHECK(fea_add_node_load(model, "B", 0, -1000, 0, 0, 0, 0, "Dead"));
+    CHECK(fea_add_member_dist_load(model, "M1", -500, -500, 0, 1.0 / 0.0, FEA_LOAD_DIRECTION_GLOBAL_FY, "Dead"));
+
+    const char *cases[] = {"Dead"};
+    const double factors[] = {1.4};
+    CHECK(fea_add_load_combo(model, "1.4D", cases, factors, 1));
+    CHECK(fea_analyze(model, FEA_ANALYSIS_TYPE_LINEAR));
+
+    size_t rows = fea_result_rows(model, FEA_RESULT_KIND_DISPLACEMENTS);
+    size_t width = fea_result_width(FEA_RESULT_KIND_DISPLACEMENTS);
+    double *displacements = malloc(rows * width * sizeof(double));
+    CHECK(fea_result_values(model, FEA_RESULT_KIND_DISPLACEMENTS, "1.4D", displacements, rows * width));
+    for (size_t row = 0; row < rows; row++) {
+        char *name = fea_result_name(model, FEA_RESULT_KIND_DISPLACEMENTS, row);
+        printf("%s DY = %.6e\n", name, displacements[row * width + 1]);
+        fea_string_free(name);
+    }
+
+    double forces[12];
+    CHECK(fea_result_values(model, FEA_RESULT_KIND_MEMBER_FORCES, "1.4D", forces, 12));
+    printf("M1 Mz at A = %.1f\n", forces[5]);
+
+    free(displacements);
+    fea_model_free(model);
+    return 0;
+}
@@ -0,0 +1,231 @@
+/* fea-solver C API. Generated by cbindgen from fea-solver-ffi; do not edit. */
+
+#ifndef FEA_SOLVER_H
+#define FEA_SOLVER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// Outcome of a call
+typedef enum FeaStatus {
+  // Success
+  FEA_STATUS_OK = 0,
+  // The solver rejected the input or the analysis failed
+  FEA_STATUS_ERROR = 1,
+  // A required pointer was null
+  FEA_STATUS_NULL_POINTER = 2,
+  // A string was not valid UTF-8
+  FEA_STATUS_INVALID_UTF8 = 3,
+  // An output buffer was shorter than the result
+  FEA_STATUS_BUFFER_TOO_SMALL = 4,
+  // The library panicked; the model may be left part-way through the call
+  FEA_STATUS_PANIC = 5,
+} FeaStatus;
+
+// Direction of a member load
+typedef enum FeaLoadDirection {
+  // Local x force
+  FEA_LOAD_DIRECTION_LOCAL_FX = 0,
+  // Local y force
+  FEA_LOAD_DIRECTION_LOCAL_FY = 1,
+  // Local z force
+  FEA_LOAD_DIRECTION_LOCAL_FZ = 2,
+  // Moment about local x
+  FEA_LOAD_DIRECTION_LOCAL_MX = 3,
+  // Moment about local y
+  FEA_LOAD_DIRECTION_LOCAL_MY = 4,
+  // Moment about local z
+  FEA_LOAD_DIRECTION_LOCAL_MZ = 5,
+  // Global X force
+  FEA_LOAD_DIRECTION_GLOBAL_FX = 6,
+  // Global Y force
+  FEA_LOAD_DIRECTION_GLOBAL_FY = 7,
+  // Global Z force
+  FEA_LOAD_DIRECTION_GLOBAL_FZ = 8,
+} FeaLoadDirection;
+
+// Static analysis to run
+typedef enum FeaAnalysisType {
+  // First-order linear elastic
+  FEA_ANALYSIS_TYPE_LINEAR = 0,
+  // Second-order P-Delta
+  FEA_ANALYSIS_TYPE_P_DELTA = 1,
+  // Tension/compression-only members and one-way supports iterated to convergence
+  FEA_ANALYSIS_TYPE_NONLINEAR = 2,
+} FeaAnalysisType;
+
+// A set of results with one row per entity
+typedef enum FeaResultKind {
+  // [DX, DY, DZ, RX, RY, RZ] per node
+  FEA_RESULT_KIND_DISPLACEMENTS = 0,
+  // [FX, FY, FZ, MX, MY, MZ] per supported node
+  FEA_RESULT_KIND_REACTIONS = 1,
+  // The 12 local end forces [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, ..., Mz_j] per member
+  FEA_RESULT_KIND_MEMBER_FORCES = 2,
+  // [sx, sy, txy, von_mises, mx, my, mxy] at the centroid of each plate, then each quad
+  FEA_RESULT_KIND_PLATE_STRESSES = 3,
+} FeaResultKind;
+
+// A structural model
+typedef struct FeaModel FeaModel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Crate version, as a static string
+const char *fea_version(void);
+
+// Description of the last failed call on this thread, or null if none has
+// failed; valid until the next failure on the thread
+const char *fea_last_error(void);
+
+// Release a string returned by the library
+void fea_string_free(char *s);
+
+// Create an empty model
+struct FeaModel *fea_model_new(void);
+
+// Load a model, and any saved results, from a JSON project file; null on failure
+struct FeaModel *fea_model_from_json(const char *json);
+
+// Serialise a model to a JSON project file, optionally with its results;
+// null on failure
+char *fea_model_to_json(const struct FeaModel *model, bool with_results);
+
+// Release a model
+void fea_model_free(struct FeaModel *model);
+
+// Add a node at global coordinates; Y is vertical
+enum FeaStatus fea_add_node(struct FeaModel *model, const char *name, double x, double y, double z);
+
+// Add an isotropic material
+enum FeaStatus fea_add_material(struct FeaModel *model,
+                                const char *name,
+                                double e,
+                                double g,
+                                double nu,
+                                double rho);
+
+// Add a section from its area, second moments of area and torsion constant
+enum FeaStatus fea_add_section(struct FeaModel *model,
+                               const char *name,
+                               double a,
+                               double iy,
+                               double iz,
+                               double j);
+
+// Add a frame member; `rotation` about its axis is in radians
+enum FeaStatus fea_add_member(struct FeaModel *model,
+                              const char *name,
+                              const char *i_node,
+                              const char *j_node,
+                              const char *material,
+                              const char *section,
+                              double rotation);
+
+// Add a rectangular plate
+enum FeaStatus fea_add_plate(struct FeaModel *model,
+                             const char *name,
+                             const char *i_node,
+                             const char *j_node,
+                             const char *m_node,
+                             const char *n_node,
+                             double thickness,
+                             const char *material);
+
+// Add a general quadrilateral shell
+enum FeaStatus fea_add_quad(struct FeaModel *model,
+                            const char *name,
+                            const char *i_node,
+                            const char *j_node,
+                            const char *m_node,
+                            const char *n_node,
+                            double thickness,
+                            const char *material);
+
+// Restrain DOFs of a node, replacing any support it already has
+enum FeaStatus fea_add_support(struct FeaModel *model,
+                               const char *node,
+                               bool dx,
+                               bool dy,
+                               bool dz,
+                               bool rx,
+                               bool ry,
+                               bool rz);
+
+// Add global forces and moments at a node
+enum FeaStatus fea_add_node_load(struct FeaModel *model,
+                                 const char *node,
+                                 double fx,
+                                 double fy,
+                                 double fz,
+                                 double mx,
+                                 double my,
+                                 double mz,
+                                 const char *load_case);
+
+// Add a point load at `x` from a member's i-node
+enum FeaStatus fea_add_member_point_load(struct FeaModel *model,
+                                         const char *member,
+                                         double magnitude,
+                                         double x,
+                                         enum FeaLoadDirection direction,
+                                         const char *load_case);
+
+// Add a linearly varying load from `x1` to `x2`; an infinite `x2` runs to
+// the j-node
+enum FeaStatus fea_add_member_dist_load(struct FeaModel *model,
+                                        const char *member,
+                                        double w1,
+                                        double w2,
+                                        double x1,
+                                        double x2,
+                                        enum FeaLoadDirection direction,
+                                        const char *load_case);
+
+// Add a uniform pressure normal to a plate or quad
+enum FeaStatus fea_add_plate_load(struct FeaModel *model,
+                                  const char *plate,
+                                  double pressure,
+                                  const char *load_case);
+
+// Add a load combination of `count` load cases and their factors
+enum FeaStatus fea_add_load_combo(struct FeaModel *model,
+                                  const char *name,
+                                  const char *const *load_cases,
+                                  const double *factors,
+                                  size_t count);
+
+// Solve every load combination
+enum FeaStatus fea_analyze(struct FeaModel *model, enum FeaAnalysisType analysis);
+
+// Number of load combinations
+size_t fea_combo_count(const struct FeaModel *model);
+
+// Name of the load combination at `index`, in analysis order; null when
+// out of range
+char *fea_combo_name(const struct FeaModel *model, size_t index);
+
+// Values per row of a result kind
+size_t fea_result_width(enum FeaResultKind kind);
+
+// Number of rows of a result kind, known once the model has been analysed
+size_t fea_result_rows(const struct FeaModel *model, enum FeaResultKind kind);
+
+// Name of the node, member or plate in `row`; null when out of range
+char *fea_result_name(const struct FeaModel *model, enum FeaResultKind kind, size_t row);
+
+// Copy the results of a combination into `out`, `fea_result_width` values
+// per row; `len` is the capacity of `out` in values
+enum FeaStatus fea_result_values(const struct FeaModel *model,
+                                 enum FeaResultKind kind,
+                                 const char *combo,
+                                 double *out,
+                                 size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FEA_SOLVER_H */
@@ -0,0 +1,685 @@
+//! C ABI for fea-solver
+//!
+//! A stable C interface for embedding the solver in C, C++ and C# (P/Invoke)
+//! desktop applications. `include/fea_solver.h` is generated from this file
+//! by cbindgen on every build.
+//!
+//! A model is an opaque `FeaModel` handle from `fea_model_new` or
+//! `fea_model_from_json`, released with `fea_model_free`. Entities are added
+//! by name, then `fea_analyze` solves every load combination and results are
+//! copied out as flat `double` arrays, one row per node, support, member or
+//! plate in the order of `fea_result_name`:
+//!
+//! ```c
+//! FeaModel *model = fea_model_new();
+//! fea_add_material(model, "Steel", 200e9, 77e9, 0.3, 7850);
+//! fea_add_section(model, "W", 0.01, 1e-4, 2e-4, 1e-5);
+//! fea_add_node(model, "A", 0, 0, 0);
+//! fea_add_node(model, "B", 3, 0, 0);
+//! fea_add_member(model, "M1", "A", "B", "Steel", "W", 0);
+//! fea_add_support(model, "A", true, true, true, true, true, true);
+//! fea_add_node_load(model, "B", 0, -1000, 0, 0, 0, 0, "Dead");
+//! if (fea_analyze(model, FEA_ANALYSIS_TYPE_LINEAR) != FEA_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", fea_last_error());
+//! }
+//! size_t rows = fea_result_rows(model, FEA_RESULT_KIND_DISPLACEMENTS);
+//! double *d = malloc(rows * 6 * sizeof(double));
+//! fea_result_values(model, FEA_RESULT_KIND_DISPLACEMENTS, "Combo 1", d, rows * 6);
+//! ```
+//!
+//! Functions returning `FeaStatus` report failures through it, with a
+//! description from `fea_last_error`; panics are caught at the boundary.
+//! Strings are NUL-terminated UTF-8. Strings returned by the library are
+//! owned by the caller and released with `fea_string_free`.
+//!
+//! # Safety
+//!
+//! Every pointer argument must be null or valid for the duration of the
+//! call: models from this library that have not been freed, NUL-terminated
+//! strings, and output buffers of at least the stated length. A model must
+//! not be used from two threads at once.
+
+#![allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use fea_solver::analysis::AnalysisOptions;
+use fea_solver::elements::{Material, Member, Node, Plate, Quad, Section, Support};
+use fea_solver::error::FEAError;
+use fea_solver::handles::{Handle, Registry};
+use fea_solver::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad};
+use fea_solver::model::FEModel;
+
+/// A structural model
+pub struct FeaModel {
+    model: FEModel,
+}
+
+/// Outcome of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeaStatus {
+    /// Success
+    Ok = 0,
+    /// The solver rejected the input or the analysis failed
+    Error = 1,
+    /// A required pointer was null
+    NullPointer = 2,
+    /// A string was not valid UTF-8
+    InvalidUtf8 = 3,
+    /// An output buffer was shorter than the result
+    BufferTooSmall = 4,
+    /// The library panicked; the model may be left part-way through the call
+    Panic = 5,
+}
+
+/// Static analysis to run
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeaAnalysisType {
+    /// First-order linear elastic
+    Linear = 0,
+    /// Second-order P-Delta
+    PDelta = 1,
+    /// Tension/compression-only members and one-way supports iterated to convergence
+    Nonlinear = 2,
+}
+
+/// Direction of a member load
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeaLoadDirection {
+    /// Local x force
+    LocalFx = 0,
+    /// Local y force
+    LocalFy = 1,
+    /// Local z force
+    LocalFz = 2,
+    /// Moment about local x
+    LocalMx = 3,
+    /// Moment about local y
+    LocalMy = 4,
+    /// Moment about local z
+    LocalMz = 5,
+    /// Global X force
+    GlobalFx = 6,
+    /// Global Y force
+    GlobalFy = 7,
+    /// Global Z force
+    GlobalFz = 8,
+}
+
+/// A set of results with one row per entity
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeaResultKind {
+    /// [DX, DY, DZ, RX, RY, RZ] per node
+    Displacements = 0,
+    /// [FX, FY, FZ, MX, MY, MZ] per supported node
+    Reactions = 1,
+    /// The 12 local end forces [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, ..., Mz_j] per member
+    MemberForces = 2,
+    /// [sx, sy, txy, von_mises, mx, my, mxy] at the centroid of each plate, then each quad
+    PlateStresses = 3,
+}
+
+impl FeaResultKind {
+    fn width(self) -> usize {
+        match self {
+            FeaResultKind::Displacements | FeaResultKind::Reactions => 6,
+            FeaResultKind::MemberForces => 12,
+            FeaResultKind::PlateStresses => 7,
+        }
+    }
+
+    /// Entity names in row order
+    fn rows(self, model: &FEModel) -> Vec<String> {
+        let store = model.result_store();
+        match self {
+            FeaResultKind::Displacements => names(store.displacements.rows()),
+            FeaResultKind::Reactions => names(store.reactions.rows()),
+            FeaResultKind::MemberForces => names(store.member_forces.rows()),
+            FeaResultKind::PlateStresses => model.plates.keys().chain(model.quads.keys()).cloned().collect(),
+        }
+    }
+}
+
+/// Failure of a call, recorded for `fea_last_error`
+struct Failure {
+    status: FeaStatus,
+    message: String,
+}
+
+impl From<FEAError> for Failure {
+    fn from(error: FEAError) -> Self {
+        Self { status: FeaStatus::Error, message: error.to_string() }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run a call, recording any failure or panic for `fea_last_error`
+fn call(f: impl FnOnce() -> Result<(), Failure>) -> FeaStatus {
+    let failure = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return FeaStatus::Ok,
+        Ok(Err(failure)) => failure,
+        Err(panic) => {
+            let message = panic.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Failure { status: FeaStatus::Panic, message: format!("fea-solver panicked: {message}") }
+        }
+    };
+    set_last_error(&failure.message);
+    failure.status
+}
+
+unsafe fn model_mut<'a>(model: *mut FeaModel) -> Result<&'a mut FEModel, Failure> {
+    model.as_mut().map(|m| &mut m.model).ok_or_else(|| null_pointer("model"))
+}
+
+unsafe fn model_ref<'a>(model: *const FeaModel) -> Result<&'a FEModel, Failure> {
+    model.as_ref().map(|m| &m.model).ok_or_else(|| null_pointer("model"))
+}
+
+unsafe fn string<'a>(s: *const c_char, argument: &str) -> Result<&'a str, Failure> {
+    if s.is_null() {
+        return Err(null_pointer(argument));
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| Failure {
+        status: FeaStatus::InvalidUtf8,
+        message: format!("{argument} is not valid UTF-8"),
+    })
+}
+
+fn null_pointer(argument: &str) -> Failure {
+    Failure { status: FeaStatus::NullPointer, message: format!("{argument} is null") }
+}
+
+fn owned_string(s: &str) -> *mut c_char {
+    CString::new(s.replace('\0', " ")).unwrap().into_raw()
+}
+
+// ========================
+// Library
+// ========================
+
+/// Crate version, as a static string
+#[no_mangle]
+pub extern "C" fn fea_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Description of the last failed call on this thread, or null if none has
+/// failed; valid until the next failure on the thread
+#[no_mangle]
+pub extern "C" fn fea_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Release a string returned by the library
+#[no_mangle]
+pub unsafe extern "C" fn fea_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// ========================
+// Model Lifetime
+// ========================
+
+/// Create an empty model
+#[no_mangle]
+pub extern "C" fn fea_model_new() -> *mut FeaModel {
+    Box::into_raw(Box::new(FeaModel { model: FEModel::new() }))
+}
+
+/// Load a model, and any saved results, from a JSON project file; null on failure
+#[no_mangle]
+pub unsafe extern "C" fn fea_model_from_json(json: *const c_char) -> *mut FeaModel {
+    let mut model = ptr::null_mut();
+    call(|| {
+        let loaded = FEModel::from_json(string(json, "json")?)?;
+        model = Box::into_raw(Box::new(FeaModel { model: loaded }));
+        Ok(())
+    });
+    model
+}
+
+/// Serialise a model to a JSON project file, optionally with its results;
+/// null on failure
+#[no_mangle]
+pub unsafe extern "C" fn fea_model_to_json(model: *const FeaModel, with_results: bool) -> *mut c_char {
+    let mut json = ptr::null_mut();
+    call(|| {
+        let model = model_ref(model)?;
+        let saved = if with_results { model.to_json_with_results()? } else { model.to_json()? };
+        json = owned_string(&saved);
+        Ok(())
+    });
+    json
+}
+
+/// Release a model
+#[no_mangle]
+pub unsafe extern "C" fn fea_model_free(model: *mut FeaModel) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+// ========================
+// Model Building
+// ========================
+
+/// Add a node at global coordinates; Y is vertical
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_node(model: *mut FeaModel, name: *const c_char, x: f64, y: f64, z: f64) -> FeaStatus {
+    call(|| Ok(model_mut(model)?.add_node(string(name, "name")?, Node::new(x, y, z))?))
+}
+
+/// Add an isotropic material
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_material(
+    model: *mut FeaModel,
+    name: *const c_char,
+    e: f64,
+    g: f64,
+    nu: f64,
+    rho: f64,
+) -> FeaStatus {
+    call(|| Ok(model_mut(model)?.add_material(string(name, "name")?, Material::new(e, g, nu, rho))?))
+}
+
+/// Add a section from its area, second moments of area and torsion constant
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_section(
+    model: *mut FeaModel,
+    name: *const c_char,
+    a: f64,
+    iy: f64,
+    iz: f64,
+    j: f64,
+) -> FeaStatus {
+    call(|| Ok(model_mut(model)?.add_section(string(name, "name")?, Section::new(a, iy, iz, j))?))
+}
+
+/// Add a frame member; `rotation` about its axis is in radians
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_member(
+    model: *mut FeaModel,
+    name: *const c_char,
+    i_node: *const c_char,
+    j_node: *const c_char,
+    material: *const c_char,
+    section: *const c_char,
+    rotation: f64,
+) -> FeaStatus {
+    call(|| {
+        let member = Member::new(
+            string(i_node, "i_node")?,
+            string(j_node, "j_node")?,
+            string(material, "material")?,
+            string(section, "section")?,
+        ).with_rotation(rotation);
+        Ok(model_mut(model)?.add_member(string(name, "name")?, member)?)
+    })
+}
+
+/// Add a rectangular plate
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_plate(
+    model: *mut FeaModel,
+    name: *const c_char,
+    i_node: *const c_char,
+    j_node: *const c_char,
+    m_node: *const c_char,
+    n_node: *const c_char,
+    thickness: f64,
+    material: *const c_char,
+) -> FeaStatus {
+    call(|| {
+        let plate = Plate::new(
+            string(i_node, "i_node")?,
+            string(j_node, "j_node")?,
+            string(m_node, "m_node")?,
+            string(n_node, "n_node")?,
+            thickness,
+            string(material, "material")?,
+        );
+        Ok(model_mut(model)?.add_plate(string(name, "name")?, plate)?)
+    })
+}
+
+/// Add a general quadrilateral shell
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_quad(
+    model: *mut FeaModel,
+    name: *const c_char,
+    i_node: *const c_char,
+    j_node: *const c_char,
+    m_node: *const c_char,
+    n_node: *const c_char,
+    thickness: f64,
+    material: *const c_char,
+) -> FeaStatus {
+    call(|| {
+        let quad = Quad::new(
+            string(i_node, "i_node")?,
+            string(j_node, "j_node")?,
+            string(m_node, "m_node")?,
+            string(n_node, "n_node")?,
+            thickness,
+            string(material, "material")?,
+        );
+        Ok(model_mut(model)?.add_quad(string(name, "name")?, quad)?)
+    })
+}
+
+/// Restrain DOFs of a node, replacing any support it already has
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_support(
+    model: *mut FeaModel,
+    node: *const c_char,
+    dx: bool,
+    dy: bool,
+    dz: bool,
+    rx: bool,
+    ry: bool,
+    rz: bool,
+) -> FeaStatus {
+    call(|| Ok(model_mut(model)?.add_support(string(node, "node")?, Support::with_restraints(dx, dy, dz, rx, ry, rz))?))
+}
+
+/// Add global forces and moments at a node
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_node_load(
+    model: *mut FeaModel,
+    node: *const c_char,
+    fx: f64,
+    fy: f64,
+    fz: f64,
+    mx: f64,
+    my: f64,
+    mz: f64,
+    load_case: *const c_char,
+) -> FeaStatus {
+    call(|| {
+        let load = NodeLoad::new(fx, fy, fz, mx, my, mz, string(load_case, "load_case")?);
+        Ok(model_mut(model)?.add_node_load(string(node, "node")?, load)?)
+    })
+}
+
+/// Add a point load at `x` from a member's i-node
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_member_point_load(
+    model: *mut FeaModel,
+    member: *const c_char,
+    magnitude: f64,
+    x: f64,
+    direction: FeaLoadDirection,
+    load_case: *const c_char,
+) -> FeaStatus {
+    call(|| {
+        let load = PointLoad::new(magnitude, x, direction.into(), string(load_case, "load_case")?);
+        Ok(model_mut(model)?.add_member_point_load(string(member, "member")?, load)?)
+    })
+}
+
+/// Add a linearly varying load from `x1` to `x2`; an infinite `x2` runs to
+/// the j-node
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_member_dist_load(
+    model: *mut FeaModel,
+    member: *const c_char,
+    w1: f64,
+    w2: f64,
+    x1: f64,
+    x2: f64,
+    direction: FeaLoadDirection,
+    load_case: *const c_char,
+) -> FeaStatus {
+    call(|| {
+        let load = DistributedLoad::new(w1, w2, x1, x2, direction.into(), string(load_case, "load_case")?);
+        Ok(model_mut(model)?.add_member_dist_load(string(member, "member")?, load)?)
+    })
+}
+
+/// Add a uniform pressure normal to a plate or quad
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_plate_load(
+    model: *mut FeaModel,
+    plate: *const c_char,
+    pressure: f64,
+    load_case: *const c_char,
+) -> FeaStatus {
+    call(|| Ok(model_mut(model)?.add_plate_load(string(plate, "plate")?, PlateLoad::new(pressure, string(load_case, "load_case")?))?))
+}
+
+/// Add a load combination of `count` load cases and their factors
+#[no_mangle]
+pub unsafe extern "C" fn fea_add_load_combo(
+    model: *mut FeaModel,
+    name: *const c_char,
+    load_cases: *const *const c_char,
+    factors: *const f64,
+    count: usize,
+) -> FeaStatus {
+    call(|| {
+        let mut combo = LoadCombination::new(string(name, "name")?);
+        if count > 0 {
+            if load_cases.is_null() {
+                return Err(null_pointer("load_cases"));
+            }
+            if factors.is_null() {
+                return Err(null_pointer("factors"));
+            }
+            let load_cases = std::slice::from_raw_parts(load_cases, count);
+            let factors = std::slice::from_raw_parts(factors, count);
+            for (&case, &factor) in load_cases.iter().zip(factors) {
+                combo = combo.with_case(string(case, "load case")?, factor);
+            }
+        }
+        Ok(model_mut(model)?.add_load_combo(combo)?)
+    })
+}
+
+// ========================
+// Analysis
+// ========================
+
+/// Solve every load combination
+#[no_mangle]
+pub unsafe extern "C" fn fea_analyze(model: *mut FeaModel, analysis: FeaAnalysisType) -> FeaStatus {
+    call(|| {
+        let options = match analysis {
+            FeaAnalysisType::Linear => AnalysisOptions::linear(),
+            FeaAnalysisType::PDelta => AnalysisOptions::p_delta(),
+            FeaAnalysisType::Nonlinear => AnalysisOptions::nonlinear(),
+        };
+        Ok(model_mut(model)?.analyze(options)?)
+    })
+}
+
+/// Number of load combinations
+#[no_mangle]
+pub unsafe extern "C" fn fea_combo_count(model: *const FeaModel) -> usize {
+    model_ref(model).map_or(0, |model| model.load_combos.len())
+}
+
+/// Name of the load combination at `index`, in analysis order; null when
+/// out of range
+#[no_mangle]
+pub unsafe extern "C" fn fea_combo_name(model: *const FeaModel, index: usize) -> *mut c_char {
+    model_ref(model).ok()
+        .and_then(|model| model.load_combos.keys().nth(index).map(|name| owned_string(name)))
+        .unwrap_or(ptr::null_mut())
+}
+
+// ========================
+// Results
+// ========================
+
+/// Values per row of a result kind
+#[no_mangle]
+pub extern "C" fn fea_result_width(kind: FeaResultKind) -> usize {
+    kind.width()
+}
+
+/// Number of rows of a result kind, known once the model has been analysed
+#[no_mangle]
+pub unsafe extern "C" fn fea_result_rows(model: *const FeaModel, kind: FeaResultKind) -> usize {
+    model_ref(model).map_or(0, |model| kind.rows(model).len())
+}
+
+/// Name of the node, member or plate in `row`; null when out of range
+#[no_mangle]
+pub unsafe extern "C" fn fea_result_name(model: *const FeaModel, kind: FeaResultKind, row: usize) -> *mut c_char {
+    model_ref(model).ok()
+        .and_then(|model| kind.rows(model).get(row).map(|name| owned_string(name)))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Copy the results of a combination into `out`, `fea_result_width` values
+/// per row; `len` is the capacity of `out` in values
+#[no_mangle]
+pub unsafe extern "C" fn fea_result_values(
+    model: *const FeaModel,
+    kind: FeaResultKind,
+    combo: *const c_char,
+    out: *mut f64,
+    len: usize,
+) -> FeaStatus {
+    call(|| {
+        let values = result_values(model_ref(model)?, kind, string(combo, "combo")?)?;
+        if len < values.len() {
+            return Err(Failure {
+                status: FeaStatus::BufferTooSmall,
+                message: format!("{kind:?} need {} values, the buffer holds {len}", values.len()),
+            });
+        }
+        if out.is_null() {
+            return Err(null_pointer("out"));
+        }
+        std::slice::from_raw_parts_mut(out, values.len()).copy_from_slice(&values);
+        Ok(())
+    })
+}
+
+fn result_values(model: &FEModel, kind: FeaResultKind, combo: &str) -> Result<Vec<f64>, Failure> {
+    let store = model.result_store();
+    let column = match kind {
+        FeaResultKind::Displacements => store.displacements.column(combo),
+        FeaResultKind::Reactions => store.reactions.column(combo),
+        FeaResultKind::MemberForces => store.member_forces.column(combo),
+        FeaResultKind::PlateStresses => {
+            let mut values = Vec::new();
+            for name in kind.rows(model) {
+                let s = model.plate_stress(&name, combo)?;
+                values.extend([s.sx, s.sy, s.txy, s.von_mises, s.mx, s.my, s.mxy]);
+            }
+            return Ok(values);
+        }
+    };
+    Ok(column.ok_or(FEAError::NotAnalyzed)?.to_vec())
+}
+
+fn names<I: Handle>(rows: &Registry<I>) -> Vec<String> {
+    rows.iter().map(|(_, name)| name.to_string()).collect()
+}
+
+impl From<FeaLoadDirection> for LoadDirection {
+    fn from(direction: FeaLoadDirection) -> Self {
+        match direction {
+            FeaLoadDirection::LocalFx => LoadDirection::Fx,
+            FeaLoadDirection::LocalFy => LoadDirection::Fy,
+            FeaLoadDirection::LocalFz => LoadDirection::Fz,
+            FeaLoadDirection::LocalMx => LoadDirection::Mx,
+            FeaLoadDirection::LocalMy => LoadDirection::My,
+            FeaLoadDirection::LocalMz => LoadDirection::Mz,
+            FeaLoadDirection::GlobalFx => LoadDirection::FX,
+            FeaLoadDirection::GlobalFy => LoadDirection::FY,
+            FeaLoadDirection::GlobalFz => LoadDirection::FZ,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_cantilever_through_c_abi() {
+        unsafe {
+            let model = fea_model_new();
+            assert_eq!(fea_add_material(model, c("Steel").as_ptr(), 200e9, 77e9, 0.3, 7850.0), FeaStatus::Ok);
+            assert_eq!(fea_add_section(model, c("W").as_ptr(), 0.01, 1e-4, 2e-4, 1e-5), FeaStatus::Ok);
+            for (name, x) in [("A", 0.0), ("B", 3.0)] {
+                assert_eq!(fea_add_node(model, c(name).as_ptr(), x, 0.0, 0.0), FeaStatus::Ok);
+            }
+            let (a, b) = (c("A"), c("B"));
+            let status = fea_add_member(model, c("M1").as_ptr(), a.as_ptr(), b.as_ptr(), c("Steel").as_ptr(), c("W").as_ptr(), 0.0);
+            assert_eq!(status, FeaStatus::Ok);
+            assert_eq!(fea_add_support(model, a.as_ptr(), true, true, true, true, true, true), FeaStatus::Ok);
+            let dead = c("Dead");
+            assert_eq!(fea_add_node_load(model, b.as_ptr(), 0.0, -1000.0, 0.0, 0.0, 0.0, 0.0, dead.as_ptr()), FeaStatus::Ok);
+            let cases = [dead.as_ptr()];
+            assert_eq!(fea_add_load_combo(model, c("1.4D").as_ptr(), cases.as_ptr(), [1.4].as_ptr(), 1), FeaStatus::Ok);
+            assert_eq!(fea_result_rows(model, FeaResultKind::Displacements), 0);
+            assert_eq!(fea_analyze(model, FeaAnalysisType::Linear), FeaStatus::Ok);
+
+            // Rows follow the names; the tip deflects by P L^3 / 3 E I
+            let rows = fea_result_rows(model, FeaResultKind::Displacements);
+            let names: Vec<String> = (0..rows).map(|row| {
+                let name = fea_result_name(model, FeaResultKind::Displacements, row);
+                let owned = CStr::from_ptr(name).to_str().unwrap().to_string();
+                fea_string_free(name);
+                owned
+            }).collect();
+            let mut values = vec![0.0; rows * fea_result_width(FeaResultKind::Displacements)];
+            let combo = c("1.4D");
+            let status = fea_result_values(model, FeaResultKind::Displacements, combo.as_ptr(), values.as_mut_ptr(), values.len());
+            assert_eq!(status, FeaStatus::Ok);
+            let tip = names.iter().position(|name| name == "B").unwrap();
+            let expected = -1400.0 * 27.0 / (3.0 * 200e9 * 2e-4);
+            assert!((values[6 * tip + 1] - expected).abs() < 1e-9 * expected.abs());
+
+            let mut reaction = [0.0; 6];
+            let status = fea_result_values(model, FeaResultKind::Reactions, combo.as_ptr(), reaction.as_mut_ptr(), 6);
+            assert_eq!(status, FeaStatus::Ok);
+            assert!((reaction[1] - 1400.0).abs() < 1e-6);
+            fea_model_free(model);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        unsafe {
+            let model = fea_model_new();
+            assert_eq!(fea_add_support(model, c("X").as_ptr(), true, true, true, true, true, true), FeaStatus::Error);
+            let message = CStr::from_ptr(fea_last_error()).to_str().unwrap();
+            assert_eq!(message, "Node 'X' not found in model");
+            assert_eq!(fea_add_node(model, ptr::null(), 0.0, 0.0, 0.0), FeaStatus::NullPointer);
+            assert_eq!(fea_add_node(ptr::null_mut(), c("A").as_ptr(), 0.0, 0.0, 0.0), FeaStatus::NullPointer);
+            let status = fea_result_values(model, FeaResultKind::Displacements, c("D").as_ptr(), ptr::null_mut(), 0);
+            assert_eq!(status, FeaStatus::Error);
+            assert!(fea_model_from_json(c("{").as_ptr()).is_null());
+            fea_model_free(model);
+        }
+    }
+}
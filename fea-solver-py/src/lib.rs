@@ -0,0 +1,688 @@
+//! Python bindings for fea-solver
+//!
+//! The `fea_solver` extension module mirrors the API of PyNite's `FEModel3D`
+//! so that existing scripts switch to the Rust solver by changing an import:
+//!
+//! ```python
+//! from fea_solver import FEModel3D
+//!
+//! model = FEModel3D()
+//! model.add_node("N1", 0, 0, 0)
+//! model.add_node("N2", 6, 0, 0)
+//! model.add_material("Steel", 200e9, 77e9, 0.3, 7850)
+//! model.add_section("W", 0.01, 1e-4, 2e-4, 1e-5)
+//! model.add_member("M1", "N1", "N2", "Steel", "W")
+//! model.def_support("N1", True, True, True, True, True, True)
+//! model.add_member_dist_load("M1", "FY", -10e3, -10e3, case="D")
+//! model.add_load_combo("1.4D", {"D": 1.4})
+//! model.analyze_linear()
+//!
+//! print(model.nodes["N2"].DY["1.4D"], model.members["M1"].max_moment("Mz", "1.4D"))
+//! ```
+//!
+//! Method names, argument names and defaults follow PyNite, including
+//! member rotations in degrees and results keyed by combination name on the
+//! `nodes` and `members` proxies. Results use the solver's sign conventions
+//! (see `MemberForces`), and member extremes are searched at
+//! [`STATIONS`] points along the member. The GIL is released while a model
+//! is analysed, and analyses use all cores through the solver's `parallel`
+//! feature.
+
+// The signatures follow PyNite's
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::BTreeMap;
+
+use fea_solver::analysis::AnalysisOptions;
+use fea_solver::elements::{Material, Member, Node, Plate, Quad, Section, Support};
+use fea_solver::error::FEAError as SolverError;
+use fea_solver::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad};
+use fea_solver::model::FEModel;
+use fea_solver::results::{MemberDeflection, MemberForces};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+
+create_exception!(fea_solver, FEAError, PyException, "Error raised by the solver, e.g. for a missing node or an unstable model");
+
+/// Points along a member at which its extreme results are searched
+pub const STATIONS: usize = 101;
+
+/// Node load directions, in PyNite's naming and the solver's DOF order
+const NODE_DIRECTIONS: [&str; 6] = ["FX", "FY", "FZ", "MX", "MY", "MZ"];
+
+/// A structural model with PyNite's `FEModel3D` interface
+#[pyclass(name = "FEModel3D", module = "fea_solver")]
+#[derive(Default)]
+pub struct Model {
+    model: FEModel,
+}
+
+#[pymethods]
+#[allow(non_snake_case)]
+impl Model {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a model, and any saved results, from a fea-solver JSON project file
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(Self { model: FEModel::from_json(json).map_err(solver_error)? })
+    }
+
+    /// Serialise the model to a fea-solver JSON project file
+    #[pyo3(signature = (with_results = false))]
+    fn to_json(&self, with_results: bool) -> PyResult<String> {
+        let json = if with_results { self.model.to_json_with_results() } else { self.model.to_json() };
+        json.map_err(solver_error)
+    }
+
+    // ========================
+    // Model Building
+    // ========================
+
+    /// Add a node at global coordinates (X, Y, Z); Y is vertical
+    fn add_node(&mut self, name: &str, X: f64, Y: f64, Z: f64) -> PyResult<()> {
+        self.model.add_node(name, Node::new(X, Y, Z)).map_err(solver_error)
+    }
+
+    /// Add an isotropic material
+    #[pyo3(signature = (name, E, G, nu, rho, fy = None))]
+    fn add_material(&mut self, name: &str, E: f64, G: f64, nu: f64, rho: f64, fy: Option<f64>) -> PyResult<()> {
+        let mut material = Material::new(E, G, nu, rho);
+        if let Some(fy) = fy {
+            material = material.with_yield_strength(fy);
+        }
+        self.model.add_material(name, material).map_err(solver_error)
+    }
+
+    /// Add a section from its area, second moments of area and torsion constant
+    fn add_section(&mut self, name: &str, A: f64, Iy: f64, Iz: f64, J: f64) -> PyResult<()> {
+        self.model.add_section(name, Section::new(A, Iy, Iz, J)).map_err(solver_error)
+    }
+
+    /// Add a frame member; `rotation` about its axis is in degrees
+    #[pyo3(signature = (name, i_node, j_node, material_name, section_name, rotation = 0.0, tension_only = false, comp_only = false))]
+    fn add_member(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        material_name: &str,
+        section_name: &str,
+        rotation: f64,
+        tension_only: bool,
+        comp_only: bool,
+    ) -> PyResult<()> {
+        let mut member = Member::new(i_node, j_node, material_name, section_name).with_rotation(rotation.to_radians());
+        if tension_only {
+            member = member.tension_only();
+        }
+        if comp_only {
+            member = member.compression_only();
+        }
+        self.model.add_member(name, member).map_err(solver_error)
+    }
+
+    /// Add a rectangular plate
+    #[pyo3(signature = (name, i_node, j_node, m_node, n_node, t, material_name, kx_mod = 1.0, ky_mod = 1.0))]
+    fn add_plate(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        m_node: &str,
+        n_node: &str,
+        t: f64,
+        material_name: &str,
+        kx_mod: f64,
+        ky_mod: f64,
+    ) -> PyResult<()> {
+        let plate = Plate::new(i_node, j_node, m_node, n_node, t, material_name).with_modifiers(kx_mod, ky_mod);
+        self.model.add_plate(name, plate).map_err(solver_error)
+    }
+
+    /// Add a general quadrilateral shell
+    #[pyo3(signature = (name, i_node, j_node, m_node, n_node, t, material_name, kx_mod = 1.0, ky_mod = 1.0))]
+    fn add_quad(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        m_node: &str,
+        n_node: &str,
+        t: f64,
+        material_name: &str,
+        kx_mod: f64,
+        ky_mod: f64,
+    ) -> PyResult<()> {
+        let quad = Quad::new(i_node, j_node, m_node, n_node, t, material_name).with_modifiers(kx_mod, ky_mod);
+        self.model.add_quad(name, quad).map_err(solver_error)
+    }
+
+    /// Restrain DOFs of a node, replacing any support it already has
+    #[pyo3(signature = (node_name, support_DX = false, support_DY = false, support_DZ = false, support_RX = false, support_RY = false, support_RZ = false))]
+    fn def_support(
+        &mut self,
+        node_name: &str,
+        support_DX: bool,
+        support_DY: bool,
+        support_DZ: bool,
+        support_RX: bool,
+        support_RY: bool,
+        support_RZ: bool,
+    ) -> PyResult<()> {
+        let support = Support::with_restraints(support_DX, support_DY, support_DZ, support_RX, support_RY, support_RZ);
+        self.model.add_support(node_name, support).map_err(solver_error)
+    }
+
+    /// Release member end DOFs, in local axes
+    #[pyo3(signature = (member_name, Dxi = false, Dyi = false, Dzi = false, Rxi = false, Ryi = false, Rzi = false,
+                        Dxj = false, Dyj = false, Dzj = false, Rxj = false, Ryj = false, Rzj = false))]
+    fn def_releases(
+        &mut self,
+        member_name: &str,
+        Dxi: bool,
+        Dyi: bool,
+        Dzi: bool,
+        Rxi: bool,
+        Ryi: bool,
+        Rzi: bool,
+        Dxj: bool,
+        Dyj: bool,
+        Dzj: bool,
+        Rxj: bool,
+        Ryj: bool,
+        Rzj: bool,
+    ) -> PyResult<()> {
+        let member = self.model.members.get_mut(member_name)
+            .ok_or_else(|| solver_error(SolverError::MemberNotFound(member_name.to_string())))?;
+        member.releases.i_node = [Dxi, Dyi, Dzi, Rxi, Ryi, Rzi];
+        member.releases.j_node = [Dxj, Dyj, Dzj, Rxj, Ryj, Rzj];
+        Ok(())
+    }
+
+    /// Add a nodal force or moment; `direction` is one of "FX", "FY", "FZ",
+    /// "MX", "MY" or "MZ"
+    #[pyo3(signature = (node_name, direction, P, case = "Case 1"))]
+    fn add_node_load(&mut self, node_name: &str, direction: &str, P: f64, case: &str) -> PyResult<()> {
+        let dof = NODE_DIRECTIONS.iter().position(|d| *d == direction).ok_or_else(|| {
+            PyValueError::new_err(format!("Node load direction must be one of {NODE_DIRECTIONS:?}, not '{direction}'"))
+        })?;
+        let mut load = [0.0; 6];
+        load[dof] = P;
+        let [fx, fy, fz, mx, my, mz] = load;
+        self.model.add_node_load(node_name, NodeLoad::new(fx, fy, fz, mx, my, mz, case)).map_err(solver_error)
+    }
+
+    /// Add a point load at `x` from the i-node, in local ("Fx", ..., "Mz") or
+    /// global ("FX", "FY", "FZ") axes
+    #[pyo3(signature = (member_name, direction, P, x, case = "Case 1"))]
+    fn add_member_pt_load(&mut self, member_name: &str, direction: &str, P: f64, x: f64, case: &str) -> PyResult<()> {
+        let load = PointLoad::new(P, x, load_direction(direction)?, case);
+        self.model.add_member_point_load(member_name, load).map_err(solver_error)
+    }
+
+    /// Add a linearly varying load from `x1` to `x2`, by default the whole member
+    #[pyo3(signature = (member_name, direction, w1, w2, x1 = None, x2 = None, case = "Case 1"))]
+    fn add_member_dist_load(
+        &mut self,
+        member_name: &str,
+        direction: &str,
+        w1: f64,
+        w2: f64,
+        x1: Option<f64>,
+        x2: Option<f64>,
+        case: &str,
+    ) -> PyResult<()> {
+        let (x1, x2) = (x1.unwrap_or(0.0), x2.unwrap_or(f64::INFINITY));
+        let load = DistributedLoad::new(w1, w2, x1, x2, load_direction(direction)?, case);
+        self.model.add_member_dist_load(member_name, load).map_err(solver_error)
+    }
+
+    /// Add a uniform pressure normal to a plate or quad
+    #[pyo3(signature = (plate_name, pressure, case = "Case 1"))]
+    fn add_plate_surface_pressure(&mut self, plate_name: &str, pressure: f64, case: &str) -> PyResult<()> {
+        self.model.add_plate_load(plate_name, PlateLoad::new(pressure, case)).map_err(solver_error)
+    }
+
+    /// Add a load combination from load case factors, e.g. `{"D": 1.2, "L": 1.6}`
+    #[pyo3(signature = (name, factors, combo_tags = None))]
+    fn add_load_combo(&mut self, name: &str, factors: BTreeMap<String, f64>, combo_tags: Option<Vec<String>>) -> PyResult<()> {
+        let mut combo = LoadCombination::new(name);
+        combo.factors = factors;
+        combo.tags = combo_tags.unwrap_or_default();
+        self.model.add_load_combo(combo).map_err(solver_error)
+    }
+
+    // ========================
+    // Analysis
+    // ========================
+
+    /// Analyse with tension/compression-only members and one-way supports
+    /// iterated to convergence
+    #[pyo3(signature = (log = false, check_stability = true, check_statics = false, max_iter = 30, sparse = true, combo_tags = None))]
+    fn analyze(
+        &mut self,
+        py: Python<'_>,
+        log: bool,
+        check_stability: bool,
+        check_statics: bool,
+        max_iter: usize,
+        sparse: bool,
+        combo_tags: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let options = AnalysisOptions { log, check_stability, check_statics, max_iterations: max_iter, sparse, combo_tags, ..AnalysisOptions::nonlinear() };
+        self.run(py, options)
+    }
+
+    /// First-order linear elastic analysis
+    #[pyo3(signature = (log = false, check_stability = true, check_statics = false, sparse = true, combo_tags = None))]
+    fn analyze_linear(
+        &mut self,
+        py: Python<'_>,
+        log: bool,
+        check_stability: bool,
+        check_statics: bool,
+        sparse: bool,
+        combo_tags: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let options = AnalysisOptions { log, check_stability, check_statics, sparse, combo_tags, ..AnalysisOptions::linear() };
+        self.run(py, options)
+    }
+
+    /// Second-order P-Delta analysis
+    #[pyo3(signature = (log = false, check_stability = true, max_iter = 30, sparse = true, combo_tags = None))]
+    fn analyze_PDelta(
+        &mut self,
+        py: Python<'_>,
+        log: bool,
+        check_stability: bool,
+        max_iter: usize,
+        sparse: bool,
+        combo_tags: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let options = AnalysisOptions { log, check_stability, max_iterations: max_iter, sparse, combo_tags, ..AnalysisOptions::p_delta() };
+        self.run(py, options)
+    }
+
+    // ========================
+    // Results
+    // ========================
+
+    /// Nodes by name, with their coordinates and results
+    #[getter]
+    fn nodes(slf: &Bound<'_, Self>) -> BTreeMap<String, Node3D> {
+        slf.borrow().model.nodes.keys()
+            .map(|name| (name.clone(), Node3D { model: slf.clone().unbind(), name: name.clone() }))
+            .collect()
+    }
+
+    /// Members, including physical members, by name, with their results
+    #[getter]
+    fn members(slf: &Bound<'_, Self>) -> BTreeMap<String, Member3D> {
+        let model = &slf.borrow().model;
+        model.members.keys().chain(model.physical_members.keys())
+            .map(|name| (name.clone(), Member3D { model: slf.clone().unbind(), name: name.clone() }))
+            .collect()
+    }
+
+    /// Load combinations by name, as load case factors
+    #[getter]
+    fn load_combos(&self) -> BTreeMap<String, BTreeMap<String, f64>> {
+        self.model.load_combos.iter().map(|(name, combo)| (name.clone(), combo.factors.clone())).collect()
+    }
+
+    /// Warnings raised by the last analysis
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.model.warnings().to_vec()
+    }
+}
+
+impl Model {
+    /// Analyse without holding the GIL
+    fn run(&mut self, py: Python<'_>, options: AnalysisOptions) -> PyResult<()> {
+        let model = &mut self.model;
+        py.detach(|| model.analyze(options)).map_err(solver_error)
+    }
+}
+
+/// A node of a model, with PyNite's result dictionaries keyed by load combination
+#[pyclass(name = "Node3D", module = "fea_solver", frozen)]
+pub struct Node3D {
+    model: Py<Model>,
+    name: String,
+}
+
+#[pymethods]
+#[allow(non_snake_case)]
+impl Node3D {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[getter]
+    fn X(&self, py: Python<'_>) -> f64 {
+        self.coordinate(py, 0)
+    }
+
+    #[getter]
+    fn Y(&self, py: Python<'_>) -> f64 {
+        self.coordinate(py, 1)
+    }
+
+    #[getter]
+    fn Z(&self, py: Python<'_>) -> f64 {
+        self.coordinate(py, 2)
+    }
+
+    #[getter]
+    fn DX(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 0)
+    }
+
+    #[getter]
+    fn DY(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 1)
+    }
+
+    #[getter]
+    fn DZ(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 2)
+    }
+
+    #[getter]
+    fn RX(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 3)
+    }
+
+    #[getter]
+    fn RY(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 4)
+    }
+
+    #[getter]
+    fn RZ(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.displacement(py, 5)
+    }
+
+    #[getter]
+    fn RxnFX(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 0)
+    }
+
+    #[getter]
+    fn RxnFY(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 1)
+    }
+
+    #[getter]
+    fn RxnFZ(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 2)
+    }
+
+    #[getter]
+    fn RxnMX(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 3)
+    }
+
+    #[getter]
+    fn RxnMY(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 4)
+    }
+
+    #[getter]
+    fn RxnMZ(&self, py: Python<'_>) -> BTreeMap<String, f64> {
+        self.reaction(py, 5)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Node3D('{}')", self.name)
+    }
+}
+
+impl Node3D {
+    fn coordinate(&self, py: Python<'_>, axis: usize) -> f64 {
+        self.model.borrow(py).model.nodes.get(&self.name).map_or(f64::NAN, |node| node.coords()[axis])
+    }
+
+    /// Displacement DOF of every solved combination
+    fn displacement(&self, py: Python<'_>, dof: usize) -> BTreeMap<String, f64> {
+        let model = self.model.borrow(py);
+        let table = &model.model.result_store().displacements;
+        table.combos()
+            .filter_map(|combo| Some((combo.to_string(), table.get(&self.name, combo)?[dof])))
+            .collect()
+    }
+
+    /// Reaction of every solved combination, zero for an unsupported node as in PyNite
+    fn reaction(&self, py: Python<'_>, dof: usize) -> BTreeMap<String, f64> {
+        let model = self.model.borrow(py);
+        let store = model.model.result_store();
+        store.displacements.combos()
+            .map(|combo| {
+                let reaction = store.reactions.get(&self.name, combo).map_or(0.0, |r| r[dof]);
+                (combo.to_string(), reaction)
+            })
+            .collect()
+    }
+}
+
+/// A member of a model, with PyNite's result methods
+#[pyclass(name = "Member3D", module = "fea_solver", frozen)]
+pub struct Member3D {
+    model: Py<Model>,
+    name: String,
+}
+
+#[pymethods]
+#[allow(non_snake_case)]
+impl Member3D {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Length of the member
+    fn L(&self, py: Python<'_>) -> PyResult<f64> {
+        member_length(&self.model.borrow(py).model, &self.name)
+    }
+
+    /// Shear force "Fy" or "Fz" at `x` from the i-node
+    #[pyo3(signature = (Direction, x, combo_name = "Combo 1"))]
+    fn shear(&self, py: Python<'_>, Direction: &str, x: f64, combo_name: &str) -> PyResult<f64> {
+        self.force(py, x, combo_name, shear_component(Direction)?)
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn max_shear(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        self.max_force(py, combo_name, shear_component(Direction)?)
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn min_shear(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        self.min_force(py, combo_name, shear_component(Direction)?)
+    }
+
+    /// Bending moment "My" or "Mz" at `x` from the i-node
+    #[pyo3(signature = (Direction, x, combo_name = "Combo 1"))]
+    fn moment(&self, py: Python<'_>, Direction: &str, x: f64, combo_name: &str) -> PyResult<f64> {
+        self.force(py, x, combo_name, moment_component(Direction)?)
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn max_moment(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        self.max_force(py, combo_name, moment_component(Direction)?)
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn min_moment(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        self.min_force(py, combo_name, moment_component(Direction)?)
+    }
+
+    /// Torsion at `x` from the i-node
+    #[pyo3(signature = (x, combo_name = "Combo 1"))]
+    fn torque(&self, py: Python<'_>, x: f64, combo_name: &str) -> PyResult<f64> {
+        self.force(py, x, combo_name, |f| f.torsion)
+    }
+
+    #[pyo3(signature = (combo_name = "Combo 1"))]
+    fn max_torque(&self, py: Python<'_>, combo_name: &str) -> PyResult<f64> {
+        self.max_force(py, combo_name, |f| f.torsion)
+    }
+
+    #[pyo3(signature = (combo_name = "Combo 1"))]
+    fn min_torque(&self, py: Python<'_>, combo_name: &str) -> PyResult<f64> {
+        self.min_force(py, combo_name, |f| f.torsion)
+    }
+
+    /// Axial force at `x` from the i-node, positive in tension
+    #[pyo3(signature = (x, combo_name = "Combo 1"))]
+    fn axial(&self, py: Python<'_>, x: f64, combo_name: &str) -> PyResult<f64> {
+        self.force(py, x, combo_name, |f| f.axial)
+    }
+
+    #[pyo3(signature = (combo_name = "Combo 1"))]
+    fn max_axial(&self, py: Python<'_>, combo_name: &str) -> PyResult<f64> {
+        self.max_force(py, combo_name, |f| f.axial)
+    }
+
+    #[pyo3(signature = (combo_name = "Combo 1"))]
+    fn min_axial(&self, py: Python<'_>, combo_name: &str) -> PyResult<f64> {
+        self.min_force(py, combo_name, |f| f.axial)
+    }
+
+    /// Local deflection "dy" or "dz" at `x` from the i-node
+    #[pyo3(signature = (Direction, x, combo_name = "Combo 1"))]
+    fn deflection(&self, py: Python<'_>, Direction: &str, x: f64, combo_name: &str) -> PyResult<f64> {
+        let (total, _) = deflection_components(Direction)?;
+        Ok(total(&self.deflection_at(py, x, combo_name)?))
+    }
+
+    /// Local deflection "dy" or "dz" at `x`, relative to the chord between
+    /// the displaced member ends
+    #[pyo3(signature = (Direction, x, combo_name = "Combo 1"))]
+    fn rel_deflection(&self, py: Python<'_>, Direction: &str, x: f64, combo_name: &str) -> PyResult<f64> {
+        let (_, relative) = deflection_components(Direction)?;
+        Ok(relative(&self.deflection_at(py, x, combo_name)?))
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn max_deflection(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        let (total, _) = deflection_components(Direction)?;
+        Ok(self.deflections(py, combo_name)?.iter().map(total).fold(f64::NEG_INFINITY, f64::max))
+    }
+
+    #[pyo3(signature = (Direction, combo_name = "Combo 1"))]
+    fn min_deflection(&self, py: Python<'_>, Direction: &str, combo_name: &str) -> PyResult<f64> {
+        let (total, _) = deflection_components(Direction)?;
+        Ok(self.deflections(py, combo_name)?.iter().map(total).fold(f64::INFINITY, f64::min))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Member3D('{}')", self.name)
+    }
+}
+
+type ForceComponent = fn(&MemberForces) -> f64;
+type DeflectionComponent = fn(&MemberDeflection) -> f64;
+
+impl Member3D {
+    fn force(&self, py: Python<'_>, x: f64, combo: &str, component: ForceComponent) -> PyResult<f64> {
+        let model = self.model.borrow(py);
+        Ok(component(&model.model.member_forces_at(&self.name, x, combo).map_err(solver_error)?))
+    }
+
+    fn max_force(&self, py: Python<'_>, combo: &str, component: ForceComponent) -> PyResult<f64> {
+        Ok(self.forces(py, combo)?.iter().map(component).fold(f64::NEG_INFINITY, f64::max))
+    }
+
+    fn min_force(&self, py: Python<'_>, combo: &str, component: ForceComponent) -> PyResult<f64> {
+        Ok(self.forces(py, combo)?.iter().map(component).fold(f64::INFINITY, f64::min))
+    }
+
+    /// Internal forces at the search stations
+    fn forces(&self, py: Python<'_>, combo: &str) -> PyResult<Vec<MemberForces>> {
+        let model = &self.model.borrow(py).model;
+        let length = member_length(model, &self.name)?;
+        (0..STATIONS)
+            .map(|k| {
+                let x = length * k as f64 / (STATIONS - 1) as f64;
+                model.member_forces_at(&self.name, x, combo).map_err(solver_error)
+            })
+            .collect()
+    }
+
+    fn deflection_at(&self, py: Python<'_>, x: f64, combo: &str) -> PyResult<MemberDeflection> {
+        self.model.borrow(py).model.member_deflection_at(&self.name, x, combo).map_err(solver_error)
+    }
+
+    fn deflections(&self, py: Python<'_>, combo: &str) -> PyResult<Vec<MemberDeflection>> {
+        self.model.borrow(py).model.member_deflections(&self.name, combo, STATIONS).map_err(solver_error)
+    }
+}
+
+/// Length of an analysis or physical member, from its end nodes before the
+/// model has been analysed
+fn member_length(model: &FEModel, name: &str) -> PyResult<f64> {
+    let member = match model.physical_members.get(name) {
+        Some(physical) if physical.length().is_some() => return Ok(physical.length().unwrap()),
+        Some(physical) => &physical.member,
+        None => model.members.get(name).ok_or_else(|| solver_error(SolverError::MemberNotFound(name.to_string())))?,
+    };
+    if let Some(length) = member.length() {
+        return Ok(length);
+    }
+    let node = |name: &str| model.nodes.get(name).ok_or_else(|| solver_error(SolverError::NodeNotFound(name.to_string())));
+    Ok(node(&member.i_node)?.distance_to(node(&member.j_node)?))
+}
+
+fn shear_component(direction: &str) -> PyResult<ForceComponent> {
+    match direction {
+        "Fy" => Ok(|f| f.shear_y),
+        "Fz" => Ok(|f| f.shear_z),
+        _ => Err(PyValueError::new_err(format!("Shear direction must be 'Fy' or 'Fz', not '{direction}'"))),
+    }
+}
+
+fn moment_component(direction: &str) -> PyResult<ForceComponent> {
+    match direction {
+        "My" => Ok(|f| f.moment_y),
+        "Mz" => Ok(|f| f.moment_z),
+        _ => Err(PyValueError::new_err(format!("Moment direction must be 'My' or 'Mz', not '{direction}'"))),
+    }
+}
+
+/// Total and chord-relative components of a deflection direction
+fn deflection_components(direction: &str) -> PyResult<(DeflectionComponent, DeflectionComponent)> {
+    match direction {
+        "dy" => Ok((|d| d.dy, |d| d.relative_y)),
+        "dz" => Ok((|d| d.dz, |d| d.relative_z)),
+        _ => Err(PyValueError::new_err(format!("Deflection direction must be 'dy' or 'dz', not '{direction}'"))),
+    }
+}
+
+/// Member load direction from its name, e.g. "Fy" or "FY"
+fn load_direction(name: &str) -> PyResult<LoadDirection> {
+    serde_json::from_value(serde_json::Value::from(name))
+        .map_err(|_| PyValueError::new_err(format!("Unknown member load direction '{name}'")))
+}
+
+fn solver_error(error: SolverError) -> PyErr {
+    FEAError::new_err(error.to_string())
+}
+
+/// Rust finite element solver for 3D frames and shells
+#[pymodule(name = "fea_solver")]
+fn fea_solver_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Model>()?;
+    m.add_class::<Node3D>()?;
+    m.add_class::<Member3D>()?;
+    m.add("FEAError", m.py().get_type::<FEAError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+"""Checks of the Python bindings against closed-form beam results
+
+Run after `maturin develop` with `python -m unittest discover tests`.
+"""
+
+import unittest
+
+from fea_solver import FEAError, FEModel3D
+
+E, G = 200e9, 77e9
+A, IY, IZ, J = 0.01, 1e-4, 2e-4, 1e-5
+
+
+def beam(span, supports):
+    model = FEModel3D()
+    model.add_material("Steel", E, G, 0.3, 7850)
+    model.add_section("W", A, IY, IZ, J)
+    model.add_node("N1", 0, 0, 0)
+    model.add_node("N2", span, 0, 0)
+    model.add_member("M1", "N1", "N2", "Steel", "W")
+    for node, restraints in supports.items():
+        model.def_support(node, *restraints)
+    return model
+
+
+class CantileverTest(unittest.TestCase):
+    def test_tip_load(self):
+        span, p = 3.0, 10e3
+        model = beam(span, {"N1": [True] * 6})
+        model.add_node_load("N2", "FY", -p, case="D")
+        model.add_load_combo("1.4D", {"D": 1.4})
+        model.analyze_linear()
+
+        load = 1.4 * p
+        self.assertAlmostEqual(model.nodes["N2"].DY["1.4D"], -load * span**3 / (3 * E * IZ), delta=1e-9)
+        self.assertAlmostEqual(model.nodes["N1"].RxnFY["1.4D"], load, delta=1e-6)
+        self.assertEqual(model.nodes["N2"].RxnFY["1.4D"], 0.0)
+        member = model.members["M1"]
+        self.assertAlmostEqual(member.L(), span)
+        self.assertAlmostEqual(abs(member.moment("Mz", 0.0, "1.4D")), load * span, delta=1e-6)
+        self.assertAlmostEqual(abs(member.shear("Fy", span / 2, "1.4D")), load, delta=1e-6)
+        extreme = max(abs(member.max_moment("Mz", "1.4D")), abs(member.min_moment("Mz", "1.4D")))
+        self.assertAlmostEqual(extreme, load * span, delta=1e-6)
+        self.assertAlmostEqual(member.deflection("dy", span, "1.4D"), model.nodes["N2"].DY["1.4D"], delta=1e-12)
+
+    def test_errors(self):
+        model = beam(3.0, {"N1": [True] * 6})
+        with self.assertRaises(FEAError):
+            model.add_node_load("N9", "FY", -1.0)
+        with self.assertRaises(ValueError):
+            model.add_member_pt_load("M1", "Fw", -1.0, 1.0)
+        with self.assertRaises(ValueError):
+            model.members["M1"].moment("Fy", 0.0)
+
+
+class SimplySupportedTest(unittest.TestCase):
+    def test_uniform_load(self):
+        span, w = 6.0, 5e3
+        model = beam(span, {"N1": [True, True, True, True, False, False], "N2": [False, True, True, False, False, False]})
+        model.add_member_dist_load("M1", "FY", -w, -w)
+        model.analyze()
+
+        member = model.members["M1"]
+        midspan = -5 * w * span**4 / (384 * E * IZ)
+        self.assertAlmostEqual(member.deflection("dy", span / 2), midspan, delta=1e-9)
+        self.assertAlmostEqual(member.min_deflection("dy"), midspan, delta=1e-9)
+        self.assertAlmostEqual(member.rel_deflection("dy", span / 2), midspan, delta=1e-9)
+        self.assertAlmostEqual(abs(member.moment("Mz", span / 2)), w * span**2 / 8, delta=1e-6)
+        self.assertEqual(list(model.load_combos), ["Combo 1"])
+
+
+if __name__ == "__main__":
+    unittest.main()
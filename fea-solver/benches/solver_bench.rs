@@ -1,6 +1,9 @@
 //! Benchmarks for FEA solver
 
+use std::collections::HashMap;
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fea_solver::math::{self, sparse::SparseMatrixBuilder};
 use fea_solver::prelude::*;
 
 fn create_cantilever_model() -> FEModel {
@@ -116,12 +119,71 @@ fn benchmark_pdelta(c: &mut Criterion) {
     });
 }
 
+/// Member stiffness assembly driven by name lookups, as done before handles
+fn assemble_by_name(model: &FEModel, builder: &mut SparseMatrixBuilder) {
+    let dof_map: HashMap<String, usize> = model.nodes.keys()
+        .map(|name| (name.clone(), model.node_id(name).unwrap().index() * 6))
+        .collect();
+    
+    for member in model.members.values() {
+        let i_node = &model.nodes[&member.i_node];
+        let j_node = &model.nodes[&member.j_node];
+        let material = &model.materials[&member.material];
+        let section = &model.sections[&member.section];
+        let length = i_node.distance_to(j_node);
+        
+        let k_local = math::member_local_stiffness(
+            material.e, material.g, section.a, section.iy, section.iz, section.j, length,
+        ) + math::member_foundation_stiffness(member.foundation_modulus, length);
+        let k_local = math::apply_releases(&k_local, &member.releases.as_array());
+        let t = math::member_transformation_matrix(&i_node.coords(), &j_node.coords(), member.rotation);
+        let k = t.transpose() * k_local * t;
+        
+        let dofs = [dof_map[&member.i_node], dof_map[&member.j_node]];
+        for (ni, &di) in dofs.iter().enumerate() {
+            for (nj, &dj) in dofs.iter().enumerate() {
+                for a in 0..6 {
+                    for b in 0..6 {
+                        builder.add(di + a, dj + b, k[(ni * 6 + a, nj * 6 + b)]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn benchmark_assembly_50k_members(c: &mut Criterion) {
+    // 100 stories x 250 bays = 50,100 members
+    let mut model = create_multi_story_frame(100, 250);
+    model.prepare().unwrap();
+    let n_dofs = model.nodes.len() * 6;
+    
+    let mut group = c.benchmark_group("assembly_50k_members");
+    group.sample_size(10);
+    group.bench_function("string_keys", |b| {
+        b.iter(|| {
+            let mut builder = SparseMatrixBuilder::new(n_dofs);
+            assemble_by_name(&model, &mut builder);
+            black_box(builder.nnz());
+        })
+    });
+    group.bench_function("handles", |b| {
+        b.iter(|| {
+            let mut builder = SparseMatrixBuilder::new(n_dofs);
+            model.assemble_stiffness(&mut builder).unwrap();
+            black_box(builder.nnz());
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_cantilever,
     benchmark_small_frame,
     benchmark_medium_frame,
     benchmark_pdelta,
+    benchmark_assembly_50k_members,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,136 @@
+//! Conditioning diagnostics for the free stiffness matrix
+//!
+//! An ill-conditioned stiffness solves without error but loses roughly
+//! `log10(condition number)` of the 16 significant digits of a double, which
+//! shows up as noisy or nonsensical results. The condition number is
+//! estimated by power iteration for the largest eigenvalue and inverse
+//! iteration for the smallest, after diagonal scaling so that mixing metres
+//! and radians does not count against the model. The usual culprits, nearly
+//! free DOFs and members far stiffer than their neighbours, are listed too.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::Factorization;
+use crate::math::{Mat, Vec as FEVec};
+
+/// Condition number above which results are flagged as unreliable
+pub const ILL_CONDITIONED: f64 = 1e10;
+
+/// Stiffness ratio between members at a node above which the pair is flagged
+pub const EXTREME_STIFFNESS_RATIO: f64 = 1e6;
+
+/// Two members meeting at a node with very different stiffness
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StiffnessRatio {
+    /// Shared node
+    pub node: String,
+    /// The stiffer member
+    pub stiff_member: String,
+    /// The more flexible member
+    pub flexible_member: String,
+    /// Ratio of their axial (EA/L) or flexural (12EI/L^3) stiffness, whichever is larger
+    pub ratio: f64,
+}
+
+impl fmt::Display for StiffnessRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is {:.1e} times stiffer than {} at node {}",
+            self.stiff_member, self.ratio, self.flexible_member, self.node)
+    }
+}
+
+/// Conditioning of a model's free stiffness matrix
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConditioningReport {
+    /// Estimated 2-norm condition number of the diagonally scaled free stiffness
+    pub condition_number: f64,
+    /// Free DOFs dominating the softest deformation mode, e.g. "N2 RX", worst first
+    pub softest_dofs: Vec<String>,
+    /// Member pairs with the largest stiffness ratios, worst first
+    pub stiffness_ratios: Vec<StiffnessRatio>,
+}
+
+impl ConditioningReport {
+    /// Whether the condition number is high enough to distrust the results
+    pub fn is_ill_conditioned(&self) -> bool {
+        self.condition_number > ILL_CONDITIONED
+    }
+
+    /// Warnings for an ill-conditioned matrix and extreme stiffness ratios
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.is_ill_conditioned() {
+            warnings.push(format!(
+                "Stiffness matrix is ill-conditioned (condition number ~{:.1e}); softest DOFs: {}",
+                self.condition_number,
+                self.softest_dofs.join(", "),
+            ));
+        }
+        warnings.extend(self.stiffness_ratios.iter()
+            .filter(|r| r.ratio > EXTREME_STIFFNESS_RATIO)
+            .map(|r| format!("Extreme stiffness ratio: {r}")));
+        warnings
+    }
+}
+
+/// Estimate the condition number of a symmetric positive definite matrix
+///
+/// `factorization` must factorise `a`. Returns the ratio of the largest to
+/// the smallest eigenvalue estimate after `iterations` power and inverse
+/// iterations, with the eigenvector of the smallest.
+pub fn estimate_condition(a: &Mat, factorization: &dyn Factorization, iterations: usize) -> (f64, FEVec) {
+    let n = a.nrows();
+    // Uneven start vector, so it is not orthogonal to a symmetric mode
+    let start = FEVec::from_fn(n, |i, _| 1.0 + (i as f64 * 0.618_034).fract());
+
+    let mut x = start.normalize();
+    let mut largest = 0.0;
+    for _ in 0..iterations {
+        let y = a * &x;
+        largest = x.dot(&y);
+        x = y.normalize();
+    }
+
+    let mut x = start.normalize();
+    let mut smallest_inverse = 0.0;
+    for _ in 0..iterations {
+        let y = factorization.solve(&x);
+        smallest_inverse = x.dot(&y);
+        x = y.normalize();
+    }
+
+    (largest * smallest_inverse.abs(), x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{LinearSolver, SolverBackend};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_condition_estimate() {
+        let a = Mat::from_diagonal(&FEVec::from_vec(vec![2.0, 1e-6, 5.0, 1.0]));
+        let factorization = SolverBackend::DenseLu.factorize(&a).unwrap();
+        let (condition, softest) = estimate_condition(&a, &*factorization, 30);
+        assert_relative_eq!(condition, 5e6, max_relative = 1e-6);
+        assert_relative_eq!(softest[1].abs(), 1.0, max_relative = 1e-9);
+
+        let report = ConditioningReport {
+            condition_number: 2e12,
+            softest_dofs: vec!["N2 RX".to_string()],
+            stiffness_ratios: vec![StiffnessRatio {
+                node: "N2".to_string(),
+                stiff_member: "M1".to_string(),
+                flexible_member: "M2".to_string(),
+                ratio: 3e7,
+            }],
+        };
+        assert_eq!(report.warnings(), [
+            "Stiffness matrix is ill-conditioned (condition number ~2.0e12); softest DOFs: N2 RX",
+            "Extreme stiffness ratio: M1 is 3.0e7 times stiffer than M2 at node N2",
+        ]);
+    }
+}
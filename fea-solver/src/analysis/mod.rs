@@ -1,28 +1,121 @@
 //! Analysis types and options
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::{FEAError, FEAResult};
+
+mod conditioning;
+mod modal;
+pub(crate) mod parallel;
+mod solver;
+pub(crate) mod stepped;
+mod time_history;
+
+pub use conditioning::{
+    estimate_condition, ConditioningReport, StiffnessRatio, EXTREME_STIFFNESS_RATIO, ILL_CONDITIONED,
+};
+pub use modal::{subspace_iteration, ModalResults, Mode};
+pub use solver::{Factorization, LinearSolver, SolverBackend};
+pub use stepped::{AnalysisProgress, AnalysisStage, SteppedAnalysis, DEFAULT_CHUNK_SIZE};
+pub use time_history::{LinkHistory, TimeHistory, TimeHistoryResults};
+
 /// Type of structural analysis to perform
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum AnalysisType {
     /// First-order linear static analysis
+    #[default]
     Linear,
     /// Second-order P-Delta analysis
     PDelta,
-    /// Nonlinear analysis with tension/compression only members
+    /// Nonlinear analysis iterating on tension/compression-only members,
+    /// one-way supports, gap/hook elements and compression-only foundations
     Nonlinear,
     /// Modal (eigenvalue) analysis for natural frequencies
     Modal,
+    /// Geometrically nonlinear (large displacement) analysis using a
+    /// co-rotational frame formulation with Newton-Raphson iteration
+    LargeDisplacement,
 }
 
-impl Default for AnalysisType {
+/// Shared flag for aborting a running analysis from another thread
+///
+/// Clones share the flag. The analysis checks it between assembly stages,
+/// load combinations, iterations and time steps, and stops with
+/// [`FEAError::Cancelled`], leaving the model unanalysed:
+///
+/// ```rust
+/// use fea_solver::prelude::*;
+///
+/// let token = CancelToken::new();
+/// let options = AnalysisOptions::p_delta().with_cancel_token(token.clone());
+/// // e.g. from a UI "Stop" button on another thread
+/// token.cancel();
+/// # let _ = options;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Create a token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every analysis holding this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`FEAError::Cancelled`] once cancellation has been requested
+    pub fn check(&self) -> FEAResult<()> {
+        if self.is_cancelled() {
+            Err(FEAError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Arc-length (Riks/Crisfield) continuation settings
+///
+/// Under arc-length control the load factor is solved for together with the
+/// displacements, so the solver can follow the equilibrium path through limit
+/// points and snap-back where pure load control fails to converge. The initial
+/// arc length corresponds to the first of `AnalysisOptions::load_steps` increments.
+///
+/// Only large displacement analysis follows the path this way. The P-Delta
+/// and nonlinear support iterations always apply the full load, and they
+/// reject arc-length settings along with modal analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArcLengthOptions {
+    /// Maximum number of arc-length increments to reach the full load
+    pub max_increments: usize,
+    /// Desired corrector iterations per increment, used to adapt the arc length
+    pub target_iterations: usize,
+}
+
+impl Default for ArcLengthOptions {
     fn default() -> Self {
-        Self::Linear
+        Self {
+            max_increments: 200,
+            target_iterations: 5,
+        }
     }
 }
 
 /// Options for structural analysis
+///
+/// Fields missing when deserialising take their default values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AnalysisOptions {
     /// Type of analysis
     pub analysis_type: AnalysisType,
@@ -30,18 +123,33 @@ pub struct AnalysisOptions {
     pub check_stability: bool,
     /// Check static equilibrium after analysis
     pub check_statics: bool,
+    /// Estimate the stiffness condition number before solving and add any
+    /// ill-conditioning to the model warnings
+    pub check_conditioning: bool,
     /// Maximum iterations for nonlinear/P-Delta analysis
     pub max_iterations: usize,
     /// Convergence tolerance
     pub tolerance: f64,
     /// Use sparse matrix solver
     pub sparse: bool,
+    /// Solver for the static stiffness systems; large displacement analysis
+    /// always uses the dense LU, as its tangent stiffness may be indefinite
+    pub solver: SolverBackend,
     /// Load combination tags to analyze (None = all)
     pub combo_tags: Option<Vec<String>>,
     /// Number of modes to calculate (for modal analysis)
     pub num_modes: usize,
+    /// Number of equal load increments (for large displacement analysis)
+    pub load_steps: usize,
+    /// Arc-length path following for large displacement analysis (None =
+    /// load control); other analysis types fail with [`FEAError::InvalidInput`]
+    /// when it is set
+    pub arc_length: Option<ArcLengthOptions>,
     /// Enable logging/progress output
     pub log: bool,
+    /// Token for aborting the analysis
+    #[serde(skip)]
+    pub cancel: CancelToken,
 }
 
 impl Default for AnalysisOptions {
@@ -50,12 +158,17 @@ impl Default for AnalysisOptions {
             analysis_type: AnalysisType::Linear,
             check_stability: true,
             check_statics: false,
+            check_conditioning: false,
             max_iterations: 30,
             tolerance: 1e-6,
             sparse: true,
+            solver: SolverBackend::Auto,
             combo_tags: None,
             num_modes: 12,
+            load_steps: 10,
+            arc_length: None,
             log: false,
+            cancel: CancelToken::new(),
         }
     }
 }
@@ -83,12 +196,35 @@ impl AnalysisOptions {
         }
     }
 
+    /// Create options for nonlinear (one-way support and gap/hook) analysis
+    pub fn nonlinear() -> Self {
+        Self {
+            analysis_type: AnalysisType::Nonlinear,
+            ..Self::default()
+        }
+    }
+
+    /// Create options for large displacement (co-rotational) analysis
+    pub fn large_displacement(load_steps: usize) -> Self {
+        Self {
+            analysis_type: AnalysisType::LargeDisplacement,
+            load_steps,
+            ..Self::default()
+        }
+    }
+
     /// Enable logging
     pub fn with_logging(mut self) -> Self {
         self.log = true;
         self
     }
 
+    /// Check the stiffness conditioning before solving
+    pub fn with_conditioning_check(mut self) -> Self {
+        self.check_conditioning = true;
+        self
+    }
+
     /// Set maximum iterations
     pub fn with_max_iter(mut self, max_iter: usize) -> Self {
         self.max_iterations = max_iter;
@@ -101,9 +237,33 @@ impl AnalysisOptions {
         self
     }
 
+    /// Set the number of load increments
+    pub fn with_load_steps(mut self, load_steps: usize) -> Self {
+        self.load_steps = load_steps;
+        self
+    }
+
+    /// Use arc-length control with default settings
+    pub fn with_arc_length(mut self) -> Self {
+        self.arc_length = Some(ArcLengthOptions::default());
+        self
+    }
+
     /// Filter by combo tags
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.combo_tags = Some(tags);
         self
     }
+
+    /// Set the linear equation solver
+    pub fn with_solver(mut self, solver: SolverBackend) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Abort the analysis when the token is cancelled
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
 }
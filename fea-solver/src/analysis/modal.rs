@@ -0,0 +1,160 @@
+//! Modal (natural vibration) analysis results and eigensolver
+//!
+//! The lowest modes of `K x = w^2 M x` are found by subspace iteration
+//! (Bathe): a block of trial vectors is repeatedly multiplied by `K^-1 M`,
+//! using a single factorisation of the free stiffness, and the projected
+//! problem is solved in the subspace at every step. Only the factorised
+//! stiffness and the lumped (diagonal) mass are needed, so the cost grows
+//! with the stiffness profile rather than with the square of the DOF count.
+//! DOFs without mass are condensed out implicitly by the `K^-1` product.
+
+use std::collections::BTreeMap;
+
+use nalgebra::SymmetricEigen;
+use serde::{Deserialize, Serialize};
+
+use super::{CancelToken, Factorization};
+use crate::error::{FEAError, FEAResult};
+use crate::math::{Mat, Vec as FEVec};
+
+/// One natural mode of vibration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mode {
+    /// Natural frequency (Hz)
+    pub frequency: f64,
+    /// Natural period (s)
+    pub period: f64,
+    /// Mass-normalised shape [DX, DY, DZ, RX, RY, RZ] by node name, with its
+    /// largest component positive
+    pub shape: BTreeMap<String, [f64; 6]>,
+    /// Modal participation factors in global X, Y and Z
+    pub participation: [f64; 3],
+    /// Effective modal mass in X, Y and Z as a fraction of the free mass
+    pub mass_ratio: [f64; 3],
+}
+
+/// Results of a modal analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModalResults {
+    /// Modes in order of increasing frequency
+    pub modes: Vec<Mode>,
+    /// Mass free to move in global X, Y and Z
+    pub free_mass: [f64; 3],
+}
+
+impl ModalResults {
+    /// Sum of the effective mass ratios of every mode found, in X, Y and Z
+    pub fn cumulative_mass_ratio(&self) -> [f64; 3] {
+        let mut total = [0.0; 3];
+        for mode in &self.modes {
+            for (sum, ratio) in total.iter_mut().zip(mode.mass_ratio) {
+                *sum += ratio;
+            }
+        }
+        total
+    }
+}
+
+/// Lowest eigenpairs of `K x = lambda M x` for a diagonal mass
+///
+/// Returns the eigenvalues in ascending order with the mass-normalised
+/// eigenvectors as columns. Convergence is reached when no wanted
+/// eigenvalue changes by more than `tolerance` (relative) in an iteration.
+pub fn subspace_iteration(
+    stiffness: &dyn Factorization,
+    mass: &FEVec,
+    num_modes: usize,
+    max_iterations: usize,
+    tolerance: f64,
+    cancel: &CancelToken,
+) -> FEAResult<(Vec<f64>, Mat)> {
+    let n = mass.len();
+    let massed = mass.iter().filter(|&&m| m > 0.0).count();
+    let p = num_modes.min(massed);
+    if p == 0 {
+        return Err(FEAError::InvalidInput("Modal analysis needs mass on at least one free DOF".to_string()));
+    }
+    let q = (2 * p).min(p + 8).min(massed);
+
+    // Start from the mass distribution plus mass-weighted pseudo-random
+    // vectors, which cannot all miss a mode the way unit vectors can
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = move || {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    let mut x = Mat::from_fn(n, q, |i, j| if j == 0 { mass[i] } else { mass[i] * random() });
+
+    let mut previous: Vec<f64> = Vec::new();
+    let mut residuals = Vec::new();
+    for _ in 0..max_iterations {
+        cancel.check()?;
+
+        // Inverse iteration on the block, then Rayleigh-Ritz in the subspace
+        let y = Mat::from_fn(n, q, |i, j| mass[i] * x[(i, j)]);
+        let mut xb = Mat::zeros(n, q);
+        for j in 0..q {
+            xb.set_column(j, &stiffness.solve(&y.column(j).into_owned()));
+        }
+        let kr = symmetric(xb.tr_mul(&y));
+        let mr = symmetric(xb.tr_mul(&Mat::from_fn(n, q, |i, j| mass[i] * xb[(i, j)])));
+
+        let l = mr.cholesky()
+            .ok_or_else(|| FEAError::AnalysisFailed("Modal trial vectors became linearly dependent".to_string()))?
+            .l();
+        let l_inv = l.clone().try_inverse()
+            .ok_or_else(|| FEAError::AnalysisFailed("Modal trial vectors became linearly dependent".to_string()))?;
+        let eigen = SymmetricEigen::new(symmetric(&l_inv * kr * l_inv.transpose()));
+        let mut order: Vec<usize> = (0..q).collect();
+        order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+        let values: Vec<f64> = order.iter().map(|&k| eigen.eigenvalues[k]).collect();
+        let vectors = Mat::from_fn(q, q, |i, j| eigen.eigenvectors[(i, order[j])]);
+        x = xb * (l_inv.transpose() * vectors);
+
+        if previous.len() == q {
+            let change = (0..p)
+                .map(|i| (values[i] - previous[i]).abs() / values[i].abs())
+                .fold(0.0, f64::max);
+            residuals.push(change);
+            if change <= tolerance {
+                return Ok((values[..p].to_vec(), x.columns(0, p).into_owned()));
+            }
+        }
+        previous = values;
+    }
+
+    Err(FEAError::ConvergenceFailed { iterations: max_iterations, residuals })
+}
+
+/// Average a nearly symmetric matrix with its transpose
+fn symmetric(a: Mat) -> Mat {
+    (&a + a.transpose()) * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{LinearSolver, SolverBackend};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_subspace_iteration_spring_chain() {
+        // Fixed-free chain of 8 unit springs and masses: lambda_k = 4 sin^2((2k - 1) pi / (4 n + 2))
+        let n = 8;
+        let k = Mat::from_fn(n, n, |i, j| match (i, j) {
+            _ if i == j && i == n - 1 => 1.0,
+            _ if i == j => 2.0,
+            _ if i.abs_diff(j) == 1 => -1.0,
+            _ => 0.0,
+        });
+        let mass = FEVec::from_element(n, 1.0);
+        let factorization = SolverBackend::DenseLu.factorize(&k).unwrap();
+        let (values, vectors) = subspace_iteration(&*factorization, &mass, 3, 50, 1e-12, &CancelToken::new()).unwrap();
+
+        for (i, value) in values.iter().enumerate() {
+            let angle = (2 * i + 1) as f64 * std::f64::consts::PI / (4 * n + 2) as f64;
+            assert_relative_eq!(*value, 4.0 * angle.sin().powi(2), max_relative = 1e-9);
+            assert_relative_eq!(vectors.column(i).norm(), 1.0, max_relative = 1e-9);
+        }
+    }
+}
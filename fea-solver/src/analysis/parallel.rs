@@ -0,0 +1,33 @@
+//! Data parallelism for assembly and load combination solves
+//!
+//! With the `parallel` feature the work is spread over rayon's global thread
+//! pool, which in the browser (`wasm-parallel`) is a set of web workers
+//! started by `initThreadPool`. Without it the same calls run sequentially,
+//! so callers are written once for both builds. Results always come back in
+//! input order, keeping the analysis deterministic.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Apply `f` to every item, in parallel when the `parallel` feature is enabled
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    items.par_iter().map(f).collect()
+}
+
+/// Apply `f` to every item, in parallel when the `parallel` feature is enabled
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<usize> = (0..1000).collect();
+        assert_eq!(map(&items, |i| i * 2), (0..1000).map(|i| i * 2).collect::<Vec<_>>());
+    }
+}
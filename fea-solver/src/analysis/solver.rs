@@ -0,0 +1,241 @@
+//! Linear equation solver backends
+//!
+//! Static analyses factorise the stiffness of the free DOFs through the
+//! [`LinearSolver`] chosen by [`SolverBackend`] in the analysis options. The
+//! dense LU handles any non-singular matrix; the sparse backends exploit the
+//! banded structure of large models but need a positive definite stiffness.
+//! The default, [`SolverBackend::Auto`], stores the matrix in skyline form
+//! when its profile is small, which the bandwidth-reducing node numbering
+//! of `FEModel` makes likely for large models.
+//! Other libraries, e.g. SuiteSparse UMFPACK bindings, plug in through
+//! [`SolverBackend::Custom`].
+
+use std::fmt;
+use std::sync::Arc;
+
+use nalgebra_sparse::CsrMatrix;
+use serde::{Deserialize, Serialize};
+
+use crate::math::sparse::{SparseCholeskySolver, SparseMatrixBuilder};
+use crate::math::{Mat, Vec as FEVec};
+
+/// A factorised matrix, reusable for several right-hand sides
+pub trait Factorization: Send + Sync {
+    /// Solve `A x = b`
+    fn solve(&self, b: &FEVec) -> FEVec;
+}
+
+/// Solver for the symmetric stiffness systems of an analysis
+pub trait LinearSolver: fmt::Debug + Send + Sync {
+    /// Factorise a symmetric matrix, or None when the backend cannot (a
+    /// singular matrix, or one that is not positive definite for Cholesky)
+    fn factorize(&self, a: &Mat) -> Option<Box<dyn Factorization>>;
+
+    /// Factorise a symmetric matrix given in sparse form
+    ///
+    /// Sparse backends override this to avoid the dense copy the default
+    /// makes.
+    fn factorize_sparse(&self, a: &CsrMatrix<f64>) -> Option<Box<dyn Factorization>> {
+        self.factorize(&dense(a))
+    }
+
+    /// Solve `A x = b` with a one-off factorisation
+    fn solve(&self, a: &Mat, b: &FEVec) -> Option<FEVec> {
+        Some(self.factorize(a)?.solve(b))
+    }
+}
+
+/// Choice of [`LinearSolver`] for static analyses
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum SolverBackend {
+    /// Skyline Cholesky when the matrix profile is at most a quarter of its
+    /// lower triangle, otherwise, or when Cholesky fails, dense LU
+    #[default]
+    Auto,
+    /// Dense LU factorisation with partial pivoting
+    DenseLu,
+    /// The crate's skyline (profile) Cholesky factorisation
+    SparseCholesky,
+    /// faer's supernodal sparse Cholesky factorisation
+    #[cfg(feature = "faer")]
+    Faer,
+    /// A user-supplied solver
+    #[serde(skip)]
+    Custom(Arc<dyn LinearSolver>),
+}
+
+impl LinearSolver for SolverBackend {
+    fn factorize(&self, a: &Mat) -> Option<Box<dyn Factorization>> {
+        match self {
+            SolverBackend::Auto => {
+                let n = a.nrows();
+                let skyline = 4 * profile(a) <= n * (n + 1) / 2;
+                skyline.then(|| SolverBackend::SparseCholesky.factorize(a))
+                    .flatten()
+                    .or_else(|| SolverBackend::DenseLu.factorize(a))
+            }
+            SolverBackend::DenseLu => {
+                let lu = a.clone().lu();
+                lu.is_invertible().then(|| Box::new(lu) as Box<dyn Factorization>)
+            }
+            SolverBackend::SparseCholesky => {
+                let mut builder = SparseMatrixBuilder::new(a.nrows());
+                for (j, column) in a.column_iter().enumerate() {
+                    for (i, &value) in column.iter().enumerate().filter(|(_, v)| **v != 0.0) {
+                        builder.add(i, j, value);
+                    }
+                }
+                self.factorize_sparse(&builder.to_csr())
+            }
+            #[cfg(feature = "faer")]
+            SolverBackend::Faer => faer_backend::factorize(a.nrows(), dense_lower_triangle(a)),
+            SolverBackend::Custom(solver) => solver.factorize(a),
+        }
+    }
+
+    fn factorize_sparse(&self, a: &CsrMatrix<f64>) -> Option<Box<dyn Factorization>> {
+        match self {
+            SolverBackend::Auto => SolverBackend::SparseCholesky.factorize_sparse(a)
+                .or_else(|| SolverBackend::DenseLu.factorize(&dense(a))),
+            SolverBackend::DenseLu => self.factorize(&dense(a)),
+            SolverBackend::SparseCholesky => {
+                let mut cholesky = SparseCholeskySolver::new(a)?;
+                cholesky.factorize().ok()?;
+                Some(Box::new(cholesky))
+            }
+            #[cfg(feature = "faer")]
+            SolverBackend::Faer => faer_backend::factorize(
+                a.nrows(),
+                a.triplet_iter().filter(|&(i, j, _)| i >= j).map(|(i, j, &v)| (i, j, v)),
+            ),
+            SolverBackend::Custom(solver) => solver.factorize_sparse(a),
+        }
+    }
+}
+
+/// Dense copy of a sparse matrix
+fn dense(a: &CsrMatrix<f64>) -> Mat {
+    let mut dense = Mat::zeros(a.nrows(), a.ncols());
+    for (i, j, &value) in a.triplet_iter() {
+        dense[(i, j)] += value;
+    }
+    dense
+}
+
+/// Non-zero entries of the lower triangle of a dense matrix
+#[cfg(feature = "faer")]
+fn dense_lower_triangle(a: &Mat) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+    let n = a.nrows();
+    (0..n)
+        .flat_map(move |j| (j..n).map(move |i| (i, j)))
+        .filter(|&(i, j)| a[(i, j)] != 0.0)
+        .map(|(i, j)| (i, j, a[(i, j)]))
+}
+
+/// Number of entries in the skyline of a symmetric matrix: from the first
+/// non-zero of each row to the diagonal
+fn profile(a: &Mat) -> usize {
+    (0..a.nrows())
+        .map(|i| (0..i).find(|&j| a[(i, j)] != 0.0).map_or(1, |j| i - j + 1))
+        .sum()
+}
+
+impl Factorization for nalgebra::linalg::LU<f64, nalgebra::Dyn, nalgebra::Dyn> {
+    fn solve(&self, b: &FEVec) -> FEVec {
+        // Only invertible factorisations are handed out
+        nalgebra::linalg::LU::solve(self, b).unwrap()
+    }
+}
+
+impl Factorization for SparseCholeskySolver {
+    fn solve(&self, b: &FEVec) -> FEVec {
+        SparseCholeskySolver::solve(self, b)
+    }
+}
+
+#[cfg(feature = "faer")]
+mod faer_backend {
+    use faer::linalg::solvers::Solve;
+    use faer::sparse::linalg::solvers::Llt;
+    use faer::sparse::{SparseColMat, Triplet};
+    use faer::{Col, Side};
+
+    use super::Factorization;
+    use crate::math::Vec as FEVec;
+
+    struct FaerLlt(Llt<usize, f64>);
+
+    impl Factorization for FaerLlt {
+        fn solve(&self, b: &FEVec) -> FEVec {
+            let mut x = Col::<f64>::from_fn(b.len(), |i| b[i]);
+            self.0.solve_in_place(&mut x);
+            FEVec::from_fn(b.len(), |i, _| x[i])
+        }
+    }
+
+    /// Cholesky factorisation of an `n` x `n` matrix from its lower triangle
+    pub(super) fn factorize(n: usize, lower: impl Iterator<Item = (usize, usize, f64)>) -> Option<Box<dyn Factorization>> {
+        let triplets: Vec<Triplet<usize, usize, f64>> = lower
+            .map(|(i, j, value)| Triplet::new(i, j, value))
+            .collect();
+        let matrix = SparseColMat::<usize, f64>::try_new_from_triplets(n, n, &triplets).ok()?;
+        let llt = matrix.sp_cholesky(Side::Lower).ok()?;
+        Some(Box::new(FaerLlt(llt)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_backends_agree() {
+        // Tridiagonal SPD system with a known solution
+        let n = 20;
+        let a = Mat::from_fn(n, n, |i, j| match i.abs_diff(j) {
+            0 => 4.0,
+            1 => -1.0,
+            _ => 0.0,
+        });
+        let x = FEVec::from_fn(n, |i, _| i as f64 - 2.0);
+        let b = &a * &x;
+
+        let mut backends = vec![SolverBackend::Auto, SolverBackend::DenseLu, SolverBackend::SparseCholesky];
+        #[cfg(feature = "faer")]
+        backends.push(SolverBackend::Faer);
+        backends.push(SolverBackend::Custom(Arc::new(SolverBackend::DenseLu)));
+        for backend in &backends {
+            let solved = backend.solve(&a, &b).unwrap();
+            for i in 0..n {
+                assert_relative_eq!(solved[i], x[i], epsilon = 1e-12);
+            }
+        }
+
+        // Cholesky rejects an indefinite matrix that LU still solves
+        let indefinite = Mat::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert!(SolverBackend::SparseCholesky.factorize(&indefinite).is_none());
+        assert!(SolverBackend::DenseLu.factorize(&indefinite).is_some());
+        assert!(SolverBackend::DenseLu.factorize(&Mat::zeros(2, 2)).is_none());
+
+        // The tridiagonal profile is small enough for the skyline, the full 2 x 2 one is not
+        assert_eq!(profile(&a), 39);
+        assert_eq!(profile(&indefinite), 3);
+        assert!(SolverBackend::Auto.factorize(&indefinite).is_some());
+
+        // The sparse entry point gives the same solutions without a dense copy
+        let mut builder = SparseMatrixBuilder::new(n);
+        for i in 0..n {
+            for j in i.saturating_sub(1)..(i + 2).min(n) {
+                builder.add(i, j, a[(i, j)]);
+            }
+        }
+        let csr = builder.to_csr();
+        for backend in &backends {
+            let solved = backend.factorize_sparse(&csr).unwrap().solve(&b);
+            for i in 0..n {
+                assert_relative_eq!(solved[i], x[i], epsilon = 1e-12);
+            }
+        }
+    }
+}
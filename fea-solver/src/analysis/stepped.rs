@@ -0,0 +1,140 @@
+//! Static analysis advanced in bounded steps
+//!
+//! [`FEModel::analyze`] runs to completion in one call, which blocks a
+//! single-threaded host such as a browser tab for as long as the solve takes.
+//! A [`SteppedAnalysis`] does the same work through repeated calls to
+//! [`FEModel::step_analysis`]: one step prepares the model, each further step
+//! assembles a chunk of members (the remaining elements with the last chunk)
+//! or solves one load combination. The caller can report progress, yield to
+//! its event loop or cancel between steps.
+//!
+//! ```rust
+//! use fea_solver::prelude::*;
+//!
+//! let mut model = FEModel::new();
+//! // ... build the model ...
+//! # model.add_material("Steel", Material::steel()).unwrap();
+//! # model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+//! # model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+//! # model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+//! # model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+//! # model.add_support("A", Support::fixed()).unwrap();
+//! let mut run = SteppedAnalysis::new(AnalysisOptions::linear());
+//! while !run.is_finished() {
+//!     let progress = model.step_analysis(&mut run).unwrap();
+//!     println!("{:?} {:.0}%", progress.stage, 100.0 * progress.fraction());
+//! }
+//! ```
+//!
+//! [`FEModel::analyze`]: crate::model::FEModel::analyze
+//! [`FEModel::step_analysis`]: crate::model::FEModel::step_analysis
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::AnalysisOptions;
+use crate::math::Mat;
+
+/// Members assembled per step unless set with [`SteppedAnalysis::with_chunk_size`]
+pub const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+/// Stage of a stepped analysis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisStage {
+    /// Checks, node numbering and load combination set-up
+    Prepare,
+    /// Assembly of the global stiffness
+    Assemble,
+    /// Solution of a load combination
+    Solve,
+}
+
+/// What the last step of a [`SteppedAnalysis`] did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisProgress {
+    /// Stage of the step just completed
+    pub stage: AnalysisStage,
+    /// Load combination just solved, for the solve stage
+    pub combo: Option<String>,
+    /// Steps completed so far, including this one
+    pub completed: usize,
+    /// Steps in the whole analysis; the assembly steps are only counted
+    /// once preparation has found whether a cached stiffness can be reused
+    pub total: usize,
+}
+
+impl AnalysisProgress {
+    /// Fraction of the analysis completed, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        self.completed as f64 / self.total.max(1) as f64
+    }
+}
+
+/// A static analysis run one step at a time by [`FEModel::step_analysis`]
+///
+/// [`FEModel::step_analysis`]: crate::model::FEModel::step_analysis
+#[derive(Debug)]
+pub struct SteppedAnalysis {
+    pub(crate) options: AnalysisOptions,
+    pub(crate) chunk_size: usize,
+    pub(crate) state: StepState,
+    pub(crate) combos: Vec<String>,
+    pub(crate) completed: usize,
+    pub(crate) total: usize,
+}
+
+/// Work left in a stepped analysis
+#[derive(Debug, Default)]
+pub(crate) enum StepState {
+    #[default]
+    Start,
+    Assemble {
+        k_global: Mat,
+        dof_map: HashMap<String, usize>,
+        fingerprint: Option<u64>,
+        next_member: usize,
+    },
+    Solve {
+        k_global: Arc<Mat>,
+        dof_map: HashMap<String, usize>,
+        next_combo: usize,
+    },
+    Finished,
+}
+
+impl SteppedAnalysis {
+    /// Analysis with the given options, not yet started
+    pub fn new(options: AnalysisOptions) -> Self {
+        Self {
+            options,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            state: StepState::Start,
+            combos: Vec::new(),
+            completed: 0,
+            total: 1,
+        }
+    }
+
+    /// Assemble this many members per step (at least one)
+    pub fn with_chunk_size(mut self, members: usize) -> Self {
+        self.chunk_size = members.max(1);
+        self
+    }
+
+    /// Options the analysis runs with
+    pub fn options(&self) -> &AnalysisOptions {
+        &self.options
+    }
+
+    /// Whether every step has been run
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, StepState::Finished)
+    }
+
+    pub(crate) fn progress(&self, stage: AnalysisStage, combo: Option<String>) -> AnalysisProgress {
+        AnalysisProgress { stage, combo, completed: self.completed, total: self.total }
+    }
+}
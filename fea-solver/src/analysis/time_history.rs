@@ -0,0 +1,116 @@
+//! Time-history (dynamic response) analysis input and results
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::CancelToken;
+
+/// Ground acceleration record for a time-history analysis
+///
+/// The structure is integrated with the Newmark average acceleration method
+/// in displacements relative to the ground, so the record loads every mass
+/// with `-m a_g` along `direction`. Supports move with the ground.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeHistory {
+    /// Time step between acceleration samples
+    pub dt: f64,
+    /// Ground acceleration at each time step, starting at t = 0
+    pub ground_acceleration: Vec<f64>,
+    /// Global direction of the ground motion
+    pub direction: [f64; 3],
+    /// Rayleigh damping coefficients (mass, stiffness): `C = a0 M + a1 K`
+    pub rayleigh: (f64, f64),
+    /// Maximum Newton iterations per step for nonlinear links
+    pub max_iterations: usize,
+    /// Relative displacement convergence tolerance
+    pub tolerance: f64,
+    /// Token for aborting the analysis, checked every time step
+    #[serde(skip)]
+    pub cancel: CancelToken,
+}
+
+impl TimeHistory {
+    /// Create an undamped ground motion record
+    pub fn new(dt: f64, ground_acceleration: Vec<f64>, direction: [f64; 3]) -> Self {
+        Self {
+            dt,
+            ground_acceleration,
+            direction,
+            rayleigh: (0.0, 0.0),
+            max_iterations: 30,
+            tolerance: 1e-8,
+            cancel: CancelToken::new(),
+        }
+    }
+
+    /// Set Rayleigh damping coefficients
+    pub fn with_rayleigh_damping(mut self, a0: f64, a1: f64) -> Self {
+        self.rayleigh = (a0, a1);
+        self
+    }
+
+    /// Abort the analysis when the token is cancelled
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Time of each step
+    pub fn times(&self) -> Vec<f64> {
+        (0..self.ground_acceleration.len()).map(|i| i as f64 * self.dt).collect()
+    }
+}
+
+/// Recorded response of a link element during a time-history analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkHistory {
+    /// Deformation at each time step
+    pub deformation: Vec<f64>,
+    /// Deformation rate at each time step
+    pub velocity: Vec<f64>,
+    /// Force at each time step
+    pub force: Vec<f64>,
+}
+
+impl LinkHistory {
+    /// Energy dissipated by the link (trapezoidal integral of F du)
+    ///
+    /// For an isolator this includes the elastic energy still stored at the
+    /// final step, which is zero once the link has returned to rest.
+    pub fn dissipated_energy(&self) -> f64 {
+        self.force.windows(2)
+            .zip(self.deformation.windows(2))
+            .map(|(f, u)| 0.5 * (f[0] + f[1]) * (u[1] - u[0]))
+            .sum()
+    }
+
+    /// Peak absolute force
+    pub fn max_force(&self) -> f64 {
+        self.force.iter().fold(0.0, |m, f| m.max(f.abs()))
+    }
+}
+
+/// Results of a time-history analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeHistoryResults {
+    /// Time of each step
+    pub time: Vec<f64>,
+    /// Node displacements relative to the ground at each step
+    pub displacements: HashMap<String, Vec<[f64; 6]>>,
+    /// Link element response histories
+    pub links: HashMap<String, LinkHistory>,
+}
+
+impl TimeHistoryResults {
+    /// Peak absolute relative displacement of a node DOF (0-5 = DX..RZ)
+    pub fn peak_displacement(&self, node_name: &str, dof: usize) -> Option<f64> {
+        let history = self.displacements.get(node_name)?;
+        Some(history.iter().fold(0.0, |m, d| m.max(d[dof].abs())))
+    }
+
+    /// Total energy dissipated by all link elements
+    pub fn dissipated_energy(&self) -> f64 {
+        self.links.values().map(LinkHistory::dissipated_energy).sum()
+    }
+}
@@ -8,11 +8,15 @@ use axum::{
     Router,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::net::SocketAddr;
 use tower_http::cors::{Any, CorsLayer};
 
 use fea_solver::prelude::*;
-use fea_solver::loads::{DistributedLoad, LoadDirection, PlateLoad};
+use fea_solver::loads::{DistributedLoad, LoadDirection, PlateLoad, PlatePointLoad};
+
+/// Stations along each member in the internal force diagrams, ends included
+const DIAGRAM_STATIONS: usize = 21;
 
 #[derive(Debug, Serialize)]
 struct HealthResponse {
@@ -34,14 +38,24 @@ struct ModelData {
     members: Vec<MemberData>,
     #[serde(default)]
     plates: Vec<PlateData>,
+    #[serde(default)]
+    solids: Vec<SolidData>,
+    #[serde(default)]
+    gaps: Vec<GapData>,
     supports: Vec<SupportData>,
     #[serde(default)]
     node_loads: Vec<NodeLoadData>,
     #[serde(default)]
     distributed_loads: Vec<DistributedLoadData>,
     #[serde(default)]
+    prestress_loads: Vec<PrestressLoadData>,
+    #[serde(default)]
     plate_loads: Vec<PlateLoadData>,
     #[serde(default)]
+    plate_point_loads: Vec<PlatePointLoadData>,
+    #[serde(default)]
+    plate_thermal_loads: Vec<PlateThermalLoadData>,
+    #[serde(default)]
     load_combos: Vec<LoadComboData>,
 }
 
@@ -55,6 +69,18 @@ struct DistributedLoadData {
     case: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PrestressLoadData {
+    member: String,
+    /// Prestress force (tension positive) or initial strain (elongation positive)
+    value: f64,
+    /// "force" or "strain"
+    #[serde(default)]
+    kind: String,
+    #[serde(default = "default_case")]
+    case: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct NodeData {
     name: String,
@@ -70,6 +96,9 @@ struct MaterialData {
     g: f64,
     nu: f64,
     rho: f64,
+    /// Coefficient of thermal expansion (1/°C)
+    #[serde(default)]
+    alpha: f64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,17 +121,47 @@ struct MemberData {
     rotation: f64,
     #[serde(default)]
     releases: Option<MemberReleasesData>,
+    /// Elastic foundation modulus in the local y direction (force/length^2)
+    #[serde(default)]
+    foundation_modulus: f64,
+    /// Initial bow or camber for P-Delta analysis
+    #[serde(default)]
+    imperfection: Option<ImperfectionData>,
+    /// Split at intermediate nodes into analysis segments, reporting forces on the whole member
+    #[serde(default)]
+    physical: bool,
+    /// Additional split positions along a physical member (distances from the i-node)
+    #[serde(default)]
+    splits: Vec<f64>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct MemberReleasesData {
+#[derive(Debug, Deserialize)]
+struct ImperfectionData {
+    /// Mid-length offset in local y
     #[serde(default)]
-    i_node_ry: bool,
+    y: f64,
+    /// Mid-length offset in local z
     #[serde(default)]
-    i_node_rz: bool,
+    z: f64,
+    /// "bow" (half sine) or "camber" (parabolic)
     #[serde(default)]
+    shape: String,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(default)]
+struct MemberReleasesData {
+    i_node_dx: bool,
+    i_node_dy: bool,
+    i_node_dz: bool,
+    i_node_rx: bool,
+    i_node_ry: bool,
+    i_node_rz: bool,
+    j_node_dx: bool,
+    j_node_dy: bool,
+    j_node_dz: bool,
+    j_node_rx: bool,
     j_node_ry: bool,
-    #[serde(default)]
     j_node_rz: bool,
 }
 
@@ -121,6 +180,31 @@ struct PlateData {
     ky_mod: f64,
     #[serde(default = "default_formulation")]
     formulation: String,
+    /// Winkler subgrade modulus (pressure per unit deflection)
+    #[serde(default)]
+    subgrade_modulus: Option<f64>,
+    /// Release the foundation where the plate lifts off, in nonlinear analysis
+    #[serde(default)]
+    compression_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolidData {
+    name: String,
+    nodes: [String; 4],
+    material: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GapData {
+    name: String,
+    i_node: String,
+    j_node: String,
+    /// "gap" (compression contact) or "hook" (slack tie)
+    kind: String,
+    #[serde(default)]
+    opening: f64,
+    stiffness: f64,
 }
 
 fn default_kx_mod() -> f64 { 1.0 }
@@ -130,9 +214,41 @@ fn default_formulation() -> String { "kirchhoff".to_string() }
 #[derive(Debug, Deserialize)]
 struct PlateLoadData {
     plate: String,
+    #[serde(default)]
     pressure: f64,
     #[serde(default = "default_case")]
     case: String,
+    /// Corner pressures (i, j, m, n) for a linearly varying load
+    #[serde(default)]
+    corner_pressures: Option<[f64; 4]>,
+    /// Fluid surface elevation (global Y) for a hydrostatic load
+    #[serde(default)]
+    fluid_level: Option<f64>,
+    #[serde(default)]
+    unit_weight: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatePointLoadData {
+    plate: String,
+    magnitude: f64,
+    x: f64,
+    y: f64,
+    #[serde(default = "default_case")]
+    case: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlateThermalLoadData {
+    plate: String,
+    /// Mid-surface temperature change
+    #[serde(default)]
+    delta_t: f64,
+    /// Temperature of the +Z face minus that of the -Z face
+    #[serde(default)]
+    gradient: f64,
+    #[serde(default = "default_case")]
+    case: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -144,6 +260,12 @@ struct SupportData {
     rx: bool,
     ry: bool,
     rz: bool,
+    /// Local support axes as rows [x, y, z] in global coordinates
+    #[serde(default)]
+    orientation: Option<[[f64; 3]; 3]>,
+    /// One-way translational restraints [DX, DY, DZ]: "positive", "negative" or null
+    #[serde(default)]
+    one_way: [Option<String>; 3],
 }
 
 #[derive(Debug, Deserialize)]
@@ -170,6 +292,8 @@ fn default_case() -> String {
 struct LoadComboData {
     name: String,
     factors: std::collections::HashMap<String, f64>,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -179,12 +303,27 @@ struct AnalysisOptionsData {
     analysis_type: String,
     #[serde(default = "default_max_iter")]
     max_iterations: usize,
+    #[serde(default = "default_load_steps")]
+    load_steps: usize,
+    #[serde(default)]
+    arc_length: bool,
+    /// Modes of vibration to find after the static analysis, none if zero
+    #[serde(default)]
+    num_modes: usize,
+    /// Solver options in full; when given they take the place of
+    /// `analysis_type`, `max_iterations`, `load_steps` and `arc_length`
+    #[serde(default)]
+    settings: Option<AnalysisOptions>,
 }
 
 fn default_max_iter() -> usize {
     30
 }
 
+fn default_load_steps() -> usize {
+    10
+}
+
 #[derive(Debug, Serialize)]
 struct AnalysisResponse {
     success: bool,
@@ -200,10 +339,41 @@ struct ResultsData {
     reactions: Vec<ReactionResult>,
     member_forces: Vec<MemberForceResult>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    member_diagrams: Vec<MemberDiagramResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     plate_stresses: Vec<PlateStressResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    solid_stresses: Vec<SolidStressResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    gap_forces: Vec<GapForceResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    modes: Vec<ModeResult>,
+    /// Model quality warnings, e.g. distorted quads
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
     summary: SummaryResult,
 }
 
+/// A mode of vibration, with its mass-normalised shape
+#[derive(Debug, Serialize)]
+struct ModeResult {
+    frequency: f64,
+    period: f64,
+    mass_ratio: [f64; 3],
+    shape: Vec<ModeShapeResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModeShapeResult {
+    node: String,
+    dx: f64,
+    dy: f64,
+    dz: f64,
+    rx: f64,
+    ry: f64,
+    rz: f64,
+}
+
 #[derive(Debug, Serialize)]
 struct NodeDisplacementResult {
     node: String,
@@ -228,6 +398,21 @@ struct ReactionResult {
     mz: f64,
 }
 
+/// Internal forces along a member, one value per station
+#[derive(Debug, Serialize)]
+struct MemberDiagramResult {
+    member: String,
+    combo: String,
+    /// Distance of each station from the i-node
+    x: Vec<f64>,
+    axial: Vec<f64>,
+    shear_y: Vec<f64>,
+    shear_z: Vec<f64>,
+    torsion: Vec<f64>,
+    moment_y: Vec<f64>,
+    moment_z: Vec<f64>,
+}
+
 #[derive(Debug, Serialize)]
 struct MemberForceResult {
     member: String,
@@ -266,6 +451,27 @@ struct PlateStressResult {
     mxy: f64,
 }
 
+#[derive(Debug, Serialize)]
+struct SolidStressResult {
+    solid: String,
+    combo: String,
+    sx: f64,
+    sy: f64,
+    sz: f64,
+    txy: f64,
+    tyz: f64,
+    tzx: f64,
+    von_mises: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct GapForceResult {
+    gap: String,
+    combo: String,
+    /// Axial force (positive = tension)
+    force: f64,
+}
+
 #[derive(Debug, Serialize)]
 struct SummaryResult {
     max_displacement: f64,
@@ -276,6 +482,8 @@ struct SummaryResult {
     num_members: usize,
     total_dofs: usize,
     free_dofs: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    uplift_nodes: Vec<String>,
 }
 
 async fn health() -> impl IntoResponse {
@@ -311,7 +519,7 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
 
     // Add materials
     for mat in request.model.materials {
-        model.add_material(&mat.name, Material::new(mat.e, mat.g, mat.nu, mat.rho))?;
+        model.add_material(&mat.name, Material::new(mat.e, mat.g, mat.nu, mat.rho).with_thermal_expansion(mat.alpha))?;
     }
 
     // Add sections
@@ -331,16 +539,31 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
     for member in request.model.members {
         let mut m = Member::new(&member.i_node, &member.j_node, &member.material, &member.section);
         m.rotation = member.rotation;
+        m.foundation_modulus = member.foundation_modulus;
+        m.imperfection = member.imperfection.map(|imp| match imp.shape.to_lowercase().as_str() {
+            "camber" => Imperfection::camber(imp.y, imp.z),
+            _ => Imperfection::bow(imp.y, imp.z),
+        });
         
         // Apply user-specified releases from the request
         if let Some(ref releases) = member.releases {
             // Apply user-specified releases: [DX, DY, DZ, RX, RY, RZ]
-            m.releases.i_node = [false, false, false, false, releases.i_node_ry, releases.i_node_rz];
-            m.releases.j_node = [false, false, false, false, releases.j_node_ry, releases.j_node_rz];
+            m.releases.i_node = [
+                releases.i_node_dx, releases.i_node_dy, releases.i_node_dz,
+                releases.i_node_rx, releases.i_node_ry, releases.i_node_rz,
+            ];
+            m.releases.j_node = [
+                releases.j_node_dx, releases.j_node_dy, releases.j_node_dz,
+                releases.j_node_rx, releases.j_node_ry, releases.j_node_rz,
+            ];
         }
         // If no releases specified, member defaults to fully fixed connections (all false)
         
-        model.add_member(&member.name, m)?;
+        if member.physical || !member.splits.is_empty() {
+            model.add_physical_member(&member.name, PhysicalMember::new(m).with_splits(member.splits))?;
+        } else {
+            model.add_member(&member.name, m)?;
+        }
     }
 
     // Add plates (4-node shell elements)
@@ -363,15 +586,42 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
         .with_modifiers(plate.kx_mod, plate.ky_mod)
         .with_formulation(formulation);
         
+        let p = match plate.subgrade_modulus {
+            Some(k) if plate.compression_only => p.with_foundation(ElasticFoundation::new(k).compression_only()),
+            Some(k) => p.with_subgrade_modulus(k),
+            None => p,
+        };
+        
         model.add_plate(&plate.name, p)?;
     }
 
+    // Add solids (4-node tetrahedra)
+    for solid in request.model.solids {
+        let [n1, n2, n3, n4] = &solid.nodes;
+        model.add_tetra(&solid.name, Tetra::new(n1, n2, n3, n4, &solid.material))?;
+    }
+
+    // Add gap/hook elements
+    for gap in request.model.gaps {
+        let element = match gap.kind.to_lowercase().as_str() {
+            "hook" => Gap::hook(&gap.i_node, &gap.j_node, gap.opening, gap.stiffness),
+            _ => Gap::contact(&gap.i_node, &gap.j_node, gap.opening, gap.stiffness),
+        };
+        model.add_gap(&gap.name, element)?;
+    }
+
     // Add supports
     for sup in request.model.supports {
-        model.add_support(
-            &sup.node,
-            Support::with_restraints(sup.dx, sup.dy, sup.dz, sup.rx, sup.ry, sup.rz),
-        )?;
+        let mut support = Support::with_restraints(sup.dx, sup.dy, sup.dz, sup.rx, sup.ry, sup.rz);
+        support.orientation = sup.orientation;
+        for (dof, direction) in sup.one_way.iter().enumerate() {
+            match direction.as_deref().map(str::to_lowercase).as_deref() {
+                Some("positive" | "+") => support = support.with_one_way(dof, OneWay::Positive),
+                Some("negative" | "-") => support = support.with_one_way(dof, OneWay::Negative),
+                _ => {}
+            }
+        }
+        model.add_support(&sup.node, support)?;
     }
 
     // Add node loads
@@ -389,21 +639,46 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
             "FX" => LoadDirection::FX,  // Global X
             "FY" => LoadDirection::FY,  // Global Y (gravity direction)
             "FZ" => LoadDirection::FZ,  // Global Z
+            "MX" | "TORQUE" => LoadDirection::Mx, // Torque about the member axis
             _ => LoadDirection::FY,     // Default to global Y (gravity direction)
         };
         
-        // Use uniform load (w1 = w2) over entire member
+        // w1 at the i-node varying linearly to w2 at the j-node
         model.add_member_dist_load(
             &load.member,
-            DistributedLoad::uniform(load.w1, dir, &load.case),
+            DistributedLoad::linear(load.w1, load.w2, dir, &load.case),
         )?;
     }
 
+    for load in request.model.prestress_loads {
+        let prestress = match load.kind.to_lowercase().as_str() {
+            "strain" => PrestressLoad::strain(load.value, &load.case),
+            _ => PrestressLoad::force(load.value, &load.case),
+        };
+        model.add_member_prestress(&load.member, prestress)?;
+    }
+
     // Add plate pressure loads
     for load in request.model.plate_loads {
-        model.add_plate_load(
+        let plate_load = match (load.corner_pressures, load.fluid_level) {
+            (Some(corners), _) => PlateLoad::bilinear(corners, &load.case),
+            (None, Some(level)) => PlateLoad::hydrostatic(level, load.unit_weight, &load.case),
+            (None, None) => PlateLoad::new(load.pressure, &load.case),
+        };
+        model.add_plate_load(&load.plate, plate_load)?;
+    }
+
+    for load in request.model.plate_point_loads {
+        model.add_plate_point_load(
             &load.plate,
-            PlateLoad::new(load.pressure, &load.case),
+            PlatePointLoad::new(load.magnitude, load.x, load.y, &load.case),
+        )?;
+    }
+
+    for load in request.model.plate_thermal_loads {
+        model.add_plate_thermal_load(
+            &load.plate,
+            PlateThermalLoad::new(load.delta_t, load.gradient, &load.case),
         )?;
     }
 
@@ -413,16 +688,29 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
         for (case, factor) in combo.factors {
             lc = lc.with_case(&case, factor);
         }
+        lc.tags = combo.tags;
         model.add_load_combo(lc)?;
     }
 
     // Determine analysis type
+    let num_modes = request.options.as_ref().map_or(0, |opts| opts.num_modes);
     let options = match request.options {
-        Some(opts) => match opts.analysis_type.to_lowercase().as_str() {
-            "pdelta" | "p-delta" | "p_delta" => AnalysisOptions::p_delta()
-                .with_max_iter(opts.max_iterations),
-            _ => AnalysisOptions::linear(),
-        },
+        Some(AnalysisOptionsData { settings: Some(settings), .. }) => settings,
+        Some(opts) => {
+            let options = match opts.analysis_type.to_lowercase().as_str() {
+                "pdelta" | "p-delta" | "p_delta" => AnalysisOptions::p_delta()
+                    .with_max_iter(opts.max_iterations),
+                "nonlinear" => AnalysisOptions::nonlinear()
+                    .with_max_iter(opts.max_iterations),
+                "large_displacement" | "large-displacement" | "corotational" => {
+                    AnalysisOptions::large_displacement(opts.load_steps)
+                        .with_max_iter(opts.max_iterations)
+                }
+                _ => AnalysisOptions::linear(),
+            };
+            // Analysis types without arc-length control reject it rather than ignore it
+            if opts.arc_length { options.with_arc_length() } else { options }
+        }
         None => AnalysisOptions::linear(),
     };
 
@@ -436,7 +724,10 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
     let mut node_displacements = Vec::new();
     let mut reactions = Vec::new();
     let mut member_forces = Vec::new();
+    let mut member_diagrams = Vec::new();
     let mut plate_stresses = Vec::new();
+    let mut solid_stresses = Vec::new();
+    let mut gap_forces = Vec::new();
 
     for combo in &combo_names {
         // Node displacements
@@ -471,8 +762,14 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
             }
         }
 
-        // Member forces
-        for member_name in model.members.keys() {
+        // Member forces, reporting physical members in place of their segments
+        let segments: HashSet<&String> = model.physical_members.values()
+            .flat_map(|physical| physical.segments().iter().map(|(segment, _, _)| segment))
+            .collect();
+        let member_names = model.members.keys()
+            .filter(|name| !segments.contains(name))
+            .chain(model.physical_members.keys());
+        for member_name in member_names {
             if let (Ok(fi), Ok(fj)) = (
                 model.member_forces_i(member_name, combo),
                 model.member_forces_j(member_name, combo),
@@ -494,6 +791,19 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
                     moment_z_j: fj.moment_z,
                 });
             }
+            if let Ok(diagram) = model.member_diagram(member_name, combo, DIAGRAM_STATIONS) {
+                member_diagrams.push(MemberDiagramResult {
+                    member: member_name.clone(),
+                    combo: combo.clone(),
+                    x: diagram.iter().map(|(x, _)| *x).collect(),
+                    axial: diagram.iter().map(|(_, f)| f.axial).collect(),
+                    shear_y: diagram.iter().map(|(_, f)| f.shear_y).collect(),
+                    shear_z: diagram.iter().map(|(_, f)| f.shear_z).collect(),
+                    torsion: diagram.iter().map(|(_, f)| f.torsion).collect(),
+                    moment_y: diagram.iter().map(|(_, f)| f.moment_y).collect(),
+                    moment_z: diagram.iter().map(|(_, f)| f.moment_z).collect(),
+                });
+            }
         }
 
         // Plate stresses (for both plates and quads)
@@ -528,16 +838,72 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
                 });
             }
         }
+
+        for solid_name in model.tetras.keys() {
+            if let Ok(stress) = model.solid_stress(solid_name, combo) {
+                solid_stresses.push(SolidStressResult {
+                    solid: solid_name.clone(),
+                    combo: combo.clone(),
+                    sx: stress.sx,
+                    sy: stress.sy,
+                    sz: stress.sz,
+                    txy: stress.txy,
+                    tyz: stress.tyz,
+                    tzx: stress.tzx,
+                    von_mises: stress.von_mises,
+                });
+            }
+        }
+
+        for gap_name in model.gaps.keys() {
+            if let Ok(force) = model.gap_force(gap_name, combo) {
+                gap_forces.push(GapForceResult {
+                    gap: gap_name.clone(),
+                    combo: combo.clone(),
+                    force,
+                });
+            }
+        }
     }
 
     // Summary
     let summary = model.summary(&first_combo)?;
 
+    // Modes of vibration; a model without mass still has its static results
+    let mut warnings = model.warnings().to_vec();
+    let mut modes = Vec::new();
+    if num_modes > 0 {
+        match model.analyze_modal(&AnalysisOptions::modal(num_modes)) {
+            Ok(modal) => {
+                modes = modal.modes.into_iter().map(|mode| ModeResult {
+                    frequency: mode.frequency,
+                    period: mode.period,
+                    mass_ratio: mode.mass_ratio,
+                    shape: mode.shape.into_iter().map(|(node, d)| ModeShapeResult {
+                        node,
+                        dx: d[0],
+                        dy: d[1],
+                        dz: d[2],
+                        rx: d[3],
+                        ry: d[4],
+                        rz: d[5],
+                    }).collect(),
+                }).collect();
+            }
+            Err(e) => warnings.push(format!("Modal analysis failed: {}", e)),
+        }
+    }
+
     Ok(ResultsData {
         node_displacements,
         reactions,
         member_forces,
+        member_diagrams,
         plate_stresses,
+        solid_stresses,
+        gap_forces,
+        modes,
+        warnings,
         summary: SummaryResult {
             max_displacement: summary.max_displacement,
             max_disp_node: summary.max_disp_node,
@@ -547,6 +913,7 @@ fn run_analysis(request: AnalysisRequest) -> Result<ResultsData, fea_solver::err
             num_members: summary.num_members,
             total_dofs: summary.total_dofs,
             free_dofs: summary.free_dofs,
+            uplift_nodes: summary.uplift_nodes,
         },
     })
 }
@@ -0,0 +1,307 @@
+//! AISC 360-16 steel member design checks (LRFD)
+//!
+//! Covers tension yielding (D2), flexural buckling (E3), flexure of I, box,
+//! pipe and solid sections (F2, F3, F6, F7, F8, F11), shear (G2, G4, G5, G6)
+//! and combined axial force and bending (H1-1). Sections with slender
+//! compression elements are not reduced for local buckling (E7), and slender
+//! box walls use the noncompact flange equation F7-2. Sections without a
+//! [`SectionShape`] are checked for yielding with their plastic (or elastic)
+//! modulus and gross area only.
+
+use std::f64::consts::PI;
+
+use crate::design::{CodeCheck, DesignParameters, DesignResult, Strength};
+use crate::elements::{Material, Section, SectionShape};
+use crate::error::{FEAError, FEAResult};
+use crate::results::MemberForces;
+
+/// Resistance factor for tension yielding, compression and flexure
+const PHI: f64 = 0.9;
+
+/// Design strengths of a member, about or along its local axes
+struct Strengths {
+    tension: Strength,
+    compression: Strength,
+    flexure_y: Strength,
+    flexure_z: Strength,
+    shear_y: Strength,
+    shear_z: Strength,
+}
+
+/// AISC 360-16 LRFD member checks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Aisc360;
+
+impl Aisc360 {
+    /// Create the checker
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CodeCheck for Aisc360 {
+    fn code(&self) -> &'static str {
+        "AISC 360-16"
+    }
+
+    fn check_member(
+        &self,
+        section: &Section,
+        material: &Material,
+        length: f64,
+        parameters: &DesignParameters,
+        forces: &[(f64, MemberForces)],
+    ) -> FEAResult<DesignResult> {
+        let strengths = strengths(section, material, length, parameters)?;
+        let mut result = DesignResult::default();
+        for &(x, f) in forces {
+            let axial = if f.axial >= 0.0 { strengths.tension } else { strengths.compression };
+            result.record(if f.axial >= 0.0 { "Tension" } else { "Compression" }, f.axial.abs(), axial, x);
+            result.record("Flexure y", f.moment_y.abs(), strengths.flexure_y, x);
+            result.record("Flexure z", f.moment_z.abs(), strengths.flexure_z, x);
+            result.record("Shear y", f.shear_y.abs(), strengths.shear_y, x);
+            result.record("Shear z", f.shear_z.abs(), strengths.shear_z, x);
+
+            // H1-1: reported as the interaction value against a capacity of 1
+            let pr = f.axial.abs() / axial.value;
+            let mr = f.moment_y.abs() / strengths.flexure_y.value + f.moment_z.abs() / strengths.flexure_z.value;
+            let (ratio, clause) = if pr >= 0.2 {
+                (pr + 8.0 / 9.0 * mr, "H1-1a")
+            } else {
+                (pr / 2.0 + mr, "H1-1b")
+            };
+            result.record("Combined", ratio, Strength::new(1.0, clause), x);
+        }
+        Ok(result)
+    }
+}
+
+/// Design strengths of a section over a member length
+fn strengths(section: &Section, material: &Material, length: f64, p: &DesignParameters) -> FEAResult<Strengths> {
+    let fy = material.fy
+        .ok_or_else(|| FEAError::InvalidInput("Steel design needs a material yield strength".to_string()))?;
+    let e = material.e;
+    let lb = p.lb.unwrap_or(length);
+
+    // E3: flexural buckling about the weaker of the two axes
+    let slenderness = (p.ky * p.ly.unwrap_or(length) / section.ry())
+        .max(p.kz * p.lz.unwrap_or(length) / section.rz());
+    let fe = PI * PI * e / (slenderness * slenderness);
+    let compression = if slenderness <= 4.71 * (e / fy).sqrt() {
+        Strength::new(PHI * 0.658f64.powf(fy / fe) * fy * section.a, "E3-2")
+    } else {
+        Strength::new(PHI * 0.877 * fe * section.a, "E3-3")
+    };
+
+    let (flexure_y, flexure_z, shear_y, shear_z) = match section.shape {
+        Some(SectionShape::WideFlange { depth: d, flange_width: bf, flange_thickness: tf, web_thickness: tw }) => {
+            let (sx, sy) = (section.iy / (d / 2.0), section.iz / (bf / 2.0));
+            let (zx, zy) = (section.zy.unwrap_or(sx), section.zz.unwrap_or(sy));
+            let flange = bf / (2.0 * tf);
+            let (lambda_p, lambda_r) = (0.38 * (e / fy).sqrt(), (e / fy).sqrt());
+
+            // F2: yielding and lateral-torsional buckling
+            let mp = fy * zx;
+            let lp = 1.76 * section.rz() * (e / fy).sqrt();
+            let h0 = d - tf;
+            let cw = section.iz * h0 * h0 / 4.0;
+            let rts = ((section.iz * cw).sqrt() / sx).sqrt();
+            let jc = section.j / (sx * h0);
+            let lr = 1.95 * rts * e / (0.7 * fy) * (jc + (jc * jc + 6.76 * (0.7 * fy / e).powi(2)).sqrt()).sqrt();
+            let mut major = if lb <= lp {
+                Strength::new(mp, "F2-1")
+            } else if lb <= lr {
+                Strength::new((p.cb * (mp - (mp - 0.7 * fy * sx) * (lb - lp) / (lr - lp))).min(mp), "F2-2")
+            } else {
+                let fcr = p.cb * PI * PI * e / (lb / rts).powi(2) * (1.0 + 0.078 * jc * (lb / rts).powi(2)).sqrt();
+                Strength::new((fcr * sx).min(mp), "F2-3")
+            };
+
+            // F3 and F6: flange local buckling
+            let mpy = (fy * zy).min(1.6 * fy * sy);
+            let mut minor = Strength::new(mpy, "F6-1");
+            if flange > lambda_r {
+                let h = d - 2.0 * tf;
+                let kc = (4.0 / (h / tw).sqrt()).clamp(0.35, 0.76);
+                major = major.min(Strength::new(0.9 * e * kc * sx / (flange * flange), "F3-2"));
+                minor = minor.min(Strength::new(0.69 * e / (flange * flange) * sy, "F6-3"));
+            } else if flange > lambda_p {
+                let reduction = (flange - lambda_p) / (lambda_r - lambda_p);
+                major = major.min(Strength::new(mp - (mp - 0.7 * fy * sx) * reduction, "F3-1"));
+                minor = minor.min(Strength::new(mpy - (mpy - 0.7 * fy * sy) * reduction, "F6-2"));
+            }
+
+            // G2: web shear, G6: flange shear in the weak direction
+            let h_tw = (d - 2.0 * tf) / tw;
+            let limit = 1.10 * (5.34 * e / fy).sqrt();
+            let web = if h_tw <= 2.24 * (e / fy).sqrt() {
+                1.0 * 0.6 * fy * d * tw
+            } else {
+                PHI * 0.6 * fy * d * tw * if h_tw <= limit { 1.0 } else { limit / h_tw }
+            };
+            (
+                Strength::new(PHI * major.value, major.clause),
+                Strength::new(PHI * minor.value, minor.clause),
+                Strength::new(PHI * 0.6 * fy * 2.0 * bf * tf, "G6-1"),
+                Strength::new(web, "G2-1"),
+            )
+        }
+        Some(SectionShape::Box { width, depth, wall_thickness: t }) => {
+            // F7 about each axis: flanges are the walls parallel to the bending axis
+            let flexure = |b: f64, d: f64, i: f64| {
+                let s = i / (d / 2.0);
+                let mp = fy * (b * d * d - (b - 2.0 * t) * (d - 2.0 * t).powi(2)) / 4.0;
+                let flange = (b - 3.0 * t) / t;
+                if flange > 1.12 * (e / fy).sqrt() {
+                    let mn = mp - (mp - fy * s) * (3.57 * flange * (fy / e).sqrt() - 4.0);
+                    Strength::new(PHI * mn.min(mp), "F7-2")
+                } else {
+                    Strength::new(PHI * mp, "F7-1")
+                }
+            };
+            // G4 with kv = 5 on the two webs
+            let shear = |h: f64| {
+                let (ratio, kv_e_fy) = (h / t, (5.0 * e / fy).sqrt());
+                let cv2 = if ratio <= 1.10 * kv_e_fy {
+                    1.0
+                } else if ratio <= 1.37 * kv_e_fy {
+                    1.10 * kv_e_fy / ratio
+                } else {
+                    1.51 * 5.0 * e / (ratio * ratio * fy)
+                };
+                Strength::new(PHI * 0.6 * fy * 2.0 * h * t * cv2, "G4-1")
+            };
+            (
+                flexure(width, depth, section.iy),
+                flexure(depth, width, section.iz),
+                shear(width - 3.0 * t),
+                shear(depth - 3.0 * t),
+            )
+        }
+        Some(SectionShape::Pipe { outer_diameter, wall_thickness }) => {
+            let z = (outer_diameter.powi(3) - (outer_diameter - 2.0 * wall_thickness).powi(3)) / 6.0;
+            let flexure = Strength::new(PHI * fy * z, "F8-1");
+            let shear = Strength::new(PHI * 0.6 * fy * section.a / 2.0, "G5-1");
+            (flexure, flexure, shear, shear)
+        }
+        Some(SectionShape::Rectangle { width, depth }) => {
+            let sx = section.iy / (depth / 2.0);
+            let sy = section.iz / (width / 2.0);
+            let mut major = Strength::new(fy * section.zy.unwrap_or(sx).min(1.6 * sx), "F11-1");
+            // F11: lateral-torsional buckling of bars bent about the major axis
+            let bar = lb * depth / (width * width);
+            if depth > width && bar > 0.08 * e / fy {
+                major = major.min(if bar <= 1.9 * e / fy {
+                    Strength::new(p.cb * (1.52 - 0.274 * bar * fy / e) * fy * sx, "F11-2")
+                } else {
+                    Strength::new(1.9 * e * p.cb / bar * sx, "F11-3")
+                });
+            }
+            let minor = Strength::new(fy * section.zz.unwrap_or(sy).min(1.6 * sy), "F11-1");
+            let shear = Strength::new(PHI * 0.6 * fy * section.a, "G1");
+            (Strength::new(PHI * major.value, major.clause), Strength::new(PHI * minor.value, minor.clause), shear, shear)
+        }
+        Some(SectionShape::Circle { diameter }) => {
+            let s = section.iy / (diameter / 2.0);
+            let flexure = Strength::new(PHI * fy * section.zy.unwrap_or(s).min(1.6 * s), "F11-1");
+            let shear = Strength::new(PHI * 0.6 * fy * section.a, "G1");
+            (flexure, flexure, shear, shear)
+        }
+        None => {
+            let modulus = |z: Option<f64>, i: f64, extent: Option<f64>| {
+                z.or(extent.map(|c| i / (c / 2.0))).ok_or_else(|| FEAError::InvalidInput(
+                    "Steel design needs a section shape, plastic modulus or depth and width".to_string(),
+                ))
+            };
+            let zy = modulus(section.zy, section.iy, section.depth)?;
+            let zz = modulus(section.zz, section.iz, section.width)?;
+            let shear = Strength::new(PHI * 0.6 * fy * section.a, "G1");
+            (Strength::new(PHI * fy * zy, "F1"), Strength::new(PHI * fy * zz, "F1"), shear, shear)
+        }
+    };
+
+    Ok(Strengths {
+        tension: Strength::new(PHI * fy * section.a, "D2-1"),
+        compression,
+        flexure_y,
+        flexure_z,
+        shear_y,
+        shear_z,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::design::DesignSettings;
+    use crate::elements::{Member, Node, Support};
+    use crate::model::FEModel;
+    use crate::loads::DistributedLoad;
+    use approx::assert_relative_eq;
+
+    fn forces(axial: f64, moment_y: f64) -> MemberForces {
+        MemberForces { axial, shear_y: 0.0, shear_z: 0.0, torsion: 0.0, moment_y, moment_z: 0.0 }
+    }
+
+    #[test]
+    fn test_braced_beam_and_column_interaction() {
+        let steel = Material::steel();
+        let section = Section::wide_flange(0.3, 0.15, 0.012, 0.008);
+        let fy = 250e6;
+
+        // A continuously braced compact beam reaches its plastic moment
+        let braced = DesignParameters::new().with_lateral_bracing(0.1, 1.0);
+        let beam = Aisc360::new()
+            .check_member(&section, &steel, 6.0, &braced, &[(3.0, forces(0.0, 100e3))])
+            .unwrap();
+        let flexure = beam.governing().unwrap();
+        assert_eq!((flexure.name.as_str(), flexure.clause.as_str()), ("Flexure y", "F2-1"));
+        assert_relative_eq!(flexure.capacity, 0.9 * fy * section.zy.unwrap(), max_relative = 1e-12);
+
+        // An unbraced one is governed by lateral-torsional buckling
+        let unbraced = Aisc360::new()
+            .check_member(&section, &steel, 6.0, &DesignParameters::new(), &[(3.0, forces(0.0, 100e3))])
+            .unwrap();
+        assert!(unbraced.governing().unwrap().capacity < flexure.capacity);
+
+        // Column buckling about the weak axis (E3-2) and H1-1a interaction
+        let column = Aisc360::new()
+            .check_member(&section, &steel, 3.0, &DesignParameters::new(), &[(0.0, forces(-500e3, 50e3))])
+            .unwrap();
+        let kl_r = 3.0 / section.rz();
+        let fe = PI * PI * steel.e / (kl_r * kl_r);
+        let pc = 0.9 * 0.658f64.powf(fy / fe) * fy * section.a;
+        let compression = column.checks.iter().find(|c| c.name == "Compression").unwrap();
+        assert_eq!(compression.clause, "E3-2");
+        assert_relative_eq!(compression.capacity, pc, max_relative = 1e-12);
+        let mc = column.checks.iter().find(|c| c.name == "Flexure y").unwrap().capacity;
+        let combined = column.governing().unwrap();
+        assert_eq!((combined.name.as_str(), combined.clause.as_str()), ("Combined", "H1-1a"));
+        assert_relative_eq!(combined.ratio, 500e3 / pc + 8.0 / 9.0 * 50e3 / mc, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_check_model_reports_each_member_and_combo() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::wide_flange(0.3, 0.15, 0.012, 0.008)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_member("B1", Member::new("N1", "N2", "Steel", "W").with_rotation(PI / 2.0)).unwrap();
+        model.add_support("N1", Support::pinned()).unwrap();
+        model.add_support("N2", Support::with_restraints(false, true, true, true, false, false)).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(20e3, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+
+        let braced = DesignParameters::new().with_lateral_bracing(0.5, 1.0);
+        let settings = DesignSettings::new().with_member("B1", braced);
+        let results = Aisc360::new().check_model(&model, &settings).unwrap();
+        let result = &results["B1"]["Combo 1"];
+        // Midspan moment wL^2/8 at the middle station
+        let flexure = result.checks.iter().find(|c| c.name == "Flexure y").unwrap();
+        assert_eq!(flexure.clause, "F2-1");
+        assert_relative_eq!(flexure.demand, 20e3 * 36.0 / 8.0, max_relative = 1e-9);
+        assert_relative_eq!(flexure.position, 3.0, max_relative = 1e-12);
+        assert!(result.passes());
+    }
+}
@@ -0,0 +1,243 @@
+//! Member deflection (serviceability) checks
+//!
+//! Deflections relative to each member's chord, from
+//! [`FEModel::member_deflections`], are compared with limits that apply to
+//! the load combinations carrying a tag, e.g. span/360 for combinations
+//! tagged "live" and span/240 for those tagged "total":
+//!
+//! ```rust
+//! use fea_solver::prelude::*;
+//!
+//! let checks = DeflectionChecks::standard()
+//!     .with_limit("total", DeflectionLimit::Absolute(0.025))
+//!     .with_span("Cantilever", 2.0 * 1.5);
+//! # let _ = checks;
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{design_members, member_length};
+use crate::error::{FEAError, FEAResult};
+use crate::model::FEModel;
+
+/// Allowable deflection of a member
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeflectionLimit {
+    /// Span divided by a ratio, e.g. 360 for span/360
+    SpanRatio(f64),
+    /// Fixed deflection, in model length units
+    Absolute(f64),
+}
+
+impl DeflectionLimit {
+    /// Allowable deflection of a member with the given span
+    pub fn allowable(&self, span: f64) -> f64 {
+        match *self {
+            DeflectionLimit::SpanRatio(ratio) => span / ratio,
+            DeflectionLimit::Absolute(limit) => limit,
+        }
+    }
+}
+
+impl fmt::Display for DeflectionLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeflectionLimit::SpanRatio(ratio) => write!(f, "L/{ratio}"),
+            DeflectionLimit::Absolute(limit) => write!(f, "{limit}"),
+        }
+    }
+}
+
+/// A deflection limit applying to the load combinations with a tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeflectionCriterion {
+    /// Load combination tag, e.g. "live"
+    pub tag: String,
+    /// Allowable deflection
+    pub limit: DeflectionLimit,
+}
+
+/// Deflection limits checked against the members of a model
+///
+/// A member's span defaults to its length; cantilevers are usually given
+/// twice their length so span/360 reads as the usual cantilever limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeflectionChecks {
+    /// Limits, each applying to the combinations with its tag
+    pub criteria: Vec<DeflectionCriterion>,
+    /// Span overrides by member (or physical member) name
+    pub spans: BTreeMap<String, f64>,
+    /// Number of check stations along each member
+    pub stations: usize,
+}
+
+impl Default for DeflectionChecks {
+    fn default() -> Self {
+        Self {
+            criteria: Vec::new(),
+            spans: BTreeMap::new(),
+            stations: 21,
+        }
+    }
+}
+
+impl DeflectionChecks {
+    /// Checks without any limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Span/360 for combinations tagged "live" and span/240 for those tagged "total"
+    pub fn standard() -> Self {
+        Self::new()
+            .with_limit("live", DeflectionLimit::SpanRatio(360.0))
+            .with_limit("total", DeflectionLimit::SpanRatio(240.0))
+    }
+
+    /// Add a limit for the combinations with a tag
+    pub fn with_limit(mut self, tag: &str, limit: DeflectionLimit) -> Self {
+        self.criteria.push(DeflectionCriterion { tag: tag.to_string(), limit });
+        self
+    }
+
+    /// Set the span a member's ratio limits are based on
+    pub fn with_span(mut self, name: &str, span: f64) -> Self {
+        self.spans.insert(name.to_string(), span);
+        self
+    }
+
+    /// Set the number of check stations along each member
+    pub fn with_stations(mut self, stations: usize) -> Self {
+        self.stations = stations.max(2);
+        self
+    }
+
+    /// Check every member against the limits of each tag
+    ///
+    /// A tag's check reports the worst ratio over its limits and over the
+    /// analysed combinations carrying it. Physical members are checked as a
+    /// whole rather than by segment.
+    pub fn check_model(&self, model: &FEModel) -> FEAResult<DeflectionResults> {
+        let mut results = DeflectionResults::new();
+        for (name, _) in design_members(model) {
+            let length = member_length(model, name).ok_or(FEAError::NotAnalyzed)?;
+            let span = self.spans.get(name).copied().unwrap_or(length);
+
+            let mut tags: BTreeMap<String, DeflectionCheck> = BTreeMap::new();
+            for (combo_name, combo) in &model.load_combos {
+                let criteria: Vec<_> = self.criteria.iter().filter(|c| combo.tags.contains(&c.tag)).collect();
+                if criteria.is_empty() || model.member_forces_i(name, combo_name).is_err() {
+                    continue;
+                }
+                let (position, deflection) = model.member_deflections(name, combo_name, self.stations)?
+                    .iter()
+                    .map(|d| (d.x, d.relative()))
+                    .fold((0.0, 0.0), |worst, station| if station.1 > worst.1 { station } else { worst });
+
+                for criterion in criteria {
+                    let allowable = criterion.limit.allowable(span);
+                    let check = DeflectionCheck {
+                        combo: combo_name.clone(),
+                        limit: criterion.limit,
+                        deflection,
+                        allowable,
+                        ratio: deflection / allowable,
+                        position,
+                    };
+                    match tags.get(&criterion.tag) {
+                        Some(existing) if existing.ratio >= check.ratio => {}
+                        _ => {
+                            tags.insert(criterion.tag.clone(), check);
+                        }
+                    }
+                }
+            }
+            if !tags.is_empty() {
+                results.insert(name.to_string(), tags);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Governing deflection check of a member for one combination tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeflectionCheck {
+    /// Load combination giving the highest ratio
+    pub combo: String,
+    /// Governing limit
+    pub limit: DeflectionLimit,
+    /// Largest deflection relative to the member chord
+    pub deflection: f64,
+    /// Allowable deflection
+    pub allowable: f64,
+    /// Deflection / allowable
+    pub ratio: f64,
+    /// Distance of the largest deflection from the i-node
+    pub position: f64,
+}
+
+impl DeflectionCheck {
+    /// Whether the deflection is within the limit
+    pub fn passes(&self) -> bool {
+        self.ratio <= 1.0
+    }
+}
+
+/// Deflection checks by member name, then load combination tag
+pub type DeflectionResults = BTreeMap<String, BTreeMap<String, DeflectionCheck>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{Material, Member, Node, Section, Support};
+    use crate::loads::{DistributedLoad, LoadCombination};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_span_ratio_and_absolute_limits() {
+        let (span, w) = (6.0, 5_000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(span, 0.0, 0.0)).unwrap();
+        model.add_member("B1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("B", Support::with_restraints(false, true, true, false, false, false)).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(w, "Dead")).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(w, "Live")).unwrap();
+        model.add_load_combo(LoadCombination::single("L", "Live").with_tag("live")).unwrap();
+        model.add_load_combo(LoadCombination::new("D+L").with_case("Dead", 1.0).with_case("Live", 1.0).with_tag("total"))
+            .unwrap();
+        model.add_load_combo(LoadCombination::new("1.2D+1.6L").with_case("Dead", 1.2).with_case("Live", 1.6))
+            .unwrap();
+        model.analyze_linear().unwrap();
+
+        let section = Section::rectangular(0.2, 0.4);
+        let live = 5.0 * w * span.powi(4) / (384.0 * Material::steel().e * section.iz);
+        let results = DeflectionChecks::standard()
+            .with_limit("total", DeflectionLimit::Absolute(1.5 * live))
+            .check_model(&model)
+            .unwrap();
+
+        // Untagged strength combinations are not checked
+        let checks = &results["B1"];
+        assert_eq!(checks.keys().collect::<Vec<_>>(), ["live", "total"]);
+        let check = &checks["live"];
+        assert_eq!(check.combo, "L");
+        assert_relative_eq!(check.deflection, live, max_relative = 1e-6);
+        assert_relative_eq!(check.position, span / 2.0);
+        assert_relative_eq!(check.ratio, live / (span / 360.0), max_relative = 1e-6);
+        assert!(check.passes());
+
+        // The absolute limit is tighter than span/240 and fails
+        let total = &checks["total"];
+        assert_eq!((total.combo.as_str(), total.limit), ("D+L", DeflectionLimit::Absolute(1.5 * live)));
+        assert_relative_eq!(total.ratio, 2.0 / 1.5, max_relative = 1e-6);
+        assert!(!total.passes());
+    }
+}
@@ -0,0 +1,358 @@
+//! EN 1993-1-1 steel member design checks
+//!
+//! Sections are classified to Table 5.2 and checked for tension (6.2.3),
+//! compression (6.2.4), bending (6.2.5), shear (6.2.6), flexural buckling
+//! (6.3.1), lateral-torsional buckling of I-sections by the general method
+//! (6.3.2.2) and combined bending and compression with the Annex B
+//! interaction factors (6.3.3). Equivalent uniform moment factors are taken
+//! as 1.0, which is conservative. Class 4 sections are checked with their
+//! gross elastic properties, and shear buckling of slender webs is not
+//! checked. Sections without a [`SectionShape`] are treated as Class 1 when
+//! they have a plastic modulus and Class 3 otherwise.
+
+use std::f64::consts::PI;
+
+use crate::design::{CodeCheck, DesignParameters, DesignResult, Strength};
+use crate::elements::{Material, Section, SectionShape};
+use crate::error::{FEAError, FEAResult};
+use crate::results::MemberForces;
+
+/// Imperfection factors of buckling curves a, b, c and d (Tables 6.1 and 6.3)
+const CURVE_A: f64 = 0.21;
+const CURVE_B: f64 = 0.34;
+const CURVE_C: f64 = 0.49;
+const CURVE_D: f64 = 0.76;
+
+/// EN 1993-1-1 member checks
+///
+/// Partial factors default to the recommended γM0 = γM1 = 1.0; set the
+/// National Annex values with [`Eurocode3::with_partial_factors`].
+#[derive(Debug, Clone, Copy)]
+pub struct Eurocode3 {
+    /// Partial factor for resistance of cross-sections
+    pub gamma_m0: f64,
+    /// Partial factor for resistance of members to instability
+    pub gamma_m1: f64,
+}
+
+impl Default for Eurocode3 {
+    fn default() -> Self {
+        Self { gamma_m0: 1.0, gamma_m1: 1.0 }
+    }
+}
+
+impl Eurocode3 {
+    /// Create the checker with the recommended partial factors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the partial factors γM0 and γM1
+    pub fn with_partial_factors(mut self, gamma_m0: f64, gamma_m1: f64) -> Self {
+        self.gamma_m0 = gamma_m0;
+        self.gamma_m1 = gamma_m1;
+        self
+    }
+}
+
+/// Cross-section data for the checks
+struct Properties {
+    /// Section class in compression, bending about y and bending about z
+    class: [u8; 3],
+    /// Section moduli for bending about y and z, by class
+    modulus: [f64; 2],
+    /// Shear areas for shear along y and z
+    shear_area: [f64; 2],
+    /// Imperfection factors for flexural buckling about y and z
+    curves: [f64; 2],
+    /// Warping constant and lateral-torsional buckling imperfection factor of I-sections
+    ltb: Option<(f64, f64)>,
+}
+
+/// Class of a compression part from its width-to-thickness ratio and Class 1, 2 and 3 limits
+fn class(ratio: f64, limits: [f64; 3]) -> u8 {
+    limits.iter().filter(|&&limit| ratio > limit).count() as u8 + 1
+}
+
+/// Reduction factor for buckling (6.49 and 6.56)
+fn reduction(slenderness: f64, alpha: f64) -> f64 {
+    if slenderness <= 0.2 {
+        return 1.0;
+    }
+    let phi = 0.5 * (1.0 + alpha * (slenderness - 0.2) + slenderness * slenderness);
+    (1.0 / (phi + (phi * phi - slenderness * slenderness).sqrt())).min(1.0)
+}
+
+fn properties(section: &Section, fy: f64) -> FEAResult<Properties> {
+    let eps = (235e6 / fy).sqrt();
+    let compression = [33.0 * eps, 38.0 * eps, 42.0 * eps];
+    let bending = [72.0 * eps, 83.0 * eps, 124.0 * eps];
+    let elastic = |i: f64, extent: f64| i / (extent / 2.0);
+    let by_class = |class: u8, plastic: f64, elastic: f64| if class <= 2 { plastic } else { elastic };
+
+    Ok(match section.shape {
+        Some(SectionShape::WideFlange { depth: d, flange_width: bf, flange_thickness: tf, web_thickness: tw }) => {
+            let flange = class((bf - tw) / 2.0 / tf, [9.0 * eps, 10.0 * eps, 14.0 * eps]);
+            let web = (d - 2.0 * tf) / tw;
+            let class = [flange.max(class(web, compression)), flange.max(class(web, bending)), flange];
+            let (wel_y, wel_z) = (elastic(section.iy, d), elastic(section.iz, bf));
+            let rolled = if d / bf > 1.2 { [CURVE_A, CURVE_B] } else { [CURVE_B, CURVE_C] };
+            let thick = if d / bf > 1.2 { [CURVE_B, CURVE_C] } else { [CURVE_D, CURVE_D] };
+            Properties {
+                class,
+                modulus: [
+                    by_class(class[1], section.zy.unwrap_or(wel_y), wel_y),
+                    by_class(class[2], section.zz.unwrap_or(wel_z), wel_z),
+                ],
+                shear_area: [2.0 * bf * tf, (section.a - 2.0 * bf * tf + tw * tf).max((d - 2.0 * tf) * tw)],
+                curves: if tf <= 0.04 { rolled } else { thick },
+                ltb: Some((section.iz * (d - tf).powi(2) / 4.0, if d / bf <= 2.0 { CURVE_A } else { CURVE_B })),
+            }
+        }
+        Some(SectionShape::Box { width: b, depth: d, wall_thickness: t }) => {
+            let (flange_y, web_y) = ((b - 3.0 * t) / t, (d - 3.0 * t) / t);
+            let class = [
+                class(flange_y, compression).max(class(web_y, compression)),
+                class(flange_y, compression).max(class(web_y, bending)),
+                class(web_y, compression).max(class(flange_y, bending)),
+            ];
+            let plastic = |b: f64, d: f64| (b * d * d - (b - 2.0 * t) * (d - 2.0 * t).powi(2)) / 4.0;
+            Properties {
+                class,
+                modulus: [
+                    by_class(class[1], plastic(b, d), elastic(section.iy, d)),
+                    by_class(class[2], plastic(d, b), elastic(section.iz, b)),
+                ],
+                shear_area: [section.a * b / (b + d), section.a * d / (b + d)],
+                curves: [CURVE_A, CURVE_A],
+                ltb: None,
+            }
+        }
+        Some(SectionShape::Pipe { outer_diameter, wall_thickness }) => {
+            let class = class(outer_diameter / wall_thickness, [50.0 * eps * eps, 70.0 * eps * eps, 90.0 * eps * eps]);
+            let plastic = (outer_diameter.powi(3) - (outer_diameter - 2.0 * wall_thickness).powi(3)) / 6.0;
+            let modulus = by_class(class, plastic, elastic(section.iy, outer_diameter));
+            Properties {
+                class: [class; 3],
+                modulus: [modulus; 2],
+                shear_area: [2.0 * section.a / PI; 2],
+                curves: [CURVE_A, CURVE_A],
+                ltb: None,
+            }
+        }
+        Some(SectionShape::Rectangle { width, depth }) => Properties {
+            class: [1; 3],
+            modulus: [
+                section.zy.unwrap_or(elastic(section.iy, depth)),
+                section.zz.unwrap_or(elastic(section.iz, width)),
+            ],
+            shear_area: [section.a; 2],
+            curves: [CURVE_C, CURVE_C],
+            ltb: None,
+        },
+        Some(SectionShape::Circle { diameter }) => Properties {
+            class: [1; 3],
+            modulus: [section.zy.unwrap_or(elastic(section.iy, diameter)); 2],
+            shear_area: [section.a; 2],
+            curves: [CURVE_C, CURVE_C],
+            ltb: None,
+        },
+        None => {
+            let modulus = |z: Option<f64>, i: f64, extent: Option<f64>| {
+                z.or(extent.map(|c| elastic(i, c))).ok_or_else(|| FEAError::InvalidInput(
+                    "Steel design needs a section shape, plastic modulus or depth and width".to_string(),
+                ))
+            };
+            let class = if section.zy.is_some() && section.zz.is_some() { 1 } else { 3 };
+            Properties {
+                class: [class; 3],
+                modulus: [modulus(section.zy, section.iy, section.depth)?, modulus(section.zz, section.iz, section.width)?],
+                shear_area: [section.a; 2],
+                curves: [CURVE_C, CURVE_C],
+                ltb: None,
+            }
+        }
+    })
+}
+
+impl CodeCheck for Eurocode3 {
+    fn code(&self) -> &'static str {
+        "EN 1993-1-1"
+    }
+
+    fn check_member(
+        &self,
+        section: &Section,
+        material: &Material,
+        length: f64,
+        p: &DesignParameters,
+        forces: &[(f64, MemberForces)],
+    ) -> FEAResult<DesignResult> {
+        let fy = material.fy
+            .ok_or_else(|| FEAError::InvalidInput("Steel design needs a material yield strength".to_string()))?;
+        let e = material.e;
+        let props = properties(section, fy)?;
+        let (gm0, gm1) = (self.gamma_m0, self.gamma_m1);
+        let n_rk = section.a * fy;
+
+        // 6.3.1: flexural buckling about y and z
+        let effective = [p.ky * p.ly.unwrap_or(length), p.kz * p.lz.unwrap_or(length)];
+        let slenderness = [(section.iy, effective[0]), (section.iz, effective[1])]
+            .map(|(i, l)| (n_rk * l * l / (PI * PI * e * i)).sqrt());
+        let chi = [reduction(slenderness[0], props.curves[0]), reduction(slenderness[1], props.curves[1])];
+        let n_b = chi.map(|c| c * n_rk / gm1);
+        let compression = Strength::new(n_rk / gm0, "6.10")
+            .min(Strength::new(n_b[0], "6.47"))
+            .min(Strength::new(n_b[1], "6.47"));
+
+        // 6.2.5 and 6.3.2: bending, with lateral-torsional buckling of I-sections about y
+        let m_rk = props.modulus.map(|w| w * fy);
+        let bending_clause = |class: u8| if class <= 2 { "6.13" } else { "6.14" };
+        let mut flexure_y = Strength::new(m_rk[0] / gm0, bending_clause(props.class[1]));
+        let flexure_z = Strength::new(m_rk[1] / gm0, bending_clause(props.class[2]));
+        let mut chi_lt = 1.0;
+        if let Some((iw, alpha_lt)) = props.ltb {
+            let lb = p.lb.unwrap_or(length);
+            let m_cr = p.cb * PI * PI * e * section.iz / (lb * lb)
+                * (iw / section.iz + lb * lb * material.g * section.j / (PI * PI * e * section.iz)).sqrt();
+            chi_lt = reduction((m_rk[0] / m_cr).sqrt(), alpha_lt);
+            flexure_y = flexure_y.min(Strength::new(chi_lt * m_rk[0] / gm1, "6.55"));
+        }
+
+        // 6.2.6: plastic shear resistance
+        let shear = props.shear_area.map(|a| Strength::new(a * fy / (3f64.sqrt() * gm0), "6.18"));
+        let tension = Strength::new(n_rk / gm0, "6.6");
+
+        // Annex B interaction factors with Cm = CmLT = 1
+        let plastic = props.class.iter().all(|&c| c <= 2);
+        let i_section = props.ltb.is_some();
+        let torsional = chi_lt < 1.0;
+
+        let mut result = DesignResult::default();
+        for &(x, f) in forces {
+            let axial = if f.axial >= 0.0 { tension } else { compression };
+            result.record(if f.axial >= 0.0 { "Tension" } else { "Compression" }, f.axial.abs(), axial, x);
+            result.record("Flexure y", f.moment_y.abs(), flexure_y, x);
+            result.record("Flexure z", f.moment_z.abs(), flexure_z, x);
+            result.record("Shear y", f.shear_y.abs(), shear[0], x);
+            result.record("Shear z", f.shear_z.abs(), shear[1], x);
+
+            let (my, mz) = (f.moment_y.abs(), f.moment_z.abs());
+            if f.axial >= 0.0 {
+                // 6.2.1(7): linear summation of utilisations
+                let ratio = f.axial / tension.value + my * gm0 / m_rk[0] + mz * gm0 / m_rk[1];
+                result.record("Combined", ratio, Strength::new(1.0, "6.2"), x);
+                continue;
+            }
+            let [n_y, n_z] = n_b.map(|n| -f.axial / n);
+            let (kyy, kzz, kyz, mut kzy) = if plastic {
+                let kyy = (1.0 + (slenderness[0] - 0.2) * n_y).min(1.0 + 0.8 * n_y);
+                let kzz = if i_section {
+                    (1.0 + (2.0 * slenderness[1] - 0.6) * n_z).min(1.0 + 1.4 * n_z)
+                } else {
+                    (1.0 + (slenderness[1] - 0.2) * n_z).min(1.0 + 0.8 * n_z)
+                };
+                (kyy, kzz, 0.6 * kzz, 0.6 * kyy)
+            } else {
+                let kyy = (1.0 + 0.6 * slenderness[0] * n_y).min(1.0 + 0.6 * n_y);
+                let kzz = (1.0 + 0.6 * slenderness[1] * n_z).min(1.0 + 0.6 * n_z);
+                (kyy, kzz, kzz, 0.8 * kyy)
+            };
+            if torsional {
+                // Table B.2 with CmLT - 0.25 = 0.75
+                let (factor, lambda) = (if plastic { 0.1 } else { 0.05 }, slenderness[1]);
+                kzy = if plastic && lambda < 0.4 {
+                    (0.6 + lambda).min(1.0 - factor * lambda * n_z / 0.75)
+                } else {
+                    (1.0 - factor * lambda * n_z / 0.75).max(1.0 - factor * n_z / 0.75)
+                };
+            }
+            let bending_y = my / (chi_lt * m_rk[0] / gm1);
+            let bending_z = mz / (m_rk[1] / gm1);
+            let about_y = n_y + kyy * bending_y + kyz * bending_z;
+            let about_z = n_z + kzy * bending_y + kzz * bending_z;
+            let (ratio, clause) = if about_y >= about_z { (about_y, "6.61") } else { (about_z, "6.62") };
+            result.record("Combined", ratio, Strength::new(1.0, clause), x);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::design::aisc::Aisc360;
+    use crate::design::DesignSettings;
+    use crate::elements::{Member, Node, Support};
+    use crate::loads::DistributedLoad;
+    use crate::model::FEModel;
+    use approx::assert_relative_eq;
+
+    fn forces(axial: f64, moment_y: f64) -> MemberForces {
+        MemberForces { axial, shear_y: 0.0, shear_z: 0.0, torsion: 0.0, moment_y, moment_z: 0.0 }
+    }
+
+    #[test]
+    fn test_flexural_buckling_and_interaction() {
+        let steel = Material::steel();
+        let section = Section::wide_flange(0.3, 0.15, 0.012, 0.008);
+        let n_rk = section.a * 250e6;
+
+        // At a relative slenderness of 1.0 about z, curve b gives chi = 0.597
+        let length = PI * (steel.e * section.iz / n_rk).sqrt();
+        let code = Eurocode3::new();
+        let column = code
+            .check_member(&section, &steel, length, &DesignParameters::new(), &[(0.0, forces(-100e3, 0.0))])
+            .unwrap();
+        let compression = &column.checks[0];
+        assert_eq!((compression.name.as_str(), compression.clause.as_str()), ("Compression", "6.47"));
+        assert_relative_eq!(compression.capacity / n_rk, 0.597, epsilon = 5e-4);
+
+        // Adding major-axis bending makes the 6.62 interaction govern
+        let beam_column = code
+            .check_member(&section, &steel, length, &DesignParameters::new(), &[(0.0, forces(-300e3, 30e3))])
+            .unwrap();
+        let combined = beam_column.governing().unwrap();
+        assert_eq!((combined.name.as_str(), combined.clause.as_str()), ("Combined", "6.62"));
+        assert!(combined.ratio > beam_column.checks.iter().find(|c| c.name == "Compression").unwrap().ratio);
+
+        // National Annex partial factors scale the resistances
+        let annex = Eurocode3::new().with_partial_factors(1.0, 1.1)
+            .check_member(&section, &steel, length, &DesignParameters::new(), &[(0.0, forces(-100e3, 0.0))])
+            .unwrap();
+        assert_relative_eq!(annex.checks[0].capacity * 1.1, compression.capacity, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_switch_codes_on_a_model() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::wide_flange(0.3, 0.15, 0.012, 0.008)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_member("B1", Member::new("N1", "N2", "Steel", "W").with_rotation(PI / 2.0)).unwrap();
+        model.add_support("N1", Support::pinned()).unwrap();
+        model.add_support("N2", Support::with_restraints(false, true, true, true, false, false)).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(20e3, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+
+        let unbraced = DesignSettings::new();
+        let braced = DesignSettings::new().with_defaults(DesignParameters::new().with_lateral_bracing(0.5, 1.0));
+        let flexure = |code: &dyn CodeCheck, settings: &DesignSettings| {
+            let results = code.check_model(&model, settings).unwrap();
+            results["B1"]["Combo 1"].checks.iter().find(|c| c.name == "Flexure y").unwrap().clone()
+        };
+
+        let codes: [Box<dyn CodeCheck>; 2] = [Box::new(Aisc360::new()), Box::new(Eurocode3::new())];
+        let clauses: Vec<_> = codes.iter()
+            .map(|code| (code.code(), flexure(code.as_ref(), &unbraced).clause, flexure(code.as_ref(), &braced).clause))
+            .collect();
+        assert_eq!(clauses[0], ("AISC 360-16", "F2-3".to_string(), "F2-1".to_string()));
+        assert_eq!(clauses[1], ("EN 1993-1-1", "6.55".to_string(), "6.13".to_string()));
+
+        // Both codes give the plastic moment of a braced compact beam (with phi = 0.9 for AISC)
+        let ec3 = flexure(codes[1].as_ref(), &braced);
+        assert_relative_eq!(ec3.capacity, 250e6 * Section::wide_flange(0.3, 0.15, 0.012, 0.008).zy.unwrap());
+        assert_relative_eq!(flexure(codes[0].as_ref(), &braced).capacity, 0.9 * ec3.capacity, max_relative = 1e-12);
+    }
+}
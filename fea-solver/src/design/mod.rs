@@ -0,0 +1,319 @@
+//! Member design checks against steel and concrete design codes
+//!
+//! Checks run on an analysed model and produce a [`DesignResult`] per member
+//! per load combination, listing each check's utilisation ratio and the code
+//! equation that governs it. Every code implements [`CodeCheck`], so a
+//! project can switch codes without changing how it is checked:
+//!
+//! ```rust
+//! use fea_solver::prelude::*;
+//!
+//! let eurocode = true;
+//! let code: Box<dyn CodeCheck> = if eurocode { Box::new(Eurocode3::new()) } else { Box::new(Aisc360::new()) };
+//! let settings = DesignSettings::new().with_member("B1", DesignParameters::new().with_lateral_bracing(1.5, 1.0));
+//! # let _ = (code, settings);
+//! ```
+//!
+//! - [`aisc`]: AISC 360-16 steel member checks (LRFD)
+//! - [`concrete`]: ACI 318-19 reinforced concrete beam and column design
+//! - [`deflection`]: span ratio and absolute deflection limits by load combination tag
+//! - [`eurocode3`]: EN 1993-1-1 steel member checks
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::elements::{Material, Member, Section};
+use crate::error::{FEAError, FEAResult};
+use crate::model::FEModel;
+use crate::results::MemberForces;
+use crate::units::{Dimension, UnitSystem};
+
+pub mod aisc;
+pub mod concrete;
+pub mod deflection;
+pub mod eurocode3;
+
+/// A member design code
+pub trait CodeCheck {
+    /// Code name, e.g. "AISC 360-16"
+    fn code(&self) -> &'static str;
+
+    /// Check one member from its internal forces at (distance from i-node, forces) stations
+    fn check_member(
+        &self,
+        section: &Section,
+        material: &Material,
+        length: f64,
+        parameters: &DesignParameters,
+        forces: &[(f64, MemberForces)],
+    ) -> FEAResult<DesignResult>;
+
+    /// Check every member of an analysed model for every analysed load combination
+    ///
+    /// Physical members are checked as a whole rather than by segment. The
+    /// checks run in SI units whatever the model's units, so demands and
+    /// capacities are in N and m.
+    fn check_model(&self, model: &FEModel, settings: &DesignSettings) -> FEAResult<DesignResults> {
+        let mut results = DesignResults::new();
+        for (name, member) in design_members(model) {
+            let section = model.sections.get(&member.section)
+                .ok_or_else(|| FEAError::SectionNotFound(member.section.clone()))?;
+            let material = model.materials.get(&member.material)
+                .ok_or_else(|| FEAError::MaterialNotFound(member.material.clone()))?;
+            let section = model.to_units(section.clone(), &UnitSystem::SI);
+            let material = model.to_units(material.clone(), &UnitSystem::SI);
+            let length = member_length(model, name).ok_or(FEAError::NotAnalyzed)?;
+            let parameters = settings.parameters(name).to_si(&model.units);
+            let si_length = model.units.convert(length, Dimension::Length, &UnitSystem::SI);
+
+            let mut combos = BTreeMap::new();
+            for combo in model.load_combos.keys() {
+                if model.member_forces_i(name, combo).is_err() {
+                    continue;
+                }
+                let forces = station_forces(model, name, length, combo, settings.stations)?;
+                combos.insert(combo.clone(), self.check_member(&section, &material, si_length, &parameters, &forces)?);
+            }
+            results.insert(name.to_string(), combos);
+        }
+        Ok(results)
+    }
+}
+
+/// Design parameters of the members of a project
+///
+/// Members use the default [`DesignParameters`] unless given their own.
+/// Forces are checked at evenly spaced stations along each member (11 by
+/// default); each check reports its worst station.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignSettings {
+    /// Parameters of members without their own
+    pub defaults: DesignParameters,
+    /// Parameters by member (or physical member) name
+    pub members: BTreeMap<String, DesignParameters>,
+    /// Number of check stations along each member
+    pub stations: usize,
+}
+
+impl Default for DesignSettings {
+    fn default() -> Self {
+        Self {
+            defaults: DesignParameters::new(),
+            members: BTreeMap::new(),
+            stations: 11,
+        }
+    }
+}
+
+impl DesignSettings {
+    /// Settings with default parameters for every member
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the parameters of members without their own
+    pub fn with_defaults(mut self, parameters: DesignParameters) -> Self {
+        self.defaults = parameters;
+        self
+    }
+
+    /// Set the parameters of a member (or physical member)
+    pub fn with_member(mut self, name: &str, parameters: DesignParameters) -> Self {
+        self.members.insert(name.to_string(), parameters);
+        self
+    }
+
+    /// Set the number of check stations along each member
+    pub fn with_stations(mut self, stations: usize) -> Self {
+        self.stations = stations.max(2);
+        self
+    }
+
+    /// Parameters of a member
+    pub fn parameters(&self, name: &str) -> &DesignParameters {
+        self.members.get(name).unwrap_or(&self.defaults)
+    }
+}
+
+/// Unbraced lengths and moment gradient data for one member
+///
+/// Lengths default to the member length. Local y and z follow the member
+/// axes, so for I-sections bending about y is major-axis bending.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DesignParameters {
+    /// Unbraced length for buckling about local y (None = member length)
+    pub ly: Option<f64>,
+    /// Unbraced length for buckling about local z (None = member length)
+    pub lz: Option<f64>,
+    /// Effective length factor for buckling about local y
+    pub ky: f64,
+    /// Effective length factor for buckling about local z
+    pub kz: f64,
+    /// Laterally unbraced length of the compression flange (None = member length)
+    pub lb: Option<f64>,
+    /// Lateral-torsional buckling moment gradient factor (AISC Cb, EC3 C1)
+    pub cb: f64,
+}
+
+impl Default for DesignParameters {
+    fn default() -> Self {
+        Self { ly: None, lz: None, ky: 1.0, kz: 1.0, lb: None, cb: 1.0 }
+    }
+}
+
+impl DesignParameters {
+    /// Parameters with every length equal to the member length and K = Cb = 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the unbraced lengths for buckling about local y and z
+    pub fn with_unbraced_lengths(mut self, ly: f64, lz: f64) -> Self {
+        self.ly = Some(ly);
+        self.lz = Some(lz);
+        self
+    }
+
+    /// Set the effective length factors for buckling about local y and z
+    pub fn with_effective_length_factors(mut self, ky: f64, kz: f64) -> Self {
+        self.ky = ky;
+        self.kz = kz;
+        self
+    }
+
+    /// Set the lateral-torsional buckling unbraced length and modification factor
+    pub fn with_lateral_bracing(mut self, lb: f64, cb: f64) -> Self {
+        self.lb = Some(lb);
+        self.cb = cb;
+        self
+    }
+    /// The parameters with lengths in a unit system converted to SI
+    fn to_si(self, units: &UnitSystem) -> Self {
+        let si = |l: Option<f64>| l.map(|l| units.convert(l, Dimension::Length, &UnitSystem::SI));
+        Self { ly: si(self.ly), lz: si(self.lz), lb: si(self.lb), ..self }
+    }
+}
+
+/// One design check of a member for one load combination
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesignCheck {
+    /// What is checked, e.g. "Compression" or "Combined"
+    pub name: String,
+    /// Governing code equation, e.g. "E3-2"
+    pub clause: String,
+    /// Design action at the governing station
+    pub demand: f64,
+    /// Design resistance at the governing station
+    pub capacity: f64,
+    /// Utilisation ratio (demand / capacity, or the interaction value)
+    pub ratio: f64,
+    /// Distance of the governing station from the i-node
+    pub position: f64,
+}
+
+/// Design checks of a member for one load combination
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DesignResult {
+    /// Checks with non-zero demand
+    pub checks: Vec<DesignCheck>,
+}
+
+impl DesignResult {
+    /// Check with the highest ratio, the earliest listed on a tie
+    pub fn governing(&self) -> Option<&DesignCheck> {
+        self.checks.iter().rev().max_by(|a, b| a.ratio.total_cmp(&b.ratio))
+    }
+
+    /// Highest utilisation ratio (0 with no demand)
+    pub fn ratio(&self) -> f64 {
+        self.governing().map_or(0.0, |c| c.ratio)
+    }
+
+    /// Whether every ratio is at most 1
+    pub fn passes(&self) -> bool {
+        self.ratio() <= 1.0
+    }
+
+    /// Record a check at a station, keeping the worst over the stations of a member
+    fn record(&mut self, name: &str, demand: f64, strength: Strength, position: f64) {
+        if demand <= 0.0 {
+            return;
+        }
+        let check = DesignCheck {
+            name: name.to_string(),
+            clause: strength.clause.to_string(),
+            demand,
+            capacity: strength.value,
+            ratio: demand / strength.value,
+            position,
+        };
+        match self.checks.iter_mut().find(|c| c.name == check.name) {
+            Some(existing) if existing.ratio >= check.ratio => {}
+            Some(existing) => *existing = check,
+            None => self.checks.push(check),
+        }
+    }
+}
+
+/// Design strength and the equation it comes from
+#[derive(Debug, Clone, Copy)]
+struct Strength {
+    value: f64,
+    clause: &'static str,
+}
+
+impl Strength {
+    fn new(value: f64, clause: &'static str) -> Self {
+        Self { value, clause }
+    }
+
+    /// Lower of two strengths
+    fn min(self, other: Self) -> Self {
+        if other.value < self.value { other } else { self }
+    }
+}
+
+/// Design results by member name, then load combination name
+pub type DesignResults = BTreeMap<String, BTreeMap<String, DesignResult>>;
+
+/// Members to design: physical members, and analysis members not part of one
+fn design_members(model: &FEModel) -> Vec<(&str, &Member)> {
+    let segments: Vec<&str> = model.physical_members.values()
+        .flat_map(|p| p.segments().iter().map(|(s, _, _)| s.as_str()))
+        .collect();
+    let mut members: Vec<(&str, &Member)> = model.physical_members.iter()
+        .map(|(name, p)| (name.as_str(), &p.member))
+        .chain(model.members.iter()
+            .filter(|(name, _)| !segments.contains(&name.as_str()))
+            .map(|(name, m)| (name.as_str(), m)))
+        .collect();
+    members.sort_by_key(|(name, _)| *name);
+    members
+}
+
+/// Length of a design member, once analysed
+fn member_length(model: &FEModel, name: &str) -> Option<f64> {
+    match model.physical_members.get(name) {
+        Some(physical) => physical.length(),
+        None => model.members.get(name)?.length(),
+    }
+}
+
+/// Internal forces at `stations` evenly spaced points along a member, both ends included, in SI units
+fn station_forces(
+    model: &FEModel,
+    name: &str,
+    length: f64,
+    combo_name: &str,
+    stations: usize,
+) -> FEAResult<Vec<(f64, MemberForces)>> {
+    let stations = stations.max(2);
+    (0..stations)
+        .map(|k| {
+            let x = length * k as f64 / (stations - 1) as f64;
+            let forces = model.to_units(model.member_forces_at(name, x, combo_name)?, &UnitSystem::SI);
+            Ok((model.units.convert(x, Dimension::Length, &UnitSystem::SI), forces))
+        })
+        .collect()
+}
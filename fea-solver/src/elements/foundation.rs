@@ -0,0 +1,32 @@
+//! Elastic (Winkler) foundation properties
+
+use serde::{Deserialize, Serialize};
+
+/// A Winkler elastic foundation supporting an element
+///
+/// The foundation acts on the element's local -Z face, resisting local Z
+/// deflection with a distributed spring of stiffness `modulus`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ElasticFoundation {
+    /// Subgrade modulus (pressure per unit deflection)
+    pub modulus: f64,
+    /// Springs resist compression only and release where the element lifts off
+    /// (iterated in nonlinear analysis; other analyses treat the springs as linear)
+    pub compression_only: bool,
+}
+
+impl ElasticFoundation {
+    /// Create a foundation acting in both tension and compression
+    pub fn new(modulus: f64) -> Self {
+        Self {
+            modulus,
+            compression_only: false,
+        }
+    }
+
+    /// Make the foundation compression-only
+    pub fn compression_only(mut self) -> Self {
+        self.compression_only = true;
+        self
+    }
+}
@@ -0,0 +1,98 @@
+//! Gap and hook elements - two-node axial contact and slack-tie springs
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Behaviour of a gap/hook element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GapKind {
+    /// Compression-only contact that closes once the nodes approach by the opening
+    Gap,
+    /// Tension-only tie that engages once the nodes separate by the opening
+    Hook,
+}
+
+/// A two-node gap or hook element acting along the line between its nodes
+///
+/// The element carries no force until its opening is taken up, then acts as
+/// an axial spring. Its state is iterated in nonlinear analysis, so it models
+/// pounding between structures, bearing contact and slack ties.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gap {
+    /// i-node name
+    pub i_node: String,
+    /// j-node name
+    pub j_node: String,
+    /// Gap (compression) or hook (tension)
+    pub kind: GapKind,
+    /// Relative axial movement before the element engages (>= 0)
+    pub opening: f64,
+    /// Axial stiffness once engaged (force/length)
+    pub stiffness: f64,
+
+    /// Axial force by load combination (positive = tension)
+    #[serde(skip)]
+    pub(crate) forces: HashMap<String, f64>,
+}
+
+impl Gap {
+    /// Create a compression-only gap element
+    pub fn contact(i_node: &str, j_node: &str, opening: f64, stiffness: f64) -> Self {
+        Self::new(i_node, j_node, GapKind::Gap, opening, stiffness)
+    }
+
+    /// Create a tension-only hook element
+    pub fn hook(i_node: &str, j_node: &str, opening: f64, stiffness: f64) -> Self {
+        Self::new(i_node, j_node, GapKind::Hook, opening, stiffness)
+    }
+
+    fn new(i_node: &str, j_node: &str, kind: GapKind, opening: f64, stiffness: f64) -> Self {
+        Self {
+            i_node: i_node.to_string(),
+            j_node: j_node.to_string(),
+            kind,
+            opening,
+            stiffness,
+            forces: HashMap::new(),
+        }
+    }
+
+    /// Whether the element is engaged at the given elongation (j away from i)
+    pub fn is_engaged(&self, elongation: f64) -> bool {
+        match self.kind {
+            GapKind::Gap => elongation < -self.opening,
+            GapKind::Hook => elongation > self.opening,
+        }
+    }
+
+    /// Axial force at the given elongation when engaged (positive = tension)
+    pub fn engaged_force(&self, elongation: f64) -> f64 {
+        match self.kind {
+            GapKind::Gap => self.stiffness * (elongation + self.opening),
+            GapKind::Hook => self.stiffness * (elongation - self.opening),
+        }
+    }
+
+    /// Get the axial force for a load combination
+    pub fn force(&self, combo_name: &str) -> Option<f64> {
+        self.forces.get(combo_name).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_and_hook_engagement() {
+        let gap = Gap::contact("A", "B", 0.01, 1e6);
+        assert!(!gap.is_engaged(-0.005));
+        assert!(gap.is_engaged(-0.02));
+        assert!((gap.engaged_force(-0.02) + 1e4).abs() < 1e-6);
+
+        let hook = Gap::hook("A", "B", 0.01, 1e6);
+        assert!(!hook.is_engaged(-0.02));
+        assert!(hook.is_engaged(0.02));
+        assert!((hook.engaged_force(0.02) - 1e4).abs() < 1e-6);
+    }
+}
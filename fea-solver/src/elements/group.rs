@@ -0,0 +1,66 @@
+//! Groups - named selection sets of model entities
+
+use serde::{Deserialize, Serialize};
+
+/// A named selection of nodes, members and plates
+///
+/// Groups are stored with the model and can be used to apply loads or
+/// property changes to many entities at once and to filter results.
+/// Members may be physical members and plates may be quads.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Group {
+    /// Node names
+    #[serde(default)]
+    pub nodes: Vec<String>,
+    /// Member names
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// Plate and quad names
+    #[serde(default)]
+    pub plates: Vec<String>,
+}
+
+impl Group {
+    /// Create an empty group
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a group of nodes
+    pub fn of_nodes(names: &[&str]) -> Self {
+        Self::new().with_nodes(names)
+    }
+
+    /// Create a group of members
+    pub fn of_members(names: &[&str]) -> Self {
+        Self::new().with_members(names)
+    }
+
+    /// Create a group of plates or quads
+    pub fn of_plates(names: &[&str]) -> Self {
+        Self::new().with_plates(names)
+    }
+
+    /// Add nodes to the group
+    pub fn with_nodes(mut self, names: &[&str]) -> Self {
+        self.nodes.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Add members to the group
+    pub fn with_members(mut self, names: &[&str]) -> Self {
+        self.members.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Add plates or quads to the group
+    pub fn with_plates(mut self, names: &[&str]) -> Self {
+        self.plates.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Check whether the group selects nothing
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.members.is_empty() && self.plates.is_empty()
+    }
+}
@@ -0,0 +1,153 @@
+//! Link elements - two-node dampers and isolators for time-history analysis
+
+use serde::{Deserialize, Serialize};
+
+/// Force-deformation behaviour of a link element
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LinkKind {
+    /// Viscous damper `F = c sign(v) |v|^alpha` (alpha = 1 is linear)
+    ViscousDamper {
+        /// Damping coefficient
+        c: f64,
+        /// Velocity exponent
+        alpha: f64,
+    },
+    /// Bilinear hysteretic isolator with kinematic hardening
+    BilinearIsolator {
+        /// Initial (elastic) stiffness
+        k1: f64,
+        /// Post-yield stiffness
+        k2: f64,
+        /// Yield force
+        fy: f64,
+    },
+}
+
+/// A two-node link acting along a single direction
+///
+/// The link deformation is the relative displacement `u_j - u_i` projected
+/// onto its direction. Links only act in time-history analysis, where their
+/// force, deformation and dissipated energy are recorded at every step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Link {
+    /// i-node name
+    pub i_node: String,
+    /// j-node name
+    pub j_node: String,
+    /// Force-deformation behaviour
+    pub kind: LinkKind,
+    /// Global direction of action (None = from the i-node to the j-node)
+    pub direction: Option<[f64; 3]>,
+}
+
+impl Link {
+    /// Create a viscous damper
+    pub fn viscous_damper(i_node: &str, j_node: &str, c: f64, alpha: f64) -> Self {
+        Self::new(i_node, j_node, LinkKind::ViscousDamper { c, alpha })
+    }
+
+    /// Create a bilinear isolator from its elastic stiffness, post-yield stiffness and yield force
+    pub fn bilinear_isolator(i_node: &str, j_node: &str, k1: f64, k2: f64, fy: f64) -> Self {
+        Self::new(i_node, j_node, LinkKind::BilinearIsolator { k1, k2, fy })
+    }
+
+    fn new(i_node: &str, j_node: &str, kind: LinkKind) -> Self {
+        Self {
+            i_node: i_node.to_string(),
+            j_node: j_node.to_string(),
+            kind,
+            direction: None,
+        }
+    }
+
+    /// Set the global direction of action (e.g. horizontal shear of an isolator)
+    pub fn with_direction(mut self, direction: [f64; 3]) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Link force and tangent for a trial deformation and velocity
+    ///
+    /// Returns `(force, stiffness, damping)` where the tangents are
+    /// `dF/du` and `dF/dv`. `state` is the committed isolator plastic
+    /// deformation and is not modified.
+    pub fn response(&self, deformation: f64, velocity: f64, state: f64) -> (f64, f64, f64) {
+        match self.kind {
+            LinkKind::ViscousDamper { c, alpha } => {
+                // Floor the velocity so the tangent stays finite for alpha < 1
+                let v = velocity.abs().max(1e-6);
+                let force = c * velocity.signum() * velocity.abs().powf(alpha);
+                (force, 0.0, c * alpha * v.powf(alpha - 1.0))
+            }
+            LinkKind::BilinearIsolator { k1, k2, fy } => {
+                let (force, _, yielding) = isolator_hysteresis(k1, k2, fy, deformation, state);
+                (force, if yielding { k2 } else { k1 }, 0.0)
+            }
+        }
+    }
+
+    /// Updated isolator plastic deformation after converging on `deformation`
+    pub fn committed_state(&self, deformation: f64, state: f64) -> f64 {
+        match self.kind {
+            LinkKind::ViscousDamper { .. } => state,
+            LinkKind::BilinearIsolator { k1, k2, fy } => isolator_hysteresis(k1, k2, fy, deformation, state).1,
+        }
+    }
+}
+
+/// Bilinear hysteresis as a linear spring `k2` in parallel with an
+/// elastic-perfectly-plastic spring `k1 - k2` that yields at `fy (1 - k2 / k1)`
+///
+/// Returns (force, plastic deformation, yielding).
+fn isolator_hysteresis(k1: f64, k2: f64, fy: f64, u: f64, up: f64) -> (f64, f64, bool) {
+    let kh = k1 - k2;
+    let capacity = fy * (1.0 - k2 / k1);
+    let trial = kh * (u - up);
+
+    if trial.abs() <= capacity {
+        (k2 * u + trial, up, false)
+    } else {
+        let hysteretic = capacity * trial.signum();
+        (k2 * u + hysteretic, u - hysteretic / kh, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::LinkHistory;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_isolator_hysteresis_loop_energy() {
+        // One full cycle to +/- 4 uy: loop area = 4 q (u_max - uy)
+        let (k1, k2, fy) = (1e6, 1e5, 1e4);
+        let link = Link::bilinear_isolator("A", "B", k1, k2, fy);
+        let u_max = 4.0 * fy / k1;
+
+        let mut history = LinkHistory::default();
+        let mut state = 0.0;
+        let n = 400;
+        // Start at the peak so the recorded cycle is the steady-state loop
+        for step in 0..=n {
+            let u = u_max * (2.0 * std::f64::consts::PI * step as f64 / n as f64).cos();
+            let (force, _, _) = link.response(u, 0.0, state);
+            state = link.committed_state(u, state);
+            history.deformation.push(u);
+            history.force.push(force);
+        }
+
+        let q = fy * (1.0 - k2 / k1);
+        let uy = fy / k1;
+        assert_relative_eq!(history.dissipated_energy(), 4.0 * q * (u_max - uy), max_relative = 0.05);
+    }
+
+    #[test]
+    fn test_nonlinear_damper_force() {
+        let damper = Link::viscous_damper("A", "B", 2e5, 0.5);
+        let (force, k, c) = damper.response(0.0, -0.04, 0.0);
+        assert_relative_eq!(force, -2e5 * 0.2, max_relative = 1e-12);
+        assert_eq!(k, 0.0);
+        assert_relative_eq!(c, 2e5 * 0.5 / 0.2, max_relative = 1e-12);
+    }
+}
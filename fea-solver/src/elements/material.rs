@@ -15,6 +15,9 @@ pub struct Material {
     pub rho: f64,
     /// Yield strength (optional) in Pa
     pub fy: Option<f64>,
+    /// Coefficient of thermal expansion in 1/°C
+    #[serde(default)]
+    pub alpha: f64,
 }
 
 impl Material {
@@ -26,6 +29,7 @@ impl Material {
             nu,
             rho,
             fy: None,
+            alpha: 0.0,
         }
     }
 
@@ -35,6 +39,12 @@ impl Material {
         self
     }
 
+    /// Set the coefficient of thermal expansion
+    pub fn with_thermal_expansion(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
     /// Create a new isotropic material from E and nu
     /// G is calculated as E / (2 * (1 + nu))
     pub fn isotropic(e: f64, nu: f64, rho: f64) -> Self {
@@ -50,6 +60,7 @@ impl Material {
             nu: 0.3,
             rho: 7850.0,   // kg/m³
             fy: Some(250e6), // 250 MPa
+            alpha: 12e-6,
         }
     }
 
@@ -66,6 +77,7 @@ impl Material {
             nu: 0.2,
             rho: 2400.0,   // kg/m³
             fy: None,
+            alpha: 10e-6,
         }
     }
 
@@ -77,6 +89,7 @@ impl Material {
             nu: 0.33,
             rho: 2700.0,   // kg/m³
             fy: Some(276e6), // 276 MPa
+            alpha: 23e-6,
         }
     }
 }
@@ -2,6 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[cfg(feature = "attributes")]
+use std::collections::BTreeMap;
+
+use crate::results::ResultStore;
 
 /// End releases for a member (allowing specific DOFs to rotate/translate freely)
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -51,6 +55,60 @@ impl MemberReleases {
     }
 }
 
+/// Shape of a member's initial out-of-straightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImperfectionShape {
+    /// Half sine wave (bow imperfection)
+    #[default]
+    Sine,
+    /// Parabola (fabricated camber)
+    Parabolic,
+}
+
+/// Initial bow or camber of a member, as mid-length offsets from the chord
+///
+/// In P-Delta analysis the imperfection is replaced by the equivalent loads
+/// of the member's axial force acting on the offset shape (`w = -N v0''`),
+/// updated with the axial force at every iteration. Other analysis types
+/// treat the member as straight. The segments of a physical member each
+/// carry their part of the physical member's imperfection.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Imperfection {
+    /// Mid-length offset in the local y direction
+    pub y: f64,
+    /// Mid-length offset in the local z direction
+    pub z: f64,
+    /// Offset profile along the member
+    #[serde(default)]
+    pub shape: ImperfectionShape,
+}
+
+impl Imperfection {
+    /// Sinusoidal bow with the given mid-length amplitudes
+    pub fn bow(y: f64, z: f64) -> Self {
+        Self { y, z, shape: ImperfectionShape::Sine }
+    }
+
+    /// Parabolic camber with the given mid-length offsets
+    pub fn camber(y: f64, z: f64) -> Self {
+        Self { y, z, shape: ImperfectionShape::Parabolic }
+    }
+
+    /// Slope and curvature of the offset profile at `x` along a member of
+    /// `length`, per unit mid-length offset
+    pub(crate) fn profile(&self, x: f64, length: f64) -> (f64, f64) {
+        match self.shape {
+            ImperfectionShape::Sine => {
+                let k = std::f64::consts::PI / length;
+                (k * (k * x).cos(), -k * k * (k * x).sin())
+            }
+            ImperfectionShape::Parabolic => {
+                (4.0 * (length - 2.0 * x) / length.powi(2), -8.0 / length.powi(2))
+            }
+        }
+    }
+}
+
 /// A 3D frame member (beam or column)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Member {
@@ -66,26 +124,28 @@ pub struct Member {
     pub rotation: f64,
     /// End releases
     pub releases: MemberReleases,
-    /// Tension-only flag (for braces)
+    /// Tension-only flag (for braces); honoured by nonlinear analysis
     pub tension_only: bool,
-    /// Compression-only flag
+    /// Compression-only flag; honoured by nonlinear analysis
     pub compression_only: bool,
+    /// Elastic foundation modulus in the local y direction (force/length^2)
+    #[serde(default)]
+    pub foundation_modulus: f64,
+    /// Initial bow or camber for second-order analysis
+    #[serde(default)]
+    pub imperfection: Option<Imperfection>,
+    /// User attributes, e.g. GUIDs, storey names or design parameters
+    #[cfg(feature = "attributes")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, serde_json::Value>,
     
     /// Calculated length
     #[serde(skip)]
     pub(crate) length: Option<f64>,
     
-    /// Local end forces by load combination [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, Fy_j, Fz_j, Mx_j, My_j, Mz_j]
-    #[serde(skip)]
-    pub(crate) local_forces: HashMap<String, [f64; 12]>,
-    
-    /// Global end forces by load combination
-    #[serde(skip)]
-    pub(crate) global_forces: HashMap<String, [f64; 12]>,
-    
-    /// Local displacements by load combination
+    /// Compressive axial force driving the imperfection loads, by load combination
     #[serde(skip)]
-    pub(crate) local_displacements: HashMap<String, [f64; 12]>,
+    pub(crate) imperfection_axial: HashMap<String, f64>,
 }
 
 impl Member {
@@ -100,10 +160,12 @@ impl Member {
             releases: MemberReleases::none(),
             tension_only: false,
             compression_only: false,
+            foundation_modulus: 0.0,
+            imperfection: None,
+            #[cfg(feature = "attributes")]
+            attributes: BTreeMap::new(),
             length: None,
-            local_forces: HashMap::new(),
-            global_forces: HashMap::new(),
-            local_displacements: HashMap::new(),
+            imperfection_axial: HashMap::new(),
         }
     }
 
@@ -119,6 +181,28 @@ impl Member {
         self
     }
 
+    /// Rest the member on an elastic foundation acting in its local y direction
+    ///
+    /// `modulus` is the spring stiffness per unit length (subgrade modulus
+    /// times bearing width), e.g. for grade beams and strip footings.
+    pub fn with_foundation_modulus(mut self, modulus: f64) -> Self {
+        self.foundation_modulus = modulus;
+        self
+    }
+
+    /// Give the member an initial bow or camber for P-Delta analysis
+    pub fn with_imperfection(mut self, imperfection: Imperfection) -> Self {
+        self.imperfection = Some(imperfection);
+        self
+    }
+
+    /// Attach a user attribute to the member
+    #[cfg(feature = "attributes")]
+    pub fn with_attribute(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.attributes.insert(key.to_string(), value.into());
+        self
+    }
+
     /// Set as tension-only member
     pub fn tension_only(mut self) -> Self {
         self.tension_only = true;
@@ -133,93 +217,100 @@ impl Member {
         self
     }
 
+    /// A colinear piece of this member between two nodes on its line
+    ///
+    /// End releases are kept only at ends shared with the whole member. The
+    /// bow imperfection, which is defined over the whole length, is dropped.
+    pub(crate) fn piece(&self, i_node: &str, j_node: &str, first: bool, last: bool) -> Self {
+        let mut piece = Self::new(i_node, j_node, &self.material, &self.section);
+        piece.rotation = self.rotation;
+        piece.tension_only = self.tension_only;
+        piece.compression_only = self.compression_only;
+        piece.foundation_modulus = self.foundation_modulus;
+        #[cfg(feature = "attributes")]
+        piece.attributes.clone_from(&self.attributes);
+        piece.releases = MemberReleases {
+            i_node: if first { self.releases.i_node } else { [false; 6] },
+            j_node: if last { self.releases.j_node } else { [false; 6] },
+        };
+        piece
+    }
+
     /// Get the member length
     pub fn length(&self) -> Option<f64> {
         self.length
     }
 
     /// Get local forces for a load combination
-    /// Returns [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, Fy_j, Fz_j, Mx_j, My_j, Mz_j]
-    pub fn local_force(&self, combo_name: &str) -> Option<[f64; 12]> {
-        self.local_forces.get(combo_name).copied()
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::local_force`")]
+    pub fn local_force(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<[f64; 12]> {
+        results.local_force(name, combo_name)
     }
 
     /// Get axial force at a position along the member
-    /// 
-    /// # Arguments
-    /// * `_x` - Distance from i-node (reserved for future use with distributed loads)
-    /// * `combo_name` - Load combination name
-    pub fn axial(&self, _x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        // Axial force is constant (for now, without distributed loads)
-        // P = -Fx_i (positive tension)
-        Some(-forces[0])
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::axial`")]
+    pub fn axial(&self, results: &ResultStore, name: &str, _x: f64, combo_name: &str) -> Option<f64> {
+        results.axial(name, combo_name)
     }
 
     /// Get shear force in local y direction at position x
-    pub fn shear_y(&self, _x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        // Vy = -Fy_i (constant without distributed loads)
-        Some(-forces[1])
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::shear_y`")]
+    pub fn shear_y(&self, results: &ResultStore, name: &str, _x: f64, combo_name: &str) -> Option<f64> {
+        results.shear_y(name, combo_name)
     }
 
     /// Get shear force in local z direction at position x
-    pub fn shear_z(&self, x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        let _ = x;
-        Some(-forces[2])
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::shear_z`")]
+    pub fn shear_z(&self, results: &ResultStore, name: &str, _x: f64, combo_name: &str) -> Option<f64> {
+        results.shear_z(name, combo_name)
     }
 
     /// Get bending moment about local y axis at position x
-    pub fn moment_y(&self, x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        // My = My_i + Fz_i * x
-        Some(forces[4] + forces[2] * x)
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::moment_y`")]
+    pub fn moment_y(&self, results: &ResultStore, name: &str, x: f64, combo_name: &str) -> Option<f64> {
+        results.moment_y(name, x, combo_name)
     }
 
     /// Get bending moment about local z axis at position x
-    pub fn moment_z(&self, x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        // Mz = Mz_i - Fy_i * x
-        Some(forces[5] - forces[1] * x)
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::moment_z`")]
+    pub fn moment_z(&self, results: &ResultStore, name: &str, x: f64, combo_name: &str) -> Option<f64> {
+        results.moment_z(name, x, combo_name)
     }
 
     /// Get torsion at position x
-    pub fn torsion(&self, x: f64, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        let _ = x;
-        // Torsion is constant
-        Some(-forces[3])
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::torsion`")]
+    pub fn torsion(&self, results: &ResultStore, name: &str, _x: f64, combo_name: &str) -> Option<f64> {
+        results.torsion(name, combo_name)
     }
 
     /// Get maximum absolute axial force
-    pub fn max_axial(&self, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        Some(forces[0].abs().max(forces[6].abs()))
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::max_axial`")]
+    pub fn max_axial(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<f64> {
+        results.max_axial(name, combo_name)
     }
 
     /// Get maximum absolute shear force in y
-    pub fn max_shear_y(&self, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        Some(forces[1].abs().max(forces[7].abs()))
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::max_shear_y`")]
+    pub fn max_shear_y(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<f64> {
+        results.max_shear_y(name, combo_name)
     }
 
     /// Get maximum absolute shear force in z
-    pub fn max_shear_z(&self, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        Some(forces[2].abs().max(forces[8].abs()))
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::max_shear_z`")]
+    pub fn max_shear_z(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<f64> {
+        results.max_shear_z(name, combo_name)
     }
 
     /// Get maximum absolute moment about y
-    pub fn max_moment_y(&self, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        Some(forces[4].abs().max(forces[10].abs()))
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::max_moment_y`")]
+    pub fn max_moment_y(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<f64> {
+        results.max_moment_y(name, combo_name)
     }
 
     /// Get maximum absolute moment about z
-    pub fn max_moment_z(&self, combo_name: &str) -> Option<f64> {
-        let forces = self.local_forces.get(combo_name)?;
-        Some(forces[5].abs().max(forces[11].abs()))
+    #[deprecated(note = "member results are kept in the model's `ResultStore`; use `ResultStore::max_moment_z`")]
+    pub fn max_moment_z(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<f64> {
+        results.max_moment_z(name, combo_name)
     }
 }
 
@@ -1,17 +1,33 @@
 //! Structural elements module
 
+mod foundation;
+mod gap;
+mod group;
+mod link;
 mod material;
 mod member;
 mod node;
+mod physical_member;
 mod plate;
 mod quad;
+mod rigid_link;
 mod section;
+mod story;
 mod support;
+mod tetra;
 
+pub use foundation::ElasticFoundation;
+pub use gap::{Gap, GapKind};
+pub use group::Group;
+pub use link::{Link, LinkKind};
 pub use material::Material;
-pub use member::{Member, MemberReleases};
+pub use member::{Imperfection, ImperfectionShape, Member, MemberReleases};
 pub use node::Node;
-pub use plate::Plate;
-pub use quad::Quad;
-pub use section::Section;
-pub use support::Support;
+pub use physical_member::PhysicalMember;
+pub use plate::{Plate, PlateStresses};
+pub use quad::{Quad, QuadQuality, QuadStresses};
+pub use rigid_link::RigidLink;
+pub use section::{Section, SectionShape};
+pub use story::StoryLevel;
+pub use support::{OneWay, Support};
+pub use tetra::Tetra;
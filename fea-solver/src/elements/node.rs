@@ -1,7 +1,10 @@
 //! Node element - represents a point in 3D space
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+#[cfg(feature = "attributes")]
+use std::collections::BTreeMap;
+
+use crate::results::ResultStore;
 
 /// A 3D node in the finite element model
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,18 +15,14 @@ pub struct Node {
     pub y: f64,
     /// Z coordinate
     pub z: f64,
+    /// User attributes, e.g. GUIDs, storey names or design parameters
+    #[cfg(feature = "attributes")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, serde_json::Value>,
     
     /// Internal ID assigned during analysis
     #[serde(skip)]
     pub(crate) id: Option<usize>,
-    
-    /// Displacement results by load combination
-    #[serde(skip)]
-    pub(crate) displacements: HashMap<String, [f64; 6]>,
-    
-    /// Reaction forces by load combination
-    #[serde(skip)]
-    pub(crate) reactions: HashMap<String, [f64; 6]>,
 }
 
 impl Node {
@@ -33,12 +32,19 @@ impl Node {
             x,
             y,
             z,
+            #[cfg(feature = "attributes")]
+            attributes: BTreeMap::new(),
             id: None,
-            displacements: HashMap::new(),
-            reactions: HashMap::new(),
         }
     }
 
+    /// Attach a user attribute to the node
+    #[cfg(feature = "attributes")]
+    pub fn with_attribute(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.attributes.insert(key.to_string(), value.into());
+        self
+    }
+
     /// Get the coordinates as an array
     pub fn coords(&self) -> [f64; 3] {
         [self.x, self.y, self.z]
@@ -54,14 +60,16 @@ impl Node {
 
     /// Get displacement for a load combination
     /// Returns [DX, DY, DZ, RX, RY, RZ]
-    pub fn displacement(&self, combo_name: &str) -> Option<[f64; 6]> {
-        self.displacements.get(combo_name).copied()
+    #[deprecated(note = "node results are kept in the model's `ResultStore`; use `ResultStore::displacement`")]
+    pub fn displacement(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<[f64; 6]> {
+        results.displacement(name, combo_name)
     }
 
     /// Get reactions for a load combination
     /// Returns [FX, FY, FZ, MX, MY, MZ]
-    pub fn reaction(&self, combo_name: &str) -> Option<[f64; 6]> {
-        self.reactions.get(combo_name).copied()
+    #[deprecated(note = "node results are kept in the model's `ResultStore`; use `ResultStore::reaction`")]
+    pub fn reaction(&self, results: &ResultStore, name: &str, combo_name: &str) -> Option<[f64; 6]> {
+        results.reaction(name, combo_name)
     }
 }
 
@@ -89,4 +97,23 @@ mod tests {
         let n2 = Node::new(3.0, 4.0, 0.0);
         assert!((n1.distance_to(&n2) - 5.0).abs() < 1e-10);
     }
+
+    #[cfg(feature = "attributes")]
+    #[test]
+    fn test_node_attributes_round_trip() {
+        let node = Node::new(0.0, 3.5, 0.0)
+            .with_attribute("guid", "2O2Fr$t4X7Zf8NOew3FLOH")
+            .with_attribute("storey", 2);
+        let json = serde_json::to_string(&node).unwrap();
+        let restored: Node = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.attributes["storey"], 2);
+        assert_eq!(restored.attributes, node.attributes);
+        // Attributes serialize in key order, whatever order they were set in
+        assert!(json.contains(r#""attributes":{"guid":"2O2Fr$t4X7Zf8NOew3FLOH","storey":2}"#));
+        let reordered = Node::new(0.0, 3.5, 0.0).with_attribute("storey", 2).with_attribute("guid", "2O2Fr$t4X7Zf8NOew3FLOH");
+        assert_eq!(serde_json::to_string(&reordered).unwrap(), json);
+        
+        // Nodes without attributes serialize as before
+        assert!(!serde_json::to_string(&Node::new(0.0, 0.0, 0.0)).unwrap().contains("attributes"));
+    }
 }
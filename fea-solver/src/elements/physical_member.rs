@@ -0,0 +1,84 @@
+//! Physical member - a member line split into colinear analysis segments
+
+use serde::{Deserialize, Serialize};
+
+use super::Member;
+
+/// A physical member spanning one or more analysis segments
+///
+/// Before each analysis the member line is split at every node lying on it
+/// and at any requested positions, and a colinear sub-member is generated
+/// for each segment. The physical member's end releases apply to its outer
+/// ends only. Loads added under the physical member's name are shared out to
+/// the segments they overlap, and forces are reported along the whole span.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhysicalMember {
+    /// End nodes, material, section, rotation and end releases
+    pub member: Member,
+    /// Requested split positions, as distances from the i-node
+    #[serde(default)]
+    pub splits: Vec<f64>,
+    /// Generated segments as (sub-member name, start, end) distances from the i-node
+    #[serde(default)]
+    pub(crate) segments: Vec<(String, f64, f64)>,
+    /// Nodes generated at requested split positions
+    #[serde(default)]
+    pub(crate) generated_nodes: Vec<String>,
+}
+
+impl PhysicalMember {
+    /// Create a physical member from a member template
+    pub fn new(member: Member) -> Self {
+        Self {
+            member,
+            splits: Vec::new(),
+            segments: Vec::new(),
+            generated_nodes: Vec::new(),
+        }
+    }
+
+    /// Request additional splits at distances from the i-node
+    pub fn with_splits(mut self, splits: Vec<f64>) -> Self {
+        self.splits = splits;
+        self
+    }
+
+    /// Generated segments as (sub-member name, start, end) distances from the i-node
+    pub fn segments(&self) -> &[(String, f64, f64)] {
+        &self.segments
+    }
+
+    /// Total length, once the member has been split for analysis
+    pub fn length(&self) -> Option<f64> {
+        self.segments.last().map(|(_, _, end)| *end)
+    }
+
+    /// Segment containing a distance `x` from the i-node, with the distance into it
+    pub fn segment_at(&self, x: f64) -> Option<(&str, f64)> {
+        let last = self.segments.last()?;
+        if x < -1e-9 || x > last.2 + 1e-9 {
+            return None;
+        }
+        let (name, start, _) = self.segments.iter()
+            .find(|(_, _, end)| x <= *end)
+            .unwrap_or(last);
+        Some((name, (x - start).max(0.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_lookup() {
+        let mut beam = PhysicalMember::new(Member::new("A", "B", "Steel", "W"));
+        assert!(beam.segment_at(1.0).is_none());
+
+        beam.segments = vec![("B1#1".into(), 0.0, 2.0), ("B1#2".into(), 2.0, 5.0)];
+        assert_eq!(beam.segment_at(1.5), Some(("B1#1", 1.5)));
+        assert_eq!(beam.segment_at(2.0), Some(("B1#1", 2.0)));
+        assert_eq!(beam.segment_at(4.0), Some(("B1#2", 2.0)));
+        assert!(beam.segment_at(5.5).is_none());
+    }
+}
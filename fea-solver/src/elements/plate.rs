@@ -2,8 +2,11 @@
 //! Supports Kirchhoff, Mindlin, and DKMQ formulations
 
 use crate::math::plate::PlateFormulation;
+use super::foundation::ElasticFoundation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[cfg(feature = "attributes")]
+use std::collections::BTreeMap;
 
 /// A rectangular plate element for shell analysis
 /// Uses a 4-node element with 6 DOFs per node (DX, DY, DZ, RX, RY, RZ)
@@ -25,8 +28,15 @@ pub struct Plate {
     pub kx_mod: f64,
     /// Local y stiffness modifier
     pub ky_mod: f64,
+    /// Winkler foundation under the element
+    #[serde(default)]
+    pub foundation: Option<ElasticFoundation>,
     /// Plate bending formulation (Kirchhoff, Mindlin, or DKMQ)
     pub formulation: PlateFormulation,
+    /// User attributes, e.g. GUIDs, storey names or design parameters
+    #[cfg(feature = "attributes")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, serde_json::Value>,
     
     /// Calculated width (j to i distance)
     #[serde(skip)]
@@ -89,7 +99,10 @@ impl Plate {
             material: material.to_string(),
             kx_mod: 1.0,
             ky_mod: 1.0,
+            foundation: None,
             formulation: PlateFormulation::Kirchhoff,
+            #[cfg(feature = "attributes")]
+            attributes: BTreeMap::new(),
             width: None,
             height: None,
             forces: HashMap::new(),
@@ -98,6 +111,13 @@ impl Plate {
         }
     }
 
+    /// Attach a user attribute to the plate
+    #[cfg(feature = "attributes")]
+    pub fn with_attribute(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.attributes.insert(key.to_string(), value.into());
+        self
+    }
+
     /// Set stiffness modifiers
     pub fn with_modifiers(mut self, kx_mod: f64, ky_mod: f64) -> Self {
         self.kx_mod = kx_mod;
@@ -105,6 +125,18 @@ impl Plate {
         self
     }
 
+    /// Rest the element on a Winkler foundation with the given subgrade modulus
+    pub fn with_subgrade_modulus(mut self, modulus: f64) -> Self {
+        self.foundation = Some(ElasticFoundation::new(modulus));
+        self
+    }
+
+    /// Rest the element on a Winkler foundation
+    pub fn with_foundation(mut self, foundation: ElasticFoundation) -> Self {
+        self.foundation = Some(foundation);
+        self
+    }
+
     /// Set plate bending formulation
     pub fn with_formulation(mut self, formulation: PlateFormulation) -> Self {
         self.formulation = formulation;
@@ -131,10 +163,14 @@ impl Plate {
 
     /// Get von Mises stress at plate center
     pub fn von_mises(&self, combo_name: &str) -> Option<f64> {
-        let s = self.stresses.get(combo_name)?;
-        // Von Mises for plane stress
-        let vm = (s.sx.powi(2) - s.sx * s.sy + s.sy.powi(2) + 3.0 * s.txy.powi(2)).sqrt();
-        Some(vm)
+        self.stresses.get(combo_name).map(PlateStresses::von_mises)
+    }
+}
+
+impl PlateStresses {
+    /// Von Mises stress of the plane stress state (sx, sy, txy)
+    pub fn von_mises(&self) -> f64 {
+        (self.sx.powi(2) - self.sx * self.sy + self.sy.powi(2) + 3.0 * self.txy.powi(2)).sqrt()
     }
 }
 
@@ -2,7 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[cfg(feature = "attributes")]
+use std::collections::BTreeMap;
+use super::foundation::ElasticFoundation;
 use super::plate::PlateStresses;
+use crate::math;
 
 /// A general quadrilateral shell element
 /// Uses MITC4 formulation for better handling of distorted elements
@@ -25,6 +29,13 @@ pub struct Quad {
     pub kx_mod: f64,
     /// Local y stiffness modifier
     pub ky_mod: f64,
+    /// Winkler foundation under the element
+    #[serde(default)]
+    pub foundation: Option<ElasticFoundation>,
+    /// User attributes, e.g. GUIDs, storey names or design parameters
+    #[cfg(feature = "attributes")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, serde_json::Value>,
     
     /// Nodal forces by load combination
     #[serde(skip)]
@@ -73,12 +84,22 @@ impl Quad {
             material: material.to_string(),
             kx_mod: 1.0,
             ky_mod: 1.0,
+            foundation: None,
+            #[cfg(feature = "attributes")]
+            attributes: BTreeMap::new(),
             forces: HashMap::new(),
             displacements: HashMap::new(),
             stresses: HashMap::new(),
         }
     }
 
+    /// Attach a user attribute to the quad
+    #[cfg(feature = "attributes")]
+    pub fn with_attribute(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.attributes.insert(key.to_string(), value.into());
+        self
+    }
+
     /// Set stiffness modifiers
     pub fn with_modifiers(mut self, kx_mod: f64, ky_mod: f64) -> Self {
         self.kx_mod = kx_mod;
@@ -86,6 +107,18 @@ impl Quad {
         self
     }
 
+    /// Rest the element on a Winkler foundation with the given subgrade modulus
+    pub fn with_subgrade_modulus(mut self, modulus: f64) -> Self {
+        self.foundation = Some(ElasticFoundation::new(modulus));
+        self
+    }
+
+    /// Rest the element on a Winkler foundation
+    pub fn with_foundation(mut self, foundation: ElasticFoundation) -> Self {
+        self.foundation = Some(foundation);
+        self
+    }
+
     /// Get nodal forces for a load combination
     pub fn nodal_forces(&self, combo_name: &str) -> Option<[f64; 24]> {
         self.forces.get(combo_name).copied()
@@ -100,19 +133,76 @@ impl Quad {
     pub fn max_von_mises(&self, combo_name: &str) -> Option<f64> {
         let s = self.stresses.get(combo_name)?;
         
-        let vm_center = von_mises_plane_stress(&s.center);
-        let vm_i = von_mises_plane_stress(&s.i_corner);
-        let vm_j = von_mises_plane_stress(&s.j_corner);
-        let vm_m = von_mises_plane_stress(&s.m_corner);
-        let vm_n = von_mises_plane_stress(&s.n_corner);
+        let vm_center = s.center.von_mises();
+        let vm_i = s.i_corner.von_mises();
+        let vm_j = s.j_corner.von_mises();
+        let vm_m = s.m_corner.von_mises();
+        let vm_n = s.n_corner.von_mises();
         
         Some(vm_center.max(vm_i).max(vm_j).max(vm_m).max(vm_n))
     }
 }
 
-/// Calculate von Mises stress for plane stress state
-fn von_mises_plane_stress(s: &PlateStresses) -> f64 {
-    (s.sx.powi(2) - s.sx * s.sy + s.sy.powi(2) + 3.0 * s.txy.powi(2)).sqrt()
+/// Shape quality metrics of a quad element
+///
+/// The element stiffness assumes a flat rectangle, so warped, elongated or
+/// skewed quads lose accuracy. Warping is handled by projecting the element
+/// onto its mean plane, but the other metrics are only reported.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuadQuality {
+    /// Offset of the corners from the mean plane, relative to the mean side length
+    pub warping: f64,
+    /// Longest to shortest side length
+    pub aspect_ratio: f64,
+    /// Deviation from 90 degrees of the angle between the lines joining
+    /// opposite side midpoints (degrees)
+    pub skew: f64,
+}
+
+impl QuadQuality {
+    /// Warping above which a warning is raised
+    pub const WARPING_LIMIT: f64 = 0.01;
+    /// Aspect ratio above which a warning is raised
+    pub const ASPECT_RATIO_LIMIT: f64 = 10.0;
+    /// Skew (degrees) above which a warning is raised
+    pub const SKEW_LIMIT: f64 = 45.0;
+
+    /// Measure a quad from its corner coordinates (i, j, m, n)
+    pub fn from_corners(corners: &[[f64; 3]; 4]) -> Self {
+        let [i, j, m, n] = corners.map(math::Vec3::from);
+        let sides = [j - i, m - j, n - m, i - n].map(|side| side.norm());
+        let mean_side = sides.iter().sum::<f64>() / 4.0;
+        let (_, offsets) = math::quad_mean_plane(corners);
+        let offset = offsets.iter().fold(0.0_f64, |max, r| max.max(math::Vec3::from(*r).norm()));
+        
+        let along = (j + m - i - n) / 2.0;
+        let across = (m + n - i - j) / 2.0;
+        let cos = along.dot(&across) / (along.norm() * across.norm());
+        
+        Self {
+            warping: offset / mean_side,
+            aspect_ratio: sides.iter().cloned().fold(0.0, f64::max) / sides.iter().cloned().fold(f64::INFINITY, f64::min),
+            skew: cos.abs().clamp(0.0, 1.0).asin().to_degrees(),
+        }
+    }
+
+    /// Descriptions of the metrics that exceed their limits
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.warping > Self::WARPING_LIMIT {
+            warnings.push(format!(
+                "warping {:.3} exceeds {}, corrected by projection onto the mean plane",
+                self.warping, Self::WARPING_LIMIT
+            ));
+        }
+        if self.aspect_ratio > Self::ASPECT_RATIO_LIMIT {
+            warnings.push(format!("aspect ratio {:.1} exceeds {}", self.aspect_ratio, Self::ASPECT_RATIO_LIMIT));
+        }
+        if self.skew > Self::SKEW_LIMIT {
+            warnings.push(format!("skew {:.1} degrees exceeds {}", self.skew, Self::SKEW_LIMIT));
+        }
+        warnings
+    }
 }
 
 impl Default for Quad {
@@ -120,3 +210,26 @@ impl Default for Quad {
         Self::new("", "", "", "", 0.1, "")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_quality_of_distorted_quads() {
+        let square = QuadQuality::from_corners(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]);
+        assert_relative_eq!(square.warping, 0.0);
+        assert_relative_eq!(square.aspect_ratio, 1.0);
+        assert_relative_eq!(square.skew, 0.0, epsilon = 1e-12);
+        assert!(square.warnings().is_empty());
+        
+        // Lifting one corner by 0.2 puts every corner 0.05 off the mean plane
+        let warped = QuadQuality::from_corners(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.2], [0.0, 1.0, 0.0]]);
+        assert!(warped.warping > 0.04 && warped.warping < 0.05);
+        assert_eq!(warped.warnings().len(), 1);
+        
+        let sheared = QuadQuality::from_corners(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 1.0, 0.0], [1.0, 1.0, 0.0]]);
+        assert_relative_eq!(sheared.skew, 45.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,27 @@
+//! Rigid link constraint - slaves one node's motion to another
+
+use serde::{Deserialize, Serialize};
+
+/// A rigid link between a master node and a slave node
+///
+/// The slave node follows the rigid-body motion of the master node:
+/// `u_s = u_m + theta_m x (x_s - x_m)` and `theta_s = theta_m`. The slave
+/// DOFs are eliminated from the system during assembly, so no artificially
+/// stiff members are needed to model eccentric connections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RigidLink {
+    /// Master (independent) node name
+    pub master: String,
+    /// Slave (dependent) node name
+    pub slave: String,
+}
+
+impl RigidLink {
+    /// Create a new rigid link
+    pub fn new(master: &str, slave: &str) -> Self {
+        Self {
+            master: master.to_string(),
+            slave: slave.to_string(),
+        }
+    }
+}
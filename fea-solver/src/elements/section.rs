@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Cross-section properties for frame elements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Section {
     /// Cross-sectional area in m²
     pub a: f64,
@@ -21,6 +21,24 @@ pub struct Section {
     pub depth: Option<f64>,
     /// Width of section (optional) in m
     pub width: Option<f64>,
+    /// Cross-section geometry, recorded by the shape constructors for design checks
+    #[serde(default)]
+    pub shape: Option<SectionShape>,
+}
+
+/// Cross-section geometry (dimensions in m)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SectionShape {
+    /// Solid rectangle, depth along local z
+    Rectangle { width: f64, depth: f64 },
+    /// Solid circle
+    Circle { diameter: f64 },
+    /// Hollow circle
+    Pipe { outer_diameter: f64, wall_thickness: f64 },
+    /// Doubly symmetric I-section, web along local z
+    WideFlange { depth: f64, flange_width: f64, flange_thickness: f64, web_thickness: f64 },
+    /// Rectangular hollow section, depth along local z
+    Box { width: f64, depth: f64, wall_thickness: f64 },
 }
 
 impl Section {
@@ -35,6 +53,7 @@ impl Section {
             zz: None,
             depth: None,
             width: None,
+            shape: None,
         }
     }
 
@@ -57,6 +76,7 @@ impl Section {
             zz: Some(depth * width.powi(2) / 4.0),
             depth: Some(depth),
             width: Some(width),
+            shape: Some(SectionShape::Rectangle { width, depth }),
         }
     }
 
@@ -77,6 +97,7 @@ impl Section {
             zz: Some(z),
             depth: Some(diameter),
             width: Some(diameter),
+            shape: Some(SectionShape::Circle { diameter }),
         }
     }
 
@@ -98,6 +119,7 @@ impl Section {
             zz: None,
             depth: Some(outer_diameter),
             width: Some(outer_diameter),
+            shape: Some(SectionShape::Pipe { outer_diameter, wall_thickness }),
         }
     }
 
@@ -145,6 +167,7 @@ impl Section {
             zz: Some(zz),
             depth: Some(d),
             width: Some(bf),
+            shape: Some(SectionShape::WideFlange { depth, flange_width, flange_thickness, web_thickness }),
         }
     }
 
@@ -174,6 +197,7 @@ impl Section {
             zz: None,
             depth: Some(d),
             width: Some(b),
+            shape: Some(SectionShape::Box { width, depth, wall_thickness }),
         }
     }
 
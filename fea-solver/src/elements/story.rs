@@ -0,0 +1,50 @@
+//! Story levels - floor definitions for story drift and story shear results
+
+use serde::{Deserialize, Serialize};
+
+/// A floor level of a building
+///
+/// The level's nodes are either all nodes within `tolerance` of `elevation`
+/// (global Y), or the nodes of a named group when one is given. Stories are
+/// bounded by consecutive levels in elevation order; the lowest story starts
+/// at the lowest node of the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoryLevel {
+    /// Floor elevation (global Y)
+    pub elevation: f64,
+    /// Elevation band half-width used to find the level's nodes
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+    /// Group whose nodes form the level, instead of the elevation band
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+fn default_tolerance() -> f64 {
+    1e-3
+}
+
+impl StoryLevel {
+    /// Create a level from the nodes at an elevation
+    pub fn new(elevation: f64) -> Self {
+        Self {
+            elevation,
+            tolerance: default_tolerance(),
+            group: None,
+        }
+    }
+
+    /// Create a level from the nodes of a group
+    pub fn from_group(elevation: f64, group: &str) -> Self {
+        Self {
+            group: Some(group.to_string()),
+            ..Self::new(elevation)
+        }
+    }
+
+    /// Set the elevation band half-width
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
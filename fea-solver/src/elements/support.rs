@@ -2,8 +2,32 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Direction in which a one-way restraint can push on its node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OneWay {
+    /// Reaction acts in the positive axis direction only; the node may lift
+    /// off in the positive direction
+    Positive,
+    /// Reaction acts in the negative axis direction only
+    Negative,
+}
+
+impl OneWay {
+    /// Sign of the allowed reaction (+1 or -1)
+    pub fn sign(self) -> f64 {
+        match self {
+            OneWay::Positive => 1.0,
+            OneWay::Negative => -1.0,
+        }
+    }
+}
+
 /// Support conditions at a node
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+///
+/// Restraints, enforced displacements and reactions are expressed in the
+/// support's local axes, which default to the global axes. An orientation
+/// models inclined rollers and skewed abutment bearings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct Support {
     /// Restrained in X translation
     pub dx: bool,
@@ -30,6 +54,16 @@ pub struct Support {
     pub enforced_ry: Option<f64>,
     /// Enforced rotation about Z
     pub enforced_rz: Option<f64>,
+    
+    /// Local support axes as rows [x, y, z] of unit vectors in global
+    /// coordinates (None = global axes)
+    #[serde(default)]
+    pub orientation: Option<[[f64; 3]; 3]>,
+    
+    /// One-way translational restraints [DX, DY, DZ] (None = two-way); only
+    /// honoured in nonlinear analysis
+    #[serde(default)]
+    pub one_way: [Option<OneWay>; 3],
 }
 
 impl Support {
@@ -124,6 +158,51 @@ impl Support {
         self
     }
 
+    /// Make a translational restraint (0 = DX, 1 = DY, 2 = DZ) one-way
+    ///
+    /// `Support::pinned().with_one_way(1, OneWay::Positive)` models a bearing
+    /// on soil that can push the node up but cannot hold it down.
+    pub fn with_one_way(mut self, dof: usize, direction: OneWay) -> Self {
+        match dof {
+            0 => self.dx = true,
+            1 => self.dy = true,
+            2 => self.dz = true,
+            _ => return self,
+        }
+        self.one_way[dof] = Some(direction);
+        self
+    }
+
+    /// Orient the support with local axes given as rows [x, y, z] in global coordinates
+    pub fn with_orientation(mut self, axes: [[f64; 3]; 3]) -> Self {
+        self.orientation = Some(axes);
+        self
+    }
+
+    /// Orient the support by rotating the global axes about global X, then Y,
+    /// then Z (radians)
+    pub fn with_skew_angles(self, rx: f64, ry: f64, rz: f64) -> Self {
+        let (sx, cx) = rx.sin_cos();
+        let (sy, cy) = ry.sin_cos();
+        let (sz, cz) = rz.sin_cos();
+        
+        // Columns of R = Rz Ry Rx are the rotated axes
+        let r = [
+            [cz * cy, cz * sy * sx - sz * cx, cz * sy * cx + sz * sx],
+            [sz * cy, sz * sy * sx + cz * cx, sz * sy * cx - cz * sx],
+            [-sy, cy * sx, cy * cx],
+        ];
+        let axes = [0, 1, 2].map(|c| [r[0][c], r[1][c], r[2][c]]);
+        self.with_orientation(axes)
+    }
+
+    /// Check whether the support axes differ from the global axes
+    pub fn is_skewed(&self) -> bool {
+        self.orientation.is_some_and(|axes| {
+            (0..3).any(|i| (0..3).any(|j| (axes[i][j] - if i == j { 1.0 } else { 0.0 }).abs() > 1e-12))
+        })
+    }
+
     /// Get list of restrained DOF indices (0-5)
     pub fn restrained_dofs(&self) -> Vec<usize> {
         let mut dofs = Vec::new();
@@ -171,25 +250,6 @@ impl Support {
     }
 }
 
-impl Default for Support {
-    fn default() -> Self {
-        Self {
-            dx: false,
-            dy: false,
-            dz: false,
-            rx: false,
-            ry: false,
-            rz: false,
-            enforced_dx: None,
-            enforced_dy: None,
-            enforced_dz: None,
-            enforced_rx: None,
-            enforced_ry: None,
-            enforced_rz: None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +270,18 @@ mod tests {
         assert_eq!(support.num_restrained(), 3);
     }
 
+    #[test]
+    fn test_skew_angles() {
+        // 30 degrees about Z: local x points up the incline
+        let support = Support::roller_y().with_skew_angles(0.0, 0.0, 30f64.to_radians());
+        let axes = support.orientation.unwrap();
+        assert!((axes[0][0] - 30f64.to_radians().cos()).abs() < 1e-12);
+        assert!((axes[0][1] - 30f64.to_radians().sin()).abs() < 1e-12);
+        assert!((axes[1][0] + 30f64.to_radians().sin()).abs() < 1e-12);
+        assert!(support.is_skewed());
+        assert!(!Support::fixed().with_skew_angles(0.0, 0.0, 0.0).is_skewed());
+    }
+
     #[test]
     fn test_enforced_displacement() {
         let support = Support::pinned().with_enforced_dy(-0.01);
@@ -0,0 +1,24 @@
+//! Tetra element - 4-node constant strain tetrahedron for solid meshes
+
+use serde::{Deserialize, Serialize};
+
+/// A 4-node tetrahedral solid element (constant strain)
+/// Uses only the translational DOFs (DX, DY, DZ) of its nodes; rotations of
+/// nodes connected to nothing but solids are restrained during analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tetra {
+    /// Corner node names
+    pub nodes: [String; 4],
+    /// Material name
+    pub material: String,
+}
+
+impl Tetra {
+    /// Create a new tetrahedral element
+    pub fn new(n1: &str, n2: &str, n3: &str, n4: &str, material: &str) -> Self {
+        Self {
+            nodes: [n1.to_string(), n2.to_string(), n3.to_string(), n4.to_string()],
+            material: material.to_string(),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for FEA Solver
 
+use std::fmt;
+
 use thiserror::Error;
 
 /// Main error type for FEA operations
@@ -20,20 +22,29 @@ pub enum FEAError {
     #[error("Plate '{0}' not found in model")]
     PlateNotFound(String),
 
+    #[error("Solid element '{0}' not found in model")]
+    SolidNotFound(String),
+
     #[error("Load combination '{0}' not found in model")]
     LoadCombinationNotFound(String),
 
+    #[error("Load envelope '{0}' not found in model")]
+    LoadEnvelopeNotFound(String),
+
     #[error("Load case '{0}' not found in model")]
     LoadCaseNotFound(String),
 
+    #[error("Group '{0}' not found in model")]
+    GroupNotFound(String),
+
     #[error("Duplicate name '{0}' already exists")]
     DuplicateName(String),
 
     #[error("Model is unstable: {0}")]
     Unstable(String),
 
-    #[error("Singular stiffness matrix - model may be unstable or have insufficient supports")]
-    SingularMatrix,
+    #[error("Singular stiffness matrix - {0}")]
+    SingularMatrix(SingularityDiagnostics),
 
     #[error("Analysis failed: {0}")]
     AnalysisFailed(String),
@@ -44,8 +55,16 @@ pub enum FEAError {
     #[error("Model not analyzed - run analyze() first")]
     NotAnalyzed,
 
-    #[error("Convergence failed after {0} iterations")]
-    ConvergenceFailed(usize),
+    #[error("Convergence failed after {iterations} iterations{}", ResidualHistory(.residuals))]
+    ConvergenceFailed {
+        /// Iterations (or increments) attempted
+        iterations: usize,
+        /// Residual measure of each iteration, oldest first
+        residuals: Vec<f64>,
+    },
+
+    #[error("Analysis cancelled")]
+    Cancelled,
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
@@ -60,5 +79,104 @@ pub enum FEAError {
     SerializationError(#[from] serde_json::Error),
 }
 
+/// A free degree of freedom without stiffness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconstrainedDof {
+    /// Node name
+    pub node: String,
+    /// Degree of freedom, one of "DX", "DY", "DZ", "RX", "RY" or "RZ"
+    pub dof: String,
+}
+
+impl fmt::Display for UnconstrainedDof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.node, self.dof)
+    }
+}
+
+/// Where a singular stiffness matrix comes from
+///
+/// `unconstrained_dofs` are the free DOFs whose pivot vanished during
+/// elimination: either nothing is attached to them, or they can move as a
+/// mechanism with the DOFs before them. `weak_members` have a zero or
+/// negligible stiffness property (E, G, A, Iy, Iz, J or length).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SingularityDiagnostics {
+    /// Free DOFs without stiffness
+    pub unconstrained_dofs: Vec<UnconstrainedDof>,
+    /// Members with near-zero stiffness
+    pub weak_members: Vec<String>,
+}
+
+impl fmt::Display for SingularityDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.unconstrained_dofs.is_empty() && self.weak_members.is_empty() {
+            return write!(f, "model may be unstable or have insufficient supports");
+        }
+        let mut parts = Vec::new();
+        if !self.unconstrained_dofs.is_empty() {
+            parts.push(format!("unconstrained DOFs: {}", list(&self.unconstrained_dofs)));
+        }
+        if !self.weak_members.is_empty() {
+            parts.push(format!("members with near-zero stiffness: {}", list(&self.weak_members)));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// Comma-separated list, shortened after the first few items
+fn list<T: fmt::Display>(items: &[T]) -> String {
+    const SHOWN: usize = 10;
+    let mut text: Vec<String> = items.iter().take(SHOWN).map(|item| item.to_string()).collect();
+    if items.len() > SHOWN {
+        text.push(format!("and {} more", items.len() - SHOWN));
+    }
+    text.join(", ")
+}
+
+/// Display of the last few residuals of a failed iteration
+struct ResidualHistory<'a>(&'a [f64]);
+
+impl fmt::Display for ResidualHistory<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SHOWN: usize = 5;
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let start = self.0.len().saturating_sub(SHOWN);
+        let values: Vec<String> = self.0[start..].iter().map(|r| format!("{r:.3e}")).collect();
+        let ellipsis = if start > 0 { "..., " } else { "" };
+        write!(f, " (residuals: {ellipsis}{})", values.join(", "))
+    }
+}
+
 /// Result type for FEA operations
 pub type FEAResult<T> = Result<T, FEAError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_messages() {
+        let singular = FEAError::SingularMatrix(SingularityDiagnostics {
+            unconstrained_dofs: vec![UnconstrainedDof { node: "N2".to_string(), dof: "RX".to_string() }],
+            weak_members: vec!["M1".to_string()],
+        });
+        assert_eq!(
+            singular.to_string(),
+            "Singular stiffness matrix - unconstrained DOFs: N2 RX; members with near-zero stiffness: M1"
+        );
+        assert_eq!(
+            FEAError::SingularMatrix(SingularityDiagnostics::default()).to_string(),
+            "Singular stiffness matrix - model may be unstable or have insufficient supports"
+        );
+
+        let residuals = (1..=7).map(|i| 10f64.powi(-i)).collect();
+        let failed = FEAError::ConvergenceFailed { iterations: 7, residuals };
+        assert_eq!(
+            failed.to_string(),
+            "Convergence failed after 7 iterations (residuals: ..., 1.000e-3, 1.000e-4, 1.000e-5, 1.000e-6, 1.000e-7)"
+        );
+    }
+}
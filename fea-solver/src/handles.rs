@@ -0,0 +1,161 @@
+//! Interned handles for model entities
+//!
+//! The model is keyed by name, which keeps the public API readable but makes
+//! every lookup a string hash. Before analysis the names are interned into
+//! dense integer handles so that assembly and recovery can index plain
+//! vectors instead. Handles are only meaningful for the model that issued
+//! them and are reassigned whenever the model is prepared again.
+
+use std::collections::HashMap;
+
+/// A dense integer handle issued by a [`Registry`]
+pub trait Handle: Copy {
+    /// Handle for a registry index
+    fn from_index(index: usize) -> Self;
+    /// Registry index of the handle
+    fn index(self) -> usize;
+}
+
+macro_rules! handle {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(u32);
+
+        impl Handle for $name {
+            #[inline]
+            fn from_index(index: usize) -> Self {
+                Self(u32::try_from(index).expect("handle index exceeds u32"))
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                self.0 as usize
+            }
+        }
+    };
+}
+
+handle!(
+    /// Handle to a node; its first global DOF is `6 * index`
+    NodeId
+);
+
+handle!(
+    /// Handle to a member
+    MemberId
+);
+
+handle!(
+    /// Handle to a load combination with stored results
+    ComboId
+);
+
+/// Two-way mapping between entity names and handles
+///
+/// Names are numbered in sorted order, or in an order derived from it, so the
+/// same model always produces the same handles regardless of `HashMap`
+/// iteration order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registry<I> {
+    names: Vec<String>,
+    ids: HashMap<String, I>,
+}
+
+impl<I> Default for Registry<I> {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<I: Handle> Registry<I> {
+    /// Intern a set of names (duplicates are ignored)
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a String>) -> Self {
+        let mut names: Vec<String> = names.into_iter().cloned().collect();
+        names.sort_unstable();
+        names.dedup();
+        let ids = names.iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), I::from_index(i)))
+            .collect();
+        Self { names, ids }
+    }
+
+    /// Intern names in the given order (duplicates are ignored)
+    pub fn from_ordered_names(names: impl IntoIterator<Item = String>) -> Self {
+        let mut registry = Self::default();
+        for name in names {
+            if !registry.ids.contains_key(&name) {
+                registry.ids.insert(name.clone(), I::from_index(registry.names.len()));
+                registry.names.push(name);
+            }
+        }
+        registry
+    }
+
+    /// Handle of a name, interning it after the existing names if it is new
+    pub fn intern(&mut self, name: &str) -> I {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = I::from_index(self.names.len());
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    /// Handle of a name
+    pub fn id(&self, name: &str) -> Option<I> {
+        self.ids.get(name).copied()
+    }
+
+    /// Name of a handle
+    pub fn name(&self, id: I) -> Option<&str> {
+        self.names.get(id.index()).map(String::as_str)
+    }
+
+    /// Number of interned names
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Handles and names in handle order
+    pub fn iter(&self) -> impl Iterator<Item = (I, &str)> {
+        self.names.iter().enumerate().map(|(i, name)| (I::from_index(i), name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_round_trip() {
+        let names = ["N3".to_string(), "N1".to_string(), "N2".to_string(), "N1".to_string()];
+        let registry: Registry<NodeId> = Registry::from_names(&names);
+
+        assert_eq!(registry.len(), 3);
+        let id = registry.id("N2").unwrap();
+        assert_eq!(id.index(), 1);
+        assert_eq!(registry.name(id), Some("N2"));
+        assert!(registry.id("N4").is_none());
+        assert_eq!(registry.iter().map(|(_, n)| n).collect::<Vec<_>>(), ["N1", "N2", "N3"]);
+
+        let ordered: Registry<NodeId> = Registry::from_ordered_names(names);
+        assert_eq!(ordered.iter().map(|(_, n)| n).collect::<Vec<_>>(), ["N3", "N1", "N2"]);
+        assert_eq!(ordered.id("N2").unwrap().index(), 2);
+
+        let mut combos: Registry<ComboId> = Registry::default();
+        assert_eq!(combos.intern("1.2D").index(), 0);
+        assert_eq!(combos.intern("D+L").index(), 1);
+        assert_eq!(combos.intern("1.2D").index(), 0);
+    }
+}
@@ -0,0 +1,211 @@
+//! CalculiX input (INP) export
+//!
+//! Writes a model as a CalculiX deck for cross-checking results:
+//!
+//! - Nodes are numbered from 1 in name order, members then plates and quads
+//!   (B31 and S4) and tetras (C3D4) from 1 in name order.
+//! - Each member gets its own `*BEAM GENERAL SECTION` so that its local y
+//!   axis, taken from the solver's member transformation, is carried over as
+//!   the CalculiX 1-direction (I11 = Iy, I22 = Iz).
+//! - Every load combination becomes a static step. Member and plate loads
+//!   are written as the solver's equivalent nodal loads, so nodal results are
+//!   directly comparable, while element forces from CalculiX will not include
+//!   the span loads.
+//!
+//! Features with no direct CalculiX counterpart (end releases, rigid links,
+//! skewed and one-way supports, gaps and links) are listed as `**` comments
+//! at the top of the deck.
+//!
+//! This is not the calculix-service input generator, which keeps its own:
+//! the service writes modal, buckling and nonlinear steps, span and pressure
+//! loads as element loads, and the output requests its result parser reads,
+//! none of which this static cross-checking export covers.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::error::FEAResult;
+use crate::loads::LoadCombination;
+use crate::math;
+use crate::model::FEModel;
+
+/// Export a model as a CalculiX INP deck
+pub fn export_inp(model: &FEModel) -> FEAResult<String> {
+    let mut model = model.clone();
+    if model.load_combos.is_empty() {
+        model.add_load_combo(LoadCombination::single("Combo 1", "Case 1"))?;
+    }
+    
+    // Computing the loads also prepares the model
+    let combos: Vec<String> = model.load_combos.keys().cloned().collect();
+    let mut combo_loads = Vec::with_capacity(combos.len());
+    for combo in combos {
+        combo_loads.push((model.equivalent_nodal_loads(&combo)?, combo));
+    }
+
+    let mut inp = String::new();
+    let node_numbers: BTreeMap<&str, usize> = model.nodes.keys()
+        .enumerate()
+        .map(|(k, name)| (name.as_str(), k + 1))
+        .collect();
+    let node_number = |name: &str| node_numbers[name];
+
+    inp.push_str("*HEADING\nfea-solver model export\n");
+    for note in unsupported_features(&model) {
+        let _ = writeln!(inp, "** Not exported: {note}");
+    }
+
+    // Nodes
+    inp.push_str("*NODE, NSET=NALL\n");
+    for (name, node) in &model.nodes {
+        let _ = writeln!(inp, "{}, {}, {}, {}", node_number(name), node.x, node.y, node.z);
+    }
+
+    // Materials
+    let material_names: BTreeMap<&String, String> = model.materials.keys()
+        .enumerate()
+        .map(|(k, name)| (name, format!("MAT{}", k + 1)))
+        .collect();
+    for (name, material) in &model.materials {
+        let _ = writeln!(inp, "** Material {name}");
+        let _ = writeln!(inp, "*MATERIAL, NAME={}", material_names[name]);
+        let _ = writeln!(inp, "*ELASTIC\n{}, {}", material.e, material.nu);
+        let _ = writeln!(inp, "*DENSITY\n{}", material.rho);
+    }
+
+    // Members
+    let mut element = 0;
+    for (name, member) in &model.members {
+        element += 1;
+        let section = &model.sections[&member.section];
+        let t = math::member_transformation_matrix(
+            &model.nodes[&member.i_node].coords(),
+            &model.nodes[&member.j_node].coords(),
+            member.rotation,
+        );
+        let _ = writeln!(inp, "** Member {name}");
+        let _ = writeln!(inp, "*ELEMENT, TYPE=B31, ELSET=E{element}");
+        let _ = writeln!(inp, "{element}, {}, {}", node_number(&member.i_node), node_number(&member.j_node));
+        let _ = writeln!(
+            inp,
+            "*BEAM GENERAL SECTION, ELSET=E{element}, MATERIAL={}, SECTION=GENERAL",
+            material_names[&member.material],
+        );
+        let _ = writeln!(inp, "{}, {}, 0, {}, {}", section.a, section.iy, section.iz, section.j);
+        let _ = writeln!(inp, "{}, {}, {}", t[(1, 0)], t[(1, 1)], t[(1, 2)]);
+    }
+
+    // Plates and quads
+    let shells = model.plates.iter()
+        .map(|(name, p)| (name, [&p.i_node, &p.j_node, &p.m_node, &p.n_node], p.thickness, &p.material))
+        .chain(model.quads.iter()
+            .map(|(name, q)| (name, [&q.i_node, &q.j_node, &q.m_node, &q.n_node], q.thickness, &q.material)));
+    for (name, nodes, thickness, material) in shells {
+        element += 1;
+        let [i, j, m, n] = nodes.map(|n| node_number(n));
+        let _ = writeln!(inp, "** Shell {name}");
+        let _ = writeln!(inp, "*ELEMENT, TYPE=S4, ELSET=E{element}");
+        let _ = writeln!(inp, "{element}, {i}, {j}, {m}, {n}");
+        let _ = writeln!(inp, "*SHELL SECTION, ELSET=E{element}, MATERIAL={}", material_names[material]);
+        let _ = writeln!(inp, "{thickness}");
+    }
+
+    // Tetras
+    for (name, tetra) in &model.tetras {
+        element += 1;
+        let [a, b, c, d] = tetra.nodes.each_ref().map(|n| node_number(n));
+        let _ = writeln!(inp, "** Solid {name}");
+        let _ = writeln!(inp, "*ELEMENT, TYPE=C3D4, ELSET=E{element}");
+        let _ = writeln!(inp, "{element}, {a}, {b}, {c}, {d}");
+        let _ = writeln!(inp, "*SOLID SECTION, ELSET=E{element}, MATERIAL={}", material_names[&tetra.material]);
+    }
+
+    // Supports, with any enforced displacements
+    if !model.supports.is_empty() {
+        inp.push_str("*BOUNDARY\n");
+        for (name, support) in &model.supports {
+            let enforced = support.enforced_displacements();
+            for dof in support.restrained_dofs() {
+                let _ = writeln!(inp, "{}, {}, {}, {}", node_number(name), dof + 1, dof + 1, enforced[dof].unwrap_or(0.0));
+            }
+        }
+    }
+
+    // One static step per load combination
+    for (loads, combo) in combo_loads {
+        let mut loads: Vec<(usize, [f64; 6])> = loads.into_iter()
+            .map(|(name, load)| (node_number(&name), load))
+            .collect();
+        loads.sort_by_key(|(node, _)| *node);
+
+        let _ = writeln!(inp, "** Load combination {combo}");
+        inp.push_str("*STEP\n*STATIC\n*CLOAD, OP=NEW\n");
+        for (node, load) in loads {
+            for (dof, value) in load.iter().enumerate().filter(|(_, v)| **v != 0.0) {
+                let _ = writeln!(inp, "{node}, {}, {value}", dof + 1);
+            }
+        }
+        inp.push_str("*NODE PRINT, NSET=NALL\nU, RF\n*END STEP\n");
+    }
+
+    Ok(inp)
+}
+
+/// Model features the export cannot represent
+fn unsupported_features(model: &FEModel) -> Vec<String> {
+    let mut notes = Vec::new();
+    for (name, member) in &model.members {
+        if member.releases.as_array().iter().any(|&r| r) {
+            notes.push(format!("end releases on member {name}"));
+        }
+    }
+    for name in model.rigid_links.keys() {
+        notes.push(format!("rigid link {name}"));
+    }
+    for (name, support) in &model.supports {
+        if support.is_skewed() {
+            notes.push(format!("skewed support axes at node {name}"));
+        }
+        if support.one_way.iter().any(Option::is_some) {
+            notes.push(format!("one-way support at node {name}"));
+        }
+    }
+    for name in model.gaps.keys().chain(model.links.keys()) {
+        notes.push(format!("gap or link element {name}"));
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_export_frame_and_slab() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::new(0.01, 2e-4, 1e-4, 5e-5)).unwrap();
+        for (name, x, z) in [("A", 0.0, 0.0), ("B", 4.0, 0.0), ("C", 4.0, 3.0), ("D", 0.0, 3.0)] {
+            model.add_node(name, Node::new(x, 0.0, z)).unwrap();
+        }
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam").with_releases(MemberReleases::pin_j())).unwrap();
+        model.add_plate("P1", Plate::new("A", "B", "C", "D", 0.2, "Steel")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_support("D", Support::pinned().with_enforced_dy(-0.01)).unwrap();
+        model.add_node_load("C", NodeLoad::fy(-5000.0, "Live")).unwrap();
+        model.add_load_combo(LoadCombination::new("ULS").with_case("Live", 1.5)).unwrap();
+
+        let inp = export_inp(&model).unwrap();
+        assert!(inp.contains("** Not exported: end releases on member M1"));
+        assert!(inp.contains("*ELEMENT, TYPE=B31, ELSET=E1\n1, 1, 2\n"));
+        // Horizontal member: local y is global Y
+        assert!(inp.contains("SECTION=GENERAL\n0.01, 0.0002, 0, 0.0001, 0.00005\n"));
+        assert!(inp.contains("\n0, 1, 0\n"));
+        assert!(inp.contains("*ELEMENT, TYPE=S4, ELSET=E2\n2, 1, 2, 3, 4\n"));
+        assert!(inp.contains("1, 6, 6, 0\n"));
+        assert!(inp.contains("4, 2, 2, -0.01\n"));
+        assert!(inp.contains("** Load combination ULS\n*STEP\n*STATIC\n*CLOAD, OP=NEW\n3, 2, -7500\n"));
+        assert_eq!(inp.matches("*END STEP").count(), 1);
+    }
+}
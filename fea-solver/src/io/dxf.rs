@@ -0,0 +1,383 @@
+//! DXF line drawing import
+//!
+//! Reads the centre lines of a frame from the `ENTITIES` section of an ASCII
+//! DXF file:
+//!
+//! | Entity                | Read as                                             |
+//! |-----------------------|-----------------------------------------------------|
+//! | `LINE`                | One line                                            |
+//! | `LWPOLYLINE`          | Lines between its vertices, at its elevation        |
+//! | `POLYLINE`, `VERTEX`  | Lines between the vertices of 2D and 3D polylines   |
+//!
+//! Closed polylines get a line back to their first vertex, and arc segments
+//! (bulges) are read as straight. Polygon and polyface meshes, block
+//! inserts and other entities are skipped with a logged warning. Entities
+//! must lie in world coordinates; extrusion directions are ignored.
+//!
+//! Coordinates are kept in drawing units, with `$INSUNITS` from the header
+//! giving their size. [`DxfDrawing::frame`] turns the lines on chosen layers
+//! into nodes and members in metres, merging line ends closer than a
+//! tolerance.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::error::{FEAError, FEAResult};
+use crate::units::LengthUnit;
+
+/// A straight line of the drawing, in drawing units
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxfLine {
+    pub layer: String,
+    pub start: [f64; 3],
+    pub end: [f64; 3],
+}
+
+/// The lines of a DXF drawing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DxfDrawing {
+    pub lines: Vec<DxfLine>,
+    /// Drawing units from `$INSUNITS`; None if unitless or not a length
+    /// unit this crate knows
+    pub units: Option<LengthUnit>,
+}
+
+/// Nodes and members made from the lines of a drawing, in metres
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DxfFrame {
+    pub nodes: Vec<[f64; 3]>,
+    pub members: Vec<DxfMember>,
+}
+
+/// A member between two nodes of a [`DxfFrame`], by index
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxfMember {
+    pub i_node: usize,
+    pub j_node: usize,
+    /// Layer of the line it was made from
+    pub layer: String,
+}
+
+impl DxfDrawing {
+    /// Layers with the number of lines on each, in name order
+    pub fn layers(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for line in &self.lines {
+            *counts.entry(&line.layer).or_default() += 1;
+        }
+        counts.into_iter().map(|(layer, n)| (layer.to_string(), n)).collect()
+    }
+
+    /// Size of a drawing unit in metres; 1 for a unitless drawing
+    pub fn metres(&self) -> f64 {
+        self.units.map_or(1.0, |u| u.metres())
+    }
+
+    /// Nodes and members of the lines on `layers`, scaled by `scale` metres
+    /// per drawing unit
+    ///
+    /// Line ends within `tolerance` (m) of a node already made join it.
+    /// Lines that merge down to a point, and lines repeating a member
+    /// between the same nodes, are dropped.
+    pub fn frame(&self, layers: &[String], tolerance: f64, scale: f64) -> DxfFrame {
+        let mut frame = DxfFrame::default();
+        let mut nodes = NodeGrid::new(tolerance);
+        let mut joined = BTreeSet::new();
+        for line in self.lines.iter().filter(|l| layers.contains(&l.layer)) {
+            let i_node = nodes.node_at(&mut frame.nodes, line.start.map(|v| v * scale));
+            let j_node = nodes.node_at(&mut frame.nodes, line.end.map(|v| v * scale));
+            if i_node != j_node && joined.insert((i_node.min(j_node), i_node.max(j_node))) {
+                frame.members.push(DxfMember { i_node, j_node, layer: line.layer.clone() });
+            }
+        }
+        frame
+    }
+}
+
+/// Nodes bucketed in cells the size of the merge tolerance, so a point is
+/// only compared with nodes in its own and neighbouring cells
+struct NodeGrid {
+    tolerance: f64,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl NodeGrid {
+    fn new(tolerance: f64) -> Self {
+        Self { tolerance: tolerance.max(1e-9), cells: HashMap::new() }
+    }
+
+    fn cell(&self, point: [f64; 3]) -> [i64; 3] {
+        point.map(|v| (v / self.tolerance).floor() as i64)
+    }
+
+    /// Index of the nearest node within the tolerance, adding one if none is
+    fn node_at(&mut self, nodes: &mut Vec<[f64; 3]>, point: [f64; 3]) -> usize {
+        let [cx, cy, cz] = self.cell(point);
+        let distance = |node: &[f64; 3]| (0..3).map(|k| (node[k] - point[k]).powi(2)).sum::<f64>().sqrt();
+        let nearest = (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [cx + dx, cy + dy, cz + dz])))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|&i| (i, distance(&nodes[i])))
+            .filter(|&(_, d)| d <= self.tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, _)) = nearest {
+            return i;
+        }
+        nodes.push(point);
+        self.cells.entry([cx, cy, cz]).or_default().push(nodes.len() - 1);
+        nodes.len() - 1
+    }
+}
+
+/// A group code and its value, with the line the value is on
+#[derive(Debug)]
+struct Pair<'a> {
+    code: i32,
+    value: &'a str,
+    line: usize,
+}
+
+/// An entity: its type, from group code 0, and the pairs up to the next one
+#[derive(Debug)]
+struct Entity<'a> {
+    kind: &'a str,
+    line: usize,
+    pairs: &'a [Pair<'a>],
+}
+
+impl Entity<'_> {
+    fn text(&self, code: i32) -> Option<&str> {
+        self.pairs.iter().find(|p| p.code == code).map(|p| p.value)
+    }
+
+    fn layer(&self) -> String {
+        self.text(8).unwrap_or("0").to_string()
+    }
+
+    /// A real value, 0 if the code is absent
+    fn real(&self, code: i32) -> FEAResult<f64> {
+        self.pairs.iter().find(|p| p.code == code).map_or(Ok(0.0), real)
+    }
+
+    fn flags(&self) -> FEAResult<i64> {
+        self.pairs.iter().find(|p| p.code == 70).map_or(Ok(0), int)
+    }
+
+    fn point(&self, x: i32) -> FEAResult<[f64; 3]> {
+        Ok([self.real(x)?, self.real(x + 10)?, self.real(x + 20)?])
+    }
+}
+
+fn real(pair: &Pair) -> FEAResult<f64> {
+    pair.value.parse().map_err(|_| {
+        FEAError::InvalidInput(format!("line {}: group {}: expected a real, found '{}'", pair.line, pair.code, pair.value))
+    })
+}
+
+fn int(pair: &Pair) -> FEAResult<i64> {
+    pair.value.parse().map_err(|_| {
+        FEAError::InvalidInput(format!("line {}: group {}: expected an integer, found '{}'", pair.line, pair.code, pair.value))
+    })
+}
+
+/// Split a file into group code / value pairs
+fn read_pairs(input: &str) -> FEAResult<Vec<Pair<'_>>> {
+    if input.starts_with("AutoCAD Binary DXF") {
+        return Err(FEAError::InvalidInput("binary DXF is not supported; save the drawing as ASCII DXF".to_string()));
+    }
+    let mut lines = input.lines().enumerate().map(|(i, l)| (i + 1, l.trim()));
+    let mut pairs = Vec::new();
+    while let Some((line, code)) = lines.next() {
+        if code.is_empty() {
+            continue;
+        }
+        let code = code.parse().map_err(|_| {
+            FEAError::InvalidInput(format!("line {line}: expected a group code, found '{code}'"))
+        })?;
+        let (line, value) = lines.next()
+            .ok_or_else(|| FEAError::InvalidInput(format!("line {line}: group {code} has no value")))?;
+        pairs.push(Pair { code, value, line });
+    }
+    Ok(pairs)
+}
+
+/// Length unit of an `$INSUNITS` code
+fn insunits(code: i64) -> Option<LengthUnit> {
+    match code {
+        1 => Some(LengthUnit::Inch),
+        2 => Some(LengthUnit::Foot),
+        4 => Some(LengthUnit::Millimetre),
+        5 => Some(LengthUnit::Centimetre),
+        6 => Some(LengthUnit::Metre),
+        _ => None,
+    }
+}
+
+/// Read the lines of an ASCII DXF drawing
+pub fn read_dxf(input: &str) -> FEAResult<DxfDrawing> {
+    let pairs = read_pairs(input)?;
+    let mut drawing = DxfDrawing::default();
+
+    // Header variables are a code 9 name followed by their value
+    if let Some(at) = pairs.iter().position(|p| p.code == 9 && p.value == "$INSUNITS") {
+        if let Some(value) = pairs.get(at + 1).filter(|p| p.code == 70) {
+            drawing.units = insunits(int(value)?);
+        }
+    }
+
+    let Some(start) = pairs.windows(2)
+        .position(|w| w[0].code == 0 && w[0].value == "SECTION" && w[1].code == 2 && w[1].value == "ENTITIES")
+    else {
+        return Err(FEAError::InvalidInput("the DXF has no ENTITIES section".to_string()));
+    };
+    let section = &pairs[start + 2..];
+    let end = section.iter().position(|p| p.code == 0 && p.value == "ENDSEC").unwrap_or(section.len());
+
+    let starts: Vec<usize> = section[..end].iter().enumerate().filter(|(_, p)| p.code == 0).map(|(i, _)| i).collect();
+    let entities: Vec<Entity> = starts.iter().enumerate()
+        .map(|(k, &i)| {
+            let next = starts.get(k + 1).copied().unwrap_or(end);
+            Entity { kind: section[i].value, line: section[i].line, pairs: &section[i + 1..next] }
+        })
+        .collect();
+
+    let mut skipped = BTreeSet::new();
+    let mut polyline: Option<(&Entity, Vec<[f64; 3]>)> = None;
+    for entity in &entities {
+        match entity.kind {
+            "LINE" => drawing.lines.push(DxfLine {
+                layer: entity.layer(),
+                start: entity.point(10)?,
+                end: entity.point(11)?,
+            }),
+            "LWPOLYLINE" => {
+                let elevation = entity.real(38)?;
+                let mut vertices: Vec<[f64; 3]> = Vec::new();
+                for pair in entity.pairs {
+                    match pair.code {
+                        10 => vertices.push([real(pair)?, 0.0, elevation]),
+                        20 => match vertices.last_mut() {
+                            Some(vertex) => vertex[1] = real(pair)?,
+                            None => return Err(FEAError::InvalidInput(format!(
+                                "line {}: LWPOLYLINE vertex has no x", pair.line
+                            ))),
+                        },
+                        _ => {}
+                    }
+                }
+                push_polyline(&mut drawing, entity.layer(), &vertices, entity.flags()? & 1 != 0);
+            }
+            "POLYLINE" => {
+                // 16 and 64 flag polygon and polyface meshes
+                if entity.flags()? & (16 | 64) != 0 {
+                    skipped.insert("POLYLINE mesh");
+                } else {
+                    polyline = Some((entity, Vec::new()));
+                }
+            }
+            "VERTEX" => {
+                if let Some((owner, vertices)) = polyline.as_mut() {
+                    let mut vertex = entity.point(10)?;
+                    // 8 flags a 3D polyline; 2D ones take their elevation
+                    if owner.flags()? & 8 == 0 {
+                        vertex[2] = owner.real(30)?;
+                    }
+                    vertices.push(vertex);
+                }
+            }
+            "SEQEND" => {
+                if let Some((owner, vertices)) = polyline.take() {
+                    push_polyline(&mut drawing, owner.layer(), &vertices, owner.flags()? & 1 != 0);
+                }
+            }
+            other => {
+                if skipped.insert(other) {
+                    log::warn!("Skipping unsupported DXF entity {other} (line {})", entity.line);
+                }
+            }
+        }
+    }
+    if skipped.contains("POLYLINE mesh") {
+        log::warn!("Skipping DXF polygon and polyface meshes");
+    }
+    Ok(drawing)
+}
+
+/// Lines between successive vertices, and back to the first if closed
+fn push_polyline(drawing: &mut DxfDrawing, layer: String, vertices: &[[f64; 3]], closed: bool) {
+    let closing = if closed && vertices.len() > 2 { vertices.last().zip(vertices.first()) } else { None };
+    let segments = vertices.windows(2).map(|w| (&w[0], &w[1])).chain(closing);
+    for (start, end) in segments {
+        drawing.lines.push(DxfLine { layer: layer.clone(), start: *start, end: *end });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Group code / value pairs as DXF lines
+    fn dxf(pairs: &[(i32, &str)]) -> String {
+        pairs.iter().map(|(code, value)| format!("{code:>3}\n{value}\n")).collect()
+    }
+
+    fn portal() -> String {
+        dxf(&[
+            (0, "SECTION"), (2, "HEADER"), (9, "$INSUNITS"), (70, "4"), (0, "ENDSEC"),
+            (0, "SECTION"), (2, "ENTITIES"),
+            // Two columns, one as a 3D polyline, and a beam whose end misses
+            // the column top by 2 mm
+            (0, "LINE"), (8, "Columns"), (10, "0"), (20, "0"), (30, "0"), (11, "0"), (21, "0"), (31, "3000"),
+            (0, "POLYLINE"), (8, "Columns"), (70, "8"),
+            (0, "VERTEX"), (8, "Columns"), (10, "6000"), (20, "0"), (30, "0"),
+            (0, "VERTEX"), (8, "Columns"), (10, "6000"), (20, "0"), (30, "3000"),
+            (0, "SEQEND"),
+            (0, "LINE"), (8, "Beams"), (10, "0"), (20, "0"), (30, "3000"), (11, "6002"), (21, "0"), (31, "3000"),
+            // The same beam drawn again, and a closed outline at 3 m
+            (0, "LINE"), (8, "Beams"), (10, "6000"), (20, "0"), (30, "3000"), (11, "0"), (21, "0"), (31, "3000"),
+            (0, "LWPOLYLINE"), (8, "Slab"), (70, "1"), (38, "3000"),
+            (10, "0"), (20, "0"), (10, "6000"), (20, "0"), (10, "6000"), (20, "4000"), (10, "0"), (20, "4000"),
+            (0, "TEXT"), (8, "Notes"), (1, "Frame A"),
+            (0, "ENDSEC"), (0, "EOF"),
+        ])
+    }
+
+    #[test]
+    fn test_read_entities_and_layers() {
+        let drawing = read_dxf(&portal()).unwrap();
+        assert_eq!(drawing.units, Some(LengthUnit::Millimetre));
+        assert_eq!(
+            drawing.layers(),
+            vec![("Beams".to_string(), 2), ("Columns".to_string(), 2), ("Slab".to_string(), 4)]
+        );
+        let slab: Vec<&DxfLine> = drawing.lines.iter().filter(|l| l.layer == "Slab").collect();
+        assert_eq!(slab[3].start, [0.0, 4000.0, 3000.0]);
+        assert_eq!(slab[3].end, [0.0, 0.0, 3000.0]);
+    }
+
+    #[test]
+    fn test_frame_merges_ends_within_tolerance() {
+        let drawing = read_dxf(&portal()).unwrap();
+        let layers = ["Beams".to_string(), "Columns".to_string()];
+
+        let frame = drawing.frame(&layers, 0.005, drawing.metres());
+        assert_eq!(frame.nodes.len(), 4);
+        // The repeated beam is dropped
+        assert_eq!(frame.members.len(), 3);
+        assert_relative_eq!(frame.nodes[1][2], 3.0);
+
+        // With a tighter tolerance the beam end stays a node of its own, and
+        // the repeated beam joins the column top instead
+        let frame = drawing.frame(&layers, 0.001, drawing.metres());
+        assert_eq!(frame.nodes.len(), 5);
+        assert_eq!(frame.members.len(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(read_dxf(&dxf(&[(0, "SECTION"), (2, "HEADER"), (0, "ENDSEC"), (0, "EOF")])).is_err());
+        let bad = dxf(&[(0, "SECTION"), (2, "ENTITIES"), (0, "LINE"), (10, "x")]);
+        assert!(matches!(read_dxf(&bad), Err(FEAError::InvalidInput(m)) if m.contains("line 8")));
+    }
+}
@@ -0,0 +1,410 @@
+//! glTF 2.0 export of geometry and result fields
+//!
+//! Writes a self-contained `.gltf` file (JSON with an embedded base64
+//! buffer) that any standard viewer, and three.js's `GLTFLoader`, can open:
+//!
+//! - A `Members` mesh with each member extruded as a box from its section
+//!   depth and width (or the equivalent rectangle `sqrt(12 I / A)` when the
+//!   section has none), oriented by the member's local axes.
+//! - A `Shells` mesh with each plate and quad as two triangles.
+//!
+//! Every vertex carries a `COLOR_0` from a blue-to-red scale over the chosen
+//! [`ResultField`], plus the raw value as the custom `_VALUE` attribute so a
+//! viewer can apply its own colour map. The field, combination and value
+//! range are stored in the scene's `extras` for drawing a legend. The solver's
+//! global Y is the vertical axis, which matches glTF's Y-up convention.
+
+use base64::Engine;
+use serde_json::{json, Value};
+
+use crate::error::{FEAError, FEAResult};
+use crate::math;
+use crate::model::FEModel;
+
+/// Result plotted on the exported geometry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResultField {
+    /// Geometry only, in a uniform colour
+    #[default]
+    None,
+    /// Translation magnitude at each node
+    Deflection,
+    /// Von Mises stress: shell corner stresses, and for members the extreme
+    /// fibre stress `|N| / A + |My| / Sy + |Mz| / Sz` at each end (shear is
+    /// not included)
+    VonMises,
+}
+
+impl ResultField {
+    fn name(self) -> &'static str {
+        match self {
+            ResultField::None => "none",
+            ResultField::Deflection => "deflection",
+            ResultField::VonMises => "von_mises",
+        }
+    }
+}
+
+/// Options for [`export_gltf`]
+#[derive(Debug, Clone, Default)]
+pub struct GltfOptions {
+    /// Result to colour the geometry by
+    pub field: ResultField,
+    /// Load combination the results and deformed shape are taken from
+    pub combo: Option<String>,
+    /// Displacement magnification for a deformed shape (0 = undeformed)
+    pub deformation_scale: f64,
+}
+
+impl GltfOptions {
+    /// Undeformed geometry with no result field
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Colour by a result of a load combination
+    pub fn with_field(mut self, field: ResultField, combo: &str) -> Self {
+        self.field = field;
+        self.combo = Some(combo.to_string());
+        self
+    }
+
+    /// Draw the deformed shape of the combination, magnified by `scale`
+    pub fn with_deformation(mut self, combo: &str, scale: f64) -> Self {
+        self.combo = Some(combo.to_string());
+        self.deformation_scale = scale;
+        self
+    }
+}
+
+/// Triangle mesh with one scalar value per vertex
+#[derive(Debug, Default)]
+struct Mesh {
+    positions: Vec<[f64; 3]>,
+    values: Vec<f64>,
+    indices: Vec<u32>,
+}
+
+impl Mesh {
+    fn vertex(&mut self, position: [f64; 3], value: f64) -> u32 {
+        self.positions.push(position);
+        self.values.push(value);
+        (self.positions.len() - 1) as u32
+    }
+}
+
+/// Export a model, optionally coloured by a result, as a glTF 2.0 scene
+pub fn export_gltf(model: &FEModel, options: &GltfOptions) -> FEAResult<String> {
+    let combo = options.combo.as_deref();
+    if let Some(combo) = combo {
+        if !model.is_analyzed() {
+            return Err(FEAError::NotAnalyzed);
+        }
+        if !model.load_combos.contains_key(combo) {
+            return Err(FEAError::LoadCombinationNotFound(combo.to_string()));
+        }
+    } else if options.field != ResultField::None {
+        return Err(FEAError::InvalidInput("a result field needs a load combination".to_string()));
+    }
+
+    // Physical members are only split into segments when the model is prepared
+    let prepared;
+    let model = if model.is_analyzed() || model.physical_members.is_empty() {
+        model
+    } else {
+        let mut copy = model.clone();
+        copy.prepare()?;
+        prepared = copy;
+        &prepared
+    };
+
+    let position = |name: &str| -> [f64; 3] {
+        let node = &model.nodes[name];
+        let d = combo
+            .filter(|_| options.deformation_scale != 0.0)
+            .and_then(|c| model.store.displacements.get(name, c))
+            .unwrap_or_default();
+        let s = options.deformation_scale;
+        [node.x + s * d[0], node.y + s * d[1], node.z + s * d[2]]
+    };
+    let deflection = |name: &str| -> f64 {
+        combo.and_then(|c| model.store.displacements.get(name, c))
+            .map_or(0.0, |d| (d[0].powi(2) + d[1].powi(2) + d[2].powi(2)).sqrt())
+    };
+
+    let mut members = Mesh::default();
+    for (name, member) in &model.members {
+        let section = &model.sections[&member.section];
+        let (i, j) = (model.nodes[&member.i_node].coords(), model.nodes[&member.j_node].coords());
+        let t = math::member_transformation_matrix(&i, &j, member.rotation);
+        let local_y = [t[(1, 0)], t[(1, 1)], t[(1, 2)]];
+        let local_z = [t[(2, 0)], t[(2, 1)], t[(2, 2)]];
+        // Depth spans local z (Iy = b d^3 / 12) and width spans local y
+        let depth = section.depth.unwrap_or_else(|| (12.0 * section.iy / section.a).sqrt());
+        let width = section.width.unwrap_or_else(|| (12.0 * section.iz / section.a).sqrt());
+
+        let end_values = match options.field {
+            ResultField::None => [0.0; 2],
+            ResultField::Deflection => [deflection(&member.i_node), deflection(&member.j_node)],
+            ResultField::VonMises => {
+                let f = combo.and_then(|c| model.store.member_forces.get(name, c)).unwrap_or([0.0; 12]);
+                let stress = |n: f64, my: f64, mz: f64| {
+                    n.abs() / section.a + my.abs() * depth / 2.0 / section.iy + mz.abs() * width / 2.0 / section.iz
+                };
+                [stress(f[0], f[4], f[5]), stress(f[6], f[10], f[11])]
+            }
+        };
+
+        let base = members.positions.len() as u32;
+        for (end, value) in [(position(&member.i_node), end_values[0]), (position(&member.j_node), end_values[1])] {
+            for (sy, sz) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                let corner = std::array::from_fn(|k| {
+                    end[k] + sy * width / 2.0 * local_y[k] + sz * depth / 2.0 * local_z[k]
+                });
+                members.vertex(corner, value);
+            }
+        }
+        // Four sides and two end caps, wound outwards
+        for [a, b, c, d] in [[0, 1, 5, 4], [1, 2, 6, 5], [2, 3, 7, 6], [3, 0, 4, 7], [0, 3, 2, 1], [4, 5, 6, 7]] {
+            members.indices.extend([a, b, c, a, c, d].map(|k| base + k));
+        }
+    }
+
+    let mut shells = Mesh::default();
+    let corner_deflections = |nodes: [&String; 4]| nodes.map(|n| deflection(n));
+    for plate in model.plates.values() {
+        let nodes = [&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node];
+        let values = match options.field {
+            ResultField::None => [0.0; 4],
+            ResultField::Deflection => corner_deflections(nodes),
+            ResultField::VonMises => [combo.and_then(|c| plate.von_mises(c)).unwrap_or(0.0); 4],
+        };
+        add_shell(&mut shells, nodes.map(|n| position(n)), values);
+    }
+    for quad in model.quads.values() {
+        let nodes = [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node];
+        let values = match options.field {
+            ResultField::None => [0.0; 4],
+            ResultField::Deflection => corner_deflections(nodes),
+            ResultField::VonMises => combo.and_then(|c| quad.stress(c))
+                .map_or([0.0; 4], |s| [&s.i_corner, &s.j_corner, &s.m_corner, &s.n_corner].map(|s| s.von_mises())),
+        };
+        add_shell(&mut shells, nodes.map(|n| position(n)), values);
+    }
+
+    let (min, max) = members.values.iter()
+        .chain(&shells.values)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    let range = if min <= max { [min, max] } else { [0.0, 0.0] };
+
+    let mut document = GltfWriter::default();
+    let mut scene_nodes = Vec::new();
+    for (name, mesh) in [("Members", &members), ("Shells", &shells)] {
+        if !mesh.indices.is_empty() {
+            scene_nodes.push(document.add_mesh(name, mesh, range, options.field != ResultField::None));
+        }
+    }
+    Ok(document.finish(scene_nodes, json!({
+        "field": options.field.name(),
+        "combo": combo,
+        "min": range[0],
+        "max": range[1],
+    })))
+}
+
+/// Two triangles (i, j, m) and (i, m, n) for a four-node shell
+fn add_shell(mesh: &mut Mesh, corners: [[f64; 3]; 4], values: [f64; 4]) {
+    let [i, j, m, n] = [0, 1, 2, 3].map(|k| mesh.vertex(corners[k], values[k]));
+    mesh.indices.extend([i, j, m, i, m, n]);
+}
+
+/// Blue, cyan, green, yellow, red colour scale for `t` in [0, 1]
+fn color_scale(t: f64) -> [f32; 3] {
+    const STOPS: [[f64; 3]; 5] = [
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ];
+    let x = t.clamp(0.0, 1.0) * 4.0;
+    let k = (x.floor() as usize).min(3);
+    let f = x - k as f64;
+    std::array::from_fn(|c| (STOPS[k][c] + f * (STOPS[k + 1][c] - STOPS[k][c])) as f32)
+}
+
+/// Accumulates buffer views, accessors and meshes of a glTF document
+#[derive(Default)]
+struct GltfWriter {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    meshes: Vec<Value>,
+    nodes: Vec<Value>,
+}
+
+impl GltfWriter {
+    const FLOAT: u32 = 5126;
+    const UNSIGNED_INT: u32 = 5125;
+    const ARRAY_BUFFER: u32 = 34962;
+    const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+    /// Append raw little-endian data as a buffer view and describe it with an accessor
+    fn add_accessor(&mut self, bytes: Vec<u8>, target: u32, accessor: Value) -> usize {
+        while !self.buffer.len().is_multiple_of(4) {
+            self.buffer.push(0);
+        }
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": bytes.len(),
+            "target": target,
+        }));
+        self.buffer.extend(bytes);
+
+        let mut accessor = accessor;
+        accessor["bufferView"] = json!(self.buffer_views.len() - 1);
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn add_floats(&mut self, data: &[f32], kind: &str, extra: Value) -> usize {
+        let components = if kind == "VEC3" { 3 } else { 1 };
+        let bytes = data.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut accessor = json!({
+            "componentType": Self::FLOAT,
+            "count": data.len() / components,
+            "type": kind,
+        });
+        if let (Value::Object(accessor), Value::Object(extra)) = (&mut accessor, extra) {
+            accessor.extend(extra);
+        }
+        self.add_accessor(bytes, Self::ARRAY_BUFFER, accessor)
+    }
+
+    /// Add a mesh and a scene node showing it, returning the node index
+    fn add_mesh(&mut self, name: &str, mesh: &Mesh, range: [f64; 2], colored: bool) -> usize {
+        let positions: Vec<f32> = mesh.positions.iter().flatten().map(|&v| v as f32).collect();
+        let (lo, hi) = (0..3).fold(([f32::MAX; 3], [f32::MIN; 3]), |(mut lo, mut hi), k| {
+            for p in positions.chunks(3) {
+                lo[k] = lo[k].min(p[k]);
+                hi[k] = hi[k].max(p[k]);
+            }
+            (lo, hi)
+        });
+        let position = self.add_floats(&positions, "VEC3", json!({ "min": lo, "max": hi }));
+
+        let span = range[1] - range[0];
+        let colors: Vec<f32> = mesh.values.iter()
+            .flat_map(|&v| match colored {
+                true => color_scale(if span > 0.0 { (v - range[0]) / span } else { 0.0 }),
+                false => [0.7; 3],
+            })
+            .collect();
+        let color = self.add_floats(&colors, "VEC3", json!({}));
+        let values: Vec<f32> = mesh.values.iter().map(|&v| v as f32).collect();
+        let value = self.add_floats(&values, "SCALAR", json!({}));
+
+        let indices = mesh.indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let indices = self.add_accessor(indices, Self::ELEMENT_ARRAY_BUFFER, json!({
+            "componentType": Self::UNSIGNED_INT,
+            "count": mesh.indices.len(),
+            "type": "SCALAR",
+        }));
+
+        self.meshes.push(json!({
+            "name": name,
+            "primitives": [{
+                "attributes": { "POSITION": position, "COLOR_0": color, "_VALUE": value },
+                "indices": indices,
+                "material": 0,
+            }],
+        }));
+        self.nodes.push(json!({ "name": name, "mesh": self.meshes.len() - 1 }));
+        self.nodes.len() - 1
+    }
+
+    fn finish(self, scene_nodes: Vec<usize>, extras: Value) -> String {
+        let data = base64::engine::general_purpose::STANDARD.encode(&self.buffer);
+        let document = json!({
+            "asset": { "version": "2.0", "generator": "fea-solver" },
+            "scene": 0,
+            "scenes": [{ "nodes": scene_nodes, "extras": extras }],
+            "nodes": self.nodes,
+            "meshes": self.meshes,
+            "materials": [{
+                "name": "Result",
+                "doubleSided": true,
+                "pbrMetallicRoughness": { "baseColorFactor": [1.0, 1.0, 1.0, 1.0], "metallicFactor": 0.0, "roughnessFactor": 0.8 },
+            }],
+            "accessors": self.accessors,
+            "bufferViews": self.buffer_views,
+            "buffers": [{
+                "byteLength": self.buffer.len(),
+                "uri": format!("data:application/octet-stream;base64,{data}"),
+            }],
+        });
+        document.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    fn decode_floats(document: &Value, accessor: usize) -> Vec<f32> {
+        let uri = document["buffers"][0]["uri"].as_str().unwrap();
+        let buffer = base64::engine::general_purpose::STANDARD
+            .decode(uri.split_once(',').unwrap().1)
+            .unwrap();
+        let accessor = &document["accessors"][accessor];
+        let view = &document["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let length = view["byteLength"].as_u64().unwrap() as usize;
+        buffer[offset..offset + length]
+            .chunks(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_export_deflection_field() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for (name, x, z) in [("A", 0.0, 0.0), ("B", 4.0, 0.0), ("C", 4.0, 3.0), ("D", 0.0, 3.0)] {
+            model.add_node(name, Node::new(x, 0.0, z)).unwrap();
+        }
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_quad("Q1", Quad::new("A", "B", "C", "D", 0.2, "Steel")).unwrap();
+        for node in ["A", "D"] {
+            model.add_support(node, Support::fixed()).unwrap();
+        }
+        model.add_node_load("B", NodeLoad::fy(-5000.0, "Live")).unwrap();
+        model.add_load_combo(LoadCombination::single("L", "Live")).unwrap();
+        model.analyze_linear().unwrap();
+
+        let options = GltfOptions::new().with_field(ResultField::Deflection, "L");
+        let document: Value = serde_json::from_str(&export_gltf(&model, &options).unwrap()).unwrap();
+        assert_eq!(document["asset"]["version"], "2.0");
+        assert_eq!(document["meshes"].as_array().unwrap().len(), 2);
+
+        // Box of 8 vertices for the member; its j end carries B's deflection
+        let attributes = &document["meshes"][0]["primitives"][0]["attributes"];
+        let positions = decode_floats(&document, attributes["POSITION"].as_u64().unwrap() as usize);
+        let values = decode_floats(&document, attributes["_VALUE"].as_u64().unwrap() as usize);
+        assert_eq!(positions.len(), 24);
+        let b = model.node_displacement("B", "L").unwrap().translation_magnitude();
+        assert_relative_eq!(values[7] as f64, b, max_relative = 1e-6);
+        assert_relative_eq!(document["scenes"][0]["extras"]["max"].as_f64().unwrap(), b);
+        // Rectangular 0.2 wide by 0.4 deep: local y is global Y, local z is global Z
+        assert_relative_eq!((positions[1] - positions[4]).abs(), 0.2, epsilon = 1e-6);
+        assert_relative_eq!((positions[8] - positions[2]).abs(), 0.4, epsilon = 1e-6);
+
+        let unsolved = GltfOptions::new().with_field(ResultField::VonMises, "Missing");
+        assert!(matches!(export_gltf(&model, &unsolved), Err(FEAError::LoadCombinationNotFound(_))));
+        assert!(export_gltf(&model, &GltfOptions::new()).is_ok());
+    }
+}
@@ -0,0 +1,1086 @@
+//! IFC structural analysis model import and export
+//!
+//! Reads and writes the IFC4 structural analysis view, as produced by BIM
+//! tools such as Tekla Structures and Revit:
+//!
+//! | Solver                      | IFC                                                       |
+//! |-----------------------------|-----------------------------------------------------------|
+//! | Node and `Support`          | `IfcStructuralPointConnection` with `IfcBoundaryNodeCondition` |
+//! | `Member`                    | `IfcStructuralCurveMember` (its `Axis` is the local z axis) |
+//! | `MemberReleases`            | `IfcRelConnectsStructuralMember` conditions (`.F.` = released) |
+//! | `Plate`, `Quad`             | `IfcStructuralSurfaceMember` (shell) with four corners    |
+//! | `Material`                  | `IfcMaterial` with `Pset_MaterialMechanical`, `Pset_MaterialCommon` and `Pset_MaterialSteel` |
+//! | `Section`                   | profile with `Pset_ProfileMechanical` (a rectangle profile alone also works) |
+//! | `NodeLoad`, `PointLoad`     | `IfcStructuralPointAction` with `IfcStructuralLoadSingleForce` |
+//! | `DistributedLoad`           | `IfcStructuralCurveAction` (constant, or linear between two locations) |
+//! | `PlateLoad` (uniform)       | `IfcStructuralPlanarAction` in local z                    |
+//! | Load cases and combinations | `IfcStructuralLoadGroup`, with `IfcRelAssignsToGroupByFactor` factors |
+//!
+//! Values are in SI base units (m, N, Pa, kg); files with prefixed length,
+//! force or pressure units are rejected rather than rescaled. Spring
+//! supports, enforced displacements and other features with no mapping above
+//! are rejected on export so that a round trip never silently changes the
+//! model. A file with load cases but no combinations gets one combination per
+//! case on import.
+//!
+//! With the `attributes` feature, imported nodes, members and surfaces keep
+//! their IFC GlobalId in a `guid` attribute, and export writes it back.
+//! Other instances get a GlobalId hashed from their entity and name (or,
+//! without a name, their arguments), so exporting an edited model leaves
+//! the ids of unchanged items as they were.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use super::step::{self, Arg, Instance};
+use crate::elements::{Material, Member, Node, Plate, Quad, Section, Support};
+use crate::error::{FEAError, FEAResult};
+use crate::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad, PressureDistribution};
+use crate::math;
+use crate::model::FEModel;
+
+/// Material property sets written and read, as (property set, property, measure type)
+const MATERIAL_PROPERTIES: [(&str, &str, &str); 6] = [
+    ("Pset_MaterialMechanical", "YoungModulus", "IFCMODULUSOFELASTICITYMEASURE"),
+    ("Pset_MaterialMechanical", "ShearModulus", "IFCMODULUSOFELASTICITYMEASURE"),
+    ("Pset_MaterialMechanical", "PoissonRatio", "IFCPOSITIVERATIOMEASURE"),
+    ("Pset_MaterialMechanical", "ThermalExpansionCoefficient", "IFCTHERMALEXPANSIONCOEFFICIENTMEASURE"),
+    ("Pset_MaterialCommon", "MassDensity", "IFCMASSDENSITYMEASURE"),
+    ("Pset_MaterialSteel", "YieldStress", "IFCPRESSUREMEASURE"),
+];
+
+/// Section properties of `Pset_ProfileMechanical`, in `Section::new` order
+const PROFILE_PROPERTIES: [(&str, &str); 4] = [
+    ("CrossSectionArea", "IFCAREAMEASURE"),
+    ("MomentOfInertiaY", "IFCMOMENTOFINERTIAMEASURE"),
+    ("MomentOfInertiaZ", "IFCMOMENTOFINERTIAMEASURE"),
+    ("TorsionalConstantX", "IFCMOMENTOFINERTIAMEASURE"),
+];
+
+/// Load direction and its component in IFC's [Fx, Fy, Fz, Mx, My, Mz] order
+fn direction_component(direction: LoadDirection) -> (usize, bool) {
+    match direction {
+        LoadDirection::Fx => (0, true),
+        LoadDirection::Fy => (1, true),
+        LoadDirection::Fz => (2, true),
+        LoadDirection::Mx => (3, true),
+        LoadDirection::My => (4, true),
+        LoadDirection::Mz => (5, true),
+        LoadDirection::FX => (0, false),
+        LoadDirection::FY => (1, false),
+        LoadDirection::FZ => (2, false),
+    }
+}
+
+/// Load direction of an IFC component, if the solver has one
+fn component_direction(component: usize, local: bool) -> Option<LoadDirection> {
+    use LoadDirection::*;
+    match (component, local) {
+        (0, true) => Some(Fx),
+        (1, true) => Some(Fy),
+        (2, true) => Some(Fz),
+        (3, true) => Some(Mx),
+        (4, true) => Some(My),
+        (5, true) => Some(Mz),
+        (0, false) => Some(FX),
+        (1, false) => Some(FY),
+        (2, false) => Some(FZ),
+        _ => None,
+    }
+}
+
+fn null() -> String {
+    "$".to_string()
+}
+
+fn boolean(value: bool) -> String {
+    format!("IFCBOOLEAN({})", if value { ".T." } else { ".F." })
+}
+
+fn components(values: [f64; 6]) -> Vec<String> {
+    values.iter().map(|&v| step::real(v)).collect()
+}
+
+/// Attribute holding the GlobalId of an imported node, member or surface
+pub const GUID_ATTRIBUTE: &str = "guid";
+
+/// GlobalId an element was imported with, if the build keeps attributes
+macro_rules! imported_guid {
+    ($element:expr) => {{
+        #[cfg(feature = "attributes")]
+        let guid = $element.attributes.get(GUID_ATTRIBUTE).and_then(serde_json::Value::as_str);
+        #[cfg(not(feature = "attributes"))]
+        let guid: Option<&str> = None;
+        guid
+    }};
+}
+
+/// 128-bit FNV-1a hash of strings, each followed by a zero byte
+fn fnv1a(parts: &[&str]) -> u128 {
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    let mut hash = OFFSET;
+    for part in parts {
+        for &byte in part.as_bytes().iter().chain(&[0]) {
+            hash ^= byte as u128;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+/// Compressed 22 character form of a 128-bit IFC GlobalId
+fn compress_guid(value: u128) -> String {
+    const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_$";
+    let mut id = String::with_capacity(22);
+    id.push(CHARS[(value >> 126) as usize] as char);
+    for k in (0..21).rev() {
+        id.push(CHARS[((value >> (6 * k)) & 63) as usize] as char);
+    }
+    id
+}
+
+/// Writes IFC instances with unique, reproducible global ids
+#[derive(Default)]
+struct IfcWriter {
+    step: step::Writer,
+    guids: HashSet<String>,
+}
+
+impl IfcWriter {
+    fn add(&mut self, entity: &str, args: &[String]) -> u64 {
+        self.step.add(entity, args)
+    }
+
+    /// GlobalId of a rooted instance: the imported one while it is unique,
+    /// otherwise hashed from the entity and `key`, rehashed on a collision
+    fn guid(&mut self, entity: &str, key: &str, imported: Option<&str>) -> String {
+        let id = match imported.filter(|id| id.len() == 22 && !self.guids.contains(*id)) {
+            Some(id) => id.to_string(),
+            None => (0u32..)
+                .map(|salt| compress_guid(fnv1a(&[entity, key, &salt.to_string()])))
+                .find(|id| !self.guids.contains(id))
+                .unwrap(),
+        };
+        self.guids.insert(id.clone());
+        step::string(&id)
+    }
+
+    /// Rooted instance: GlobalId, OwnerHistory, Name, Description, then `rest`
+    fn rooted(&mut self, entity: &str, name: Option<&str>, rest: &[String]) -> u64 {
+        self.rooted_with_guid(entity, name, None, rest)
+    }
+
+    /// Rooted instance keeping an imported GlobalId
+    fn rooted_with_guid(&mut self, entity: &str, name: Option<&str>, guid: Option<&str>, rest: &[String]) -> u64 {
+        let guid = match name {
+            Some(name) => self.guid(entity, name, guid),
+            None => self.guid(entity, &rest.join(","), guid),
+        };
+        let mut args = vec![guid, null(), name.map_or_else(null, step::string), null()];
+        args.extend_from_slice(rest);
+        self.add(entity, &args)
+    }
+
+    fn property(&mut self, name: &str, measure: &str, value: f64) -> u64 {
+        self.add("IFCPROPERTYSINGLEVALUE", &[step::string(name), null(), format!("{measure}({})", step::real(value)), null()])
+    }
+
+    /// Topology representation of a single item, wrapped in a product shape
+    fn shape(&mut self, context: u64, kind: &str, item: u64) -> u64 {
+        let representation = self.add("IFCTOPOLOGYREPRESENTATION", &[
+            step::reference(context),
+            step::string("Reference"),
+            step::string(kind),
+            step::ref_list(&[item]),
+        ]);
+        self.add("IFCPRODUCTDEFINITIONSHAPE", &[null(), null(), step::ref_list(&[representation])])
+    }
+
+    fn vertex(&mut self, coords: [f64; 3]) -> u64 {
+        let point = self.add("IFCCARTESIANPOINT", &[step::list(coords.map(step::real))]);
+        self.add("IFCVERTEXPOINT", &[step::reference(point)])
+    }
+}
+
+/// Export a model as an IFC4 structural analysis model
+pub fn export_ifc(model: &FEModel) -> FEAResult<String> {
+    let unsupported = unsupported_features(model);
+    if !unsupported.is_empty() {
+        return Err(FEAError::InvalidInput(format!(
+            "model uses features with no IFC mapping: {}",
+            unsupported.join(", "),
+        )));
+    }
+
+    let mut ifc = IfcWriter::default();
+
+    // Project, context and SI units
+    let origin = ifc.add("IFCCARTESIANPOINT", &[step::list([0.0; 3].map(step::real))]);
+    let placement = ifc.add("IFCAXIS2PLACEMENT3D", &[step::reference(origin), null(), null()]);
+    let context = ifc.add("IFCGEOMETRICREPRESENTATIONCONTEXT", &[
+        null(),
+        step::string("Model"),
+        "3".to_string(),
+        step::real(1e-5),
+        step::reference(placement),
+        null(),
+    ]);
+    let units: Vec<u64> = [
+        ("LENGTHUNIT", "$", "METRE"),
+        ("FORCEUNIT", "$", "NEWTON"),
+        ("PRESSUREUNIT", "$", "PASCAL"),
+        ("MASSUNIT", ".KILO.", "GRAM"),
+        ("PLANEANGLEUNIT", "$", "RADIAN"),
+        ("THERMODYNAMICTEMPERATUREUNIT", "$", "KELVIN"),
+    ]
+    .into_iter()
+    .map(|(kind, prefix, name)| ifc.add("IFCSIUNIT", &["*".to_string(), format!(".{kind}."), prefix.to_string(), format!(".{name}.")]))
+    .collect();
+    let units = ifc.add("IFCUNITASSIGNMENT", &[step::ref_list(&units)]);
+    let project = ifc.rooted("IFCPROJECT", Some("fea-solver model"), &[
+        null(),
+        null(),
+        null(),
+        step::ref_list(&[context]),
+        step::reference(units),
+    ]);
+
+    // Materials with their property sets
+    let mut materials = BTreeMap::new();
+    for (name, material) in &model.materials {
+        let id = ifc.add("IFCMATERIAL", &[step::string(name), null(), null()]);
+        let values = [Some(material.e), Some(material.g), Some(material.nu), Some(material.alpha), Some(material.rho), material.fy];
+        let mut sets: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+        for ((set, property, measure), value) in MATERIAL_PROPERTIES.into_iter().zip(values) {
+            if let Some(value) = value {
+                sets.entry(set).or_default().push(ifc.property(property, measure, value));
+            }
+        }
+        for (set, properties) in sets {
+            ifc.add("IFCMATERIALPROPERTIES", &[step::string(set), null(), step::ref_list(&properties), step::reference(id)]);
+        }
+        materials.insert(name.as_str(), id);
+    }
+
+    // Profiles with their mechanical properties
+    let mut profiles = BTreeMap::new();
+    for (name, section) in &model.sections {
+        let id = ifc.add("IFCPROFILEDEF", &[".AREA.".to_string(), step::string(name)]);
+        let properties: Vec<u64> = PROFILE_PROPERTIES.into_iter()
+            .zip([section.a, section.iy, section.iz, section.j])
+            .map(|((property, measure), value)| ifc.property(property, measure, value))
+            .collect();
+        ifc.add("IFCPROFILEPROPERTIES", &[
+            step::string("Pset_ProfileMechanical"),
+            null(),
+            step::ref_list(&properties),
+            step::reference(id),
+        ]);
+        profiles.insert(name.as_str(), id);
+    }
+
+    // Point connections
+    let mut nodes = BTreeMap::new();
+    let mut vertices = BTreeMap::new();
+    for (name, node) in &model.nodes {
+        let vertex = ifc.vertex(node.coords());
+        let shape = ifc.shape(context, "Vertex", vertex);
+        let condition = match model.supports.get(name) {
+            Some(s) => {
+                let restraints = [s.dx, s.dy, s.dz, s.rx, s.ry, s.rz].map(boolean);
+                let mut args = vec![null()];
+                args.extend(restraints);
+                step::reference(ifc.add("IFCBOUNDARYNODECONDITION", &args))
+            }
+            None => null(),
+        };
+        let id = ifc.rooted_with_guid("IFCSTRUCTURALPOINTCONNECTION", Some(name), imported_guid!(node), &[
+            null(),
+            null(),
+            step::reference(shape),
+            condition,
+            null(),
+        ]);
+        nodes.insert(name.as_str(), id);
+        vertices.insert(name.as_str(), vertex);
+    }
+
+    // Curve members, connected to their end nodes
+    let mut members = BTreeMap::new();
+    let mut profile_sets: BTreeMap<(&str, &str), Vec<u64>> = BTreeMap::new();
+    for (name, member) in &model.members {
+        let (i, j) = (&model.nodes[&member.i_node], &model.nodes[&member.j_node]);
+        let edge = ifc.add("IFCEDGE", &[step::reference(vertices[member.i_node.as_str()]), step::reference(vertices[member.j_node.as_str()])]);
+        let shape = ifc.shape(context, "Edge", edge);
+        let t = math::member_transformation_matrix(&i.coords(), &j.coords(), member.rotation);
+        let axis = ifc.add("IFCDIRECTION", &[step::list([t[(2, 0)], t[(2, 1)], t[(2, 2)]].map(step::real))]);
+        let id = ifc.rooted_with_guid("IFCSTRUCTURALCURVEMEMBER", Some(name), imported_guid!(member), &[
+            null(),
+            null(),
+            step::reference(shape),
+            ".RIGID_JOINED_MEMBER.".to_string(),
+            step::reference(axis),
+        ]);
+        for (node, releases) in [(&member.i_node, member.releases.i_node), (&member.j_node, member.releases.j_node)] {
+            let condition = if releases.iter().any(|&r| r) {
+                let mut args = vec![null()];
+                args.extend(releases.map(|released| boolean(!released)));
+                step::reference(ifc.add("IFCBOUNDARYNODECONDITION", &args))
+            } else {
+                null()
+            };
+            ifc.rooted("IFCRELCONNECTSSTRUCTURALMEMBER", None, &[
+                step::reference(id),
+                step::reference(nodes[node.as_str()]),
+                condition,
+                null(),
+                null(),
+                null(),
+            ]);
+        }
+        profile_sets.entry((&member.material, &member.section)).or_default().push(id);
+        members.insert(name.as_str(), id);
+    }
+    for ((material, section), used_by) in profile_sets {
+        let profile = ifc.add("IFCMATERIALPROFILE", &[
+            null(),
+            null(),
+            step::reference(materials[material]),
+            step::reference(profiles[section]),
+            null(),
+            null(),
+        ]);
+        let set = ifc.add("IFCMATERIALPROFILESET", &[step::string(&format!("{material} {section}")), null(), step::ref_list(&[profile]), null()]);
+        ifc.rooted("IFCRELASSOCIATESMATERIAL", None, &[step::ref_list(&used_by), step::reference(set)]);
+    }
+
+    // Surface members, connected to their corner nodes
+    let mut surfaces = BTreeMap::new();
+    let mut surface_materials: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    let shells = model.plates.iter()
+        .map(|(name, p)| (name, "Plate", [&p.i_node, &p.j_node, &p.m_node, &p.n_node], p.thickness, &p.material, imported_guid!(p)))
+        .chain(model.quads.iter()
+            .map(|(name, q)| (name, "Quad", [&q.i_node, &q.j_node, &q.m_node, &q.n_node], q.thickness, &q.material, imported_guid!(q))));
+    for (name, kind, corners, thickness, material, guid) in shells {
+        let oriented: Vec<u64> = (0..4)
+            .map(|k| {
+                let (a, b) = (vertices[corners[k].as_str()], vertices[corners[(k + 1) % 4].as_str()]);
+                let edge = ifc.add("IFCEDGE", &[step::reference(a), step::reference(b)]);
+                ifc.add("IFCORIENTEDEDGE", &["*".to_string(), "*".to_string(), step::reference(edge), ".T.".to_string()])
+            })
+            .collect();
+        let lp = ifc.add("IFCEDGELOOP", &[step::ref_list(&oriented)]);
+        let bound = ifc.add("IFCFACEOUTERBOUND", &[step::reference(lp), ".T.".to_string()]);
+        let face = ifc.add("IFCFACE", &[step::ref_list(&[bound])]);
+        let shape = ifc.shape(context, "Face", face);
+        let id = ifc.rooted_with_guid("IFCSTRUCTURALSURFACEMEMBER", Some(name), guid, &[
+            step::string(kind),
+            null(),
+            step::reference(shape),
+            ".SHELL.".to_string(),
+            step::real(thickness),
+        ]);
+        for node in corners {
+            ifc.rooted("IFCRELCONNECTSSTRUCTURALMEMBER", None, &[
+                step::reference(id),
+                step::reference(nodes[node.as_str()]),
+                null(),
+                null(),
+                null(),
+                null(),
+            ]);
+        }
+        surface_materials.entry(material).or_default().push(id);
+        surfaces.insert(name.as_str(), id);
+    }
+    for (material, used_by) in surface_materials {
+        ifc.rooted("IFCRELASSOCIATESMATERIAL", None, &[step::ref_list(&used_by), step::reference(materials[material])]);
+    }
+
+    // Actions, collected per load case
+    let mut actions: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    let mut add_action = |ifc: &mut IfcWriter, entity: &str, target: u64, shape: String, load: u64, local: bool, extra: &[String], case: &str| {
+        let mut args = vec![
+            null(),
+            null(),
+            shape,
+            step::reference(load),
+            if local { ".LOCAL_COORDS." } else { ".GLOBAL_COORDS." }.to_string(),
+            ".F.".to_string(),
+        ];
+        args.extend_from_slice(extra);
+        let action = ifc.rooted(entity, None, &args);
+        ifc.rooted("IFCRELCONNECTSSTRUCTURALACTIVITY", None, &[step::reference(target), step::reference(action)]);
+        actions.entry(case.to_string()).or_default().push(action);
+    };
+    for (node, loads) in &model.node_loads {
+        for load in loads {
+            let mut args = vec![null()];
+            args.extend(components(load.as_array()));
+            let force = ifc.add("IFCSTRUCTURALLOADSINGLEFORCE", &args);
+            let shape = step::reference(ifc.shape(context, "Vertex", vertices[node.as_str()]));
+            add_action(&mut ifc, "IFCSTRUCTURALPOINTACTION", nodes[node.as_str()], shape, force, false, &[], &load.case);
+        }
+    }
+    for (name, loads) in &model.member_point_loads {
+        let member = &model.members[name];
+        let (i, j) = (model.nodes[&member.i_node].coords(), model.nodes[&member.j_node].coords());
+        let length = model.nodes[&member.i_node].distance_to(&model.nodes[&member.j_node]);
+        for load in loads {
+            let (component, local) = direction_component(load.direction);
+            let mut values = [0.0; 6];
+            values[component] = load.magnitude;
+            let mut args = vec![null()];
+            args.extend(components(values));
+            let force = ifc.add("IFCSTRUCTURALLOADSINGLEFORCE", &args);
+            let at = std::array::from_fn(|k| i[k] + (j[k] - i[k]) * load.position / length);
+            let vertex = ifc.vertex(at);
+            let shape = step::reference(ifc.shape(context, "Vertex", vertex));
+            add_action(&mut ifc, "IFCSTRUCTURALPOINTACTION", members[name.as_str()], shape, force, local, &[], &load.case);
+        }
+    }
+    for (name, loads) in &model.member_dist_loads {
+        let member = &model.members[name];
+        let length = model.nodes[&member.i_node].distance_to(&model.nodes[&member.j_node]);
+        for load in loads {
+            let (component, local) = direction_component(load.direction);
+            let linear_force = |ifc: &mut IfcWriter, w: f64| {
+                let mut values = [0.0; 6];
+                values[component] = w;
+                let mut args = vec![null()];
+                args.extend(components(values));
+                ifc.add("IFCSTRUCTURALLOADLINEARFORCE", &args)
+            };
+            let x2 = load.x2.min(length);
+            let full_length = load.x1 == 0.0 && x2 == length;
+            let (applied, kind) = if load.is_uniform() && full_length {
+                (linear_force(&mut ifc, load.w1), ".CONST.")
+            } else {
+                let (start, end) = (linear_force(&mut ifc, load.w1), linear_force(&mut ifc, load.w2));
+                let locations = step::list([load.x1, x2].map(|x| step::list([step::real(x)])));
+                (ifc.add("IFCSTRUCTURALLOADCONFIGURATION", &[null(), step::ref_list(&[start, end]), locations]), ".LINEAR.")
+            };
+            add_action(&mut ifc, "IFCSTRUCTURALCURVEACTION", members[name.as_str()], null(), applied, local, &[
+                ".TRUE_LENGTH.".to_string(),
+                kind.to_string(),
+            ], &load.case);
+        }
+    }
+    for (name, loads) in &model.plate_loads {
+        for load in loads {
+            let pressure = ifc.add("IFCSTRUCTURALLOADPLANARFORCE", &[null(), step::real(0.0), step::real(0.0), step::real(load.pressure)]);
+            add_action(&mut ifc, "IFCSTRUCTURALPLANARACTION", surfaces[name.as_str()], null(), pressure, true, &[
+                ".TRUE_LENGTH.".to_string(),
+                ".CONST.".to_string(),
+            ], &load.case);
+        }
+    }
+
+    // Load cases and combinations
+    let cases: BTreeSet<&str> = actions.keys().map(String::as_str)
+        .chain(model.load_combos.values().flat_map(|c| c.factors.keys().map(String::as_str)))
+        .collect();
+    let load_group = |ifc: &mut IfcWriter, name: &str, kind: &str| {
+        ifc.rooted("IFCSTRUCTURALLOADGROUP", Some(name), &[
+            null(),
+            format!(".{kind}."),
+            ".NOTDEFINED.".to_string(),
+            ".NOTDEFINED.".to_string(),
+            null(),
+            null(),
+        ])
+    };
+    let mut groups = Vec::new();
+    let mut case_groups = BTreeMap::new();
+    for case in cases {
+        let group = load_group(&mut ifc, case, "LOAD_CASE");
+        if let Some(actions) = actions.get(case) {
+            ifc.rooted("IFCRELASSIGNSTOGROUP", None, &[step::ref_list(actions), null(), step::reference(group)]);
+        }
+        case_groups.insert(case, group);
+        groups.push(group);
+    }
+    for (name, combo) in &model.load_combos {
+        let group = load_group(&mut ifc, name, "LOAD_COMBINATION");
+        for (case, factor) in &combo.factors {
+            ifc.rooted("IFCRELASSIGNSTOGROUPBYFACTOR", None, &[
+                step::ref_list(&[case_groups[case.as_str()]]),
+                null(),
+                step::reference(group),
+                step::real(*factor),
+            ]);
+        }
+        groups.push(group);
+    }
+
+    // The analysis model groups the structural items and is loaded by the load groups
+    let analysis_model = ifc.rooted("IFCSTRUCTURALANALYSISMODEL", Some("Structural analysis model"), &[
+        null(),
+        ".LOADING_3D.".to_string(),
+        null(),
+        step::ref_list(&groups),
+        null(),
+        null(),
+    ]);
+    let items: Vec<u64> = nodes.values().chain(members.values()).chain(surfaces.values()).copied().collect();
+    ifc.rooted("IFCRELASSIGNSTOGROUP", None, &[step::ref_list(&items), null(), step::reference(analysis_model)]);
+    ifc.rooted("IFCRELDECLARES", None, &[step::reference(project), step::ref_list(&[analysis_model])]);
+
+    Ok(ifc.step.finish("ViewDefinition [StructuralAnalysisView]", "IFC4"))
+}
+
+/// Model features with no IFC mapping
+fn unsupported_features(model: &FEModel) -> Vec<String> {
+    let mut notes = Vec::new();
+    let kinds: [(&str, Vec<&String>); 9] = [
+        ("physical member", model.physical_members.keys().collect()),
+        ("tetra", model.tetras.keys().collect()),
+        ("rigid link", model.rigid_links.keys().collect()),
+        ("gap", model.gaps.keys().collect()),
+        ("link", model.links.keys().collect()),
+        ("prestress on member", model.member_prestress_loads.keys().collect()),
+        ("plate point load on", model.plate_point_loads.keys().collect()),
+        ("plate thermal load on", model.plate_thermal_loads.keys().collect()),
+        ("non-uniform pressure on", model.plate_loads.iter()
+            .filter(|(_, loads)| loads.iter().any(|l| l.distribution != PressureDistribution::Uniform))
+            .map(|(name, _)| name)
+            .collect()),
+    ];
+    for (kind, names) in kinds {
+        notes.extend(names.into_iter().map(|name| format!("{kind} {name}")));
+    }
+    for (name, support) in &model.supports {
+        if support.is_skewed() || support.one_way.iter().any(Option::is_some) {
+            notes.push(format!("skewed or one-way support at {name}"));
+        }
+        if support.enforced_displacements().iter().any(|d| d.is_some_and(|d| d != 0.0)) {
+            notes.push(format!("enforced displacement at {name}"));
+        }
+    }
+    notes
+}
+
+/// Instances of an IFC file with lookups along its relationships
+struct IfcReader {
+    instances: HashMap<u64, Instance>,
+}
+
+impl IfcReader {
+    fn get(&self, id: u64) -> FEAResult<&Instance> {
+        self.instances.get(&id)
+            .ok_or_else(|| FEAError::InvalidInput(format!("IFC instance #{id} not found")))
+    }
+
+    fn get_ref(&self, arg: &Arg) -> FEAResult<&Instance> {
+        let id = arg.as_ref().ok_or_else(|| FEAError::InvalidInput(format!("expected a reference, found {arg:?}")))?;
+        self.get(id)
+    }
+
+    /// Instances of an entity type in id order
+    fn all(&self, entity: &str) -> Vec<(u64, &Instance)> {
+        let mut found: Vec<(u64, &Instance)> = self.instances.iter()
+            .filter(|(_, instance)| instance.name == entity)
+            .map(|(&id, instance)| (id, instance))
+            .collect();
+        found.sort_by_key(|(id, _)| *id);
+        found
+    }
+
+    /// Name of a rooted instance, or a fallback from its id
+    fn name(&self, id: u64, instance: &Instance, prefix: &str) -> String {
+        instance.arg(2).as_str().map_or_else(|| format!("{prefix}{id}"), str::to_string)
+    }
+
+    fn point(&self, vertex: &Arg) -> FEAResult<[f64; 3]> {
+        let vertex = self.get_ref(vertex)?;
+        let point = self.get_ref(vertex.arg(0))?;
+        let coords: Vec<f64> = point.arg(0).as_list().iter().filter_map(Arg::as_f64).collect();
+        Ok([0, 1, 2].map(|k| coords.get(k).copied().unwrap_or(0.0)))
+    }
+
+    /// Items of a product's first topology representation
+    fn representation_items(&self, product: &Instance) -> FEAResult<Vec<u64>> {
+        let shape = self.get_ref(product.arg(6))?;
+        for representation in shape.arg(2).refs() {
+            let representation = self.get(representation)?;
+            if representation.name == "IFCTOPOLOGYREPRESENTATION" {
+                return Ok(representation.arg(3).refs());
+            }
+        }
+        Err(FEAError::InvalidInput("structural item has no topology representation".to_string()))
+    }
+
+    /// Named properties of the property sets that describe `target`
+    fn properties(&self, entity: &str, target: u64) -> FEAResult<HashMap<String, f64>> {
+        let mut values = HashMap::new();
+        for (_, set) in self.all(entity) {
+            if set.arg(3).as_ref() != Some(target) {
+                continue;
+            }
+            for property in set.arg(2).refs() {
+                let property = self.get(property)?;
+                if let (Some(name), Some(value)) = (property.arg(0).as_str(), property.arg(2).as_f64()) {
+                    values.insert(name.to_string(), value);
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    /// Six restraint flags of a boundary node condition; `$` is free
+    fn condition(&self, condition: &Arg, node: &str) -> FEAResult<Option<[bool; 6]>> {
+        if condition.as_ref().is_none() {
+            return Ok(None);
+        }
+        let condition = self.get_ref(condition)?;
+        let mut flags = [false; 6];
+        for (k, flag) in flags.iter_mut().enumerate() {
+            *flag = match condition.arg(k + 1) {
+                Arg::Null => false,
+                value => match value.as_enum() {
+                    Some("T") => true,
+                    Some("F") => false,
+                    _ => {
+                        return Err(FEAError::InvalidInput(format!(
+                            "spring condition at {node} is not supported"
+                        )))
+                    }
+                },
+            };
+        }
+        Ok(Some(flags))
+    }
+}
+
+/// Import an IFC4 structural analysis model
+pub fn import_ifc(input: &str) -> FEAResult<FEModel> {
+    let ifc = IfcReader { instances: step::parse(input)? };
+    let mut model = FEModel::new();
+
+    for (_, unit) in ifc.all("IFCSIUNIT") {
+        let kind = unit.arg(1).as_enum().unwrap_or("");
+        if matches!(kind, "LENGTHUNIT" | "FORCEUNIT" | "PRESSUREUNIT") && unit.arg(2) != &Arg::Null {
+            return Err(FEAError::InvalidInput(format!("{kind} must be an SI base unit")));
+        }
+    }
+
+    // Materials and profiles, by instance id
+    let mut material_names = HashMap::new();
+    for (id, material) in ifc.all("IFCMATERIAL") {
+        let name = material.arg(0).as_str().map_or_else(|| format!("MAT{id}"), str::to_string);
+        let p = ifc.properties("IFCMATERIALPROPERTIES", id)?;
+        if let Some(&e) = p.get("YoungModulus") {
+            let nu = p.get("PoissonRatio").copied().unwrap_or(0.3);
+            let g = p.get("ShearModulus").copied().unwrap_or(e / (2.0 * (1.0 + nu)));
+            let mut m = Material::new(e, g, nu, p.get("MassDensity").copied().unwrap_or(0.0));
+            if let Some(&alpha) = p.get("ThermalExpansionCoefficient") {
+                m = m.with_thermal_expansion(alpha);
+            }
+            if let Some(&fy) = p.get("YieldStress") {
+                m = m.with_yield_strength(fy);
+            }
+            model.add_material(&name, m)?;
+        }
+        material_names.insert(id, name);
+    }
+    let mut section_names: HashMap<u64, String> = HashMap::new();
+    let mut section_for = |profile: u64, model: &mut FEModel| -> FEAResult<String> {
+        if let Some(name) = section_names.get(&profile) {
+            return Ok(name.clone());
+        }
+        let instance = ifc.get(profile)?;
+        let name = instance.arg(1).as_str().map_or_else(|| format!("SEC{profile}"), str::to_string);
+        let p = ifc.properties("IFCPROFILEPROPERTIES", profile)?;
+        let section = match PROFILE_PROPERTIES.map(|(property, _)| p.get(property).copied()) {
+            [Some(a), Some(iy), Some(iz), Some(j)] => Section::new(a, iy, iz, j),
+            _ if instance.name == "IFCRECTANGLEPROFILEDEF" => {
+                // Profile x spans the member's local y (width), profile y its local z (depth)
+                let dims = [3, 4].map(|k| instance.arg(k).as_f64());
+                match dims {
+                    [Some(width), Some(depth)] => Section::rectangular(width, depth),
+                    _ => return Err(FEAError::InvalidInput(format!("rectangle profile {name} has no dimensions"))),
+                }
+            }
+            _ => return Err(FEAError::InvalidInput(format!("profile {name} has no Pset_ProfileMechanical"))),
+        };
+        model.add_section(&name, section)?;
+        section_names.insert(profile, name.clone());
+        Ok(name)
+    };
+
+    // Material (and profile) associated with each structural member
+    let mut associations: HashMap<u64, (u64, Option<u64>)> = HashMap::new();
+    for (_, rel) in ifc.all("IFCRELASSOCIATESMATERIAL") {
+        let mut relating = ifc.get_ref(rel.arg(5))?;
+        if relating.name == "IFCMATERIALPROFILESETUSAGE" {
+            relating = ifc.get_ref(relating.arg(0))?;
+        }
+        if relating.name == "IFCMATERIALPROFILESET" {
+            relating = ifc.get(relating.arg(2).refs().first().copied().unwrap_or(0))?;
+        }
+        if relating.name == "IFCMATERIALLAYERSETUSAGE" {
+            relating = ifc.get_ref(relating.arg(0))?;
+        }
+        if relating.name == "IFCMATERIALLAYERSET" {
+            relating = ifc.get(relating.arg(0).refs().first().copied().unwrap_or(0))?;
+        }
+        let association = match relating.name.as_str() {
+            "IFCMATERIALPROFILE" => (relating.arg(2).as_ref(), relating.arg(3).as_ref()),
+            "IFCMATERIALLAYER" => (relating.arg(0).as_ref(), None),
+            "IFCMATERIAL" => (rel.arg(5).as_ref(), None),
+            _ => (None, None),
+        };
+        if let (Some(material), profile) = association {
+            for object in rel.arg(4).refs() {
+                associations.insert(object, (material, profile));
+            }
+        }
+    }
+    let material_of = |id: u64, name: &str| -> FEAResult<(String, Option<u64>)> {
+        let (material, profile) = associations.get(&id)
+            .ok_or_else(|| FEAError::InvalidInput(format!("{name} has no associated material")))?;
+        let material = material_names.get(material)
+            .ok_or_else(|| FEAError::InvalidInput(format!("{name} has an unknown material")))?;
+        Ok((material.clone(), *profile))
+    };
+
+    // Point connections become nodes, with any support condition
+    let mut node_names: HashMap<u64, String> = HashMap::new();
+    let mut node_coords: Vec<([f64; 3], String)> = Vec::new();
+    for (id, connection) in ifc.all("IFCSTRUCTURALPOINTCONNECTION") {
+        let name = ifc.name(id, connection, "N");
+        let items = ifc.representation_items(connection)?;
+        let vertex = items.first().ok_or_else(|| FEAError::InvalidInput(format!("connection {name} has no vertex")))?;
+        let coords = ifc.point(&Arg::Ref(*vertex))?;
+        model.add_node(&name, Node::new(coords[0], coords[1], coords[2]))?;
+        if let Some([dx, dy, dz, rx, ry, rz]) = ifc.condition(connection.arg(7), &name)? {
+            if [dx, dy, dz, rx, ry, rz].iter().any(|&r| r) {
+                model.add_support(&name, Support::with_restraints(dx, dy, dz, rx, ry, rz))?;
+            }
+        }
+        node_names.insert(id, name.clone());
+        node_coords.push((coords, name));
+    }
+    let node_at = |vertex: &Arg| -> FEAResult<String> {
+        let p = ifc.point(vertex)?;
+        node_coords.iter()
+            .find(|(c, _)| (0..3).all(|k| (c[k] - p[k]).abs() < 1e-6))
+            .map(|(_, name)| name.clone())
+            .ok_or_else(|| FEAError::InvalidInput(format!("no point connection at {p:?}")))
+    };
+
+    // Curve members
+    let mut element_names: HashMap<u64, String> = HashMap::new();
+    for (id, instance) in ifc.all("IFCSTRUCTURALCURVEMEMBER") {
+        let name = ifc.name(id, instance, "M");
+        let edge = ifc.get(ifc.representation_items(instance)?.first().copied().unwrap_or(0))?;
+        let (i_node, j_node) = (node_at(edge.arg(0))?, node_at(edge.arg(1))?);
+        let (material, profile) = material_of(id, &name)?;
+        let profile = profile.ok_or_else(|| FEAError::InvalidInput(format!("member {name} has no profile")))?;
+        let section = section_for(profile, &mut model)?;
+        let mut member = Member::new(&i_node, &j_node, &material, &section);
+
+        if let Some(axis) = instance.arg(8).as_ref() {
+            let a: Vec<f64> = ifc.get(axis)?.arg(0).as_list().iter().filter_map(Arg::as_f64).collect();
+            let t = math::member_transformation_matrix(&model.nodes[&i_node].coords(), &model.nodes[&j_node].coords(), 0.0);
+            let dot = |row: usize| (0..3).map(|k| t[(row, k)] * a.get(k).copied().unwrap_or(0.0)).sum::<f64>();
+            // Axis is the local z axis: z = -y0 sin(r) + z0 cos(r)
+            member = member.with_rotation((-dot(1)).atan2(dot(2)));
+        }
+        model.add_member(&name, member)?;
+        element_names.insert(id, name);
+    }
+
+    // Surface members
+    for (id, instance) in ifc.all("IFCSTRUCTURALSURFACEMEMBER") {
+        let name = ifc.name(id, instance, "S");
+        let face = ifc.get(ifc.representation_items(instance)?.first().copied().unwrap_or(0))?;
+        let bound = ifc.get(face.arg(0).refs().first().copied().unwrap_or(0))?;
+        let edge_loop = ifc.get_ref(bound.arg(0))?;
+        let mut corners = Vec::new();
+        for oriented in edge_loop.arg(0).refs() {
+            let oriented = ifc.get(oriented)?;
+            let edge = ifc.get_ref(oriented.arg(2))?;
+            let forward = oriented.arg(3).as_enum() != Some("F");
+            corners.push(node_at(edge.arg(if forward { 0 } else { 1 }))?);
+        }
+        let [i, j, m, n]: [String; 4] = corners.try_into()
+            .map_err(|_| FEAError::InvalidInput(format!("surface member {name} must have four corners")))?;
+        let thickness = instance.arg(8).as_f64()
+            .ok_or_else(|| FEAError::InvalidInput(format!("surface member {name} has no thickness")))?;
+        let (material, _) = material_of(id, &name)?;
+        if instance.arg(4).as_str() == Some("Plate") {
+            model.add_plate(&name, Plate::new(&i, &j, &m, &n, thickness, &material))?;
+        } else {
+            model.add_quad(&name, Quad::new(&i, &j, &m, &n, thickness, &material))?;
+        }
+        element_names.insert(id, name);
+    }
+
+    #[cfg(feature = "attributes")]
+    keep_guids(&ifc, &mut model, &node_names, &element_names)?;
+
+    // Member end conditions as releases
+    for (_, rel) in ifc.all("IFCRELCONNECTSSTRUCTURALMEMBER") {
+        let (Some(member), Some(node)) = (rel.arg(4).as_ref(), rel.arg(5).as_ref()) else { continue };
+        let (Some(member), Some(node)) = (element_names.get(&member), node_names.get(&node)) else { continue };
+        let Some(member) = model.members.get_mut(member) else { continue };
+        if let Some(fixed) = ifc.condition(rel.arg(6), node)? {
+            let released = fixed.map(|f| !f);
+            if &member.i_node == node {
+                member.releases.i_node = released;
+            } else {
+                member.releases.j_node = released;
+            }
+        }
+    }
+
+    // Load cases and combinations
+    let mut cases = HashMap::new();
+    let mut combos = HashMap::new();
+    for (id, group) in ifc.all("IFCSTRUCTURALLOADGROUP") {
+        let name = ifc.name(id, group, "LC");
+        if group.arg(5).as_enum() == Some("LOAD_COMBINATION") {
+            combos.insert(id, LoadCombination::new(&name));
+        } else {
+            cases.insert(id, name);
+        }
+    }
+    for (_, rel) in ifc.all("IFCRELASSIGNSTOGROUPBYFACTOR") {
+        let Some(combo) = rel.arg(6).as_ref().and_then(|g| combos.get_mut(&g)) else { continue };
+        let factor = rel.arg(7).as_f64().unwrap_or(1.0);
+        for case in rel.arg(4).refs() {
+            if let Some(case) = cases.get(&case) {
+                let combined = combo.factor(case) + factor;
+                combo.factors.insert(case.clone(), combined);
+            }
+        }
+    }
+    let mut action_cases = HashMap::new();
+    for (_, rel) in ifc.all("IFCRELASSIGNSTOGROUP") {
+        if let Some(case) = rel.arg(6).as_ref().and_then(|g| cases.get(&g)) {
+            for action in rel.arg(4).refs() {
+                action_cases.insert(action, case.clone());
+            }
+        }
+    }
+
+    // Actions on nodes, members and surfaces
+    let mut used_cases = BTreeSet::new();
+    for (_, rel) in ifc.all("IFCRELCONNECTSSTRUCTURALACTIVITY") {
+        let (Some(target), Some(action_id)) = (rel.arg(4).as_ref(), rel.arg(5).as_ref()) else { continue };
+        let action = ifc.get(action_id)?;
+        let case = action_cases.get(&action_id)
+            .ok_or_else(|| FEAError::InvalidInput(format!("action #{action_id} is not in a load case")))?
+            .clone();
+        let load = ifc.get_ref(action.arg(7))?;
+        let local = action.arg(8).as_enum() == Some("LOCAL_COORDS");
+        let values = |load: &Instance| -> [f64; 6] { std::array::from_fn(|k| load.arg(k + 1).as_f64().unwrap_or(0.0)) };
+        let direction = |component: usize| {
+            component_direction(component, local).ok_or_else(|| {
+                FEAError::InvalidInput(format!("global moment in action #{action_id} is not supported on members"))
+            })
+        };
+
+        match (action.name.as_str(), load.name.as_str()) {
+            ("IFCSTRUCTURALPOINTACTION", "IFCSTRUCTURALLOADSINGLEFORCE") if node_names.contains_key(&target) => {
+                let [fx, fy, fz, mx, my, mz] = values(load);
+                model.add_node_load(&node_names[&target], NodeLoad::new(fx, fy, fz, mx, my, mz, &case))?;
+            }
+            ("IFCSTRUCTURALPOINTACTION", "IFCSTRUCTURALLOADSINGLEFORCE") => {
+                let member_name = element_names.get(&target)
+                    .ok_or_else(|| FEAError::InvalidInput(format!("action #{action_id} acts on an unknown item")))?;
+                let member = &model.members[member_name];
+                let vertex = ifc.representation_items(action)?.first().copied().unwrap_or(0);
+                let at = ifc.point(&Arg::Ref(vertex))?;
+                let position = model.nodes[&member.i_node].distance_to(&Node::new(at[0], at[1], at[2]));
+                let mut loads = Vec::new();
+                for (k, value) in values(load).into_iter().enumerate().filter(|(_, v)| *v != 0.0) {
+                    loads.push(PointLoad::new(value, position, direction(k)?, &case));
+                }
+                for load in loads {
+                    model.add_member_point_load(member_name, load)?;
+                }
+            }
+            ("IFCSTRUCTURALCURVEACTION" | "IFCSTRUCTURALLINEARACTION", _) => {
+                let member_name = element_names.get(&target)
+                    .ok_or_else(|| FEAError::InvalidInput(format!("action #{action_id} acts on an unknown item")))?;
+                let (w1, w2, x1, x2) = if load.name == "IFCSTRUCTURALLOADCONFIGURATION" {
+                    let ends = load.arg(1).refs();
+                    let locations: Vec<f64> = load.arg(2).as_list().iter()
+                        .filter_map(|l| l.as_list().first().and_then(Arg::as_f64))
+                        .collect();
+                    let (&[start, end], &[x1, x2]) = (ends.as_slice(), locations.as_slice()) else {
+                        return Err(FEAError::InvalidInput(format!("action #{action_id} must vary between two locations")));
+                    };
+                    (values(ifc.get(start)?), values(ifc.get(end)?), x1, x2)
+                } else {
+                    (values(load), values(load), 0.0, f64::INFINITY)
+                };
+                let mut loads = Vec::new();
+                for k in (0..6).filter(|&k| w1[k] != 0.0 || w2[k] != 0.0) {
+                    loads.push(DistributedLoad::new(w1[k], w2[k], x1, x2, direction(k)?, &case));
+                }
+                for load in loads {
+                    model.add_member_dist_load(member_name, load)?;
+                }
+            }
+            ("IFCSTRUCTURALPLANARACTION" | "IFCSTRUCTURALSURFACEACTION", "IFCSTRUCTURALLOADPLANARFORCE") => {
+                let surface = element_names.get(&target)
+                    .ok_or_else(|| FEAError::InvalidInput(format!("action #{action_id} acts on an unknown item")))?;
+                let [px, py, pz] = [1, 2, 3].map(|k| load.arg(k).as_f64().unwrap_or(0.0));
+                if !local || px != 0.0 || py != 0.0 {
+                    return Err(FEAError::InvalidInput(format!("action #{action_id}: only local z pressures are supported")));
+                }
+                model.add_plate_load(surface, PlateLoad::new(pz, &case))?;
+            }
+            (action, load) => {
+                return Err(FEAError::InvalidInput(format!("{action} with {load} is not supported")));
+            }
+        }
+        used_cases.insert(case);
+    }
+
+    if combos.is_empty() {
+        for case in used_cases {
+            model.add_load_combo(LoadCombination::single(&case, &case))?;
+        }
+    }
+    let mut combos: Vec<LoadCombination> = combos.into_values().collect();
+    combos.sort_by(|a, b| a.name.cmp(&b.name));
+    for combo in combos {
+        model.add_load_combo(combo)?;
+    }
+    Ok(model)
+}
+
+/// Keep the GlobalIds of imported nodes, members and surfaces as attributes
+#[cfg(feature = "attributes")]
+fn keep_guids(
+    ifc: &IfcReader,
+    model: &mut FEModel,
+    node_names: &HashMap<u64, String>,
+    element_names: &HashMap<u64, String>,
+) -> FEAResult<()> {
+    for (&id, name) in node_names.iter().chain(element_names) {
+        let instance = ifc.get(id)?;
+        let Some(guid) = instance.arg(0).as_str() else { continue };
+        let attributes = match instance.name.as_str() {
+            "IFCSTRUCTURALPOINTCONNECTION" => model.nodes.get_mut(name).map(|n| &mut n.attributes),
+            "IFCSTRUCTURALCURVEMEMBER" => model.members.get_mut(name).map(|m| &mut m.attributes),
+            _ => match model.plates.get_mut(name) {
+                Some(plate) => Some(&mut plate.attributes),
+                None => model.quads.get_mut(name).map(|q| &mut q.attributes),
+            },
+        };
+        let Some(attributes) = attributes else { continue };
+        attributes.insert(GUID_ATTRIBUTE.to_string(), guid.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    fn frame_and_slab() -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("S355", Material::steel().with_yield_strength(355e6)).unwrap();
+        model.add_section("HEA200", Section::new(5.38e-3, 3.69e-5, 1.34e-5, 2.1e-7)).unwrap();
+        for (name, x, y, z) in [("A", 0.0, 0.0, 0.0), ("B", 0.0, 3.0, 0.0), ("C", 5.0, 3.0, 0.0), ("D", 5.0, 3.0, 4.0), ("E", 0.0, 3.0, 4.0)] {
+            model.add_node(name, Node::new(x, y, z)).unwrap();
+        }
+        model.add_member("Col", Member::new("A", "B", "S355", "HEA200").with_rotation(0.3)).unwrap();
+        model.add_member("Beam", Member::new("B", "C", "S355", "HEA200").with_releases(MemberReleases::pin_j())).unwrap();
+        model.add_quad("Slab", Quad::new("B", "C", "D", "E", 0.2, "S355")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        for node in ["C", "D", "E"] {
+            model.add_support(node, Support::pinned()).unwrap();
+        }
+        model.add_node_load("B", NodeLoad::fx(12e3, "W")).unwrap();
+        model.add_member_point_load("Beam", PointLoad::new(-8e3, 2.0, LoadDirection::FY, "D")).unwrap();
+        model.add_member_dist_load("Beam", DistributedLoad::uniform_downward(3e3, "D")).unwrap();
+        model.add_member_dist_load("Col", DistributedLoad::new(1e3, 2e3, 0.5, 2.5, LoadDirection::Fz, "W")).unwrap();
+        model.add_plate_load("Slab", PlateLoad::new(-5e3, "D")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.35D+1.5W").with_case("D", 1.35).with_case("W", 1.5)).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_round_trip_gives_same_results() {
+        let mut model = frame_and_slab();
+        let ifc = export_ifc(&model).unwrap();
+        assert!(ifc.contains("FILE_SCHEMA(('IFC4'));"));
+        assert!(ifc.contains("IFCSTRUCTURALCURVEMEMBER("));
+
+        let mut imported = import_ifc(&ifc).unwrap();
+        assert_relative_eq!(imported.members["Col"].rotation, 0.3, epsilon = 1e-12);
+        assert!(imported.members["Beam"].releases.j_node[5]);
+        assert!(!imported.members["Beam"].releases.i_node[5]);
+        assert_eq!(imported.materials["S355"].fy, Some(355e6));
+        assert_eq!(imported.load_combos["1.35D+1.5W"].factor("W"), 1.5);
+
+        model.analyze_linear().unwrap();
+        imported.analyze_linear().unwrap();
+        for node in ["B", "C"] {
+            let (a, b) = (model.node_displacement(node, "1.35D+1.5W").unwrap(), imported.node_displacement(node, "1.35D+1.5W").unwrap());
+            for (x, y) in [(a.dx, b.dx), (a.dy, b.dy), (a.dz, b.dz), (a.rz, b.rz)] {
+                assert_relative_eq!(x, y, epsilon = 1e-12, max_relative = 1e-9);
+            }
+        }
+    }
+
+    /// GlobalId of the rooted instance named `name`
+    fn guid_of<'a>(ifc: &'a str, entity: &str, name: &str) -> &'a str {
+        let line = ifc.lines()
+            .find(|line| line.contains(&format!("{entity}(")) && line.contains(&format!(",'{name}',")))
+            .unwrap();
+        line.split('\'').nth(1).unwrap()
+    }
+
+    #[test]
+    fn test_guids_are_stable() {
+        let ifc = export_ifc(&frame_and_slab()).unwrap();
+        let mut edited = frame_and_slab();
+        edited.add_node("F", Node::new(9.0, 0.0, 0.0)).unwrap();
+        edited.add_node_load("C", NodeLoad::fx(1e3, "W")).unwrap();
+        let edited = export_ifc(&edited).unwrap();
+        
+        let col = guid_of(&ifc, "IFCSTRUCTURALCURVEMEMBER", "Col");
+        assert_eq!(col.len(), 22);
+        assert_eq!(col, guid_of(&edited, "IFCSTRUCTURALCURVEMEMBER", "Col"));
+        assert_eq!(guid_of(&ifc, "IFCSTRUCTURALPOINTCONNECTION", "A"), guid_of(&edited, "IFCSTRUCTURALPOINTCONNECTION", "A"));
+        assert_ne!(col, guid_of(&ifc, "IFCSTRUCTURALCURVEMEMBER", "Beam"));
+    }
+
+    #[cfg(feature = "attributes")]
+    #[test]
+    fn test_imported_guids_are_written_back() {
+        let guid = "2O2Fr$t4X7Zf8NOew3FLOH";
+        let ifc = export_ifc(&frame_and_slab()).unwrap();
+        let ifc = ifc.replace(guid_of(&ifc, "IFCSTRUCTURALCURVEMEMBER", "Col"), guid);
+        let imported = import_ifc(&ifc).unwrap();
+        assert_eq!(imported.members["Col"].attributes[GUID_ATTRIBUTE], guid);
+        assert!(imported.quads["Slab"].attributes.contains_key(GUID_ATTRIBUTE));
+        
+        let exported = export_ifc(&imported).unwrap();
+        assert_eq!(guid_of(&exported, "IFCSTRUCTURALCURVEMEMBER", "Col"), guid);
+        for (entity, name) in [("IFCSTRUCTURALPOINTCONNECTION", "B"), ("IFCSTRUCTURALSURFACEMEMBER", "Slab")] {
+            assert_eq!(guid_of(&exported, entity, name), guid_of(&ifc, entity, name));
+        }
+    }
+
+    #[test]
+    fn test_rejects_unmapped_features_and_scaled_units() {
+        let mut model = frame_and_slab();
+        model.supports.insert("A".to_string(), Support::fixed().with_enforced_dy(-0.01));
+        let err = export_ifc(&model).unwrap_err().to_string();
+        assert!(err.contains("enforced displacement at A"), "{err}");
+
+        let millimetres = export_ifc(&frame_and_slab()).unwrap()
+            .replace("IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.)", "IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.)");
+        assert!(matches!(import_ifc(&millimetres), Err(FEAError::InvalidInput(_))));
+    }
+}
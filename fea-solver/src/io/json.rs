@@ -0,0 +1,289 @@
+//! Versioned JSON project files
+//!
+//! A project file wraps the serialised model in an envelope carrying the
+//! schema version, and optionally the results of the last analysis:
+//!
+//! ```json
+//! { "format": "fea-solver", "version": 1, "model": { ... }, "results": { ... } }
+//! ```
+//!
+//! Files written by older versions are migrated step by step on load.
+//! Version 0 is a bare serialised `FEModel` with no envelope.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::analysis::AnalysisType;
+use crate::elements::{PlateStresses, QuadStresses};
+use crate::error::{FEAError, FEAResult};
+use crate::handles::{Handle, Registry};
+use crate::model::FEModel;
+use crate::results::{ResultStore, ResultTable};
+
+/// Format tag written to every project file
+pub const FORMAT: &str = "fea-solver";
+
+/// Current project file schema version
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Analysis results stored alongside a model
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedResults {
+    analysis: AnalysisType,
+    #[serde(default)]
+    nodes: BTreeMap<String, NodeResults>,
+    #[serde(default)]
+    members: BTreeMap<String, MemberResults>,
+    #[serde(default)]
+    plates: BTreeMap<String, ShellResults<PlateStresses>>,
+    #[serde(default)]
+    quads: BTreeMap<String, ShellResults<QuadStresses>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NodeResults {
+    displacements: BTreeMap<String, [f64; 6]>,
+    reactions: BTreeMap<String, [f64; 6]>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MemberResults {
+    local_forces: BTreeMap<String, [f64; 12]>,
+    local_displacements: BTreeMap<String, [f64; 12]>,
+    #[serde(default)]
+    imperfection_axial: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ShellResults<S> {
+    forces: BTreeMap<String, [f64; 24]>,
+    displacements: BTreeMap<String, [f64; 24]>,
+    stresses: BTreeMap<String, S>,
+}
+
+impl FEModel {
+    /// Serialise the model to a versioned JSON project file
+    pub fn to_json(&self) -> FEAResult<String> {
+        self.write_json(false)
+    }
+
+    /// Serialise the model and the results of its last analysis
+    ///
+    /// Results are omitted if the model has not been analysed.
+    pub fn to_json_with_results(&self) -> FEAResult<String> {
+        self.write_json(true)
+    }
+
+    /// Load a model from a JSON project file, migrating older versions
+    ///
+    /// Any saved results are restored, so result queries work without
+    /// re-running the analysis.
+    pub fn from_json(json: &str) -> FEAResult<Self> {
+        let mut document: Value = serde_json::from_str(json)?;
+        let version = schema_version(&document)?;
+        for from in version..SCHEMA_VERSION {
+            document = migrate(document, from)?;
+        }
+
+        let Value::Object(mut document) = document else {
+            return Err(FEAError::InvalidInput("project file is not a JSON object".to_string()));
+        };
+        let model = document.remove("model")
+            .ok_or_else(|| FEAError::InvalidInput("project file has no model".to_string()))?;
+        let mut model: FEModel = serde_json::from_value(model)?;
+
+        if let Some(results) = document.remove("results").filter(|r| !r.is_null()) {
+            let results: SavedResults = serde_json::from_value(results)?;
+            model.prepare()?;
+            model.restore_results(results)?;
+        }
+        Ok(model)
+    }
+
+    fn write_json(&self, with_results: bool) -> FEAResult<String> {
+        let mut document = json!({
+            "format": FORMAT,
+            "version": SCHEMA_VERSION,
+            "model": self,
+        });
+        if with_results && self.is_analyzed() {
+            document["results"] = serde_json::to_value(self.saved_results())?;
+        }
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    fn saved_results(&self) -> SavedResults {
+        let store = &self.store;
+        let nodes = self.nodes.keys()
+            .map(|name| (name.clone(), NodeResults {
+                displacements: columns(&store.displacements, name),
+                reactions: columns(&store.reactions, name),
+            }))
+            .collect();
+        let members = self.members.iter()
+            .map(|(name, member)| (name.clone(), MemberResults {
+                local_forces: columns(&store.member_forces, name),
+                local_displacements: columns(&store.member_displacements, name),
+                imperfection_axial: sorted(&member.imperfection_axial),
+            }))
+            .collect();
+        let plates = self.plates.iter()
+            .map(|(name, plate)| (name.clone(), ShellResults {
+                forces: sorted(&plate.forces),
+                displacements: sorted(&plate.displacements),
+                stresses: sorted(&plate.stresses),
+            }))
+            .collect();
+        let quads = self.quads.iter()
+            .map(|(name, quad)| (name.clone(), ShellResults {
+                forces: sorted(&quad.forces),
+                displacements: sorted(&quad.displacements),
+                stresses: sorted(&quad.stresses),
+            }))
+            .collect();
+
+        SavedResults {
+            analysis: self.solution.unwrap_or_default(),
+            nodes,
+            members,
+            plates,
+            quads,
+        }
+    }
+
+    fn restore_results(&mut self, results: SavedResults) -> FEAResult<()> {
+        let supported = self.supports.iter().filter(|(_, s)| s.is_supported()).map(|(name, _)| name);
+        self.store = ResultStore::new(
+            Registry::from_names(self.nodes.keys()),
+            Registry::from_names(supported),
+            Registry::from_names(self.members.keys()),
+        );
+        for (name, saved) in results.nodes {
+            if !self.nodes.contains_key(&name) {
+                return Err(FEAError::NodeNotFound(name));
+            }
+            for (combo, displacements) in saved.displacements {
+                self.store.displacements.insert(&name, &combo, displacements);
+            }
+            for (combo, reactions) in saved.reactions {
+                self.store.reactions.insert(&name, &combo, reactions);
+            }
+        }
+        for (name, saved) in results.members {
+            let member = self.members.get_mut(&name).ok_or(FEAError::MemberNotFound(name.clone()))?;
+            member.imperfection_axial = saved.imperfection_axial.into_iter().collect();
+            for (combo, forces) in saved.local_forces {
+                self.store.member_forces.insert(&name, &combo, forces);
+            }
+            for (combo, displacements) in saved.local_displacements {
+                self.store.member_displacements.insert(&name, &combo, displacements);
+            }
+        }
+        for (name, saved) in results.plates {
+            let plate = self.plates.get_mut(&name).ok_or(FEAError::PlateNotFound(name))?;
+            plate.forces = saved.forces.into_iter().collect();
+            plate.displacements = saved.displacements.into_iter().collect();
+            plate.stresses = saved.stresses.into_iter().collect();
+        }
+        for (name, saved) in results.quads {
+            let quad = self.quads.get_mut(&name).ok_or(FEAError::PlateNotFound(name))?;
+            quad.forces = saved.forces.into_iter().collect();
+            quad.displacements = saved.displacements.into_iter().collect();
+            quad.stresses = saved.stresses.into_iter().collect();
+        }
+        self.solution = Some(results.analysis);
+        Ok(())
+    }
+}
+
+/// Results of one entity from a result table, in load combination order
+fn columns<I: Handle, const N: usize>(table: &ResultTable<I, N>, name: &str) -> BTreeMap<String, [f64; N]> {
+    table.combos()
+        .filter_map(|combo| Some((combo.to_string(), table.get(name, combo)?)))
+        .collect()
+}
+
+/// Result map in load combination order
+fn sorted<T: Clone>(map: &HashMap<String, T>) -> BTreeMap<String, T> {
+    map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+/// Schema version of a project file (0 for a bare model)
+fn schema_version(document: &Value) -> FEAResult<u32> {
+    let Some(version) = document.get("version") else {
+        return Ok(0);
+    };
+    if document.get("format").and_then(Value::as_str) != Some(FORMAT) {
+        return Err(FEAError::InvalidInput("not an fea-solver project file".to_string()));
+    }
+    let version = version.as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| FEAError::InvalidInput(format!("invalid schema version {version}")))?;
+    if version > SCHEMA_VERSION {
+        return Err(FEAError::InvalidInput(format!(
+            "project file version {version} is newer than supported version {SCHEMA_VERSION}"
+        )));
+    }
+    Ok(version)
+}
+
+/// Upgrade a document from schema version `from` to `from + 1`
+fn migrate(document: Value, from: u32) -> FEAResult<Value> {
+    match from {
+        // Bare model: wrap it in an envelope
+        0 => Ok(json!({ "format": FORMAT, "version": 1, "model": document })),
+        _ => Err(FEAError::InvalidInput(format!("no migration from schema version {from}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    fn cantilever() -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Section", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(4.0, 0.0, 0.0)).unwrap();
+        model.add_member("M", Member::new("A", "B", "Steel", "Section")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_member_dist_load("M", DistributedLoad::uniform_downward(2000.0, "Dead")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.4D").with_case("Dead", 1.4)).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_round_trip_with_results() {
+        let mut model = cantilever();
+        model.analyze_linear().unwrap();
+        let tip = model.node_displacement("B", "1.4D").unwrap();
+
+        // Full-length loads (x2 = infinity) survive the round trip
+        let restored = FEModel::from_json(&model.to_json_with_results().unwrap()).unwrap();
+        assert!(restored.is_analyzed());
+        assert!(restored.member_dist_loads["M"][0].x2.is_infinite());
+        assert_relative_eq!(restored.node_displacement("B", "1.4D").unwrap().dy, tip.dy);
+        let moment = restored.member_forces_i("M", "1.4D").unwrap().moment_z;
+        assert_relative_eq!(moment.abs(), 1.4 * 2000.0 * 16.0 / 2.0, max_relative = 1e-9);
+
+        let unsolved = FEModel::from_json(&model.to_json().unwrap()).unwrap();
+        assert!(!unsolved.is_analyzed());
+        assert_eq!(unsolved.load_combos["1.4D"].factor("Dead"), 1.4);
+    }
+
+    #[test]
+    fn test_migrates_bare_model_and_rejects_newer_versions() {
+        let legacy = serde_json::to_string(&cantilever()).unwrap();
+        let mut model = FEModel::from_json(&legacy).unwrap();
+        assert_eq!(model.members.len(), 1);
+        model.analyze_linear().unwrap();
+
+        let newer = json!({ "format": FORMAT, "version": SCHEMA_VERSION + 1, "model": {} }).to_string();
+        assert!(matches!(FEModel::from_json(&newer), Err(FEAError::InvalidInput(_))));
+    }
+}
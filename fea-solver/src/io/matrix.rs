@@ -0,0 +1,225 @@
+//! Matrix Market and NumPy export of the assembled system
+//!
+//! [`FEModel::assembled_system`] returns the global stiffness matrix, the
+//! lumped mass matrix and a load vector per combination, with a label for
+//! every DOF, so the system can be inspected (conditioning, sparsity) or
+//! solved by another package:
+//!
+//! ```rust,no_run
+//! use fea_solver::prelude::*;
+//!
+//! let mut model = FEModel::new();
+//! // ... build the model ...
+//! let system = model.assembled_system().unwrap();
+//! system.write_matrix_market("system".as_ref()).unwrap();
+//! ```
+//!
+//! DOFs are numbered six per node (DX, DY, DZ, RX, RY, RZ) in node handle
+//! order, which reduces the bandwidth rather than following the names.
+//! Matrix Market files are 1-based coordinate (matrices) or array (vectors)
+//! files; `.npy` files are little-endian `f64` arrays that load with
+//! `numpy.load`. `dofs.csv` lists each DOF's label and whether it is free, so
+//! the supported DOFs can be partitioned out.
+//!
+//! [`FEModel::assembled_system`]: crate::model::FEModel::assembled_system
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::FEAResult;
+use crate::math::{Mat, Vec as FEVec};
+
+/// Global matrices and load vectors of a model, before supports are applied
+#[derive(Debug, Clone)]
+pub struct AssembledSystem {
+    /// Global stiffness matrix
+    pub stiffness: Mat,
+    /// Lumped (diagonal) mass matrix
+    pub mass: Mat,
+    /// Equivalent nodal load vector by combination name
+    pub loads: BTreeMap<String, FEVec>,
+    /// Label of each DOF, e.g. "N1 DX"
+    pub dofs: Vec<String>,
+    /// Indices of the unsupported DOFs, ascending
+    pub free_dofs: Vec<usize>,
+}
+
+impl AssembledSystem {
+    /// Stiffness matrix of the free DOFs alone, the matrix the solver factorises
+    pub fn free_stiffness(&self) -> Mat {
+        let n = self.free_dofs.len();
+        Mat::from_fn(n, n, |i, j| self.stiffness[(self.free_dofs[i], self.free_dofs[j])])
+    }
+
+    /// Write `stiffness.mtx`, `mass.mtx`, `load_<combo>.mtx` and `dofs.csv` into a directory
+    pub fn write_matrix_market(&self, dir: &Path) -> FEAResult<Vec<PathBuf>> {
+        self.write(dir, "mtx", |m| to_matrix_market(m).into_bytes(), |v| vector_to_matrix_market(v).into_bytes())
+    }
+
+    /// Write `stiffness.npy`, `mass.npy`, `load_<combo>.npy` and `dofs.csv` into a directory
+    pub fn write_npy(&self, dir: &Path) -> FEAResult<Vec<PathBuf>> {
+        self.write(dir, "npy", to_npy, vector_to_npy)
+    }
+
+    fn write(
+        &self,
+        dir: &Path,
+        extension: &str,
+        matrix: impl Fn(&Mat) -> Vec<u8>,
+        vector: impl Fn(&FEVec) -> Vec<u8>,
+    ) -> FEAResult<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+        let mut files = vec![
+            (format!("stiffness.{extension}"), matrix(&self.stiffness)),
+            (format!("mass.{extension}"), matrix(&self.mass)),
+        ];
+        for (combo, load) in &self.loads {
+            files.push((format!("load_{}.{extension}", file_stem(combo)), vector(load)));
+        }
+        files.push(("dofs.csv".to_string(), self.dofs_csv().into_bytes()));
+
+        let mut paths = Vec::with_capacity(files.len());
+        for (name, contents) in files {
+            let path = dir.join(name);
+            fs::write(&path, contents)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// `index,label,free` rows, with 0-based indices
+    fn dofs_csv(&self) -> String {
+        let mut csv = String::from("index,label,free\n");
+        for (i, label) in self.dofs.iter().enumerate() {
+            let _ = writeln!(csv, "{i},{label},{}", self.free_dofs.binary_search(&i).is_ok());
+        }
+        csv
+    }
+}
+
+/// Combination name made safe for a file name
+fn file_stem(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect()
+}
+
+/// Matrix Market coordinate file of the non-zero entries of a matrix
+pub fn to_matrix_market(matrix: &Mat) -> String {
+    let entries: Vec<(usize, usize, f64)> = (0..matrix.ncols())
+        .flat_map(|j| (0..matrix.nrows()).map(move |i| (i, j)))
+        .map(|(i, j)| (i, j, matrix[(i, j)]))
+        .filter(|&(_, _, value)| value != 0.0)
+        .collect();
+
+    let mut mtx = String::from("%%MatrixMarket matrix coordinate real general\n");
+    let _ = writeln!(mtx, "{} {} {}", matrix.nrows(), matrix.ncols(), entries.len());
+    for (i, j, value) in entries {
+        let _ = writeln!(mtx, "{} {} {value:e}", i + 1, j + 1);
+    }
+    mtx
+}
+
+/// Matrix Market array file of a column vector
+pub fn vector_to_matrix_market(vector: &FEVec) -> String {
+    let mut mtx = String::from("%%MatrixMarket matrix array real general\n");
+    let _ = writeln!(mtx, "{} 1", vector.len());
+    for value in vector.iter() {
+        let _ = writeln!(mtx, "{value:e}");
+    }
+    mtx
+}
+
+/// NumPy `.npy` file of a matrix
+pub fn to_npy(matrix: &Mat) -> Vec<u8> {
+    // nalgebra stores matrices column-major, i.e. Fortran order
+    npy(&format!("({}, {})", matrix.nrows(), matrix.ncols()), true, matrix.as_slice())
+}
+
+/// NumPy `.npy` file of a vector, as a 1-D array
+pub fn vector_to_npy(vector: &FEVec) -> Vec<u8> {
+    npy(&format!("({},)", vector.len()), false, vector.as_slice())
+}
+
+/// Version 1.0 `.npy` file: magic, header length, a Python dict literal
+/// padded to a multiple of 64 bytes, then the raw little-endian data
+fn npy(shape: &str, fortran_order: bool, data: &[f64]) -> Vec<u8> {
+    let order = if fortran_order { "True" } else { "False" };
+    let mut header = format!("{{'descr': '<f8', 'fortran_order': {order}, 'shape': {shape}, }}");
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(10 + header.len() + 8 * data.len());
+    bytes.extend_from_slice(b"\x93NUMPY\x01\x00");
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for value in data {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{Material, Member, Node, Section, Support};
+    use crate::loads::NodeLoad;
+    use crate::model::FEModel;
+
+    fn cantilever() -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_assembled_system() {
+        let system = cantilever().assembled_system().unwrap();
+        assert_eq!(system.stiffness.shape(), (12, 12));
+        assert_eq!(system.dofs[7], "B DY");
+        assert_eq!(system.free_dofs, (6..12).collect::<Vec<_>>());
+        assert_eq!(system.loads["Combo 1"][7], -1000.0);
+
+        // The free stiffness solves for the same tip deflection as the analysis
+        let mut model = cantilever();
+        model.analyze_linear().unwrap();
+        let p = FEVec::from_fn(6, |i, _| system.loads["Combo 1"][system.free_dofs[i]]);
+        let d = system.free_stiffness().lu().solve(&p).unwrap();
+        assert!((d[1] - model.node_displacement("B", "Combo 1").unwrap().dy).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_matrix_market_and_npy_formats() {
+        let matrix = Mat::from_row_slice(2, 3, &[1.5, 0.0, -2.0, 0.0, 0.0, 4e9]);
+        assert_eq!(
+            to_matrix_market(&matrix),
+            "%%MatrixMarket matrix coordinate real general\n2 3 3\n1 1 1.5e0\n1 3 -2e0\n2 3 4e9\n"
+        );
+        assert_eq!(
+            vector_to_matrix_market(&FEVec::from_vec(vec![0.25, 0.0])),
+            "%%MatrixMarket matrix array real general\n2 1\n2.5e-1\n0e0\n"
+        );
+
+        let npy = to_npy(&matrix);
+        let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+        assert_eq!(&npy[..8], b"\x93NUMPY\x01\x00");
+        assert_eq!((10 + header_len) % 64, 0);
+        assert!(header.starts_with("{'descr': '<f8', 'fortran_order': True, 'shape': (2, 3), }"));
+        assert!(header.ends_with('\n'));
+
+        // Column-major data: the second value is row 2 of column 1
+        let data: Vec<f64> = npy[10 + header_len..]
+            .chunks(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(data, [1.5, 0.0, 0.0, 0.0, -2.0, 4e9]);
+    }
+}
@@ -0,0 +1,24 @@
+//! Model import and export
+//!
+//! - [`calculix`]: CalculiX INP export for cross-checking results
+//! - [`dxf`]: frame centre lines from DXF drawings
+//! - [`gltf`]: glTF 2.0 scenes of the geometry coloured by a result field
+//! - [`ifc`]: IFC4 structural analysis model import and export
+//! - [`matrix`]: Matrix Market and NumPy export of the assembled system
+//! - [`nastran`]: Nastran bulk data (BDF) import
+//! - [`pynite`]: PyNite model interop and result comparison
+//! - `json`: versioned JSON project files (`FEModel::to_json` / `FEModel::from_json`)
+//! - `text`: human-writable TOML model definitions (`FEModel::from_toml`)
+
+pub mod calculix;
+pub mod dxf;
+pub mod gltf;
+pub mod ifc;
+mod json;
+pub mod matrix;
+pub mod nastran;
+pub mod pynite;
+mod step;
+mod text;
+
+pub use json::{FORMAT, SCHEMA_VERSION};
@@ -0,0 +1,408 @@
+//! Nastran bulk data (BDF) import
+//!
+//! Reads the structural cards of a bulk data deck:
+//!
+//! | Card            | Mapped to                                              |
+//! |-----------------|--------------------------------------------------------|
+//! | `GRID`          | `Node` (basic coordinates), with `PS` as a support     |
+//! | `CBAR`, `PBAR`  | `Member` and `Section` (orientation vector, pin flags) |
+//! | `CQUAD4`, `PSHELL` | `Quad` with the shell thickness and `MID1` material |
+//! | `MAT1`          | `Material` (missing E, G or NU derived from the others) |
+//! | `SPC`, `SPC1`   | `Support`, with enforced displacements from `SPC`      |
+//! | `FORCE`, `MOMENT` | `NodeLoad` in load case named after the `SID`        |
+//!
+//! Entities are named by their Nastran ids. Each load set gets a load
+//! combination of the same name with a factor of 1. Small-field, large-field
+//! and free-field (comma separated) formats are accepted, with continuation
+//! lines. Only the bulk data section is read if the deck has a `BEGIN BULK`
+//! line; other cards are skipped with a logged warning. All SPC sets are
+//! applied, and cards must use the basic coordinate system.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::elements::{Material, Member, MemberReleases, Node, Quad, Section, Support};
+use crate::error::{FEAError, FEAResult};
+use crate::loads::{LoadCombination, NodeLoad};
+use crate::math;
+use crate::model::FEModel;
+
+/// A bulk data card with its fields flattened across continuation lines
+///
+/// `fields[0]` is Nastran field 2 of the first line; fields 2-9 of each
+/// continuation line follow on.
+#[derive(Debug)]
+struct Card {
+    name: String,
+    line: usize,
+    fields: Vec<String>,
+}
+
+impl Card {
+    /// Raw text of a Nastran field (2 = first data field)
+    fn text(&self, field: usize) -> &str {
+        self.fields.get(field - 2).map_or("", |f| f.trim())
+    }
+
+    fn error(&self, field: usize, what: &str) -> FEAError {
+        FEAError::InvalidInput(format!(
+            "line {}: {} field {field}: {what} '{}'",
+            self.line,
+            self.name,
+            self.text(field),
+        ))
+    }
+
+    fn int(&self, field: usize) -> FEAResult<Option<i64>> {
+        let text = self.text(field);
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse().map(Some).map_err(|_| self.error(field, "expected an integer, found"))
+    }
+
+    fn real(&self, field: usize) -> FEAResult<Option<f64>> {
+        let text = self.text(field);
+        if text.is_empty() {
+            return Ok(None);
+        }
+        parse_real(text).map(Some).ok_or_else(|| self.error(field, "expected a real, found"))
+    }
+
+    fn required_int(&self, field: usize) -> FEAResult<i64> {
+        self.int(field)?.ok_or_else(|| self.error(field, "missing integer"))
+    }
+
+    fn required_real(&self, field: usize) -> FEAResult<f64> {
+        self.real(field)?.ok_or_else(|| self.error(field, "missing real"))
+    }
+
+    /// Name of the entity an integer id field refers to
+    fn id(&self, field: usize) -> FEAResult<String> {
+        Ok(self.required_int(field)?.to_string())
+    }
+
+    /// Component digits such as "123456", as 0-based DOF indices
+    fn dofs(&self, field: usize) -> FEAResult<Vec<usize>> {
+        self.text(field).chars()
+            .map(|c| match c.to_digit(10) {
+                Some(d @ 1..=6) => Ok(d as usize - 1),
+                _ => Err(self.error(field, "invalid component code")),
+            })
+            .collect()
+    }
+
+    fn require_basic(&self, field: usize) -> FEAResult<()> {
+        match self.int(field)? {
+            None | Some(0) => Ok(()),
+            Some(_) => Err(self.error(field, "only the basic coordinate system is supported, found")),
+        }
+    }
+}
+
+/// Parse a Nastran real, including the implied exponent forms `1.5+3` and `2.-4`
+fn parse_real(text: &str) -> Option<f64> {
+    let text = text.replace(['D', 'd'], "E");
+    if let Ok(value) = text.parse() {
+        return Some(value);
+    }
+    let split = text.char_indices()
+        .skip(1)
+        .filter(|&(i, c)| (c == '+' || c == '-') && !text[..i].ends_with(['E', 'e']))
+        .map(|(i, _)| i)
+        .last()?;
+    format!("{}E{}", &text[..split], &text[split..]).parse().ok()
+}
+
+/// Split a deck into cards, joining continuation lines
+fn read_cards(input: &str) -> Vec<Card> {
+    let bulk_start = input.lines()
+        .position(|l| l.trim_start().to_ascii_uppercase().starts_with("BEGIN BULK"))
+        .map_or(0, |i| i + 1);
+
+    let mut cards: Vec<Card> = Vec::new();
+    for (number, line) in input.lines().enumerate().skip(bulk_start) {
+        let line = line.split('$').next().unwrap_or("").trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.trim_start().to_ascii_uppercase().starts_with("ENDDATA") {
+            break;
+        }
+
+        let (head, fields) = split_fields(line);
+        let continuation = head.is_empty() || head.starts_with(['+', '*']);
+        match cards.last_mut() {
+            Some(card) if continuation => card.fields.extend(fields),
+            _ => cards.push(Card {
+                name: head.trim_end_matches('*').to_ascii_uppercase(),
+                line: number + 1,
+                fields,
+            }),
+        }
+    }
+    cards
+}
+
+/// Split one line into its first field and data fields 2-9
+fn split_fields(line: &str) -> (String, Vec<String>) {
+    if line.contains(',') {
+        let mut parts = line.split(',').map(|f| f.trim().to_string());
+        let head = parts.next().unwrap_or_default();
+        return (head, parts.take(8).collect());
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let slice = |start: usize, width: usize| -> String {
+        chars.iter().skip(start).take(width).collect::<String>().trim().to_string()
+    };
+    let head = slice(0, 8);
+    // Large-field cards use 16 character fields, four to a line
+    let width = if head.ends_with('*') { 16 } else { 8 };
+    let fields = (0..64 / width).map(|k| slice(8 + k * width, width)).collect();
+    (head, fields)
+}
+
+/// Import a Nastran bulk data deck as a model
+pub fn import_bdf(input: &str) -> FEAResult<FEModel> {
+    let cards = read_cards(input);
+    let mut model = FEModel::new();
+    let mut skipped = BTreeSet::new();
+
+    // Properties and materials first, so elements can refer to them
+    let mut shell_properties = BTreeMap::new();
+    for card in &cards {
+        match card.name.as_str() {
+            "MAT1" => model.add_material(&card.id(2)?, read_mat1(card)?)?,
+            "PBAR" => {
+                // I1 bends in plane 1 (about element z), I2 about element y
+                let [a, i1, i2, j] = [4, 5, 6, 7].map(|f| card.real(f));
+                let section = Section::new(a?.unwrap_or(0.0), i2?.unwrap_or(0.0), i1?.unwrap_or(0.0), j?.unwrap_or(0.0));
+                model.add_section(&card.id(2)?, section)?;
+            }
+            "PSHELL" => {
+                shell_properties.insert(card.id(2)?, (card.id(3)?, card.required_real(4)?));
+            }
+            _ => {}
+        }
+    }
+    // PBAR also names its material; keep it with the section id
+    let bar_materials: BTreeMap<String, String> = cards.iter()
+        .filter(|c| c.name == "PBAR")
+        .map(|c| Ok((c.id(2)?, c.id(3)?)))
+        .collect::<FEAResult<_>>()?;
+
+    let mut restraints: BTreeMap<String, ([bool; 6], [Option<f64>; 6])> = BTreeMap::new();
+    for card in cards.iter().filter(|c| c.name == "GRID") {
+        card.require_basic(3)?;
+        card.require_basic(7)?;
+        let [x, y, z] = [4, 5, 6].map(|f| card.real(f));
+        let name = card.id(2)?;
+        model.add_node(&name, Node::new(x?.unwrap_or(0.0), y?.unwrap_or(0.0), z?.unwrap_or(0.0)))?;
+        for dof in card.dofs(8)? {
+            restraints.entry(name.clone()).or_default().0[dof] = true;
+        }
+    }
+
+    let mut load_cases = BTreeSet::new();
+    for card in &cards {
+        match card.name.as_str() {
+            "GRID" | "MAT1" | "PBAR" | "PSHELL" => {}
+            "CBAR" => {
+                let section = card.id(3)?;
+                let material = bar_materials.get(&section)
+                    .ok_or_else(|| FEAError::SectionNotFound(section.clone()))?;
+                let (i_node, j_node) = (card.id(4)?, card.id(5)?);
+                let mut member = Member::new(&i_node, &j_node, material, &section)
+                    .with_releases(MemberReleases {
+                        i_node: flags(&card.dofs(10)?),
+                        j_node: flags(&card.dofs(11)?),
+                    });
+                if let Some(v) = bar_orientation(card, &model)? {
+                    member = member.with_rotation(member_rotation(&model, &i_node, &j_node, v)?);
+                }
+                model.add_member(&card.id(2)?, member)?;
+            }
+            "CQUAD4" => {
+                let property = card.id(3)?;
+                let (material, thickness) = shell_properties.get(&property)
+                    .ok_or_else(|| FEAError::InvalidInput(format!("PSHELL {property} not found")))?;
+                let [i, j, m, n] = [4, 5, 6, 7].map(|f| card.id(f));
+                model.add_quad(&card.id(2)?, Quad::new(&i?, &j?, &m?, &n?, *thickness, material))?;
+            }
+            "SPC" => {
+                for (g, c, d) in [(3, 4, 5), (6, 7, 8)] {
+                    if card.text(g).is_empty() {
+                        continue;
+                    }
+                    let entry = restraints.entry(card.id(g)?).or_default();
+                    let enforced = card.real(d)?.filter(|v| *v != 0.0);
+                    for dof in card.dofs(c)? {
+                        entry.0[dof] = true;
+                        entry.1[dof] = enforced;
+                    }
+                }
+            }
+            "SPC1" => {
+                let dofs = card.dofs(3)?;
+                for node in spc1_nodes(card)? {
+                    let entry = restraints.entry(node).or_default();
+                    for &dof in &dofs {
+                        entry.0[dof] = true;
+                    }
+                }
+            }
+            "FORCE" | "MOMENT" => {
+                card.require_basic(4)?;
+                let case = card.id(2)?;
+                let scale = card.required_real(5)?;
+                let [n1, n2, n3] = [6, 7, 8].map(|f| card.real(f));
+                let v = [n1?.unwrap_or(0.0), n2?.unwrap_or(0.0), n3?.unwrap_or(0.0)].map(|n| n * scale);
+                let load = if card.name == "FORCE" {
+                    NodeLoad::force(v[0], v[1], v[2], &case)
+                } else {
+                    NodeLoad::moment(v[0], v[1], v[2], &case)
+                };
+                model.add_node_load(&card.id(3)?, load)?;
+                load_cases.insert(case);
+            }
+            other => {
+                if skipped.insert(other.to_string()) {
+                    log::warn!("Skipping unsupported Nastran card {other}");
+                }
+            }
+        }
+    }
+
+    for (node, (restrained, enforced)) in restraints {
+        let [dx, dy, dz, rx, ry, rz] = restrained;
+        let mut support = Support::with_restraints(dx, dy, dz, rx, ry, rz);
+        [
+            support.enforced_dx,
+            support.enforced_dy,
+            support.enforced_dz,
+            support.enforced_rx,
+            support.enforced_ry,
+            support.enforced_rz,
+        ] = enforced;
+        model.add_support(&node, support)?;
+    }
+    for case in load_cases {
+        model.add_load_combo(LoadCombination::single(&case, &case))?;
+    }
+    Ok(model)
+}
+
+/// Isotropic material from a MAT1 card, deriving whichever of E, G and NU is blank
+fn read_mat1(card: &Card) -> FEAResult<Material> {
+    let (e, g, nu) = (card.real(3)?, card.real(4)?, card.real(5)?);
+    let (e, g, nu) = match (e, g, nu) {
+        (Some(e), Some(g), Some(nu)) => (e, g, nu),
+        (Some(e), None, Some(nu)) => (e, e / (2.0 * (1.0 + nu)), nu),
+        (Some(e), Some(g), None) => (e, g, e / (2.0 * g) - 1.0),
+        (None, Some(g), Some(nu)) => (2.0 * (1.0 + nu) * g, g, nu),
+        (Some(e), None, None) => (e, e / 2.0, 0.0),
+        _ => return Err(card.error(3, "MAT1 needs E, or G and NU; found E =")),
+    };
+    Ok(Material::new(e, g, nu, card.real(6)?.unwrap_or(0.0)))
+}
+
+/// Orientation vector of a CBAR, given directly or by a G0 node
+fn bar_orientation(card: &Card, model: &FEModel) -> FEAResult<Option<[f64; 3]>> {
+    if card.text(6).is_empty() {
+        return Ok(None);
+    }
+    if card.text(7).is_empty() && card.text(8).is_empty() {
+        if let Ok(g0) = card.text(6).parse::<i64>() {
+            let node = |name: String| model.nodes.get(&name).map(Node::coords).ok_or(FEAError::NodeNotFound(name));
+            let (a, o) = (node(card.id(4)?)?, node(g0.to_string())?);
+            return Ok(Some([o[0] - a[0], o[1] - a[1], o[2] - a[2]]));
+        }
+    }
+    let [x1, x2, x3] = [6, 7, 8].map(|f| card.real(f));
+    Ok(Some([x1?.unwrap_or(0.0), x2?.unwrap_or(0.0), x3?.unwrap_or(0.0)]))
+}
+
+/// Member rotation that turns its local y axis towards the orientation vector `v`
+fn member_rotation(model: &FEModel, i_node: &str, j_node: &str, v: [f64; 3]) -> FEAResult<f64> {
+    let coords = |name: &str| {
+        model.nodes.get(name).map(Node::coords).ok_or_else(|| FEAError::NodeNotFound(name.to_string()))
+    };
+    let (i, j) = (coords(i_node)?, coords(j_node)?);
+    if (0..3).all(|k| (j[k] - i[k]).abs() < 1e-10) {
+        return Err(FEAError::InvalidGeometry(format!("CBAR between {i_node} and {j_node} has zero length")));
+    }
+    let t = math::member_transformation_matrix(&i, &j, 0.0);
+    let dot = |row: usize| (0..3).map(|k| t[(row, k)] * v[k]).sum::<f64>();
+    Ok(dot(2).atan2(dot(1)))
+}
+
+/// Grid ids listed on an SPC1 card, expanding `THRU` ranges
+fn spc1_nodes(card: &Card) -> FEAResult<Vec<String>> {
+    let last = card.fields.len() + 1;
+    if card.text(5).eq_ignore_ascii_case("THRU") {
+        let (first, last) = (card.required_int(4)?, card.required_int(6)?);
+        return Ok((first..=last).map(|id| id.to_string()).collect());
+    }
+    (4..=last)
+        .filter(|&f| !card.text(f).is_empty())
+        .map(|f| card.id(f))
+        .collect()
+}
+
+/// Release flags from 0-based DOF indices
+fn flags(dofs: &[usize]) -> [bool; 6] {
+    let mut flags = [false; 6];
+    for &dof in dofs {
+        flags[dof] = true;
+    }
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_real_formats() {
+        for (text, value) in [("1.5+3", 1500.0), ("2.-4", 2e-4), ("-7.E6", -7e6), (".5", 0.5), ("1.0D2", 100.0), ("-3", -3.0)] {
+            assert_eq!(parse_real(text), Some(value), "{text}");
+        }
+        assert_eq!(parse_real("abc"), None);
+    }
+
+    #[test]
+    fn test_import_cantilever_deck() {
+        // 2 m cantilever in small, large and free field, with a tip load
+        let deck = "\
+SOL 101
+CEND
+BEGIN BULK
+$ Grids
+GRID           1       0      0.      0.      0.       0  123456
+GRID*                  2               0             1.0             0.0
+*                    0.0
+GRID,3,,2.,0.,0.
+CBAR          10      20       1       2      0.      1.      0.
+CBAR          11      20       2       3      0.      0.      1.
+PBAR          20      30    1.-2    2.-4    1.-4    5.-5
+MAT1          30  2.+11             0.3  7850.
+FORCE,99,3,0,1000.,0.,-1.,0.
+ENDDATA
+";
+        let mut model = import_bdf(deck).unwrap();
+        assert_eq!(model.nodes.len(), 3);
+        assert_relative_eq!(model.materials["30"].g, 2e11 / 2.6);
+        assert_relative_eq!(model.sections["20"].iz, 2e-4);
+        assert!(model.supports["1"].rz);
+        // Bar 11 has its local y along global Z
+        assert_relative_eq!(model.members["11"].rotation.abs(), std::f64::consts::FRAC_PI_2, epsilon = 1e-12);
+
+        model.analyze_linear().unwrap();
+        let tip = model.node_displacement("3", "99").unwrap();
+        // Bar 10 bends about I1 = 2e-4 and the rotated bar 11 about I2 = 1e-4:
+        // virtual work gives P / E (7 / 3 / I1 + 1 / 3 / I2) for the tip
+        let (p, e) = (1000.0, 2e11);
+        assert_relative_eq!(tip.dy, -p / e * (7.0 / 3.0 / 2e-4 + 1.0 / 3.0 / 1e-4), max_relative = 1e-9);
+    }
+}
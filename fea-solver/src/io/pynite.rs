@@ -0,0 +1,565 @@
+//! PyNite model interop and result comparison
+//!
+//! Models are exchanged as JSON that mirrors the attributes of a PyNite
+//! `FEModel3D`, so a PyNite model can be dumped or rebuilt attribute for
+//! attribute:
+//!
+//! ```json
+//! {
+//!   "nodes": { "N1": { "X": 0.0, "Y": 0.0, "Z": 0.0, "support_DX": true,
+//!                      "Enforced_DY": -0.01, "NodeLoads": [["FY", -10.0, "Case 1"]] } },
+//!   "materials": { "Steel": { "E": 2e11, "G": 7.7e10, "nu": 0.3, "rho": 7850.0 } },
+//!   "sections": { "W": { "A": 0.01, "Iy": 1e-4, "Iz": 2e-4, "J": 1e-5 } },
+//!   "members": { "M1": { "i_node": "N1", "j_node": "N2", "material_name": "Steel",
+//!                        "section_name": "W", "rotation": 90.0, "Releases": [false, ...],
+//!                        "PtLoads": [["Fy", -5.0, 2.0, "Case 1"]],
+//!                        "DistLoads": [["FY", -1.0, -1.0, 0.0, 6.0, "Case 1"]] } },
+//!   "plates": { "P1": { "i_node": "N1", "j_node": "N2", "m_node": "N3", "n_node": "N4",
+//!                       "t": 0.2, "material_name": "Steel", "pressures": [[-5.0, "Case 1"]] } },
+//!   "quads": { },
+//!   "load_combos": { "1.4D": { "factors": { "D": 1.4 } } }
+//! }
+//! ```
+//!
+//! As in PyNite, member rotations are in degrees and every entry is optional
+//! apart from the element definitions. Features PyNite does not share with the
+//! solver (physical members, solids, links, one-way or skewed supports,
+//! non-uniform plate pressures and so on) are rejected on export rather than
+//! dropped, so a round trip through PyNite never silently changes the model.
+//!
+//! PyNite node results are compared from a dump of each node's result
+//! dictionaries, `{node: {"DX": {combo: value}, ..., "RxnFX": {combo: value}, ...}}`,
+//! with [`compare_results`].
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::elements::{Material, Member, MemberReleases, Node, Plate, Quad, Section, Support};
+use crate::error::{FEAError, FEAResult};
+use crate::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad, PressureDistribution};
+use crate::model::FEModel;
+
+/// Node load directions, in PyNite's naming and the solver's DOF order
+const NODE_DIRECTIONS: [&str; 6] = ["FX", "FY", "FZ", "MX", "MY", "MZ"];
+
+/// Displacement result keys of a PyNite node
+const DISPLACEMENTS: [&str; 6] = ["DX", "DY", "DZ", "RX", "RY", "RZ"];
+
+/// Reaction result keys of a PyNite node
+const REACTIONS: [&str; 6] = ["RxnFX", "RxnFY", "RxnFZ", "RxnMX", "RxnMY", "RxnMZ"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteModel {
+    #[serde(default)]
+    nodes: BTreeMap<String, PyNiteNode>,
+    #[serde(default)]
+    materials: BTreeMap<String, PyNiteMaterial>,
+    #[serde(default)]
+    sections: BTreeMap<String, PyNiteSection>,
+    #[serde(default)]
+    members: BTreeMap<String, PyNiteMember>,
+    #[serde(default)]
+    plates: BTreeMap<String, PyNiteShell>,
+    #[serde(default)]
+    quads: BTreeMap<String, PyNiteShell>,
+    #[serde(default)]
+    load_combos: BTreeMap<String, PyNiteCombo>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteNode {
+    #[serde(rename = "X")]
+    x: f64,
+    #[serde(rename = "Y")]
+    y: f64,
+    #[serde(rename = "Z")]
+    z: f64,
+    #[serde(default, rename = "support_DX")]
+    support_dx: bool,
+    #[serde(default, rename = "support_DY")]
+    support_dy: bool,
+    #[serde(default, rename = "support_DZ")]
+    support_dz: bool,
+    #[serde(default, rename = "support_RX")]
+    support_rx: bool,
+    #[serde(default, rename = "support_RY")]
+    support_ry: bool,
+    #[serde(default, rename = "support_RZ")]
+    support_rz: bool,
+    #[serde(default, rename = "Enforced_DX")]
+    enforced_dx: Option<f64>,
+    #[serde(default, rename = "Enforced_DY")]
+    enforced_dy: Option<f64>,
+    #[serde(default, rename = "Enforced_DZ")]
+    enforced_dz: Option<f64>,
+    #[serde(default, rename = "Enforced_RX")]
+    enforced_rx: Option<f64>,
+    #[serde(default, rename = "Enforced_RY")]
+    enforced_ry: Option<f64>,
+    #[serde(default, rename = "Enforced_RZ")]
+    enforced_rz: Option<f64>,
+    /// (direction, magnitude, case)
+    #[serde(default, rename = "NodeLoads")]
+    loads: Vec<(String, f64, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteMaterial {
+    #[serde(rename = "E")]
+    e: f64,
+    #[serde(rename = "G")]
+    g: f64,
+    nu: f64,
+    rho: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fy: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteSection {
+    #[serde(rename = "A")]
+    a: f64,
+    #[serde(rename = "Iy")]
+    iy: f64,
+    #[serde(rename = "Iz")]
+    iz: f64,
+    #[serde(rename = "J")]
+    j: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteMember {
+    i_node: String,
+    j_node: String,
+    material_name: String,
+    section_name: String,
+    /// Degrees
+    #[serde(default)]
+    rotation: f64,
+    #[serde(default, rename = "Releases")]
+    releases: [bool; 12],
+    /// (direction, P, x, case)
+    #[serde(default, rename = "PtLoads")]
+    point_loads: Vec<(LoadDirection, f64, f64, String)>,
+    /// (direction, w1, w2, x1, x2, case), with a null x2 running to the j-node
+    #[serde(default, rename = "DistLoads")]
+    dist_loads: Vec<(LoadDirection, f64, f64, f64, Option<f64>, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteShell {
+    i_node: String,
+    j_node: String,
+    m_node: String,
+    n_node: String,
+    t: f64,
+    material_name: String,
+    #[serde(default = "unit_modifier")]
+    kx_mod: f64,
+    #[serde(default = "unit_modifier")]
+    ky_mod: f64,
+    /// (pressure, case)
+    #[serde(default)]
+    pressures: Vec<(f64, String)>,
+}
+
+fn unit_modifier() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PyNiteCombo {
+    factors: BTreeMap<String, f64>,
+}
+
+/// Build a model from a PyNite model description
+pub fn import_pynite(json: &str) -> FEAResult<FEModel> {
+    let input: PyNiteModel = serde_json::from_str(json)?;
+    let mut model = FEModel::new();
+
+    for (name, m) in &input.materials {
+        let mut material = Material::new(m.e, m.g, m.nu, m.rho);
+        if let Some(fy) = m.fy {
+            material = material.with_yield_strength(fy);
+        }
+        model.add_material(name, material)?;
+    }
+    for (name, s) in &input.sections {
+        model.add_section(name, Section::new(s.a, s.iy, s.iz, s.j))?;
+    }
+    for (name, n) in &input.nodes {
+        model.add_node(name, Node::new(n.x, n.y, n.z))?;
+    }
+    for (name, m) in &input.members {
+        let mut releases = MemberReleases::none();
+        releases.i_node.copy_from_slice(&m.releases[..6]);
+        releases.j_node.copy_from_slice(&m.releases[6..]);
+        let member = Member::new(&m.i_node, &m.j_node, &m.material_name, &m.section_name)
+            .with_rotation(m.rotation.to_radians())
+            .with_releases(releases);
+        model.add_member(name, member)?;
+    }
+    for (name, p) in &input.plates {
+        let plate = Plate::new(&p.i_node, &p.j_node, &p.m_node, &p.n_node, p.t, &p.material_name)
+            .with_modifiers(p.kx_mod, p.ky_mod);
+        model.add_plate(name, plate)?;
+    }
+    for (name, q) in &input.quads {
+        let quad = Quad::new(&q.i_node, &q.j_node, &q.m_node, &q.n_node, q.t, &q.material_name)
+            .with_modifiers(q.kx_mod, q.ky_mod);
+        model.add_quad(name, quad)?;
+    }
+
+    for (name, n) in &input.nodes {
+        let restrained = [n.support_dx, n.support_dy, n.support_dz, n.support_rx, n.support_ry, n.support_rz];
+        if restrained.iter().any(|&r| r) {
+            let [dx, dy, dz, rx, ry, rz] = restrained;
+            let mut support = Support::with_restraints(dx, dy, dz, rx, ry, rz);
+            support.enforced_dx = n.enforced_dx;
+            support.enforced_dy = n.enforced_dy;
+            support.enforced_dz = n.enforced_dz;
+            support.enforced_rx = n.enforced_rx;
+            support.enforced_ry = n.enforced_ry;
+            support.enforced_rz = n.enforced_rz;
+            model.add_support(name, support)?;
+        }
+        for (direction, value, case) in &n.loads {
+            let dof = NODE_DIRECTIONS.iter()
+                .position(|d| d == direction)
+                .ok_or_else(|| FEAError::InvalidInput(format!("node {name}: unknown load direction '{direction}'")))?;
+            let mut load = [0.0; 6];
+            load[dof] = *value;
+            let [fx, fy, fz, mx, my, mz] = load;
+            model.add_node_load(name, NodeLoad::new(fx, fy, fz, mx, my, mz, case))?;
+        }
+    }
+    for (name, m) in &input.members {
+        for (direction, p, x, case) in &m.point_loads {
+            model.add_member_point_load(name, PointLoad::new(*p, *x, *direction, case))?;
+        }
+        for (direction, w1, w2, x1, x2, case) in &m.dist_loads {
+            let load = DistributedLoad::new(*w1, *w2, *x1, x2.unwrap_or(f64::INFINITY), *direction, case);
+            model.add_member_dist_load(name, load)?;
+        }
+    }
+    for (name, shell) in input.plates.iter().chain(&input.quads) {
+        for (pressure, case) in &shell.pressures {
+            model.add_plate_load(name, PlateLoad::new(*pressure, case))?;
+        }
+    }
+
+    for (name, combo) in &input.load_combos {
+        let combo = combo.factors.iter()
+            .fold(LoadCombination::new(name), |combo, (case, &factor)| combo.with_case(case, factor));
+        model.add_load_combo(combo)?;
+    }
+    Ok(model)
+}
+
+/// Write a model as a PyNite model description
+///
+/// Fails with `InvalidInput` listing every feature PyNite cannot represent.
+pub fn export_pynite(model: &FEModel) -> FEAResult<String> {
+    let unsupported = unsupported_features(model);
+    if !unsupported.is_empty() {
+        return Err(FEAError::InvalidInput(format!(
+            "model uses features PyNite does not support: {}",
+            unsupported.join(", "),
+        )));
+    }
+
+    let mut output = PyNiteModel::default();
+    for (name, node) in &model.nodes {
+        let support = model.supports.get(name).copied().unwrap_or_default();
+        let loads = model.node_loads.get(name).into_iter().flatten()
+            .flat_map(|load| {
+                load.as_array().into_iter()
+                    .zip(NODE_DIRECTIONS)
+                    .filter(|(value, _)| *value != 0.0)
+                    .map(|(value, direction)| (direction.to_string(), value, load.case.clone()))
+            })
+            .collect();
+        output.nodes.insert(name.clone(), PyNiteNode {
+            x: node.x,
+            y: node.y,
+            z: node.z,
+            support_dx: support.dx,
+            support_dy: support.dy,
+            support_dz: support.dz,
+            support_rx: support.rx,
+            support_ry: support.ry,
+            support_rz: support.rz,
+            enforced_dx: support.enforced_dx,
+            enforced_dy: support.enforced_dy,
+            enforced_dz: support.enforced_dz,
+            enforced_rx: support.enforced_rx,
+            enforced_ry: support.enforced_ry,
+            enforced_rz: support.enforced_rz,
+            loads,
+        });
+    }
+    for (name, m) in &model.materials {
+        output.materials.insert(name.clone(), PyNiteMaterial { e: m.e, g: m.g, nu: m.nu, rho: m.rho, fy: m.fy });
+    }
+    for (name, s) in &model.sections {
+        output.sections.insert(name.clone(), PyNiteSection { a: s.a, iy: s.iy, iz: s.iz, j: s.j });
+    }
+    for (name, member) in &model.members {
+        let length = model.nodes[&member.i_node].distance_to(&model.nodes[&member.j_node]);
+        let point_loads = model.member_point_loads.get(name).into_iter().flatten()
+            .map(|l| (l.direction, l.magnitude, l.position, l.case.clone()))
+            .collect();
+        let dist_loads = model.member_dist_loads.get(name).into_iter().flatten()
+            .map(|l| (l.direction, l.w1, l.w2, l.x1, Some(l.x2.min(length)), l.case.clone()))
+            .collect();
+        output.members.insert(name.clone(), PyNiteMember {
+            i_node: member.i_node.clone(),
+            j_node: member.j_node.clone(),
+            material_name: member.material.clone(),
+            section_name: member.section.clone(),
+            rotation: member.rotation.to_degrees(),
+            releases: member.releases.as_array(),
+            point_loads,
+            dist_loads,
+        });
+    }
+    let pressures = |name: &String| -> Vec<(f64, String)> {
+        model.plate_loads.get(name).into_iter().flatten()
+            .map(|l| (l.pressure, l.case.clone()))
+            .collect()
+    };
+    for (name, p) in &model.plates {
+        output.plates.insert(name.clone(), PyNiteShell {
+            i_node: p.i_node.clone(),
+            j_node: p.j_node.clone(),
+            m_node: p.m_node.clone(),
+            n_node: p.n_node.clone(),
+            t: p.thickness,
+            material_name: p.material.clone(),
+            kx_mod: p.kx_mod,
+            ky_mod: p.ky_mod,
+            pressures: pressures(name),
+        });
+    }
+    for (name, q) in &model.quads {
+        output.quads.insert(name.clone(), PyNiteShell {
+            i_node: q.i_node.clone(),
+            j_node: q.j_node.clone(),
+            m_node: q.m_node.clone(),
+            n_node: q.n_node.clone(),
+            t: q.thickness,
+            material_name: q.material.clone(),
+            kx_mod: q.kx_mod,
+            ky_mod: q.ky_mod,
+            pressures: pressures(name),
+        });
+    }
+    for (name, combo) in &model.load_combos {
+        output.load_combos.insert(name.clone(), PyNiteCombo { factors: combo.factors.clone() });
+    }
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+/// Model features with no PyNite equivalent
+fn unsupported_features(model: &FEModel) -> Vec<String> {
+    let mut notes = Vec::new();
+    let mut note = |kind: &str, names: &mut dyn Iterator<Item = &String>| {
+        notes.extend(names.map(|name| format!("{kind} {name}")));
+    };
+    note("physical member", &mut model.physical_members.keys());
+    note("tetra", &mut model.tetras.keys());
+    note("rigid link", &mut model.rigid_links.keys());
+    note("gap", &mut model.gaps.keys());
+    note("link", &mut model.links.keys());
+    note("node mass at", &mut model.node_masses.keys());
+    note("prestress on member", &mut model.member_prestress_loads.keys());
+    note("plate point load on", &mut model.plate_point_loads.keys());
+    note("plate thermal load on", &mut model.plate_thermal_loads.keys());
+    note("skewed or one-way support at", &mut model.supports.iter()
+        .filter(|(_, s)| s.is_skewed() || s.one_way.iter().any(Option::is_some))
+        .map(|(name, _)| name));
+    note("nonlinear or imperfect member", &mut model.members.iter()
+        .filter(|(_, m)| m.tension_only || m.compression_only || m.foundation_modulus != 0.0 || m.imperfection.is_some())
+        .map(|(name, _)| name));
+    note("elastic foundation under", &mut model.plates.iter()
+        .filter(|(_, p)| p.foundation.is_some())
+        .map(|(name, _)| name)
+        .chain(model.quads.iter().filter(|(_, q)| q.foundation.is_some()).map(|(name, _)| name)));
+    note("non-uniform pressure on", &mut model.plate_loads.iter()
+        .filter(|(_, loads)| loads.iter().any(|l| l.distribution != PressureDistribution::Uniform))
+        .map(|(name, _)| name));
+    notes
+}
+
+/// One node result that differs from the reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultDifference {
+    /// Node name
+    pub node: String,
+    /// Load combination name
+    pub combo: String,
+    /// PyNite result key, such as `"DY"` or `"RxnMZ"`
+    pub quantity: &'static str,
+    /// Value from this solver
+    pub value: f64,
+    /// Value from PyNite
+    pub reference: f64,
+}
+
+/// Outcome of comparing node results with PyNite
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    /// Number of values compared
+    pub compared: usize,
+    /// Values outside the tolerance, in node, combo and quantity order
+    pub differences: Vec<ResultDifference>,
+    /// Reference results with no counterpart in the model, as `node/combo`
+    pub missing: Vec<String>,
+}
+
+impl Comparison {
+    /// Whether every reference value was found and within tolerance
+    pub fn is_match(&self) -> bool {
+        self.differences.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Compare an analysed model's displacements and reactions with PyNite's
+///
+/// A value matches if it is within `tolerance` of the reference, relative to
+/// the largest reference magnitude of the same quantity in that combination,
+/// so that near-zero values are not held to a relative test.
+pub fn compare_results(model: &FEModel, reference_json: &str, tolerance: f64) -> FEAResult<Comparison> {
+    if !model.is_analyzed() {
+        return Err(FEAError::NotAnalyzed);
+    }
+    let reference: BTreeMap<String, BTreeMap<String, BTreeMap<String, f64>>> =
+        serde_json::from_str(reference_json)?;
+
+    let quantities: Vec<(usize, &'static str)> = DISPLACEMENTS.into_iter()
+        .chain(REACTIONS)
+        .enumerate()
+        .collect();
+    let mut scale: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    for results in reference.values() {
+        for &(_, quantity) in &quantities {
+            for (combo, value) in results.get(quantity).into_iter().flatten() {
+                let entry = scale.entry((quantity, combo.as_str())).or_default();
+                *entry = entry.max(value.abs());
+            }
+        }
+    }
+
+    let mut comparison = Comparison::default();
+    for (name, results) in &reference {
+        let combos: std::collections::BTreeSet<&String> = results.values().flat_map(|r| r.keys()).collect();
+        for combo in combos {
+            let store = model.result_store();
+            let Some(displacement) = store.displacements.get(name, combo) else {
+                comparison.missing.push(format!("{name}/{combo}"));
+                continue;
+            };
+            // Free nodes carry no reactions
+            let reaction = store.reactions.get(name, combo).unwrap_or_default();
+            for &(k, quantity) in &quantities {
+                let Some(&expected) = results.get(quantity).and_then(|r| r.get(combo)) else {
+                    continue;
+                };
+                let value = if k < 6 { displacement[k] } else { reaction[k - 6] };
+                comparison.compared += 1;
+                let allowed = tolerance * scale[&(quantity, combo.as_str())];
+                if (value - expected).abs() > allowed {
+                    comparison.differences.push(ResultDifference {
+                        node: name.clone(),
+                        combo: combo.clone(),
+                        quantity,
+                        value,
+                        reference: expected,
+                    });
+                }
+            }
+        }
+    }
+    Ok(comparison)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use serde_json::json;
+
+    /// A PyNite-style portal frame with a rotated column and a pinned beam end
+    fn portal() -> String {
+        json!({
+            "nodes": {
+                "N1": { "X": 0.0, "Y": 0.0, "Z": 0.0, "support_DX": true, "support_DY": true, "support_DZ": true,
+                        "support_RX": true, "support_RY": true, "support_RZ": true },
+                "N2": { "X": 0.0, "Y": 3.0, "Z": 0.0, "NodeLoads": [["FX", 10e3, "W"]] },
+                "N3": { "X": 5.0, "Y": 3.0, "Z": 0.0 },
+                "N4": { "X": 5.0, "Y": 0.0, "Z": 0.0, "support_DX": true, "support_DY": true, "support_DZ": true }
+            },
+            "materials": { "Steel": { "E": 200e9, "G": 77e9, "nu": 0.3, "rho": 7850.0 } },
+            "sections": { "W": { "A": 0.01, "Iy": 1e-4, "Iz": 2e-4, "J": 1e-5 } },
+            "members": {
+                "C1": { "i_node": "N1", "j_node": "N2", "material_name": "Steel", "section_name": "W", "rotation": 90.0 },
+                "B1": { "i_node": "N2", "j_node": "N3", "material_name": "Steel", "section_name": "W",
+                        "DistLoads": [["FY", -2e3, -2e3, 0.0, 5.0, "D"]] },
+                "C2": { "i_node": "N4", "j_node": "N3", "material_name": "Steel", "section_name": "W",
+                        "Releases": [false, false, false, false, false, false, false, false, false, false, true, true] }
+            },
+            "load_combos": { "1.2D+W": { "factors": { "D": 1.2, "W": 1.0 } } }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_import_export_round_trip() {
+        let model = import_pynite(&portal()).unwrap();
+        assert_relative_eq!(model.members["C1"].rotation, std::f64::consts::FRAC_PI_2);
+        assert!(model.members["C2"].releases.j_node[5]);
+        assert!(model.supports["N4"].dz && !model.supports["N4"].rx);
+        assert_eq!(model.node_loads["N2"][0].fx, 10e3);
+
+        let exported = export_pynite(&model).unwrap();
+        let again = import_pynite(&exported).unwrap();
+        assert_eq!(serde_json::to_value(&again).unwrap(), serde_json::to_value(&model).unwrap());
+
+        let mut with_mass = model;
+        with_mass.add_node_mass("N3", 100.0).unwrap();
+        let err = export_pynite(&with_mass).unwrap_err().to_string();
+        assert!(err.contains("node mass at N3"), "{err}");
+    }
+
+    #[test]
+    fn test_compare_results() {
+        let mut model = import_pynite(&portal()).unwrap();
+        model.analyze_linear().unwrap();
+        let dx = model.node_displacement("N2", "1.2D+W").unwrap().dx;
+        let fy = model.node_reactions("N1", "1.2D+W").unwrap().fy;
+
+        let reference = json!({
+            "N2": { "DX": { "1.2D+W": dx * (1.0 + 1e-6) }, "RxnFX": { "1.2D+W": 0.0 } },
+            "N1": { "RxnFY": { "1.2D+W": fy } }
+        });
+        let comparison = compare_results(&model, &reference.to_string(), 1e-4).unwrap();
+        assert_eq!(comparison.compared, 3);
+        assert!(comparison.is_match(), "{comparison:?}");
+
+        let reference = json!({
+            "N2": { "DX": { "1.2D+W": 1.1 * dx, "Other": 0.0 } }
+        });
+        let comparison = compare_results(&model, &reference.to_string(), 1e-4).unwrap();
+        assert_eq!(comparison.differences.len(), 1);
+        assert_eq!(comparison.differences[0].quantity, "DX");
+        assert_eq!(comparison.missing, ["N2/Other"]);
+    }
+}
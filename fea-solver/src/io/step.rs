@@ -0,0 +1,357 @@
+//! Minimal ISO 10303-21 (STEP physical file) reading and writing
+//!
+//! Just enough of the format for IFC: instances are read from the `DATA`
+//! section into a map of typed argument trees, and written one per line with
+//! sequential ids. Schema knowledge lives with the caller.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::error::{FEAError, FEAResult};
+
+/// An argument of a STEP instance
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Arg {
+    /// `$`
+    Null,
+    /// `*`
+    Derived,
+    Number(f64),
+    Str(String),
+    /// `.NAME.`, including the booleans `.T.` and `.F.`
+    Enum(String),
+    /// `#id`
+    Ref(u64),
+    List(Vec<Arg>),
+    /// A typed value such as `IFCBOOLEAN(.T.)`
+    Typed(String, Box<Arg>),
+}
+
+impl Arg {
+    pub(crate) fn as_ref(&self) -> Option<u64> {
+        match self {
+            Arg::Ref(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Number, looking through a typed wrapper
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Arg::Number(v) => Some(*v),
+            Arg::Typed(_, value) => value.as_f64(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Arg::Str(s) => Some(s),
+            Arg::Typed(_, value) => value.as_str(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_enum(&self) -> Option<&str> {
+        match self {
+            Arg::Enum(e) => Some(e),
+            Arg::Typed(_, value) => value.as_enum(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_list(&self) -> &[Arg] {
+        match self {
+            Arg::List(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// Ids referenced by a list argument
+    pub(crate) fn refs(&self) -> Vec<u64> {
+        self.as_list().iter().filter_map(Arg::as_ref).collect()
+    }
+}
+
+/// An instance from the `DATA` section, with its upper-case entity name
+#[derive(Debug, Clone)]
+pub(crate) struct Instance {
+    pub name: String,
+    pub args: Vec<Arg>,
+}
+
+impl Instance {
+    /// Argument by position, `Null` if absent
+    pub(crate) fn arg(&self, index: usize) -> &Arg {
+        self.args.get(index).unwrap_or(&Arg::Null)
+    }
+}
+
+/// Parse the `DATA` section of a STEP file
+pub(crate) fn parse(input: &str) -> FEAResult<HashMap<u64, Instance>> {
+    let start = input.find("DATA;")
+        .ok_or_else(|| FEAError::InvalidInput("STEP file has no DATA section".to_string()))?;
+    let mut parser = Parser { chars: input[start + 5..].chars().collect(), pos: 0 };
+
+    let mut instances = HashMap::new();
+    loop {
+        parser.skip_whitespace();
+        if parser.peek() != Some('#') {
+            break;
+        }
+        parser.pos += 1;
+        let id = parser.number()? as u64;
+        parser.expect('=')?;
+        let name = parser.keyword();
+        let args = parser.list()?;
+        parser.expect(';')?;
+        instances.insert(id, Instance { name, args });
+    }
+    Ok(instances)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            while self.peek().is_some_and(char::is_whitespace) {
+                self.pos += 1;
+            }
+            if self.peek() == Some('/') && self.chars.get(self.pos + 1) == Some(&'*') {
+                let rest: String = self.chars[self.pos..].iter().collect();
+                self.pos += rest.find("*/").map_or(rest.len(), |end| end + 2);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, what: &str) -> FEAError {
+        let context: String = self.chars.iter().skip(self.pos).take(30).collect();
+        FEAError::InvalidInput(format!("STEP parse error: {what} at '{context}'"))
+    }
+
+    fn expect(&mut self, c: char) -> FEAResult<()> {
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{c}'")));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn keyword(&mut self) -> String {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect::<String>().to_ascii_uppercase()
+    }
+
+    fn number(&mut self) -> FEAResult<f64> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'E' | 'e')) {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().map_err(|_| self.error("invalid number"))
+    }
+
+    fn list(&mut self) -> FEAResult<Vec<Arg>> {
+        self.expect('(')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(self.arg()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => return Err(self.error("expected ',' or ')'")),
+            }
+        }
+    }
+
+    fn arg(&mut self) -> FEAResult<Arg> {
+        self.skip_whitespace();
+        match self.peek().ok_or_else(|| self.error("unexpected end of file"))? {
+            '$' => {
+                self.pos += 1;
+                Ok(Arg::Null)
+            }
+            '*' => {
+                self.pos += 1;
+                Ok(Arg::Derived)
+            }
+            '#' => {
+                self.pos += 1;
+                Ok(Arg::Ref(self.number()? as u64))
+            }
+            '\'' => self.string().map(Arg::Str),
+            '.' => {
+                self.pos += 1;
+                let name = self.keyword();
+                self.expect('.')?;
+                Ok(Arg::Enum(name))
+            }
+            '(' => self.list().map(Arg::List),
+            c if c.is_ascii_alphabetic() => {
+                let name = self.keyword();
+                let mut args = self.list()?;
+                let value = if args.len() == 1 { args.remove(0) } else { Arg::List(args) };
+                Ok(Arg::Typed(name, Box::new(value)))
+            }
+            _ => self.number().map(Arg::Number),
+        }
+    }
+
+    /// A quoted string, decoding `''` and `\X2\...\X0\` escapes
+    fn string(&mut self) -> FEAResult<String> {
+        self.pos += 1;
+        let mut raw = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some('\'') if self.chars.get(self.pos + 1) == Some(&'\'') => {
+                    raw.push('\'');
+                    self.pos += 2;
+                }
+                Some('\'') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(c) => {
+                    raw.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        let mut decoded = String::new();
+        let mut rest = raw.as_str();
+        while let Some(start) = rest.find("\\X2\\") {
+            decoded.push_str(&rest[..start].replace("\\\\", "\\"));
+            let after = &rest[start + 4..];
+            let end = after.find("\\X0\\").unwrap_or(after.len());
+            let units: Vec<u16> = (0..end / 4)
+                .filter_map(|k| u16::from_str_radix(&after[4 * k..4 * k + 4], 16).ok())
+                .collect();
+            decoded.push_str(&String::from_utf16_lossy(&units));
+            rest = after.get(end + 4..).unwrap_or("");
+        }
+        decoded.push_str(&rest.replace("\\\\", "\\"));
+        Ok(decoded)
+    }
+}
+
+/// Writes instances with sequential ids
+#[derive(Debug, Default)]
+pub(crate) struct Writer {
+    data: String,
+    next: u64,
+}
+
+impl Writer {
+    /// Append an instance and return its id
+    pub(crate) fn add(&mut self, entity: &str, args: &[String]) -> u64 {
+        self.next += 1;
+        let _ = writeln!(self.data, "#{}={entity}({});", self.next, args.join(","));
+        self.next
+    }
+
+    /// Complete file with a header for the given schema
+    pub(crate) fn finish(self, description: &str, schema: &str) -> String {
+        format!(
+            "ISO-10303-21;\nHEADER;\nFILE_DESCRIPTION(({}),'2;1');\nFILE_NAME('','',(''),(''),'fea-solver','fea-solver','');\nFILE_SCHEMA(({}));\nENDSEC;\nDATA;\n{}ENDSEC;\nEND-ISO-10303-21;\n",
+            string(description),
+            string(schema),
+            self.data,
+        )
+    }
+}
+
+/// A real, always with a decimal point as STEP requires
+pub(crate) fn real(value: f64) -> String {
+    let text = format!("{value:?}").to_ascii_uppercase();
+    match text.split_once('E') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => format!("{mantissa}.E{exponent}"),
+        _ => text,
+    }
+}
+
+/// A quoted string, escaping quotes and non-ASCII characters
+pub(crate) fn string(value: &str) -> String {
+    let mut out = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => out.push_str("''"),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            c => {
+                out.push_str("\\X2\\");
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    let _ = write!(out, "{unit:04X}");
+                }
+                out.push_str("\\X0\\");
+            }
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// `#id`
+pub(crate) fn reference(id: u64) -> String {
+    format!("#{id}")
+}
+
+/// `(a,b,...)`
+pub(crate) fn list<T: AsRef<str>>(items: impl IntoIterator<Item = T>) -> String {
+    let items: Vec<String> = items.into_iter().map(|i| i.as_ref().to_string()).collect();
+    format!("({})", items.join(","))
+}
+
+/// `(#a,#b,...)`
+pub(crate) fn ref_list(ids: &[u64]) -> String {
+    list(ids.iter().map(|&id| reference(id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_parse() {
+        let mut writer = Writer::default();
+        let point = writer.add("IFCCARTESIANPOINT", &[list([real(1.0), real(-2.5e-7), real(3e11)])]);
+        writer.add("IFCMATERIAL", &[string("Stahl 'S355' \u{e4}"), "$".to_string(), "*".to_string()]);
+        writer.add("IFCBOUNDARYNODECONDITION", &["$".to_string(), "IFCBOOLEAN(.T.)".to_string(), reference(point)]);
+        let file = writer.finish("ViewDefinition [StructuralAnalysisView]", "IFC4");
+        assert!(file.contains("#1=IFCCARTESIANPOINT((1.0,-2.5E-7,300000000000.0));"), "{file}");
+        assert_eq!(real(1e-5), "1.E-5");
+
+        let instances = parse(&file).unwrap();
+        let coords: Vec<f64> = instances[&1].arg(0).as_list().iter().filter_map(Arg::as_f64).collect();
+        assert_eq!(coords, [1.0, -2.5e-7, 3e11]);
+        assert_eq!(instances[&2].arg(0).as_str(), Some("Stahl 'S355' \u{e4}"));
+        assert_eq!(instances[&2].arg(2), &Arg::Derived);
+        assert_eq!(instances[&3].arg(1), &Arg::Typed("IFCBOOLEAN".to_string(), Box::new(Arg::Enum("T".to_string()))));
+        assert_eq!(instances[&3].arg(2).as_ref(), Some(1));
+    }
+}
@@ -0,0 +1,370 @@
+//! Human-writable TOML model definitions
+//!
+//! A compact, PyNite-like format for scripting models and keeping them under
+//! version control. Every section is optional:
+//!
+//! ```toml
+//! [materials.Steel]
+//! e = 200e9
+//! nu = 0.3          # g defaults to e / (2 (1 + nu))
+//! rho = 7850.0
+//!
+//! [sections.W310]
+//! a = 7.65e-3
+//! iy = 204e-6
+//! iz = 17.3e-6
+//! j = 0.3e-6
+//!
+//! [nodes]
+//! N1 = [0.0, 0.0, 0.0]
+//! N2 = [6.0, 0.0, 0.0]
+//!
+//! [members.M1]
+//! i = "N1"
+//! j = "N2"
+//! material = "Steel"
+//! section = "W310"
+//! release_j = ["ry", "rz"]
+//!
+//! [supports]
+//! N1 = "fixed"                  # or "pinned", or a list such as ["dx", "dy"]
+//!
+//! [[node_loads]]
+//! node = "N2"
+//! case = "Live"
+//! fy = -10e3
+//!
+//! [[member_dist_loads]]
+//! member = "M1"
+//! case = "Dead"
+//! direction = "FY"
+//! w1 = -2e3                     # w2 defaults to w1, x1 to 0, x2 to the j-node
+//!
+//! [combos]
+//! "1.2D+1.6L" = { Dead = 1.2, Live = 1.6 }
+//! ```
+//!
+//! Plates and quads are given as `[plates.P1]` / `[quads.Q1]` tables with
+//! `nodes = ["A", "B", "C", "D"]`, `thickness` and `material`, and loaded
+//! with `[[plate_loads]]` entries (`plate`, `case`, `pressure`). Member point
+//! loads use `[[member_point_loads]]` with `member`, `case`, `direction`,
+//! `p` and `x`. Unknown keys are rejected so typos do not pass silently.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::elements::{Material, Member, MemberReleases, Node, Plate, Quad, Section, Support};
+use crate::error::{FEAError, FEAResult};
+use crate::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad};
+use crate::model::FEModel;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModelInput {
+    #[serde(default)]
+    materials: BTreeMap<String, MaterialInput>,
+    #[serde(default)]
+    sections: BTreeMap<String, SectionInput>,
+    #[serde(default)]
+    nodes: BTreeMap<String, [f64; 3]>,
+    #[serde(default)]
+    members: BTreeMap<String, MemberInput>,
+    #[serde(default)]
+    plates: BTreeMap<String, ShellInput>,
+    #[serde(default)]
+    quads: BTreeMap<String, ShellInput>,
+    #[serde(default)]
+    supports: BTreeMap<String, SupportInput>,
+    #[serde(default)]
+    node_loads: Vec<NodeLoadInput>,
+    #[serde(default)]
+    member_point_loads: Vec<PointLoadInput>,
+    #[serde(default)]
+    member_dist_loads: Vec<DistLoadInput>,
+    #[serde(default)]
+    plate_loads: Vec<PlateLoadInput>,
+    #[serde(default)]
+    combos: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaterialInput {
+    e: f64,
+    nu: f64,
+    g: Option<f64>,
+    #[serde(default)]
+    rho: f64,
+    fy: Option<f64>,
+    alpha: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SectionInput {
+    a: f64,
+    iy: f64,
+    iz: f64,
+    j: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemberInput {
+    i: String,
+    j: String,
+    material: String,
+    section: String,
+    #[serde(default)]
+    rotation: f64,
+    #[serde(default)]
+    release_i: Vec<Dof>,
+    #[serde(default)]
+    release_j: Vec<Dof>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShellInput {
+    nodes: [String; 4],
+    thickness: f64,
+    material: String,
+}
+
+/// A degree of freedom named in a support or release list
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Dof {
+    Dx,
+    Dy,
+    Dz,
+    Rx,
+    Ry,
+    Rz,
+}
+
+impl Dof {
+    fn flags(dofs: &[Dof]) -> [bool; 6] {
+        let mut flags = [false; 6];
+        for &dof in dofs {
+            flags[dof as usize] = true;
+        }
+        flags
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SupportInput {
+    Preset(SupportPreset),
+    Restraints(Vec<Dof>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SupportPreset {
+    Fixed,
+    Pinned,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeLoadInput {
+    node: String,
+    case: String,
+    #[serde(default)]
+    fx: f64,
+    #[serde(default)]
+    fy: f64,
+    #[serde(default)]
+    fz: f64,
+    #[serde(default)]
+    mx: f64,
+    #[serde(default)]
+    my: f64,
+    #[serde(default)]
+    mz: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PointLoadInput {
+    member: String,
+    case: String,
+    direction: LoadDirection,
+    p: f64,
+    x: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DistLoadInput {
+    member: String,
+    case: String,
+    direction: LoadDirection,
+    w1: f64,
+    w2: Option<f64>,
+    #[serde(default)]
+    x1: f64,
+    x2: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlateLoadInput {
+    plate: String,
+    case: String,
+    pressure: f64,
+}
+
+impl FEModel {
+    /// Build a model from a TOML model definition
+    ///
+    /// Syntax errors are reported with their line and column; references to
+    /// undefined nodes, materials, sections or elements fail as they would
+    /// through the builder methods.
+    pub fn from_toml(input: &str) -> FEAResult<Self> {
+        let input: ModelInput = toml::from_str(input).map_err(|e| FEAError::InvalidInput(e.to_string()))?;
+        let mut model = FEModel::new();
+
+        for (name, m) in &input.materials {
+            let mut material = match m.g {
+                Some(g) => Material::new(m.e, g, m.nu, m.rho),
+                None => Material::isotropic(m.e, m.nu, m.rho),
+            };
+            if let Some(fy) = m.fy {
+                material = material.with_yield_strength(fy);
+            }
+            if let Some(alpha) = m.alpha {
+                material = material.with_thermal_expansion(alpha);
+            }
+            model.add_material(name, material)?;
+        }
+        for (name, s) in &input.sections {
+            model.add_section(name, Section::new(s.a, s.iy, s.iz, s.j))?;
+        }
+        for (name, &[x, y, z]) in &input.nodes {
+            model.add_node(name, Node::new(x, y, z))?;
+        }
+        for (name, m) in &input.members {
+            let releases = MemberReleases {
+                i_node: Dof::flags(&m.release_i),
+                j_node: Dof::flags(&m.release_j),
+            };
+            let member = Member::new(&m.i, &m.j, &m.material, &m.section)
+                .with_rotation(m.rotation)
+                .with_releases(releases);
+            model.add_member(name, member)?;
+        }
+        for (name, p) in &input.plates {
+            let [i, j, m, n] = &p.nodes;
+            model.add_plate(name, Plate::new(i, j, m, n, p.thickness, &p.material))?;
+        }
+        for (name, q) in &input.quads {
+            let [i, j, m, n] = &q.nodes;
+            model.add_quad(name, Quad::new(i, j, m, n, q.thickness, &q.material))?;
+        }
+        for (node, support) in &input.supports {
+            let support = match support {
+                SupportInput::Preset(SupportPreset::Fixed) => Support::fixed(),
+                SupportInput::Preset(SupportPreset::Pinned) => Support::pinned(),
+                SupportInput::Restraints(dofs) => {
+                    let [dx, dy, dz, rx, ry, rz] = Dof::flags(dofs);
+                    Support::with_restraints(dx, dy, dz, rx, ry, rz)
+                }
+            };
+            model.add_support(node, support)?;
+        }
+
+        for l in &input.node_loads {
+            model.add_node_load(&l.node, NodeLoad::new(l.fx, l.fy, l.fz, l.mx, l.my, l.mz, &l.case))?;
+        }
+        for l in &input.member_point_loads {
+            model.add_member_point_load(&l.member, PointLoad::new(l.p, l.x, l.direction, &l.case))?;
+        }
+        for l in &input.member_dist_loads {
+            let load = DistributedLoad::new(
+                l.w1,
+                l.w2.unwrap_or(l.w1),
+                l.x1,
+                l.x2.unwrap_or(f64::INFINITY),
+                l.direction,
+                &l.case,
+            );
+            model.add_member_dist_load(&l.member, load)?;
+        }
+        for l in &input.plate_loads {
+            model.add_plate_load(&l.plate, PlateLoad::new(l.pressure, &l.case))?;
+        }
+
+        for (name, factors) in &input.combos {
+            let combo = factors.iter()
+                .fold(LoadCombination::new(name), |combo, (case, &factor)| combo.with_case(case, factor));
+            model.add_load_combo(combo)?;
+        }
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const PROPPED_CANTILEVER: &str = r#"
+        [materials.Steel]
+        e = 200e9
+        nu = 0.3
+
+        [sections.Beam]
+        a = 0.01
+        iy = 1e-4
+        iz = 2e-4
+        j = 1e-5
+
+        [nodes]
+        A = [0.0, 0.0, 0.0]
+        B = [5.0, 0.0, 0.0]
+
+        [members.M1]
+        i = "A"
+        j = "B"
+        material = "Steel"
+        section = "Beam"
+
+        [supports]
+        A = "fixed"
+        B = ["dx", "dy", "dz", "rx"]
+
+        [[member_dist_loads]]
+        member = "M1"
+        case = "Dead"
+        direction = "FY"
+        w1 = -4e3
+
+        [combos]
+        "1.5D" = { Dead = 1.5 }
+    "#;
+
+    #[test]
+    fn test_parse_and_analyze() {
+        let mut model = FEModel::from_toml(PROPPED_CANTILEVER).unwrap();
+        assert_relative_eq!(model.materials["Steel"].g, 200e9 / 2.6);
+        model.analyze_linear().unwrap();
+
+        // Fixed-end moment of a propped cantilever: w L^2 / 8
+        let m = model.member_forces_i("M1", "1.5D").unwrap().moment_z.abs();
+        assert_relative_eq!(m, 1.5 * 4e3 * 25.0 / 8.0, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn test_rejects_typos_and_bad_references() {
+        let typo = PROPPED_CANTILEVER.replace("iz = 2e-4", "izz = 2e-4");
+        let err = FEModel::from_toml(&typo).unwrap_err().to_string();
+        assert!(err.contains("izz"), "{err}");
+
+        let dangling = PROPPED_CANTILEVER.replace(r#"j = "B""#, r#"j = "C""#);
+        assert!(matches!(FEModel::from_toml(&dangling), Err(FEAError::NodeNotFound(_))));
+    }
+}
@@ -6,7 +6,8 @@
 //! - Shell/Plate elements (MITC4 formulation)
 //! - Linear static analysis
 //! - P-Delta (second order) analysis
-//! - Modal analysis (eigenvalue)
+//! - Large displacement (co-rotational) analysis
+//! - Modal analysis (subspace iteration eigensolver)
 //!
 //! ## Example
 //! ```rust
@@ -39,28 +40,73 @@
 //! // Get results
 //! let displacement = model.node_displacement("N2", "Combo 1").unwrap();
 //! ```
+//!
+//! ## Results
+//! Node displacements, reactions and member end forces are kept in
+//! per-combination tables of [`results::ResultStore`] rather than on the
+//! nodes and members, less than half the memory of per-element maps keyed by
+//! combination name (about 0.65 GB instead of 1.5 GB for 5,000 nodes, 10,000
+//! members and 300 combinations). The per-element accessors such as
+//! `displacement`, `local_force`, `moment_z` and `max_moment_z` are methods
+//! of the store, taking the node or member name; the old methods on
+//! [`elements::Node`] and [`elements::Member`] are deprecated and forward to
+//! the store:
+//!
+//! ```rust
+//! # use fea_solver::prelude::*;
+//! # let model = FEModel::new();
+//! let store = model.result_store();
+//! let mz = store.moment_z("M1", 2.5, "Combo 1");
+//! ```
 
 pub mod analysis;
+pub mod design;
 pub mod elements;
 pub mod error;
+pub mod handles;
+pub mod io;
 pub mod loads;
 pub mod math;
 pub mod model;
+pub mod report;
 pub mod results;
+pub mod units;
+pub mod verification;
 
 // Re-export common types
 pub mod prelude {
-    pub use crate::analysis::{AnalysisOptions, AnalysisType};
+    pub use crate::analysis::{
+        AnalysisOptions, AnalysisProgress, AnalysisStage, AnalysisType, ArcLengthOptions, CancelToken, ConditioningReport,
+        Factorization, LinearSolver, LinkHistory, ModalResults, Mode, SolverBackend, SteppedAnalysis, StiffnessRatio,
+        TimeHistory, TimeHistoryResults,
+    };
+    pub use crate::design::aisc::Aisc360;
+    pub use crate::design::concrete::{Aci318, ConcreteDesign, ConcreteSection};
+    pub use crate::design::deflection::{
+        DeflectionCheck, DeflectionChecks, DeflectionCriterion, DeflectionLimit, DeflectionResults,
+    };
+    pub use crate::design::eurocode3::Eurocode3;
+    pub use crate::design::{CodeCheck, DesignCheck, DesignParameters, DesignResult, DesignResults, DesignSettings};
     pub use crate::elements::{
-        Material, Member, MemberReleases, Node, Plate, Quad, Section, Support,
+        ElasticFoundation, Gap, GapKind, Group, Imperfection, ImperfectionShape, Link, LinkKind, Material, Member,
+        MemberReleases, Node, OneWay, PhysicalMember, Plate, Quad, QuadQuality, RigidLink, Section, SectionShape,
+        StoryLevel, Support, Tetra,
     };
-    pub use crate::error::{FEAError, FEAResult};
+    pub use crate::error::{FEAError, FEAResult, SingularityDiagnostics, UnconstrainedDof};
+    pub use crate::handles::{ComboId, Handle, MemberId, NodeId};
     pub use crate::loads::{
-        DistributedLoad, LoadCase, LoadCombination, NodeLoad, PlateLoad, PointLoad,
+        DistributedLoad, LoadCase, LoadCombination, LoadEnvelope, NodeLoad, PlateLoad, PlatePointLoad,
+        PlateThermalLoad, PointLoad, PressureDistribution, PrestressKind, PrestressLoad,
     };
     pub use crate::math::PlateFormulation;
     pub use crate::model::FEModel;
-    pub use crate::results::{MemberForces, NodeDisplacement, PlateStress, PlateStressResult, Reactions};
+    pub use crate::report::{Report, ReportOptions};
+    pub use crate::results::{
+        Envelope, MemberDeflection, MemberForces, NodalShellStress, NodeDisplacement, PlateStress, PlateStressResult,
+        Quantity, Reactions, ResultFilter, ResultStore, ResultTable, ResultsDb, SectionCut, SectionCutResult, ShellSurfaceStress,
+        SlabDesignMoments, SolidStress, StoryResult, StressPoints,
+    };
+    pub use crate::units::{ConvertUnits, Dimension, ForceUnit, LengthUnit, UnitSystem};
 }
 
 #[cfg(feature = "wasm")]
@@ -12,7 +12,9 @@ pub struct DistributedLoad {
     pub w2: f64,
     /// Start position (distance from i-node)
     pub x1: f64,
-    /// End position (distance from i-node)
+    /// End position (distance from i-node); infinite to run to the j-node,
+    /// stored as `null` in JSON
+    #[serde(with = "end_position")]
     pub x2: f64,
     /// Load direction
     pub direction: LoadDirection,
@@ -39,11 +41,23 @@ impl DistributedLoad {
         Self::new(w, w, 0.0, f64::INFINITY, direction, case)
     }
 
+    /// Create a load varying linearly from `w1` at the i-node to `w2` at the
+    /// j-node
+    pub fn linear(w1: f64, w2: f64, direction: LoadDirection, case: &str) -> Self {
+        Self::new(w1, w2, 0.0, f64::INFINITY, direction, case)
+    }
+
     /// Create a uniform downward load (negative global Y)
     pub fn uniform_downward(w: f64, case: &str) -> Self {
         Self::uniform(-w.abs(), LoadDirection::FY, case)
     }
 
+    /// Create a uniform distributed torque (moment about local x per unit length)
+    /// over the full member length
+    pub fn torque(t: f64, case: &str) -> Self {
+        Self::uniform(t, LoadDirection::Mx, case)
+    }
+
     /// Create a triangular load (zero at start, max at end)
     pub fn triangular(w_max: f64, x1: f64, x2: f64, direction: LoadDirection, case: &str) -> Self {
         Self::new(0.0, w_max, x1, x2, direction, case)
@@ -66,6 +80,27 @@ impl DistributedLoad {
         }
     }
 
+    /// The part of this load between `start` and `end` on a member of `length`,
+    /// re-measured from `start`
+    ///
+    /// Linearly varying magnitudes are interpolated at the cut. Returns `None`
+    /// if the load does not overlap the range.
+    pub fn clipped(&self, start: f64, end: f64, length: f64) -> Option<Self> {
+        let x2 = self.x2.min(length);
+        let (lo, hi) = (self.x1.max(start), x2.min(end));
+        if hi - lo <= 1e-9 * length {
+            return None;
+        }
+        let w_at = |x: f64| {
+            if x2 - self.x1 > 1e-12 {
+                self.w1 + (self.w2 - self.w1) * (x - self.x1) / (x2 - self.x1)
+            } else {
+                self.w1
+            }
+        };
+        Some(Self::new(w_at(lo), w_at(hi), lo - start, hi - start, self.direction, &self.case))
+    }
+
     /// Get the total force from this load
     pub fn total_force(&self) -> f64 {
         let length = self.x2 - self.x1;
@@ -85,3 +120,16 @@ impl DistributedLoad {
         )
     }
 }
+
+/// Serde for an end position that may be infinite (JSON has no infinity)
+mod end_position {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        x.is_finite().then_some(*x).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+    }
+}
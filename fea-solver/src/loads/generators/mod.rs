@@ -0,0 +1,8 @@
+//! Code-based load generators
+//!
+//! Generators compute load intensities from design code parameters and add
+//! the resulting node/member/plate loads to a model as new load cases.
+
+pub mod notional;
+pub mod seismic;
+pub mod wind;
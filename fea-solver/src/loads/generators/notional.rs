@@ -0,0 +1,135 @@
+//! Notional lateral loads for the direct analysis method (AISC 360-16 Section C2.2b)
+//!
+//! Each gravity combination gets companion combinations with a lateral
+//! notional load `Ni = 0.002 alpha Yi` added in every direction, where `Yi`
+//! is the factored gravity load. The notional load is applied at every node
+//! in proportion to the gravity load it carries, so the total at each level
+//! is `0.002 alpha` times the gravity load of that level. Gravity acts along
+//! global -Y.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FEAError, FEAResult};
+use crate::loads::NodeLoad;
+use crate::model::FEModel;
+
+/// Generates notional load cases and combinations from gravity combinations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotionalLoadGenerator {
+    /// Notional load ratio (0.002 for an assumed out-of-plumbness of L/500)
+    pub coefficient: f64,
+    /// Load level factor alpha (1.0 for LRFD, 1.6 for ASD)
+    pub alpha: f64,
+    /// Gravity load combinations to derive notional loads from
+    pub gravity_combos: Vec<String>,
+    /// Load directions (name, horizontal unit vector)
+    pub directions: Vec<(String, [f64; 3])>,
+}
+
+impl NotionalLoadGenerator {
+    /// Create an LRFD generator (alpha = 1.0) acting in +X, -X, +Z and -Z
+    pub fn new(gravity_combos: &[&str]) -> Self {
+        Self {
+            coefficient: 0.002,
+            alpha: 1.0,
+            gravity_combos: gravity_combos.iter().map(|c| c.to_string()).collect(),
+            directions: vec![
+                ("+X".to_string(), [1.0, 0.0, 0.0]),
+                ("-X".to_string(), [-1.0, 0.0, 0.0]),
+                ("+Z".to_string(), [0.0, 0.0, 1.0]),
+                ("-Z".to_string(), [0.0, 0.0, -1.0]),
+            ],
+        }
+    }
+
+    /// Use the ASD load level factor alpha = 1.6
+    pub fn asd(mut self) -> Self {
+        self.alpha = 1.6;
+        self
+    }
+
+    /// Set the notional load ratio
+    pub fn with_coefficient(mut self, coefficient: f64) -> Self {
+        self.coefficient = coefficient;
+        self
+    }
+
+    /// Add notional load cases and combinations to a model
+    ///
+    /// For gravity combination `C` and direction `d`, the load case
+    /// `"{C} Notional {d}"` holds the notional loads and the combination
+    /// `"{C} + Notional {d}"` adds it to `C`. Returns the new combination names.
+    pub fn apply(&self, model: &mut FEModel) -> FEAResult<Vec<String>> {
+        let mut combos = Vec::new();
+
+        for combo_name in &self.gravity_combos {
+            let gravity_combo = model.load_combos.get(combo_name).cloned()
+                .ok_or_else(|| FEAError::LoadCombinationNotFound(combo_name.clone()))?;
+            let nodal_loads = model.equivalent_nodal_loads(combo_name)?;
+
+            // Sort for a deterministic load order
+            let mut gravity: Vec<(String, f64)> = nodal_loads.into_iter()
+                .map(|(node, loads)| (node, -loads[1]))
+                .filter(|(_, y)| y.abs() > 1e-9)
+                .collect();
+            gravity.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (dir_name, direction) in &self.directions {
+                let norm = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+                if norm < 1e-12 {
+                    return Err(FEAError::InvalidInput(format!("Notional load direction '{dir_name}' has zero length")));
+                }
+
+                let case = format!("{combo_name} Notional {dir_name}");
+                for (node, y) in &gravity {
+                    let n = self.coefficient * self.alpha * y;
+                    let [fx, fy, fz] = direction.map(|d| n * d / norm);
+                    model.add_node_load(node, NodeLoad::force(fx, fy, fz, &case))?;
+                }
+
+                let mut combo = gravity_combo.clone().with_case(&case, 1.0).with_tag("notional");
+                combo.name = format!("{combo_name} + Notional {dir_name}");
+                combos.push(combo.name.clone());
+                model.add_load_combo(combo)?;
+            }
+        }
+
+        Ok(combos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_notional_loads_from_gravity_combo() {
+        // Portal frame: 4 m beam with a 10 kN/m dead load and a 50 kN point load
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::rectangular(0.2, 0.3)).unwrap();
+        for (name, x, y) in [("A", 0.0, 0.0), ("B", 0.0, 3.0), ("C", 4.0, 3.0), ("D", 4.0, 0.0)] {
+            model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+        }
+        model.add_member("Col1", Member::new("A", "B", "Steel", "W")).unwrap();
+        model.add_member("Beam", Member::new("B", "C", "Steel", "W")).unwrap();
+        model.add_member("Col2", Member::new("D", "C", "Steel", "W")).unwrap();
+        model.add_member_dist_load("Beam", DistributedLoad::uniform_downward(10_000.0, "Dead")).unwrap();
+        model.add_node_load("C", NodeLoad::fy(-50_000.0, "Dead")).unwrap();
+        model.add_load_combo(LoadCombination::lrfd_dead_only()).unwrap();
+
+        let combos = NotionalLoadGenerator::new(&["1.4D"]).apply(&mut model).unwrap();
+        assert_eq!(combos.len(), 4);
+        assert!(model.load_combos["1.4D + Notional +X"].includes("1.4D Notional +X"));
+
+        // Total notional load = 0.002 x factored gravity load
+        let total: f64 = model.node_loads.values()
+            .flatten()
+            .filter(|l| l.case == "1.4D Notional +X")
+            .map(|l| l.fx)
+            .sum();
+        assert_relative_eq!(total, 0.002 * 1.4 * (40_000.0 + 50_000.0), max_relative = 1e-9);
+    }
+}
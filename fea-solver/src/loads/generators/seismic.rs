@@ -0,0 +1,271 @@
+//! Equivalent lateral force (ELF) seismic load generator (ASCE 7-16 Section 12.8)
+//!
+//! The seismic base shear `V = Cs W` is computed from the design spectral
+//! accelerations and distributed over the height of the building with
+//! `Fx = Cvx V`, `Cvx = wx hx^k / sum(wi hi^k)`. Story forces are applied as
+//! nodal loads at each story's diaphragm master node, one load case per
+//! direction. Global Y is the vertical axis.
+//!
+//! Codes using the same ELF structure can be represented by supplying the
+//! equivalent design spectral values through [`SeismicParameters`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FEAError, FEAResult};
+use crate::loads::NodeLoad;
+use crate::model::FEModel;
+
+/// Design spectral parameters and system factors
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SeismicParameters {
+    /// Design short-period spectral acceleration SDS (g)
+    pub sds: f64,
+    /// Design 1-second spectral acceleration SD1 (g)
+    pub sd1: f64,
+    /// Mapped 1-second spectral acceleration S1 (g), for the near-fault minimum
+    pub s1: f64,
+    /// Long-period transition period TL (s)
+    pub tl: f64,
+    /// Response modification coefficient R
+    pub r: f64,
+    /// Importance factor Ie
+    pub ie: f64,
+}
+
+impl SeismicParameters {
+    /// Parameters from mapped accelerations Ss, S1 and site coefficients Fa, Fv
+    ///
+    /// SDS = 2/3 Fa Ss and SD1 = 2/3 Fv S1 (ASCE 7-16 Eqs. 11.4-1 to 11.4-4).
+    pub fn from_mapped(ss: f64, s1: f64, fa: f64, fv: f64, r: f64, ie: f64, tl: f64) -> Self {
+        Self {
+            sds: 2.0 / 3.0 * fa * ss,
+            sd1: 2.0 / 3.0 * fv * s1,
+            s1,
+            tl,
+            r,
+            ie,
+        }
+    }
+
+    /// Seismic response coefficient Cs for fundamental period `t` (Eqs. 12.8-2 to 12.8-6)
+    pub fn response_coefficient(&self, t: f64) -> f64 {
+        let r_ie = self.r / self.ie;
+        let cs = self.sds / r_ie;
+
+        let cs_max = if t <= self.tl {
+            self.sd1 / (t * r_ie)
+        } else {
+            self.sd1 * self.tl / (t * t * r_ie)
+        };
+
+        let mut cs_min = (0.044 * self.sds * self.ie).max(0.01);
+        if self.s1 >= 0.6 {
+            cs_min = cs_min.max(0.5 * self.s1 / r_ie);
+        }
+
+        cs.min(cs_max).max(cs_min)
+    }
+}
+
+/// A story level with its seismic weight and diaphragm master node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Story {
+    /// Story name
+    pub name: String,
+    /// Floor elevation (global Y)
+    pub elevation: f64,
+    /// Effective seismic weight (N)
+    pub weight: f64,
+    /// Node receiving the story force
+    pub master_node: String,
+}
+
+impl Story {
+    /// Create a new story
+    pub fn new(name: &str, elevation: f64, weight: f64, master_node: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            elevation,
+            weight,
+            master_node: master_node.to_string(),
+        }
+    }
+}
+
+/// Generates ELF seismic load cases from story weights and heights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeismicLoadGenerator {
+    /// Design spectral parameters
+    pub parameters: SeismicParameters,
+    /// Fundamental period (s); None = approximate period Ct hn^x
+    pub period: Option<f64>,
+    /// Approximate period coefficient Ct (SI)
+    pub ct: f64,
+    /// Approximate period exponent x
+    pub x: f64,
+    /// Base elevation (global Y)
+    pub base_elevation: f64,
+    /// Story levels
+    pub stories: Vec<Story>,
+    /// Load directions (name, horizontal unit vector), one load case each
+    pub directions: Vec<(String, [f64; 3])>,
+    /// Load case name prefix
+    pub case_prefix: String,
+}
+
+impl SeismicLoadGenerator {
+    /// Create a generator for global X and Z using the "all other structural
+    /// systems" approximate period (Ct = 0.0488, x = 0.75)
+    pub fn new(parameters: SeismicParameters) -> Self {
+        Self {
+            parameters,
+            period: None,
+            ct: 0.0488,
+            x: 0.75,
+            base_elevation: 0.0,
+            stories: Vec::new(),
+            directions: vec![
+                ("X".to_string(), [1.0, 0.0, 0.0]),
+                ("Z".to_string(), [0.0, 0.0, 1.0]),
+            ],
+            case_prefix: "Seismic".to_string(),
+        }
+    }
+
+    /// Add a story level
+    pub fn with_story(mut self, story: Story) -> Self {
+        self.stories.push(story);
+        self
+    }
+
+    /// Use a known fundamental period instead of the approximate period
+    pub fn with_period(mut self, period: f64) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    /// Set the base elevation
+    pub fn with_base_elevation(mut self, elevation: f64) -> Self {
+        self.base_elevation = elevation;
+        self
+    }
+
+    /// Set the load case name prefix
+    pub fn with_case_prefix(mut self, prefix: &str) -> Self {
+        self.case_prefix = prefix.to_string();
+        self
+    }
+
+    /// Fundamental period used for the base shear
+    pub fn fundamental_period(&self) -> f64 {
+        self.period.unwrap_or_else(|| {
+            let hn = self.stories.iter()
+                .map(|s| s.elevation - self.base_elevation)
+                .fold(0.0, f64::max);
+            self.ct * hn.powf(self.x)
+        })
+    }
+
+    /// Total seismic weight W
+    pub fn seismic_weight(&self) -> f64 {
+        self.stories.iter().map(|s| s.weight).sum()
+    }
+
+    /// Seismic base shear V = Cs W
+    pub fn base_shear(&self) -> f64 {
+        self.parameters.response_coefficient(self.fundamental_period()) * self.seismic_weight()
+    }
+
+    /// Lateral force at each story, in the order the stories were added
+    pub fn story_forces(&self) -> Vec<f64> {
+        let t = self.fundamental_period();
+        let k = (1.0 + (t - 0.5) / 2.0).clamp(1.0, 2.0);
+
+        let terms: Vec<f64> = self.stories.iter()
+            .map(|s| s.weight * (s.elevation - self.base_elevation).max(0.0).powf(k))
+            .collect();
+        let total: f64 = terms.iter().sum();
+        if total <= 0.0 {
+            return vec![0.0; self.stories.len()];
+        }
+
+        let v = self.base_shear();
+        terms.iter().map(|term| v * term / total).collect()
+    }
+
+    /// Add the seismic load cases to a model
+    ///
+    /// Returns the names of the created load cases, one per direction.
+    pub fn apply(&self, model: &mut FEModel) -> FEAResult<Vec<String>> {
+        if self.stories.is_empty() {
+            return Err(FEAError::InvalidInput("Seismic generator has no stories".to_string()));
+        }
+
+        let forces = self.story_forces();
+        let mut cases = Vec::new();
+
+        for (name, direction) in &self.directions {
+            let case = format!("{} {name}", self.case_prefix);
+            let norm = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+            if norm < 1e-12 {
+                return Err(FEAError::InvalidInput(format!("Seismic direction '{name}' has zero length")));
+            }
+
+            for (story, force) in self.stories.iter().zip(&forces) {
+                let [fx, fy, fz] = direction.map(|d| force * d / norm);
+                model.add_node_load(&story.master_node, NodeLoad::force(fx, fy, fz, &case))?;
+            }
+            cases.push(case);
+        }
+
+        Ok(cases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    fn three_story_generator() -> SeismicLoadGenerator {
+        let params = SeismicParameters::from_mapped(1.5, 0.6, 1.0, 1.5, 8.0, 1.0, 8.0);
+        SeismicLoadGenerator::new(params)
+            .with_story(Story::new("L1", 4.0, 2.0e6, "M1"))
+            .with_story(Story::new("L2", 8.0, 2.0e6, "M2"))
+            .with_story(Story::new("Roof", 12.0, 1.5e6, "M3"))
+    }
+
+    #[test]
+    fn test_base_shear_and_distribution() {
+        let generator = three_story_generator();
+        let params = generator.parameters;
+
+        // SDS = 1.0, SD1 = 0.6, Ta = 0.0488 * 12^0.75 = 0.314 s: Cs = SDS / R
+        assert_relative_eq!(params.sds, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(generator.fundamental_period(), 0.0488 * 12.0_f64.powf(0.75), epsilon = 1e-12);
+        assert_relative_eq!(generator.base_shear(), 5.5e6 / 8.0, max_relative = 1e-12);
+
+        // T < 0.5 s gives k = 1: forces proportional to w * h
+        let forces = generator.story_forces();
+        assert_relative_eq!(forces.iter().sum::<f64>(), generator.base_shear(), max_relative = 1e-12);
+        assert_relative_eq!(forces[1] / forces[0], 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_story_forces_applied_at_master_nodes() {
+        let mut model = FEModel::new();
+        for (i, y) in [4.0, 8.0, 12.0].iter().enumerate() {
+            model.add_node(&format!("M{}", i + 1), Node::new(0.0, *y, 0.0)).unwrap();
+        }
+
+        let generator = three_story_generator();
+        let cases = generator.apply(&mut model).unwrap();
+        assert_eq!(cases, vec!["Seismic X".to_string(), "Seismic Z".to_string()]);
+
+        let roof = &model.node_loads["M3"];
+        assert_eq!(roof.len(), 2);
+        assert_relative_eq!(roof[0].fx, generator.story_forces()[2], epsilon = 1e-9);
+        assert_relative_eq!(roof[1].fz, generator.story_forces()[2], epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,414 @@
+//! Wind load generator (ASCE 7-16 / EN 1991-1-4)
+//!
+//! Velocity pressures are computed from the code's exposure/terrain profile
+//! and applied to the vertical wall surfaces of a building as plate pressures
+//! or member line loads. Global Y is the vertical axis (gravity acts along -Y).
+//!
+//! For every wind direction a separate load case is created. Each surface is
+//! classified from its outward normal as windward, leeward or side wall and
+//! loaded with the corresponding external pressure coefficient:
+//! - windward walls use the velocity pressure at the height of each point
+//! - leeward and side walls use the velocity pressure at the reference (roof) height
+//!
+//! References:
+//! - ASCE/SEI 7-16, Chapters 26-27 (Directional Procedure)
+//! - EN 1991-1-4:2005, Sections 4 and 7.2.2
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FEAError, FEAResult};
+use crate::loads::{DistributedLoad, LoadDirection, PlateLoad};
+use crate::math::Vec3;
+use crate::model::FEModel;
+
+/// ASCE 7 exposure category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Exposure {
+    /// Urban and suburban areas
+    B,
+    /// Open terrain with scattered obstructions
+    C,
+    /// Flat, unobstructed areas and water surfaces
+    D,
+}
+
+impl Exposure {
+    /// Terrain exposure constants (alpha, zg in m) from ASCE 7-16 Table 26.11-1
+    fn constants(self) -> (f64, f64) {
+        match self {
+            Exposure::B => (7.0, 365.76),
+            Exposure::C => (9.5, 274.32),
+            Exposure::D => (11.5, 213.36),
+        }
+    }
+}
+
+/// EN 1991-1-4 terrain category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TerrainCategory {
+    /// Sea or coastal area exposed to the open sea
+    Zero,
+    /// Lakes or flat areas with negligible vegetation
+    I,
+    /// Low vegetation and isolated obstacles
+    II,
+    /// Regular cover of vegetation or buildings
+    III,
+    /// At least 15% of the surface covered by buildings over 15 m
+    IV,
+}
+
+impl TerrainCategory {
+    /// Roughness length z0 and minimum height zmin in m (EN 1991-1-4 Table 4.1)
+    fn constants(self) -> (f64, f64) {
+        match self {
+            TerrainCategory::Zero => (0.003, 1.0),
+            TerrainCategory::I => (0.01, 1.0),
+            TerrainCategory::II => (0.05, 2.0),
+            TerrainCategory::III => (0.3, 5.0),
+            TerrainCategory::IV => (1.0, 10.0),
+        }
+    }
+}
+
+/// Design code and site parameters for the velocity pressure
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum WindCode {
+    /// ASCE 7-16 velocity pressure qz = 0.613 Kz Kzt Kd Ke V^2
+    Asce7 {
+        /// Basic wind speed V (m/s)
+        basic_speed: f64,
+        /// Exposure category
+        exposure: Exposure,
+        /// Topographic factor Kzt
+        kzt: f64,
+        /// Wind directionality factor Kd
+        kd: f64,
+        /// Ground elevation factor Ke
+        ke: f64,
+    },
+    /// EN 1991-1-4 peak velocity pressure qp(z) = [1 + 7 Iv(z)] 0.5 rho vm(z)^2
+    En1991 {
+        /// Basic wind velocity vb (m/s), including directional and season factors
+        basic_velocity: f64,
+        /// Terrain category
+        terrain: TerrainCategory,
+        /// Orography factor c0
+        c0: f64,
+    },
+}
+
+impl WindCode {
+    /// ASCE 7-16 with flat terrain (Kzt = 1), Kd = 0.85 and sea-level Ke = 1
+    pub fn asce7(basic_speed: f64, exposure: Exposure) -> Self {
+        WindCode::Asce7 {
+            basic_speed,
+            exposure,
+            kzt: 1.0,
+            kd: 0.85,
+            ke: 1.0,
+        }
+    }
+
+    /// EN 1991-1-4 with flat terrain (c0 = 1)
+    pub fn en1991(basic_velocity: f64, terrain: TerrainCategory) -> Self {
+        WindCode::En1991 {
+            basic_velocity,
+            terrain,
+            c0: 1.0,
+        }
+    }
+
+    /// Velocity pressure (Pa) at height `z` (m) above ground
+    pub fn velocity_pressure(&self, z: f64) -> f64 {
+        match *self {
+            WindCode::Asce7 { basic_speed, exposure, kzt, kd, ke } => {
+                let (alpha, zg) = exposure.constants();
+                let kz = 2.01 * (z.clamp(4.57, zg) / zg).powf(2.0 / alpha);
+                0.613 * kz * kzt * kd * ke * basic_speed * basic_speed
+            }
+            WindCode::En1991 { basic_velocity, terrain, c0 } => {
+                let (z0, zmin) = terrain.constants();
+                let z = z.clamp(zmin, 200.0);
+                let kr = 0.19 * (z0 / 0.05).powf(0.07);
+                let vm = kr * (z / z0).ln() * c0 * basic_velocity;
+                let iv = 1.0 / (c0 * (z / z0).ln());
+                (1.0 + 7.0 * iv) * 0.5 * 1.25 * vm * vm
+            }
+        }
+    }
+
+    /// Default external pressure coefficients for this code
+    pub fn default_coefficients(&self) -> PressureCoefficients {
+        match self {
+            // G * Cp with the rigid-building gust factor G = 0.85 (Figure 27.3-1)
+            WindCode::Asce7 { .. } => PressureCoefficients {
+                windward: 0.85 * 0.8,
+                leeward: 0.85 * -0.5,
+                side: 0.85 * -0.7,
+            },
+            // cpe,10 for zones D, E (h/d = 1) and B (Table 7.1)
+            WindCode::En1991 { .. } => PressureCoefficients {
+                windward: 0.8,
+                leeward: -0.5,
+                side: -0.8,
+            },
+        }
+    }
+}
+
+/// External pressure coefficients (positive = towards the surface)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PressureCoefficients {
+    /// Windward wall
+    pub windward: f64,
+    /// Leeward wall
+    pub leeward: f64,
+    /// Side walls
+    pub side: f64,
+}
+
+/// A wall surface made up of plates and/or members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindSurface {
+    /// Surface name
+    pub name: String,
+    /// Horizontal outward normal of the wall
+    pub outward_normal: [f64; 3],
+    /// Plates/quads forming the wall
+    pub plates: Vec<String>,
+    /// Members (girts, columns) loaded by the wall, with their tributary width (m)
+    pub members: Vec<(String, f64)>,
+}
+
+impl WindSurface {
+    /// Create a new, empty surface
+    pub fn new(name: &str, outward_normal: [f64; 3]) -> Self {
+        Self {
+            name: name.to_string(),
+            outward_normal,
+            plates: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+
+    /// Add plates/quads to the surface
+    pub fn with_plates(mut self, plates: &[&str]) -> Self {
+        self.plates.extend(plates.iter().map(|p| p.to_string()));
+        self
+    }
+
+    /// Add a member with the wall width it collects load from
+    pub fn with_member(mut self, member: &str, tributary_width: f64) -> Self {
+        self.members.push((member.to_string(), tributary_width));
+        self
+    }
+}
+
+/// A named horizontal wind direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindDirection {
+    /// Direction name, appended to the load case prefix
+    pub name: String,
+    /// Direction the wind blows towards
+    pub direction: [f64; 3],
+}
+
+impl WindDirection {
+    /// Create a new wind direction
+    pub fn new(name: &str, direction: [f64; 3]) -> Self {
+        Self {
+            name: name.to_string(),
+            direction,
+        }
+    }
+
+    /// The four orthogonal horizontal directions +X, -X, +Z and -Z
+    pub fn orthogonal() -> Vec<Self> {
+        vec![
+            Self::new("+X", [1.0, 0.0, 0.0]),
+            Self::new("-X", [-1.0, 0.0, 0.0]),
+            Self::new("+Z", [0.0, 0.0, 1.0]),
+            Self::new("-Z", [0.0, 0.0, -1.0]),
+        ]
+    }
+}
+
+/// Generates directional wind load cases on building wall surfaces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindLoadGenerator {
+    /// Code and site parameters
+    pub code: WindCode,
+    /// External pressure coefficients
+    pub coefficients: PressureCoefficients,
+    /// Ground elevation (global Y)
+    pub base_elevation: f64,
+    /// Reference (roof) height above ground for leeward and side walls
+    pub reference_height: f64,
+    /// Wall surfaces
+    pub surfaces: Vec<WindSurface>,
+    /// Wind directions, one load case each
+    pub directions: Vec<WindDirection>,
+    /// Load case name prefix
+    pub case_prefix: String,
+}
+
+impl WindLoadGenerator {
+    /// Create a generator for the four orthogonal directions with the code's default coefficients
+    pub fn new(code: WindCode, reference_height: f64) -> Self {
+        Self {
+            code,
+            coefficients: code.default_coefficients(),
+            base_elevation: 0.0,
+            reference_height,
+            surfaces: Vec::new(),
+            directions: WindDirection::orthogonal(),
+            case_prefix: "Wind".to_string(),
+        }
+    }
+
+    /// Add a wall surface
+    pub fn with_surface(mut self, surface: WindSurface) -> Self {
+        self.surfaces.push(surface);
+        self
+    }
+
+    /// Set the ground elevation
+    pub fn with_base_elevation(mut self, elevation: f64) -> Self {
+        self.base_elevation = elevation;
+        self
+    }
+
+    /// Set the wind directions
+    pub fn with_directions(mut self, directions: Vec<WindDirection>) -> Self {
+        self.directions = directions;
+        self
+    }
+
+    /// Set the load case name prefix
+    pub fn with_case_prefix(mut self, prefix: &str) -> Self {
+        self.case_prefix = prefix.to_string();
+        self
+    }
+
+    /// Net pressure (Pa, positive = towards the surface) on a surface at global elevation `y`
+    fn surface_pressure(&self, normal: &Vec3, wind: &Vec3, y: f64) -> f64 {
+        let cos = normal.dot(wind);
+        let q_ref = self.code.velocity_pressure(self.reference_height);
+
+        if cos < -0.5 {
+            let z = (y - self.base_elevation).clamp(0.0, self.reference_height);
+            self.coefficients.windward * self.code.velocity_pressure(z)
+        } else if cos > 0.5 {
+            self.coefficients.leeward * q_ref
+        } else {
+            self.coefficients.side * q_ref
+        }
+    }
+
+    /// Add the directional wind load cases to a model
+    ///
+    /// Returns the names of the created load cases, one per direction.
+    pub fn apply(&self, model: &mut FEModel) -> FEAResult<Vec<String>> {
+        let mut cases = Vec::new();
+
+        for dir in &self.directions {
+            let wind = Vec3::from(dir.direction);
+            if wind.norm() < 1e-12 {
+                return Err(FEAError::InvalidInput(format!("Wind direction '{}' has zero length", dir.name)));
+            }
+            let wind = wind.normalize();
+            let case = format!("{} {}", self.case_prefix, dir.name);
+
+            for surface in &self.surfaces {
+                let normal = Vec3::from(surface.outward_normal);
+                if normal.norm() < 1e-12 {
+                    return Err(FEAError::InvalidInput(format!("Surface '{}' has no outward normal", surface.name)));
+                }
+                let normal = normal.normalize();
+
+                for plate_name in &surface.plates {
+                    let corners = model.plate_corner_nodes(plate_name)?;
+                    let coords = corners.map(|name| model.nodes[&name].coords());
+
+                    // Plate local z = (j - i) x (n - i), as in the plate transformation
+                    let [i, j, _, n] = coords.map(Vec3::from);
+                    let local_z = (j - i).cross(&(n - i)).normalize();
+                    let sign = -normal.dot(&local_z);
+
+                    let pressures = coords.map(|c| sign * self.surface_pressure(&normal, &wind, c[1]));
+                    model.add_plate_load(plate_name, PlateLoad::bilinear(pressures, &case))?;
+                }
+
+                for (member_name, width) in &surface.members {
+                    let member = model.members.get(member_name)
+                        .ok_or_else(|| FEAError::MemberNotFound(member_name.clone()))?;
+                    let y_mid = (model.nodes[&member.i_node].y + model.nodes[&member.j_node].y) / 2.0;
+                    let force = -normal * self.surface_pressure(&normal, &wind, y_mid) * *width;
+
+                    for (axis, direction) in [LoadDirection::FX, LoadDirection::FY, LoadDirection::FZ].into_iter().enumerate() {
+                        if force[axis].abs() > 1e-9 {
+                            model.add_member_dist_load(member_name, DistributedLoad::uniform(force[axis], direction, &case))?;
+                        }
+                    }
+                }
+            }
+
+            cases.push(case);
+        }
+
+        Ok(cases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_velocity_pressure_profiles() {
+        // ASCE 7: Exposure C at 10 m has Kz ~= 1.00
+        let asce = WindCode::asce7(50.0, Exposure::C);
+        assert_relative_eq!(asce.velocity_pressure(10.0), 0.613 * 1.0 * 0.85 * 2500.0, max_relative = 0.01);
+
+        // EN 1991-1-4: terrain II at 10 m has ce ~= 2.35 (Figure 4.2)
+        let en = WindCode::en1991(25.0, TerrainCategory::II);
+        assert_relative_eq!(en.velocity_pressure(10.0), 2.35 * 0.5 * 1.25 * 625.0, max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_windward_and_leeward_cases() {
+        // A 4 m x 4 m wall panel facing -X, backed by a leeward column
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Col", Section::rectangular(0.2, 0.2)).unwrap();
+        for (name, y, z) in [("A", 0.0, 0.0), ("B", 0.0, 4.0), ("C", 4.0, 4.0), ("D", 4.0, 0.0)] {
+            model.add_node(name, Node::new(0.0, y, z)).unwrap();
+        }
+        model.add_node("E", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_node("F", Node::new(6.0, 4.0, 0.0)).unwrap();
+        model.add_plate("Wall", Plate::new("A", "B", "C", "D", 0.1, "Steel")).unwrap();
+        model.add_member("Back", Member::new("E", "F", "Steel", "Col")).unwrap();
+
+        let code = WindCode::asce7(45.0, Exposure::B);
+        let generator = WindLoadGenerator::new(code, 4.0)
+            .with_directions(vec![WindDirection::new("+X", [1.0, 0.0, 0.0])])
+            .with_surface(WindSurface::new("West", [-1.0, 0.0, 0.0]).with_plates(&["Wall"]))
+            .with_surface(WindSurface::new("East", [1.0, 0.0, 0.0]).with_member("Back", 3.0));
+
+        let cases = generator.apply(&mut model).unwrap();
+        assert_eq!(cases, vec!["Wind +X".to_string()]);
+
+        // Windward pressure pushes the wall in +X: with local z = -X the
+        // pressure acts in local -z
+        let wall = &model.plate_loads["Wall"][0];
+        assert!(wall.pressure < 0.0);
+
+        // Leeward suction pulls the back column in +X
+        let back = &model.member_dist_loads["Back"][0];
+        assert_eq!(back.direction, LoadDirection::FX);
+        assert_relative_eq!(back.w1, 0.85 * 0.5 * code.velocity_pressure(4.0) * 3.0, max_relative = 1e-9);
+    }
+}
@@ -1,7 +1,7 @@
 //! Load combinations
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A load combination defines how load cases are combined for analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,7 +9,7 @@ pub struct LoadCombination {
     /// Name of the load combination
     pub name: String,
     /// Factors for each load case (case_name -> factor)
-    pub factors: HashMap<String, f64>,
+    pub factors: BTreeMap<String, f64>,
     /// Optional tags for filtering
     pub tags: Vec<String>,
 }
@@ -19,7 +19,7 @@ impl LoadCombination {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
-            factors: HashMap::new(),
+            factors: BTreeMap::new(),
             tags: Vec::new(),
         }
     }
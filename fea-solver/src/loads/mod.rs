@@ -1,15 +1,21 @@
 //! Load types and load combinations
 
+pub mod generators;
+
 mod distributed;
 mod load_case;
 mod load_combo;
 mod node_load;
+mod pattern;
 mod plate_load;
 mod point_load;
+mod prestress;
 
 pub use distributed::DistributedLoad;
 pub use load_case::LoadCase;
 pub use load_combo::LoadCombination;
 pub use node_load::NodeLoad;
-pub use plate_load::PlateLoad;
+pub use pattern::{skip_load_patterns, LoadEnvelope};
+pub use plate_load::{PlateLoad, PlatePointLoad, PlateThermalLoad, PressureDistribution};
 pub use point_load::{LoadDirection, PointLoad};
+pub use prestress::{PrestressKind, PrestressLoad};
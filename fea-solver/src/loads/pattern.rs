@@ -0,0 +1,92 @@
+//! Pattern (skip) live loading and result envelopes
+
+use serde::{Deserialize, Serialize};
+
+/// A named set of load combinations whose results are enveloped (max/min)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadEnvelope {
+    /// Name of the envelope
+    pub name: String,
+    /// Load combinations included in the envelope
+    pub combos: Vec<String>,
+}
+
+impl LoadEnvelope {
+    /// Create a new, empty envelope
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            combos: Vec::new(),
+        }
+    }
+
+    /// Add a load combination to the envelope
+    pub fn with_combo(mut self, combo: &str) -> Self {
+        self.combos.push(combo.to_string());
+        self
+    }
+}
+
+/// Skip (checkerboard) loading patterns for a row of `num_spans` continuous spans
+///
+/// Each pattern lists the indices of the loaded spans. The patterns are:
+/// - all spans loaded
+/// - alternate spans loaded (odd and even), giving the maximum span moments
+/// - for every interior support, the two adjacent spans plus every second
+///   span beyond them, giving the maximum hogging moment and shear there
+///
+/// Duplicate patterns (which occur for short runs of spans) are removed.
+pub fn skip_load_patterns(num_spans: usize) -> Vec<Vec<usize>> {
+    let mut patterns: Vec<Vec<usize>> = vec![(0..num_spans).collect()];
+
+    if num_spans > 1 {
+        patterns.push((0..num_spans).step_by(2).collect());
+        patterns.push((1..num_spans).step_by(2).collect());
+    }
+
+    // Support s sits between span s and span s + 1
+    for s in 0..num_spans.saturating_sub(1) {
+        let loaded = (0..num_spans)
+            .filter(|&span| {
+                if span <= s {
+                    (s - span) % 2 == 0
+                } else {
+                    (span - s - 1) % 2 == 0
+                }
+            })
+            .collect();
+        patterns.push(loaded);
+    }
+
+    let mut unique: Vec<Vec<usize>> = Vec::new();
+    for pattern in patterns {
+        if !pattern.is_empty() && !unique.contains(&pattern) {
+            unique.push(pattern);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_span_patterns() {
+        let patterns = skip_load_patterns(2);
+        assert_eq!(patterns, vec![vec![0, 1], vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn test_four_span_support_patterns() {
+        let patterns = skip_load_patterns(4);
+        // All, odd, even, then one pattern per interior support
+        assert_eq!(patterns[0], vec![0, 1, 2, 3]);
+        assert_eq!(patterns[1], vec![0, 2]);
+        assert_eq!(patterns[2], vec![1, 3]);
+        assert!(patterns.contains(&vec![0, 1, 3]));
+        assert!(patterns.contains(&vec![0, 2, 3]));
+        assert!(patterns.contains(&vec![1, 2]));
+        assert_eq!(patterns.len(), 6);
+    }
+}
@@ -2,13 +2,34 @@
 
 use serde::{Deserialize, Serialize};
 
+/// How a plate pressure varies over the element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PressureDistribution {
+    /// Constant pressure equal to `PlateLoad::pressure`
+    #[default]
+    Uniform,
+    /// Pressure varying bilinearly between the i, j, m and n corner values
+    Bilinear([f64; 4]),
+    /// Fluid pressure `unit_weight * (fluid_level - y)` below the fluid level,
+    /// measured along global Y (the gravity axis), zero above it
+    Hydrostatic {
+        /// Global Y elevation of the free surface
+        fluid_level: f64,
+        /// Fluid unit weight (positive = pressure in the local +z direction)
+        unit_weight: f64,
+    },
+}
+
 /// A surface pressure load on a plate element
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlateLoad {
-    /// Pressure magnitude (positive = outward from +Z face)
+    /// Pressure magnitude (positive = outward from +Z face), used by uniform loads
     pub pressure: f64,
     /// Load case
     pub case: String,
+    /// Pressure distribution over the element
+    #[serde(default)]
+    pub distribution: PressureDistribution,
 }
 
 impl PlateLoad {
@@ -17,6 +38,7 @@ impl PlateLoad {
         Self {
             pressure,
             case: case.to_string(),
+            distribution: PressureDistribution::Uniform,
         }
     }
 
@@ -30,11 +52,113 @@ impl PlateLoad {
         Self::new(pressure.abs(), case)
     }
 
+    /// Create a linearly varying pressure from the i, j, m and n corner values
+    pub fn bilinear(corner_pressures: [f64; 4], case: &str) -> Self {
+        Self {
+            pressure: corner_pressures.iter().sum::<f64>() / 4.0,
+            case: case.to_string(),
+            distribution: PressureDistribution::Bilinear(corner_pressures),
+        }
+    }
+
+    /// Create a hydrostatic pressure below a fluid surface at global Y = `fluid_level`
+    ///
+    /// Use a negative `unit_weight` when the fluid is on the +Z face of the plate.
+    pub fn hydrostatic(fluid_level: f64, unit_weight: f64, case: &str) -> Self {
+        Self {
+            pressure: 0.0,
+            case: case.to_string(),
+            distribution: PressureDistribution::Hydrostatic { fluid_level, unit_weight },
+        }
+    }
+
     /// Scale the load by a factor
     pub fn scaled(&self, factor: f64) -> Self {
+        let distribution = match self.distribution {
+            PressureDistribution::Uniform => PressureDistribution::Uniform,
+            PressureDistribution::Bilinear(p) => PressureDistribution::Bilinear(p.map(|v| v * factor)),
+            PressureDistribution::Hydrostatic { fluid_level, unit_weight } => PressureDistribution::Hydrostatic {
+                fluid_level,
+                unit_weight: unit_weight * factor,
+            },
+        };
+
         Self {
             pressure: self.pressure * factor,
             case: self.case.clone(),
+            distribution,
+        }
+    }
+}
+
+/// A concentrated load normal to a plate at a point inside the element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatePointLoad {
+    /// Load magnitude (positive = local +Z direction)
+    pub magnitude: f64,
+    /// Local x position, measured from the i-node towards the j-node
+    pub x: f64,
+    /// Local y position, measured from the i-node towards the n-node
+    pub y: f64,
+    /// Load case
+    pub case: String,
+}
+
+impl PlatePointLoad {
+    /// Create a new point load at local position (x, y)
+    pub fn new(magnitude: f64, x: f64, y: f64, case: &str) -> Self {
+        Self {
+            magnitude,
+            x,
+            y,
+            case: case.to_string(),
+        }
+    }
+
+    /// Scale the load by a factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            magnitude: self.magnitude * factor,
+            x: self.x,
+            y: self.y,
+            case: self.case.clone(),
         }
     }
 }
+
+/// A temperature change in a plate element
+///
+/// The temperature varies linearly through the thickness: `delta_t` is the
+/// change at the mid-surface and `gradient` the difference between the +Z
+/// and -Z faces. Strains follow from the material's thermal expansion
+/// coefficient.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlateThermalLoad {
+    /// Mid-surface temperature change
+    pub delta_t: f64,
+    /// Temperature of the +Z face minus that of the -Z face
+    pub gradient: f64,
+    /// Load case
+    pub case: String,
+}
+
+impl PlateThermalLoad {
+    /// Create a thermal load with a uniform change and a through-thickness gradient
+    pub fn new(delta_t: f64, gradient: f64, case: &str) -> Self {
+        Self {
+            delta_t,
+            gradient,
+            case: case.to_string(),
+        }
+    }
+
+    /// Create a uniform temperature change
+    pub fn uniform(delta_t: f64, case: &str) -> Self {
+        Self::new(delta_t, 0.0, case)
+    }
+
+    /// Create a through-thickness temperature gradient (+Z face minus -Z face)
+    pub fn gradient(gradient: f64, case: &str) -> Self {
+        Self::new(0.0, gradient, case)
+    }
+}
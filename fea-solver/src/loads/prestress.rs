@@ -0,0 +1,56 @@
+//! Prestress loads on members
+
+use serde::{Deserialize, Serialize};
+
+/// How a prestress load is specified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrestressKind {
+    /// Locked-in axial force (tension positive)
+    Force,
+    /// Initial axial strain (elongation positive), e.g. a lack of fit
+    Strain,
+}
+
+/// An initial axial force or strain locked into a member
+///
+/// A prestress force `T` is the axial force the member would carry if both
+/// of its ends were held fixed, as for a tensioned rod or a post-tensioned
+/// element. An initial strain `eps0` is equivalent to `T = -E A eps0`. The
+/// structure then relaxes part of the prestress as it deforms.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrestressLoad {
+    /// Force or strain value
+    pub value: f64,
+    /// Whether `value` is a force or a strain
+    pub kind: PrestressKind,
+    /// Load case
+    pub case: String,
+}
+
+impl PrestressLoad {
+    /// Prestress force (tension positive)
+    pub fn force(tension: f64, case: &str) -> Self {
+        Self {
+            value: tension,
+            kind: PrestressKind::Force,
+            case: case.to_string(),
+        }
+    }
+
+    /// Initial strain (elongation positive)
+    pub fn strain(strain: f64, case: &str) -> Self {
+        Self {
+            value: strain,
+            kind: PrestressKind::Strain,
+            case: case.to_string(),
+        }
+    }
+
+    /// Locked-in tension for a member with axial rigidity `ea`
+    pub fn tension(&self, ea: f64) -> f64 {
+        match self.kind {
+            PrestressKind::Force => self.value,
+            PrestressKind::Strain => -ea * self.value,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Mathematical utilities for FEA calculations
 
+pub mod corotational;
 pub mod plate;
+pub mod solid;
+pub mod sparse;
 
 use nalgebra::{DMatrix, DVector, Matrix3, Matrix6, SMatrix, SVector, Vector3};
 
@@ -22,11 +25,38 @@ pub type Vec24 = SVector<f64, 24>;
 // Re-export plate functions
 pub use plate::{
     plate_local_stiffness, plate_local_stiffness_with_formulation,
-    plate_transformation_matrix, plate_fer_pressure,
+    plate_transformation_matrix, plate_fer_pressure, plate_fer_pressure_field,
+    plate_fer_bilinear, plate_fer_hydrostatic, plate_fer_point_load, plate_fer_thermal, plate_foundation_stiffness,
+    plate_thermal_resultants, quad_mean_plane, quad_warping_correction,
     plate_moments, plate_membrane_stress,
     PlateFormulation,
 };
 
+// Re-export solid element functions
+pub use solid::{solid_elasticity_matrix, tet4_stiffness, tet4_stress, tet4_volume};
+
+use sparse::SparseMatrixBuilder;
+
+/// Global matrix that element matrices can be assembled into
+pub trait Assemble {
+    /// Add a value to an entry
+    fn add_entry(&mut self, row: usize, col: usize, value: f64);
+}
+
+impl Assemble for Mat {
+    #[inline]
+    fn add_entry(&mut self, row: usize, col: usize, value: f64) {
+        self[(row, col)] += value;
+    }
+}
+
+impl Assemble for SparseMatrixBuilder {
+    #[inline]
+    fn add_entry(&mut self, row: usize, col: usize, value: f64) {
+        self.add(row, col, value);
+    }
+}
+
 /// Compute the transformation matrix for a 3D frame element
 /// 
 /// # Arguments
@@ -240,6 +270,41 @@ pub fn member_local_stiffness(
     Mat12::from_row_slice(&data)
 }
 
+/// Compute the consistent stiffness of an elastic foundation along a member
+///
+/// Winkler springs resist deflection in the local y direction, integrated
+/// with the cubic Hermite shape functions (same terms as the consistent mass
+/// matrix).
+///
+/// # Arguments
+/// * `modulus` - Foundation modulus (force per unit length per unit deflection)
+/// * `length` - Member length
+pub fn member_foundation_stiffness(modulus: f64, length: f64) -> Mat12 {
+    let mut k = Mat12::zeros();
+    if modulus == 0.0 {
+        return k;
+    }
+
+    let l = length;
+    let c = modulus * l / 420.0;
+    #[rustfmt::skip]
+    let terms = [
+        [156.0,      22.0 * l,      54.0,       -13.0 * l],
+        [22.0 * l,   4.0 * l * l,   13.0 * l,   -3.0 * l * l],
+        [54.0,       13.0 * l,      156.0,      -22.0 * l],
+        [-13.0 * l,  -3.0 * l * l,  -22.0 * l,  4.0 * l * l],
+    ];
+
+    // v_i, rz_i, v_j, rz_j
+    let dofs = [1, 5, 7, 11];
+    for (a, &da) in dofs.iter().enumerate() {
+        for (b, &db) in dofs.iter().enumerate() {
+            k[(da, db)] = c * terms[a][b];
+        }
+    }
+    k
+}
+
 /// Compute the geometric stiffness matrix for P-Delta analysis
 /// 
 /// # Arguments
@@ -273,7 +338,7 @@ pub fn member_geometric_stiffness(p: f64, a: f64, iy: f64, iz: f64, length: f64)
         0.0,        0.0,         -6.0*p_l/5.0, 0.0,           p_l*l/10.0,      0.0,            0.0,        0.0,         6.0*p_l/5.0,  0.0,           p_l*l/10.0,      0.0,
         0.0,        0.0,         0.0,          -p_l*ip/a,     0.0,             0.0,            0.0,        0.0,         0.0,          p_l*ip/a,      0.0,             0.0,
         0.0,        0.0,         -p_l*l/10.0,  0.0,           -p_l*l2/30.0,    0.0,            0.0,        0.0,         p_l*l/10.0,   0.0,           2.0*p_l*l2/15.0, 0.0,
-        0.0,        -p_l*l/10.0, 0.0,          0.0,           0.0,             -p_l*l2/30.0,   0.0,        p_l*l/10.0,  0.0,          0.0,           0.0,             2.0*p_l*l2/15.0,
+        0.0,        p_l*l/10.0,  0.0,          0.0,           0.0,             -p_l*l2/30.0,   0.0,        -p_l*l/10.0, 0.0,          0.0,           0.0,             2.0*p_l*l2/15.0,
     ];
     
     Mat12::from_row_slice(&data)
@@ -461,6 +526,89 @@ pub fn fer_uniform_load(w: f64, length: f64, direction: usize) -> Vec12 {
     fer
 }
 
+/// Compute fixed end reactions for a linearly varying distributed load
+///
+/// The load varies from `w1` at `x1` to `w2` at `x2`, so it may cover only
+/// part of the member and be trapezoidal or triangular.
+///
+/// # Arguments
+/// * `w1`, `w2` - Load intensity at the start and end of the load
+/// * `x1`, `x2` - Start and end of the loaded length, measured from the i-node
+/// * `length` - Member length
+/// * `direction` - Load direction index (0=X, 1=Y, 2=Z in local coords)
+pub fn fer_linear_load(w1: f64, w2: f64, x1: f64, x2: f64, length: f64, direction: usize) -> Vec12 {
+    let mut fer = Vec12::zeros();
+    let span = x2 - x1;
+    if span <= 0.0 {
+        return fer;
+    }
+
+    // Three-point Gauss rule over point loads: exact for a linear load times
+    // the cubic point load reactions
+    let g = 0.6_f64.sqrt();
+    for (xi, weight) in [(-g, 5.0 / 9.0), (0.0, 8.0 / 9.0), (g, 5.0 / 9.0)] {
+        let s = (xi + 1.0) / 2.0;
+        let w = w1 + (w2 - w1) * s;
+        fer += fer_point_load(w * weight * span / 2.0, x1 + span * s, length, direction);
+    }
+
+    fer
+}
+
+/// Compute fixed end reactions for the equivalent loads of a member imperfection
+///
+/// A compressive force `compression` acting on an initial offset profile
+/// `v0` is equivalent to the lateral load `w = compression * -v0''` plus end
+/// forces balancing it. On a fixed-fixed member the end shears cancel,
+/// leaving end moments of `factor * compression * offset`, where `factor` is
+/// 2/pi for a half-sine bow and 2/3 for a parabolic camber.
+///
+/// # Arguments
+/// * `compression` - Axial compression (positive) in the member
+/// * `ey`, `ez` - Mid-length offsets in local y and z
+/// * `factor` - End moment factor of the offset profile
+pub fn fer_imperfection(compression: f64, ey: f64, ez: f64, factor: f64) -> Vec12 {
+    let mut fer = Vec12::zeros();
+    let (my, mz) = (factor * compression * ez, factor * compression * ey);
+    
+    fer[4] = my;
+    fer[5] = -mz;
+    fer[10] = -my;
+    fer[11] = mz;
+    
+    fer
+}
+
+/// Compute fixed end reactions for a distributed torque
+///
+/// The torque varies linearly from `t1` at `x1` to `t2` at `x2` and is shared
+/// between the ends of the (torsionally fixed-fixed) member in proportion to
+/// the distance from the opposite end.
+///
+/// # Arguments
+/// * `t1`, `t2` - Torque per unit length about local x at the start and end of the load
+/// * `x1`, `x2` - Start and end of the loaded length, measured from the i-node
+/// * `length` - Member length
+pub fn fer_distributed_torque(t1: f64, t2: f64, x1: f64, x2: f64, length: f64) -> Vec12 {
+    let mut fer = Vec12::zeros();
+    let span = x2 - x1;
+    if span <= 0.0 {
+        return fer;
+    }
+
+    // Two-point Gauss rule: exact for a linear torque times linear end shares
+    let g = 1.0 / 3.0_f64.sqrt();
+    for xi in [-g, g] {
+        let x = x1 + span * (xi + 1.0) / 2.0;
+        let t = t1 + (t2 - t1) * (x - x1) / span;
+        let weight = span / 2.0;
+        fer[3] -= t * (length - x) / length * weight;
+        fer[9] -= t * x / length * weight;
+    }
+
+    fer
+}
+
 /// Compute fixed end reactions for a point load
 /// 
 /// # Arguments
@@ -507,6 +655,40 @@ pub fn solve_linear_system(a: &Mat, b: &Vec) -> Option<Vec> {
     a.clone().lu().solve(b)
 }
 
+/// Rows of a symmetric matrix whose pivot vanishes during elimination
+///
+/// Gaussian elimination runs in row order without pivoting. A row whose pivot
+/// falls below `tolerance` times its diagonal (or whose diagonal is below
+/// `tolerance` times the largest one) is either empty or dependent on the rows
+/// before it; it is dropped so the elimination can carry on and find the rest.
+pub fn zero_pivots(a: &Mat, tolerance: f64) -> std::vec::Vec<usize> {
+    let n = a.nrows();
+    let scale = a.diagonal().iter().fold(0.0_f64, |max, v| max.max(v.abs()));
+    let mut m = a.clone();
+    let mut dependent = std::vec::Vec::new();
+    
+    for k in 0..n {
+        let pivot = m[(k, k)];
+        if pivot.abs() <= tolerance * a[(k, k)].abs() || a[(k, k)].abs() <= tolerance * scale {
+            dependent.push(k);
+            for i in k..n {
+                m[(i, k)] = 0.0;
+                m[(k, i)] = 0.0;
+            }
+            continue;
+        }
+        for i in k + 1..n {
+            let factor = m[(i, k)] / pivot;
+            if factor != 0.0 {
+                for j in k + 1..n {
+                    m[(i, j)] -= factor * m[(k, j)];
+                }
+            }
+        }
+    }
+    dependent
+}
+
 /// Solve a linear system using Cholesky decomposition (for symmetric positive definite)
 pub fn solve_cholesky(a: &Mat, b: &Vec) -> Option<Vec> {
     a.clone().cholesky().map(|chol| chol.solve(b))
@@ -561,4 +743,40 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_zero_pivots() {
+        // Row 2 is the sum of rows 0 and 1, row 3 is empty
+        let a = Mat::from_row_slice(4, 4, &[
+            2.0, -1.0, 1.0, 0.0,
+            -1.0, 2.0, 1.0, 0.0,
+            1.0, 1.0, 2.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+        assert_eq!(zero_pivots(&a, 1e-10), vec![2, 3]);
+        assert!(zero_pivots(&Mat::identity(3, 3), 1e-10).is_empty());
+    }
+
+    #[test]
+    fn test_fer_linear_load() {
+        let (w, l) = (10.0, 6.0);
+        let full = fer_linear_load(w, w, 0.0, l, l, 1);
+        let uniform = fer_uniform_load(w, l, 1);
+        for k in 0..12 {
+            assert_relative_eq!(full[k], uniform[k], epsilon = 1e-9);
+        }
+
+        // Triangular, zero at i: reactions 3wL/20 and 7wL/20, moments wL²/30 and wL²/20
+        let triangle = fer_linear_load(0.0, w, 0.0, l, l, 2);
+        assert_relative_eq!(triangle[2], -3.0 * w * l / 20.0, max_relative = 1e-12);
+        assert_relative_eq!(triangle[8], -7.0 * w * l / 20.0, max_relative = 1e-12);
+        assert_relative_eq!(triangle[4], w * l * l / 30.0, max_relative = 1e-12);
+        assert_relative_eq!(triangle[10], -w * l * l / 20.0, max_relative = 1e-12);
+
+        // Uniform over the first half: moments 11wL²/192 and 5wL²/192
+        let half = fer_linear_load(w, w, 0.0, l / 2.0, l, 1);
+        assert_relative_eq!(half[5], -11.0 * w * l * l / 192.0, max_relative = 1e-12);
+        assert_relative_eq!(half[11], 5.0 * w * l * l / 192.0, max_relative = 1e-12);
+        assert_relative_eq!(half[1] + half[7], -w * l / 2.0, max_relative = 1e-12);
+    }
 }
@@ -17,7 +17,7 @@
 //! - **Mindlin**: Thick plate theory with transverse shear, uses numerical integration
 //! - **DKMQ**: Discrete Kirchhoff-Mindlin Quadrilateral, best for general quads
 
-use nalgebra::{Matrix3, SMatrix, SVector};
+use nalgebra::{Matrix3, SMatrix, SVector, Vector3};
 use serde::{Deserialize, Serialize};
 
 pub type Mat24 = SMatrix<f64, 24, 24>;
@@ -91,19 +91,6 @@ fn bending_constitutive_matrix(e: f64, nu: f64, t: f64, kx_mod: f64, ky_mod: f64
     )
 }
 
-/// Compute the Jacobian matrix for a rectangular element at natural coordinates (r, s)
-/// 
-/// For a rectangular element with corners at (0,0), (width,0), (width,height), (0,height)
-fn jacobian(width: f64, height: f64, _r: f64, _s: f64) -> [[f64; 2]; 2] {
-    // For a rectangular element, the Jacobian is constant
-    // J = [dx/dr  dy/dr]   = [width/2    0    ]
-    //     [dx/ds  dy/ds]     [  0     height/2]
-    [
-        [width / 2.0, 0.0],
-        [0.0, height / 2.0],
-    ]
-}
-
 /// Compute the inverse Jacobian
 fn jacobian_inverse(width: f64, height: f64) -> [[f64; 2]; 2] {
     [
@@ -481,6 +468,7 @@ pub fn plate_local_stiffness(
 }
 
 /// Compute plate stiffness with specified formulation
+#[allow(clippy::too_many_arguments)]
 pub fn plate_local_stiffness_with_formulation(
     e: f64,
     nu: f64,
@@ -518,10 +506,7 @@ pub fn plate_local_stiffness_with_formulation(
     // Fallback if no valid rotational stiffness found
     if min_rot == f64::MAX {
         // Use a reasonable fraction of membrane stiffness
-        let mut max_mem = 0.0_f64;
-        for i in 0..8 {
-            max_mem = max_mem.max(k_m[i][i].abs());
-        }
+        let max_mem = (0..8).map(|i| k_m[i][i].abs()).fold(0.0_f64, f64::max);
         min_rot = max_mem / 100.0;
     }
     
@@ -877,9 +862,9 @@ fn dkmq_bending_b_matrix(
     let phi_avg = (phi_12 + phi_23 + phi_34 + phi_41) / 4.0;
     let scale = 1.0 - 0.5 * phi_avg;  // Reduce stiffness for thicker plates
     
-    for i in 0..3 {
-        for j in 0..12 {
-            b[i][j] *= scale;
+    for row in b.iter_mut() {
+        for value in row.iter_mut() {
+            *value *= scale;
         }
     }
     
@@ -1010,6 +995,46 @@ pub fn plate_transformation_matrix(
     t
 }
 
+/// Project a quad's corners onto its mean plane
+///
+/// The mean plane passes through the centroid, normal to both diagonals.
+/// Returns the projected corners and the offset from each node to its
+/// projected corner, which is zero for a flat quad.
+pub fn quad_mean_plane(corners: &[[f64; 3]; 4]) -> ([[f64; 3]; 4], [[f64; 3]; 4]) {
+    let [i, j, m, n] = corners.map(Vector3::from);
+    let centroid = (i + j + m + n) / 4.0;
+    let normal = (m - i).cross(&(n - j)).normalize();
+    
+    let offsets: [[f64; 3]; 4] = corners.map(|c| (-(Vector3::from(c) - centroid).dot(&normal) * normal).into());
+    let mut projected = *corners;
+    for (p, r) in projected.iter_mut().zip(&offsets) {
+        for k in 0..3 {
+            p[k] += r[k];
+        }
+    }
+    (projected, offsets)
+}
+
+/// Warping correction from node DOFs to the DOFs of a quad flattened onto its mean plane
+///
+/// Each projected corner is tied to its node by a rigid offset `r`, so it
+/// translates by `u + theta x r` and rotates by `theta`. Pre-multiplying by
+/// the plate transformation of the flat corners gives the element's local DOFs.
+pub fn quad_warping_correction(offsets: &[[f64; 3]; 4]) -> Mat24 {
+    let mut w = Mat24::identity();
+    for (node, r) in offsets.iter().enumerate() {
+        let base = node * 6;
+        // theta x r = -[r]x theta
+        w[(base, base + 4)] = r[2];
+        w[(base, base + 5)] = -r[1];
+        w[(base + 1, base + 3)] = -r[2];
+        w[(base + 1, base + 5)] = r[0];
+        w[(base + 2, base + 3)] = r[1];
+        w[(base + 2, base + 4)] = -r[0];
+    }
+    w
+}
+
 /// Compute fixed end reactions for a uniform pressure on a plate
 /// 
 /// # Arguments
@@ -1053,6 +1078,214 @@ pub fn plate_fer_pressure(pressure: f64, width: f64, height: f64) -> Vec24 {
     fer
 }
 
+/// Cubic Hermite shape functions on [0, length]: [w_start, slope_start, w_end, slope_end]
+fn hermite_shape_functions(x: f64, length: f64) -> [f64; 4] {
+    let xi = x / length;
+    [
+        1.0 - 3.0 * xi.powi(2) + 2.0 * xi.powi(3),
+        x * (1.0 - xi).powi(2),
+        3.0 * xi.powi(2) - 2.0 * xi.powi(3),
+        x * (xi.powi(2) - xi),
+    ]
+}
+
+/// Bending shape functions [w, rx, ry] x [i, j, m, n] at local coordinates (x, y)
+///
+/// Tensor-product cubic Hermite interpolation of the transverse displacement,
+/// with rx = dw/dy and ry = -dw/dx. Corners are i (0, 0), j (W, 0), m (W, H)
+/// and n (0, H).
+fn plate_bending_shape_functions(x: f64, y: f64, width: f64, height: f64) -> [f64; 12] {
+    let hx = hermite_shape_functions(x, width);
+    let hy = hermite_shape_functions(y, height);
+
+    let mut n = [0.0; 12];
+    for (k, &(ix, iy)) in [(0, 0), (2, 0), (2, 2), (0, 2)].iter().enumerate() {
+        n[3 * k] = hx[ix] * hy[iy];
+        n[3 * k + 1] = hx[ix] * hy[iy + 1];
+        n[3 * k + 2] = -hx[ix + 1] * hy[iy];
+    }
+    n
+}
+
+/// Expand a 12-DOF bending FER vector (w, rx, ry per node) to 24 DOFs
+fn expand_bending_fer(fer_12: &[f64; 12]) -> Vec24 {
+    // Mapping: w->DZ (2), rx->RX (3), ry->RY (4) for each node
+    let mapping = [2, 3, 4, 8, 9, 10, 14, 15, 16, 20, 21, 22];
+    let mut fer = Vec24::zeros();
+    for (i, &mi) in mapping.iter().enumerate() {
+        fer[mi] = fer_12[i];
+    }
+    fer
+}
+
+/// Compute fixed end reactions for an arbitrary pressure field on a plate
+///
+/// The pressure is integrated against the Hermite bending shape functions,
+/// which reproduce [`plate_fer_pressure`] for a uniform pressure. The field is
+/// integrated with 3x3 Gauss points on each of `divisions x divisions`
+/// sub-cells, which is exact for pressures varying (bi)linearly over each
+/// sub-cell.
+///
+/// # Arguments
+/// * `pressure` - Pressure at local coordinates (x, y), with x in [0, width] and y in [0, height]
+/// * `width` - Plate width (i to j)
+/// * `height` - Plate height (j to m)
+/// * `divisions` - Sub-cells per direction
+///
+/// # Returns
+/// 24-element fixed end reaction vector
+pub fn plate_fer_pressure_field(
+    pressure: impl Fn(f64, f64) -> f64,
+    width: f64,
+    height: f64,
+    divisions: usize,
+) -> Vec24 {
+    let gauss = [
+        (-(0.6_f64).sqrt(), 5.0 / 9.0),
+        (0.0, 8.0 / 9.0),
+        ((0.6_f64).sqrt(), 5.0 / 9.0),
+    ];
+    let n = divisions.max(1);
+    let (dx, dy) = (width / n as f64, height / n as f64);
+
+    let mut fer_12 = [0.0; 12];
+    for cx in 0..n {
+        for cy in 0..n {
+            for &(gx, wx) in &gauss {
+                for &(gy, wy) in &gauss {
+                    let x = dx * (cx as f64 + (gx + 1.0) / 2.0);
+                    let y = dy * (cy as f64 + (gy + 1.0) / 2.0);
+                    let p = pressure(x, y);
+                    if p == 0.0 {
+                        continue;
+                    }
+
+                    let weight = wx * wy * dx * dy / 4.0;
+                    let shape = plate_bending_shape_functions(x, y, width, height);
+                    for (f, n) in fer_12.iter_mut().zip(shape) {
+                        *f -= p * n * weight;
+                    }
+                }
+            }
+        }
+    }
+
+    expand_bending_fer(&fer_12)
+}
+
+/// Compute the consistent Winkler foundation stiffness of a plate
+///
+/// Integrates `k N^T N` over the plate with the Hermite bending shape
+/// functions, using 4x4 Gauss points (exact for the degree 6 products in
+/// each direction). Only the w, rx and ry DOFs are coupled.
+///
+/// # Arguments
+/// * `modulus` - Subgrade modulus (pressure per unit deflection)
+/// * `width` - Plate width (i to j)
+/// * `height` - Plate height (j to m)
+///
+/// # Returns
+/// 24x24 local foundation stiffness matrix
+pub fn plate_foundation_stiffness(modulus: f64, width: f64, height: f64) -> Mat24 {
+    let a = (3.0 / 7.0 - 2.0 / 7.0 * 1.2_f64.sqrt()).sqrt();
+    let b = (3.0 / 7.0 + 2.0 / 7.0 * 1.2_f64.sqrt()).sqrt();
+    let (wa, wb) = ((18.0 + 30.0_f64.sqrt()) / 36.0, (18.0 - 30.0_f64.sqrt()) / 36.0);
+    let gauss = [(-b, wb), (-a, wa), (a, wa), (b, wb)];
+
+    let mut k_12 = [[0.0; 12]; 12];
+    for &(gx, wx) in &gauss {
+        for &(gy, wy) in &gauss {
+            let x = width * (gx + 1.0) / 2.0;
+            let y = height * (gy + 1.0) / 2.0;
+            let weight = modulus * wx * wy * width * height / 4.0;
+            let shape = plate_bending_shape_functions(x, y, width, height);
+            for i in 0..12 {
+                for j in 0..12 {
+                    k_12[i][j] += shape[i] * shape[j] * weight;
+                }
+            }
+        }
+    }
+
+    let mapping = [2, 3, 4, 8, 9, 10, 14, 15, 16, 20, 21, 22];
+    let mut k = Mat24::zeros();
+    for (i, &mi) in mapping.iter().enumerate() {
+        for (j, &mj) in mapping.iter().enumerate() {
+            k[(mi, mj)] = k_12[i][j];
+        }
+    }
+    k
+}
+
+/// Compute fixed end reactions for a concentrated load inside a plate
+///
+/// The load is distributed to the corner w, rx and ry DOFs through the
+/// bending shape functions, so it is statically equivalent to the point load
+/// rather than being lumped at the nearest node.
+///
+/// # Arguments
+/// * `p` - Load magnitude (positive = local +z)
+/// * `x`, `y` - Local load position (0 to width, 0 to height)
+/// * `width` - Plate width
+/// * `height` - Plate height
+pub fn plate_fer_point_load(p: f64, x: f64, y: f64, width: f64, height: f64) -> Vec24 {
+    let shape = plate_bending_shape_functions(x, y, width, height);
+    expand_bending_fer(&shape.map(|n| -p * n))
+}
+
+/// Compute fixed end reactions for a pressure varying bilinearly between the corners
+///
+/// # Arguments
+/// * `corner_pressures` - Pressures at the i, j, m and n corners (positive = local +z)
+/// * `width` - Plate width
+/// * `height` - Plate height
+pub fn plate_fer_bilinear(corner_pressures: [f64; 4], width: f64, height: f64) -> Vec24 {
+    let [p_i, p_j, p_m, p_n] = corner_pressures;
+    let pressure = |x: f64, y: f64| {
+        let (r, s) = (x / width, y / height);
+        p_i * (1.0 - r) * (1.0 - s) + p_j * r * (1.0 - s) + p_m * r * s + p_n * (1.0 - r) * s
+    };
+    plate_fer_pressure_field(pressure, width, height, 1)
+}
+
+/// Compute fixed end reactions for a hydrostatic pressure
+///
+/// The pressure is `unit_weight * (fluid_level - elevation)` below the fluid
+/// level and zero above it, with the elevation interpolated bilinearly from
+/// the corners. Partially submerged plates are integrated on sub-cells so the
+/// kink at the free surface is captured.
+///
+/// # Arguments
+/// * `fluid_level` - Elevation of the free surface
+/// * `unit_weight` - Fluid unit weight (positive = pressure in local +z)
+/// * `corner_elevations` - Elevations of the i, j, m and n corners
+/// * `width` - Plate width
+/// * `height` - Plate height
+pub fn plate_fer_hydrostatic(
+    fluid_level: f64,
+    unit_weight: f64,
+    corner_elevations: [f64; 4],
+    width: f64,
+    height: f64,
+) -> Vec24 {
+    let heads = corner_elevations.map(|z| fluid_level - z);
+
+    if heads.iter().all(|&h| h >= 0.0) {
+        return plate_fer_bilinear(heads.map(|h| unit_weight * h), width, height);
+    }
+    if heads.iter().all(|&h| h <= 0.0) {
+        return Vec24::zeros();
+    }
+
+    let [h_i, h_j, h_m, h_n] = heads;
+    let pressure = |x: f64, y: f64| {
+        let (r, s) = (x / width, y / height);
+        let head = h_i * (1.0 - r) * (1.0 - s) + h_j * r * (1.0 - s) + h_m * r * s + h_n * (1.0 - r) * s;
+        unit_weight * head.max(0.0)
+    };
+    plate_fer_pressure_field(pressure, width, height, 16)
+}
+
 /// Calculate internal moments at a point in the plate
 /// 
 /// # Arguments
@@ -1063,6 +1296,7 @@ pub fn plate_fer_pressure(pressure: f64, width: f64, height: f64) -> Vec24 {
 /// 
 /// # Returns
 /// [Mx, My, Mxy] - Internal moments per unit width
+#[allow(clippy::too_many_arguments)]
 pub fn plate_moments(
     x: f64,
     y: f64,
@@ -1112,6 +1346,69 @@ pub fn plate_moments(
     [mx, my, mxy]
 }
 
+/// Restrained thermal membrane forces and moments per unit width
+///
+/// A temperature change `delta_t` at the mid-surface and a difference
+/// `gradient` between the +z and -z faces give the free thermal strain
+/// `alpha delta_t` and curvature `alpha gradient / t` in both directions.
+///
+/// # Returns
+/// [Nx, Ny, Mx, My] - Forces and moments needed to suppress them
+#[allow(clippy::too_many_arguments)]
+pub fn plate_thermal_resultants(
+    alpha: f64,
+    delta_t: f64,
+    gradient: f64,
+    e: f64,
+    nu: f64,
+    t: f64,
+    kx_mod: f64,
+    ky_mod: f64,
+) -> [f64; 4] {
+    let dm = membrane_constitutive_matrix(e, nu, kx_mod, ky_mod);
+    let db = bending_constitutive_matrix(e, nu, t, kx_mod, ky_mod);
+    let strain = alpha * delta_t;
+    let curvature = alpha * gradient / t;
+
+    [
+        t * (dm[(0, 0)] + dm[(0, 1)]) * strain,
+        t * (dm[(1, 0)] + dm[(1, 1)]) * strain,
+        (db[(0, 0)] + db[(0, 1)]) * curvature,
+        (db[(1, 0)] + db[(1, 1)]) * curvature,
+    ]
+}
+
+/// Compute fixed end reactions for a plate temperature change
+///
+/// The restrained thermal forces and moments from `plate_thermal_resultants`
+/// are constant over the element, so the consistent nodal loads reduce to
+/// their resultants along each edge, shared equally by the edge's corners.
+/// A hotter +z face curls the plate towards -z at its edges.
+///
+/// # Arguments
+/// * `resultants` - [Nx, Ny, Mx, My] from `plate_thermal_resultants`
+/// * `width` - Plate width
+/// * `height` - Plate height
+///
+/// # Returns
+/// 24-element fixed end reaction vector
+pub fn plate_fer_thermal(resultants: [f64; 4], width: f64, height: f64) -> Vec24 {
+    let [nx, ny, mx, my] = resultants;
+    let mut fer = Vec24::zeros();
+
+    // Outward edge normal signs of the i, j, m and n corners
+    let signs = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    for (k, (sx, sy)) in signs.iter().enumerate() {
+        fer[6 * k] = -sx * nx * height / 2.0;
+        fer[6 * k + 1] = -sy * ny * width / 2.0;
+        // Edge moments rotating the x = width edge about +y and the y = height edge about -x
+        fer[6 * k + 3] = sy * my * width / 2.0;
+        fer[6 * k + 4] = -sx * mx * height / 2.0;
+    }
+
+    fer
+}
+
 /// Calculate membrane stresses at a point in the plate
 /// 
 /// # Arguments
@@ -1122,6 +1419,7 @@ pub fn plate_moments(
 /// 
 /// # Returns
 /// [sigma_x, sigma_y, tau_xy] - In-plane stresses
+#[allow(clippy::too_many_arguments)]
 pub fn plate_membrane_stress(
     x: f64,
     y: f64,
@@ -1175,6 +1473,52 @@ mod tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_bilinear_fer_matches_uniform() {
+        let uniform = plate_fer_pressure(1500.0, 2.0, 1.5);
+        let bilinear = plate_fer_bilinear([1500.0; 4], 2.0, 1.5);
+        assert_relative_eq!(bilinear, uniform, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_hydrostatic_fer_total_force() {
+        // 3m tall wall panel, fluid surface 1m below the top: resultant is
+        // gamma * 2^2 / 2 per metre of width
+        let (gamma, width) = (9810.0, 2.0);
+        let fer = plate_fer_hydrostatic(2.0, gamma, [0.0, 0.0, 3.0, 3.0], width, 3.0);
+        let total: f64 = (0..4).map(|k| fer[6 * k + 2]).sum();
+        assert_relative_eq!(-total, gamma * 2.0 * width, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn test_point_load_fer_is_statically_equivalent() {
+        // Forces sum to the load and moments about the i-node match its position
+        let (p, x, y) = (-10_000.0, 1.3, 0.4);
+        let fer = plate_fer_point_load(p, x, y, 2.0, 1.5);
+        let corners = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.5), (0.0, 1.5)];
+
+        let fz: f64 = (0..4).map(|k| fer[6 * k + 2]).sum();
+        let mx: f64 = (0..4).map(|k| fer[6 * k + 3] + corners[k].1 * fer[6 * k + 2]).sum();
+        let my: f64 = (0..4).map(|k| fer[6 * k + 4] - corners[k].0 * fer[6 * k + 2]).sum();
+
+        assert_relative_eq!(fz, -p, epsilon = 1e-8);
+        assert_relative_eq!(mx, -p * y, epsilon = 1e-8);
+        assert_relative_eq!(my, p * x, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_foundation_stiffness_rigid_settlement() {
+        // A uniform settlement w = 1 mobilizes k * area in total
+        let k = plate_foundation_stiffness(50e6, 2.0, 1.5);
+        let w_dofs = [2, 8, 14, 20];
+        let total: f64 = w_dofs.iter()
+            .flat_map(|&i| w_dofs.iter().map(move |&j| (i, j)))
+            .map(|(i, j)| k[(i, j)])
+            .sum();
+        assert_relative_eq!(total, 50e6 * 3.0, max_relative = 1e-12);
+        assert_relative_eq!(k, k.transpose(), epsilon = 1e-6);
+    }
+
     #[test]
     fn test_membrane_constitutive_isotropic() {
         let e = 200e9;
@@ -1233,4 +1577,30 @@ mod tests {
         assert_relative_eq!(t[(1, 2)], 1.0, epsilon = 1e-10);  // y = Z
         assert_relative_eq!(t[(2, 1)], -1.0, epsilon = 1e-10); // z = -Y
     }
+
+    #[test]
+    fn test_warped_quad_rigid_rotation_is_strain_free() {
+        // A twisted rectangle: corners alternately 0.1 either side of the XZ plane
+        let corners = [[0.0, 0.1, 0.0], [2.0, -0.1, 0.0], [2.0, 0.1, 1.5], [0.0, -0.1, 1.5]];
+        let (flat, offsets) = quad_mean_plane(&corners);
+        assert_relative_eq!(flat[2][1], 0.0, epsilon = 1e-12);
+        let k = plate_local_stiffness(200e9, 0.3, 0.02, 2.0, 1.5, 1.0, 1.0);
+        let t = plate_transformation_matrix(&flat[0], &flat[1], &flat[3]);
+        let corrected = t * quad_warping_correction(&offsets);
+        
+        // Small rigid rotation about in-plane axes
+        let theta = Vector3::new(1e-3, 0.0, 2e-3);
+        let mut d = Vec24::zeros();
+        for (node, c) in corners.iter().enumerate() {
+            let u = theta.cross(&Vector3::from(*c));
+            for a in 0..3 {
+                d[node * 6 + a] = u[a];
+                d[node * 6 + 3 + a] = theta[a];
+            }
+        }
+        let uncorrected = (t.transpose() * k * t * d).norm();
+        let f = (corrected.transpose() * k * corrected * d).norm();
+        assert!(uncorrected > 1e3, "expected spurious forces without correction");
+        assert!(f < 1e-9 * uncorrected, "rigid rotation produced forces {f}");
+    }
 }
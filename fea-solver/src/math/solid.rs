@@ -0,0 +1,157 @@
+//! Solid element calculations - constant strain tetrahedron (Tet4)
+//!
+//! The Tet4 element has linear displacement shape functions, so the strain
+//! and stress are constant over the element. Each node carries the three
+//! translational DOFs [ux, uy, uz]; element vectors are ordered node by node.
+//! Strains and stresses use the Voigt order [xx, yy, zz, xy, yz, zx] with
+//! engineering shear strains.
+
+use nalgebra::{Matrix4, SMatrix};
+
+use super::{Mat12, Mat6, Vec12};
+
+/// 6x12 strain-displacement matrix of a Tet4 element
+pub type Mat6x12 = SMatrix<f64, 6, 12>;
+
+/// Isotropic 3D elasticity matrix relating stress to engineering strain
+pub fn solid_elasticity_matrix(e: f64, nu: f64) -> Mat6 {
+    let c = e / ((1.0 + nu) * (1.0 - 2.0 * nu));
+    let mut d = Mat6::zeros();
+
+    for i in 0..3 {
+        for j in 0..3 {
+            d[(i, j)] = if i == j { c * (1.0 - nu) } else { c * nu };
+        }
+        d[(i + 3, i + 3)] = c * (1.0 - 2.0 * nu) / 2.0;
+    }
+
+    d
+}
+
+/// Signed volume of a tetrahedron
+///
+/// Positive when nodes 2, 3 and 4 are ordered counter-clockwise when viewed
+/// from node 1.
+pub fn tet4_volume(coords: &[[f64; 3]; 4]) -> f64 {
+    let [a, b, c, d] = coords;
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let w = [d[0] - a[0], d[1] - a[1], d[2] - a[2]];
+
+    let triple = u[0] * (v[1] * w[2] - v[2] * w[1])
+        - u[1] * (v[0] * w[2] - v[2] * w[0])
+        + u[2] * (v[0] * w[1] - v[1] * w[0]);
+    triple / 6.0
+}
+
+/// Strain-displacement matrix B of a Tet4 element
+///
+/// Returns None for a degenerate (flat) tetrahedron.
+pub fn tet4_strain_displacement(coords: &[[f64; 3]; 4]) -> Option<Mat6x12> {
+    // Shape functions N = [1 x y z] M^-1, so dNi/dx_k = (M^-1)[k + 1, i]
+    let mut m = Matrix4::zeros();
+    for (i, c) in coords.iter().enumerate() {
+        m[(i, 0)] = 1.0;
+        m[(i, 1)] = c[0];
+        m[(i, 2)] = c[1];
+        m[(i, 3)] = c[2];
+    }
+
+    // Reject slivers whose volume is negligible relative to the longest edge
+    let mut longest = 0.0_f64;
+    for i in 0..4 {
+        for j in i + 1..4 {
+            let l2: f64 = (0..3).map(|k| (coords[i][k] - coords[j][k]).powi(2)).sum();
+            longest = longest.max(l2.sqrt());
+        }
+    }
+    if tet4_volume(coords).abs() <= 1e-10 * longest.powi(3) {
+        return None;
+    }
+    let m_inv = m.try_inverse()?;
+
+    let mut b = Mat6x12::zeros();
+    for i in 0..4 {
+        let (dx, dy, dz) = (m_inv[(1, i)], m_inv[(2, i)], m_inv[(3, i)]);
+        let col = i * 3;
+
+        b[(0, col)] = dx;
+        b[(1, col + 1)] = dy;
+        b[(2, col + 2)] = dz;
+        b[(3, col)] = dy;
+        b[(3, col + 1)] = dx;
+        b[(4, col + 1)] = dz;
+        b[(4, col + 2)] = dy;
+        b[(5, col)] = dz;
+        b[(5, col + 2)] = dx;
+    }
+
+    Some(b)
+}
+
+/// Global stiffness matrix of a Tet4 element, `K = V B^T D B`
+///
+/// Returns None for a degenerate tetrahedron.
+pub fn tet4_stiffness(coords: &[[f64; 3]; 4], e: f64, nu: f64) -> Option<Mat12> {
+    let b = tet4_strain_displacement(coords)?;
+    let d = solid_elasticity_matrix(e, nu);
+    Some(tet4_volume(coords).abs() * b.transpose() * d * b)
+}
+
+/// Constant stress [sx, sy, sz, txy, tyz, tzx] in a Tet4 element
+///
+/// Returns None for a degenerate tetrahedron.
+pub fn tet4_stress(coords: &[[f64; 3]; 4], e: f64, nu: f64, displacements: &Vec12) -> Option<[f64; 6]> {
+    let b = tet4_strain_displacement(coords)?;
+    let stress = solid_elasticity_matrix(e, nu) * b * displacements;
+    Some(stress.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const UNIT_TET: [[f64; 3]; 4] = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ];
+
+    #[test]
+    fn test_tet4_rigid_body_motion_is_stress_free() {
+        let k = tet4_stiffness(&UNIT_TET, 200e9, 0.3).unwrap();
+        assert_relative_eq!(tet4_volume(&UNIT_TET), 1.0 / 6.0, epsilon = 1e-15);
+
+        // Translation plus a small rotation about Z: u = -theta y, v = theta x
+        let theta = 1e-3;
+        let mut d = Vec12::zeros();
+        for (i, c) in UNIT_TET.iter().enumerate() {
+            d[i * 3] = 0.01 - theta * c[1];
+            d[i * 3 + 1] = -0.02 + theta * c[0];
+            d[i * 3 + 2] = 0.005;
+        }
+
+        let f = k * d;
+        assert!(f.norm() < 1e-6 * k.norm() * d.norm());
+    }
+
+    #[test]
+    fn test_tet4_uniaxial_stress() {
+        // Displacement field of uniaxial stress sx = E * eps
+        let (e, nu, eps) = (200e9, 0.3, 1e-4);
+        let mut d = Vec12::zeros();
+        for (i, c) in UNIT_TET.iter().enumerate() {
+            d[i * 3] = eps * c[0];
+            d[i * 3 + 1] = -nu * eps * c[1];
+            d[i * 3 + 2] = -nu * eps * c[2];
+        }
+
+        let s = tet4_stress(&UNIT_TET, e, nu, &d).unwrap();
+        assert_relative_eq!(s[0], e * eps, max_relative = 1e-12);
+        for component in &s[1..] {
+            assert!(component.abs() < 1e-6 * e * eps);
+        }
+    }
+}
@@ -36,9 +36,9 @@ impl SparseMatrixBuilder {
     /// Add a dense block to the sparse matrix
     #[inline]
     pub fn add_block(&mut self, row_start: usize, col_start: usize, block: &[[f64; 6]; 6]) {
-        for i in 0..6 {
-            for j in 0..6 {
-                self.add(row_start + i, col_start + j, block[i][j]);
+        for (i, row) in block.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                self.add(row_start + i, col_start + j, value);
             }
         }
     }
@@ -121,10 +121,7 @@ impl SparseCholeskySolver {
         }
         
         // Allocate skyline storage
-        let mut skyline: Vec<Vec<f64>> = Vec::with_capacity(size);
-        for i in 0..size {
-            skyline.push(vec![0.0; heights[i] + 1]);
-        }
+        let mut skyline: Vec<Vec<f64>> = heights.iter().map(|&height| vec![0.0; height + 1]).collect();
         
         // Copy values into skyline storage
         for (row, col, &val) in csr.triplet_iter() {
@@ -419,12 +416,9 @@ pub fn reverse_cuthill_mckee(csr: &CsrMatrix<f64>) -> Vec<usize> {
         
         // Handle disconnected components
         if queue.is_empty() && result.len() < n {
-            for i in 0..n {
-                if !visited[i] {
-                    queue.push_back(i);
-                    visited[i] = true;
-                    break;
-                }
+            if let Some(i) = visited.iter().position(|&v| !v) {
+                queue.push_back(i);
+                visited[i] = true;
             }
         }
     }
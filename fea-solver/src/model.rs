@@ -1,46 +1,275 @@
 //! FE Model - Main structural model container
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+use std::sync::Arc;
+use nalgebra_sparse::CsrMatrix;
 use serde::{Deserialize, Serialize};
 
-use crate::analysis::{AnalysisOptions, AnalysisType};
-use crate::elements::{Material, Member, Node, Plate, Quad, Section, Support};
-use crate::error::{FEAError, FEAResult};
-use crate::loads::{DistributedLoad, LoadCombination, NodeLoad, PlateLoad, PointLoad};
+use crate::analysis::stepped::StepState;
+use crate::analysis::{
+    estimate_condition, parallel, subspace_iteration, AnalysisOptions, AnalysisProgress, AnalysisStage, AnalysisType,
+    CancelToken, ConditioningReport, Factorization, LinearSolver, ModalResults, Mode, SolverBackend, StiffnessRatio,
+    SteppedAnalysis, TimeHistory, TimeHistoryResults,
+};
+use crate::elements::{
+    Gap, Group, Imperfection, ImperfectionShape, Link, LinkKind, Material, Member, Node, PhysicalMember, Plate, Quad,
+    QuadQuality, RigidLink, Section, StoryLevel, Support, Tetra,
+};
+use crate::error::{FEAError, FEAResult, SingularityDiagnostics, UnconstrainedDof};
+use crate::handles::{Handle, MemberId, NodeId, Registry};
+use crate::io::matrix::AssembledSystem;
+use crate::loads::{
+    skip_load_patterns, DistributedLoad, LoadCombination, LoadDirection, LoadEnvelope, NodeLoad, PlateLoad,
+    PlatePointLoad, PlateThermalLoad, PointLoad, PressureDistribution, PrestressLoad,
+};
 use crate::math::{self, Mat, Vec as FEVec};
-use crate::results::{AnalysisSummary, MemberForces, NodeDisplacement, PlateStressResult, Reactions};
+use crate::math::sparse::SparseMatrixBuilder;
+use crate::results::{
+    AnalysisSummary, Envelope, MemberDeflection, MemberForces, NodalShellStress, NodeDisplacement,
+    PlateStressResult, Reactions, ResultStore, ResultsDb, SectionCut, SectionCutResult, ShellSurfaceStress, SlabDesignMoments,
+    SolidStress, StoryResult, StressPoints,
+};
+use crate::units::{ConvertUnits, UnitSystem};
 
 /// The main 3D finite element model
+///
+/// Entities are kept in name order, so assembly, DOF numbering and serialised
+/// output do not depend on the order the model was built in.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FEModel {
     /// Nodes in the model
-    pub nodes: HashMap<String, Node>,
+    pub nodes: BTreeMap<String, Node>,
     /// Materials in the model
-    pub materials: HashMap<String, Material>,
+    pub materials: BTreeMap<String, Material>,
     /// Sections in the model
-    pub sections: HashMap<String, Section>,
+    pub sections: BTreeMap<String, Section>,
     /// Members (frame elements) in the model
-    pub members: HashMap<String, Member>,
+    pub members: BTreeMap<String, Member>,
+    /// Physical members, split into analysis segments in `members` before each analysis
+    #[serde(default)]
+    pub physical_members: BTreeMap<String, PhysicalMember>,
     /// Plates (rectangular shell elements)
-    pub plates: HashMap<String, Plate>,
+    pub plates: BTreeMap<String, Plate>,
     /// Quads (general quadrilateral shell elements)
-    pub quads: HashMap<String, Quad>,
+    pub quads: BTreeMap<String, Quad>,
+    /// Tetras (4-node solid elements)
+    #[serde(default)]
+    pub tetras: BTreeMap<String, Tetra>,
     /// Support conditions at nodes
-    pub supports: HashMap<String, Support>,
+    pub supports: BTreeMap<String, Support>,
+    /// Rigid link constraints
+    #[serde(default)]
+    pub rigid_links: BTreeMap<String, RigidLink>,
+    /// Gap and hook elements
+    #[serde(default)]
+    pub gaps: BTreeMap<String, Gap>,
+    /// Damper and isolator link elements (time-history analysis only)
+    #[serde(default)]
+    pub links: BTreeMap<String, Link>,
+    /// Additional lumped translational masses at nodes
+    #[serde(default)]
+    pub node_masses: BTreeMap<String, f64>,
     /// Node loads
-    pub node_loads: HashMap<String, Vec<NodeLoad>>,
+    pub node_loads: BTreeMap<String, Vec<NodeLoad>>,
     /// Member point loads
-    pub member_point_loads: HashMap<String, Vec<PointLoad>>,
+    pub member_point_loads: BTreeMap<String, Vec<PointLoad>>,
     /// Member distributed loads
-    pub member_dist_loads: HashMap<String, Vec<DistributedLoad>>,
+    pub member_dist_loads: BTreeMap<String, Vec<DistributedLoad>>,
+    /// Member prestress loads
+    #[serde(default)]
+    pub member_prestress_loads: BTreeMap<String, Vec<PrestressLoad>>,
     /// Plate/quad pressure loads
-    pub plate_loads: HashMap<String, Vec<PlateLoad>>,
+    pub plate_loads: BTreeMap<String, Vec<PlateLoad>>,
+    /// Plate/quad concentrated loads
+    #[serde(default)]
+    pub plate_point_loads: BTreeMap<String, Vec<PlatePointLoad>>,
+    /// Plate/quad temperature loads
+    #[serde(default)]
+    pub plate_thermal_loads: BTreeMap<String, Vec<PlateThermalLoad>>,
     /// Load combinations
-    pub load_combos: HashMap<String, LoadCombination>,
+    pub load_combos: BTreeMap<String, LoadCombination>,
+    /// Load envelopes (sets of combinations enveloped for design)
+    #[serde(default)]
+    pub load_envelopes: BTreeMap<String, LoadEnvelope>,
+    /// Named groups (selection sets) of nodes, members and plates
+    #[serde(default)]
+    pub groups: BTreeMap<String, Group>,
+    /// Story levels for story drift and story shear results
+    #[serde(default)]
+    pub stories: BTreeMap<String, StoryLevel>,
+    /// Units of every value in the model and its results
+    #[serde(default)]
+    pub units: UnitSystem,
     
     /// Analysis solution status
     #[serde(skip)]
-    solution: Option<AnalysisType>,
+    pub(crate) solution: Option<AnalysisType>,
+    
+    /// Nodes with lifted one-way supports by load combination
+    #[serde(skip)]
+    uplift: HashMap<String, Vec<String>>,
+    
+    /// Tension- or compression-only members gone slack by load combination
+    #[serde(skip)]
+    slack_members: HashMap<String, Vec<String>>,
+    
+    /// Model quality warnings from the last analysis
+    #[serde(skip)]
+    warnings: Vec<String>,
+    
+    /// Node handles, rebuilt when the model is prepared for analysis
+    #[serde(skip)]
+    node_ids: Registry<NodeId>,
+    
+    /// Member handles, rebuilt when the model is prepared for analysis
+    #[serde(skip)]
+    member_ids: Registry<MemberId>,
+    
+    /// End nodes of each member, indexed by member handle
+    #[serde(skip)]
+    member_nodes: Vec<[NodeId; 2]>,
+    
+    /// Node coordinates, indexed by node handle
+    #[serde(skip)]
+    node_coords: Vec<[f64; 3]>,
+    
+    /// Stiffness half-bandwidth in DOFs with nodes in name order, and as numbered
+    #[serde(skip)]
+    bandwidth: (usize, usize),
+    
+    /// Global stiffness and factorisation of the last analysis
+    #[serde(skip)]
+    stiffness_cache: Option<StiffnessCache>,
+    
+    /// Node and member results by load combination
+    #[serde(skip)]
+    pub(crate) store: ResultStore,
+}
+
+/// Assembled global stiffness, reused by later analyses while the stiffness
+/// inputs (geometry, properties, elements, supports) hash the same, so that
+/// editing only loads skips assembly and factorisation
+#[derive(Clone)]
+struct StiffnessCache {
+    fingerprint: u64,
+    k_global: Arc<Mat>,
+    dof_map: HashMap<String, usize>,
+    /// Factorised free stiffness, once a linear solve has needed it
+    factorization: Option<Arc<dyn Factorization>>,
+}
+
+impl fmt::Debug for StiffnessCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StiffnessCache")
+            .field("fingerprint", &self.fingerprint)
+            .field("dofs", &self.k_global.nrows())
+            .field("factorized", &self.factorization.is_some())
+            .finish()
+    }
+}
+
+/// Factorised free stiffness of a linear analysis, shared by the load
+/// combinations solved against it
+struct LinearSystem {
+    n_dofs: usize,
+    /// Rigid link and skewed support transformation to the full DOFs
+    constraints: Option<Constraints>,
+    free_dofs: Vec<usize>,
+    enforced_displacements: BTreeMap<usize, f64>,
+    /// K12 * D2 for the enforced support displacements
+    enforced_load: FEVec,
+    factorization: Arc<dyn Factorization>,
+}
+
+impl LinearSystem {
+    /// Full displacement vector under a global load vector
+    fn solve(&self, p_global: &FEVec) -> FEVec {
+        let constrained;
+        let p_global = match &self.constraints {
+            Some(c) => {
+                constrained = c.transform_load(p_global);
+                &constrained
+            }
+            None => p_global,
+        };
+        
+        // Solve K11 * D1 = P1 - K12 * D2
+        let p1 = FEVec::from_fn(self.free_dofs.len(), |i, _| p_global[self.free_dofs[i]] - self.enforced_load[i]);
+        let d1 = self.factorization.solve(&p1);
+
+        // Assemble full displacement vector
+        let mut d_full = FEVec::zeros(self.n_dofs);
+        for (i, &di) in self.free_dofs.iter().enumerate() {
+            d_full[di] = d1[i];
+        }
+        for (&di, &val) in &self.enforced_displacements {
+            d_full[di] = val;
+        }
+        
+        // Recover slave displacements from their masters
+        match &self.constraints {
+            Some(c) => c.recover(&d_full),
+            None => d_full,
+        }
+    }
+}
+
+/// Rigid link and skewed support transformation `d = C d'`, kept as the rows
+/// of `C` that differ from the identity so that it only touches the DOFs it
+/// constrains
+struct Constraints {
+    /// Non-zero entries `(column, coefficient)` of each constrained row
+    rows: BTreeMap<usize, Vec<(usize, f64)>>,
+}
+
+impl Constraints {
+    /// `C^T K C`, updating only the rows and columns of the constrained DOFs
+    fn transform_stiffness(&self, k: &Mat) -> Mat {
+        let mut transformed = k.clone();
+        
+        // K C = K + K[:, r] (C[r, :] - e_r) over the constrained rows r
+        let columns: Vec<FEVec> = self.rows.keys().map(|&r| k.column(r).into_owned()).collect();
+        for ((&r, row), column) in self.rows.iter().zip(&columns) {
+            transformed.column_mut(r).axpy(-1.0, column, 1.0);
+            for &(j, c) in row {
+                transformed.column_mut(j).axpy(c, column, 1.0);
+            }
+        }
+        
+        // C^T (K C), the same update on the rows
+        let rows: Vec<_> = self.rows.keys().map(|&r| transformed.row(r).into_owned()).collect();
+        for ((&r, row), values) in self.rows.iter().zip(&rows) {
+            transformed.row_mut(r).zip_apply(values, |t, v| *t -= v);
+            for &(j, c) in row {
+                transformed.row_mut(j).zip_apply(values, |t, v| *t += c * v);
+            }
+        }
+        transformed
+    }
+
+    /// `C^T p`, the load on the transformed DOFs
+    fn transform_load(&self, p: &FEVec) -> FEVec {
+        let mut transformed = p.clone();
+        for (&r, row) in &self.rows {
+            transformed[r] -= p[r];
+            for &(j, c) in row {
+                transformed[j] += c * p[r];
+            }
+        }
+        transformed
+    }
+
+    /// `C d`, the full displacements from the transformed DOFs
+    fn recover(&self, d: &FEVec) -> FEVec {
+        let mut full = d.clone();
+        for (&r, row) in &self.rows {
+            full[r] = row.iter().map(|&(j, c)| c * d[j]).sum();
+        }
+        full
+    }
 }
 
 impl Default for FEModel {
@@ -53,19 +282,42 @@ impl FEModel {
     /// Create a new empty model
     pub fn new() -> Self {
         Self {
-            nodes: HashMap::new(),
-            materials: HashMap::new(),
-            sections: HashMap::new(),
-            members: HashMap::new(),
-            plates: HashMap::new(),
-            quads: HashMap::new(),
-            supports: HashMap::new(),
-            node_loads: HashMap::new(),
-            member_point_loads: HashMap::new(),
-            member_dist_loads: HashMap::new(),
-            plate_loads: HashMap::new(),
-            load_combos: HashMap::new(),
+            nodes: BTreeMap::new(),
+            materials: BTreeMap::new(),
+            sections: BTreeMap::new(),
+            members: BTreeMap::new(),
+            physical_members: BTreeMap::new(),
+            plates: BTreeMap::new(),
+            quads: BTreeMap::new(),
+            tetras: BTreeMap::new(),
+            supports: BTreeMap::new(),
+            rigid_links: BTreeMap::new(),
+            gaps: BTreeMap::new(),
+            links: BTreeMap::new(),
+            node_masses: BTreeMap::new(),
+            node_loads: BTreeMap::new(),
+            member_point_loads: BTreeMap::new(),
+            member_dist_loads: BTreeMap::new(),
+            member_prestress_loads: BTreeMap::new(),
+            plate_loads: BTreeMap::new(),
+            plate_point_loads: BTreeMap::new(),
+            plate_thermal_loads: BTreeMap::new(),
+            load_combos: BTreeMap::new(),
+            load_envelopes: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            stories: BTreeMap::new(),
+            units: UnitSystem::SI,
             solution: None,
+            uplift: HashMap::new(),
+            slack_members: HashMap::new(),
+            warnings: Vec::new(),
+            node_ids: Registry::default(),
+            member_ids: Registry::default(),
+            member_nodes: Vec::new(),
+            node_coords: Vec::new(),
+            bandwidth: (0, 0),
+            stiffness_cache: None,
+            store: ResultStore::default(),
         }
     }
 
@@ -125,6 +377,34 @@ impl FEModel {
         Ok(())
     }
 
+    /// Add a physical member, split at intermediate nodes into analysis segments
+    ///
+    /// Segments named `{name}#1`, `{name}#2`, ... are generated in `members`
+    /// at the start of each analysis. Loads can be added under the physical
+    /// member's name, and results are reported along its full length.
+    pub fn add_physical_member(&mut self, name: &str, physical: PhysicalMember) -> FEAResult<()> {
+        let member = &physical.member;
+        if !self.nodes.contains_key(&member.i_node) {
+            return Err(FEAError::NodeNotFound(member.i_node.clone()));
+        }
+        if !self.nodes.contains_key(&member.j_node) {
+            return Err(FEAError::NodeNotFound(member.j_node.clone()));
+        }
+        if !self.materials.contains_key(&member.material) {
+            return Err(FEAError::MaterialNotFound(member.material.clone()));
+        }
+        if !self.sections.contains_key(&member.section) {
+            return Err(FEAError::SectionNotFound(member.section.clone()));
+        }
+        if self.members.contains_key(name) || self.physical_members.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        
+        self.physical_members.insert(name.to_string(), physical);
+        self.solution = None;
+        Ok(())
+    }
+
     /// Add a plate element to the model
     pub fn add_plate(&mut self, name: &str, plate: Plate) -> FEAResult<()> {
         // Validate nodes exist
@@ -164,11 +444,439 @@ impl FEModel {
         Ok(())
     }
 
+    /// Add a tetrahedral solid element to the model
+    pub fn add_tetra(&mut self, name: &str, tetra: Tetra) -> FEAResult<()> {
+        for node_name in &tetra.nodes {
+            if !self.nodes.contains_key(node_name) {
+                return Err(FEAError::NodeNotFound(node_name.clone()));
+            }
+        }
+        if !self.materials.contains_key(&tetra.material) {
+            return Err(FEAError::MaterialNotFound(tetra.material.clone()));
+        }
+        if self.tetras.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        if math::solid::tet4_strain_displacement(&self.tetra_coords(&tetra)).is_none() {
+            return Err(FEAError::InvalidGeometry(format!("Tetra '{name}' has zero volume")));
+        }
+        
+        self.tetras.insert(name.to_string(), tetra);
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Add a gap or hook element
+    pub fn add_gap(&mut self, name: &str, gap: Gap) -> FEAResult<()> {
+        for node_name in [&gap.i_node, &gap.j_node] {
+            if !self.nodes.contains_key(node_name) {
+                return Err(FEAError::NodeNotFound(node_name.clone()));
+            }
+        }
+        if self.gaps.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        if self.nodes[&gap.i_node].distance_to(&self.nodes[&gap.j_node]) < 1e-10 {
+            return Err(FEAError::InvalidGeometry(format!("Gap '{name}' has coincident nodes")));
+        }
+        if gap.opening < 0.0 || gap.stiffness <= 0.0 {
+            return Err(FEAError::InvalidInput(format!(
+                "Gap '{name}' needs a non-negative opening and a positive stiffness"
+            )));
+        }
+        
+        self.gaps.insert(name.to_string(), gap);
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Add a viscous damper or isolator link element
+    ///
+    /// Links between coincident nodes (e.g. an isolator at a column base)
+    /// need an explicit direction.
+    pub fn add_link(&mut self, name: &str, link: Link) -> FEAResult<()> {
+        for node_name in [&link.i_node, &link.j_node] {
+            if !self.nodes.contains_key(node_name) {
+                return Err(FEAError::NodeNotFound(node_name.clone()));
+            }
+        }
+        if self.links.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        let length = self.nodes[&link.i_node].distance_to(&self.nodes[&link.j_node]);
+        match link.direction {
+            Some(d) if d.iter().map(|v| v * v).sum::<f64>() < 1e-20 => {
+                return Err(FEAError::InvalidInput(format!("Link '{name}' has a zero direction vector")));
+            }
+            None if length < 1e-10 => {
+                return Err(FEAError::InvalidGeometry(format!(
+                    "Link '{name}' has coincident nodes and no direction"
+                )));
+            }
+            _ => {}
+        }
+        let valid = match link.kind {
+            LinkKind::ViscousDamper { c, alpha } => c > 0.0 && alpha > 0.0,
+            LinkKind::BilinearIsolator { k1, k2, fy } => k1 > 0.0 && (0.0..k1).contains(&k2) && fy > 0.0,
+        };
+        if !valid {
+            return Err(FEAError::InvalidInput(format!(
+                "Link '{name}' needs positive properties with a post-yield stiffness below the elastic stiffness"
+            )));
+        }
+        
+        self.links.insert(name.to_string(), link);
+        Ok(())
+    }
+
+    /// Add a lumped translational mass at a node (accumulates)
+    pub fn add_node_mass(&mut self, node_name: &str, mass: f64) -> FEAResult<()> {
+        if !self.nodes.contains_key(node_name) {
+            return Err(FEAError::NodeNotFound(node_name.to_string()));
+        }
+        *self.node_masses.entry(node_name.to_string()).or_insert(0.0) += mass;
+        Ok(())
+    }
+
+    /// Add a rigid link constraint
+    ///
+    /// A node may be the slave of only one link, and slave nodes cannot be
+    /// masters of other links or carry supports.
+    pub fn add_rigid_link(&mut self, name: &str, link: RigidLink) -> FEAResult<()> {
+        for node_name in [&link.master, &link.slave] {
+            if !self.nodes.contains_key(node_name) {
+                return Err(FEAError::NodeNotFound(node_name.clone()));
+            }
+        }
+        if self.rigid_links.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        if link.master == link.slave {
+            return Err(FEAError::InvalidInput(format!("Rigid link '{name}' connects node '{}' to itself", link.master)));
+        }
+        for (other_name, other) in &self.rigid_links {
+            if other.slave == link.slave {
+                return Err(FEAError::InvalidInput(format!(
+                    "Node '{}' is already the slave of rigid link '{other_name}'", link.slave
+                )));
+            }
+            if other.slave == link.master || other.master == link.slave {
+                return Err(FEAError::InvalidInput(format!(
+                    "Rigid link '{name}' would chain with rigid link '{other_name}'"
+                )));
+            }
+        }
+        
+        self.rigid_links.insert(name.to_string(), link);
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Tie members to a shell mesh with rigid links
+    ///
+    /// Each end node of the given members is linked (as slave) to the nearest
+    /// corner node of the given plates/quads within `max_distance`, giving
+    /// composite action between an eccentric beam and a meshed slab. Member
+    /// nodes shared with the shell are left alone. Returns the names of the
+    /// created links (`"Tie {node}"`).
+    pub fn tie_members_to_shell(
+        &mut self,
+        members: &[&str],
+        plates: &[&str],
+        max_distance: f64,
+    ) -> FEAResult<Vec<String>> {
+        let mut shell_nodes: Vec<String> = Vec::new();
+        for plate in plates {
+            for node in self.plate_corner_nodes(plate)? {
+                if !shell_nodes.contains(&node) {
+                    shell_nodes.push(node);
+                }
+            }
+        }
+        
+        let mut beam_nodes: Vec<String> = Vec::new();
+        for name in members {
+            let member = self.members.get(*name)
+                .ok_or_else(|| FEAError::MemberNotFound(name.to_string()))?;
+            for node in [&member.i_node, &member.j_node] {
+                if !beam_nodes.contains(node) && !shell_nodes.contains(node) {
+                    beam_nodes.push(node.clone());
+                }
+            }
+        }
+        
+        let mut links = Vec::new();
+        for beam_node in beam_nodes {
+            let nearest = shell_nodes.iter()
+                .map(|s| (s, self.nodes[&beam_node].distance_to(&self.nodes[s])))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            
+            match nearest {
+                Some((shell_node, distance)) if distance <= max_distance => {
+                    let link_name = format!("Tie {beam_node}");
+                    self.add_rigid_link(&link_name, RigidLink::new(&shell_node.clone(), &beam_node))?;
+                    links.push(link_name);
+                }
+                _ => {
+                    return Err(FEAError::InvalidGeometry(format!(
+                        "No shell node within {max_distance} of member node '{beam_node}'"
+                    )));
+                }
+            }
+        }
+        
+        Ok(links)
+    }
+
+    /// Merge nodes closer together than `tolerance`, e.g. after a CAD import
+    ///
+    /// Nodes are visited in name order and each absorbs the later nodes within
+    /// `tolerance` of it. Element connectivity, node loads and masses move to
+    /// the kept node, as does a support if the kept node has none. Members,
+    /// gaps, links and rigid links left with both ends on one node are removed
+    /// along with their loads. Returns a map from each removed node to the
+    /// node it was merged into.
+    pub fn merge_duplicate_nodes(&mut self, tolerance: f64) -> FEAResult<HashMap<String, String>> {
+        if tolerance < 0.0 {
+            return Err(FEAError::InvalidInput(format!("Negative merge tolerance {tolerance}")));
+        }
+        // Nodes generated for physical members are rebuilt on every analysis
+        let generated: HashSet<&String> = self.physical_members.values()
+            .flat_map(|physical| &physical.generated_nodes)
+            .collect();
+        let mut names: Vec<&String> = self.nodes.keys().filter(|name| !generated.contains(name)).collect();
+        names.sort();
+        
+        // Sweep in x so that only nearby nodes are compared
+        let mut by_x: Vec<(f64, &String)> = names.iter().map(|&name| (self.nodes[name].x, name)).collect();
+        by_x.sort_by(|a, b| a.0.total_cmp(&b.0));
+        
+        let mut merged: HashMap<String, String> = HashMap::new();
+        for &name in &names {
+            if merged.contains_key(name) {
+                continue;
+            }
+            let node = &self.nodes[name];
+            let first = by_x.partition_point(|(x, _)| *x < node.x - tolerance);
+            for &(x, other) in &by_x[first..] {
+                if x > node.x + tolerance {
+                    break;
+                }
+                if other > name && !merged.contains_key(other) && node.distance_to(&self.nodes[other]) <= tolerance {
+                    merged.insert(other.clone(), name.clone());
+                }
+            }
+        }
+        if merged.is_empty() {
+            return Ok(merged);
+        }
+        
+        self.repoint_nodes(&merged);
+        for group in self.groups.values_mut() {
+            let mut seen = HashSet::new();
+            group.nodes.retain(|n| seen.insert(merged.get(n).unwrap_or(n).clone()));
+            for node in &mut group.nodes {
+                if let Some(kept) = merged.get(node) {
+                    node.clone_from(kept);
+                }
+            }
+        }
+        
+        let mut removed: Vec<(&String, &String)> = merged.iter().collect();
+        removed.sort();
+        for (old, kept) in removed {
+            self.nodes.remove(old);
+            if let Some(support) = self.supports.remove(old) {
+                self.supports.entry(kept.clone()).or_insert(support);
+            }
+            if let Some(loads) = self.node_loads.remove(old) {
+                self.node_loads.entry(kept.clone()).or_default().extend(loads);
+            }
+            if let Some(mass) = self.node_masses.remove(old) {
+                *self.node_masses.entry(kept.clone()).or_default() += mass;
+            }
+        }
+        
+        // Drop line elements that collapsed onto a single node
+        let collapsed: Vec<String> = self.members.iter()
+            .filter(|(_, m)| m.i_node == m.j_node)
+            .map(|(name, _)| name.clone())
+            .chain(self.physical_members.iter()
+                .filter(|(_, p)| p.member.i_node == p.member.j_node)
+                .map(|(name, _)| name.clone()))
+            .collect();
+        for name in collapsed {
+            self.members.remove(&name);
+            self.physical_members.remove(&name);
+            self.member_point_loads.remove(&name);
+            self.member_dist_loads.remove(&name);
+            self.member_prestress_loads.remove(&name);
+            for group in self.groups.values_mut() {
+                group.members.retain(|m| *m != name);
+            }
+        }
+        self.gaps.retain(|_, gap| gap.i_node != gap.j_node);
+        self.links.retain(|_, link| link.i_node != link.j_node);
+        self.rigid_links.retain(|_, link| link.master != link.slave);
+        
+        self.solution = None;
+        Ok(merged)
+    }
+
+    /// Split members where they cross each other or pass through a node
+    ///
+    /// Members crossing within 1e-6 times their length of each other get a
+    /// new shared node `X1`, `X2`, ... at the crossing, and every member is
+    /// also split at existing nodes lying on it (T-junctions). A split member
+    /// `M` is replaced by `M.1`, `M.2`, ... from its i-node, keeping its end
+    /// releases at the outer ends. Its distributed and point loads move to
+    /// the pieces they fall on and its prestress is applied to every piece.
+    /// Physical members are left alone as they split themselves. Returns the
+    /// pieces of each split member.
+    pub fn split_intersecting_members(&mut self) -> FEAResult<HashMap<String, Vec<String>>> {
+        let segments: HashSet<&String> = self.physical_members.values()
+            .flat_map(|physical| physical.segments.iter().map(|(segment, _, _)| segment))
+            .collect();
+        let mut names: Vec<String> = self.members.keys()
+            .filter(|name| !segments.contains(name))
+            .cloned()
+            .collect();
+        names.sort();
+        
+        // (start, unit axis, length) of each member line
+        let lines: Vec<(math::Vec3, math::Vec3, f64)> = names.iter()
+            .map(|name| {
+                let member = &self.members[name];
+                let start = math::Vec3::from(self.nodes[&member.i_node].coords());
+                let end = math::Vec3::from(self.nodes[&member.j_node].coords());
+                let length = (end - start).norm();
+                (start, (end - start) / length.max(1e-12), length)
+            })
+            .collect();
+        
+        let mut stations: Vec<Vec<(f64, String)>> = vec![Vec::new(); names.len()];
+        for (k, (start, axis, length)) in lines.iter().enumerate() {
+            for (node_name, node) in &self.nodes {
+                if let Some(x) = interior_station(start, axis, *length, &math::Vec3::from(node.coords())) {
+                    stations[k].push((x, node_name.clone()));
+                }
+            }
+        }
+        
+        let mut new_nodes: Vec<(String, math::Vec3)> = Vec::new();
+        let mut next = 1;
+        for a in 0..names.len() {
+            for b in a + 1..names.len() {
+                let ((pa, da, la), (pb, db, lb)) = (&lines[a], &lines[b]);
+                let tol = 1e-6 * la.min(*lb);
+                let Some((xa, xb, point)) = line_crossing((pa, da), (pb, db), tol) else {
+                    continue;
+                };
+                // Crossings at member ends are picked up as nodes on the other line
+                let interior = |x: f64, length: f64| x > 1e-6 * length && x < length * (1.0 - 1e-6);
+                if !interior(xa, *la) || !interior(xb, *lb) {
+                    continue;
+                }
+                if stations[a].iter().any(|(x, _)| (x - xa).abs() <= tol) {
+                    continue;
+                }
+                let node_name = match new_nodes.iter().find(|(_, p)| (p - point).norm() <= tol) {
+                    Some((name, _)) => name.clone(),
+                    None => {
+                        while self.nodes.contains_key(&format!("X{next}")) {
+                            next += 1;
+                        }
+                        let name = format!("X{next}");
+                        next += 1;
+                        new_nodes.push((name.clone(), point));
+                        name
+                    }
+                };
+                stations[a].push((xa, node_name.clone()));
+                stations[b].push((xb, node_name));
+            }
+        }
+        
+        // Check piece names before changing the model
+        for (name, stations) in names.iter().zip(&stations) {
+            for k in 1..=stations.len() + 1 {
+                let piece = format!("{name}.{k}");
+                if self.members.contains_key(&piece) || self.physical_members.contains_key(&piece) {
+                    return Err(FEAError::DuplicateName(piece));
+                }
+            }
+        }
+        
+        for (name, point) in new_nodes {
+            self.nodes.insert(name, Node::new(point.x, point.y, point.z));
+        }
+        
+        let mut pieces_by_member = HashMap::new();
+        for ((name, mut stations), (_, _, length)) in names.into_iter().zip(stations).zip(lines) {
+            if stations.is_empty() {
+                continue;
+            }
+            let tol = 1e-6 * length;
+            stations.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+            stations.dedup_by(|next, prev| next.0 - prev.0 <= tol);
+            
+            let member = self.members.remove(&name).unwrap();
+            let mut points = vec![(0.0, member.i_node.clone())];
+            points.extend(stations);
+            points.push((length, member.j_node.clone()));
+            
+            let dist_loads = self.member_dist_loads.remove(&name).unwrap_or_default();
+            let point_loads = self.member_point_loads.remove(&name).unwrap_or_default();
+            let prestress = self.member_prestress_loads.remove(&name);
+            
+            let last = points.len() - 2;
+            let mut pieces = Vec::with_capacity(last + 1);
+            for (k, pair) in points.windows(2).enumerate() {
+                let piece = format!("{name}.{}", k + 1);
+                let (start, end) = (pair[0].0, pair[1].0);
+                self.members.insert(piece.clone(), member.piece(&pair[0].1, &pair[1].1, k == 0, k == last));
+                
+                let dist: Vec<DistributedLoad> = dist_loads.iter()
+                    .filter_map(|load| load.clipped(start, end, length))
+                    .collect();
+                if !dist.is_empty() {
+                    self.member_dist_loads.insert(piece.clone(), dist);
+                }
+                let points: Vec<PointLoad> = point_loads.iter()
+                    .filter(|load| load.position >= start && (load.position < end || k == last))
+                    .map(|load| PointLoad { position: load.position - start, ..load.clone() })
+                    .collect();
+                if !points.is_empty() {
+                    self.member_point_loads.insert(piece.clone(), points);
+                }
+                if let Some(prestress) = &prestress {
+                    self.member_prestress_loads.insert(piece.clone(), prestress.clone());
+                }
+                pieces.push(piece);
+            }
+            pieces_by_member.insert(name, pieces);
+        }
+        
+        self.solution = None;
+        Ok(pieces_by_member)
+    }
+
     /// Add a support condition
     pub fn add_support(&mut self, node_name: &str, support: Support) -> FEAResult<()> {
         if !self.nodes.contains_key(node_name) {
             return Err(FEAError::NodeNotFound(node_name.to_string()));
         }
+        if let Some(axes) = support.orientation {
+            let orthonormal = (0..3).all(|i| (0..3).all(|j| {
+                let dot: f64 = (0..3).map(|k| axes[i][k] * axes[j][k]).sum();
+                (dot - if i == j { 1.0 } else { 0.0 }).abs() < 1e-6
+            }));
+            if !orthonormal {
+                return Err(FEAError::InvalidInput(format!(
+                    "Support axes at node '{node_name}' must be orthogonal unit vectors"
+                )));
+            }
+        }
         self.supports.insert(node_name.to_string(), support);
         self.solution = None;
         Ok(())
@@ -189,7 +897,7 @@ impl FEModel {
 
     /// Add a point load to a member
     pub fn add_member_point_load(&mut self, member_name: &str, load: PointLoad) -> FEAResult<()> {
-        if !self.members.contains_key(member_name) {
+        if !self.members.contains_key(member_name) && !self.physical_members.contains_key(member_name) {
             return Err(FEAError::MemberNotFound(member_name.to_string()));
         }
         self.member_point_loads
@@ -202,7 +910,7 @@ impl FEModel {
 
     /// Add a distributed load to a member
     pub fn add_member_dist_load(&mut self, member_name: &str, load: DistributedLoad) -> FEAResult<()> {
-        if !self.members.contains_key(member_name) {
+        if !self.members.contains_key(member_name) && !self.physical_members.contains_key(member_name) {
             return Err(FEAError::MemberNotFound(member_name.to_string()));
         }
         self.member_dist_loads
@@ -213,6 +921,19 @@ impl FEModel {
         Ok(())
     }
 
+    /// Add a prestress (initial force or strain) to a member
+    pub fn add_member_prestress(&mut self, member_name: &str, load: PrestressLoad) -> FEAResult<()> {
+        if !self.members.contains_key(member_name) && !self.physical_members.contains_key(member_name) {
+            return Err(FEAError::MemberNotFound(member_name.to_string()));
+        }
+        self.member_prestress_loads
+            .entry(member_name.to_string())
+            .or_default()
+            .push(load);
+        self.solution = None;
+        Ok(())
+    }
+
     /// Add a pressure load to a plate
     pub fn add_plate_load(&mut self, plate_name: &str, load: PlateLoad) -> FEAResult<()> {
         if !self.plates.contains_key(plate_name) && !self.quads.contains_key(plate_name) {
@@ -226,1298 +947,6409 @@ impl FEModel {
         Ok(())
     }
 
-    /// Add a load combination
-    pub fn add_load_combo(&mut self, combo: LoadCombination) -> FEAResult<()> {
-        let name = combo.name.clone();
-        if self.load_combos.contains_key(&name) {
-            return Err(FEAError::DuplicateName(name));
+    /// Add a temperature load to a plate or quad
+    pub fn add_plate_thermal_load(&mut self, plate_name: &str, load: PlateThermalLoad) -> FEAResult<()> {
+        if !self.plates.contains_key(plate_name) && !self.quads.contains_key(plate_name) {
+            return Err(FEAError::PlateNotFound(plate_name.to_string()));
         }
-        self.load_combos.insert(name, combo);
+        self.plate_thermal_loads
+            .entry(plate_name.to_string())
+            .or_default()
+            .push(load);
         self.solution = None;
         Ok(())
     }
 
-    // ========================
-    // Analysis Methods
-    // ========================
-
-    /// Run linear static analysis
-    pub fn analyze_linear(&mut self) -> FEAResult<()> {
-        self.analyze(AnalysisOptions::linear())
+    /// Add a concentrated load at a point inside a plate
+    ///
+    /// The position is in the plate's local coordinates, measured from the
+    /// i-node along the i-j edge (x) and the i-n edge (y).
+    pub fn add_plate_point_load(&mut self, plate_name: &str, load: PlatePointLoad) -> FEAResult<()> {
+        if !self.plates.contains_key(plate_name) && !self.quads.contains_key(plate_name) {
+            return Err(FEAError::PlateNotFound(plate_name.to_string()));
+        }
+        if load.x < 0.0 || load.y < 0.0 {
+            return Err(FEAError::InvalidInput(format!(
+                "Point load position ({}, {}) lies outside plate '{plate_name}'",
+                load.x, load.y
+            )));
+        }
+        self.plate_point_loads
+            .entry(plate_name.to_string())
+            .or_default()
+            .push(load);
+        self.solution = None;
+        Ok(())
     }
 
-    /// Run P-Delta (second order) analysis
-    pub fn analyze_p_delta(&mut self) -> FEAResult<()> {
-        self.analyze(AnalysisOptions::p_delta())
+    /// Get the corner node names (i, j, m, n) of a plate or quad
+    pub fn plate_corner_nodes(&self, plate_name: &str) -> FEAResult<[String; 4]> {
+        if let Some(plate) = self.plates.get(plate_name) {
+            Ok([plate.i_node.clone(), plate.j_node.clone(), plate.m_node.clone(), plate.n_node.clone()])
+        } else if let Some(quad) = self.quads.get(plate_name) {
+            Ok([quad.i_node.clone(), quad.j_node.clone(), quad.m_node.clone(), quad.n_node.clone()])
+        } else {
+            Err(FEAError::PlateNotFound(plate_name.to_string()))
+        }
     }
 
-    /// Run analysis with custom options
-    pub fn analyze(&mut self, options: AnalysisOptions) -> FEAResult<()> {
-        // Ensure at least one load combination exists
-        if self.load_combos.is_empty() {
-            self.load_combos.insert(
-                "Combo 1".to_string(),
-                LoadCombination::single("Combo 1", "Case 1"),
-            );
+    /// Add a load combination
+    pub fn add_load_combo(&mut self, combo: LoadCombination) -> FEAResult<()> {
+        let name = combo.name.clone();
+        if self.load_combos.contains_key(&name) {
+            return Err(FEAError::DuplicateName(name));
         }
+        self.load_combos.insert(name, combo);
+        self.solution = None;
+        Ok(())
+    }
 
-        // Prepare the model
-        self.prepare_model()?;
-
-        // Build global stiffness matrix and load vector
-        let (k_global, dof_map) = self.build_global_stiffness()?;
-        
-        // Analyze each load combination
-        let combo_names: Vec<String> = self.load_combos.keys().cloned().collect();
-        
-        for combo_name in &combo_names {
-            let combo = self.load_combos.get(combo_name).unwrap().clone();
-            
-            // Build load vector for this combination
-            let p_global = self.build_load_vector(&combo, &dof_map)?;
-            
-            // Partition and solve based on analysis type
-            match options.analysis_type {
-                AnalysisType::Linear => {
-                    self.solve_linear(&k_global, &p_global, &dof_map, combo_name)?;
-                }
-                AnalysisType::PDelta => {
-                    self.solve_p_delta(&k_global, &p_global, &dof_map, combo_name, &options)?;
-                }
-                _ => {
-                    return Err(FEAError::AnalysisFailed(
-                        "Analysis type not yet implemented".to_string(),
-                    ));
-                }
+    /// Add a load envelope
+    pub fn add_load_envelope(&mut self, envelope: LoadEnvelope) -> FEAResult<()> {
+        for combo in &envelope.combos {
+            if !self.load_combos.contains_key(combo) {
+                return Err(FEAError::LoadCombinationNotFound(combo.clone()));
             }
-            
-            // Calculate member forces
-            self.calculate_member_forces(combo_name)?;
-            
-            // Calculate reactions
-            self.calculate_reactions(combo_name, &dof_map)?;
         }
-
-        self.solution = Some(options.analysis_type);
+        if self.load_envelopes.contains_key(&envelope.name) {
+            return Err(FEAError::DuplicateName(envelope.name));
+        }
+        self.load_envelopes.insert(envelope.name.clone(), envelope);
         Ok(())
     }
 
-    /// Prepare model for analysis (assign IDs, calculate lengths, etc.)
-    fn prepare_model(&mut self) -> FEAResult<()> {
-        // Assign node IDs
-        for (i, node) in self.nodes.values_mut().enumerate() {
-            node.id = Some(i);
+    /// Add a named group of nodes, members and plates
+    pub fn add_group(&mut self, name: &str, group: Group) -> FEAResult<()> {
+        for node in &group.nodes {
+            if !self.nodes.contains_key(node) {
+                return Err(FEAError::NodeNotFound(node.clone()));
+            }
         }
-
-        // Calculate member lengths
-        for member in self.members.values_mut() {
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            let length = i_node.distance_to(j_node);
-            
-            if length < 1e-10 {
-                return Err(FEAError::InvalidGeometry(format!(
-                    "Member has zero length: i={}, j={}",
-                    member.i_node, member.j_node
-                )));
+        for member in &group.members {
+            if !self.members.contains_key(member) && !self.physical_members.contains_key(member) {
+                return Err(FEAError::MemberNotFound(member.clone()));
             }
-            
-            member.length = Some(length);
         }
+        for plate in &group.plates {
+            if !self.plates.contains_key(plate) && !self.quads.contains_key(plate) {
+                return Err(FEAError::PlateNotFound(plate.clone()));
+            }
+        }
+        if self.groups.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
+        }
+        self.groups.insert(name.to_string(), group);
+        Ok(())
+    }
 
-        // Calculate plate dimensions
-        for plate in self.plates.values_mut() {
-            let i_node = self.nodes.get(&plate.i_node).unwrap();
-            let j_node = self.nodes.get(&plate.j_node).unwrap();
-            let m_node = self.nodes.get(&plate.m_node).unwrap();
-            
-            plate.width = Some(i_node.distance_to(j_node));
-            plate.height = Some(j_node.distance_to(m_node));
+    /// Add a story level
+    pub fn add_story(&mut self, name: &str, story: StoryLevel) -> FEAResult<()> {
+        if let Some(group) = &story.group {
+            self.group(group)?;
+        }
+        if self.stories.contains_key(name) {
+            return Err(FEAError::DuplicateName(name.to_string()));
         }
+        self.stories.insert(name.to_string(), story);
+        Ok(())
+    }
+
+    /// Get a group by name
+    pub fn group(&self, name: &str) -> FEAResult<&Group> {
+        self.groups.get(name).ok_or_else(|| FEAError::GroupNotFound(name.to_string()))
+    }
 
+    /// Add a node load to every node of a group
+    pub fn add_group_node_load(&mut self, group_name: &str, load: NodeLoad) -> FEAResult<()> {
+        for node in self.group(group_name)?.nodes.clone() {
+            self.add_node_load(&node, load.clone())?;
+        }
         Ok(())
     }
 
-    /// Build the global stiffness matrix
-    fn build_global_stiffness(&self) -> FEAResult<(Mat, HashMap<String, usize>)> {
-        let n_nodes = self.nodes.len();
-        let n_dofs = n_nodes * 6;
-        
-        let mut k_global = Mat::zeros(n_dofs, n_dofs);
-        
-        // Map node names to DOF indices
-        let mut dof_map: HashMap<String, usize> = HashMap::new();
-        for (name, node) in &self.nodes {
-            dof_map.insert(name.clone(), node.id.unwrap() * 6);
+    /// Add a distributed load to every member of a group
+    pub fn add_group_dist_load(&mut self, group_name: &str, load: DistributedLoad) -> FEAResult<()> {
+        for member in self.group(group_name)?.members.clone() {
+            self.add_member_dist_load(&member, load.clone())?;
         }
+        Ok(())
+    }
 
-        // Add member stiffness
-        for member in self.members.values() {
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            let material = self.materials.get(&member.material).unwrap();
-            let section = self.sections.get(&member.section).unwrap();
-            
-            let length = member.length.unwrap();
-            
-            // Get local stiffness matrix
-            let k_local = math::member_local_stiffness(
-                material.e,
-                material.g,
-                section.a,
-                section.iy,
-                section.iz,
-                section.j,
-                length,
-            );
-            
-            // Apply end releases
-            let k_local = math::apply_releases(&k_local, &member.releases.as_array());
-            
-            // Get transformation matrix
-            let t = math::member_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                member.rotation,
-            );
-            
-            // Transform to global: K_global = T^T * K_local * T
-            let k_member_global = t.transpose() * k_local * t;
-            
-            // Assemble into global matrix
-            let i_dof = dof_map[&member.i_node];
-            let j_dof = dof_map[&member.j_node];
-            
-            // i-i block
-            for a in 0..6 {
-                for b in 0..6 {
-                    k_global[(i_dof + a, i_dof + b)] += k_member_global[(a, b)];
-                }
-            }
-            
-            // i-j block
-            for a in 0..6 {
-                for b in 0..6 {
-                    k_global[(i_dof + a, j_dof + b)] += k_member_global[(a, b + 6)];
-                }
-            }
-            
-            // j-i block
-            for a in 0..6 {
-                for b in 0..6 {
-                    k_global[(j_dof + a, i_dof + b)] += k_member_global[(a + 6, b)];
-                }
-            }
-            
-            // j-j block
-            for a in 0..6 {
-                for b in 0..6 {
-                    k_global[(j_dof + a, j_dof + b)] += k_member_global[(a + 6, b + 6)];
-                }
-            }
+    /// Add a pressure load to every plate and quad of a group
+    pub fn add_group_plate_load(&mut self, group_name: &str, load: PlateLoad) -> FEAResult<()> {
+        for plate in self.group(group_name)?.plates.clone() {
+            self.add_plate_load(&plate, load.clone())?;
         }
+        Ok(())
+    }
 
-        // Add plate stiffness
-        for plate in self.plates.values() {
-            let i_node = self.nodes.get(&plate.i_node).unwrap();
-            let j_node = self.nodes.get(&plate.j_node).unwrap();
-            let n_node = self.nodes.get(&plate.n_node).unwrap();
-            let material = self.materials.get(&plate.material).unwrap();
-            
-            let width = plate.width.unwrap();
-            let height = plate.height.unwrap();
-            
-            // Get local stiffness matrix with specified formulation
-            let k_local = math::plate_local_stiffness_with_formulation(
-                material.e,
-                material.nu,
-                plate.thickness,
-                width,
-                height,
-                plate.kx_mod,
-                plate.ky_mod,
-                plate.formulation,
-            );
-            
-            // Get transformation matrix
-            let t = math::plate_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                &n_node.coords(),
-            );
-            
-            // Transform to global: K_global = T^T * K_local * T
-            let k_plate_global = t.transpose() * k_local * t;
-            
-            // Assemble into global matrix - 4 nodes, each with 6 DOFs
-            let dofs = [
-                dof_map[&plate.i_node],
-                dof_map[&plate.j_node],
-                dof_map[&plate.m_node],
-                dof_map[&plate.n_node],
-            ];
-            
-            // Add all 4x4 node blocks
-            for (ni, &di) in dofs.iter().enumerate() {
-                for (nj, &dj) in dofs.iter().enumerate() {
-                    let ki = ni * 6;
-                    let kj = nj * 6;
-                    for a in 0..6 {
-                        for b in 0..6 {
-                            k_global[(di + a, dj + b)] += k_plate_global[(ki + a, kj + b)];
-                        }
-                    }
-                }
+    /// Assign a section to every member of a group
+    pub fn set_group_section(&mut self, group_name: &str, section: &str) -> FEAResult<()> {
+        if !self.sections.contains_key(section) {
+            return Err(FEAError::SectionNotFound(section.to_string()));
+        }
+        let group = self.groups.get(group_name)
+            .ok_or_else(|| FEAError::GroupNotFound(group_name.to_string()))?;
+        for name in &group.members {
+            if let Some(member) = self.members.get_mut(name) {
+                member.section = section.to_string();
+            } else if let Some(physical) = self.physical_members.get_mut(name) {
+                physical.member.section = section.to_string();
             }
         }
+        self.solution = None;
+        Ok(())
+    }
 
-        // Add quad element stiffness (same as plate but with MITC4 formulation)
-        for quad in self.quads.values() {
-            let i_node = self.nodes.get(&quad.i_node).unwrap();
-            let j_node = self.nodes.get(&quad.j_node).unwrap();
-            let m_node = self.nodes.get(&quad.m_node).unwrap();
-            let n_node = self.nodes.get(&quad.n_node).unwrap();
-            let material = self.materials.get(&quad.material).unwrap();
-            
-            // Calculate dimensions from node positions
-            let width = i_node.distance_to(j_node);
-            let height = j_node.distance_to(m_node);
-            
-            // Get local stiffness matrix (using same plate formulation for now)
-            let k_local = math::plate_local_stiffness(
-                material.e,
-                material.nu,
-                quad.thickness,
-                width,
-                height,
-                quad.kx_mod,
-                quad.ky_mod,
-            );
-            
-            // Get transformation matrix
-            let t = math::plate_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                &n_node.coords(),
-            );
-            
-            // Transform to global
-            let k_quad_global = t.transpose() * k_local * t;
-            
-            // Assemble into global matrix
-            let dofs = [
-                dof_map[&quad.i_node],
-                dof_map[&quad.j_node],
-                dof_map[&quad.m_node],
-                dof_map[&quad.n_node],
-            ];
-            
-            for (ni, &di) in dofs.iter().enumerate() {
-                for (nj, &dj) in dofs.iter().enumerate() {
-                    let ki = ni * 6;
-                    let kj = nj * 6;
-                    for a in 0..6 {
-                        for b in 0..6 {
-                            k_global[(di + a, dj + b)] += k_quad_global[(ki + a, kj + b)];
-                        }
-                    }
-                }
+    /// Assign a material to every member, plate and quad of a group
+    pub fn set_group_material(&mut self, group_name: &str, material: &str) -> FEAResult<()> {
+        if !self.materials.contains_key(material) {
+            return Err(FEAError::MaterialNotFound(material.to_string()));
+        }
+        let group = self.groups.get(group_name)
+            .ok_or_else(|| FEAError::GroupNotFound(group_name.to_string()))?;
+        for name in &group.members {
+            if let Some(member) = self.members.get_mut(name) {
+                member.material = material.to_string();
+            } else if let Some(physical) = self.physical_members.get_mut(name) {
+                physical.member.material = material.to_string();
             }
         }
-
-        Ok((k_global, dof_map))
+        for name in &group.plates {
+            if let Some(plate) = self.plates.get_mut(name) {
+                plate.material = material.to_string();
+            } else if let Some(quad) = self.quads.get_mut(name) {
+                quad.material = material.to_string();
+            }
+        }
+        self.solution = None;
+        Ok(())
     }
 
-    /// Build the global load vector for a load combination
-    fn build_load_vector(
-        &self,
+    /// Generate skip (pattern) live loading for a row of continuous spans
+    ///
+    /// `spans` groups the members/plates of each span in order along the row.
+    /// For every pattern from [`skip_load_patterns`] a load case
+    /// `"{live_case} Pattern {n}"` is created, holding the `live_case` loads of
+    /// the loaded spans plus any `live_case` loads outside the grouped
+    /// elements. Each case gets a copy of `combo` with the live case factor
+    /// moved onto it, and the copies are collected into an envelope named
+    /// `"{combo} Envelope"`, which is returned.
+    pub fn add_pattern_live_loads(
+        &mut self,
+        live_case: &str,
+        spans: &[Vec<String>],
         combo: &LoadCombination,
-        dof_map: &HashMap<String, usize>,
-    ) -> FEAResult<FEVec> {
-        let n_dofs = self.nodes.len() * 6;
-        let mut p = FEVec::zeros(n_dofs);
-
-        // Add node loads
-        for (node_name, loads) in &self.node_loads {
-            let dof = dof_map[node_name];
-            
-            for load in loads {
-                let factor = combo.factor(&load.case);
-                if factor.abs() > 1e-10 {
-                    let load_arr = load.as_array();
-                    for i in 0..6 {
-                        p[dof + i] += factor * load_arr[i];
-                    }
-                }
+    ) -> FEAResult<LoadEnvelope> {
+        for name in spans.iter().flatten() {
+            if !self.members.contains_key(name)
+                && !self.plates.contains_key(name)
+                && !self.quads.contains_key(name)
+            {
+                return Err(FEAError::MemberNotFound(name.clone()));
             }
         }
+        if !combo.includes(live_case) {
+            return Err(FEAError::InvalidInput(format!(
+                "Load combination '{}' does not include live case '{live_case}'",
+                combo.name
+            )));
+        }
 
-        // Add fixed end reactions from member loads (simplified - uniform loads only for now)
-        for (member_name, loads) in &self.member_dist_loads {
-            let member = self.members.get(member_name).unwrap();
-            let length = member.length.unwrap();
-            
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            
-            let t = math::member_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                member.rotation,
-            );
-            
-            for load in loads {
-                let factor = combo.factor(&load.case);
-                if factor.abs() < 1e-10 {
-                    continue;
-                }
-                
-                let w = factor * load.w1; // Assume uniform for now
-                let fer_local;
-                
-                // Handle both local and global direction loads
-                match load.direction {
-                    crate::loads::LoadDirection::Fx => {
-                        fer_local = math::fer_uniform_load(w, length, 0);
-                    }
-                    crate::loads::LoadDirection::Fy => {
-                        fer_local = math::fer_uniform_load(w, length, 1);
-                    }
-                    crate::loads::LoadDirection::Fz => {
-                        fer_local = math::fer_uniform_load(w, length, 2);
-                    }
-                    crate::loads::LoadDirection::FX | 
-                    crate::loads::LoadDirection::FY | 
-                    crate::loads::LoadDirection::FZ => {
-                        // Global direction loads need transformation
-                        // Create a global load vector and transform to local
-                        let global_dir = match load.direction {
-                            crate::loads::LoadDirection::FX => [1.0, 0.0, 0.0],
-                            crate::loads::LoadDirection::FY => [0.0, 1.0, 0.0],
-                            crate::loads::LoadDirection::FZ => [0.0, 0.0, 1.0],
-                            _ => unreachable!(),
-                        };
-                        
-                        // Get local direction by transforming global to local
-                        // T transforms local to global, so T^T transforms global to local
-                        // Extract the 3x3 rotation matrix from T
-                        let r = math::extract_rotation_matrix(&t);
-                        
-                        // Transform global direction to local
-                        let local_dir = [
-                            r[(0, 0)] * global_dir[0] + r[(0, 1)] * global_dir[1] + r[(0, 2)] * global_dir[2],
-                            r[(1, 0)] * global_dir[0] + r[(1, 1)] * global_dir[1] + r[(1, 2)] * global_dir[2],
-                            r[(2, 0)] * global_dir[0] + r[(2, 1)] * global_dir[1] + r[(2, 2)] * global_dir[2],
-                        ];
-                        
-                        // Apply FER in each local direction proportionally
-                        let mut fer_total = math::Vec12::zeros();
-                        if local_dir[0].abs() > 1e-10 {
-                            let fer = math::fer_uniform_load(w * local_dir[0], length, 0);
-                            for i in 0..12 { fer_total[i] += fer[i]; }
-                        }
-                        if local_dir[1].abs() > 1e-10 {
-                            let fer = math::fer_uniform_load(w * local_dir[1], length, 1);
-                            for i in 0..12 { fer_total[i] += fer[i]; }
-                        }
-                        if local_dir[2].abs() > 1e-10 {
-                            let fer = math::fer_uniform_load(w * local_dir[2], length, 2);
-                            for i in 0..12 { fer_total[i] += fer[i]; }
-                        }
-                        fer_local = fer_total;
-                    }
-                    _ => continue, // Skip moment loads
-                };
-                
-                // Transform to global
-                let fer_global = t.transpose() * fer_local;
-                
-                // Subtract from load vector (FER is reaction, so negate)
-                let i_dof = dof_map[&member.i_node];
-                let j_dof = dof_map[&member.j_node];
-                
-                for i in 0..6 {
-                    p[i_dof + i] -= fer_global[i];
-                    p[j_dof + i] -= fer_global[i + 6];
-                }
-            }
-        }
-
-        // Add fixed end reactions from plate pressure loads
-        for (plate_name, loads) in &self.plate_loads {
-            // Try plate first, then quad
-            let (i_node, j_node, m_node, n_node, width, height) = 
-                if let Some(plate) = self.plates.get(plate_name) {
-                    (plate.i_node.clone(), plate.j_node.clone(), plate.m_node.clone(), plate.n_node.clone(),
-                     plate.width.unwrap(), plate.height.unwrap())
-                } else if let Some(quad) = self.quads.get(plate_name) {
-                    let i = self.nodes.get(&quad.i_node).unwrap();
-                    let j = self.nodes.get(&quad.j_node).unwrap();
-                    let m = self.nodes.get(&quad.m_node).unwrap();
-                    let width = i.distance_to(j);
-                    let height = j.distance_to(m);
-                    (quad.i_node.clone(), quad.j_node.clone(), quad.m_node.clone(), quad.n_node.clone(),
-                     width, height)
-                } else {
-                    continue;
-                };
-            
-            let i_node_ref = self.nodes.get(&i_node).unwrap();
-            let j_node_ref = self.nodes.get(&j_node).unwrap();
-            let n_node_ref = self.nodes.get(&n_node).unwrap();
-            
-            let t = math::plate_transformation_matrix(
-                &i_node_ref.coords(),
-                &j_node_ref.coords(),
-                &n_node_ref.coords(),
-            );
-            
-            for load in loads {
-                let factor = combo.factor(&load.case);
-                if factor.abs() < 1e-10 {
-                    continue;
-                }
-                
-                let pressure = factor * load.pressure;
-                let fer_local = math::plate_fer_pressure(pressure, width, height);
-                
-                // Transform to global
-                let fer_global = t.transpose() * fer_local;
-                
-                // Subtract from load vector (FER is reaction, so negate)
-                let dofs = [
-                    dof_map[&i_node],
-                    dof_map[&j_node],
-                    dof_map[&m_node],
-                    dof_map[&n_node],
-                ];
-                
-                for (ni, &di) in dofs.iter().enumerate() {
-                    for a in 0..6 {
-                        p[di + a] -= fer_global[ni * 6 + a];
-                    }
-                }
+        let mut envelope = LoadEnvelope::new(&format!("{} Envelope", combo.name));
+        if self.load_envelopes.contains_key(&envelope.name) {
+            return Err(FEAError::DuplicateName(envelope.name));
+        }
+
+        let grouped: HashSet<&String> = spans.iter().flatten().collect();
+
+        for (n, pattern) in skip_load_patterns(spans.len()).iter().enumerate() {
+            let case = format!("{live_case} Pattern {}", n + 1);
+            let loaded: HashSet<&String> = pattern.iter().flat_map(|&i| spans[i].iter()).collect();
+            let applies = |name: &String| !grouped.contains(name) || loaded.contains(name);
+
+            let node_loads: Vec<(String, NodeLoad)> = self.node_loads.iter()
+                .flat_map(|(name, loads)| loads.iter().map(move |l| (name.clone(), l)))
+                .filter(|(_, l)| l.case == live_case)
+                .map(|(name, l)| (name, NodeLoad { case: case.clone(), ..l.clone() }))
+                .collect();
+            let point_loads: Vec<(String, PointLoad)> = self.member_point_loads.iter()
+                .filter(|(name, _)| applies(name))
+                .flat_map(|(name, loads)| loads.iter().map(move |l| (name.clone(), l)))
+                .filter(|(_, l)| l.case == live_case)
+                .map(|(name, l)| (name, PointLoad { case: case.clone(), ..l.clone() }))
+                .collect();
+            let dist_loads: Vec<(String, DistributedLoad)> = self.member_dist_loads.iter()
+                .filter(|(name, _)| applies(name))
+                .flat_map(|(name, loads)| loads.iter().map(move |l| (name.clone(), l)))
+                .filter(|(_, l)| l.case == live_case)
+                .map(|(name, l)| (name, DistributedLoad { case: case.clone(), ..l.clone() }))
+                .collect();
+            let plate_loads: Vec<(String, PlateLoad)> = self.plate_loads.iter()
+                .filter(|(name, _)| applies(name))
+                .flat_map(|(name, loads)| loads.iter().map(move |l| (name.clone(), l)))
+                .filter(|(_, l)| l.case == live_case)
+                .map(|(name, l)| (name, PlateLoad { case: case.clone(), ..l.clone() }))
+                .collect();
+
+            for (name, load) in node_loads {
+                self.add_node_load(&name, load)?;
+            }
+            for (name, load) in point_loads {
+                self.add_member_point_load(&name, load)?;
             }
+            for (name, load) in dist_loads {
+                self.add_member_dist_load(&name, load)?;
+            }
+            let plate_point_loads: Vec<(String, PlatePointLoad)> = self.plate_point_loads.iter()
+                .filter(|(name, _)| applies(name))
+                .flat_map(|(name, loads)| loads.iter().map(move |l| (name.clone(), l)))
+                .filter(|(_, l)| l.case == live_case)
+                .map(|(name, l)| (name, PlatePointLoad { case: case.clone(), ..l.clone() }))
+                .collect();
+
+            for (name, load) in plate_loads {
+                self.add_plate_load(&name, load)?;
+            }
+            for (name, load) in plate_point_loads {
+                self.add_plate_point_load(&name, load)?;
+            }
+
+            let mut pattern_combo = combo.clone();
+            pattern_combo.name = format!("{} ({case})", combo.name);
+            pattern_combo.factors.remove(live_case);
+            pattern_combo.factors.insert(case, combo.factor(live_case));
+            envelope.combos.push(pattern_combo.name.clone());
+            self.add_load_combo(pattern_combo)?;
         }
 
-        Ok(p)
+        self.add_load_envelope(envelope.clone())?;
+        Ok(envelope)
     }
 
-    /// Solve linear system with support conditions
-    fn solve_linear(
-        &mut self,
-        k_global: &Mat,
-        p_global: &FEVec,
-        dof_map: &HashMap<String, usize>,
-        combo_name: &str,
-    ) -> FEAResult<()> {
-        let n_dofs = self.nodes.len() * 6;
+    /// Equivalent nodal loads [FX, FY, FZ, MX, MY, MZ] of a load combination
+    ///
+    /// Member and plate loads are converted to their fixed-end equivalents, so
+    /// this is the load vector seen by the solver, keyed by node name.
+    pub fn equivalent_nodal_loads(&mut self, combo_name: &str) -> FEAResult<HashMap<String, [f64; 6]>> {
+        let combo = self.load_combos.get(combo_name).cloned()
+            .ok_or_else(|| FEAError::LoadCombinationNotFound(combo_name.to_string()))?;
         
-        // Identify free and restrained DOFs
-        let mut free_dofs: Vec<usize> = Vec::new();
-        let mut restrained_dofs: Vec<usize> = Vec::new();
-        let mut enforced_displacements: HashMap<usize, f64> = HashMap::new();
+        self.prepare_model()?;
+        let dof_map = self.build_dof_map();
+        let p = self.build_load_vector(&combo, &dof_map)?;
         
-        for node_name in self.nodes.keys() {
-            let base_dof = dof_map[node_name];
-            
-            if let Some(support) = self.supports.get(node_name) {
-                let restraints = [
-                    support.dx,
-                    support.dy,
-                    support.dz,
-                    support.rx,
-                    support.ry,
-                    support.rz,
-                ];
-                let enforced = support.enforced_displacements();
-                
-                for i in 0..6 {
-                    if restraints[i] {
-                        restrained_dofs.push(base_dof + i);
-                        if let Some(val) = enforced[i] {
-                            enforced_displacements.insert(base_dof + i, val);
-                        }
-                    } else {
-                        free_dofs.push(base_dof + i);
-                    }
-                }
-            } else {
-                for i in 0..6 {
-                    free_dofs.push(base_dof + i);
-                }
-            }
-        }
+        Ok(dof_map.into_iter()
+            .map(|(name, dof)| {
+                let mut loads = [0.0; 6];
+                loads.copy_from_slice(&p.as_slice()[dof..dof + 6]);
+                (name, loads)
+            })
+            .collect())
+    }
 
-        if free_dofs.is_empty() {
-            return Err(FEAError::AnalysisFailed(
-                "No free degrees of freedom".to_string(),
-            ));
+    /// Assembled global stiffness, lumped mass and combination load vectors
+    ///
+    /// This is the system the solver starts from, before rigid link constraints
+    /// are applied and the supports are partitioned out; see [`io::matrix`] for
+    /// writing it to Matrix Market or NumPy files. A model without
+    /// combinations gets the "Combo 1" load vector of "Case 1".
+    ///
+    /// [`io::matrix`]: crate::io::matrix
+    pub fn assembled_system(&mut self) -> FEAResult<AssembledSystem> {
+        const DOFS: [&str; 6] = ["DX", "DY", "DZ", "RX", "RY", "RZ"];
+        self.prepare_model()?;
+        let (stiffness, dof_map) = self.build_global_stiffness(&CancelToken::new())?;
+        let mass = Mat::from_diagonal(&self.lumped_masses(&dof_map));
+        
+        let mut combos: Vec<LoadCombination> = self.load_combos.values().cloned().collect();
+        if combos.is_empty() {
+            combos.push(LoadCombination::single("Combo 1", "Case 1"));
+        }
+        let mut loads = BTreeMap::new();
+        for combo in &combos {
+            loads.insert(combo.name.clone(), self.build_load_vector(combo, &dof_map)?);
         }
-
-        // Partition stiffness matrix and load vector
-        let n_free = free_dofs.len();
-        let mut k11 = Mat::zeros(n_free, n_free);
-        let mut p1 = FEVec::zeros(n_free);
         
-        for (i, &di) in free_dofs.iter().enumerate() {
-            p1[i] = p_global[di];
-            
-            for (j, &dj) in free_dofs.iter().enumerate() {
-                k11[(i, j)] = k_global[(di, dj)];
-            }
-            
-            // Account for enforced displacements
-            for (&dj, &val) in &enforced_displacements {
-                p1[i] -= k_global[(di, dj)] * val;
+        let mut dofs = vec![String::new(); stiffness.nrows()];
+        for (name, &base) in &dof_map {
+            for (i, dof) in DOFS.iter().enumerate() {
+                dofs[base + i] = format!("{name} {dof}");
             }
         }
+        let (free_dofs, _) = self.partition_dofs(&dof_map, &HashSet::new());
+        
+        Ok(AssembledSystem { stiffness, mass, loads, dofs, free_dofs })
+    }
 
-        // Solve K11 * D1 = P1
-        let d1 = match math::solve_linear_system(&k11, &p1) {
-            Some(d) => d,
-            None => return Err(FEAError::SingularMatrix),
-        };
+    /// Estimate the conditioning of the free stiffness matrix
+    ///
+    /// Reports the condition number, the DOFs of the softest deformation mode
+    /// and the members much stiffer than their neighbours; see
+    /// [`ConditioningReport::warnings`] for a readable summary.
+    pub fn conditioning_report(&mut self) -> FEAResult<ConditioningReport> {
+        self.prepare_model()?;
+        let (k_global, dof_map) = self.build_global_stiffness(&CancelToken::new())?;
+        self.conditioning(&k_global, &dof_map, &SolverBackend::default())
+    }
 
-        // Assemble full displacement vector
-        let mut d_full = FEVec::zeros(n_dofs);
+    // ========================
+    // Model Editing Methods
+    // ========================
+
+    /// Remove a node with its support, loads and mass, and every element connected to it
+    pub fn remove_node(&mut self, name: &str) -> FEAResult<()> {
+        if self.nodes.remove(name).is_none() {
+            return Err(FEAError::NodeNotFound(name.to_string()));
+        }
+        self.supports.remove(name);
+        self.node_loads.remove(name);
+        self.node_masses.remove(name);
+        for group in self.groups.values_mut() {
+            group.nodes.retain(|n| n != name);
+        }
         
-        for (i, &di) in free_dofs.iter().enumerate() {
-            d_full[di] = d1[i];
+        let members: Vec<String> = self.members.iter()
+            .filter(|(_, m)| m.i_node == name || m.j_node == name)
+            .map(|(member, _)| member.clone())
+            .chain(self.physical_members.iter()
+                .filter(|(_, p)| p.member.i_node == name || p.member.j_node == name)
+                .map(|(member, _)| member.clone()))
+            .collect();
+        for member in members {
+            // Segments of a removed physical member may already be gone
+            if self.members.contains_key(&member) || self.physical_members.contains_key(&member) {
+                self.remove_member(&member)?;
+            }
         }
         
-        for (&di, &val) in &enforced_displacements {
-            d_full[di] = val;
+        let plates: Vec<String> = self.plates.iter()
+            .filter(|(_, p)| [&p.i_node, &p.j_node, &p.m_node, &p.n_node].iter().any(|n| *n == name))
+            .map(|(plate, _)| plate.clone())
+            .chain(self.quads.iter()
+                .filter(|(_, q)| [&q.i_node, &q.j_node, &q.m_node, &q.n_node].iter().any(|n| *n == name))
+                .map(|(quad, _)| quad.clone()))
+            .collect();
+        for plate in plates {
+            self.remove_plate(&plate)?;
         }
+        
+        self.tetras.retain(|_, tetra| !tetra.nodes.iter().any(|n| n == name));
+        self.gaps.retain(|_, gap| gap.i_node != name && gap.j_node != name);
+        self.links.retain(|_, link| link.i_node != name && link.j_node != name);
+        self.rigid_links.retain(|_, link| link.master != name && link.slave != name);
+        self.solution = None;
+        Ok(())
+    }
 
-        // Store nodal displacements
-        for (node_name, node) in self.nodes.iter_mut() {
-            let base_dof = dof_map[node_name];
-            let disp = [
-                d_full[base_dof],
-                d_full[base_dof + 1],
-                d_full[base_dof + 2],
-                d_full[base_dof + 3],
-                d_full[base_dof + 4],
-                d_full[base_dof + 5],
-            ];
-            node.displacements.insert(combo_name.to_string(), disp);
+    /// Remove a member or physical member and its loads
+    ///
+    /// Removing a physical member also removes its analysis segments and the
+    /// nodes generated at its requested splits.
+    pub fn remove_member(&mut self, name: &str) -> FEAResult<()> {
+        if self.members.remove(name).is_none() {
+            let physical = self.physical_members.remove(name)
+                .ok_or_else(|| FEAError::MemberNotFound(name.to_string()))?;
+            for (segment, _, _) in &physical.segments {
+                self.members.remove(segment);
+            }
+            for node in &physical.generated_nodes {
+                self.nodes.remove(node);
+            }
+        }
+        self.member_point_loads.remove(name);
+        self.member_dist_loads.remove(name);
+        self.member_prestress_loads.remove(name);
+        for group in self.groups.values_mut() {
+            group.members.retain(|m| m != name);
         }
+        self.solution = None;
+        Ok(())
+    }
 
+    /// Remove a plate or quad and its loads
+    pub fn remove_plate(&mut self, name: &str) -> FEAResult<()> {
+        if self.plates.remove(name).is_none() && self.quads.remove(name).is_none() {
+            return Err(FEAError::PlateNotFound(name.to_string()));
+        }
+        self.plate_loads.remove(name);
+        self.plate_point_loads.remove(name);
+        self.plate_thermal_loads.remove(name);
+        for group in self.groups.values_mut() {
+            group.plates.retain(|p| p != name);
+        }
+        self.solution = None;
         Ok(())
     }
 
-    /// Solve using P-Delta iteration
-    fn solve_p_delta(
-        &mut self,
-        k_global: &Mat,
-        p_global: &FEVec,
-        dof_map: &HashMap<String, usize>,
-        combo_name: &str,
-        options: &AnalysisOptions,
-    ) -> FEAResult<()> {
-        // First iteration: linear solution
-        self.solve_linear(k_global, p_global, dof_map, combo_name)?;
-        
-        // Iterative P-Delta
-        for _iter in 0..options.max_iterations {
-            // Calculate member axial forces
-            self.calculate_member_forces(combo_name)?;
-            
-            // Build geometric stiffness matrix
-            let kg = self.build_geometric_stiffness(dof_map)?;
-            
-            // Combined stiffness
-            let k_combined = k_global + &kg;
-            
-            // Solve again
-            let old_displacements: Vec<f64> = self.nodes.values()
-                .filter_map(|n| n.displacements.get(combo_name))
-                .flat_map(|d| d.iter().copied())
-                .collect();
-            
-            self.solve_linear(&k_combined, p_global, dof_map, combo_name)?;
-            
-            // Check convergence
-            let new_displacements: Vec<f64> = self.nodes.values()
-                .filter_map(|n| n.displacements.get(combo_name))
-                .flat_map(|d| d.iter().copied())
-                .collect();
-            
-            let mut max_diff = 0.0_f64;
-            for (old, new) in old_displacements.iter().zip(new_displacements.iter()) {
-                max_diff = max_diff.max((new - old).abs());
-            }
-            
-            if max_diff < options.tolerance {
-                return Ok(());
-            }
+    /// Remove the support at a node, returning it
+    pub fn remove_support(&mut self, node_name: &str) -> FEAResult<Support> {
+        if !self.nodes.contains_key(node_name) {
+            return Err(FEAError::NodeNotFound(node_name.to_string()));
+        }
+        let support = self.supports.remove(node_name)
+            .ok_or_else(|| FEAError::InvalidInput(format!("Node {node_name} has no support")))?;
+        self.solution = None;
+        Ok(support)
+    }
+
+    /// Remove every load of a load case, returning how many were removed
+    ///
+    /// Load combinations still referring to the case are left unchanged.
+    pub fn remove_load_case(&mut self, case: &str) -> usize {
+        fn purge<T>(loads: &mut BTreeMap<String, Vec<T>>, is_case: impl Fn(&T) -> bool) -> usize {
+            let mut removed = 0;
+            loads.retain(|_, list| {
+                let before = list.len();
+                list.retain(|load| !is_case(load));
+                removed += before - list.len();
+                !list.is_empty()
+            });
+            removed
         }
         
-        Err(FEAError::ConvergenceFailed(options.max_iterations))
+        let removed = purge(&mut self.node_loads, |l| l.case == case)
+            + purge(&mut self.member_point_loads, |l| l.case == case)
+            + purge(&mut self.member_dist_loads, |l| l.case == case)
+            + purge(&mut self.member_prestress_loads, |l| l.case == case)
+            + purge(&mut self.plate_loads, |l| l.case == case)
+            + purge(&mut self.plate_point_loads, |l| l.case == case)
+            + purge(&mut self.plate_thermal_loads, |l| l.case == case);
+        if removed > 0 {
+            self.solution = None;
+        }
+        removed
     }
 
-    /// Build geometric stiffness matrix for P-Delta
-    fn build_geometric_stiffness(&self, dof_map: &HashMap<String, usize>) -> FEAResult<Mat> {
-        let n_dofs = self.nodes.len() * 6;
-        let mut kg_global = Mat::zeros(n_dofs, n_dofs);
+    /// Rename a node, re-pointing elements, its support, loads and mass
+    pub fn rename_node(&mut self, old: &str, new: &str) -> FEAResult<()> {
+        if old == new {
+            return if self.nodes.contains_key(old) { Ok(()) } else { Err(FEAError::NodeNotFound(old.to_string())) };
+        }
+        if self.nodes.contains_key(new) {
+            return Err(FEAError::DuplicateName(new.to_string()));
+        }
+        let node = self.nodes.remove(old)
+            .ok_or_else(|| FEAError::NodeNotFound(old.to_string()))?;
+        self.nodes.insert(new.to_string(), node);
+        move_entry(&mut self.supports, old, new);
+        move_entry(&mut self.node_loads, old, new);
+        move_entry(&mut self.node_masses, old, new);
+        self.repoint_nodes(&HashMap::from([(old.to_string(), new.to_string())]));
+        for group in self.groups.values_mut() {
+            rename_in(&mut group.nodes, old, new);
+        }
+        self.solution = None;
+        Ok(())
+    }
 
-        for member in self.members.values() {
-            // Get axial force from latest analysis
-            let p = member.local_forces.values()
-                .next()
-                .map(|f| -f[0])
-                .unwrap_or(0.0);
-            
-            if p.abs() < 1e-10 {
-                continue;
+    /// Rename a member or physical member, moving its loads
+    pub fn rename_member(&mut self, old: &str, new: &str) -> FEAResult<()> {
+        if old == new {
+            let found = self.members.contains_key(old) || self.physical_members.contains_key(old);
+            return if found { Ok(()) } else { Err(FEAError::MemberNotFound(old.to_string())) };
+        }
+        if self.members.contains_key(new) || self.physical_members.contains_key(new) {
+            return Err(FEAError::DuplicateName(new.to_string()));
+        }
+        if let Some(member) = self.members.remove(old) {
+            self.members.insert(new.to_string(), member);
+        } else {
+            let mut physical = self.physical_members.remove(old)
+                .ok_or_else(|| FEAError::MemberNotFound(old.to_string()))?;
+            // Segments are named after the physical member and regenerated on analysis
+            for (segment, _, _) in physical.segments.drain(..) {
+                self.members.remove(&segment);
             }
-            
-            let section = self.sections.get(&member.section).unwrap();
-            let length = member.length.unwrap();
-            
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            
-            // Local geometric stiffness
-            let kg_local = math::member_geometric_stiffness(
-                p,
-                section.a,
-                section.iy,
-                section.iz,
-                length,
-            );
-            
-            // Transform to global
-            let t = math::member_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                member.rotation,
-            );
-            
-            let kg_member_global = t.transpose() * kg_local * t;
-            
-            // Assemble
-            let i_dof = dof_map[&member.i_node];
-            let j_dof = dof_map[&member.j_node];
-            
-            for a in 0..6 {
-                for b in 0..6 {
-                    kg_global[(i_dof + a, i_dof + b)] += kg_member_global[(a, b)];
-                    kg_global[(i_dof + a, j_dof + b)] += kg_member_global[(a, b + 6)];
-                    kg_global[(j_dof + a, i_dof + b)] += kg_member_global[(a + 6, b)];
-                    kg_global[(j_dof + a, j_dof + b)] += kg_member_global[(a + 6, b + 6)];
-                }
+            for node in physical.generated_nodes.drain(..) {
+                self.nodes.remove(&node);
             }
+            self.physical_members.insert(new.to_string(), physical);
         }
-
-        Ok(kg_global)
+        move_entry(&mut self.member_point_loads, old, new);
+        move_entry(&mut self.member_dist_loads, old, new);
+        move_entry(&mut self.member_prestress_loads, old, new);
+        for group in self.groups.values_mut() {
+            rename_in(&mut group.members, old, new);
+        }
+        self.solution = None;
+        Ok(())
     }
 
-    /// Calculate member forces from displacements
-    /// 
-    /// The total member forces are:
-    ///   F_total = K_local * d_local + FER
-    /// 
-    /// where FER (fixed end reactions) accounts for distributed loads along the member.
-    /// This is the same approach as PyNite and standard structural analysis.
-    fn calculate_member_forces(&mut self, combo_name: &str) -> FEAResult<()> {
-        // Get load combination for factor lookup
-        let combo = self.load_combos.get(combo_name).cloned()
-            .ok_or_else(|| FEAError::AnalysisFailed(format!("Load combo not found: {}", combo_name)))?;
+    /// Rename a plate or quad, moving its loads
+    pub fn rename_plate(&mut self, old: &str, new: &str) -> FEAResult<()> {
+        if old == new {
+            let found = self.plates.contains_key(old) || self.quads.contains_key(old);
+            return if found { Ok(()) } else { Err(FEAError::PlateNotFound(old.to_string())) };
+        }
+        if self.plates.contains_key(new) || self.quads.contains_key(new) {
+            return Err(FEAError::DuplicateName(new.to_string()));
+        }
+        if !move_entry(&mut self.plates, old, new) && !move_entry(&mut self.quads, old, new) {
+            return Err(FEAError::PlateNotFound(old.to_string()));
+        }
+        move_entry(&mut self.plate_loads, old, new);
+        move_entry(&mut self.plate_point_loads, old, new);
+        move_entry(&mut self.plate_thermal_loads, old, new);
+        for group in self.groups.values_mut() {
+            rename_in(&mut group.plates, old, new);
+        }
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Rename a material, re-pointing the elements that use it
+    pub fn rename_material(&mut self, old: &str, new: &str) -> FEAResult<()> {
+        if old == new {
+            return if self.materials.contains_key(old) { Ok(()) } else { Err(FEAError::MaterialNotFound(old.to_string())) };
+        }
+        if self.materials.contains_key(new) {
+            return Err(FEAError::DuplicateName(new.to_string()));
+        }
+        if !move_entry(&mut self.materials, old, new) {
+            return Err(FEAError::MaterialNotFound(old.to_string()));
+        }
+        let materials = self.members.values_mut().map(|m| &mut m.material)
+            .chain(self.physical_members.values_mut().map(|p| &mut p.member.material))
+            .chain(self.plates.values_mut().map(|p| &mut p.material))
+            .chain(self.quads.values_mut().map(|q| &mut q.material))
+            .chain(self.tetras.values_mut().map(|t| &mut t.material));
+        for material in materials.filter(|m| *m == old) {
+            *material = new.to_string();
+        }
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Rename a section, re-pointing the members that use it
+    pub fn rename_section(&mut self, old: &str, new: &str) -> FEAResult<()> {
+        if old == new {
+            return if self.sections.contains_key(old) { Ok(()) } else { Err(FEAError::SectionNotFound(old.to_string())) };
+        }
+        if self.sections.contains_key(new) {
+            return Err(FEAError::DuplicateName(new.to_string()));
+        }
+        if !move_entry(&mut self.sections, old, new) {
+            return Err(FEAError::SectionNotFound(old.to_string()));
+        }
+        let sections = self.members.values_mut().map(|m| &mut m.section)
+            .chain(self.physical_members.values_mut().map(|p| &mut p.member.section));
+        for section in sections.filter(|s| *s == old) {
+            *section = new.to_string();
+        }
+        self.solution = None;
+        Ok(())
+    }
+
+    /// Re-point element connectivity from old to new node names
+    fn repoint_nodes(&mut self, renamed: &HashMap<String, String>) {
+        let rename = |node: &mut String| {
+            if let Some(new) = renamed.get(node.as_str()) {
+                node.clone_from(new);
+            }
+        };
+        for member in self.members.values_mut() {
+            rename(&mut member.i_node);
+            rename(&mut member.j_node);
+        }
+        for physical in self.physical_members.values_mut() {
+            rename(&mut physical.member.i_node);
+            rename(&mut physical.member.j_node);
+        }
+        for plate in self.plates.values_mut() {
+            for node in [&mut plate.i_node, &mut plate.j_node, &mut plate.m_node, &mut plate.n_node] {
+                rename(node);
+            }
+        }
+        for quad in self.quads.values_mut() {
+            for node in [&mut quad.i_node, &mut quad.j_node, &mut quad.m_node, &mut quad.n_node] {
+                rename(node);
+            }
+        }
+        for tetra in self.tetras.values_mut() {
+            tetra.nodes.iter_mut().for_each(rename);
+        }
+        for link in self.rigid_links.values_mut() {
+            rename(&mut link.master);
+            rename(&mut link.slave);
+        }
+        for gap in self.gaps.values_mut() {
+            rename(&mut gap.i_node);
+            rename(&mut gap.j_node);
+        }
+        for link in self.links.values_mut() {
+            rename(&mut link.i_node);
+            rename(&mut link.j_node);
+        }
+    }
+
+    // ========================
+    // Handle Methods
+    // ========================
+
+    /// Prepare the model for analysis and issue node and member handles
+    ///
+    /// Analyses do this themselves; call it directly to use handles before
+    /// analysing. Handles issued earlier are invalidated.
+    pub fn prepare(&mut self) -> FEAResult<()> {
+        self.prepare_model()
+    }
+
+    /// Handle of a node, once the model has been prepared
+    pub fn node_id(&self, name: &str) -> Option<NodeId> {
+        self.node_ids.id(name)
+    }
+
+    /// Name of a node handle
+    pub fn node_name(&self, id: NodeId) -> Option<&str> {
+        self.node_ids.name(id)
+    }
+
+    /// Handle of a member, once the model has been prepared
+    pub fn member_id(&self, name: &str) -> Option<MemberId> {
+        self.member_ids.id(name)
+    }
+
+    /// Name of a member handle
+    pub fn member_name(&self, id: MemberId) -> Option<&str> {
+        self.member_ids.name(id)
+    }
+
+    /// End (i, j) node handles of a member
+    pub fn member_end_nodes(&self, id: MemberId) -> Option<[NodeId; 2]> {
+        self.member_nodes.get(id.index()).copied()
+    }
+
+    /// Assemble the global stiffness of a prepared model into a matrix
+    ///
+    /// Node `n` occupies DOFs `6 * n.index()` to `6 * n.index() + 5`. Supports
+    /// are not applied.
+    pub fn assemble_stiffness(&self, k_global: &mut impl math::Assemble) -> FEAResult<()> {
+        self.assemble_member_stiffness(k_global, 0..self.member_ids.len());
         
-        // Collect member names and their distributed loads first to avoid borrow issues
-        let member_names: Vec<String> = self.members.keys().cloned().collect();
+        // Shell and solid elements are still assembled through the name map
+        if !(self.plates.is_empty() && self.quads.is_empty() && self.tetras.is_empty()) {
+            let dof_map = self.build_dof_map();
+            self.assemble_shell_stiffness(k_global, &dof_map);
+            self.assemble_solid_stiffness(k_global, &dof_map)?;
+        }
+        Ok(())
+    }
+
+    /// Global stiffness matrix in sparse (CSR) form, numbered by node handle
+    pub fn sparse_stiffness(&mut self) -> FEAResult<CsrMatrix<f64>> {
+        self.prepare_model()?;
+        let mut builder = SparseMatrixBuilder::new(self.nodes.len() * 6);
+        self.assemble_stiffness(&mut builder)?;
+        Ok(builder.to_csr())
+    }
+
+    // ========================
+    // Analysis Methods
+    // ========================
+
+    /// Run linear static analysis
+    pub fn analyze_linear(&mut self) -> FEAResult<()> {
+        self.analyze(AnalysisOptions::linear())
+    }
+
+    /// Run P-Delta (second order) analysis
+    pub fn analyze_p_delta(&mut self) -> FEAResult<()> {
+        self.analyze(AnalysisOptions::p_delta())
+    }
+
+    /// Run nonlinear analysis, iterating until one-way supports, gaps and
+    /// compression-only foundations are consistent
+    pub fn analyze_nonlinear(&mut self) -> FEAResult<()> {
+        self.analyze(AnalysisOptions::nonlinear())
+    }
+
+    /// Run large displacement (co-rotational) analysis with the given number of load steps
+    pub fn analyze_large_displacement(&mut self, load_steps: usize) -> FEAResult<()> {
+        self.analyze(AnalysisOptions::large_displacement(load_steps))
+    }
+
+    /// Run analysis with custom options
+    pub fn analyze(&mut self, options: AnalysisOptions) -> FEAResult<()> {
+        let (k_global, dof_map) = self.start_analysis(&options)?;
+        self.uplift.clear();
+        self.slack_members.clear();
+        let combo_names = self.analysis_combos(&options)?;
         
-        for member_name in member_names {
-            let member = self.members.get(&member_name).unwrap();
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            let material = self.materials.get(&member.material).unwrap();
-            let section = self.sections.get(&member.section).unwrap();
-            let length = member.length.unwrap();
-            let rotation = member.rotation;
-            
-            // Get nodal displacements
-            let d_i = i_node.displacements.get(combo_name)
-                .ok_or_else(|| FEAError::NotAnalyzed)?;
-            let d_j = j_node.displacements.get(combo_name)
-                .ok_or_else(|| FEAError::NotAnalyzed)?;
-            
-            // Global displacement vector for member
-            let d_global = math::Vec12::from_iterator(
-                d_i.iter().chain(d_j.iter()).copied()
-            );
-            
-            // Transformation matrix
-            let t = math::member_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                rotation,
-            );
-            
-            // Local displacements
-            let d_local = t * d_global;
-            
-            // Get member releases for static condensation
-            let releases = member.releases.as_array();
-            
-            // Local stiffness - get the uncondensed matrix first
-            let k_local_uncondensed = math::member_local_stiffness(
-                material.e,
-                material.g,
-                section.a,
-                section.iy,
-                section.iz,
-                section.j,
-                length,
-            );
-            
-            // Apply static condensation for releases (same as Pynite's k() method)
-            // This sets rows/columns for released DOFs to zero, so F = K_condensed * d 
-            // will give zero forces at released DOFs
-            let k_local = math::apply_releases(&k_local_uncondensed, &releases);
-            
-            // Local forces from nodal displacements: F_elastic = K_condensed * d_local
-            let mut f_local = k_local * d_local;
-            
-            // Add fixed end reactions (FER) from distributed loads
-            // This is critical: FER accounts for loads applied between nodes
-            // Following PyNite's convention: F = K*d + FER_condensed
-            // The FER must also be condensed for releases (same as stiffness)
-            if let Some(loads) = self.member_dist_loads.get(&member_name) {
-                for load in loads {
-                    let factor = combo.factor(&load.case);
-                    if factor.abs() < 1e-10 {
-                        continue;
+        // Linear combinations share one factorisation, so they are solved together
+        if options.analysis_type == AnalysisType::Linear {
+            self.solve_linear_combos(&combo_names, &k_global, &dof_map, &options)?;
+        } else {
+            for combo_name in &combo_names {
+                self.solve_combo(combo_name, &k_global, &dof_map, &options)?;
+            }
+        }
+
+        self.solution = Some(options.analysis_type);
+        Ok(())
+    }
+
+    /// Solve the load vectors of several linear combinations in parallel
+    /// against a single factorisation, then recover their forces and reactions
+    fn solve_linear_combos(
+        &mut self,
+        combo_names: &[String],
+        k_global: &Mat,
+        dof_map: &HashMap<String, usize>,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        let system = self.linear_system(k_global, dof_map, options, &HashSet::new())?;
+        let solved = parallel::map(combo_names, |combo_name| -> FEAResult<FEVec> {
+            options.cancel.check()?;
+            let p_global = self.build_load_vector(&self.load_combos[combo_name], dof_map)?;
+            Ok(system.solve(&p_global))
+        });
+        for (combo_name, d_full) in combo_names.iter().zip(solved) {
+            self.store_displacements(&d_full?, dof_map, combo_name);
+            self.calculate_member_forces(combo_name)?;
+            self.calculate_reactions(combo_name, dof_map)?;
+        }
+        Ok(())
+    }
+
+    /// Solve a single load combination on demand
+    ///
+    /// The stiffness factorisation is shared with [`FEModel::analyze`] and
+    /// earlier calls, so switching the combination of interest only costs a
+    /// load vector and a back-substitution. Results of other combinations
+    /// from earlier analyses are left in place; they are stale if the model
+    /// has changed since.
+    pub fn analyze_combo(&mut self, combo_name: &str, options: AnalysisOptions) -> FEAResult<()> {
+        // A model without combinations gets the default "Combo 1"
+        let default_combo = self.load_combos.is_empty() && combo_name == "Combo 1";
+        if !default_combo && !self.load_combos.contains_key(combo_name) {
+            return Err(FEAError::LoadCombinationNotFound(combo_name.to_string()));
+        }
+        let (k_global, dof_map) = self.start_analysis(&options)?;
+        self.uplift.remove(combo_name);
+        self.slack_members.remove(combo_name);
+        
+        self.solve_combo(combo_name, &k_global, &dof_map, &options)?;
+        self.solution = Some(options.analysis_type);
+        Ok(())
+    }
+
+    /// Run the next step of a [`SteppedAnalysis`]
+    ///
+    /// The steps together do the same as [`FEModel::analyze`] with the run's
+    /// options: the first prepares the model, the next ones assemble the
+    /// members a chunk at a time (skipped when the cached stiffness is still
+    /// valid), then each solves one load combination. The model is only
+    /// marked as analysed after the last step, and must not be edited while
+    /// a run is in progress.
+    pub fn step_analysis(&mut self, run: &mut SteppedAnalysis) -> FEAResult<AnalysisProgress> {
+        run.options.cancel.check()?;
+        let (stage, combo) = match std::mem::replace(&mut run.state, StepState::Finished) {
+            StepState::Start => {
+                self.prepare_static_analysis(&run.options)?;
+                self.uplift.clear();
+                self.slack_members.clear();
+                run.combos = self.analysis_combos(&run.options)?;
+                
+                let fingerprint = self.stiffness_fingerprint(&run.options.solver);
+                let assembly_steps = match self.cached_stiffness(fingerprint) {
+                    Some((k_global, dof_map)) => {
+                        self.check_conditioning(&k_global, &dof_map, &run.options)?;
+                        run.state = StepState::Solve { k_global, dof_map, next_combo: 0 };
+                        0
                     }
-                    
-                    let w = factor * load.w1; // Assume uniform load
-                    let fer_uncondensed;
-                    
-                    // Handle both local and global direction loads
-                    match load.direction {
-                        crate::loads::LoadDirection::Fx => {
-                            fer_uncondensed = math::fer_uniform_load(w, length, 0);
-                        }
-                        crate::loads::LoadDirection::Fy => {
-                            fer_uncondensed = math::fer_uniform_load(w, length, 1);
-                        }
-                        crate::loads::LoadDirection::Fz => {
-                            fer_uncondensed = math::fer_uniform_load(w, length, 2);
-                        }
-                        crate::loads::LoadDirection::FX | 
-                        crate::loads::LoadDirection::FY | 
-                        crate::loads::LoadDirection::FZ => {
-                            // Global direction loads need transformation
-                            let global_dir = match load.direction {
-                                crate::loads::LoadDirection::FX => [1.0, 0.0, 0.0],
-                                crate::loads::LoadDirection::FY => [0.0, 1.0, 0.0],
-                                crate::loads::LoadDirection::FZ => [0.0, 0.0, 1.0],
-                                _ => unreachable!(),
-                            };
-                            
-                            // Get local direction by transforming global to local
-                            let r = math::extract_rotation_matrix(&t);
-                            let local_dir = [
-                                r[(0, 0)] * global_dir[0] + r[(0, 1)] * global_dir[1] + r[(0, 2)] * global_dir[2],
-                                r[(1, 0)] * global_dir[0] + r[(1, 1)] * global_dir[1] + r[(1, 2)] * global_dir[2],
-                                r[(2, 0)] * global_dir[0] + r[(2, 1)] * global_dir[1] + r[(2, 2)] * global_dir[2],
-                            ];
-                            
-                            // Apply FER in each local direction proportionally
-                            let mut fer_total = math::Vec12::zeros();
-                            if local_dir[0].abs() > 1e-10 {
-                                let f = math::fer_uniform_load(w * local_dir[0], length, 0);
-                                for i in 0..12 { fer_total[i] += f[i]; }
-                            }
-                            if local_dir[1].abs() > 1e-10 {
-                                let f = math::fer_uniform_load(w * local_dir[1], length, 1);
-                                for i in 0..12 { fer_total[i] += f[i]; }
-                            }
-                            if local_dir[2].abs() > 1e-10 {
-                                let f = math::fer_uniform_load(w * local_dir[2], length, 2);
-                                for i in 0..12 { fer_total[i] += f[i]; }
-                            }
-                            fer_uncondensed = fer_total;
-                        }
-                        _ => continue, // Skip moment loads
-                    };
-                    
-                    // Apply static condensation to FER for releases (PyNite method)
-                    // fer_condensed = fer1 - k12 * inv(k22) * fer2
-                    let fer_condensed = math::apply_fer_releases(&fer_uncondensed, &k_local_uncondensed, &releases);
-                    
-                    // Add condensed FER to elastic forces: F_member = K*d + FER_condensed
-                    for i in 0..12 {
-                        f_local[i] += fer_condensed[i];
+                    None => {
+                        let n_dofs = self.nodes.len() * 6;
+                        run.state = StepState::Assemble {
+                            k_global: Mat::zeros(n_dofs, n_dofs),
+                            dof_map: self.build_dof_map(),
+                            fingerprint,
+                            next_member: 0,
+                        };
+                        self.member_ids.len().div_ceil(run.chunk_size).max(1)
                     }
+                };
+                run.total = 1 + assembly_steps + run.combos.len();
+                (AnalysisStage::Prepare, None)
+            }
+            StepState::Assemble { mut k_global, dof_map, fingerprint, next_member } => {
+                let end = (next_member + run.chunk_size).min(self.member_ids.len());
+                self.assemble_member_stiffness(&mut k_global, next_member..end);
+                if end < self.member_ids.len() {
+                    run.state = StepState::Assemble { k_global, dof_map, fingerprint, next_member: end };
+                } else {
+                    self.assemble_shell_stiffness(&mut k_global, &dof_map);
+                    self.assemble_solid_stiffness(&mut k_global, &dof_map)?;
+                    let k_global = self.cache_stiffness(fingerprint, k_global, &dof_map);
+                    self.check_conditioning(&k_global, &dof_map, &run.options)?;
+                    run.state = StepState::Solve { k_global, dof_map, next_combo: 0 };
                 }
+                (AnalysisStage::Assemble, None)
             }
-            
-            // Explicitly zero out forces at released DOFs
-            // While static condensation should theoretically do this, we enforce it
-            // to ensure numerical precision and correct moment diagrams at hinges/pins
-            for (i, &released) in releases.iter().enumerate() {
-                if released {
-                    f_local[i] = 0.0;
+            StepState::Solve { k_global, dof_map, next_combo } => {
+                let combo_name = run.combos[next_combo].clone();
+                self.solve_combo(&combo_name, &k_global, &dof_map, &run.options)?;
+                if next_combo + 1 < run.combos.len() {
+                    run.state = StepState::Solve { k_global, dof_map, next_combo: next_combo + 1 };
+                } else {
+                    self.solution = Some(run.options.analysis_type);
                 }
+                (AnalysisStage::Solve, Some(combo_name))
             }
-            
-            // Store results
-            let mut forces = [0.0; 12];
-            for i in 0..12 {
-                forces[i] = f_local[i];
+            StepState::Finished => {
+                return Err(FEAError::InvalidInput("The stepped analysis has already finished".to_string()));
             }
-            
-            let mut displacements = [0.0; 12];
-            for i in 0..12 {
-                displacements[i] = d_local[i];
+        };
+        run.completed += 1;
+        Ok(run.progress(stage, combo))
+    }
+
+    /// Combinations an analysis solves: all of them, or with
+    /// [`AnalysisOptions::combo_tags`] those carrying one of the tags
+    fn analysis_combos(&self, options: &AnalysisOptions) -> FEAResult<Vec<String>> {
+        let Some(tags) = &options.combo_tags else {
+            return Ok(self.load_combos.keys().cloned().collect());
+        };
+        let names: Vec<String> = self.load_combos.iter()
+            .filter(|(_, combo)| combo.tags.iter().any(|tag| tags.contains(tag)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if names.is_empty() {
+            return Err(FEAError::InvalidInput(format!(
+                "No load combination is tagged {}", tags.join(" or ")
+            )));
+        }
+        Ok(names)
+    }
+
+    /// Checks and preparation shared by every static analysis, returning the
+    /// global stiffness and DOF map
+    fn start_analysis(&mut self, options: &AnalysisOptions) -> FEAResult<(Arc<Mat>, HashMap<String, usize>)> {
+        self.prepare_static_analysis(options)?;
+        
+        // Build global stiffness matrix
+        let (k_global, dof_map) = self.cached_global_stiffness(options)?;
+        self.check_conditioning(&k_global, &dof_map, options)?;
+        Ok((k_global, dof_map))
+    }
+
+    /// Validate the options and model and prepare it for a static analysis
+    fn prepare_static_analysis(&mut self, options: &AnalysisOptions) -> FEAResult<()> {
+        if options.analysis_type == AnalysisType::Modal {
+            return Err(FEAError::InvalidInput(
+                "Modal analysis returns its results from analyze_modal".to_string(),
+            ));
+        }
+        if options.arc_length.is_some() && options.analysis_type != AnalysisType::LargeDisplacement {
+            return Err(FEAError::InvalidInput(format!(
+                "Arc-length control needs large displacement analysis, not {:?}", options.analysis_type
+            )));
+        }
+
+        // Ensure at least one load combination exists
+        if self.load_combos.is_empty() {
+            self.load_combos.insert(
+                "Combo 1".to_string(),
+                LoadCombination::single("Combo 1", "Case 1"),
+            );
+        }
+
+        // Prepare the model, discarding the previous solution
+        self.solution = None;
+        self.prepare_model()?;
+        for member in self.members.values_mut() {
+            member.imperfection_axial.clear();
+        }
+
+        if !self.gaps.is_empty() && options.analysis_type != AnalysisType::Nonlinear {
+            return Err(FEAError::InvalidInput(
+                "Gap and hook elements require nonlinear analysis".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Add any ill-conditioning of the stiffness to the warnings, if the options ask for it
+    fn check_conditioning(&mut self, k_global: &Mat, dof_map: &HashMap<String, usize>, options: &AnalysisOptions) -> FEAResult<()> {
+        if options.check_conditioning {
+            let report = self.conditioning(k_global, dof_map, &options.solver)?;
+            self.warnings.extend(report.warnings());
+        }
+        Ok(())
+    }
+
+    /// Solve one load combination and recover its forces and reactions
+    fn solve_combo(
+        &mut self,
+        combo_name: &str,
+        k_global: &Mat,
+        dof_map: &HashMap<String, usize>,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        options.cancel.check()?;
+        let combo = self.load_combos.get(combo_name).unwrap().clone();
+        
+        // Build load vector for this combination
+        let p_global = self.build_load_vector(&combo, dof_map)?;
+        
+        // Partition and solve based on analysis type
+        match options.analysis_type {
+            AnalysisType::Linear => {
+                self.solve_linear(k_global, &p_global, dof_map, combo_name, options)?;
             }
-            
-            // Update the member
-            let member = self.members.get_mut(&member_name).unwrap();
-            member.local_forces.insert(combo_name.to_string(), forces);
-            member.local_displacements.insert(combo_name.to_string(), displacements);
+            AnalysisType::PDelta => {
+                self.solve_p_delta(k_global, &p_global, dof_map, combo_name, options)?;
+            }
+            AnalysisType::Nonlinear => {
+                // Forces and reactions come from the converged iteration
+                return self.solve_nonlinear(k_global, &p_global, dof_map, combo_name, options);
+            }
+            AnalysisType::LargeDisplacement => {
+                // Forces and reactions are recovered in the deformed configuration
+                return self.solve_large_displacement(&p_global, dof_map, combo_name, options);
+            }
+            _ => {
+                return Err(FEAError::AnalysisFailed(
+                    "Analysis type not yet implemented".to_string(),
+                ));
+            }
+        }
+        
+        // Calculate member forces
+        self.calculate_member_forces(combo_name)?;
+        
+        // Calculate reactions
+        self.calculate_reactions(combo_name, dof_map)
+    }
+
+    /// Run a time-history analysis under a ground acceleration record
+    ///
+    /// Masses are lumped at the nodes from the element densities plus any
+    /// node masses. Link elements contribute their nonlinear forces at every
+    /// step, found by Newton iteration within the Newmark average acceleration
+    /// scheme. Displacements are relative to the ground; static results are
+    /// left untouched.
+    pub fn analyze_time_history(&mut self, history: &TimeHistory) -> FEAResult<TimeHistoryResults> {
+        let steps = history.ground_acceleration.len();
+        if history.dt <= 0.0 || steps == 0 {
+            return Err(FEAError::InvalidInput(
+                "Time history needs a positive time step and at least one sample".to_string(),
+            ));
+        }
+        if !self.rigid_links.is_empty() || !self.gaps.is_empty() || self.supports.values().any(|s| s.is_skewed()) {
+            return Err(FEAError::InvalidInput(
+                "Time-history analysis does not support rigid links, gaps or skewed supports".to_string(),
+            ));
+        }
+        let dir_norm = history.direction.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if dir_norm < 1e-12 {
+            return Err(FEAError::InvalidInput("Ground motion direction is zero".to_string()));
+        }
+        
+        self.prepare_model()?;
+        let (k_global, dof_map) = self.build_global_stiffness(&history.cancel)?;
+        let (free_dofs, _) = self.partition_dofs(&dof_map, &HashSet::new());
+        if free_dofs.is_empty() {
+            return Err(FEAError::AnalysisFailed("No free degrees of freedom".to_string()));
+        }
+        let index: HashMap<usize, usize> = free_dofs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+        
+        // Free DOF stiffness, lumped mass and ground motion influence vector
+        let n = free_dofs.len();
+        let masses = self.lumped_masses(&dof_map);
+        let mut k = Mat::zeros(n, n);
+        let mut m = FEVec::zeros(n);
+        let mut r = FEVec::zeros(n);
+        for (i, &di) in free_dofs.iter().enumerate() {
+            m[i] = masses[di];
+            if di % 6 < 3 {
+                r[i] = history.direction[di % 6] / dir_norm;
+            }
+            for (j, &dj) in free_dofs.iter().enumerate() {
+                k[(i, j)] = k_global[(di, dj)];
+            }
+        }
+        let m_diag = Mat::from_diagonal(&m);
+        let c = &m_diag * history.rayleigh.0 + &k * history.rayleigh.1;
+        
+        // Link deformation = g . u over the free DOFs
+        let mut link_names: Vec<String> = self.links.keys().cloned().collect();
+        link_names.sort();
+        let gradients: Vec<Vec<(usize, f64)>> = link_names.iter()
+            .map(|name| {
+                let link = &self.links[name];
+                let e = self.link_axis(link);
+                let mut g = Vec::new();
+                for (node_name, sign) in [(&link.i_node, -1.0), (&link.j_node, 1.0)] {
+                    for (a, ea) in e.iter().enumerate() {
+                        if let Some(&i) = index.get(&(dof_map[node_name] + a)) {
+                            g.push((i, sign * ea));
+                        }
+                    }
+                }
+                g
+            })
+            .collect();
+        let dot = |g: &[(usize, f64)], x: &FEVec| g.iter().map(|&(i, gi)| gi * x[i]).sum::<f64>();
+        
+        // Newmark average acceleration: gamma = 1/2, beta = 1/4
+        let dt = history.dt;
+        let (b_u, b_v) = (4.0 / (dt * dt), 2.0 / dt);
+        let k_linear = &k + &m_diag * b_u + &c * b_v;
+        
+        let mut results = TimeHistoryResults {
+            time: history.times(),
+            ..Default::default()
+        };
+        let mut states = vec![0.0; link_names.len()];
+        let mut u = FEVec::zeros(n);
+        let mut v = FEVec::zeros(n);
+        // Starting from rest, only the ground motion accelerates the masses
+        let mut acc = m.zip_map(&r, |mi, ri| if mi > 0.0 { -ri * history.ground_acceleration[0] } else { 0.0 });
+        
+        for step in 0..steps {
+            history.cancel.check()?;
+            if step > 0 {
+                let p = m.component_mul(&r) * -history.ground_acceleration[step];
+                let (u_n, v_n, a_n) = (u.clone(), v.clone(), acc.clone());
+                
+                let mut converged = false;
+                let mut residuals = Vec::new();
+                for _ in 0..history.max_iterations {
+                    v = (&u - &u_n) * b_v - &v_n;
+                    acc = (&u - &u_n) * b_u - &v_n * (4.0 / dt) - &a_n;
+                    
+                    let mut residual = &p - m.component_mul(&acc) - &c * &v - &k * &u;
+                    let mut k_eff = k_linear.clone();
+                    for ((name, g), state) in link_names.iter().zip(&gradients).zip(&states) {
+                        let (force, kt, ct) = self.links[name].response(dot(g, &u), dot(g, &v), *state);
+                        let tangent = kt + b_v * ct;
+                        for &(a, ga) in g {
+                            residual[a] -= force * ga;
+                            for &(b, gb) in g {
+                                k_eff[(a, b)] += tangent * ga * gb;
+                            }
+                        }
+                    }
+                    
+                    let du = math::solve_linear_system(&k_eff, &residual)
+                        .ok_or_else(|| self.singular_matrix(&k_eff, &free_dofs, &dof_map))?;
+                    u += &du;
+                    residuals.push(du.norm() / u.norm().max(1e-12));
+                    if du.norm() <= history.tolerance * u.norm().max(1e-12) {
+                        converged = true;
+                        break;
+                    }
+                }
+                if !converged {
+                    return Err(FEAError::ConvergenceFailed { iterations: history.max_iterations, residuals });
+                }
+                v = (&u - &u_n) * b_v - &v_n;
+                acc = (&u - &u_n) * b_u - &v_n * (4.0 / dt) - &a_n;
+            }
+            
+            // Record the converged step and commit the isolator states
+            for ((name, g), state) in link_names.iter().zip(&gradients).zip(states.iter_mut()) {
+                let link = &self.links[name];
+                let (deformation, velocity) = (dot(g, &u), dot(g, &v));
+                let (force, _, _) = link.response(deformation, velocity, *state);
+                *state = link.committed_state(deformation, *state);
+                
+                let record = results.links.entry(name.clone()).or_default();
+                record.deformation.push(deformation);
+                record.velocity.push(velocity);
+                record.force.push(force);
+            }
+            for node_name in self.nodes.keys() {
+                let base = dof_map[node_name];
+                let disp: [f64; 6] = std::array::from_fn(|a| index.get(&(base + a)).map_or(0.0, |&i| u[i]));
+                results.displacements.entry(node_name.clone()).or_default().push(disp);
+            }
+        }
+        
+        Ok(results)
+    }
+
+    /// Find the lowest `options.num_modes` natural modes
+    ///
+    /// Masses are lumped at the nodes from the element densities plus any
+    /// node masses, with rotary inertia on the rotational DOFs (see
+    /// `lumped_masses`). The modes are found by subspace iteration on the
+    /// free stiffness, assembled in sparse form and factorised once by the
+    /// options' solver backend, using the options' tolerance and iteration
+    /// limit; static results are left untouched.
+    pub fn analyze_modal(&mut self, options: &AnalysisOptions) -> FEAResult<ModalResults> {
+        if options.arc_length.is_some() {
+            return Err(FEAError::InvalidInput("Arc-length control needs large displacement analysis, not modal".to_string()));
+        }
+        if !self.rigid_links.is_empty() || !self.gaps.is_empty() || self.supports.values().any(|s| s.is_skewed()) {
+            return Err(FEAError::InvalidInput(
+                "Modal analysis does not support rigid links, gaps or skewed supports".to_string(),
+            ));
+        }
+
+        self.prepare_model()?;
+        options.cancel.check()?;
+        let dof_map = self.build_dof_map();
+        let (free_dofs, _) = self.partition_dofs(&dof_map, &HashSet::new());
+        if free_dofs.is_empty() {
+            return Err(FEAError::AnalysisFailed("No free degrees of freedom".to_string()));
+        }
+        let index: HashMap<usize, usize> = free_dofs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+        let n = free_dofs.len();
+        let mut k_global = SparseMatrixBuilder::new(self.nodes.len() * 6);
+        self.assemble_stiffness(&mut k_global)?;
+        let mut k = SparseMatrixBuilder::new(n);
+        for (row, col, &value) in k_global.to_csr().triplet_iter() {
+            if let (Some(&i), Some(&j)) = (index.get(&row), index.get(&col)) {
+                k.add(i, j, value);
+            }
+        }
+        let masses = self.lumped_masses(&dof_map);
+        let m = FEVec::from_fn(n, |i, _| masses[free_dofs[i]]);
+        options.cancel.check()?;
+        let factorization = options.solver.factorize_sparse(&k.to_csr())
+            .ok_or_else(|| self.singular_matrix(&k.to_dense(), &free_dofs, &dof_map))?;
+        let (eigenvalues, shapes) = subspace_iteration(
+            &*factorization, &m, options.num_modes, options.max_iterations, options.tolerance, &options.cancel,
+        )?;
+
+        let mut results = ModalResults::default();
+        for (i, &d) in free_dofs.iter().enumerate() {
+            if d % 6 < 3 {
+                results.free_mass[d % 6] += m[i];
+            }
+        }
+        for (mode, &eigenvalue) in eigenvalues.iter().enumerate() {
+            let mut shape = shapes.column(mode).into_owned();
+            let largest = shape.iter().copied().fold(0.0, |a: f64, b| if b.abs() > a.abs() { b } else { a });
+            if largest < 0.0 {
+                shape.neg_mut();
+            }
+
+            // Participation of the unit-modal-mass shape in a rigid translation
+            let mut participation = [0.0; 3];
+            for (i, &d) in free_dofs.iter().enumerate() {
+                if d % 6 < 3 {
+                    participation[d % 6] += m[i] * shape[i];
+                }
+            }
+            let mass_ratio = std::array::from_fn(|a| {
+                if results.free_mass[a] > 0.0 { participation[a].powi(2) / results.free_mass[a] } else { 0.0 }
+            });
+
+            let omega = eigenvalue.max(0.0).sqrt();
+            let frequency = omega / (2.0 * std::f64::consts::PI);
+            results.modes.push(Mode {
+                frequency,
+                period: if frequency > 0.0 { 1.0 / frequency } else { f64::INFINITY },
+                shape: self.nodes.keys()
+                    .map(|name| {
+                        let base = dof_map[name];
+                        (name.clone(), std::array::from_fn(|a| index.get(&(base + a)).map_or(0.0, |&i| shape[i])))
+                    })
+                    .collect(),
+                participation,
+                mass_ratio,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Prepare model for analysis (assign IDs, calculate lengths, etc.)
+    fn prepare_model(&mut self) -> FEAResult<()> {
+        self.split_physical_members()?;
+        
+        // Intern names, and number the DOFs in node handle order
+        self.node_ids = self.node_ordering();
+        self.node_coords.clear();
+        for (id, name) in self.node_ids.iter() {
+            let node = self.nodes.get_mut(name).unwrap();
+            node.id = Some(id.index());
+            self.node_coords.push(node.coords());
+        }
+        self.member_ids = Registry::from_names(self.members.keys());
+        self.member_nodes = self.member_ids.iter()
+            .map(|(_, name)| {
+                let member = &self.members[name];
+                [&member.i_node, &member.j_node].map(|n| self.node_ids.id(n).unwrap())
+            })
+            .collect();
+        let supported = self.supports.iter().filter(|(_, s)| s.is_supported()).map(|(name, _)| name);
+        self.store.renumber(&self.node_ids, Registry::from_names(supported), &self.member_ids);
+
+        // Calculate member lengths
+        for member in self.members.values_mut() {
+            let i_node = self.nodes.get(&member.i_node).unwrap();
+            let j_node = self.nodes.get(&member.j_node).unwrap();
+            let length = i_node.distance_to(j_node);
+            
+            if length < 1e-10 {
+                return Err(FEAError::InvalidGeometry(format!(
+                    "Member has zero length: i={}, j={}",
+                    member.i_node, member.j_node
+                )));
+            }
+            
+            member.length = Some(length);
+        }
+
+        // Calculate plate dimensions
+        for plate in self.plates.values_mut() {
+            let i_node = self.nodes.get(&plate.i_node).unwrap();
+            let j_node = self.nodes.get(&plate.j_node).unwrap();
+            let m_node = self.nodes.get(&plate.m_node).unwrap();
+            
+            plate.width = Some(i_node.distance_to(j_node));
+            plate.height = Some(j_node.distance_to(m_node));
+        }
+        
+        // Flag distorted quads
+        self.warnings.clear();
+        let mut quad_names: Vec<&String> = self.quads.keys().collect();
+        quad_names.sort();
+        for name in quad_names {
+            let quality = QuadQuality::from_corners(&self.quad_corners(&self.quads[name]));
+            self.warnings.extend(quality.warnings().into_iter().map(|w| format!("Quad {name}: {w}")));
+        }
+
+        Ok(())
+    }
+
+    /// Node handles in reverse Cuthill-McKee order
+    ///
+    /// The ordering is found from the name-ordered connectivity, so it is as
+    /// deterministic as the names, and is only used when it narrows the
+    /// stiffness bandwidth. Both bandwidths are recorded for the summary.
+    fn node_ordering(&mut self) -> Registry<NodeId> {
+        let sorted: Registry<NodeId> = Registry::from_names(self.nodes.keys());
+        let index = |name: &String| sorted.id(name).unwrap().index();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut connect = |nodes: &[&String]| {
+            for (k, a) in nodes.iter().enumerate() {
+                for b in &nodes[k + 1..] {
+                    edges.push((index(a), index(b)));
+                }
+            }
+        };
+        for member in self.members.values() {
+            connect(&[&member.i_node, &member.j_node]);
+        }
+        for plate in self.plates.values() {
+            connect(&[&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node]);
+        }
+        for quad in self.quads.values() {
+            connect(&[&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node]);
+        }
+        for tetra in self.tetras.values() {
+            connect(&tetra.nodes.iter().collect::<Vec<_>>());
+        }
+        for link in self.rigid_links.values() {
+            connect(&[&link.master, &link.slave]);
+        }
+        for gap in self.gaps.values() {
+            connect(&[&gap.i_node, &gap.j_node]);
+        }
+        for link in self.links.values() {
+            connect(&[&link.i_node, &link.j_node]);
+        }
+        
+        let mut graph = SparseMatrixBuilder::new(sorted.len());
+        for &(a, b) in &edges {
+            graph.add(a, b, 1.0);
+            graph.add(b, a, 1.0);
+        }
+        let order = math::sparse::reverse_cuthill_mckee(&graph.to_csr());
+        let position = math::sparse::inverse_permutation(&order);
+        
+        // Half-bandwidth in DOFs for a node numbering
+        let bandwidth = |position: &dyn Fn(usize) -> usize| {
+            edges.iter().map(|&(a, b)| 6 * position(a).abs_diff(position(b)) + 5).max().unwrap_or(5)
+        };
+        let original = bandwidth(&|i| i);
+        let reordered = bandwidth(&|i| position[i]);
+        if reordered < original {
+            self.bandwidth = (original, reordered);
+            Registry::from_ordered_names(order.iter().map(|&i| sorted.name(NodeId::from_index(i)).unwrap().to_string()))
+        } else {
+            self.bandwidth = (original, original);
+            sorted
+        }
+    }
+
+    /// Corner coordinates (i, j, m, n) of a quad
+    fn quad_corners(&self, quad: &Quad) -> [[f64; 3]; 4] {
+        [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node].map(|n| self.nodes[n].coords())
+    }
+
+    /// Width, height and global-to-local DOF transformation of a quad
+    ///
+    /// The element is flattened onto its mean plane, with rigid offsets tying
+    /// the flat corners to the nodes, so warped quads stay consistent.
+    fn quad_frame(&self, quad: &Quad) -> (f64, f64, math::Mat24) {
+        let (flat, offsets) = math::quad_mean_plane(&self.quad_corners(quad));
+        let [i, j, m, _] = flat.map(math::Vec3::from);
+        let t = math::plate_transformation_matrix(&flat[0], &flat[1], &flat[3]);
+        ((j - i).norm(), (m - j).norm(), t * math::quad_warping_correction(&offsets))
+    }
+
+    /// Regenerate the analysis segments of every physical member
+    ///
+    /// Segments and split nodes from a previous analysis are removed first, so
+    /// nodes added or moved since are picked up. Each physical member is split
+    /// at every node within a small tolerance of its line and at its requested
+    /// split positions, where new nodes are generated unless one already exists.
+    fn split_physical_members(&mut self) -> FEAResult<()> {
+        for physical in self.physical_members.values_mut() {
+            for (segment, _, _) in physical.segments.drain(..) {
+                self.members.remove(&segment);
+            }
+            for node in physical.generated_nodes.drain(..) {
+                self.nodes.remove(&node);
+            }
+        }
+        
+        let mut names: Vec<String> = self.physical_members.keys().cloned().collect();
+        names.sort();
+        
+        for name in names {
+            let physical = &self.physical_members[&name];
+            let template = physical.member.clone();
+            let splits = physical.splits.clone();
+            
+            let start = math::Vec3::from(self.nodes[&template.i_node].coords());
+            let end = math::Vec3::from(self.nodes[&template.j_node].coords());
+            let length = (end - start).norm();
+            if length < 1e-10 {
+                return Err(FEAError::InvalidGeometry(format!("Physical member {name} has zero length")));
+            }
+            let axis = (end - start) / length;
+            let tol = 1e-6 * length;
+            
+            // Stations (distance, node) along the member line
+            let mut stations = vec![(0.0, template.i_node.clone()), (length, template.j_node.clone())];
+            for (node_name, node) in &self.nodes {
+                if let Some(x) = interior_station(&start, &axis, length, &math::Vec3::from(node.coords())) {
+                    stations.push((x, node_name.clone()));
+                }
+            }
+            
+            let mut generated_nodes = Vec::new();
+            for (k, &x) in splits.iter().enumerate() {
+                if x <= 0.0 || x >= length {
+                    return Err(FEAError::InvalidInput(format!(
+                        "Split at {x} is outside physical member {name} of length {length}"
+                    )));
+                }
+                if stations.iter().any(|(station, _)| (station - x).abs() <= tol) {
+                    continue;
+                }
+                let node_name = format!("{name}@{}", k + 1);
+                if self.nodes.contains_key(&node_name) {
+                    return Err(FEAError::DuplicateName(node_name));
+                }
+                let point = start + axis * x;
+                self.nodes.insert(node_name.clone(), Node::new(point.x, point.y, point.z));
+                stations.push((x, node_name.clone()));
+                generated_nodes.push(node_name);
+            }
+            
+            // Coincident nodes on the line: the segments connect to the first
+            stations.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+            stations.dedup_by(|next, prev| next.0 - prev.0 <= tol);
+            
+            let last = stations.len() - 2;
+            let mut segments = Vec::with_capacity(last + 1);
+            for (k, pair) in stations.windows(2).enumerate() {
+                let segment_name = format!("{name}#{}", k + 1);
+                if self.members.contains_key(&segment_name) {
+                    return Err(FEAError::DuplicateName(segment_name));
+                }
+                let mut segment = template.piece(&pair[0].1, &pair[1].1, k == 0, k == last);
+                // Each segment takes its share of the physical member's bow
+                segment.imperfection = template.imperfection;
+                self.members.insert(segment_name.clone(), segment);
+                segments.push((segment_name, pair[0].0, pair[1].0));
+            }
+            
+            let physical = self.physical_members.get_mut(&name).unwrap();
+            physical.segments = segments;
+            physical.generated_nodes = generated_nodes;
+        }
+        
+        Ok(())
+    }
+
+    /// Map node names to their first global DOF index
+    fn build_dof_map(&self) -> HashMap<String, usize> {
+        self.nodes.iter()
+            .map(|(name, node)| (name.clone(), node.id.unwrap() * 6))
+            .collect()
+    }
+
+    /// Build the global stiffness matrix
+    fn build_global_stiffness(&self, cancel: &CancelToken) -> FEAResult<(Mat, HashMap<String, usize>)> {
+        let n_nodes = self.nodes.len();
+        let n_dofs = n_nodes * 6;
+        
+        let mut k_global = Mat::zeros(n_dofs, n_dofs);
+        
+        let dof_map = self.build_dof_map();
+
+        // Add member stiffness
+        cancel.check()?;
+        self.assemble_member_stiffness(&mut k_global, 0..self.member_ids.len());
+
+        // Add plate and quad stiffness
+        cancel.check()?;
+        self.assemble_shell_stiffness(&mut k_global, &dof_map);
+        
+        // Add solid stiffness
+        cancel.check()?;
+        self.assemble_solid_stiffness(&mut k_global, &dof_map)?;
+
+        Ok((k_global, dof_map))
+    }
+
+    /// Global stiffness matrix, reused from the last analysis when no
+    /// stiffness input has changed since
+    fn cached_global_stiffness(&mut self, options: &AnalysisOptions) -> FEAResult<(Arc<Mat>, HashMap<String, usize>)> {
+        let fingerprint = self.stiffness_fingerprint(&options.solver);
+        if let Some(cached) = self.cached_stiffness(fingerprint) {
+            return Ok(cached);
+        }
+        
+        let (k_global, dof_map) = self.build_global_stiffness(&options.cancel)?;
+        Ok((self.cache_stiffness(fingerprint, k_global, &dof_map), dof_map))
+    }
+
+    /// Global stiffness and DOF map of the last analysis, if its fingerprint matches
+    fn cached_stiffness(&self, fingerprint: Option<u64>) -> Option<(Arc<Mat>, HashMap<String, usize>)> {
+        self.stiffness_cache.as_ref()
+            .filter(|c| Some(c.fingerprint) == fingerprint)
+            .map(|cache| (cache.k_global.clone(), cache.dof_map.clone()))
+    }
+
+    /// Keep a freshly assembled global stiffness for later analyses
+    fn cache_stiffness(&mut self, fingerprint: Option<u64>, k_global: Mat, dof_map: &HashMap<String, usize>) -> Arc<Mat> {
+        let k_global = Arc::new(k_global);
+        self.stiffness_cache = fingerprint.map(|fingerprint| StiffnessCache {
+            fingerprint,
+            k_global: k_global.clone(),
+            dof_map: dof_map.clone(),
+            factorization: None,
+        });
+        k_global
+    }
+
+    /// Hash of everything the free stiffness and its factorisation depend on
+    ///
+    /// Results are skipped when serialising, so only the inputs are hashed.
+    fn stiffness_fingerprint(&self, solver: &SolverBackend) -> Option<u64> {
+        let inputs = (
+            &self.nodes, &self.materials, &self.sections, &self.members, &self.plates, &self.quads,
+            &self.tetras, &self.supports, &self.rigid_links, &self.gaps,
+        );
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_vec(&inputs).ok()?.hash(&mut hasher);
+        format!("{solver:?}").hash(&mut hasher);
+        Some(hasher.finish())
+    }
+
+    /// Assemble the stiffness of a range of members, by handle index, into a global matrix
+    ///
+    /// This is the assembly hot path for frame models, so members are visited
+    /// in handle order and their DOFs come from the node handles rather than
+    /// from name lookups. The element matrices are formed in parallel and
+    /// added in order.
+    fn assemble_member_stiffness(&self, k_global: &mut impl math::Assemble, members: Range<usize>) {
+        let ids: Vec<(MemberId, &str)> = self.member_ids.iter().skip(members.start).take(members.len()).collect();
+        let blocks = parallel::map(&ids, |&(id, name)| self.member_global_stiffness(id, name));
+
+        // Assemble the i-i, i-j, j-i and j-j blocks
+        for (dofs, k_member_global) in blocks {
+            for (ni, &di) in dofs.iter().enumerate() {
+                for (nj, &dj) in dofs.iter().enumerate() {
+                    for a in 0..6 {
+                        for b in 0..6 {
+                            k_global.add_entry(di + a, dj + b, k_member_global[(ni * 6 + a, nj * 6 + b)]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Global stiffness of a member, with the first DOF of its i and j nodes
+    fn member_global_stiffness(&self, id: MemberId, name: &str) -> ([usize; 2], math::Mat12) {
+            let member = &self.members[name];
+            let material = &self.materials[&member.material];
+            let section = &self.sections[&member.section];
+            let [i_node, j_node] = self.member_nodes[id.index()];
+            
+            let length = member.length.unwrap();
+            
+            // Get local stiffness matrix, including any elastic foundation
+            let k_local = math::member_local_stiffness(
+                material.e,
+                material.g,
+                section.a,
+                section.iy,
+                section.iz,
+                section.j,
+                length,
+            ) + math::member_foundation_stiffness(member.foundation_modulus, length);
+            
+            // Apply end releases
+            let k_local = math::apply_releases(&k_local, &member.releases.as_array());
+            
+            // Get transformation matrix
+            let t = math::member_transformation_matrix(
+                &self.node_coords[i_node.index()],
+                &self.node_coords[j_node.index()],
+                member.rotation,
+            );
+            
+            // Transform to global: K_global = T^T * K_local * T
+            ([i_node.index() * 6, j_node.index() * 6], t.transpose() * k_local * t)
+    }
+        
+    /// Add a member's global stiffness, scaled by `factor`, to a global matrix
+    fn add_member_stiffness(&self, k_global: &mut impl math::Assemble, name: &str, factor: f64) {
+        let id = self.member_ids.id(name).unwrap();
+        let (dofs, k_member_global) = self.member_global_stiffness(id, name);
+            for (ni, &di) in dofs.iter().enumerate() {
+                for (nj, &dj) in dofs.iter().enumerate() {
+                    for a in 0..6 {
+                        for b in 0..6 {
+                        k_global.add_entry(di + a, dj + b, factor * k_member_global[(ni * 6 + a, nj * 6 + b)]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Assemble plate and quad element stiffness into a global matrix
+    fn assemble_shell_stiffness(&self, k_global: &mut impl math::Assemble, dof_map: &HashMap<String, usize>) {
+        // Plates first, then quads, each in name order
+        let names: Vec<&String> = self.plates.keys().chain(self.quads.keys()).collect();
+        let shells = parallel::map(&names, |name| self.shell_stiffness(name).unwrap());
+        for (nodes, k_shell_global) in shells {
+            let dofs = nodes.map(|node| dof_map[node]);
+            
+            // Add all 4x4 node blocks
+            for (ni, &di) in dofs.iter().enumerate() {
+                for (nj, &dj) in dofs.iter().enumerate() {
+                    let ki = ni * 6;
+                    let kj = nj * 6;
+                    for a in 0..6 {
+                        for b in 0..6 {
+                            k_global.add_entry(di + a, dj + b, k_shell_global[(ki + a, kj + b)]);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Add Winkler foundation stiffness under plates and quads
+        for foundation in self.shell_foundations() {
+            Self::add_foundation_stiffness(k_global, dof_map, &foundation, 1.0);
+        }
+    }
+
+    /// Corner nodes and global stiffness matrix of a plate or quad
+    fn shell_stiffness(&self, name: &str) -> Option<([&String; 4], math::Mat24)> {
+        if let Some(plate) = self.plates.get(name) {
+            let material = self.materials.get(&plate.material)?;
+            
+            // Local stiffness matrix with the plate's formulation
+            let k_local = math::plate_local_stiffness_with_formulation(
+                material.e,
+                material.nu,
+                plate.thickness,
+                plate.width?,
+                plate.height?,
+                plate.kx_mod,
+                plate.ky_mod,
+                plate.formulation,
+            );
+            let t = math::plate_transformation_matrix(
+                &self.nodes[&plate.i_node].coords(),
+                &self.nodes[&plate.j_node].coords(),
+                &self.nodes[&plate.n_node].coords(),
+            );
+            
+            // Transform to global: K_global = T^T * K_local * T
+            let nodes = [&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node];
+            return Some((nodes, t.transpose() * k_local * t));
+        }
+        
+        let quad = self.quads.get(name)?;
+        let material = self.materials.get(&quad.material)?;
+        
+        // Dimensions and transformation of the flattened element
+        let (width, height, t) = self.quad_frame(quad);
+        
+        // Get local stiffness matrix (using same plate formulation for now)
+        let k_local = math::plate_local_stiffness(
+            material.e,
+            material.nu,
+            quad.thickness,
+            width,
+            height,
+            quad.kx_mod,
+            quad.ky_mod,
+        );
+        let nodes = [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node];
+        Some((nodes, t.transpose() * k_local * t))
+    }
+
+    /// Winkler foundations under plates and quads, sorted by element name
+    fn shell_foundations(&self) -> Vec<ShellFoundation> {
+        let plates = self.plates.iter().filter_map(|(name, plate)| {
+            let foundation = plate.foundation?;
+            let nodes = [&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node];
+            let t = math::plate_transformation_matrix(
+                &self.nodes[nodes[0]].coords(),
+                &self.nodes[nodes[1]].coords(),
+                &self.nodes[nodes[3]].coords(),
+            );
+            Some((name, nodes, foundation, plate.width?, plate.height?, t))
+        });
+        let quads = self.quads.iter().filter_map(|(name, quad)| {
+            let foundation = quad.foundation?;
+            let nodes = [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node];
+            let (width, height, t) = self.quad_frame(quad);
+            Some((name, nodes, foundation, width, height, t))
+        });
+        
+        let mut foundations: Vec<ShellFoundation> = plates.chain(quads)
+            .map(|(name, nodes, foundation, width, height, t)| {
+                let k_local = math::plate_foundation_stiffness(foundation.modulus, width, height);
+                ShellFoundation {
+                    element: name.clone(),
+                    nodes: nodes.map(|n| n.clone()),
+                    k: t.transpose() * k_local * t,
+                    t,
+                    modulus: foundation.modulus,
+                    compression_only: foundation.compression_only,
+                }
+            })
+            .collect();
+        foundations.sort_by(|a, b| a.element.cmp(&b.element));
+        foundations
+    }
+
+    /// Add a scaled foundation stiffness matrix into a global matrix
+    fn add_foundation_stiffness(
+        k_global: &mut impl math::Assemble,
+        dof_map: &HashMap<String, usize>,
+        foundation: &ShellFoundation,
+        factor: f64,
+    ) {
+        for (ni, node_i) in foundation.nodes.iter().enumerate() {
+            for (nj, node_j) in foundation.nodes.iter().enumerate() {
+                let (di, dj) = (dof_map[node_i], dof_map[node_j]);
+                for a in 0..6 {
+                    for b in 0..6 {
+                        k_global.add_entry(di + a, dj + b, factor * foundation.k[(ni * 6 + a, nj * 6 + b)]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Average local z deflection of a foundation element (positive = lifting off)
+    fn foundation_deflection(&self, foundation: &ShellFoundation, combo_name: &str) -> FEAResult<f64> {
+        let mut d_global = math::Vec24::zeros();
+        for (i, node_name) in foundation.nodes.iter().enumerate() {
+            let disp = self.store.displacements.get(node_name, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            for k in 0..6 {
+                d_global[i * 6 + k] = disp[k];
+            }
+        }
+        let d_local = foundation.t * d_global;
+        Ok((d_local[2] + d_local[8] + d_local[14] + d_local[20]) / 4.0)
+    }
+
+    /// Assemble tetra element stiffness into the translational DOFs of a global matrix
+    fn assemble_solid_stiffness(&self, k_global: &mut impl math::Assemble, dof_map: &HashMap<String, usize>) -> FEAResult<()> {
+        for (name, tetra) in &self.tetras {
+            let k = self.tetra_stiffness(name, tetra)?;
+            
+            for (ni, node_i) in tetra.nodes.iter().enumerate() {
+                for (nj, node_j) in tetra.nodes.iter().enumerate() {
+                    let (di, dj) = (dof_map[node_i], dof_map[node_j]);
+                    for a in 0..3 {
+                        for b in 0..3 {
+                            k_global.add_entry(di + a, dj + b, k[(ni * 3 + a, nj * 3 + b)]);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stiffness of a tetra element; its geometry is checked again here, as
+    /// nodes may have been moved or merged since it was added
+    fn tetra_stiffness(&self, name: &str, tetra: &Tetra) -> FEAResult<math::Mat12> {
+        let material = self.materials.get(&tetra.material)
+            .ok_or_else(|| FEAError::MaterialNotFound(tetra.material.clone()))?;
+        math::tet4_stiffness(&self.tetra_coords(tetra), material.e, material.nu)
+            .ok_or_else(|| FEAError::InvalidGeometry(format!("Tetra '{name}' has zero volume")))
+    }
+
+    /// Corner coordinates of a tetra element
+    fn tetra_coords(&self, tetra: &Tetra) -> [[f64; 3]; 4] {
+        tetra.nodes.each_ref().map(|n| self.nodes[n].coords())
+    }
+
+    /// Translational displacements of a tetra's nodes for a combination
+    fn tetra_displacements(&self, tetra: &Tetra, combo_name: &str) -> FEAResult<math::Vec12> {
+        let mut d = math::Vec12::zeros();
+        for (i, node_name) in tetra.nodes.iter().enumerate() {
+            let disp = self.store.displacements.get(node_name, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            for k in 0..3 {
+                d[i * 3 + k] = disp[k];
+            }
+        }
+        Ok(d)
+    }
+
+    /// Nodes connected only to solid elements
+    ///
+    /// Solids have no rotational stiffness, so the rotations of these nodes
+    /// are restrained to keep the stiffness matrix non-singular.
+    fn solid_only_nodes(&self) -> HashSet<&String> {
+        let mut nodes: HashSet<&String> = self.tetras.values().flat_map(|t| t.nodes.iter()).collect();
+        if nodes.is_empty() {
+            return nodes;
+        }
+        
+        for member in self.members.values() {
+            nodes.remove(&member.i_node);
+            nodes.remove(&member.j_node);
+        }
+        for plate in self.plates.values() {
+            for n in [&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node] {
+                nodes.remove(n);
+            }
+        }
+        for quad in self.quads.values() {
+            for n in [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node] {
+                nodes.remove(n);
+            }
+        }
+        for link in self.rigid_links.values() {
+            nodes.remove(&link.master);
+            nodes.remove(&link.slave);
+        }
+        nodes
+    }
+
+    /// Physical member an analysis segment belongs to, with the segment's start and end along it
+    fn segment_parent(&self, member_name: &str) -> Option<(&str, &PhysicalMember, f64, f64)> {
+        self.physical_members.iter().find_map(|(name, physical)| {
+            physical.segments.iter()
+                .find(|(segment, _, _)| segment == member_name)
+                .map(|&(_, start, end)| (name.as_str(), physical, start, end))
+        })
+    }
+
+    /// Distributed loads on a member, including its share of loads on its physical member
+    ///
+    /// Physical member loads are clipped to the segment and re-measured from
+    /// the segment's i-node, with linearly varying magnitudes interpolated.
+    fn member_dist_loads_on(&self, member_name: &str) -> Vec<DistributedLoad> {
+        let mut loads = self.member_dist_loads.get(member_name).cloned().unwrap_or_default();
+        let Some((parent, physical, start, end)) = self.segment_parent(member_name) else {
+            return loads;
+        };
+        let Some(parent_loads) = self.member_dist_loads.get(parent) else {
+            return loads;
+        };
+        let length = physical.length().unwrap_or(end);
+        
+        loads.extend(parent_loads.iter().filter_map(|load| load.clipped(start, end, length)));
+        loads
+    }
+
+    /// Sum the local fixed end reactions of all member loads for a combination
+    ///
+    /// Returns the uncondensed 12-element FER vector in member local coordinates.
+    /// Global-direction loads are resolved into local components first.
+    fn member_fer(&self, member_name: &str, combo: &LoadCombination) -> math::Vec12 {
+        let mut fer_total = math::Vec12::zeros();
+        
+        let Some(member) = self.members.get(member_name) else {
+            return fer_total;
+        };
+        let length = member.length.unwrap();
+        
+        let i_node = self.nodes.get(&member.i_node).unwrap();
+        let j_node = self.nodes.get(&member.j_node).unwrap();
+        let t = math::member_transformation_matrix(
+            &i_node.coords(),
+            &j_node.coords(),
+            member.rotation,
+        );
+        // T transforms global to local, so its rows are the local axes
+        let r = math::extract_rotation_matrix(&t);
+        
+        for load in &self.member_dist_loads_on(member_name) {
+            let factor = combo.factor(&load.case);
+            if factor.abs() < 1e-10 {
+                continue;
+            }
+            
+            let (w1, w2) = (factor * load.w1, factor * load.w2);
+            let x2 = load.x2.min(length);
+            
+            // Handle both local and global direction loads
+            match load.direction {
+                LoadDirection::Fx => fer_total += math::fer_linear_load(w1, w2, load.x1, x2, length, 0),
+                LoadDirection::Fy => fer_total += math::fer_linear_load(w1, w2, load.x1, x2, length, 1),
+                LoadDirection::Fz => fer_total += math::fer_linear_load(w1, w2, load.x1, x2, length, 2),
+                LoadDirection::FX | LoadDirection::FY | LoadDirection::FZ => {
+                    let global_dir = match load.direction {
+                        LoadDirection::FX => math::Vec3::new(1.0, 0.0, 0.0),
+                        LoadDirection::FY => math::Vec3::new(0.0, 1.0, 0.0),
+                        _ => math::Vec3::new(0.0, 0.0, 1.0),
+                    };
+                    
+                    // Apply FER in each local direction proportionally
+                    let local_dir = r * global_dir;
+                    for axis in 0..3 {
+                        if local_dir[axis].abs() > 1e-10 {
+                            let (c1, c2) = (w1 * local_dir[axis], w2 * local_dir[axis]);
+                            fer_total += math::fer_linear_load(c1, c2, load.x1, x2, length, axis);
+                        }
+                    }
+                }
+                LoadDirection::Mx => {
+                    fer_total += math::fer_distributed_torque(w1, w2, load.x1, x2, length);
+                }
+                _ => continue, // Skip distributed bending moments
+            }
+        }
+        
+        // Locked-in prestress, held by the fixed ends
+        let tension = self.member_prestress(member_name, combo);
+        fer_total[0] -= tension;
+        fer_total[6] += tension;
+        
+        // Equivalent loads of the initial bow under the current axial compression
+        if let (Some(imperfection), Some(&compression)) =
+            (member.imperfection, member.imperfection_axial.get(&combo.name))
+        {
+            if let Some((_, physical, start, end)) = self.segment_parent(member_name) {
+                let physical_length = physical.length().unwrap_or(end);
+                fer_total += segment_imperfection_fer(&imperfection, compression, start, end, physical_length);
+            } else {
+                let factor = match imperfection.shape {
+                    ImperfectionShape::Sine => 2.0 / std::f64::consts::PI,
+                    ImperfectionShape::Parabolic => 2.0 / 3.0,
+                };
+                fer_total += math::fer_imperfection(compression, imperfection.y, imperfection.z, factor);
+            }
+        }
+        
+        fer_total
+    }
+
+    /// Restrained thermal forces and moments [Nx, Ny, Mx, My] of a plate or quad
+    fn plate_thermal_resultants(&self, plate_name: &str, combo: &LoadCombination) -> [f64; 4] {
+        let Some(loads) = self.plate_thermal_loads.get(plate_name) else {
+            return [0.0; 4];
+        };
+        let (thickness, material, kx_mod, ky_mod) = match self.plates.get(plate_name) {
+            Some(p) => (p.thickness, &p.material, p.kx_mod, p.ky_mod),
+            None => {
+                let q = &self.quads[plate_name];
+                (q.thickness, &q.material, q.kx_mod, q.ky_mod)
+            }
+        };
+        let material = &self.materials[material];
+        
+        let (delta_t, gradient) = loads.iter().fold((0.0, 0.0), |(dt, grad), load| {
+            let factor = combo.factor(&load.case);
+            (dt + factor * load.delta_t, grad + factor * load.gradient)
+        });
+        math::plate_thermal_resultants(
+            material.alpha, delta_t, gradient, material.e, material.nu, thickness, kx_mod, ky_mod,
+        )
+    }
+
+    /// Total prestress tension in a member for a load combination
+    fn member_prestress(&self, member_name: &str, combo: &LoadCombination) -> f64 {
+        let member = &self.members[member_name];
+        let ea = self.materials[&member.material].e * self.sections[&member.section].a;
+        let parent = self.segment_parent(member_name).map(|(name, ..)| name);
+        
+        [Some(member_name), parent].into_iter()
+            .flatten()
+            .filter_map(|name| self.member_prestress_loads.get(name))
+            .flatten()
+            .map(|load| combo.factor(&load.case) * load.tension(ea))
+            .sum()
+    }
+
+    /// Build the global load vector for a load combination
+    fn build_load_vector(
+        &self,
+        combo: &LoadCombination,
+        dof_map: &HashMap<String, usize>,
+    ) -> FEAResult<FEVec> {
+        let n_dofs = self.nodes.len() * 6;
+        let mut p = FEVec::zeros(n_dofs);
+
+        // Add node loads
+        for (node_name, loads) in &self.node_loads {
+            let dof = dof_map[node_name];
+            
+            for load in loads {
+                let factor = combo.factor(&load.case);
+                if factor.abs() > 1e-10 {
+                    let load_arr = load.as_array();
+                    for i in 0..6 {
+                        p[dof + i] += factor * load_arr[i];
+                    }
+                }
+            }
+        }
+
+        // Add fixed end reactions from member loads
+        for (member_name, member) in &self.members {
+            let fer_local = self.member_fer(member_name, combo);
+            if fer_local.iter().all(|v| v.abs() < 1e-14) {
+                continue;
+            }
+            
+            let i_node = self.nodes.get(&member.i_node).unwrap();
+            let j_node = self.nodes.get(&member.j_node).unwrap();
+            let t = math::member_transformation_matrix(
+                &i_node.coords(),
+                &j_node.coords(),
+                member.rotation,
+            );
+            
+            // Transform to global
+            let fer_global = t.transpose() * fer_local;
+            
+            // Subtract from load vector (FER is reaction, so negate)
+            let i_dof = dof_map[&member.i_node];
+            let j_dof = dof_map[&member.j_node];
+            
+            for i in 0..6 {
+                p[i_dof + i] -= fer_global[i];
+                p[j_dof + i] -= fer_global[i + 6];
+            }
+        }
+
+        // Add fixed end reactions from plate pressure, point and thermal loads
+        let loaded_plates: BTreeSet<&String> = self.plate_loads.keys()
+            .chain(self.plate_point_loads.keys())
+            .chain(self.plate_thermal_loads.keys())
+            .collect();
+        
+        for plate_name in loaded_plates {
+            let Some((nodes, fer_global)) = self.shell_fer(plate_name, combo)? else {
+                continue;
+            };
+            
+            // Subtract from load vector (FER is reaction, so negate)
+            let dofs = nodes.map(|node| dof_map[&node]);
+            for (ni, &di) in dofs.iter().enumerate() {
+                for a in 0..6 {
+                    p[di + a] -= fer_global[ni * 6 + a];
+                }
+            }
+        }
+
+        Ok(p)
+    }
+
+    /// Corner nodes and global fixed end reactions of the pressure, point and
+    /// thermal loads on a plate or quad
+    fn shell_fer(&self, plate_name: &str, combo: &LoadCombination) -> FEAResult<Option<([String; 4], math::Vec24)>> {
+        // Try plate first, then quad
+        let (i_node, j_node, m_node, n_node, width, height, t) = 
+            if let Some(plate) = self.plates.get(plate_name) {
+                let t = math::plate_transformation_matrix(
+                    &self.nodes[&plate.i_node].coords(),
+                    &self.nodes[&plate.j_node].coords(),
+                    &self.nodes[&plate.n_node].coords(),
+                );
+                (plate.i_node.clone(), plate.j_node.clone(), plate.m_node.clone(), plate.n_node.clone(),
+                 plate.width.unwrap(), plate.height.unwrap(), t)
+            } else if let Some(quad) = self.quads.get(plate_name) {
+                let (width, height, t) = self.quad_frame(quad);
+                (quad.i_node.clone(), quad.j_node.clone(), quad.m_node.clone(), quad.n_node.clone(),
+                 width, height, t)
+            } else {
+                return Ok(None);
+            };
+        
+        let mut fer_local = math::Vec24::zeros();
+        
+        for load in self.plate_loads.get(plate_name).into_iter().flatten() {
+            let factor = combo.factor(&load.case);
+            if factor.abs() < 1e-10 {
+                continue;
+            }
+            
+            let load = load.scaled(factor);
+            fer_local += match load.distribution {
+                PressureDistribution::Uniform => {
+                    math::plate_fer_pressure(load.pressure, width, height)
+                }
+                PressureDistribution::Bilinear(corner_pressures) => {
+                    math::plate_fer_bilinear(corner_pressures, width, height)
+                }
+                PressureDistribution::Hydrostatic { fluid_level, unit_weight } => {
+                    let elevations = [&i_node, &j_node, &m_node, &n_node].map(|name| self.nodes[name].y);
+                    math::plate_fer_hydrostatic(fluid_level, unit_weight, elevations, width, height)
+                }
+            };
+        }
+        
+        for load in self.plate_point_loads.get(plate_name).into_iter().flatten() {
+            let factor = combo.factor(&load.case);
+            if factor.abs() < 1e-10 {
+                continue;
+            }
+            
+            if load.x > width + 1e-9 || load.y > height + 1e-9 {
+                return Err(FEAError::InvalidInput(format!(
+                    "Point load position ({}, {}) lies outside plate '{plate_name}'",
+                    load.x, load.y
+                )));
+            }
+            fer_local += math::plate_fer_point_load(factor * load.magnitude, load.x, load.y, width, height);
+        }
+        
+        if self.plate_thermal_loads.contains_key(plate_name) {
+            let resultants = self.plate_thermal_resultants(plate_name, combo);
+            fer_local += math::plate_fer_thermal(resultants, width, height);
+        }
+        
+        // Transform to global
+        let fer_global = t.transpose() * fer_local;
+        
+        Ok(Some(([i_node, j_node, m_node, n_node], fer_global)))
+    }
+
+    /// Split the global DOFs into free DOFs and enforced support displacements
+    ///
+    /// Returns the sorted free DOF indices and a map of restrained DOF index to its
+    /// enforced displacement. Restrained DOFs without an enforced value are fixed at zero.
+    /// Rigid link slave DOFs belong to neither set, and the rotations of nodes
+    /// connected only to solid elements are fixed. Restrained DOFs listed in
+    /// `released` (lifted one-way supports) are treated as free.
+    fn partition_dofs(
+        &self,
+        dof_map: &HashMap<String, usize>,
+        released: &HashSet<usize>,
+    ) -> (Vec<usize>, BTreeMap<usize, f64>) {
+        let mut free_dofs: Vec<usize> = Vec::new();
+        let mut enforced_displacements: BTreeMap<usize, f64> = BTreeMap::new();
+        let slaves: HashSet<&String> = self.rigid_links.values().map(|link| &link.slave).collect();
+        let solid_only = self.solid_only_nodes();
+        
+        for node_name in self.nodes.keys() {
+            // Rigid link slave DOFs are eliminated by the constraint transformation
+            if slaves.contains(node_name) {
+                continue;
+            }
+            let base_dof = dof_map[node_name];
+            let n_active = if solid_only.contains(node_name) { 3 } else { 6 };
+            
+            if let Some(support) = self.supports.get(node_name) {
+                let restraints = [
+                    support.dx,
+                    support.dy,
+                    support.dz,
+                    support.rx,
+                    support.ry,
+                    support.rz,
+                ];
+                let enforced = support.enforced_displacements();
+                
+                for i in 0..n_active {
+                    if restraints[i] && !released.contains(&(base_dof + i)) {
+                        if let Some(val) = enforced[i] {
+                            enforced_displacements.insert(base_dof + i, val);
+                        }
+                    } else {
+                        free_dofs.push(base_dof + i);
+                    }
+                }
+            } else {
+                for i in 0..n_active {
+                    free_dofs.push(base_dof + i);
+                }
+            }
+        }
+        
+        free_dofs.sort_unstable();
+        (free_dofs, enforced_displacements)
+    }
+
+    /// Singular matrix error naming the free DOFs and members at fault
+    ///
+    /// `k11` is the stiffness on `free_dofs`, in the same order.
+    fn singular_matrix(&self, k11: &Mat, free_dofs: &[usize], dof_map: &HashMap<String, usize>) -> FEAError {
+        const DOFS: [&str; 6] = ["DX", "DY", "DZ", "RX", "RY", "RZ"];
+        let nodes: HashMap<usize, &String> = dof_map.iter().map(|(name, &base)| (base / 6, name)).collect();
+        let unconstrained_dofs = math::zero_pivots(k11, 1e-10)
+            .into_iter()
+            .map(|i| UnconstrainedDof {
+                node: nodes[&(free_dofs[i] / 6)].clone(),
+                dof: DOFS[free_dofs[i] % 6].to_string(),
+            })
+            .collect();
+        
+        // Stiffness properties that are zero or negligible next to the largest in the model
+        let properties: Vec<(&String, [f64; 7])> = self.members.iter()
+            .filter_map(|(name, member)| {
+                let material = self.materials.get(&member.material)?;
+                let section = self.sections.get(&member.section)?;
+                let length = member.length().unwrap_or(0.0);
+                Some((name, [material.e, material.g, section.a, section.iy, section.iz, section.j, length]))
+            })
+            .collect();
+        let mut largest = [0.0_f64; 7];
+        for (_, values) in &properties {
+            for (max, value) in largest.iter_mut().zip(values) {
+                *max = max.max(value.abs());
+            }
+        }
+        let mut weak_members: Vec<String> = properties.iter()
+            .filter(|(_, values)| values.iter().zip(&largest).any(|(value, max)| value.abs() <= 1e-9 * max))
+            .map(|(name, _)| (*name).clone())
+            .collect();
+        weak_members.sort();
+        
+        FEAError::SingularMatrix(SingularityDiagnostics { unconstrained_dofs, weak_members })
+    }
+
+    /// Condition number of the diagonally scaled free stiffness and the
+    /// largest stiffness ratios between members meeting at a node
+    fn conditioning(
+        &self,
+        k_global: &Mat,
+        dof_map: &HashMap<String, usize>,
+        solver: &SolverBackend,
+    ) -> FEAResult<ConditioningReport> {
+        const DOFS: [&str; 6] = ["DX", "DY", "DZ", "RX", "RY", "RZ"];
+        let constrained;
+        let k_global = match self.build_constraints(dof_map)? {
+            Some(c) => {
+                constrained = c.transform_stiffness(k_global);
+                &constrained
+            }
+            None => k_global,
+        };
+        let (free_dofs, _) = self.partition_dofs(dof_map, &HashSet::new());
+        if free_dofs.is_empty() {
+            return Err(FEAError::AnalysisFailed("No free degrees of freedom".to_string()));
+        }
+
+        // Scale to a unit diagonal so translations and rotations are comparable
+        let n = free_dofs.len();
+        let scale: Vec<f64> = free_dofs.iter()
+            .map(|&d| if k_global[(d, d)] > 0.0 { 1.0 / k_global[(d, d)].sqrt() } else { 1.0 })
+            .collect();
+        let k11 = Mat::from_fn(n, n, |i, j| k_global[(free_dofs[i], free_dofs[j])] * scale[i] * scale[j]);
+        let factorization = solver.factorize(&k11)
+            .ok_or_else(|| self.singular_matrix(&k11, &free_dofs, dof_map))?;
+        let (condition_number, softest) = estimate_condition(&k11, &*factorization, 30);
+
+        let nodes: HashMap<usize, &String> = dof_map.iter().map(|(name, &base)| (base / 6, name)).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| softest[b].abs().total_cmp(&softest[a].abs()));
+        let softest_dofs = order.iter()
+            .take(5)
+            .map(|&i| format!("{} {}", nodes[&(free_dofs[i] / 6)], DOFS[free_dofs[i] % 6]))
+            .collect();
+
+        // Axial and flexural stiffness of each member, compared at shared nodes
+        let mut stiffness: BTreeMap<&String, [f64; 2]> = BTreeMap::new();
+        for (name, member) in &self.members {
+            let (Some(material), Some(section), Some(length)) =
+                (self.materials.get(&member.material), self.sections.get(&member.section), member.length())
+            else {
+                continue;
+            };
+            stiffness.insert(name, [
+                material.e * section.a / length,
+                12.0 * material.e * section.iy.max(section.iz) / length.powi(3),
+            ]);
+        }
+        let mut at_node: BTreeMap<&String, Vec<&String>> = BTreeMap::new();
+        for name in stiffness.keys() {
+            let member = &self.members[*name];
+            at_node.entry(&member.i_node).or_default().push(name);
+            at_node.entry(&member.j_node).or_default().push(name);
+        }
+        let mut stiffness_ratios = Vec::new();
+        for (node, members) in at_node {
+            for (k, a) in members.iter().enumerate() {
+                for b in &members[k + 1..] {
+                    let (ka, kb) = (stiffness[a], stiffness[b]);
+                    let ratio = |i: usize| ka[i].max(kb[i]) / ka[i].min(kb[i]).max(f64::MIN_POSITIVE);
+                    let kind = if ratio(0) >= ratio(1) { 0 } else { 1 };
+                    let (stiff, flexible) = if ka[kind] >= kb[kind] { (a, b) } else { (b, a) };
+                    stiffness_ratios.push(StiffnessRatio {
+                        node: node.clone(),
+                        stiff_member: (*stiff).clone(),
+                        flexible_member: (*flexible).clone(),
+                        ratio: ratio(kind),
+                    });
+                }
+            }
+        }
+        stiffness_ratios.sort_by(|a, b| b.ratio.total_cmp(&a.ratio));
+        stiffness_ratios.truncate(10);
+
+        Ok(ConditioningReport { condition_number, softest_dofs, stiffness_ratios })
+    }
+
+    /// Build the rigid link and skewed support transformation `d = C d`
+    ///
+    /// Each slave DOF is expressed in terms of its master's DOFs; all other rows
+    /// are identity. Slave columns are zero, so `C^T K C` has empty rows and
+    /// columns at the slave DOFs. The DOFs of skewed supports are then rotated
+    /// into the support axes, so their restraints apply in the local frame.
+    /// Returns None when the model has neither.
+    fn build_constraints(&self, dof_map: &HashMap<String, usize>) -> FEAResult<Option<Constraints>> {
+        let skewed: Vec<(&String, &Support)> = self.supports.iter()
+            .filter(|(_, support)| support.is_skewed())
+            .collect();
+        if self.rigid_links.is_empty() && skewed.is_empty() {
+            return Ok(None);
+        }
+        
+        let mut links: BTreeMap<usize, Vec<(usize, f64)>> = BTreeMap::new();
+        for (name, link) in &self.rigid_links {
+            if self.supports.get(&link.slave).is_some_and(|s| s.is_supported()) {
+                return Err(FEAError::InvalidInput(format!(
+                    "Slave node '{}' of rigid link '{name}' cannot be supported", link.slave
+                )));
+            }
+            
+            let (m, sl) = (dof_map[&link.master], dof_map[&link.slave]);
+            let xm = self.nodes[&link.master].coords();
+            let xs = self.nodes[&link.slave].coords();
+            let r = [xs[0] - xm[0], xs[1] - xm[1], xs[2] - xm[2]];
+            
+            // u_s = u_m + theta_m x r
+            links.insert(sl, vec![(m, 1.0), (m + 4, r[2]), (m + 5, -r[1])]);
+            links.insert(sl + 1, vec![(m + 1, 1.0), (m + 5, r[0]), (m + 3, -r[2])]);
+            links.insert(sl + 2, vec![(m + 2, 1.0), (m + 3, r[1]), (m + 4, -r[0])]);
+            for i in 3..6 {
+                links.insert(sl + i, vec![(m + i, 1.0)]);
+            }
+        }
+        
+        // Global DOFs of a skewed support from its local DOFs: u = axes^T u_local
+        let mut skews: HashMap<usize, Vec<(usize, f64)>> = HashMap::new();
+        for (node_name, support) in skewed {
+            let axes = support.orientation.unwrap();
+            let base = dof_map[node_name];
+            for offset in [0, 3] {
+                for i in 0..3 {
+                    skews.insert(base + offset + i, axes.iter().enumerate().map(|(j, axis)| (base + offset + j, axis[i])).collect());
+                }
+            }
+        }
+        
+        // C = C_links T, expanding the link rows through the rotated DOFs
+        let mut rows = BTreeMap::new();
+        for (r, row) in links {
+            let mut combined: BTreeMap<usize, f64> = BTreeMap::new();
+            for (j, c) in row {
+                match skews.get(&j) {
+                    Some(skew) => skew.iter().for_each(|&(k, t)| *combined.entry(k).or_default() += c * t),
+                    None => *combined.entry(j).or_default() += c,
+                }
+            }
+            rows.insert(r, combined.into_iter().collect());
+        }
+        for (r, row) in skews {
+            rows.entry(r).or_insert(row);
+        }
+        
+        Ok(Some(Constraints { rows }))
+    }
+
+    /// Solve linear system with support conditions
+    fn solve_linear(
+        &mut self,
+        k_global: &Mat,
+        p_global: &FEVec,
+        dof_map: &HashMap<String, usize>,
+        combo_name: &str,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        self.solve_linear_released(k_global, p_global, dof_map, combo_name, options, &HashSet::new())
+    }
+
+    /// Solve linear system with the `released` restrained DOFs freed
+    fn solve_linear_released(
+        &mut self,
+        k_global: &Mat,
+        p_global: &FEVec,
+        dof_map: &HashMap<String, usize>,
+        combo_name: &str,
+        options: &AnalysisOptions,
+        released: &HashSet<usize>,
+    ) -> FEAResult<()> {
+        let system = self.linear_system(k_global, dof_map, options, released)?;
+        let d_full = system.solve(p_global);
+        self.store_displacements(&d_full, dof_map, combo_name);
+        Ok(())
+    }
+
+    /// Factorised free stiffness with the `released` restrained DOFs freed,
+    /// ready to solve any number of load vectors
+    fn linear_system(
+        &mut self,
+        k_global: &Mat,
+        dof_map: &HashMap<String, usize>,
+        options: &AnalysisOptions,
+        released: &HashSet<usize>,
+    ) -> FEAResult<LinearSystem> {
+        let unconstrained = k_global;
+        
+        // Eliminate rigid link slave DOFs
+        let constraints = self.build_constraints(dof_map)?;
+        let constrained;
+        let k_global = match &constraints {
+            Some(c) => {
+                constrained = c.transform_stiffness(k_global);
+                &constrained
+            }
+            None => k_global,
+        };
+        
+        // Identify free and restrained DOFs
+        let (free_dofs, enforced_displacements) = self.partition_dofs(dof_map, released);
+
+        if free_dofs.is_empty() {
+            return Err(FEAError::AnalysisFailed(
+                "No free degrees of freedom".to_string(),
+            ));
+        }
+
+        // Load on the free DOFs from the enforced displacements, the same for every combination
+        let n_free = free_dofs.len();
+        let mut enforced_load = FEVec::zeros(n_free);
+        for (i, &di) in free_dofs.iter().enumerate() {
+            for (&dj, &val) in &enforced_displacements {
+                enforced_load[i] += k_global[(di, dj)] * val;
+            }
+        }
+
+        // Factorise K11 unless the cached global stiffness, unreleased, was already factorised
+        let cacheable = released.is_empty() && self.stiffness_cache.as_ref()
+            .is_some_and(|cache| std::ptr::eq(&*cache.k_global, unconstrained));
+        let cached = self.stiffness_cache.as_ref()
+            .and_then(|cache| cache.factorization.clone())
+            .filter(|_| cacheable);
+        let factorization = match cached {
+            Some(factorization) => factorization,
+            None => {
+                let k11 = Mat::from_fn(n_free, n_free, |i, j| k_global[(free_dofs[i], free_dofs[j])]);
+                let factorization: Arc<dyn Factorization> = match options.solver.factorize(&k11) {
+                    Some(f) => f.into(),
+                    None => return Err(self.singular_matrix(&k11, &free_dofs, dof_map)),
+                };
+                if cacheable {
+                    if let Some(cache) = self.stiffness_cache.as_mut() {
+                        cache.factorization = Some(factorization.clone());
+                    }
+                }
+                factorization
+            }
+        };
+
+        Ok(LinearSystem {
+            n_dofs: self.nodes.len() * 6,
+            constraints,
+            free_dofs,
+            enforced_displacements,
+            enforced_load,
+            factorization,
+        })
+    }
+
+    /// Store a full displacement vector as the nodal displacements of a combination
+    fn store_displacements(&mut self, d_full: &FEVec, dof_map: &HashMap<String, usize>, combo_name: &str) {
+        for node_name in self.nodes.keys() {
+            let base_dof = dof_map[node_name];
+            let disp = [
+                d_full[base_dof],
+                d_full[base_dof + 1],
+                d_full[base_dof + 2],
+                d_full[base_dof + 3],
+                d_full[base_dof + 4],
+                d_full[base_dof + 5],
+            ];
+            self.store.displacements.insert(node_name, combo_name, disp);
+        }
+    }
+
+    /// Solve with tension- and compression-only members, one-way supports,
+    /// gap/hook elements and compression-only foundations
+    ///
+    /// Each iteration drops the stiffness of tension-only (compression-only)
+    /// members carrying compression (tension) and restores slack members that
+    /// are stretched (shortened) again, releases active one-way restraints
+    /// whose reaction has the wrong sign, re-engages released ones whose node
+    /// has moved through the support, updates which gaps are closed and hooks
+    /// are taut, and removes the foundation stiffness of every
+    /// compression-only element whose average deflection is away from the
+    /// foundation, until none of these change. Members, gaps and foundations
+    /// start engaged so slack ties can carry load from the first iteration.
+    fn solve_nonlinear(
+        &mut self,
+        k_global: &Mat,
+        p_global: &FEVec,
+        dof_map: &HashMap<String, usize>,
+        combo_name: &str,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        // (node, local DOF, allowed reaction sign), in a deterministic order
+        let mut one_way: Vec<(String, usize, f64)> = self.supports.iter()
+            .flat_map(|(node, support)| {
+                support.one_way.iter().enumerate()
+                    .filter_map(move |(dof, dir)| dir.map(|d| (node.clone(), dof, d.sign())))
+            })
+            .collect();
+        one_way.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+        
+        let mut gap_names: Vec<String> = self.gaps.keys().cloned().collect();
+        gap_names.sort();
+        let mut engaged = vec![true; gap_names.len()];
+        
+        let foundations: Vec<ShellFoundation> = self.shell_foundations().into_iter()
+            .filter(|f| f.compression_only)
+            .collect();
+        let mut lifted = vec![false; foundations.len()];
+        
+        // (member, allowed axial force sign, positive for tension)
+        let one_way_members: Vec<(String, f64)> = self.members.iter()
+            .filter(|(_, m)| m.tension_only || m.compression_only)
+            .map(|(name, m)| (name.clone(), if m.tension_only { 1.0 } else { -1.0 }))
+            .collect();
+        let mut slack = vec![false; one_way_members.len()];
+
+        let mut released: HashSet<usize> = HashSet::new();
+        // Residual: the number of members, supports, gaps and foundations changing state
+        let mut residuals = Vec::new();
+        for _ in 0..options.max_iterations {
+            options.cancel.check()?;
+            let mut k_iter = k_global.clone();
+            let mut p_iter = p_global.clone();
+            for (name, _) in gap_names.iter().zip(&engaged).filter(|(_, &on)| on) {
+                self.add_gap_stiffness(&mut k_iter, &mut p_iter, dof_map, &self.gaps[name]);
+            }
+            for (foundation, _) in foundations.iter().zip(&lifted).filter(|(_, &off)| off) {
+                Self::add_foundation_stiffness(&mut k_iter, dof_map, foundation, -1.0);
+            }
+            let slack_names: Vec<String> = one_way_members.iter().zip(&slack)
+                .filter(|(_, &off)| off)
+                .map(|((name, _), _)| name.clone())
+                .collect();
+            for name in &slack_names {
+                self.add_member_stiffness(&mut k_iter, name, -1.0);
+            }
+            self.slack_members.insert(combo_name.to_string(), slack_names);
+            
+            self.solve_linear_released(&k_iter, &p_iter, dof_map, combo_name, options, &released)?;
+            
+            let mut changes = 0;
+            for (name, on) in gap_names.iter().zip(engaged.iter_mut()) {
+                let gap = &self.gaps[name];
+                let elongation = self.gap_elongation(gap, combo_name)?;
+                let force = if *on { gap.engaged_force(elongation) } else { 0.0 };
+                let now = gap.is_engaged(elongation);
+                self.gaps.get_mut(name).unwrap().forces.insert(combo_name.to_string(), force);
+                if now != *on {
+                    *on = now;
+                    changes += 1;
+                }
+            }
+            for (foundation, off) in foundations.iter().zip(lifted.iter_mut()) {
+                let now = self.foundation_deflection(foundation, combo_name)? > 0.0;
+                if now != *off {
+                    *off = now;
+                    changes += 1;
+                }
+            }
+            
+            self.calculate_member_forces(combo_name)?;
+            self.calculate_reactions(combo_name, dof_map)?;
+            
+            for ((name, sign), off) in one_way_members.iter().zip(slack.iter_mut()) {
+                let now = if *off {
+                    // Restore when the member is stretched (or shortened) again
+                    let d = self.store.member_displacements.get(name, combo_name).ok_or(FEAError::NotAnalyzed)?;
+                    (d[6] - d[0]) * sign <= options.tolerance * 1e-3
+                } else {
+                    let forces = self.store.member_forces.get(name, combo_name).ok_or(FEAError::NotAnalyzed)?;
+                    -forces[0] * sign < -options.tolerance
+                };
+                if now != *off {
+                    *off = now;
+                    changes += 1;
+                }
+            }
+            for (node_name, dof, sign) in &one_way {
+                let global_dof = dof_map[node_name] + dof;
+                if released.contains(&global_dof) {
+                    // Re-engage when the node has moved through the support
+                    let disp = self.store.displacements.get(node_name, combo_name).ok_or(FEAError::NotAnalyzed)?;
+                    let local: f64 = match self.supports[node_name].orientation {
+                        Some(axes) => (0..3).map(|j| axes[*dof][j] * disp[j]).sum(),
+                        None => disp[*dof],
+                    };
+                    if local * sign < -options.tolerance * 1e-3 {
+                        released.remove(&global_dof);
+                        changes += 1;
+                    }
+                } else if self.store.reactions.get(node_name, combo_name).ok_or(FEAError::NotAnalyzed)?[*dof] * sign
+                    < -options.tolerance
+                {
+                    released.insert(global_dof);
+                    changes += 1;
+                }
+            }
+            
+            if changes == 0 {
+                let mut uplift: Vec<String> = Vec::new();
+                for (node_name, dof, _) in &one_way {
+                    if released.contains(&(dof_map[node_name] + dof)) {
+                        // A lifted support carries no load
+                        if let Some(reactions) = self.store.reactions.get_mut(node_name, combo_name) {
+                            reactions[*dof] = 0.0;
+                        }
+                        uplift.push(node_name.clone());
+                    }
+                }
+                uplift.dedup();
+                self.uplift.insert(combo_name.to_string(), uplift);
+                return Ok(());
+            }
+            residuals.push(changes as f64);
+        }
+        
+        Err(FEAError::ConvergenceFailed { iterations: options.max_iterations, residuals })
+    }
+
+    /// Unit direction of action of a link element
+    fn link_axis(&self, link: &Link) -> [f64; 3] {
+        let d = link.direction.unwrap_or_else(|| {
+            let (xi, xj) = (self.nodes[&link.i_node].coords(), self.nodes[&link.j_node].coords());
+            [xj[0] - xi[0], xj[1] - xi[1], xj[2] - xi[2]]
+        });
+        let length = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        d.map(|v| v / length)
+    }
+
+    /// Lumped mass and rotary inertia at every global DOF
+    ///
+    /// Members share `rho A L` equally between their ends, shells share
+    /// `rho t area` between their four corners and tetras `rho V` between
+    /// their four nodes. Node masses are added on top. The rotational DOFs
+    /// get the rotary inertia of the cross-section: `rho Ix L / 2` at each
+    /// member end about each local axis (`Ix = Iy + Iz` about the member
+    /// axis) and `rho t^3 area / 48` at each shell corner about the
+    /// in-plane axes, resolved onto the global axes. Only the diagonal of
+    /// the resolved inertia is kept. Tetra nodes carry no rotations.
+    fn lumped_masses(&self, dof_map: &HashMap<String, usize>) -> FEVec {
+        let mut masses = FEVec::zeros(self.nodes.len() * 6);
+        let mut add = |node_name: &String, mass: f64, inertia: [f64; 3]| {
+            for a in 0..3 {
+                masses[dof_map[node_name] + a] += mass;
+                masses[dof_map[node_name] + 3 + a] += inertia[a];
+            }
+        };
+        
+        for member in self.members.values() {
+            let rho = self.materials[&member.material].rho;
+            let section = &self.sections[&member.section];
+            let length = member.length.unwrap();
+            let mass = rho * section.a * length;
+            let local = [section.iy + section.iz, section.iy, section.iz].map(|i| rho * i * length / 2.0);
+            let t = math::member_transformation_matrix(
+                &self.nodes[&member.i_node].coords(),
+                &self.nodes[&member.j_node].coords(),
+                member.rotation,
+            );
+            let inertia = std::array::from_fn(|a| (0..3).map(|k| t[(k, a)].powi(2) * local[k]).sum());
+            add(&member.i_node, mass / 2.0, inertia);
+            add(&member.j_node, mass / 2.0, inertia);
+        }
+        let shells = self.plates.values()
+            .map(|p| ([&p.i_node, &p.j_node, &p.m_node, &p.n_node], p.thickness, &p.material))
+            .chain(self.quads.values().map(|q| ([&q.i_node, &q.j_node, &q.m_node, &q.n_node], q.thickness, &q.material)));
+        for (nodes, thickness, material) in shells {
+            let x = nodes.map(|n| self.nodes[n].coords());
+            let area = triangle_area(&x[0], &x[1], &x[2]) + triangle_area(&x[0], &x[2], &x[3]);
+            let mass = self.materials[material].rho * thickness * area;
+            let rotary = mass * thickness.powi(2) / 48.0;
+            let (u, v): ([f64; 3], [f64; 3]) = (
+                std::array::from_fn(|a| x[2][a] - x[0][a]),
+                std::array::from_fn(|a| x[3][a] - x[1][a]),
+            );
+            let normal = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+            let norm = normal.iter().map(|n| n * n).sum::<f64>().sqrt();
+            let inertia = normal.map(|n| rotary * (1.0 - (n / norm).powi(2)));
+            for node_name in nodes {
+                add(node_name, mass / 4.0, inertia);
+            }
+        }
+        for tetra in self.tetras.values() {
+            let volume = math::tet4_volume(&self.tetra_coords(tetra)).abs();
+            let mass = self.materials[&tetra.material].rho * volume;
+            for node_name in &tetra.nodes {
+                add(node_name, mass / 4.0, [0.0; 3]);
+            }
+        }
+        for (node_name, &mass) in &self.node_masses {
+            add(node_name, mass, [0.0; 3]);
+        }
+        
+        masses
+    }
+
+    /// Unit vector from a gap's i-node to its j-node
+    fn gap_axis(&self, gap: &Gap) -> [f64; 3] {
+        let (xi, xj) = (self.nodes[&gap.i_node].coords(), self.nodes[&gap.j_node].coords());
+        let d = [xj[0] - xi[0], xj[1] - xi[1], xj[2] - xi[2]];
+        let length = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        d.map(|v| v / length)
+    }
+
+    /// Axial elongation of a gap element (positive = nodes moving apart)
+    fn gap_elongation(&self, gap: &Gap, combo_name: &str) -> FEAResult<f64> {
+        let e = self.gap_axis(gap);
+        let di = self.store.displacements.get(&gap.i_node, combo_name).ok_or(FEAError::NotAnalyzed)?;
+        let dj = self.store.displacements.get(&gap.j_node, combo_name).ok_or(FEAError::NotAnalyzed)?;
+        Ok((0..3).map(|k| e[k] * (dj[k] - di[k])).sum())
+    }
+
+    /// Add an engaged gap's axial stiffness and opening offset to the system
+    ///
+    /// The engaged force `N = k (elongation -/+ opening)` splits into the axial
+    /// spring `k e e^T` and a constant term moved to the load vector.
+    fn add_gap_stiffness(&self, k_global: &mut Mat, p_global: &mut FEVec, dof_map: &HashMap<String, usize>, gap: &Gap) {
+        let e = self.gap_axis(gap);
+        let (i, j) = (dof_map[&gap.i_node], dof_map[&gap.j_node]);
+        let offset = gap.engaged_force(0.0);
+        
+        for a in 0..3 {
+            for b in 0..3 {
+                let kab = gap.stiffness * e[a] * e[b];
+                k_global[(i + a, i + b)] += kab;
+                k_global[(j + a, j + b)] += kab;
+                k_global[(i + a, j + b)] -= kab;
+                k_global[(j + a, i + b)] -= kab;
+            }
+            p_global[i + a] += offset * e[a];
+            p_global[j + a] -= offset * e[a];
+        }
+    }
+
+    /// Solve using P-Delta iteration
+    fn solve_p_delta(
+        &mut self,
+        k_global: &Mat,
+        p_global: &FEVec,
+        dof_map: &HashMap<String, usize>,
+        combo_name: &str,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        // First iteration: linear solution, stiffened by any prestress
+        let combo = self.load_combos[combo_name].clone();
+        let prestress: HashMap<String, f64> = self.members.keys()
+            .map(|name| (name.clone(), self.member_prestress(name, &combo)))
+            .filter(|(_, tension)| *tension != 0.0)
+            .collect();
+        if prestress.values().any(|t| t.abs() > 0.0) {
+            let kg = self.build_geometric_stiffness(dof_map, &prestress)?;
+            self.solve_linear(&(k_global + kg), p_global, dof_map, combo_name, options)?;
+        } else {
+            self.solve_linear(k_global, p_global, dof_map, combo_name, options)?;
+        }
+        let has_imperfections = self.members.values().any(|m| m.imperfection.is_some());
+        let mut p_iter = p_global.clone();
+        let mut residuals = Vec::new();
+        
+        // Iterative P-Delta
+        for _iter in 0..options.max_iterations {
+            options.cancel.check()?;
+            // Calculate member axial forces
+            self.calculate_member_forces(combo_name)?;
+            
+            // Update the imperfection loads with the new axial forces
+            if has_imperfections {
+                for (name, member) in self.members.iter_mut().filter(|(_, m)| m.imperfection.is_some()) {
+                    let compression = self.store.member_forces.get(name, combo_name).ok_or(FEAError::NotAnalyzed)?[0];
+                    member.imperfection_axial.insert(combo_name.to_string(), compression);
+                }
+                p_iter = self.build_load_vector(&combo, dof_map)?;
+            }
+            
+            // Build geometric stiffness matrix from the member tensions
+            let tensions: HashMap<String, f64> = self.members.keys()
+                .filter_map(|name| Some((name.clone(), -self.store.member_forces.get(name, combo_name)?[0])))
+                .collect();
+            let kg = self.build_geometric_stiffness(dof_map, &tensions)?;
+            
+            // Combined stiffness
+            let k_combined = k_global + &kg;
+            
+            // Solve again
+            let old_displacements: Vec<f64> = self.nodes.keys()
+                .filter_map(|n| self.store.displacements.get(n, combo_name))
+                .flatten()
+                .collect();
+            
+            self.solve_linear(&k_combined, &p_iter, dof_map, combo_name, options)?;
+            
+            // Check convergence
+            let new_displacements: Vec<f64> = self.nodes.keys()
+                .filter_map(|n| self.store.displacements.get(n, combo_name))
+                .flatten()
+                .collect();
+            
+            let mut max_diff = 0.0_f64;
+            for (old, new) in old_displacements.iter().zip(new_displacements.iter()) {
+                max_diff = max_diff.max((new - old).abs());
+            }
+            
+            residuals.push(max_diff);
+            if max_diff < options.tolerance {
+                return Ok(());
+            }
+        }
+        
+        Err(FEAError::ConvergenceFailed { iterations: options.max_iterations, residuals })
+    }
+
+    /// Solve using co-rotational large displacement analysis
+    ///
+    /// The combination loads are applied in `options.load_steps` equal increments and
+    /// each increment is equilibrated with full Newton-Raphson iteration on the
+    /// co-rotational member internal forces. With `options.arc_length` set, the load
+    /// factor becomes an unknown and the equilibrium path is traced with a cylindrical
+    /// (Crisfield) arc-length constraint instead, starting from an arc equal to the
+    /// first load step. Plates and quads contribute their linear
+    /// stiffness. Member span loads act as fixed-direction equivalent nodal loads.
+    ///
+    /// Member forces and reactions are evaluated in the deformed configuration here,
+    /// so `calculate_member_forces`/`calculate_reactions` must not be run afterwards.
+    fn solve_large_displacement(
+        &mut self,
+        p_global: &FEVec,
+        dof_map: &HashMap<String, usize>,
+        combo_name: &str,
+        options: &AnalysisOptions,
+    ) -> FEAResult<()> {
+        if !self.rigid_links.is_empty() {
+            return Err(FEAError::InvalidInput(
+                "Rigid links are not supported in large displacement analysis".to_string(),
+            ));
+        }
+        if self.supports.values().any(|s| s.is_skewed()) {
+            return Err(FEAError::InvalidInput(
+                "Skewed supports are not supported in large displacement analysis".to_string(),
+            ));
+        }
+        
+        let n_dofs = self.nodes.len() * 6;
+        let (free_dofs, enforced_displacements) = self.partition_dofs(dof_map, &HashSet::new());
+        
+        if free_dofs.is_empty() {
+            return Err(FEAError::AnalysisFailed(
+                "No free degrees of freedom".to_string(),
+            ));
+        }
+        
+        let mut k_shell = Mat::zeros(n_dofs, n_dofs);
+        self.assemble_shell_stiffness(&mut k_shell, dof_map);
+        self.assemble_solid_stiffness(&mut k_shell, dof_map)?;
+        
+        // Undeformed nodal coordinates indexed by base DOF / 6
+        let mut coords = vec![math::Vec3::zeros(); self.nodes.len()];
+        for (name, node) in &self.nodes {
+            let c = node.coords();
+            coords[dof_map[name] / 6] = math::Vec3::new(c[0], c[1], c[2]);
+        }
+        
+        // Constant per-member data for the co-rotational update
+        let elements: Vec<CorotationalMember> = self.members.iter()
+            .map(|(name, member)| {
+                let i_node = self.nodes.get(&member.i_node).unwrap();
+                let j_node = self.nodes.get(&member.j_node).unwrap();
+                let material = self.materials.get(&member.material).unwrap();
+                let section = self.sections.get(&member.section).unwrap();
+                let length = member.length.unwrap();
+                
+                let t = math::member_transformation_matrix(
+                    &i_node.coords(),
+                    &j_node.coords(),
+                    member.rotation,
+                );
+                let k_uncondensed = math::member_local_stiffness(
+                    material.e,
+                    material.g,
+                    section.a,
+                    section.iy,
+                    section.iz,
+                    section.j,
+                    length,
+                ) + math::member_foundation_stiffness(member.foundation_modulus, length);
+                
+                CorotationalMember {
+                    name: name.clone(),
+                    i_dof: dof_map[&member.i_node],
+                    j_dof: dof_map[&member.j_node],
+                    frame_0: math::extract_rotation_matrix(&t).transpose(),
+                    length_0: length,
+                    k_local: math::apply_releases(&k_uncondensed, &member.releases.as_array()),
+                    k_uncondensed,
+                    releases: member.releases.as_array(),
+                }
+            })
+            .collect();
+        
+        // Internal force vector and tangent stiffness at the current state
+        let evaluate = |d: &FEVec, rotations: &[math::Mat3]| {
+            let mut f_int = &k_shell * d;
+            let mut k_t = k_shell.clone();
+            let mut responses = Vec::with_capacity(elements.len());
+            
+            for el in &elements {
+                let (ni, nj) = (el.i_dof / 6, el.j_dof / 6);
+                let x_i = coords[ni] + d.fixed_rows::<3>(el.i_dof);
+                let x_j = coords[nj] + d.fixed_rows::<3>(el.j_dof);
+                
+                let resp = math::corotational::corotational_member_response(
+                    &x_i,
+                    &x_j,
+                    &rotations[ni],
+                    &rotations[nj],
+                    &el.frame_0,
+                    el.length_0,
+                    &el.k_local,
+                );
+                
+                let dofs = [el.i_dof, el.j_dof];
+                for (bi, &di) in dofs.iter().enumerate() {
+                    for a in 0..6 {
+                        f_int[di + a] += resp.f_global[bi * 6 + a];
+                        for (bj, &dj) in dofs.iter().enumerate() {
+                            for b in 0..6 {
+                                k_t[(di + a, dj + b)] += resp.k_tangent[(bi * 6 + a, bj * 6 + b)];
+                            }
+                        }
+                    }
+                }
+                responses.push(resp);
+            }
+            
+            (f_int, k_t, responses)
+        };
+        
+        // Translations are additive, rotations are updated multiplicatively
+        let apply_increment = |d: &mut FEVec, rotations: &mut [math::Mat3], dd: &FEVec| {
+            for (node, rot) in rotations.iter_mut().enumerate() {
+                let base = node * 6;
+                for i in 0..3 {
+                    d[base + i] += dd[base + i];
+                }
+                let dtheta = math::Vec3::new(dd[base + 3], dd[base + 4], dd[base + 5]);
+                *rot = math::corotational::rotation_from_vector(&dtheta) * *rot;
+                let theta = math::corotational::rotation_to_vector(rot);
+                for i in 0..3 {
+                    d[base + 3 + i] = theta[i];
+                }
+            }
+        };
+        
+        // Solve the tangent system on the free DOFs for one or more right-hand sides
+        let solve_free = |k_t: &Mat, rhs: &[&FEVec]| -> FEAResult<Vec<FEVec>> {
+            let n_free = free_dofs.len();
+            let k11 = Mat::from_fn(n_free, n_free, |i, j| k_t[(free_dofs[i], free_dofs[j])]);
+            let lu = k11.clone().lu();
+            rhs.iter()
+                .map(|r| {
+                    let r1 = FEVec::from_fn(n_free, |i, _| r[free_dofs[i]]);
+                    let x1 = lu.solve(&r1).ok_or_else(|| self.singular_matrix(&k11, &free_dofs, dof_map))?;
+                    let mut x = FEVec::zeros(n_dofs);
+                    for (i, &di) in free_dofs.iter().enumerate() {
+                        x[di] = x1[i];
+                    }
+                    Ok(x)
+                })
+                .collect()
+        };
+        
+        let free_norm = |v: &FEVec| free_dofs.iter().map(|&i| v[i] * v[i]).sum::<f64>().sqrt();
+        
+        // Newton-Raphson equilibrium iterations at a fixed load factor
+        let equilibrate = |d: &mut FEVec, rotations: &mut [math::Mat3], lambda: f64| -> FEAResult<()> {
+            let p_step = p_global * lambda;
+            let mut residuals = Vec::new();
+            for _iter in 0..options.max_iterations {
+                options.cancel.check()?;
+                let (f_int, k_t, _) = evaluate(d, rotations);
+                let residual = &p_step - f_int;
+                residuals.push(free_norm(&residual));
+                let dd = solve_free(&k_t, &[&residual])?.remove(0);
+                apply_increment(d, rotations, &dd);
+                
+                if free_norm(&dd) <= options.tolerance * free_norm(d) {
+                    return Ok(());
+                }
+            }
+            Err(FEAError::ConvergenceFailed { iterations: options.max_iterations, residuals })
+        };
+        
+        let mut d = FEVec::zeros(n_dofs);
+        let mut rotations = vec![math::Mat3::identity(); self.nodes.len()];
+        let steps = options.load_steps.max(1);
+        
+        match &options.arc_length {
+            None => {
+                for step in 1..=steps {
+                    // Enforced support displacements follow the load factor
+                    let mut dd_enforced = FEVec::zeros(n_dofs);
+                    for (&dof, &val) in &enforced_displacements {
+                        dd_enforced[dof] = val / steps as f64;
+                    }
+                    apply_increment(&mut d, &mut rotations, &dd_enforced);
+                    
+                    equilibrate(&mut d, &mut rotations, step as f64 / steps as f64)?;
+                }
+            }
+            Some(arc) => {
+                if !enforced_displacements.is_empty() {
+                    return Err(FEAError::InvalidInput(
+                        "Arc-length control does not support enforced support displacements".to_string(),
+                    ));
+                }
+                
+                let mut lambda = 0.0;
+                let mut arc_len: Option<f64> = None;
+                let mut prev_increment: Option<FEVec> = None;
+                let mut increments = 0;
+                // Out-of-balance force at the end of each increment's corrector
+                let mut residuals = Vec::new();
+                
+                while lambda < 1.0 {
+                    increments += 1;
+                    if increments > arc.max_increments {
+                        return Err(FEAError::ConvergenceFailed { iterations: arc.max_increments, residuals });
+                    }
+                    
+                    // Predictor along the tangent, scaled to the arc length
+                    let (_, k_t, _) = evaluate(&d, &rotations);
+                    let du_t = solve_free(&k_t, &[p_global])?.remove(0);
+                    let dl = *arc_len.get_or_insert(free_norm(&du_t) / steps as f64);
+                    let sign = match &prev_increment {
+                        Some(prev) if prev.dot(&du_t) < 0.0 => -1.0,
+                        _ => 1.0,
+                    };
+                    
+                    let (d_start, rot_start, lambda_start) = (d.clone(), rotations.clone(), lambda);
+                    let d_lambda = sign * dl / free_norm(&du_t);
+                    let mut total = &du_t * d_lambda;
+                    apply_increment(&mut d, &mut rotations, &total);
+                    lambda += d_lambda;
+                    
+                    // Corrector iterations on the cylindrical arc-length constraint
+                    let mut iterations = None;
+                    let mut out_of_balance = 0.0;
+                    for iter in 1..=options.max_iterations {
+                        options.cancel.check()?;
+                        let (f_int, k_t, _) = evaluate(&d, &rotations);
+                        let residual = p_global * lambda - f_int;
+                        out_of_balance = free_norm(&residual);
+                        let sol = solve_free(&k_t, &[&residual, p_global])?;
+                        let (du_r, du_t) = (&sol[0], &sol[1]);
+                        
+                        let base = &total + du_r;
+                        let a1 = du_t.dot(du_t);
+                        let a2 = 2.0 * du_t.dot(&base);
+                        let a3 = base.dot(&base) - dl * dl;
+                        let disc = a2 * a2 - 4.0 * a1 * a3;
+                        if disc < 0.0 {
+                            break;
+                        }
+                        
+                        // Pick the root that keeps the path moving forward
+                        let roots = [(-a2 + disc.sqrt()) / (2.0 * a1), (-a2 - disc.sqrt()) / (2.0 * a1)];
+                        let d_lambda = if (&base + du_t * roots[0]).dot(&total)
+                            >= (&base + du_t * roots[1]).dot(&total)
+                        {
+                            roots[0]
+                        } else {
+                            roots[1]
+                        };
+                        
+                        let dd = du_r + du_t * d_lambda;
+                        apply_increment(&mut d, &mut rotations, &dd);
+                        total += &dd;
+                        lambda += d_lambda;
+                        
+                        if free_norm(&dd) <= options.tolerance * free_norm(&d) {
+                            iterations = Some(iter);
+                            break;
+                        }
+                    }
+                    
+                    residuals.push(out_of_balance);
+                    
+                    match iterations {
+                        Some(_) if lambda > 1.0 && lambda - 1.0 > 0.1 * (lambda - lambda_start) => {
+                            // Overshot the full load by a large margin - retry with
+                            // an arc that ends close to it
+                            arc_len = Some(dl * (1.0 - lambda_start) / (lambda - lambda_start));
+                            d = d_start;
+                            rotations = rot_start;
+                            lambda = lambda_start;
+                        }
+                        Some(iters) => {
+                            // Adapt the arc length to the convergence rate
+                            let scale = (arc.target_iterations as f64 / iters as f64).sqrt().clamp(0.5, 2.0);
+                            arc_len = Some(dl * scale);
+                            prev_increment = Some(total);
+                        }
+                        None => {
+                            // Restart the increment with a shorter arc
+                            d = d_start;
+                            rotations = rot_start;
+                            lambda = lambda_start;
+                            arc_len = Some(dl / 2.0);
+                        }
+                    }
+                }
+                
+                // Land exactly on the full load from the last (slightly overshooting) increment
+                equilibrate(&mut d, &mut rotations, 1.0)?;
+            }
+        }
+        
+        // Final state: store displacements, member forces and reactions
+        let (f_int, _, responses) = evaluate(&d, &rotations);
+        let combo = self.load_combos.get(combo_name).cloned()
+            .ok_or_else(|| FEAError::LoadCombinationNotFound(combo_name.to_string()))?;
+        
+        for (el, resp) in elements.iter().zip(responses.iter()) {
+            let fer = self.member_fer(&el.name, &combo);
+            let mut f_local = resp.f_local + math::apply_fer_releases(&fer, &el.k_uncondensed, &el.releases);
+            for (i, &released) in el.releases.iter().enumerate() {
+                if released {
+                    f_local[i] = 0.0;
+                }
+            }
+            
+            self.store.member_forces.insert(&el.name, combo_name, f_local.into());
+            self.store.member_displacements.insert(&el.name, combo_name, resp.d_local.into());
+        }
+        
+        for node_name in self.nodes.keys() {
+            let base = dof_map[node_name];
+            let mut disp = [0.0; 6];
+            disp.copy_from_slice(&d.as_slice()[base..base + 6]);
+            self.store.displacements.insert(node_name, combo_name, disp);
+            
+            if let Some(support) = self.supports.get(node_name) {
+                if !support.is_supported() {
+                    continue;
+                }
+                let mask = [support.dx, support.dy, support.dz, support.rx, support.ry, support.rz];
+                let mut reactions = [0.0; 6];
+                for i in 0..6 {
+                    if mask[i] {
+                        reactions[i] = f_int[base + i] - p_global[base + i];
+                    }
+                }
+                self.store.reactions.insert(node_name, combo_name, reactions);
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Build geometric stiffness matrix for P-Delta from member tensions (by member name)
+    fn build_geometric_stiffness(
+        &self,
+        dof_map: &HashMap<String, usize>,
+        tensions: &HashMap<String, f64>,
+    ) -> FEAResult<Mat> {
+        let n_dofs = self.nodes.len() * 6;
+        let mut kg_global = Mat::zeros(n_dofs, n_dofs);
+
+        for (member_name, member) in &self.members {
+            let p = tensions.get(member_name).copied().unwrap_or(0.0);
+            
+            if p.abs() < 1e-10 {
+                continue;
+            }
+            
+            let section = self.sections.get(&member.section).unwrap();
+            let length = member.length.unwrap();
+            
+            let i_node = self.nodes.get(&member.i_node).unwrap();
+            let j_node = self.nodes.get(&member.j_node).unwrap();
+            
+            // Local geometric stiffness
+            let kg_local = math::member_geometric_stiffness(
+                p,
+                section.a,
+                section.iy,
+                section.iz,
+                length,
+            );
+            
+            // Transform to global
+            let t = math::member_transformation_matrix(
+                &i_node.coords(),
+                &j_node.coords(),
+                member.rotation,
+            );
+            
+            let kg_member_global = t.transpose() * kg_local * t;
+            
+            // Assemble
+            let i_dof = dof_map[&member.i_node];
+            let j_dof = dof_map[&member.j_node];
+            
+            for a in 0..6 {
+                for b in 0..6 {
+                    kg_global[(i_dof + a, i_dof + b)] += kg_member_global[(a, b)];
+                    kg_global[(i_dof + a, j_dof + b)] += kg_member_global[(a, b + 6)];
+                    kg_global[(j_dof + a, i_dof + b)] += kg_member_global[(a + 6, b)];
+                    kg_global[(j_dof + a, j_dof + b)] += kg_member_global[(a + 6, b + 6)];
+                }
+            }
+        }
+
+        Ok(kg_global)
+    }
+
+    /// Calculate member forces from displacements
+    /// 
+    /// The total member forces are:
+    ///   F_total = K_local * d_local + FER
+    /// 
+    /// where FER (fixed end reactions) accounts for distributed loads along the member.
+    /// This is the same approach as PyNite and standard structural analysis.
+    fn calculate_member_forces(&mut self, combo_name: &str) -> FEAResult<()> {
+        // Get load combination for factor lookup
+        let combo = self.load_combos.get(combo_name).cloned()
+            .ok_or_else(|| FEAError::AnalysisFailed(format!("Load combo not found: {}", combo_name)))?;
+        
+        // Slack members carry only their span loads
+        let slack: HashSet<&String> = self.slack_members.get(combo_name).into_iter().flatten().collect();
+
+        // Recover the members in parallel, then store them in order
+        let member_names: Vec<&String> = self.members.keys().collect();
+        let recovered = parallel::map(&member_names, |&member_name| -> FEAResult<([f64; 12], [f64; 12])> {
+            let member = self.members.get(member_name).unwrap();
+            let i_node = self.nodes.get(&member.i_node).unwrap();
+            let j_node = self.nodes.get(&member.j_node).unwrap();
+            let material = self.materials.get(&member.material).unwrap();
+            let section = self.sections.get(&member.section).unwrap();
+            let length = member.length.unwrap();
+            let rotation = member.rotation;
+            
+            // Get nodal displacements
+            let d_i = self.store.displacements.get(&member.i_node, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            let d_j = self.store.displacements.get(&member.j_node, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            
+            // Global displacement vector for member
+            let d_global = math::Vec12::from_iterator(
+                d_i.iter().chain(d_j.iter()).copied()
+            );
+            
+            // Transformation matrix
+            let t = math::member_transformation_matrix(
+                &i_node.coords(),
+                &j_node.coords(),
+                rotation,
+            );
+            
+            // Local displacements
+            let d_local = t * d_global;
+            
+            // Get member releases for static condensation
+            let releases = member.releases.as_array();
+            
+            // Local stiffness - get the uncondensed matrix first
+            let k_local_uncondensed = math::member_local_stiffness(
+                material.e,
+                material.g,
+                section.a,
+                section.iy,
+                section.iz,
+                section.j,
+                length,
+            ) + math::member_foundation_stiffness(member.foundation_modulus, length);
+            
+            // Apply static condensation for releases (same as Pynite's k() method)
+            // This sets rows/columns for released DOFs to zero, so F = K_condensed * d 
+            // will give zero forces at released DOFs
+            let k_local = math::apply_releases(&k_local_uncondensed, &releases);
+            
+            // Local forces from nodal displacements: F_elastic = K_condensed * d_local
+            let mut f_local = if slack.contains(member_name) { math::Vec12::zeros() } else { k_local * d_local };
+            
+            // Add fixed end reactions (FER) from distributed loads
+            // This is critical: FER accounts for loads applied between nodes
+            // Following PyNite's convention: F = K*d + FER_condensed
+            // The FER must also be condensed for releases (same as stiffness)
+            let fer_uncondensed = self.member_fer(member_name, &combo);
+            let fer_condensed = math::apply_fer_releases(&fer_uncondensed, &k_local_uncondensed, &releases);
+            f_local += fer_condensed;
+            
+            // Explicitly zero out forces at released DOFs
+            // While static condensation should theoretically do this, we enforce it
+            // to ensure numerical precision and correct moment diagrams at hinges/pins
+            for (i, &released) in releases.iter().enumerate() {
+                if released {
+                    f_local[i] = 0.0;
+                }
+            }
+            
+            // Store results
+            let mut forces = [0.0; 12];
+            for i in 0..12 {
+                forces[i] = f_local[i];
+            }
+            
+            let mut displacements = [0.0; 12];
+            for i in 0..12 {
+                displacements[i] = d_local[i];
+            }
+            Ok((forces, displacements))
+        });
+        
+        // Store the member results
+        for (member_name, result) in member_names.iter().zip(recovered) {
+            let (forces, displacements) = result?;
+            self.store.member_forces.insert(member_name, combo_name, forces);
+            self.store.member_displacements.insert(member_name, combo_name, displacements);
+        }
+
+        Ok(())
+    }
+
+    /// Calculate reactions at supports
+    fn calculate_reactions(
+        &mut self,
+        combo_name: &str,
+        _dof_map: &HashMap<String, usize>,
+    ) -> FEAResult<()> {
+        // First, collect all the reaction contributions
+        let mut all_reactions: HashMap<String, [f64; 6]> = HashMap::new();
+        
+        for (node_name, support) in &self.supports {
+            if !support.is_supported() {
+                continue;
+            }
+            all_reactions.insert(node_name.clone(), [0.0; 6]);
+        }
+        
+        // Rigid link slaves pass their unbalanced force on to their master
+        for link in self.rigid_links.values() {
+            if all_reactions.contains_key(&link.master) {
+                all_reactions.insert(link.slave.clone(), [0.0; 6]);
+            }
+        }
+        
+        // Sum forces from connected members
+        for (name, member) in &self.members {
+            let forces = self.store.member_forces.get(name, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            
+            let i_node = self.nodes.get(&member.i_node).unwrap();
+            let j_node = self.nodes.get(&member.j_node).unwrap();
+            
+            let t = math::member_transformation_matrix(
+                &i_node.coords(),
+                &j_node.coords(),
+                member.rotation,
+            );
+            
+            let f_local = math::Vec12::from_iterator(forces.iter().copied());
+            let f_global = t.transpose() * f_local;
+            
+            if let Some(reactions) = all_reactions.get_mut(&member.i_node) {
+                for i in 0..6 {
+                    reactions[i] += f_global[i];
+                }
+            }
+            if let Some(reactions) = all_reactions.get_mut(&member.j_node) {
+                for i in 0..6 {
+                    reactions[i] += f_global[i + 6];
+                }
+            }
+        }
+        
+        // Sum forces from gap/hook elements
+        for gap in self.gaps.values() {
+            let force = gap.forces.get(combo_name).copied().unwrap_or(0.0);
+            let e = self.gap_axis(gap);
+            if let Some(reactions) = all_reactions.get_mut(&gap.i_node) {
+                for k in 0..3 {
+                    reactions[k] -= force * e[k];
+                }
+            }
+            if let Some(reactions) = all_reactions.get_mut(&gap.j_node) {
+                for k in 0..3 {
+                    reactions[k] += force * e[k];
+                }
+            }
+        }
+        
+        // Sum forces from connected solids
+        for (name, tetra) in &self.tetras {
+            if !tetra.nodes.iter().any(|n| all_reactions.contains_key(n)) {
+                continue;
+            }
+            let k = self.tetra_stiffness(name, tetra)?;
+            let f = k * self.tetra_displacements(tetra, combo_name)?;
+            
+            for (i, node_name) in tetra.nodes.iter().enumerate() {
+                if let Some(reactions) = all_reactions.get_mut(node_name) {
+                    for dof in 0..3 {
+                        reactions[dof] += f[i * 3 + dof];
+                    }
+                }
+            }
+        }
+        
+        // Subtract applied loads and store results
+        for (node_name, reactions) in &mut all_reactions {
+            if let Some(loads) = self.node_loads.get(node_name) {
+                let combo = self.load_combos.get(combo_name).unwrap();
+                for load in loads {
+                    let factor = combo.factor(&load.case);
+                    let load_arr = load.as_array();
+                    for i in 0..6 {
+                        reactions[i] -= factor * load_arr[i];
+                    }
+                }
+            }
+        }
+        
+        for link in self.rigid_links.values() {
+            if let Some(f) = all_reactions.remove(&link.slave) {
+                let xm = self.nodes[&link.master].coords();
+                let xs = self.nodes[&link.slave].coords();
+                let r = [xs[0] - xm[0], xs[1] - xm[1], xs[2] - xm[2]];
+                let reactions = all_reactions.get_mut(&link.master).unwrap();
+                
+                // Force plus the moment r x F it produces about the master
+                reactions[0] += f[0];
+                reactions[1] += f[1];
+                reactions[2] += f[2];
+                reactions[3] += f[3] + r[1] * f[2] - r[2] * f[1];
+                reactions[4] += f[4] + r[2] * f[0] - r[0] * f[2];
+                reactions[5] += f[5] + r[0] * f[1] - r[1] * f[0];
+            }
+        }
+        
+        // Store reactions in nodes - only for restrained DOFs
+        for (node_name, mut reactions) in all_reactions {
+            if let Some(support) = self.supports.get(&node_name) {
+                // Skewed supports report reactions in their own axes
+                if let Some(axes) = support.orientation {
+                    let global = reactions;
+                    for offset in [0, 3] {
+                        for i in 0..3 {
+                            reactions[offset + i] = (0..3).map(|j| axes[i][j] * global[offset + j]).sum();
+                        }
+                    }
+                }
+                
+                // Mask out reactions for DOFs that are not restrained
+                let mask = [support.dx, support.dy, support.dz, support.rx, support.ry, support.rz];
+                for i in 0..6 {
+                    if !mask[i] {
+                        reactions[i] = 0.0;
+                    }
+                }
+            }
+            
+            self.store.reactions.insert(&node_name, combo_name, reactions);
+        }
+
+        Ok(())
+    }
+
+    // ========================
+    // Result Access Methods
+    // ========================
+
+    /// Get node displacement
+    pub fn node_displacement(&self, node_name: &str, combo_name: &str) -> FEAResult<NodeDisplacement> {
+        if !self.nodes.contains_key(node_name) {
+            return Err(FEAError::NodeNotFound(node_name.to_string()));
+        }
+        
+        let disp = self.store.displacements.get(node_name, combo_name)
+            .ok_or(FEAError::NotAnalyzed)?;
+        
+        Ok(NodeDisplacement::from_array(disp))
+    }
+
+    /// Get node reactions
+    pub fn node_reactions(&self, node_name: &str, combo_name: &str) -> FEAResult<Reactions> {
+        if !self.nodes.contains_key(node_name) {
+            return Err(FEAError::NodeNotFound(node_name.to_string()));
+        }
+        
+        let rxn = self.store.reactions.get(node_name, combo_name)
+            .ok_or(FEAError::NotAnalyzed)?;
+        
+        Ok(Reactions::from_array(rxn))
+    }
+
+    /// Get the local end forces of an analysis member
+    /// [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, Fy_j, Fz_j, Mx_j, My_j, Mz_j]
+    pub fn member_local_forces(&self, member_name: &str, combo_name: &str) -> FEAResult<[f64; 12]> {
+        if !self.members.contains_key(member_name) {
+            return Err(FEAError::MemberNotFound(member_name.to_string()));
+        }
+        self.store.member_forces.get(member_name, combo_name)
+            .ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Get member forces at i-node (the first segment's i-node for a physical member)
+    pub fn member_forces_i(&self, member_name: &str, combo_name: &str) -> FEAResult<MemberForces> {
+        if let Some(physical) = self.physical_members.get(member_name) {
+            let (segment, _, _) = physical.segments.first().ok_or(FEAError::NotAnalyzed)?;
+            return self.member_forces_i(segment, combo_name);
+        }
+        let forces = self.member_local_forces(member_name, combo_name)?;
+        Ok(MemberForces::from_i_node_forces(&forces))
+    }
+
+    /// Get member forces at j-node (the last segment's j-node for a physical member)
+    pub fn member_forces_j(&self, member_name: &str, combo_name: &str) -> FEAResult<MemberForces> {
+        if let Some(physical) = self.physical_members.get(member_name) {
+            let (segment, _, _) = physical.segments.last().ok_or(FEAError::NotAnalyzed)?;
+            return self.member_forces_j(segment, combo_name);
+        }
+        let forces = self.member_local_forces(member_name, combo_name)?;
+        Ok(MemberForces::from_j_node_forces(&forces))
+    }
+
+    /// Get internal forces at a distance `x` from a physical member's i-node
+    ///
+    /// Forces follow the diagram sign convention, with axial tension positive.
+    /// Within a segment they are interpolated from its end
+    /// forces assuming shear varies linearly, which is exact for uniform span
+    /// loads. At a segment boundary the segment ending there is used.
+    pub fn physical_member_forces(&self, name: &str, x: f64, combo_name: &str) -> FEAResult<MemberForces> {
+        let physical = self.physical_members.get(name)
+            .ok_or_else(|| FEAError::MemberNotFound(name.to_string()))?;
+        let length = physical.length().ok_or(FEAError::NotAnalyzed)?;
+        let (segment_name, x) = physical.segment_at(x).ok_or_else(|| FEAError::InvalidInput(format!(
+            "Position {x} is outside physical member {name} of length {length}"
+        )))?;
+        
+        let forces = self.member_local_forces(segment_name, combo_name)?;
+        segment_forces(&self.members[segment_name], &forces, x)
+    }
+
+    /// Get internal forces at a distance `x` from a member's i-node
+    ///
+    /// Works for both analysis members and physical members, with the
+    /// interpolation of [`FEModel::physical_member_forces`].
+    pub fn member_forces_at(&self, member_name: &str, x: f64, combo_name: &str) -> FEAResult<MemberForces> {
+        if self.physical_members.contains_key(member_name) {
+            return self.physical_member_forces(member_name, x, combo_name);
+        }
+        let member = self.members.get(member_name)
+            .ok_or_else(|| FEAError::MemberNotFound(member_name.to_string()))?;
+        let length = member.length.ok_or(FEAError::NotAnalyzed)?;
+        if !(-1e-9..=length + 1e-9).contains(&x) {
+            return Err(FEAError::InvalidInput(format!(
+                "Position {x} is outside member {member_name} of length {length}"
+            )));
+        }
+        segment_forces(member, &self.member_local_forces(member_name, combo_name)?, x)
+    }
+
+    /// Internal forces at `points` evenly spaced stations along a physical member
+    ///
+    /// Returns (distance from the i-node, forces) pairs including both ends.
+    pub fn physical_member_diagram(
+        &self,
+        name: &str,
+        combo_name: &str,
+        points: usize,
+    ) -> FEAResult<Vec<(f64, MemberForces)>> {
+        let physical = self.physical_members.get(name)
+            .ok_or_else(|| FEAError::MemberNotFound(name.to_string()))?;
+        let length = physical.length().ok_or(FEAError::NotAnalyzed)?;
+        let points = points.max(2);
+        
+        (0..points)
+            .map(|k| {
+                let x = length * k as f64 / (points - 1) as f64;
+                Ok((x, self.physical_member_forces(name, x, combo_name)?))
+            })
+            .collect()
+    }
+
+    /// Internal forces at `points` evenly spaced stations along a member
+    ///
+    /// Works for both analysis members and physical members, with the
+    /// interpolation of [`FEModel::member_forces_at`]. Returns (distance from
+    /// the i-node, forces) pairs including both ends.
+    pub fn member_diagram(
+        &self,
+        member_name: &str,
+        combo_name: &str,
+        points: usize,
+    ) -> FEAResult<Vec<(f64, MemberForces)>> {
+        let length = self.analysed_length(member_name)?;
+        let points = points.max(2);
+
+        (0..points)
+            .map(|k| {
+                let x = length * k as f64 / (points - 1) as f64;
+                Ok((x, self.member_forces_at(member_name, x, combo_name)?))
+            })
+            .collect()
+    }
+
+    /// Transverse deflections at `points` evenly spaced stations along a member
+    ///
+    /// Works for both analysis members and physical members; a physical
+    /// member's chord runs between its own ends, not those of its segments.
+    /// Between nodes the curvature M / EI of the interpolated moment diagram
+    /// of [`FEModel::member_forces_at`] is integrated, so deflections are exact
+    /// for end and uniform span loads and exclude shear deformation.
+    pub fn member_deflections(
+        &self,
+        member_name: &str,
+        combo_name: &str,
+        points: usize,
+    ) -> FEAResult<Vec<MemberDeflection>> {
+        let length = self.analysed_length(member_name)?;
+        let points = points.max(2);
+
+        let mut deflections = (0..points)
+            .map(|k| {
+                let x = length * k as f64 / (points - 1) as f64;
+                let [dy, dz] = self.local_deflection(member_name, x, combo_name)?;
+                Ok(MemberDeflection { x, dy, dz, relative_y: 0.0, relative_z: 0.0 })
+            })
+            .collect::<FEAResult<Vec<_>>>()?;
+
+        let (first, last) = (deflections[0], deflections[points - 1]);
+        for deflection in &mut deflections {
+            let s = deflection.x / length;
+            deflection.relative_y = deflection.dy - first.dy - (last.dy - first.dy) * s;
+            deflection.relative_z = deflection.dz - first.dz - (last.dz - first.dz) * s;
+        }
+        Ok(deflections)
+    }
+
+    /// Transverse deflection at a distance `x` from a member's i-node, as
+    /// computed by [`FEModel::member_deflections`]
+    pub fn member_deflection_at(&self, member_name: &str, x: f64, combo_name: &str) -> FEAResult<MemberDeflection> {
+        let length = self.analysed_length(member_name)?;
+        if !(-1e-9..=length + 1e-9).contains(&x) {
+            return Err(FEAError::InvalidInput(format!(
+                "Position {x} is outside member {member_name} of length {length}"
+            )));
+        }
+        let [dy, dz] = self.local_deflection(member_name, x, combo_name)?;
+        let [dy_i, dz_i] = self.local_deflection(member_name, 0.0, combo_name)?;
+        let [dy_j, dz_j] = self.local_deflection(member_name, length, combo_name)?;
+        let s = x / length;
+        Ok(MemberDeflection {
+            x,
+            dy,
+            dz,
+            relative_y: dy - dy_i - (dy_j - dy_i) * s,
+            relative_z: dz - dz_i - (dz_j - dz_i) * s,
+        })
+    }
+
+    /// Length of an analysis or physical member
+    fn analysed_length(&self, member_name: &str) -> FEAResult<f64> {
+        match self.physical_members.get(member_name) {
+            Some(physical) => physical.length(),
+            None => self.members.get(member_name)
+                .ok_or_else(|| FEAError::MemberNotFound(member_name.to_string()))?
+                .length,
+        }.ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Local [dy, dz] of an analysis or physical member at `x` from its i-node
+    fn local_deflection(&self, member_name: &str, x: f64, combo_name: &str) -> FEAResult<[f64; 2]> {
+        let (segment, x_segment) = match self.physical_members.get(member_name) {
+            Some(physical) => physical.segment_at(x).ok_or(FEAError::NotAnalyzed)?,
+            None => (member_name, x),
+        };
+        let member = &self.members[segment];
+        let section = self.sections.get(&member.section)
+            .ok_or_else(|| FEAError::SectionNotFound(member.section.clone()))?;
+        let material = self.materials.get(&member.material)
+            .ok_or_else(|| FEAError::MaterialNotFound(member.material.clone()))?;
+        let forces = self.member_local_forces(segment, combo_name)?;
+        let displacements = self.store.member_displacements.get(segment, combo_name)
+            .ok_or(FEAError::NotAnalyzed)?;
+        segment_deflection(member, section, material, &forces, &displacements, x_segment)
+    }
+
+    /// Displacements of the nodes of a group, in the group's order
+    pub fn group_node_displacements(
+        &self,
+        group_name: &str,
+        combo_name: &str,
+    ) -> FEAResult<Vec<(String, NodeDisplacement)>> {
+        self.group(group_name)?.nodes.iter()
+            .map(|node| Ok((node.clone(), self.node_displacement(node, combo_name)?)))
+            .collect()
+    }
+
+    /// End forces (i, j) of the members of a group, in the group's order
+    pub fn group_member_forces(
+        &self,
+        group_name: &str,
+        combo_name: &str,
+    ) -> FEAResult<Vec<(String, MemberForces, MemberForces)>> {
+        self.group(group_name)?.members.iter()
+            .map(|member| {
+                Ok((
+                    member.clone(),
+                    self.member_forces_i(member, combo_name)?,
+                    self.member_forces_j(member, combo_name)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Story shears, overturning moments and drifts of a combination, bottom story first
+    pub fn story_results(&self, combo_name: &str) -> FEAResult<Vec<StoryResult>> {
+        if !self.is_analyzed() {
+            return Err(FEAError::NotAnalyzed);
+        }
+        let combo = self.load_combos.get(combo_name)
+            .ok_or_else(|| FEAError::LoadCombinationNotFound(combo_name.to_string()))?;
+        let mut stories: Vec<(&String, &StoryLevel)> = self.stories.iter().collect();
+        stories.sort_by(|a, b| a.1.elevation.total_cmp(&b.1.elevation));
+
+        // External forces on each node: equivalent nodal loads plus reactions
+        let dof_map = self.build_dof_map();
+        let p = self.build_load_vector(combo, &dof_map)?;
+        let external: Vec<([f64; 3], [f64; 6])> = self.nodes.iter()
+            .map(|(name, node)| {
+                let dof = dof_map[name];
+                let reaction = self.store.reactions.get(name, combo_name).unwrap_or([0.0; 6]);
+                (node.coords(), std::array::from_fn(|k| p[dof + k] + reaction[k]))
+            })
+            .collect();
+
+        let level_nodes = |story: &StoryLevel| -> FEAResult<Vec<(&String, &Node)>> {
+            Ok(match &story.group {
+                Some(group) => self.group(group)?.nodes.iter().map(|n| (n, &self.nodes[n])).collect(),
+                None => self.nodes.iter().filter(|(_, n)| (n.y - story.elevation).abs() <= story.tolerance).collect(),
+            })
+        };
+        let displacement = |node: &str| self.store.displacements.get(node, combo_name).ok_or(FEAError::NotAnalyzed);
+
+        let Some(&(_, lowest)) = stories.first() else {
+            return Ok(Vec::new());
+        };
+        let base_elevation = self.nodes.values().map(|n| n.y).fold(f64::INFINITY, f64::min);
+        let mut below = level_nodes(&StoryLevel::new(base_elevation).with_tolerance(lowest.tolerance))?;
+        let mut below_elevation = base_elevation;
+        let mut below_tolerance = lowest.tolerance;
+        let mut results = Vec::new();
+        for (name, story) in stories {
+            let nodes = level_nodes(story)?;
+            if nodes.is_empty() {
+                return Err(FEAError::InvalidInput(format!("Story '{name}' has no nodes")));
+            }
+            let height = story.elevation - below_elevation;
+            if height <= 0.0 {
+                return Err(FEAError::InvalidGeometry(format!("Story '{name}' is not above the level below it")));
+            }
+
+            // Resultant of the external forces above the level below
+            let (mut shear, mut overturning) = ([0.0; 2], [0.0; 2]);
+            for (coords, f) in external.iter().filter(|(c, _)| c[1] > below_elevation + below_tolerance) {
+                let (x, y, z) = (coords[0], coords[1] - below_elevation, coords[2]);
+                shear[0] += f[0];
+                shear[1] += f[2];
+                overturning[0] += f[3] + y * f[2] - z * f[1];
+                overturning[1] += f[5] + x * f[1] - y * f[0];
+            }
+
+            let average = |nodes: &[(&String, &Node)]| -> FEAResult<[f64; 2]> {
+                let mut sum = [0.0; 2];
+                for (node, _) in nodes {
+                    let d = displacement(node)?;
+                    sum[0] += d[0];
+                    sum[1] += d[2];
+                }
+                let count = nodes.len().max(1) as f64;
+                Ok([sum[0] / count, sum[1] / count])
+            };
+            let (upper, lower) = (average(&nodes)?, average(&below)?);
+            let drift = [upper[0] - lower[0], upper[1] - lower[1]];
+
+            // Largest drift between nodes stacked at the same plan position
+            let mut max_drift: Option<[f64; 2]> = None;
+            for (name, node) in &nodes {
+                let stacked = below.iter()
+                    .find(|(_, b)| (b.x - node.x).abs() <= story.tolerance && (b.z - node.z).abs() <= story.tolerance);
+                if let Some((below_name, _)) = stacked {
+                    let (d, db) = (displacement(name)?, displacement(below_name)?);
+                    let m = max_drift.get_or_insert([0.0; 2]);
+                    m[0] = m[0].max((d[0] - db[0]).abs());
+                    m[1] = m[1].max((d[2] - db[2]).abs());
+                }
+            }
+            let max_drift = max_drift.unwrap_or([drift[0].abs(), drift[1].abs()]);
+
+            results.push(StoryResult {
+                story: name.clone(),
+                elevation: story.elevation,
+                height,
+                shear_x: shear[0],
+                shear_z: shear[1],
+                overturning_x: overturning[0],
+                overturning_z: overturning[1],
+                displacement_x: upper[0],
+                displacement_z: upper[1],
+                drift_x: drift[0],
+                drift_z: drift[1],
+                max_drift_x: max_drift[0],
+                max_drift_z: max_drift[1],
+            });
+            below = nodes;
+            below_elevation = story.elevation;
+            below_tolerance = story.tolerance;
+        }
+        Ok(results)
+    }
+
+    /// Resultant forces across a section cut through members, plates and quads
+    ///
+    /// Sums the nodal forces of each crossing element at its nodes on the
+    /// positive side, so the result includes the element's own span loads.
+    pub fn section_cut(&self, cut: &SectionCut, combo_name: &str) -> FEAResult<SectionCutResult> {
+        if !self.is_analyzed() {
+            return Err(FEAError::NotAnalyzed);
+        }
+        let combo = self.load_combos.get(combo_name)
+            .ok_or_else(|| FEAError::LoadCombinationNotFound(combo_name.to_string()))?;
+        let displacement = |node: &str| self.store.displacements.get(node, combo_name).ok_or(FEAError::NotAnalyzed);
+
+        // Crossing elements as (name, nodes, global nodal forces acting on the element)
+        let mut crossing: Vec<(&String, Vec<&String>, Vec<f64>)> = Vec::new();
+        let crosses = |nodes: &[&String]| -> bool {
+            let coords: Vec<[f64; 3]> = nodes.iter().map(|n| self.nodes[*n].coords()).collect();
+            let distances: Vec<f64> = coords.iter().map(|&c| cut.distance(c)).collect();
+            let positive = |d: f64| d > cut.tolerance;
+            if distances.iter().all(|&d| positive(d)) || !distances.iter().any(|&d| positive(d)) {
+                return false;
+            }
+            // Average of the points where the element's edges meet the plane
+            let mut points = Vec::new();
+            let edges = if coords.len() == 2 { 1 } else { coords.len() };
+            for k in 0..edges {
+                let l = (k + 1) % coords.len();
+                if positive(distances[k]) != positive(distances[l]) {
+                    let s = (distances[k] / (distances[k] - distances[l])).clamp(0.0, 1.0);
+                    points.push(std::array::from_fn::<f64, 3, _>(|c| coords[k][c] + (coords[l][c] - coords[k][c]) * s));
+                }
+            }
+            let count = points.len() as f64;
+            cut.contains(std::array::from_fn(|c| points.iter().map(|p| p[c]).sum::<f64>() / count))
+        };
+
+        for (name, member) in &self.members {
+            let nodes = vec![&member.i_node, &member.j_node];
+            if !crosses(&nodes) {
+                continue;
+            }
+            let forces = self.store.member_forces.get(name, combo_name).ok_or(FEAError::NotAnalyzed)?;
+            let t = math::member_transformation_matrix(
+                &self.nodes[&member.i_node].coords(),
+                &self.nodes[&member.j_node].coords(),
+                member.rotation,
+            );
+            let f_global = t.transpose() * math::Vec12::from_iterator(forces.iter().copied());
+            crossing.push((name, nodes, f_global.iter().copied().collect()));
+        }
+        for name in self.plates.keys().chain(self.quads.keys()) {
+            let (nodes, k) = self.shell_stiffness(name)
+                .ok_or_else(|| FEAError::PlateNotFound(name.clone()))?;
+            if !crosses(&nodes) {
+                continue;
+            }
+            let mut d = math::Vec24::zeros();
+            for (i, node) in nodes.iter().enumerate() {
+                let disp = displacement(node)?;
+                for dof in 0..6 {
+                    d[i * 6 + dof] = disp[dof];
+                }
+            }
+            let mut f_global = k * d;
+            if let Some((_, fer)) = self.shell_fer(name, combo)? {
+                f_global += fer;
+            }
+            crossing.push((name, nodes.to_vec(), f_global.iter().copied().collect()));
+        }
+        crossing.sort_by(|a, b| a.0.cmp(b.0));
+
+        // Sum the forces at the positive side nodes, with their moments about the origin
+        let (mut force, mut moment) = ([0.0; 3], [0.0; 3]);
+        for (_, nodes, f) in &crossing {
+            for (i, node) in nodes.iter().enumerate() {
+                let p = self.nodes[*node].coords();
+                if cut.distance(p) <= cut.tolerance {
+                    continue;
+                }
+                let r = [p[0] - cut.origin[0], p[1] - cut.origin[1], p[2] - cut.origin[2]];
+                let f = &f[i * 6..i * 6 + 6];
+                for c in 0..3 {
+                    force[c] += f[c];
+                }
+                moment[0] += f[3] + r[1] * f[2] - r[2] * f[1];
+                moment[1] += f[4] + r[2] * f[0] - r[0] * f[2];
+                moment[2] += f[5] + r[0] * f[1] - r[1] * f[0];
+            }
+        }
+        Ok(cut.resolve(force, moment, crossing.into_iter().map(|(name, _, _)| name.clone()).collect()))
+    }
+
+    /// Get the axial force in a gap or hook element (positive = tension)
+    pub fn gap_force(&self, gap_name: &str, combo_name: &str) -> FEAResult<f64> {
+        let gap = self.gaps.get(gap_name)
+            .ok_or_else(|| FEAError::InvalidInput(format!("Gap '{gap_name}' not found in model")))?;
+        gap.force(combo_name).ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Get the average foundation bearing pressure under a plate or quad
+    ///
+    /// Positive values are compression. After a nonlinear analysis,
+    /// compression-only foundations report zero where the element has lifted
+    /// off; linear analyses treat them as acting in tension too.
+    pub fn foundation_pressure(&self, element_name: &str, combo_name: &str) -> FEAResult<f64> {
+        let foundation = self.shell_foundations()
+            .into_iter()
+            .find(|f| f.element == element_name)
+            .ok_or_else(|| FEAError::InvalidInput(format!("Element '{element_name}' has no foundation")))?;
+        
+        let pressure = -foundation.modulus * self.foundation_deflection(&foundation, combo_name)?;
+        if foundation.compression_only && self.solution == Some(AnalysisType::Nonlinear) {
+            Ok(pressure.max(0.0))
+        } else {
+            Ok(pressure)
+        }
+    }
+
+    /// Get the constant stress in a tetra element
+    pub fn solid_stress(&self, tetra_name: &str, combo_name: &str) -> FEAResult<SolidStress> {
+        let tetra = self.tetras.get(tetra_name)
+            .ok_or_else(|| FEAError::SolidNotFound(tetra_name.to_string()))?;
+        let material = self.materials.get(&tetra.material)
+            .ok_or_else(|| FEAError::MaterialNotFound(tetra.material.clone()))?;
+        
+        let d = self.tetra_displacements(tetra, combo_name)?;
+        let stress = math::tet4_stress(&self.tetra_coords(tetra), material.e, material.nu, &d)
+            .ok_or_else(|| FEAError::InvalidGeometry(format!("Tetra '{tetra_name}' has zero volume")))?;
+        Ok(SolidStress::from_array(stress))
+    }
+
+    /// Get plate stress at center (works for both Plate and Quad elements)
+    pub fn plate_stress(&self, plate_name: &str, combo_name: &str) -> FEAResult<PlateStressResult> {
+        let recovery = self.shell_recovery(plate_name, combo_name, [[0.0, 0.0]])?;
+        let [(membrane, moments)] = recovery.points;
+        // von_mises reflects the top surface (membrane + bending)
+        let surface = ShellSurfaceStress::from_resultants(membrane, moments, recovery.thickness);
+        
+        Ok(PlateStressResult {
+            sx: membrane[0],
+            sy: membrane[1],
+            txy: membrane[2],
+            von_mises: surface.top.von_mises,
+            mx: moments[0],
+            my: moments[1],
+            mxy: moments[2],
+        })
+    }
+
+    /// Get top and bottom surface stresses of a plate or quad at its corners or Gauss points
+    ///
+    /// Values are in the element's local axes, in i, j, m, n order.
+    pub fn shell_surface_stresses(
+        &self,
+        plate_name: &str,
+        combo_name: &str,
+        points: StressPoints,
+    ) -> FEAResult<[ShellSurfaceStress; 4]> {
+        let recovery = self.shell_recovery(plate_name, combo_name, points.natural_coordinates())?;
+        Ok(recovery.points.map(|(membrane, moments)| {
+            ShellSurfaceStress::from_resultants(membrane, moments, recovery.thickness)
+        }))
+    }
+
+    /// Get Wood-Armer reinforcement design moments of a plate or quad at its corners or Gauss points
+    ///
+    /// Values are for reinforcement along the element's local axes, in i, j, m, n order.
+    pub fn slab_design_moments(
+        &self,
+        plate_name: &str,
+        combo_name: &str,
+        points: StressPoints,
+    ) -> FEAResult<[SlabDesignMoments; 4]> {
+        let recovery = self.shell_recovery(plate_name, combo_name, points.natural_coordinates())?;
+        Ok(recovery.points.map(|(_, [mx, my, mxy])| SlabDesignMoments::from_moments(mx, my, mxy)))
+    }
+
+    /// Get nodal-averaged surface stresses at every node of a plate or quad
+    ///
+    /// Corner stresses of the adjacent elements are rotated into the local
+    /// axes of the first adjacent element by name and averaged. The
+    /// un-averaged corner values are returned alongside, in the same axes.
+    pub fn shell_nodal_stresses(&self, combo_name: &str) -> FEAResult<BTreeMap<String, NodalShellStress>> {
+        let mut names: Vec<&String> = self.plates.keys().chain(self.quads.keys()).collect();
+        names.sort();
+        
+        // Elements are visited in name order, so the first to reach a node sets its axes
+        let mut corners = BTreeMap::new();
+        for name in names {
+            let recovery = self.shell_recovery(name, combo_name, StressPoints::Corners.natural_coordinates())?;
+            for (node, (membrane, moments)) in recovery.nodes.iter().zip(recovery.points) {
+                let stress = ShellSurfaceStress::from_resultants(membrane, moments, recovery.thickness);
+                let (axes, elements) = corners.entry(node.to_string()).or_insert((recovery.axes, Vec::new()));
+                elements.push((name.clone(), stress.transform(recovery.axes, *axes)));
+            }
+        }
+        
+        Ok(corners.into_iter()
+            .map(|(node, (_, elements))| {
+                let stresses: Vec<ShellSurfaceStress> = elements.iter().map(|(_, s)| *s).collect();
+                (node, NodalShellStress { averaged: ShellSurfaceStress::mean(&stresses), elements })
+            })
+            .collect())
+    }
+
+    /// Recover membrane stresses and moments of a plate or quad at natural coordinates
+    fn shell_recovery<const N: usize>(
+        &self,
+        plate_name: &str,
+        combo_name: &str,
+        points: [[f64; 2]; N],
+    ) -> FEAResult<ShellRecovery<'_, N>> {
+        let (nodes, material, thickness, [kx_mod, ky_mod], width, height, t, rotation) =
+            if let Some(plate) = self.plates.get(plate_name) {
+                let width = plate.width.ok_or(FEAError::NotAnalyzed)?;
+                let height = plate.height.ok_or(FEAError::NotAnalyzed)?;
+                let [i, j, n] = [&plate.i_node, &plate.j_node, &plate.n_node].map(|n| self.nodes[n].coords());
+                let t = math::plate_transformation_matrix(&i, &j, &n);
+                (
+                    [&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node],
+                    &plate.material, plate.thickness, [plate.kx_mod, plate.ky_mod], width, height, t, t,
+                )
+            } else if let Some(quad) = self.quads.get(plate_name) {
+                let (width, height, t) = self.quad_frame(quad);
+                let (flat, _) = math::quad_mean_plane(&self.quad_corners(quad));
+                let rotation = math::plate_transformation_matrix(&flat[0], &flat[1], &flat[3]);
+                (
+                    [&quad.i_node, &quad.j_node, &quad.m_node, &quad.n_node],
+                    &quad.material, quad.thickness, [quad.kx_mod, quad.ky_mod], width, height, t, rotation,
+                )
+            } else {
+                return Err(FEAError::PlateNotFound(plate_name.to_string()));
+            };
+        let material = self.materials.get(material)
+            .ok_or_else(|| FEAError::MaterialNotFound(material.clone()))?;
+        
+        // Build global displacement vector and transform to local coordinates
+        let mut d_global = math::plate::Vec24::zeros();
+        for (i, node) in nodes.iter().enumerate() {
+            let disp = self.store.displacements.get(node, combo_name)
+                .ok_or(FEAError::NotAnalyzed)?;
+            for j in 0..6 {
+                d_global[i * 6 + j] = disp[j];
+            }
+        }
+        let d_local = t * d_global;
+        
+        let points = points.map(|[r, s]| {
+            let x = width / 2.0 * (1.0 + r);
+            let y = height / 2.0 * (1.0 + s);
+            let mut membrane = math::plate_membrane_stress(
+                x, y, &d_local,
+                material.e, material.nu, thickness,
+                width, height, kx_mod, ky_mod,
+            );
+            let mut moments = math::plate_moments(
+                x, y, &d_local,
+                material.e, material.nu, thickness,
+                width, height, kx_mod, ky_mod,
+            );
+            self.remove_thermal_stress(plate_name, combo_name, thickness, &mut membrane, &mut moments);
+            (membrane, moments)
+        });
+        
+        Ok(ShellRecovery {
+            nodes,
+            thickness,
+            axes: [0, 1].map(|r| [0, 1, 2].map(|c| rotation[(r, c)])),
+            points,
+        })
+    }
+
+    /// Subtract the restrained thermal strains from a plate's recovered stresses and moments
+    fn remove_thermal_stress(
+        &self,
+        plate_name: &str,
+        combo_name: &str,
+        thickness: f64,
+        membrane: &mut [f64; 3],
+        moments: &mut [f64; 3],
+    ) {
+        let Some(combo) = self.load_combos.get(combo_name) else {
+            return;
+        };
+        let [nx, ny, mx, my] = self.plate_thermal_resultants(plate_name, combo);
+        membrane[0] -= nx / thickness;
+        membrane[1] -= ny / thickness;
+        moments[0] -= mx;
+        moments[1] -= my;
+    }
+
+    /// Get the envelope of node displacements over a load envelope
+    pub fn node_displacement_envelope(&self, node_name: &str, envelope_name: &str) -> FEAResult<Envelope<NodeDisplacement>> {
+        let values = self.envelope_combos(envelope_name)?.iter()
+            .map(|combo| self.node_displacement(node_name, combo).map(|d| [d.dx, d.dy, d.dz, d.rx, d.ry, d.rz]))
+            .collect::<FEAResult<Vec<_>>>()?;
+        
+        Envelope::from_arrays(values)
+            .map(|env| env.map(NodeDisplacement::from_array))
+            .ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Get the envelope of node reactions over a load envelope
+    pub fn node_reactions_envelope(&self, node_name: &str, envelope_name: &str) -> FEAResult<Envelope<Reactions>> {
+        let values = self.envelope_combos(envelope_name)?.iter()
+            .map(|combo| self.node_reactions(node_name, combo).map(|r| [r.fx, r.fy, r.fz, r.mx, r.my, r.mz]))
+            .collect::<FEAResult<Vec<_>>>()?;
+        
+        Envelope::from_arrays(values)
+            .map(|env| env.map(Reactions::from_array))
+            .ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Get the envelope of member forces at the i-node over a load envelope
+    pub fn member_forces_i_envelope(&self, member_name: &str, envelope_name: &str) -> FEAResult<Envelope<MemberForces>> {
+        let values = self.envelope_combos(envelope_name)?.iter()
+            .map(|combo| self.member_forces_i(member_name, combo).map(|f| f.to_array()))
+            .collect::<FEAResult<Vec<_>>>()?;
+        
+        Envelope::from_arrays(values)
+            .map(|env| env.map(MemberForces::from_array))
+            .ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Get the envelope of member forces at the j-node over a load envelope
+    pub fn member_forces_j_envelope(&self, member_name: &str, envelope_name: &str) -> FEAResult<Envelope<MemberForces>> {
+        let values = self.envelope_combos(envelope_name)?.iter()
+            .map(|combo| self.member_forces_j(member_name, combo).map(|f| f.to_array()))
+            .collect::<FEAResult<Vec<_>>>()?;
+        
+        Envelope::from_arrays(values)
+            .map(|env| env.map(MemberForces::from_array))
+            .ok_or(FEAError::NotAnalyzed)
+    }
+
+    /// Combination names of a load envelope
+    fn envelope_combos(&self, envelope_name: &str) -> FEAResult<&[String]> {
+        self.load_envelopes.get(envelope_name)
+            .map(|env| env.combos.as_slice())
+            .ok_or_else(|| FEAError::LoadEnvelopeNotFound(envelope_name.to_string()))
+    }
+
+    /// Get analysis summary
+    pub fn summary(&self, combo_name: &str) -> FEAResult<AnalysisSummary> {
+        if self.solution.is_none() {
+            return Err(FEAError::NotAnalyzed);
+        }
+        
+        let mut summary = AnalysisSummary {
+            num_nodes: self.nodes.len(),
+            num_members: self.members.len(),
+            num_plates: self.plates.len() + self.quads.len(),
+            total_dofs: self.nodes.len() * 6,
+            original_bandwidth: self.bandwidth.0,
+            bandwidth: self.bandwidth.1,
+            ..Default::default()
+        };
+        
+        // Find max displacement
+        for name in self.nodes.keys() {
+            if let Some(disp) = self.store.displacements.get(name, combo_name) {
+                let mag = (disp[0].powi(2) + disp[1].powi(2) + disp[2].powi(2)).sqrt();
+                if mag > summary.max_displacement {
+                    summary.max_displacement = mag;
+                    summary.max_disp_node = name.clone();
+                }
+            }
+        }
+        
+        // Find max reaction
+        for name in self.nodes.keys() {
+            if let Some(rxn) = self.store.reactions.get(name, combo_name) {
+                let mag = (rxn[0].powi(2) + rxn[1].powi(2) + rxn[2].powi(2)).sqrt();
+                if mag > summary.max_reaction {
+                    summary.max_reaction = mag;
+                    summary.max_reaction_node = name.clone();
+                }
+            }
+        }
+        
+        // Find max member forces
+        for name in self.members.keys() {
+            if let Some(forces) = self.store.member_forces.get(name, combo_name) {
+                let axial = forces[0].abs();
+                if axial > summary.max_axial {
+                    summary.max_axial = axial;
+                    summary.max_axial_member = name.clone();
+                }
+                
+                let moment = forces[4].abs().max(forces[5].abs())
+                    .max(forces[10].abs()).max(forces[11].abs());
+                if moment > summary.max_moment {
+                    summary.max_moment = moment;
+                    summary.max_moment_member = name.clone();
+                }
+            }
+        }
+        
+        // Count free DOFs
+        let mut restrained = 0;
+        for support in self.supports.values() {
+            restrained += support.num_restrained();
+        }
+        summary.free_dofs = summary.total_dofs - restrained;
+        summary.uplift_nodes = self.uplift.get(combo_name).cloned().unwrap_or_default();
+        summary.slack_members = self.slack_members.get(combo_name).cloned().unwrap_or_default();
+        
+        Ok(summary)
+    }
+
+    /// Shape quality metrics of a quad
+    pub fn quad_quality(&self, quad_name: &str) -> FEAResult<QuadQuality> {
+        let quad = self.quads.get(quad_name)
+            .ok_or_else(|| FEAError::PlateNotFound(quad_name.to_string()))?;
+        Ok(QuadQuality::from_corners(&self.quad_corners(quad)))
+    }
+
+    /// Model quality warnings raised while preparing the last analysis
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Snapshot all results into a queryable [`ResultsDb`]
+    pub fn results(&self) -> FEAResult<ResultsDb> {
+        ResultsDb::from_model(self)
+    }
+
+    /// Node and member results of the static analyses, by load combination
+    pub fn result_store(&self) -> &ResultStore {
+        &self.store
+    }
+
+    /// Check if model has been analyzed
+    pub fn is_analyzed(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    /// Get the analysis type used
+    pub fn solution_type(&self) -> Option<AnalysisType> {
+        self.solution
+    }
+
+    /// Express a value given in another unit system in the model's units
+    ///
+    /// e.g. `model.from_units(NodeLoad::fy(-10.0, "Live"), &UnitSystem::KN_M)`
+    /// for a 10 kN load, whatever the model's own units.
+    pub fn from_units<T: ConvertUnits>(&self, value: T, units: &UnitSystem) -> T {
+        value.convert_units(units, &self.units)
+    }
+
+    /// Express a value in the model's units, such as a result, in another unit system
+    pub fn to_units<T: ConvertUnits>(&self, value: T, units: &UnitSystem) -> T {
+        value.convert_units(&self.units, units)
+    }
+
+    /// Get all load combination names
+    pub fn combo_names(&self) -> Vec<String> {
+        self.load_combos.keys().cloned().collect()
+    }
+
+    /// Get all load case names
+    pub fn load_cases(&self) -> Vec<String> {
+        let mut cases: Vec<String> = Vec::new();
+        
+        for loads in self.node_loads.values() {
+            for load in loads {
+                if !cases.contains(&load.case) {
+                    cases.push(load.case.clone());
+                }
+            }
+        }
+        
+        for loads in self.member_dist_loads.values() {
+            for load in loads {
+                if !cases.contains(&load.case) {
+                    cases.push(load.case.clone());
+                }
+            }
+        }
+        
+        for loads in self.member_prestress_loads.values() {
+            for load in loads {
+                if !cases.contains(&load.case) {
+                    cases.push(load.case.clone());
+                }
+            }
+        }
+        
+        cases.sort();
+        cases
+    }
+}
+
+/// Area of the triangle with corners a, b and c
+fn triangle_area(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> f64 {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    0.5 * (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt()
+}
+
+/// Move a map entry to a new key, returning whether it existed
+fn move_entry<T>(map: &mut BTreeMap<String, T>, old: &str, new: &str) -> bool {
+    match map.remove(old) {
+        Some(value) => {
+            map.insert(new.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Replace a name in a list of names
+fn rename_in(names: &mut [String], old: &str, new: &str) {
+    for name in names.iter_mut().filter(|n| *n == old) {
+        *name = new.to_string();
+    }
+}
+
+/// Distance along a line to a point lying strictly between its ends
+///
+/// Points within 1e-6 times the line length of the line count as on it.
+fn interior_station(start: &math::Vec3, axis: &math::Vec3, length: f64, point: &math::Vec3) -> Option<f64> {
+    let tol = 1e-6 * length;
+    let offset = point - start;
+    let x = offset.dot(axis);
+    (x > tol && x < length - tol && (offset - axis * x).norm() <= tol).then_some(x)
+}
+
+/// Crossing point of two lines given by start points and unit axes
+///
+/// Returns the distances along each line to the crossing and the point, or
+/// `None` if the lines are parallel or miss each other by more than `tol`.
+fn line_crossing(
+    a: (&math::Vec3, &math::Vec3),
+    b: (&math::Vec3, &math::Vec3),
+    tol: f64,
+) -> Option<(f64, f64, math::Vec3)> {
+    let ((pa, da), (pb, db)) = (a, b);
+    let w0 = pa - pb;
+    let cos = da.dot(db);
+    let denom = 1.0 - cos * cos;
+    if denom < 1e-12 {
+        return None;
+    }
+    let (d, e) = (da.dot(&w0), db.dot(&w0));
+    let s = (cos * e - d) / denom;
+    let t = (e - cos * d) / denom;
+    let (ca, cb) = (pa + da * s, pb + db * t);
+    ((ca - cb).norm() <= tol).then(|| (s, t, (ca + cb) / 2.0))
+}
+
+/// Fixed end reactions of a physical member's bow on the segment from `start` to `end`
+///
+/// The segment carries the bow's lateral load `w = -N v0''` over its length
+/// and, where it ends at an end of the physical member, the transverse
+/// component `N v0'` of the axial force there that balances that load. Over
+/// a single segment this is [`math::fer_imperfection`].
+fn segment_imperfection_fer(
+    imperfection: &Imperfection,
+    compression: f64,
+    start: f64,
+    end: f64,
+    length: f64,
+) -> math::Vec12 {
+    let mut fer = math::Vec12::zeros();
+    let span = end - start;
+    let offsets = [(1, imperfection.y), (2, imperfection.z)];
+    
+    // Five-point Gauss rule: the sine load is smooth over a segment
+    let (a, b) = (0.538_469_310_105_683_1, 0.906_179_845_938_664);
+    let (wa, wb) = (0.478_628_670_499_366_5, 0.236_926_885_056_189_1);
+    for (xi, weight) in [(-b, wb), (-a, wa), (0.0, 128.0 / 225.0), (a, wa), (b, wb)] {
+        let x = span * (xi + 1.0) / 2.0;
+        let (_, curvature) = imperfection.profile(start + x, length);
+        for (direction, offset) in offsets {
+            let w = -compression * offset * curvature;
+            fer += math::fer_point_load(w * weight * span / 2.0, x, span, direction);
+        }
+    }
+    
+    let tol = 1e-9 * length;
+    for (x, cut, at, sign) in [(0.0, start, 0.0, -1.0), (length, end, span, 1.0)] {
+        if (x - cut).abs() <= tol {
+            let (slope, _) = imperfection.profile(x, length);
+            for (direction, offset) in offsets {
+                fer += math::fer_point_load(sign * compression * offset * slope, at, span, direction);
+            }
+        }
+    }
+    fer
+}
+
+/// Internal forces at a distance `x` along an analysis member, assuming shear varies linearly
+fn segment_forces(member: &Member, forces: &[f64; 12], x: f64) -> FEAResult<MemberForces> {
+    let l = member.length.ok_or(FEAError::NotAnalyzed)?;
+    let s = (x / l).clamp(0.0, 1.0);
+    
+    // End values in the diagram convention: (i, j)
+    let axial = (-forces[0], forces[6]);
+    let shear_y = (-forces[1], forces[7]);
+    let shear_z = (-forces[2], forces[8]);
+    let torsion = (-forces[3], forces[9]);
+    let lerp = |(i, j): (f64, f64)| i + (j - i) * s;
+    // Integrate the linear shear: dMz/dx = Vy and dMy/dx = -Vz
+    let integral = |(i, j): (f64, f64)| i * x + (j - i) * x * s / 2.0;
+    
+    Ok(MemberForces {
+        axial: lerp(axial),
+        shear_y: lerp(shear_y),
+        shear_z: lerp(shear_z),
+        torsion: lerp(torsion),
+        moment_y: forces[4] - integral(shear_z),
+        moment_z: forces[5] + integral(shear_y),
+    })
+}
+
+/// Local (y, z) displacement at a distance `x` from an analysed member's i-node
+///
+/// The chord between the end displacements plus the deflection from the
+/// curvature of the [`segment_forces`] moment diagram, which is zero at both
+/// ends: v(x) = -(l - x) / l ∫₀ˣ s κ ds - x / l ∫ₓˡ (l - s) κ ds. The moments
+/// are quadratic, so Simpson's rule on each side of `x` is exact.
+fn segment_deflection(
+    member: &Member,
+    section: &Section,
+    material: &Material,
+    forces: &[f64; 12],
+    d: &[f64; 12],
+    x: f64,
+) -> FEAResult<[f64; 2]> {
+    let l = member.length.ok_or(FEAError::NotAnalyzed)?;
+    let x = x.clamp(0.0, l);
+    let s = x / l;
+
+    let curvature = |x: f64| -> FEAResult<[f64; 2]> {
+        let forces = segment_forces(member, forces, x)?;
+        Ok([-forces.moment_z / (material.e * section.iz), forces.moment_y / (material.e * section.iy)])
+    };
+    let (k_left, k_x, k_right) = (curvature(x / 2.0)?, curvature(x)?, curvature((x + l) / 2.0)?);
+    let bow = |k: usize| {
+        -x * (l - x) / (6.0 * l) * (x * (2.0 * k_left[k] + k_x[k]) + (l - x) * (k_x[k] + 2.0 * k_right[k]))
+    };
+
+    Ok([
+        d[1] + (d[7] - d[1]) * s + bow(0),
+        d[2] + (d[8] - d[2]) * s + bow(1),
+    ])
+}
+
+/// Winkler foundation stiffness of one plate or quad
+struct ShellFoundation {
+    element: String,
+    nodes: [String; 4],
+    /// Foundation stiffness in global coordinates
+    k: math::Mat24,
+    /// Element local-to-global transformation
+    t: math::Mat24,
+    modulus: f64,
+    compression_only: bool,
+}
+
+/// Membrane stresses and moments of a plate or quad recovered at a set of points
+struct ShellRecovery<'a, const N: usize> {
+    nodes: [&'a String; 4],
+    thickness: f64,
+    /// Local x and y axes in global coordinates
+    axes: [[f64; 3]; 2],
+    /// Membrane stresses [sx, sy, txy] and moments [mx, my, mxy] at each point
+    points: [([f64; 3], [f64; 3]); N],
+}
+
+/// Constant data for one member during a co-rotational analysis
+struct CorotationalMember {
+    name: String,
+    i_dof: usize,
+    j_dof: usize,
+    /// Undeformed local frame (columns are the local axes)
+    frame_0: math::Mat3,
+    length_0: f64,
+    /// Local stiffness with end releases applied
+    k_local: math::Mat12,
+    k_uncondensed: math::Mat12,
+    releases: [bool; 12],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{SolverBackend, EXTREME_STIFFNESS_RATIO};
+    use crate::elements::{ElasticFoundation, Gap, Imperfection, Link, MemberReleases, OneWay};
+    use crate::loads::{PlateThermalLoad, PrestressLoad};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_simple_cantilever() {
+        let mut model = FEModel::new();
+        
+        // Add material
+        model.add_material("Steel", Material::steel()).unwrap();
+        
+        // Add section (arbitrary rectangular)
+        model.add_section("Section1", Section::rectangular(0.3, 0.5)).unwrap();
+        
+        // Add nodes (10m cantilever)
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(10.0, 0.0, 0.0)).unwrap();
+        
+        // Add member
+        model.add_member("M1", Member::new("N1", "N2", "Steel", "Section1")).unwrap();
+        
+        // Add support (fixed at N1)
+        model.add_support("N1", Support::fixed()).unwrap();
+        
+        // Add load (10 kN downward at N2)
+        model.add_node_load("N2", NodeLoad::fy(-10000.0, "Case 1")).unwrap();
+        
+        // Analyze
+        model.analyze_linear().unwrap();
+        
+        // Check results
+        let disp = model.node_displacement("N2", "Combo 1").unwrap();
+        
+        // Tip deflection should be negative (downward)
+        assert!(disp.dy < 0.0, "Expected negative Y displacement");
+        
+        // Check reactions
+        let rxn = model.node_reactions("N1", "Combo 1").unwrap();
+        assert_relative_eq!(rxn.fy, 10000.0, epsilon = 1.0); // Should equal applied load
+    }
+
+    #[test]
+    fn test_pattern_live_load_envelope() {
+        // Three equal continuous spans: the hogging moment over an interior
+        // support peaks with the two adjacent spans loaded (0.1167wL^2),
+        // exceeding the all-spans-loaded value (0.100wL^2).
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.3, 0.5)).unwrap();
+        
+        let (span, w) = (6.0, 10_000.0);
+        for i in 0..4 {
+            model.add_node(&format!("N{i}"), Node::new(span * i as f64, 0.0, 0.0)).unwrap();
+        }
+        let mut spans = Vec::new();
+        for i in 0..3 {
+            let name = format!("M{i}");
+            model.add_member(&name, Member::new(&format!("N{i}"), &format!("N{}", i + 1), "Steel", "Beam")).unwrap();
+            model.add_member_dist_load(&name, DistributedLoad::uniform_downward(w, "Live")).unwrap();
+            spans.push(vec![name]);
+        }
+        model.add_support("N0", Support { dx: true, dy: true, dz: true, rx: true, ..Default::default() }).unwrap();
+        for i in 1..4 {
+            model.add_support(&format!("N{i}"), Support { dy: true, dz: true, ..Default::default() }).unwrap();
+        }
+        
+        let combo = LoadCombination::single("Service", "Live");
+        model.add_load_combo(combo.clone()).unwrap();
+        let envelope = model.add_pattern_live_loads("Live", &spans, &combo).unwrap();
+        assert_eq!(envelope.combos.len(), 5);
+        
+        model.analyze_linear().unwrap();
+        
+        let all_loaded = model.member_forces_j("M0", "Service").unwrap().moment_z.abs();
+        assert_relative_eq!(all_loaded, 0.1 * w * span * span, max_relative = 1e-3);
+        
+        let env = model.member_forces_j_envelope("M0", &envelope.name).unwrap();
+        let peak = env.max.moment_z.abs().max(env.min.moment_z.abs());
+        assert_relative_eq!(peak, 7.0 / 60.0 * w * span * span, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn test_hydrostatic_wall_panel_load() {
+        // Vertical 2m x 3m panel in the XY plane with water up to Y = 2m:
+        // the nodal loads add up to the fluid resultant gamma*h^2/2*b
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        for (name, x, y) in [("A", 0.0, 0.0), ("B", 2.0, 0.0), ("C", 2.0, 3.0), ("D", 0.0, 3.0)] {
+            model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+        }
+        model.add_plate("Wall", Plate::new("A", "B", "C", "D", 0.2, "Concrete")).unwrap();
+        model.add_plate_load("Wall", PlateLoad::hydrostatic(2.0, 9810.0, "Case 1")).unwrap();
+        
+        model.prepare_model().unwrap();
+        let (_, dof_map) = model.build_global_stiffness(&CancelToken::new()).unwrap();
+        let p = model.build_load_vector(&LoadCombination::default(), &dof_map).unwrap();
+        
+        let total: f64 = dof_map.values().map(|&dof| p[dof + 2]).sum();
+        assert_relative_eq!(total, 9810.0 * 2.0 * 2.0, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn test_plate_point_load_distribution() {
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        for (name, x, z) in [("A", 0.0, 0.0), ("B", 4.0, 0.0), ("C", 4.0, 2.0), ("D", 0.0, 2.0)] {
+            model.add_node(name, Node::new(x, 0.0, z)).unwrap();
+        }
+        model.add_plate("Slab", Plate::new("A", "B", "C", "D", 0.2, "Concrete")).unwrap();
+        
+        // A load over a corner goes entirely to that node; one at the centre
+        // is shared equally between the four corners
+        model.add_plate_point_load("Slab", PlatePointLoad::new(-8000.0, 4.0, 0.0, "Corner")).unwrap();
+        model.add_plate_point_load("Slab", PlatePointLoad::new(-8000.0, 2.0, 1.0, "Centre")).unwrap();
+        
+        model.prepare_model().unwrap();
+        let (_, dof_map) = model.build_global_stiffness(&CancelToken::new()).unwrap();
+        let axis = |p: &FEVec, node: &str| (0..3).map(|a| p[dof_map[node] + a].abs()).sum::<f64>();
+        
+        let corner = model.build_load_vector(&LoadCombination::single("C", "Corner"), &dof_map).unwrap();
+        assert_relative_eq!(axis(&corner, "B"), 8000.0, epsilon = 1e-6);
+        assert_relative_eq!(axis(&corner, "A") + axis(&corner, "C") + axis(&corner, "D"), 0.0, epsilon = 1e-6);
+        
+        let centre = model.build_load_vector(&LoadCombination::single("C", "Centre"), &dof_map).unwrap();
+        for node in ["A", "B", "C", "D"] {
+            assert_relative_eq!(axis(&centre, node), 2000.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_distributed_torque_cantilever() {
+        // Uniform torque t on a cantilever: base reaction -tL, tip twist tL^2/(2GJ)
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Box", Section::box_section(0.2, 0.3, 0.01)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(4.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("N1", "N2", "Steel", "Box")).unwrap();
+        model.add_support("N1", Support::fixed()).unwrap();
+        
+        let (t, length) = (1500.0, 4.0);
+        model.add_member_dist_load("M1", DistributedLoad::torque(t, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        let gj = Material::steel().g * Section::box_section(0.2, 0.3, 0.01).j;
+        let tip = model.node_displacement("N2", "Combo 1").unwrap();
+        assert_relative_eq!(tip.rx, t * length * length / (2.0 * gj), max_relative = 1e-9);
+        
+        let rxn = model.node_reactions("N1", "Combo 1").unwrap();
+        assert_relative_eq!(rxn.mx, -t * length, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_rigid_link_eccentric_load() {
+        // A force on a rigidly linked offset node equals the force plus its
+        // eccentricity moment applied at the master
+        let build = || {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Bar", Section::rectangular(0.2, 0.3)).unwrap();
+            model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+            model.add_node("N2", Node::new(4.0, 0.0, 0.0)).unwrap();
+            model.add_node("N3", Node::new(4.0, 1.0, 0.0)).unwrap();
+            model.add_member("M1", Member::new("N1", "N2", "Steel", "Bar")).unwrap();
+            model.add_support("N1", Support::fixed()).unwrap();
+            model
+        };
+        
+        let mut linked = build();
+        linked.add_rigid_link("L1", RigidLink::new("N2", "N3")).unwrap();
+        linked.add_node_load("N3", NodeLoad::fx(10_000.0, "Case 1")).unwrap();
+        linked.analyze_linear().unwrap();
+        
+        let mut direct = build();
+        direct.add_support("N3", Support::fixed()).unwrap();
+        direct.add_node_load("N2", NodeLoad::new(10_000.0, 0.0, 0.0, 0.0, 0.0, -10_000.0, "Case 1")).unwrap();
+        direct.analyze_linear().unwrap();
+        
+        let d_linked = linked.node_displacement("N2", "Combo 1").unwrap();
+        let d_direct = direct.node_displacement("N2", "Combo 1").unwrap();
+        assert_relative_eq!(d_linked.dx, d_direct.dx, max_relative = 1e-9);
+        assert_relative_eq!(d_linked.rz, d_direct.rz, max_relative = 1e-9);
+        
+        let slave = linked.node_displacement("N3", "Combo 1").unwrap();
+        assert_relative_eq!(slave.dx, d_linked.dx - d_linked.rz, max_relative = 1e-9);
+        assert_relative_eq!(slave.dy, d_linked.dy, max_relative = 1e-9);
+        
+        let rxn = linked.node_reactions("N1", "Combo 1").unwrap();
+        assert_relative_eq!(rxn.fx, -10_000.0, max_relative = 1e-9);
+        assert_relative_eq!(rxn.mz, 10_000.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_constraints_match_dense_transformation() {
+        // A rigid link whose master sits on a skewed support: the row-wise
+        // transformation equals the dense C^T K C, C^T p and C d
+        let mut model = FEModel::new();
+        for (name, x, y) in [("N1", 0.0, 0.0), ("N2", 4.0, 0.0), ("N3", 4.0, 1.0), ("N4", 6.0, 1.0)] {
+            model.add_node(name, Node::new(x, y, 0.5 * x)).unwrap();
+        }
+        let (c, s) = (0.6, 0.8);
+        model.add_support("N2", Support::pinned().with_orientation([[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]])).unwrap();
+        model.add_rigid_link("L1", RigidLink::new("N2", "N3")).unwrap();
+        model.prepare_model().unwrap();
+        let dof_map = model.build_dof_map();
+        let constraints = model.build_constraints(&dof_map).unwrap().unwrap();
+        
+        let n = 24;
+        let mut dense = Mat::identity(n, n);
+        for (&r, row) in &constraints.rows {
+            dense.row_mut(r).fill(0.0);
+            for &(j, value) in row {
+                dense[(r, j)] = value;
+            }
+        }
+        let a = Mat::from_fn(n, n, |i, j| ((i * 7 + j * 3) % 11) as f64 - 5.0);
+        let k = &a + a.transpose();
+        let p = FEVec::from_fn(n, |i, _| (i % 5) as f64 - 2.0);
+        
+        assert_relative_eq!(constraints.transform_stiffness(&k), dense.tr_mul(&k) * &dense, epsilon = 1e-12);
+        assert_relative_eq!(constraints.transform_load(&p), dense.tr_mul(&p), epsilon = 1e-12);
+        assert_relative_eq!(constraints.recover(&p), &dense * &p, epsilon = 1e-12);
+        // The slave follows the master's rotated DOFs, not its own
+        assert!(constraints.rows[&dof_map["N3"]].iter().all(|&(j, _)| j / 6 == dof_map["N2"] / 6));
+    }
+
+    #[test]
+    fn test_beam_tied_to_slab_is_stiffer() {
+        // 6 m beam 0.3 m below a 1 m wide slab strip
+        let build = |tied: bool| {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Beam", Section::rectangular(0.1, 0.3)).unwrap();
+            for i in 0..=6 {
+                let x = i as f64;
+                model.add_node(&format!("B{i}"), Node::new(x, 0.0, 0.0)).unwrap();
+                model.add_node(&format!("S{i}"), Node::new(x, 0.3, 0.0)).unwrap();
+                model.add_node(&format!("E{i}"), Node::new(x, 0.3, 1.0)).unwrap();
+            }
+            let mut members = Vec::new();
+            let mut plates = Vec::new();
+            for i in 0..6 {
+                let (a, b) = (i, i + 1);
+                members.push(format!("M{i}"));
+                plates.push(format!("P{i}"));
+                model.add_member(&members[i], Member::new(&format!("B{a}"), &format!("B{b}"), "Steel", "Beam")).unwrap();
+                model.add_plate(&plates[i], Plate::new(&format!("S{a}"), &format!("S{b}"), &format!("E{b}"), &format!("E{a}"), 0.1, "Steel")).unwrap();
+                model.add_node_load(&format!("B{b}"), NodeLoad::fy(-10_000.0, "Case 1")).unwrap();
+            }
+            // Tied beam ends become slaves, so the bearings go on the slab edge
+            let (left, right) = if tied { ("S0", "S6") } else { ("B0", "B6") };
+            model.add_support(left, Support::pinned()).unwrap();
+            model.add_support(right, Support { dy: true, dz: true, rx: true, ..Default::default() }).unwrap();
+            for i in 0..=6 {
+                model.add_support(&format!("E{i}"), Support { dx: true, dz: true, ..Default::default() }).unwrap();
+                if !tied && i > 0 && i < 6 {
+                    model.add_support(&format!("S{i}"), Support { dx: true, dy: true, dz: true, ..Default::default() }).unwrap();
+                }
+            }
+            if !tied {
+                model.add_support("S0", Support::fixed()).unwrap();
+                model.add_support("S6", Support::fixed()).unwrap();
+            }
+            if tied {
+                let members: Vec<&str> = members.iter().map(String::as_str).collect();
+                let plates: Vec<&str> = plates.iter().map(String::as_str).collect();
+                let links = model.tie_members_to_shell(&members, &plates, 0.5).unwrap();
+                assert_eq!(links.len(), 7);
+            }
+            model.analyze_linear().unwrap();
+            model.node_displacement("B3", "Combo 1").unwrap().dy
+        };
+        
+        let (bare, composite) = (build(false), build(true));
+        assert!(bare < 0.0 && composite < 0.0);
+        assert!(composite.abs() < 0.5 * bare.abs(), "composite {composite} vs bare {bare}");
+    }
+
+    #[test]
+    fn test_tetra_cube_uniaxial_tension() {
+        // Unit cube split into 6 tetras along the 000-111 diagonal
+        let (e, nu, p) = (200e9, 0.3, 1.0e6);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::isotropic(e, nu, 7850.0)).unwrap();
+        for x in 0..2 {
+            for y in 0..2 {
+                for z in 0..2 {
+                    model.add_node(&format!("N{x}{y}{z}"), Node::new(x as f64, y as f64, z as f64)).unwrap();
+                }
+            }
+        }
+        for (i, axes) in [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]].iter().enumerate() {
+            let mut corner = [0; 3];
+            let mut names = vec!["N000".to_string()];
+            for &axis in axes {
+                corner[axis] = 1;
+                names.push(format!("N{}{}{}", corner[0], corner[1], corner[2]));
+            }
+            model.add_tetra(&format!("T{i}"), Tetra::new(&names[0], &names[1], &names[2], &names[3], "Steel")).unwrap();
+        }
+        
+        // Roller supports on the x = 0 face, just enough to stop rigid body motion
+        let roller = |dy: bool, dz: bool| Support { dx: true, dy, dz, ..Default::default() };
+        model.add_support("N000", roller(true, true)).unwrap();
+        model.add_support("N010", roller(false, true)).unwrap();
+        model.add_support("N001", roller(true, false)).unwrap();
+        model.add_support("N011", roller(false, false)).unwrap();
+        
+        // Consistent nodal loads for a uniform traction on the x = 1 face
+        for (node, share) in [("N100", 1.0 / 3.0), ("N111", 1.0 / 3.0), ("N110", 1.0 / 6.0), ("N101", 1.0 / 6.0)] {
+            model.add_node_load(node, NodeLoad::fx(p * share, "Case 1")).unwrap();
+        }
+        model.analyze_linear().unwrap();
+        
+        let d = model.node_displacement("N111", "Combo 1").unwrap();
+        assert_relative_eq!(d.dx, p / e, max_relative = 1e-9);
+        assert_relative_eq!(d.dy, -nu * p / e, max_relative = 1e-9);
+        
+        let stress = model.solid_stress("T3", "Combo 1").unwrap();
+        assert_relative_eq!(stress.sx, p, max_relative = 1e-9);
+        assert_relative_eq!(stress.von_mises, p, max_relative = 1e-9);
+        
+        let total: f64 = ["N000", "N010", "N001", "N011"].iter()
+            .map(|n| model.node_reactions(n, "Combo 1").unwrap().fx)
+            .sum();
+        assert_relative_eq!(total, -p, max_relative = 1e-9);
+        
+        // Flattening a tetra after it was added is an error, not a panic
+        model.nodes.get_mut("N111").unwrap().z = 0.0;
+        assert!(matches!(model.analyze_linear(), Err(FEAError::InvalidGeometry(_))));
+    }
+
+    /// Horizontal strip of 1 m x 1 m plates along X with local z = global +Y,
+    /// restrained in-plane so only the foundation resists vertical motion
+    fn plate_strip_on_foundation(count: usize, foundation: ElasticFoundation) -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        for i in 0..=count {
+            let x = i as f64;
+            model.add_node(&format!("A{i}"), Node::new(x, 0.0, 0.0)).unwrap();
+            model.add_node(&format!("B{i}"), Node::new(x, 0.0, -1.0)).unwrap();
+            for node in [format!("A{i}"), format!("B{i}")] {
+                model.add_support(&node, Support { dx: true, dz: true, ry: true, ..Default::default() }).unwrap();
+            }
+        }
+        for i in 0..count {
+            let plate = Plate::new(&format!("A{i}"), &format!("A{}", i + 1), &format!("B{}", i + 1), &format!("B{i}"), 0.3, "Concrete")
+                .with_foundation(foundation);
+            model.add_plate(&format!("P{i}"), plate).unwrap();
+        }
+        model
+    }
+
+    #[test]
+    fn test_plate_on_winkler_foundation_uniform_settlement() {
+        let (k, q) = (50e6, 20e3);
+        let mut model = plate_strip_on_foundation(2, ElasticFoundation::new(k));
+        for plate in ["P0", "P1"] {
+            model.add_plate_load(plate, PlateLoad::downward(q, "Case 1")).unwrap();
+        }
+        model.analyze_linear().unwrap();
+        
+        for node in ["A0", "A1", "B2"] {
+            assert_relative_eq!(model.node_displacement(node, "Combo 1").unwrap().dy, -q / k, max_relative = 1e-6);
+        }
+        assert_relative_eq!(model.foundation_pressure("P1", "Combo 1").unwrap(), q, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn test_compression_only_foundation_uplift() {
+        // Heavy load on the left end, uplift on the right end
+        let analyze = |foundation: ElasticFoundation| {
+            let mut model = plate_strip_on_foundation(2, foundation);
+            for (node, fy) in [("A0", -100e3), ("B0", -100e3), ("A2", 40e3), ("B2", 40e3)] {
+                model.add_node_load(node, NodeLoad::fy(fy, "Case 1")).unwrap();
+            }
+            model.analyze_nonlinear().unwrap();
+            let dy = model.node_displacement("A0", "Combo 1").unwrap().dy;
+            let nonlinear = (dy, model.foundation_pressure("P1", "Combo 1").unwrap());
+            model.analyze_linear().unwrap();
+            let linear = (model.node_displacement("A0", "Combo 1").unwrap().dy, model.foundation_pressure("P1", "Combo 1").unwrap());
+            (nonlinear, linear)
+        };
+        
+        let ((dy_bilateral, p_bilateral), _) = analyze(ElasticFoundation::new(50e6));
+        let ((dy_uplift, p_uplift), linear) = analyze(ElasticFoundation::new(50e6).compression_only());
+        assert!(p_bilateral < 0.0);
+        assert_eq!(p_uplift, 0.0);
+        assert!(dy_uplift < dy_bilateral);
+        // Linear analysis keeps the springs in tension as well
+        assert_eq!(linear, (dy_bilateral, p_bilateral));
+    }
+
+    #[test]
+    fn test_beam_on_elastic_foundation_point_load() {
+        // Long beam with a central point load: w0 = P beta / (2 k), beta = (k / 4EI)^(1/4)
+        let (k, p, n) = (20e6, 100e3, 80);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.3, 0.5)).unwrap();
+        for i in 0..=n {
+            let node = format!("N{i}");
+            model.add_node(&node, Node::new(i as f64 * 0.5, 0.0, 0.0)).unwrap();
+            model.add_support(&node, Support { dx: i == n / 2, dz: true, rx: true, ry: true, ..Default::default() }).unwrap();
+        }
+        for i in 0..n {
+            let member = Member::new(&format!("N{i}"), &format!("N{}", i + 1), "Steel", "Beam")
+                .with_foundation_modulus(k);
+            model.add_member(&format!("M{i}"), member).unwrap();
+        }
+        model.add_node_load(&format!("N{}", n / 2), NodeLoad::fy(-p, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        let ei = model.materials["Steel"].e * model.sections["Beam"].iz;
+        let beta = (k / (4.0 * ei)).powf(0.25);
+        let w0 = model.node_displacement(&format!("N{}", n / 2), "Combo 1").unwrap().dy;
+        assert_relative_eq!(w0, -p * beta / (2.0 * k), max_relative = 0.01);
+        
+        // Moment under the load: M0 = P / (4 beta)
+        let m0 = model.member_forces_j(&format!("M{}", n / 2 - 1), "Combo 1").unwrap().moment_z;
+        assert_relative_eq!(m0.abs(), p / (4.0 * beta), max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_inclined_roller_support() {
+        // Simply supported beam whose right bearing rolls on a 30 degree incline
+        let (p, angle) = (10_000.0, 30f64.to_radians());
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("C", Node::new(2.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(4.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "C", "Steel", "Beam")).unwrap();
+        model.add_member("M2", Member::new("C", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        let roller = Support::with_restraints(false, true, true, false, false, false).with_skew_angles(0.0, 0.0, angle);
+        model.add_support("B", roller).unwrap();
+        model.add_node_load("C", NodeLoad::fy(-p, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        // The bearing reaction acts normal to the incline
+        let rb = model.node_reactions("B", "Combo 1").unwrap();
+        assert_relative_eq!(rb.fy, p / 2.0 / angle.cos(), max_relative = 1e-9);
+        assert_eq!(rb.fx, 0.0);
+        let ra = model.node_reactions("A", "Combo 1").unwrap();
+        assert_relative_eq!(ra.fx, p / 2.0 * angle.tan(), max_relative = 1e-9);
+        assert_relative_eq!(ra.fy, p / 2.0, max_relative = 1e-9);
+        
+        // The bearing slides along the incline
+        let db = model.node_displacement("B", "Combo 1").unwrap();
+        assert!(db.dx.abs() > 0.0);
+        assert_relative_eq!(db.dy, db.dx * angle.tan(), max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_one_way_support_uplift() {
+        // Two-span beam loaded in the second span only: the end support of the
+        // unloaded span would have to hold the beam down
+        let p = 10_000.0;
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for (name, x) in [("A", 0.0), ("B", 4.0), ("D", 6.0), ("C", 8.0)] {
+            model.add_node(name, Node::new(x, 0.0, 0.0)).unwrap();
+        }
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_member("M2", Member::new("B", "D", "Steel", "Beam")).unwrap();
+        model.add_member("M3", Member::new("D", "C", "Steel", "Beam")).unwrap();
+        let bearing = |dx: bool, rx: bool| {
+            Support::with_restraints(dx, false, true, rx, false, false).with_one_way(1, OneWay::Positive)
+        };
+        model.add_support("A", bearing(false, false)).unwrap();
+        model.add_support("B", bearing(true, true)).unwrap();
+        model.add_support("C", bearing(false, false)).unwrap();
+        model.add_node_load("D", NodeLoad::fy(-p, "Case 1")).unwrap();
+        
+        model.analyze_linear().unwrap();
+        assert_relative_eq!(model.node_reactions("A", "Combo 1").unwrap().fy, -3.0 * p / 32.0, max_relative = 1e-9);
+        
+        model.analyze_nonlinear().unwrap();
+        assert_eq!(model.node_reactions("A", "Combo 1").unwrap().fy, 0.0);
+        assert_relative_eq!(model.node_reactions("B", "Combo 1").unwrap().fy, p / 2.0, max_relative = 1e-9);
+        assert!(model.node_displacement("A", "Combo 1").unwrap().dy > 0.0);
+        assert_eq!(model.summary("Combo 1").unwrap().uplift_nodes, vec!["A".to_string()]);
+    }
+
+    #[test]
+    fn test_tension_only_braces() {
+        // Pinned portal with crossed tension-only braces: under a lateral load
+        // the compression brace goes slack and the frame matches one with only
+        // the tension brace
+        let build = |braces: &[(&str, &str, &str)], tension_only: bool| {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Frame", Section::rectangular(0.2, 0.3)).unwrap();
+            model.add_section("Brace", Section::new(1e-3, 1e-9, 1e-9, 1e-9)).unwrap();
+            for (name, x, y) in [("A", 0.0, 0.0), ("B", 0.0, 3.0), ("C", 4.0, 3.0), ("D", 4.0, 0.0)] {
+                model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+            }
+            for (name, i, j) in [("Left", "A", "B"), ("Beam", "B", "C"), ("Right", "D", "C")] {
+                model.add_member(name, Member::new(i, j, "Steel", "Frame").with_releases(MemberReleases::pin_both())).unwrap();
+            }
+            for &(name, i, j) in braces {
+                let brace = Member::new(i, j, "Steel", "Brace").with_releases(MemberReleases::pin_both());
+                model.add_member(name, if tension_only { brace.tension_only() } else { brace }).unwrap();
+            }
+            // Every member end is pinned, so the joint rotations are held
+            for node in ["A", "D"] {
+                model.add_support(node, Support::fixed()).unwrap();
+            }
+            for node in ["B", "C"] {
+                model.add_support(node, Support::with_restraints(false, false, true, true, true, true)).unwrap();
+            }
+            model.add_node_load("B", NodeLoad::fx(10e3, "Case 1")).unwrap();
+            model
+        };
+        let crossed = [("AC", "A", "C"), ("DB", "D", "B")];
+
+        let mut braced = build(&crossed, true);
+        braced.analyze_nonlinear().unwrap();
+        let mut single = build(&crossed[..1], false);
+        single.analyze_linear().unwrap();
+        let dx = braced.node_displacement("B", "Combo 1").unwrap().dx;
+        assert_relative_eq!(dx, single.node_displacement("B", "Combo 1").unwrap().dx, max_relative = 1e-9);
+        assert_eq!(braced.result_store().axial("DB", "Combo 1").unwrap(), 0.0);
+        assert_relative_eq!(braced.result_store().axial("AC", "Combo 1").unwrap(), 10e3 * 5.0 / 4.0, max_relative = 1e-6);
+        assert_eq!(braced.summary("Combo 1").unwrap().slack_members, vec!["DB".to_string()]);
+        let rxn = |node| braced.node_reactions(node, "Combo 1").unwrap().fx;
+        assert_relative_eq!(rxn("A") + rxn("D"), -10e3, max_relative = 1e-9);
+
+        // Linear analysis keeps both braces
+        let mut linear = build(&crossed, true);
+        linear.analyze_linear().unwrap();
+        let mut bilateral = build(&crossed, false);
+        bilateral.analyze_linear().unwrap();
+        assert_relative_eq!(
+            linear.node_displacement("B", "Combo 1").unwrap().dx,
+            bilateral.node_displacement("B", "Combo 1").unwrap().dx,
+            max_relative = 1e-12,
+        );
+        assert!(linear.node_displacement("B", "Combo 1").unwrap().dx < dx);
+
+        // The other brace takes over when the load is reversed
+        let mut reversed = build(&crossed, true);
+        reversed.node_loads.clear();
+        reversed.add_node_load("B", NodeLoad::fx(-10e3, "Case 1")).unwrap();
+        reversed.analyze_nonlinear().unwrap();
+        assert_eq!(reversed.summary("Combo 1").unwrap().slack_members, vec!["AC".to_string()]);
+    }
+
+    #[test]
+    fn test_slack_hook_engages_under_large_load() {
+        // Cantilever tip hangs from an anchor by a tie with 5 mm of slack
+        let (length, slack, k_hook) = (4.0, 0.005, 1e6);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.1, 0.2)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(length, 0.0, 0.0)).unwrap();
+        model.add_node("H", Node::new(length, 2.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("N1", "N2", "Steel", "Beam")).unwrap();
+        model.add_support("N1", Support::fixed()).unwrap();
+        model.add_support("H", Support::fixed()).unwrap();
+        model.add_gap("Tie", Gap::hook("H", "N2", slack, k_hook)).unwrap();
+        model.add_node_load("N2", NodeLoad::fy(-500.0, "Small")).unwrap();
+        model.add_node_load("N2", NodeLoad::fy(-20_000.0, "Large")).unwrap();
+        model.add_load_combo(LoadCombination::single("Small", "Small")).unwrap();
+        model.add_load_combo(LoadCombination::single("Large", "Large")).unwrap();
+        
+        assert!(model.analyze_linear().is_err());
+        model.analyze_nonlinear().unwrap();
+        
+        let ei = model.materials["Steel"].e * model.sections["Beam"].iz;
+        let k_beam = 3.0 * ei / length.powi(3);
+        
+        // Small load: the slack is not taken up
+        assert_relative_eq!(model.node_displacement("N2", "Small").unwrap().dy, -500.0 / k_beam, max_relative = 1e-9);
+        assert_eq!(model.gap_force("Tie", "Small").unwrap(), 0.0);
+        
+        // Large load: beam and tie share the load beyond the slack
+        let w = (20_000.0 + k_hook * slack) / (k_beam + k_hook);
+        assert_relative_eq!(model.node_displacement("N2", "Large").unwrap().dy, -w, max_relative = 1e-9);
+        let tension = model.gap_force("Tie", "Large").unwrap();
+        assert_relative_eq!(tension, k_hook * (w - slack), max_relative = 1e-9);
+        assert_relative_eq!(model.node_reactions("H", "Large").unwrap().fy, tension, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_bow_imperfection_p_delta() {
+        // Pinned strut with a half-sine bow e0: under P the bow grows by
+        // P / (Pcr - P), so the end slope is pi e0 / L * Pcr / (Pcr - P)
+        let (length, e0) = (5.0, 0.01);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Strut", Section::rectangular(0.1, 0.1)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(length, 0.0, 0.0)).unwrap();
+        let strut = Member::new("N1", "N2", "Steel", "Strut").with_imperfection(Imperfection::bow(e0, 0.0));
+        model.add_member("M1", strut).unwrap();
+        model.add_support("N1", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("N2", Support::with_restraints(false, true, true, true, false, false)).unwrap();
+        
+        let ei = model.materials["Steel"].e * model.sections["Strut"].iz;
+        let p_cr = std::f64::consts::PI.powi(2) * ei / length.powi(2);
+        let p = 0.2 * p_cr;
+        model.add_node_load("N2", NodeLoad::fx(-p, "Case 1")).unwrap();
+        
+        // A linear analysis treats the strut as straight
+        model.analyze_linear().unwrap();
+        assert_eq!(model.node_displacement("N1", "Combo 1").unwrap().rz, 0.0);
+        
+        model.analyze_p_delta().unwrap();
+        let slope = std::f64::consts::PI * e0 / length * p / (p_cr - p);
+        let rz = model.node_displacement("N1", "Combo 1").unwrap().rz;
+        // One cubic element slightly underestimates the amplification
+        assert_relative_eq!(rz, slope, max_relative = 0.05);
+        assert!(model.node_displacement("N2", "Combo 1").unwrap().rz * rz < 0.0);
+    }
+
+    #[test]
+    fn test_bow_imperfection_on_physical_member() {
+        // The strut of test_bow_imperfection_p_delta as a physical member in
+        // four segments, each carrying its share of the bow
+        let (length, e0) = (5.0, 0.01);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Strut", Section::rectangular(0.1, 0.1)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(length, 0.0, 0.0)).unwrap();
+        let strut = Member::new("N1", "N2", "Steel", "Strut").with_imperfection(Imperfection::bow(e0, 0.0));
+        let splits = vec![0.25 * length, 0.5 * length, 0.75 * length];
+        model.add_physical_member("S1", PhysicalMember::new(strut).with_splits(splits)).unwrap();
+        model.add_support("N1", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("N2", Support::with_restraints(false, true, true, true, false, false)).unwrap();
+        
+        let ei = model.materials["Steel"].e * model.sections["Strut"].iz;
+        let p_cr = std::f64::consts::PI.powi(2) * ei / length.powi(2);
+        let p = 0.2 * p_cr;
+        model.add_node_load("N2", NodeLoad::fx(-p, "Case 1")).unwrap();
+        model.analyze_p_delta().unwrap();
+        
+        let slope = std::f64::consts::PI * e0 / length * p / (p_cr - p);
+        assert_relative_eq!(model.node_displacement("N1", "Combo 1").unwrap().rz, slope, max_relative = 0.01);
+        // Mid-length deflection e0 P / (Pcr - P), on top of the bow
+        assert_relative_eq!(model.node_displacement("S1@2", "Combo 1").unwrap().dy, e0 * p / (p_cr - p), max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_partial_and_trapezoidal_loads() {
+        // Fixed-fixed beam, as a physical member split at a third of its
+        // length, under a triangular load that is zero at A and, separately,
+        // a uniform load over the first half
+        let (span, w) = (6.0, 12_000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(span, 0.0, 0.0)).unwrap();
+        let beam = PhysicalMember::new(Member::new("A", "B", "Steel", "Beam")).with_splits(vec![span / 3.0]);
+        model.add_physical_member("B1", beam).unwrap();
+        for node in ["A", "B"] {
+            model.add_support(node, Support::fixed()).unwrap();
         }
+        model.add_member_dist_load("B1", DistributedLoad::triangular(-w, 0.0, span, LoadDirection::FY, "Triangle")).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::new(-w, -w, 0.0, span / 2.0, LoadDirection::FY, "Half")).unwrap();
+        model.add_load_combo(LoadCombination::single("Triangle", "Triangle")).unwrap();
+        model.add_load_combo(LoadCombination::single("Half", "Half")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        let reactions = |combo: &str| (model.node_reactions("A", combo).unwrap(), model.node_reactions("B", combo).unwrap());
+        let (a, b) = reactions("Triangle");
+        assert_relative_eq!(a.fy, 3.0 * w * span / 20.0, max_relative = 1e-6);
+        assert_relative_eq!(b.fy, 7.0 * w * span / 20.0, max_relative = 1e-6);
+        assert_relative_eq!(a.mz, w * span.powi(2) / 30.0, max_relative = 1e-6);
+        assert_relative_eq!(b.mz, -w * span.powi(2) / 20.0, max_relative = 1e-6);
+        
+        let (a, b) = reactions("Half");
+        assert_relative_eq!(a.fy, 13.0 * w * span / 32.0, max_relative = 1e-6);
+        assert_relative_eq!(b.fy, 3.0 * w * span / 32.0, max_relative = 1e-6);
+        assert_relative_eq!(a.mz, 11.0 * w * span.powi(2) / 192.0, max_relative = 1e-6);
+        assert_relative_eq!(b.mz, -5.0 * w * span.powi(2) / 192.0, max_relative = 1e-6);
+    }
 
-        Ok(())
+    #[test]
+    fn test_prestressed_rod_relaxes_into_structure() {
+        // Rod A-B prestressed against member B-C: B moves until both carry
+        // the same tension, T * k_bc / (k_ab + k_bc)
+        let tension = 50_000.0;
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Rod", Section::new(1e-3, 1e-6, 1e-6, 1e-6)).unwrap();
+        model.add_section("Strut", Section::new(3e-3, 1e-6, 1e-6, 1e-6)).unwrap();
+        for (name, x) in [("A", 0.0), ("B", 2.0), ("C", 3.0)] {
+            model.add_node(name, Node::new(x, 0.0, 0.0)).unwrap();
+        }
+        model.add_member("AB", Member::new("A", "B", "Steel", "Rod")).unwrap();
+        model.add_member("BC", Member::new("B", "C", "Steel", "Strut")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_support("C", Support::fixed()).unwrap();
+        model.add_member_prestress("AB", PrestressLoad::force(tension, "PT")).unwrap();
+        let e = model.materials["Steel"].e;
+        model.add_member_prestress("AB", PrestressLoad::strain(-tension / (e * 1e-3), "Fit")).unwrap();
+        model.add_load_combo(LoadCombination::single("PT", "PT")).unwrap();
+        model.add_load_combo(LoadCombination::single("Fit", "Fit")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        let (k_ab, k_bc) = (e * 1e-3 / 2.0, e * 3e-3 / 1.0);
+        let expected = tension * k_bc / (k_ab + k_bc);
+        for combo in ["PT", "Fit"] {
+            let rod = -model.member_local_forces("AB", combo).unwrap()[0];
+            assert_relative_eq!(rod, expected, max_relative = 1e-9);
+            assert_relative_eq!(-model.member_local_forces("BC", combo).unwrap()[0], expected, max_relative = 1e-9);
+            assert_relative_eq!(model.node_reactions("A", combo).unwrap().fx, -expected, max_relative = 1e-9);
+        }
     }
 
-    /// Calculate reactions at supports
-    fn calculate_reactions(
-        &mut self,
-        combo_name: &str,
-        _dof_map: &HashMap<String, usize>,
-    ) -> FEAResult<()> {
-        // First, collect all the reaction contributions
-        let mut all_reactions: HashMap<String, [f64; 6]> = HashMap::new();
+    #[test]
+    fn test_prestressed_cable_lateral_stiffness() {
+        // A taut string deflects P L / 4T under a mid-span load; without the
+        // prestress geometric stiffness it would have almost no stiffness
+        let (length, tension, p, segments) = (10.0, 10_000.0, 100.0, 20);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Cable", Section::new(1e-4, 1e-12, 1e-12, 1e-8)).unwrap();
+        for i in 0..=segments {
+            let x = length * i as f64 / segments as f64;
+            model.add_node(&format!("N{i}"), Node::new(x, 0.0, 0.0)).unwrap();
+        }
+        for i in 0..segments {
+            let name = format!("C{i}");
+            model.add_member(&name, Member::new(&format!("N{i}"), &format!("N{}", i + 1), "Steel", "Cable")).unwrap();
+            model.add_member_prestress(&name, PrestressLoad::force(tension, "Case 1")).unwrap();
+        }
+        let anchor = Support::with_restraints(true, true, true, true, false, false);
+        model.add_support("N0", anchor).unwrap();
+        model.add_support(&format!("N{segments}"), anchor).unwrap();
+        model.add_node_load("N10", NodeLoad::fy(-p, "Case 1")).unwrap();
         
-        for (node_name, support) in &self.supports {
-            if !support.is_supported() {
-                continue;
-            }
-            all_reactions.insert(node_name.clone(), [0.0; 6]);
+        model.analyze_p_delta().unwrap();
+        let dy = model.node_displacement("N10", "Combo 1").unwrap().dy;
+        assert_relative_eq!(dy, -p * length / (4.0 * tension), max_relative = 0.02);
+    }
+
+    /// Single 2 m x 1 m concrete slab element in the XY plane
+    fn thermal_slab(supports: [Support; 4], load: PlateThermalLoad) -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        let corners = [("I", 0.0, 0.0), ("J", 2.0, 0.0), ("M", 2.0, 1.0), ("N", 0.0, 1.0)];
+        for ((name, x, y), support) in corners.into_iter().zip(supports) {
+            model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+            model.add_support(name, support).unwrap();
         }
+        model.add_plate("S1", Plate::new("I", "J", "M", "N", 0.2, "Concrete")).unwrap();
+        model.add_plate_thermal_load("S1", load).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_restrained_plate_temperature_stress() {
+        // Fully restrained: sigma = -E alpha dT / (1 - nu), M = -E t^2 alpha grad / (12 (1 - nu))
+        let (delta_t, gradient) = (20.0, 10.0);
+        let clamped = Support::with_restraints(true, true, true, true, true, false);
+        let mut model = thermal_slab([clamped; 4], PlateThermalLoad::new(delta_t, gradient, "Case 1"));
+        model.analyze_linear().unwrap();
         
-        // Sum forces from connected members
-        for member in self.members.values() {
-            let forces = member.local_forces.get(combo_name)
-                .ok_or_else(|| FEAError::NotAnalyzed)?;
-            
-            let i_node = self.nodes.get(&member.i_node).unwrap();
-            let j_node = self.nodes.get(&member.j_node).unwrap();
-            
-            let t = math::member_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                member.rotation,
-            );
-            
-            let f_local = math::Vec12::from_iterator(forces.iter().copied());
-            let f_global = t.transpose() * f_local;
-            
-            if let Some(reactions) = all_reactions.get_mut(&member.i_node) {
-                for i in 0..6 {
-                    reactions[i] += f_global[i];
-                }
-            }
-            if let Some(reactions) = all_reactions.get_mut(&member.j_node) {
-                for i in 0..6 {
-                    reactions[i] += f_global[i + 6];
-                }
-            }
+        let material = &model.materials["Concrete"];
+        let factor = material.e * material.alpha / (1.0 - material.nu);
+        let stress = model.plate_stress("S1", "Combo 1").unwrap();
+        assert_relative_eq!(stress.sx, -factor * delta_t, max_relative = 1e-9);
+        assert_relative_eq!(stress.sy, -factor * delta_t, max_relative = 1e-9);
+        assert_relative_eq!(stress.mx, -factor * 0.04 * gradient / 12.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_free_plate_temperature_gradient_curls() {
+        // Statically determinate supports: the slab curls freely without stress
+        let pin = Support::pinned();
+        let roller = Support::with_restraints(false, true, true, false, false, false);
+        let vertical = Support::with_restraints(false, false, true, false, false, false);
+        let gradient = 15.0;
+        let mut model = thermal_slab([pin, roller, Support::default(), vertical], PlateThermalLoad::gradient(gradient, "Case 1"));
+        model.analyze_linear().unwrap();
+        
+        // w = -kappa (x^2 + y^2) / 2 through the supports, kappa = alpha grad / t
+        let kappa = model.materials["Concrete"].alpha * gradient / 0.2;
+        let corner = model.node_displacement("I", "Combo 1").unwrap();
+        assert_relative_eq!(corner.ry, -kappa * 2.0 / 2.0, max_relative = 1e-6);
+        assert_relative_eq!(corner.rx, kappa * 1.0 / 2.0, max_relative = 1e-6);
+        let stress = model.plate_stress("S1", "Combo 1").unwrap();
+        assert!(stress.mx.abs() < 1e-6 * model.materials["Concrete"].e * 0.04 * kappa);
+    }
+
+    /// Massless cantilever column with a tip mass, optionally damped by a
+    /// horizontal viscous damper to a fixed anchor
+    fn tip_mass_column(mass: f64, damper: Option<f64>) -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Massless", Material::new(200e9, 77e9, 0.3, 0.0)).unwrap();
+        model.add_section("Column", Section::rectangular(0.2, 0.2)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(0.0, 3.0, 0.0)).unwrap();
+        model.add_member("C1", Member::new("N1", "N2", "Massless", "Column")).unwrap();
+        model.add_support("N1", Support::fixed()).unwrap();
+        model.add_node_mass("N2", mass).unwrap();
+        if let Some(c) = damper {
+            model.add_node("A", Node::new(-1.0, 3.0, 0.0)).unwrap();
+            model.add_support("A", Support::fixed()).unwrap();
+            model.add_link("D1", Link::viscous_damper("A", "N2", c, 1.0)).unwrap();
         }
+        model
+    }
+
+    #[test]
+    fn test_time_history_step_ground_motion() {
+        // A suddenly applied ground acceleration doubles the static deflection
+        let (mass, ag) = (5000.0, 2.0);
+        let mut model = tip_mass_column(mass, None);
+        let k = 3.0 * model.materials["Massless"].e * model.sections["Column"].iz / 27.0;
+        let period = 2.0 * std::f64::consts::PI * (mass / k).sqrt();
         
-        // Subtract applied loads and store results
-        for (node_name, reactions) in &mut all_reactions {
-            if let Some(loads) = self.node_loads.get(node_name) {
-                let combo = self.load_combos.get(combo_name).unwrap();
-                for load in loads {
-                    let factor = combo.factor(&load.case);
-                    let load_arr = load.as_array();
-                    for i in 0..6 {
-                        reactions[i] -= factor * load_arr[i];
-                    }
-                }
-            }
+        let history = TimeHistory::new(period / 200.0, vec![ag; 201], [1.0, 0.0, 0.0]);
+        let results = model.analyze_time_history(&history).unwrap();
+        assert_relative_eq!(results.peak_displacement("N2", 0).unwrap(), 2.0 * mass * ag / k, max_relative = 1e-3);
+        
+        // A damper with 10% of critical damping cuts the overshoot and, once the
+        // column has settled at the static deflection, has dissipated F^2 / 2k
+        let c = 0.2 * (k * mass).sqrt();
+        let mut model = tip_mass_column(mass, Some(c));
+        let history = TimeHistory::new(period / 100.0, vec![ag; 2001], [1.0, 0.0, 0.0]);
+        let results = model.analyze_time_history(&history).unwrap();
+        let peak = results.peak_displacement("N2", 0).unwrap();
+        assert!(peak < 0.9 * 2.0 * mass * ag / k);
+        assert_relative_eq!(results.dissipated_energy(), 0.5 * (mass * ag).powi(2) / k, max_relative = 0.01);
+        assert_eq!(results.links["D1"].force.len(), 2001);
+    }
+
+    #[test]
+    fn test_physical_member_continuous_beam() {
+        // Two equal spans under uniform load: the beam is split at the middle
+        // support and at a requested split, and reports the continuous moments
+        let (span, w) = (5.0, 10_000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(span, 0.0, 0.0)).unwrap();
+        model.add_node("C", Node::new(2.0 * span, 0.0, 0.0)).unwrap();
+        let beam = PhysicalMember::new(Member::new("A", "C", "Steel", "Beam")).with_splits(vec![span / 2.0]);
+        model.add_physical_member("B1", beam).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        for node in ["B", "C"] {
+            model.add_support(node, Support::with_restraints(false, true, true, false, false, false)).unwrap();
         }
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(w, "Case 1")).unwrap();
         
-        // Store reactions in nodes - only for restrained DOFs
-        for (node_name, mut reactions) in all_reactions {
-            // Mask out reactions for DOFs that are not restrained
-            if let Some(support) = self.supports.get(&node_name) {
-                let mask = [support.dx, support.dy, support.dz, support.rx, support.ry, support.rz];
-                for i in 0..6 {
-                    if !mask[i] {
-                        reactions[i] = 0.0;
-                    }
+        // Re-analysis regenerates the same segments
+        model.analyze_linear().unwrap();
+        model.analyze_linear().unwrap();
+        let segments: Vec<&str> = model.physical_members["B1"].segments().iter().map(|s| s.0.as_str()).collect();
+        assert_eq!(segments, ["B1#1", "B1#2", "B1#3"]);
+        assert!(model.nodes.contains_key("B1@1"));
+        
+        assert_relative_eq!(model.node_reactions("B", "Combo 1").unwrap().fy, 1.25 * w * span, max_relative = 1e-6);
+        let moment = |x: f64| model.physical_member_forces("B1", x, "Combo 1").unwrap().moment_z;
+        let hogging = moment(span);
+        assert_relative_eq!(hogging.abs(), w * span.powi(2) / 8.0, max_relative = 1e-6);
+        assert_relative_eq!(moment(span / 2.0), -hogging / 2.0, max_relative = 1e-6);
+        // Point of contraflexure at 3/4 of the span, inside a segment
+        assert!(moment(0.75 * span).abs() < 1e-6 * hogging.abs());
+        
+        let end = model.member_forces_j("B1", "Combo 1").unwrap();
+        assert_relative_eq!(end.moment_z, 0.0, epsilon = 1e-6 * hogging.abs());
+        assert_eq!(model.physical_member_diagram("B1", "Combo 1", 11).unwrap().len(), 11);
+    }
+
+    #[test]
+    fn test_member_diagram() {
+        // Simply supported beam under uniform load
+        let (span, w) = (6.0, 10_000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(span, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("B", Support::with_restraints(false, true, true, false, false, false)).unwrap();
+        model.add_member_dist_load("M1", DistributedLoad::uniform_downward(w, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+
+        let diagram = model.member_diagram("M1", "Combo 1", 5).unwrap();
+        let stations: Vec<f64> = diagram.iter().map(|(x, _)| *x).collect();
+        assert_eq!(stations, [0.0, 1.5, 3.0, 4.5, 6.0]);
+        let moment = w * span.powi(2) / 8.0;
+        assert_relative_eq!(diagram[0].1.moment_z, 0.0, epsilon = 1e-6 * moment);
+        assert_relative_eq!(diagram[2].1.moment_z.abs(), moment, max_relative = 1e-6);
+        assert_relative_eq!(diagram[4].1.moment_z, 0.0, epsilon = 1e-6 * moment);
+        assert_relative_eq!(diagram[0].1.shear_y, -diagram[4].1.shear_y, max_relative = 1e-6);
+        assert_relative_eq!(diagram[0].1.shear_y.abs(), w * span / 2.0, max_relative = 1e-6);
+        assert!(diagram[2].1.shear_y.abs() < 1e-6 * w * span);
+        assert!(model.member_diagram("M2", "Combo 1", 5).is_err());
+    }
+
+    #[test]
+    fn test_member_deflections() {
+        // Simply supported beam split off-centre, loaded in global -Y and +Z
+        let (span, w) = (6.0, 10_000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(span, 0.0, 0.0)).unwrap();
+        let beam = PhysicalMember::new(Member::new("A", "B", "Steel", "Beam")).with_splits(vec![span / 3.0]);
+        model.add_physical_member("B1", beam).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("B", Support::with_restraints(false, true, true, false, false, false)).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(w, "Case 1")).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform(w / 2.0, LoadDirection::FZ, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+
+        let deflections = model.member_deflections("B1", "Combo 1", 5).unwrap();
+        assert_eq!(deflections.len(), 5);
+        let (e, section) = (Material::steel().e, Section::rectangular(0.2, 0.4));
+        // 5 w L^4 / 384 EI at midspan, and w x (L^3 - 2 L x^2 + x^3) / 24 EI at quarter span
+        let midspan = deflections[2];
+        assert_relative_eq!(midspan.x, span / 2.0);
+        assert_relative_eq!(midspan.dy, -5.0 * w * span.powi(4) / (384.0 * e * section.iz), max_relative = 1e-6);
+        assert_relative_eq!(midspan.dz, 5.0 * w / 2.0 * span.powi(4) / (384.0 * e * section.iy), max_relative = 1e-6);
+        let x = span / 4.0;
+        let quarter = w * x * (span.powi(3) - 2.0 * span * x * x + x.powi(3)) / (24.0 * e * section.iz);
+        assert_relative_eq!(deflections[1].relative_y, -quarter, max_relative = 1e-6);
+        assert_relative_eq!(deflections[4].dy, 0.0, epsilon = 1e-12);
+
+        // Per-segment results match the physical member at the shared node
+        let segment = model.member_deflections("B1#2", "Combo 1", 2).unwrap();
+        let x = span / 3.0;
+        let at_split = w * x * (span.powi(3) - 2.0 * span * x * x + x.powi(3)) / (24.0 * e * section.iz);
+        assert_relative_eq!(segment[0].dy, -at_split, max_relative = 1e-6);
+        assert_relative_eq!(segment[0].relative_y, 0.0);
+
+        // A single station agrees with the evenly spaced ones
+        let at = model.member_deflection_at("B1", span / 4.0, "Combo 1").unwrap();
+        assert_relative_eq!(at.relative_y, deflections[1].relative_y, max_relative = 1e-12);
+        assert!(model.member_deflection_at("B1", span + 1.0, "Combo 1").is_err());
+    }
+
+    #[test]
+    fn test_merge_duplicate_nodes() {
+        // A cantilever imported as two members whose shared ends don't quite meet
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(2.0, 0.0, 0.0)).unwrap();
+        model.add_node("B'", Node::new(2.0, 1e-7, 0.0)).unwrap();
+        model.add_node("C", Node::new(4.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_member("M2", Member::new("B'", "C", "Steel", "Beam")).unwrap();
+        model.add_member("Stub", Member::new("B", "B'", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B'", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+        
+        let merged = model.merge_duplicate_nodes(1e-6).unwrap();
+        assert_eq!(merged, HashMap::from([("B'".to_string(), "B".to_string())]));
+        assert_eq!(model.members["M2"].i_node, "B");
+        assert!(!model.members.contains_key("Stub"));
+        assert_eq!(model.node_loads["B"].len(), 1);
+        
+        model.analyze_linear().unwrap();
+        assert_relative_eq!(model.node_reactions("A", "Combo 1").unwrap().fy, 1000.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_split_intersecting_members() {
+        // M1 along x is crossed by M2 at x = 5 and met by M3 at x = 2
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for (name, x, z) in [("A", 0.0, 0.0), ("B", 10.0, 0.0), ("C", 5.0, -5.0), ("D", 5.0, 5.0), ("E", 2.0, 0.0), ("F", 2.0, 4.0)] {
+            model.add_node(name, Node::new(x, 0.0, z)).unwrap();
+        }
+        let released = Member::new("A", "B", "Steel", "Beam").with_releases(MemberReleases::pin_both());
+        model.add_member("M1", released).unwrap();
+        model.add_member("M2", Member::new("C", "D", "Steel", "Beam")).unwrap();
+        model.add_member("M3", Member::new("F", "E", "Steel", "Beam")).unwrap();
+        model.add_member_dist_load("M1", DistributedLoad::new(0.0, 10.0, 0.0, 10.0, LoadDirection::FY, "Case 1")).unwrap();
+        
+        let pieces = model.split_intersecting_members().unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces["M1"], ["M1.1", "M1.2", "M1.3"]);
+        assert_eq!(pieces["M2"], ["M2.1", "M2.2"]);
+        assert_eq!(model.members["M1.2"].i_node, "E");
+        assert_eq!(model.members["M1.2"].j_node, "X1");
+        assert_eq!(model.members["M2.2"].i_node, "X1");
+        assert!(model.members["M1.1"].releases.i_node[5] && !model.members["M1.1"].releases.j_node[5]);
+        assert!(model.members["M1.3"].releases.j_node[5]);
+        
+        let load = &model.member_dist_loads["M1.3"][0];
+        assert_relative_eq!(load.w1, 5.0);
+        assert_relative_eq!(load.x2, 5.0);
+        assert!(model.split_intersecting_members().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_warped_quad_warning() {
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        // Corners alternately 0.1 either side of the XZ plane
+        let corners = [("I", 0.0, 0.1, 0.0), ("J", 2.0, -0.1, 0.0), ("M", 2.0, 0.1, 2.0), ("N", 0.0, -0.1, 2.0)];
+        for (name, x, y, z) in corners {
+            model.add_node(name, Node::new(x, y, z)).unwrap();
+        }
+        model.add_quad("Q1", Quad::new("I", "J", "M", "N", 0.2, "Concrete")).unwrap();
+        
+        // A small rigid rotation about X, enforced at three corners, must
+        // carry the fourth along without straining the quad
+        let theta = 1e-3;
+        for (node, _, y, z) in corners.into_iter().filter(|c| c.0 != "M") {
+            let support = Support::default()
+                .with_enforced_dx(0.0)
+                .with_enforced_dy(-theta * z)
+                .with_enforced_dz(theta * y);
+            model.add_support(node, support).unwrap();
+        }
+        model.add_node_load("M", NodeLoad::fy(0.0, "Case 1")).unwrap();
+        
+        model.analyze_linear().unwrap();
+        assert!(model.quad_quality("Q1").unwrap().warping > QuadQuality::WARPING_LIMIT);
+        assert_eq!(model.warnings().len(), 1);
+        assert!(model.warnings()[0].starts_with("Quad Q1: warping"));
+        let d = model.node_displacement("M", "Combo 1").unwrap();
+        assert!(d.dx.abs() < 1e-9);
+        assert!((d.dy + theta * 2.0).abs() < 1e-9);
+        assert!((d.dz - theta * 0.1).abs() < 1e-9);
+        assert!((d.rx - theta).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_warped_quads_match_flat_mesh() {
+        // A cantilever strip with its nodes bumped alternately up and down
+        // out of plane, so every quad is warped past the limit, should deflect
+        // like the flat strip under an in-plane tip load
+        let tip_deflection = |bump: f64| {
+            let mut model = FEModel::new();
+            model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+            let node = |i: usize, j: usize| format!("N{i}_{j}");
+            for i in 0..=8 {
+                for j in 0..=2 {
+                    let z = if (i + j) % 2 == 0 { bump } else { -bump };
+                    model.add_node(&node(i, j), Node::new(0.5 * i as f64, 0.5 * j as f64, z)).unwrap();
                 }
             }
-            
-            if let Some(node) = self.nodes.get_mut(&node_name) {
-                node.reactions.insert(combo_name.to_string(), reactions);
+            for i in 0..8 {
+                for j in 0..2 {
+                    let quad = Quad::new(&node(i, j), &node(i + 1, j), &node(i + 1, j + 1), &node(i, j + 1), 0.2, "Concrete");
+                    model.add_quad(&format!("Q{i}_{j}"), quad).unwrap();
+                }
             }
-        }
+            for j in 0..=2 {
+                model.add_support(&node(0, j), Support::fixed()).unwrap();
+                model.add_node_load(&node(8, j), NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+            }
+            model.analyze_linear().unwrap();
+            (model.warnings().len(), model.node_displacement(&node(8, 1), "Combo 1").unwrap().dy)
+        };
+        
+        let (warnings, flat) = tip_deflection(0.0);
+        assert_eq!(warnings, 0);
+        let (warnings, warped) = tip_deflection(0.02);
+        assert_eq!(warnings, 16);
+        assert!(((warped - flat) / flat).abs() < 1e-3);
+    }
 
-        Ok(())
+    #[test]
+    fn test_remove_node_cascades() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for (name, x) in [("A", 0.0), ("B", 4.0), ("C", 8.0)] {
+            model.add_node(name, Node::new(x, 0.0, 0.0)).unwrap();
+        }
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_member("M2", Member::new("B", "C", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_support("C", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+        model.add_member_dist_load("M2", DistributedLoad::uniform_downward(500.0, "Case 1")).unwrap();
+        model.add_member_dist_load("M1", DistributedLoad::uniform_downward(200.0, "Snow")).unwrap();
+        
+        model.remove_node("C").unwrap();
+        assert!(!model.members.contains_key("M2"));
+        assert!(!model.member_dist_loads.contains_key("M2"));
+        assert!(!model.supports.contains_key("C"));
+        assert!(matches!(model.remove_member("M2"), Err(FEAError::MemberNotFound(_))));
+        
+        assert_eq!(model.remove_load_case("Snow"), 1);
+        assert!(model.member_dist_loads.is_empty());
+        model.analyze_linear().unwrap();
+        assert_relative_eq!(model.node_reactions("A", "Combo 1").unwrap().fy, 1000.0, max_relative = 1e-9);
     }
 
-    // ========================
-    // Result Access Methods
-    // ========================
+    #[test]
+    fn test_rename_entities() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("N2", Node::new(4.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("N1", "N2", "Steel", "Beam")).unwrap();
+        model.add_support("N1", Support::fixed()).unwrap();
+        model.add_member_dist_load("M1", DistributedLoad::uniform_downward(1000.0, "Case 1")).unwrap();
+        
+        model.rename_node("N1", "Base").unwrap();
+        model.rename_member("M1", "Cantilever").unwrap();
+        model.rename_section("Beam", "R200x400").unwrap();
+        assert!(matches!(model.rename_node("N2", "Base"), Err(FEAError::DuplicateName(_))));
+        assert_eq!(model.members["Cantilever"].i_node, "Base");
+        assert_eq!(model.members["Cantilever"].section, "R200x400");
+        // Renaming to the same name is a no-op
+        model.rename_node("Base", "Base").unwrap();
+        model.rename_member("Cantilever", "Cantilever").unwrap();
+        assert!(matches!(model.rename_node("N9", "N9"), Err(FEAError::NodeNotFound(_))));
+        
+        model.analyze_linear().unwrap();
+        assert_relative_eq!(model.node_reactions("Base", "Combo 1").unwrap().fy, 4000.0, max_relative = 1e-9);
+        // Renaming a material or section invalidates the results
+        model.rename_material("Steel", "S355").unwrap();
+        assert!(!model.is_analyzed());
+    }
 
-    /// Get node displacement
-    pub fn node_displacement(&self, node_name: &str, combo_name: &str) -> FEAResult<NodeDisplacement> {
-        let node = self.nodes.get(node_name)
-            .ok_or_else(|| FEAError::NodeNotFound(node_name.to_string()))?;
+    #[test]
+    fn test_group_loads_properties_and_results() {
+        // Two identical cantilevers loaded and resized through a group
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Small", Section::rectangular(0.1, 0.2)).unwrap();
+        model.add_section("Large", Section::rectangular(0.2, 0.4)).unwrap();
+        for (k, z) in [(1, 0.0), (2, 3.0)] {
+            model.add_node(&format!("B{k}"), Node::new(0.0, 0.0, z)).unwrap();
+            model.add_node(&format!("T{k}"), Node::new(4.0, 0.0, z)).unwrap();
+            model.add_member(&format!("M{k}"), Member::new(&format!("B{k}"), &format!("T{k}"), "Steel", "Small")).unwrap();
+            model.add_support(&format!("B{k}"), Support::fixed()).unwrap();
+        }
+        model.add_group("Beams", Group::of_members(&["M1", "M2"]).with_nodes(&["T1", "T2"])).unwrap();
+        assert!(matches!(model.add_group("Bad", Group::of_members(&["M3"])), Err(FEAError::MemberNotFound(_))));
+        
+        let load = DistributedLoad::new(-1000.0, -1000.0, 0.0, 4.0, LoadDirection::FY, "Case 1");
+        model.add_group_dist_load("Beams", load).unwrap();
+        model.set_group_section("Beams", "Large").unwrap();
+        model.rename_member("M2", "Beam 2").unwrap();
+        model.analyze_linear().unwrap();
         
-        let disp = node.displacements.get(combo_name)
-            .ok_or_else(|| FEAError::NotAnalyzed)?;
+        let forces = model.group_member_forces("Beams", "Combo 1").unwrap();
+        assert_eq!(forces.len(), 2);
+        assert_eq!(forces[1].0, "Beam 2");
+        for (_, fi, _) in &forces {
+            assert_relative_eq!(fi.moment_z.abs(), 1000.0 * 16.0 / 2.0, max_relative = 1e-9);
+        }
+        let tips = model.group_node_displacements("Beams", "Combo 1").unwrap();
+        assert_relative_eq!(tips[0].1.dy, tips[1].1.dy, max_relative = 1e-9);
         
-        Ok(NodeDisplacement::from_array(*disp))
+        let json = serde_json::to_string(&model).unwrap();
+        let restored: FEModel = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.groups["Beams"], model.groups["Beams"]);
     }
 
-    /// Get node reactions
-    pub fn node_reactions(&self, node_name: &str, combo_name: &str) -> FEAResult<Reactions> {
-        let node = self.nodes.get(node_name)
-            .ok_or_else(|| FEAError::NodeNotFound(node_name.to_string()))?;
+    #[test]
+    fn test_handles_and_sparse_stiffness() {
+        // Portal frame with a roof plate: the handle-numbered sparse matrix
+        // matches the dense analysis matrix entry for entry
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Section", Section::rectangular(0.2, 0.3)).unwrap();
+        for (name, x, y, z) in [("A", 0.0, 0.0, 0.0), ("B", 0.0, 3.0, 0.0), ("C", 4.0, 3.0, 0.0),
+                                ("D", 4.0, 0.0, 0.0), ("E", 4.0, 3.0, 2.0), ("F", 0.0, 3.0, 2.0)] {
+            model.add_node(name, Node::new(x, y, z)).unwrap();
+        }
+        for (name, i, j) in [("Col1", "A", "B"), ("Beam", "B", "C"), ("Col2", "D", "C")] {
+            model.add_member(name, Member::new(i, j, "Steel", "Section")).unwrap();
+        }
+        model.add_plate("Roof", Plate::new("B", "C", "E", "F", 0.1, "Steel")).unwrap();
         
-        let rxn = node.reactions.get(combo_name)
-            .ok_or_else(|| FEAError::NotAnalyzed)?;
+        assert!(model.member_id("Beam").is_none());
+        let sparse = model.sparse_stiffness().unwrap();
+        let beam = model.member_id("Beam").unwrap();
+        let [i, j] = model.member_end_nodes(beam).unwrap();
+        assert_eq!(model.member_name(beam), Some("Beam"));
+        assert_eq!((model.node_name(i), model.node_name(j)), (Some("B"), Some("C")));
+        assert_eq!(model.nodes["C"].id, Some(j.index()));
         
-        Ok(Reactions::from_array(*rxn))
+        let (dense, _) = model.build_global_stiffness(&CancelToken::new()).unwrap();
+        let mut diff = Mat::from(&sparse) - &dense;
+        diff.apply(|v| *v = v.abs());
+        assert!(diff.max() < 1e-6 * dense.amax());
     }
 
-    /// Get member forces at i-node
-    pub fn member_forces_i(&self, member_name: &str, combo_name: &str) -> FEAResult<MemberForces> {
-        let member = self.members.get(member_name)
-            .ok_or_else(|| FEAError::MemberNotFound(member_name.to_string()))?;
+    #[test]
+    fn test_results_independent_of_insertion_order() {
+        // The same slab-on-frame built in two different orders gives
+        // bit-identical results and serialises identically
+        let build = |reverse: bool| {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Section", Section::rectangular(0.2, 0.3)).unwrap();
+            let mut corners: std::vec::Vec<(String, f64, f64)> = (0..9)
+                .map(|k| (format!("N{k}"), (k % 3) as f64 * 2.0, (k / 3) as f64 * 2.0))
+                .collect();
+            if reverse {
+                corners.reverse();
+            }
+            for (name, x, z) in &corners {
+                model.add_node(name, Node::new(*x, 0.0, *z)).unwrap();
+                model.add_support(name, Support::pinned()).unwrap();
+            }
+            for (k, [i, j, m, n]) in [[0, 1, 4, 3], [1, 2, 5, 4], [3, 4, 7, 6], [4, 5, 8, 7]].into_iter().enumerate() {
+                let [i, j, m, n] = [i, j, m, n].map(|c| format!("N{c}"));
+                model.add_plate(&format!("P{k}"), Plate::new(&i, &j, &m, &n, 0.1, "Steel")).unwrap();
+                model.add_plate_load(&format!("P{k}"), PlateLoad::new(-1000.0 * (k + 1) as f64, "Case 1")).unwrap();
+            }
+            model.add_member("Edge", Member::new("N0", "N2", "Steel", "Section")).unwrap();
+            model
+        };
+        
+        let (mut a, mut b) = (build(false), build(true));
+        assert_eq!(serde_json::to_string(&a).unwrap(), serde_json::to_string(&b).unwrap());
+        a.analyze_linear().unwrap();
+        b.analyze_linear().unwrap();
+        let da = a.node_displacement("N4", "Combo 1").unwrap();
+        let db = b.node_displacement("N4", "Combo 1").unwrap();
+        assert_eq!(da.dy.to_bits(), db.dy.to_bits());
+        assert_eq!(da.rx.to_bits(), db.rx.to_bits());
+    }
+
+    #[test]
+    fn test_large_displacement_cantilever_end_moment() {
+        // A tip moment M = pi*EI/L bends a cantilever into a semicircle of
+        // radius L/pi, bringing the tip back over the support at height 2L/pi.
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Bar", Section::rectangular(0.1, 0.1)).unwrap();
+        
+        let length = 10.0;
+        let n_elem = 20;
+        for i in 0..=n_elem {
+            let x = length * i as f64 / n_elem as f64;
+            model.add_node(&format!("N{i}"), Node::new(x, 0.0, 0.0)).unwrap();
+        }
+        for i in 0..n_elem {
+            model
+                .add_member(&format!("M{i}"), Member::new(&format!("N{i}"), &format!("N{}", i + 1), "Steel", "Bar"))
+                .unwrap();
+        }
+        model.add_support("N0", Support::fixed()).unwrap();
+        
+        let ei = Material::steel().e * Section::rectangular(0.1, 0.1).iz;
+        let moment = std::f64::consts::PI * ei / length;
+        model
+            .add_node_load(&format!("N{n_elem}"), NodeLoad::moment(0.0, 0.0, moment, "Case 1"))
+            .unwrap();
+        
+        model
+            .analyze(AnalysisOptions::large_displacement(10).with_max_iter(50))
+            .unwrap();
         
-        let forces = member.local_forces.get(combo_name)
-            .ok_or_else(|| FEAError::NotAnalyzed)?;
+        let tip = model.node_displacement(&format!("N{n_elem}"), "Combo 1").unwrap();
+        assert_relative_eq!(tip.dx, -length, epsilon = 0.05);
+        assert_relative_eq!(tip.dy, 2.0 * length / std::f64::consts::PI, epsilon = 0.05);
+        assert_relative_eq!(tip.rz.abs(), std::f64::consts::PI, epsilon = 1e-3);
         
-        Ok(MemberForces::from_i_node_forces(forces))
+        let rxn = model.node_reactions("N0", "Combo 1").unwrap();
+        assert_relative_eq!(rxn.mz, -moment, epsilon = moment * 1e-6);
     }
 
-    /// Get member forces at j-node
-    pub fn member_forces_j(&self, member_name: &str, combo_name: &str) -> FEAResult<MemberForces> {
-        let member = self.members.get(member_name)
-            .ok_or_else(|| FEAError::MemberNotFound(member_name.to_string()))?;
+    #[test]
+    fn test_arc_length_shallow_arch_snap_through() {
+        // Shallow two-member arch loaded past its limit point: the apex snaps
+        // through to the inverted configuration below the supports.
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Bar", Section::rectangular(0.1, 0.1)).unwrap();
+        
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(5.0, 0.5, 0.0)).unwrap();
+        model.add_node("C", Node::new(10.0, 0.0, 0.0)).unwrap();
+        model.add_member("AB", Member::new("A", "B", "Steel", "Bar")).unwrap();
+        model.add_member("BC", Member::new("B", "C", "Steel", "Bar")).unwrap();
+        
+        let base = Support::with_restraints(true, true, true, true, true, false);
+        model.add_support("A", base).unwrap();
+        model.add_support("C", base).unwrap();
+        model
+            .add_support("B", Support::with_restraints(false, false, true, true, true, true))
+            .unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1.5e6, "Case 1")).unwrap();
+        
+        model
+            .analyze(AnalysisOptions::large_displacement(20).with_arc_length().with_max_iter(50))
+            .unwrap();
+        
+        let apex = model.node_displacement("B", "Combo 1").unwrap();
+        assert!(apex.dy < -1.0, "apex did not snap through: dy = {}", apex.dy);
+        
+        let rxn_a = model.node_reactions("A", "Combo 1").unwrap();
+        let rxn_c = model.node_reactions("C", "Combo 1").unwrap();
+        assert_relative_eq!(rxn_a.fy + rxn_c.fy, 1.5e6, epsilon = 1.0);
+        
+        // Other analysis types reject arc-length settings rather than ignore them
+        let p_delta = AnalysisOptions { analysis_type: AnalysisType::PDelta, ..AnalysisOptions::default().with_arc_length() };
+        assert!(matches!(model.analyze(p_delta), Err(FEAError::InvalidInput(_))));
+        let nonlinear = AnalysisOptions::nonlinear().with_arc_length();
+        assert!(matches!(model.analyze(nonlinear), Err(FEAError::InvalidInput(_))));
+        assert!(matches!(model.analyze_modal(&AnalysisOptions::default().with_arc_length()), Err(FEAError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_story_shears_and_drifts() {
+        // Two-story, one-bay frame with lateral loads at both floors
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::new(7.65e-3, 2.04e-4, 1.73e-5, 3e-7)).unwrap();
+        for (level, y) in [(0, 0.0), (1, 3.0), (2, 6.0)] {
+            model.add_node(&format!("A{level}"), Node::new(0.0, y, 0.0)).unwrap();
+            model.add_node(&format!("B{level}"), Node::new(6.0, y, 0.0)).unwrap();
+        }
+        for level in 1..=2 {
+            for line in ["A", "B"] {
+                let column = Member::new(&format!("{line}{}", level - 1), &format!("{line}{level}"), "Steel", "W");
+                model.add_member(&format!("C{line}{level}"), column).unwrap();
+            }
+            model.add_member(&format!("B{level}"), Member::new(&format!("A{level}"), &format!("B{level}"), "Steel", "W")).unwrap();
+        }
+        model.add_support("A0", Support::fixed()).unwrap();
+        model.add_support("B0", Support::fixed()).unwrap();
+        model.add_node_load("A1", NodeLoad::fx(10e3, "E")).unwrap();
+        model.add_node_load("A2", NodeLoad::fx(20e3, "E")).unwrap();
+        model.add_load_combo(LoadCombination::single("E", "E")).unwrap();
+        model.add_group("Roof", Group::of_nodes(&["A2", "B2"])).unwrap();
+        model.add_story("Roof", StoryLevel::from_group(6.0, "Roof")).unwrap();
+        model.add_story("Level 1", StoryLevel::new(3.0)).unwrap();
+        model.analyze_linear().unwrap();
         
-        let forces = member.local_forces.get(combo_name)
-            .ok_or_else(|| FEAError::NotAnalyzed)?;
+        let stories = model.story_results("E").unwrap();
+        assert_eq!(stories.iter().map(|s| s.story.as_str()).collect::<Vec<_>>(), ["Level 1", "Roof"]);
+        let (first, roof) = (&stories[0], &stories[1]);
+        assert_relative_eq!(first.shear_x, 30e3, epsilon = 1e-6);
+        assert_relative_eq!(roof.shear_x, 20e3, epsilon = 1e-6);
+        assert_relative_eq!(first.overturning_z, -(10e3 * 3.0 + 20e3 * 6.0), epsilon = 1e-6);
+        assert_relative_eq!(roof.overturning_z, -20e3 * 3.0, epsilon = 1e-6);
         
-        Ok(MemberForces::from_j_node_forces(forces))
+        let dx = |node: &str| model.node_displacement(node, "E").unwrap().dx;
+        assert_relative_eq!(roof.drift_x, (dx("A2") + dx("B2") - dx("A1") - dx("B1")) / 2.0, epsilon = 1e-15);
+        assert_relative_eq!(roof.max_drift_x, (dx("A2") - dx("A1")).abs().max((dx("B2") - dx("B1")).abs()), epsilon = 1e-15);
+        assert_relative_eq!(first.drift_ratio().0, first.drift_x / 3.0);
+        assert!(first.drift_x > 0.0 && first.max_drift_z == 0.0);
     }
 
-    /// Get plate stress at center (works for both Plate and Quad elements)
-    pub fn plate_stress(&self, plate_name: &str, combo_name: &str) -> FEAResult<PlateStressResult> {
-        // Try plates first, then quads
-        if let Some(plate) = self.plates.get(plate_name) {
-            let width = plate.width.ok_or(FEAError::NotAnalyzed)?;
-            let height = plate.height.ok_or(FEAError::NotAnalyzed)?;
-            let material = self.materials.get(&plate.material)
-                .ok_or_else(|| FEAError::MaterialNotFound(plate.material.clone()))?;
-            
-            // Get displacements for each node
-            let i_disp = self.nodes.get(&plate.i_node)
-                .and_then(|n| n.displacements.get(combo_name))
-                .ok_or(FEAError::NotAnalyzed)?;
-            let j_disp = self.nodes.get(&plate.j_node)
-                .and_then(|n| n.displacements.get(combo_name))
-                .ok_or(FEAError::NotAnalyzed)?;
-            let m_disp = self.nodes.get(&plate.m_node)
-                .and_then(|n| n.displacements.get(combo_name))
-                .ok_or(FEAError::NotAnalyzed)?;
-            let n_disp = self.nodes.get(&plate.n_node)
-                .and_then(|n| n.displacements.get(combo_name))
-                .ok_or(FEAError::NotAnalyzed)?;
-            
-            // Build global displacement vector
-            let mut d_global = math::plate::Vec24::zeros();
-            for (i, disp) in [i_disp, j_disp, m_disp, n_disp].iter().enumerate() {
-                for j in 0..6 {
-                    d_global[i * 6 + j] = disp[j];
+    #[test]
+    fn test_section_cuts_through_walls_and_columns() {
+        // Two cantilever shear walls of 2 x 4 quads in the XY plane, and a
+        // column, each loaded laterally at the top
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        model.add_section("Col", Section::rectangular(0.4, 0.4)).unwrap();
+        for (wall, x0) in [("W1", 0.0), ("W2", 4.0)] {
+            for row in 0..=4 {
+                for col in 0..=2 {
+                    let name = format!("{wall}_{row}_{col}");
+                    model.add_node(&name, Node::new(x0 + col as f64, row as f64, 0.0)).unwrap();
+                    if row == 0 {
+                        model.add_support(&name, Support::fixed()).unwrap();
+                    }
                 }
             }
-            
-            // Transform to local coordinates
-            let i_node = self.nodes.get(&plate.i_node).unwrap();
-            let j_node = self.nodes.get(&plate.j_node).unwrap();
-            let n_node = self.nodes.get(&plate.n_node).unwrap();
-            let t = math::plate_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                &n_node.coords(),
-            );
-            let d_local = t * d_global;
-            
-            // Calculate stresses at center
-            let center_x = width / 2.0;
-            let center_y = height / 2.0;
-            
-            let membrane = math::plate_membrane_stress(
-                center_x, center_y, &d_local,
-                material.e, material.nu, plate.thickness,
-                width, height, plate.kx_mod, plate.ky_mod,
-            );
-            
-            let moments = math::plate_moments(
-                center_x, center_y, &d_local,
-                material.e, material.nu, plate.thickness,
-                width, height, plate.kx_mod, plate.ky_mod,
-            );
-            
-            // Calculate von Mises stress at plate surface from bending
-            // Bending stress at surface: sigma = 6*M / t^2 (M is moment per unit width)
-            let t2 = plate.thickness * plate.thickness;
-            let sigma_x_bend = 6.0 * moments[0] / t2;
-            let sigma_y_bend = 6.0 * moments[1] / t2;
-            let tau_xy_bend = 6.0 * moments[2] / t2;
-            
-            // Total stress = membrane + bending (at surface)
-            let sigma_x_total = membrane[0] + sigma_x_bend;
-            let sigma_y_total = membrane[1] + sigma_y_bend;
-            let tau_xy_total = membrane[2] + tau_xy_bend;
-            
-            // Von Mises from total stresses
-            let von_mises = (sigma_x_total.powi(2) - sigma_x_total * sigma_y_total + 
-                           sigma_y_total.powi(2) + 3.0 * tau_xy_total.powi(2)).sqrt();
-            
-            Ok(PlateStressResult {
-                // Keep membrane components as-is; von_mises reflects surface (membrane + bending).
-                sx: membrane[0],
-                sy: membrane[1],
-                txy: membrane[2],
-                von_mises,
-                mx: moments[0],
-                my: moments[1],
-                mxy: moments[2],
-            })
-        } else if let Some(quad) = self.quads.get(plate_name) {
-            // Similar for quads
-            let i_node = self.nodes.get(&quad.i_node).unwrap();
-            let j_node = self.nodes.get(&quad.j_node).unwrap();
-            let m_node = self.nodes.get(&quad.m_node).unwrap();
-            let n_node = self.nodes.get(&quad.n_node).unwrap();
-            
-            let width = i_node.distance_to(j_node);
-            let height = j_node.distance_to(m_node);
-            let material = self.materials.get(&quad.material)
-                .ok_or_else(|| FEAError::MaterialNotFound(quad.material.clone()))?;
-            
-            // Get displacements
-            let i_disp = i_node.displacements.get(combo_name).ok_or(FEAError::NotAnalyzed)?;
-            let j_disp = j_node.displacements.get(combo_name).ok_or(FEAError::NotAnalyzed)?;
-            let m_disp = m_node.displacements.get(combo_name).ok_or(FEAError::NotAnalyzed)?;
-            let n_disp = n_node.displacements.get(combo_name).ok_or(FEAError::NotAnalyzed)?;
-            
-            // Build global displacement vector
-            let mut d_global = math::plate::Vec24::zeros();
-            for (i, disp) in [i_disp, j_disp, m_disp, n_disp].iter().enumerate() {
-                for j in 0..6 {
-                    d_global[i * 6 + j] = disp[j];
+            for row in 0..4 {
+                for col in 0..2 {
+                    let n = |r: usize, c: usize| format!("{wall}_{r}_{c}");
+                    let quad = Quad::new(&n(row, col), &n(row, col + 1), &n(row + 1, col + 1), &n(row + 1, col), 0.2, "Concrete");
+                    model.add_quad(&format!("{wall}Q{row}{col}"), quad).unwrap();
                 }
             }
-            
-            // Transform to local
-            let t = math::plate_transformation_matrix(
-                &i_node.coords(),
-                &j_node.coords(),
-                &n_node.coords(),
-            );
-            let d_local = t * d_global;
-            
-            // Calculate stresses
-            let center_x = width / 2.0;
-            let center_y = height / 2.0;
-            
-            let membrane = math::plate_membrane_stress(
-                center_x, center_y, &d_local,
-                material.e, material.nu, quad.thickness,
-                width, height, quad.kx_mod, quad.ky_mod,
-            );
-            
-            let moments = math::plate_moments(
-                center_x, center_y, &d_local,
-                material.e, material.nu, quad.thickness,
-                width, height, quad.kx_mod, quad.ky_mod,
-            );
-            
-            // Calculate bending stresses at the plate surface (z = t/2)
-            // σ = 6M/t² (from flexural stress formula σ = Mc/I where c = t/2 and I = bt³/12)
-            let t = quad.thickness;
-            let sigma_x_bend = 6.0 * moments[0] / (t * t);
-            let sigma_y_bend = 6.0 * moments[1] / (t * t);
-            let tau_xy_bend = 6.0 * moments[2] / (t * t);
-            
-            // Total stress = membrane + bending (at surface)
-            let sigma_x_total = membrane[0] + sigma_x_bend;
-            let sigma_y_total = membrane[1] + sigma_y_bend;
-            let tau_xy_total = membrane[2] + tau_xy_bend;
-            
-            // Von Mises stress from combined stresses
-            let von_mises = (sigma_x_total.powi(2) - sigma_x_total * sigma_y_total + 
-                           sigma_y_total.powi(2) + 3.0 * tau_xy_total.powi(2)).sqrt();
-            
-            Ok(PlateStressResult {
-                sx: membrane[0],
-                sy: membrane[1],
-                txy: membrane[2],
-                von_mises,
-                mx: moments[0],
-                my: moments[1],
-                mxy: moments[2],
-            })
-        } else {
-            Err(FEAError::PlateNotFound(plate_name.to_string()))
         }
+        model.add_node("C0", Node::new(8.0, 0.0, 0.0)).unwrap();
+        model.add_node("C4", Node::new(8.0, 4.0, 0.0)).unwrap();
+        model.add_member("C", Member::new("C0", "C4", "Concrete", "Col")).unwrap();
+        model.add_support("C0", Support::fixed()).unwrap();
+        model.add_node_load("W1_4_0", NodeLoad::fx(100e3, "E")).unwrap();
+        model.add_node_load("W2_4_2", NodeLoad::fx(60e3, "E")).unwrap();
+        model.add_node_load("W2_4_1", NodeLoad::fy(-50e3, "E")).unwrap();
+        model.add_node_load("C4", NodeLoad::fx(5e3, "E")).unwrap();
+        model.add_load_combo(LoadCombination::single("E", "E")).unwrap();
+        model.analyze_linear().unwrap();
+        
+        // The whole structure: equilibrium with everything above the cut
+        let cut = SectionCut::plane([0.0, 2.0, 0.0], [0.0, 1.0, 0.0]).unwrap();
+        let all = model.section_cut(&cut, "E").unwrap();
+        assert_eq!(all.elements.len(), 5);
+        assert_relative_eq!(all.force[0], 165e3, max_relative = 1e-9);
+        assert_relative_eq!(all.axial, -50e3, max_relative = 1e-9);
+        let expected_mz = -2.0 * 165e3 + 5.0 * -50e3;
+        assert_relative_eq!(all.moment[2], expected_mz, max_relative = 1e-9);
+        
+        // Bounded cut around wall 2 only, between rows
+        let around = |x0: f64, x1: f64| vec![[x0, 2.5, 1.0], [x1, 2.5, 1.0], [x1, 2.5, -1.0], [x0, 2.5, -1.0]];
+        let w2 = model.section_cut(&SectionCut::polyline(&around(3.5, 6.5)).unwrap(), "E").unwrap();
+        assert_eq!(w2.elements, ["W2Q20", "W2Q21"]);
+        assert_relative_eq!(w2.force[0], 60e3, max_relative = 1e-6);
+        assert_relative_eq!(w2.force[1], -50e3, max_relative = 1e-6);
+        // Moment about the polygon centre (5, 2.5, 0) of the loads above
+        assert_relative_eq!(w2.moment[2], -1.5 * 60e3 + 0.0 * -50e3, epsilon = 1e-3 * 60e3);
+        
+        let column = model.section_cut(&SectionCut::polyline(&around(7.5, 8.5)).unwrap(), "E").unwrap();
+        assert_eq!(column.elements, ["C"]);
+        assert_relative_eq!(column.force[0], 5e3, max_relative = 1e-9);
     }
 
-    /// Get analysis summary
-    pub fn summary(&self, combo_name: &str) -> FEAResult<AnalysisSummary> {
-        if self.solution.is_none() {
-            return Err(FEAError::NotAnalyzed);
+    #[test]
+    fn test_shell_surface_stresses_uniform_tension() {
+        // Two plates pulled along X carry a uniform membrane stress P/(h*t),
+        // equal on both faces, at every corner and Gauss point
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        for k in 0..6 {
+            model.add_node(&format!("N{k}"), Node::new((k % 3) as f64, (k / 3) as f64, 0.0)).unwrap();
+            let (dx, dy) = (k % 3 == 0, k == 0);
+            model.add_support(&format!("N{k}"), Support::with_restraints(dx, dy, true, true, true, true)).unwrap();
         }
-        
-        let mut summary = AnalysisSummary {
-            num_nodes: self.nodes.len(),
-            num_members: self.members.len(),
-            num_plates: self.plates.len() + self.quads.len(),
-            total_dofs: self.nodes.len() * 6,
-            ..Default::default()
-        };
-        
-        // Find max displacement
-        for (name, node) in &self.nodes {
-            if let Some(disp) = node.displacements.get(combo_name) {
-                let mag = (disp[0].powi(2) + disp[1].powi(2) + disp[2].powi(2)).sqrt();
-                if mag > summary.max_displacement {
-                    summary.max_displacement = mag;
-                    summary.max_disp_node = name.clone();
-                }
-            }
+        model.add_plate("P0", Plate::new("N0", "N1", "N4", "N3", 0.1, "Steel")).unwrap();
+        model.add_plate("P1", Plate::new("N1", "N2", "N5", "N4", 0.1, "Steel")).unwrap();
+        for node in ["N2", "N5"] {
+            model.add_node_load(node, NodeLoad::force(500.0, 0.0, 0.0, "Case 1")).unwrap();
         }
+        model.analyze_linear().unwrap();
         
-        // Find max reaction
-        for (name, node) in &self.nodes {
-            if let Some(rxn) = node.reactions.get(combo_name) {
-                let mag = (rxn[0].powi(2) + rxn[1].powi(2) + rxn[2].powi(2)).sqrt();
-                if mag > summary.max_reaction {
-                    summary.max_reaction = mag;
-                    summary.max_reaction_node = name.clone();
-                }
+        for stress in model.shell_surface_stresses("P1", "Combo 1", StressPoints::GaussPoints).unwrap() {
+            assert_relative_eq!(stress.top.sx, 1e4, max_relative = 1e-6);
+            assert_relative_eq!(stress.bottom.sx, 1e4, max_relative = 1e-6);
+            assert_relative_eq!(stress.max_von_mises(), 1e4, max_relative = 1e-6);
+        }
+        let nodal = model.shell_nodal_stresses("Combo 1").unwrap();
+        assert_eq!(nodal.len(), 6);
+        let shared = &nodal["N1"];
+        assert_eq!(shared.elements.iter().map(|(name, _)| name.as_str()).collect::<std::vec::Vec<_>>(), ["P0", "P1"]);
+        assert_relative_eq!(shared.averaged.top.s1, 1e4, max_relative = 1e-6);
+        assert!(shared.von_mises_deviation() < 1e-6 * 1e4);
+    }
+
+    #[test]
+    fn test_shell_nodal_stress_averaging_under_pressure() {
+        // At the edge midpoint of a pinned 2 x 2 slab the corner twisting
+        // moments of the two elements cancel, so only the un-averaged values
+        // show the mesh error
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        for k in 0..9 {
+            model.add_node(&format!("N{k}"), Node::new((k % 3) as f64 * 2.0, 0.0, (k / 3) as f64 * 2.0)).unwrap();
+            if k != 4 {
+                model.add_support(&format!("N{k}"), Support::pinned()).unwrap();
             }
         }
+        for (k, [i, j, m, n]) in [[0, 1, 4, 3], [1, 2, 5, 4], [3, 4, 7, 6], [4, 5, 8, 7]].into_iter().enumerate() {
+            let [i, j, m, n] = [i, j, m, n].map(|c| format!("N{c}"));
+            model.add_plate(&format!("P{k}"), Plate::new(&i, &j, &m, &n, 0.2, "Concrete")).unwrap();
+            model.add_plate_load(&format!("P{k}"), PlateLoad::new(-1000.0, "Case 1")).unwrap();
+        }
+        model.analyze_linear().unwrap();
         
-        // Find max member forces
-        for (name, member) in &self.members {
-            if let Some(forces) = member.local_forces.get(combo_name) {
-                let axial = forces[0].abs();
-                if axial > summary.max_axial {
-                    summary.max_axial = axial;
-                    summary.max_axial_member = name.clone();
-                }
-                
-                let moment = forces[4].abs().max(forces[5].abs())
-                    .max(forces[10].abs()).max(forces[11].abs());
-                if moment > summary.max_moment {
-                    summary.max_moment = moment;
-                    summary.max_moment_member = name.clone();
-                }
+        let nodal = model.shell_nodal_stresses("Combo 1").unwrap();
+        let centre = &nodal["N4"];
+        assert_eq!(centre.elements.len(), 4);
+        assert!(centre.averaged.top.sx.abs() > 1.0);
+        assert_relative_eq!(centre.averaged.top.sx, -centre.averaged.bottom.sx, max_relative = 1e-9);
+        
+        let edge = &nodal["N1"];
+        let [(_, p0), (_, p1)] = [&edge.elements[0], &edge.elements[1]];
+        assert_relative_eq!(p0.top.txy, -p1.top.txy, max_relative = 1e-9);
+        assert!(p0.top.txy.abs() > 0.1 * p0.top.sx.abs());
+        assert!(edge.averaged.top.txy.abs() < 1e-9 * p0.top.txy.abs());
+        assert_relative_eq!(edge.von_mises_deviation(), p0.top.von_mises - edge.averaged.top.von_mises, max_relative = 1e-9);
+        
+        // Un-averaged values are the element corner stresses, and the
+        // element centre von Mises stress stays that of the top face
+        let corners = model.shell_surface_stresses("P0", "Combo 1", StressPoints::Corners).unwrap();
+        assert_relative_eq!(corners[2].top.sx, centre.elements[0].1.top.sx, max_relative = 1e-12);
+        let result = model.plate_stress("P0", "Combo 1").unwrap();
+        let surface = ShellSurfaceStress::from_resultants(
+            [result.sx, result.sy, result.txy], [result.mx, result.my, result.mxy], 0.2,
+        );
+        assert_relative_eq!(result.von_mises, surface.top.von_mises, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_wood_armer_design_moments() {
+        // Pure twist needs |mxy| both ways on both faces
+        let twist = SlabDesignMoments::from_moments(0.0, 0.0, 10.0);
+        assert_eq!([twist.top_x, twist.top_y, twist.bottom_x, twist.bottom_y], [10.0; 4]);
+        
+        // mx + |mxy| < 0 on the top face switches to my + mxy^2/|mx|
+        let hogging = SlabDesignMoments::from_moments(-40.0, 5.0, 20.0);
+        assert_eq!([hogging.top_x, hogging.top_y], [0.0, 15.0]);
+        assert_eq!([hogging.bottom_x, hogging.bottom_y], [60.0, 15.0]);
+        
+        // At the centre of a pinned slab under pressure, with no twist, the
+        // design moments are the bending moments on the tension face only
+        let mut model = FEModel::new();
+        model.add_material("Concrete", Material::concrete(30e6)).unwrap();
+        for k in 0..9 {
+            model.add_node(&format!("N{k}"), Node::new((k % 3) as f64 * 2.0, 0.0, (k / 3) as f64 * 2.0)).unwrap();
+            if k != 4 {
+                model.add_support(&format!("N{k}"), Support::pinned()).unwrap();
             }
         }
-        
-        // Count free DOFs
-        let mut restrained = 0;
-        for support in self.supports.values() {
-            restrained += support.num_restrained();
+        for (k, [i, j, m, n]) in [[0, 1, 4, 3], [1, 2, 5, 4], [3, 4, 7, 6], [4, 5, 8, 7]].into_iter().enumerate() {
+            let [i, j, m, n] = [i, j, m, n].map(|c| format!("N{c}"));
+            model.add_plate(&format!("P{k}"), Plate::new(&i, &j, &m, &n, 0.2, "Concrete")).unwrap();
+            model.add_plate_load(&format!("P{k}"), PlateLoad::new(-1000.0, "Case 1")).unwrap();
         }
-        summary.free_dofs = summary.total_dofs - restrained;
+        model.analyze_linear().unwrap();
         
-        Ok(summary)
+        let centre = model.slab_design_moments("P0", "Combo 1", StressPoints::Corners).unwrap()[2];
+        let result = model.plate_stress("P0", "Combo 1").unwrap();
+        assert!(result.mx < 0.0);
+        let stress = model.shell_surface_stresses("P0", "Combo 1", StressPoints::Corners).unwrap()[2];
+        let mx = stress.top.sx * 0.2 * 0.2 / 6.0;
+        assert_relative_eq!(centre.bottom_x, -mx, max_relative = 1e-9);
+        assert_eq!([centre.top_x, centre.top_y], [0.0, 0.0]);
     }
 
-    /// Check if model has been analyzed
-    pub fn is_analyzed(&self) -> bool {
-        self.solution.is_some()
-    }
+    #[test]
+    fn test_cancelled_analysis() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
 
-    /// Get the analysis type used
-    pub fn solution_type(&self) -> Option<AnalysisType> {
-        self.solution
+        let token = CancelToken::new();
+        let options = AnalysisOptions::p_delta().with_cancel_token(token.clone());
+        model.analyze(options.clone()).unwrap();
+
+        // Clones share the flag, and a cancelled analysis leaves no solution behind
+        token.cancel();
+        let mut cancelled = model.clone();
+        assert!(matches!(cancelled.analyze(options), Err(FEAError::Cancelled)));
+        assert!(matches!(cancelled.summary("Combo 1"), Err(FEAError::NotAnalyzed)));
+        let history = TimeHistory::new(0.01, vec![0.0; 10], [1.0, 0.0, 0.0]).with_cancel_token(token);
+        assert!(matches!(model.analyze_time_history(&history), Err(FEAError::Cancelled)));
     }
 
-    /// Get all load combination names
-    pub fn combo_names(&self) -> Vec<String> {
-        self.load_combos.keys().cloned().collect()
+    #[test]
+    fn test_error_diagnostics() {
+        let mut section = Section::rectangular(0.2, 0.4);
+        section.j = 0.0;
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Open", section).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_node("C", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Open")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+
+        // B cannot twist without torsional stiffness and C is not connected at all
+        let Err(FEAError::SingularMatrix(diagnostics)) = model.clone().analyze_linear() else {
+            panic!("expected a singular matrix");
+        };
+        let dofs: Vec<String> = diagnostics.unconstrained_dofs.iter().map(|d| d.to_string()).collect();
+        assert_eq!(dofs, ["B RX", "C DX", "C DY", "C DZ", "C RX", "C RY", "C RZ"]);
+        assert_eq!(diagnostics.weak_members, ["M1"]);
+
+        // A P-Delta run stopped early reports its residual history
+        model.sections.get_mut("Open").unwrap().j = 1e-4;
+        model.add_support("C", Support::fixed()).unwrap();
+        let options = AnalysisOptions::p_delta().with_max_iter(2).with_tolerance(0.0);
+        let Err(FEAError::ConvergenceFailed { iterations, residuals }) = model.analyze(options) else {
+            panic!("expected a convergence failure");
+        };
+        assert_eq!((iterations, residuals.len()), (2, 2));
+        assert!(residuals.iter().all(|r| r.is_finite()));
     }
 
-    /// Get all load case names
-    pub fn load_cases(&self) -> Vec<String> {
-        let mut cases: Vec<String> = Vec::new();
-        
-        for loads in self.node_loads.values() {
-            for load in loads {
-                if !cases.contains(&load.case) {
-                    cases.push(load.case.clone());
-                }
+    #[test]
+    fn test_solver_backends() {
+        let build = || {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+            for (name, x, y) in [("A", 0.0, 0.0), ("B", 0.0, 4.0), ("C", 6.0, 4.0), ("D", 6.0, 0.0)] {
+                model.add_node(name, Node::new(x, y, 0.0)).unwrap();
             }
+            model.add_member("C1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+            model.add_member("B1", Member::new("B", "C", "Steel", "Beam")).unwrap();
+            model.add_member("C2", Member::new("D", "C", "Steel", "Beam")).unwrap();
+            model.add_support("A", Support::fixed()).unwrap();
+            model.add_support("D", Support::fixed()).unwrap();
+            model.add_node_load("B", NodeLoad::fx(20e3, "Case 1")).unwrap();
+            model.add_member_dist_load("B1", DistributedLoad::uniform_downward(10e3, "Case 1")).unwrap();
+            model
+        };
+        let sway = |options: AnalysisOptions| {
+            let mut model = build();
+            model.analyze(options).unwrap();
+            model.node_displacement("C", "Combo 1").unwrap().dx
+        };
+        
+        let dense = sway(AnalysisOptions::linear());
+        assert_relative_eq!(sway(AnalysisOptions::linear().with_solver(SolverBackend::SparseCholesky)), dense, max_relative = 1e-9);
+        assert_relative_eq!(sway(AnalysisOptions::p_delta().with_solver(SolverBackend::SparseCholesky)),
+            sway(AnalysisOptions::p_delta()), max_relative = 1e-9);
+        #[cfg(feature = "faer")]
+        assert_relative_eq!(sway(AnalysisOptions::linear().with_solver(SolverBackend::Faer)), dense, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_rcm_node_numbering() {
+        // A cantilever whose node names alternate between the two ends of
+        // the alphabet, so name order jumps back and forth along the chain
+        let names = ["A", "J", "B", "I", "C", "H", "D", "G", "E", "F"];
+        let (l, p) = (9.0_f64, 1000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for (k, name) in names.iter().enumerate() {
+            model.add_node(name, Node::new(k as f64, 0.0, 0.0)).unwrap();
         }
+        for k in 1..names.len() {
+            model.add_member(&format!("M{k}"), Member::new(names[k - 1], names[k], "Steel", "Beam")).unwrap();
+        }
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("F", NodeLoad::fy(-p, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
         
-        for loads in self.member_dist_loads.values() {
-            for load in loads {
-                if !cases.contains(&load.case) {
-                    cases.push(load.case.clone());
-                }
-            }
+        // Neighbours along the chain get consecutive handles
+        for pair in names.windows(2) {
+            let [a, b] = [pair[0], pair[1]].map(|n| model.node_id(n).unwrap().index());
+            assert_eq!(a.abs_diff(b), 1);
         }
+        let summary = model.summary("Combo 1").unwrap();
+        assert_eq!((summary.original_bandwidth, summary.bandwidth), (59, 11));
         
-        cases.sort();
-        cases
+        let ei = Material::steel().e * Section::rectangular(0.2, 0.4).iz;
+        let dy = model.node_displacement("F", "Combo 1").unwrap().dy;
+        assert_relative_eq!(dy, -p * l.powi(3) / (3.0 * ei), max_relative = 1e-9);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::assert_relative_eq;
 
     #[test]
-    fn test_simple_cantilever() {
+    fn test_modal_cantilever() {
+        // Ten-element cantilever restrained to vibrate in the XY plane:
+        // w_n = (beta_n L)^2 sqrt(EI / (m L^4)), the first mode carrying 61% of the mass.
+        // It is slender enough for the section's rotary inertia not to matter.
+        let (l, elements) = (6.0_f64, 10);
         let mut model = FEModel::new();
-        
-        // Add material
         model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for k in 0..=elements {
+            model.add_node(&format!("N{k}"), Node::new(l * k as f64 / elements as f64, 0.0, 0.0)).unwrap();
+            let support = if k == 0 { Support::fixed() } else { Support::with_restraints(false, false, true, true, true, false) };
+            model.add_support(&format!("N{k}"), support).unwrap();
+        }
+        for k in 1..=elements {
+            model.add_member(&format!("M{k}"), Member::new(&format!("N{}", k - 1), &format!("N{k}"), "Steel", "Beam")).unwrap();
+        }
         
-        // Add section (arbitrary rectangular)
-        model.add_section("Section1", Section::rectangular(0.3, 0.5)).unwrap();
+        let options = AnalysisOptions::modal(3).with_solver(SolverBackend::SparseCholesky);
+        let results = model.analyze_modal(&options).unwrap();
+        assert_eq!(results.modes.len(), 3);
         
-        // Add nodes (10m cantilever)
-        model.add_node("N1", Node::new(0.0, 0.0, 0.0)).unwrap();
-        model.add_node("N2", Node::new(10.0, 0.0, 0.0)).unwrap();
+        let (material, section) = (Material::steel(), Section::rectangular(0.2, 0.4));
+        let scale = (material.e * section.iz / (material.rho * section.a * l.powi(4))).sqrt() / (2.0 * std::f64::consts::PI);
+        assert_relative_eq!(results.modes[0].frequency, 1.875104_f64.powi(2) * scale, max_relative = 0.01);
+        assert_relative_eq!(results.modes[1].frequency, 4.694091_f64.powi(2) * scale, max_relative = 0.03);
+        assert_relative_eq!(results.modes[2].frequency, 7.854757_f64.powi(2) * scale, max_relative = 0.03);
+        assert!(results.modes[0].shape["N10"][1] > 0.0);
         
-        // Add member
-        model.add_member("M1", Member::new("N1", "N2", "Steel", "Section1")).unwrap();
+        // Half an element's mass sits on the fixed node and is excluded
+        let free_fraction = (elements as f64 - 0.5) / elements as f64;
+        assert_relative_eq!(results.modes[0].mass_ratio[1], 0.613 / free_fraction, max_relative = 0.02);
+        assert!(results.cumulative_mass_ratio()[1] > 0.9);
+        assert!(matches!(model.analyze(options), Err(FEAError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_modal_torsion() {
+        // Fixed-free shaft free only to twist: w_n = (2n - 1) pi / (2 L) sqrt(G J / (rho Ip)),
+        // with Ip = Iy + Iz the polar moment of the section
+        let (l, elements) = (3.0_f64, 10);
+        let (material, section) = (Material::steel(), Section::new(0.01, 2e-5, 2e-5, 4e-5));
+        let mut model = FEModel::new();
+        model.add_material("Steel", material.clone()).unwrap();
+        model.add_section("Shaft", section.clone()).unwrap();
+        for k in 0..=elements {
+            model.add_node(&format!("N{k}"), Node::new(l * k as f64 / elements as f64, 0.0, 0.0)).unwrap();
+            let support = if k == 0 { Support::fixed() } else { Support::with_restraints(true, true, true, false, true, true) };
+            model.add_support(&format!("N{k}"), support).unwrap();
+        }
+        for k in 1..=elements {
+            model.add_member(&format!("M{k}"), Member::new(&format!("N{}", k - 1), &format!("N{k}"), "Steel", "Shaft")).unwrap();
+        }
         
-        // Add support (fixed at N1)
-        model.add_support("N1", Support::fixed()).unwrap();
+        let results = model.analyze_modal(&AnalysisOptions::modal(2)).unwrap();
+        let scale = (material.g * section.j / (material.rho * (section.iy + section.iz))).sqrt()
+            / (2.0 * l * 2.0 * std::f64::consts::PI);
+        assert_relative_eq!(results.modes[0].frequency, std::f64::consts::PI * scale, max_relative = 0.01);
+        assert_relative_eq!(results.modes[1].frequency, 3.0 * std::f64::consts::PI * scale, max_relative = 0.02);
+        assert!(results.modes[0].shape["N10"][3] > results.modes[0].shape["N5"][3]);
+        assert_eq!(results.free_mass, [0.0; 3]);
+    }
+
+    #[test]
+    fn test_conditioning_diagnostics() {
+        // A cantilever whose tip member is a stiff "rigid" stub: fine as
+        // built, ill-conditioned once the stub is made a million times stiffer
+        let build = |factor: f64| {
+            let mut model = FEModel::new();
+            model.add_material("Steel", Material::steel()).unwrap();
+            model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+            let beam = Section::rectangular(0.2, 0.4);
+            model.add_section("Stub", Section::new(beam.a * factor, beam.iy * factor, beam.iz * factor, beam.j * factor)).unwrap();
+            for (k, x) in [0.0, 3.0, 6.0, 6.1].into_iter().enumerate() {
+                model.add_node(&format!("N{k}"), Node::new(x, 0.0, 0.0)).unwrap();
+            }
+            model.add_member("M1", Member::new("N0", "N1", "Steel", "Beam")).unwrap();
+            model.add_member("M2", Member::new("N1", "N2", "Steel", "Beam")).unwrap();
+            model.add_member("M3", Member::new("N2", "N3", "Steel", "Stub")).unwrap();
+            model.add_support("N0", Support::fixed()).unwrap();
+            model.add_node_load("N3", NodeLoad::fy(-1000.0, "Case 1")).unwrap();
+            model
+        };
         
-        // Add load (10 kN downward at N2)
-        model.add_node_load("N2", NodeLoad::fy(-10000.0, "Case 1")).unwrap();
+        let report = build(1.0).conditioning_report().unwrap();
+        assert!(!report.is_ill_conditioned());
+        assert_eq!(report.stiffness_ratios[0].stiff_member, "M3");
+        assert_eq!(report.stiffness_ratios[0].node, "N2");
+        assert!(report.warnings().is_empty());
         
-        // Analyze
+        let mut model = build(1e8);
+        model.analyze(AnalysisOptions::linear().with_conditioning_check()).unwrap();
+        let warnings = model.warnings().to_vec();
+        let report = model.conditioning_report().unwrap();
+        assert!(report.is_ill_conditioned(), "{report:?}");
+        assert!(report.softest_dofs.iter().any(|dof| dof.starts_with("N3") || dof.starts_with("N2")));
+        assert!(report.stiffness_ratios[0].ratio > EXTREME_STIFFNESS_RATIO);
+        assert_eq!(warnings, report.warnings());
+    }
+
+    #[test]
+    fn test_load_only_reanalysis_reuses_factorization() {
+        let (l, p) = (3.0_f64, 1000.0);
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(l, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-p, "Case 1")).unwrap();
         model.analyze_linear().unwrap();
+        let factorization = |model: &FEModel| model.stiffness_cache.as_ref().unwrap().factorization.clone().unwrap();
+        let first = factorization(&model);
         
-        // Check results
-        let disp = model.node_displacement("N2", "Combo 1").unwrap();
+        // A load edit solves with the same factorisation
+        model.add_node_load("B", NodeLoad::fy(-p, "Case 1")).unwrap();
+        model.analyze_linear().unwrap();
+        assert!(Arc::ptr_eq(&first, &factorization(&model)));
+        let ei = Material::steel().e * Section::rectangular(0.2, 0.4).iz;
+        let dy = model.node_displacement("B", "Combo 1").unwrap().dy;
+        assert_relative_eq!(dy, -2.0 * p * l.powi(3) / (3.0 * ei), max_relative = 1e-9);
         
-        // Tip deflection should be negative (downward)
-        assert!(disp.dy < 0.0, "Expected negative Y displacement");
+        // A stiffness edit, even through the public fields, refactorises
+        model.sections.get_mut("Beam").unwrap().iz *= 2.0;
+        model.analyze_linear().unwrap();
+        assert!(!Arc::ptr_eq(&first, &factorization(&model)));
+        let dy = model.node_displacement("B", "Combo 1").unwrap().dy;
+        assert_relative_eq!(dy, -p * l.powi(3) / (3.0 * ei), max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_single_combo() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Dead")).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-500.0, "Live")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.2D").with_case("Dead", 1.2)).unwrap();
+        model.add_load_combo(LoadCombination::new("D+L").with_case("Dead", 1.0).with_case("Live", 1.0)).unwrap();
         
-        // Check reactions
-        let rxn = model.node_reactions("N1", "Combo 1").unwrap();
-        assert_relative_eq!(rxn.fy, 10000.0, epsilon = 1.0); // Should equal applied load
+        // Only the requested combination is solved
+        model.analyze_combo("D+L", AnalysisOptions::linear()).unwrap();
+        assert!(matches!(model.node_displacement("B", "1.2D"), Err(FEAError::NotAnalyzed)));
+        let factorization = model.stiffness_cache.as_ref().unwrap().factorization.clone().unwrap();
+        
+        // Switching combinations reuses the factorisation and matches a full analysis
+        model.analyze_combo("1.2D", AnalysisOptions::linear()).unwrap();
+        assert!(Arc::ptr_eq(&factorization, model.stiffness_cache.as_ref().unwrap().factorization.as_ref().unwrap()));
+        let lazy = [model.node_displacement("B", "1.2D").unwrap().dy, model.node_displacement("B", "D+L").unwrap().dy];
+        model.analyze_linear().unwrap();
+        assert_eq!(lazy, [model.node_displacement("B", "1.2D").unwrap().dy, model.node_displacement("B", "D+L").unwrap().dy]);
+        assert!(matches!(
+            model.analyze_combo("Wind", AnalysisOptions::linear()),
+            Err(FEAError::LoadCombinationNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_analyze_tagged_combos() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-1000.0, "Dead")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.4D").with_case("Dead", 1.4).with_tag("strength")).unwrap();
+        model.add_load_combo(LoadCombination::new("D").with_case("Dead", 1.0).with_tag("service")).unwrap();
+
+        model.analyze(AnalysisOptions::linear().with_tags(vec!["service".to_string()])).unwrap();
+        assert!(model.node_displacement("B", "D").is_ok());
+        assert!(matches!(model.node_displacement("B", "1.4D"), Err(FEAError::NotAnalyzed)));
+        assert!(matches!(
+            model.analyze(AnalysisOptions::linear().with_tags(vec!["seismic".to_string()])),
+            Err(FEAError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_compact_result_store() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(3.0, 0.0, 0.0)).unwrap();
+        model.add_node("C", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_member("M2", Member::new("B", "C", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("C", NodeLoad::fy(-1000.0, "Dead")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.4D").with_case("Dead", 1.4)).unwrap();
+        model.add_load_combo(LoadCombination::new("D").with_case("Dead", 1.0)).unwrap();
+        model.analyze_linear().unwrap();
+
+        // One f64 per value plus a flag per row and combination, reactions only
+        // at A; the flag vectors round up to at least 8 bytes each
+        let values = 2 * 8 * (3 * 6 + 6 + 2 * 2 * 12);
+        let bytes = model.result_store().heap_bytes();
+        assert!(bytes >= values + 2 * (3 + 1 + 2 * 2) && bytes <= values + 4 * 8, "{bytes}");
+        assert_eq!(model.result_store().reactions.rows().len(), 1);
+
+        // Restored results survive renumbering when the model is prepared again
+        let dy = model.node_displacement("C", "D").unwrap().dy;
+        let mut restored = FEModel::from_json(&model.to_json_with_results().unwrap()).unwrap();
+        restored.prepare().unwrap();
+        assert_eq!(restored.node_displacement("C", "D").unwrap().dy, dy);
+        assert_eq!(restored.member_local_forces("M2", "1.4D").unwrap(), model.member_local_forces("M2", "1.4D").unwrap());
+    }
+
+    #[test]
+    fn test_stepped_analysis_matches_analyze() {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", Section::rectangular(0.2, 0.4)).unwrap();
+        for i in 0..=5 {
+            model.add_node(&format!("N{i}"), Node::new(i as f64, 0.0, 0.0)).unwrap();
+        }
+        for i in 0..5 {
+            model.add_member(&format!("M{i}"), Member::new(&format!("N{i}"), &format!("N{}", i + 1), "Steel", "Beam")).unwrap();
+        }
+        model.add_support("N0", Support::fixed()).unwrap();
+        model.add_node_load("N5", NodeLoad::fy(-1000.0, "Dead")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.4D").with_case("Dead", 1.4)).unwrap();
+        model.add_load_combo(LoadCombination::new("D").with_case("Dead", 1.0)).unwrap();
+        let mut reference = model.clone();
+        reference.analyze_linear().unwrap();
+        
+        // Prepare, three chunks of at most two members, then one step per combination
+        let mut run = SteppedAnalysis::new(AnalysisOptions::linear()).with_chunk_size(2);
+        let mut steps = Vec::new();
+        while !run.is_finished() {
+            assert!(!model.is_analyzed());
+            steps.push(model.step_analysis(&mut run).unwrap());
+        }
+        let stages: Vec<AnalysisStage> = steps.iter().map(|p| p.stage).collect();
+        assert_eq!(stages, [
+            AnalysisStage::Prepare, AnalysisStage::Assemble, AnalysisStage::Assemble, AnalysisStage::Assemble,
+            AnalysisStage::Solve, AnalysisStage::Solve,
+        ]);
+        assert_eq!(steps[4].combo.as_deref(), Some("1.4D"));
+        assert_eq!(steps.last().unwrap().fraction(), 1.0);
+        for combo in ["1.4D", "D"] {
+            assert_eq!(model.node_displacement("N5", combo).unwrap().dy, reference.node_displacement("N5", combo).unwrap().dy);
+            assert_eq!(model.member_local_forces("M0", combo).unwrap(), reference.member_local_forces("M0", combo).unwrap());
+        }
+        assert!(matches!(model.step_analysis(&mut run), Err(FEAError::InvalidInput(_))));
+        
+        // Unchanged stiffness: no assembly steps
+        let mut run = SteppedAnalysis::new(AnalysisOptions::linear());
+        let progress = model.step_analysis(&mut run).unwrap();
+        assert_eq!((progress.completed, progress.total), (1, 3));
     }
 }
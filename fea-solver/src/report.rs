@@ -0,0 +1,635 @@
+//! Calculation reports
+//!
+//! [`generate`] collects a model summary, the load cases and combinations,
+//! key results, design check tables and optional SVG diagrams into a
+//! structured [`Report`], which renders as Markdown or standalone HTML for
+//! calculation packages:
+//!
+//! ```rust
+//! use fea_solver::prelude::*;
+//! use fea_solver::report;
+//!
+//! let model = FEModel::new();
+//! let report = report::generate(&model, &ReportOptions::new("Footbridge").with_diagrams(true)).unwrap();
+//! let markdown = report.to_markdown();
+//! # assert!(markdown.starts_with("# Footbridge"));
+//! ```
+//!
+//! Values are reported in the model's units, stated in its summary.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::design::deflection::DeflectionResults;
+use crate::design::DesignResults;
+use crate::error::FEAResult;
+use crate::math;
+use crate::model::FEModel;
+
+/// What to include in a report
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Report title
+    pub title: String,
+    /// Combinations to report results for (None = every analysed combination)
+    pub combos: Option<Vec<String>>,
+    /// Whether to list the support reactions of each combination
+    pub reactions: bool,
+    /// Whether to draw the deformed shape of each combination
+    pub diagrams: bool,
+    /// Member design results, by code name
+    pub design: Vec<(String, DesignResults)>,
+    /// Deflection check results
+    pub deflections: Option<DeflectionResults>,
+}
+
+impl ReportOptions {
+    /// Options with a title, reporting reactions but no diagrams or design checks
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            reactions: true,
+            ..Default::default()
+        }
+    }
+
+    /// Report results for these combinations only
+    pub fn with_combos(mut self, combos: &[&str]) -> Self {
+        self.combos = Some(combos.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Set whether support reactions are listed
+    pub fn with_reactions(mut self, reactions: bool) -> Self {
+        self.reactions = reactions;
+        self
+    }
+
+    /// Set whether deformed shape diagrams are drawn
+    pub fn with_diagrams(mut self, diagrams: bool) -> Self {
+        self.diagrams = diagrams;
+        self
+    }
+
+    /// Add the results of a member design code, e.g. from [`CodeCheck::check_model`]
+    ///
+    /// [`CodeCheck::check_model`]: crate::design::CodeCheck::check_model
+    pub fn with_design(mut self, code: &str, results: DesignResults) -> Self {
+        self.design.push((code.to_string(), results));
+        self
+    }
+
+    /// Add deflection check results
+    pub fn with_deflections(mut self, results: DeflectionResults) -> Self {
+        self.deflections = Some(results);
+        self
+    }
+}
+
+/// A calculation report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// Report title
+    pub title: String,
+    /// Sections in order
+    pub sections: Vec<ReportSection>,
+}
+
+/// A headed section of a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSection {
+    /// Section heading
+    pub heading: String,
+    /// Content in order
+    pub blocks: Vec<ReportBlock>,
+}
+
+/// A piece of report content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReportBlock {
+    /// Plain text
+    Paragraph(String),
+    /// Table of values
+    Table(ReportTable),
+    /// SVG drawing with a caption
+    Figure {
+        /// Figure caption
+        caption: String,
+        /// Standalone SVG document
+        svg: String,
+    },
+}
+
+/// A captioned table of formatted values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTable {
+    /// Table caption
+    pub caption: String,
+    /// Column headers
+    pub headers: Vec<String>,
+    /// Rows of cells, one per header
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ReportTable {
+    fn new(caption: &str, headers: &[&str]) -> Self {
+        Self {
+            caption: caption.to_string(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// Generate a report of a model and its results
+///
+/// Result sections only cover analysed combinations, so an unanalysed
+/// model still gets its model summary and loading.
+pub fn generate(model: &FEModel, options: &ReportOptions) -> FEAResult<Report> {
+    let mut sections = vec![model_summary(model), load_cases(model), load_combinations(model)];
+
+    let combos: Vec<String> = options.combos.clone()
+        .unwrap_or_else(|| model.combo_names())
+        .into_iter()
+        .filter(|combo| is_analyzed(model, combo))
+        .collect();
+    if !combos.is_empty() {
+        sections.push(results(model, &combos, options.reactions)?);
+    }
+    for (code, design) in &options.design {
+        sections.push(design_checks(code, design));
+    }
+    if let Some(deflections) = &options.deflections {
+        sections.push(deflection_checks(deflections));
+    }
+    if options.diagrams && !combos.is_empty() {
+        let mut blocks = Vec::new();
+        for combo in &combos {
+            let (svg, scale) = deformed_shape(model, combo)?;
+            blocks.push(ReportBlock::Figure {
+                caption: format!("Deformed shape, {combo} (displacements × {})", number(scale)),
+                svg,
+            });
+        }
+        sections.push(ReportSection { heading: "Diagrams".to_string(), blocks });
+    }
+
+    Ok(Report { title: options.title.clone(), sections })
+}
+
+impl Report {
+    /// Render as Markdown, with figures as inline SVG
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# {}\n", self.title);
+        for section in &self.sections {
+            let _ = write!(md, "\n## {}\n", section.heading);
+            for block in &section.blocks {
+                match block {
+                    ReportBlock::Paragraph(text) => {
+                        let _ = write!(md, "\n{text}\n");
+                    }
+                    ReportBlock::Table(table) => {
+                        let row = |cells: &[String]| {
+                            let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+                            format!("| {} |\n", cells.join(" | "))
+                        };
+                        let _ = write!(md, "\n**{}**\n\n", table.caption);
+                        md.push_str(&row(&table.headers));
+                        let _ = writeln!(md, "|{}", "---|".repeat(table.headers.len()));
+                        for cells in &table.rows {
+                            md.push_str(&row(cells));
+                        }
+                    }
+                    ReportBlock::Figure { caption, svg } => {
+                        let _ = write!(md, "\n<figure>\n{svg}\n<figcaption>{}</figcaption>\n</figure>\n", escape(caption));
+                    }
+                }
+            }
+        }
+        md
+    }
+
+    /// Render as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(html, "<title>{}</title>", escape(&self.title));
+        html.push_str(concat!(
+            "<style>\n",
+            "body { font-family: sans-serif; margin: 2em; }\n",
+            "table { border-collapse: collapse; margin: 1em 0; }\n",
+            "caption { font-weight: bold; text-align: left; }\n",
+            "th, td { border: 1px solid #999; padding: 0.2em 0.6em; text-align: right; }\n",
+            "th:first-child, td:first-child { text-align: left; }\n",
+            "</style>\n</head>\n<body>\n",
+        ));
+        let _ = writeln!(html, "<h1>{}</h1>", escape(&self.title));
+        for section in &self.sections {
+            let _ = writeln!(html, "<h2>{}</h2>", escape(&section.heading));
+            for block in &section.blocks {
+                match block {
+                    ReportBlock::Paragraph(text) => {
+                        let _ = writeln!(html, "<p>{}</p>", escape(text));
+                    }
+                    ReportBlock::Table(table) => {
+                        let _ = writeln!(html, "<table>\n<caption>{}</caption>", escape(&table.caption));
+                        let row = |tag: &str, cells: &[String]| {
+                            let cells: String = cells.iter().map(|c| format!("<{tag}>{}</{tag}>", escape(c))).collect();
+                            format!("<tr>{cells}</tr>\n")
+                        };
+                        html.push_str(&row("th", &table.headers));
+                        for cells in &table.rows {
+                            html.push_str(&row("td", cells));
+                        }
+                        html.push_str("</table>\n");
+                    }
+                    ReportBlock::Figure { caption, svg } => {
+                        let _ = writeln!(html, "<figure>\n{svg}\n<figcaption>{}</figcaption>\n</figure>", escape(caption));
+                    }
+                }
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Element counts, materials and sections
+fn model_summary(model: &FEModel) -> ReportSection {
+    let mut blocks = vec![ReportBlock::Paragraph(format!("Units: {}", model.units))];
+    if let Some(analysis) = model.solution_type() {
+        blocks.push(ReportBlock::Paragraph(format!("Analysis type: {analysis:?}")));
+    }
+    for warning in model.warnings() {
+        blocks.push(ReportBlock::Paragraph(format!("Warning: {warning}")));
+    }
+
+    let mut counts = ReportTable::new("Model size", &["Item", "Count"]);
+    for (item, count) in [
+        ("Nodes", model.nodes.len()),
+        ("Members", model.members.len()),
+        ("Physical members", model.physical_members.len()),
+        ("Plates", model.plates.len()),
+        ("Quads", model.quads.len()),
+        ("Tetras", model.tetras.len()),
+        ("Supports", model.supports.len()),
+        ("Links", model.rigid_links.len() + model.gaps.len() + model.links.len()),
+    ] {
+        if count > 0 {
+            counts.rows.push(vec![item.to_string(), count.to_string()]);
+        }
+    }
+    blocks.push(ReportBlock::Table(counts));
+
+    let mut materials = ReportTable::new("Materials", &["Material", "E", "G", "ν", "ρ"]);
+    for (name, m) in &model.materials {
+        materials.rows.push(vec![name.clone(), number(m.e), number(m.g), number(m.nu), number(m.rho)]);
+    }
+    let mut sections = ReportTable::new("Sections", &["Section", "A", "Iy", "Iz", "J"]);
+    for (name, s) in &model.sections {
+        sections.rows.push(vec![name.clone(), number(s.a), number(s.iy), number(s.iz), number(s.j)]);
+    }
+    for table in [materials, sections] {
+        if !table.rows.is_empty() {
+            blocks.push(ReportBlock::Table(table));
+        }
+    }
+
+    ReportSection { heading: "Model summary".to_string(), blocks }
+}
+
+/// Number of loads of each kind in each load case
+fn load_cases(model: &FEModel) -> ReportSection {
+    let mut counts: BTreeMap<String, [usize; 3]> = model.load_cases().into_iter().map(|c| (c, [0; 3])).collect();
+    let mut count = |case: &str, kind: usize| counts.entry(case.to_string()).or_default()[kind] += 1;
+    model.node_loads.values().flatten().for_each(|l| count(&l.case, 0));
+    model.member_point_loads.values().flatten().for_each(|l| count(&l.case, 1));
+    model.member_dist_loads.values().flatten().for_each(|l| count(&l.case, 1));
+    model.member_prestress_loads.values().flatten().for_each(|l| count(&l.case, 1));
+    model.plate_loads.values().flatten().for_each(|l| count(&l.case, 2));
+    model.plate_point_loads.values().flatten().for_each(|l| count(&l.case, 2));
+    model.plate_thermal_loads.values().flatten().for_each(|l| count(&l.case, 2));
+
+    let mut table = ReportTable::new("Load cases", &["Case", "Node loads", "Member loads", "Plate loads"]);
+    for (case, [nodes, members, plates]) in counts {
+        table.rows.push(vec![case, nodes.to_string(), members.to_string(), plates.to_string()]);
+    }
+    ReportSection { heading: "Load cases".to_string(), blocks: vec![ReportBlock::Table(table)] }
+}
+
+/// Factored definition and tags of each combination
+fn load_combinations(model: &FEModel) -> ReportSection {
+    let mut table = ReportTable::new("Load combinations", &["Combination", "Definition", "Tags"]);
+    for (name, combo) in &model.load_combos {
+        let definition: Vec<String> = combo.factors.iter()
+            .map(|(case, factor)| format!("{factor} {case}"))
+            .collect();
+        table.rows.push(vec![name.clone(), definition.join(" + "), combo.tags.join(", ")]);
+    }
+    ReportSection { heading: "Load combinations".to_string(), blocks: vec![ReportBlock::Table(table)] }
+}
+
+/// Extreme results and support reactions of each combination
+fn results(model: &FEModel, combos: &[String], reactions: bool) -> FEAResult<ReportSection> {
+    let mut extremes = ReportTable::new("Extreme results", &[
+        "Combination", "Max displacement", "Node", "Max reaction", "Node", "Max axial", "Member",
+        "Max moment", "Member", "Max deflection", "Member",
+    ]);
+    let mut blocks = Vec::new();
+    for combo in combos {
+        let s = model.summary(combo)?;
+        // The summary only sees member end moments; span moments and deflections come from stations
+        let (mut moment, mut moment_member) = (0.0, String::new());
+        let (mut deflection, mut deflection_member) = (0.0, String::new());
+        for (name, member) in &model.members {
+            let length = member.length().unwrap_or(0.0);
+            for k in 0..11 {
+                let forces = model.member_forces_at(name, length * k as f64 / 10.0, combo)?;
+                let m = forces.moment_y.abs().max(forces.moment_z.abs());
+                if m > moment {
+                    (moment, moment_member) = (m, name.clone());
+                }
+            }
+            for d in model.member_deflections(name, combo, 11)? {
+                if d.relative() > deflection {
+                    (deflection, deflection_member) = (d.relative(), name.clone());
+                }
+            }
+        }
+        extremes.rows.push(vec![
+            combo.clone(),
+            number(s.max_displacement), s.max_disp_node,
+            number(s.max_reaction), s.max_reaction_node,
+            number(s.max_axial), s.max_axial_member,
+            number(moment), moment_member,
+            number(deflection), deflection_member,
+        ]);
+
+        if reactions && !model.supports.is_empty() {
+            let values = model.supports.keys()
+                .map(|node| model.node_reactions(node, combo).map(|r| [r.fx, r.fy, r.fz, r.mx, r.my, r.mz]))
+                .collect::<FEAResult<Vec<_>>>()?;
+            // Round-off in otherwise zero reactions is shown as zero
+            let tolerance = 1e-9 * values.iter().flatten().fold(0.0, |max: f64, v| max.max(v.abs()));
+            let cell = |v: f64| number(if v.abs() < tolerance { 0.0 } else { v });
+
+            let mut table = ReportTable::new(
+                &format!("Support reactions, {combo}"),
+                &["Node", "FX", "FY", "FZ", "MX", "MY", "MZ"],
+            );
+            let mut total = [0.0; 3];
+            for (node, values) in model.supports.keys().zip(&values) {
+                for k in 0..3 {
+                    total[k] += values[k];
+                }
+                table.rows.push(std::iter::once(node.clone()).chain(values.iter().map(|v| cell(*v))).collect());
+            }
+            table.rows.push(std::iter::once("Total".to_string())
+                .chain(total.iter().map(|v| cell(*v)))
+                .chain(std::iter::repeat_n(String::new(), 3))
+                .collect());
+            blocks.push(ReportBlock::Table(table));
+        }
+    }
+    blocks.insert(0, ReportBlock::Table(extremes));
+    Ok(ReportSection { heading: "Results".to_string(), blocks })
+}
+
+/// Governing check of each member for each combination
+fn design_checks(code: &str, design: &DesignResults) -> ReportSection {
+    let mut table = ReportTable::new(code, &["Member", "Combination", "Check", "Clause", "Ratio", "Status"]);
+    let mut passing = 0;
+    for (member, combos) in design {
+        passing += combos.values().all(|r| r.passes()) as usize;
+        for (combo, result) in combos {
+            let (check, clause) = result.governing().map_or(("-", "-"), |c| (c.name.as_str(), c.clause.as_str()));
+            table.rows.push(vec![
+                member.clone(),
+                combo.clone(),
+                check.to_string(),
+                clause.to_string(),
+                format!("{:.3}", result.ratio()),
+                status(result.passes()).to_string(),
+            ]);
+        }
+    }
+    ReportSection {
+        heading: format!("Design checks, {code}"),
+        blocks: vec![
+            ReportBlock::Paragraph(format!("{passing} of {} members pass.", design.len())),
+            ReportBlock::Table(table),
+        ],
+    }
+}
+
+/// Governing deflection check of each member for each combination tag
+fn deflection_checks(deflections: &DeflectionResults) -> ReportSection {
+    let mut table = ReportTable::new(
+        "Deflections relative to the member chord",
+        &["Member", "Tag", "Combination", "Limit", "Deflection", "Allowable", "Ratio", "Status"],
+    );
+    for (member, tags) in deflections {
+        for (tag, check) in tags {
+            table.rows.push(vec![
+                member.clone(),
+                tag.clone(),
+                check.combo.clone(),
+                check.limit.to_string(),
+                number(check.deflection),
+                number(check.allowable),
+                format!("{:.3}", check.ratio),
+                status(check.passes()).to_string(),
+            ]);
+        }
+    }
+    ReportSection { heading: "Deflection checks".to_string(), blocks: vec![ReportBlock::Table(table)] }
+}
+
+/// Elevation of the undeformed and deformed members and shell edges, and the displacement scale
+///
+/// The model is viewed along global Z, or along X when it is deeper in Z
+/// than it is wide in X, with Y up.
+fn deformed_shape(model: &FEModel, combo_name: &str) -> FEAResult<(String, f64)> {
+    // (undeformed points, displacements) of each polyline
+    let mut lines = Vec::new();
+    for (name, member) in &model.members {
+        let i = model.nodes[&member.i_node].coords();
+        let j = model.nodes[&member.j_node].coords();
+        let t = math::member_transformation_matrix(&i, &j, member.rotation);
+        let axis = |row: usize| [t[(row, 0)], t[(row, 1)], t[(row, 2)]];
+        let (x_axis, y_axis, z_axis) = (axis(0), axis(1), axis(2));
+        let axial = |node: &str| -> FEAResult<f64> {
+            let d = model.node_displacement(node, combo_name)?;
+            Ok(d.dx * x_axis[0] + d.dy * x_axis[1] + d.dz * x_axis[2])
+        };
+        let (axial_i, axial_j) = (axial(&member.i_node)?, axial(&member.j_node)?);
+        let deflections = model.member_deflections(name, combo_name, 11)?;
+        let length = deflections.last().map_or(1.0, |d| d.x);
+
+        let mut undeformed = Vec::new();
+        let mut displacements: Vec<[f64; 3]> = Vec::new();
+        for d in deflections {
+            let s = d.x / length;
+            let u = axial_i + (axial_j - axial_i) * s;
+            let point: [f64; 3] = std::array::from_fn(|k| i[k] + (j[k] - i[k]) * s);
+            undeformed.push(point);
+            displacements.push(std::array::from_fn(|k| u * x_axis[k] + d.dy * y_axis[k] + d.dz * z_axis[k]));
+        }
+        lines.push((undeformed, displacements));
+    }
+    let shells = model.plates.values().map(|p| [&p.i_node, &p.j_node, &p.m_node, &p.n_node])
+        .chain(model.quads.values().map(|q| [&q.i_node, &q.j_node, &q.m_node, &q.n_node]));
+    for nodes in shells {
+        let mut undeformed = Vec::new();
+        let mut displacements = Vec::new();
+        for node in nodes.iter().chain(std::iter::once(&nodes[0])) {
+            let d = model.node_displacement(node, combo_name)?;
+            undeformed.push(model.nodes[*node].coords());
+            displacements.push([d.dx, d.dy, d.dz]);
+        }
+        lines.push((undeformed, displacements));
+    }
+
+    let points = || lines.iter().flat_map(|(u, _)| u.iter());
+    let extent = |k: usize| {
+        let (lo, hi) = points().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p[k]), hi.max(p[k])));
+        (lo, (hi - lo).max(0.0))
+    };
+    let horizontal = if extent(2).1 > extent(0).1 { 2 } else { 0 };
+    let ((h0, width), (v0, height)) = (extent(horizontal), extent(1));
+    let size = width.max(height).max(1e-9);
+    let max_displacement = lines.iter()
+        .flat_map(|(_, d)| d.iter())
+        .map(|d| (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt())
+        .fold(0.0, f64::max);
+    let scale = if max_displacement > 0.0 { 0.1 * size / max_displacement } else { 1.0 };
+
+    // 480 px across the larger model dimension, with a 10% margin for the deformation
+    let (px, margin) = (480.0 / size, 0.15 * 480.0);
+    let (svg_width, svg_height) = (width * px + 2.0 * margin, height * px + 2.0 * margin);
+    let screen = |p: &[f64; 3]| (margin + (p[horizontal] - h0) * px, svg_height - margin - (p[1] - v0) * px);
+    let polyline = |points: &mut dyn Iterator<Item = [f64; 3]>, colour: &str| {
+        let points: Vec<String> = points.map(|p| {
+            let (x, y) = screen(&p);
+            format!("{x:.1},{y:.1}")
+        }).collect();
+        format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"1.5\"/>\n", points.join(" "))
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{svg_width:.0}\" height=\"{svg_height:.0}\" viewBox=\"0 0 {svg_width:.0} {svg_height:.0}\">\n"
+    );
+    for (undeformed, _) in &lines {
+        svg.push_str(&polyline(&mut undeformed.iter().copied(), "#aaaaaa"));
+    }
+    for (undeformed, displacements) in &lines {
+        let mut shifted = undeformed.iter().zip(displacements).map(|(p, d)| std::array::from_fn(|k| p[k] + scale * d[k]));
+        svg.push_str(&polyline(&mut shifted, "#cc0000"));
+    }
+    svg.push_str("</svg>");
+    Ok((svg, scale))
+}
+
+/// Whether a combination has results
+fn is_analyzed(model: &FEModel, combo_name: &str) -> bool {
+    model.nodes.keys().next().is_some_and(|node| model.node_displacement(node, combo_name).is_ok())
+}
+
+fn status(passes: bool) -> &'static str {
+    if passes { "OK" } else { "FAIL" }
+}
+
+/// A value to 4 significant figures, in scientific notation when very large or small
+fn number(value: f64) -> String {
+    let magnitude = value.abs();
+    if value == 0.0 {
+        "0".to_string()
+    } else if !value.is_finite() {
+        format!("{value}")
+    } else if (1e-3..1e6).contains(&magnitude) {
+        let decimals = (3 - magnitude.log10().floor() as i32).max(0) as usize;
+        format!("{value:.decimals$}")
+    } else {
+        format!("{value:.3e}")
+    }
+}
+
+/// Escape text for HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::design::aisc::Aisc360;
+    use crate::design::deflection::DeflectionChecks;
+    use crate::design::{CodeCheck, DesignSettings};
+    use crate::elements::{Material, Member, Node, Section, Support};
+    use crate::loads::{DistributedLoad, LoadCombination};
+
+    fn beam() -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::wide_flange(0.3, 0.15, 0.012, 0.008)).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(6.0, 0.0, 0.0)).unwrap();
+        model.add_member("B1", Member::new("A", "B", "Steel", "W").with_rotation(std::f64::consts::FRAC_PI_2)).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("B", Support::with_restraints(false, true, true, false, false, false)).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(5_000.0, "Live")).unwrap();
+        model.add_load_combo(LoadCombination::new("1.6L").with_case("Live", 1.6)).unwrap();
+        model.add_load_combo(LoadCombination::single("L", "Live").with_tag("live")).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let mut model = beam();
+        let unanalyzed = generate(&model, &ReportOptions::new("Beam")).unwrap();
+        let headings: Vec<&str> = unanalyzed.sections.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(headings, ["Model summary", "Load cases", "Load combinations"]);
+
+        model.analyze_linear().unwrap();
+        let options = ReportOptions::new("Beam")
+            .with_design("AISC 360-16", Aisc360::new().check_model(&model, &DesignSettings::new()).unwrap())
+            .with_deflections(DeflectionChecks::standard().check_model(&model).unwrap());
+        let md = generate(&model, &options).unwrap().to_markdown();
+
+        assert!(md.contains("| 1.6L | 1.6 Live |  |"));
+        assert!(md.contains("| L | 1 Live | live |"));
+        // Half the total factored load at each support
+        assert!(md.contains("| A | 0 | 24000 | 0 | 0 | 0 | 0 |"));
+        assert!(md.contains("| Total | 0 | 48000 | 0 |  |  |  |"));
+        // Midspan moment w L^2 / 8 of the factored load
+        assert!(md.contains("| 1.6L | 0 |  | 24000 | B | 0 |  | 36000 | B1 |"));
+        assert!(md.contains("## Design checks, AISC 360-16"));
+        assert!(md.contains("1 of 1 members pass."));
+        assert!(md.contains("| B1 | live | L | L/360 |"));
+        assert!(!md.contains("<svg"));
+    }
+
+    #[test]
+    fn test_html_report_with_diagrams() {
+        let mut model = beam();
+        model.analyze_linear().unwrap();
+        let report = generate(&model, &ReportOptions::new("<Beam>").with_combos(&["L"]).with_diagrams(true)).unwrap();
+
+        let diagrams = report.sections.last().unwrap();
+        assert_eq!(diagrams.heading, "Diagrams");
+        let ReportBlock::Figure { svg, .. } = &diagrams.blocks[0] else { panic!("expected a figure") };
+        // Undeformed and deformed beam, 11 stations each
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert_eq!(svg.matches(',').count(), 22);
+
+        let html = report.to_html();
+        assert!(html.contains("<h1>&lt;Beam&gt;</h1>"));
+        assert!(html.contains("<caption>Support reactions, L</caption>"));
+        assert!(!html.contains("Support reactions, 1.6L"));
+        assert_eq!(html.matches("<table>").count(), report.sections.iter()
+            .flat_map(|s| &s.blocks)
+            .filter(|b| matches!(b, ReportBlock::Table(_)))
+            .count());
+    }
+}
@@ -2,6 +2,14 @@
 
 use serde::{Deserialize, Serialize};
 
+mod cut;
+mod db;
+mod store;
+
+pub use cut::{SectionCut, SectionCutResult};
+pub use db::{Extreme, MemberResult, NodeResult, MEMBER_STATIONS, Quantity, ResultFilter, ResultsDb, ShellResult};
+pub use store::{ResultStore, ResultTable};
+
 /// Displacement results at a node
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct NodeDisplacement {
@@ -114,6 +122,23 @@ impl MemberForces {
         }
     }
 
+    /// Create from array [axial, shear_y, shear_z, torsion, moment_y, moment_z]
+    pub fn from_array(arr: [f64; 6]) -> Self {
+        Self {
+            axial: arr[0],
+            shear_y: arr[1],
+            shear_z: arr[2],
+            torsion: arr[3],
+            moment_y: arr[4],
+            moment_z: arr[5],
+        }
+    }
+
+    /// Convert to array [axial, shear_y, shear_z, torsion, moment_y, moment_z]
+    pub fn to_array(&self) -> [f64; 6] {
+        [self.axial, self.shear_y, self.shear_z, self.torsion, self.moment_y, self.moment_z]
+    }
+
     /// Create from local force array at j-node
     pub fn from_j_node_forces(forces: &[f64; 12]) -> Self {
         Self {
@@ -127,6 +152,63 @@ impl MemberForces {
     }
 }
 
+/// Transverse deflection at a point along a member, in member local axes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MemberDeflection {
+    /// Distance from the i-node
+    pub x: f64,
+    /// Displacement in local y direction
+    pub dy: f64,
+    /// Displacement in local z direction
+    pub dz: f64,
+    /// Local y deflection relative to the chord between the displaced member ends
+    pub relative_y: f64,
+    /// Local z deflection relative to the chord between the displaced member ends
+    pub relative_z: f64,
+}
+
+impl MemberDeflection {
+    /// Magnitude of the deflection relative to the chord
+    pub fn relative(&self) -> f64 {
+        self.relative_y.hypot(self.relative_z)
+    }
+}
+
+/// Maximum and minimum of a result over the combinations of a load envelope
+///
+/// Each component is enveloped independently, so `max` and `min` generally
+/// come from different load combinations.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Envelope<T> {
+    /// Component-wise maximum
+    pub max: T,
+    /// Component-wise minimum
+    pub min: T,
+}
+
+impl Envelope<[f64; 6]> {
+    /// Envelope a set of result arrays (None if the set is empty)
+    pub fn from_arrays(values: impl IntoIterator<Item = [f64; 6]>) -> Option<Self> {
+        let mut env: Option<Self> = None;
+        for value in values {
+            let e = env.get_or_insert(Self { max: value, min: value });
+            for (i, &v) in value.iter().enumerate() {
+                e.max[i] = e.max[i].max(v);
+                e.min[i] = e.min[i].min(v);
+            }
+        }
+        env
+    }
+
+    /// Convert both bounds with the given constructor
+    pub fn map<T>(self, f: impl Fn([f64; 6]) -> T) -> Envelope<T> {
+        Envelope {
+            max: f(self.max),
+            min: f(self.min),
+        }
+    }
+}
+
 /// Stress results in a plate/shell element
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PlateStress {
@@ -178,6 +260,11 @@ impl PlateStressResult {
         // Von Mises at surface
         (sx_total.powi(2) - sx_total * sy_total + sy_total.powi(2) + 3.0 * self.txy.powi(2)).sqrt()
     }
+
+    /// Wood-Armer reinforcement design moments from the bending moments
+    pub fn design_moments(&self) -> SlabDesignMoments {
+        SlabDesignMoments::from_moments(self.mx, self.my, self.mxy)
+    }
 }
 
 impl PlateStress {
@@ -202,6 +289,244 @@ impl PlateStress {
     }
 }
 
+/// Points of a plate or quad at which stresses are recovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StressPoints {
+    /// The i, j, m and n corners
+    Corners,
+    /// The 2 x 2 Gauss points, nearest the i, j, m and n corners in turn
+    GaussPoints,
+}
+
+impl StressPoints {
+    /// Natural coordinates (r, s) of the points, with the i-node at (-1, -1)
+    pub fn natural_coordinates(&self) -> [[f64; 2]; 4] {
+        let g = match self {
+            StressPoints::Corners => 1.0,
+            StressPoints::GaussPoints => 1.0 / 3f64.sqrt(),
+        };
+        [[-g, -g], [g, -g], [g, g], [-g, g]]
+    }
+}
+
+/// Stresses on the top (local +z) and bottom (local -z) faces of a plate or quad
+///
+/// Each face combines the membrane stress with the bending stress 6M/t^2,
+/// with moments positive when they put the top face in tension.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ShellSurfaceStress {
+    /// Top face stresses
+    pub top: PlateStress,
+    /// Bottom face stresses
+    pub bottom: PlateStress,
+}
+
+impl ShellSurfaceStress {
+    /// Face stresses from membrane stresses [sx, sy, txy] and moments per unit width [mx, my, mxy]
+    pub fn from_resultants(membrane: [f64; 3], moments: [f64; 3], thickness: f64) -> Self {
+        let bending = moments.map(|m| 6.0 * m / (thickness * thickness));
+        let face = |sign: f64| {
+            PlateStress::from_components(
+                membrane[0] + sign * bending[0],
+                membrane[1] + sign * bending[1],
+                membrane[2] + sign * bending[2],
+            )
+        };
+        Self { top: face(1.0), bottom: face(-1.0) }
+    }
+
+    /// Larger von Mises stress of the two faces
+    pub fn max_von_mises(&self) -> f64 {
+        self.top.von_mises.max(self.bottom.von_mises)
+    }
+
+    /// Re-express the stresses from in-plane axes `from` to in-plane axes `to` (unit global vectors)
+    ///
+    /// Components out of the `to` plane are dropped, which only matters where
+    /// elements meet at an angle.
+    pub(crate) fn transform(&self, from: [[f64; 3]; 2], to: [[f64; 3]; 2]) -> Self {
+        let c = to.map(|b| from.map(|a| b[0] * a[0] + b[1] * a[1] + b[2] * a[2]));
+        let face = |s: &PlateStress| {
+            let tensor = [[s.sx, s.txy], [s.txy, s.sy]];
+            let component = |p: usize, q: usize| {
+                (0..2).flat_map(|a| (0..2).map(move |b| (a, b)))
+                    .map(|(a, b)| c[p][a] * c[q][b] * tensor[a][b])
+                    .sum::<f64>()
+            };
+            PlateStress::from_components(component(0, 0), component(1, 1), component(0, 1))
+        };
+        Self { top: face(&self.top), bottom: face(&self.bottom) }
+    }
+
+    /// Component-wise mean of stresses in common axes
+    pub(crate) fn mean(stresses: &[Self]) -> Self {
+        let count = stresses.len() as f64;
+        let face = |get: fn(&Self) -> &PlateStress| {
+            let sum = |f: fn(&PlateStress) -> f64| stresses.iter().map(|s| f(get(s))).sum::<f64>() / count;
+            PlateStress::from_components(sum(|p| p.sx), sum(|p| p.sy), sum(|p| p.txy))
+        };
+        Self { top: face(|s| &s.top), bottom: face(|s| &s.bottom) }
+    }
+}
+
+/// Surface stresses of the plates and quads meeting at a node
+///
+/// All components are in the local axes of the first adjacent element by
+/// name. `averaged` is the mean of the element corner tensors; the individual
+/// corner values are kept so that the spread between them can be used as a
+/// discretization error indicator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodalShellStress {
+    /// Nodal average over the adjacent elements
+    pub averaged: ShellSurfaceStress,
+    /// Un-averaged corner stresses of each adjacent element, by element name
+    pub elements: Vec<(String, ShellSurfaceStress)>,
+}
+
+impl NodalShellStress {
+    /// Largest difference between an element's corner von Mises stress and the average, on either face
+    pub fn von_mises_deviation(&self) -> f64 {
+        self.elements.iter()
+            .flat_map(|(_, s)| {
+                [
+                    (s.top.von_mises - self.averaged.top.von_mises).abs(),
+                    (s.bottom.von_mises - self.averaged.bottom.von_mises).abs(),
+                ]
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Wood-Armer reinforcement design moments of a slab, per unit width
+///
+/// Moments are magnitudes to be resisted by reinforcement in the local x and
+/// y directions at each face, so all are zero or positive. Plate moments put
+/// the top (local +z) face in tension when positive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlabDesignMoments {
+    /// Design moment for top reinforcement along local x
+    pub top_x: f64,
+    /// Design moment for top reinforcement along local y
+    pub top_y: f64,
+    /// Design moment for bottom reinforcement along local x
+    pub bottom_x: f64,
+    /// Design moment for bottom reinforcement along local y
+    pub bottom_y: f64,
+}
+
+impl SlabDesignMoments {
+    /// Design moments from the moments per unit width mx, my and mxy
+    pub fn from_moments(mx: f64, my: f64, mxy: f64) -> Self {
+        let [top_x, top_y] = wood_armer(mx, my, mxy);
+        let [bottom_x, bottom_y] = wood_armer(-mx, -my, mxy);
+        Self { top_x, top_y, bottom_x, bottom_y }
+    }
+}
+
+/// Wood-Armer moments [mx*, my*] for the face put in tension by positive mx and my
+fn wood_armer(mx: f64, my: f64, mxy: f64) -> [f64; 2] {
+    let twist = mxy.abs();
+    let (mut x, mut y) = (mx + twist, my + twist);
+    if x < 0.0 {
+        x = 0.0;
+        y = my + mxy * mxy / mx.abs();
+    } else if y < 0.0 {
+        y = 0.0;
+        x = mx + mxy * mxy / my.abs();
+    }
+    [x.max(0.0), y.max(0.0)]
+}
+
+/// Stress results in a solid element
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SolidStress {
+    /// Normal stress in global X direction
+    pub sx: f64,
+    /// Normal stress in global Y direction
+    pub sy: f64,
+    /// Normal stress in global Z direction
+    pub sz: f64,
+    /// Shear stress XY
+    pub txy: f64,
+    /// Shear stress YZ
+    pub tyz: f64,
+    /// Shear stress ZX
+    pub tzx: f64,
+    /// Von Mises equivalent stress
+    pub von_mises: f64,
+}
+
+impl SolidStress {
+    /// Create from stress components [sx, sy, sz, txy, tyz, tzx]
+    pub fn from_array(arr: [f64; 6]) -> Self {
+        let [sx, sy, sz, txy, tyz, tzx] = arr;
+        let von_mises = (0.5 * ((sx - sy).powi(2) + (sy - sz).powi(2) + (sz - sx).powi(2))
+            + 3.0 * (txy.powi(2) + tyz.powi(2) + tzx.powi(2)))
+            .sqrt();
+        
+        Self {
+            sx,
+            sy,
+            sz,
+            txy,
+            tyz,
+            tzx,
+            von_mises,
+        }
+    }
+}
+
+/// Story shear, overturning moment and drift of one story in one combination
+///
+/// A story spans from the level below (or the lowest node of the model) up to
+/// its own level. Shears and overturning moments are the resultants of every
+/// applied load and support reaction above the level below, with element loads
+/// taken as their equivalent nodal loads. Moments are about global axes through
+/// the point (0, elevation below, 0). Drifts are differences of the average
+/// level displacements; the maximum drifts are over nodes of the two levels at
+/// the same plan position, or equal the average drifts if none line up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryResult {
+    /// Story level name
+    pub story: String,
+    /// Level elevation (global Y)
+    pub elevation: f64,
+    /// Story height
+    pub height: f64,
+    /// Story shear in global X
+    pub shear_x: f64,
+    /// Story shear in global Z
+    pub shear_z: f64,
+    /// Overturning moment about global X
+    pub overturning_x: f64,
+    /// Overturning moment about global Z
+    pub overturning_z: f64,
+    /// Average level displacement in global X
+    pub displacement_x: f64,
+    /// Average level displacement in global Z
+    pub displacement_z: f64,
+    /// Story drift in global X
+    pub drift_x: f64,
+    /// Story drift in global Z
+    pub drift_z: f64,
+    /// Largest drift magnitude in global X
+    pub max_drift_x: f64,
+    /// Largest drift magnitude in global Z
+    pub max_drift_z: f64,
+}
+
+impl StoryResult {
+    /// Drift ratios (drift / height) in global X and Z
+    pub fn drift_ratio(&self) -> (f64, f64) {
+        (self.drift_x / self.height, self.drift_z / self.height)
+    }
+
+    /// Largest drift ratios in global X and Z
+    pub fn max_drift_ratio(&self) -> (f64, f64) {
+        (self.max_drift_x / self.height, self.max_drift_z / self.height)
+    }
+}
+
 /// Summary of analysis results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisSummary {
@@ -231,6 +556,18 @@ pub struct AnalysisSummary {
     pub total_dofs: usize,
     /// Free DOFs (unknown)
     pub free_dofs: usize,
+    /// Nodes whose one-way supports lifted off
+    #[serde(default)]
+    pub uplift_nodes: Vec<String>,
+    /// Tension- or compression-only members that went slack
+    #[serde(default)]
+    pub slack_members: Vec<String>,
+    /// Stiffness half-bandwidth in DOFs with the nodes numbered in name order
+    #[serde(default)]
+    pub original_bandwidth: usize,
+    /// Stiffness half-bandwidth in DOFs after reverse Cuthill-McKee renumbering
+    #[serde(default)]
+    pub bandwidth: usize,
 }
 
 impl Default for AnalysisSummary {
@@ -249,6 +586,10 @@ impl Default for AnalysisSummary {
             num_plates: 0,
             total_dofs: 0,
             free_dofs: 0,
+            uplift_nodes: Vec::new(),
+            slack_members: Vec::new(),
+            original_bandwidth: 0,
+            bandwidth: 0,
         }
     }
 }
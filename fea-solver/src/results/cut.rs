@@ -0,0 +1,161 @@
+//! Section cuts through members, plates and quads
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FEAError, FEAResult};
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn unit(a: [f64; 3]) -> Option<[f64; 3]> {
+    let length = dot(a, a).sqrt();
+    (length > 1e-12).then(|| a.map(|c| c / length))
+}
+
+/// A cutting plane, optionally bounded by a closed polyline in the plane
+///
+/// Nodes more than `tolerance` along the normal from the plane are on the
+/// positive side, all others (including nodes on the plane) on the negative
+/// side. An element crosses the cut if it has nodes on both sides and, for a
+/// bounded cut, its intersection with the plane lies inside the polyline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionCut {
+    /// Point on the plane, about which moments are taken
+    pub origin: [f64; 3],
+    /// Unit normal, pointing to the positive side
+    pub normal: [f64; 3],
+    /// Closed boundary in the plane (None = unbounded)
+    pub boundary: Option<Vec<[f64; 3]>>,
+    /// Distance within which nodes count as on the plane
+    pub tolerance: f64,
+}
+
+impl SectionCut {
+    /// Unbounded plane through `origin` with normal `normal`
+    pub fn plane(origin: [f64; 3], normal: [f64; 3]) -> FEAResult<Self> {
+        let normal = unit(normal)
+            .ok_or_else(|| FEAError::InvalidGeometry("Section cut normal has zero length".to_string()))?;
+        Ok(Self { origin, normal, boundary: None, tolerance: 1e-6 })
+    }
+
+    /// Plane of a closed, planar polyline, bounded by it
+    ///
+    /// The last point joins the first. The normal follows the right-hand rule
+    /// around the points and moments are taken about their average.
+    pub fn polyline(points: &[[f64; 3]]) -> FEAResult<Self> {
+        if points.len() < 3 {
+            return Err(FEAError::InvalidGeometry("Section cut polyline needs at least 3 points".to_string()));
+        }
+        // Newell's method
+        let mut normal = [0.0; 3];
+        for (k, p) in points.iter().enumerate() {
+            let q = points[(k + 1) % points.len()];
+            normal[0] += (p[1] - q[1]) * (p[2] + q[2]);
+            normal[1] += (p[2] - q[2]) * (p[0] + q[0]);
+            normal[2] += (p[0] - q[0]) * (p[1] + q[1]);
+        }
+        let count = points.len() as f64;
+        let origin = [0, 1, 2].map(|k| points.iter().map(|p| p[k]).sum::<f64>() / count);
+        let mut cut = Self::plane(origin, normal)
+            .map_err(|_| FEAError::InvalidGeometry("Section cut polyline encloses no area".to_string()))?;
+        cut.boundary = Some(points.to_vec());
+        Ok(cut)
+    }
+
+    /// Set the distance within which nodes count as on the plane
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// In-plane axes (u, v), with (u, v, normal) right-handed
+    ///
+    /// u is global X projected onto the plane, or global Y when the normal is
+    /// close to X.
+    pub fn axes(&self) -> ([f64; 3], [f64; 3]) {
+        let project = |a: [f64; 3]| {
+            let d = dot(a, self.normal);
+            unit([a[0] - d * self.normal[0], a[1] - d * self.normal[1], a[2] - d * self.normal[2]])
+        };
+        let u = project([1.0, 0.0, 0.0])
+            .filter(|_| self.normal[0].abs() < 0.9)
+            .or_else(|| project([0.0, 1.0, 0.0]))
+            .expect("a unit normal is not along both X and Y");
+        (u, cross(self.normal, u))
+    }
+
+    /// Signed distance of a point from the plane
+    pub(crate) fn distance(&self, p: [f64; 3]) -> f64 {
+        dot(self.normal, [p[0] - self.origin[0], p[1] - self.origin[1], p[2] - self.origin[2]])
+    }
+
+    /// Whether a point on the plane lies inside the boundary
+    pub(crate) fn contains(&self, p: [f64; 3]) -> bool {
+        let Some(boundary) = &self.boundary else {
+            return true;
+        };
+        let (u, v) = self.axes();
+        let plane = |q: [f64; 3]| {
+            let r = [q[0] - self.origin[0], q[1] - self.origin[1], q[2] - self.origin[2]];
+            (dot(r, u), dot(r, v))
+        };
+        // Even-odd ray casting along +u
+        let (x, y) = plane(p);
+        let mut inside = false;
+        for (k, a) in boundary.iter().enumerate() {
+            let ((xa, ya), (xb, yb)) = (plane(*a), plane(boundary[(k + 1) % boundary.len()]));
+            if (ya > y) != (yb > y) && x < xa + (y - ya) * (xb - xa) / (yb - ya) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Resolve a global force and moment into cut components
+    pub(crate) fn resolve(&self, force: [f64; 3], moment: [f64; 3], elements: Vec<String>) -> SectionCutResult {
+        let (u, v) = self.axes();
+        SectionCutResult {
+            force,
+            moment,
+            axial: dot(force, self.normal),
+            shear_u: dot(force, u),
+            shear_v: dot(force, v),
+            torsion: dot(moment, self.normal),
+            moment_u: dot(moment, u),
+            moment_v: dot(moment, v),
+            elements,
+        }
+    }
+}
+
+/// Resultant transmitted across a section cut
+///
+/// The resultant is the force the positive side exerts on the negative side
+/// through the crossing elements, so the axial component is positive in
+/// tension. Moments are about the cut origin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionCutResult {
+    /// Resultant force in global axes
+    pub force: [f64; 3],
+    /// Resultant moment about the origin in global axes
+    pub moment: [f64; 3],
+    /// Force along the normal (positive = tension)
+    pub axial: f64,
+    /// Force along the cut u axis
+    pub shear_u: f64,
+    /// Force along the cut v axis
+    pub shear_v: f64,
+    /// Moment about the normal
+    pub torsion: f64,
+    /// Moment about the cut u axis
+    pub moment_u: f64,
+    /// Moment about the cut v axis
+    pub moment_v: f64,
+    /// Names of the members, plates and quads crossing the cut
+    pub elements: Vec<String>,
+}
@@ -0,0 +1,472 @@
+//! Queryable snapshot of analysis results
+//!
+//! [`ResultsDb`] copies every result of an analyzed model into flat,
+//! name-ordered records together with the load combination tags and group
+//! memberships needed to filter them. Editing or re-analyzing the model
+//! afterwards does not change a snapshot.
+//!
+//! ```rust,ignore
+//! let db = model.results()?;
+//! let filter = ResultFilter::new().with_group("Columns").with_tag("seismic");
+//! let worst = db.max_abs(Quantity::MomentY, &filter);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{MemberForces, NodeDisplacement, Reactions};
+use crate::elements::PlateStresses;
+use crate::error::{FEAError, FEAResult};
+use crate::model::FEModel;
+
+/// A scalar result that can be filtered and enveloped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quantity {
+    /// Node displacements and rotations
+    Dx,
+    Dy,
+    Dz,
+    Rx,
+    Ry,
+    Rz,
+    /// Resultant translation of a node
+    Translation,
+    /// Support reactions
+    ReactionFx,
+    ReactionFy,
+    ReactionFz,
+    ReactionMx,
+    ReactionMy,
+    ReactionMz,
+    /// Member internal forces, in the [`MemberForces`] convention, enveloped
+    /// over stations along the member
+    Axial,
+    ShearY,
+    ShearZ,
+    Torsion,
+    MomentY,
+    MomentZ,
+    /// Plate and quad centre stresses and resultants, in the [`PlateStresses`] convention
+    Sx,
+    Sy,
+    Txy,
+    Mx,
+    My,
+    Mxy,
+    Qx,
+    Qy,
+    /// Plane stress von Mises stress of the membrane components
+    VonMises,
+}
+
+/// Kind of entity a quantity belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Node,
+    Member,
+    Shell,
+}
+
+impl Quantity {
+    fn source(self) -> Source {
+        use Quantity::*;
+        match self {
+            Dx | Dy | Dz | Rx | Ry | Rz | Translation | ReactionFx | ReactionFy | ReactionFz | ReactionMx
+            | ReactionMy | ReactionMz => Source::Node,
+            Axial | ShearY | ShearZ | Torsion | MomentY | MomentZ => Source::Member,
+            Sx | Sy | Txy | Mx | My | Mxy | Qx | Qy | VonMises => Source::Shell,
+        }
+    }
+}
+
+/// Displacement and (for supported nodes) reaction of a node in one combination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeResult {
+    pub node: String,
+    pub combo: String,
+    pub displacement: NodeDisplacement,
+    pub reaction: Option<Reactions>,
+}
+
+impl NodeResult {
+    /// Value of a node quantity (None for reactions of free nodes and other kinds)
+    pub fn value(&self, quantity: Quantity) -> Option<f64> {
+        let d = &self.displacement;
+        let r = self.reaction.as_ref();
+        match quantity {
+            Quantity::Dx => Some(d.dx),
+            Quantity::Dy => Some(d.dy),
+            Quantity::Dz => Some(d.dz),
+            Quantity::Rx => Some(d.rx),
+            Quantity::Ry => Some(d.ry),
+            Quantity::Rz => Some(d.rz),
+            Quantity::Translation => Some(d.translation_magnitude()),
+            Quantity::ReactionFx => r.map(|r| r.fx),
+            Quantity::ReactionFy => r.map(|r| r.fy),
+            Quantity::ReactionFz => r.map(|r| r.fz),
+            Quantity::ReactionMx => r.map(|r| r.mx),
+            Quantity::ReactionMy => r.map(|r| r.my),
+            Quantity::ReactionMz => r.map(|r| r.mz),
+            _ => None,
+        }
+    }
+}
+
+/// Stations along each member at which internal forces are recorded, both
+/// ends included, as for the design checks
+pub const MEMBER_STATIONS: usize = 11;
+
+/// Internal forces of a member in one combination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberResult {
+    pub member: String,
+    pub combo: String,
+    /// Forces at the i-node
+    pub i: MemberForces,
+    /// Forces at the j-node
+    pub j: MemberForces,
+    /// (distance from the i-node, forces) at [`MEMBER_STATIONS`] evenly
+    /// spaced points, from [`FEModel::member_diagram`]
+    pub stations: Vec<(f64, MemberForces)>,
+}
+
+impl MemberResult {
+    /// The value of a member quantity with the largest magnitude along the member
+    pub fn value(&self, quantity: Quantity) -> Option<f64> {
+        let index = match quantity {
+            Quantity::Axial => 0,
+            Quantity::ShearY => 1,
+            Quantity::ShearZ => 2,
+            Quantity::Torsion => 3,
+            Quantity::MomentY => 4,
+            Quantity::MomentZ => 5,
+            _ => return None,
+        };
+        let ends = [&self.i, &self.j];
+        ends.into_iter()
+            .chain(self.stations.iter().map(|(_, forces)| forces))
+            .map(|forces| forces.to_array()[index])
+            .reduce(|max, v| if v.abs() > max.abs() { v } else { max })
+    }
+}
+
+/// Centre stresses of a plate or quad in one combination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellResult {
+    pub element: String,
+    pub combo: String,
+    pub stress: PlateStresses,
+}
+
+impl ShellResult {
+    /// Value of a shell quantity
+    pub fn value(&self, quantity: Quantity) -> Option<f64> {
+        let s = &self.stress;
+        match quantity {
+            Quantity::Sx => Some(s.sx),
+            Quantity::Sy => Some(s.sy),
+            Quantity::Txy => Some(s.txy),
+            Quantity::Mx => Some(s.mx),
+            Quantity::My => Some(s.my),
+            Quantity::Mxy => Some(s.mxy),
+            Quantity::Qx => Some(s.qx),
+            Quantity::Qy => Some(s.qy),
+            Quantity::VonMises => Some(s.von_mises()),
+            _ => None,
+        }
+    }
+}
+
+/// The record and combination where a quantity is extreme
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Extreme {
+    /// Node, member or shell name
+    pub name: String,
+    pub combo: String,
+    pub value: f64,
+}
+
+/// Selects results by group, combination tag and combination name
+///
+/// Unset criteria select everything. A combination matches the tags if it
+/// carries any of them.
+#[derive(Debug, Clone, Default)]
+pub struct ResultFilter {
+    group: Option<String>,
+    tags: Vec<String>,
+    combos: Option<Vec<String>>,
+}
+
+impl ResultFilter {
+    /// Create a filter that selects everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only entities of a group
+    pub fn with_group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Only combinations tagged `tag` (or any other tag given)
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Only the named combinations
+    pub fn with_combos(mut self, combos: &[&str]) -> Self {
+        self.combos = Some(combos.iter().map(|c| c.to_string()).collect());
+        self
+    }
+}
+
+/// Group members resolved to analysis entity names
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GroupSets {
+    nodes: BTreeSet<String>,
+    members: BTreeSet<String>,
+    shells: BTreeSet<String>,
+}
+
+/// Owned, queryable results of an analyzed model
+///
+/// Records are ordered by entity name, then combination name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultsDb {
+    nodes: Vec<NodeResult>,
+    members: Vec<MemberResult>,
+    shells: Vec<ShellResult>,
+    combo_tags: BTreeMap<String, Vec<String>>,
+    groups: BTreeMap<String, GroupSets>,
+}
+
+impl ResultsDb {
+    /// Snapshot the results of an analyzed model
+    pub fn from_model(model: &FEModel) -> FEAResult<Self> {
+        if !model.is_analyzed() {
+            return Err(FEAError::NotAnalyzed);
+        }
+        let combo_tags: BTreeMap<String, Vec<String>> = model.load_combos.iter()
+            .map(|(name, combo)| (name.clone(), combo.tags.clone()))
+            .collect();
+
+        let store = model.result_store();
+        let mut nodes = Vec::new();
+        for name in model.nodes.keys() {
+            for combo in combo_tags.keys() {
+                if let Some(d) = store.displacements.get(name, combo) {
+                    nodes.push(NodeResult {
+                        node: name.clone(),
+                        combo: combo.clone(),
+                        displacement: NodeDisplacement::from_array(d),
+                        reaction: store.reactions.get(name, combo).map(Reactions::from_array),
+                    });
+                }
+            }
+        }
+
+        let mut members = Vec::new();
+        for name in model.members.keys() {
+            for combo in combo_tags.keys() {
+                if let Some(forces) = store.member_forces.get(name, combo) {
+                    members.push(MemberResult {
+                        member: name.clone(),
+                        combo: combo.clone(),
+                        i: MemberForces::from_i_node_forces(&forces),
+                        j: MemberForces::from_j_node_forces(&forces),
+                        stations: model.member_diagram(name, combo, MEMBER_STATIONS)?,
+                    });
+                }
+            }
+        }
+
+        let mut shells = Vec::new();
+        for combo in combo_tags.keys() {
+            let plates = model.plates.iter().filter_map(|(name, p)| Some((name, p.stress(combo)?)));
+            let quads = model.quads.iter().filter_map(|(name, q)| Some((name, q.stress(combo)?.center)));
+            for (name, stress) in plates.chain(quads) {
+                shells.push(ShellResult { element: name.clone(), combo: combo.clone(), stress });
+            }
+        }
+        shells.sort_by(|a, b| (&a.element, &a.combo).cmp(&(&b.element, &b.combo)));
+
+        // Physical members in groups select their analysis segments
+        let groups = model.groups.iter()
+            .map(|(name, group)| {
+                let members = group.members.iter()
+                    .flat_map(|m| match model.physical_members.get(m) {
+                        Some(physical) => physical.segments().iter().map(|(s, _, _)| s.clone()).collect(),
+                        None => vec![m.clone()],
+                    })
+                    .collect();
+                let sets = GroupSets {
+                    nodes: group.nodes.iter().cloned().collect(),
+                    members,
+                    shells: group.plates.iter().cloned().collect(),
+                };
+                (name.clone(), sets)
+            })
+            .collect();
+
+        Ok(Self { nodes, members, shells, combo_tags, groups })
+    }
+
+    /// Names of the combinations with results
+    pub fn combos(&self) -> impl Iterator<Item = &str> + '_ {
+        self.combo_tags.keys().map(String::as_str)
+    }
+
+    /// Node results selected by a filter
+    pub fn nodes<'a>(&'a self, filter: &'a ResultFilter) -> FEAResult<impl Iterator<Item = &'a NodeResult> + 'a> {
+        let group = self.group(filter)?;
+        Ok(self.nodes.iter()
+            .filter(move |r| group.is_none_or(|g| g.nodes.contains(&r.node)) && self.combo_matches(filter, &r.combo)))
+    }
+
+    /// Member results selected by a filter
+    pub fn members<'a>(&'a self, filter: &'a ResultFilter) -> FEAResult<impl Iterator<Item = &'a MemberResult> + 'a> {
+        let group = self.group(filter)?;
+        Ok(self.members.iter()
+            .filter(move |r| group.is_none_or(|g| g.members.contains(&r.member)) && self.combo_matches(filter, &r.combo)))
+    }
+
+    /// Plate and quad results selected by a filter
+    pub fn shells<'a>(&'a self, filter: &'a ResultFilter) -> FEAResult<impl Iterator<Item = &'a ShellResult> + 'a> {
+        let group = self.group(filter)?;
+        Ok(self.shells.iter()
+            .filter(move |r| group.is_none_or(|g| g.shells.contains(&r.element)) && self.combo_matches(filter, &r.combo)))
+    }
+
+    /// (name, combination, value) of a quantity over the filtered records
+    pub fn values<'a>(
+        &'a self,
+        quantity: Quantity,
+        filter: &'a ResultFilter,
+    ) -> FEAResult<Box<dyn Iterator<Item = (&'a str, &'a str, f64)> + 'a>> {
+        Ok(match quantity.source() {
+            Source::Node => Box::new(self.nodes(filter)?
+                .filter_map(move |r| Some((r.node.as_str(), r.combo.as_str(), r.value(quantity)?)))),
+            Source::Member => Box::new(self.members(filter)?
+                .filter_map(move |r| Some((r.member.as_str(), r.combo.as_str(), r.value(quantity)?)))),
+            Source::Shell => Box::new(self.shells(filter)?
+                .filter_map(move |r| Some((r.element.as_str(), r.combo.as_str(), r.value(quantity)?)))),
+        })
+    }
+
+    /// Largest value of a quantity over the filtered records
+    pub fn max(&self, quantity: Quantity, filter: &ResultFilter) -> FEAResult<Option<Extreme>> {
+        self.extreme(quantity, filter, |v| v)
+    }
+
+    /// Smallest value of a quantity over the filtered records
+    pub fn min(&self, quantity: Quantity, filter: &ResultFilter) -> FEAResult<Option<Extreme>> {
+        self.extreme(quantity, filter, |v| -v)
+    }
+
+    /// Value of a quantity with the largest magnitude over the filtered records, keeping its sign
+    pub fn max_abs(&self, quantity: Quantity, filter: &ResultFilter) -> FEAResult<Option<Extreme>> {
+        self.extreme(quantity, filter, f64::abs)
+    }
+
+    /// First record maximising `key`, so ties resolve in name then combination order
+    fn extreme(&self, quantity: Quantity, filter: &ResultFilter, key: impl Fn(f64) -> f64) -> FEAResult<Option<Extreme>> {
+        let mut best: Option<(&str, &str, f64)> = None;
+        for (name, combo, value) in self.values(quantity, filter)? {
+            if best.is_none_or(|(_, _, b)| key(value) > key(b)) {
+                best = Some((name, combo, value));
+            }
+        }
+        Ok(best.map(|(name, combo, value)| Extreme { name: name.to_string(), combo: combo.to_string(), value }))
+    }
+
+    fn group(&self, filter: &ResultFilter) -> FEAResult<Option<&GroupSets>> {
+        filter.group.as_ref()
+            .map(|name| self.groups.get(name).ok_or_else(|| FEAError::GroupNotFound(name.clone())))
+            .transpose()
+    }
+
+    fn combo_matches(&self, filter: &ResultFilter, combo: &str) -> bool {
+        let named = filter.combos.as_ref().is_none_or(|combos| combos.iter().any(|c| c == combo));
+        let tagged = filter.tags.is_empty()
+            || self.combo_tags.get(combo).is_some_and(|tags| tags.iter().any(|t| filter.tags.contains(t)));
+        named && tagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    /// Two cantilever columns and a beam with a gravity and a seismic combination
+    fn portal() -> FEModel {
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("W", Section::new(7.65e-3, 2.04e-4, 1.73e-5, 3e-7)).unwrap();
+        for (name, x, y) in [("A", 0.0, 0.0), ("B", 0.0, 4.0), ("C", 6.0, 4.0), ("D", 6.0, 0.0)] {
+            model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+        }
+        model.add_member("C1", Member::new("A", "B", "Steel", "W")).unwrap();
+        model.add_member("B1", Member::new("B", "C", "Steel", "W")).unwrap();
+        model.add_member("C2", Member::new("D", "C", "Steel", "W")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_support("D", Support::fixed()).unwrap();
+        model.add_member_dist_load("B1", DistributedLoad::uniform_downward(10e3, "D")).unwrap();
+        model.add_node_load("B", NodeLoad::fx(20e3, "E")).unwrap();
+        model.add_load_combo(LoadCombination::single("Gravity", "D")).unwrap();
+        model.add_load_combo(LoadCombination::new("Seismic").with_case("D", 1.0).with_case("E", 1.0).with_tag("seismic")).unwrap();
+        model.add_group("Columns", Group::of_members(&["C1", "C2"])).unwrap();
+        model.analyze_linear().unwrap();
+        model
+    }
+
+    #[test]
+    fn test_max_moment_in_group_for_tagged_combos() {
+        let model = portal();
+        let db = model.results().unwrap();
+        let filter = ResultFilter::new().with_group("Columns").with_tag("seismic");
+
+        let worst = db.max_abs(Quantity::MomentZ, &filter).unwrap().unwrap();
+        assert_eq!(worst.combo, "Seismic");
+        let expected = model.member_forces_i(&worst.name, "Seismic").unwrap().moment_z
+            .abs()
+            .max(model.member_forces_j(&worst.name, "Seismic").unwrap().moment_z.abs());
+        assert_eq!(worst.value.abs(), expected);
+        assert_eq!(db.members(&filter).unwrap().count(), 2);
+        assert!(db.values(Quantity::MomentZ, &filter).unwrap().all(|(name, _, _)| name != "B1"));
+
+        let reactions = db.max(Quantity::ReactionFy, &ResultFilter::new().with_combos(&["Gravity"])).unwrap().unwrap();
+        assert_eq!(reactions.value, model.node_reactions(&reactions.name, "Gravity").unwrap().fy);
+        assert!(matches!(db.nodes(&ResultFilter::new().with_group("Beams")), Err(FEAError::GroupNotFound(_))));
+    }
+
+    #[test]
+    fn test_member_values_envelope_the_span() {
+        let model = portal();
+        let db = model.results().unwrap();
+        let filter = ResultFilter::new().with_combos(&["Gravity"]);
+
+        let beam = db.members(&filter).unwrap().find(|r| r.member == "B1").unwrap();
+        assert_eq!(beam.stations.len(), MEMBER_STATIONS);
+        // The sagging moment at midspan exceeds the end moments of the flexible columns
+        let midspan = model.member_forces_at("B1", 3.0, "Gravity").unwrap().moment_z;
+        assert!(midspan.abs() > beam.i.moment_z.abs().max(beam.j.moment_z.abs()));
+        assert_eq!(beam.value(Quantity::MomentZ), Some(midspan));
+        assert_eq!(db.max_abs(Quantity::MomentZ, &filter).unwrap().unwrap().value, midspan);
+    }
+
+    #[test]
+    fn test_snapshot_is_independent_of_model() {
+        let mut model = portal();
+        let db = model.results().unwrap();
+        let before = db.max(Quantity::Dx, &ResultFilter::new()).unwrap().unwrap();
+
+        model.remove_load_case("E");
+        model.analyze_linear().unwrap();
+        assert_eq!(db.max(Quantity::Dx, &ResultFilter::new()).unwrap().unwrap(), before);
+        assert!(FEModel::new().results().is_err());
+    }
+}
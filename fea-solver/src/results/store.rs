@@ -0,0 +1,347 @@
+//! Compact storage of per-combination node and member results
+//!
+//! Results used to live on each node and member in a
+//! `HashMap<String, [f64; N]>` keyed by combination name, which costs a
+//! heap-allocated copy of the name, the hash table overhead and its spare
+//! capacity for every entity and combination. [`ResultTable`] instead
+//! numbers the entities and the combinations once and keeps the values in
+//! one column-major array: each combination owns a contiguous column of `N`
+//! values per entity, so solving another combination appends a column.
+//!
+//! Per entity and combination, with hash tables at a typical two-thirds
+//! load, a node's displacements drop from ~130 to 49 bytes and a member's end
+//! forces and local displacements from ~420 to 194 bytes. Reactions are only
+//! stored for supported nodes. For a model with 5,000 nodes, 10,000 members
+//! and 300 combinations that is roughly 0.65 GB instead of 1.5 GB.
+
+use std::mem::size_of;
+
+use crate::handles::{ComboId, Handle, MemberId, NodeId, Registry};
+
+/// `N` result values per entity of one kind, per load combination
+#[derive(Debug, Clone)]
+pub struct ResultTable<I, const N: usize> {
+    rows: Registry<I>,
+    combos: Registry<ComboId>,
+    /// Value `k` of row `r` for combination `c` at `(c * rows + r) * N + k`
+    values: Vec<f64>,
+    /// Whether row `r` has results for combination `c`, at `c * rows + r`
+    stored: Vec<bool>,
+}
+
+impl<I, const N: usize> Default for ResultTable<I, N> {
+    fn default() -> Self {
+        Self {
+            rows: Registry::default(),
+            combos: Registry::default(),
+            values: Vec::new(),
+            stored: Vec::new(),
+        }
+    }
+}
+
+impl<I: Handle, const N: usize> ResultTable<I, N> {
+    /// Empty table for the named entities, one row per handle
+    pub fn new(rows: Registry<I>) -> Self {
+        Self { rows, ..Self::default() }
+    }
+
+    /// Entities with a row in the table
+    pub fn rows(&self) -> &Registry<I> {
+        &self.rows
+    }
+
+    /// Combinations with a column in the table, in the order they were solved
+    pub fn combos(&self) -> impl Iterator<Item = &str> {
+        self.combos.iter().map(|(_, name)| name)
+    }
+
+    /// Results of an entity for a combination
+    pub fn get(&self, name: &str, combo: &str) -> Option<[f64; N]> {
+        let slot = self.slot(name, combo)?;
+        self.stored[slot].then(|| self.values[slot * N..(slot + 1) * N].try_into().unwrap())
+    }
+
+    /// Mutable results of an entity for a combination
+    pub fn get_mut(&mut self, name: &str, combo: &str) -> Option<&mut [f64; N]> {
+        let slot = self.slot(name, combo)?;
+        if !self.stored[slot] {
+            return None;
+        }
+        Some((&mut self.values[slot * N..(slot + 1) * N]).try_into().unwrap())
+    }
+
+    /// Values of every row for a combination, `N` per row in row order, with
+    /// zeros for rows without results; a contiguous slice of the table
+    pub fn column(&self, combo: &str) -> Option<&[f64]> {
+        let column = self.combos.id(combo)?.index();
+        let size = self.rows.len() * N;
+        self.values.get(column * size..(column + 1) * size)
+    }
+
+    /// Store the results of an entity for a combination; entities without a
+    /// row are ignored
+    pub fn insert(&mut self, name: &str, combo: &str, values: [f64; N]) {
+        let Some(row) = self.rows.id(name) else {
+            return;
+        };
+        let column = self.combos.intern(combo).index();
+        let rows = self.rows.len();
+        if self.stored.len() < (column + 1) * rows {
+            self.stored.resize((column + 1) * rows, false);
+            self.values.resize((column + 1) * rows * N, 0.0);
+        }
+        let slot = column * rows + row.index();
+        self.values[slot * N..(slot + 1) * N].copy_from_slice(&values);
+        self.stored[slot] = true;
+    }
+
+    /// Bytes allocated for the stored values and flags
+    pub fn heap_bytes(&self) -> usize {
+        self.values.capacity() * size_of::<f64>() + self.stored.capacity() * size_of::<bool>()
+    }
+
+    /// The same results with the rows numbered by another registry; rows
+    /// without a name in it are dropped
+    pub fn reindexed(&self, rows: Registry<I>) -> Self {
+        let mut table = Self { rows, combos: self.combos.clone(), ..Self::default() };
+        let size = self.combos.len() * table.rows.len();
+        table.values = vec![0.0; size * N];
+        table.stored = vec![false; size];
+        for (column, _) in self.combos.iter() {
+            for (row, name) in self.rows.iter() {
+                let from = column.index() * self.rows.len() + row.index();
+                let Some(new_row) = table.rows.id(name).filter(|_| self.stored[from]) else {
+                    continue;
+                };
+                let to = column.index() * table.rows.len() + new_row.index();
+                table.values[to * N..(to + 1) * N].copy_from_slice(&self.values[from * N..(from + 1) * N]);
+                table.stored[to] = true;
+            }
+        }
+        table
+    }
+
+    fn slot(&self, name: &str, combo: &str) -> Option<usize> {
+        let row = self.rows.id(name)?.index();
+        let column = self.combos.id(combo)?.index();
+        Some(column * self.rows.len() + row)
+    }
+}
+
+/// Static analysis results of a model's nodes and members
+#[derive(Debug, Clone, Default)]
+pub struct ResultStore {
+    /// Node displacements [DX, DY, DZ, RX, RY, RZ]
+    pub displacements: ResultTable<NodeId, 6>,
+    /// Reactions [FX, FY, FZ, MX, MY, MZ] of supported nodes
+    pub reactions: ResultTable<NodeId, 6>,
+    /// Local member end forces [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, ..., Mz_j]
+    pub member_forces: ResultTable<MemberId, 12>,
+    /// Local member end displacements
+    pub member_displacements: ResultTable<MemberId, 12>,
+}
+
+impl ResultStore {
+    /// Empty store for the given nodes, supported nodes and members
+    pub fn new(nodes: Registry<NodeId>, supported: Registry<NodeId>, members: Registry<MemberId>) -> Self {
+        Self {
+            displacements: ResultTable::new(nodes),
+            reactions: ResultTable::new(supported),
+            member_forces: ResultTable::new(members.clone()),
+            member_displacements: ResultTable::new(members),
+        }
+    }
+
+    /// Number the rows by new registries, keeping the results of entities
+    /// that are still present
+    pub fn renumber(&mut self, nodes: &Registry<NodeId>, supported: Registry<NodeId>, members: &Registry<MemberId>) {
+        if self.displacements.rows() != nodes {
+            self.displacements = self.displacements.reindexed(nodes.clone());
+        }
+        if self.reactions.rows() != &supported {
+            self.reactions = self.reactions.reindexed(supported);
+        }
+        if self.member_forces.rows() != members {
+            self.member_forces = self.member_forces.reindexed(members.clone());
+            self.member_displacements = self.member_displacements.reindexed(members.clone());
+        }
+    }
+
+    /// Bytes allocated for all stored results
+    pub fn heap_bytes(&self) -> usize {
+        self.displacements.heap_bytes()
+            + self.reactions.heap_bytes()
+            + self.member_forces.heap_bytes()
+            + self.member_displacements.heap_bytes()
+    }
+
+    /// Get displacement of a node for a load combination
+    /// Returns [DX, DY, DZ, RX, RY, RZ]
+    pub fn displacement(&self, node: &str, combo_name: &str) -> Option<[f64; 6]> {
+        self.displacements.get(node, combo_name)
+    }
+
+    /// Get reactions of a node for a load combination
+    /// Returns [FX, FY, FZ, MX, MY, MZ]; `None` for unsupported nodes
+    pub fn reaction(&self, node: &str, combo_name: &str) -> Option<[f64; 6]> {
+        self.reactions.get(node, combo_name)
+    }
+
+    /// Get local forces of a member for a load combination
+    /// Returns [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, Fy_j, Fz_j, Mx_j, My_j, Mz_j]
+    pub fn local_force(&self, member: &str, combo_name: &str) -> Option<[f64; 12]> {
+        self.member_forces.get(member, combo_name)
+    }
+
+    /// Get axial force of a member
+    ///
+    /// These accessors only use the end forces, so the shears and the axial
+    /// force are constant and the moments linear along the member; use
+    /// [`crate::model::FEModel::member_forces_at`] for members with span loads.
+    pub fn axial(&self, member: &str, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        // P = -Fx_i (positive tension)
+        Some(-forces[0])
+    }
+
+    /// Get shear force in local y direction
+    pub fn shear_y(&self, member: &str, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        Some(-forces[1])
+    }
+
+    /// Get shear force in local z direction
+    pub fn shear_z(&self, member: &str, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        Some(-forces[2])
+    }
+
+    /// Get bending moment about local y axis at position x
+    pub fn moment_y(&self, member: &str, x: f64, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        // My = My_i + Fz_i * x
+        Some(forces[4] + forces[2] * x)
+    }
+
+    /// Get bending moment about local z axis at position x
+    pub fn moment_z(&self, member: &str, x: f64, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        // Mz = Mz_i - Fy_i * x
+        Some(forces[5] - forces[1] * x)
+    }
+
+    /// Get torsion
+    pub fn torsion(&self, member: &str, combo_name: &str) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        Some(-forces[3])
+    }
+
+    /// Get maximum absolute end axial force
+    pub fn max_axial(&self, member: &str, combo_name: &str) -> Option<f64> {
+        self.max_end_force(member, combo_name, 0)
+    }
+
+    /// Get maximum absolute end shear force in y
+    pub fn max_shear_y(&self, member: &str, combo_name: &str) -> Option<f64> {
+        self.max_end_force(member, combo_name, 1)
+    }
+
+    /// Get maximum absolute end shear force in z
+    pub fn max_shear_z(&self, member: &str, combo_name: &str) -> Option<f64> {
+        self.max_end_force(member, combo_name, 2)
+    }
+
+    /// Get maximum absolute end moment about y
+    pub fn max_moment_y(&self, member: &str, combo_name: &str) -> Option<f64> {
+        self.max_end_force(member, combo_name, 4)
+    }
+
+    /// Get maximum absolute end moment about z
+    pub fn max_moment_z(&self, member: &str, combo_name: &str) -> Option<f64> {
+        self.max_end_force(member, combo_name, 5)
+    }
+
+    fn max_end_force(&self, member: &str, combo_name: &str, dof: usize) -> Option<f64> {
+        let forces = self.local_force(member, combo_name)?;
+        Some(forces[dof].abs().max(forces[dof + 6].abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_table_columns() {
+        let names: Vec<String> = (1..=3).map(|i| format!("N{i}")).collect();
+        let mut table: ResultTable<NodeId, 2> = ResultTable::new(Registry::from_names(&names));
+        assert_eq!(table.get("N1", "D"), None);
+
+        table.insert("N2", "D", [1.0, 2.0]);
+        table.insert("N4", "D", [9.0, 9.0]);
+        table.insert("N1", "D+L", [3.0, 4.0]);
+        assert_eq!(table.get("N2", "D"), Some([1.0, 2.0]));
+        assert_eq!(table.get("N1", "D"), None);
+        assert_eq!(table.get("N4", "D"), None);
+        assert_eq!(table.combos().collect::<Vec<_>>(), ["D", "D+L"]);
+
+        // Column-major: the second combination follows the whole first column
+        assert_eq!(table.values[6..8], [3.0, 4.0]);
+        assert_eq!(table.column("D"), Some(&[0.0, 0.0, 1.0, 2.0, 0.0, 0.0][..]));
+        assert_eq!(table.column("L"), None);
+        table.get_mut("N1", "D+L").unwrap()[1] = 5.0;
+        assert_eq!(table.get("N1", "D+L"), Some([3.0, 5.0]));
+        assert!(table.heap_bytes() >= 2 * 3 * (2 * 8 + 1));
+
+        // Renumbering keeps the results of the rows that remain
+        let names: Vec<String> = ["N0", "N1", "N2"].map(String::from).to_vec();
+        let table = table.reindexed(Registry::from_names(&names));
+        assert_eq!(table.get("N1", "D+L"), Some([3.0, 5.0]));
+        assert_eq!(table.get("N2", "D"), Some([1.0, 2.0]));
+        assert_eq!(table.get("N3", "D"), None);
+    }
+
+    #[test]
+    fn test_member_accessors() {
+        let names = vec!["M1".to_string()];
+        let mut store = ResultStore::new(Registry::default(), Registry::default(), Registry::from_names(&names));
+        // Cantilever tip load: 10 kN down, 4 m long, fixed at i
+        store.member_forces.insert("M1", "D", [5.0, 10.0, 0.0, 1.0, 0.0, 40.0, -5.0, -10.0, 0.0, -1.0, 0.0, 0.0]);
+
+        assert_eq!(store.local_force("M1", "D").unwrap()[5], 40.0);
+        assert_eq!(store.axial("M1", "D"), Some(-5.0));
+        assert_eq!(store.shear_y("M1", "D"), Some(-10.0));
+        assert_eq!(store.torsion("M1", "D"), Some(-1.0));
+        assert_eq!(store.moment_z("M1", 2.0, "D"), Some(20.0));
+        assert_eq!(store.moment_z("M1", 4.0, "D"), Some(0.0));
+        assert_eq!(store.max_moment_z("M1", "D"), Some(40.0));
+        assert_eq!(store.max_shear_y("M1", "D"), Some(10.0));
+        assert_eq!(store.axial("M1", "L"), None);
+        assert_eq!(store.displacement("N1", "D"), None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_element_accessors_forward_to_the_store() {
+        use crate::elements::{Member, Node};
+        let mut store = ResultStore::new(
+            Registry::from_names(&["N1".to_string()]),
+            Registry::from_names(&["N1".to_string()]),
+            Registry::from_names(&["M1".to_string()]),
+        );
+        store.displacements.insert("N1", "D", [0.0, -0.01, 0.0, 0.0, 0.0, 0.002]);
+        store.reactions.insert("N1", "D", [0.0, 10.0, 0.0, 0.0, 0.0, 40.0]);
+        store.member_forces.insert("M1", "D", [5.0, 10.0, 0.0, 1.0, 0.0, 40.0, -5.0, -10.0, 0.0, -1.0, 0.0, 0.0]);
+
+        let node = Node::new(0.0, 0.0, 0.0);
+        assert_eq!(node.displacement(&store, "N1", "D"), store.displacement("N1", "D"));
+        assert_eq!(node.reaction(&store, "N1", "D").unwrap()[5], 40.0);
+        let member = Member::new("N1", "N2", "Steel", "W");
+        assert_eq!(member.local_force(&store, "M1", "D"), store.local_force("M1", "D"));
+        assert_eq!(member.axial(&store, "M1", 2.0, "D"), Some(-5.0));
+        assert_eq!(member.moment_z(&store, "M1", 2.0, "D"), Some(20.0));
+        assert_eq!(member.max_moment_z(&store, "M1", "D"), Some(40.0));
+        assert_eq!(member.torsion(&store, "M2", 2.0, "D"), None);
+    }
+}
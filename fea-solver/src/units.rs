@@ -0,0 +1,481 @@
+//! Unit systems
+//!
+//! The solver works in any consistent set of units: a model's values are
+//! taken to be in its [`FEModel::units`], SI (N, m) by default, with time
+//! in seconds so mass is force × s² / length. [`ConvertUnits`] rescales
+//! inputs and results between systems, so values can be entered and read
+//! in the units at hand:
+//!
+//! ```rust
+//! use fea_solver::prelude::*;
+//!
+//! let mut model = FEModel::new();
+//! // A 10 kN load on an SI model
+//! let load = model.from_units(NodeLoad::fy(-10.0, "Live"), &UnitSystem::KN_M);
+//! assert_eq!(load.fy, -10e3);
+//! ```
+//!
+//! Material presets and the design codes are in SI; the design checks
+//! convert a model's sections and forces to SI before checking it.
+//!
+//! [`FEModel::units`]: crate::model::FEModel::units
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::elements::{Material, Section, SectionShape};
+use crate::loads::{DistributedLoad, LoadDirection, NodeLoad, PlateLoad, PointLoad};
+use crate::results::{MemberDeflection, MemberForces, NodeDisplacement, PlateStress, Reactions};
+
+/// Unit of force
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForceUnit {
+    /// N
+    Newton,
+    /// kN
+    Kilonewton,
+    /// MN
+    Meganewton,
+    /// lbf
+    PoundForce,
+    /// kip (1000 lbf)
+    Kip,
+}
+
+impl ForceUnit {
+    /// Size of the unit in newtons
+    pub fn newtons(&self) -> f64 {
+        match self {
+            ForceUnit::Newton => 1.0,
+            ForceUnit::Kilonewton => 1e3,
+            ForceUnit::Meganewton => 1e6,
+            ForceUnit::PoundForce => 4.448_221_615_260_5,
+            ForceUnit::Kip => 4_448.221_615_260_5,
+        }
+    }
+
+    /// Unit symbol, e.g. "kN"
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ForceUnit::Newton => "N",
+            ForceUnit::Kilonewton => "kN",
+            ForceUnit::Meganewton => "MN",
+            ForceUnit::PoundForce => "lbf",
+            ForceUnit::Kip => "kip",
+        }
+    }
+}
+
+/// Unit of length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LengthUnit {
+    /// mm
+    Millimetre,
+    /// cm
+    Centimetre,
+    /// m
+    Metre,
+    /// in
+    Inch,
+    /// ft
+    Foot,
+}
+
+impl LengthUnit {
+    /// Size of the unit in metres
+    pub fn metres(&self) -> f64 {
+        match self {
+            LengthUnit::Millimetre => 1e-3,
+            LengthUnit::Centimetre => 1e-2,
+            LengthUnit::Metre => 1.0,
+            LengthUnit::Inch => 0.0254,
+            LengthUnit::Foot => 0.3048,
+        }
+    }
+
+    /// Unit symbol, e.g. "mm"
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            LengthUnit::Millimetre => "mm",
+            LengthUnit::Centimetre => "cm",
+            LengthUnit::Metre => "m",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
+}
+
+/// Physical dimension of a value, as powers of force and length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dimension {
+    /// Ratios, strains and rotations
+    Dimensionless,
+    /// Lengths, coordinates and displacements
+    Length,
+    /// Area (length²)
+    Area,
+    /// Section modulus (length³)
+    SectionModulus,
+    /// Second moment of area and torsion constant (length⁴)
+    SecondMoment,
+    /// Force
+    Force,
+    /// Moment, and force × length generally
+    Moment,
+    /// Line load, and moment per unit length in shells
+    ForcePerLength,
+    /// Stress, pressure and elastic moduli
+    Stress,
+    /// Mass (force × s² / length)
+    Mass,
+    /// Density (mass / length³)
+    Density,
+}
+
+impl Dimension {
+    /// Powers of (force, length)
+    pub fn exponents(&self) -> (i32, i32) {
+        match self {
+            Dimension::Dimensionless => (0, 0),
+            Dimension::Length => (0, 1),
+            Dimension::Area => (0, 2),
+            Dimension::SectionModulus => (0, 3),
+            Dimension::SecondMoment => (0, 4),
+            Dimension::Force => (1, 0),
+            Dimension::Moment => (1, 1),
+            Dimension::ForcePerLength => (1, -1),
+            Dimension::Stress => (1, -2),
+            Dimension::Mass => (1, -1),
+            Dimension::Density => (1, -4),
+        }
+    }
+}
+
+/// A consistent set of force and length units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitSystem {
+    /// Unit of force
+    pub force: ForceUnit,
+    /// Unit of length
+    pub length: LengthUnit,
+}
+
+impl Default for UnitSystem {
+    fn default() -> Self {
+        Self::SI
+    }
+}
+
+impl UnitSystem {
+    /// Newtons and metres
+    pub const SI: Self = Self::new(ForceUnit::Newton, LengthUnit::Metre);
+    /// Kilonewtons and metres
+    pub const KN_M: Self = Self::new(ForceUnit::Kilonewton, LengthUnit::Metre);
+    /// Newtons and millimetres (stresses in MPa)
+    pub const N_MM: Self = Self::new(ForceUnit::Newton, LengthUnit::Millimetre);
+    /// Kilonewtons and millimetres
+    pub const KN_MM: Self = Self::new(ForceUnit::Kilonewton, LengthUnit::Millimetre);
+    /// Kips and feet
+    pub const KIP_FT: Self = Self::new(ForceUnit::Kip, LengthUnit::Foot);
+    /// Kips and inches (stresses in ksi)
+    pub const KIP_IN: Self = Self::new(ForceUnit::Kip, LengthUnit::Inch);
+
+    /// Create a unit system
+    pub const fn new(force: ForceUnit, length: LengthUnit) -> Self {
+        Self { force, length }
+    }
+
+    /// Size of this system's unit of a dimension in SI units
+    pub fn si_factor(&self, dimension: Dimension) -> f64 {
+        let (f, l) = dimension.exponents();
+        self.force.newtons().powi(f) * self.length.metres().powi(l)
+    }
+
+    /// Convert a value of a dimension from this system to another
+    pub fn convert(&self, value: f64, dimension: Dimension, to: &UnitSystem) -> f64 {
+        if self == to {
+            return value;
+        }
+        value * self.si_factor(dimension) / to.si_factor(dimension)
+    }
+
+    /// Unit label of a dimension, e.g. "kN·m" or "N/mm²"
+    pub fn label(&self, dimension: Dimension) -> String {
+        let (f, l) = (self.force.symbol(), self.length.symbol());
+        match dimension {
+            Dimension::Dimensionless => String::new(),
+            Dimension::Length => l.to_string(),
+            Dimension::Area => format!("{l}²"),
+            Dimension::SectionModulus => format!("{l}³"),
+            Dimension::SecondMoment => format!("{l}⁴"),
+            Dimension::Force => f.to_string(),
+            Dimension::Moment => format!("{f}·{l}"),
+            Dimension::ForcePerLength => format!("{f}/{l}"),
+            Dimension::Stress => format!("{f}/{l}²"),
+            Dimension::Mass => format!("{f}·s²/{l}"),
+            Dimension::Density => format!("{f}·s²/{l}⁴"),
+        }
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.force.symbol(), self.length.symbol())
+    }
+}
+
+/// A value that can be expressed in another unit system
+pub trait ConvertUnits: Sized {
+    /// The value with its quantities converted from one unit system to another
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self;
+}
+
+/// Converter of values of a dimension between two systems
+fn converter(from: UnitSystem, to: UnitSystem) -> impl Fn(f64, Dimension) -> f64 {
+    move |value, dimension| from.convert(value, dimension, &to)
+}
+
+impl ConvertUnits for Material {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            e: c(self.e, Dimension::Stress),
+            g: c(self.g, Dimension::Stress),
+            rho: c(self.rho, Dimension::Density),
+            fy: self.fy.map(|fy| c(fy, Dimension::Stress)),
+            ..self.clone()
+        }
+    }
+}
+
+impl ConvertUnits for Section {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        let l = |v: f64| c(v, Dimension::Length);
+        Self {
+            a: c(self.a, Dimension::Area),
+            iy: c(self.iy, Dimension::SecondMoment),
+            iz: c(self.iz, Dimension::SecondMoment),
+            j: c(self.j, Dimension::SecondMoment),
+            zy: self.zy.map(|z| c(z, Dimension::SectionModulus)),
+            zz: self.zz.map(|z| c(z, Dimension::SectionModulus)),
+            depth: self.depth.map(l),
+            width: self.width.map(l),
+            shape: self.shape.map(|shape| match shape {
+                SectionShape::Rectangle { width, depth } => SectionShape::Rectangle { width: l(width), depth: l(depth) },
+                SectionShape::Circle { diameter } => SectionShape::Circle { diameter: l(diameter) },
+                SectionShape::Pipe { outer_diameter, wall_thickness } => SectionShape::Pipe {
+                    outer_diameter: l(outer_diameter),
+                    wall_thickness: l(wall_thickness),
+                },
+                SectionShape::WideFlange { depth, flange_width, flange_thickness, web_thickness } => {
+                    SectionShape::WideFlange {
+                        depth: l(depth),
+                        flange_width: l(flange_width),
+                        flange_thickness: l(flange_thickness),
+                        web_thickness: l(web_thickness),
+                    }
+                }
+                SectionShape::Box { width, depth, wall_thickness } => SectionShape::Box {
+                    width: l(width),
+                    depth: l(depth),
+                    wall_thickness: l(wall_thickness),
+                },
+            }),
+        }
+    }
+}
+
+impl ConvertUnits for NodeLoad {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            fx: c(self.fx, Dimension::Force),
+            fy: c(self.fy, Dimension::Force),
+            fz: c(self.fz, Dimension::Force),
+            mx: c(self.mx, Dimension::Moment),
+            my: c(self.my, Dimension::Moment),
+            mz: c(self.mz, Dimension::Moment),
+            case: self.case.clone(),
+        }
+    }
+}
+
+/// Whether a member load direction is a moment
+fn is_moment(direction: LoadDirection) -> bool {
+    matches!(direction, LoadDirection::Mx | LoadDirection::My | LoadDirection::Mz)
+}
+
+impl ConvertUnits for PointLoad {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        let dimension = if is_moment(self.direction) { Dimension::Moment } else { Dimension::Force };
+        Self {
+            magnitude: c(self.magnitude, dimension),
+            position: c(self.position, Dimension::Length),
+            ..self.clone()
+        }
+    }
+}
+
+impl ConvertUnits for DistributedLoad {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        // A distributed moment (force × length / length) scales as a force
+        let dimension = if is_moment(self.direction) { Dimension::Force } else { Dimension::ForcePerLength };
+        Self {
+            w1: c(self.w1, dimension),
+            w2: c(self.w2, dimension),
+            x1: c(self.x1, Dimension::Length),
+            x2: c(self.x2, Dimension::Length),
+            ..self.clone()
+        }
+    }
+}
+
+impl ConvertUnits for PlateLoad {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        Self {
+            pressure: from.convert(self.pressure, Dimension::Stress, to),
+            ..self.clone()
+        }
+    }
+}
+
+impl ConvertUnits for NodeDisplacement {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            dx: c(self.dx, Dimension::Length),
+            dy: c(self.dy, Dimension::Length),
+            dz: c(self.dz, Dimension::Length),
+            ..*self
+        }
+    }
+}
+
+impl ConvertUnits for Reactions {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            fx: c(self.fx, Dimension::Force),
+            fy: c(self.fy, Dimension::Force),
+            fz: c(self.fz, Dimension::Force),
+            mx: c(self.mx, Dimension::Moment),
+            my: c(self.my, Dimension::Moment),
+            mz: c(self.mz, Dimension::Moment),
+        }
+    }
+}
+
+impl ConvertUnits for MemberForces {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            axial: c(self.axial, Dimension::Force),
+            shear_y: c(self.shear_y, Dimension::Force),
+            shear_z: c(self.shear_z, Dimension::Force),
+            torsion: c(self.torsion, Dimension::Moment),
+            moment_y: c(self.moment_y, Dimension::Moment),
+            moment_z: c(self.moment_z, Dimension::Moment),
+        }
+    }
+}
+
+impl ConvertUnits for MemberDeflection {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            x: c(self.x, Dimension::Length),
+            dy: c(self.dy, Dimension::Length),
+            dz: c(self.dz, Dimension::Length),
+            relative_y: c(self.relative_y, Dimension::Length),
+            relative_z: c(self.relative_z, Dimension::Length),
+        }
+    }
+}
+
+impl ConvertUnits for PlateStress {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        let c = converter(*from, *to);
+        Self {
+            sx: c(self.sx, Dimension::Stress),
+            sy: c(self.sy, Dimension::Stress),
+            txy: c(self.txy, Dimension::Stress),
+            von_mises: c(self.von_mises, Dimension::Stress),
+            s1: c(self.s1, Dimension::Stress),
+            s2: c(self.s2, Dimension::Stress),
+        }
+    }
+}
+
+impl<T: ConvertUnits> ConvertUnits for Vec<T> {
+    fn convert_units(&self, from: &UnitSystem, to: &UnitSystem) -> Self {
+        self.iter().map(|value| value.convert_units(from, to)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dimension_conversions() {
+        let kip_in = UnitSystem::KIP_IN;
+        // 1 ksi = 6.894757 MPa, 1 kip·ft = 1.355818 kN·m
+        assert_relative_eq!(kip_in.convert(1.0, Dimension::Stress, &UnitSystem::N_MM), 6.894757, max_relative = 1e-6);
+        assert_relative_eq!(UnitSystem::KIP_FT.convert(1.0, Dimension::Moment, &UnitSystem::KN_M), 1.355818, max_relative = 1e-6);
+        // Steel density 7850 kg/m³ is 7.85 t/m³ in kN-m units
+        assert_relative_eq!(UnitSystem::SI.convert(7850.0, Dimension::Density, &UnitSystem::KN_M), 7.85);
+        assert_eq!(UnitSystem::KN_MM.label(Dimension::Stress), "kN/mm²");
+        assert_eq!(UnitSystem::KIP_FT.to_string(), "kip, ft");
+
+        // Round trip of a section, including its shape dimensions
+        let section = Section::wide_flange(0.3, 0.15, 0.012, 0.008);
+        let mm = section.convert_units(&UnitSystem::SI, &UnitSystem::N_MM);
+        assert_relative_eq!(mm.iy, section.iy * 1e12, max_relative = 1e-12);
+        assert!(matches!(mm.shape, Some(SectionShape::WideFlange { depth, .. }) if (depth - 300.0).abs() < 1e-9));
+        let back = mm.convert_units(&UnitSystem::N_MM, &UnitSystem::SI);
+        assert_relative_eq!(back.a, section.a, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_model_in_kn_mm() {
+        use crate::design::aisc::Aisc360;
+        use crate::design::{CodeCheck, DesignSettings};
+        use crate::elements::{Member, Node, Support};
+        use crate::model::FEModel;
+
+        // The same cantilever built in SI and in kN-mm
+        let build = |units: UnitSystem| {
+            let mut model = FEModel::new();
+            model.units = units;
+            let c = |v: f64, d: Dimension| UnitSystem::SI.convert(v, d, &units);
+            model.add_material("Steel", model.from_units(Material::steel(), &UnitSystem::SI)).unwrap();
+            model.add_section("W", model.from_units(Section::wide_flange(0.3, 0.15, 0.012, 0.008), &UnitSystem::SI))
+                .unwrap();
+            model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+            model.add_node("B", Node::new(c(3.0, Dimension::Length), 0.0, 0.0)).unwrap();
+            model.add_member("M1", Member::new("A", "B", "Steel", "W")).unwrap();
+            model.add_support("A", Support::fixed()).unwrap();
+            model.add_node_load("B", model.from_units(NodeLoad::fy(-10.0, "Case 1"), &UnitSystem::KN_M)).unwrap();
+            model.analyze_linear().unwrap();
+            model
+        };
+        let (si, kn_mm) = (build(UnitSystem::SI), build(UnitSystem::KN_MM));
+
+        let tip = kn_mm.node_displacement("B", "Combo 1").unwrap();
+        let expected = si.to_units(si.node_displacement("B", "Combo 1").unwrap(), &UnitSystem::KN_MM);
+        assert_relative_eq!(tip.dy, expected.dy, max_relative = 1e-9);
+        assert_relative_eq!(tip.rz, expected.rz, max_relative = 1e-9);
+        let moment = kn_mm.to_units(kn_mm.node_reactions("A", "Combo 1").unwrap(), &UnitSystem::KN_M).mz;
+        assert_relative_eq!(moment, 30.0, max_relative = 1e-9);
+
+        // Design checks run in SI either way
+        let ratio = |model: &FEModel| Aisc360::new().check_model(model, &DesignSettings::new()).unwrap()["M1"]["Combo 1"]
+            .ratio();
+        assert_relative_eq!(ratio(&kn_mm), ratio(&si), max_relative = 1e-9);
+    }
+}
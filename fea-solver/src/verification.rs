@@ -0,0 +1,402 @@
+//! Analytical verification problems
+//!
+//! Each [`Benchmark`] builds a small model of a textbook problem and pairs
+//! the solver's results with the closed-form answer. The same problems back
+//! the crate's own tests and make a confidence report that users can run on
+//! their build:
+//!
+//! ```rust
+//! use fea_solver::verification;
+//!
+//! let report = verification::run_all().unwrap();
+//! println!("{}", report.to_markdown());
+//! # assert!(report.passes());
+//! ```
+//!
+//! Beam problems are exact for the Euler-Bernoulli members used by the
+//! solver. The portal frame's closed form ignores axial deformation and the
+//! plate problems compare a coarse mesh with series solutions, so both carry
+//! looser tolerances.
+
+use std::fmt;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::elements::{Material, Member, Node, Plate, Section, Support};
+use crate::error::FEAResult;
+use crate::loads::{DistributedLoad, NodeLoad, PlateLoad};
+use crate::model::FEModel;
+
+/// Extracts a result from an analysed model
+type Extract = Box<dyn Fn(&FEModel) -> FEAResult<f64> + Send + Sync>;
+
+/// A closed-form value a benchmark is checked against
+pub struct BenchmarkCheck {
+    /// What is compared, e.g. "Tip deflection"
+    pub label: String,
+    /// Closed-form answer
+    pub expected: f64,
+    /// Allowed relative error
+    pub tolerance: f64,
+    extract: Extract,
+}
+
+impl fmt::Debug for BenchmarkCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BenchmarkCheck")
+            .field("label", &self.label)
+            .field("expected", &self.expected)
+            .field("tolerance", &self.tolerance)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A canonical problem with a closed-form solution
+#[derive(Debug)]
+pub struct Benchmark {
+    /// Short name
+    pub name: String,
+    /// Geometry, supports and loading
+    pub description: String,
+    /// Source of the closed-form answer
+    pub reference: String,
+    /// Unanalysed model of the problem, analysed for the "Combo 1" combination
+    pub model: FEModel,
+    /// Values compared with the solver's results
+    pub checks: Vec<BenchmarkCheck>,
+}
+
+impl Benchmark {
+    fn new(name: &str, description: &str, reference: &str, model: FEModel) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            reference: reference.to_string(),
+            model,
+            checks: Vec::new(),
+        }
+    }
+
+    fn with_check(
+        mut self,
+        label: &str,
+        expected: f64,
+        tolerance: f64,
+        extract: impl Fn(&FEModel) -> FEAResult<f64> + Send + Sync + 'static,
+    ) -> Self {
+        self.checks.push(BenchmarkCheck {
+            label: label.to_string(),
+            expected,
+            tolerance,
+            extract: Box::new(extract),
+        });
+        self
+    }
+
+    /// Cantilever with a point load at its tip
+    pub fn cantilever_point_load() -> Self {
+        let (l, p) = (3.0, 10e3);
+        let section = Section::rectangular(0.2, 0.4);
+        let ei = Material::steel().e * section.iz;
+
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", section).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(l, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fy(-p, "Case 1")).unwrap();
+
+        Self::new(
+            "Cantilever, tip load",
+            "3 m steel cantilever, 200 x 400 mm, 10 kN downward at the tip",
+            "Roark's Formulas for Stress and Strain, Table 8.1 case 1a",
+            model,
+        )
+        .with_check("Tip deflection", -p * l.powi(3) / (3.0 * ei), 1e-6, |m| {
+            Ok(m.node_displacement("B", "Combo 1")?.dy)
+        })
+        .with_check("Tip rotation", -p * l * l / (2.0 * ei), 1e-6, |m| {
+            Ok(m.node_displacement("B", "Combo 1")?.rz)
+        })
+        .with_check("Support moment", p * l, 1e-6, |m| Ok(m.node_reactions("A", "Combo 1")?.mz))
+    }
+
+    /// Simply supported beam with a uniform load
+    pub fn simply_supported_udl() -> Self {
+        let (l, w) = (6.0, 5e3);
+        let section = Section::rectangular(0.2, 0.4);
+        let ei = Material::steel().e * section.iz;
+
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Beam", section).unwrap();
+        model.add_node("A", Node::new(0.0, 0.0, 0.0)).unwrap();
+        model.add_node("B", Node::new(l, 0.0, 0.0)).unwrap();
+        model.add_member("M1", Member::new("A", "B", "Steel", "Beam")).unwrap();
+        model.add_support("A", Support::with_restraints(true, true, true, true, false, false)).unwrap();
+        model.add_support("B", Support::with_restraints(false, true, true, false, false, false)).unwrap();
+        model.add_member_dist_load("M1", DistributedLoad::uniform_downward(w, "Case 1")).unwrap();
+
+        Self::new(
+            "Simply supported beam, uniform load",
+            "6 m steel beam, 200 x 400 mm, 5 kN/m downward over the full span",
+            "Roark's Formulas for Stress and Strain, Table 8.1 case 2e",
+            model,
+        )
+        .with_check("Midspan deflection", -5.0 * w * l.powi(4) / (384.0 * ei), 1e-6, move |m| {
+            Ok(m.member_deflections("M1", "Combo 1", 3)?[1].dy)
+        })
+        .with_check("Midspan moment", w * l * l / 8.0, 1e-6, move |m| {
+            Ok(m.member_forces_at("M1", l / 2.0, "Combo 1")?.moment_z.abs())
+        })
+        .with_check("End reaction", w * l / 2.0, 1e-6, |m| Ok(m.node_reactions("A", "Combo 1")?.fy))
+    }
+
+    /// Fixed-base portal frame under a lateral load at beam level
+    pub fn portal_frame_sway() -> Self {
+        let (h, l, p) = (4.0, 6.0, 20e3);
+        let (column, beam) = (Section::rectangular(0.3, 0.3), Section::rectangular(0.4, 0.4));
+        let e = Material::steel().e;
+
+        // Slope-deflection with equal joint rotations: c and b are the column
+        // and beam stiffnesses EI/h and EI/L
+        let (c, b) = (e * column.iz / h, e * beam.iz / l);
+        let sway = p * h * h * (2.0 * c + 3.0 * b) / (12.0 * c * (c + 6.0 * b));
+        let rotation = 3.0 * c * (sway / h) / (2.0 * c + 3.0 * b);
+        let base_moment = 2.0 * c * (3.0 * sway / h - rotation);
+
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        model.add_section("Column", column).unwrap();
+        model.add_section("Beam", beam).unwrap();
+        for (name, x, y) in [("A", 0.0, 0.0), ("B", 0.0, h), ("C", l, h), ("D", l, 0.0)] {
+            model.add_node(name, Node::new(x, y, 0.0)).unwrap();
+        }
+        model.add_member("C1", Member::new("A", "B", "Steel", "Column")).unwrap();
+        model.add_member("B1", Member::new("B", "C", "Steel", "Beam")).unwrap();
+        model.add_member("C2", Member::new("D", "C", "Steel", "Column")).unwrap();
+        model.add_support("A", Support::fixed()).unwrap();
+        model.add_support("D", Support::fixed()).unwrap();
+        model.add_node_load("B", NodeLoad::fx(p, "Case 1")).unwrap();
+
+        // The closed form ignores axial deformation, which tilts the beam as
+        // the columns stretch and shorten under the overturning moment
+        Self::new(
+            "Portal frame, lateral load",
+            "Fixed-base steel portal, 4 m high, 6 m span, 300 mm square columns, \
+             400 mm square beam, 20 kN lateral at beam level",
+            "Slope-deflection method, e.g. Hibbeler, Structural Analysis, chapter 11",
+            model,
+        )
+        .with_check("Sway", sway, 0.02, |m| Ok(m.node_displacement("C", "Combo 1")?.dx))
+        .with_check("Joint rotation", -rotation, 0.02, |m| Ok(m.node_displacement("B", "Combo 1")?.rz))
+        .with_check("Base moment", base_moment, 0.02, |m| Ok(m.node_reactions("A", "Combo 1")?.mz.abs()))
+    }
+
+    /// Simply supported square plate under uniform pressure
+    pub fn simply_supported_plate() -> Self {
+        // Roark: y = alpha q a^4 / (E t^3), alpha = 0.0444 for a/b = 1 and nu = 0.3
+        Self::square_plate(
+            "Simply supported square plate, uniform pressure",
+            "Roark's Formulas for Stress and Strain, Table 11.4 case 1a",
+            false,
+            0.0444,
+        )
+    }
+
+    /// Square plate with clamped edges under uniform pressure
+    pub fn clamped_plate() -> Self {
+        // Roark: y = alpha q a^4 / (E t^3), alpha = 0.0138 for a/b = 1 and nu = 0.3
+        Self::square_plate(
+            "Clamped square plate, uniform pressure",
+            "Roark's Formulas for Stress and Strain, Table 11.4 case 8",
+            true,
+            0.0138,
+        )
+    }
+
+    /// 2 m square, 20 mm steel plate in the XY plane on an 8 x 8 mesh
+    fn square_plate(name: &str, reference: &str, clamped: bool, alpha: f64) -> Self {
+        const N: usize = 8;
+        let (a, t, q) = (2.0, 0.02, 10e3);
+        let node = |i: usize, j: usize| format!("N{i}_{j}");
+
+        let mut model = FEModel::new();
+        model.add_material("Steel", Material::steel()).unwrap();
+        for j in 0..=N {
+            for i in 0..=N {
+                let (x, y) = (a * i as f64 / N as f64, a * j as f64 / N as f64);
+                model.add_node(&node(i, j), Node::new(x, y, 0.0)).unwrap();
+                if i == 0 || j == 0 || i == N || j == N {
+                    // Edge supports, with a corner holding the plate in its plane
+                    let corner = i == 0 && j == 0;
+                    let support = Support {
+                        dx: corner,
+                        dy: corner || (i == N && j == 0),
+                        dz: true,
+                        rx: clamped && (j == 0 || j == N),
+                        ry: clamped && (i == 0 || i == N),
+                        rz: corner,
+                        ..Default::default()
+                    };
+                    model.add_support(&node(i, j), support).unwrap();
+                }
+            }
+        }
+        for j in 0..N {
+            for i in 0..N {
+                let name = format!("P{i}_{j}");
+                let plate = Plate::new(&node(i, j), &node(i + 1, j), &node(i + 1, j + 1), &node(i, j + 1), t, "Steel");
+                model.add_plate(&name, plate).unwrap();
+                model.add_plate_load(&name, PlateLoad::new(-q, "Case 1")).unwrap();
+            }
+        }
+
+        let edges = if clamped { "clamped" } else { "simply supported" };
+        let description = format!("2 m square, 20 mm steel plate, {edges} edges, 10 kPa pressure, 8 x 8 mesh");
+        let deflection = alpha * q * a.powi(4) / (Material::steel().e * t.powi(3));
+        let centre = node(N / 2, N / 2);
+        Self::new(name, &description, reference, model).with_check("Centre deflection", deflection, 0.05, move |m| {
+            Ok(m.node_displacement(&centre, "Combo 1")?.dz.abs())
+        })
+    }
+
+    /// Analyse a copy of the model and compare its results with the closed-form answers
+    pub fn run(&self) -> FEAResult<BenchmarkResult> {
+        let mut model = self.model.clone();
+        model.analyze_linear()?;
+        let checks = self.checks.iter()
+            .map(|check| {
+                let computed = (check.extract)(&model)?;
+                Ok(CheckResult {
+                    label: check.label.clone(),
+                    expected: check.expected,
+                    computed,
+                    error: (computed - check.expected).abs() / check.expected.abs(),
+                    tolerance: check.tolerance,
+                })
+            })
+            .collect::<FEAResult<Vec<_>>>()?;
+        Ok(BenchmarkResult {
+            name: self.name.clone(),
+            reference: self.reference.clone(),
+            checks,
+        })
+    }
+}
+
+/// Every benchmark problem
+pub fn benchmarks() -> Vec<Benchmark> {
+    vec![
+        Benchmark::cantilever_point_load(),
+        Benchmark::simply_supported_udl(),
+        Benchmark::portal_frame_sway(),
+        Benchmark::simply_supported_plate(),
+        Benchmark::clamped_plate(),
+    ]
+}
+
+/// Run every benchmark problem
+pub fn run_all() -> FEAResult<VerificationReport> {
+    let results = benchmarks().iter().map(Benchmark::run).collect::<FEAResult<Vec<_>>>()?;
+    Ok(VerificationReport { results })
+}
+
+/// Solver result next to its closed-form answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// What is compared
+    pub label: String,
+    /// Closed-form answer
+    pub expected: f64,
+    /// Solver result
+    pub computed: f64,
+    /// Relative error of the solver result
+    pub error: f64,
+    /// Allowed relative error
+    pub tolerance: f64,
+}
+
+impl CheckResult {
+    /// Whether the error is within the tolerance
+    pub fn passes(&self) -> bool {
+        self.error <= self.tolerance
+    }
+}
+
+/// Results of one benchmark problem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// Benchmark name
+    pub name: String,
+    /// Source of the closed-form answers
+    pub reference: String,
+    /// Compared values
+    pub checks: Vec<CheckResult>,
+}
+
+impl BenchmarkResult {
+    /// Whether every value is within its tolerance
+    pub fn passes(&self) -> bool {
+        self.checks.iter().all(CheckResult::passes)
+    }
+}
+
+/// Results of every benchmark problem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Results by benchmark, in run order
+    pub results: Vec<BenchmarkResult>,
+}
+
+impl VerificationReport {
+    /// Whether every benchmark passes
+    pub fn passes(&self) -> bool {
+        self.results.iter().all(BenchmarkResult::passes)
+    }
+
+    /// Markdown table of every compared value
+    pub fn to_markdown(&self) -> String {
+        let passed = self.results.iter().filter(|r| r.passes()).count();
+        let mut out = String::from("# Solver verification\n\n");
+        let _ = writeln!(out, "{passed} of {} benchmarks pass.\n", self.results.len());
+        for result in &self.results {
+            let _ = writeln!(out, "## {}\n\n{}\n", result.name, result.reference);
+            out.push_str("| Quantity | Expected | Computed | Error | Tolerance | Status |\n");
+            out.push_str("|---|---|---|---|---|---|\n");
+            for check in &result.checks {
+                let _ = writeln!(
+                    out,
+                    "| {} | {:.4e} | {:.4e} | {:.3}% | {}% | {} |",
+                    check.label,
+                    check.expected,
+                    check.computed,
+                    100.0 * check.error,
+                    100.0 * check.tolerance,
+                    if check.passes() { "OK" } else { "FAIL" },
+                );
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmarks_match_closed_form() {
+        let report = run_all().unwrap();
+        for result in &report.results {
+            for check in &result.checks {
+                assert!(check.passes(), "{}: {:?}", result.name, check);
+            }
+        }
+        assert!(report.to_markdown().contains("5 of 5 benchmarks pass."));
+    }
+}
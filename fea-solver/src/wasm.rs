@@ -0,0 +1,627 @@
+//! WebAssembly bindings for analysing models in the browser
+//!
+//! Build with `wasm-pack build --target web --features wasm --no-default-features`.
+//! [`WasmModel`] mirrors the native `FEModel` API: models are built with
+//! `addNode`, `addMember`, `addSupport` and so on, analysed, and queried
+//! through getters returning plain JS objects typed by the generated
+//! TypeScript declarations. Whole models can also cross the boundary as
+//! JSON project files (see [`crate::io::json`]).
+//!
+//! ```js
+//! const model = new WasmModel();
+//! model.addMaterial("Steel", 200e9, 77e9, 0.3, 7850);
+//! model.addSection("Beam", 0.08, 1.07e-3, 2.67e-4, 7e-4);
+//! model.addNode("A", 0, 0, 0);
+//! model.addNode("B", 3, 0, 0);
+//! model.addMember("M1", "A", "B", "Steel", "Beam");
+//! model.addSupport("A", true, true, true, true, true, true);
+//! model.addNodeLoad("B", 0, -1000, 0, 0, 0, 0, "Dead");
+//! model.addLoadCombo("1.4D", { Dead: 1.4 });
+//! model.analyze({ analysis_type: "Linear" });
+//! const { dy } = model.nodeDisplacement("B", "1.4D");
+//! ```
+//!
+//! With the `wasm-parallel` feature, assembly and load combinations run on
+//! rayon's thread pool of web workers. The page must be cross-origin isolated
+//! for `SharedArrayBuffer`, and start the pool once before analysing:
+//!
+//! ```js
+//! import init, { initThreadPool, analyze } from "./pkg/fea_solver.js";
+//!
+//! await init();
+//! await initThreadPool(navigator.hardwareConcurrency);
+//! const solved = analyze(projectJson);
+//! ```
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use crate::analysis::{AnalysisOptions, SteppedAnalysis};
+use crate::elements::{Material, Member, Node, Plate, Quad, Section, Support};
+use crate::error::FEAError;
+use crate::handles::{Handle, Registry};
+use crate::loads::{DistributedLoad, LoadCombination, LoadDirection, NodeLoad, PlateLoad, PointLoad};
+use crate::model::FEModel;
+use crate::results::ResultTable;
+
+#[cfg(feature = "wasm-parallel")]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+// wee_alloc cannot be shared between the workers of a threaded build
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm-parallel")))]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+/// Route Rust panics to the browser console
+#[wasm_bindgen]
+pub fn init() {
+    console_error_panic_hook::set_once();
+}
+
+/// Crate version
+#[wasm_bindgen]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Analyse a JSON project file and return it with its results
+///
+/// `options_json` is a serialised `AnalysisOptions`; a linear analysis is
+/// run when it is omitted.
+#[wasm_bindgen]
+pub fn analyze(project_json: &str, options_json: Option<String>) -> Result<String, JsError> {
+    let mut model = FEModel::from_json(project_json)?;
+    let options = match options_json {
+        Some(json) => serde_json::from_str(&json)?,
+        None => AnalysisOptions::linear(),
+    };
+    model.analyze(options)?;
+    Ok(model.to_json_with_results()?)
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const RESULT_TYPES: &str = r#"
+export interface NodeDisplacement { dx: number; dy: number; dz: number; rx: number; ry: number; rz: number }
+export interface Reactions { fx: number; fy: number; fz: number; mx: number; my: number; mz: number }
+export interface MemberForces {
+    axial: number; shear_y: number; shear_z: number; torsion: number; moment_y: number; moment_z: number
+}
+export interface MemberDeflection { x: number; dy: number; dz: number; relative_y: number; relative_z: number }
+export interface PlateStressResult {
+    sx: number; sy: number; txy: number; von_mises: number; mx: number; my: number; mxy: number
+}
+export interface AnalysisSummary {
+    max_displacement: number; max_disp_node: string; max_reaction: number; max_reaction_node: string;
+    max_axial: number; max_axial_member: string; max_moment: number; max_moment_member: string;
+    num_nodes: number; num_members: number; num_plates: number; total_dofs: number; free_dofs: number;
+    uplift_nodes: string[]; original_bandwidth: number; bandwidth: number
+}
+export interface AnalysisProgress {
+    stage: "prepare" | "assemble" | "solve"; combo: string | null; completed: number; total: number
+}
+export type LoadDirection = "Fx" | "Fy" | "Fz" | "Mx" | "My" | "Mz" | "FX" | "FY" | "FZ";
+"#;
+
+/// A structural model built, analysed and queried from JavaScript
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct WasmModel {
+    /// Shared with the future of a pending `analyzeAsync`, which keeps it
+    /// borrowed until the analysis ends
+    model: Rc<RefCell<FEModel>>,
+}
+
+#[wasm_bindgen]
+impl WasmModel {
+    /// Create an empty model
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a model, and any saved results, from a JSON project file
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmModel, JsError> {
+        Ok(Self { model: Rc::new(RefCell::new(FEModel::from_json(json)?)) })
+    }
+
+    /// Serialise the model to a JSON project file, optionally with its results
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self, with_results: bool) -> Result<String, JsError> {
+        let model = self.model()?;
+        let json = if with_results { model.to_json_with_results() } else { model.to_json() };
+        Ok(json?)
+    }
+
+    // ========================
+    // Model Building
+    // ========================
+
+    /// Add a node at global coordinates
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, name: &str, x: f64, y: f64, z: f64) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_node(name, Node::new(x, y, z))?)
+    }
+
+    /// Add a material from its elastic and shear moduli, Poisson's ratio and density
+    #[wasm_bindgen(js_name = addMaterial)]
+    pub fn add_material(&mut self, name: &str, e: f64, g: f64, nu: f64, rho: f64) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_material(name, Material::new(e, g, nu, rho))?)
+    }
+
+    /// Add a section from its area, second moments of area and torsion constant
+    #[wasm_bindgen(js_name = addSection)]
+    pub fn add_section(&mut self, name: &str, a: f64, iy: f64, iz: f64, j: f64) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_section(name, Section::new(a, iy, iz, j))?)
+    }
+
+    /// Add a frame member, optionally rotated about its axis (radians)
+    #[wasm_bindgen(js_name = addMember)]
+    pub fn add_member(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        material: &str,
+        section: &str,
+        rotation: Option<f64>,
+    ) -> Result<(), JsError> {
+        let member = Member::new(i_node, j_node, material, section).with_rotation(rotation.unwrap_or(0.0));
+        Ok(self.model_mut()?.add_member(name, member)?)
+    }
+
+    /// Add a rectangular plate with corner nodes in i, j, m, n order
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addPlate)]
+    pub fn add_plate(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        m_node: &str,
+        n_node: &str,
+        thickness: f64,
+        material: &str,
+    ) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_plate(name, Plate::new(i_node, j_node, m_node, n_node, thickness, material))?)
+    }
+
+    /// Add a general quadrilateral shell with corner nodes in i, j, m, n order
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addQuad)]
+    pub fn add_quad(
+        &mut self,
+        name: &str,
+        i_node: &str,
+        j_node: &str,
+        m_node: &str,
+        n_node: &str,
+        thickness: f64,
+        material: &str,
+    ) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_quad(name, Quad::new(i_node, j_node, m_node, n_node, thickness, material))?)
+    }
+
+    /// Restrain a node's DX, DY, DZ, RX, RY and RZ
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addSupport)]
+    pub fn add_support(
+        &mut self,
+        node: &str,
+        dx: bool,
+        dy: bool,
+        dz: bool,
+        rx: bool,
+        ry: bool,
+        rz: bool,
+    ) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_support(node, Support::with_restraints(dx, dy, dz, rx, ry, rz))?)
+    }
+
+    /// Add global forces and moments at a node
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addNodeLoad)]
+    pub fn add_node_load(
+        &mut self,
+        node: &str,
+        fx: f64,
+        fy: f64,
+        fz: f64,
+        mx: f64,
+        my: f64,
+        mz: f64,
+        case: &str,
+    ) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_node_load(node, NodeLoad::new(fx, fy, fz, mx, my, mz, case))?)
+    }
+
+    /// Add a point load at distance `position` along a member
+    #[wasm_bindgen(js_name = addMemberPointLoad)]
+    pub fn add_member_point_load(
+        &mut self,
+        member: &str,
+        magnitude: f64,
+        position: f64,
+        #[wasm_bindgen(unchecked_param_type = "LoadDirection")] direction: &str,
+        case: &str,
+    ) -> Result<(), JsError> {
+        let load = PointLoad::new(magnitude, position, load_direction(direction)?, case);
+        Ok(self.model_mut()?.add_member_point_load(member, load)?)
+    }
+
+    /// Add a load varying linearly from `w1` at `x1` to `w2` at `x2` along a member
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addMemberDistLoad)]
+    pub fn add_member_dist_load(
+        &mut self,
+        member: &str,
+        w1: f64,
+        w2: f64,
+        x1: f64,
+        x2: f64,
+        #[wasm_bindgen(unchecked_param_type = "LoadDirection")] direction: &str,
+        case: &str,
+    ) -> Result<(), JsError> {
+        let load = DistributedLoad::new(w1, w2, x1, x2, load_direction(direction)?, case);
+        Ok(self.model_mut()?.add_member_dist_load(member, load)?)
+    }
+
+    /// Add a uniform pressure normal to a plate or quad
+    #[wasm_bindgen(js_name = addPlateLoad)]
+    pub fn add_plate_load(&mut self, plate: &str, pressure: f64, case: &str) -> Result<(), JsError> {
+        Ok(self.model_mut()?.add_plate_load(plate, PlateLoad::new(pressure, case))?)
+    }
+
+    /// Add a load combination from an object of load case factors
+    #[wasm_bindgen(js_name = addLoadCombo)]
+    pub fn add_load_combo(
+        &mut self,
+        name: &str,
+        #[wasm_bindgen(unchecked_param_type = "Record<string, number>")] factors: JsValue,
+    ) -> Result<(), JsError> {
+        let factors: BTreeMap<String, f64> = from_js(factors)?;
+        let combo = factors.iter().fold(LoadCombination::new(name), |combo, (case, &factor)| combo.with_case(case, factor));
+        Ok(self.model_mut()?.add_load_combo(combo)?)
+    }
+
+    // ========================
+    // Analysis
+    // ========================
+
+    /// Run an analysis; `options` holds any `AnalysisOptions` fields to
+    /// change from their defaults, e.g. `{ analysis_type: "PDelta" }`
+    pub fn analyze(&mut self, options: JsValue) -> Result<(), JsError> {
+        let options = analysis_options(options)?;
+        Ok(self.model_mut()?.analyze(options)?)
+    }
+
+    /// Run an analysis without blocking the page
+    ///
+    /// Returns a promise resolving once every load combination is solved.
+    /// The analysis yields to the event loop after preparing the model,
+    /// after each chunk of `chunkSize` members assembled (1000 by default)
+    /// and after each combination solved, first calling `onProgress` with an
+    /// `AnalysisProgress`. The analysis starts on the next microtask; from
+    /// then until the promise settles, the other methods of the model throw.
+    #[wasm_bindgen(js_name = analyzeAsync, unchecked_return_type = "Promise<void>")]
+    // The model stays borrowed across the yields so that it cannot change
+    // under the analysis; the other methods report it as busy meanwhile
+    #[allow(clippy::await_holding_refcell_ref)]
+    pub fn analyze_async(
+        &self,
+        options: JsValue,
+        #[wasm_bindgen(js_name = onProgress)] on_progress: Option<js_sys::Function>,
+        #[wasm_bindgen(js_name = chunkSize)] chunk_size: Option<usize>,
+    ) -> Result<js_sys::Promise, JsError> {
+        let mut run = SteppedAnalysis::new(analysis_options(options)?);
+        if let Some(members) = chunk_size {
+            run = run.with_chunk_size(members);
+        }
+        let model = Rc::clone(&self.model);
+        Ok(future_to_promise(async move {
+            let mut model = model.try_borrow_mut().map_err(|_| busy())?;
+            while !run.is_finished() {
+                let progress = model.step_analysis(&mut run).map_err(JsError::from)?;
+                if let Some(callback) = &on_progress {
+                    callback.call1(&JsValue::NULL, &to_js(&progress)?)?;
+                }
+                next_task().await?;
+            }
+            Ok(JsValue::UNDEFINED)
+        }))
+    }
+
+    /// Load combination names, in analysis order
+    #[wasm_bindgen(js_name = loadCombos)]
+    pub fn load_combos(&self) -> Result<Vec<String>, JsError> {
+        Ok(self.model()?.load_combos.keys().cloned().collect())
+    }
+
+    /// Warnings raised by the last analysis
+    pub fn warnings(&self) -> Result<Vec<String>, JsError> {
+        Ok(self.model()?.warnings().to_vec())
+    }
+
+    // ========================
+    // Results
+    // ========================
+
+    /// Displacements of a node
+    #[wasm_bindgen(js_name = nodeDisplacement, unchecked_return_type = "NodeDisplacement")]
+    pub fn node_displacement(&self, node: &str, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.node_displacement(node, combo)?)
+    }
+
+    /// Reactions at a supported node
+    #[wasm_bindgen(js_name = nodeReactions, unchecked_return_type = "Reactions")]
+    pub fn node_reactions(&self, node: &str, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.node_reactions(node, combo)?)
+    }
+
+    /// Internal forces at a member's i-node
+    #[wasm_bindgen(js_name = memberForcesI, unchecked_return_type = "MemberForces")]
+    pub fn member_forces_i(&self, member: &str, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.member_forces_i(member, combo)?)
+    }
+
+    /// Internal forces at a member's j-node
+    #[wasm_bindgen(js_name = memberForcesJ, unchecked_return_type = "MemberForces")]
+    pub fn member_forces_j(&self, member: &str, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.member_forces_j(member, combo)?)
+    }
+
+    /// Internal forces at distance `x` along a member
+    #[wasm_bindgen(js_name = memberForcesAt, unchecked_return_type = "MemberForces")]
+    pub fn member_forces_at(&self, member: &str, x: f64, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.member_forces_at(member, x, combo)?)
+    }
+
+    /// Internal forces at `points` equally spaced stations along a member,
+    /// as [x, forces] pairs
+    #[wasm_bindgen(js_name = memberDiagram, unchecked_return_type = "[number, MemberForces][]")]
+    pub fn member_diagram(&self, member: &str, combo: &str, points: usize) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.member_diagram(member, combo, points)?)
+    }
+
+    /// Deflected shape of a member at `points` equally spaced stations
+    #[wasm_bindgen(js_name = memberDeflections, unchecked_return_type = "MemberDeflection[]")]
+    pub fn member_deflections(&self, member: &str, combo: &str, points: usize) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.member_deflections(member, combo, points)?)
+    }
+
+    /// Centroid stresses and moments of a plate or quad
+    #[wasm_bindgen(js_name = plateStress, unchecked_return_type = "PlateStressResult")]
+    pub fn plate_stress(&self, plate: &str, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.plate_stress(plate, combo)?)
+    }
+
+    /// Maximum displacement, reaction and member forces of a combination
+    #[wasm_bindgen(unchecked_return_type = "AnalysisSummary")]
+    pub fn summary(&self, combo: &str) -> Result<JsValue, JsError> {
+        to_js(&self.model()?.summary(combo)?)
+    }
+
+    // ========================
+    // Binary Results
+    // ========================
+    //
+    // Whole result sets of a combination as `Float64Array`s, copied straight
+    // from the result tables instead of building one JS object per entity.
+    // Rows follow the order of the matching `*Names` list, which is the
+    // analysis numbering and may change whenever the model is re-analysed.
+
+    /// Node names in the row order of `displacementArray`
+    #[wasm_bindgen(js_name = nodeNames)]
+    pub fn node_names(&self) -> Result<Vec<String>, JsError> {
+        Ok(names(self.model()?.result_store().displacements.rows()))
+    }
+
+    /// Supported node names in the row order of `reactionArray`
+    #[wasm_bindgen(js_name = supportNames)]
+    pub fn support_names(&self) -> Result<Vec<String>, JsError> {
+        Ok(names(self.model()?.result_store().reactions.rows()))
+    }
+
+    /// Member names in the row order of `memberForceArray`
+    #[wasm_bindgen(js_name = memberNames)]
+    pub fn member_names(&self) -> Result<Vec<String>, JsError> {
+        Ok(names(self.model()?.result_store().member_forces.rows()))
+    }
+
+    /// Plate then quad names, in the row order of `plateStressArray`
+    #[wasm_bindgen(js_name = plateNames)]
+    pub fn plate_names(&self) -> Result<Vec<String>, JsError> {
+        let model = self.model()?;
+        Ok(model.plates.keys().chain(model.quads.keys()).cloned().collect())
+    }
+
+    /// [DX, DY, DZ, RX, RY, RZ] of every node
+    #[wasm_bindgen(js_name = displacementArray)]
+    pub fn displacement_array(&self, combo: &str) -> Result<Vec<f64>, JsError> {
+        column(&self.model()?.result_store().displacements, combo)
+    }
+
+    /// [FX, FY, FZ, MX, MY, MZ] of every supported node
+    #[wasm_bindgen(js_name = reactionArray)]
+    pub fn reaction_array(&self, combo: &str) -> Result<Vec<f64>, JsError> {
+        column(&self.model()?.result_store().reactions, combo)
+    }
+
+    /// The 12 local end forces [Fx_i, Fy_i, Fz_i, Mx_i, My_i, Mz_i, Fx_j, ..., Mz_j] of every member
+    #[wasm_bindgen(js_name = memberForceArray)]
+    pub fn member_force_array(&self, combo: &str) -> Result<Vec<f64>, JsError> {
+        column(&self.model()?.result_store().member_forces, combo)
+    }
+
+    /// [sx, sy, txy, von_mises, mx, my, mxy] at the centroid of every plate and quad
+    #[wasm_bindgen(js_name = plateStressArray)]
+    pub fn plate_stress_array(&self, combo: &str) -> Result<Vec<f64>, JsError> {
+        let names = self.plate_names()?;
+        let model = self.model()?;
+        let mut values = Vec::with_capacity(7 * names.len());
+        for name in names {
+            let s = model.plate_stress(&name, combo)?;
+            values.extend([s.sx, s.sy, s.txy, s.von_mises, s.mx, s.my, s.mxy]);
+        }
+        Ok(values)
+    }
+}
+
+impl WasmModel {
+    fn model(&self) -> Result<Ref<'_, FEModel>, JsError> {
+        self.model.try_borrow().map_err(|_| busy())
+    }
+
+    fn model_mut(&self) -> Result<RefMut<'_, FEModel>, JsError> {
+        self.model.try_borrow_mut().map_err(|_| busy())
+    }
+}
+
+fn busy() -> JsError {
+    JsError::new("The model is busy with an analysis")
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(callback: &js_sys::Function, delay: i32);
+}
+
+/// Resolve on a later turn of the event loop, letting the page render and
+/// handle input in between
+async fn next_task() -> Result<(), JsValue> {
+    let tick = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    JsFuture::from(tick).await.map(|_| ())
+}
+
+/// Analysis options from any `AnalysisOptions` fields; linear when omitted
+fn analysis_options(options: JsValue) -> Result<AnalysisOptions, JsError> {
+    if options.is_undefined() || options.is_null() {
+        Ok(AnalysisOptions::linear())
+    } else {
+        from_js(options)
+    }
+}
+
+fn names<I: Handle>(rows: &Registry<I>) -> Vec<String> {
+    rows.iter().map(|(_, name)| name.to_string()).collect()
+}
+
+fn column<I: Handle, const N: usize>(table: &ResultTable<I, N>, combo: &str) -> Result<Vec<f64>, JsError> {
+    Ok(table.column(combo).ok_or(FEAError::NotAnalyzed)?.to_vec())
+}
+
+/// Member load direction from its name, e.g. "FY"
+fn load_direction(name: &str) -> Result<LoadDirection, JsError> {
+    Ok(serde_json::from_value(serde_json::Value::from(name))?)
+}
+
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Plain JS object (not `Map`) of a result
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    // Methods taking or returning `JsValue` need a JS host, so these tests
+    // build through the plain-argument methods, analyse the model directly
+    // and read the results back without crossing into JS
+
+    /// Cantilever A-B along X, loaded at the tip and along its length in "Case 1"
+    fn cantilever() -> WasmModel {
+        let mut model = WasmModel::new();
+        model.add_material("Steel", 200e9, 77e9, 0.3, 7850.0).unwrap();
+        model.add_section("Beam", 0.01, 1e-4, 1e-4, 2e-4).unwrap();
+        model.add_node("A", 0.0, 0.0, 0.0).unwrap();
+        model.add_node("B", 3.0, 0.0, 0.0).unwrap();
+        model.add_member("M1", "A", "B", "Steel", "Beam", None).unwrap();
+        model.add_support("A", true, true, true, true, true, true).unwrap();
+        model.add_node_load("B", 0.0, -1000.0, 0.0, 0.0, 0.0, 0.0, "Case 1").unwrap();
+        model.add_member_dist_load("M1", -200.0, -200.0, 0.0, 3.0, "FY", "Case 1").unwrap();
+        model
+    }
+
+    #[test]
+    fn test_cantilever_built_through_the_bindings() {
+        let model = cantilever();
+        model.model_mut().unwrap().analyze(AnalysisOptions::linear()).unwrap();
+        assert_eq!(model.load_combos().unwrap(), ["Combo 1"]);
+
+        // Tip deflection P L^3 / 3EI + w L^4 / 8EI; the support takes P + wL
+        let (p, w, l, ei): (f64, f64, f64, f64) = (1000.0, 200.0, 3.0, 200e9 * 1e-4);
+        let inner = model.model().unwrap();
+        let store = inner.result_store();
+        let tip = store.displacement("B", "Combo 1").unwrap();
+        assert_relative_eq!(tip[1], -(p * l.powi(3) / (3.0 * ei) + w * l.powi(4) / (8.0 * ei)), max_relative = 1e-6);
+        let reaction = store.reaction("A", "Combo 1").unwrap();
+        assert_relative_eq!(reaction[1], p + w * l, max_relative = 1e-9);
+        assert_relative_eq!(reaction[5], p * l + w * l * l / 2.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_binary_results_follow_the_name_lists() {
+        let mut model = cantilever();
+        // A propped span C-D-E and a simply supported plate alongside
+        for (name, x) in [("E", 8.0), ("C", 4.0), ("D", 6.0)] {
+            model.add_node(name, x, 0.0, 0.0).unwrap();
+        }
+        model.add_member("M2", "C", "D", "Steel", "Beam", None).unwrap();
+        model.add_member("M3", "D", "E", "Steel", "Beam", None).unwrap();
+        model.add_support("C", true, true, true, true, true, true).unwrap();
+        model.add_support("E", false, true, true, false, false, false).unwrap();
+        model.add_node_load("D", 500.0, -3000.0, 0.0, 0.0, 0.0, 0.0, "Case 1").unwrap();
+        for (name, x, z) in [("P1", 0.0, 2.0), ("P2", 2.0, 2.0), ("P3", 2.0, 3.0), ("P4", 0.0, 3.0)] {
+            model.add_node(name, x, 0.0, z).unwrap();
+            model.add_support(name, true, true, true, false, false, false).unwrap();
+        }
+        model.add_plate("S1", "P1", "P2", "P3", "P4", 0.2, "Steel").unwrap();
+        model.add_plate_load("S1", -5000.0, "Case 1").unwrap();
+        model.model_mut().unwrap().analyze(AnalysisOptions::linear()).unwrap();
+
+        let inner = model.model().unwrap();
+        let store = inner.result_store();
+        let nodes = model.node_names().unwrap();
+        let displacements = model.displacement_array("Combo 1").unwrap();
+        assert_eq!(nodes.len(), 9);
+        assert_eq!(displacements.len(), 6 * nodes.len());
+        for (row, node) in nodes.iter().enumerate() {
+            assert_eq!(displacements[6 * row..6 * row + 6], store.displacement(node, "Combo 1").unwrap());
+        }
+
+        let supports = model.support_names().unwrap();
+        let reactions = model.reaction_array("Combo 1").unwrap();
+        assert_eq!(supports.len(), 7);
+        assert_eq!(reactions.len(), 6 * supports.len());
+        for (row, node) in supports.iter().enumerate() {
+            assert_eq!(reactions[6 * row..6 * row + 6], store.reaction(node, "Combo 1").unwrap());
+        }
+
+        let members = model.member_names().unwrap();
+        let forces = model.member_force_array("Combo 1").unwrap();
+        assert_eq!(members.len(), 3);
+        assert_eq!(forces.len(), 12 * members.len());
+        for (row, member) in members.iter().enumerate() {
+            assert_eq!(forces[12 * row..12 * row + 12], store.local_force(member, "Combo 1").unwrap());
+        }
+
+        let stresses = model.plate_stress_array("Combo 1").unwrap();
+        let s = inner.plate_stress("S1", "Combo 1").unwrap();
+        assert_eq!(model.plate_names().unwrap(), ["S1"]);
+        assert_eq!(stresses, [s.sx, s.sy, s.txy, s.von_mises, s.mx, s.my, s.mxy]);
+        assert!(s.mx.abs() > 0.0);
+    }
+}
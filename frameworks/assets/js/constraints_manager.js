@@ -12,6 +12,9 @@ const constraintSymbols = new Map(); // nodeId -> constraint mesh group
 // Expose globally for visibility toggle
 window.constraintSymbols = constraintSymbols;
 
+// Size of the support glyphs relative to their default
+let supportScale = 1;
+
 // Get selectedNodes from global (set by three_canvas.js) to avoid module instance issues
 function getSelectedNodes() {
     return window.selectedNodes || new Set();
@@ -35,29 +38,38 @@ export function applyNodeConstraints(constraintData, sceneData) {
         return;
     }
     
-    // Determine support type
-    const supportType = determineSupportType(constraintData);
-    
-    console.log(`Applying ${supportType} constraint to ${selectedNodes.size} node(s)`, constraintData);
+    console.log(`Applying constraint to ${selectedNodes.size} node(s)`, constraintData);
     
     selectedNodes.forEach(node => {
         console.log('Processing node:', node);
-        // Remove existing constraint symbol if any
-        removeConstraintSymbol(node, sceneData);
-        
-        // Create new constraint symbol
-        createConstraintSymbol(node, supportType, constraintData, sceneData);
-        
-        // Store constraint data on the node
-        node.userData.constraint = {
-            ...constraintData,
-            type: supportType
-        };
+        setNodeConstraint(node, constraintData, sceneData);
     });
     
     console.log(`Constraints applied to ${selectedNodes.size} node(s)`);
 }
 
+/**
+ * Constrain one node and draw its symbol
+ * @param {THREE.Mesh} node - Node mesh
+ * @param {Object} constraintData - { dx, dy, dz, rx, ry, rz, kx, ky, kz }
+ * @param {Object} sceneData - Scene data
+ */
+export function setNodeConstraint(node, constraintData, sceneData) {
+    const supportType = determineSupportType(constraintData);
+    
+    // Remove existing constraint symbol if any
+    removeConstraintSymbol(node, sceneData);
+    
+    // Create new constraint symbol
+    createConstraintSymbol(node, supportType, constraintData, sceneData);
+    
+    // Store constraint data on the node
+    node.userData.constraint = {
+        ...constraintData,
+        type: supportType
+    };
+}
+
 /**
  * Clear constraints from selected nodes
  * @param {Object} sceneData - Scene data
@@ -147,6 +159,7 @@ function createConstraintSymbol(node, supportTypeInfo, constraintData, sceneData
     
     const group = new THREE.Group();
     group.position.copy(node.position);
+    group.scale.setScalar(supportScale);
     
     let symbol;
     
@@ -214,6 +227,22 @@ export function removeConstraintSymbol(node, sceneData) {
     }
 }
 
+/**
+ * Give a node back a saved constraint, or none, with its symbol
+ * @param {THREE.Mesh} node - Node mesh
+ * @param {Object|undefined} constraint - Saved node.userData.constraint
+ * @param {Object} sceneData - Scene data
+ */
+export function restoreNodeConstraint(node, constraint, sceneData) {
+    removeConstraintSymbol(node, sceneData);
+    if (constraint) {
+        createConstraintSymbol(node, determineSupportType(constraint), constraint, sceneData);
+        node.userData.constraint = constraint;
+    } else {
+        delete node.userData.constraint;
+    }
+}
+
 /**
  * Create fixed support symbol (2D line with hatching - billboard style)
  * @returns {THREE.Group}
@@ -421,6 +450,15 @@ function createCustomSymbol() {
     return group;
 }
 
+/**
+ * Resize all support glyphs, and those created later
+ * @param {number} scale - Size relative to the default
+ */
+export function setSupportScale(scale) {
+    supportScale = scale;
+    constraintSymbols.forEach(symbol => symbol.scale.setScalar(scale));
+}
+
 /**
  * Get all constraint symbols
  * @returns {Map}
@@ -0,0 +1,86 @@
+/**
+ * Construction Grid Module
+ * Labelled gridlines of the grid defined in the app's Grids & Storeys dialog
+ *
+ * Gridlines along X are numbered and those along Z lettered, with a bubble
+ * at each end. They are drawn at every level of the grid, faint above the
+ * ground, and extend past the outer gridlines so the bubbles stay clear of
+ * the frame.
+ */
+
+import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.module.js';
+import { createLabel } from './labels_manager.js';
+
+const GRID_COLOR = 0x9e9e9e;
+
+// Length of the gridlines past the outer ones (m)
+const OVERHANG = 2.0;
+
+let gridGroup = null;
+
+function gridLine(from, to, opacity) {
+    const line = new THREE.Line(
+        new THREE.BufferGeometry().setFromPoints([from, to]),
+        new THREE.LineDashedMaterial({ color: GRID_COLOR, dashSize: 0.5, gapSize: 0.25, transparent: true, opacity })
+    );
+    line.computeLineDistances();
+    return line;
+}
+
+/**
+ * Draw a construction grid, replacing the one shown; null removes it
+ * @param {Object|null} grid - { x: [{label, at}], z: [{label, at}], levels: [y] }
+ */
+window.setConstructionGrid = function(grid) {
+    const sceneData = window.sceneData;
+    if (!sceneData) return;
+
+    if (gridGroup) {
+        sceneData.scene.remove(gridGroup);
+        gridGroup.traverse(object => {
+            if (object.geometry) object.geometry.dispose();
+            if (object.material) {
+                if (object.material.map) object.material.map.dispose();
+                object.material.dispose();
+            }
+        });
+        gridGroup = null;
+    }
+    if (!grid) return;
+
+    gridGroup = new THREE.Group();
+    gridGroup.name = 'constructionGrid';
+
+    const xs = grid.x.map(line => line.at);
+    const zs = grid.z.map(line => line.at);
+    const xMin = Math.min(...xs) - OVERHANG;
+    const xMax = Math.max(...xs) + OVERHANG;
+    const zMin = Math.min(...zs) - OVERHANG;
+    const zMax = Math.max(...zs) + OVERHANG;
+    const levels = grid.levels.length > 0 ? grid.levels : [0];
+
+    levels.forEach((y, level) => {
+        const opacity = level === 0 ? 0.9 : 0.35;
+        grid.x.forEach(({ at }) => {
+            gridGroup.add(gridLine(new THREE.Vector3(at, y, zMin), new THREE.Vector3(at, y, zMax), opacity));
+        });
+        grid.z.forEach(({ at }) => {
+            gridGroup.add(gridLine(new THREE.Vector3(xMin, y, at), new THREE.Vector3(xMax, y, at), opacity));
+        });
+    });
+
+    // Bubbles at both ends of the ground gridlines
+    const ground = levels[0];
+    grid.x.forEach(({ label, at }) => {
+        [zMin, zMax].forEach(z => {
+            gridGroup.add(createLabel(label, new THREE.Vector3(at, ground, z), '#ffffff', '#616161', 0, 0, 0, true));
+        });
+    });
+    grid.z.forEach(({ label, at }) => {
+        [xMin, xMax].forEach(x => {
+            gridGroup.add(createLabel(label, new THREE.Vector3(x, ground, at), '#ffffff', '#616161', 0, 0, 0, true));
+        });
+    });
+
+    sceneData.scene.add(gridGroup);
+};
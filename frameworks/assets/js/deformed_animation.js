@@ -0,0 +1,92 @@
+/**
+ * Deformed Animation Module
+ * Line drawing of the analysed model whose vertices follow node positions
+ * sent from Rust, for the deformed shape and mode shape animations
+ *
+ * beginDeformedAnimation builds the members and plate edges once, against
+ * the node order Rust generates frames in; setDeformedFrame then copies
+ * each frame's positions into the vertex buffers.
+ */
+
+import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.module.js';
+
+const DEFORMED_COLOR = 0x0097c4;
+
+let animation = null;
+
+/**
+ * Draw the model for deforming, replacing any diagram shown
+ * @param {string[]} nodeNames - Nodes in the order frames give positions in
+ * @returns {boolean} Whether there is an analysed model to draw
+ */
+window.beginDeformedAnimation = function(nodeNames) {
+    const model = window.feaModel;
+    const sceneData = window.sceneData;
+    if (!model || !sceneData) {
+        console.error('No FEA results available');
+        return false;
+    }
+
+    window.clearFEADiagrams();
+    window.currentDiagramType = 'deformed_animation';
+
+    // Each segment is a pair of node indices into the frame
+    const index = new Map(nodeNames.map((name, k) => [name, k]));
+    const segments = [];
+    const addSegment = (a, b) => {
+        if (index.has(a) && index.has(b)) segments.push(index.get(a), index.get(b));
+    };
+    model.members.forEach(m => addSegment(m.i_node, m.j_node));
+    (model.plates || []).forEach(p => {
+        const corners = [p.i_node, p.j_node, p.m_node, p.n_node];
+        corners.forEach((node, k) => addSegment(node, corners[(k + 1) % corners.length]));
+    });
+
+    const lineGeometry = new THREE.BufferGeometry();
+    lineGeometry.setAttribute('position', new THREE.BufferAttribute(new Float32Array(segments.length * 3), 3));
+    const lines = new THREE.LineSegments(lineGeometry, new THREE.LineBasicMaterial({ color: DEFORMED_COLOR }));
+    lines.frustumCulled = false;
+
+    const pointGeometry = new THREE.BufferGeometry();
+    pointGeometry.setAttribute('position', new THREE.BufferAttribute(new Float32Array(nodeNames.length * 3), 3));
+    const points = new THREE.Points(pointGeometry, new THREE.PointsMaterial({ color: DEFORMED_COLOR, size: 6, sizeAttenuation: false }));
+    points.frustumCulled = false;
+
+    [lines, points].forEach(object => {
+        sceneData.scene.add(object);
+        window.feaDiagramObjects.push(object);
+    });
+    animation = { segments, lines, points };
+    return true;
+};
+
+/**
+ * Move the drawing to one frame
+ * @param {Float32Array} positions - x, y, z of each node, in the order given to beginDeformedAnimation
+ * @returns {boolean} Whether the drawing is still shown, false once another diagram replaced it
+ */
+window.setDeformedFrame = function(positions) {
+    if (!animation || window.currentDiagramType !== 'deformed_animation') return false;
+    const { segments, lines, points } = animation;
+
+    const linePositions = lines.geometry.attributes.position;
+    segments.forEach((node, k) => {
+        linePositions.setXYZ(k, positions[node * 3], positions[node * 3 + 1], positions[node * 3 + 2]);
+    });
+    linePositions.needsUpdate = true;
+
+    points.geometry.attributes.position.array.set(positions.subarray(0, points.geometry.attributes.position.array.length));
+    points.geometry.attributes.position.needsUpdate = true;
+    return true;
+};
+
+/**
+ * Remove the drawing, if it is still shown
+ */
+window.endDeformedAnimation = function() {
+    if (animation && window.currentDiagramType === 'deformed_animation') {
+        window.clearFEADiagrams();
+        window.currentDiagramType = null;
+    }
+    animation = null;
+};
@@ -0,0 +1,34 @@
+/**
+ * Display Units Module
+ * Values are kept in SI throughout the scene; the app's unit preference
+ * decides only how they read. Rust sets window.displayUnits to the symbol
+ * and SI factor of each quantity whenever the preference changes.
+ */
+
+/**
+ * Unit symbol of a quantity, e.g. 'kN' for 'force'
+ * @param {string} quantity - Key of the quantity, as units.rs names it
+ */
+window.unitSymbol = function(quantity) {
+    const unit = window.displayUnits && window.displayUnits[quantity];
+    return unit ? unit.symbol : '';
+};
+
+/**
+ * An SI value in the display unit of its quantity
+ * @param {string} quantity - Key of the quantity, as units.rs names it
+ * @param {number} value - Value in SI
+ */
+window.displayValue = function(quantity, value) {
+    const unit = window.displayUnits && window.displayUnits[quantity];
+    return unit ? value / unit.factor : value;
+};
+
+/**
+ * An SI value with its unit, e.g. '12.50 kN'
+ */
+window.formatQuantity = function(quantity, value, decimals = 2) {
+    const symbol = window.unitSymbol(quantity);
+    const text = window.displayValue(quantity, value).toFixed(decimals);
+    return symbol ? `${text} ${symbol}` : text;
+};
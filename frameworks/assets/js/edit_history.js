@@ -0,0 +1,243 @@
+/**
+ * Edit History Module
+ * Undo/redo of model edits, kept as a stack of commands
+ *
+ * A command is { label, undo(), redo() }. Most edits are recorded with
+ * recordEdit(), which captures the model before and after the edit: the
+ * nodes, beams, plates and mesh elements in the scene with their userData,
+ * the loads and the supports. Deleted objects are only detached from the
+ * scene, so undoing re-attaches the same objects.
+ */
+
+import { clearSelection } from './geometry_manager.js';
+import { snapshotLoads, restoreLoads } from './loads_manager.js';
+import { constraintSymbols, restoreNodeConstraint } from './constraints_manager.js';
+import { updateNodeLabels, updateBeamLabels, updatePlateLabels } from './labels_manager.js';
+
+const MAX_EDIT_STEPS = 50;
+
+const undoStack = [];
+const redoStack = [];
+
+// Scene the recorded edits apply to (set by three_canvas.js)
+let sceneData = null;
+
+export function setEditHistoryScene(data) {
+    sceneData = data;
+}
+
+/**
+ * Add a command that has already been applied
+ * @param {Object} command - { label, undo(), redo() }
+ */
+export function pushEdit(command) {
+    undoStack.push(command);
+    if (undoStack.length > MAX_EDIT_STEPS) {
+        undoStack.shift();
+    }
+    redoStack.length = 0;
+    notifyHistoryChanged();
+}
+
+/**
+ * Run an edit and record it as one command, if it changed the model
+ * @param {string} label - Shown in the history, e.g. 'Add node'
+ * @param {Function} edit - Applies the edit; may return a Promise
+ * @returns The edit's return value
+ */
+export function recordEdit(label, edit) {
+    if (!sceneData) {
+        return edit();
+    }
+    const before = captureModel();
+    const result = edit();
+    const record = () => {
+        const after = captureModel();
+        if (!sameModel(before, after)) {
+            pushEdit({
+                label,
+                undo: () => restoreModel(before),
+                redo: () => restoreModel(after),
+            });
+        }
+    };
+    if (result instanceof Promise) {
+        return result.then(value => {
+            record();
+            return value;
+        });
+    }
+    record();
+    return result;
+}
+
+/**
+ * Undo the last edit
+ * @returns {boolean} Whether there was an edit to undo
+ */
+export function undoEdit() {
+    const command = undoStack.pop();
+    if (!command) {
+        console.log('Nothing to undo');
+        return false;
+    }
+    command.undo();
+    redoStack.push(command);
+    console.log('Undid:', command.label);
+    notifyHistoryChanged();
+    return true;
+}
+
+/**
+ * Redo the last undone edit
+ * @returns {boolean} Whether there was an edit to redo
+ */
+export function redoEdit() {
+    const command = redoStack.pop();
+    if (!command) {
+        console.log('Nothing to redo');
+        return false;
+    }
+    command.redo();
+    undoStack.push(command);
+    console.log('Redid:', command.label);
+    notifyHistoryChanged();
+    return true;
+}
+
+/**
+ * Forget all edits, e.g. when the model is replaced
+ */
+export function clearEditHistory() {
+    undoStack.length = 0;
+    redoStack.length = 0;
+    notifyHistoryChanged();
+}
+
+/**
+ * Labels of the edits that can be undone and redone, most recent last
+ */
+export function getEditHistory() {
+    return {
+        undo: undoStack.map(c => c.label),
+        redo: redoStack.map(c => c.label).reverse(),
+    };
+}
+
+function notifyHistoryChanged() {
+    window.dispatchEvent(new CustomEvent('editHistoryChanged', {
+        detail: {
+            canUndo: undoStack.length > 0,
+            canRedo: redoStack.length > 0,
+            undoLabel: undoStack.length > 0 ? undoStack[undoStack.length - 1].label : null,
+            redoLabel: redoStack.length > 0 ? redoStack[redoStack.length - 1].label : null,
+        }
+    }));
+}
+
+/**
+ * Children of a group with a copy of each child's userData
+ */
+function captureGroup(group) {
+    return {
+        group,
+        children: group.children.map(object => ({ object, userData: { ...object.userData } })),
+    };
+}
+
+function captureModel() {
+    const groups = [sceneData.nodesGroup, sceneData.beamsGroup, sceneData.platesGroup]
+        .filter(Boolean)
+        .map(captureGroup);
+
+    // Mesh elements of each plate, which can be deleted on their own
+    const meshes = [];
+    if (sceneData.platesGroup) {
+        sceneData.platesGroup.children.forEach(plate => {
+            plate.children
+                .filter(child => child.userData.isMeshViz)
+                .forEach(mesh => meshes.push(captureGroup(mesh)));
+        });
+    }
+
+    return { groups, meshes, loads: snapshotLoads() };
+}
+
+function sameGroup(a, b) {
+    return a.group === b.group
+        && a.children.length === b.children.length
+        && a.children.every((child, i) => child.object === b.children[i].object
+            && sameUserData(child.userData, b.children[i].userData));
+}
+
+function sameUserData(a, b) {
+    const keys = Object.keys(a);
+    return keys.length === Object.keys(b).length && keys.every(key => a[key] === b[key]);
+}
+
+function sameMap(a, b) {
+    return a.size === b.size && Array.from(a).every(([id, loads]) => {
+        const other = b.get(id);
+        return other && other.length === loads.length && loads.every((load, i) => load === other[i]);
+    });
+}
+
+function sameModel(a, b) {
+    return a.groups.length === b.groups.length
+        && a.groups.every((group, i) => sameGroup(group, b.groups[i]))
+        && a.meshes.length === b.meshes.length
+        && a.meshes.every((mesh, i) => sameGroup(mesh, b.meshes[i]))
+        && sameMap(a.loads.beamLoads, b.loads.beamLoads)
+        && sameMap(a.loads.plateLoads, b.loads.plateLoads)
+        && sameMap(a.loads.elementLoads, b.loads.elementLoads)
+        && a.loads.pointLoads.length === b.loads.pointLoads.length;
+}
+
+function restoreGroup({ group, children }) {
+    group.clear();
+    children.forEach(({ object, userData }) => {
+        object.userData = { ...userData };
+        group.add(object);
+    });
+}
+
+function restoreModel(state) {
+    // Selected objects may not be in the restored model
+    clearSelection();
+    if (window.selectedElements) {
+        window.selectedElements.clear();
+    }
+
+    const nodes = state.groups.find(g => g.group === sceneData.nodesGroup);
+    if (nodes) {
+        // Recreate support symbols whose constraint differs, before the
+        // nodes get their saved userData back
+        const restored = new Set();
+        nodes.children.forEach(({ object, userData }) => {
+            restored.add(object.uuid);
+            const current = object.parent === sceneData.nodesGroup ? object.userData.constraint : undefined;
+            if (current !== userData.constraint || !!userData.constraint !== constraintSymbols.has(object.uuid)) {
+                restoreNodeConstraint(object, userData.constraint, sceneData);
+            }
+        });
+        // Supports of nodes that are not in the restored model
+        sceneData.nodesGroup.children
+            .filter(node => !restored.has(node.uuid))
+            .forEach(node => restoreNodeConstraint(node, undefined, sceneData));
+    }
+
+    state.groups.forEach(restoreGroup);
+    state.meshes.forEach(restoreGroup);
+    restoreLoads(state.loads, sceneData);
+
+    // Release indicators follow the beams' releases
+    if (window.updateAllReleaseIndicators) {
+        window.updateAllReleaseIndicators();
+    }
+
+    updateNodeLabels(sceneData.nodesGroup);
+    updateBeamLabels(sceneData.beamsGroup);
+    if (sceneData.platesGroup) {
+        updatePlateLabels(sceneData.platesGroup);
+    }
+}
@@ -24,6 +24,18 @@ window.currentDiagramType = null;
 window.refreshCurrentDiagram = function() {
     if (!window.currentDiagramType) return;
     
+    // Diagrams from the member stations (member_diagrams.js)
+    if (window.currentDiagramType.startsWith('member_')) {
+        window.showMemberForceDiagram(window.currentDiagramType.slice('member_'.length));
+        return;
+    }
+    
+    // Plate contours, with the current contour options
+    if (window.currentDiagramType.startsWith('plate_stress_')) {
+        window.showPlateContour(window.currentDiagramType.slice('plate_stress_'.length));
+        return;
+    }
+    
     switch (window.currentDiagramType) {
         case 'moment_xy':
             window.clearFEADiagrams();
@@ -163,7 +175,14 @@ let feaProgressTracker = null;
 // Structure Data Extraction
 // ========================
 
-window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
+/**
+ * @param {Object} materialConfig - Material for all members and plates
+ * @param {Object} sectionConfig - Section properties computed in Rust, { default, assigned: { name: section } }:
+ *     members take the section assigned to them by name, or the default
+ * @param {Object} loading - Optional { caseNames: { id: name }, combos: [{ name, factors, tags }] }
+ *     from the load case and combination editors; without it all loads are in 'Case 1'
+ */
+window.extractFEAStructure = function(materialConfig, sectionConfig, loading = null) {
     const sceneData = window.sceneData;
     if (!sceneData) {
         console.error('Scene data not available');
@@ -182,7 +201,14 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
         supports: [],
         node_loads: [],
         distributed_loads: [],
-        load_combos: []
+        load_combos: loading ? loading.combos : []
+    };
+
+    // Solver load case of a load, named after its load case in the editor
+    const caseName = load => {
+        if (!loading) return 'Case 1';
+        const id = load.loadCase ?? 1;
+        return loading.caseNames[id] ?? `Case ${id}`;
     };
 
     // Node name mapping (index -> name)
@@ -224,19 +250,18 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
         rho: material.density || material.rho || 7850
     });
 
-    // Add section from beam section config
-    const section = beamSectionConfig || {
-        section_type: 'Rectangular',
-        width: 0.3,
-        height: 0.5,
-        flange_thickness: 0.02,
-        web_thickness: 0.015
+    // Sections as computed in Rust; each is added once a member uses it
+    const defaultSection = sectionConfig.default;
+    const assignedSections = sectionConfig.assigned || {};
+    const sectionOf = (beamMesh) => {
+        const assigned = beamMesh.userData.section && assignedSections[beamMesh.userData.section.name];
+        const section = assigned || defaultSection;
+        if (!model.sections.some(s => s.name === section.name)) {
+            model.sections.push(section);
+        }
+        return section.name;
     };
 
-    // Calculate section properties based on type
-    const sectionProps = calculateSectionProperties(section);
-    model.sections.push(sectionProps);
-
     // Member name map
     const memberNameMap = new Map(); // uuid -> member name
 
@@ -286,24 +311,20 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                 // Get member releases from userData (default: all fixed)
                 // Handle empty objects {} by checking for actual properties
                 const userReleases = beamMesh.userData.releases;
-                const releases = (userReleases && typeof userReleases.i_node_ry === 'boolean') ? {
-                    i_node_ry: !!userReleases.i_node_ry,
-                    i_node_rz: !!userReleases.i_node_rz,
-                    j_node_ry: !!userReleases.j_node_ry,
-                    j_node_rz: !!userReleases.j_node_rz
-                } : {
-                    i_node_ry: false,
-                    i_node_rz: false,
-                    j_node_ry: false,
-                    j_node_rz: false
-                };
+                const hasReleases = userReleases && typeof userReleases.i_node_ry === 'boolean';
+                const releases = {};
+                ['i_node', 'j_node'].forEach(end => {
+                    ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                        releases[`${end}_${dof}`] = hasReleases && !!userReleases[`${end}_${dof}`];
+                    });
+                });
                 
                 model.members.push({
                     name: memberName,
                     i_node: startNodeName,
                     j_node: endNodeName,
                     material: model.materials[0].name,
-                    section: sectionProps.name,
+                    section: sectionOf(beamMesh),
                     rotation: rotation,
                     releases: releases
                 });
@@ -517,7 +538,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                         mx: load.mx || 0,
                         my: load.my || 0,
                         mz: load.mz || 0,
-                        case: 'Case 1'
+                        case: caseName(load)
                     });
                     console.log(`Point load at structural node ${nodeName}:`, load);
                 }
@@ -533,7 +554,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                     mx: 0,
                     my: 0,
                     mz: 0,
-                    case: 'Case 1'
+                    case: caseName(load)
                 });
                 console.log(`Point load at mesh node ${load.meshNodeName}:`, load);
             }
@@ -555,7 +576,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                         w1: magnitude_Nm,
                         w2: magnitude_Nm,  // Uniform load
                         direction: `F${(load.direction || 'Y').toUpperCase()}`,
-                        case: 'Case 1'
+                        case: caseName(load)
                     });
                     console.log(`Distributed load on ${memberName}: ${load.magnitude} kN/m = ${magnitude_Nm} N/m`);
                 }
@@ -585,7 +606,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                     model.plate_loads.push({
                         plate: plateName,
                         pressure: pressure_Pa,
-                        case: 'Case 1'
+                        case: caseName(load)
                     });
                     elementLoadCount++;
                 }
@@ -615,7 +636,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                         model.plate_loads.push({
                             plate: directPlateName,
                             pressure: pressure_Pa,
-                            case: 'Case 1'
+                            case: caseName(load)
                         });
                         plateLoadCount++;
                     }
@@ -645,7 +666,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                                 model.plate_loads.push({
                                     plate: plateName,
                                     pressure: pressure_Pa,
-                                    case: 'Case 1'
+                                    case: caseName(load)
                                 });
                                 plateLoadCount++;
                             }
@@ -667,7 +688,7 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
                             model.plate_loads.push({
                                 plate: plateName,
                                 pressure: pressure_Pa,
-                                case: 'Case 1'
+                                case: caseName(load)
                             });
                             plateLoadCount++;
                         }
@@ -698,74 +719,18 @@ window.extractFEAStructure = function(materialConfig, beamSectionConfig) {
     return model;
 };
 
-// Calculate section properties from section config
-function calculateSectionProperties(section) {
-    const type = (section.section_type || 'Rectangular').toLowerCase();
-    const w = section.width || 0.3;   // Width (horizontal dimension, local z)
-    const h = section.height || 0.5;  // Height (vertical dimension, local y)
-    const tf = section.flange_thickness || 0.02;
-    const tw = section.web_thickness || 0.015;
-
-    let a, iy, iz, j;
-
-    // Section orientation convention:
-    // - Local y-axis points UP (vertical) for horizontal members
-    // - Local z-axis points sideways (horizontal) for horizontal members
-    // - Height (h) is in the local y direction
-    // - Width (w) is in the local z direction
-    // 
-    // For bending:
-    // - Iz = moment of inertia about local z-axis = resists bending in XY plane (vertical bending)
-    // - Iy = moment of inertia about local y-axis = resists bending in XZ plane (horizontal bending)
-
-    if (type === 'rectangular') {
-        a = w * h;
-        // Iz = b*h³/12 where h is the dimension perpendicular to the z-axis (vertical)
-        // For vertical bending (moment about z), we need the height
-        iz = w * Math.pow(h, 3) / 12;  // Strong axis for vertical bending
-        // Iy = h*b³/12 where b is the dimension perpendicular to the y-axis (horizontal)
-        iy = h * Math.pow(w, 3) / 12;  // Weak axis for horizontal bending
-        // Approximate torsional constant
-        const aMax = Math.max(w, h);
-        const bMin = Math.min(w, h);
-        j = aMax * Math.pow(bMin, 3) * (1/3 - 0.21 * (bMin/aMax) * (1 - Math.pow(bMin, 4) / (12 * Math.pow(aMax, 4))));
-    } else if (type === 'circular') {
-        const r = w / 2;  // Use width as diameter
-        a = Math.PI * r * r;
-        iy = Math.PI * Math.pow(r, 4) / 4;
-        iz = iy;
-        j = Math.PI * Math.pow(r, 4) / 2;
-    } else if (type === 'ibeam' || type === 'i-beam' || type === 'wideflange') {
-        // I-beam with flanges top/bottom, web vertical
-        // Height h is total depth (vertical), width w is flange width (horizontal)
-        a = 2 * w * tf + (h - 2 * tf) * tw;
-        // Iz = strong axis about z (vertical bending) - uses h (depth)
-        iz = (w * Math.pow(h, 3) - (w - tw) * Math.pow(h - 2 * tf, 3)) / 12;
-        // Iy = weak axis about y (horizontal bending) - uses w (flange width)
-        iy = (2 * tf * Math.pow(w, 3) + (h - 2 * tf) * Math.pow(tw, 3)) / 12;
-        j = (2 * w * Math.pow(tf, 3) + (h - 2 * tf) * Math.pow(tw, 3)) / 3;
-    } else {
-        // Default to rectangular - same as above
-        a = w * h;
-        iz = w * Math.pow(h, 3) / 12;  // Strong axis for vertical bending
-        iy = h * Math.pow(w, 3) / 12;  // Weak axis for horizontal bending
-        j = Math.min(w, h) * Math.pow(Math.max(w, h), 3) / 3;
-    }
-
-    return {
-        name: `Section_${type}_${Math.round(w*1000)}x${Math.round(h*1000)}`,
-        a: a,
-        iy: iy,
-        iz: iz,
-        j: j
-    };
-}
-
 // ========================
 // Analysis Execution
 // ========================
 
-window.runFEAAnalysis = async function(materialConfig, beamSectionConfig, analysisType = 'linear') {
+// `solve`, if given, analyses the request in place of the server: the app
+// passes one running fea-solver in the browser (the `local-solver` build).
+// A model it leaves to the server (null back from `solve`) is sent there.
+// `loading` names the load cases and lists the combinations to solve, as
+// for extractFEAStructure. `options` go with the model as the request's
+// options: the analysis type, the modes of vibration to find after the
+// static analysis, and the solver's AnalysisOptions in full as `settings`.
+window.runFEAAnalysis = async function(materialConfig, sectionConfig, options = null, solve = null, loading = null) {
     // Start progress tracking
     feaProgressTracker = new ConsoleProgressTracker('FEA Analysis');
     feaProgressTracker.start();
@@ -775,7 +740,7 @@ window.runFEAAnalysis = async function(materialConfig, beamSectionConfig, analys
     }
 
     feaProgressTracker.update('Extracting structure data...');
-    const model = window.extractFEAStructure(materialConfig, beamSectionConfig);
+    const model = window.extractFEAStructure(materialConfig, sectionConfig, loading);
     
     if (!model) {
         const error = 'Failed to extract structure data from scene';
@@ -811,57 +776,65 @@ window.runFEAAnalysis = async function(materialConfig, beamSectionConfig, analys
     
     if (window.addSolverLog) {
         window.addSolverLog(`Model: ${modelSummary}, ${model.supports.length} supports`, 'info');
-        window.addSolverLog('Sending to FEA solver...', 'info');
+        window.addSolverLog(solve ? 'Solving in the browser...' : 'Sending to FEA solver...', 'info');
     }
 
     try {
         const request = {
             model: model,
-            options: {
-                analysis_type: analysisType,
-                max_iterations: 30
-            }
+            options: options || { analysis_type: 'linear', max_iterations: 30, num_modes: 0 }
         };
 
-        feaProgressTracker.update('Sending request to solver backend (not WASM - server-side Rust)...');
-
-        // If the backend isn't running (or an intermediate proxy is wedged), fetch can appear to
-        // “hang forever”. Put a hard cap on wait time so UX recovers.
-        const controller = new AbortController();
-        const timeoutMs = 300000;  // 5 minutes for large plate models
-        const timeoutId = setTimeout(() => controller.abort(), timeoutMs);
-        
-        let response;
-        try {
-            response = await fetch(`${FEA_SERVER_URL}/api/v1/analyze`, {
-                method: 'POST',
-                headers: { 'Content-Type': 'application/json' },
-                body: JSON.stringify(request),
-                signal: controller.signal
-            });
-        } finally {
-            clearTimeout(timeoutId);
+        // `solve` answers null for a model it leaves to the server
+        let data = null;
+        if (solve) {
+            feaProgressTracker.update('Solving in the browser...');
+            data = await solve(request);
+            if (!data && window.addSolverLog) {
+                window.addSolverLog('Model not supported in the browser, sending to FEA solver...', 'info');
+            }
         }
+        if (!data) {
+            feaProgressTracker.update('Sending request to solver backend (not WASM - server-side Rust)...');
 
-        if (!response.ok) {
-            // Try to get error message from response body
-            let errorMsg = `HTTP error: ${response.status}`;
+            // If the backend isn't running (or an intermediate proxy is wedged), fetch can appear to
+            // “hang forever”. Put a hard cap on wait time so UX recovers.
+            const controller = new AbortController();
+            const timeoutMs = 300000;  // 5 minutes for large plate models
+            const timeoutId = setTimeout(() => controller.abort(), timeoutMs);
+        
+            let response;
             try {
-                const errorData = await response.json();
-                if (errorData.error) {
-                    errorMsg = errorData.error;
+                response = await fetch(`${FEA_SERVER_URL}/api/v1/analyze`, {
+                    method: 'POST',
+                    headers: { 'Content-Type': 'application/json' },
+                    body: JSON.stringify(request),
+                    signal: controller.signal
+                });
+            } finally {
+                clearTimeout(timeoutId);
+            }
+
+            if (!response.ok) {
+                // Try to get error message from response body
+                let errorMsg = `HTTP error: ${response.status}`;
+                try {
+                    const errorData = await response.json();
+                    if (errorData.error) {
+                        errorMsg = errorData.error;
+                    }
+                } catch (e) {
+                    // Response body wasn't JSON
                 }
-            } catch (e) {
-                // Response body wasn't JSON
+                if (window.addSolverLog) window.addSolverLog(errorMsg, 'error');
+                console.error('FEA Server Error:', errorMsg);
+                feaProgressTracker.error(errorMsg);
+                return { error: errorMsg };
             }
-            if (window.addSolverLog) window.addSolverLog(errorMsg, 'error');
-            console.error('FEA Server Error:', errorMsg);
-            feaProgressTracker.error(errorMsg);
-            return { error: errorMsg };
-        }
 
-        feaProgressTracker.update('Processing solver response...');
-        const data = await response.json();
+            feaProgressTracker.update('Processing solver response...');
+            data = await response.json();
+        }
 
         if (data.success && data.results) {
             // Calculate actual max deflection for simply-supported beams with UDL
@@ -906,25 +879,20 @@ window.runFEAAnalysis = async function(materialConfig, beamSectionConfig, analys
             
             if (window.addSolverLog) {
                 window.addSolverLog('Analysis completed successfully!', 'success');
-                window.addSolverLog(`Max displacement: ${(calculatedMaxDefl * 1000).toFixed(2)} mm`, 'info');
+                window.addSolverLog(`Max displacement: ${window.formatQuantity('displacement', calculatedMaxDefl)}`, 'info');
             }
             
             // Complete progress tracking
-            feaProgressTracker.complete(`Max displacement: ${(calculatedMaxDefl * 1000).toFixed(2)} mm`);
+            feaProgressTracker.complete(`Max displacement: ${window.formatQuantity('displacement', calculatedMaxDefl)}`);
             
-            // Store results globally
-            window.feaResults = data.results;
+            // Store results globally; diagrams and tables show one
+            // combination at a time, the first until another is chosen
+            window.feaAllResults = data.results;
             window.feaModel = model;
+            const combos = resultCombos(data.results);
+            window.setFEAResultCombo(combos[0]);
             
-            // Update visualization
-            window.updateFEAVisualization(data.results, model);
-            
-            // Update tabulated results panel
-            if (window.updateTablesPanel) {
-                window.updateTablesPanel(data.results);
-            }
-            
-            return { success: true, results: data.results };
+            return { success: true, results: window.feaResults, combos };
         } else {
             const error = data.error || 'Analysis failed';
             if (window.addSolverLog) window.addSolverLog(error, 'error');
@@ -952,6 +920,49 @@ window.runFEAAnalysis = async function(materialConfig, beamSectionConfig, analys
 // Store visualization objects for cleanup
 window.feaDiagramObjects = [];
 
+/**
+ * Names of the load combinations in a set of results, in solver order
+ */
+function resultCombos(results) {
+    return [...new Set((results.node_displacements || []).map(r => r.combo))];
+}
+
+/**
+ * Show the results of one load combination in the diagrams and tables
+ * @param {string} combo - Combination name
+ * @returns {Object|null} Summary for the combination, null without results
+ */
+window.setFEAResultCombo = function(combo) {
+    const all = window.feaAllResults;
+    if (!all) return null;
+
+    // Every result list carries the combination of each entry
+    const results = {};
+    Object.entries(all).forEach(([key, value]) => {
+        results[key] = Array.isArray(value) && value.length > 0 && value[0].combo !== undefined
+            ? value.filter(r => r.combo === combo)
+            : value;
+    });
+
+    // The summary sent with the results is for the first combination
+    if (combo !== resultCombos(all)[0]) {
+        const magnitude = (x, y, z) => Math.sqrt(x * x + y * y + z * z);
+        results.summary = {
+            ...all.summary,
+            max_displacement: Math.max(0, ...results.node_displacements.map(d => magnitude(d.dx, d.dy, d.dz))),
+            max_reaction: Math.max(0, ...results.reactions.map(r => magnitude(r.fx, r.fy, r.fz)))
+        };
+    }
+
+    window.feaResultCombo = combo;
+    window.updateFEAVisualization(results, window.feaModel);
+    if (window.updateTablesPanel) {
+        window.updateTablesPanel(results);
+    }
+    window.refreshCurrentDiagram();
+    return results.summary;
+};
+
 window.updateFEAVisualization = function(results, model) {
     console.log('Updating FEA visualization:', results);
     
@@ -1022,11 +1033,8 @@ window.clearFEADiagrams = function() {
     window.feaDiagramObjects = [];
     window.currentDiagramType = null;
     
-    // Also remove any stress legend
-    const existingLegend = document.getElementById('stress-legend');
-    if (existingLegend) {
-        existingLegend.remove();
-    }
+    // Also hide any contour legend
+    window.dispatchEvent(new CustomEvent('contourLegendChanged', { detail: null }));
     
     console.log('FEA diagrams cleared');
 };
@@ -2502,9 +2510,8 @@ window.showFEAReactions = function(scale = 0.001) {
                 window.feaDiagramObjects.push(arrow);
                 
                 // Collect label info - each on its own line
-                const valueKn = r.value / 1000;
-                labelLines.push(`${r.label}=${valueKn.toFixed(1)} kN`);
-                summaryParts.push(`${r.label}=${valueKn.toFixed(2)} kN`);
+                labelLines.push(`${r.label}=${window.formatQuantity('force', r.value, 1)}`);
+                summaryParts.push(`${r.label}=${window.formatQuantity('force', r.value)}`);
             }
         });
 
@@ -2512,9 +2519,8 @@ window.showFEAReactions = function(scale = 0.001) {
         momentReactions.forEach(m => {
             const absVal = Math.abs(m.value);
             if (absVal > 1) { // Only show significant moments (> 1 N·m)
-                const valueKnm = m.value / 1000;
-                labelLines.push(`${m.label}=${valueKnm.toFixed(1)} kN·m`);
-                summaryParts.push(`${m.label}=${valueKnm.toFixed(2)} kN·m`);
+                labelLines.push(`${m.label}=${window.formatQuantity('moment', m.value, 1)}`);
+                summaryParts.push(`${m.label}=${window.formatQuantity('moment', m.value)}`);
             }
         });
 
@@ -2725,6 +2731,54 @@ window.getFEAResultsSummary = function() {
 // Plate Stress Visualization
 // ========================
 
+// Contour colormap: blue -> cyan -> green -> yellow -> orange -> red, with
+// the blue-cyan range spread out as in ANSYS/CalculiX
+// Format: { t: position 0-1, r/g/b: 0-255 sRGB values }
+const CONTOUR_COLOR_STOPS = [
+    { t: 0.00, r: 0,   g: 0,   b: 180 },  // Deep blue
+    { t: 0.08, r: 0,   g: 40,  b: 200 },  // Blue
+    { t: 0.16, r: 0,   g: 80,  b: 220 },  // Lighter blue
+    { t: 0.24, r: 0,   g: 120, b: 220 },  // Blue-cyan
+    { t: 0.32, r: 0,   g: 160, b: 200 },  // Cyan-blue
+    { t: 0.40, r: 0,   g: 190, b: 170 },  // Cyan
+    { t: 0.48, r: 0,   g: 200, b: 120 },  // Cyan-teal
+    { t: 0.56, r: 40,  g: 200, b: 60  },  // Teal-green
+    { t: 0.64, r: 100, g: 200, b: 30  },  // Green
+    { t: 0.72, r: 170, g: 200, b: 0   },  // Green-yellow
+    { t: 0.80, r: 220, g: 180, b: 0   },  // Yellow
+    { t: 0.88, r: 240, g: 120, b: 0   },  // Orange
+    { t: 0.96, r: 220, g: 60,  b: 20  },  // Red-orange
+    { t: 1.00, r: 180, g: 0,   b: 0   },  // Red
+];
+
+/**
+ * Colormap at t in 0-1, as 0-255 sRGB
+ */
+function contourRGB(t) {
+    const stops = CONTOUR_COLOR_STOPS;
+    let i = 0;
+    while (i < stops.length - 1 && stops[i + 1].t < t) {
+        i++;
+    }
+    if (i >= stops.length - 1) {
+        const last = stops[stops.length - 1];
+        return [last.r, last.g, last.b];
+    }
+    const c0 = stops[i];
+    const c1 = stops[i + 1];
+    const s = (t - c0.t) / (c1.t - c0.t);
+    return [c0.r + s * (c1.r - c0.r), c0.g + s * (c1.g - c0.g), c0.b + s * (c1.b - c0.b)];
+}
+
+// Quantities that are never negative, contoured from zero
+const UNSIGNED_CONTOURS = ['von_mises', 'deflection'];
+
+// Mapping and bands of plate contours, set from the analysis panel:
+// mapping 'node' averages element values at shared nodes for smooth
+// contours, 'element' colors each element by its own value; bands 0 is a
+// continuous scale, otherwise the number of discrete color bands
+window.plateContourOptions = window.plateContourOptions || { mapping: 'node', bands: 0 };
+
 /**
  * Show von Mises stress contour on plate elements
  */
@@ -2733,32 +2787,45 @@ window.showPlateVonMisesStress = function() {
 };
 
 /**
- * Show specific stress component on plate elements with smooth interpolation
- * Uses node-averaged stresses and vertex colors for smooth contour (like CalculiX)
+ * Show a plate stress component with the current contour options
  * @param {string} stressType - 'von_mises', 'sx', 'sy', 'txy', 'mx', 'my', 'mxy'
  */
 window.showPlateStress = function(stressType = 'von_mises') {
+    return window.showPlateContour(stressType, window.plateContourOptions);
+};
+
+/**
+ * Show a contour of plate results, by node or by element, continuous or in
+ * bands. The legend is drawn by the app from the contourLegendChanged event.
+ * @param {string} quantity - A plate stress component ('von_mises', 'sx', 'sy', 'txy', 'mx', 'my', 'mxy') or 'deflection'
+ * @param {Object} options - { mapping: 'node' | 'element', bands: 0 for continuous or the number of bands }
+ * @returns {Object|null} The legend, null without plate results
+ */
+window.showPlateContour = function(quantity = 'von_mises', options = window.plateContourOptions) {
     const results = window.feaResults;
     if (!results) {
         console.warn('No FEA results available');
-        return;
+        return null;
     }
+    const byElement = options?.mapping === 'element';
+    const bands = Math.max(0, Math.floor(options?.bands || 0));
+    window.plateContourOptions = { mapping: byElement ? 'element' : 'node', bands };
     
     // Clear previous diagrams
     window.clearFEADiagrams();
-    window.currentDiagramType = 'plate_stress_' + stressType;
+    window.currentDiagramType = 'plate_stress_' + quantity;
     
     const sceneData = window.sceneData;
     if (!sceneData) {
         console.warn('No scene data available (window.sceneData is null)');
-        return;
+        return null;
     }
     
     // Get plate stress results
     const plateStresses = results.plate_stresses || [];
     if (plateStresses.length === 0) {
         console.log('No plate stress results available');
-        return;
+        return null;
     }
     
     // Build plate name to stress map
@@ -2772,15 +2839,48 @@ window.showPlateStress = function(stressType = 'von_mises') {
     // Use platesGroup from sceneData
     if (!sceneData.platesGroup) {
         console.warn('No platesGroup found in sceneData');
-        return;
+        return null;
     }
     
-    // Step 1: Build node stress accumulator
-    // For each node, collect all stress values from elements that share it
+    // Deflection is a nodal result: the total translation of each node
+    const deflection = quantity === 'deflection';
+    const nodeDeflection = new Map();
+    if (deflection) {
+        (results.node_displacements || []).forEach(d => {
+            nodeDeflection.set(d.node, Math.sqrt(d.dx * d.dx + d.dy * d.dy + d.dz * d.dz));
+        });
+    }
+    
+    // Value of one element: its stress, or the mean deflection of its corners
+    const elementValue = (plateName, nodeNames) => {
+        if (deflection) {
+            if (nodeNames.length === 0) return null;
+            return nodeNames.reduce((sum, name) => sum + (nodeDeflection.get(name) || 0), 0) / nodeNames.length;
+        }
+        const stressData = plateStressMap.get(plateName);
+        return stressData ? (stressData[quantity] ?? stressData.von_mises ?? 0) : null;
+    };
+    
+    // Step 1: Value of each element, and the elements at each node
+    const elementValues = new Map(); // element uuid -> value
     const nodeStressAccum = new Map(); // nodeName -> {sum: number, count: number}
+    const accumulate = (object, plateName) => {
+        const nodeNames = object.userData.nodeNames || [];
+        const value = elementValue(plateName, nodeNames);
+        if (value === null) return;
+        elementValues.set(object.uuid, value);
+        nodeNames.forEach(nodeName => {
+            if (!nodeStressAccum.has(nodeName)) {
+                nodeStressAccum.set(nodeName, { sum: 0, count: 0 });
+            }
+            const accum = nodeStressAccum.get(nodeName);
+            accum.sum += value;
+            accum.count++;
+        });
+    };
     
     let plateElementIndex = 0;
-    sceneData.platesGroup.children.forEach((plate, plateIdx) => {
+    sceneData.platesGroup.children.forEach((plate) => {
         const meshViz = plate.children.find(c => c.userData && c.userData.isMeshViz);
         
         if (meshViz) {
@@ -2791,119 +2891,46 @@ window.showPlateStress = function(stressType = 'von_mises') {
                         plateElementIndex++;
                         plateName = `P${plateElementIndex}`;
                     }
-                    
-                    const stressData = plateStressMap.get(plateName);
-                    if (stressData) {
-                        const value = (stressData[stressType] ?? stressData.von_mises ?? 0);
-                        const nodeNames = element.userData.nodeNames || [];
-                        
-                        // Add this element's stress to each of its nodes
-                        nodeNames.forEach(nodeName => {
-                            if (!nodeStressAccum.has(nodeName)) {
-                                nodeStressAccum.set(nodeName, { sum: 0, count: 0 });
-                            }
-                            const accum = nodeStressAccum.get(nodeName);
-                            accum.sum += value;
-                            accum.count++;
-                        });
-                    }
+                    accumulate(element, plateName);
                 }
             });
         } else {
             // Unmeshed plate
             plateElementIndex++;
-            let plateName = plateNameMap.get(plate.uuid) || `P${plateElementIndex}`;
-            const stressData = plateStressMap.get(plateName);
-            if (stressData) {
-                const value = (stressData[stressType] ?? stressData.von_mises ?? 0);
-                const nodeNames = plate.userData.nodeNames || [];
-                nodeNames.forEach(nodeName => {
-                    if (!nodeStressAccum.has(nodeName)) {
-                        nodeStressAccum.set(nodeName, { sum: 0, count: 0 });
-                    }
-                    const accum = nodeStressAccum.get(nodeName);
-                    accum.sum += value;
-                    accum.count++;
-                });
-            }
+            accumulate(plate, plateNameMap.get(plate.uuid) || `P${plateElementIndex}`);
         }
     });
     
-    // Step 2: Calculate averaged node stresses
-    const nodeStressMap = new Map(); // nodeName -> averaged stress value
-    nodeStressAccum.forEach((accum, nodeName) => {
-        nodeStressMap.set(nodeName, accum.count > 0 ? accum.sum / accum.count : 0);
-    });
-    
-    console.log(`Built node stress map with ${nodeStressMap.size} nodes`);
-    
-    // Find max stress from node-averaged values for color scaling
-    // Always start from 0 (not minimum value) for consistent color mapping
-    let minStress = 0;
-    let maxStress = -Infinity;
-    nodeStressMap.forEach(value => {
-        if (value > maxStress) maxStress = value;
-    });
-    
-    // Handle edge case where max is 0 or negative
-    if (maxStress <= 0) {
-        maxStress = 1;
+    // Step 2: Value at each node: the nodal deflection, or the mean of the
+    // elements sharing the node
+    const nodeStressMap = new Map(); // nodeName -> value
+    if (deflection) {
+        nodeStressAccum.forEach((_, nodeName) => nodeStressMap.set(nodeName, nodeDeflection.get(nodeName) || 0));
+    } else {
+        nodeStressAccum.forEach((accum, nodeName) => {
+            nodeStressMap.set(nodeName, accum.count > 0 ? accum.sum / accum.count : 0);
+        });
     }
-    if (!isFinite(maxStress)) maxStress = 1;
+    
+    // Range of the values shown; quantities that can't be negative start from 0
+    const shownValues = byElement ? [...elementValues.values()] : [...nodeStressMap.values()];
+    let minStress = UNSIGNED_CONTOURS.includes(quantity) ? 0 : Math.min(0, ...shownValues);
+    let maxStress = Math.max(0, ...shownValues);
+    if (!isFinite(minStress)) minStress = 0;
+    if (!isFinite(maxStress) || maxStress <= minStress) maxStress = minStress + 1;
     
     const stressRange = maxStress - minStress;
     
-    console.log(`Plate ${stressType} stress range: ${minStress.toExponential(2)} to ${maxStress.toExponential(2)}`);
+    console.log(`Plate ${quantity} range: ${minStress.toExponential(2)} to ${maxStress.toExponential(2)}`);
     
-    // Color function using rainbow/jet colormap matching commercial FEA style
-    // Blue -> Cyan -> Green -> Yellow -> Orange -> Red with smooth transitions
+    // Color of a value; with bands, each band has the color of its middle
     function getStressColor(value) {
-        const t = Math.max(0, Math.min(1, (value - minStress) / stressRange));
-        const color = new THREE.Color();
-
-        // Balanced FEA-style colormap with more blue-cyan-teal gradation
-        // Spreads out blue/cyan range more evenly, similar to ANSYS/CalculiX
-        // Format: { t: position 0-1, r/g/b: 0-255 sRGB values }
-        const colorStops = [
-            { t: 0.00, r: 0,   g: 0,   b: 180 },  // Deep blue
-            { t: 0.08, r: 0,   g: 40,  b: 200 },  // Blue
-            { t: 0.16, r: 0,   g: 80,  b: 220 },  // Lighter blue
-            { t: 0.24, r: 0,   g: 120, b: 220 },  // Blue-cyan
-            { t: 0.32, r: 0,   g: 160, b: 200 },  // Cyan-blue
-            { t: 0.40, r: 0,   g: 190, b: 170 },  // Cyan
-            { t: 0.48, r: 0,   g: 200, b: 120 },  // Cyan-teal
-            { t: 0.56, r: 40,  g: 200, b: 60  },  // Teal-green
-            { t: 0.64, r: 100, g: 200, b: 30  },  // Green
-            { t: 0.72, r: 170, g: 200, b: 0   },  // Green-yellow
-            { t: 0.80, r: 220, g: 180, b: 0   },  // Yellow
-            { t: 0.88, r: 240, g: 120, b: 0   },  // Orange
-            { t: 0.96, r: 220, g: 60,  b: 20  },  // Red-orange
-            { t: 1.00, r: 180, g: 0,   b: 0   },  // Red
-        ];
-        
-        // Find the two color stops to interpolate between
-        let i = 0;
-        while (i < colorStops.length - 1 && colorStops[i + 1].t < t) {
-            i++;
-        }
-        
-        let r, g, b;
-        if (i >= colorStops.length - 1) {
-            const last = colorStops[colorStops.length - 1];
-            r = last.r; g = last.g; b = last.b;
-        } else {
-            const c0 = colorStops[i];
-            const c1 = colorStops[i + 1];
-            const s = (t - c0.t) / (c1.t - c0.t);
-            
-            r = c0.r + s * (c1.r - c0.r);
-            g = c0.g + s * (c1.g - c0.g);
-            b = c0.b + s * (c1.b - c0.b);
+        let t = Math.max(0, Math.min(1, (value - minStress) / stressRange));
+        if (bands > 0) {
+            t = (Math.min(Math.floor(t * bands), bands - 1) + 0.5) / bands;
         }
-        
-        // Convert to 0-1 range and set directly
-        color.setRGB(r / 255, g / 255, b / 255);
-        return color;
+        const [r, g, b] = contourRGB(t);
+        return new THREE.Color().setRGB(r / 255, g / 255, b / 255);
     }
     
     // Helper to ensure a geometry has a color attribute
@@ -3013,23 +3040,17 @@ window.showPlateStress = function(stressType = 'von_mises') {
         return newGeometry;
     }
     
-    // Step 3: Apply vertex colors to mesh elements with smooth gradients
+    // Step 3: Apply vertex colors to mesh elements, graded between the
+    // node values or uniform per element
     let coloredElements = 0;
-    plateElementIndex = 0;
     
-    sceneData.platesGroup.children.forEach((plate, plateIdx) => {
+    sceneData.platesGroup.children.forEach((plate) => {
         const meshViz = plate.children.find(c => c.userData && c.userData.isMeshViz);
         
         if (meshViz) {
-            // Meshed plate - apply smooth gradient colors to each element
+            // Meshed plate - color each element
             meshViz.children.forEach((element) => {
                 if (element.userData && element.userData.isMeshElement && element.isMesh) {
-                    let plateName = plateNameMap.get(element.uuid);
-                    if (!plateName) {
-                        plateElementIndex++;
-                        plateName = `P${plateElementIndex}`;
-                    }
-                    
                     const nodeNames = element.userData.nodeNames || [];
                     if (nodeNames.length !== 4) {
                         console.warn(`Element missing nodeNames, has ${nodeNames.length}`);
@@ -3045,9 +3066,9 @@ window.showPlateStress = function(stressType = 'von_mises') {
                     }
                     
                     // Get stress values at each corner node
-                    const cornerStresses = nodeNames.map(nodeName => {
-                        return nodeStressMap.get(nodeName) || 0;
-                    });
+                    const cornerStresses = byElement
+                        ? nodeNames.map(() => elementValues.get(element.uuid) || 0)
+                        : nodeNames.map(nodeName => nodeStressMap.get(nodeName) || 0);
                     
                     // Create subdivided geometry with smooth color interpolation
                     const subdividedGeometry = createSubdividedColoredQuad(element, cornerStresses, 6);
@@ -3088,14 +3109,9 @@ window.showPlateStress = function(stressType = 'von_mises') {
                 }
             });
         } else {
-            // Unmeshed plate - apply uniform color based on averaged corner stresses
-            plateElementIndex++;
-            let plateName = plateNameMap.get(plate.uuid) || `P${plateElementIndex}`;
-            const stressData = plateStressMap.get(plateName);
-            
-            if (stressData) {
-                const value = (stressData[stressType] ?? stressData.von_mises ?? 0);
-                const color = getStressColor(value);
+            // Unmeshed plate - apply uniform color from the plate's value
+            if (elementValues.has(plate.uuid)) {
+                const color = getStressColor(elementValues.get(plate.uuid));
                 
                 plate.children.forEach(child => {
                     if (child.isMesh) {
@@ -3118,111 +3134,21 @@ window.showPlateStress = function(stressType = 'von_mises') {
         }
     });
     
-    console.log(`Colored ${coloredElements} plate elements with smooth interpolation`);
-    
-    // Add color legend
-    addStressLegend(stressType, minStress, maxStress);
-    
-    console.log(`Showing ${stressType} stress for ${plateStresses.length} plates`);
-};
-
-/**
- * Add a color legend for stress visualization
- */
-function addStressLegend(stressType, minVal, maxVal) {
-    // Remove existing legend
-    const existingLegend = document.getElementById('stress-legend');
-    if (existingLegend) {
-        existingLegend.remove();
-    }
+    console.log(`Colored ${coloredElements} plate elements`);
     
-    // Create HTML-based legend - clean, minimal style
-    const legendDiv = document.createElement('div');
-    legendDiv.id = 'stress-legend';
-    legendDiv.style.cssText = `
-        position: absolute;
-        left: 12px;
-        bottom: 12px;
-        background: rgba(30, 30, 30, 0.75);
-        border-radius: 4px;
-        padding: 8px 12px;
-        z-index: 10;
-        font-family: Arial, sans-serif;
-        pointer-events: none;
-    `;
-    
-    // Title
-    const titleMap = {
-        'von_mises': 'Von Mises Stress',
-        'sx': 'Membrane Stress σx',
-        'sy': 'Membrane Stress σy',
-        'txy': 'Shear Stress τxy',
-        'mx': 'Bending Moment Mx',
-        'my': 'Bending Moment My',
-        'mxy': 'Twisting Moment Mxy'
+    // Legend, drawn by the app
+    const legend = {
+        quantity,
+        min: minStress,
+        max: maxStress,
+        bands,
+        stops: CONTOUR_COLOR_STOPS.map(c => [c.t, c.r, c.g, c.b])
     };
+    window.dispatchEvent(new CustomEvent('contourLegendChanged', { detail: legend }));
     
-    const title = document.createElement('div');
-    title.style.cssText = 'font-weight: 500; font-size: 12px; margin-bottom: 6px; text-align: center; color: #eee;';
-    title.textContent = titleMap[stressType] || stressType;
-    legendDiv.appendChild(title);
-    
-    // Color bar - matching the exact contour plot colors (balanced blue-cyan-teal-green-yellow-red)
-    const colorBar = document.createElement('div');
-    colorBar.style.cssText = `
-        width: 140px;
-        height: 16px;
-        background: linear-gradient(to right, 
-            rgb(0, 0, 180) 0%,
-            rgb(0, 40, 200) 8%,
-            rgb(0, 80, 220) 16%,
-            rgb(0, 120, 220) 24%,
-            rgb(0, 160, 200) 32%,
-            rgb(0, 190, 170) 40%,
-            rgb(0, 200, 120) 48%,
-            rgb(40, 200, 60) 56%,
-            rgb(100, 200, 30) 64%,
-            rgb(170, 200, 0) 72%,
-            rgb(220, 180, 0) 80%,
-            rgb(240, 120, 0) 88%,
-            rgb(220, 60, 20) 96%,
-            rgb(180, 0, 0) 100%);
-        border-radius: 2px;
-    `;
-    legendDiv.appendChild(colorBar);
-    
-    // Min/Max labels
-    const labelsDiv = document.createElement('div');
-    labelsDiv.style.cssText = 'display: flex; justify-content: space-between; margin-top: 4px; font-size: 10px; color: #ccc;';
-    
-    const minLabel = document.createElement('span');
-    minLabel.textContent = formatStressValue(minVal);
-    labelsDiv.appendChild(minLabel);
-    
-    const maxLabel = document.createElement('span');
-    maxLabel.textContent = formatStressValue(maxVal);
-    labelsDiv.appendChild(maxLabel);
-    
-    legendDiv.appendChild(labelsDiv);
-    
-    const canvasEl = window.sceneData?.renderer?.domElement;
-    const container = canvasEl?.parentElement || document.body;
-    if (container !== document.body) {
-        const computed = window.getComputedStyle(container);
-        if (computed.position === 'static') {
-            container.style.position = 'relative';
-        }
-    }
-    container.appendChild(legendDiv);
-}
-
-/**
- * Format stress value for display - always in MPa
- */
-function formatStressValue(value) {
-    // Always display in MPa (1 MPa = 1e6 Pa)
-    return (value / 1e6).toFixed(3) + ' MPa';
-}
+    console.log(`Showing ${quantity} for ${plateStresses.length} plates`);
+    return legend;
+};
 
 /**
  * Clear plate stress colors and restore original materials
@@ -44,69 +44,6 @@ function getNextId(group) {
     return maxId + 1;
 }
 
-// Undo history
-const undoHistory = [];
-const MAX_UNDO_STEPS = 20;
-
-/**
- * Add action to undo history
- */
-function addToUndoHistory(action) {
-    undoHistory.push(action);
-    if (undoHistory.length > MAX_UNDO_STEPS) {
-        undoHistory.shift();
-    }
-    console.log(`Undo history: ${undoHistory.length} action(s)`);
-}
-
-/**
- * Undo the last action
- */
-export function undoLastAction(nodesGroup, beamsGroup) {
-    if (undoHistory.length === 0) {
-        console.log('Nothing to undo');
-        return;
-    }
-    
-    const action = undoHistory.pop();
-    console.log('Undoing action:', action.type);
-    
-    if (action.type === 'extrude') {
-        // Clear current selection visuals before removing geometry
-        clearSelection();
-
-        // Remove created nodes and beams
-        action.nodesCreated.forEach(node => {
-            if (selectionHighlightsGroup) {
-                removeNodeSelectionHighlight(selectionHighlightsGroup, node);
-            }
-            nodesGroup.remove(node);
-            node.geometry.dispose();
-            node.material.dispose();
-            selectedNodes.delete(node);
-        });
-        action.beamsCreated.forEach(beam => {
-            beamsGroup.remove(beam);
-            beam.geometry.dispose();
-            beam.material.dispose();
-        });
-        
-        // Restore previous selection
-        action.previousSelection.forEach(node => {
-            if (nodesGroup.children.includes(node)) {
-                selectedNodes.add(node);
-                if (selectionHighlightsGroup) {
-                    addNodeSelectionHighlight(selectionHighlightsGroup, node);
-                }
-            }
-        });
-        
-        console.log(`Undid extrusion: removed ${action.nodesCreated.length} node(s) and ${action.beamsCreated.length} beam(s), restored ${selectedNodes.size} selected node(s)`);
-        updateNodeLabels(nodesGroup);
-        updateBeamLabels(beamsGroup);
-    }
-}
-
 /**
  * Find existing node at position
  * @param {THREE.Group} nodesGroup
@@ -375,44 +312,78 @@ export function selectAll(nodesGroup, beamsGroup, platesGroup, filter = 'all') {
     }
     
     if (filter === 'all' || filter === 'nodes') {
-        nodesGroup.children.forEach(node => {
-            selectedNodes.add(node);
-            if (selectionHighlightsGroup) {
-                addNodeSelectionHighlight(selectionHighlightsGroup, node);
-            }
-        });
+        nodesGroup.children.forEach(selectNode);
     }
     
     if (filter === 'all' || filter === 'beams') {
-        beamsGroup.children.forEach(beam => {
-            selectedBeams.add(beam);
-            if (!beam.userData.originalColor) {
-                beam.userData.originalColor = beam.material.color.getHex();
-                beam.userData.originalEmissive = beam.material.emissive.getHex();
-            }
-            beam.material.color.setHex(0x00ff00);
-            beam.material.emissive.setHex(0x00aa00);
-        });
+        beamsGroup.children.forEach(selectBeam);
     }
     
     if (platesGroup && (filter === 'all' || filter === 'plates')) {
-        platesGroup.children.forEach(plate => {
-            selectedPlates.add(plate);
-            if (!plate.userData.originalColor) {
-                plate.userData.originalColor = plate.material.color.getHex();
-            }
-            if (!plate.userData.originalOpacity) {
-                plate.userData.originalOpacity = plate.material.opacity;
-            }
-            // Use orange highlight for selected plates
-            plate.material.color.setHex(0xff8800);
-            plate.material.opacity = 0.9;
-        });
+        platesGroup.children.forEach(selectPlate);
     }
     
     console.log(`Selected all: ${selectedNodes.size} nodes, ${selectedBeams.size} beams, ${selectedPlates.size} plates`);
 }
 
+/**
+ * Select nodes, beams and plates by their ids, as a query over the model picked them
+ * @param {THREE.Group} nodesGroup
+ * @param {THREE.Group} beamsGroup
+ * @param {THREE.Group} platesGroup
+ * @param {Object} ids - { nodes, members, plates } arrays of ids
+ * @param {boolean} add - Add to the current selection instead of replacing it
+ */
+export function selectByIds(nodesGroup, beamsGroup, platesGroup, ids, add = false) {
+    if (!nodesGroup || !beamsGroup) {
+        console.warn('Scene not initialized');
+        return;
+    }
+    if (!add) {
+        clearSelection();
+    }
+    
+    const picked = (group, list) => {
+        const wanted = new Set(list || []);
+        return group ? group.children.filter(obj => wanted.has(obj.userData.id)) : [];
+    };
+    picked(nodesGroup, ids.nodes).forEach(selectNode);
+    picked(beamsGroup, ids.members).forEach(selectBeam);
+    picked(platesGroup, ids.plates).forEach(selectPlate);
+    
+    console.log(`Selected by query: ${selectedNodes.size} nodes, ${selectedBeams.size} beams, ${selectedPlates.size} plates`);
+}
+
+function selectNode(node) {
+    selectedNodes.add(node);
+    if (selectionHighlightsGroup) {
+        addNodeSelectionHighlight(selectionHighlightsGroup, node);
+    }
+}
+
+function selectBeam(beam) {
+    selectedBeams.add(beam);
+    if (!beam.userData.originalColor) {
+        beam.userData.originalColor = beam.material.color.getHex();
+        beam.userData.originalEmissive = beam.material.emissive.getHex();
+    }
+    beam.material.color.setHex(0x00ff00);
+    beam.material.emissive.setHex(0x00aa00);
+}
+
+function selectPlate(plate) {
+    selectedPlates.add(plate);
+    if (!plate.userData.originalColor) {
+        plate.userData.originalColor = plate.material.color.getHex();
+    }
+    if (!plate.userData.originalOpacity) {
+        plate.userData.originalOpacity = plate.material.opacity;
+    }
+    // Use orange highlight for selected plates
+    plate.material.color.setHex(0xff8800);
+    plate.material.opacity = 0.9;
+}
+
 /**
  * Clear all selections
  */
@@ -445,6 +416,7 @@ export function clearSelection() {
 
 /**
  * Delete selected nodes and beams
+ * Objects are detached but not disposed, so the edit history can restore them
  * @param {THREE.Group} nodesGroup
  * @param {THREE.Group} beamsGroup
  * @param {THREE.Group} platesGroup
@@ -467,15 +439,6 @@ export function deleteSelected(nodesGroup, beamsGroup, platesGroup) {
             if (parent) {
                 parent.remove(element);
             }
-            // Also remove edges if they exist
-            if (element.children) {
-                element.children.forEach(child => {
-                    if (child.geometry) child.geometry.dispose();
-                    if (child.material) child.material.dispose();
-                });
-            }
-            element.geometry.dispose();
-            element.material.dispose();
             deletedPlates++; // Count as plates for now
         });
         selectedElements.clear();
@@ -485,8 +448,6 @@ export function deleteSelected(nodesGroup, beamsGroup, platesGroup) {
     if (platesGroup) {
         selectedPlates.forEach(plate => {
             platesGroup.remove(plate);
-            plate.geometry.dispose();
-            plate.material.dispose();
             deletedPlates++;
         });
         selectedPlates.clear();
@@ -495,8 +456,6 @@ export function deleteSelected(nodesGroup, beamsGroup, platesGroup) {
     // Delete selected beams
     selectedBeams.forEach(beam => {
         beamsGroup.remove(beam);
-        beam.geometry.dispose();
-        beam.material.dispose();
         deletedBeams++;
     });
     selectedBeams.clear();
@@ -529,8 +488,6 @@ export function deleteSelected(nodesGroup, beamsGroup, platesGroup) {
         
         beamsToRemove.forEach(beam => {
             beamsGroup.remove(beam);
-            beam.geometry.dispose();
-            beam.material.dispose();
             selectedBeams.delete(beam);
             deletedBeams++;
         });
@@ -545,8 +502,6 @@ export function deleteSelected(nodesGroup, beamsGroup, platesGroup) {
             removeConstraintSymbol(node, { scene: nodesGroup.parent });
         }
         
-        node.geometry.dispose();
-        node.material.dispose();
         deletedNodes++;
     });
     selectedNodes.clear();
@@ -642,8 +597,6 @@ function cleanupOrphanedNodes(nodesGroup, beamsGroup, platesGroup) {
     
     nodesToRemove.forEach(node => {
         nodesGroup.remove(node);
-        if (node.geometry) node.geometry.dispose();
-        if (node.material) node.material.dispose();
         // Also remove from selection if present
         selectedNodes.delete(node);
         if (selectionHighlightsGroup) {
@@ -682,8 +635,6 @@ export function extrudeBeams(nodesGroup, beamsGroup, direction, length) {
     else if (direction === 'z') directionVector.set(0, 0, length);
 
     const newNodes = [];
-    const newBeams = [];
-    const previousSelection = Array.from(selectedNodes);
     
     selectedNodes.forEach(node => {
         const startPos = node.position.clone();
@@ -702,10 +653,7 @@ export function extrudeBeams(nodesGroup, beamsGroup, direction, length) {
         }
 
         // Create beam if it doesn't exist (pass node references)
-        const beam = createBeam(beamsGroup, startPos, endPos, node, endNode);
-        if (beam) {
-            newBeams.push(beam);
-        }
+        createBeam(beamsGroup, startPos, endPos, node, endNode);
     });
     
     // Select new nodes
@@ -716,12 +664,4 @@ export function extrudeBeams(nodesGroup, beamsGroup, direction, length) {
             addNodeSelectionHighlight(selectionHighlightsGroup, node);
         }
     });
-    
-    // Add to undo history
-    addToUndoHistory({
-        type: 'extrude',
-        nodesCreated: newNodes,
-        beamsCreated: newBeams,
-        previousSelection: previousSelection
-    });
 }
@@ -4,9 +4,10 @@
  */
 
 import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.module.js';
-import { selectedNodes, selectedBeams, selectedPlates, selectedElements, createNode, createBeam, createPlateMesh, findBeamBetweenPositions } from './geometry_manager.js';
+import { selectedNodes, selectedBeams, selectedPlates, selectedElements, createNode, createBeam, createPlateMesh, findBeamBetweenPositions, findNodeAtPosition } from './geometry_manager.js';
 import { addNodeSelectionHighlight, removeNodeSelectionHighlight } from './scene_setup.js';
 import { updateNodeLabels, updateBeamLabels } from './labels_manager.js';
+import { recordEdit } from './edit_history.js';
 
 // Selection highlights group reference (set by three_canvas.js)
 let selectionHighlightsGroup = null;
@@ -42,6 +43,35 @@ export let hoveredElement = null; // For mesh elements
 
 // Beam snap point state
 let currentSnapPoint = null; // { position: Vector3, type: 'midpoint'|'perpendicular', beam: beamMesh }
+let currentPlanePoint = null; // Snapped point on the work plane, when no node or beam is under the cursor
+let lastPlacedNode = null; // Anchor for relative coordinates once no beam is being drawn
+
+// Snap increment (m, 0 for none) and ortho lock for placing points, set from Rust
+export let drawSettings = { snap: 1, ortho: false };
+
+export function setDrawSettings(settings) {
+    drawSettings = { ...drawSettings, ...settings };
+}
+
+/**
+ * Point snapped to the increment along X and Z; with ortho on and an anchor,
+ * held to the axis it is furthest along from the anchor
+ * @param {THREE.Vector3} point
+ * @param {THREE.Vector3|null} anchor
+ * @returns {THREE.Vector3}
+ */
+function snapToIncrement(point, anchor = null) {
+    const snap = v => drawSettings.snap > 0 ? Math.round(v / drawSettings.snap) * drawSettings.snap : v;
+    const snapped = new THREE.Vector3(snap(point.x), point.y, snap(point.z));
+    if (anchor && drawSettings.ortho) {
+        if (Math.abs(snapped.x - anchor.x) >= Math.abs(snapped.z - anchor.z)) {
+            snapped.z = anchor.z;
+        } else {
+            snapped.x = anchor.x;
+        }
+    }
+    return snapped;
+}
 let snapPointIndicator = null; // Visual indicator for snap point
 
 // Plate drawing state
@@ -728,8 +758,26 @@ export function handleDrawBeamModeMove(sceneData) {
         clearSnapIndicator(scene);
     }
     
+    // Nothing to snap to: place on the work plane, level with the first node
+    currentPlanePoint = null;
+    if (!closestNode && !snapPoint) {
+        const plane = firstBeamNode
+            ? new THREE.Plane(new THREE.Vector3(0, 1, 0), -firstBeamNode.position.y)
+            : sceneData.gridPlane;
+        const point = new THREE.Vector3();
+        if (plane && raycaster.ray.intersectPlane(plane, point)) {
+            currentPlanePoint = snapToIncrement(point, firstBeamNode ? firstBeamNode.position : null);
+        }
+    }
+    if (sceneData.highlight) {
+        sceneData.highlight.visible = !!currentPlanePoint;
+        if (currentPlanePoint) {
+            sceneData.highlight.position.copy(currentPlanePoint);
+        }
+    }
+    
     // Show temporary line from first node to hovered target
-    const targetPosition = closestNode ? closestNode.position : (snapPoint ? snapPoint.position : null);
+    const targetPosition = closestNode ? closestNode.position : (snapPoint ? snapPoint.position : currentPlanePoint);
     
     if (firstBeamNode && targetPosition) {
         // Remove old temp line
@@ -877,11 +925,8 @@ export function handleAddNodeModeMove(sceneData) {
     const point = new THREE.Vector3();
     raycaster.ray.intersectPlane(gridPlane, point);
 
-    const snapped = new THREE.Vector3(
-        Math.round(point.x),
-        0,
-        Math.round(point.z)
-    );
+    const snapped = snapToIncrement(point);
+    snapped.y = 0;
     
     highlight.position.copy(snapped);
     highlight.visible = true;
@@ -909,15 +954,14 @@ export function handleAddNodeClick(sceneData, event) {
         yCoord = window.get2DElevation();
     }
 
-    const snapped = new THREE.Vector3(
-        Math.round(point.x),
-        yCoord,
-        Math.round(point.z)
-    );
+    const snapped = snapToIncrement(point);
+    snapped.y = yCoord;
 
     const node = createNode(nodesGroup, snapped);
     if (!node) {
         console.log('Cannot create node: a node already exists at this location');
+    } else {
+        lastPlacedNode = node;
     }
 }
 
@@ -1356,6 +1400,88 @@ export function handleSelectClick() {
     }
 }
 
+/**
+ * Take a node as the start of a beam, or as its end and add the beam
+ * @param {Object} sceneData
+ * @param {THREE.Mesh} node
+ * @param {boolean} chain - Start the next beam at the end of this one
+ */
+function pickBeamNode(sceneData, node, chain = false) {
+    const { scene, beamsGroup } = sceneData;
+    lastPlacedNode = node;
+    
+    if (!firstBeamNode) {
+        // First click: select start node
+        firstBeamNode = node;
+        firstBeamNode.material.color.setHex(0xff8800);
+        console.log('First node selected for beam');
+    } else if (node !== firstBeamNode) {
+        // Second click: create beam to end node
+        const startPos = firstBeamNode.position.clone();
+        const endPos = node.position.clone();
+        
+        // Check if beam already exists
+        const existingBeam = findBeamBetweenPositions(beamsGroup, startPos, endPos);
+        if (existingBeam) {
+            console.log('Beam already exists between these nodes');
+        } else {
+            createBeam(beamsGroup, startPos, endPos, firstBeamNode, node);
+        }
+        
+        // Reset state
+        firstBeamNode.material.color.setHex(firstBeamNode.userData.originalColor);
+        firstBeamNode = null;
+        
+        if (tempBeamLine) {
+            scene.remove(tempBeamLine);
+            tempBeamLine = null;
+        }
+        clearSnapIndicator(scene);
+        
+        if (chain) {
+            firstBeamNode = node;
+            firstBeamNode.material.color.setHex(0xff8800);
+        }
+    } else {
+        console.log('Cannot create beam to same node');
+    }
+}
+
+/**
+ * Place a node at typed coordinates; drawing beams, the node also starts or
+ * ends a beam, and each typed end starts the next
+ * @param {Object} sceneData
+ * @param {Object} position - { x, y, z } in metres
+ * @returns {number|null} Id of the node placed, or found there
+ */
+export function placeNodeAt(sceneData, position) {
+    const { nodesGroup } = sceneData;
+    const point = new THREE.Vector3(position.x, position.y, position.z);
+    const node = findNodeAtPosition(nodesGroup, point) || createNode(nodesGroup, point);
+    if (!node) {
+        return null;
+    }
+    if (modes.drawBeam) {
+        pickBeamNode(sceneData, node, true);
+    } else {
+        lastPlacedNode = node;
+    }
+    return node.userData.id;
+}
+
+/**
+ * Point relative coordinates are measured from: the start of the beam being
+ * drawn, else the last node placed
+ * @returns {Object|null} { x, y, z }
+ */
+export function drawAnchor() {
+    const node = firstBeamNode || lastPlacedNode;
+    if (!node || !node.parent) {
+        return null;
+    }
+    return { x: node.position.x, y: node.position.y, z: node.position.z };
+}
+
 /**
  * Handle click in draw beam mode
  * @param {Object} sceneData
@@ -1365,35 +1491,13 @@ export function handleDrawBeamClick(sceneData) {
     
     // Handle clicking on a node
     if (hoveredNode) {
-        if (!firstBeamNode) {
-            // First click: select start node
-            firstBeamNode = hoveredNode;
-            firstBeamNode.material.color.setHex(0xff8800);
-            console.log('First node selected for beam');
-        } else if (hoveredNode !== firstBeamNode) {
-            // Second click: create beam to end node
-            const startPos = firstBeamNode.position.clone();
-            const endPos = hoveredNode.position.clone();
-            
-            // Check if beam already exists
-            const existingBeam = findBeamBetweenPositions(beamsGroup, startPos, endPos);
-            if (existingBeam) {
-                console.log('Beam already exists between these nodes');
-            } else {
-                createBeam(beamsGroup, startPos, endPos, firstBeamNode, hoveredNode);
-            }
-            
-            // Reset state
-            firstBeamNode.material.color.setHex(firstBeamNode.userData.originalColor);
-            firstBeamNode = null;
-            
-            if (tempBeamLine) {
-                scene.remove(tempBeamLine);
-                tempBeamLine = null;
-            }
-            clearSnapIndicator(scene);
-        } else {
-            console.log('Cannot create beam to same node');
+        pickBeamNode(sceneData, hoveredNode);
+    }
+    // Handle clicking on the work plane: add a node there first
+    else if (currentPlanePoint && !currentSnapPoint) {
+        const node = findNodeAtPosition(nodesGroup, currentPlanePoint) || createNode(nodesGroup, currentPlanePoint);
+        if (node) {
+            pickBeamNode(sceneData, node);
         }
     }
     // Handle clicking on a snap point (beam intersection)
@@ -1486,8 +1590,9 @@ function createNodeAndSplitBeam(sceneData, snapPoint) {
         beam1.userData.section = section;
         // Apply start releases to first beam's i-node
         if (releases.i_node_ry !== undefined) {
-            beam1.userData.releases.i_node_ry = releases.i_node_ry;
-            beam1.userData.releases.i_node_rz = releases.i_node_rz;
+            ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                beam1.userData.releases[`i_node_${dof}`] = !!releases[`i_node_${dof}`];
+            });
         }
     }
     
@@ -1496,8 +1601,9 @@ function createNodeAndSplitBeam(sceneData, snapPoint) {
         beam2.userData.section = section;
         // Apply end releases to second beam's j-node
         if (releases.j_node_ry !== undefined) {
-            beam2.userData.releases.j_node_ry = releases.j_node_ry;
-            beam2.userData.releases.j_node_rz = releases.j_node_rz;
+            ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                beam2.userData.releases[`j_node_${dof}`] = !!releases[`j_node_${dof}`];
+            });
         }
     }
     
@@ -2081,12 +2187,14 @@ export function splitSelectedBeam(options) {
             newBeam.userData.section = section;
             // Only apply releases at the original beam ends
             if (i === 0 && releases.i_node_ry !== undefined) {
-                newBeam.userData.releases.i_node_ry = releases.i_node_ry;
-                newBeam.userData.releases.i_node_rz = releases.i_node_rz;
+                ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                    newBeam.userData.releases[`i_node_${dof}`] = !!releases[`i_node_${dof}`];
+                });
             }
             if (i === allNodes.length - 2 && releases.j_node_ry !== undefined) {
-                newBeam.userData.releases.j_node_ry = releases.j_node_ry;
-                newBeam.userData.releases.j_node_rz = releases.j_node_rz;
+                ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                    newBeam.userData.releases[`j_node_${dof}`] = !!releases[`j_node_${dof}`];
+                });
             }
             newBeams.push(newBeam);
             console.log(`Created segment ${i + 1}: length=${segmentLength.toFixed(3)}m`);
@@ -2115,7 +2223,7 @@ export function splitSelectedBeam(options) {
 }
 
 // Expose to window for Rust integration
-window.splitSelectedBeam = splitSelectedBeam;
+window.splitSelectedBeam = (options) => recordEdit('Split beam', () => splitSelectedBeam(options));
 
 /**
  * Get info about the first selected beam for the split panel
@@ -42,7 +42,7 @@ export function initLabels(scene) {
     labelsGroup.add(meshElementLabelsGroup);
 }
 
-function createLabel(text, position, color = '#000000', bgColor = 'transparent', offsetX = 0, offsetY = 0, offsetZ = 0, isOval = false, fontWeight = '700') {
+export function createLabel(text, position, color = '#000000', bgColor = 'transparent', offsetX = 0, offsetY = 0, offsetZ = 0, isOval = false, fontWeight = '700') {
     const canvas = document.createElement('canvas');
     const context = canvas.getContext('2d');
     
@@ -23,12 +23,52 @@ export { beamLoads, plateLoads, elementLoads };
 // Store load visualizations: Map<loadId, THREE.Group>
 const loadVisuals = new Map();
 
+// How loads are drawn, set from the Rust design state: shown at all, arrow
+// length scale, value labels, and load cases hidden
+let loadDisplay = { visible: true, scale: 1, labels: true, hiddenCases: [] };
+
+/**
+ * Whether loads of a case are shown
+ * @param {number} loadCase
+ * @returns {boolean}
+ */
+function isCaseShown(loadCase) {
+    return loadDisplay.visible && !loadDisplay.hiddenCases.includes(loadCase ?? 1);
+}
+
+/**
+ * Settings a visual is drawn with; visuals drawn with others are redrawn
+ * @returns {string}
+ */
+function drawKey() {
+    return `${loadDisplay.scale}|${loadDisplay.labels}`;
+}
+
+/**
+ * Mark a visual with the load case and settings it is drawn for, and show
+ * it if its case is shown
+ */
+function tagVisual(visual, loadCase) {
+    visual.userData.loadCase = loadCase ?? 1;
+    visual.userData.drawnWith = drawKey();
+    visual.visible = isCaseShown(loadCase);
+    return visual;
+}
+
 // Load counter for unique IDs
 let loadIdCounter = 0;
 
+/**
+ * Load case new loads are added to, as chosen in the toolbar
+ * @returns {number} Load case id
+ */
+export function activeLoadCase() {
+    return window.activeLoadCase || 1;
+}
+
 /**
  * Add a point load to selected beams
- * @param {Object} loadData - { magnitude, direction: 'x'|'y'|'z', position: 0-1, beamIds: [] }
+ * @param {Object} loadData - { magnitude, direction: 'x'|'y'|'z', position: 0-1, beamIds: [], loadCase }
  * @param {Object} sceneData - Scene data
  */
 export function addPointLoad(loadData, sceneData) {
@@ -45,9 +85,10 @@ export function addPointLoad(loadData, sceneData) {
             type: 'point',
             magnitude: parseFloat(loadData.magnitude) || 0,
             direction: loadData.direction || 'y',
-            position: parseFloat(loadData.position) || 0.5, // Position along beam (0-1)
+            position: Number.isFinite(parseFloat(loadData.position)) ? parseFloat(loadData.position) : 0.5, // Position along beam (0-1)
             beamId: beamId,
-            color: loadData.color // Store user-selected color
+            color: loadData.color, // Store user-selected color
+            loadCase: loadData.loadCase ?? activeLoadCase()
         };
         
         // Store load data
@@ -59,7 +100,7 @@ export function addPointLoad(loadData, sceneData) {
         // Create visualization
         const beam = sceneData.beamsGroup.children.find(b => b.uuid === beamId);
         if (beam) {
-            const visual = createPointLoadVisual(load, beam);
+            const visual = tagVisual(createPointLoadVisual(load, beam), load.loadCase);
             loadVisuals.set(load.id, visual);
             sceneData.scene.add(visual);
         } else {
@@ -72,6 +113,101 @@ export function addPointLoad(loadData, sceneData) {
     console.log('Total loads:', beamLoads.size, 'Total visuals:', loadVisuals.size);
 }
 
+/**
+ * Add a point load to a structural node
+ * @param {THREE.Mesh} node - Node mesh
+ * @param {Object} forces - { fx, fy, fz } in N, and optionally the loadCase
+ * @param {string} color - Arrow color
+ * @param {Object} sceneData - Scene data
+ * @returns {Object} The load, as stored in window.pointLoads
+ */
+export function addNodeLoad(node, forces, color, sceneData) {
+    const load = {
+        nodeUuid: node.uuid,
+        fx: forces.fx || 0,
+        fy: forces.fy || 0,
+        fz: forces.fz || 0,
+        color,
+        loadCase: forces.loadCase ?? activeLoadCase()
+    };
+    if (!window.pointLoads) window.pointLoads = [];
+    window.pointLoads.push(load);
+    const position = new THREE.Vector3();
+    node.getWorldPosition(position);
+    drawNodeLoad(position, load, sceneData);
+    return load;
+}
+
+/**
+ * Arrows of a node load, one per force component, sized in kN
+ * @param {THREE.Vector3} position - Where the load acts
+ * @param {Object} load - As stored in window.pointLoads
+ * @param {Object} sceneData - Scene data
+ */
+function drawNodeLoad(position, load, sceneData) {
+    ['x', 'y', 'z'].forEach(dir => {
+        const force = load[`f${dir}`];
+        if (force !== 0) {
+            createNodeLoadArrow(position, force / 1000, dir, load.color, sceneData, load.loadCase);
+        }
+    });
+}
+
+/**
+ * Mesh element of a plate by uuid
+ * @param {string} elementId
+ * @param {Object} sceneData - Scene data
+ * @returns {THREE.Mesh|null}
+ */
+function findMeshElement(elementId, sceneData) {
+    // Search in all plates -> meshElementsGroup -> elements
+    for (const plate of sceneData.platesGroup.children) {
+        const meshGroup = plate.children.find(c => c.userData.isMeshViz);
+        const element = meshGroup && meshGroup.children.find(e => e.uuid === elementId);
+        if (element) {
+            return element;
+        }
+    }
+    return null;
+}
+
+/**
+ * Create point load arrow at a node position
+ * Arrow points in the direction of the applied force
+ * Positive magnitude = arrow points in positive axis direction
+ * Negative magnitude = arrow points in negative axis direction
+ */
+export function createNodeLoadArrow(position, magnitude, direction, color, sceneData, loadCase = 1) {
+    const arrowLength = Math.min(2, Math.max(0.5, Math.abs(magnitude) * 0.15)) * loadDisplay.scale;
+    
+    // Arrow direction matches force direction (positive magnitude = positive axis)
+    const sign = magnitude >= 0 ? 1 : -1;
+    const arrowDir = new THREE.Vector3(
+        direction === 'x' ? sign : 0,
+        direction === 'y' ? sign : 0,
+        direction === 'z' ? sign : 0
+    );
+    
+    // Arrow starts offset from node and points toward the applied force direction
+    // The arrow "tip" points where the force goes, tail is offset back
+    const arrowOrigin = position.clone().sub(arrowDir.clone().multiplyScalar(arrowLength));
+    const arrowHelper = new THREE.ArrowHelper(
+        arrowDir,
+        arrowOrigin,
+        arrowLength,
+        new THREE.Color(color),
+        arrowLength * 0.35,
+        arrowLength * 0.2
+    );
+    arrowHelper.userData.isLoadVisual = true;
+    tagVisual(arrowHelper, loadCase);
+    sceneData.scene.add(arrowHelper);
+    
+    // Track for cleanup
+    if (!window.nodeLoadVisuals) window.nodeLoadVisuals = [];
+    window.nodeLoadVisuals.push(arrowHelper);
+}
+
 /**
  * Add a pressure load to selected plates
  * @param {Object} loadData - { magnitude, beamIds: [] (actually plateIds) }
@@ -88,22 +224,11 @@ export function addPressureLoad(loadData, sceneData) {
                 type: 'pressure_element',
                 magnitude: parseFloat(loadData.magnitude) || 0,
                 elementId: elId,
-                color: loadData.color || 0xff0000
+                color: loadData.color || 0xff0000,
+                loadCase: loadData.loadCase ?? activeLoadCase()
             };
             
-            // Find the element mesh
-            let foundElement = null;
-            // Search in all plates -> meshElementsGroup -> elements
-            for (const plate of sceneData.platesGroup.children) {
-                const meshGroup = plate.children.find(c => c.userData.isMeshViz);
-                if (meshGroup) {
-                    const el = meshGroup.children.find(e => e.uuid === elId);
-                    if (el) {
-                        foundElement = el;
-                        break;
-                    }
-                }
-            }
+            const foundElement = findMeshElement(elId, sceneData);
             
             if (foundElement) {
                 console.log('Found element mesh:', foundElement.uuid);
@@ -114,7 +239,7 @@ export function addPressureLoad(loadData, sceneData) {
                 }
                 elementLoads.get(elId).push(load);
 
-                const visual = createPressureLoadVisual(load, foundElement);
+                const visual = tagVisual(createPressureLoadVisual(load, foundElement), load.loadCase);
                 loadVisuals.set(load.id, visual);
                 sceneData.scene.add(visual);
             } else {
@@ -135,7 +260,8 @@ export function addPressureLoad(loadData, sceneData) {
             type: 'pressure',
             magnitude: parseFloat(loadData.magnitude) || 0,
             plateId: plateId,
-            color: loadData.color || 0xff0000 // Default red
+            color: loadData.color || 0xff0000, // Default red
+            loadCase: loadData.loadCase ?? activeLoadCase()
         };
         
         // Store load data
@@ -152,7 +278,7 @@ export function addPressureLoad(loadData, sceneData) {
         
         if (plate) {
             console.log('Creating visual for plate...');
-            const visual = createPressureLoadVisual(load, plate);
+            const visual = tagVisual(createPressureLoadVisual(load, plate), load.loadCase);
             console.log('Visual created:', visual);
             console.log('Visual children count:', visual.children.length);
             loadVisuals.set(load.id, visual);
@@ -188,7 +314,8 @@ export function addDistributedLoad(loadData, sceneData) {
             startPos: parseFloat(loadData.startPos) || 0,
             endPos: parseFloat(loadData.endPos) || 1,
             beamId: beamId,
-            color: loadData.color // Store user-selected color
+            color: loadData.color, // Store user-selected color
+            loadCase: loadData.loadCase ?? activeLoadCase()
         };
         
         // Store load data
@@ -200,7 +327,7 @@ export function addDistributedLoad(loadData, sceneData) {
         // Create visualization
         const beam = sceneData.beamsGroup.children.find(b => b.uuid === beamId);
         if (beam) {
-            const visual = createDistributedLoadVisual(load, beam);
+            const visual = tagVisual(createDistributedLoadVisual(load, beam), load.loadCase);
             loadVisuals.set(load.id, visual);
             sceneData.scene.add(visual);
         } else {
@@ -263,6 +390,21 @@ export function clearLoadsFromPlates(plateIds, sceneData) {
     console.log('Cleared loads from plates:', plateIds);
 }
 
+/**
+ * Remove every load and its visual
+ * @param {Object} sceneData - Scene data
+ */
+export function clearAllLoads(sceneData) {
+    restoreLoads({
+        beamLoads: new Map(),
+        plateLoads: new Map(),
+        elementLoads: new Map(),
+        visuals: new Map(),
+        pointLoads: [],
+        nodeLoadVisuals: []
+    }, sceneData);
+}
+
 /**
  * Create 3D arrow visualization for point load
  * @param {Object} load - Load data
@@ -301,7 +443,7 @@ function createPointLoadVisual(load, beam) {
     }
     
     // Create arrow - for negative loads, start from offset position to show arrow pointing down from above
-    const arrowLength = Math.abs(load.magnitude) * 0.1; // Scale factor
+    const arrowLength = Math.abs(load.magnitude) * 0.1 * loadDisplay.scale;
     const arrowOrigin = loadPosition.clone();
     
     // For negative loads, offset the arrow start point in the opposite direction
@@ -321,6 +463,10 @@ function createPointLoadVisual(load, beam) {
     group.add(arrowHelper);
     group.position.set(0, 0, 0);
     
+    if (!loadDisplay.labels) {
+        return group;
+    }
+    
     // Add label with magnitude - solid dark text, offset from arrow
     const canvas = document.createElement('canvas');
     const context = canvas.getContext('2d');
@@ -397,7 +543,7 @@ function createDistributedLoadVisual(load, beam) {
     
     // Create multiple arrows along the distributed load
     const numArrows = 5;
-    const arrowLength = Math.abs(load.magnitude) * 0.08;
+    const arrowLength = Math.abs(load.magnitude) * 0.08 * loadDisplay.scale;
     
     for (let i = 0; i <= numArrows; i++) {
         const t = i / numArrows;
@@ -445,6 +591,10 @@ function createDistributedLoadVisual(load, beam) {
     const line = new THREE.Line(lineGeometry, lineMaterial);
     group.add(line);
     
+    if (!loadDisplay.labels) {
+        return group;
+    }
+    
     // Add label - solid dark text, offset from arrows
     const canvas = document.createElement('canvas');
     const context = canvas.getContext('2d');
@@ -558,7 +708,7 @@ function createPressureLoadVisual(load, plate) {
     // 1. Create Volume Visualization (Filled Cube Space)
     // We want to extrude the plate geometry along the normal to create a volume.
     // Height of extrusion corresponds to the visual length of the load.
-    const height = 0.8;
+    const height = 0.8 * loadDisplay.scale;
     
     // Determine extrusion direction based on load sign
     // Push (Mag > 0): Force is INTO surface. Visual should be ABOVE surface (opposite to push).
@@ -749,7 +899,7 @@ function createPressureLoadVisual(load, plate) {
     }
     
     // Add label at centroid (on top face) - clean text only
-    if (uniquePoints.length > 0) {
+    if (loadDisplay.labels && uniquePoints.length > 0) {
         const center = new THREE.Vector3();
         uniquePoints.forEach(p => center.add(p));
         center.divideScalar(uniquePoints.length);
@@ -811,39 +961,112 @@ export function getAllLoads() {
     return beamLoads;
 }
 
-// Track visibility state
-let loadsVisible = true;
+/**
+ * Copy of every load and its visual, for the edit history
+ * @returns {Object}
+ */
+export function snapshotLoads() {
+    const copy = map => new Map(Array.from(map, ([id, loads]) => [id, [...loads]]));
+    return {
+        beamLoads: copy(beamLoads),
+        plateLoads: copy(plateLoads),
+        elementLoads: copy(elementLoads),
+        visuals: new Map(loadVisuals),
+        pointLoads: [...(window.pointLoads || [])],
+        nodeLoadVisuals: [...(window.nodeLoadVisuals || [])]
+    };
+}
 
 /**
- * Toggle visibility of all load visuals (beam loads and node loads)
+ * Put back the loads of a snapshot, replacing the current ones
+ * @param {Object} snapshot - From snapshotLoads()
+ * @param {Object} sceneData - Scene data
  */
-export function toggleLoadsVisibility() {
-    loadsVisible = !loadsVisible;
-    
-    // Toggle beam/plate load visuals
-    loadVisuals.forEach((visual) => {
-        visual.visible = loadsVisible;
-    });
-    
-    // Toggle node load visuals (point loads on nodes/mesh elements)
-    if (window.nodeLoadVisuals) {
-        window.nodeLoadVisuals.forEach((visual) => {
-            visual.visible = loadsVisible;
+export function restoreLoads(snapshot, sceneData) {
+    [[beamLoads, snapshot.beamLoads], [plateLoads, snapshot.plateLoads], [elementLoads, snapshot.elementLoads]]
+        .forEach(([loads, saved]) => {
+            loads.clear();
+            saved.forEach((list, id) => loads.set(id, [...list]));
         });
+
+    loadVisuals.forEach(visual => sceneData.scene.remove(visual));
+    loadVisuals.clear();
+    snapshot.visuals.forEach((visual, id) => {
+        visual.visible = isCaseShown(visual.userData.loadCase);
+        loadVisuals.set(id, visual);
+        sceneData.scene.add(visual);
+    });
+
+    (window.nodeLoadVisuals || []).forEach(visual => sceneData.scene.remove(visual));
+    window.nodeLoadVisuals = [...snapshot.nodeLoadVisuals];
+    window.nodeLoadVisuals.forEach(visual => {
+        visual.visible = isCaseShown(visual.userData.loadCase);
+        sceneData.scene.add(visual);
+    });
+    window.pointLoads = [...snapshot.pointLoads];
+
+    // Visuals saved before the display settings last changed
+    const stale = visual => visual.userData.drawnWith !== drawKey();
+    if ([...loadVisuals.values()].some(stale) || window.nodeLoadVisuals.some(stale)) {
+        redrawLoadVisuals(sceneData);
     }
-    
-    console.log(`Loads visibility: ${loadsVisible ? 'shown' : 'hidden'}`);
-    return loadsVisible;
 }
 
 /**
- * Get current loads visibility state
- * @returns {boolean}
+ * Draw every load again from its data, with the current display settings
+ * @param {Object} sceneData - Scene data
  */
-export function getLoadsVisibility() {
-    return loadsVisible;
+function redrawLoadVisuals(sceneData) {
+    const redraw = (loads, findTarget, create) => {
+        loads.forEach((list, targetId) => {
+            const target = findTarget(targetId);
+            if (!target) return;
+            list.forEach(load => {
+                const old = loadVisuals.get(load.id);
+                if (old) sceneData.scene.remove(old);
+                const visual = tagVisual(create(load, target), load.loadCase);
+                loadVisuals.set(load.id, visual);
+                sceneData.scene.add(visual);
+            });
+        });
+    };
+    const child = group => id => group.children.find(obj => obj.uuid === id);
+    redraw(beamLoads, child(sceneData.beamsGroup), (load, beam) => load.type === 'point'
+        ? createPointLoadVisual(load, beam)
+        : createDistributedLoadVisual(load, beam));
+    redraw(plateLoads, child(sceneData.platesGroup), createPressureLoadVisual);
+    redraw(elementLoads, id => findMeshElement(id, sceneData), createPressureLoadVisual);
+
+    (window.nodeLoadVisuals || []).forEach(visual => sceneData.scene.remove(visual));
+    window.nodeLoadVisuals = [];
+    (window.pointLoads || []).forEach(load => {
+        // Loads on mesh nodes keep their position rather than a node
+        const node = load.nodeUuid && child(sceneData.nodesGroup)(load.nodeUuid);
+        if (node) {
+            drawNodeLoad(node.getWorldPosition(new THREE.Vector3()), load, sceneData);
+        } else if (load.position) {
+            const { x, y, z } = load.position;
+            drawNodeLoad(new THREE.Vector3(x, y, z), load, sceneData);
+        }
+    });
 }
 
-// Expose globally
-window.toggleLoadsVisibility = toggleLoadsVisibility;
-window.getLoadsVisibility = getLoadsVisibility;
+/**
+ * Apply the load display settings of the design state
+ * @param {Object} settings - { visible, scale, labels, hiddenCases }
+ * @param {Object} sceneData - Scene data
+ */
+export function setLoadDisplay(settings, sceneData) {
+    const before = drawKey();
+    loadDisplay = { ...loadDisplay, ...settings };
+    if (drawKey() !== before) {
+        redrawLoadVisuals(sceneData);
+        return;
+    }
+    loadVisuals.forEach(visual => {
+        visual.visible = isCaseShown(visual.userData.loadCase);
+    });
+    (window.nodeLoadVisuals || []).forEach(visual => {
+        visual.visible = isCaseShown(visual.userData.loadCase);
+    });
+}
@@ -0,0 +1,224 @@
+/**
+ * Member Diagrams Module
+ * Axial, shear, torsion and bending moment diagrams drawn from the internal
+ * forces the solver reports at stations along each member
+ *
+ * The results carry member_diagrams: per member and combination, the
+ * distance of each station from the i-node and the forces there. Diagrams
+ * are drawn for the combination shown (window.feaResults), offset along the
+ * member's local axis, with moments on the tension side. Hovering a diagram
+ * shows the value at the nearest station.
+ */
+
+import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.module.js';
+
+/**
+ * Force shown by each diagram type: the local axis it is drawn along, the
+ * sign to draw it with, and how values are labelled (forces are in N and N·m,
+ * shown in the display units)
+ */
+const DIAGRAM_TYPES = {
+    axial: { axis: 'y', sign: 1, label: 'N', quantity: 'force' },
+    shear_y: { axis: 'y', sign: 1, label: 'Vy', quantity: 'force' },
+    shear_z: { axis: 'z', sign: 1, label: 'Vz', quantity: 'force' },
+    torsion: { axis: 'y', sign: 1, label: 'T', quantity: 'moment' },
+    // Sagging Mz is negative and My positive in the solver's convention
+    moment_z: { axis: 'y', sign: 1, label: 'Mz', quantity: 'moment' },
+    moment_y: { axis: 'z', sign: -1, label: 'My', quantity: 'moment' },
+};
+
+const POSITIVE_COLOR = 0x0066ff;
+const NEGATIVE_COLOR = 0xe53935;
+
+// Largest diagram offset as a fraction of the model's size, at scale 1
+const DIAGRAM_SIZE = 0.1;
+
+/**
+ * Local axes of a member as the solver builds them, turned by the member's
+ * rotation about its axis
+ */
+function memberAxes(iPos, jPos, rotation) {
+    const xAxis = new THREE.Vector3().subVectors(jPos, iPos);
+    const length = xAxis.length();
+    xAxis.divideScalar(length);
+
+    let yAxis;
+    let zAxis;
+    if (Math.abs(xAxis.x) < 1e-10 && Math.abs(xAxis.z) < 1e-10) {
+        // Vertical member
+        yAxis = new THREE.Vector3(0, 0, xAxis.y > 0 ? 1 : -1);
+        zAxis = new THREE.Vector3(1, 0, 0);
+    } else {
+        zAxis = new THREE.Vector3().crossVectors(xAxis, new THREE.Vector3(0, 1, 0)).normalize();
+        yAxis = new THREE.Vector3().crossVectors(zAxis, xAxis);
+    }
+
+    if (Math.abs(rotation || 0) > 1e-10) {
+        const cos = Math.cos(rotation);
+        const sin = Math.sin(rotation);
+        const y0 = yAxis.clone();
+        yAxis = y0.clone().multiplyScalar(cos).addScaledVector(zAxis, sin);
+        zAxis = zAxis.clone().multiplyScalar(cos).addScaledVector(y0, -sin);
+    }
+    return { xAxis, yAxis, zAxis, length };
+}
+
+/**
+ * Filled diagram along one member, and its outline
+ */
+function createMemberDiagram(start, axes, stations, values, offsetAxis, scale) {
+    const positions = [];
+    const colors = [];
+    const outline = [];
+    const base = k => start.clone().addScaledVector(axes.xAxis, stations[k]);
+    const tip = k => base(k).addScaledVector(offsetAxis, values[k] * scale);
+    const color = k => new THREE.Color(values[k] >= 0 ? POSITIVE_COLOR : NEGATIVE_COLOR);
+
+    for (let k = 0; k < stations.length - 1; k++) {
+        const quad = [base(k), tip(k), tip(k + 1), base(k), tip(k + 1), base(k + 1)];
+        const quadColors = [color(k), color(k), color(k + 1), color(k), color(k + 1), color(k + 1)];
+        quad.forEach(p => positions.push(p.x, p.y, p.z));
+        quadColors.forEach(c => colors.push(c.r, c.g, c.b));
+    }
+    outline.push(base(0));
+    stations.forEach((_, k) => outline.push(tip(k)));
+    outline.push(base(stations.length - 1));
+
+    const geometry = new THREE.BufferGeometry();
+    geometry.setAttribute('position', new THREE.Float32BufferAttribute(positions, 3));
+    geometry.setAttribute('color', new THREE.Float32BufferAttribute(colors, 3));
+    const fill = new THREE.Mesh(geometry, new THREE.MeshBasicMaterial({
+        vertexColors: true,
+        transparent: true,
+        opacity: 0.35,
+        side: THREE.DoubleSide,
+        depthWrite: false
+    }));
+
+    const line = new THREE.Line(
+        new THREE.BufferGeometry().setFromPoints(outline),
+        new THREE.LineBasicMaterial({ color: 0x333333 })
+    );
+    return { fill, line };
+}
+
+/**
+ * Show a diagram of one internal force for the combination shown
+ * @param {string} type - 'axial', 'shear_y', 'shear_z', 'torsion', 'moment_z' or 'moment_y'
+ * @returns {boolean} Whether the results have member diagrams to show
+ */
+window.showMemberForceDiagram = function(type = 'moment_z') {
+    const results = window.feaResults;
+    const model = window.feaModel;
+    const sceneData = window.sceneData;
+    const diagramType = DIAGRAM_TYPES[type];
+    if (!results || !model || !sceneData || !diagramType) {
+        console.error('No FEA results available');
+        return false;
+    }
+    const diagrams = results.member_diagrams || [];
+    if (diagrams.length === 0) {
+        console.warn('The results have no member diagrams; re-run the analysis');
+        return false;
+    }
+
+    window.clearFEADiagrams();
+    window.currentDiagramType = `member_${type}`;
+
+    const nodePos = new Map(model.nodes.map(n => [n.name, new THREE.Vector3(n.x, n.y, n.z)]));
+    const members = new Map(model.members.map(m => [m.name, m]));
+
+    // Size of the model, for scaling the largest value of the diagram
+    const bounds = new THREE.Box3();
+    nodePos.forEach(p => bounds.expandByPoint(p));
+    const size = Math.max(bounds.getSize(new THREE.Vector3()).length(), 1);
+    const maxValue = Math.max(...diagrams.map(d => Math.max(...d[type].map(Math.abs))));
+    const scale = maxValue > 0 ? DIAGRAM_SIZE * size * (window.diagramScale || 1) / maxValue : 0;
+
+    diagrams.forEach(diagram => {
+        const member = members.get(diagram.member);
+        const iPos = member && nodePos.get(member.i_node);
+        const jPos = member && nodePos.get(member.j_node);
+        if (!iPos || !jPos) return;
+
+        const axes = memberAxes(iPos, jPos, member.rotation);
+        const offsetAxis = (diagramType.axis === 'y' ? axes.yAxis : axes.zAxis)
+            .clone().multiplyScalar(diagramType.sign);
+        const values = diagram[type];
+        const { fill, line } = createMemberDiagram(iPos, axes, diagram.x, values, offsetAxis, scale);
+        fill.userData.memberDiagram = {
+            member: diagram.member,
+            start: iPos,
+            xAxis: axes.xAxis,
+            stations: diagram.x,
+            values
+        };
+        [fill, line].forEach(object => {
+            sceneData.scene.add(object);
+            window.feaDiagramObjects.push(object);
+        });
+    });
+    return true;
+};
+
+// ========================
+// Hover Readout
+// ========================
+
+let tooltip = null;
+const raycaster = new THREE.Raycaster();
+
+function showTooltip(text, clientX, clientY) {
+    if (!tooltip) {
+        tooltip = document.createElement('div');
+        tooltip.className = 'member-diagram-tooltip';
+        document.body.appendChild(tooltip);
+    }
+    tooltip.textContent = text;
+    tooltip.style.left = `${clientX + 14}px`;
+    tooltip.style.top = `${clientY + 14}px`;
+    tooltip.style.display = 'block';
+}
+
+function hideTooltip() {
+    if (tooltip) {
+        tooltip.style.display = 'none';
+    }
+}
+
+/**
+ * Value at the station nearest the pointer, while a member diagram is shown
+ */
+function onPointerMove(event) {
+    const sceneData = window.sceneData;
+    const type = (window.currentDiagramType || '').replace(/^member_/, '');
+    if (!sceneData || !window.currentDiagramType?.startsWith('member_') || !DIAGRAM_TYPES[type]) {
+        hideTooltip();
+        return;
+    }
+
+    const rect = sceneData.renderer.domElement.getBoundingClientRect();
+    const pointer = new THREE.Vector2(
+        ((event.clientX - rect.left) / rect.width) * 2 - 1,
+        -((event.clientY - rect.top) / rect.height) * 2 + 1
+    );
+    raycaster.setFromCamera(pointer, sceneData.camera);
+    const fills = window.feaDiagramObjects.filter(object => object.userData?.memberDiagram);
+    const hit = raycaster.intersectObjects(fills, false)[0];
+    if (!hit) {
+        hideTooltip();
+        return;
+    }
+
+    const { member, start, xAxis, stations, values } = hit.object.userData.memberDiagram;
+    const x = hit.point.clone().sub(start).dot(xAxis);
+    let nearest = 0;
+    stations.forEach((station, k) => {
+        if (Math.abs(station - x) < Math.abs(stations[nearest] - x)) nearest = k;
+    });
+    const { label, quantity } = DIAGRAM_TYPES[type];
+    const at = window.formatQuantity('length', stations[nearest]);
+    showTooltip(`${member} @ ${at}: ${label} = ${window.formatQuantity(quantity, values[nearest])}`, event.clientX, event.clientY);
+}
+
+window.addEventListener('pointermove', onPointerMove);
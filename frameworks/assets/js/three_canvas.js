@@ -9,10 +9,10 @@ import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.m
 import { initializeScene, initializeCameraControls, setViewportView } from './scene_setup.js';
 import { 
     selectAll, 
+    selectByIds,
     clearSelection, 
     deleteSelected as deleteSelectedGeometry, 
     extrudeBeams as extrudeBeamsGeometry,
-    undoLastAction as undoLastActionGeometry,
     setSelectionHighlightsGroup,
     selectedBeams,
     selectedNodes,
@@ -48,18 +48,26 @@ import {
     cancelCopyFromPoint,
     showCopyContextMenu,
     selectionFilter,
-    setSelectionFilter
+    setSelectionFilter,
+    setDrawSettings,
+    placeNodeAt,
+    drawAnchor
 } from './interaction_handlers.js';
 import {
     applyNodeConstraints,
-    clearNodeConstraints
+    clearNodeConstraints,
+    setSupportScale
 } from './constraints_manager.js';
 import {
     addPointLoad,
     addDistributedLoad,
     addPressureLoad,
+    addNodeLoad,
+    createNodeLoadArrow,
     clearLoadsFromBeams,
-    clearLoadsFromPlates
+    clearLoadsFromPlates,
+    setLoadDisplay,
+    activeLoadCase
 } from './loads_manager.js';
 import {
     extractStructureData,
@@ -68,6 +76,18 @@ import {
 import {
     generateMesh
 } from './meshing_manager.js';
+import {
+    recordEdit,
+    undoEdit,
+    redoEdit,
+    clearEditHistory,
+    getEditHistory,
+    setEditHistoryScene
+} from './edit_history.js';
+import {
+    serializeViewportModel,
+    loadViewportModel
+} from './viewport_model.js';
 import {
     initLabels,
     toggleNodeLabels,
@@ -90,7 +110,11 @@ import {
 } from './analysis_diagrams.js';
 
 // Import FEA solver integration (attaches functions to window)
+import './display_units.js';
 import './fea_integration.js';
+import './member_diagrams.js';
+import './deformed_animation.js';
+import './construction_grid.js';
 
 // Global scene data
 let sceneData = null;
@@ -136,11 +160,11 @@ export async function init_three_canvas(canvas) {
     
     // Expose sceneData globally for analysis
     window.sceneData = sceneData;
+    setEditHistoryScene(sceneData);
     
     // Expose extractStructureData globally
     window.extractStructureData = extractStructureData;
     window.getStructureJSON = getStructureJSON;
-    window.applyNodeConstraints = applyNodeConstraints;
     
     // Expose label toggles
     window.toggleNodeLabels = (visible) => toggleNodeLabels(visible, sceneData.nodesGroup);
@@ -201,12 +225,32 @@ export async function init_three_canvas(canvas) {
     window.toggleDrawBeamMode = toggleDrawBeamMode;
     window.toggleDrawPlateMode = toggleDrawPlateMode;
     window.selectAllNodes = selectAllNodes;
+    window.selectByIds = (ids, add = false) => selectByIds(sceneData.nodesGroup, sceneData.beamsGroup, sceneData.platesGroup, ids, add);
+    window.getSelectedIds = () => ({
+        nodes: [...selectedNodes].map(node => node.userData.id),
+        members: [...selectedBeams].map(beam => beam.userData.id),
+        plates: [...selectedPlates].map(plate => plate.userData.id)
+    });
     window.clearNodeSelection = clearNodeSelection;
     window.deleteSelected = deleteSelected;
     window.extrudeBeams = extrudeBeams;
-    window.undoLastAction = undoLastAction;
+    window.undoEdit = undoEdit;
+    window.redoEdit = redoEdit;
+    window.getEditHistory = getEditHistory;
     window.startCopyElements = startCopyElements;
     window.setSelectionFilter = setSelectionFilter;
+    window.setDrawSettings = setDrawSettings;
+    window.getDrawAnchor = drawAnchor;
+    window.setDisplaySettings = (settings) => {
+        setLoadDisplay({
+            visible: settings.loads_visible,
+            scale: settings.load_scale,
+            labels: settings.load_labels,
+            hiddenCases: settings.hidden_load_cases
+        }, sceneData);
+        setSupportScale(settings.support_scale);
+    };
+    window.placeNodeAt = (position) => recordEdit(modes.drawBeam ? 'Add beam' : 'Add node', () => placeNodeAt(sceneData, position));
     window.setViewportView = (view) => setViewportView(sceneData.camera, cameraControls, view);
     window.setModalOpen = (open) => { isModalOpen = open; };
     window.modes = modes;
@@ -350,8 +394,8 @@ export async function init_three_canvas(canvas) {
         const physicalGroup = new THREE.Group();
         physicalGroup.name = 'physicalGeometry';
         
-        // Get beam section properties
-        const beamSection = window.currentBeamSection || {
+        // Default beam section, for members without one assigned
+        const defaultSection = window.currentBeamSection || {
             section_type: 'Rectangular',
             width: 0.3,
             height: 0.5,
@@ -387,6 +431,7 @@ export async function init_three_canvas(canvas) {
             const direction = new THREE.Vector3().subVectors(endPos, startPos);
             const beamLength = direction.length();
             
+            const beamSection = sectionDimensions(beam.userData.section) || defaultSection;
             let beamGeom;
             
             if (beamSection.section_type === 'I-Beam' || beamSection.section_type === 'IBeam') {
@@ -412,6 +457,9 @@ export async function init_three_canvas(canvas) {
             }
             
             const physicalBeam = new THREE.Mesh(beamGeom, beamRenderMat.clone());
+            if (beam.userData.section && beam.userData.section.color) {
+                physicalBeam.material.color.set(beam.userData.section.color);
+            }
             
             // Position at midpoint
             const midpoint = new THREE.Vector3().addVectors(startPos, endPos).multiplyScalar(0.5);
@@ -509,6 +557,38 @@ export async function init_three_canvas(canvas) {
     /**
      * Remove physical geometry and restore original view
      */
+    /**
+     * Dimensions to draw an assigned section with, from the shape of its
+     * solver section; null if it has none
+     */
+    function sectionDimensions(assigned) {
+        const shape = assigned && assigned.section && assigned.section.shape;
+        if (!shape) return null;
+        if (shape.Rectangle) {
+            return { section_type: 'Rectangular', width: shape.Rectangle.width, height: shape.Rectangle.depth };
+        }
+        if (shape.Box) {
+            return { section_type: 'Rectangular', width: shape.Box.width, height: shape.Box.depth };
+        }
+        if (shape.Circle) {
+            return { section_type: 'Circular', width: shape.Circle.diameter };
+        }
+        if (shape.Pipe) {
+            return { section_type: 'Circular', width: shape.Pipe.outer_diameter };
+        }
+        if (shape.WideFlange) {
+            const wf = shape.WideFlange;
+            return {
+                section_type: 'IBeam',
+                width: wf.flange_width,
+                height: wf.depth,
+                flange_thickness: wf.flange_thickness,
+                web_thickness: wf.web_thickness
+            };
+        }
+        return null;
+    }
+    
     function removePhysicalGeometry(sceneData) {
         const existing = sceneData.scene.getObjectByName('physicalGeometry');
         if (existing) {
@@ -711,7 +791,7 @@ export async function init_three_canvas(canvas) {
         console.log('sceneData:', sceneData);
         console.log('selectedNodes:', sceneData ? sceneData.selectedNodes : 'no sceneData');
         if (sceneData) {
-            applyNodeConstraints(constraintData, sceneData);
+            recordEdit('Apply supports', () => applyNodeConstraints(constraintData, sceneData));
         } else {
             console.error('sceneData not available');
         }
@@ -719,12 +799,12 @@ export async function init_three_canvas(canvas) {
     window.clearNodeConstraints = () => {
         console.log('window.clearNodeConstraints called');
         if (sceneData) {
-            clearNodeConstraints(sceneData);
+            recordEdit('Clear supports', () => clearNodeConstraints(sceneData));
         }
     };
     
     // Expose load functions
-    window.applyPointLoad = (loadData) => {
+    window.applyPointLoad = (loadData) => recordEdit('Add point load', () => {
         console.log('window.applyPointLoad called with:', loadData);
         console.log('selectedBeams:', selectedBeams, 'size:', selectedBeams.size);
         console.log('selectedNodes:', selectedNodes, 'size:', selectedNodes.size);
@@ -748,18 +828,11 @@ export async function init_three_canvas(canvas) {
             selectedNodes.forEach(node => {
                 // Positive magnitude = force in positive axis direction
                 // Negative magnitude = force in negative axis direction (e.g. -Y for gravity)
-                const load = {
-                    nodeUuid: node.uuid,
+                const load = addNodeLoad(node, {
                     fx: dir === 'x' ? mag * 1000 : 0, // Convert kN to N
                     fy: dir === 'y' ? mag * 1000 : 0,
                     fz: dir === 'z' ? mag * 1000 : 0
-                };
-                window.pointLoads.push(load);
-                
-                // Create visual arrow at node position
-                const pos = new THREE.Vector3();
-                node.getWorldPosition(pos);
-                createNodePointLoadVisual(pos, mag, dir, color, sceneData);
+                }, color, sceneData);
                 
                 console.log(`Added point load to node ${node.uuid}:`, load);
             });
@@ -805,12 +878,14 @@ export async function init_three_canvas(canvas) {
                                 position: { x: localPos.x, y: localPos.y, z: localPos.z },
                                 fx: dir === 'x' ? mag * 1000 : 0,
                                 fy: dir === 'y' ? mag * 1000 : 0,
-                                fz: dir === 'z' ? mag * 1000 : 0
+                                fz: dir === 'z' ? mag * 1000 : 0,
+                                color,
+                                loadCase: activeLoadCase()
                             };
                             window.pointLoads.push(load);
                             
                             // Create visual
-                            createNodePointLoadVisual(localPos, mag, dir, color, sceneData);
+                            createNodeLoadArrow(localPos, mag, dir, color, sceneData, load.loadCase);
                             console.log(`Added point load to mesh node ${nodeName}:`, load);
                         }
                     }
@@ -819,43 +894,9 @@ export async function init_three_canvas(canvas) {
         } else {
             console.warn('No beams, nodes, or elements selected for point load');
         }
-    };
-    
-    // Helper to create point load visual at a node position
-    // Arrow points in the direction of the applied force
-    // Positive magnitude = arrow points in positive axis direction
-    // Negative magnitude = arrow points in negative axis direction
-    function createNodePointLoadVisual(position, magnitude, direction, color, sceneData) {
-        const arrowLength = Math.min(2, Math.max(0.5, Math.abs(magnitude) * 0.15));
-        
-        // Arrow direction matches force direction (positive magnitude = positive axis)
-        const sign = magnitude >= 0 ? 1 : -1;
-        const arrowDir = new THREE.Vector3(
-            direction === 'x' ? sign : 0,
-            direction === 'y' ? sign : 0,
-            direction === 'z' ? sign : 0
-        );
-        
-        // Arrow starts offset from node and points toward the applied force direction
-        // The arrow "tip" points where the force goes, tail is offset back
-        const arrowOrigin = position.clone().sub(arrowDir.clone().multiplyScalar(arrowLength));
-        const arrowHelper = new THREE.ArrowHelper(
-            arrowDir,
-            arrowOrigin,
-            arrowLength,
-            new THREE.Color(color),
-            arrowLength * 0.35,
-            arrowLength * 0.2
-        );
-        arrowHelper.userData.isLoadVisual = true;
-        sceneData.scene.add(arrowHelper);
-        
-        // Track for cleanup
-        if (!window.nodeLoadVisuals) window.nodeLoadVisuals = [];
-        window.nodeLoadVisuals.push(arrowHelper);
-    }
+    });
     
-    window.applyDistributedLoad = (loadData) => {
+    window.applyDistributedLoad = (loadData) => recordEdit('Add distributed load', () => {
         console.log('window.applyDistributedLoad called with:', loadData);
         console.log('selectedBeams:', selectedBeams, 'size:', selectedBeams.size);
         if (sceneData && selectedBeams.size > 0) {
@@ -864,9 +905,9 @@ export async function init_three_canvas(canvas) {
         } else {
             console.warn('No beams selected for distributed load');
         }
-    };
+    });
 
-    window.applyPressureLoad = (loadData) => {
+    window.applyPressureLoad = (loadData) => recordEdit('Add pressure load', () => {
         console.log('=== window.applyPressureLoad called ===');
         console.log('loadData:', loadData);
         console.log('selectedPlates size:', selectedPlates.size);
@@ -893,9 +934,9 @@ export async function init_three_canvas(canvas) {
         } else {
             console.warn('No selection found for pressure load');
         }
-    };
+    });
     
-    window.clearLoadsFromSelectedBeams = () => {
+    window.clearLoadsFromSelectedBeams = () => recordEdit('Clear loads', () => {
         console.log('clearLoadsFromSelectedBeams called');
         console.log('selectedBeams:', selectedBeams, 'size:', selectedBeams.size);
         if (sceneData && selectedBeams.size > 0) {
@@ -921,9 +962,9 @@ export async function init_three_canvas(canvas) {
         } else {
             console.warn('No beams selected to clear loads from');
         }
-    };
+    });
 
-    window.clearLoadsFromSelectedPlates = () => {
+    window.clearLoadsFromSelectedPlates = () => recordEdit('Clear loads', () => {
         console.log('clearLoadsFromSelectedPlates called');
         
         const platesToClear = new Set();
@@ -944,7 +985,7 @@ export async function init_three_canvas(canvas) {
         } else {
             console.warn('No plates selected to clear loads from');
         }
-    };
+    });
     
     window.showPointLoadPanel = () => {
         console.log('showPointLoadPanel called');
@@ -970,6 +1011,11 @@ export async function init_three_canvas(canvas) {
         console.log('getStructureJSON called');
         return getStructureJSON(sceneData);
     };
+    
+    // The model owned by the Rust ModelStore: read after every edit, and
+    // pushed back here when Rust changes it (as one undoable edit)
+    window.getViewportModel = () => serializeViewportModel(sceneData);
+    window.loadViewportModel = (model, label = 'Edit model') => recordEdit(label, () => loadViewportModel(model, sceneData));
 
     // === Programmatic geometry creation helpers ===
     // These use the same THREE.js instance as the scene, avoiding version conflicts
@@ -1201,6 +1247,18 @@ export async function init_three_canvas(canvas) {
         
         console.log('updateAllReleaseIndicators: Checking', sceneData.beamsGroup.children.length, 'beams');
         
+        // Drop indicators of beams no longer in the model
+        const beamIds = new Set(sceneData.beamsGroup.children.map(beam => beam.userData.id));
+        sceneData.releaseIndicatorsGroup.children
+            .filter(child => !beamIds.has(child.userData.beamId))
+            .forEach(child => {
+                sceneData.releaseIndicatorsGroup.remove(child);
+                child.traverse(obj => {
+                    if (obj.geometry) obj.geometry.dispose();
+                    if (obj.material) obj.material.dispose();
+                });
+            });
+        
         sceneData.beamsGroup.children.forEach(beam => {
             updateBeamReleaseIndicators(beam);
         });
@@ -1213,22 +1271,37 @@ export async function init_three_canvas(canvas) {
         const beams = window.selectedBeams || new Set();
         if (beams.size === 0) return;
         
-        // Validate and normalize the releases object
-        const validReleases = {
-            i_node_ry: !!(releases && releases.i_node_ry),
-            i_node_rz: !!(releases && releases.i_node_rz),
-            j_node_ry: !!(releases && releases.j_node_ry),
-            j_node_rz: !!(releases && releases.j_node_rz)
-        };
+        // Validate and normalize the releases object, one flag per end and DOF
+        const validReleases = {};
+        ['i_node', 'j_node'].forEach(end => {
+            ['dx', 'dy', 'dz', 'rx', 'ry', 'rz'].forEach(dof => {
+                validReleases[`${end}_${dof}`] = !!(releases && releases[`${end}_${dof}`]);
+            });
+        });
         
-        beams.forEach(beam => {
+        recordEdit('Set releases', () => beams.forEach(beam => {
             beam.userData.releases = { ...validReleases };
             // Update release indicators for this beam
             updateBeamReleaseIndicators(beam);
-        });
+        }));
         console.log(`Updated releases for ${beams.size} beam(s):`, validReleases);
     };
     
+    // Assign a section, as MemberSection serializes it from Rust, to the selected beam(s)
+    window.setSelectedBeamSection = (section) => {
+        const beams = window.selectedBeams || new Set();
+        if (beams.size === 0 || !section) return;
+        
+        recordEdit('Assign section', () => beams.forEach(beam => {
+            beam.userData.section = section;
+        }));
+        // Redraw the rendered members in their new sections
+        if (sceneData.scene.getObjectByName('physicalGeometry')) {
+            createPhysicalGeometry(sceneData);
+        }
+        console.log(`Assigned section ${section.name} to ${beams.size} beam(s)`);
+    };
+    
     window.createConstraintSymbol = (node, constraintType) => {
         if (!sceneData || !sceneData.scene) {
             console.error('Scene not initialized');
@@ -1324,6 +1397,9 @@ export async function init_three_canvas(canvas) {
             });
         });
         
+        // Earlier edits refer to the objects just removed
+        clearEditHistory();
+        
         console.log('All geometry cleared');
     };
     
@@ -1374,13 +1450,6 @@ function onKeyDown(e) {
         isDKeyHeld = true;
     }
     
-    // Undo: Ctrl+Z
-    if ((e.ctrlKey || e.metaKey) && e.key.toLowerCase() === 'z') {
-        e.preventDefault();
-        undoLastAction();
-        return;
-    }
-
     // Delete: Delete or Backspace
     if (e.key === 'Delete' || e.key === 'Backspace') {
         e.preventDefault();
@@ -1390,7 +1459,7 @@ function onKeyDown(e) {
 
     if (modes.drawPlate) {
         if (e.key === 'Enter') {
-            completePlate(sceneData);
+            recordEdit('Add plate', () => completePlate(sceneData));
         } else if (e.key === 'Escape') {
             cancelPlateDrawing(sceneData.scene);
         }
@@ -1561,15 +1630,15 @@ function onClick(e) {
     
     if (modes.copyFromPoint) {
         console.log('Calling handleCopyFromPointClick');
-        handleCopyFromPointClick(sceneData);
+        recordEdit('Copy elements', () => handleCopyFromPointClick(sceneData));
     } else if (modes.addNode) {
-        handleAddNodeClick({ ...sceneData, canvas }, e);
+        recordEdit('Add node', () => handleAddNodeClick({ ...sceneData, canvas }, e));
     } else if (modes.selectNode) {
         handleSelectClick();
     } else if (modes.drawBeam) {
-        handleDrawBeamClick(sceneData);
+        recordEdit('Add beam', () => handleDrawBeamClick(sceneData));
     } else if (modes.drawPlate) {
-        handleDrawPlateClick(sceneData);
+        recordEdit('Add plate', () => handleDrawPlateClick(sceneData));
     }
     
     isDragging = false;
@@ -1717,7 +1786,7 @@ export function clearNodeSelection() {
 }
 
 export function deleteSelected() {
-    deleteSelectedGeometry(sceneData.nodesGroup, sceneData.beamsGroup, sceneData.platesGroup);
+    recordEdit('Delete', () => deleteSelectedGeometry(sceneData.nodesGroup, sceneData.beamsGroup, sceneData.platesGroup));
 }
 
 /**
@@ -1726,14 +1795,7 @@ export function deleteSelected() {
  * @param {number} length
  */
 export function extrudeBeams(direction, length) {
-    extrudeBeamsGeometry(sceneData.nodesGroup, sceneData.beamsGroup, direction, length);
-}
-
-/**
- * Undo the last action
- */
-export function undoLastAction() {
-    undoLastActionGeometry(sceneData.nodesGroup, sceneData.beamsGroup);
+    recordEdit('Extrude', () => extrudeBeamsGeometry(sceneData.nodesGroup, sceneData.beamsGroup, direction, length));
 }
 
 /**
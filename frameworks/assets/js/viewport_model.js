@@ -0,0 +1,278 @@
+/**
+ * Viewport Model Module
+ * Converts between the scene and the model owned by the Rust ModelStore
+ *
+ * The model lists nodes, members, plates, supports and loads, referring to
+ * each other by the ids shown in the viewport (userData.id). Edits made in
+ * the viewport are serialized back to Rust after every change; edits made
+ * in Rust rebuild the scene from the model. Plate meshes are not part of the
+ * model and are generated again after a rebuild.
+ */
+
+import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.164.0/build/three.module.js';
+import { createNode, createBeam, createPlateMesh, findNodeAtPosition, clearSelection, selectedElements } from './geometry_manager.js';
+import {
+    beamLoads,
+    plateLoads,
+    addPointLoad,
+    addDistributedLoad,
+    addPressureLoad,
+    addNodeLoad,
+    clearAllLoads
+} from './loads_manager.js';
+import { setNodeConstraint, restoreNodeConstraint } from './constraints_manager.js';
+import { updateNodeLabels, updateBeamLabels, updatePlateLabels } from './labels_manager.js';
+
+/**
+ * Load colors are stored as '#rrggbb' whether they were picked or defaulted
+ */
+function colorString(color) {
+    if (typeof color === 'number') {
+        return '#' + color.toString(16).padStart(6, '0');
+    }
+    return color || null;
+}
+
+/**
+ * Serialize the scene to the Rust ViewportModel format
+ * @param {Object} sceneData - Scene data
+ * @returns {Object|null} null before the scene is initialized
+ */
+export function serializeViewportModel(sceneData) {
+    if (!sceneData || !sceneData.nodesGroup || !sceneData.beamsGroup) {
+        return null;
+    }
+
+    const nodeIds = new Map(); // node uuid -> id
+    const nodes = sceneData.nodesGroup.children.map(node => {
+        nodeIds.set(node.uuid, node.userData.id);
+        return { id: node.userData.id, x: node.position.x, y: node.position.y, z: node.position.z };
+    });
+
+    const supports = sceneData.nodesGroup.children
+        .filter(node => node.userData.constraint)
+        .map(node => {
+            const c = node.userData.constraint;
+            return {
+                node: node.userData.id,
+                dx: !!c.dx, dy: !!c.dy, dz: !!c.dz,
+                rx: !!c.rx, ry: !!c.ry, rz: !!c.rz,
+                kx: Number(c.kx) || 0, ky: Number(c.ky) || 0, kz: Number(c.kz) || 0
+            };
+        });
+
+    // Members are the beams that know their end nodes
+    const memberIds = new Map(); // beam uuid -> id
+    const members = [];
+    sceneData.beamsGroup.children.forEach(beam => {
+        const { startNode, endNode } = beam.userData;
+        if (!startNode || !endNode || !nodeIds.has(startNode.uuid) || !nodeIds.has(endNode.uuid)) {
+            return;
+        }
+        memberIds.set(beam.uuid, beam.userData.id);
+        members.push({
+            id: beam.userData.id,
+            i_node: nodeIds.get(startNode.uuid),
+            j_node: nodeIds.get(endNode.uuid),
+            releases: { ...beam.userData.releases },
+            section: beam.userData.section || null
+        });
+    });
+
+    const plateIds = new Map(); // plate uuid -> id
+    const plates = [];
+    if (sceneData.platesGroup) {
+        sceneData.platesGroup.children.forEach(plate => {
+            const plateNodes = plate.userData.nodes || [];
+            if (plateNodes.length < 3 || !plateNodes.every(n => nodeIds.has(n.uuid))) {
+                return;
+            }
+            plateIds.set(plate.uuid, plate.userData.id);
+            plates.push({
+                id: plate.userData.id,
+                node_ids: plateNodes.map(n => nodeIds.get(n.uuid)),
+                thickness: plate.userData.thickness ?? null
+            });
+        });
+    }
+
+    const member_loads = [];
+    beamLoads.forEach((loads, uuid) => {
+        if (!memberIds.has(uuid)) return;
+        const member = memberIds.get(uuid);
+        loads.forEach(load => {
+            if (load.type === 'point') {
+                member_loads.push({
+                    type: 'point', member,
+                    magnitude: load.magnitude, direction: load.direction, position: load.position,
+                    color: colorString(load.color), load_case: load.loadCase ?? 1
+                });
+            } else if (load.type === 'distributed') {
+                member_loads.push({
+                    type: 'distributed', member,
+                    magnitude: load.magnitude, direction: load.direction,
+                    start_pos: load.startPos, end_pos: load.endPos,
+                    color: colorString(load.color), load_case: load.loadCase ?? 1
+                });
+            }
+        });
+    });
+
+    const plate_loads = [];
+    plateLoads.forEach((loads, uuid) => {
+        if (!plateIds.has(uuid)) return;
+        loads
+            .filter(load => load.type === 'pressure')
+            .forEach(load => plate_loads.push({
+                plate: plateIds.get(uuid),
+                magnitude: load.magnitude,
+                color: colorString(load.color),
+                load_case: load.loadCase ?? 1
+            }));
+    });
+
+    // Loads on mesh nodes go with the mesh, so only structural nodes here
+    const node_loads = (window.pointLoads || [])
+        .filter(load => load.nodeUuid && nodeIds.has(load.nodeUuid))
+        .map(load => ({
+            node: nodeIds.get(load.nodeUuid),
+            fx: load.fx, fy: load.fy, fz: load.fz,
+            color: colorString(load.color),
+            load_case: load.loadCase ?? 1
+        }));
+
+    return { nodes, members, plates, supports, member_loads, plate_loads, node_loads };
+}
+
+/**
+ * Remove the model from the scene. Objects are detached, not disposed, so
+ * the edit history can restore them.
+ * @param {Object} sceneData - Scene data
+ */
+function clearViewport(sceneData) {
+    clearSelection();
+    selectedElements.clear();
+    sceneData.nodesGroup.children.forEach(node => restoreNodeConstraint(node, undefined, sceneData));
+    [sceneData.nodesGroup, sceneData.beamsGroup, sceneData.platesGroup]
+        .filter(Boolean)
+        .forEach(group => group.clear());
+    clearAllLoads(sceneData);
+}
+
+/**
+ * Replace the scene with a model from Rust
+ * @param {Object} model - Rust ViewportModel
+ * @param {Object} sceneData - Scene data
+ */
+export function loadViewportModel(model, sceneData) {
+    if (!sceneData || !sceneData.nodesGroup || !sceneData.beamsGroup) {
+        console.error('Scene not initialized');
+        return;
+    }
+    clearViewport(sceneData);
+
+    const nodes = new Map(); // id -> node mesh
+    model.nodes.forEach(n => {
+        const position = new THREE.Vector3(n.x, n.y, n.z);
+        const node = createNode(sceneData.nodesGroup, position, true);
+        if (node) {
+            node.userData.id = n.id;
+            nodes.set(n.id, node);
+        } else {
+            // Coincident nodes are merged into the first
+            nodes.set(n.id, findNodeAtPosition(sceneData.nodesGroup, position));
+        }
+    });
+
+    const beams = new Map(); // id -> beam mesh
+    model.members.forEach(m => {
+        const start = nodes.get(m.i_node);
+        const end = nodes.get(m.j_node);
+        if (!start || !end) {
+            console.warn(`Member ${m.id} refers to a missing node`);
+            return;
+        }
+        const beam = createBeam(sceneData.beamsGroup, start.position, end.position, start, end, true);
+        if (beam) {
+            beam.userData.id = m.id;
+            beam.userData.releases = { ...m.releases };
+            if (m.section) {
+                beam.userData.section = m.section;
+            }
+            beams.set(m.id, beam);
+        }
+    });
+
+    const plates = new Map(); // id -> plate mesh
+    if (sceneData.platesGroup) {
+        model.plates.forEach(p => {
+            const plateNodes = p.node_ids.map(id => nodes.get(id));
+            if (plateNodes.some(node => !node)) {
+                console.warn(`Plate ${p.id} refers to a missing node`);
+                return;
+            }
+            const plate = createPlateMesh(plateNodes, sceneData.platesGroup);
+            if (plate) {
+                plate.userData.id = p.id;
+                if (p.thickness !== null && p.thickness !== undefined) {
+                    plate.userData.thickness = p.thickness;
+                }
+                plates.set(p.id, plate);
+            }
+        });
+    }
+
+    model.supports.forEach(s => {
+        const node = nodes.get(s.node);
+        if (node) {
+            const { node: _, ...constraintData } = s;
+            setNodeConstraint(node, constraintData, sceneData);
+        }
+    });
+
+    model.member_loads.forEach(load => {
+        const beam = beams.get(load.member);
+        if (!beam) return;
+        const loadData = {
+            magnitude: load.magnitude,
+            direction: load.direction,
+            color: load.color || '#ff0000',
+            beamIds: [beam.uuid],
+            loadCase: load.load_case
+        };
+        if (load.type === 'point') {
+            addPointLoad({ ...loadData, position: load.position }, sceneData);
+        } else {
+            addDistributedLoad({ ...loadData, startPos: load.start_pos, endPos: load.end_pos }, sceneData);
+        }
+    });
+
+    model.plate_loads.forEach(load => {
+        const plate = plates.get(load.plate);
+        if (plate) {
+            addPressureLoad({
+                targetType: 'plate',
+                plateIds: [plate.uuid],
+                magnitude: load.magnitude,
+                color: load.color || '#ff0000',
+                loadCase: load.load_case
+            }, sceneData);
+        }
+    });
+
+    model.node_loads.forEach(load => {
+        const node = nodes.get(load.node);
+        if (node) {
+            addNodeLoad(node, { ...load, loadCase: load.load_case }, load.color || '#ff0000', sceneData);
+        }
+    });
+
+    updateNodeLabels(sceneData.nodesGroup);
+    updateBeamLabels(sceneData.beamsGroup);
+    if (sceneData.platesGroup) {
+        updatePlateLabels(sceneData.platesGroup);
+    }
+    if (window.updateAllReleaseIndicators) {
+        window.updateAllReleaseIndicators();
+    }
+}
@@ -1,10 +1,65 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
-use crate::components::layout::{BeamProperties, ShellProperties, MaterialProperties};
+use fea_solver::analysis::{AnalysisOptions, AnalysisType, ArcLengthOptions, SolverBackend};
+use plane_s::fea_client::FEAAnalysisOptions;
+use plane_s::units::Quantity;
+
+use crate::components::layout::{BeamProperties, ShellProperties, MaterialProperties, ResultsToolbar};
+use crate::components::layout::load_combos_modal::analysis_combos;
+use crate::hooks::use_design_state::DesignState;
+use crate::hooks::use_model_store::ModelStore;
 
 #[allow(unused_imports)]
 use crate::types::*;
 
+/// Internal forces with a diagram along the members: force, button, tooltip
+const MEMBER_DIAGRAMS: [(&str, &str, &str); 6] = [
+    ("axial", "N", "Axial force"),
+    ("shear_y", "Vy", "Shear in local y"),
+    ("shear_z", "Vz", "Shear in local z"),
+    ("moment_z", "Mz", "Bending moment about local z"),
+    ("moment_y", "My", "Bending moment about local y"),
+    ("torsion", "T", "Torsion"),
+];
+
+/// Static analyses offered: type, key (as the server's `analysis_type`), label
+const ANALYSIS_TYPES: [(AnalysisType, &str, &str); 4] = [
+    (AnalysisType::Linear, "linear", "Linear Static"),
+    (AnalysisType::PDelta, "pdelta", "P-Delta (2nd Order)"),
+    (AnalysisType::Nonlinear, "nonlinear", "Nonlinear (One-Way Supports)"),
+    (AnalysisType::LargeDisplacement, "large_displacement", "Large Displacement"),
+];
+
+/// Equation solvers offered: key, label, tooltip
+const SOLVER_BACKENDS: [(&str, &str, &str); 3] = [
+    ("auto", "Automatic", "Sparse Cholesky for banded models, dense LU otherwise"),
+    ("sparse_cholesky", "Sparse Cholesky", "Skyline Cholesky; fast for large, well-numbered models"),
+    ("dense_lu", "Dense LU", "Robust, but slow and memory hungry for large models"),
+];
+
+fn analysis_key(analysis_type: AnalysisType) -> &'static str {
+    ANALYSIS_TYPES.iter().find(|(t, _, _)| *t == analysis_type).map_or("linear", |(_, key, _)| key)
+}
+
+fn solver_key(solver: &SolverBackend) -> &'static str {
+    match solver {
+        SolverBackend::SparseCholesky => "sparse_cholesky",
+        SolverBackend::DenseLu => "dense_lu",
+        _ => "auto",
+    }
+}
+
+/// Numbers of discrete bands offered for plate contours
+const CONTOUR_BANDS: [usize; 4] = [6, 8, 10, 12];
+
+/// Set the plate contour options, redrawing the contour if one is shown
+fn set_contour_options(mapping: String, bands: usize) {
+    eval(&format!(
+        "window.plateContourOptions = {{ mapping: '{mapping}', bands: {bands} }}; \
+         if ((window.currentDiagramType || '').startsWith('plate_stress_')) {{ window.refreshCurrentDiagram(); }}"
+    ));
+}
+
 #[component]
 pub fn AnalysisPanel(
     show: Signal<bool>,
@@ -15,28 +70,84 @@ pub fn AnalysisPanel(
     let mut is_analyzing = use_signal(|| false);
     let mut analysis_error = use_signal(|| None::<String>);
     let mut show_results = use_signal(|| false);
-    let mut analysis_type = use_signal(|| "linear".to_string());
+    // Solver options sent with the model. Modes of vibration, none if zero,
+    // are found after the static analysis for the mode shape animation.
+    let mut settings = use_signal(|| AnalysisOptions { num_modes: 0, ..AnalysisOptions::default() });
     let mut plate_formulation = use_signal(|| "kirchhoff".to_string());
+    // "server", or "browser" to solve with fea-solver built into the app
+    let mut solver = use_signal(|| "server".to_string());
     
-    // Results state
+    // Results state, in SI
     let mut max_displacement = use_signal(|| 0.0_f64);
     let mut max_reaction = use_signal(|| 0.0_f64);
+    // Combinations in the results, and the one shown
+    let mut result_combos = use_signal(Vec::<String>::new);
+    let mut result_combo = use_signal(String::new);
+    // Frequency of each mode found (Hz)
+    let mut mode_frequencies = use_signal(Vec::<f64>::new);
     
     // Deformation scale (default 50 for visible deformation)
     let mut deform_scale = use_signal(|| 50.0_f64);
     
     // Label size scale (default 1.0)
     let mut label_scale = use_signal(|| 1.0_f64);
+    
+    // Plate contours: "node" or "element" mapping, and bands (0 for continuous)
+    let mut contour_mapping = use_signal(|| "node".to_string());
+    let mut contour_bands = use_signal(|| 0_usize);
+
+    let model_store = use_context::<ModelStore>();
+    let design_state = use_context::<DesignState>();
+    let load_cases = design_state.load_cases;
+    let load_combos = design_state.load_combos;
+    let units = design_state.units;
 
     let run_fea_analysis = move |_| {
+        if let Some(problem) = model_store.model.read().analysis_problem() {
+            analysis_error.set(Some(problem.to_string()));
+            return;
+        }
         spawn(async move {
             is_analyzing.set(true);
             analysis_error.set(None);
             
             let mat = material_props();
             let beam = beam_props();
-            let analysis = analysis_type();
             let formulation = plate_formulation();
+            let in_browser = solver() == "browser";
+            let mut options = settings();
+            // Tags no combination carries any more are dropped
+            if let Some(tags) = options.combo_tags.as_mut() {
+                tags.retain(|tag| load_combos.read().iter().any(|c| c.tags.contains(tag)));
+            }
+            options.combo_tags = options.combo_tags.filter(|tags| !tags.is_empty());
+            // The solver only takes arc-length settings for large displacement analysis
+            if options.analysis_type != AnalysisType::LargeDisplacement {
+                options.arc_length = None;
+            }
+            let options_js = serde_json::to_string(&FEAAnalysisOptions {
+                analysis_type: analysis_key(options.analysis_type).to_string(),
+                max_iterations: options.max_iterations,
+                load_steps: options.load_steps,
+                arc_length: options.arc_length.is_some(),
+                num_modes: options.num_modes,
+                settings: Some(options),
+            }).unwrap_or_else(|_| "null".to_string());
+            
+            // Load case names for the loads, and the combinations to solve
+            let cases = load_cases();
+            let loading_js = serde_json::json!({
+                "caseNames": cases.iter().map(|c| (c.id.to_string(), c.title.clone())).collect::<std::collections::HashMap<_, _>>(),
+                "combos": analysis_combos(&cases, &load_combos.read()),
+            });
+            
+            // In the browser, the model comes back here to be solved; `null`
+            // tells us it never got that far
+            let solve_js = if in_browser {
+                "async (request) => { solved = true; dioxus.send(request); return await dioxus.recv(); }"
+            } else {
+                "null"
+            };
             
             // Build material config for JavaScript
             let material_js = format!(
@@ -44,36 +155,59 @@ pub fn AnalysisPanel(
                 mat.name, mat.elastic_modulus, mat.poisson_ratio, mat.density
             );
             
-            // Build beam section config
-            let beam_section_js = format!(
-                "{{ section_type: '{}', width: {}, height: {}, flange_thickness: {}, web_thickness: {} }}",
-                beam.section_type, beam.width, beam.height, beam.flange_thickness, beam.web_thickness
-            );
+            // Section properties for the analysis: the default section, and
+            // those assigned to members by name
+            let sections_js = serde_json::json!({
+                "default": beam.section().to_analysis(),
+                "assigned": model_store.model.read().assigned_sections().iter()
+                    .map(|s| (s.name.clone(), s.to_analysis()))
+                    .collect::<std::collections::HashMap<_, _>>(),
+            });
 
-            let result = eval(
+            #[cfg_attr(not(feature = "local-solver"), allow(unused_mut))]
+            let mut js = eval(
                 &format!(r#"
                 const material = {material_js};
-                const beamSection = {beam_section_js};
-                const analysisType = '{analysis}';
+                const sections = {sections_js};
+                const options = {options_js};
+                const loading = {loading_js};
                 
                 // Set plate formulation before analysis
                 window.plateFormulation = '{formulation}';
                 
-                const result = await window.runFEAAnalysis(material, beamSection, analysisType);
+                let solved = false;
+                const result = await window.runFEAAnalysis(material, sections, options, {solve_js}, loading);
+                if ({in_browser} && !solved) {{
+                    dioxus.send(null);
+                }}
                 
                 if (result.success && result.results) {{
                     return {{
                         success: true,
-                        maxDisplacement: result.results.summary.max_displacement * 1000,
-                        maxReaction: result.results.summary.max_reaction / 1000,
+                        maxDisplacement: result.results.summary.max_displacement,
+                        maxReaction: result.results.summary.max_reaction,
                         numNodes: result.results.summary.num_nodes,
-                        numMembers: result.results.summary.num_members
+                        numMembers: result.results.summary.num_members,
+                        combos: result.combos,
+                        modes: (result.results.modes || []).map(m => m.frequency)
                     }};
                 }} else {{
                     return {{ error: result.error || 'Analysis failed' }};
                 }}
                 "#)
-            ).await;
+            );
+            
+            // A request the local solver can't read, or a model it leaves to
+            // the server, is answered with `null` and goes to the server
+            #[cfg(feature = "local-solver")]
+            if in_browser {
+                match js.recv::<Option<plane_s::fea_client::FEAAnalysisRequest>>().await {
+                    Ok(Some(request)) => { let _ = js.send(plane_s::local_solver::analyze(&request)); }
+                    Ok(None) => {}
+                    Err(_) => { let _ = js.send(serde_json::Value::Null); }
+                }
+            }
+            let result = js.await;
             
             match result {
                 Ok(value) => {
@@ -88,6 +222,14 @@ pub fn AnalysisPanel(
                             if let Some(react) = obj.get("maxReaction").and_then(|v| v.as_f64()) {
                                 max_reaction.set(react);
                             }
+                            let combos: Vec<String> = obj.get("combos")
+                                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                                .unwrap_or_default();
+                            result_combo.set(combos.first().cloned().unwrap_or_default());
+                            result_combos.set(combos);
+                            mode_frequencies.set(obj.get("modes")
+                                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                                .unwrap_or_default());
                         }
                     }
                 }
@@ -100,6 +242,13 @@ pub fn AnalysisPanel(
         });
     };
 
+    // Tags of the combinations, to choose which are solved
+    let combo_tags: Vec<String> = load_combos.read().iter()
+        .flat_map(|c| c.tags.iter().cloned())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
     rsx! {
         div {
             class: "right-panel analysis-panel",
@@ -128,10 +277,169 @@ pub fn AnalysisPanel(
                         label { "Analysis Type" }
                         select {
                             class: "analysis-type-select",
-                            value: "{analysis_type}",
-                            onchange: move |evt| analysis_type.set(evt.value()),
-                            option { value: "linear", "Linear Static" }
-                            option { value: "pdelta", "P-Delta (2nd Order)" }
+                            value: "{analysis_key(settings().analysis_type)}",
+                            onchange: move |evt| {
+                                if let Some((chosen, _, _)) = ANALYSIS_TYPES.iter().find(|(_, key, _)| *key == evt.value()) {
+                                    settings.write().analysis_type = *chosen;
+                                }
+                            },
+                            for (option_type, key, name) in ANALYSIS_TYPES {
+                                option { value: "{key}", selected: settings().analysis_type == option_type, "{name}" }
+                            }
+                        }
+                    }
+                    
+                    if settings().analysis_type != AnalysisType::Linear {
+                        div { class: "control-row",
+                            label { "Max Iterations" }
+                            input {
+                                r#type: "number",
+                                class: "analysis-type-select",
+                                min: "1",
+                                value: "{settings().max_iterations}",
+                                title: "Iterations allowed for P-Delta, support and large displacement convergence",
+                                onchange: move |evt| {
+                                    if let Ok(n) = evt.value().parse::<usize>() {
+                                        settings.write().max_iterations = n.max(1);
+                                    }
+                                },
+                            }
+                        }
+                        div { class: "control-row",
+                            label { "Tolerance" }
+                            input {
+                                r#type: "number",
+                                class: "analysis-type-select",
+                                min: "0",
+                                step: "any",
+                                value: "{settings().tolerance}",
+                                title: "Relative change in displacements taken as converged",
+                                onchange: move |evt| {
+                                    if let Ok(tolerance) = evt.value().parse::<f64>() {
+                                        if tolerance > 0.0 {
+                                            settings.write().tolerance = tolerance;
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                    }
+                    
+                    if settings().analysis_type == AnalysisType::LargeDisplacement {
+                        div { class: "control-row",
+                            label { "Load Steps" }
+                            input {
+                                r#type: "number",
+                                class: "analysis-type-select",
+                                min: "1",
+                                value: "{settings().load_steps}",
+                                title: "Equal load increments up to the full load",
+                                onchange: move |evt| {
+                                    if let Ok(n) = evt.value().parse::<usize>() {
+                                        settings.write().load_steps = n.max(1);
+                                    }
+                                },
+                            }
+                        }
+                        div { class: "control-row",
+                            label { "Arc-Length" }
+                            input {
+                                r#type: "checkbox",
+                                checked: settings().arc_length.is_some(),
+                                title: "Follow the load path past limit points, rather than stepping the load",
+                                onchange: move |evt| {
+                                    settings.write().arc_length = evt.checked().then(ArcLengthOptions::default);
+                                },
+                            }
+                        }
+                    }
+                    
+                    div { class: "control-row",
+                        label { "Equation Solver" }
+                        select {
+                            class: "analysis-type-select",
+                            value: "{solver_key(&settings().solver)}",
+                            onchange: move |evt| {
+                                settings.write().solver = match evt.value().as_str() {
+                                    "sparse_cholesky" => SolverBackend::SparseCholesky,
+                                    "dense_lu" => SolverBackend::DenseLu,
+                                    _ => SolverBackend::Auto,
+                                };
+                            },
+                            for (key, name, tip) in SOLVER_BACKENDS {
+                                option {
+                                    value: "{key}",
+                                    title: "{tip}",
+                                    selected: solver_key(&settings().solver) == key,
+                                    "{name}"
+                                }
+                            }
+                        }
+                    }
+                    
+                    div { class: "control-row",
+                        label { "Check Conditioning" }
+                        input {
+                            r#type: "checkbox",
+                            checked: settings().check_conditioning,
+                            title: "Estimate the stiffness condition number and warn of near mechanisms",
+                            onchange: move |evt| settings.write().check_conditioning = evt.checked(),
+                        }
+                    }
+                    
+                    if !combo_tags.is_empty() {
+                        div { class: "control-row",
+                            label { title: "Solve only combinations with one of the ticked tags; all if none is", "Combinations" }
+                            div { class: "analysis-combo-tags",
+                                for tag in combo_tags {
+                                    label {
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: settings().combo_tags.as_ref().is_some_and(|tags| tags.contains(&tag)),
+                                            onchange: {
+                                                let tag = tag.clone();
+                                                move |evt: FormEvent| {
+                                                    let mut options = settings.write();
+                                                    let mut tags = options.combo_tags.take().unwrap_or_default();
+                                                    tags.retain(|t| *t != tag);
+                                                    if evt.checked() {
+                                                        tags.push(tag.clone());
+                                                    }
+                                                    options.combo_tags = (!tags.is_empty()).then_some(tags);
+                                                }
+                                            },
+                                        }
+                                        "{tag}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    
+                    if cfg!(feature = "local-solver") {
+                        div { class: "control-row",
+                            label { "Solver" }
+                            select {
+                                class: "analysis-type-select",
+                                value: "{solver}",
+                                title: "Solve on the FEA server, or in the browser for small and medium models",
+                                onchange: move |evt| solver.set(evt.value()),
+                                option { value: "server", "FEA Server" }
+                                option { value: "browser", "In-Browser" }
+                            }
+                        }
+                    }
+                    
+                    div { class: "control-row",
+                        label { "Modes" }
+                        input {
+                            r#type: "number",
+                            class: "analysis-type-select",
+                            min: "0",
+                            max: "50",
+                            value: "{settings().num_modes}",
+                            title: "Modes of vibration to find after the static analysis, from the material densities",
+                            oninput: move |evt| settings.write().num_modes = evt.value().parse().unwrap_or(0),
                         }
                     }
                     
@@ -174,15 +482,45 @@ pub fn AnalysisPanel(
                             "Analysis Complete"
                         }
                         
+                        // Load combination shown in the summary, diagrams and tables
+                        if result_combos.read().len() > 1 {
+                            div { class: "control-row",
+                                label { "Combination" }
+                                select {
+                                    class: "analysis-type-select",
+                                    value: "{result_combo}",
+                                    onchange: move |evt| {
+                                        let combo = evt.value();
+                                        result_combo.set(combo.clone());
+                                        spawn(async move {
+                                            let Ok(combo_js) = serde_json::to_string(&combo) else { return };
+                                            let summary = eval(&format!("return window.setFEAResultCombo({combo_js});")).await;
+                                            if let Ok(summary) = summary {
+                                                if let Some(disp) = summary.get("max_displacement").and_then(|v| v.as_f64()) {
+                                                    max_displacement.set(disp);
+                                                }
+                                                if let Some(react) = summary.get("max_reaction").and_then(|v| v.as_f64()) {
+                                                    max_reaction.set(react);
+                                                }
+                                            }
+                                        });
+                                    },
+                                    for combo in result_combos.read().iter() {
+                                        option { value: "{combo}", selected: *result_combo.read() == *combo, "{combo}" }
+                                    }
+                                }
+                            }
+                        }
+                        
                         // Results Summary
                         div { class: "results-summary",
                             div { class: "result-item",
                                 span { class: "result-label", "Max Displacement" }
-                                span { class: "result-value", "{max_displacement():.3} mm" }
+                                span { class: "result-value", "{units().from_si(Quantity::Displacement, max_displacement()):.3} {units().symbol(Quantity::Displacement)}" }
                             }
                             div { class: "result-item",
                                 span { class: "result-label", "Max Reaction" }
-                                span { class: "result-value", "{max_reaction():.2} kN" }
+                                span { class: "result-value", "{units().from_si(Quantity::Force, max_reaction()):.2} {units().symbol(Quantity::Force)}" }
                             }
                         }
                         
@@ -206,6 +544,8 @@ pub fn AnalysisPanel(
                             }
                         }
                         
+                        ResultsToolbar { deform_scale, mode_frequencies }
+                        
                         // Label Size Slider
                         div { class: "control-row",
                             label { "Label Size: {label_scale():.1}x" }
@@ -275,6 +615,19 @@ pub fn AnalysisPanel(
                                     "Shear Force"
                                 }
                             }
+                            div { class: "control-group-label", "Member Diagrams" }
+                            div { class: "button-row",
+                                for (force, label, title) in MEMBER_DIAGRAMS {
+                                    button {
+                                        class: "diagram-btn",
+                                        title: "{title}; hover a diagram for values along the member",
+                                        onclick: move |_| {
+                                            eval(&format!("window.showMemberForceDiagram('{force}')"));
+                                        },
+                                        "{label}"
+                                    }
+                                }
+                            }
                             div { class: "control-group-label", "Plate/Shell Stresses" }
                             div { class: "button-row",
                                 button {
@@ -328,6 +681,53 @@ pub fn AnalysisPanel(
                                     "τxy"
                                 }
                             }
+                            div { class: "button-row",
+                                button {
+                                    class: "diagram-btn stress-btn",
+                                    title: "Show twisting moment Mxy",
+                                    onclick: move |_| {
+                                        eval("window.showPlateStress && window.showPlateStress('mxy')");
+                                    },
+                                    "Mxy"
+                                }
+                                button {
+                                    class: "diagram-btn stress-btn",
+                                    title: "Show total deflection of the plate nodes",
+                                    onclick: move |_| {
+                                        eval("window.showPlateStress && window.showPlateStress('deflection')");
+                                    },
+                                    "Deflection"
+                                }
+                            }
+                            div { class: "control-row",
+                                label { "Contour Mapping" }
+                                select {
+                                    class: "analysis-type-select",
+                                    value: "{contour_mapping}",
+                                    title: "Average element values at shared nodes for smooth contours, or color each element by its own value",
+                                    onchange: move |evt| {
+                                        contour_mapping.set(evt.value());
+                                        set_contour_options(contour_mapping(), contour_bands());
+                                    },
+                                    option { value: "node", "Nodal (Averaged)" }
+                                    option { value: "element", "Per Element" }
+                                }
+                            }
+                            div { class: "control-row",
+                                label { "Contour Bands" }
+                                select {
+                                    class: "analysis-type-select",
+                                    value: "{contour_bands}",
+                                    onchange: move |evt| {
+                                        contour_bands.set(evt.value().parse().unwrap_or(0));
+                                        set_contour_options(contour_mapping(), contour_bands());
+                                    },
+                                    option { value: "0", "Continuous" }
+                                    for bands in CONTOUR_BANDS {
+                                        option { value: "{bands}", "{bands} Bands" }
+                                    }
+                                }
+                            }
                             div { class: "button-row",
                                 button {
                                     class: "diagram-btn",
@@ -1,4 +1,11 @@
 use dioxus::prelude::*;
+use dioxus::document::eval;
+use fea_solver::elements::SectionShape;
+
+use plane_s::sections::{CatalogFamily, MemberSection, CATALOG};
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
 
 /// Full 6-DOF member end releases configuration
 #[derive(Clone, PartialEq, Debug, Default)]
@@ -77,7 +84,10 @@ pub struct SelectedBeamInfo {
 
 #[derive(Clone, PartialEq)]
 pub struct BeamProperties {
+    /// "Rectangular", "Circular", "IBeam", or "Catalog" for a catalog shape
     pub section_type: String,
+    /// Designation of the catalog shape
+    pub catalog: String,
     pub width: f64,
     pub height: f64,
     pub flange_thickness: f64,
@@ -90,6 +100,7 @@ impl Default for BeamProperties {
     fn default() -> Self {
         Self {
             section_type: "Rectangular".to_string(),
+            catalog: CATALOG[0].name.to_string(),
             width: 0.1,
             height: 0.2,
             flange_thickness: 0.015,
@@ -100,6 +111,23 @@ impl Default for BeamProperties {
     }
 }
 
+impl BeamProperties {
+    /// The section described, as analysed and assigned to members
+    pub fn section(&self) -> MemberSection {
+        match self.section_type.as_str() {
+            "Catalog" => MemberSection::catalog(&self.catalog).unwrap_or_default(),
+            "Circular" => MemberSection::from_shape(SectionShape::Circle { diameter: self.width }),
+            "IBeam" => MemberSection::from_shape(SectionShape::WideFlange {
+                depth: self.height,
+                flange_width: self.width,
+                flange_thickness: self.flange_thickness,
+                web_thickness: self.web_thickness,
+            }),
+            _ => MemberSection::from_shape(SectionShape::Rectangle { width: self.width, depth: self.height }),
+        }
+    }
+}
+
 #[component]
 pub fn BeamPropertiesPanel(
     show: Signal<bool>,
@@ -112,13 +140,6 @@ pub fn BeamPropertiesPanel(
     let mut selected_end_node = use_signal(|| String::new());
     let mut has_selection = use_signal(|| false);
     
-    // Section properties
-    let mut section_type = use_signal(|| properties().section_type.clone());
-    let mut width = use_signal(|| properties().width);
-    let mut height = use_signal(|| properties().height);
-    let mut flange_thickness = use_signal(|| properties().flange_thickness);
-    let mut web_thickness = use_signal(|| properties().web_thickness);
-    
     // Full 6-DOF releases for i-node (start)
     let mut i_fx = use_signal(|| properties().releases.i_fx);
     let mut i_fy = use_signal(|| properties().releases.i_fy);
@@ -157,10 +178,12 @@ pub fn BeamPropertiesPanel(
             let mut selected_start_node_clone = selected_start_node.clone();
             let mut selected_end_node_clone = selected_end_node.clone();
             let mut has_selection_clone = has_selection.clone();
-            let mut i_my_clone = i_my.clone();
-            let mut i_mz_clone = i_mz.clone();
-            let mut j_my_clone = j_my.clone();
-            let mut j_mz_clone = j_mz.clone();
+            let release_signals = [
+                ("i_node_dx", i_fx), ("i_node_dy", i_fy), ("i_node_dz", i_fz),
+                ("i_node_rx", i_mx), ("i_node_ry", i_my), ("i_node_rz", i_mz),
+                ("j_node_dx", j_fx), ("j_node_dy", j_fy), ("j_node_dz", j_fz),
+                ("j_node_rx", j_mx), ("j_node_ry", j_my), ("j_node_rz", j_mz),
+            ];
             let mut releases_modified_clone = releases_modified.clone();
             
             let beam_selected_handler = Closure::wrap(Box::new(move |event: web_sys::CustomEvent| {
@@ -190,17 +213,10 @@ pub fn BeamPropertiesPanel(
                 }
                 // Load releases from selected beam
                 if let Ok(releases) = js_sys::Reflect::get(&event.detail(), &JsValue::from_str("releases")) {
-                    if let Ok(i_ry) = js_sys::Reflect::get(&releases, &JsValue::from_str("i_node_ry")) {
-                        i_my_clone.set(i_ry.as_bool().unwrap_or(false));
-                    }
-                    if let Ok(i_rz) = js_sys::Reflect::get(&releases, &JsValue::from_str("i_node_rz")) {
-                        i_mz_clone.set(i_rz.as_bool().unwrap_or(false));
-                    }
-                    if let Ok(j_ry) = js_sys::Reflect::get(&releases, &JsValue::from_str("j_node_ry")) {
-                        j_my_clone.set(j_ry.as_bool().unwrap_or(false));
-                    }
-                    if let Ok(j_rz) = js_sys::Reflect::get(&releases, &JsValue::from_str("j_node_rz")) {
-                        j_mz_clone.set(j_rz.as_bool().unwrap_or(false));
+                    for (key, mut signal) in release_signals {
+                        if let Ok(value) = js_sys::Reflect::get(&releases, &JsValue::from_str(key)) {
+                            signal.set(value.as_bool().unwrap_or(false));
+                        }
                     }
                 }
                 has_selection_clone.set(true);
@@ -247,8 +263,16 @@ pub fn BeamPropertiesPanel(
                 if let Ok(f) = func {
                     if f.is_function() {
                         let js_releases = serde_wasm_bindgen::to_value(&serde_json::json!({
+                            "i_node_dx": i_fx(),
+                            "i_node_dy": i_fy(),
+                            "i_node_dz": i_fz(),
+                            "i_node_rx": i_mx(),
                             "i_node_ry": i_my(),
                             "i_node_rz": i_mz(),
+                            "j_node_dx": j_fx(),
+                            "j_node_dy": j_fy(),
+                            "j_node_dz": j_fz(),
+                            "j_node_rx": j_mx(),
                             "j_node_ry": j_my(),
                             "j_node_rz": j_mz(),
                         })).unwrap_or(JsValue::NULL);
@@ -273,8 +297,16 @@ pub fn BeamPropertiesPanel(
                 if let Ok(f) = func {
                     if f.is_function() {
                         let js_releases = serde_wasm_bindgen::to_value(&serde_json::json!({
+                            "i_node_dx": i_fx(),
+                            "i_node_dy": i_fy(),
+                            "i_node_dz": i_fz(),
+                            "i_node_rx": i_mx(),
                             "i_node_ry": i_my(),
                             "i_node_rz": i_mz(),
+                            "j_node_dx": j_fx(),
+                            "j_node_dy": j_fy(),
+                            "j_node_dz": j_fz(),
+                            "j_node_rx": j_mx(),
                             "j_node_ry": j_my(),
                             "j_node_rz": j_mz(),
                         })).unwrap_or(JsValue::NULL);
@@ -289,8 +321,26 @@ pub fn BeamPropertiesPanel(
         releases_modified.set(false);
     };
 
-    let is_ibeam = section_type() == "IBeam";
-    let is_circular = section_type() == "Circular";
+    // Give the selected members the section described here
+    let assign_section = move |_| {
+        let section = serde_json::to_string(&properties.peek().section()).unwrap_or_default();
+        eval(&format!("if (window.setSelectedBeamSection) {{ window.setSelectedBeamSection({section}); }}"));
+    };
+
+    let units = use_context::<DesignState>().units;
+    let length_unit = units().symbol(Quantity::Length);
+    let shown = move |metres: f64| units().from_si(Quantity::Length, metres);
+    let entered = move |value: f64| units().to_si(Quantity::Length, value);
+    
+    let section_type = properties().section_type.clone();
+    let is_ibeam = section_type == "IBeam";
+    let is_circular = section_type == "Circular";
+    let is_catalog = section_type == "Catalog";
+    let width = shown(properties().width);
+    let height = shown(properties().height);
+    let flange_thickness = shown(properties().flange_thickness);
+    let web_thickness = shown(properties().web_thickness);
+    let beam_length = shown(selected_beam_length());
     
     // Generate fixity codes
     let i_code = format!("{}{}{}{}{}{}",
@@ -339,8 +389,8 @@ pub fn BeamPropertiesPanel(
                                 span { class: "info-value", "{selected_beam_name}" }
                             }
                             div { class: "info-row",
-                                span { class: "info-label", "Length (m)" }
-                                span { class: "info-value", "{selected_beam_length:.3}" }
+                                span { class: "info-label", "Length ({length_unit})" }
+                                span { class: "info-value", "{beam_length:.3}" }
                             }
                             div { class: "info-row",
                                 span { class: "info-label", "Node A" }
@@ -499,26 +549,19 @@ pub fn BeamPropertiesPanel(
                     h4 { "▼ Section Type" }
                     div { class: "property-group",
                         div { class: "section-type-buttons",
-                            button {
-                                class: if section_type() == "Rectangular" { "section-btn active" } else { "section-btn" },
-                                onclick: move |_| section_type.set("Rectangular".to_string()),
-                                title: "Rectangular Section",
-                                div { class: "section-icon rect-icon" }
-                                span { "Rect" }
-                            }
-                            button {
-                                class: if section_type() == "Circular" { "section-btn active" } else { "section-btn" },
-                                onclick: move |_| section_type.set("Circular".to_string()),
-                                title: "Circular Section",
-                                div { class: "section-icon circ-icon" }
-                                span { "Circ" }
-                            }
-                            button {
-                                class: if section_type() == "IBeam" { "section-btn active" } else { "section-btn" },
-                                onclick: move |_| section_type.set("IBeam".to_string()),
-                                title: "I-Beam / Box Section",
-                                div { class: "section-icon ibeam-icon" }
-                                span { "I-Beam" }
+                            for (kind, label, title, icon) in [
+                                ("Rectangular", "Rect", "Rectangular Section", "rect-icon"),
+                                ("Circular", "Circ", "Circular Section", "circ-icon"),
+                                ("IBeam", "I-Beam", "I-Beam Section", "ibeam-icon"),
+                                ("Catalog", "Catalog", "W-Shape or HSS from the catalog", "ibeam-icon"),
+                            ] {
+                                button {
+                                    class: if section_type == kind { "section-btn active" } else { "section-btn" },
+                                    onclick: move |_| properties.write().section_type = kind.to_string(),
+                                    title: "{title}",
+                                    div { class: "section-icon {icon}" }
+                                    span { "{label}" }
+                                }
                             }
                         }
                     }
@@ -526,18 +569,39 @@ pub fn BeamPropertiesPanel(
                 
                 // Dimensions
                 div { class: "section",
-                    h4 { "▼ Dimensions" }
+                    h4 { if is_catalog { "▼ Shape" } else { "▼ Dimensions" } }
                     
-                    if is_circular {
+                    if is_catalog {
                         div { class: "property-group",
-                            label { "Diameter (m)" }
+                            label { "Designation" }
+                            select {
+                                class: "analysis-type-select",
+                                value: "{properties().catalog}",
+                                onchange: move |evt| properties.write().catalog = evt.value(),
+                                for (family, group) in [(CatalogFamily::W, "W-Shapes"), (CatalogFamily::Hss, "HSS")] {
+                                    optgroup { label: "{group}",
+                                        for shape in CATALOG.iter().filter(|c| c.family == family) {
+                                            option {
+                                                value: "{shape.name}",
+                                                selected: properties().catalog == shape.name,
+                                                "{shape.name}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    } else if is_circular {
+                        div { class: "property-group",
+                            label { "Diameter ({length_unit})" }
                             input {
                                 r#type: "number",
                                 value: "{width}",
                                 oninput: move |evt| {
                                     if let Ok(val) = evt.value().parse::<f64>() {
-                                        width.set(val);
-                                        height.set(val);
+                                        let mut props = properties.write();
+                                        props.width = entered(val);
+                                        props.height = entered(val);
                                     }
                                 },
                                 step: "0.01",
@@ -546,13 +610,13 @@ pub fn BeamPropertiesPanel(
                         }
                     } else {
                         div { class: "property-group",
-                            label { if is_ibeam { "Flange Width (m)" } else { "Width (m)" } }
+                            label { if is_ibeam { "Flange Width ({length_unit})" } else { "Width ({length_unit})" } }
                             input {
                                 r#type: "number",
                                 value: "{width}",
                                 oninput: move |evt| {
                                     if let Ok(val) = evt.value().parse::<f64>() {
-                                        width.set(val);
+                                        properties.write().width = entered(val);
                                     }
                                 },
                                 step: "0.01",
@@ -560,13 +624,13 @@ pub fn BeamPropertiesPanel(
                             }
                         }
                         div { class: "property-group",
-                            label { if is_ibeam { "Total Depth (m)" } else { "Height (m)" } }
+                            label { if is_ibeam { "Total Depth ({length_unit})" } else { "Height ({length_unit})" } }
                             input {
                                 r#type: "number",
                                 value: "{height}",
                                 oninput: move |evt| {
                                     if let Ok(val) = evt.value().parse::<f64>() {
-                                        height.set(val);
+                                        properties.write().height = entered(val);
                                     }
                                 },
                                 step: "0.01",
@@ -577,13 +641,13 @@ pub fn BeamPropertiesPanel(
                     
                     if is_ibeam {
                         div { class: "property-group",
-                            label { "Flange Thickness (m)" }
+                            label { "Flange Thickness ({length_unit})" }
                             input {
                                 r#type: "number",
                                 value: "{flange_thickness}",
                                 oninput: move |evt| {
                                     if let Ok(val) = evt.value().parse::<f64>() {
-                                        flange_thickness.set(val);
+                                        properties.write().flange_thickness = entered(val);
                                     }
                                 },
                                 step: "0.001",
@@ -591,13 +655,13 @@ pub fn BeamPropertiesPanel(
                             }
                         }
                         div { class: "property-group",
-                            label { "Web Thickness (m)" }
+                            label { "Web Thickness ({length_unit})" }
                             input {
                                 r#type: "number",
                                 value: "{web_thickness}",
                                 oninput: move |evt| {
                                     if let Ok(val) = evt.value().parse::<f64>() {
-                                        web_thickness.set(val);
+                                        properties.write().web_thickness = entered(val);
                                     }
                                 },
                                 step: "0.001",
@@ -607,50 +671,51 @@ pub fn BeamPropertiesPanel(
                     }
                 }
                 
-                // Section Properties
+                // Section Properties, as the analysis is sent them
                 div { class: "section",
                     h4 { "▼ Section Properties" }
                     div { class: "calculated-props",
                         {
-                            let (area, iy, iz) = if is_circular {
-                                let r = width() / 2.0;
-                                let a = std::f64::consts::PI * r * r;
-                                let i = std::f64::consts::PI * r.powi(4) / 4.0;
-                                (a, i, i)
-                            } else if is_ibeam {
-                                let b = width();
-                                let h = height();
-                                let tf = flange_thickness();
-                                let tw = web_thickness();
-                                let a = 2.0 * b * tf + (h - 2.0 * tf) * tw;
-                                let iy = b * h.powi(3) / 12.0 - (b - tw) * (h - 2.0 * tf).powi(3) / 12.0;
-                                let iz = 2.0 * tf * b.powi(3) / 12.0 + (h - 2.0 * tf) * tw.powi(3) / 12.0;
-                                (a, iy, iz)
-                            } else {
-                                let b = width();
-                                let h = height();
-                                let a = b * h;
-                                let iy = b * h.powi(3) / 12.0;
-                                let iz = h * b.powi(3) / 12.0;
-                                (a, iy, iz)
-                            };
+                            let section = properties().section();
+                            let props = section.to_analysis();
+                            let area = units().from_si(Quantity::Area, props.a);
+                            let [iy, iz, j] = [props.iy, props.iz, props.j].map(|i| units().from_si(Quantity::SecondMoment, i));
+                            let (area_unit, inertia_unit) = (units().symbol(Quantity::Area), units().symbol(Quantity::SecondMoment));
                             
                             rsx! {
+                                div { class: "prop-row",
+                                    span { class: "prop-label", "Section:" }
+                                    span { class: "prop-value", "{section.name}" }
+                                }
                                 div { class: "prop-row",
                                     span { class: "prop-label", "Area:" }
-                                    span { class: "prop-value", "{area:.6} m²" }
+                                    span { class: "prop-value", "{area:.6} {area_unit}" }
                                 }
                                 div { class: "prop-row",
                                     span { class: "prop-label", "Iy:" }
-                                    span { class: "prop-value", "{iy:.8} m⁴" }
+                                    span { class: "prop-value", "{iy:.8} {inertia_unit}" }
                                 }
                                 div { class: "prop-row",
                                     span { class: "prop-label", "Iz:" }
-                                    span { class: "prop-value", "{iz:.8} m⁴" }
+                                    span { class: "prop-value", "{iz:.8} {inertia_unit}" }
+                                }
+                                div { class: "prop-row",
+                                    span { class: "prop-label", "J:" }
+                                    span { class: "prop-value", "{j:.8} {inertia_unit}" }
                                 }
                             }
                         }
                     }
+                    if has_selection() {
+                        div { class: "release-apply",
+                            button {
+                                class: "apply-btn",
+                                onclick: assign_section,
+                                title: "Give the selected members this section; others take the default",
+                                "✓ Assign to Selected"
+                            }
+                        }
+                    }
                 }
             }
         }
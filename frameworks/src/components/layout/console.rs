@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
+use plane_s::result_tables::ResultTables;
+use plane_s::units::Units;
+
+use crate::hooks::use_design_state::DesignState;
 
 #[derive(Clone, PartialEq)]
 pub enum ConsoleTab {
@@ -12,6 +16,28 @@ pub enum ConsoleTab {
 pub fn Console() -> Element {
     let mut active_tab = use_signal(|| ConsoleTab::SolverLog);
     let mut show_table_panel = use_signal(|| false);
+    let mut units = use_context::<DesignState>().units;
+    let mut tables = use_signal(ResultTables::default);
+    
+    // Tell the scene how to label values whenever the preference changes
+    use_effect(move || {
+        eval(&format!("window.displayUnits = {};", units().to_js()));
+    });
+    
+    // Rows of the combination shown, from the scene
+    use_hook(move || {
+        spawn(async move {
+            let mut shown = eval(r#"
+                window.addEventListener('resultTablesChanged', (e) => dioxus.send(e.detail));
+            "#);
+            while let Ok(value) = shown.recv::<serde_json::Value>().await {
+                match serde_json::from_value::<ResultTables>(value) {
+                    Ok(rows) => tables.set(rows),
+                    Err(e) => web_sys::console::error_1(&format!("Unreadable result tables: {e}").into()),
+                }
+            }
+        });
+    });
     
     // Initialize console output handler on mount
     use_effect(move || {
@@ -55,6 +81,38 @@ pub fn Console() -> Element {
                     }
                 };
                 
+                // Follow a calculix-service job, logging its progress events live
+                window.followSolverProgress = function(serviceUrl, jobId, onFinished) {
+                    const source = new EventSource(serviceUrl + '/api/v1/jobs/' + jobId + '/events');
+                    source.onmessage = function(e) {
+                        const event = JSON.parse(e.data);
+                        switch (event.type) {
+                            case 'phase':
+                                window.addSolverLog('Job ' + event.phase, 'info');
+                                break;
+                            case 'log':
+                                if (event.line.trim()) window.addSolverLog(event.line, 'info');
+                                break;
+                            case 'finished':
+                                source.close();
+                                if (event.status === 'Success') {
+                                    window.addSolverLog('Solver finished', 'success');
+                                } else {
+                                    window.addSolverLog('Solver failed: ' + (event.error_message || 'unknown error'), 'error');
+                                }
+                                if (onFinished) onFinished(event);
+                                break;
+                            // step/increment/iteration events repeat lines already logged
+                        }
+                    };
+                    source.onerror = function() {
+                        if (source.readyState === EventSource.CLOSED) {
+                            window.addSolverLog('Lost connection to the solver progress stream', 'warning');
+                        }
+                    };
+                    return source;
+                };
+
                 // Function to update results summary
                 window.updateResultsSummary = function(results) {
                     window.analysisResultsSummary = results;
@@ -65,9 +123,9 @@ pub fn Console() -> Element {
                     let html = '<div class="results-section">';
                     html += '<h4>Analysis Summary</h4>';
                     html += '<div class="result-item"><span class="result-label">Max Displacement:</span><span class="result-value">' + 
-                            (results.max_displacement * 1000).toFixed(4) + ' mm</span></div>';
+                            window.formatQuantity('displacement', results.max_displacement, 4) + '</span></div>';
                     html += '<div class="result-item"><span class="result-label">Max Stress:</span><span class="result-value">' + 
-                            (results.max_stress / 1e6).toFixed(2) + ' MPa</span></div>';
+                            window.formatQuantity('stress', results.max_stress) + '</span></div>';
                     
                     if (results.displacements && results.displacements.length > 0) {
                         html += '<div class="result-item"><span class="result-label">Nodes Analyzed:</span><span class="result-value">' + 
@@ -83,97 +141,48 @@ pub fn Console() -> Element {
                     container.innerHTML = html;
                 };
                 
-                // Update tables panel - supports both legacy format and FEA server format
+                // Update tables panel - supports both legacy format and FEA server format.
+                // Rows go to the tables in SI; they are formatted in the display units there
                 window.updateTablesPanel = function(results) {
                     if (!results) return;
                     
                     // Also check for FEA results in window.feaResults
                     const feaResults = window.feaResults;
+                    // Handle both formats: node (string) or node_id (number)
+                    const nodeName = function(r) {
+                        return String(r.node !== undefined ? r.node : (r.node_id !== undefined ? (r.node_id + 1) : '?'));
+                    };
                     
-                    // Update displacements table
-                    const dispTable = document.querySelector('.displacements-table tbody');
-                    if (dispTable) {
-                        let html = '';
-                        // Try FEA format first (node_displacements), then legacy format (displacements)
-                        const disps = feaResults?.node_displacements || results?.displacements || [];
-                        disps.forEach(function(d) {
-                            html += '<tr>';
-                            // Handle both formats: node (string) or node_id (number)
-                            const nodeName = d.node !== undefined ? d.node : (d.node_id !== undefined ? (d.node_id + 1) : '?');
-                            html += '<td>' + nodeName + '</td>';
-                            html += '<td>' + (d.dx * 1000).toFixed(4) + '</td>';
-                            html += '<td>' + (d.dy * 1000).toFixed(4) + '</td>';
-                            html += '<td>' + (d.dz * 1000).toFixed(4) + '</td>';
-                            const mag = Math.sqrt(d.dx*d.dx + d.dy*d.dy + d.dz*d.dz) * 1000;
-                            html += '<td>' + mag.toFixed(4) + '</td>';
-                            html += '</tr>';
-                        });
-                        dispTable.innerHTML = html;
-                    }
-                    
-                    // Update reactions table
-                    const reactTable = document.querySelector('.reactions-table tbody');
-                    if (reactTable) {
-                        let html = '';
-                        // Try FEA format first, then legacy format
-                        const reactions = feaResults?.reactions || results?.reactions || [];
-                        reactions.forEach(function(r) {
-                            html += '<tr>';
-                            // Handle both formats: node (string) or node_id (number)
-                            const nodeName = r.node !== undefined ? r.node : (r.node_id !== undefined ? (r.node_id + 1) : '?');
-                            html += '<td>' + nodeName + '</td>';
-                            html += '<td>' + (r.fx/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (r.fy/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (r.fz/1000).toFixed(2) + '</td>';
-                            html += '<td>' + ((r.mx || 0)/1000).toFixed(2) + '</td>';
-                            html += '<td>' + ((r.my || 0)/1000).toFixed(2) + '</td>';
-                            html += '<td>' + ((r.mz || 0)/1000).toFixed(2) + '</td>';
-                            html += '</tr>';
-                        });
-                        reactTable.innerHTML = html;
-                    }
-                    
-                    // Update beam forces table
-                    const beamTable = document.querySelector('.beam-forces-table tbody');
-                    if (beamTable) {
-                        let html = '';
-                        // Try FEA format first (member_forces), then legacy format (beam_forces)
-                        const forces = feaResults?.member_forces || results?.beam_forces || [];
-                        forces.forEach(function(bf) {
-                            html += '<tr>';
-                            // Handle both formats: member (string) or element_id (number)
-                            const elemName = bf.member !== undefined ? bf.member : (bf.element_id !== undefined ? (bf.element_id + 1) : '?');
-                            html += '<td>' + elemName + '</td>';
-                            // Handle both formats for force data
-                            const axial = bf.axial_i !== undefined ? bf.axial_i : (bf.axial_force || 0);
-                            const vy = bf.shear_y_i !== undefined ? bf.shear_y_i : (bf.shear_y || 0);
-                            const vz = bf.shear_z_i !== undefined ? bf.shear_z_i : (bf.shear_z || 0);
-                            const my = bf.moment_y_i !== undefined ? bf.moment_y_i : (bf.moment_y || 0);
-                            const mz = bf.moment_z_i !== undefined ? bf.moment_z_i : (bf.moment_z || 0);
-                            html += '<td>' + (axial/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (vy/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (vz/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (my/1000).toFixed(2) + '</td>';
-                            html += '<td>' + (mz/1000).toFixed(2) + '</td>';
-                            html += '</tr>';
-                        });
-                        beamTable.innerHTML = html;
-                    }
+                    // Try FEA format first (node_displacements), then legacy format (displacements)
+                    const displacements = (feaResults?.node_displacements || results?.displacements || []).map(function(d) {
+                        return { node: nodeName(d), dx: d.dx, dy: d.dy, dz: d.dz };
+                    });
+                    const reactions = (feaResults?.reactions || results?.reactions || []).map(function(r) {
+                        return { node: nodeName(r), fx: r.fx, fy: r.fy, fz: r.fz, mx: r.mx || 0, my: r.my || 0, mz: r.mz || 0 };
+                    });
+                    // Try FEA format first (member_forces), then legacy format (beam_forces)
+                    const memberForces = (feaResults?.member_forces || results?.beam_forces || []).map(function(bf) {
+                        return {
+                            member: String(bf.member !== undefined ? bf.member : (bf.element_id !== undefined ? (bf.element_id + 1) : '?')),
+                            axial: bf.axial_i !== undefined ? bf.axial_i : (bf.axial_force || 0),
+                            vy: bf.shear_y_i !== undefined ? bf.shear_y_i : (bf.shear_y || 0),
+                            vz: bf.shear_z_i !== undefined ? bf.shear_z_i : (bf.shear_z || 0),
+                            my: bf.moment_y_i !== undefined ? bf.moment_y_i : (bf.moment_y || 0),
+                            mz: bf.moment_z_i !== undefined ? bf.moment_z_i : (bf.moment_z || 0)
+                        };
+                    });
+                    const stresses = (results.stresses || []).map(function(s) {
+                        return {
+                            node: String(s.node_id + 1), // Display 1-based
+                            von_mises: s.von_mises,
+                            top: s.von_mises_top || s.von_mises,
+                            bottom: s.von_mises_bottom || s.von_mises
+                        };
+                    });
                     
-                    // Update stresses table
-                    const stressTable = document.querySelector('.stresses-table tbody');
-                    if (stressTable && results.stresses) {
-                        let html = '';
-                        results.stresses.forEach(function(s) {
-                            html += '<tr>';
-                            html += '<td>' + (s.node_id + 1) + '</td>'; // Display 1-based
-                            html += '<td>' + (s.von_mises/1e6).toFixed(2) + '</td>';
-                            html += '<td>' + ((s.von_mises_top || s.von_mises)/1e6).toFixed(2) + '</td>';
-                            html += '<td>' + ((s.von_mises_bottom || s.von_mises)/1e6).toFixed(2) + '</td>';
-                            html += '</tr>';
-                        });
-                        stressTable.innerHTML = html;
-                    }
+                    window.dispatchEvent(new CustomEvent('resultTablesChanged', {
+                        detail: { combo: window.feaResultCombo || null, displacements, reactions, member_forces: memberForces, stresses }
+                    }));
                 };
                 
                 // Hook into analysis results update
@@ -182,8 +191,8 @@ pub fn Console() -> Element {
                     if (originalUpdateResults) originalUpdateResults(results);
                     
                     window.addSolverLog('Analysis completed successfully', 'success');
-                    window.addSolverLog('Max displacement: ' + (results.max_displacement * 1000).toFixed(4) + ' mm', 'info');
-                    window.addSolverLog('Max stress: ' + (results.max_stress / 1e6).toFixed(2) + ' MPa', 'info');
+                    window.addSolverLog('Max displacement: ' + window.formatQuantity('displacement', results.max_displacement, 4), 'info');
+                    window.addSolverLog('Max stress: ' + window.formatQuantity('stress', results.max_stress), 'info');
                     
                     if (results.displacements) {
                         window.addSolverLog('Nodes with displacements: ' + results.displacements.length, 'info');
@@ -326,6 +335,23 @@ pub fn Console() -> Element {
                         }
                     }
                     div { class: "console-actions",
+                        select {
+                            class: "console-units-select",
+                            title: "Units values are entered and shown in",
+                            value: "{units().key()}",
+                            onchange: move |evt| {
+                                if let Some(chosen) = Units::from_key(&evt.value()) {
+                                    units.set(chosen);
+                                }
+                            },
+                            for option_units in Units::ALL {
+                                option {
+                                    value: "{option_units.key()}",
+                                    selected: units() == option_units,
+                                    "{option_units.name()}"
+                                }
+                            }
+                        }
                         button {
                             class: if show_table_panel() { "console-action-btn active" } else { "console-action-btn" },
                             title: "Toggle Tables Panel",
@@ -387,6 +413,28 @@ pub fn Console() -> Element {
                         div { class: "tables-panel",
                             div { class: "tables-header",
                                 span { "Tabulated Results" }
+                                button {
+                                    class: "tables-export-btn",
+                                    title: "Export displacements, reactions and member forces to CSV",
+                                    disabled: tables.read().displacements.is_empty(),
+                                    onclick: move |_| {
+                                        let shown = tables.read();
+                                        let csv = shown.to_csv(units());
+                                        let (Ok(csv_js), Ok(name_js)) = (serde_json::to_string(&csv), serde_json::to_string(&shown.csv_file_name())) else { return };
+                                        spawn(async move {
+                                            let _ = eval(&format!(r#"
+                                                const url = URL.createObjectURL(new Blob([{csv_js}], {{ type: 'text/csv' }}));
+                                                const link = document.createElement('a');
+                                                link.href = url;
+                                                link.download = {name_js};
+                                                link.click();
+                                                URL.revokeObjectURL(url);
+                                                window.addSolverLog('Exported results to ' + {name_js}, 'success');
+                                            "#)).await;
+                                        });
+                                    },
+                                    "Export"
+                                }
                                 button {
                                     class: "tables-close-btn",
                                     onclick: move |_| show_table_panel.set(false),
@@ -394,73 +442,27 @@ pub fn Console() -> Element {
                                 }
                             }
                             div { class: "tables-content",
-                                // Displacements Table
-                                div { class: "table-section",
-                                    h4 { "Displacements (mm)" }
-                                    table { class: "data-table displacements-table",
-                                        thead {
-                                            tr {
-                                                th { "Node" }
-                                                th { "DX" }
-                                                th { "DY" }
-                                                th { "DZ" }
-                                                th { "Mag" }
+                                for table in tables.read().tables(units()) {
+                                    div { class: "table-section",
+                                        h4 { "{table.title}" }
+                                        table { class: "data-table",
+                                            thead {
+                                                tr {
+                                                    for column in table.columns {
+                                                        th { "{column}" }
+                                                    }
+                                                }
                                             }
-                                        }
-                                        tbody {}
-                                    }
-                                }
-                                
-                                // Reactions Table
-                                div { class: "table-section",
-                                    h4 { "Reactions (kN, kN·m)" }
-                                    table { class: "data-table reactions-table",
-                                        thead {
-                                            tr {
-                                                th { "Node" }
-                                                th { "Fx" }
-                                                th { "Fy" }
-                                                th { "Fz" }
-                                                th { "Mx" }
-                                                th { "My" }
-                                                th { "Mz" }
-                                            }
-                                        }
-                                        tbody {}
-                                    }
-                                }
-                                
-                                // Beam Forces Table
-                                div { class: "table-section",
-                                    h4 { "Beam Forces (kN, kN·m)" }
-                                    table { class: "data-table beam-forces-table",
-                                        thead {
-                                            tr {
-                                                th { "Elem" }
-                                                th { "Axial" }
-                                                th { "Vy" }
-                                                th { "Vz" }
-                                                th { "My" }
-                                                th { "Mz" }
-                                            }
-                                        }
-                                        tbody {}
-                                    }
-                                }
-                                
-                                // Stresses Table
-                                div { class: "table-section",
-                                    h4 { "Stresses (MPa)" }
-                                    table { class: "data-table stresses-table",
-                                        thead {
-                                            tr {
-                                                th { "Node" }
-                                                th { "VM" }
-                                                th { "Top" }
-                                                th { "Bot" }
+                                            tbody {
+                                                for row in table.rows {
+                                                    tr {
+                                                        for cell in row {
+                                                            td { "{cell}" }
+                                                        }
+                                                    }
+                                                }
                                             }
                                         }
-                                        tbody {}
                                     }
                                 }
                             }
@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+
+use crate::hooks::use_design_state::DesignState;
 
 #[component]
 pub fn ConstraintsPanel(show_constraints: Signal<bool>) -> Element {
@@ -15,6 +18,8 @@ pub fn ConstraintsPanel(show_constraints: Signal<bool>) -> Element {
     let mut spring_kx = use_signal(|| "0".to_string());
     let mut spring_ky = use_signal(|| "0".to_string());
     let mut spring_kz = use_signal(|| "0".to_string());
+    let units = use_context::<DesignState>().units;
+    let stiffness_label = units().label("Spring Stiffnesses", Quantity::Stiffness);
 
     rsx! {
         div {
@@ -211,7 +216,7 @@ pub fn ConstraintsPanel(show_constraints: Signal<bool>) -> Element {
                     class: "form-group",
                     label {
                         class: "form-label",
-                        "Spring Stiffnesses (kN/m)"
+                        "{stiffness_label}"
                     }
                     div {
                         class: "spring-inputs",
@@ -268,10 +273,14 @@ pub fn ConstraintsPanel(show_constraints: Signal<bool>) -> Element {
                 button {
                     class: "btn-primary",
                     onclick: move |_| {
-                        // Parse spring values, default to 0 if empty or invalid
-                        let kx_val = spring_kx().parse::<f64>().unwrap_or(0.0);
-                        let ky_val = spring_ky().parse::<f64>().unwrap_or(0.0);
-                        let kz_val = spring_kz().parse::<f64>().unwrap_or(0.0);
+                        // Parse spring values, default to 0 if empty or invalid;
+                        // entered in the display units, stored in the scene's
+                        let stiffness = |text: String| {
+                            units().convert(Quantity::Stiffness, text.parse::<f64>().unwrap_or(0.0), Units::SCENE)
+                        };
+                        let kx_val = stiffness(spring_kx());
+                        let ky_val = stiffness(spring_ky());
+                        let kz_val = stiffness(spring_kz());
                         
                         // Apply constraints to selected nodes
                         let constraint_data = format!(
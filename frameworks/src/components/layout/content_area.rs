@@ -11,6 +11,8 @@ pub fn ContentArea() -> Element {
     let show_analysis_panel = use_signal(|| false);
     let show_mesh_panel = use_signal(|| false);
     let show_split_beam_panel = use_signal(|| false);
+    let show_copy_panel = use_signal(|| false);
+    let show_display_panel = use_signal(|| false);
     
     // New property panels
     let show_beam_properties = use_signal(|| false);
@@ -42,10 +44,14 @@ pub fn ContentArea() -> Element {
                             show_shell_properties: show_shell_properties,
                             show_material_properties: show_material_properties,
                             show_split_beam_panel: show_split_beam_panel,
+                            show_copy_panel: show_copy_panel,
+                            show_display_panel: show_display_panel,
                         }
                     }
                     div { class: "canvas-wrapper",
                         crate::components::visualization::ThreeJsCanvas  {}
+                        crate::components::layout::ContourLegend {}
+                        crate::components::layout::CoordinateBar {}
                     }
                     crate::components::layout::Console {}
                     crate::components::layout::RightPanel {
@@ -88,6 +94,12 @@ pub fn ContentArea() -> Element {
                     crate::components::layout::SplitBeamPanel {
                         show_panel: show_split_beam_panel,
                     }
+                    crate::components::layout::CopyPanel {
+                        show_panel: show_copy_panel,
+                    }
+                    crate::components::layout::DisplaySettingsPanel {
+                        show_panel: show_display_panel,
+                    }
                 }
             }
         }
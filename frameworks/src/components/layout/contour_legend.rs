@@ -0,0 +1,140 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+use serde::Deserialize;
+
+use crate::hooks::use_design_state::DesignState;
+
+/// Labels on a continuous scale, from the top to the bottom
+const CONTINUOUS_TICKS: usize = 5;
+
+/// Scale of the contour shown in the viewport, as the scene sends it: the
+/// quantity, its range in SI units, the number of bands (0 for continuous)
+/// and the colormap as [t, r, g, b] stops
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct ContourScale {
+    pub quantity: String,
+    pub min: f64,
+    pub max: f64,
+    pub bands: usize,
+    pub stops: Vec<[f64; 4]>,
+}
+
+impl ContourScale {
+    /// Title of the result, and the quantity it is shown as
+    fn title(&self) -> (&'static str, Option<Quantity>) {
+        match self.quantity.as_str() {
+            "von_mises" => ("Von Mises Stress", Some(Quantity::Stress)),
+            "sx" => ("Membrane Stress σx", Some(Quantity::Stress)),
+            "sy" => ("Membrane Stress σy", Some(Quantity::Stress)),
+            "txy" => ("Shear Stress τxy", Some(Quantity::Stress)),
+            "mx" => ("Bending Moment Mx", Some(Quantity::MomentPerLength)),
+            "my" => ("Bending Moment My", Some(Quantity::MomentPerLength)),
+            "mxy" => ("Twisting Moment Mxy", Some(Quantity::MomentPerLength)),
+            "deflection" => ("Deflection", Some(Quantity::Displacement)),
+            _ => ("Result", None),
+        }
+    }
+
+    /// Colormap at t in 0-1
+    fn color_at(&self, t: f64) -> String {
+        let next = self.stops.iter().position(|s| s[0] >= t).unwrap_or(self.stops.len().saturating_sub(1));
+        let rgb = match (next.checked_sub(1).and_then(|i| self.stops.get(i)), self.stops.get(next)) {
+            (Some(a), Some(b)) if b[0] > a[0] => {
+                let s = (t - a[0]) / (b[0] - a[0]);
+                [1, 2, 3].map(|k| a[k] + s * (b[k] - a[k]))
+            }
+            (_, Some(b)) => [b[1], b[2], b[3]],
+            _ => [0.0; 3],
+        };
+        format!("rgb({:.0}, {:.0}, {:.0})", rgb[0], rgb[1], rgb[2])
+    }
+
+    /// Background of the color bar, from the bottom (min) to the top (max)
+    fn bar_background(&self) -> String {
+        let stops: Vec<String> = if self.bands > 0 {
+            // Hard edges between bands, each the color of its middle
+            (0..self.bands).map(|band| {
+                let color = self.color_at((band as f64 + 0.5) / self.bands as f64);
+                let from = 100.0 * band as f64 / self.bands as f64;
+                let to = 100.0 * (band + 1) as f64 / self.bands as f64;
+                format!("{color} {from:.2}%, {color} {to:.2}%")
+            }).collect()
+        } else {
+            self.stops.iter()
+                .map(|s| format!("rgb({:.0}, {:.0}, {:.0}) {:.2}%", s[1], s[2], s[3], 100.0 * s[0]))
+                .collect()
+        };
+        format!("linear-gradient(to top, {})", stops.join(", "))
+    }
+
+    /// Values labelled beside the bar in display units, from the top: the
+    /// band edges, or evenly spaced values on a continuous scale
+    fn ticks(&self, units: Units) -> Vec<f64> {
+        let (_, quantity) = self.title();
+        let intervals = if self.bands > 0 { self.bands } else { CONTINUOUS_TICKS - 1 };
+        (0..=intervals).rev()
+            .map(|k| self.min + (self.max - self.min) * k as f64 / intervals as f64)
+            .map(|si| quantity.map_or(si, |q| units.from_si(q, si)))
+            .collect()
+    }
+}
+
+/// Decimals that tell the ticks of a range apart
+fn tick_decimals(ticks: &[f64]) -> usize {
+    let step = ticks.windows(2).map(|w| (w[0] - w[1]).abs()).fold(f64::INFINITY, f64::min);
+    if !step.is_finite() || step <= 0.0 {
+        return 2;
+    }
+    (2.0 - step.log10().floor()).clamp(0.0, 6.0) as usize
+}
+
+/// Legend of the plate contour shown in the viewport: the quantity and its
+/// units, and the color scale with its range, banded or continuous
+#[component]
+pub fn ContourLegend() -> Element {
+    let mut scale = use_signal(|| None::<ContourScale>);
+    let units = use_context::<DesignState>().units;
+
+    use_hook(move || {
+        spawn(async move {
+            let mut legend = eval(r#"
+                window.addEventListener('contourLegendChanged', (e) => dioxus.send(e.detail));
+            "#);
+            while let Ok(value) = legend.recv::<serde_json::Value>().await {
+                match serde_json::from_value::<Option<ContourScale>>(value) {
+                    Ok(shown) => scale.set(shown),
+                    Err(e) => web_sys::console::error_1(&format!("Unreadable contour legend: {e}").into()),
+                }
+            }
+        });
+    });
+
+    let Some(shown) = scale() else {
+        return rsx! {};
+    };
+    let (title, quantity) = shown.title();
+    let unit = quantity.map_or("", |q| units().symbol(q));
+    let ticks = shown.ticks(units());
+    let decimals = tick_decimals(&ticks);
+    let labels: Vec<String> = ticks.iter().map(|tick| format!("{tick:.decimals$}")).collect();
+
+    rsx! {
+        div { class: "contour-legend",
+            div { class: "contour-legend-title",
+                if unit.is_empty() { "{title}" } else { "{title} ({unit})" }
+            }
+            div { class: "contour-legend-scale",
+                div {
+                    class: "contour-legend-bar",
+                    style: "background: {shown.bar_background()};",
+                }
+                div { class: "contour-legend-ticks",
+                    for label in labels {
+                        span { "{label}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+use serde::{Deserialize, Serialize};
+
+use crate::hooks::use_design_state::DesignState;
+
+/// How points picked on the work plane are placed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct DrawSettings {
+    /// Increment (m) points snap to along X and Z; 0 for none
+    pub snap: f64,
+    /// Hold a beam being drawn to the X or Z axis through its start
+    pub ortho: bool,
+}
+
+impl Default for DrawSettings {
+    fn default() -> Self {
+        Self { snap: 1.0, ortho: false }
+    }
+}
+
+impl DrawSettings {
+    /// Send the settings to the viewport's draw tools
+    pub fn apply(&self) {
+        if let Ok(settings) = serde_json::to_string(self) {
+            eval(&format!("if (window.setDrawSettings) {{ window.setDrawSettings({settings}); }}"));
+        }
+    }
+}
+
+/// A typed point, in metres: `x, y, z`, or `@dx, dy, dz` from the last point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoordinateEntry {
+    Absolute([f64; 3]),
+    Relative([f64; 3]),
+}
+
+impl CoordinateEntry {
+    /// Parse coordinates typed in the display units, separated by commas or
+    /// spaces
+    pub fn parse(text: &str, units: Units) -> Result<Self, String> {
+        let text = text.trim();
+        let (relative, values) = match text.strip_prefix('@') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let values = values.split([',', ' ']).map(str::trim).filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f64>().map_err(|_| format!("'{v}' is not a number")))
+            .collect::<Result<Vec<f64>, String>>()?;
+        let [x, y, z] = values[..] else {
+            return Err("Enter three coordinates: x, y, z".to_string());
+        };
+        let point = [x, y, z].map(|v| units.to_si(Quantity::Length, v));
+        Ok(if relative { Self::Relative(point) } else { Self::Absolute(point) })
+    }
+
+    /// The point entered, relative coordinates measured from `anchor`
+    pub fn point(&self, anchor: Option<[f64; 3]>) -> Result<[f64; 3], String> {
+        match (self, anchor) {
+            (Self::Absolute(point), _) => Ok(*point),
+            (Self::Relative([dx, dy, dz]), Some([x, y, z])) => Ok([x + dx, y + dy, z + dz]),
+            (Self::Relative(_), None) => Err("Place a node before entering a relative point".to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Anchor {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+/// Coordinate entry and snap settings for placing nodes and drawing beams
+/// precisely: a typed point adds a node, and with the beam tool on it also
+/// starts or continues a beam
+#[component]
+pub fn CoordinateBar() -> Element {
+    let design_state = use_context::<DesignState>();
+    let units = design_state.units;
+    let mut draw_settings = design_state.draw_settings;
+    let mut text = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    use_effect(move || draw_settings().apply());
+
+    let mut place = move || {
+        let entry = match CoordinateEntry::parse(&text(), units()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                status.set(Some(Err(e)));
+                return;
+            }
+        };
+        spawn(async move {
+            let anchor = match eval("return window.getDrawAnchor ? window.getDrawAnchor() : null;").await {
+                Ok(value) => serde_json::from_value::<Option<Anchor>>(value).ok().flatten().map(|a| [a.x, a.y, a.z]),
+                Err(_) => None,
+            };
+            let [x, y, z] = match entry.point(anchor) {
+                Ok(point) => point,
+                Err(e) => {
+                    status.set(Some(Err(e)));
+                    return;
+                }
+            };
+            let placed = eval(&format!("return window.placeNodeAt ? window.placeNodeAt({{ x: {x}, y: {y}, z: {z} }}) : null;")).await;
+            match placed.ok().and_then(|id| id.as_u64()) {
+                Some(id) => {
+                    let shown = [x, y, z].map(|v| units.peek().from_si(Quantity::Length, v));
+                    let unit = units.peek().symbol(Quantity::Length);
+                    status.set(Some(Ok(format!("Node {id} at ({:.3}, {:.3}, {:.3}) {unit}", shown[0], shown[1], shown[2]))));
+                    text.set(String::new());
+                }
+                None => status.set(Some(Err("The viewport is not ready".to_string()))),
+            }
+        });
+    };
+
+    let length_unit = units().symbol(Quantity::Length);
+    let placeholder = format!("x, y, z or @dx, dy, dz ({length_unit})");
+    let shown_snap = units().from_si(Quantity::Length, draw_settings().snap);
+    let ortho = draw_settings().ortho;
+
+    rsx! {
+        div { class: "coordinate-bar",
+            input {
+                class: "coordinate-input",
+                r#type: "text",
+                placeholder: "{placeholder}",
+                title: "Type a point and press Enter to place a node there; with Draw Beam on, points chain into beams",
+                value: "{text}",
+                oninput: move |e| text.set(e.value()),
+                onkeydown: move |e| {
+                    if e.key() == Key::Enter {
+                        place();
+                    }
+                },
+            }
+            label { class: "coordinate-snap",
+                "Snap ({length_unit})"
+                input {
+                    r#type: "number",
+                    min: "0",
+                    step: "any",
+                    title: "Increment points snap to on the work plane; 0 for none",
+                    value: "{shown_snap}",
+                    onchange: move |e| {
+                        if let Ok(snap) = e.value().parse::<f64>() {
+                            draw_settings.write().snap = units().to_si(Quantity::Length, snap.max(0.0));
+                        }
+                    },
+                }
+            }
+            button {
+                class: if ortho { "coordinate-ortho active" } else { "coordinate-ortho" },
+                title: "Ortho: hold beams to the X or Z axis through their start",
+                onclick: move |_| draw_settings.write().ortho = !ortho,
+                "Ortho"
+            }
+            match status() {
+                Some(Ok(message)) => rsx! { span { class: "coordinate-status", "{message}" } },
+                Some(Err(message)) => rsx! { span { class: "coordinate-status error", "{message}" } },
+                None => rsx! {},
+            }
+        }
+    }
+}
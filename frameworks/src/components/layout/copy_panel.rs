@@ -0,0 +1,217 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
+use crate::hooks::use_model_store::{Axis, ModelStore, Selection};
+
+/// Copy the selection as an array at a spacing, mirrored about a plane, or
+/// interactively from a base point. Array and mirror copies are made in the
+/// model store, so the supports and loads on what is copied go with it.
+#[component]
+pub fn CopyPanel(show_panel: Signal<bool>) -> Element {
+    let units = use_context::<DesignState>().units;
+    let model_store = use_context::<ModelStore>();
+    let mut mode = use_signal(|| String::from("array"));
+    let mut count = use_signal(|| String::from("1"));
+    let mut dx = use_signal(|| String::from("0"));
+    let mut dy = use_signal(|| String::from("0"));
+    let mut dz = use_signal(|| String::from("0"));
+    let mut axis = use_signal(|| Axis::X);
+    let mut coordinate = use_signal(|| String::from("0"));
+    let mut status = use_signal(|| None::<String>);
+
+    let apply = move |_| {
+        // Lengths are entered in the display units and kept in metres
+        let length = |text: String| units.peek().to_si(Quantity::Length, text.parse().unwrap_or(0.0));
+        let offset = [length(dx()), length(dy()), length(dz())];
+        let copies: usize = count().parse().unwrap_or(0);
+        let plane = (axis(), length(coordinate()));
+        let array = mode() == "array";
+        let mut model_store = model_store;
+
+        spawn(async move {
+            let picked = eval("return window.getSelectedIds ? window.getSelectedIds() : null;").await;
+            let selection = match picked.map(serde_json::from_value::<Option<Selection>>) {
+                Ok(Ok(Some(selection))) if !selection.is_empty() => selection,
+                Ok(Ok(_)) => {
+                    status.set(Some("Select members, plates or nodes to copy first".to_string()));
+                    return;
+                }
+                Ok(Err(e)) => {
+                    web_sys::console::error_1(&format!("Unreadable selection: {e}").into());
+                    return;
+                }
+                Err(e) => {
+                    web_sys::console::error_1(&format!("Could not read the selection: {e:?}").into());
+                    return;
+                }
+            };
+            if array && (copies == 0 || offset == [0.0; 3]) {
+                status.set(Some("Enter the number of copies and a spacing".to_string()));
+                return;
+            }
+
+            let mut model = model_store.model.peek().clone();
+            let (copied, label) = if array {
+                (model.copy_array(&selection, copies, offset), "Array copy")
+            } else {
+                (model.mirror(&selection, plane.0, plane.1), "Mirror copy")
+            };
+            if copied.is_empty() {
+                status.set(Some("The copy would only duplicate what is there".to_string()));
+                return;
+            }
+            model_store.set(model, label);
+            copied.apply(false);
+            status.set(Some(format!(
+                "Added {} members, {} plates and {} nodes",
+                copied.members.len(), copied.plates.len(), copied.nodes.len()
+            )));
+        });
+    };
+
+    let length_unit = units().symbol(Quantity::Length);
+
+    rsx! {
+        div {
+            class: "right-panel copy-panel",
+            style: if *show_panel.read() {
+                "transform: translateX(0); pointer-events: auto;"
+            } else {
+                "transform: translateX(100%); pointer-events: none;"
+            },
+
+            div { class: "panel-header",
+                h3 { "Copy" }
+                button {
+                    class: "close-btn",
+                    onclick: move |_| show_panel.set(false),
+                    "×"
+                }
+            }
+
+            div { class: "panel-content",
+                div { class: "form-group",
+                    label { r#for: "copy-mode", "Mode" }
+                    select {
+                        id: "copy-mode",
+                        value: "{mode}",
+                        onchange: move |evt| {
+                            mode.set(evt.value());
+                            status.set(None);
+                        },
+                        option { value: "array", selected: mode() == "array", "Array" }
+                        option { value: "mirror", selected: mode() == "mirror", "Mirror" }
+                        option { value: "base_point", selected: mode() == "base_point", "From base point" }
+                    }
+                    span { class: "help-text", "Copies the selected members, plates and nodes" }
+                }
+
+                if mode() == "array" {
+                    div { class: "form-group",
+                        label { r#for: "copy-count", "Number of copies" }
+                        input {
+                            r#type: "number",
+                            id: "copy-count",
+                            min: "1",
+                            step: "1",
+                            value: "{count}",
+                            oninput: move |evt| count.set(evt.value())
+                        }
+                    }
+                    div { class: "form-group",
+                        label { "Spacing ({length_unit})" }
+                        div { class: "copy-offset",
+                            input {
+                                r#type: "number",
+                                step: "any",
+                                title: "dX",
+                                value: "{dx}",
+                                oninput: move |evt| dx.set(evt.value())
+                            }
+                            input {
+                                r#type: "number",
+                                step: "any",
+                                title: "dY",
+                                value: "{dy}",
+                                oninput: move |evt| dy.set(evt.value())
+                            }
+                            input {
+                                r#type: "number",
+                                step: "any",
+                                title: "dZ",
+                                value: "{dz}",
+                                oninput: move |evt| dz.set(evt.value())
+                            }
+                        }
+                        span { class: "help-text", "dX, dY, dZ between successive copies" }
+                    }
+                } else if mode() == "mirror" {
+                    div { class: "form-group",
+                        label { r#for: "mirror-axis", "Mirror plane" }
+                        select {
+                            id: "mirror-axis",
+                            onchange: move |evt| {
+                                if let Some(chosen) = Axis::ALL.into_iter().find(|a| a.key() == evt.value()) {
+                                    axis.set(chosen);
+                                }
+                            },
+                            for option_axis in Axis::ALL {
+                                option {
+                                    value: "{option_axis.key()}",
+                                    selected: axis() == option_axis,
+                                    "Normal to {option_axis.key().to_uppercase()}"
+                                }
+                            }
+                        }
+                    }
+                    div { class: "form-group",
+                        label { r#for: "mirror-coordinate", "{axis().key().to_uppercase()} of the plane ({length_unit})" }
+                        input {
+                            r#type: "number",
+                            id: "mirror-coordinate",
+                            step: "any",
+                            value: "{coordinate}",
+                            oninput: move |evt| coordinate.set(evt.value())
+                        }
+                        span { class: "help-text", "Loads along the plane normal are reversed on the copy" }
+                    }
+                } else {
+                    div { class: "form-group",
+                        span { class: "help-text", "Pick a base node, then the node to copy it to" }
+                    }
+                }
+
+                if let Some(s) = status() {
+                    div { class: "form-group",
+                        span { class: "help-text", "{s}" }
+                    }
+                }
+
+                div { class: "button-group",
+                    if mode() == "base_point" {
+                        button {
+                            class: "apply-btn",
+                            onclick: move |_| {
+                                eval("if (window.startCopyElements) window.startCopyElements();");
+                            },
+                            "Pick Base Point"
+                        }
+                    } else {
+                        button {
+                            class: "apply-btn",
+                            onclick: apply,
+                            "Copy"
+                        }
+                    }
+                    button {
+                        class: "close-btn-secondary",
+                        onclick: move |_| show_panel.set(false),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
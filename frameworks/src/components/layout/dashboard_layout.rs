@@ -1,10 +1,13 @@
 use dioxus::prelude::*;
 use crate::hooks::use_design_state::{use_design_state, DesignState};
+use crate::hooks::use_model_store::use_model_store;
 
 #[component]
 pub fn DashboardLayout() -> Element {
     let design_state = use_design_state();
     use_context_provider(|| design_state.clone());
+    let model_store = use_model_store();
+    use_context_provider(|| model_store);
     
     rsx! {
         div {
@@ -0,0 +1,169 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use serde::Serialize;
+
+use crate::hooks::use_design_state::DesignState;
+
+/// How loads and supports are drawn in the viewport
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DisplaySettings {
+    /// Show loads at all
+    pub loads_visible: bool,
+    /// Length of load arrows relative to the default
+    pub load_scale: f64,
+    /// Label load arrows with their values
+    pub load_labels: bool,
+    /// Size of support glyphs relative to the default
+    pub support_scale: f64,
+    /// Load cases whose loads are hidden, by id
+    pub hidden_load_cases: Vec<usize>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            loads_visible: true,
+            load_scale: 1.0,
+            load_labels: true,
+            support_scale: 1.0,
+            hidden_load_cases: Vec::new(),
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Send the settings to the viewport
+    pub fn apply(&self) {
+        if let Ok(settings) = serde_json::to_string(self) {
+            eval(&format!("if (window.setDisplaySettings) {{ window.setDisplaySettings({settings}); }}"));
+        }
+    }
+
+    /// Whether loads of a case are shown
+    pub fn shows_case(&self, id: usize) -> bool {
+        !self.hidden_load_cases.contains(&id)
+    }
+
+    /// Show or hide the loads of a case
+    pub fn set_case_shown(&mut self, id: usize, shown: bool) {
+        self.hidden_load_cases.retain(|&hidden| hidden != id);
+        if !shown {
+            self.hidden_load_cases.push(id);
+        }
+    }
+}
+
+/// Scale of load arrows and support glyphs, value labels, and which load
+/// cases are drawn
+#[component]
+pub fn DisplaySettingsPanel(show_panel: Signal<bool>) -> Element {
+    let design_state = use_context::<DesignState>();
+    let load_cases = design_state.load_cases;
+    let mut display_settings = design_state.display_settings;
+
+    use_effect(move || display_settings().apply());
+
+    let settings = display_settings();
+
+    rsx! {
+        div {
+            class: "right-panel display-settings-panel",
+            style: if *show_panel.read() {
+                "transform: translateX(0); pointer-events: auto;"
+            } else {
+                "transform: translateX(100%); pointer-events: none;"
+            },
+
+            div { class: "panel-header",
+                h3 { "Display Settings" }
+                button {
+                    class: "close-btn",
+                    onclick: move |_| show_panel.set(false),
+                    "×"
+                }
+            }
+
+            div { class: "panel-content",
+                div { class: "form-group",
+                    label { class: "display-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings.loads_visible,
+                            onchange: move |e| display_settings.write().loads_visible = e.checked(),
+                        }
+                        "Show loads"
+                    }
+                    label { class: "display-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings.load_labels,
+                            onchange: move |e| display_settings.write().load_labels = e.checked(),
+                        }
+                        "Label load values"
+                    }
+                }
+
+                div { class: "form-group",
+                    label { r#for: "load-arrow-scale", "Load arrow scale" }
+                    input {
+                        r#type: "number",
+                        id: "load-arrow-scale",
+                        min: "0.1",
+                        step: "0.1",
+                        value: "{settings.load_scale}",
+                        onchange: move |e| {
+                            if let Ok(scale) = e.value().parse::<f64>() {
+                                display_settings.write().load_scale = scale.max(0.1);
+                            }
+                        },
+                    }
+                    span { class: "help-text", "Arrow length relative to the default" }
+                }
+
+                div { class: "form-group",
+                    label { "Load cases shown" }
+                    for case in load_cases() {
+                        label { class: "display-toggle",
+                            input {
+                                r#type: "checkbox",
+                                checked: settings.shows_case(case.id),
+                                onchange: move |e| display_settings.write().set_case_shown(case.id, e.checked()),
+                            }
+                            "{case.title}"
+                        }
+                    }
+                }
+
+                div { class: "form-group",
+                    label { r#for: "support-glyph-scale", "Support glyph size" }
+                    input {
+                        r#type: "number",
+                        id: "support-glyph-scale",
+                        min: "0.1",
+                        step: "0.1",
+                        value: "{settings.support_scale}",
+                        onchange: move |e| {
+                            if let Ok(scale) = e.value().parse::<f64>() {
+                                display_settings.write().support_scale = scale.max(0.1);
+                            }
+                        },
+                    }
+                    span { class: "help-text", "Size relative to the default" }
+                }
+
+                div { class: "button-group",
+                    button {
+                        class: "apply-btn",
+                        onclick: move |_| display_settings.set(DisplaySettings::default()),
+                        "Reset"
+                    }
+                    button {
+                        class: "close-btn-secondary",
+                        onclick: move |_| show_panel.set(false),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
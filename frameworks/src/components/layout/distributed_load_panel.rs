@@ -1,8 +1,12 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+
+use crate::hooks::use_design_state::DesignState;
 
 #[component]
 pub fn DistributedLoadPanel(show_panel: Signal<bool>) -> Element {
+    let units = use_context::<DesignState>().units;
     let mut magnitude = use_signal(|| String::from("5.0"));
     let mut direction = use_signal(|| String::from("y"));
     let mut start_pos = use_signal(|| String::from("0.0"));
@@ -10,7 +14,8 @@ pub fn DistributedLoadPanel(show_panel: Signal<bool>) -> Element {
     let mut color = use_signal(|| String::from("#ff6600"));
 
     let apply_load = move |_| {
-        let mag_value = magnitude.read().clone();
+        // Entered in the display units, applied in the scene's
+        let mag_value = units().convert(Quantity::LineLoad, magnitude.read().parse().unwrap_or(0.0), Units::SCENE);
         let dir_value = direction.read().clone();
         let start_value = start_pos.read().clone();
         let end_value = end_pos.read().clone();
@@ -35,6 +40,8 @@ pub fn DistributedLoadPanel(show_panel: Signal<bool>) -> Element {
         eval(&js_code);
     };
 
+    let magnitude_label = units().label("Magnitude", Quantity::LineLoad);
+
     let close_panel = move |_| {
         show_panel.set(false);
     };
@@ -61,7 +68,7 @@ pub fn DistributedLoadPanel(show_panel: Signal<bool>) -> Element {
                 
                 // Magnitude input
                 div { class: "form-group",
-                    label { r#for: "magnitude", "Magnitude (kN/m)" }
+                    label { r#for: "magnitude", "{magnitude_label}" }
                     input {
                         r#type: "number",
                         id: "magnitude",
@@ -0,0 +1,322 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
+use crate::hooks::use_model_store::ModelStore;
+use crate::types::Storey;
+
+/// Construction grid and storeys: gridlines along X numbered 1, 2, 3...,
+/// gridlines along plan Y (the scene's Z) lettered A, B, C..., and storey
+/// heights up from the ground at Y = 0. All in metres, from the origin.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GridSystem {
+    /// Spacings between gridlines 1, 2, 3... along X
+    pub x_spacings: Vec<f64>,
+    /// Spacings between gridlines A, B, C... along plan Y
+    pub y_spacings: Vec<f64>,
+    /// Height of each storey, from the ground up
+    pub storey_heights: Vec<f64>,
+}
+
+impl Default for GridSystem {
+    fn default() -> Self {
+        Self {
+            x_spacings: vec![6.0; 3],
+            y_spacings: vec![6.0; 2],
+            storey_heights: vec![3.0; 3],
+        }
+    }
+}
+
+impl GridSystem {
+    /// Spacings as typed: comma- or space-separated lengths, with `n@s` for
+    /// n equal spacings of s, e.g. "3@6, 8"
+    pub fn parse_spacings(text: &str) -> Result<Vec<f64>, String> {
+        let mut spacings = Vec::new();
+        for term in text.split([',', ' ']).map(str::trim).filter(|t| !t.is_empty()) {
+            let (count, length) = match term.split_once('@') {
+                Some((n, s)) => (
+                    n.trim().parse::<usize>().map_err(|_| format!("'{term}': expected a count before '@'"))?,
+                    s.trim(),
+                ),
+                None => (1, term),
+            };
+            let length = length.parse::<f64>().map_err(|_| format!("'{term}' is not a length"))?;
+            if !(length > 0.0) {
+                return Err(format!("'{term}': spacings must be positive"));
+            }
+            spacings.extend(std::iter::repeat(length).take(count));
+        }
+        Ok(spacings)
+    }
+
+    /// Spacings in the `n@s` form, repeated lengths grouped
+    pub fn format_spacings(spacings: &[f64]) -> String {
+        let mut terms: Vec<String> = Vec::new();
+        let mut rest = spacings;
+        while let Some(&first) = rest.first() {
+            let count = rest.iter().take_while(|&&s| s == first).count();
+            terms.push(if count > 1 { format!("{count}@{first}") } else { first.to_string() });
+            rest = &rest[count..];
+        }
+        terms.join(", ")
+    }
+
+    /// Gridlines along X: label and X coordinate
+    pub fn x_lines(&self) -> Vec<(String, f64)> {
+        offsets(&self.x_spacings).into_iter().enumerate()
+            .map(|(i, x)| ((i + 1).to_string(), x))
+            .collect()
+    }
+
+    /// Gridlines along plan Y: label and scene Z coordinate
+    pub fn y_lines(&self) -> Vec<(String, f64)> {
+        offsets(&self.y_spacings).into_iter().enumerate()
+            .map(|(i, z)| (letter_label(i), z))
+            .collect()
+    }
+
+    /// Ground and each floor: name and elevation
+    pub fn levels(&self) -> Vec<(String, f64)> {
+        offsets(&self.storey_heights).into_iter().enumerate()
+            .map(|(i, y)| (if i == 0 { "Ground".to_string() } else { format!("Level {i}") }, y))
+            .collect()
+    }
+
+    /// The grid as the viewport draws it
+    fn to_js(&self) -> serde_json::Value {
+        let lines = |lines: Vec<(String, f64)>| -> Vec<serde_json::Value> {
+            lines.into_iter().map(|(label, at)| serde_json::json!({ "label": label, "at": at })).collect()
+        };
+        serde_json::json!({
+            "x": lines(self.x_lines()),
+            "z": lines(self.y_lines()),
+            "levels": self.levels().into_iter().map(|(_, y)| y).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Positions from the origin of lines with these spacings between them
+fn offsets(spacings: &[f64]) -> Vec<f64> {
+    std::iter::once(0.0)
+        .chain(spacings.iter().scan(0.0, |at, s| {
+            *at += s;
+            Some(*at)
+        }))
+        .collect()
+}
+
+/// A, B, ..., Z, AA, AB, ... for gridline `index`
+fn letter_label(index: usize) -> String {
+    let mut label = String::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        label.insert(0, (b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    label
+}
+
+/// Draw the grid in the viewport, or remove it
+pub fn show_grid(grid: Option<&GridSystem>) {
+    let grid_js = grid.map_or(serde_json::Value::Null, GridSystem::to_js);
+    eval(&format!("if (window.setConstructionGrid) {{ window.setConstructionGrid({grid_js}); }}"));
+}
+
+#[component]
+pub fn GridModal(show: Signal<bool>) -> Element {
+    let design_state = use_context::<DesignState>();
+    let mut grid = design_state.grid;
+    let mut storeys = design_state.storeys;
+    let mut model_store = use_context::<ModelStore>();
+    let units = design_state.units;
+
+    // Spacings are typed in the display units and kept in metres
+    let shown = move |spacings: &[f64]| -> String {
+        let shown: Vec<f64> = spacings.iter().map(|&s| units.peek().from_si(Quantity::Length, s)).collect();
+        GridSystem::format_spacings(&shown)
+    };
+    let parsed = move |text: &str| -> Result<Vec<f64>, String> {
+        Ok(GridSystem::parse_spacings(text)?.into_iter().map(|s| units.peek().to_si(Quantity::Length, s)).collect())
+    };
+
+    let initial = grid.peek().clone().unwrap_or_default();
+    let mut x_text = use_signal(|| shown(&initial.x_spacings));
+    let mut y_text = use_signal(|| shown(&initial.y_spacings));
+    let mut heights_text = use_signal(|| shown(&initial.storey_heights));
+    let mut columns = use_signal(|| true);
+    let mut beams = use_signal(|| true);
+    let mut supports = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+
+    if !show() {
+        return rsx! {};
+    }
+
+    // The grid as entered, or why it cannot be used
+    let entered = move || -> Result<GridSystem, String> {
+        let grid = GridSystem {
+            x_spacings: parsed(&x_text()).map_err(|e| format!("X spacings: {e}"))?,
+            y_spacings: parsed(&y_text()).map_err(|e| format!("Y spacings: {e}"))?,
+            storey_heights: parsed(&heights_text()).map_err(|e| format!("Storey heights: {e}"))?,
+        };
+        if grid.x_spacings.is_empty() && grid.y_spacings.is_empty() {
+            return Err("Enter spacings along X or Y".to_string());
+        }
+        Ok(grid)
+    };
+
+    // Draw the grid and replace the storeys with its levels
+    let mut apply = move || -> Option<GridSystem> {
+        match entered() {
+            Ok(entered) => {
+                error.set(None);
+                show_grid(Some(&entered));
+                storeys.set(entered.levels().into_iter()
+                    .map(|(name, elevation)| Storey { name, elevation, visible: true })
+                    .collect());
+                grid.set(Some(entered.clone()));
+                Some(entered)
+            }
+            Err(e) => {
+                error.set(Some(e));
+                None
+            }
+        }
+    };
+
+    let grid_shown = grid().is_some();
+    let length_unit = units().symbol(Quantity::Length);
+
+    rsx! {
+        div {
+            class: "modal-overlay",
+            onclick: move |_| show.set(false),
+
+            div {
+                class: "modal-content load-cases-modal grid-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "modal-header",
+                    h3 { "Grids & Storeys" }
+                    button {
+                        class: "modal-close-btn",
+                        onclick: move |_| show.set(false),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-body",
+                    p { class: "grid-hint",
+                        "Spacings in {length_unit}, separated by commas; 3@6 is three spacings of 6."
+                    }
+                    div { class: "add-case-form",
+                        div { class: "form-row",
+                            div { class: "form-field flex-grow",
+                                label { "X spacings (gridlines 1, 2, 3...)" }
+                                input {
+                                    r#type: "text",
+                                    value: "{x_text}",
+                                    oninput: move |e| x_text.set(e.value()),
+                                }
+                            }
+                        }
+                        div { class: "form-row",
+                            div { class: "form-field flex-grow",
+                                label { "Y spacings (gridlines A, B, C...)" }
+                                input {
+                                    r#type: "text",
+                                    value: "{y_text}",
+                                    oninput: move |e| y_text.set(e.value()),
+                                }
+                            }
+                        }
+                        div { class: "form-row",
+                            div { class: "form-field flex-grow",
+                                label { "Storey heights (from the ground up)" }
+                                input {
+                                    r#type: "text",
+                                    value: "{heights_text}",
+                                    oninput: move |e| heights_text.set(e.value()),
+                                }
+                            }
+                        }
+                        div { class: "form-row",
+                            button {
+                                class: "btn-add",
+                                title: "Draw the gridlines and set the storeys",
+                                onclick: move |_| {
+                                    apply();
+                                },
+                                "Apply Grid"
+                            }
+                            if grid_shown {
+                                button {
+                                    class: "btn-cancel",
+                                    onclick: move |_| {
+                                        show_grid(None);
+                                        grid.set(None);
+                                    },
+                                    "Remove Grid"
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "add-case-form",
+                        h4 { "Generate Frame" }
+                        div { class: "form-row grid-options",
+                            label {
+                                input {
+                                    r#type: "checkbox",
+                                    checked: columns(),
+                                    onchange: move |e| columns.set(e.checked()),
+                                }
+                                "Columns at intersections"
+                            }
+                            label {
+                                input {
+                                    r#type: "checkbox",
+                                    checked: beams(),
+                                    onchange: move |e| beams.set(e.checked()),
+                                }
+                                "Beams along gridlines"
+                            }
+                            label {
+                                input {
+                                    r#type: "checkbox",
+                                    checked: supports(),
+                                    onchange: move |e| supports.set(e.checked()),
+                                }
+                                "Pinned bases"
+                            }
+                        }
+                        div { class: "form-row",
+                            button {
+                                class: "btn-add",
+                                title: "Add members on the grid to the model; existing nodes and members are reused",
+                                onclick: move |_| {
+                                    if let Some(grid) = apply() {
+                                        let mut model = model_store.model.peek().clone();
+                                        model.add_grid_frame(&grid, columns(), beams(), supports());
+                                        model_store.set(model, "Generate grid frame");
+                                        show.set(false);
+                                    }
+                                },
+                                "Generate"
+                            }
+                        }
+                    }
+
+                    if let Some(e) = error() {
+                        div { class: "analysis-error",
+                            div { class: "error-text", "{e}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use dioxus::prelude::*;
+use fea_solver::io::dxf::{read_dxf, DxfDrawing};
+use fea_solver::units::LengthUnit;
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
+use crate::hooks::use_model_store::ModelStore;
+
+/// Units a drawing may be in, for drawings without `$INSUNITS`
+const DRAWING_UNITS: [LengthUnit; 5] = [
+    LengthUnit::Millimetre,
+    LengthUnit::Centimetre,
+    LengthUnit::Metre,
+    LengthUnit::Inch,
+    LengthUnit::Foot,
+];
+
+/// Ends of lines closer than this (m) become one node, unless changed
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// Which way up a drawing is, in the scene where Y is up
+#[derive(Clone, Copy, PartialEq)]
+enum DrawingPlane {
+    /// Drawing X and Y on the scene's plan X and Z, drawing Z up
+    Plan,
+    /// Drawing X and Y on the scene's X and Y, as an elevation
+    Elevation,
+}
+
+impl DrawingPlane {
+    const ALL: [DrawingPlane; 2] = [DrawingPlane::Plan, DrawingPlane::Elevation];
+
+    fn key(&self) -> &'static str {
+        match self {
+            DrawingPlane::Plan => "plan",
+            DrawingPlane::Elevation => "elevation",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DrawingPlane::Plan => "Plan or 3D (drawing Z is up)",
+            DrawingPlane::Elevation => "Elevation (drawing Y is up)",
+        }
+    }
+
+    fn to_scene(self, [x, y, z]: [f64; 3]) -> [f64; 3] {
+        match self {
+            DrawingPlane::Plan => [x, z, y],
+            DrawingPlane::Elevation => [x, y, z],
+        }
+    }
+}
+
+/// Import frame centre lines from a DXF drawing: pick the layers to bring
+/// in, the drawing's units and orientation, and how close line ends must
+/// be to join, then add the nodes and members to the model store
+#[component]
+pub fn ImportDxfModal(show: Signal<bool>) -> Element {
+    let units = use_context::<DesignState>().units;
+    let mut model_store = use_context::<ModelStore>();
+
+    // File name and the drawing read from it
+    let mut drawing = use_signal(|| None::<(String, DxfDrawing)>);
+    let mut layers = use_signal(Vec::<String>::new);
+    let mut drawing_unit = use_signal(|| LengthUnit::Metre);
+    let mut plane = use_signal(|| DrawingPlane::Plan);
+    let mut tolerance = use_signal(|| DEFAULT_TOLERANCE);
+    let mut error = use_signal(|| None::<String>);
+
+    if !show() {
+        return rsx! {};
+    }
+
+    let open_file = move |e: FormEvent| {
+        let Some(file) = e.files().into_iter().next() else { return };
+        spawn(async move {
+            let name = file.name();
+            let text = match file.read_string().await {
+                Ok(text) => text,
+                Err(e) => {
+                    error.set(Some(format!("Could not read {name}: {e}")));
+                    return;
+                }
+            };
+            match read_dxf(&text) {
+                Ok(read) if read.lines.is_empty() => {
+                    drawing.set(None);
+                    error.set(Some(format!("{name} has no lines or polylines to import")));
+                }
+                Ok(read) => {
+                    layers.set(read.layers().into_iter().map(|(layer, _)| layer).collect());
+                    if let Some(unit) = read.units {
+                        drawing_unit.set(unit);
+                    }
+                    error.set(None);
+                    drawing.set(Some((name, read)));
+                }
+                Err(e) => {
+                    drawing.set(None);
+                    error.set(Some(format!("{name}: {e}")));
+                }
+            }
+        });
+    };
+
+    let import = move |_| {
+        let frame = match drawing.peek().as_ref() {
+            Some((_, read)) => read.frame(&layers.peek(), tolerance(), drawing_unit().metres()),
+            None => return,
+        };
+        let points: Vec<[f64; 3]> = frame.nodes.iter().map(|&point| plane().to_scene(point)).collect();
+        let members: Vec<(usize, usize)> = frame.members.iter().map(|m| (m.i_node, m.j_node)).collect();
+
+        let mut model = model_store.model.peek().clone();
+        let added = model.add_frame(&points, &members);
+        if added.is_empty() {
+            error.set(Some("The model already has every member on these layers".to_string()));
+            return;
+        }
+        model_store.set(model, "Import DXF");
+        added.apply(false);
+        show.set(false);
+    };
+
+    let length_unit = units().symbol(Quantity::Length);
+    let shown_tolerance = units().from_si(Quantity::Length, tolerance());
+    // Preview of what the import would make with the current choices
+    let preview = drawing.read().as_ref().map(|(name, read)| {
+        let frame = read.frame(&layers(), tolerance(), drawing_unit().metres());
+        (name.clone(), read.layers(), frame.nodes.len(), frame.members.len(), read.units.is_some())
+    });
+
+    rsx! {
+        div {
+            class: "modal-overlay",
+            onclick: move |_| show.set(false),
+
+            div {
+                class: "modal-content load-cases-modal import-dxf-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "modal-header",
+                    h3 { "Import DXF" }
+                    button {
+                        class: "modal-close-btn",
+                        onclick: move |_| show.set(false),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-body",
+                    div { class: "add-case-form",
+                        div { class: "form-row",
+                            div { class: "form-field flex-grow",
+                                label { "Drawing" }
+                                input {
+                                    r#type: "file",
+                                    accept: ".dxf",
+                                    onchange: open_file,
+                                }
+                            }
+                        }
+                        p { class: "grid-hint", "Lines and polylines become members; ends that meet become nodes." }
+
+                        if let Some((name, drawing_layers, nodes, members, has_units)) = preview {
+                            div { class: "form-row",
+                                div { class: "form-field flex-grow",
+                                    label { "Layers in {name}" }
+                                    div { class: "import-dxf-layers",
+                                        for (layer, count) in drawing_layers {
+                                            label {
+                                                input {
+                                                    r#type: "checkbox",
+                                                    checked: layers().contains(&layer),
+                                                    onchange: {
+                                                        let layer = layer.clone();
+                                                        move |e: FormEvent| {
+                                                            layers.write().retain(|l| *l != layer);
+                                                            if e.checked() {
+                                                                layers.write().push(layer.clone());
+                                                            }
+                                                        }
+                                                    },
+                                                }
+                                                "{layer} ({count})"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "form-row",
+                                div { class: "form-field flex-grow",
+                                    label { "Drawing units" }
+                                    select {
+                                        onchange: move |e| {
+                                            if let Some(unit) = DRAWING_UNITS.into_iter().find(|u| u.symbol() == e.value()) {
+                                                drawing_unit.set(unit);
+                                            }
+                                        },
+                                        for unit in DRAWING_UNITS {
+                                            option {
+                                                value: "{unit.symbol()}",
+                                                selected: drawing_unit() == unit,
+                                                "{unit.symbol()}"
+                                            }
+                                        }
+                                    }
+                                }
+                                div { class: "form-field flex-grow",
+                                    label { "Merge tolerance ({length_unit})" }
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        step: "any",
+                                        value: "{shown_tolerance}",
+                                        onchange: move |e| {
+                                            if let Ok(value) = e.value().parse::<f64>() {
+                                                tolerance.set(units().to_si(Quantity::Length, value.max(0.0)));
+                                            }
+                                        },
+                                    }
+                                }
+                            }
+                            if !has_units {
+                                p { class: "grid-hint", "The drawing does not say its units; check them above." }
+                            }
+                            div { class: "form-row",
+                                div { class: "form-field flex-grow",
+                                    label { "Orientation" }
+                                    select {
+                                        onchange: move |e| {
+                                            if let Some(chosen) = DrawingPlane::ALL.into_iter().find(|p| p.key() == e.value()) {
+                                                plane.set(chosen);
+                                            }
+                                        },
+                                        for option_plane in DrawingPlane::ALL {
+                                            option {
+                                                value: "{option_plane.key()}",
+                                                selected: plane() == option_plane,
+                                                "{option_plane.name()}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "form-row import-dxf-actions",
+                                span { class: "grid-hint", "{nodes} nodes and {members} members" }
+                                button {
+                                    class: "btn-add",
+                                    disabled: members == 0,
+                                    onclick: import,
+                                    "Import"
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(e) = error() {
+                        div { class: "analysis-error",
+                            div { class: "error-text", "{e}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ pub struct LoadCase {
     pub id: usize,
     pub title: String,
     pub comment: String,
+    pub kind: LoadCaseKind,
 }
 
 impl Default for LoadCase {
@@ -14,10 +15,51 @@ impl Default for LoadCase {
             id: 1,
             title: "Dead Load".to_string(),
             comment: "Self-weight and permanent loads".to_string(),
+            kind: LoadCaseKind::Dead,
         }
     }
 }
 
+/// Type of load in a case, which decides its factors in generated combinations
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LoadCaseKind {
+    Dead,
+    Live,
+    Wind,
+    Snow,
+    /// Not included in generated combinations
+    Other,
+}
+
+impl LoadCaseKind {
+    pub const ALL: [LoadCaseKind; 5] = [Self::Dead, Self::Live, Self::Wind, Self::Snow, Self::Other];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dead => "Dead",
+            Self::Live => "Live",
+            Self::Wind => "Wind",
+            Self::Snow => "Snow",
+            Self::Other => "Other",
+        }
+    }
+
+    /// Letter used in combination names, e.g. D in 1.2D + 1.6L
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Dead => "D",
+            Self::Live => "L",
+            Self::Wind => "W",
+            Self::Snow => "S",
+            Self::Other => "O",
+        }
+    }
+
+    fn from_label(label: &str) -> Self {
+        Self::ALL.into_iter().find(|k| k.label() == label).unwrap_or(Self::Other)
+    }
+}
+
 #[component]
 pub fn LoadCasesModal(
     show: Signal<bool>,
@@ -26,9 +68,11 @@ pub fn LoadCasesModal(
 ) -> Element {
     let mut new_title = use_signal(|| String::new());
     let mut new_comment = use_signal(|| String::new());
+    let mut new_kind = use_signal(|| LoadCaseKind::Live);
     let mut editing_id = use_signal(|| None::<usize>);
     let mut edit_title = use_signal(|| String::new());
     let mut edit_comment = use_signal(|| String::new());
+    let edit_kind = use_signal(|| LoadCaseKind::Dead);
     
     // Initialize with default load case if empty
     use_effect(move || {
@@ -72,6 +116,7 @@ pub fn LoadCasesModal(
                                 tr {
                                     th { class: "col-case", "Case" }
                                     th { class: "col-title", "Title" }
+                                    th { class: "col-kind", "Type" }
                                     th { class: "col-comment", "Comment" }
                                     th { class: "col-actions", "Actions" }
                                 }
@@ -87,6 +132,7 @@ pub fn LoadCasesModal(
                                         editing_id,
                                         edit_title,
                                         edit_comment,
+                                        edit_kind,
                                     )}
                                 }
                             }
@@ -106,6 +152,16 @@ pub fn LoadCasesModal(
                                     oninput: move |e| new_title.set(e.value().clone()),
                                 }
                             }
+                            div { class: "form-field",
+                                label { "Type" }
+                                select {
+                                    value: "{new_kind().label()}",
+                                    onchange: move |e| new_kind.set(LoadCaseKind::from_label(&e.value())),
+                                    for kind in LoadCaseKind::ALL {
+                                        option { value: "{kind.label()}", selected: new_kind() == kind, "{kind.label()}" }
+                                    }
+                                }
+                            }
                             div { class: "form-field flex-grow",
                                 label { "Comment" }
                                 input {
@@ -129,6 +185,7 @@ pub fn LoadCasesModal(
                                         id: next_id,
                                         title,
                                         comment: new_comment.read().trim().to_string(),
+                                        kind: new_kind(),
                                     });
                                     load_cases.set(cases.clone());
                                     new_title.set(String::new());
@@ -155,8 +212,10 @@ fn render_case_row(
     mut editing_id: Signal<Option<usize>>,
     mut edit_title: Signal<String>,
     mut edit_comment: Signal<String>,
+    mut edit_kind: Signal<LoadCaseKind>,
 ) -> Element {
     let case_id = case.id;
+    let case_kind = case.kind;
     let case_title = case.title.clone();
     let case_comment = case.comment.clone();
     let cases_len = load_cases.read().len();
@@ -174,6 +233,16 @@ fn render_case_row(
                         oninput: move |e| edit_title.set(e.value().clone()),
                     }
                 }
+                td { class: "col-kind",
+                    select {
+                        class: "edit-input",
+                        value: "{edit_kind().label()}",
+                        onchange: move |e| edit_kind.set(LoadCaseKind::from_label(&e.value())),
+                        for kind in LoadCaseKind::ALL {
+                            option { value: "{kind.label()}", selected: edit_kind() == kind, "{kind.label()}" }
+                        }
+                    }
+                }
                 td { class: "col-comment",
                     input {
                         r#type: "text",
@@ -190,6 +259,7 @@ fn render_case_row(
                             if let Some(c) = cases.iter_mut().find(|c| c.id == case_id) {
                                 c.title = edit_title.read().trim().to_string();
                                 c.comment = edit_comment.read().trim().to_string();
+                                c.kind = edit_kind();
                             }
                             load_cases.set(cases.clone());
                             editing_id.set(None);
@@ -222,6 +292,7 @@ fn render_case_row(
                 },
                 td { class: "col-case", "{case_id}" }
                 td { class: "col-title", "{case_title}" }
+                td { class: "col-kind", "{case_kind.label()}" }
                 td { class: "col-comment", "{case_comment}" }
                 td { class: "col-actions",
                     button {
@@ -231,6 +302,7 @@ fn render_case_row(
                             editing_id.set(Some(case_id));
                             edit_title.set(title_for_edit.clone());
                             edit_comment.set(comment_for_edit.clone());
+                            edit_kind.set(case_kind);
                         },
                         "Edit"
                     }
@@ -267,7 +339,8 @@ fn update_js_load_cases(cases: &[LoadCase]) {
         serde_json::json!({
             "id": c.id,
             "title": c.title,
-            "comment": c.comment
+            "comment": c.comment,
+            "kind": c.kind.label()
         })
     }).collect();
     
@@ -0,0 +1,339 @@
+use dioxus::prelude::*;
+use plane_s::fea_client::FEALoadComboData;
+
+use crate::components::layout::{LoadCase, LoadCaseKind};
+
+/// A load combination: factors on load cases, by case id
+#[derive(Clone, PartialEq, Debug)]
+pub struct LoadCombo {
+    pub name: String,
+    pub factors: Vec<(usize, f64)>,
+    /// e.g. "strength" or "service", for filtering results and design checks
+    pub tags: Vec<String>,
+}
+
+impl LoadCombo {
+    /// One load case at factor 1.0, named after the case
+    pub fn single(case: &LoadCase) -> Self {
+        Self {
+            name: case.title.clone(),
+            factors: vec![(case.id, 1.0)],
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn factor(&self, case: usize) -> f64 {
+        self.factors.iter().find(|(id, _)| *id == case).map_or(0.0, |(_, f)| *f)
+    }
+
+    pub fn set_factor(&mut self, case: usize, factor: f64) {
+        self.factors.retain(|(id, _)| *id != case);
+        if factor != 0.0 {
+            self.factors.push((case, factor));
+            self.factors.sort_by_key(|(id, _)| *id);
+        }
+    }
+
+    /// The combination as the solver takes it, with load cases named by
+    /// their titles. Cases that no longer exist are left out.
+    pub fn to_request(&self, cases: &[LoadCase]) -> FEALoadComboData {
+        let factors = self.factors.iter()
+            .filter_map(|(id, factor)| cases.iter().find(|c| c.id == *id).map(|c| (c.title.clone(), *factor)))
+            .collect();
+        FEALoadComboData {
+            name: self.name.clone(),
+            factors,
+            tags: self.tags.clone(),
+        }
+    }
+
+    /// Combinations of a design code for the load cases. A combination is
+    /// left out when the cases lack one of the loads it is for, or when one
+    /// with the same name is in `existing`.
+    pub fn generate(code: CombinationCode, cases: &[LoadCase], existing: &[LoadCombo]) -> Vec<LoadCombo> {
+        let has = |kind: LoadCaseKind| cases.iter().any(|c| c.kind == kind);
+        let mut combos: Vec<LoadCombo> = Vec::new();
+
+        for (required, kind_factors, tags) in code.rules() {
+            if !required.iter().all(|kind| has(*kind)) {
+                continue;
+            }
+            let mut combo = LoadCombo { name: String::new(), factors: Vec::new(), tags: tags.iter().map(|t| t.to_string()).collect() };
+            let mut terms = Vec::new();
+            for (kind, factor) in CODE_KINDS.iter().zip(kind_factors) {
+                if *factor == 0.0 || !has(*kind) {
+                    continue;
+                }
+                for case in cases.iter().filter(|c| c.kind == *kind) {
+                    combo.set_factor(case.id, *factor);
+                }
+                terms.push(if *factor == 1.0 {
+                    kind.symbol().to_string()
+                } else {
+                    format!("{}{}", factor, kind.symbol())
+                });
+            }
+            combo.name = terms.join(" + ");
+
+            if !combo.factors.is_empty()
+                && !existing.iter().chain(combos.iter()).any(|c| c.name == combo.name)
+            {
+                combos.push(combo);
+            }
+        }
+        combos
+    }
+}
+
+/// Combinations to analyse: those defined, or each load case on its own
+pub fn analysis_combos(cases: &[LoadCase], combos: &[LoadCombo]) -> Vec<FEALoadComboData> {
+    if combos.is_empty() {
+        cases.iter().map(|case| LoadCombo::single(case).to_request(cases)).collect()
+    } else {
+        combos.iter().map(|combo| combo.to_request(cases)).collect()
+    }
+}
+
+/// Kinds of load the factors of a code's rules apply to, in order
+const CODE_KINDS: [LoadCaseKind; 4] = [LoadCaseKind::Dead, LoadCaseKind::Live, LoadCaseKind::Wind, LoadCaseKind::Snow];
+
+/// Loads the combination is for, factors on dead, live, wind and snow, and tags
+type CombinationRule = (&'static [LoadCaseKind], [f64; 4], &'static [&'static str]);
+
+/// Design codes whose load combinations can be generated
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CombinationCode {
+    AsceLrfd,
+    AsceAsd,
+    En1990,
+}
+
+impl CombinationCode {
+    pub const ALL: [CombinationCode; 3] = [Self::AsceLrfd, Self::AsceAsd, Self::En1990];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::AsceLrfd => "ASCE 7 LRFD",
+            Self::AsceAsd => "ASCE 7 ASD",
+            Self::En1990 => "EN 1990",
+        }
+    }
+
+    fn from_label(label: &str) -> Self {
+        Self::ALL.into_iter().find(|c| c.label() == label).unwrap_or(Self::AsceLrfd)
+    }
+
+    fn rules(&self) -> &'static [CombinationRule] {
+        use LoadCaseKind::*;
+        match self {
+            // ASCE 7 section 2.3.1
+            Self::AsceLrfd => &[
+                (&[Dead], [1.4, 0.0, 0.0, 0.0], &["strength"]),
+                (&[Live], [1.2, 1.6, 0.0, 0.5], &["strength"]),
+                (&[Snow], [1.2, 1.0, 0.0, 1.6], &["strength"]),
+                (&[Snow, Wind], [1.2, 0.0, 0.5, 1.6], &["strength"]),
+                (&[Wind], [1.2, 1.0, 1.0, 0.5], &["strength"]),
+                (&[Wind], [0.9, 0.0, 1.0, 0.0], &["strength"]),
+            ],
+            // ASCE 7 section 2.4.1
+            Self::AsceAsd => &[
+                (&[Dead], [1.0, 0.0, 0.0, 0.0], &["service"]),
+                (&[Live], [1.0, 1.0, 0.0, 0.0], &["service", "total"]),
+                (&[Snow], [1.0, 0.0, 0.0, 1.0], &["service"]),
+                (&[Live, Snow], [1.0, 0.75, 0.0, 0.75], &["service"]),
+                (&[Wind], [1.0, 0.0, 0.6, 0.0], &["service"]),
+                (&[Wind], [1.0, 0.75, 0.45, 0.75], &["service"]),
+                (&[Wind], [0.6, 0.0, 0.6, 0.0], &["service"]),
+            ],
+            // EN 1990 equation 6.10 for ultimate limit states, with ψ0 of
+            // 0.7 for imposed loads, 0.6 for wind and 0.5 for snow, and the
+            // characteristic combination for serviceability
+            Self::En1990 => &[
+                (&[Dead], [1.35, 0.0, 0.0, 0.0], &["uls"]),
+                (&[Live], [1.35, 1.5, 0.9, 0.75], &["uls"]),
+                (&[Wind], [1.35, 1.05, 1.5, 0.75], &["uls"]),
+                (&[Snow], [1.35, 1.05, 0.9, 1.5], &["uls"]),
+                (&[Wind], [1.0, 0.0, 1.5, 0.0], &["uls"]),
+                (&[Live], [1.0, 1.0, 0.0, 0.0], &["sls", "total"]),
+            ],
+        }
+    }
+}
+
+#[component]
+pub fn LoadCombosModal(
+    show: Signal<bool>,
+    load_cases: Signal<Vec<LoadCase>>,
+    mut load_combos: Signal<Vec<LoadCombo>>,
+) -> Element {
+    let mut code = use_signal(|| CombinationCode::AsceLrfd);
+
+    if !show() {
+        return rsx! {};
+    }
+
+    let cases_list = load_cases.read().clone();
+    let combos_list = load_combos.read().clone();
+
+    rsx! {
+        div {
+            class: "modal-overlay",
+            onclick: move |_| show.set(false),
+
+            div {
+                class: "modal-content load-cases-modal load-combos-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "modal-header",
+                    h3 { "Load Combinations" }
+                    button {
+                        class: "modal-close-btn",
+                        onclick: move |_| show.set(false),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-body",
+                    if combos_list.is_empty() {
+                        p { class: "combos-empty",
+                            "No combinations defined: each load case is analysed on its own."
+                        }
+                    } else {
+                        div { class: "load-cases-table-container",
+                            table { class: "load-cases-table",
+                                thead {
+                                    tr {
+                                        th { class: "col-title", "Name" }
+                                        for case in cases_list.iter() {
+                                            th { class: "col-factor", title: "{case.title}", "{case.id}: {case.title}" }
+                                        }
+                                        th { class: "col-tags", "Tags" }
+                                        th { class: "col-actions", "" }
+                                    }
+                                }
+                                tbody {
+                                    for (index, combo) in combos_list.iter().enumerate() {
+                                        {render_combo_row(index, combo.clone(), &cases_list, load_combos)}
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "add-case-form",
+                        h4 { "Add Combinations" }
+                        div { class: "form-row",
+                            button {
+                                class: "btn-add",
+                                onclick: move |_| {
+                                    let cases = load_cases.read().clone();
+                                    let mut combos = load_combos.write();
+                                    let number = (combos.len() + 1..)
+                                        .find(|n| !combos.iter().any(|c| c.name == format!("Combo {n}")))
+                                        .unwrap_or(1);
+                                    let mut combo = cases.first().map(LoadCombo::single).unwrap_or(LoadCombo {
+                                        name: String::new(),
+                                        factors: Vec::new(),
+                                        tags: Vec::new(),
+                                    });
+                                    combo.name = format!("Combo {number}");
+                                    combos.push(combo);
+                                },
+                                "Add Combination"
+                            }
+                            div { class: "form-field flex-grow" }
+                            div { class: "form-field",
+                                label { "Design Code" }
+                                select {
+                                    value: "{code().label()}",
+                                    onchange: move |e| code.set(CombinationCode::from_label(&e.value())),
+                                    for c in CombinationCode::ALL {
+                                        option { value: "{c.label()}", selected: code() == c, "{c.label()}" }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "btn-add",
+                                title: "Add the code's combinations for the dead, live, wind and snow load cases",
+                                onclick: move |_| {
+                                    let cases = load_cases.read().clone();
+                                    let generated = LoadCombo::generate(code(), &cases, &load_combos.read());
+                                    load_combos.write().extend(generated);
+                                },
+                                "Generate"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_combo_row(
+    index: usize,
+    combo: LoadCombo,
+    cases: &[LoadCase],
+    mut load_combos: Signal<Vec<LoadCombo>>,
+) -> Element {
+    let factors: Vec<(usize, f64)> = cases.iter().map(|c| (c.id, combo.factor(c.id))).collect();
+    let tags = combo.tags.join(", ");
+
+    rsx! {
+        tr { key: "{index}",
+            td { class: "col-title",
+                input {
+                    r#type: "text",
+                    class: "edit-input",
+                    value: "{combo.name}",
+                    oninput: move |e| {
+                        if let Some(c) = load_combos.write().get_mut(index) {
+                            c.name = e.value();
+                        }
+                    },
+                }
+            }
+            for (case_id, factor) in factors {
+                td { class: "col-factor",
+                    input {
+                        r#type: "number",
+                        class: "edit-input",
+                        step: "0.05",
+                        value: "{factor}",
+                        oninput: move |e| {
+                            let factor = e.value().parse::<f64>().unwrap_or(0.0);
+                            if let Some(c) = load_combos.write().get_mut(index) {
+                                c.set_factor(case_id, factor);
+                            }
+                        },
+                    }
+                }
+            }
+            td { class: "col-tags",
+                input {
+                    r#type: "text",
+                    class: "edit-input",
+                    placeholder: "e.g. strength",
+                    value: "{tags}",
+                    onchange: move |e| {
+                        if let Some(c) = load_combos.write().get_mut(index) {
+                            c.tags = e.value().split(',')
+                                .map(|t| t.trim().to_string())
+                                .filter(|t| !t.is_empty())
+                                .collect();
+                        }
+                    },
+                }
+            }
+            td { class: "col-actions",
+                button {
+                    class: "btn-delete",
+                    onclick: move |_| {
+                        load_combos.write().remove(index);
+                    },
+                    "×"
+                }
+            }
+        }
+    }
+}
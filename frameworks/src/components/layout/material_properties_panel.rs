@@ -1,4 +1,7 @@
 use dioxus::prelude::*;
+use plane_s::units::{Quantity, Units};
+
+use crate::hooks::use_design_state::DesignState;
 
 #[derive(Clone, PartialEq)]
 pub struct MaterialProperties {
@@ -77,6 +80,10 @@ pub fn MaterialPropertiesPanel(
     let mut poisson_ratio = use_signal(|| properties().poisson_ratio);
     let mut density = use_signal(|| properties().density);
     let mut custom_mode = use_signal(|| false);
+    let units = use_context::<DesignState>().units;
+    // Moduli as shown, from the GPa they are kept in
+    let shown_modulus = move |gpa: f64| Units::SCENE.convert(Quantity::Modulus, gpa, units());
+    let modulus_unit = units().symbol(Quantity::Modulus);
 
     // Update parent properties when values change
     use_effect(move || {
@@ -179,13 +186,13 @@ pub fn MaterialPropertiesPanel(
                     h4 { "Properties" }
                     
                     div { class: "property-group",
-                        label { "Elastic Modulus E (GPa)" }
+                        label { "Elastic Modulus E ({modulus_unit})" }
                         input {
                             r#type: "number",
-                            value: "{elastic_modulus}",
+                            value: "{shown_modulus(elastic_modulus())}",
                             oninput: move |evt| {
                                 if let Ok(val) = evt.value().parse::<f64>() {
-                                    elastic_modulus.set(val);
+                                    elastic_modulus.set(units().convert(Quantity::Modulus, val, Units::SCENE));
                                     custom_mode.set(true);
                                     material_type.set("custom".to_string());
                                 }
@@ -242,12 +249,13 @@ pub fn MaterialPropertiesPanel(
                             let g = e / (2.0 * (1.0 + nu));
                             // Bulk modulus K = E / (3(1 - 2ν))
                             let k = if nu < 0.5 { e / (3.0 * (1.0 - 2.0 * nu)) } else { f64::INFINITY };
-                            let k_str = if k.is_infinite() { "Inf".to_string() } else { format!("{:.2} GPa", k) };
+                            let g = shown_modulus(g);
+                            let k_str = if k.is_infinite() { "Inf".to_string() } else { format!("{:.2} {modulus_unit}", shown_modulus(k)) };
                             
                             rsx! {
                                 div { class: "prop-row",
                                     span { class: "prop-label", "Shear Modulus G:" }
-                                    span { class: "prop-value", "{g:.2} GPa" }
+                                    span { class: "prop-value", "{g:.2} {modulus_unit}" }
                                 }
                                 div { class: "prop-row",
                                     span { class: "prop-label", "Bulk Modulus K:" }
@@ -262,7 +270,7 @@ pub fn MaterialPropertiesPanel(
                 div { class: "section",
                     p { class: "help-text info-box",
                         "These properties will be applied to all elements in the analysis. "
-                        "Units: Forces in kN, lengths in m, stresses in kPa."
+                        "Values are in {units().name()}, as chosen in the console."
                     }
                 }
             }
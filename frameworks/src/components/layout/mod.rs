@@ -15,7 +15,16 @@ pub mod beam_properties_panel;
 pub mod shell_properties_panel;
 pub mod material_properties_panel;
 pub mod load_cases_modal;
+pub mod load_combos_modal;
 pub mod split_beam_panel;
+pub mod results_toolbar;
+pub mod contour_legend;
+pub mod grid_modal;
+pub mod select_by_modal;
+pub mod copy_panel;
+pub mod coordinate_bar;
+pub mod display_settings_panel;
+pub mod import_dxf_modal;
 
 
 pub use analysis_panel::AnalysisPanel;
@@ -34,5 +43,14 @@ pub use mesh_panel::MeshPanel;
 pub use beam_properties_panel::{BeamPropertiesPanel, BeamProperties};
 pub use shell_properties_panel::{ShellPropertiesPanel, ShellProperties};
 pub use material_properties_panel::{MaterialPropertiesPanel, MaterialProperties};
-pub use load_cases_modal::{LoadCasesModal, LoadCase};
-pub use split_beam_panel::SplitBeamPanel;
\ No newline at end of file
+pub use load_cases_modal::{LoadCasesModal, LoadCase, LoadCaseKind};
+pub use load_combos_modal::{LoadCombosModal, LoadCombo};
+pub use split_beam_panel::SplitBeamPanel;
+pub use results_toolbar::ResultsToolbar;
+pub use contour_legend::ContourLegend;
+pub use grid_modal::{GridModal, GridSystem};
+pub use select_by_modal::SelectByModal;
+pub use copy_panel::CopyPanel;
+pub use coordinate_bar::{CoordinateBar, DrawSettings};
+pub use display_settings_panel::{DisplaySettingsPanel, DisplaySettings};
+pub use import_dxf_modal::ImportDxfModal;
\ No newline at end of file
@@ -1,15 +1,20 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+
+use crate::hooks::use_design_state::DesignState;
 
 #[component]
 pub fn PointLoadPanel(show_panel: Signal<bool>) -> Element {
+    let units = use_context::<DesignState>().units;
     let mut magnitude = use_signal(|| String::from("10.0"));
     let mut direction = use_signal(|| String::from("y"));
     let mut position = use_signal(|| String::from("0.5"));
     let mut color = use_signal(|| String::from("#ff0000"));
 
     let apply_load = move |_| {
-        let mag_value = magnitude.read().clone();
+        // Entered in the display units, applied in the scene's
+        let mag_value = units().convert(Quantity::Force, magnitude.read().parse().unwrap_or(0.0), Units::SCENE);
         let dir_value = direction.read().clone();
         let pos_value = position.read().clone();
         let color_value = color.read().clone();
@@ -32,6 +37,8 @@ pub fn PointLoadPanel(show_panel: Signal<bool>) -> Element {
         eval(&js_code);
     };
 
+    let magnitude_label = units().label("Magnitude", Quantity::Force);
+
     let close_panel = move |_| {
         show_panel.set(false);
     };
@@ -58,7 +65,7 @@ pub fn PointLoadPanel(show_panel: Signal<bool>) -> Element {
                 
                 // Magnitude input
                 div { class: "form-group",
-                    label { r#for: "magnitude", "Magnitude (kN)" }
+                    label { r#for: "magnitude", "{magnitude_label}" }
                     input {
                         r#type: "number",
                         id: "magnitude",
@@ -1,13 +1,18 @@
 use dioxus::prelude::*;
 use dioxus::document::eval;
+use plane_s::units::{Quantity, Units};
+
+use crate::hooks::use_design_state::DesignState;
 
 #[component]
 pub fn PressureLoadPanel(show_panel: Signal<bool>) -> Element {
+    let units = use_context::<DesignState>().units;
     let mut magnitude = use_signal(|| String::from("5.0"));
     let mut color = use_signal(|| String::from("#ff0000"));
 
     let apply_load = move |_| {
-        let mag_value = magnitude.read().clone();
+        // Entered in the display units, applied in the scene's
+        let mag_value = units().convert(Quantity::Pressure, magnitude.read().parse().unwrap_or(0.0), Units::SCENE);
         let color_value = color.read().clone();
         
         // Call JavaScript function to apply load
@@ -26,6 +31,8 @@ pub fn PressureLoadPanel(show_panel: Signal<bool>) -> Element {
         eval(&js_code);
     };
 
+    let magnitude_label = units().label("Magnitude", Quantity::Pressure);
+
     let close_panel = move |_| {
         show_panel.set(false);
     };
@@ -52,7 +59,7 @@ pub fn PressureLoadPanel(show_panel: Signal<bool>) -> Element {
                 
                 // Magnitude input
                 div { class: "form-group",
-                    label { r#for: "magnitude", "Magnitude (kPa)" }
+                    label { r#for: "magnitude", "{magnitude_label}" }
                     input {
                         r#type: "number",
                         id: "magnitude",
@@ -0,0 +1,144 @@
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use js_sys::Promise;
+use serde::Deserialize;
+use wasm_bindgen_futures::JsFuture;
+
+use plane_s::deformation::{DeformedShape, Motion};
+use plane_s::fea_client::{FEAModeResult, FEANodeData, FEANodeDisplacementResult};
+
+use crate::components::visualization::three_bindings::{
+    begin_deformed_animation, end_deformed_animation, set_deformed_frame,
+};
+
+/// Frames in one cycle of an animation, and the time each is shown
+const FRAMES_PER_CYCLE: usize = 40;
+const FRAME_MS: i32 = 33;
+
+/// What a deformed shape is drawn from: the analysed nodes, and the
+/// displacements of the combination shown and the modes found
+#[derive(Deserialize)]
+struct ShapeData {
+    nodes: Vec<FEANodeData>,
+    displacements: Vec<FEANodeDisplacementResult>,
+    modes: Vec<FEAModeResult>,
+}
+
+async fn shape_data() -> Option<ShapeData> {
+    let value = eval(r#"
+        return {
+            nodes: window.feaModel ? window.feaModel.nodes : [],
+            displacements: window.feaResults ? window.feaResults.node_displacements : [],
+            modes: (window.feaResults && window.feaResults.modes) || []
+        };
+    "#).await.ok()?;
+    serde_json::from_value(value).ok()
+}
+
+async fn wait(ms: i32) {
+    let delay = Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+        }
+    });
+    JsFuture::from(delay).await.ok();
+}
+
+/// Deformed shape and mode shape display for the results: the deflection
+/// of the combination shown or a mode, drawn at the deform scale or
+/// animated, with the frames generated here and streamed to the scene
+#[component]
+pub fn ResultsToolbar(deform_scale: Signal<f64>, mode_frequencies: Signal<Vec<f64>>) -> Element {
+    // 0 for the deflection, otherwise the mode number
+    let mut shape = use_signal(|| 0_usize);
+    let mut playing = use_signal(|| false);
+    // Bumped to stop the running animation
+    let mut run = use_signal(|| 0_u32);
+
+    let mut show = move |animate: bool| {
+        let id = *run.peek() + 1;
+        run.set(id);
+        playing.set(animate);
+        spawn(async move {
+            let Some(data) = shape_data().await else {
+                playing.set(false);
+                return;
+            };
+            let (deformed, motion) = match shape() {
+                0 => (DeformedShape::from_displacements(&data.nodes, &data.displacements), Motion::Load),
+                mode => match data.modes.get(mode - 1) {
+                    Some(m) => (DeformedShape::from_mode(&data.nodes, m), Motion::Vibration),
+                    None => {
+                        playing.set(false);
+                        return;
+                    }
+                },
+            };
+            let factor = deformed.auto_scale(deform_scale());
+            if !begin_deformed_animation(deformed.nodes.clone()) {
+                playing.set(false);
+                return;
+            }
+            if !animate {
+                set_deformed_frame(&deformed.frame(factor));
+                return;
+            }
+
+            let frames = deformed.frames(factor, FRAMES_PER_CYCLE, motion);
+            for frame in frames.iter().cycle() {
+                // Stopped, or another diagram replaced the drawing
+                if *run.peek() != id || !set_deformed_frame(frame) {
+                    break;
+                }
+                wait(FRAME_MS).await;
+            }
+            if *run.peek() == id {
+                playing.set(false);
+            }
+        });
+    };
+
+    let mut stop = move || {
+        run += 1;
+        playing.set(false);
+        end_deformed_animation();
+    };
+
+    use_drop(move || run += 1);
+
+    rsx! {
+        div { class: "results-toolbar",
+            select {
+                class: "analysis-type-select",
+                value: "{shape}",
+                title: "Deflection of the combination shown, or a mode of vibration",
+                onchange: move |evt| {
+                    shape.set(evt.value().parse().unwrap_or(0));
+                    if playing() {
+                        show(true);
+                    }
+                },
+                option { value: "0", selected: shape() == 0, "Deflection" }
+                for (index, frequency) in mode_frequencies.read().iter().enumerate() {
+                    option {
+                        value: "{index + 1}",
+                        selected: shape() == index + 1,
+                        "Mode {index + 1} ({frequency:.2} Hz)"
+                    }
+                }
+            }
+            button {
+                class: "diagram-btn",
+                title: "Draw the shape at the deform scale",
+                onclick: move |_| show(false),
+                "Show"
+            }
+            button {
+                class: if playing() { "diagram-btn active" } else { "diagram-btn" },
+                title: "Animate the shape at the deform scale",
+                onclick: move |_| if playing() { stop() } else { show(true) },
+                if playing() { "Stop" } else { "Animate" }
+            }
+        }
+    }
+}
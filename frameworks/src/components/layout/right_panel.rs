@@ -202,7 +202,7 @@ pub fn RightPanel(show_extrude: Signal<bool>) -> Element {
                         button {
                             class: "btn-secondary",
                             onclick: move |_| {
-                                eval("if(window.undoLastAction) { window.undoLastAction(); } else { console.error('undoLastAction not available'); }");
+                                eval("if(window.undoEdit) { window.undoEdit(); } else { console.error('undoEdit not available'); }");
                             },
                             "Undo"
                         }
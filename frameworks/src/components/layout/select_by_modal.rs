@@ -0,0 +1,234 @@
+use dioxus::prelude::*;
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
+use crate::hooks::use_model_store::{ModelStore, SelectQuery};
+
+/// What a "select by" query picks out
+#[derive(Clone, Copy, PartialEq)]
+enum QueryKind {
+    Section,
+    Elevation,
+    Thickness,
+}
+
+impl QueryKind {
+    const ALL: [QueryKind; 3] = [QueryKind::Section, QueryKind::Elevation, QueryKind::Thickness];
+
+    fn key(&self) -> &'static str {
+        match self {
+            QueryKind::Section => "section",
+            QueryKind::Elevation => "elevation",
+            QueryKind::Thickness => "thickness",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            QueryKind::Section => "Members with section",
+            QueryKind::Elevation => "Nodes at elevation",
+            QueryKind::Thickness => "Plates thicker than",
+        }
+    }
+}
+
+/// Select part of the model by a query over the model store, rather than by
+/// picking in the viewport
+#[component]
+pub fn SelectByModal(show: Signal<bool>) -> Element {
+    let design_state = use_context::<DesignState>();
+    let storeys = design_state.storeys;
+    let units = design_state.units;
+    let model_store = use_context::<ModelStore>();
+
+    let mut kind = use_signal(|| QueryKind::Section);
+    // Section name; empty for members left with the default section
+    let mut section = use_signal(String::new);
+    let mut elevation_text = use_signal(String::new);
+    let mut thickness_text = use_signal(String::new);
+    let mut add = use_signal(|| false);
+    let mut message = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    if !show() {
+        return rsx! {};
+    }
+
+    // A length typed in the display units, in metres
+    let length = move |text: &str, what: &str| -> Result<f64, String> {
+        let value: f64 = text.trim().parse().map_err(|_| format!("Enter the {what} as a number"))?;
+        Ok(units.peek().to_si(Quantity::Length, value))
+    };
+
+    // The query as entered, or why it cannot be run
+    let query = move || -> Result<SelectQuery, String> {
+        Ok(match kind() {
+            QueryKind::Section => {
+                let name = section();
+                SelectQuery::MembersWithSection((!name.is_empty()).then_some(name))
+            }
+            QueryKind::Elevation => SelectQuery::NodesAtElevation(length(&elevation_text(), "elevation")?),
+            QueryKind::Thickness => SelectQuery::PlatesThickerThan(length(&thickness_text(), "thickness")?),
+        })
+    };
+
+    let mut run = move || {
+        message.set(None);
+        match query() {
+            Ok(query) => {
+                error.set(None);
+                let selection = model_store.model.read().select(&query);
+                if selection.is_empty() {
+                    message.set(Some("Nothing matches".to_string()));
+                    return;
+                }
+                selection.apply(add());
+                let count = |n: usize, what: &str| match n {
+                    0 => None,
+                    1 => Some(format!("1 {what}")),
+                    n => Some(format!("{n} {what}s")),
+                };
+                let counts: Vec<String> = [
+                    count(selection.nodes.len(), "node"),
+                    count(selection.members.len(), "member"),
+                    count(selection.plates.len(), "plate"),
+                ].into_iter().flatten().collect();
+                message.set(Some(format!("Selected {}", counts.join(", "))));
+            }
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    let section_names: Vec<String> = model_store.model.read().assigned_sections().iter()
+        .map(|s| s.name.clone())
+        .collect();
+    let length_unit = units().symbol(Quantity::Length);
+    let storey_options: Vec<(String, String)> = storeys().iter()
+        .map(|s| (s.name.clone(), format!("{}", units().from_si(Quantity::Length, s.elevation))))
+        .collect();
+
+    rsx! {
+        div {
+            class: "modal-overlay",
+            onclick: move |_| show.set(false),
+
+            div {
+                class: "modal-content load-cases-modal select-by-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "modal-header",
+                    h3 { "Select By" }
+                    button {
+                        class: "modal-close-btn",
+                        onclick: move |_| show.set(false),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-body",
+                    div { class: "add-case-form",
+                        div { class: "form-row",
+                            div { class: "form-field flex-grow",
+                                label { "Select" }
+                                select {
+                                    value: "{kind().key()}",
+                                    onchange: move |e| {
+                                        if let Some(chosen) = QueryKind::ALL.into_iter().find(|k| k.key() == e.value()) {
+                                            kind.set(chosen);
+                                            message.set(None);
+                                            error.set(None);
+                                        }
+                                    },
+                                    for option_kind in QueryKind::ALL {
+                                        option {
+                                            value: "{option_kind.key()}",
+                                            selected: kind() == option_kind,
+                                            "{option_kind.name()}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "form-row",
+                            match kind() {
+                                QueryKind::Section => rsx! {
+                                    div { class: "form-field flex-grow",
+                                        label { "Section" }
+                                        select {
+                                            value: "{section}",
+                                            onchange: move |e| section.set(e.value()),
+                                            option { value: "", selected: section().is_empty(), "Default (unassigned)" }
+                                            for name in section_names {
+                                                option {
+                                                    value: "{name}",
+                                                    selected: section() == name,
+                                                    "{name}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                },
+                                QueryKind::Elevation => rsx! {
+                                    div { class: "form-field flex-grow",
+                                        label { "Elevation ({length_unit})" }
+                                        input {
+                                            r#type: "number",
+                                            step: "any",
+                                            list: "select-by-storeys",
+                                            value: "{elevation_text}",
+                                            oninput: move |e| elevation_text.set(e.value()),
+                                        }
+                                        datalist { id: "select-by-storeys",
+                                            for (name, elevation) in storey_options {
+                                                option { value: "{elevation}", "{name}" }
+                                            }
+                                        }
+                                    }
+                                },
+                                QueryKind::Thickness => rsx! {
+                                    div { class: "form-field flex-grow",
+                                        label { "Thickness ({length_unit})" }
+                                        input {
+                                            r#type: "number",
+                                            step: "any",
+                                            min: "0",
+                                            value: "{thickness_text}",
+                                            oninput: move |e| thickness_text.set(e.value()),
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                        if kind() == QueryKind::Thickness {
+                            p { class: "grid-hint", "Plates taking the default thickness are not included." }
+                        }
+                        div { class: "form-row select-by-actions",
+                            label {
+                                input {
+                                    r#type: "checkbox",
+                                    checked: add(),
+                                    onchange: move |e| add.set(e.checked()),
+                                }
+                                "Add to current selection"
+                            }
+                            button {
+                                class: "btn-add",
+                                onclick: move |_| run(),
+                                "Select"
+                            }
+                        }
+                    }
+
+                    if let Some(m) = message() {
+                        p { class: "grid-hint", "{m}" }
+                    }
+                    if let Some(e) = error() {
+                        div { class: "analysis-error",
+                            div { class: "error-text", "{e}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
 use dioxus::prelude::*;
+use plane_s::units::Quantity;
+
+use crate::hooks::use_design_state::DesignState;
 
 #[derive(Clone, PartialEq)]
 pub struct ShellProperties {
@@ -22,6 +25,9 @@ pub fn ShellPropertiesPanel(
 ) -> Element {
     let mut thickness = use_signal(|| properties().thickness);
     let mut is_quadratic = use_signal(|| properties().is_quadratic);
+    let units = use_context::<DesignState>().units;
+    let thickness_label = units().label("Thickness", Quantity::Length);
+    let shown_thickness = units().from_si(Quantity::Length, thickness());
 
     // Update parent properties when values change
     use_effect(move || {
@@ -53,13 +59,13 @@ pub fn ShellPropertiesPanel(
                 div { class: "section",
                     h4 { "Plate Thickness" }
                     div { class: "property-group",
-                        label { "Thickness (m)" }
+                        label { "{thickness_label}" }
                         input {
                             r#type: "number",
-                            value: "{thickness}",
+                            value: "{shown_thickness}",
                             oninput: move |evt| {
                                 if let Ok(val) = evt.value().parse::<f64>() {
-                                    thickness.set(val);
+                                    thickness.set(units().to_si(Quantity::Length, val));
                                 }
                             },
                             step: "0.01",
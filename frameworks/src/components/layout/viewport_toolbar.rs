@@ -3,9 +3,11 @@ use dioxus::document::eval;
 use crate::components::visualization::three_bindings::{
     toggle_add_node_mode, toggle_select_node_mode, toggle_draw_beam_mode, toggle_draw_plate_mode,
     select_all_nodes, clear_node_selection, delete_selected, set_plan_view, reset_view,
+    undo_edit, redo_edit,
 };
 use crate::hooks::use_design_state::{DesignState, ViewMode};
-use crate::components::layout::{LoadCasesModal, LoadCase};
+use crate::hooks::use_model_store::{ModelStore, ViewportModel};
+use crate::components::layout::{GridModal, ImportDxfModal, LoadCasesModal, LoadCombosModal, SelectByModal};
 
 // Clean SVG icons as inline strings
 const ICON_NODE: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor"><circle cx="12" cy="12" r="4"/></svg>"#;
@@ -23,6 +25,7 @@ const ICON_RUN: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor"><polygon
 const ICON_CONSTRAINT: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><path d="M12 4v8"/><path d="M6 12l12 0"/><path d="M4 16l16 0"/><path d="M6 20l2-4M12 20v-4M18 20l-2-4"/></svg>"#;
 const ICON_MESH: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="4" y="4" width="16" height="16"/><line x1="4" y1="12" x2="20" y2="12"/><line x1="12" y1="4" x2="12" y2="20"/></svg>"#;
 const ICON_SELECT: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><path d="M4 4l7 18 2-7 7-2L4 4z"/></svg>"#;
+const ICON_SELECT_BY: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><path d="M4 5h16l-6 7v6l-4 2v-8z"/></svg>"#;
 const ICON_SELECT_ALL: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="3" y="3" width="18" height="18" rx="2" stroke-dasharray="3 3"/><rect x="7" y="7" width="10" height="10" rx="1"/></svg>"#;
 const ICON_CLEAR: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="2" fill="none"><line x1="6" y1="6" x2="18" y2="18"/><line x1="6" y1="18" x2="18" y2="6"/></svg>"#;
 const ICON_DELETE: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><path d="M4 7h16M10 11v6M14 11v6M5 7l1 12a2 2 0 0 0 2 2h8a2 2 0 0 0 2-2l1-12M9 7V4a1 1 0 0 1 1-1h4a1 1 0 0 1 1 1v3"/></svg>"#;
@@ -31,6 +34,9 @@ const ICON_ORBIT: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" strok
 const ICON_PLAN: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="4" y="4" width="16" height="16" rx="1"/><circle cx="12" cy="12" r="2" fill="currentColor"/></svg>"#;
 const ICON_EXAMPLE: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="4" y="14" width="16" height="6" rx="1"/><rect x="6" y="8" width="12" height="6" rx="1"/><rect x="8" y="2" width="8" height="6" rx="1"/></svg>"#;
 const ICON_LOAD_CASES: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="3" y="3" width="18" height="18" rx="2"/><line x1="3" y1="9" x2="21" y2="9"/><line x1="3" y1="15" x2="21" y2="15"/><line x1="9" y1="3" x2="9" y2="21"/></svg>"#;
+const ICON_LOAD_COMBOS: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><rect x="3" y="3" width="18" height="18" rx="2"/><path d="M8 12h8M12 8v8"/></svg>"#;
+const ICON_IMPORT: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><path d="M12 3v12M7 10l5 5 5-5"/><path d="M4 17v3h16v-3"/></svg>"#;
+const ICON_GRID: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="1.5" fill="none"><line x1="8" y1="3" x2="8" y2="21" stroke-dasharray="3 2"/><line x1="16" y1="3" x2="16" y2="21" stroke-dasharray="3 2"/><line x1="3" y1="8" x2="21" y2="8" stroke-dasharray="3 2"/><line x1="3" y1="16" x2="21" y2="16" stroke-dasharray="3 2"/></svg>"#;
 const ICON_SPLIT_BEAM: &str = r#"<svg viewBox="0 0 24 24" stroke="currentColor" stroke-width="2" fill="none"><line x1="4" y1="12" x2="10" y2="12"/><line x1="14" y1="12" x2="20" y2="12"/><circle cx="12" cy="12" r="2" fill="currentColor"/><line x1="12" y1="6" x2="12" y2="9" stroke-dasharray="2 1"/><line x1="12" y1="15" x2="12" y2="18" stroke-dasharray="2 1"/></svg>"#;
 
 #[component]
@@ -46,22 +52,29 @@ pub fn ViewportToolbar(
     show_shell_properties: Signal<bool>,
     show_material_properties: Signal<bool>,
     show_split_beam_panel: Signal<bool>,
+    show_copy_panel: Signal<bool>,
+    show_display_panel: Signal<bool>,
 ) -> Element {
     let mut grid_visible = use_signal(|| true);
     let mut axes_visible = use_signal(|| true);
     let mut active_tool = use_signal(|| "none".to_string());
     let mut selection_filter = use_signal(|| "all".to_string());
     
-    // Load cases state
     let mut show_load_cases_modal = use_signal(|| false);
-    let mut load_cases = use_signal(|| vec![LoadCase::default()]);
-    let mut active_load_case = use_signal(|| 1usize);
+    let mut show_load_combos_modal = use_signal(|| false);
+    let mut show_grid_modal = use_signal(|| false);
+    let mut show_select_by_modal = use_signal(|| false);
+    let mut show_import_dxf_modal = use_signal(|| false);
     
-    // Get design state to track view mode
+    // Get design state to track view mode and load cases
     let design_state = use_context::<DesignState>();
+    let load_cases = design_state.load_cases;
+    let mut active_load_case = design_state.active_load_case;
+    let load_combos = design_state.load_combos;
     let mut ds_for_toggle = design_state.clone();
     let mut ds_for_shortcuts = design_state.clone();
     let view_mode = design_state.view_mode.read();
+    let mut model_store = use_context::<ModelStore>();
 
     // Keyboard shortcuts
     let mut init_shortcuts = use_signal(|| false);
@@ -79,10 +92,18 @@ pub fn ViewportToolbar(
 
                 let key = e.key.toUpperCase();
                 let shift = e.shiftKey;
+                let ctrl = e.ctrlKey || e.metaKey;
+                
+                // Undo/redo: Ctrl+Z, Ctrl+Y and Ctrl+Shift+Z
+                if (ctrl && (key === 'Z' || key === 'Y')) {
+                    e.preventDefault();
+                    dioxus.send({ key, shift, ctrl });
+                    return;
+                }
                 
                 // Send relevant keys to Rust
                 if (['A', 'X', 'E', 'V', 'S', 'P', 'N', 'B', 'DELETE', 'BACKSPACE'].includes(key)) {
-                    dioxus.send({ key, shift });
+                    dioxus.send({ key, shift, ctrl });
                 }
             });
         "#);
@@ -94,8 +115,12 @@ pub fn ViewportToolbar(
                 if let Ok(val) = serde_json::from_value::<serde_json::Value>(msg) {
                     let key = val["key"].as_str().unwrap_or("");
                     let shift = val["shift"].as_bool().unwrap_or(false);
+                    let ctrl = val["ctrl"].as_bool().unwrap_or(false);
                     
                     match key {
+                        "Z" if ctrl && shift => { redo_edit(); },
+                        "Z" if ctrl => { undo_edit(); },
+                        "Y" if ctrl => { redo_edit(); },
                         "A" => select_all_nodes(),
                         "X" => clear_node_selection(),
                         "E" => show_extrude_panel.set(!show_extrude_panel()),
@@ -172,10 +197,27 @@ pub fn ViewportToolbar(
                                 class: "tool-button-icon",
                                 title: "Load Example 3-Story Building",
                                 onclick: move |_| {
-                                    eval("if (window.loadExampleStructure) { const result = window.loadExampleStructure(); console.log('Example structure loaded:', result); }");
+                                    model_store.set(ViewportModel::example_building(), "Load example building");
+                                    reset_view();
                                 },
                                 span { class: "btn-icon", dangerous_inner_html: ICON_EXAMPLE }
                             }
+                            button {
+                                class: "tool-button-icon",
+                                title: "Grids & Storeys",
+                                onclick: move |_| {
+                                    show_grid_modal.set(true);
+                                },
+                                span { class: "btn-icon", dangerous_inner_html: ICON_GRID }
+                            }
+                            button {
+                                class: "tool-button-icon",
+                                title: "Import DXF",
+                                onclick: move |_| {
+                                    show_import_dxf_modal.set(true);
+                                },
+                                span { class: "btn-icon", dangerous_inner_html: ICON_IMPORT }
+                            }
                             button {
                                 class: if active_tool() == "add_node" { "tool-button-icon active" } else { "tool-button-icon" },
                                 title: "Add Node (N)",
@@ -232,6 +274,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_extrude_panel.set(opening);
                                 },
@@ -241,7 +285,22 @@ pub fn ViewportToolbar(
                                 class: "tool-button-icon",
                                 title: "Copy Elements",
                                 onclick: move |_| {
-                                    eval("if (window.startCopyElements) window.startCopyElements();");
+                                    let opening = !show_copy_panel();
+                                    if opening {
+                                        show_extrude_panel.set(false);
+                                        show_constraints_panel.set(false);
+                                        show_point_load_panel.set(false);
+                                        show_distributed_load_panel.set(false);
+                                        show_pressure_load_panel.set(false);
+                                        show_analysis_panel.set(false);
+                                        show_mesh_panel.set(false);
+                                        show_beam_properties.set(false);
+                                        show_shell_properties.set(false);
+                                        show_material_properties.set(false);
+                                        show_split_beam_panel.set(false);
+                                        show_display_panel.set(false);
+                                    }
+                                    show_copy_panel.set(opening);
                                 },
                                 span { class: "btn-icon", dangerous_inner_html: ICON_COPY }
                             }
@@ -262,6 +321,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_split_beam_panel.set(opening);
                                 },
@@ -292,6 +353,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_beam_properties.set(opening);
                                 },
@@ -313,6 +376,8 @@ pub fn ViewportToolbar(
                                         show_beam_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_shell_properties.set(opening);
                                 },
@@ -375,6 +440,14 @@ pub fn ViewportToolbar(
                                 },
                                 span { class: "btn-icon", dangerous_inner_html: ICON_LOAD_CASES }
                             }
+                            button {
+                                class: "tool-button-icon",
+                                title: "Load Combinations",
+                                onclick: move |_| {
+                                    show_load_combos_modal.set(true);
+                                },
+                                span { class: "btn-icon", dangerous_inner_html: ICON_LOAD_COMBOS }
+                            }
                             button {
                                 class: "tool-button-icon",
                                 title: "Point Load",
@@ -391,6 +464,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_point_load_panel.set(opening);
                                 },
@@ -412,6 +487,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_distributed_load_panel.set(opening);
                                 },
@@ -433,6 +510,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_pressure_load_panel.set(opening);
                                 },
@@ -440,9 +519,24 @@ pub fn ViewportToolbar(
                             }
                             button {
                                 class: "tool-button-icon",
-                                title: "Toggle Load Visibility",
+                                title: "Load and Support Display",
                                 onclick: move |_| {
-                                    eval(r#"window.toggleLoadsVisibility();"#);
+                                    let opening = !show_display_panel();
+                                    if opening {
+                                        show_extrude_panel.set(false);
+                                        show_constraints_panel.set(false);
+                                        show_point_load_panel.set(false);
+                                        show_distributed_load_panel.set(false);
+                                        show_pressure_load_panel.set(false);
+                                        show_analysis_panel.set(false);
+                                        show_mesh_panel.set(false);
+                                        show_beam_properties.set(false);
+                                        show_shell_properties.set(false);
+                                        show_material_properties.set(false);
+                                        show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                    }
+                                    show_display_panel.set(opening);
                                 },
                                 span { class: "btn-icon", dangerous_inner_html: ICON_VISIBLE }
                             }
@@ -471,6 +565,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_analysis_panel.set(opening);
                                 },
@@ -501,6 +597,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_constraints_panel.set(opening);
                                 },
@@ -522,6 +620,8 @@ pub fn ViewportToolbar(
                                         show_shell_properties.set(false);
                                         show_material_properties.set(false);
                                         show_split_beam_panel.set(false);
+                                        show_copy_panel.set(false);
+                                        show_display_panel.set(false);
                                     }
                                     show_mesh_panel.set(opening);
                                 },
@@ -599,6 +699,14 @@ pub fn ViewportToolbar(
                                 },
                                 span { class: "btn-icon", dangerous_inner_html: ICON_SELECT_ALL }
                             }
+                            button {
+                                class: "tool-button-icon",
+                                title: "Select By Section, Elevation or Thickness",
+                                onclick: move |_| {
+                                    show_select_by_modal.set(true);
+                                },
+                                span { class: "btn-icon", dangerous_inner_html: ICON_SELECT_BY }
+                            }
                             button {
                                 class: "tool-button-icon",
                                 title: "Clear Selection (Esc)",
@@ -736,6 +844,24 @@ pub fn ViewportToolbar(
             load_cases: load_cases,
             active_case: active_load_case,
         }
+        
+        LoadCombosModal {
+            show: show_load_combos_modal,
+            load_cases: load_cases,
+            load_combos: load_combos,
+        }
+        
+        GridModal {
+            show: show_grid_modal,
+        }
+        
+        SelectByModal {
+            show: show_select_by_modal,
+        }
+        
+        ImportDxfModal {
+            show: show_import_dxf_modal,
+        }
     }
 }
 
@@ -1,4 +1,6 @@
 use dioxus::prelude::*;
+use plane_s::units::Quantity;
+
 use crate::hooks::use_design_state::{DesignState, ViewMode};
 use crate::types::Storey;
 use crate::components::visualization::three_bindings::{set_plan_view, reset_view};
@@ -14,6 +16,8 @@ pub fn StoreysSection() -> Element {
     // Clone data to avoid borrowing issues
     let storeys: Vec<Storey> = design_state.storeys.read().clone();
     let active_idx: Option<usize> = *design_state.active_storey_index.read();
+    let units = design_state.units;
+    let length_unit = units().symbol(Quantity::Length);
     
     // Clone for add storey callback
     let mut ds_add = design_state.clone();
@@ -51,7 +55,7 @@ pub fn StoreysSection() -> Element {
                         input {
                             r#type: "number",
                             step: "0.1",
-                            placeholder: "Elevation (m)",
+                            placeholder: "Elevation ({length_unit})",
                             class: "text-xs px-2 py-1 border rounded",
                             value: "{new_storey_elevation}",
                             oninput: move |evt| new_storey_elevation.set(evt.value())
@@ -62,10 +66,11 @@ pub fn StoreysSection() -> Element {
                                 class: "px-2 py-1 text-xs font-medium text-white bg-green-600 rounded hover:bg-green-700 transition-colors",
                                 onclick: move |_| {
                                     let elev_str = new_storey_elevation.read().clone();
-                                    if let Ok(elev) = elev_str.parse::<f64>() {
+                                    if let Ok(shown) = elev_str.parse::<f64>() {
+                                        let elev = units().to_si(Quantity::Length, shown);
                                         let name = new_storey_name.read().clone();
                                         let name = if name.is_empty() { 
-                                            format!("Storey @ {:.1}m", elev) 
+                                            format!("Storey @ {shown:.1}{length_unit}") 
                                         } else { 
                                             name 
                                         };
@@ -93,6 +98,7 @@ pub fn StoreysSection() -> Element {
                     let is_active = active_idx == Some(idx);
                     let storey_name = storey.name.clone();
                     let storey_elev = storey.elevation;
+                    let shown_elev = units().from_si(Quantity::Length, storey_elev);
                     let storey_vis = storey.visible;
                     let mut ds = design_state.clone();
                     let mut ds2 = design_state.clone();
@@ -130,7 +136,7 @@ pub fn StoreysSection() -> Element {
                                     },
                                     if storey_vis { "☑" } else { "☐" }
                                 }
-                                span { "{storey_name} ({storey_elev:.1}m)" }
+                                span { "{storey_name} ({shown_elev:.1}{length_unit})" }
                             }
                             button {
                                 class: "px-2 py-0.5 text-xs font-bold text-red-600 hover:text-red-800 transition-colors",
@@ -26,12 +26,27 @@ extern "C" {
     #[wasm_bindgen(js_name = "extrudeBeams")]
     pub fn extrude_beams(direction: &str, length: f64);
     
+    #[wasm_bindgen(js_name = "undoEdit")]
+    pub fn undo_edit() -> bool;
+    
+    #[wasm_bindgen(js_name = "redoEdit")]
+    pub fn redo_edit() -> bool;
+    
     #[wasm_bindgen(js_name = "setPlanView")]
     pub fn set_plan_view(elevation: f64);
     
     #[wasm_bindgen(js_name = "resetView")]
     pub fn reset_view();
     
+    #[wasm_bindgen(js_name = "beginDeformedAnimation")]
+    pub fn begin_deformed_animation(node_names: Vec<String>) -> bool;
+    
+    #[wasm_bindgen(js_name = "setDeformedFrame")]
+    pub fn set_deformed_frame(positions: &[f32]) -> bool;
+    
+    #[wasm_bindgen(js_name = "endDeformedAnimation")]
+    pub fn end_deformed_animation();
+    
     #[wasm_bindgen(js_name = "cleanupCanvas")]
     pub fn cleanup_canvas();
 }
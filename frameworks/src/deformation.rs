@@ -0,0 +1,160 @@
+//! Deformed shapes of analysed models, as vertex frames for the viewport
+//!
+//! A static deflection or a mode shape is taken as a displacement per node;
+//! frames are the node positions with that displacement scaled, flattened to
+//! `[x, y, z, ...]` in node order so the scene can copy them straight into
+//! its vertex buffers.
+
+use std::collections::HashMap;
+
+use crate::fea_client::{FEAModeResult, FEANodeData, FEANodeDisplacementResult};
+
+/// How the displacement is scaled over one cycle of an animation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    /// From the undeformed shape to the deflection and back
+    Load,
+    /// Swinging either side of the undeformed shape, as a mode vibrates
+    Vibration,
+}
+
+/// Node positions and the displacement of each node
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeformedShape {
+    pub nodes: Vec<String>,
+    positions: Vec<[f64; 3]>,
+    displacements: Vec<[f64; 3]>,
+}
+
+impl DeformedShape {
+    /// Shape from translations by node name; nodes without one stay put
+    pub fn new(nodes: &[FEANodeData], translations: &HashMap<&str, [f64; 3]>) -> Self {
+        Self {
+            nodes: nodes.iter().map(|n| n.name.clone()).collect(),
+            positions: nodes.iter().map(|n| [n.x, n.y, n.z]).collect(),
+            displacements: nodes.iter()
+                .map(|n| translations.get(n.name.as_str()).copied().unwrap_or([0.0; 3]))
+                .collect(),
+        }
+    }
+
+    /// Deflection under the load combination the displacements are for
+    pub fn from_displacements(nodes: &[FEANodeData], displacements: &[FEANodeDisplacementResult]) -> Self {
+        let translations = displacements.iter()
+            .map(|d| (d.node.as_str(), [d.dx, d.dy, d.dz]))
+            .collect();
+        Self::new(nodes, &translations)
+    }
+
+    pub fn from_mode(nodes: &[FEANodeData], mode: &FEAModeResult) -> Self {
+        let translations = mode.shape.iter()
+            .map(|d| (d.node.as_str(), [d.dx, d.dy, d.dz]))
+            .collect();
+        Self::new(nodes, &translations)
+    }
+
+    /// Factor on the displacements that makes the largest one `scale`% of
+    /// half the model's size, as the deformed shape diagram scales them
+    pub fn auto_scale(&self, scale: f64) -> f64 {
+        let size = self.positions.iter()
+            .flat_map(|p| p.iter().map(|c| c.abs()))
+            .fold(0.0, f64::max);
+        let largest = self.displacements.iter()
+            .map(|d| (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt())
+            .fold(0.0, f64::max);
+        if largest > 0.0 {
+            size * (scale / 100.0) * 0.5 / largest
+        } else {
+            0.0
+        }
+    }
+
+    /// Node positions with the displacements multiplied by `factor`
+    pub fn frame(&self, factor: f64) -> Vec<f32> {
+        self.positions.iter()
+            .zip(&self.displacements)
+            .flat_map(|(p, d)| (0..3).map(move |k| (p[k] + d[k] * factor) as f32))
+            .collect()
+    }
+
+    /// One cycle of an animation in `count` frames, the displacements
+    /// reaching `factor` at the peak
+    pub fn frames(&self, factor: f64, count: usize, motion: Motion) -> Vec<Vec<f32>> {
+        (0..count)
+            .map(|k| {
+                let phase = 2.0 * std::f64::consts::PI * k as f64 / count as f64;
+                let amplitude = match motion {
+                    Motion::Load => (1.0 - phase.cos()) / 2.0,
+                    Motion::Vibration => phase.sin(),
+                };
+                self.frame(factor * amplitude)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fea_client::FEAModeShapeResult;
+
+    fn nodes() -> Vec<FEANodeData> {
+        [("N1", 0.0, 0.0), ("N2", 4.0, 0.0), ("N3", 4.0, 3.0)]
+            .map(|(name, x, y)| FEANodeData { name: name.to_string(), x, y, z: 0.0 })
+            .to_vec()
+    }
+
+    fn displaced(node: &str, dx: f64, dy: f64) -> FEANodeDisplacementResult {
+        FEANodeDisplacementResult {
+            node: node.to_string(),
+            combo: "Combo 1".to_string(),
+            dx,
+            dy,
+            dz: 0.0,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_frame_offsets_nodes_in_order() {
+        // N1 has no result and stays put
+        let shape = DeformedShape::from_displacements(&nodes(), &[displaced("N3", 0.02, 0.0), displaced("N2", 0.0, -0.01)]);
+        assert_eq!(shape.nodes, ["N1", "N2", "N3"]);
+        assert_eq!(shape.frame(0.0), [0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 4.0, 3.0, 0.0]);
+        assert_eq!(shape.frame(100.0), [0.0, 0.0, 0.0, 4.0, -1.0, 0.0, 6.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_auto_scale() {
+        // Largest displacement 0.02 m drawn at 10% of half the 4 m model
+        let shape = DeformedShape::from_displacements(&nodes(), &[displaced("N3", 0.012, 0.016)]);
+        assert!((shape.auto_scale(10.0) - 10.0).abs() < 1e-9);
+        let still = DeformedShape::from_displacements(&nodes(), &[]);
+        assert_eq!(still.auto_scale(10.0), 0.0);
+    }
+
+    #[test]
+    fn test_animation_cycles() {
+        let mode = FEAModeResult {
+            frequency: 2.0,
+            period: 0.5,
+            mass_ratio: [1.0, 0.0, 0.0],
+            shape: vec![FEAModeShapeResult { node: "N3".to_string(), dx: 1.0, dy: 0.0, dz: 0.0, rx: 0.0, ry: 0.0, rz: 0.0 }],
+        };
+        let shape = DeformedShape::from_mode(&nodes(), &mode);
+        let tip_x = |motion| -> Vec<f32> { shape.frames(0.5, 4, motion).iter().map(|f| f[6]).collect() };
+
+        // A load grows from nothing to the full deflection and back
+        let load = tip_x(Motion::Load);
+        for (x, expected) in load.iter().zip([4.0, 4.25, 4.5, 4.25]) {
+            assert!((x - expected).abs() < 1e-6, "{load:?}");
+        }
+        // A mode swings either side of the undeformed shape
+        let vibration = tip_x(Motion::Vibration);
+        for (x, expected) in vibration.iter().zip([4.0, 4.5, 4.0, 3.5]) {
+            assert!((x - expected).abs() < 1e-6, "{vibration:?}");
+        }
+    }
+}
@@ -1,8 +1,11 @@
 //! Client for FEA Solver service
 
+use fea_solver::elements::SectionShape;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::sections::MemberSection;
+
 /// Client for the native Rust FEA solver service
 pub struct FEAClient {
     base_url: String,
@@ -25,7 +28,13 @@ impl FEAClient {
 // Request Types
 // ========================
 
+/// A request for the FEA server's `/api/v1/analyze`
+///
+/// The request types reject fields they don't know, so a request using
+/// server features this client doesn't model fails to parse rather than
+/// dropping them; the local solver leaves such requests to the server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEAAnalysisRequest {
     pub model: FEAModelData,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -33,20 +42,26 @@ pub struct FEAAnalysisRequest {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEAModelData {
     pub nodes: Vec<FEANodeData>,
     pub materials: Vec<FEAMaterialData>,
     pub sections: Vec<FEASectionData>,
     pub members: Vec<FEAMemberData>,
+    #[serde(default)]
+    pub plates: Vec<FEAPlateData>,
     pub supports: Vec<FEASupportData>,
     pub node_loads: Vec<FEANodeLoadData>,
     #[serde(default)]
     pub distributed_loads: Vec<FEADistributedLoadData>,
     #[serde(default)]
+    pub plate_loads: Vec<FEAPlateLoadData>,
+    #[serde(default)]
     pub load_combos: Vec<FEALoadComboData>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEANodeData {
     pub name: String,
     pub x: f64,
@@ -55,6 +70,7 @@ pub struct FEANodeData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEAMaterialData {
     pub name: String,
     pub e: f64,     // Elastic modulus (Pa)
@@ -64,6 +80,7 @@ pub struct FEAMaterialData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEASectionData {
     pub name: String,
     pub a: f64,     // Cross-sectional area (m²)
@@ -73,15 +90,73 @@ pub struct FEASectionData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEAMemberData {
     pub name: String,
     pub i_node: String,
     pub j_node: String,
     pub material: String,
     pub section: String,
+    #[serde(default)]
+    pub rotation: f64,      // Rotation about the member axis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub releases: Option<FEAMemberReleasesData>,
+}
+
+/// End releases per DOF, true where the member end is released
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FEAMemberReleasesData {
+    pub i_node_dx: bool,
+    pub i_node_dy: bool,
+    pub i_node_dz: bool,
+    pub i_node_rx: bool,
+    pub i_node_ry: bool,
+    pub i_node_rz: bool,
+    pub j_node_dx: bool,
+    pub j_node_dy: bool,
+    pub j_node_dz: bool,
+    pub j_node_rx: bool,
+    pub j_node_ry: bool,
+    pub j_node_rz: bool,
+}
+
+impl FEAMemberReleasesData {
+    /// i-node releases in DOF order [DX, DY, DZ, RX, RY, RZ]
+    pub fn i_node(&self) -> [bool; 6] {
+        [self.i_node_dx, self.i_node_dy, self.i_node_dz, self.i_node_rx, self.i_node_ry, self.i_node_rz]
+    }
+
+    /// j-node releases in DOF order [DX, DY, DZ, RX, RY, RZ]
+    pub fn j_node(&self) -> [bool; 6] {
+        [self.j_node_dx, self.j_node_dy, self.j_node_dz, self.j_node_rx, self.j_node_ry, self.j_node_rz]
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FEAPlateData {
+    pub name: String,
+    pub i_node: String,
+    pub j_node: String,
+    pub m_node: String,
+    pub n_node: String,
+    pub thickness: f64,
+    pub material: String,
+    #[serde(default = "default_modifier")]
+    pub kx_mod: f64,
+    #[serde(default = "default_modifier")]
+    pub ky_mod: f64,
+    #[serde(default)]
+    pub formulation: String,  // "kirchhoff", "mindlin" or "dkmq"
+}
+
+fn default_modifier() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEASupportData {
     pub node: String,
     pub dx: bool,
@@ -93,6 +168,7 @@ pub struct FEASupportData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEANodeLoadData {
     pub node: String,
     pub fx: f64,
@@ -113,6 +189,7 @@ fn default_case() -> String {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEADistributedLoadData {
     pub member: String,
     pub w1: f64,        // Start magnitude
@@ -123,23 +200,54 @@ pub struct FEADistributedLoadData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FEAPlateLoadData {
+    pub plate: String,
+    pub pressure: f64,  // Pa
+    #[serde(default = "default_case")]
+    pub case: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEALoadComboData {
     pub name: String,
     pub factors: HashMap<String, f64>,
+    /// e.g. "strength" or "service", for filtering results and design checks
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FEAAnalysisOptions {
     #[serde(default)]
     pub analysis_type: String,
     #[serde(default = "default_max_iter")]
     pub max_iterations: usize,
+    /// Load increments for large displacement analysis
+    #[serde(default = "default_load_steps")]
+    pub load_steps: usize,
+    /// Arc-length control for large displacement analysis
+    #[serde(default)]
+    pub arc_length: bool,
+    /// Modes of vibration to find after the static analysis, none if zero
+    #[serde(default)]
+    pub num_modes: usize,
+    /// Solver options in full; when given they take the place of
+    /// `analysis_type`, `max_iterations`, `load_steps` and `arc_length`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<fea_solver::analysis::AnalysisOptions>,
 }
 
 fn default_max_iter() -> usize {
     30
 }
 
+fn default_load_steps() -> usize {
+    10
+}
+
 // ========================
 // Response Types
 // ========================
@@ -158,6 +266,14 @@ pub struct FEAResultsData {
     pub node_displacements: Vec<FEANodeDisplacementResult>,
     pub reactions: Vec<FEAReactionResult>,
     pub member_forces: Vec<FEAMemberForceResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub member_diagrams: Vec<FEAMemberDiagramResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plate_stresses: Vec<FEAPlateStressResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<FEAModeResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
     pub summary: FEASummaryResult,
 }
 
@@ -203,6 +319,55 @@ pub struct FEAMemberForceResult {
     pub moment_z_j: f64,
 }
 
+/// Internal forces along a member, one value per station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FEAMemberDiagramResult {
+    pub member: String,
+    pub combo: String,
+    /// Distance of each station from the i-node
+    pub x: Vec<f64>,
+    pub axial: Vec<f64>,
+    pub shear_y: Vec<f64>,
+    pub shear_z: Vec<f64>,
+    pub torsion: Vec<f64>,
+    pub moment_y: Vec<f64>,
+    pub moment_z: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FEAPlateStressResult {
+    pub plate: String,
+    pub combo: String,
+    pub sx: f64,
+    pub sy: f64,
+    pub txy: f64,
+    pub von_mises: f64,
+    pub mx: f64,
+    pub my: f64,
+    pub mxy: f64,
+}
+
+/// A mode of vibration, with its mass-normalised shape
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FEAModeResult {
+    pub frequency: f64,
+    pub period: f64,
+    /// Effective modal mass in X, Y and Z as a fraction of the free mass
+    pub mass_ratio: [f64; 3],
+    pub shape: Vec<FEAModeShapeResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FEAModeShapeResult {
+    pub node: String,
+    pub dx: f64,
+    pub dy: f64,
+    pub dz: f64,
+    pub rx: f64,
+    pub ry: f64,
+    pub rz: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FEASummaryResult {
     pub max_displacement: f64,
@@ -225,63 +390,26 @@ pub struct FEAHealthResponse {
 // Section Properties Calculator
 // ========================
 
-/// Calculate section properties for common cross-section types
+/// Section properties for common cross-section types, as sent to an
+/// analysis; computed by the solver's `Section` (see [`MemberSection`])
 pub struct SectionCalculator;
 
 impl SectionCalculator {
-    /// Calculate rectangular section properties
     pub fn rectangular(width: f64, height: f64) -> FEASectionData {
-        let a = width * height;
-        let iy = width * height.powi(3) / 12.0;
-        let iz = height * width.powi(3) / 12.0;
-        // Approximate torsional constant for rectangular sections
-        let (a_dim, b_dim) = if width > height { (width, height) } else { (height, width) };
-        let j = a_dim * b_dim.powi(3) * (1.0 / 3.0 - 0.21 * (b_dim / a_dim) * (1.0 - b_dim.powi(4) / (12.0 * a_dim.powi(4))));
-        
-        FEASectionData {
-            name: format!("Rect_{:.0}x{:.0}", width * 1000.0, height * 1000.0),
-            a,
-            iy,
-            iz,
-            j,
-        }
+        MemberSection::from_shape(SectionShape::Rectangle { width, depth: height }).to_analysis()
     }
 
-    /// Calculate circular section properties
     pub fn circular(diameter: f64) -> FEASectionData {
-        let r = diameter / 2.0;
-        let a = std::f64::consts::PI * r.powi(2);
-        let i = std::f64::consts::PI * r.powi(4) / 4.0;
-        let j = std::f64::consts::PI * r.powi(4) / 2.0;
-        
-        FEASectionData {
-            name: format!("Circ_{:.0}", diameter * 1000.0),
-            a,
-            iy: i,
-            iz: i,
-            j,
-        }
+        MemberSection::from_shape(SectionShape::Circle { diameter }).to_analysis()
     }
 
-    /// Calculate I-beam/wide flange section properties
+    /// Wide flange with the web vertical
     pub fn i_beam(height: f64, width: f64, flange_thickness: f64, web_thickness: f64) -> FEASectionData {
-        let a = 2.0 * width * flange_thickness + (height - 2.0 * flange_thickness) * web_thickness;
-        
-        // Strong axis (about z, bending in y-z plane)
-        let iz = (width * height.powi(3) - (width - web_thickness) * (height - 2.0 * flange_thickness).powi(3)) / 12.0;
-        
-        // Weak axis (about y)
-        let iy = (2.0 * flange_thickness * width.powi(3) + (height - 2.0 * flange_thickness) * web_thickness.powi(3)) / 12.0;
-        
-        // Torsional constant (approximate for open thin-walled sections)
-        let j = (2.0 * width * flange_thickness.powi(3) + (height - 2.0 * flange_thickness) * web_thickness.powi(3)) / 3.0;
-        
-        FEASectionData {
-            name: format!("IBeam_{:.0}x{:.0}", height * 1000.0, width * 1000.0),
-            a,
-            iy,
-            iz,
-            j,
-        }
+        MemberSection::from_shape(SectionShape::WideFlange {
+            depth: height,
+            flange_width: width,
+            flange_thickness,
+            web_thickness,
+        }).to_analysis()
     }
 }
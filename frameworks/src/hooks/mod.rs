@@ -1 +1,2 @@
 pub mod use_design_state;
+pub mod use_model_store;
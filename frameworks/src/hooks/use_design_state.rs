@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
+use plane_s::units::Units;
+
 use crate::types::{Plate, ModellingTool, Structure, Material, Storey};
+use crate::components::layout::{DisplaySettings, DrawSettings, GridSystem, LoadCase, LoadCombo};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum ViewMode {
@@ -15,6 +18,18 @@ pub struct DesignState {
     pub storeys: Signal<Vec<Storey>>,
     pub active_storey_index: Signal<Option<usize>>,
     pub view_mode: Signal<ViewMode>,
+    pub load_cases: Signal<Vec<LoadCase>>,
+    /// Load case new loads are added to, by id
+    pub active_load_case: Signal<usize>,
+    pub load_combos: Signal<Vec<LoadCombo>>,
+    /// Construction grid drawn in the viewport, if one is defined
+    pub grid: Signal<Option<GridSystem>>,
+    /// Units values are entered and shown in; stored values are unaffected
+    pub units: Signal<Units>,
+    /// Snapping and ortho lock for placing nodes and drawing beams
+    pub draw_settings: Signal<DrawSettings>,
+    /// Scale of load arrows and support glyphs, and which loads are drawn
+    pub display_settings: Signal<DisplaySettings>,
 }
 
 pub fn use_design_state() -> DesignState {
@@ -24,6 +39,13 @@ pub fn use_design_state() -> DesignState {
     let storeys = use_signal(|| Vec::new());
     let active_storey_index = use_signal(|| None);
     let view_mode = use_signal(|| ViewMode::ThreeD);
+    let load_cases = use_signal(|| vec![LoadCase::default()]);
+    let active_load_case = use_signal(|| 1usize);
+    let load_combos = use_signal(Vec::new);
+    let grid = use_signal(|| None);
+    let units = use_signal(Units::default);
+    let draw_settings = use_signal(DrawSettings::default);
+    let display_settings = use_signal(DisplaySettings::default);
     
     DesignState {
         plates,
@@ -32,6 +54,13 @@ pub fn use_design_state() -> DesignState {
         storeys,
         active_storey_index,
         view_mode,
+        load_cases,
+        active_load_case,
+        load_combos,
+        grid,
+        units,
+        draw_settings,
+        display_settings,
     }
 }
 
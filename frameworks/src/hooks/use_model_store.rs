@@ -0,0 +1,741 @@
+//! The structural model shown in the viewport, owned on the Rust side
+//!
+//! The scene in three.js is a view of the [`ViewportModel`] held here.
+//! Edits made in the viewport (drawing, deleting, loads, supports, undo)
+//! come back as the serialized scene after every change; edits made in Rust
+//! go through [`ModelStore::set`], which rebuilds the scene from the model
+//! as one undoable edit. Panels, analysis and
+//! persistence read the model from here rather than from JS globals.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use dioxus::prelude::*;
+use dioxus::document::eval;
+use serde::{Deserialize, Serialize};
+
+use plane_s::sections::MemberSection;
+
+use crate::components::layout::GridSystem;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewportModel {
+    pub nodes: Vec<ModelNode>,
+    pub members: Vec<ModelMember>,
+    pub plates: Vec<ModelPlate>,
+    pub supports: Vec<ModelSupport>,
+    pub member_loads: Vec<MemberLoad>,
+    pub plate_loads: Vec<PlateLoad>,
+    pub node_loads: Vec<NodeLoad>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelNode {
+    pub id: usize,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelMember {
+    pub id: usize,
+    pub i_node: usize,
+    pub j_node: usize,
+    #[serde(default)]
+    pub releases: MemberReleases,
+    /// None takes the default section of the analysis
+    #[serde(default)]
+    pub section: Option<MemberSection>,
+}
+
+/// Moment releases at the member ends; all fixed by default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemberReleases {
+    pub i_node_ry: bool,
+    pub i_node_rz: bool,
+    pub j_node_ry: bool,
+    pub j_node_rz: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelPlate {
+    pub id: usize,
+    /// Corner nodes in order around the plate
+    pub node_ids: Vec<usize>,
+    /// None takes the default thickness of the analysis
+    #[serde(default)]
+    pub thickness: Option<f64>,
+}
+
+/// Restrained directions of a node, with spring stiffnesses (kN/m) for
+/// elastic supports
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelSupport {
+    pub node: usize,
+    pub dx: bool,
+    pub dy: bool,
+    pub dz: bool,
+    pub rx: bool,
+    pub ry: bool,
+    pub rz: bool,
+    #[serde(default)]
+    pub kx: f64,
+    #[serde(default)]
+    pub ky: f64,
+    #[serde(default)]
+    pub kz: f64,
+}
+
+impl ModelSupport {
+    pub fn pinned(node: usize) -> Self {
+        Self { node, dx: true, dy: true, dz: true, rx: false, ry: false, rz: false, kx: 0.0, ky: 0.0, kz: 0.0 }
+    }
+}
+
+/// Loads with no load case are in the first one
+fn default_load_case() -> usize {
+    1
+}
+
+/// Load on a member, in kN or kN/m along a global axis (`x`, `y` or `z`),
+/// in a load case by id
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MemberLoad {
+    Point {
+        member: usize,
+        magnitude: f64,
+        direction: String,
+        /// Position along the member, 0 to 1
+        position: f64,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default = "default_load_case")]
+        load_case: usize,
+    },
+    Distributed {
+        member: usize,
+        magnitude: f64,
+        direction: String,
+        start_pos: f64,
+        end_pos: f64,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default = "default_load_case")]
+        load_case: usize,
+    },
+}
+
+/// Pressure on a plate, in kN/m²
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlateLoad {
+    pub plate: usize,
+    pub magnitude: f64,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default = "default_load_case")]
+    pub load_case: usize,
+}
+
+/// Force on a node, in N as the viewport stores it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeLoad {
+    pub node: usize,
+    pub fx: f64,
+    pub fy: f64,
+    pub fz: f64,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default = "default_load_case")]
+    pub load_case: usize,
+}
+
+/// Nodes within this of an elevation (m) are at it
+const ELEVATION_TOLERANCE: f64 = 1e-3;
+
+/// A query selecting part of the model, complementing picking in the viewport
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectQuery {
+    /// Members assigned the named section; None for members left with the
+    /// default section
+    MembersWithSection(Option<String>),
+    /// Nodes at an elevation (m)
+    NodesAtElevation(f64),
+    /// Plates given a thickness (m) greater than this; plates taking the
+    /// default thickness are not
+    PlatesThickerThan(f64),
+}
+
+/// Ids of selected objects, as the viewport selects them by
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Selection {
+    pub nodes: Vec<usize>,
+    pub members: Vec<usize>,
+    pub plates: Vec<usize>,
+}
+
+impl Selection {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.members.is_empty() && self.plates.is_empty()
+    }
+
+    /// Push into the viewport, replacing the selection there or adding to it
+    pub fn apply(&self, add: bool) {
+        let Ok(ids) = serde_json::to_string(self) else { return };
+        eval(&format!("if (window.selectByIds) {{ window.selectByIds({ids}, {add}); }}"));
+    }
+}
+
+/// Global axis, naming the plane normal to it for a mirror
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+
+    /// Name the load panels give the direction along the axis
+    pub fn key(&self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        }
+    }
+}
+
+impl ViewportModel {
+    /// Why the model cannot be analysed yet, if it cannot
+    pub fn analysis_problem(&self) -> Option<&'static str> {
+        if self.members.is_empty() && self.plates.is_empty() {
+            Some("Model has no members or plates to analyse")
+        } else if self.supports.is_empty() {
+            Some("Model has no supports; add supports to some nodes")
+        } else {
+            None
+        }
+    }
+
+    /// Id for the next member or plate added
+    pub fn next_member_id(&self) -> usize {
+        self.members.iter().map(|m| m.id).max().unwrap_or(0) + 1
+    }
+
+    pub fn next_plate_id(&self) -> usize {
+        self.plates.iter().map(|p| p.id).max().unwrap_or(0) + 1
+    }
+
+    pub fn next_node_id(&self) -> usize {
+        self.nodes.iter().map(|n| n.id).max().unwrap_or(0) + 1
+    }
+
+    /// Sections assigned to members, once each by name
+    pub fn assigned_sections(&self) -> Vec<&MemberSection> {
+        let mut sections: Vec<&MemberSection> = Vec::new();
+        for section in self.members.iter().filter_map(|m| m.section.as_ref()) {
+            if !sections.iter().any(|s| s.name == section.name) {
+                sections.push(section);
+            }
+        }
+        sections
+    }
+
+    /// Ids of the nodes, members and plates a query picks out
+    pub fn select(&self, query: &SelectQuery) -> Selection {
+        let mut selection = Selection::default();
+        match query {
+            SelectQuery::MembersWithSection(name) => {
+                selection.members = self.members.iter()
+                    .filter(|m| m.section.as_ref().map(|s| &s.name) == name.as_ref())
+                    .map(|m| m.id)
+                    .collect();
+            }
+            SelectQuery::NodesAtElevation(elevation) => {
+                selection.nodes = self.nodes.iter()
+                    .filter(|n| (n.y - elevation).abs() < ELEVATION_TOLERANCE)
+                    .map(|n| n.id)
+                    .collect();
+            }
+            SelectQuery::PlatesThickerThan(thickness) => {
+                selection.plates = self.plates.iter()
+                    .filter(|p| p.thickness.is_some_and(|t| t > *thickness))
+                    .map(|p| p.id)
+                    .collect();
+            }
+        }
+        selection
+    }
+
+    /// Copy the selection `count` times, each copy offset by `offset` (m)
+    /// from the last; returns what the copies added
+    pub fn copy_array(&mut self, selection: &Selection, count: usize, offset: [f64; 3]) -> Selection {
+        let mut copies = Selection::default();
+        for k in 1..=count {
+            let d = offset.map(|o| o * k as f64);
+            let copy = self.copy_transformed(selection, |[x, y, z]| [x + d[0], y + d[1], z + d[2]], None);
+            copies.nodes.extend(copy.nodes);
+            copies.members.extend(copy.members);
+            copies.plates.extend(copy.plates);
+        }
+        copies
+    }
+
+    /// Copy the selection mirrored about the plane normal to `axis` at
+    /// `coordinate` (m); returns what the copy added
+    pub fn mirror(&mut self, selection: &Selection, axis: Axis, coordinate: f64) -> Selection {
+        let reflect = |mut p: [f64; 3]| {
+            p[axis as usize] = 2.0 * coordinate - p[axis as usize];
+            p
+        };
+        self.copy_transformed(selection, reflect, Some(axis))
+    }
+
+    /// Copy the selected nodes, members and plates to new positions, with
+    /// the supports and loads on them. Members and plates bring their nodes;
+    /// a copied node landing on an existing one is merged with it, and a
+    /// member or plate whose copy would duplicate one already there is not
+    /// copied. A mirror reverses load components along its axis and the
+    /// corner order of plates, so pressures keep their side.
+    fn copy_transformed(&mut self, selection: &Selection, place: impl Fn([f64; 3]) -> [f64; 3], mirror: Option<Axis>) -> Selection {
+        let members: Vec<ModelMember> = self.members.iter()
+            .filter(|m| selection.members.contains(&m.id))
+            .cloned()
+            .collect();
+        let plates: Vec<ModelPlate> = self.plates.iter()
+            .filter(|p| selection.plates.contains(&p.id))
+            .cloned()
+            .collect();
+        let sources: BTreeSet<usize> = selection.nodes.iter().copied()
+            .chain(members.iter().flat_map(|m| [m.i_node, m.j_node]))
+            .chain(plates.iter().flat_map(|p| p.node_ids.iter().copied()))
+            .collect();
+
+        let mut copied = Selection::default();
+        let mut node_map = BTreeMap::new();
+        let nodes: Vec<ModelNode> = self.nodes.iter().filter(|n| sources.contains(&n.id)).cloned().collect();
+        for node in &nodes {
+            let [x, y, z] = place([node.x, node.y, node.z]);
+            let id = self.node_at(x, y, z);
+            node_map.insert(node.id, id);
+            if selection.nodes.contains(&node.id) {
+                copied.nodes.push(id);
+            }
+        }
+
+        let mut member_map = BTreeMap::new();
+        for member in &members {
+            let (i_node, j_node) = (node_map[&member.i_node], node_map[&member.j_node]);
+            let exists = self.members.iter()
+                .any(|m| (m.i_node, m.j_node) == (i_node, j_node) || (m.i_node, m.j_node) == (j_node, i_node));
+            if exists || i_node == j_node {
+                continue;
+            }
+            let id = self.next_member_id();
+            self.members.push(ModelMember { id, i_node, j_node, ..member.clone() });
+            member_map.insert(member.id, id);
+            copied.members.push(id);
+        }
+
+        let mut plate_map = BTreeMap::new();
+        for plate in &plates {
+            let mut node_ids: Vec<usize> = plate.node_ids.iter().map(|n| node_map[n]).collect();
+            if mirror.is_some() {
+                node_ids.reverse();
+            }
+            let corners: BTreeSet<usize> = node_ids.iter().copied().collect();
+            let exists = self.plates.iter().any(|p| p.node_ids.iter().copied().collect::<BTreeSet<_>>() == corners);
+            if exists || corners.len() < node_ids.len() {
+                continue;
+            }
+            let id = self.next_plate_id();
+            self.plates.push(ModelPlate { id, node_ids, thickness: plate.thickness });
+            plate_map.insert(plate.id, id);
+            copied.plates.push(id);
+        }
+
+        // Supports and loads go with their copied node, member or plate
+        let flip = |along: Axis| if mirror == Some(along) { -1.0 } else { 1.0 };
+        for (&source, &copy) in &node_map {
+            if source == copy {
+                continue;
+            }
+            if let Some(support) = self.supports.iter().find(|s| s.node == source).cloned() {
+                if !self.supports.iter().any(|s| s.node == copy) {
+                    self.supports.push(ModelSupport { node: copy, ..support });
+                }
+            }
+            let loads: Vec<NodeLoad> = self.node_loads.iter().filter(|l| l.node == source).cloned().collect();
+            self.node_loads.extend(loads.into_iter().map(|l| NodeLoad {
+                node: copy,
+                fx: l.fx * flip(Axis::X),
+                fy: l.fy * flip(Axis::Y),
+                fz: l.fz * flip(Axis::Z),
+                ..l
+            }));
+        }
+        let member_loads: Vec<MemberLoad> = self.member_loads.iter()
+            .filter_map(|load| {
+                let mut load = load.clone();
+                let (MemberLoad::Point { member, magnitude, direction, .. }
+                    | MemberLoad::Distributed { member, magnitude, direction, .. }) = &mut load;
+                *member = *member_map.get(member)?;
+                if mirror.is_some_and(|axis| axis.key() == direction.as_str()) {
+                    *magnitude = -*magnitude;
+                }
+                Some(load)
+            })
+            .collect();
+        self.member_loads.extend(member_loads);
+        let plate_loads: Vec<PlateLoad> = self.plate_loads.iter()
+            .filter_map(|load| Some(PlateLoad { plate: *plate_map.get(&load.plate)?, ..load.clone() }))
+            .collect();
+        self.plate_loads.extend(plate_loads);
+
+        copied
+    }
+
+    /// Id of the node at a position, adding one if there is none
+    fn node_at(&mut self, x: f64, y: f64, z: f64) -> usize {
+        const TOLERANCE: f64 = 1e-3;
+        if let Some(node) = self.nodes.iter()
+            .find(|n| (n.x - x).abs() < TOLERANCE && (n.y - y).abs() < TOLERANCE && (n.z - z).abs() < TOLERANCE)
+        {
+            return node.id;
+        }
+        let id = self.next_node_id();
+        self.nodes.push(ModelNode { id, x, y, z });
+        id
+    }
+
+    /// Add a member between two nodes, unless one joins them already
+    fn connect(&mut self, i_node: usize, j_node: usize) {
+        let exists = self.members.iter()
+            .any(|m| (m.i_node, m.j_node) == (i_node, j_node) || (m.i_node, m.j_node) == (j_node, i_node));
+        if !exists && i_node != j_node {
+            let id = self.next_member_id();
+            self.members.push(ModelMember { id, i_node, j_node, releases: MemberReleases::default(), section: None });
+        }
+    }
+
+    /// Members between points given by index into `points`, as from an
+    /// imported drawing. Ends join nodes already at the same positions, and
+    /// members already in the model are not repeated. Returns what was added.
+    pub fn add_frame(&mut self, points: &[[f64; 3]], members: &[(usize, usize)]) -> Selection {
+        let first_node = self.next_node_id();
+        let first_member = self.members.len();
+        let mut ids: BTreeMap<usize, usize> = BTreeMap::new();
+        for &(i, j) in members {
+            let [i_node, j_node] = [i, j].map(|point| {
+                *ids.entry(point).or_insert_with(|| {
+                    let [x, y, z] = points[point];
+                    self.node_at(x, y, z)
+                })
+            });
+            self.connect(i_node, j_node);
+        }
+        Selection {
+            nodes: self.nodes.iter().map(|n| n.id).filter(|&id| id >= first_node).collect(),
+            members: self.members[first_member..].iter().map(|m| m.id).collect(),
+            plates: Vec::new(),
+        }
+    }
+
+    /// Frame on a construction grid: columns at the gridline intersections
+    /// between levels, beams along the gridlines at each floor, and pinned
+    /// supports at the column bases. Nodes and members already on the grid
+    /// are reused.
+    pub fn add_grid_frame(&mut self, grid: &GridSystem, columns: bool, beams: bool, supports: bool) {
+        let xs: Vec<f64> = grid.x_lines().into_iter().map(|(_, x)| x).collect();
+        let zs: Vec<f64> = grid.y_lines().into_iter().map(|(_, z)| z).collect();
+        let levels: Vec<f64> = grid.levels().into_iter().map(|(_, y)| y).collect();
+
+        for (level, &y) in levels.iter().enumerate() {
+            // Ground nodes are only for column bases
+            if level == 0 && !columns {
+                continue;
+            }
+            let ids: Vec<Vec<usize>> = xs.iter()
+                .map(|&x| zs.iter().map(|&z| self.node_at(x, y, z)).collect())
+                .collect();
+
+            if level == 0 {
+                if columns && supports {
+                    for &id in ids.iter().flatten() {
+                        if !self.supports.iter().any(|s| s.node == id) {
+                            self.supports.push(ModelSupport::pinned(id));
+                        }
+                    }
+                }
+            } else {
+                if columns {
+                    let below = levels[level - 1];
+                    for (i, &x) in xs.iter().enumerate() {
+                        for (j, &z) in zs.iter().enumerate() {
+                            let base = self.node_at(x, below, z);
+                            self.connect(base, ids[i][j]);
+                        }
+                    }
+                }
+                if beams {
+                    for i in 0..xs.len() {
+                        for j in 0..zs.len() {
+                            if i + 1 < xs.len() {
+                                self.connect(ids[i][j], ids[i + 1][j]);
+                            }
+                            if j + 1 < zs.len() {
+                                self.connect(ids[i][j], ids[i][j + 1]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Example 3-storey building: a 16m x 15m footprint with columns, floor
+    /// slabs, a stair core and pinned supports
+    pub fn example_building() -> Self {
+        const STOREY_HEIGHT: f64 = 3.0;
+        const LEVELS: usize = 4; // Ground + 3 floors
+        let footprint: [(f64, f64); 15] = [
+            // Left edge (X=5)
+            (5.0, 5.0), (5.0, 0.0), (5.0, -5.0), (5.0, -10.0),
+            // Middle bay (X=13)
+            (13.0, -10.0), (13.0, 5.0),
+            // Right edge (X=21)
+            (21.0, 5.0), (21.0, -10.0), (21.0, 0.0), (21.0, -5.0),
+            // Interior columns
+            (13.0, 0.0), (13.0, -5.0),
+            // Core opening
+            (13.0, -2.0), (11.0, -2.0), (11.0, 0.0),
+        ];
+        let node_id = |level: usize, i: usize| level * footprint.len() + i + 1;
+
+        let mut model = Self::default();
+        for level in 0..LEVELS {
+            for (i, &(x, z)) in footprint.iter().enumerate() {
+                model.nodes.push(ModelNode { id: node_id(level, i), x, y: level as f64 * STOREY_HEIGHT, z });
+            }
+        }
+        for level in 0..LEVELS - 1 {
+            for i in 0..footprint.len() {
+                let id = model.next_member_id();
+                model.members.push(ModelMember {
+                    id,
+                    i_node: node_id(level, i),
+                    j_node: node_id(level + 1, i),
+                    releases: MemberReleases::default(),
+                    section: None,
+                });
+            }
+        }
+        model.supports = (0..footprint.len()).map(|i| ModelSupport::pinned(node_id(0, i))).collect();
+
+        let add_plate = |model: &mut Self, node_ids: Vec<usize>| {
+            let id = model.next_plate_id();
+            model.plates.push(ModelPlate { id, node_ids, thickness: None });
+        };
+        // Floor slabs: front-left, back-left, back-right, front-right
+        for level in 1..LEVELS {
+            add_plate(&mut model, [0, 3, 7, 6].iter().map(|&i| node_id(level, i)).collect());
+        }
+        // Stair core walls, one storey high
+        for level in 0..LEVELS - 1 {
+            for (a, b) in [(14, 13), (13, 12), (12, 10)] {
+                add_plate(&mut model, vec![node_id(level, a), node_id(level, b), node_id(level + 1, b), node_id(level + 1, a)]);
+            }
+        }
+        model
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct ModelStore {
+    /// Read-only outside the store: changes go through `set` so the
+    /// viewport follows
+    pub model: Signal<ViewportModel>,
+}
+
+impl ModelStore {
+    /// Replace the model and rebuild the viewport from it
+    pub fn set(&mut self, model: ViewportModel, label: &str) {
+        push_to_viewport(&model, label);
+        self.model.set(model);
+    }
+}
+
+fn push_to_viewport(model: &ViewportModel, label: &str) {
+    match (serde_json::to_string(model), serde_json::to_string(label)) {
+        (Ok(model), Ok(label)) => {
+            eval(&format!("if (window.loadViewportModel) {{ window.loadViewportModel({model}, {label}); }}"));
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            web_sys::console::error_1(&format!("Failed to serialize the model: {e}").into());
+        }
+    }
+}
+
+/// Create the store and keep it in step with edits made in the viewport
+pub fn use_model_store() -> ModelStore {
+    let mut model = use_signal(ViewportModel::default);
+
+    use_hook(move || {
+        spawn(async move {
+            // Every change to the scene goes through the edit history
+            let mut sync = eval(r#"
+                const send = () => dioxus.send(window.getViewportModel ? window.getViewportModel() : null);
+                window.addEventListener('editHistoryChanged', send);
+                send();
+            "#);
+            while let Ok(scene) = sync.recv::<serde_json::Value>().await {
+                match serde_json::from_value::<Option<ViewportModel>>(scene) {
+                    Ok(Some(scene)) if *model.peek() != scene => model.set(scene),
+                    Ok(_) => {}
+                    Err(e) => web_sys::console::error_1(&format!("Unreadable viewport model: {e}").into()),
+                }
+            }
+        });
+    });
+
+    ModelStore { model }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pinned portal in the XY plane with a plate off the beam towards +Z,
+    /// a node load at the top of the left column, and loads on the beam and
+    /// plate
+    fn portal() -> ViewportModel {
+        let w12 = MemberSection::catalog("W12x26");
+        let member = |id, i_node, j_node, section: &Option<MemberSection>| ModelMember {
+            id,
+            i_node,
+            j_node,
+            releases: MemberReleases::default(),
+            section: section.clone(),
+        };
+        let nodes = [(0.0, 0.0, 0.0), (0.0, 3.0, 0.0), (4.0, 3.0, 0.0), (4.0, 0.0, 0.0), (0.0, 3.0, 4.0), (4.0, 3.0, 4.0)];
+        ViewportModel {
+            nodes: nodes.iter().enumerate().map(|(i, &(x, y, z))| ModelNode { id: i + 1, x, y, z }).collect(),
+            members: vec![member(1, 1, 2, &w12), member(2, 2, 3, &None), member(3, 4, 3, &w12)],
+            plates: vec![ModelPlate { id: 1, node_ids: vec![2, 3, 6, 5], thickness: Some(0.25) }],
+            supports: vec![ModelSupport::pinned(1), ModelSupport::pinned(4)],
+            member_loads: vec![MemberLoad::Point {
+                member: 2,
+                magnitude: 5.0,
+                direction: "x".to_string(),
+                position: 0.5,
+                color: None,
+                load_case: 1,
+            }],
+            plate_loads: vec![PlateLoad { plate: 1, magnitude: -2.0, color: None, load_case: 2 }],
+            node_loads: vec![NodeLoad { node: 2, fx: 10.0, fy: -4.0, fz: 0.0, color: None, load_case: 1 }],
+        }
+    }
+
+    fn position(model: &ViewportModel, id: usize) -> [f64; 3] {
+        let node = model.nodes.iter().find(|n| n.id == id).unwrap();
+        [node.x, node.y, node.z]
+    }
+
+    #[test]
+    fn test_select_queries() {
+        let mut model = portal();
+        model.plates.push(ModelPlate { id: 2, node_ids: vec![1, 2, 5], thickness: None });
+        let select = |query| model.select(&query);
+
+        assert_eq!(select(SelectQuery::MembersWithSection(Some("W12x26".to_string()))).members, [1, 3]);
+        assert_eq!(select(SelectQuery::MembersWithSection(None)).members, [2]);
+        assert!(select(SelectQuery::MembersWithSection(Some("HSS4x4x1/4".to_string()))).is_empty());
+
+        let at_roof = select(SelectQuery::NodesAtElevation(3.0005));
+        assert_eq!(at_roof.nodes, [2, 3, 5, 6]);
+        assert!(at_roof.members.is_empty() && at_roof.plates.is_empty());
+        assert!(select(SelectQuery::NodesAtElevation(1.5)).is_empty());
+
+        // The plate with the default thickness is never picked
+        assert_eq!(select(SelectQuery::PlatesThickerThan(0.2)).plates, [1]);
+        assert!(select(SelectQuery::PlatesThickerThan(0.25)).is_empty());
+    }
+
+    #[test]
+    fn test_copy_array() {
+        let mut model = portal();
+        let frame = Selection { nodes: vec![1, 2, 3, 4], members: vec![1, 2, 3], plates: Vec::new() };
+        let copies = model.copy_array(&frame, 2, [0.0, 0.0, 5.0]);
+
+        assert_eq!(copies.nodes.len(), 8);
+        assert_eq!(copies.members.len(), 6);
+        assert!(copies.plates.is_empty());
+        assert_eq!(model.nodes.len(), 6 + 8);
+        assert_eq!(model.members.len(), 3 + 6);
+        assert_eq!(model.plates.len(), 1);
+        assert_eq!(position(&model, copies.nodes[7]), [4.0, 0.0, 10.0]);
+
+        // Each copy brings the supports, node loads and member loads with it
+        assert_eq!(model.supports.len(), 2 + 4);
+        assert_eq!(model.node_loads.len(), 1 + 2);
+        assert_eq!(model.member_loads.len(), 1 + 2);
+        assert!(model.node_loads.iter().all(|l| l.fx == 10.0 && l.fy == -4.0));
+        assert_eq!(model.plate_loads.len(), 1);
+        for support in &model.supports {
+            assert_eq!(position(&model, support.node)[1], 0.0);
+        }
+        // Sections and releases are kept
+        let copied_beams = model.members.iter().filter(|m| copies.members.contains(&m.id) && m.section.is_none()).count();
+        assert_eq!(copied_beams, 2);
+
+        // A copy onto the original adds nothing
+        let before = model.clone();
+        assert!(model.copy_array(&frame, 1, [0.0; 3]).members.is_empty());
+        assert_eq!(model, before);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut model = portal();
+        let right = Selection { nodes: Vec::new(), members: vec![2, 3], plates: vec![1] };
+        let copy = model.mirror(&right, Axis::X, 0.0);
+
+        assert!(copy.nodes.is_empty());
+        assert_eq!(copy.members.len(), 2);
+        assert_eq!(copy.plates.len(), 1);
+        // Nodes on the plane are shared with the original
+        assert_eq!(model.nodes.len(), 6 + 3);
+        let beam = model.members.iter().find(|m| m.id == copy.members[0]).unwrap();
+        assert_eq!(beam.i_node, 2);
+        assert_eq!(position(&model, beam.j_node), [-4.0, 3.0, 0.0]);
+
+        // Corners run the other way round so the pressure keeps its side
+        let plate = model.plates.iter().find(|p| p.id == copy.plates[0]).unwrap();
+        let corners: Vec<[f64; 3]> = plate.node_ids.iter().map(|&id| position(&model, id)).collect();
+        assert_eq!(corners, [[0.0, 3.0, 4.0], [-4.0, 3.0, 4.0], [-4.0, 3.0, 0.0], [0.0, 3.0, 0.0]]);
+        assert_eq!(plate.thickness, Some(0.25));
+        assert_eq!(model.plate_loads[1], PlateLoad { plate: plate.id, ..model.plate_loads[0].clone() });
+
+        // Loads along the mirror axis are reversed
+        let MemberLoad::Point { member, magnitude, .. } = &model.member_loads[1] else { panic!() };
+        assert_eq!((*member, *magnitude), (beam.id, -5.0));
+
+        // The support at the right column base goes to the left; node 2 is
+        // on the plane, so its load is not repeated
+        assert_eq!(model.supports.len(), 3);
+        assert_eq!(position(&model, model.supports[2].node), [-4.0, 0.0, 0.0]);
+        assert_eq!(model.node_loads.len(), 1);
+    }
+
+    #[test]
+    fn test_mirror_reverses_node_loads() {
+        let mut model = portal();
+        let left = Selection { nodes: vec![2], members: Vec::new(), plates: Vec::new() };
+        let copy = model.mirror(&left, Axis::Y, 0.0);
+        assert_eq!(position(&model, copy.nodes[0]), [0.0, -3.0, 0.0]);
+        assert_eq!(model.node_loads[1], NodeLoad { node: copy.nodes[0], fx: 10.0, fy: 4.0, ..model.node_loads[0].clone() });
+    }
+}
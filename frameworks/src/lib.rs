@@ -2,3 +2,9 @@
 pub mod types;
 pub mod calculix_client;
 pub mod fea_client;
+pub mod deformation;
+pub mod sections;
+pub mod units;
+pub mod result_tables;
+#[cfg(feature = "local-solver")]
+pub mod local_solver;
@@ -0,0 +1,296 @@
+//! In-browser analysis with fea-solver compiled into the app
+//!
+//! Takes the same request the FEA server's `/api/v1/analyze` does and
+//! answers with the same response, so results go to the viewport without a
+//! round trip. Only built with the `local-solver` feature. The model is built
+//! as the server builds it; larger models, as the solve runs on the page's
+//! thread, and analysis types this build doesn't know are left to the server.
+
+use fea_solver::prelude::*;
+use fea_solver::loads::LoadDirection;
+
+use crate::fea_client::*;
+
+/// Largest model analysed in the browser
+pub const MAX_NODES: usize = 5_000;
+
+/// Stations along each member in the internal force diagrams, as the server
+const DIAGRAM_STATIONS: usize = 21;
+
+/// Analyse a model in-process, reporting failures in the response, or
+/// `None` for a request to send to the server instead
+pub fn analyze(request: &FEAAnalysisRequest) -> Option<FEAAnalysisResponse> {
+    match run(request) {
+        Ok(results) => Some(FEAAnalysisResponse { success: true, error: None, results: Some(results) }),
+        Err(LocalSolverError::Solver(e)) => Some(FEAAnalysisResponse { success: false, error: Some(e.to_string()), results: None }),
+        Err(_) => None,
+    }
+}
+
+fn run(request: &FEAAnalysisRequest) -> Result<FEAResultsData, LocalSolverError> {
+    if request.model.nodes.len() > MAX_NODES {
+        return Err(LocalSolverError::TooLarge(request.model.nodes.len()));
+    }
+    let mut model = build_model(&request.model)?;
+
+    let options = match &request.options {
+        Some(FEAAnalysisOptions { settings: Some(settings), .. }) => settings.clone(),
+        Some(opts) => {
+            let options = match opts.analysis_type.to_lowercase().as_str() {
+                "" | "linear" => AnalysisOptions::linear(),
+                "pdelta" | "p-delta" | "p_delta" => AnalysisOptions::p_delta().with_max_iter(opts.max_iterations),
+                "nonlinear" => AnalysisOptions::nonlinear().with_max_iter(opts.max_iterations),
+                "large_displacement" | "large-displacement" | "corotational" => {
+                    AnalysisOptions::large_displacement(opts.load_steps).with_max_iter(opts.max_iterations)
+                }
+                other => return Err(LocalSolverError::Unsupported(other.to_string())),
+            };
+            if opts.arc_length { options.with_arc_length() } else { options }
+        }
+        None => AnalysisOptions::linear(),
+    };
+    model.analyze(options)?;
+    let mut results = collect_results(&model)?;
+
+    // Modes of vibration; a model without mass still has its static results
+    let num_modes = request.options.as_ref().map_or(0, |opts| opts.num_modes);
+    if num_modes > 0 {
+        match model.analyze_modal(&AnalysisOptions::modal(num_modes)) {
+            Ok(modal) => results.modes = modal.modes.into_iter().map(|mode| FEAModeResult {
+                frequency: mode.frequency,
+                period: mode.period,
+                mass_ratio: mode.mass_ratio,
+                shape: mode.shape.into_iter().map(|(node, d)| FEAModeShapeResult {
+                    node,
+                    dx: d[0], dy: d[1], dz: d[2],
+                    rx: d[3], ry: d[4], rz: d[5],
+                }).collect(),
+            }).collect(),
+            Err(e) => results.warnings.push(format!("Modal analysis failed: {}", e)),
+        }
+    }
+    Ok(results)
+}
+
+fn build_model(data: &FEAModelData) -> FEAResult<FEModel> {
+    let mut model = FEModel::new();
+
+    for mat in &data.materials {
+        model.add_material(&mat.name, Material::new(mat.e, mat.g, mat.nu, mat.rho))?;
+    }
+    for sec in &data.sections {
+        model.add_section(&sec.name, Section::new(sec.a, sec.iy, sec.iz, sec.j))?;
+    }
+    for node in &data.nodes {
+        model.add_node(&node.name, Node::new(node.x, node.y, node.z))?;
+    }
+    for member in &data.members {
+        let mut m = Member::new(&member.i_node, &member.j_node, &member.material, &member.section);
+        m.rotation = member.rotation;
+        if let Some(releases) = &member.releases {
+            m.releases.i_node = releases.i_node();
+            m.releases.j_node = releases.j_node();
+        }
+        model.add_member(&member.name, m)?;
+    }
+    for plate in &data.plates {
+        let formulation = match plate.formulation.to_lowercase().as_str() {
+            "mindlin" | "mindlin-reissner" | "thick" => PlateFormulation::Mindlin,
+            "dkmq" | "discrete-kirchhoff" => PlateFormulation::DKMQ,
+            _ => PlateFormulation::Kirchhoff,
+        };
+        let p = Plate::new(&plate.i_node, &plate.j_node, &plate.m_node, &plate.n_node, plate.thickness, &plate.material)
+            .with_modifiers(plate.kx_mod, plate.ky_mod)
+            .with_formulation(formulation);
+        model.add_plate(&plate.name, p)?;
+    }
+    for sup in &data.supports {
+        model.add_support(&sup.node, Support::with_restraints(sup.dx, sup.dy, sup.dz, sup.rx, sup.ry, sup.rz))?;
+    }
+    for load in &data.node_loads {
+        model.add_node_load(&load.node, NodeLoad::new(load.fx, load.fy, load.fz, load.mx, load.my, load.mz, &load.case))?;
+    }
+    for load in &data.distributed_loads {
+        // Loads from the viewport are in global directions
+        let direction = match load.direction.to_uppercase().as_str() {
+            "FX" => LoadDirection::FX,
+            "FZ" => LoadDirection::FZ,
+            "MX" | "TORQUE" => LoadDirection::Mx,
+            _ => LoadDirection::FY,
+        };
+        model.add_member_dist_load(&load.member, DistributedLoad::linear(load.w1, load.w2, direction, &load.case))?;
+    }
+    for load in &data.plate_loads {
+        model.add_plate_load(&load.plate, PlateLoad::new(load.pressure, &load.case))?;
+    }
+    for combo in &data.load_combos {
+        let combination = combo.factors.iter()
+            .fold(LoadCombination::new(&combo.name), |lc, (case, &factor)| lc.with_case(case, factor));
+        let combination = combo.tags.iter().fold(combination, |lc, tag| lc.with_tag(tag));
+        model.add_load_combo(combination)?;
+    }
+    Ok(model)
+}
+
+/// Results of every combination, in the server's response format
+fn collect_results(model: &FEModel) -> FEAResult<FEAResultsData> {
+    let combo_names = model.combo_names();
+    let mut node_displacements = Vec::new();
+    let mut reactions = Vec::new();
+    let mut member_forces = Vec::new();
+    let mut member_diagrams = Vec::new();
+    let mut plate_stresses = Vec::new();
+
+    for combo in &combo_names {
+        for node in model.nodes.keys() {
+            if let Ok(d) = model.node_displacement(node, combo) {
+                node_displacements.push(FEANodeDisplacementResult {
+                    node: node.clone(),
+                    combo: combo.clone(),
+                    dx: d.dx, dy: d.dy, dz: d.dz,
+                    rx: d.rx, ry: d.ry, rz: d.rz,
+                });
+            }
+        }
+        for node in model.supports.keys() {
+            if let Ok(r) = model.node_reactions(node, combo) {
+                reactions.push(FEAReactionResult {
+                    node: node.clone(),
+                    combo: combo.clone(),
+                    fx: r.fx, fy: r.fy, fz: r.fz,
+                    mx: r.mx, my: r.my, mz: r.mz,
+                });
+            }
+        }
+        for member in model.members.keys() {
+            if let (Ok(fi), Ok(fj)) = (model.member_forces_i(member, combo), model.member_forces_j(member, combo)) {
+                member_forces.push(FEAMemberForceResult {
+                    member: member.clone(),
+                    combo: combo.clone(),
+                    axial_i: fi.axial, shear_y_i: fi.shear_y, shear_z_i: fi.shear_z,
+                    torsion_i: fi.torsion, moment_y_i: fi.moment_y, moment_z_i: fi.moment_z,
+                    axial_j: fj.axial, shear_y_j: fj.shear_y, shear_z_j: fj.shear_z,
+                    torsion_j: fj.torsion, moment_y_j: fj.moment_y, moment_z_j: fj.moment_z,
+                });
+            }
+            if let Ok(diagram) = model.member_diagram(member, combo, DIAGRAM_STATIONS) {
+                member_diagrams.push(FEAMemberDiagramResult {
+                    member: member.clone(),
+                    combo: combo.clone(),
+                    x: diagram.iter().map(|(x, _)| *x).collect(),
+                    axial: diagram.iter().map(|(_, f)| f.axial).collect(),
+                    shear_y: diagram.iter().map(|(_, f)| f.shear_y).collect(),
+                    shear_z: diagram.iter().map(|(_, f)| f.shear_z).collect(),
+                    torsion: diagram.iter().map(|(_, f)| f.torsion).collect(),
+                    moment_y: diagram.iter().map(|(_, f)| f.moment_y).collect(),
+                    moment_z: diagram.iter().map(|(_, f)| f.moment_z).collect(),
+                });
+            }
+        }
+        for plate in model.plates.keys() {
+            if let Ok(s) = model.plate_stress(plate, combo) {
+                plate_stresses.push(FEAPlateStressResult {
+                    plate: plate.clone(),
+                    combo: combo.clone(),
+                    sx: s.sx, sy: s.sy, txy: s.txy, von_mises: s.von_mises,
+                    mx: s.mx, my: s.my, mxy: s.mxy,
+                });
+            }
+        }
+    }
+
+    let first_combo = combo_names.first().cloned().unwrap_or_else(|| "Combo 1".to_string());
+    let summary = model.summary(&first_combo)?;
+    Ok(FEAResultsData {
+        node_displacements,
+        reactions,
+        member_forces,
+        member_diagrams,
+        plate_stresses,
+        modes: Vec::new(),
+        warnings: model.warnings().to_vec(),
+        summary: FEASummaryResult {
+            max_displacement: summary.max_displacement,
+            max_disp_node: summary.max_disp_node,
+            max_reaction: summary.max_reaction,
+            max_reaction_node: summary.max_reaction_node,
+            num_nodes: summary.num_nodes,
+            num_members: summary.num_members,
+            total_dofs: summary.total_dofs,
+            free_dofs: summary.free_dofs,
+        },
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalSolverError {
+    #[error("Model has {0} nodes; models over {MAX_NODES} nodes are analysed on the server")]
+    TooLarge(usize),
+    #[error("Analysis type '{0}' is analysed on the server")]
+    Unsupported(String),
+    #[error(transparent)]
+    Solver(#[from] FEAError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two members in line between fixed ends, pushed along their axis at the middle node
+    fn request(releases: serde_json::Value, options: serde_json::Value) -> FEAAnalysisRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": {
+                "nodes": [
+                    { "name": "N1", "x": 0.0, "y": 0.0, "z": 0.0 },
+                    { "name": "N2", "x": 4.0, "y": 0.0, "z": 0.0 },
+                    { "name": "N3", "x": 8.0, "y": 0.0, "z": 0.0 },
+                ],
+                "materials": [{ "name": "Steel", "e": 200e9, "g": 77e9, "nu": 0.3, "rho": 7850.0 }],
+                "sections": [{ "name": "S1", "a": 0.01, "iy": 1e-4, "iz": 1e-4, "j": 2e-4 }],
+                "members": [
+                    { "name": "M1", "i_node": "N1", "j_node": "N2", "material": "Steel", "section": "S1" },
+                    { "name": "M2", "i_node": "N2", "j_node": "N3", "material": "Steel", "section": "S1", "releases": releases },
+                ],
+                "supports": (["N1", "N3"].map(|node| serde_json::json!({
+                    "node": node, "dx": true, "dy": true, "dz": true, "rx": true, "ry": true, "rz": true,
+                }))),
+                "node_loads": [{ "node": "N2", "fx": 10e3, "fy": 0.0, "fz": 0.0 }],
+                "load_combos": [{ "name": "Combo 1", "factors": { "Case 1": 1.0 } }],
+            },
+            "options": options,
+        })).unwrap()
+    }
+
+    fn reaction_fx(response: &FEAAnalysisResponse, node: &str) -> f64 {
+        let results = response.results.as_ref().unwrap();
+        results.reactions.iter().find(|r| r.node == node).unwrap().fx
+    }
+
+    #[test]
+    fn test_axial_release_is_applied() {
+        let fixed = analyze(&request(serde_json::json!({}), serde_json::Value::Null)).unwrap();
+        assert!((reaction_fx(&fixed, "N3") + 5e3).abs() < 1.0);
+
+        // With M2's axial force released at N2, N1 takes the whole load
+        let released = analyze(&request(serde_json::json!({ "i_node_dx": true }), serde_json::Value::Null)).unwrap();
+        assert!(released.success, "{:?}", released.error);
+        assert!(reaction_fx(&released, "N3").abs() < 1.0);
+        assert!((reaction_fx(&released, "N1") + 10e3).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_analysis_types_follow_the_server() {
+        let nonlinear = analyze(&request(serde_json::json!({}), serde_json::json!({ "analysis_type": "nonlinear" })));
+        assert!(nonlinear.unwrap().success);
+
+        // A type this build doesn't know is left to the server
+        assert!(analyze(&request(serde_json::json!({}), serde_json::json!({ "analysis_type": "buckling" }))).is_none());
+    }
+
+    #[test]
+    fn test_unknown_model_features_do_not_parse() {
+        let mut value = serde_json::to_value(request(serde_json::json!({}), serde_json::Value::Null)).unwrap();
+        value["model"]["gaps"] = serde_json::json!([{ "name": "G1", "i_node": "N1", "j_node": "N2" }]);
+        assert!(serde_json::from_value::<FEAAnalysisRequest>(value).is_err());
+    }
+}
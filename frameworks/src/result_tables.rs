@@ -0,0 +1,253 @@
+//! Tabulated results of the combination shown, as the console lists them
+//!
+//! The scene sends the rows in SI, whichever solver produced them; they are
+//! turned into text in the display units only when tabulated.
+
+use serde::Deserialize;
+
+use crate::units::{Quantity, Units};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ResultTables {
+    /// Load combination the rows are for, when the results have several
+    #[serde(default)]
+    pub combo: Option<String>,
+    pub displacements: Vec<DisplacementRow>,
+    pub reactions: Vec<ReactionRow>,
+    pub member_forces: Vec<MemberForceRow>,
+    pub stresses: Vec<StressRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DisplacementRow {
+    pub node: String,
+    pub dx: f64,
+    pub dy: f64,
+    pub dz: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ReactionRow {
+    pub node: String,
+    pub fx: f64,
+    pub fy: f64,
+    pub fz: f64,
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+/// Forces at the i end of a member
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MemberForceRow {
+    pub member: String,
+    pub axial: f64,
+    pub vy: f64,
+    pub vz: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+/// Von Mises stress at a plate node, and at its top and bottom surfaces
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StressRow {
+    pub node: String,
+    pub von_mises: f64,
+    pub top: f64,
+    pub bottom: f64,
+}
+
+/// One table as shown: a title with its units, column headings, and the
+/// formatted values
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultTable {
+    pub title: String,
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ResultTable {
+    /// The table as CSV: a heading row, then one line per row
+    pub fn to_csv(&self) -> String {
+        std::iter::once(self.columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>())
+            .chain(self.rows.iter().map(|row| row.iter().map(|cell| csv_field(cell)).collect()))
+            .map(|fields| fields.join(",") + "\n")
+            .collect()
+    }
+}
+
+/// A field quoted if it holds a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl ResultTables {
+    /// The tables in the display units
+    pub fn tables(&self, units: Units) -> Vec<ResultTable> {
+        vec![
+            self.displacement_table(units),
+            self.reaction_table(units),
+            self.member_force_table(units),
+            self.stress_table(units),
+        ]
+    }
+
+    /// The displacement, reaction and member force tables in one CSV file,
+    /// each under a line with its title and separated by a blank line
+    pub fn to_csv(&self, units: Units) -> String {
+        let mut csv = String::new();
+        if let Some(combo) = &self.combo {
+            csv += &format!("Combination,{}\n\n", csv_field(combo));
+        }
+        let tables = [
+            self.displacement_table(units),
+            self.reaction_table(units),
+            self.member_force_table(units),
+        ];
+        for (i, table) in tables.iter().enumerate() {
+            if i > 0 {
+                csv.push('\n');
+            }
+            csv += &csv_field(&table.title);
+            csv.push('\n');
+            csv += &table.to_csv();
+        }
+        csv
+    }
+
+    /// Name to download the CSV as, after the combination
+    pub fn csv_file_name(&self) -> String {
+        match &self.combo {
+            Some(combo) => {
+                let combo: String = combo.chars()
+                    .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+                    .collect();
+                format!("results_{combo}.csv")
+            }
+            None => "results.csv".to_string(),
+        }
+    }
+
+    fn displacement_table(&self, units: Units) -> ResultTable {
+        ResultTable {
+            title: format!("Displacements ({})", units.symbol(Quantity::Displacement)),
+            columns: vec!["Node", "DX", "DY", "DZ", "Mag"],
+            rows: self.displacements.iter().map(|d| {
+                let magnitude = (d.dx * d.dx + d.dy * d.dy + d.dz * d.dz).sqrt();
+                let mut row = vec![d.node.clone()];
+                row.extend([d.dx, d.dy, d.dz, magnitude].map(|v| value(units, Quantity::Displacement, v, 4)));
+                row
+            }).collect(),
+        }
+    }
+
+    fn reaction_table(&self, units: Units) -> ResultTable {
+        let force = |si| value(units, Quantity::Force, si, 2);
+        let moment = |si| value(units, Quantity::Moment, si, 2);
+        ResultTable {
+            title: format!("Reactions ({}, {})", units.symbol(Quantity::Force), units.symbol(Quantity::Moment)),
+            columns: vec!["Node", "Fx", "Fy", "Fz", "Mx", "My", "Mz"],
+            rows: self.reactions.iter().map(|r| vec![
+                r.node.clone(), force(r.fx), force(r.fy), force(r.fz), moment(r.mx), moment(r.my), moment(r.mz),
+            ]).collect(),
+        }
+    }
+
+    fn member_force_table(&self, units: Units) -> ResultTable {
+        let force = |si| value(units, Quantity::Force, si, 2);
+        let moment = |si| value(units, Quantity::Moment, si, 2);
+        ResultTable {
+            title: format!("Beam Forces ({}, {})", units.symbol(Quantity::Force), units.symbol(Quantity::Moment)),
+            columns: vec!["Elem", "Axial", "Vy", "Vz", "My", "Mz"],
+            rows: self.member_forces.iter().map(|b| vec![
+                b.member.clone(), force(b.axial), force(b.vy), force(b.vz), moment(b.my), moment(b.mz),
+            ]).collect(),
+        }
+    }
+
+    fn stress_table(&self, units: Units) -> ResultTable {
+        ResultTable {
+            title: format!("Stresses ({})", units.symbol(Quantity::Stress)),
+            columns: vec!["Node", "VM", "Top", "Bot"],
+            rows: self.stresses.iter().map(|s| {
+                let mut row = vec![s.node.clone()];
+                row.extend([s.von_mises, s.top, s.bottom].map(|v| value(units, Quantity::Stress, v, 2)));
+                row
+            }).collect(),
+        }
+    }
+}
+
+/// An SI value in the display unit, to a number of decimals
+fn value(units: Units, quantity: Quantity, si: f64, decimals: usize) -> String {
+    format!("{:.decimals$}", units.from_si(quantity, si))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables() -> ResultTables {
+        ResultTables {
+            combo: Some("1.2D + 1.6L".to_string()),
+            displacements: vec![DisplacementRow { node: "N2".to_string(), dx: 0.003, dy: -0.004, dz: 0.0 }],
+            reactions: vec![ReactionRow {
+                node: "N1".to_string(),
+                fx: -1500.0,
+                fy: 2000.0,
+                fz: 0.0,
+                mx: 0.0,
+                my: 0.0,
+                mz: 4500.0,
+            }],
+            member_forces: vec![MemberForceRow {
+                member: "Beam, \"B1\"".to_string(),
+                axial: 1500.0,
+                vy: 0.0,
+                vz: 0.0,
+                my: 0.0,
+                mz: 0.0,
+            }],
+            stresses: vec![StressRow { node: "N1".to_string(), von_mises: 1e6, top: 1e6, bottom: -1e6 }],
+        }
+    }
+
+    #[test]
+    fn test_csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("N1"), "N1");
+        assert_eq!(csv_field("1.2D + 1.6L"), "1.2D + 1.6L");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_csv_in_display_units() {
+        let csv = tables().to_csv(Units::MetricKn);
+        assert_eq!(csv, "Combination,1.2D + 1.6L\n\n\
+            Displacements (mm)\n\
+            Node,DX,DY,DZ,Mag\n\
+            N2,3.0000,-4.0000,0.0000,5.0000\n\n\
+            \"Reactions (kN, kN·m)\"\n\
+            Node,Fx,Fy,Fz,Mx,My,Mz\n\
+            N1,-1.50,2.00,0.00,0.00,0.00,4.50\n\n\
+            \"Beam Forces (kN, kN·m)\"\n\
+            Elem,Axial,Vy,Vz,My,Mz\n\
+            \"Beam, \"\"B1\"\"\",1.50,0.00,0.00,0.00,0.00\n");
+        assert!(tables().to_csv(Units::Si).contains("N1,-1500.00,2000.00,0.00,0.00,0.00,4500.00\n"));
+        // Stresses are tabulated but left out of the file
+        assert!(!csv.contains("Stresses"));
+        assert_eq!(tables().tables(Units::MetricKn)[3].rows, [["N1", "1.00", "1.00", "-1.00"]]);
+    }
+
+    #[test]
+    fn test_csv_file_name() {
+        assert_eq!(tables().csv_file_name(), "results_1_2D___1_6L.csv");
+        assert_eq!(ResultTables::default().csv_file_name(), "results.csv");
+        assert!(!ResultTables::default().to_csv(Units::MetricKn).starts_with("Combination"));
+    }
+}
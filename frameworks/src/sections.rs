@@ -0,0 +1,173 @@
+//! Member sections: the solver's [`Section`], named and colored for the
+//! viewport, and a catalog of rolled steel shapes
+//!
+//! Section properties are always those computed by the solver's own
+//! constructors, so what the properties panel shows, what the viewport
+//! stores on a member and what an analysis is sent all agree. The solver
+//! puts the depth of a shape along local z; the viewport draws it along
+//! local y (up for a horizontal member), so the bending axes are swapped
+//! once, in [`MemberSection::to_analysis`].
+
+use fea_solver::elements::{Section, SectionShape};
+use serde::{Deserialize, Serialize};
+
+use crate::fea_client::FEASectionData;
+
+/// Metres in an inch, for the catalog dimensions
+const INCH: f64 = 0.0254;
+
+/// A section assigned to members
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberSection {
+    pub name: String,
+    pub section: Section,
+    /// CSS color members of this section are rendered in
+    pub color: String,
+}
+
+impl MemberSection {
+    /// Named section in the default color of its shape
+    pub fn new(name: impl Into<String>, section: Section) -> Self {
+        let color = default_color(section.shape.as_ref()).to_string();
+        Self { name: name.into(), section, color }
+    }
+
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Section of a shape, named by its dimensions in mm
+    pub fn from_shape(shape: SectionShape) -> Self {
+        let mm = |m: f64| (m * 1000.0).round();
+        let (name, section) = match shape {
+            SectionShape::Rectangle { width, depth } => (
+                format!("Rect {}x{}", mm(width), mm(depth)),
+                Section::rectangular(width, depth),
+            ),
+            SectionShape::Circle { diameter } => (
+                format!("Circ {}", mm(diameter)),
+                Section::circular(diameter),
+            ),
+            SectionShape::Pipe { outer_diameter, wall_thickness } => (
+                format!("Pipe {}x{}", mm(outer_diameter), mm(wall_thickness)),
+                Section::pipe(outer_diameter, wall_thickness),
+            ),
+            SectionShape::WideFlange { depth, flange_width, flange_thickness, web_thickness } => (
+                format!("I {}x{}", mm(depth), mm(flange_width)),
+                Section::wide_flange(depth, flange_width, flange_thickness, web_thickness),
+            ),
+            SectionShape::Box { width, depth, wall_thickness } => (
+                format!("Box {}x{}x{}", mm(depth), mm(width), mm(wall_thickness)),
+                Section::box_section(width, depth, wall_thickness),
+            ),
+        };
+        Self::new(name, section)
+    }
+
+    /// Catalog section by designation, e.g. "W12x26" or "HSS6x6x3/8"
+    pub fn catalog(name: &str) -> Option<Self> {
+        CATALOG.iter().find(|c| c.name == name).map(CatalogSection::to_section)
+    }
+
+    /// Properties as sent to an analysis, about the viewport's axes: Iz
+    /// resists bending about local z, across the depth
+    pub fn to_analysis(&self) -> FEASectionData {
+        FEASectionData {
+            name: self.name.clone(),
+            a: self.section.a,
+            iy: self.section.iz,
+            iz: self.section.iy,
+            j: self.section.j,
+        }
+    }
+}
+
+impl Default for MemberSection {
+    fn default() -> Self {
+        Self::from_shape(SectionShape::Rectangle { width: 0.3, depth: 0.5 })
+    }
+}
+
+fn default_color(shape: Option<&SectionShape>) -> &'static str {
+    match shape {
+        Some(SectionShape::WideFlange { .. }) => "#607d8b",
+        Some(SectionShape::Box { .. } | SectionShape::Pipe { .. }) => "#78909c",
+        _ => "#9e9e9e",
+    }
+}
+
+/// Families of shapes in the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFamily {
+    /// Wide flange I-shapes
+    W,
+    /// Rectangular and square hollow structural sections
+    Hss,
+}
+
+/// A rolled shape, with its dimensions in inches as tabulated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CatalogSection {
+    pub name: &'static str,
+    pub family: CatalogFamily,
+    /// Overall depth
+    pub depth: f64,
+    /// Flange width, or overall width of a hollow section
+    pub width: f64,
+    /// Flange thickness, or design wall thickness of a hollow section
+    pub thickness: f64,
+    /// Web thickness; the wall thickness for a hollow section
+    pub web_thickness: f64,
+}
+
+impl CatalogSection {
+    pub fn shape(&self) -> SectionShape {
+        match self.family {
+            CatalogFamily::W => SectionShape::WideFlange {
+                depth: self.depth * INCH,
+                flange_width: self.width * INCH,
+                flange_thickness: self.thickness * INCH,
+                web_thickness: self.web_thickness * INCH,
+            },
+            CatalogFamily::Hss => SectionShape::Box {
+                width: self.width * INCH,
+                depth: self.depth * INCH,
+                wall_thickness: self.thickness * INCH,
+            },
+        }
+    }
+
+    pub fn to_section(&self) -> MemberSection {
+        MemberSection::new(self.name, MemberSection::from_shape(self.shape()).section)
+    }
+}
+
+const fn w(name: &'static str, depth: f64, width: f64, tf: f64, tw: f64) -> CatalogSection {
+    CatalogSection { name, family: CatalogFamily::W, depth, width, thickness: tf, web_thickness: tw }
+}
+
+const fn hss(name: &'static str, depth: f64, width: f64, t: f64) -> CatalogSection {
+    CatalogSection { name, family: CatalogFamily::Hss, depth, width, thickness: t, web_thickness: t }
+}
+
+/// Shapes offered by the section picker; HSS walls are the design
+/// thickness, 0.93 of nominal
+pub const CATALOG: &[CatalogSection] = &[
+    w("W8x31", 8.00, 8.00, 0.435, 0.285),
+    w("W10x33", 9.73, 7.96, 0.435, 0.290),
+    w("W12x26", 12.2, 6.49, 0.380, 0.230),
+    w("W12x50", 12.2, 8.08, 0.640, 0.370),
+    w("W14x22", 13.7, 5.00, 0.335, 0.230),
+    w("W14x48", 13.8, 8.03, 0.595, 0.340),
+    w("W16x40", 16.0, 7.00, 0.505, 0.305),
+    w("W18x50", 18.0, 7.50, 0.570, 0.355),
+    w("W21x62", 21.0, 8.24, 0.615, 0.400),
+    w("W24x76", 23.9, 8.99, 0.680, 0.440),
+    hss("HSS4x4x1/4", 4.0, 4.0, 0.233),
+    hss("HSS6x4x1/4", 6.0, 4.0, 0.233),
+    hss("HSS6x6x3/8", 6.0, 6.0, 0.349),
+    hss("HSS8x4x3/8", 8.0, 4.0, 0.349),
+    hss("HSS8x8x1/2", 8.0, 8.0, 0.465),
+    hss("HSS10x6x1/2", 10.0, 6.0, 0.465),
+];
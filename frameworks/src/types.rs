@@ -1,3 +1,4 @@
+use fea_solver::elements::Section;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -58,14 +59,7 @@ pub struct Node {
 pub struct Beam {
     pub id: usize,
     pub node_ids: Vec<usize>,
-    pub section: BeamSection,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct BeamSection {
-    pub width: f64,
-    pub height: f64,
-    pub section_type: SectionType,
+    pub section: Section,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -75,13 +69,6 @@ pub struct Shell {
     pub thickness: f64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum SectionType {
-    Rectangular,
-    Circular,
-    IBeam,
-}
-
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Material {
     pub name: String,
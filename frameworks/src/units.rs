@@ -0,0 +1,204 @@
+//! Display units: the unit preference values are entered and shown in
+//!
+//! Results are in SI (N, m, Pa) and the scene keeps its values in
+//! [`Units::SCENE`]; a [`Units`] preference only changes what the user reads
+//! and types. Each
+//! quantity is shown in a unit of one of the solver's [`UnitSystem`]s, so
+//! the conversion factors are the solver's own.
+
+use fea_solver::units::{Dimension, ForceUnit, LengthUnit, UnitSystem};
+use serde::{Deserialize, Serialize};
+
+/// Pounds and feet, for area loads
+const LBF_FT: UnitSystem = UnitSystem::new(ForceUnit::PoundForce, LengthUnit::Foot);
+
+/// Physical quantities values are entered and shown as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    /// Coordinates, spans and section dimensions
+    Length,
+    /// Nodal displacements and deflections
+    Displacement,
+    /// Section areas
+    Area,
+    /// Second moments of area and torsion constants
+    SecondMoment,
+    Force,
+    Moment,
+    /// Distributed loads on members
+    LineLoad,
+    /// Area loads on plates
+    Pressure,
+    Stress,
+    /// Elastic and shear moduli
+    Modulus,
+    /// Spring support stiffness
+    Stiffness,
+    /// Plate bending and twisting moments
+    MomentPerLength,
+}
+
+impl Quantity {
+    pub const ALL: [Quantity; 12] = [
+        Quantity::Length,
+        Quantity::Displacement,
+        Quantity::Area,
+        Quantity::SecondMoment,
+        Quantity::Force,
+        Quantity::Moment,
+        Quantity::LineLoad,
+        Quantity::Pressure,
+        Quantity::Stress,
+        Quantity::Modulus,
+        Quantity::Stiffness,
+        Quantity::MomentPerLength,
+    ];
+
+    /// Name the scene looks the quantity up by
+    pub fn key(&self) -> &'static str {
+        match self {
+            Quantity::Length => "length",
+            Quantity::Displacement => "displacement",
+            Quantity::Area => "area",
+            Quantity::SecondMoment => "second_moment",
+            Quantity::Force => "force",
+            Quantity::Moment => "moment",
+            Quantity::LineLoad => "line_load",
+            Quantity::Pressure => "pressure",
+            Quantity::Stress => "stress",
+            Quantity::Modulus => "modulus",
+            Quantity::Stiffness => "stiffness",
+            Quantity::MomentPerLength => "moment_per_length",
+        }
+    }
+
+    fn dimension(&self) -> Dimension {
+        match self {
+            Quantity::Length | Quantity::Displacement => Dimension::Length,
+            Quantity::Area => Dimension::Area,
+            Quantity::SecondMoment => Dimension::SecondMoment,
+            Quantity::Force | Quantity::MomentPerLength => Dimension::Force,
+            Quantity::Moment => Dimension::Moment,
+            Quantity::LineLoad | Quantity::Stiffness => Dimension::ForcePerLength,
+            Quantity::Pressure | Quantity::Stress | Quantity::Modulus => Dimension::Stress,
+        }
+    }
+}
+
+/// Unit preference for the whole app
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Units {
+    /// N and m; millimetres for displacements, MPa for stresses
+    Si,
+    /// kN and m; millimetres for displacements, MPa for stresses
+    #[default]
+    MetricKn,
+    /// kip and ft; inches for displacements, ksi for stresses
+    Imperial,
+}
+
+impl Units {
+    pub const ALL: [Units; 3] = [Units::Si, Units::MetricKn, Units::Imperial];
+
+    /// Units the scene stores loads, spring stiffnesses and lengths in
+    pub const SCENE: Units = Units::MetricKn;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Units::Si => "SI (N, m)",
+            Units::MetricKn => "Metric (kN, m)",
+            Units::Imperial => "Imperial (kip, ft)",
+        }
+    }
+
+    /// Short form for settings and `<select>` values
+    pub fn key(&self) -> &'static str {
+        match self {
+            Units::Si => "si",
+            Units::MetricKn => "metric_kn",
+            Units::Imperial => "imperial",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|u| u.key() == key)
+    }
+
+    /// The system a quantity is shown in, and its unit symbol
+    fn unit(&self, quantity: Quantity) -> (UnitSystem, &'static str) {
+        use Quantity::*;
+        match (self, quantity) {
+            (Units::Imperial, Length) => (UnitSystem::KIP_FT, "ft"),
+            (Units::Imperial, Displacement) => (UnitSystem::KIP_IN, "in"),
+            (Units::Imperial, Area) => (UnitSystem::KIP_IN, "in²"),
+            (Units::Imperial, SecondMoment) => (UnitSystem::KIP_IN, "in⁴"),
+            (Units::Imperial, Force) => (UnitSystem::KIP_FT, "kip"),
+            (Units::Imperial, Moment) => (UnitSystem::KIP_FT, "kip·ft"),
+            (Units::Imperial, LineLoad) => (UnitSystem::KIP_FT, "kip/ft"),
+            (Units::Imperial, Pressure) => (LBF_FT, "psf"),
+            (Units::Imperial, Stress | Modulus) => (UnitSystem::KIP_IN, "ksi"),
+            (Units::Imperial, Stiffness) => (UnitSystem::KIP_IN, "kip/in"),
+            (Units::Imperial, MomentPerLength) => (UnitSystem::KIP_FT, "kip·ft/ft"),
+
+            // Metric lengths and stresses read the same in N and kN
+            (_, Length) => (UnitSystem::SI, "m"),
+            (_, Displacement) => (UnitSystem::N_MM, "mm"),
+            (_, Area) => (UnitSystem::SI, "m²"),
+            (_, SecondMoment) => (UnitSystem::SI, "m⁴"),
+            (_, Stress) => (UnitSystem::N_MM, "MPa"),
+            (_, Modulus) => (UnitSystem::KN_MM, "GPa"),
+
+            (Units::Si, Force) => (UnitSystem::SI, "N"),
+            (Units::Si, Moment) => (UnitSystem::SI, "N·m"),
+            (Units::Si, LineLoad) => (UnitSystem::SI, "N/m"),
+            (Units::Si, Pressure) => (UnitSystem::SI, "Pa"),
+            (Units::Si, Stiffness) => (UnitSystem::SI, "N/m"),
+            (Units::Si, MomentPerLength) => (UnitSystem::SI, "N·m/m"),
+
+            (Units::MetricKn, Force) => (UnitSystem::KN_M, "kN"),
+            (Units::MetricKn, Moment) => (UnitSystem::KN_M, "kN·m"),
+            (Units::MetricKn, LineLoad) => (UnitSystem::KN_M, "kN/m"),
+            (Units::MetricKn, Pressure) => (UnitSystem::KN_M, "kPa"),
+            (Units::MetricKn, Stiffness) => (UnitSystem::KN_M, "kN/m"),
+            (Units::MetricKn, MomentPerLength) => (UnitSystem::KN_M, "kN·m/m"),
+        }
+    }
+
+    pub fn symbol(&self, quantity: Quantity) -> &'static str {
+        self.unit(quantity).1
+    }
+
+    /// Size of the display unit of a quantity in SI
+    pub fn si_factor(&self, quantity: Quantity) -> f64 {
+        self.unit(quantity).0.si_factor(quantity.dimension())
+    }
+
+    /// An SI value in the display unit
+    pub fn from_si(&self, quantity: Quantity, value: f64) -> f64 {
+        value / self.si_factor(quantity)
+    }
+
+    /// A value in the display unit, in SI
+    pub fn to_si(&self, quantity: Quantity, value: f64) -> f64 {
+        value * self.si_factor(quantity)
+    }
+
+    /// A value in these display units, in another preference's
+    pub fn convert(&self, quantity: Quantity, value: f64, to: Units) -> f64 {
+        to.from_si(quantity, self.to_si(quantity, value))
+    }
+
+    /// Heading for a value of a quantity, e.g. "Magnitude (kN)"
+    pub fn label(&self, name: &str, quantity: Quantity) -> String {
+        format!("{name} ({})", self.symbol(quantity))
+    }
+
+    /// Symbol and SI factor of every quantity, for the scene's labels
+    pub fn to_js(&self) -> serde_json::Value {
+        Quantity::ALL.iter()
+            .map(|&q| (q.key().to_string(), serde_json::json!({ "symbol": self.symbol(q), "factor": self.si_factor(q) })))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
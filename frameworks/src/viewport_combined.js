@@ -2353,181 +2353,3 @@ window.clearDiagrams = function() {
     removeDiagramsByName('ContourVisualization');
 };
 
-/**
- * Load example 3-story building structure
- * This creates a 16m x 15m building footprint with 3 floors at 3m height each
- * Including floor slabs and wall panels
- */
-window.loadExampleStructure = function() {
-    console.log('=== loadExampleStructure called ===');
-    
-    try {
-        // Check if helper functions are available
-        if (!window.createNodeAtPosition || !window.createBeamBetweenNodes) {
-            console.error('Geometry helper functions not available. Canvas may not be initialized.');
-            return;
-        }
-        
-        // Clear existing geometry
-        if (window.clearAllGeometry) {
-            window.clearAllGeometry();
-        }
-
-        console.log('Creating example 3-story building structure...');
-
-        // Node positions for ground level (Y=0)
-        // Building footprint: corners and key column locations
-        const groundNodePositions = [
-            // Left edge (X=5)
-            [5, 0, 5],    // 0 - front left
-            [5, 0, 0],    // 1
-            [5, 0, -5],   // 2
-            [5, 0, -10],  // 3 - back left
-            // Middle bay (X=13)
-            [13, 0, -10], // 4 - back middle
-            [13, 0, 5],   // 5 - front middle
-            // Right edge (X=21)
-            [21, 0, 5],   // 6 - front right
-            [21, 0, -10], // 7 - back right
-            [21, 0, 0],   // 8
-            [21, 0, -5],  // 9
-            // Interior columns
-            [13, 0, 0],   // 10
-            [13, 0, -5],  // 11
-            // Core opening nodes
-            [13, 0, -2],  // 12
-            [11, 0, -2],  // 13
-            [11, 0, 0],   // 14
-        ];
-
-        // Store nodes by level for geometry creation
-        const nodesByLevel = [];
-        const storeyHeight = 3;
-        const numStoreys = 4; // Ground + 3 floors
-
-        // Create nodes for each level
-        for (let level = 0; level < numStoreys; level++) {
-            const levelNodes = [];
-            const y = level * storeyHeight;
-            
-            groundNodePositions.forEach(([x, _, z]) => {
-                const node = window.createNodeAtPosition(x, y, z);
-                if (node) {
-                    levelNodes.push(node);
-                }
-            });
-            
-            nodesByLevel.push(levelNodes);
-        }
-
-        console.log(`Created ${nodesByLevel.length * groundNodePositions.length} nodes`);
-
-        // Create vertical columns (beams between levels)
-        for (let level = 0; level < numStoreys - 1; level++) {
-            const lowerNodes = nodesByLevel[level];
-            const upperNodes = nodesByLevel[level + 1];
-            
-            for (let i = 0; i < lowerNodes.length; i++) {
-                window.createBeamBetweenNodes(lowerNodes[i], upperNodes[i]);
-            }
-        }
-
-        console.log('Created columns');
-
-        // Apply pinned constraints to all ground level nodes
-        const groundNodes = nodesByLevel[0];
-        groundNodes.forEach(node => {
-            if (window.createConstraintSymbol) {
-                window.createConstraintSymbol(node, 'pinned');
-            }
-        });
-
-        console.log('Created pinned supports');
-
-        // Create floor slabs at each level above ground
-        // Main floor slab corners: nodes 0 (front-left), 3 (back-left), 7 (back-right), 6 (front-right)
-        if (window.createPlateFromNodes) {
-            for (let level = 1; level < numStoreys; level++) {
-                const levelNodes = nodesByLevel[level];
-                // Main floor slab: front-left -> back-left -> back-right -> front-right
-                // Indices: 0, 3, 7, 6
-                const floorPlate = window.createPlateFromNodes([
-                    levelNodes[0],  // front left (5, y, 5)
-                    levelNodes[3],  // back left (5, y, -10)
-                    levelNodes[7],  // back right (21, y, -10)
-                    levelNodes[6]   // front right (21, y, 5)
-                ]);
-                if (floorPlate) {
-                    console.log(`Floor slab created at level ${level} (y=${level * storeyHeight}m)`);
-                }
-            }
-
-            // Create wall panels (vertical plates) - stair core walls
-            // Wall on left side of core: between nodes 14 and 13 (X=11, Z=0 to Z=-2)
-            for (let level = 0; level < numStoreys - 1; level++) {
-                const lowerNodes = nodesByLevel[level];
-                const upperNodes = nodesByLevel[level + 1];
-                
-                // Core wall 1: left side (X=11, Z from 0 to -2)
-                // Nodes: 14 (11,y,0) -> 13 (11,y,-2) at both levels
-                const wall1 = window.createPlateFromNodes([
-                    lowerNodes[14], // 11, lower, 0
-                    lowerNodes[13], // 11, lower, -2
-                    upperNodes[13], // 11, upper, -2
-                    upperNodes[14]  // 11, upper, 0
-                ]);
-                if (wall1) {
-                    console.log(`Core wall 1 created at level ${level}`);
-                }
-                
-                // Core wall 2: back side (Z=-2, X from 11 to 13)
-                // Nodes: 13 (11,y,-2) -> 12 (13,y,-2) at both levels
-                const wall2 = window.createPlateFromNodes([
-                    lowerNodes[13], // 11, lower, -2
-                    lowerNodes[12], // 13, lower, -2
-                    upperNodes[12], // 13, upper, -2
-                    upperNodes[13]  // 11, upper, -2
-                ]);
-                if (wall2) {
-                    console.log(`Core wall 2 created at level ${level}`);
-                }
-                
-                // Core wall 3: right side (X=13, Z from -2 to 0)
-                // Nodes: 12 (13,y,-2) -> 10 (13,y,0) at both levels
-                const wall3 = window.createPlateFromNodes([
-                    lowerNodes[12], // 13, lower, -2
-                    lowerNodes[10], // 13, lower, 0
-                    upperNodes[10], // 13, upper, 0
-                    upperNodes[12]  // 13, upper, -2
-                ]);
-                if (wall3) {
-                    console.log(`Core wall 3 created at level ${level}`);
-                }
-            }
-        }
-
-        const sceneData = window.sceneData;
-        const plateCount = sceneData.platesGroup ? sceneData.platesGroup.children.length : 0;
-        
-        console.log('Example structure loaded:');
-        console.log(`  - ${sceneData.nodesGroup.children.length} nodes`);
-        console.log(`  - ${sceneData.beamsGroup.children.length} beams (columns)`);
-        console.log(`  - ${plateCount} plates (floor slabs + walls)`);
-        console.log(`  - ${groundNodes.length} pinned supports`);
-        
-        // Reset camera to see the structure
-        if (window.resetView) {
-            window.resetView();
-        }
-        
-        return {
-            nodes: sceneData.nodesGroup.children.length,
-            beams: sceneData.beamsGroup.children.length,
-            plates: plateCount,
-            supports: groundNodes.length
-        };
-    } catch (error) {
-        console.error('Error loading example structure:', error);
-        console.error('Stack trace:', error.stack);
-    }
-};
@@ -64,7 +64,7 @@ cargo build --release --bin calculix-service
 say "Starting service on $HOST:$PORT"
 # Use env vars to be explicit; ccx discovery is handled by resolve_ccx_path()
 nohup env HOST="$HOST" PORT="$PORT" \
-  CALCULIX_DEBUG_EXPORT="${CALCULIX_DEBUG_EXPORT:-$SERVICE_DIR/debug_export}" \
+  ANALYSIS_ARTIFACT_DIR="${ANALYSIS_ARTIFACT_DIR:-$SERVICE_DIR/jobs}" \
   RUST_LOG="${RUST_LOG:-calculix_service=debug,tower_http=info,axum=info}" \
   "$BIN_PATH" \
   >"$LOG_FILE" 2>&1 &